@@ -0,0 +1,453 @@
+//! Fyrox Asset Tool - headless asset processing for scripts and CI.
+
+use clap::{Parser, Subcommand};
+use fyrox::{
+    asset::{
+        io::encrypted::{EncryptedResourceIo, ENCRYPTION_KEY_SIZE},
+        manager::ResourceManager,
+        options::OPTIONS_EXTENSION,
+        state::LoadError,
+        untyped::UntypedResource,
+        ResourceData,
+    },
+    core::{
+        append_extension,
+        futures::{executor::block_on, future::join_all},
+        log::{Log, MessageKind},
+    },
+    engine::{initialize_resource_manager_loaders, SerializationContext},
+    resource::{
+        spritesheet::SpriteSheet,
+        texture::{
+            atlas::{TextureAtlas, TextureAtlasBuilder, DEFAULT_MAX_ATLAS_SIZE},
+            Texture,
+        },
+    },
+};
+use std::{
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Root directory of the project. All resource paths are relative to it.
+    #[clap(long, default_value = ".")]
+    project: PathBuf,
+
+    /// Prints every message of the engine, not just warnings and errors.
+    #[clap(short, long)]
+    verbose: bool,
+
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Imports every resource in the given directory using its current import options and
+    /// reports resources that failed to import.
+    Reimport {
+        #[clap(short, long, default_value = "data")]
+        path: PathBuf,
+    },
+    /// Checks every resource in the given directory: reports resources that fail to load,
+    /// references to missing resources, type mismatches and cyclic dependencies.
+    Validate {
+        #[clap(short, long, default_value = "data")]
+        path: PathBuf,
+    },
+    /// Manages the derived data cache of imported resources.
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommands,
+    },
+    /// Copies every resource in the given directory together with its import options to the
+    /// output directory, optionally encrypting them.
+    Package {
+        #[clap(short, long, default_value = "data")]
+        path: PathBuf,
+
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Hex-encoded 32-byte key. If specified, every file of the package will be encrypted.
+        #[clap(short, long)]
+        key: Option<String>,
+    },
+    /// Packs every texture in the given directory into a single texture atlas. Writes the atlas
+    /// texture to the output path and UV remapping data next to it with `.atlas` extension.
+    Atlas {
+        #[clap(short, long)]
+        path: PathBuf,
+
+        /// Path of the atlas texture, for example `data/atlas.png`.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Amount of empty pixels between packed textures.
+        #[clap(long, default_value = "1")]
+        padding: u32,
+
+        /// Maximum size of a side of the atlas texture.
+        #[clap(long, default_value_t = DEFAULT_MAX_ATLAS_SIZE)]
+        max_size: u32,
+    },
+    /// Imports a sprite sheet from Aseprite JSON file or from an atlas produced by `atlas` command
+    /// and saves it in the engine format.
+    #[clap(name = "spritesheet")]
+    SpriteSheet {
+        /// Path of Aseprite JSON file or `.atlas` file. The texture of an atlas must be next to it
+        /// and have `.png` extension.
+        #[clap(short, long)]
+        input: PathBuf,
+
+        /// Path of the sprite sheet, for example `data/knight.spritesheet`.
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Duration of every frame in seconds. It is used only for atlases, Aseprite files have
+        /// their own frame durations.
+        #[clap(long, default_value = "0.1")]
+        frame_duration: f32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommands {
+    /// Removes the derived data cache and imports every resource in the given directory, that
+    /// has an importer, again.
+    Rebuild {
+        #[clap(short, long, default_value = "data")]
+        path: PathBuf,
+    },
+    /// Removes the derived data cache.
+    Clear,
+}
+
+fn make_resource_manager() -> ResourceManager {
+    let resource_manager = ResourceManager::new();
+    initialize_resource_manager_loaders(&resource_manager, Arc::new(SerializationContext::new()));
+    resource_manager
+}
+
+fn find_resources(resource_manager: &ResourceManager, path: &Path) -> Vec<PathBuf> {
+    match block_on(resource_manager.find_loadable_resources(path)) {
+        Ok(paths) => paths,
+        Err(err) => {
+            println!(
+                "Unable to read {} directory. Reason: {:?}",
+                path.display(),
+                err
+            );
+            exit(1);
+        }
+    }
+}
+
+fn load_all(
+    resource_manager: &ResourceManager,
+    paths: &[PathBuf],
+) -> Vec<Result<UntypedResource, LoadError>> {
+    block_on(join_all(
+        paths
+            .iter()
+            .map(|path| resource_manager.request_untyped(path)),
+    ))
+}
+
+fn reimport(path: &Path) -> usize {
+    let resource_manager = make_resource_manager();
+    let paths = find_resources(&resource_manager, path);
+    let mut failed = 0;
+    for (path, result) in paths.iter().zip(load_all(&resource_manager, &paths)) {
+        match result {
+            Ok(_) => println!("Imported {}", path.display()),
+            Err(err) => {
+                println!("Failed to import {}. Reason: {:?}", path.display(), err);
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "{} resources imported, {} failed.",
+        paths.len() - failed,
+        failed
+    );
+    failed
+}
+
+fn validate(path: &Path) -> usize {
+    let resource_manager = make_resource_manager();
+    let report = block_on(resource_manager.validate_project(path));
+    print!("{report}");
+    report.problem_count()
+}
+
+fn clear_cache(resource_manager: &ResourceManager) -> usize {
+    let cache = resource_manager.state().derived_data_cache.clone();
+    match cache.clear() {
+        Ok(_) => {
+            println!("Derived data cache {} is cleared.", cache.root().display());
+            0
+        }
+        Err(err) => {
+            println!(
+                "Unable to clear derived data cache {}. Reason: {:?}",
+                cache.root().display(),
+                err
+            );
+            1
+        }
+    }
+}
+
+fn rebuild_cache(path: &Path) -> usize {
+    let resource_manager = make_resource_manager();
+    if clear_cache(&resource_manager) > 0 {
+        return 1;
+    }
+    let mut paths = find_resources(&resource_manager, path);
+    {
+        let state = resource_manager.state();
+        paths.retain(|path| state.importers.find_for_path(path).is_some());
+    }
+    let mut failed = 0;
+    for (path, result) in paths.iter().zip(load_all(&resource_manager, &paths)) {
+        if let Err(err) = result {
+            println!("Failed to import {}. Reason: {:?}", path.display(), err);
+            failed += 1;
+        }
+    }
+    println!(
+        "{} resources imported, {} failed.",
+        paths.len() - failed,
+        failed
+    );
+    failed
+}
+
+fn parse_key(key: &str) -> Option<[u8; ENCRYPTION_KEY_SIZE]> {
+    if key.len() != ENCRYPTION_KEY_SIZE * 2 {
+        return None;
+    }
+    let mut bytes = [0u8; ENCRYPTION_KEY_SIZE];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(key.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn package(path: &Path, output: &Path, key: Option<&str>) -> usize {
+    let resource_manager = make_resource_manager();
+    let io = resource_manager.resource_io();
+    let encryption = key.map(|key| match parse_key(key) {
+        Some(key) => EncryptedResourceIo::new(io.clone(), &key),
+        None => {
+            println!("The key must be {} hex-encoded bytes!", ENCRYPTION_KEY_SIZE);
+            exit(1);
+        }
+    });
+
+    let mut files = Vec::new();
+    for resource_path in find_resources(&resource_manager, path) {
+        let options_path = append_extension(&resource_path, OPTIONS_EXTENSION);
+        if block_on(io.exists(&options_path)) {
+            files.push(options_path);
+        }
+        files.push(resource_path);
+    }
+
+    let mut failed = 0;
+    for file in files.iter() {
+        let result = block_on(io.load_file(file))
+            .map_err(|err| format!("{:?}", err))
+            .and_then(|data| match encryption.as_ref() {
+                Some(encryption) => encryption.encrypt(&data).map_err(|e| format!("{:?}", e)),
+                None => Ok(data),
+            })
+            .and_then(|data| {
+                let output_path = output.join(file);
+                if let Some(parent) = output_path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(output_path, data).map_err(|e| e.to_string())
+            });
+
+        match result {
+            Ok(_) => println!("Packaged {}", file.display()),
+            Err(err) => {
+                println!("Failed to package {}. Reason: {}", file.display(), err);
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "{} files packaged, {} failed.",
+        files.len() - failed,
+        failed
+    );
+    failed
+}
+
+fn atlas(path: &Path, output: &Path, padding: u32, max_size: u32) -> usize {
+    let resource_manager = make_resource_manager();
+    let mut builder = TextureAtlasBuilder::new()
+        .with_padding(padding)
+        .with_max_size(max_size);
+    let mut failed = 0;
+    for texture_path in find_resources(&resource_manager, path) {
+        let Ok(texture) = block_on(resource_manager.request::<Texture>(&texture_path)) else {
+            // Not a texture or failed to load.
+            continue;
+        };
+        // Textures are named by their path relative to the packed directory.
+        let name = texture_path
+            .strip_prefix(path)
+            .unwrap_or(&texture_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let result = builder.add_texture(&name, &texture.data_ref());
+        if let Err(err) = result {
+            println!("Unable to pack {}. Reason: {}", texture_path.display(), err);
+            failed += 1;
+        }
+    }
+
+    match builder.build() {
+        Ok((mut texture, mut atlas)) => {
+            if let Some(parent) = output.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            texture.set_path(output);
+            if let Err(err) = texture.save() {
+                println!(
+                    "Unable to save atlas texture {}. Reason: {:?}",
+                    output.display(),
+                    err
+                );
+                return failed + 1;
+            }
+            let atlas_path = output.with_extension("atlas");
+            if let Err(err) = atlas.save(&atlas_path) {
+                println!(
+                    "Unable to save atlas {}. Reason: {:?}",
+                    atlas_path.display(),
+                    err
+                );
+                return failed + 1;
+            }
+            println!(
+                "{} textures packed into {}x{} atlas.",
+                atlas.regions.len(),
+                atlas.size.x,
+                atlas.size.y
+            );
+            failed
+        }
+        Err(err) => {
+            println!("Unable to build atlas. Reason: {}", err);
+            failed + 1
+        }
+    }
+}
+
+fn import_sprite_sheet(input: &Path, frame_duration: f32) -> Result<SpriteSheet, String> {
+    let resource_manager = make_resource_manager();
+    let request_texture = |path: PathBuf| {
+        block_on(resource_manager.request::<Texture>(&path))
+            .map_err(|e| format!("Unable to load texture {}. Reason: {:?}", path.display(), e))
+    };
+
+    if input.extension().is_some_and(|e| e == "atlas") {
+        let atlas = block_on(TextureAtlas::load(input)).map_err(|e| format!("{:?}", e))?;
+        let texture = request_texture(input.with_extension("png"))?;
+        Ok(SpriteSheet::from_atlas(
+            &atlas,
+            Some(texture),
+            frame_duration,
+        ))
+    } else {
+        let json = std::fs::read_to_string(input).map_err(|e| e.to_string())?;
+        let texture = match SpriteSheet::aseprite_image_path(&json).map_err(|e| e.to_string())? {
+            Some(image) => Some(request_texture(
+                input.parent().unwrap_or(Path::new("")).join(image),
+            )?),
+            None => None,
+        };
+        SpriteSheet::from_aseprite_json(&json, texture).map_err(|e| e.to_string())
+    }
+}
+
+fn sprite_sheet(input: &Path, output: &Path, frame_duration: f32) -> usize {
+    let result = import_sprite_sheet(input, frame_duration).and_then(|mut sprite_sheet| {
+        sprite_sheet
+            .save(output)
+            .map(|_| sprite_sheet)
+            .map_err(|e| e.to_string())
+    });
+
+    match result {
+        Ok(sprite_sheet) => {
+            println!(
+                "Sprite sheet with {} frames and {} animations saved to {}.",
+                sprite_sheet.frames.len(),
+                sprite_sheet.animations.len(),
+                output.display()
+            );
+            0
+        }
+        Err(err) => {
+            println!(
+                "Unable to import sprite sheet {}. Reason: {}",
+                input.display(),
+                err
+            );
+            1
+        }
+    }
+}
+
+fn main() {
+    let args: Args = Args::parse();
+
+    if !args.verbose {
+        Log::set_verbosity(MessageKind::Warning);
+    }
+
+    if let Err(err) = std::env::set_current_dir(&args.project) {
+        println!(
+            "Unable to use {} as project directory. Reason: {}",
+            args.project.display(),
+            err
+        );
+        exit(1);
+    }
+
+    let failures = match args.command {
+        Commands::Reimport { path } => reimport(&path),
+        Commands::Validate { path } => validate(&path),
+        Commands::Cache { command } => match command {
+            CacheCommands::Rebuild { path } => rebuild_cache(&path),
+            CacheCommands::Clear => clear_cache(&make_resource_manager()),
+        },
+        Commands::Package { path, output, key } => package(&path, &output, key.as_deref()),
+        Commands::Atlas {
+            path,
+            output,
+            padding,
+            max_size,
+        } => atlas(&path, &output, padding, max_size),
+        Commands::SpriteSheet {
+            input,
+            output,
+            frame_duration,
+        } => sprite_sheet(&input, &output, frame_duration),
+    };
+
+    if failures > 0 {
+        exit(1);
+    }
+}
@@ -34,19 +34,19 @@ use std::{
 };
 
 #[derive(Debug, Clone, PartialEq, Visit, Reflect, Default)]
-pub(super) struct Entry {
+pub struct Entry {
     pub node: Handle<UiNode>,
     pub initial_position: Vector2<f32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Visit, Reflect, Default)]
-pub(super) struct DragContext {
+pub struct DragContext {
     initial_cursor_position: Vector2<f32>,
     entries: Vec<Entry>,
 }
 
 #[derive(Debug, Clone, PartialEq, Visit, Reflect)]
-pub(super) enum Mode {
+pub enum Mode {
     Normal,
     Drag {
         drag_context: DragContext,
@@ -64,7 +64,7 @@ pub(super) enum Mode {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub(super) enum AbsmCanvasMessage {
+pub enum AbsmCanvasMessage {
     SwitchMode(Mode),
     CommitTransition {
         source_node: Handle<UiNode>,
@@ -1,6 +1,8 @@
 use crate::{command::Command, scene::commands::SceneContext};
 use fyrox::{
-    animation::machine::{LayerMask, Machine, MachineLayer, PoseNode, State, Transition},
+    animation::machine::{
+        LayerBlendMode, LayerMask, Machine, MachineLayer, PoseNode, State, Transition,
+    },
     core::{
         algebra::Vector2,
         pool::{Handle, Ticket},
@@ -880,3 +882,63 @@ impl Command for SetLayerMaskCommand {
         self.swap(context)
     }
 }
+
+#[derive(Debug)]
+pub struct SetLayerBlendModeCommand {
+    pub absm_node_handle: Handle<Node>,
+    pub layer_index: usize,
+    pub blend_mode: LayerBlendMode,
+}
+
+impl SetLayerBlendModeCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let layer =
+            &mut fetch_machine(context, self.absm_node_handle).layers_mut()[self.layer_index];
+        self.blend_mode = layer.set_blend_mode(self.blend_mode);
+    }
+}
+
+impl Command for SetLayerBlendModeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Layer Blend Mode".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
+#[derive(Debug)]
+pub struct SetLayerWeightCommand {
+    pub absm_node_handle: Handle<Node>,
+    pub layer_index: usize,
+    pub weight: f32,
+}
+
+impl SetLayerWeightCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let layer =
+            &mut fetch_machine(context, self.absm_node_handle).layers_mut()[self.layer_index];
+        let old = layer.weight();
+        layer.set_weight(self.weight);
+        self.weight = old;
+    }
+}
+
+impl Command for SetLayerWeightCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Layer Weight".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
@@ -38,10 +38,10 @@ use fyrox::{
 };
 
 mod blendspace;
-mod canvas;
+pub mod canvas;
 pub mod command;
 mod connection;
-mod node;
+pub mod node;
 mod parameter;
 mod segment;
 mod selectable;
@@ -50,13 +50,13 @@ mod socket;
 mod state_graph;
 mod state_viewer;
 mod toolbar;
-mod transition;
+pub mod transition;
 
-const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
-const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
+pub const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
+pub const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
 const BORDER_COLOR: Color = Color::opaque(70, 70, 70);
-const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
-const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
+pub const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
+pub const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
 
 struct PreviewModeData {
     machine: Machine,
@@ -1,7 +1,10 @@
 use crate::message::MessageSender;
 use crate::{
     absm::{
-        command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
+        command::{
+            AddLayerCommand, RemoveLayerCommand, SetLayerBlendModeCommand, SetLayerMaskCommand,
+            SetLayerNameCommand, SetLayerWeightCommand,
+        },
         fetch_selection,
         selection::AbsmSelection,
     },
@@ -15,7 +18,7 @@ use crate::{
     send_sync_message,
 };
 use fyrox::{
-    animation::machine::{LayerMask, MachineLayer},
+    animation::machine::{LayerBlendMode, LayerMask, MachineLayer},
     core::pool::Handle,
     fxhash::FxHashSet,
     gui::{
@@ -24,6 +27,7 @@ use fyrox::{
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
         image::ImageBuilder,
         message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
         text_box::{TextBox, TextBoxBuilder},
@@ -47,6 +51,8 @@ pub struct Toolbar {
     pub add_layer: Handle<UiNode>,
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
+    pub weight: Handle<UiNode>,
+    pub additive: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
 }
 
@@ -64,6 +70,8 @@ impl Toolbar {
         let add_layer;
         let remove_layer;
         let edit_mask;
+        let weight;
+        let additive;
         let panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -144,6 +152,38 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    weight = NumericUpDownBuilder::<f32>::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(60.0)
+                            .with_tooltip(make_simple_tooltip(ctx, "Weight of the layer.")),
+                    )
+                    .with_min_value(0.0)
+                    .with_value(1.0)
+                    .build(ctx);
+                    weight
+                })
+                .with_child({
+                    additive = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Add the pose of the layer on top of the poses of previous \
+                                layers. Additive layers should play additive animations.",
+                            )),
+                    )
+                    .with_content(
+                        TextBuilder::new(
+                            WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Center),
+                        )
+                        .with_text("Additive")
+                        .build(ctx),
+                    )
+                    .build(ctx);
+                    additive
                 }),
         )
         .with_orientation(Orientation::Horizontal)
@@ -157,6 +197,8 @@ impl Toolbar {
             add_layer,
             remove_layer,
             edit_mask,
+            weight,
+            additive,
             node_selector: Handle::NONE,
         }
     }
@@ -172,14 +214,38 @@ impl Toolbar {
         let selection = fetch_selection(&editor_scene.selection);
 
         if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
-            if message.destination() == self.preview
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.preview {
+                    return if *value {
+                        ToolbarAction::EnterPreviewMode
+                    } else {
+                        ToolbarAction::LeavePreviewMode
+                    };
+                } else if message.destination() == self.additive {
+                    if let Some(layer_index) = selection.layer {
+                        sender.do_scene_command(SetLayerBlendModeCommand {
+                            absm_node_handle: selection.absm_node_handle,
+                            layer_index,
+                            blend_mode: if *value {
+                                LayerBlendMode::Additive
+                            } else {
+                                LayerBlendMode::Override
+                            },
+                        });
+                    }
+                }
+            }
+        } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+            if message.destination() == self.weight
                 && message.direction() == MessageDirection::FromWidget
             {
-                return if *value {
-                    ToolbarAction::EnterPreviewMode
-                } else {
-                    ToolbarAction::LeavePreviewMode
-                };
+                if let Some(layer_index) = selection.layer {
+                    sender.do_scene_command(SetLayerWeightCommand {
+                        absm_node_handle: selection.absm_node_handle,
+                        layer_index,
+                        weight: *value,
+                    });
+                }
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.destination() == self.layers
@@ -381,6 +447,24 @@ impl Toolbar {
                         layer.name().to_string(),
                     ),
                 );
+
+                send_sync_message(
+                    ui,
+                    NumericUpDownMessage::value(
+                        self.weight,
+                        MessageDirection::ToWidget,
+                        layer.weight(),
+                    ),
+                );
+
+                send_sync_message(
+                    ui,
+                    CheckBoxMessage::checked(
+                        self.additive,
+                        MessageDirection::ToWidget,
+                        Some(layer.blend_mode() == LayerBlendMode::Additive),
+                    ),
+                );
             }
         }
     }
@@ -0,0 +1,609 @@
+//! Dope sheet is a compact view of animation tracks, where every track is shown as a row of keys
+//! at their time positions. Values of keys are not shown, which makes it much faster for blocking
+//! out timing of an animation, than the curve editor: multiple columns of keys of every track could
+//! be selected and moved at once.
+
+use fyrox::{
+    core::{
+        algebra::{Matrix3, Point2, Vector2},
+        color::Color,
+        curve::Curve,
+        math::Rect,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::Uuid,
+        visitor::prelude::*,
+    },
+    fxhash::FxHashSet,
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        formatted_text::{FormattedText, FormattedTextBuilder},
+        message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARK, BRUSH_DARKER,
+        BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DopeSheetTrack {
+    pub id: Uuid,
+    pub name: String,
+    pub curves: Vec<Curve>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DopeSheetMessage {
+    Zoom(f32),
+    ViewPosition(f32),
+    Sync(Vec<DopeSheetTrack>),
+    /// Selects keys with the given ids. The dope sheet sends this message back (with
+    /// [`MessageDirection::FromWidget`]) every time when a user changes the selection.
+    SelectKeys(Vec<Uuid>),
+    /// Sent by the dope sheet when a user has moved or removed keys. Contains every modified curve.
+    ChangeCurves(Vec<Curve>),
+}
+
+impl DopeSheetMessage {
+    define_constructor!(DopeSheetMessage:Zoom => fn zoom(f32), layout: false);
+    define_constructor!(DopeSheetMessage:ViewPosition => fn view_position(f32), layout: false);
+    define_constructor!(DopeSheetMessage:Sync => fn sync(Vec<DopeSheetTrack>), layout: false);
+    define_constructor!(DopeSheetMessage:SelectKeys => fn select_keys(Vec<Uuid>), layout: false);
+    define_constructor!(DopeSheetMessage:ChangeCurves => fn change_curves(Vec<Curve>), layout: false);
+}
+
+/// Keys of a row, that have the same location. Every column is shown as a single diamond.
+#[derive(Clone, Debug)]
+struct KeyColumn {
+    location: f32,
+    keys: Vec<Uuid>,
+}
+
+impl KeyColumn {
+    // Keys closer than that are shown as a single diamond.
+    const LOCATION_EPSILON: f32 = 0.0001;
+
+    fn collect<'a>(curves: impl Iterator<Item = &'a Curve>) -> Vec<KeyColumn> {
+        let mut keys = curves
+            .flat_map(|curve| curve.keys().iter())
+            .map(|key| (key.location(), key.id))
+            .collect::<Vec<_>>();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut columns = Vec::<KeyColumn>::new();
+        for (location, id) in keys {
+            match columns.last_mut() {
+                Some(column) if (location - column.location).abs() <= Self::LOCATION_EPSILON => {
+                    column.keys.push(id)
+                }
+                _ => columns.push(KeyColumn {
+                    location,
+                    keys: vec![id],
+                }),
+            }
+        }
+        columns
+    }
+}
+
+#[derive(Clone, Debug)]
+enum OperationContext {
+    DragKeys {
+        // In local coordinates.
+        initial_mouse_pos: Vector2<f32>,
+        mouse_pos: Vector2<f32>,
+        // Moves every key after the selected ones too.
+        ripple: bool,
+    },
+    MoveView {
+        initial_mouse_pos: Vector2<f32>,
+        initial_view_position: f32,
+    },
+    BoxSelection {
+        // In local coordinates.
+        initial_mouse_pos: Vector2<f32>,
+        mouse_pos: Vector2<f32>,
+        additive: bool,
+    },
+}
+
+#[derive(Clone, Visit, Reflect)]
+pub struct DopeSheet {
+    widget: Widget,
+    zoom: f32,
+    view_position: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    tracks: Vec<DopeSheetTrack>,
+    // The first row is a summary of every track.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    rows: Vec<Vec<KeyColumn>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    selection: FxHashSet<Uuid>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    operation_context: Option<OperationContext>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    text: RefCell<FormattedText>,
+}
+
+impl Debug for DopeSheet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DopeSheet")
+    }
+}
+
+define_widget_deref!(DopeSheet);
+
+impl DopeSheet {
+    const ROW_HEIGHT: f32 = 20.0;
+    const KEY_SIZE: f32 = 10.0;
+
+    // Must match the view transform of the ruler and the curve editor.
+    fn view_matrix(&self) -> Matrix3<f32> {
+        Matrix3::new_nonuniform_scaling_wrt_point(
+            &Vector2::new(self.zoom, 1.0),
+            &Point2::from(self.actual_local_size().scale(0.5)),
+        ) * Matrix3::new_translation(&Vector2::new(self.view_position, 0.0))
+    }
+
+    fn local_to_view(&self, x: f32) -> f32 {
+        self.view_matrix().transform_point(&Point2::new(x, 0.0)).x
+    }
+
+    fn view_to_local(&self, x: f32) -> f32 {
+        self.view_matrix()
+            .try_inverse()
+            .unwrap_or_default()
+            .transform_point(&Point2::new(x, 0.0))
+            .x
+    }
+
+    fn key_position(&self, row: usize, location: f32) -> Vector2<f32> {
+        Vector2::new(
+            self.local_to_view(location),
+            (row as f32 + 0.5) * Self::ROW_HEIGHT,
+        )
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        self.rows.push(KeyColumn::collect(
+            self.tracks.iter().flat_map(|track| track.curves.iter()),
+        ));
+        for track in self.tracks.iter() {
+            self.rows.push(KeyColumn::collect(track.curves.iter()));
+        }
+    }
+
+    fn pick(&self, local_pos: Vector2<f32>) -> Option<&KeyColumn> {
+        let row = (local_pos.y / Self::ROW_HEIGHT).floor();
+        if row < 0.0 {
+            return None;
+        }
+        let row = row as usize;
+        self.rows.get(row)?.iter().find(|column| {
+            let position = self.key_position(row, column.location);
+            (position.x - local_pos.x).abs() <= Self::KEY_SIZE * 0.5
+        })
+    }
+
+    fn is_column_selected(&self, column: &KeyColumn) -> bool {
+        column.keys.iter().all(|key| self.selection.contains(key))
+    }
+
+    fn set_selection(&mut self, selection: FxHashSet<Uuid>, ui: &UserInterface) {
+        if self.selection != selection {
+            self.selection = selection;
+            ui.send_message(DopeSheetMessage::select_keys(
+                self.handle,
+                MessageDirection::FromWidget,
+                self.selection.iter().cloned().collect(),
+            ));
+        }
+    }
+
+    fn drag_offset(&self) -> Option<(f32, bool)> {
+        if let Some(OperationContext::DragKeys {
+            initial_mouse_pos,
+            mouse_pos,
+            ripple,
+        }) = self.operation_context.as_ref()
+        {
+            Some((
+                self.view_to_local(mouse_pos.x) - self.view_to_local(initial_mouse_pos.x),
+                *ripple,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn ripple_start(&self) -> f32 {
+        self.tracks
+            .iter()
+            .flat_map(|track| track.curves.iter())
+            .flat_map(|curve| curve.keys().iter())
+            .filter(|key| self.selection.contains(&key.id))
+            .map(|key| key.location())
+            .fold(f32::MAX, f32::min)
+    }
+
+    fn is_moved(&self, id: Uuid, location: f32, ripple_start: Option<f32>) -> bool {
+        self.selection.contains(&id)
+            || ripple_start.is_some_and(|start| location >= start - KeyColumn::LOCATION_EPSILON)
+    }
+
+    fn moved_curves(&self, offset: f32, ripple: bool) -> Vec<Curve> {
+        let ripple_start = ripple.then(|| self.ripple_start());
+        let mut curves = Vec::new();
+        for curve in self.tracks.iter().flat_map(|track| track.curves.iter()) {
+            let moved = curve
+                .keys()
+                .iter()
+                .filter(|key| self.is_moved(key.id, key.location(), ripple_start))
+                .map(|key| (key.id, key.location()))
+                .collect::<Vec<_>>();
+            if moved.is_empty() {
+                continue;
+            }
+            let mut curve = curve.clone();
+            for (id, location) in moved {
+                if let Some(index) = curve.keys().iter().position(|key| key.id == id) {
+                    curve.move_key(index, location + offset);
+                }
+            }
+            curves.push(curve);
+        }
+        curves
+    }
+
+    fn curves_without_selection(&self) -> Vec<Curve> {
+        let mut curves = Vec::new();
+        for curve in self.tracks.iter().flat_map(|track| track.curves.iter()) {
+            if curve
+                .keys()
+                .iter()
+                .any(|key| self.selection.contains(&key.id))
+            {
+                let mut new_curve = curve.clone();
+                new_curve.clear();
+                for key in curve.keys() {
+                    if !self.selection.contains(&key.id) {
+                        new_curve.add_key(key.clone());
+                    }
+                }
+                curves.push(new_curve);
+            }
+        }
+        curves
+    }
+
+    fn apply_curves(&mut self, curves: &[Curve]) {
+        for curve in curves {
+            for track in self.tracks.iter_mut() {
+                if let Some(existing) = track.curves.iter_mut().find(|c| c.id() == curve.id()) {
+                    *existing = curve.clone();
+                }
+            }
+        }
+        self.rebuild_rows();
+    }
+
+    fn change_curves(&mut self, curves: Vec<Curve>, ui: &UserInterface) {
+        if !curves.is_empty() {
+            self.apply_curves(&curves);
+            ui.send_message(DopeSheetMessage::change_curves(
+                self.handle,
+                MessageDirection::FromWidget,
+                curves,
+            ));
+        }
+    }
+
+    fn box_selection_bounds(&self) -> Option<Rect<f32>> {
+        if let Some(OperationContext::BoxSelection {
+            initial_mouse_pos,
+            mouse_pos,
+            ..
+        }) = self.operation_context.as_ref()
+        {
+            let min = initial_mouse_pos.inf(mouse_pos);
+            let max = initial_mouse_pos.sup(mouse_pos);
+            Some(Rect::new(min.x, min.y, max.x - min.x, max.y - min.y))
+        } else {
+            None
+        }
+    }
+
+    fn draw_key(&self, ctx: &mut DrawingContext, center: Vector2<f32>) {
+        let half_size = Self::KEY_SIZE * 0.5;
+        let top = center - Vector2::new(0.0, half_size);
+        let right = center + Vector2::new(half_size, 0.0);
+        let bottom = center + Vector2::new(0.0, half_size);
+        let left = center - Vector2::new(half_size, 0.0);
+        ctx.push_triangle_filled([top, right, bottom]);
+        ctx.push_triangle_filled([bottom, left, top]);
+    }
+}
+
+impl Control for DopeSheet {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let local_bounds = self.bounding_rect();
+
+        // Add clickable rectangle first.
+        ctx.push_rect_filled(&local_bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.background.clone(),
+            CommandTexture::None,
+            None,
+        );
+
+        // Stripes of the rows.
+        for row in 0..self.rows.len() {
+            if row % 2 == 0 {
+                ctx.push_rect_filled(
+                    &Rect::new(
+                        0.0,
+                        row as f32 * Self::ROW_HEIGHT,
+                        local_bounds.w(),
+                        Self::ROW_HEIGHT,
+                    ),
+                    None,
+                );
+            }
+        }
+        ctx.commit(self.clip_bounds(), BRUSH_DARKER, CommandTexture::None, None);
+
+        // Names of the rows.
+        let mut text = self.text.borrow_mut();
+        for (row, name) in std::iter::once("Summary")
+            .chain(self.tracks.iter().map(|track| track.name.as_str()))
+            .enumerate()
+        {
+            text.set_text(name).build();
+            ctx.draw_text(
+                self.clip_bounds(),
+                Vector2::new(2.0, row as f32 * Self::ROW_HEIGHT + 2.0),
+                &text,
+            );
+        }
+
+        // Keys. Selected keys are drawn at their new positions while they are dragged.
+        let offset = self.drag_offset();
+        let ripple_start = offset.and_then(|(_, ripple)| ripple.then(|| self.ripple_start()));
+        for (selected, brush) in [(false, BRUSH_LIGHTEST), (true, BRUSH_BRIGHT)] {
+            for (row, columns) in self.rows.iter().enumerate() {
+                for column in columns {
+                    if self.is_column_selected(column) != selected {
+                        continue;
+                    }
+                    let mut location = column.location;
+                    if let Some((offset, _)) = offset {
+                        if column
+                            .keys
+                            .iter()
+                            .all(|key| self.is_moved(*key, column.location, ripple_start))
+                        {
+                            location += offset;
+                        }
+                    }
+                    self.draw_key(ctx, self.key_position(row, location));
+                }
+            }
+            ctx.commit(self.clip_bounds(), brush, CommandTexture::None, None);
+        }
+
+        if let Some(bounds) = self.box_selection_bounds() {
+            ctx.push_rect_filled(&bounds, None);
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(Color::from_rgba(255, 255, 255, 20)),
+                CommandTexture::None,
+                None,
+            );
+            ctx.push_rect(&bounds, 1.0);
+            ctx.commit(self.clip_bounds(), BRUSH_LIGHT, CommandTexture::None, None);
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<DopeSheetMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    DopeSheetMessage::Zoom(zoom) => {
+                        self.zoom = *zoom;
+                    }
+                    DopeSheetMessage::ViewPosition(position) => {
+                        self.view_position = *position;
+                    }
+                    DopeSheetMessage::Sync(tracks) => {
+                        self.tracks = tracks.clone();
+                        self.rebuild_rows();
+                    }
+                    DopeSheetMessage::SelectKeys(keys) => {
+                        // Do not send the selection back, it was set by the user of the widget.
+                        self.selection = keys.iter().cloned().collect();
+                    }
+                    DopeSheetMessage::ChangeCurves(_) => {
+                        // Do nothing. This message is only for output.
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match msg {
+                    WidgetMessage::MouseDown { pos, button } => {
+                        let local_pos = self.screen_to_local(*pos);
+                        match button {
+                            MouseButton::Left => {
+                                ui.capture_mouse(self.handle);
+
+                                let modifiers = ui.keyboard_modifiers();
+                                if let Some(column) = self.pick(local_pos).cloned() {
+                                    let mut selection = if modifiers.control {
+                                        self.selection.clone()
+                                    } else if self.is_column_selected(&column) {
+                                        // Keep the selection to be able to drag multiple columns.
+                                        self.selection.clone()
+                                    } else {
+                                        FxHashSet::default()
+                                    };
+                                    if modifiers.control && self.is_column_selected(&column) {
+                                        for key in column.keys.iter() {
+                                            selection.remove(key);
+                                        }
+                                    } else {
+                                        selection.extend(column.keys.iter().cloned());
+                                    }
+                                    self.set_selection(selection, ui);
+
+                                    if !modifiers.control {
+                                        self.operation_context = Some(OperationContext::DragKeys {
+                                            initial_mouse_pos: local_pos,
+                                            mouse_pos: local_pos,
+                                            ripple: modifiers.shift,
+                                        });
+                                    }
+                                } else {
+                                    self.operation_context = Some(OperationContext::BoxSelection {
+                                        initial_mouse_pos: local_pos,
+                                        mouse_pos: local_pos,
+                                        additive: modifiers.control,
+                                    });
+                                }
+                            }
+                            MouseButton::Middle => {
+                                ui.capture_mouse(self.handle);
+                                self.operation_context = Some(OperationContext::MoveView {
+                                    initial_mouse_pos: local_pos,
+                                    initial_view_position: self.view_position,
+                                });
+                            }
+                            _ => (),
+                        }
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => {
+                        let local_pos = self.screen_to_local(*pos);
+                        let (handle, zoom) = (self.handle, self.zoom);
+                        match self.operation_context.as_mut() {
+                            Some(OperationContext::DragKeys { mouse_pos, .. })
+                            | Some(OperationContext::BoxSelection { mouse_pos, .. }) => {
+                                *mouse_pos = local_pos;
+                            }
+                            Some(OperationContext::MoveView {
+                                initial_mouse_pos,
+                                initial_view_position,
+                            }) => {
+                                let delta = (local_pos.x - initial_mouse_pos.x) / zoom;
+                                ui.send_message(DopeSheetMessage::view_position(
+                                    handle,
+                                    MessageDirection::FromWidget,
+                                    *initial_view_position + delta,
+                                ));
+                            }
+                            None => (),
+                        }
+                    }
+                    WidgetMessage::MouseUp { .. } => {
+                        if let Some(offset) = self.drag_offset() {
+                            if offset.0.abs() > f32::EPSILON {
+                                let curves = self.moved_curves(offset.0, offset.1);
+                                self.change_curves(curves, ui);
+                            }
+                        } else if let Some(bounds) = self.box_selection_bounds() {
+                            let mut selection = match self.operation_context.as_ref() {
+                                Some(OperationContext::BoxSelection { additive: true, .. }) => {
+                                    self.selection.clone()
+                                }
+                                _ => FxHashSet::default(),
+                            };
+                            for (row, columns) in self.rows.iter().enumerate() {
+                                for column in columns {
+                                    if bounds.contains(self.key_position(row, column.location)) {
+                                        selection.extend(column.keys.iter().cloned());
+                                    }
+                                }
+                            }
+                            self.set_selection(selection, ui);
+                        }
+
+                        if self.operation_context.take().is_some() {
+                            ui.release_mouse_capture();
+                        }
+                    }
+                    WidgetMessage::MouseWheel { amount, .. } => {
+                        let k = if *amount < 0.0 { 0.9 } else { 1.1 };
+                        ui.send_message(DopeSheetMessage::zoom(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            self.zoom * k,
+                        ));
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::KeyUp(KeyCode::Delete) => {
+                        let curves = self.curves_without_selection();
+                        self.selection.clear();
+                        self.change_curves(curves, ui);
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+pub struct DopeSheetBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl DopeSheetBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let dope_sheet = DopeSheet {
+            widget: self
+                .widget_builder
+                .with_background(BRUSH_DARK)
+                .with_foreground(BRUSH_LIGHTER)
+                .build(),
+            zoom: 1.0,
+            view_position: 0.0,
+            tracks: Default::default(),
+            rows: vec![Default::default()],
+            selection: Default::default(),
+            operation_context: None,
+            text: RefCell::new(FormattedTextBuilder::new(ctx.default_font()).build()),
+        };
+
+        ctx.add_node(UiNode::new(dope_sheet))
+    }
+}
@@ -5,13 +5,17 @@ use crate::{
             AddAnimationSignal, MoveAnimationSignal, RemoveAnimationSignal,
             ReplaceTrackCurveCommand,
         },
+        dopesheet::{DopeSheetBuilder, DopeSheetMessage, DopeSheetTrack},
         ruler::{RulerBuilder, RulerMessage, SignalView},
         selection::{AnimationSelection, SelectedEntity},
         thumb::{ThumbBuilder, ThumbMessage},
         toolbar::{Toolbar, ToolbarAction},
         track::TrackList,
     },
-    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
+    scene::{
+        commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
     send_sync_message, Message,
 };
 use fyrox::{
@@ -22,7 +26,7 @@ use fyrox::{
     gui::{
         border::BorderBuilder,
         check_box::CheckBoxMessage,
-        curve::{CurveEditorBuilder, CurveEditorMessage, HighlightZone},
+        curve::{CurveEditor, CurveEditorBuilder, CurveEditorMessage, HighlightZone},
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
@@ -33,6 +37,7 @@ use fyrox::{
 };
 
 pub mod command;
+mod dopesheet;
 mod ruler;
 pub mod selection;
 mod thumb;
@@ -47,6 +52,7 @@ pub struct AnimationEditor {
     pub window: Handle<UiNode>,
     track_list: TrackList,
     curve_editor: Handle<UiNode>,
+    dope_sheet: Handle<UiNode>,
     toolbar: Toolbar,
     content: Handle<UiNode>,
     ruler: Handle<UiNode>,
@@ -82,6 +88,7 @@ fn fetch_selection(editor_selection: &Selection) -> AnimationSelection {
 impl AnimationEditor {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let curve_editor;
+        let dope_sheet;
         let ruler;
         let thumb;
 
@@ -117,6 +124,15 @@ impl AnimationEditor {
                                             .with_show_x_values(false)
                                             .build(ctx);
                                             curve_editor
+                                        })
+                                        .with_child({
+                                            dope_sheet = DopeSheetBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_visibility(false)
+                                                    .on_row(1),
+                                            )
+                                            .build(ctx);
+                                            dope_sheet
                                         }),
                                 )
                                 .add_row(Row::strict(22.0))
@@ -162,6 +178,7 @@ impl AnimationEditor {
             window,
             track_list,
             curve_editor,
+            dope_sheet,
             toolbar,
             content,
             ruler,
@@ -234,6 +251,11 @@ impl AnimationEditor {
                                     MessageDirection::ToWidget,
                                     position.x,
                                 ));
+                                ui.send_message(DopeSheetMessage::view_position(
+                                    self.dope_sheet,
+                                    MessageDirection::ToWidget,
+                                    position.x,
+                                ));
                             }
                             CurveEditorMessage::Zoom(zoom) => {
                                 ui.send_message(RulerMessage::zoom(
@@ -245,8 +267,73 @@ impl AnimationEditor {
                                     self.thumb,
                                     MessageDirection::ToWidget,
                                     zoom.x,
+                                ));
+                                ui.send_message(DopeSheetMessage::zoom(
+                                    self.dope_sheet,
+                                    MessageDirection::ToWidget,
+                                    zoom.x,
                                 ))
                             }
+                            CurveEditorMessage::SelectKeys(keys) => {
+                                ui.send_message(DopeSheetMessage::select_keys(
+                                    self.dope_sheet,
+                                    MessageDirection::ToWidget,
+                                    keys.clone(),
+                                ));
+                            }
+                            _ => (),
+                        }
+                    }
+                } else if let Some(msg) = message.data::<DopeSheetMessage>() {
+                    if message.destination() == self.dope_sheet
+                        && message.direction() == MessageDirection::FromWidget
+                    {
+                        let ui = &engine.user_interface;
+                        // The curve editor is the owner of the view, it sends its new view back and
+                        // the rest of the widgets are synced from there.
+                        let curve_editor = ui
+                            .node(self.curve_editor)
+                            .query_component::<CurveEditor>()
+                            .unwrap();
+                        match msg {
+                            DopeSheetMessage::ChangeCurves(curves) => {
+                                sender.do_scene_command(
+                                    CommandGroup::from(
+                                        curves
+                                            .iter()
+                                            .map(|curve| {
+                                                SceneCommand::new(ReplaceTrackCurveCommand {
+                                                    animation_player: selection.animation_player,
+                                                    animation: selection.animation,
+                                                    curve: curve.clone(),
+                                                })
+                                            })
+                                            .collect::<Vec<_>>(),
+                                    )
+                                    .with_custom_name("Change Keys"),
+                                );
+                            }
+                            DopeSheetMessage::SelectKeys(keys) => {
+                                ui.send_message(CurveEditorMessage::select_keys(
+                                    self.curve_editor,
+                                    MessageDirection::ToWidget,
+                                    keys.clone(),
+                                ));
+                            }
+                            DopeSheetMessage::Zoom(zoom) => {
+                                ui.send_message(CurveEditorMessage::zoom(
+                                    self.curve_editor,
+                                    MessageDirection::ToWidget,
+                                    Vector2::new(*zoom, curve_editor.zoom().y),
+                                ));
+                            }
+                            DopeSheetMessage::ViewPosition(position) => {
+                                ui.send_message(CurveEditorMessage::view_position(
+                                    self.curve_editor,
+                                    MessageDirection::ToWidget,
+                                    Vector2::new(*position, curve_editor.view_position().y),
+                                ));
+                            }
                             _ => (),
                         }
                     }
@@ -411,6 +498,19 @@ impl AnimationEditor {
                             }
                         }
                     }
+                    ToolbarAction::SetDopeSheetMode(enabled) => {
+                        for (widget, visibility) in
+                            [(self.dope_sheet, enabled), (self.curve_editor, !enabled)]
+                        {
+                            engine
+                                .user_interface
+                                .send_message(WidgetMessage::visibility(
+                                    widget,
+                                    MessageDirection::ToWidget,
+                                    visibility,
+                                ));
+                        }
+                    }
                     ToolbarAction::Stop => {
                         if self.preview_mode_data.is_some() {
                             if let Some(animation) = animation_player
@@ -584,6 +684,31 @@ impl AnimationEditor {
                     &mut engine.user_interface,
                 );
 
+                send_sync_message(
+                    &engine.user_interface,
+                    DopeSheetMessage::sync(
+                        self.dope_sheet,
+                        MessageDirection::ToWidget,
+                        animation
+                            .tracks()
+                            .iter()
+                            .map(|track| DopeSheetTrack {
+                                id: track.id(),
+                                name: format!(
+                                    "{}: {}",
+                                    scene
+                                        .graph
+                                        .try_get(track.target())
+                                        .map(|n| n.name())
+                                        .unwrap_or_default(),
+                                    track.binding()
+                                ),
+                                curves: track.data_container().curves_ref().to_vec(),
+                            })
+                            .collect(),
+                    ),
+                );
+
                 send_sync_message(
                     &engine.user_interface,
                     CurveEditorMessage::hightlight_zones(
@@ -665,6 +790,13 @@ impl AnimationEditor {
             );
         }
 
+        if !is_animation_selected || !is_animation_player_selected {
+            send_sync_message(
+                ui,
+                DopeSheetMessage::sync(self.dope_sheet, MessageDirection::ToWidget, Vec::new()),
+            );
+        }
+
         if !is_animation_selected || !is_animation_player_selected || !is_curve_selected {
             send_sync_message(
                 ui,
@@ -63,8 +63,10 @@ pub struct Toolbar {
     pub remove_current_animation: Handle<UiNode>,
     pub rename_current_animation: Handle<UiNode>,
     pub clone_current_animation: Handle<UiNode>,
+    pub make_additive_animation: Handle<UiNode>,
     pub animation_name: Handle<UiNode>,
     pub preview: Handle<UiNode>,
+    pub dope_sheet: Handle<UiNode>,
     pub time_slice_start: Handle<UiNode>,
     pub time_slice_end: Handle<UiNode>,
     pub import: Handle<UiNode>,
@@ -368,6 +370,7 @@ pub enum ToolbarAction {
     SelectAnimation(Handle<Animation>),
     PlayPause,
     Stop,
+    SetDopeSheetMode(bool),
 }
 
 impl Toolbar {
@@ -380,8 +383,10 @@ impl Toolbar {
         let remove_current_animation;
         let rename_current_animation;
         let clone_current_animation;
+        let make_additive_animation;
         let animation_name;
         let preview;
+        let dope_sheet;
         let time_slice_start;
         let time_slice_end;
         let import;
@@ -567,6 +572,25 @@ impl Toolbar {
                                 .build(ctx);
                                 clone_current_animation
                             })
+                            .with_child({
+                                make_additive_animation = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_height(20.0)
+                                        .with_vertical_alignment(VerticalAlignment::Center)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Create an additive copy of the selected animation, \
+                                            that uses the first frame of the animation as the \
+                                            reference pose. Additive animations should be played \
+                                            on additive layers of animation blending state machines.",
+                                        )),
+                                )
+                                .with_text("Make Additive")
+                                .build(ctx);
+                                make_additive_animation
+                            })
                             .with_child({
                                 looping = CheckBoxBuilder::new(
                                     WidgetBuilder::new()
@@ -713,6 +737,28 @@ impl Toolbar {
                                 .build(ctx);
                                 preview
                             })
+                            .with_child({
+                                dope_sheet = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show keys of every track as a dope sheet instead of \
+                                            the curve editor.",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Dope Sheet")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                dope_sheet
+                            })
                             .with_child({
                                 play_pause = ButtonBuilder::new(
                                     WidgetBuilder::new().with_enabled(false).with_margin(
@@ -814,9 +860,11 @@ impl Toolbar {
             remove_current_animation,
             animation_name,
             preview,
+            dope_sheet,
             time_slice_start,
             time_slice_end,
             clone_current_animation,
+            make_additive_animation,
             import,
             reimport,
             node_selector,
@@ -940,6 +988,18 @@ impl Toolbar {
                         animation_clone,
                     ));
                 }
+            } else if message.destination() == self.make_additive_animation {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let mut additive_animation = animation.clone();
+                    additive_animation.make_additive(animation, animation.time_slice().start);
+                    additive_animation.set_name(format!("{} Additive", animation.name()));
+
+                    sender.do_scene_command(AddAnimationCommand::new(
+                        animation_player_handle,
+                        additive_animation,
+                    ));
+                }
             }
         } else if let Some(CheckBoxMessage::Check(Some(checked))) = message.data() {
             if message.direction() == MessageDirection::FromWidget {
@@ -949,6 +1009,8 @@ impl Toolbar {
                     } else {
                         ToolbarAction::LeavePreviewMode
                     };
+                } else if message.destination() == self.dope_sheet {
+                    return ToolbarAction::SetDopeSheetMode(*checked);
                 } else if message.destination() == self.looping {
                     sender.do_scene_command(SetAnimationLoopingCommand {
                         node_handle: animation_player_handle,
@@ -1249,6 +1311,7 @@ impl Toolbar {
             self.time_slice_start,
             self.time_slice_end,
             self.clone_current_animation,
+            self.make_additive_animation,
             self.looping,
             self.enabled,
             self.root_motion,
@@ -0,0 +1,1052 @@
+use crate::{message::MessageSender, AssetItem, Message};
+use fyrox::{
+    asset::{
+        collect_used_resources,
+        graph::{ResourceDependencyGraph, ResourceGraphNode},
+        key::ResourcePathKey,
+        manager::ResourceManager,
+        options::OPTIONS_EXTENSION,
+        remap_used_resources,
+        state::ResourceState,
+        untyped::UntypedResource,
+    },
+    core::{
+        futures::executor::block_on, log::Log, make_relative_path, pool::Handle, reflect::Reflect,
+        TypeUuidProvider,
+    },
+    engine::Engine,
+    fxhash::FxHashSet,
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        decorator::DecoratorBuilder,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::{model::Model, texture::Texture},
+    scene::{sound::SoundBuffer, Scene},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+};
+
+/// A resource file, that references an asset either directly or via its embedded resources (an
+/// embedded material of a scene, for example).
+pub struct AssetReference {
+    pub resource: UntypedResource,
+    /// The resource itself and its embedded resources, that use the asset directly.
+    pub referencing: Vec<UntypedResource>,
+}
+
+impl AssetReference {
+    pub fn path(&self) -> PathBuf {
+        self.resource.path()
+    }
+}
+
+fn is_asset(resource: &UntypedResource, asset: &UntypedResource, key: &ResourcePathKey) -> bool {
+    resource == asset || key.matches(&resource.path())
+}
+
+fn find_referencing(
+    node: &ResourceGraphNode,
+    asset: &UntypedResource,
+    key: &ResourcePathKey,
+    referencing: &mut Vec<UntypedResource>,
+) {
+    for child in node.children.iter() {
+        if is_asset(&child.resource, asset, key) {
+            if !referencing.contains(&node.resource) {
+                referencing.push(node.resource.clone());
+            }
+        } else if child.resource.is_embedded() {
+            // Other files are checked separately, embedded resources are saved with the file.
+            find_referencing(child, asset, key, referencing);
+        }
+    }
+}
+
+/// Returns `false` if the file at the given path could only be loaded as a resource, that cannot
+/// reference other assets (a texture or a sound buffer). Such files are not loaded by the scan.
+fn may_reference_assets(path: &Path, resource_manager: &ResourceManager) -> bool {
+    let Some(ext) = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+    else {
+        return true;
+    };
+    let leaf_types = [Texture::type_uuid(), SoundBuffer::type_uuid()];
+    let state = resource_manager.state();
+    let mut loaders = state.loaders.iter_for_extension(&ext).peekable();
+    loaders.peek().is_none() || loaders.any(|loader| !leaf_types.contains(&loader.data_type_uuid()))
+}
+
+/// Loads every resource of the project, that could reference other assets, and returns the ones,
+/// that reference the given asset. Only the resources stored in files are returned, references
+/// from their embedded resources are attributed to the files. `progress` is called with the
+/// amount of checked and total files after each file. The scan stops early, if `cancel` is set.
+pub fn find_asset_references(
+    asset: &UntypedResource,
+    resource_manager: &ResourceManager,
+    cancel: &AtomicBool,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Vec<AssetReference> {
+    let key = resource_manager.state().path_key(&asset.path());
+
+    let paths = match block_on(resource_manager.find_loadable_resources("./")) {
+        Ok(paths) => paths,
+        Err(err) => {
+            Log::err(format!("Unable to scan the project. Reason: {:?}", err));
+            return Default::default();
+        }
+    };
+
+    let mut references = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        progress(i, paths.len());
+
+        let Ok(path) = make_relative_path(path) else {
+            continue;
+        };
+        if key.matches(&path) || !may_reference_assets(&path, resource_manager) {
+            continue;
+        }
+
+        let Ok(resource) = block_on(resource_manager.request_untyped(&path)) else {
+            continue;
+        };
+
+        let mut referencing = Vec::new();
+        find_referencing(
+            &ResourceDependencyGraph::new(&resource).root,
+            asset,
+            &key,
+            &mut referencing,
+        );
+        if !referencing.is_empty() {
+            references.push(AssetReference {
+                resource,
+                referencing,
+            });
+        }
+    }
+    progress(paths.len(), paths.len());
+    references.sort_by_key(|reference| reference.path());
+    references
+}
+
+/// Returns `true` if the entity uses the asset directly, and the embedded resources used by the
+/// entity (recursively), that use the asset directly.
+#[allow(clippy::mutable_key_type)]
+fn find_referencing_embedded(
+    entity: &dyn Reflect,
+    asset: &UntypedResource,
+    key: &ResourcePathKey,
+) -> (bool, Vec<UntypedResource>) {
+    let mut used = FxHashSet::default();
+    collect_used_resources(entity, &mut used);
+
+    let mut direct = false;
+    let mut referencing = Vec::new();
+    for resource in used.iter() {
+        if is_asset(resource, asset, key) {
+            direct = true;
+        } else if resource.is_embedded() {
+            find_referencing(
+                &ResourceDependencyGraph::new(resource).root,
+                asset,
+                key,
+                &mut referencing,
+            );
+        }
+    }
+    (direct, referencing)
+}
+
+/// Returns `true` if the scene uses the asset directly or via its embedded resources.
+pub fn scene_uses_asset(scene: &Scene, asset: &UntypedResource, key: &ResourcePathKey) -> bool {
+    let (direct, referencing) = find_referencing_embedded(scene, asset, key);
+    direct || !referencing.is_empty()
+}
+
+fn remap_resource(
+    resource: &UntypedResource,
+    asset: &UntypedResource,
+    key: &ResourcePathKey,
+    replacement: &UntypedResource,
+) {
+    if let ResourceState::Ok(ref mut data) = *resource.lock() {
+        data.as_reflect_mut(&mut |entity| {
+            remap_used_resources(entity, &mut |used| {
+                if is_asset(used, asset, key) {
+                    Some(replacement.clone())
+                } else {
+                    None
+                }
+            })
+        });
+    }
+}
+
+/// Replaces every usage of the asset in the scene, including its embedded resources, with the
+/// replacement. The scene must be saved to keep the changes.
+pub fn replace_scene_references(
+    scene: &mut Scene,
+    asset: &UntypedResource,
+    replacement: &UntypedResource,
+    resource_manager: &ResourceManager,
+) {
+    let key = resource_manager.state().path_key(&asset.path());
+    let (_, referencing) = find_referencing_embedded(scene, asset, &key);
+    for resource in referencing.iter() {
+        remap_resource(resource, asset, &key, replacement);
+    }
+    remap_used_resources(scene, &mut |used| {
+        if is_asset(used, asset, &key) {
+            Some(replacement.clone())
+        } else {
+            None
+        }
+    });
+}
+
+/// Returns the reason, why references in the resource file cannot be replaced, if any.
+fn save_back_error(
+    resource: &UntypedResource,
+    resource_manager: &ResourceManager,
+) -> Option<String> {
+    if resource.type_uuid() == Model::type_uuid()
+        && !resource
+            .path()
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rgs"))
+    {
+        // Models could be imported from other formats (FBX, glTF, etc.), but they are always
+        // saved as native scenes, which would overwrite the source file.
+        return Some("Imported models cannot be saved back to their source format.".to_string());
+    }
+    resource_manager
+        .state()
+        .check_writable(resource)
+        .err()
+        .map(|err| err.to_string())
+}
+
+/// The result of [`replace_asset_references`].
+#[derive(Default, Debug)]
+pub struct ReplacementReport {
+    /// Paths of the resource files, that were updated and saved.
+    pub saved: Vec<PathBuf>,
+    /// Paths of the resource files, that still reference the asset, with the reasons.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl ReplacementReport {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Replaces every usage of the asset in the given references with the replacement and saves each
+/// of the modified resource files. Files, that cannot be saved back (imported models, read-only
+/// resources), are left untouched. Other resources are not saved, even if they are modified.
+pub fn replace_asset_references(
+    references: &[AssetReference],
+    asset: &UntypedResource,
+    replacement: &UntypedResource,
+    resource_manager: &ResourceManager,
+) -> ReplacementReport {
+    let key = resource_manager.state().path_key(&asset.path());
+
+    let mut report = ReplacementReport::default();
+    for reference in references {
+        if let Some(reason) = save_back_error(&reference.resource, resource_manager) {
+            report.failed.push((reference.path(), reason));
+            continue;
+        }
+
+        for resource in reference.referencing.iter() {
+            remap_resource(resource, asset, &key, replacement);
+        }
+
+        match resource_manager.save_resource(&reference.resource) {
+            Ok(()) => report.saved.push(reference.path()),
+            Err(err) => report.failed.push((reference.path(), err.to_string())),
+        }
+    }
+    report
+}
+
+fn delete_asset_file(path: &Path) -> bool {
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            let mut options_path = path.as_os_str().to_owned();
+            options_path.push(".");
+            options_path.push(OPTIONS_EXTENSION);
+            let options_path = PathBuf::from(options_path);
+            if options_path.exists() {
+                Log::verify(std::fs::remove_file(options_path));
+            }
+            true
+        }
+        Err(err) => {
+            Log::err(format!(
+                "Unable to delete {}. Reason: {:?}",
+                path.display(),
+                err
+            ));
+            false
+        }
+    }
+}
+
+enum ScanEvent {
+    Progress { checked: usize, total: usize },
+    Finished(Vec<AssetReference>),
+}
+
+/// A reference scan running on a separate thread.
+struct ReferenceScan {
+    receiver: Receiver<ScanEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl ReferenceScan {
+    fn start(asset: UntypedResource, resource_manager: ResourceManager) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            let references = find_asset_references(
+                &asset,
+                &resource_manager,
+                &thread_cancel,
+                &mut |checked, total| {
+                    let _ = sender.send(ScanEvent::Progress { checked, total });
+                },
+            );
+            let _ = sender.send(ScanEvent::Finished(references));
+        });
+        Self { receiver, cancel }
+    }
+}
+
+impl Drop for ReferenceScan {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A confirmation dialog for asset deletion. It lists every scene, prefab or other resource, that
+/// references the asset, and allows to replace the references with some other asset, so deletion
+/// does not leave dangling paths.
+pub struct AssetDeletionDialog {
+    pub window: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    list: Handle<UiNode>,
+    replacement_path: Handle<UiNode>,
+    delete: Handle<UiNode>,
+    replace_and_delete: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    asset: Option<UntypedResource>,
+    references: Vec<AssetReference>,
+    /// Open scenes, that use the asset, and their names.
+    scenes: Vec<(Handle<Scene>, String)>,
+    scan: Option<ReferenceScan>,
+    replacement: String,
+}
+
+fn make_reference_item(text: String, ctx: &mut BuildContext) -> Handle<UiNode> {
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_child(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                .with_text(text)
+                .build(ctx),
+        ),
+    ))
+    .build(ctx)
+}
+
+fn make_button(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(130.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl AssetDeletionDialog {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let summary;
+        let list;
+        let replacement_path;
+        let delete;
+        let replace_and_delete;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(420.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Delete Asset"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            summary = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            summary
+                        })
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            list
+                        })
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Replace With")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        replacement_path = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_allow_drop(true)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Path of an asset of the same type. \
+                                                    Drag an asset here to use its path.",
+                                                )),
+                                        )
+                                        .with_text_commit_mode(TextCommitMode::Immediate)
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx);
+                                        replacement_path
+                                    }),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::strict(90.0))
+                            .add_column(Column::stretch())
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(2.0))
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(3)
+                                    .with_child({
+                                        replace_and_delete = make_button("Replace And Delete", ctx);
+                                        replace_and_delete
+                                    })
+                                    .with_child({
+                                        delete = make_button("Delete", ctx);
+                                        delete
+                                    })
+                                    .with_child({
+                                        cancel = make_button("Cancel", ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(26.0))
+                .add_row(Row::strict(26.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            summary,
+            list,
+            replacement_path,
+            delete,
+            replace_and_delete,
+            cancel,
+            asset: None,
+            references: Default::default(),
+            scenes: Default::default(),
+            scan: None,
+            replacement: Default::default(),
+        }
+    }
+
+    /// Opens the dialog and starts looking for the references to the asset at the given path in
+    /// the project files. `open_scenes` is a list of the scenes opened in the editor and their
+    /// names, the ones that use the asset are listed as well.
+    pub fn open(
+        &mut self,
+        path: &Path,
+        open_scenes: &[(Handle<Scene>, String)],
+        engine: &mut Engine,
+    ) {
+        let Ok(path) = make_relative_path(path) else {
+            return;
+        };
+        let resource_manager = &engine.resource_manager;
+        let asset = match block_on(resource_manager.request_untyped(&path)) {
+            Ok(asset) => asset,
+            Err(err) => {
+                // The asset could not be used anywhere, if it cannot be loaded. Still, deletion
+                // must be confirmed.
+                Log::warn(format!(
+                    "Unable to load {} to find its references. Reason: {:?}",
+                    path.display(),
+                    err
+                ));
+                resource_manager.request_untyped(&path)
+            }
+        };
+
+        let key = resource_manager.state().path_key(&path);
+        self.scenes = open_scenes
+            .iter()
+            .filter(|(scene, _)| {
+                engine
+                    .scenes
+                    .try_get(*scene)
+                    .is_some_and(|scene| scene_uses_asset(scene, &asset, &key))
+            })
+            .cloned()
+            .collect();
+        self.references.clear();
+        self.scan = Some(ReferenceScan::start(
+            asset.clone(),
+            resource_manager.clone(),
+        ));
+        self.asset = Some(asset);
+
+        let ui = &mut engine.user_interface;
+        self.sync_summary(
+            ui,
+            format!("Looking for references to {}...", path.display()),
+        );
+        self.sync_list(ui);
+
+        self.replacement.clear();
+        ui.send_message(TextMessage::text(
+            self.replacement_path,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+        self.sync_buttons(ui);
+
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn sync_summary(&self, ui: &UserInterface, summary: String) {
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            summary,
+        ));
+    }
+
+    fn sync_list(&self, ui: &mut UserInterface) {
+        let ctx = &mut ui.build_ctx();
+        let texts = self
+            .scenes
+            .iter()
+            .map(|(_, name)| format!("{name} (open scene)"))
+            .chain(self.references.iter().map(|reference| {
+                let mut text = reference.path().display().to_string();
+                let embedded = reference
+                    .referencing
+                    .iter()
+                    .filter(|resource| *resource != &reference.resource)
+                    .count();
+                if embedded > 0 {
+                    text += &format!(" ({embedded} embedded resource(s))");
+                }
+                text
+            }))
+            .collect::<Vec<_>>();
+        let items = texts
+            .into_iter()
+            .map(|text| make_reference_item(text, ctx))
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+    }
+
+    fn sync_buttons(&self, ui: &UserInterface) {
+        let scanning = self.scan.is_some();
+        ui.send_message(WidgetMessage::enabled(
+            self.delete,
+            MessageDirection::ToWidget,
+            !scanning,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.replace_and_delete,
+            MessageDirection::ToWidget,
+            !scanning && (!self.references.is_empty() || !self.scenes.is_empty()),
+        ));
+    }
+
+    fn on_scan_finished(&mut self, references: Vec<AssetReference>, ui: &mut UserInterface) {
+        self.scan = None;
+        self.references = references;
+
+        let path = self
+            .asset
+            .as_ref()
+            .map(|asset| asset.path())
+            .unwrap_or_default();
+        let summary = if self.references.is_empty() && self.scenes.is_empty() {
+            format!("{} is not referenced by the project.", path.display())
+        } else {
+            format!(
+                "{} is referenced by {} resource(s) and {} open scene(s). Deleting it will leave \
+                dangling references, unless they are replaced.",
+                path.display(),
+                self.references.len(),
+                self.scenes.len()
+            )
+        };
+        self.sync_summary(ui, summary);
+        self.sync_list(ui);
+        self.sync_buttons(ui);
+    }
+
+    /// Polls the reference scan, must be called every frame.
+    pub fn update(&mut self, ui: &mut UserInterface) {
+        let Some(scan) = self.scan.as_ref() else {
+            return;
+        };
+        let mut progress = None;
+        let mut finished = None;
+        for event in scan.receiver.try_iter() {
+            match event {
+                ScanEvent::Progress { checked, total } => progress = Some((checked, total)),
+                ScanEvent::Finished(references) => finished = Some(references),
+            }
+        }
+        if let Some(references) = finished {
+            self.on_scan_finished(references, ui);
+        } else if let Some((checked, total)) = progress {
+            let path = self
+                .asset
+                .as_ref()
+                .map(|asset| asset.path())
+                .unwrap_or_default();
+            self.sync_summary(
+                ui,
+                format!(
+                    "Looking for references to {}... {checked}/{total} file(s) checked.",
+                    path.display()
+                ),
+            );
+        }
+    }
+
+    fn close(&mut self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    /// Replaces the references with the asset at the replacement path. Returns `None` if the
+    /// replacement is not suitable.
+    fn replace_references(
+        &mut self,
+        asset: &UntypedResource,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) -> Option<ReplacementReport> {
+        let resource_manager = &engine.resource_manager;
+        let Ok(replacement_path) = make_relative_path(self.replacement.trim()) else {
+            Log::err(format!(
+                "Replacement asset {} does not exist!",
+                self.replacement
+            ));
+            return None;
+        };
+        let replacement = match block_on(resource_manager.request_untyped(&replacement_path)) {
+            Ok(replacement) => replacement,
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to load replacement asset {}. Reason: {:?}",
+                    replacement_path.display(),
+                    err
+                ));
+                return None;
+            }
+        };
+        if replacement == *asset {
+            Log::err("An asset cannot be replaced with itself!");
+            return None;
+        }
+        if replacement.type_uuid() != asset.type_uuid() {
+            Log::err(format!(
+                "Unable to replace {} with {}, because their types do not match!",
+                asset.path().display(),
+                replacement_path.display()
+            ));
+            return None;
+        }
+
+        for (scene, _) in self.scenes.drain(..) {
+            if let Some(scene_ref) = engine.scenes.try_get_mut(scene) {
+                replace_scene_references(scene_ref, asset, &replacement, &engine.resource_manager);
+                sender.send(Message::MarkSceneModified(scene));
+            }
+        }
+
+        let report = replace_asset_references(
+            &self.references,
+            asset,
+            &replacement,
+            &engine.resource_manager,
+        );
+        Log::info(format!(
+            "References to {} were replaced with {}, {} resource(s) saved, {} failed.",
+            asset.path().display(),
+            replacement_path.display(),
+            report.saved.len(),
+            report.failed.len()
+        ));
+        // Only the files, that still reference the asset, are kept.
+        self.references
+            .retain(|reference| !report.saved.contains(&reference.path()));
+        Some(report)
+    }
+
+    fn show_failures(&self, report: &ReplacementReport, ui: &mut UserInterface) {
+        self.sync_summary(
+            ui,
+            format!(
+                "{} resource(s) were saved, but the following resource(s) still reference the \
+                asset, so it was not deleted. Fix them manually or delete the asset anyway.",
+                report.saved.len()
+            ),
+        );
+        let ctx = &mut ui.build_ctx();
+        let items = report
+            .failed
+            .iter()
+            .map(|(path, reason)| make_reference_item(format!("{}: {reason}", path.display()), ctx))
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        self.sync_buttons(ui);
+    }
+
+    /// Handles the dialog messages. Returns `true` if the asset was deleted.
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) -> bool {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.cancel {
+                self.close(&engine.user_interface);
+            } else if message.destination() == self.delete
+                || message.destination() == self.replace_and_delete
+            {
+                let Some(asset) = self.asset.clone() else {
+                    return false;
+                };
+                if message.destination() == self.replace_and_delete {
+                    match self.replace_references(&asset, engine, sender) {
+                        Some(report) if report.is_complete() => (),
+                        Some(report) => {
+                            // Deleting the asset would leave dangling references.
+                            self.show_failures(&report, &mut engine.user_interface);
+                            return false;
+                        }
+                        None => return false,
+                    }
+                }
+                let deleted = delete_asset_file(&asset.path());
+                self.close(&engine.user_interface);
+                return deleted;
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.replacement_path
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.replacement.clone_from(text);
+            }
+        } else if let Some(WidgetMessage::Drop(dropped)) = message.data() {
+            if message.destination() == self.replacement_path {
+                let ui = &engine.user_interface;
+                if let Some(item) = ui
+                    .try_get_node(*dropped)
+                    .and_then(|n| n.cast::<AssetItem>())
+                {
+                    ui.send_message(TextMessage::text(
+                        self.replacement_path,
+                        MessageDirection::ToWidget,
+                        item.path.to_string_lossy().to_string(),
+                    ));
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.asset = None;
+                self.references.clear();
+                self.scenes.clear();
+                self.scan = None;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::{
+        asset::ResourceData,
+        core::{
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+        },
+    };
+    use std::{any::Any, error::Error, slice};
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct StubFile {
+        #[reflect(hidden)]
+        path: PathBuf,
+        #[reflect(hidden)]
+        embedded: bool,
+        #[reflect(hidden)]
+        fail_save: bool,
+        #[reflect(hidden)]
+        saves: usize,
+        used: Vec<UntypedResource>,
+    }
+
+    impl TypeUuidProvider for StubFile {
+        fn type_uuid() -> Uuid {
+            uuid!("6f0f7f4e-3c0d-4d4e-9a43-2f4b1f6ee1a9")
+        }
+    }
+
+    impl ResourceData for StubFile {
+        fn path(&self) -> &Path {
+            &self.path
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            self.embedded
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+            if self.fail_save {
+                return Err("Disk is full".into());
+            }
+            self.saves += 1;
+            Ok(())
+        }
+    }
+
+    fn file(path: &str, used: Vec<UntypedResource>) -> UntypedResource {
+        UntypedResource::new_ok(StubFile {
+            path: path.into(),
+            used,
+            ..Default::default()
+        })
+    }
+
+    fn used(resource: &UntypedResource) -> Vec<UntypedResource> {
+        resource
+            .try_cast::<StubFile>()
+            .unwrap()
+            .data_ref()
+            .used
+            .clone()
+    }
+
+    fn saves(resource: &UntypedResource) -> usize {
+        resource.try_cast::<StubFile>().unwrap().data_ref().saves
+    }
+
+    fn references(
+        files: &[&UntypedResource],
+        asset: &UntypedResource,
+        key: &ResourcePathKey,
+    ) -> Vec<AssetReference> {
+        files
+            .iter()
+            .filter_map(|&resource| {
+                let mut referencing = Vec::new();
+                find_referencing(
+                    &ResourceDependencyGraph::new(resource).root,
+                    asset,
+                    key,
+                    &mut referencing,
+                );
+                (!referencing.is_empty()).then(|| AssetReference {
+                    resource: resource.clone(),
+                    referencing,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_referencing() {
+        let manager = ResourceManager::new();
+        let asset = file("asset.txt", vec![]);
+        let key = manager.state().path_key(&asset.path());
+
+        let embedded = UntypedResource::new_ok(StubFile {
+            embedded: true,
+            used: vec![asset.clone()],
+            ..Default::default()
+        });
+        let via_embedded = file("a.txt", vec![embedded.clone()]);
+        let direct = file("b.txt", vec![asset.clone()]);
+        // Another instance of the asset, that has the same path.
+        let by_path = file("c.txt", vec![file("asset.txt", vec![])]);
+        let unrelated = file("d.txt", vec![file("other.txt", vec![asset.clone()])]);
+
+        let find = |resource: &UntypedResource| {
+            let mut referencing = Vec::new();
+            find_referencing(
+                &ResourceDependencyGraph::new(resource).root,
+                &asset,
+                &key,
+                &mut referencing,
+            );
+            referencing
+        };
+        assert_eq!(find(&via_embedded), [embedded]);
+        assert_eq!(find(&direct), slice::from_ref(&direct));
+        assert_eq!(find(&by_path), slice::from_ref(&by_path));
+        // Other files are checked on their own.
+        assert!(find(&unrelated).is_empty());
+    }
+
+    #[test]
+    fn test_replace_asset_references() {
+        let manager = ResourceManager::new();
+        let asset = file("asset.txt", vec![]);
+        let replacement = file("replacement.txt", vec![]);
+        let key = manager.state().path_key(&asset.path());
+
+        let embedded = UntypedResource::new_ok(StubFile {
+            embedded: true,
+            used: vec![asset.clone()],
+            ..Default::default()
+        });
+        let via_embedded = file("a.txt", vec![embedded.clone()]);
+        let direct = file("b.txt", vec![asset.clone(), replacement.clone()]);
+        let read_only = file("c.txt", vec![asset.clone()]);
+        manager
+            .state()
+            .read_only_resources_mut()
+            .protect(&read_only);
+        let failing = UntypedResource::new_ok(StubFile {
+            path: "d.txt".into(),
+            fail_save: true,
+            used: vec![asset.clone()],
+            ..Default::default()
+        });
+
+        let references = references(
+            &[&via_embedded, &direct, &read_only, &failing],
+            &asset,
+            &key,
+        );
+        assert_eq!(references.len(), 4);
+
+        let report = replace_asset_references(&references, &asset, &replacement, &manager);
+
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.saved,
+            [PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert_eq!(
+            report
+                .failed
+                .iter()
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>(),
+            [PathBuf::from("c.txt"), PathBuf::from("d.txt")]
+        );
+
+        assert_eq!(used(&embedded), slice::from_ref(&replacement));
+        assert_eq!(saves(&via_embedded), 1);
+        assert_eq!(used(&direct), [replacement.clone(), replacement.clone()]);
+        assert_eq!(saves(&direct), 1);
+        // Read-only resources are left untouched.
+        assert_eq!(used(&read_only), slice::from_ref(&asset));
+        assert_eq!(saves(&read_only), 0);
+        assert_eq!(saves(&failing), 0);
+    }
+}
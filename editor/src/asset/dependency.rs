@@ -37,7 +37,7 @@ fn build_tree_recursively(node: &ResourceGraphNode, ctx: &mut BuildContext) -> H
         .collect();
 
     let mut embedded = false;
-    let data_type = if let ResourceState::Ok(data) = &*node.resource.0.lock() {
+    let data_type = if let ResourceState::Ok(data) = &*node.resource.lock() {
         embedded = data.is_embedded();
         data.type_name().to_string()
     } else {
@@ -3,8 +3,11 @@ use crate::{
 };
 use fyrox::core::reflect::Reflect;
 use fyrox::{
-    asset::{manager::ResourceManager, options::BaseImportOptions},
-    core::{append_extension, futures::executor::block_on, log::Log, pool::Handle},
+    asset::{
+        manager::ResourceManager,
+        options::{self, BaseImportOptions},
+    },
+    core::{futures::executor::block_on, log::Log, pool::Handle},
     engine::Engine,
     gui::{
         button::{ButtonBuilder, ButtonMessage},
@@ -136,8 +139,11 @@ impl AssetInspector {
     pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
         if let Some(context) = self.context.as_mut() {
             if let Some(extension) = context.resource_path.extension() {
-                let default_import_options =
-                    default_import_options(extension, &engine.resource_manager);
+                let default_import_options = default_import_options(
+                    &context.resource_path,
+                    extension,
+                    &engine.resource_manager,
+                );
 
                 if let Some(ButtonMessage::Click) = message.data() {
                     if message.destination() == self.revert {
@@ -164,9 +170,9 @@ impl AssetInspector {
                             });
                         }
                     } else if message.destination() == self.apply {
-                        context
-                            .import_options
-                            .save(&append_extension(&context.resource_path, "options"));
+                        engine
+                            .resource_manager
+                            .save_import_options(&*context.import_options, &context.resource_path);
 
                         if let Ok(resource) = block_on(
                             engine
@@ -197,9 +203,15 @@ impl AssetInspector {
 }
 
 fn default_import_options(
+    resource_path: &Path,
     extension: &OsStr,
     resource_manager: &ResourceManager,
 ) -> Option<Box<dyn BaseImportOptions>> {
+    // Project-wide defaults registered for the extension have priority over the loader defaults.
+    if let Some(import_options) = options::default_import_options_opaque(resource_path) {
+        return Some(import_options);
+    }
+
     let rm_state = resource_manager.state();
     for loader in rm_state.loaders.iter() {
         if loader.supports_extension(&extension.to_string_lossy()) {
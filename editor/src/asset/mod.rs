@@ -1,7 +1,7 @@
 use crate::{
     asset::{
-        dependency::DependencyViewer, inspector::AssetInspector, item::AssetItemBuilder,
-        preview::AssetPreviewGeneratorsCollection,
+        deletion::AssetDeletionDialog, dependency::DependencyViewer, inspector::AssetInspector,
+        item::AssetItemBuilder, preview::AssetPreviewGeneratorsCollection, usage::AssetUsageViewer,
     },
     gui::{make_dropdown_list_option, AssetItemMessage},
     message::MessageSender,
@@ -10,7 +10,9 @@ use crate::{
     AssetItem, Message, Mode,
 };
 use fyrox::{
-    asset::{manager::ResourceManager, state::ResourceState, untyped::UntypedResource},
+    asset::{
+        io::is_temp_path, manager::ResourceManager, state::ResourceState, untyped::UntypedResource,
+    },
     core::{
         color::Color, futures::executor::block_on, log::Log, make_relative_path,
         parking_lot::lock_api::Mutex, pool::Handle, scope_profile, TypeUuidProvider,
@@ -41,8 +43,11 @@ use fyrox::{
         UserInterface, VerticalAlignment, BRUSH_DARK,
     },
     material::Material,
-    resource::{model::Model, texture::Texture},
-    scene::sound::SoundBuffer,
+    resource::{
+        behavior_tree::BehaviorTreeAsset, dialogue::Dialogue, model::Model,
+        post_effects::PostEffects, sound_bank::SoundBank, texture::Texture,
+    },
+    scene::{sound::SoundBuffer, Scene},
     walkdir,
 };
 use std::{
@@ -52,10 +57,12 @@ use std::{
     sync::Arc,
 };
 
+pub mod deletion;
 mod dependency;
 mod inspector;
 pub mod item;
 pub mod preview;
+pub mod usage;
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -66,6 +73,7 @@ struct ContextMenu {
     delete: Handle<UiNode>,
     placement_target: Handle<UiNode>,
     dependencies: Handle<UiNode>,
+    usage: Handle<UiNode>,
 }
 
 fn execute_command(command: &mut Command) {
@@ -104,6 +112,7 @@ impl ContextMenu {
         let copy_path;
         let copy_file_name;
         let dependencies;
+        let usage;
         let menu = PopupBuilder::new(WidgetBuilder::new())
             .with_content(
                 StackPanelBuilder::new(
@@ -143,6 +152,12 @@ impl ContextMenu {
                                 .with_content(MenuItemContent::text("Dependencies"))
                                 .build(ctx);
                             dependencies
+                        })
+                        .with_child({
+                            usage = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Usage In Scene"))
+                                .build(ctx);
+                            usage
                         }),
                 )
                 .build(ctx),
@@ -159,6 +174,7 @@ impl ContextMenu {
             placement_target: Default::default(),
             copy_file_name,
             dependencies,
+            usage,
         }
     }
 
@@ -178,9 +194,7 @@ impl ContextMenu {
                 .try_get_node(self.placement_target)
                 .and_then(|n| n.cast::<AssetItem>())
             {
-                if message.destination() == self.delete {
-                    Log::verify(std::fs::remove_file(&item.path))
-                } else if message.destination() == self.show_in_explorer {
+                if message.destination() == self.show_in_explorer {
                     if let Ok(canonical_path) = item.path.canonicalize() {
                         show_in_explorer(canonical_path)
                     }
@@ -195,6 +209,42 @@ impl ContextMenu {
                                 sender.send(Message::OpenMaterialEditor(material));
                             }
                         }
+                    } else if item.path.extension().is_some_and(|ext| ext == "dialogue") {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(dialogue) =
+                                block_on(engine.resource_manager.request::<Dialogue>(path))
+                            {
+                                sender.send(Message::OpenDialogueEditor(dialogue));
+                            }
+                        }
+                    } else if item.path.extension().is_some_and(|ext| ext == "behavior") {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(tree) =
+                                block_on(engine.resource_manager.request::<BehaviorTreeAsset>(path))
+                            {
+                                sender.send(Message::OpenBehaviorTreeEditor(tree));
+                            }
+                        }
+                    } else if item.path.extension().is_some_and(|ext| ext == "soundbank") {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(bank) =
+                                block_on(engine.resource_manager.request::<SoundBank>(path))
+                            {
+                                sender.send(Message::OpenSoundBankEditor(bank));
+                            }
+                        }
+                    } else if item
+                        .path
+                        .extension()
+                        .is_some_and(|ext| ext == "posteffects")
+                    {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(post_effects) =
+                                block_on(engine.resource_manager.request::<PostEffects>(path))
+                            {
+                                sender.send(Message::OpenPostEffectsEditor(post_effects));
+                            }
+                        }
                     } else {
                         open_in_explorer(&item.path)
                     }
@@ -206,6 +256,11 @@ impl ContextMenu {
                     if let Some(file_name) = item.path.clone().file_name() {
                         put_path_to_clipboard(engine, file_name)
                     }
+                } else if message.destination() == self.usage {
+                    sender.send(Message::ShowAssetUsage(item.path.clone()));
+                } else if message.destination() == self.delete {
+                    // References to the asset are checked first, deletion must be confirmed.
+                    sender.send(Message::DeleteAsset(item.path.clone()));
                 }
             }
         }
@@ -428,6 +483,8 @@ pub struct AssetBrowser {
     context_menu: ContextMenu,
     selected_path: PathBuf,
     dependency_viewer: DependencyViewer,
+    usage_viewer: AssetUsageViewer,
+    deletion_dialog: AssetDeletionDialog,
     resource_creator: Option<ResourceCreator>,
     pub preview_generators: AssetPreviewGeneratorsCollection,
 }
@@ -570,9 +627,13 @@ impl AssetBrowser {
         let context_menu = ContextMenu::new(ctx);
 
         let dependency_viewer = DependencyViewer::new(ctx);
+        let usage_viewer = AssetUsageViewer::new(ctx);
+        let deletion_dialog = AssetDeletionDialog::new(ctx);
 
         Self {
             dependency_viewer,
+            usage_viewer,
+            deletion_dialog,
             window,
             content_panel,
             folder_browser,
@@ -673,6 +734,7 @@ impl AssetBrowser {
             for entry in dir_iter.flatten() {
                 if let Ok(entry_path) = make_relative_path(entry.path()) {
                     if !entry_path.is_dir()
+                        && !is_temp_path(&entry_path)
                         && entry_path
                             .extension()
                             .map_or(false, |ext| is_supported_resource(ext, resource_manager))
@@ -734,6 +796,14 @@ impl AssetBrowser {
             .handle_ui_message(message, &sender, engine);
         self.dependency_viewer
             .handle_ui_message(message, &mut engine.user_interface);
+        self.usage_viewer
+            .handle_ui_message(message, &mut engine.user_interface, &sender);
+        if self
+            .deletion_dialog
+            .handle_ui_message(message, engine, &sender)
+        {
+            self.refresh(&mut engine.user_interface, &engine.resource_manager);
+        }
         if let Some(resource_creator) = self.resource_creator.as_mut() {
             let asset_added = resource_creator.handle_ui_message(
                 message,
@@ -898,7 +968,37 @@ impl AssetBrowser {
     }
 
     pub fn update(&mut self, engine: &mut Engine) {
-        self.preview.update(engine)
+        self.preview.update(engine);
+        self.deletion_dialog.update(&mut engine.user_interface);
+    }
+
+    /// Opens the deletion confirmation dialog for the asset at the given path. `open_scenes` is a
+    /// list of the scenes opened in the editor and their names.
+    pub fn delete_asset(
+        &mut self,
+        path: &Path,
+        open_scenes: &[(Handle<Scene>, String)],
+        engine: &mut Engine,
+    ) {
+        self.deletion_dialog.open(path, open_scenes, engine);
+    }
+
+    /// Shows every node of the given scene, that uses the asset at the given path.
+    pub fn show_asset_usage(&mut self, path: &Path, scene: Handle<Scene>, engine: &mut Engine) {
+        let Ok(path) = make_relative_path(path) else {
+            return;
+        };
+        match block_on(engine.resource_manager.request_untyped(&path)) {
+            Ok(resource) => {
+                self.usage_viewer
+                    .open(&resource, &engine.scenes[scene], &mut engine.user_interface)
+            }
+            Err(err) => Log::err(format!(
+                "Unable to find usages of {}. Reason: {:?}",
+                path.display(),
+                err
+            )),
+        }
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
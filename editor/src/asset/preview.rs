@@ -15,9 +15,11 @@ use fyrox::{
     resource::{
         curve::CurveResourceState,
         model::{Model, ModelResourceExtension},
+        spritesheet::SpriteSheet,
         texture::Texture,
     },
     scene::{
+        animation::spritesheet::SpriteAnimationPlayerBuilder,
         base::BaseBuilder,
         mesh::{
             surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
@@ -25,6 +27,7 @@ use fyrox::{
         },
         node::Node,
         sound::{HrirSphereResourceData, SoundBuffer, SoundBuilder, Status},
+        sprite::SpriteBuilder,
         Scene,
     },
     utils::into_gui_texture,
@@ -45,6 +48,7 @@ impl AssetPreviewGeneratorsCollection {
         this.add(Material::type_uuid(), MaterialPreview);
         this.add(HrirSphereResourceData::type_uuid(), HrirPreview);
         this.add(CurveResourceState::type_uuid(), CurvePreview);
+        this.add(SpriteSheet::type_uuid(), SpriteSheetPreview);
         this
     }
 
@@ -289,3 +293,59 @@ impl AssetPreview for CurvePreview {
         load_image(include_bytes!("../../resources/curve.png"))
     }
 }
+
+pub struct SpriteSheetPreview;
+
+impl AssetPreview for SpriteSheetPreview {
+    fn generate(
+        &mut self,
+        resource: &UntypedResource,
+        _resource_manager: &ResourceManager,
+        scene: &mut Scene,
+    ) -> Handle<Node> {
+        if let Some(sprite_sheet) = resource.try_cast::<SpriteSheet>() {
+            let (texture, animation) = {
+                let data = sprite_sheet.data_ref();
+                // Preview the first animation or every frame if there's no animations.
+                let animation = data
+                    .animations
+                    .first()
+                    .map(|a| a.name.clone())
+                    .unwrap_or_default();
+                (data.texture.clone(), animation)
+            };
+
+            let mut material = Material::standard_sprite();
+            Log::verify(material.set_property(
+                &ImmutableString::new("diffuseTexture"),
+                PropertyValue::Sampler {
+                    value: texture,
+                    fallback: Default::default(),
+                },
+            ));
+
+            let sprite = SpriteBuilder::new(BaseBuilder::new())
+                .with_material(MaterialResource::new_ok(material))
+                .build(&mut scene.graph);
+
+            SpriteAnimationPlayerBuilder::new(BaseBuilder::new().with_children(&[sprite]))
+                .with_sprite_sheet(Some(sprite_sheet))
+                .with_target(sprite)
+                .with_animation(animation)
+                .build(&mut scene.graph)
+        } else {
+            Handle::NONE
+        }
+    }
+
+    fn icon(
+        &self,
+        resource: &UntypedResource,
+        _resource_manager: &ResourceManager,
+    ) -> Option<SharedTexture> {
+        resource
+            .try_cast::<SpriteSheet>()
+            .and_then(|sprite_sheet| sprite_sheet.data_ref().texture.clone())
+            .map(into_gui_texture)
+    }
+}
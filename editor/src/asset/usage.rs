@@ -0,0 +1,334 @@
+use crate::{message::MessageSender, Message};
+use fyrox::{
+    asset::{collect_used_resources, graph::ResourceDependencyGraph, untyped::UntypedResource},
+    core::pool::Handle,
+    fxhash::{FxHashMap, FxHashSet},
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        decorator::DecoratorBuilder,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, node::Node, Scene},
+};
+use std::{any::TypeId, path::PathBuf};
+
+/// A node, that uses an asset either directly or via other resources (a texture via a material, for example).
+pub struct AssetUsage {
+    pub node: Handle<Node>,
+    /// A resource, that is used by the node and depends on the asset. `None` means direct usage.
+    pub via: Option<UntypedResource>,
+    /// Path of the prefab, that the node is instantiated from. `None` for nodes of the scene itself.
+    pub prefab: Option<PathBuf>,
+}
+
+/// Finds every node of the graph, that uses the given resource. It uses the same reflection-based
+/// resource collector as dependency analysis, so resources in hidden fields are not found.
+#[allow(clippy::mutable_key_type)]
+pub fn collect_asset_usage(resource: &UntypedResource, graph: &Graph) -> Vec<AssetUsage> {
+    // Resource -> whether it depends on the asset. Dependency graphs are expensive to build, so
+    // each used resource is checked once.
+    let mut depends_on_asset = FxHashMap::<usize, bool>::default();
+
+    let mut usages = Vec::new();
+    for (handle, node) in graph.pair_iter() {
+        let prefab = node.resource();
+
+        let mut used_resources = FxHashSet::default();
+        collect_used_resources(node, &mut used_resources);
+
+        let via = if used_resources.contains(resource) {
+            Some(None)
+        } else {
+            used_resources
+                .into_iter()
+                // Every node of a prefab instance refers to the prefab, it is not a usage of the
+                // prefab dependencies.
+                .filter(|used| prefab.as_ref().map(|prefab| prefab.key()) != Some(used.key()))
+                .find(|used| {
+                    *depends_on_asset.entry(used.key()).or_insert_with(|| {
+                        let mut found = false;
+                        ResourceDependencyGraph::new(used)
+                            .root
+                            .for_each(&mut |dependency| found |= dependency == resource);
+                        found
+                    })
+                })
+                .map(Some)
+        };
+
+        if let Some(via) = via {
+            usages.push(AssetUsage {
+                node: handle,
+                via,
+                prefab: prefab.map(|prefab| prefab.path()),
+            });
+        }
+    }
+    usages
+}
+
+pub struct AssetUsageViewer {
+    pub window: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    list: Handle<UiNode>,
+    select_all: Handle<UiNode>,
+    close: Handle<UiNode>,
+    nodes: Vec<Handle<Node>>,
+}
+
+fn make_usage_item(text: String, ctx: &mut BuildContext) -> Handle<UiNode> {
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_child(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                .with_text(text)
+                .build(ctx),
+        ),
+    ))
+    .build(ctx)
+}
+
+impl AssetUsageViewer {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let summary;
+        let list;
+        let select_all;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Asset Usage"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            summary = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            summary
+                        })
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            list
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(2.0))
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .with_child({
+                                        select_all = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Select All")
+                                        .build(ctx);
+                                        select_all
+                                    })
+                                    .with_child({
+                                        close = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Close")
+                                        .build(ctx);
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            summary,
+            list,
+            select_all,
+            close,
+            nodes: Default::default(),
+        }
+    }
+
+    pub fn open(&mut self, resource: &UntypedResource, scene: &Scene, ui: &mut UserInterface) {
+        let usages = collect_asset_usage(resource, &scene.graph);
+
+        // Amount of usages per prefab, nodes of the scene itself go first.
+        let mut per_prefab = FxHashMap::<Option<PathBuf>, usize>::default();
+        for usage in usages.iter() {
+            *per_prefab.entry(usage.prefab.clone()).or_default() += 1;
+        }
+        let mut per_prefab = per_prefab.into_iter().collect::<Vec<_>>();
+        per_prefab.sort_by(|(a, a_count), (b, b_count)| {
+            a.is_some().cmp(&b.is_some()).then(b_count.cmp(a_count))
+        });
+
+        let mut summary = format!("{} node(s) use {}", usages.len(), resource.path().display());
+        for (prefab, count) in per_prefab {
+            match prefab {
+                Some(prefab) => summary += &format!("\n    {}: {count}", prefab.display()),
+                None => summary += &format!("\n    Scene: {count}"),
+            }
+        }
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            summary,
+        ));
+
+        let ctx = &mut ui.build_ctx();
+        let items = usages
+            .iter()
+            .map(|usage| {
+                let name = scene.graph[usage.node].name();
+                let mut text = format!("{name} ({})", usage.node);
+                match usage.via.as_ref().map(|via| via.path()) {
+                    Some(path) if path.as_os_str().is_empty() => text += " via embedded resource",
+                    Some(path) => text += &format!(" via {}", path.display()),
+                    None => (),
+                }
+                make_usage_item(text, ctx)
+            })
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        self.nodes = usages.into_iter().map(|usage| usage.node).collect();
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.select_all {
+                // Selected nodes are highlighted both in the scene preview and in the world viewer.
+                sender.send(Message::SelectNodes(self.nodes.clone()));
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(node) = self.nodes.get(*index) {
+                    sender.send(Message::SelectObject {
+                        type_id: TypeId::of::<Node>(),
+                        handle: (*node).into(),
+                    });
+                    sender.send(Message::FocusObject(*node));
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.nodes.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::{
+        asset::Resource,
+        core::{algebra::Matrix4, sstorage::ImmutableString, uuid::Uuid, TypeUuidProvider},
+        material::{Material, MaterialResource},
+        resource::texture::{Texture, TextureResource},
+        scene::{
+            base::BaseBuilder,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            pivot::PivotBuilder,
+        },
+    };
+
+    fn mesh(material: &MaterialResource, graph: &mut Graph) -> Handle<Node> {
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(data)
+                .with_material(material.clone())
+                .build()])
+            .build(graph)
+    }
+
+    #[test]
+    fn asset_usage() {
+        let texture: TextureResource = Resource::from(UntypedResource::new_pending(
+            "texture.png".into(),
+            <Texture as TypeUuidProvider>::type_uuid(),
+        ));
+        let mut textured = Material::standard();
+        textured
+            .set_texture(
+                &ImmutableString::new("diffuseTexture"),
+                Some(texture.clone()),
+            )
+            .unwrap();
+        let textured = MaterialResource::new_ok(textured);
+
+        let mut graph = Graph::new();
+        let textured_mesh = mesh(&textured, &mut graph);
+        mesh(&MaterialResource::new_ok(Material::standard()), &mut graph);
+        PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let texture = texture.into_untyped();
+        let usages = collect_asset_usage(&texture, &graph);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].node, textured_mesh);
+        // The texture is used by the material of the mesh.
+        assert_eq!(
+            usages[0].via.as_ref().map(|via| via.key()),
+            Some(textured.clone().into_untyped().key())
+        );
+        assert_eq!(usages[0].prefab, None);
+
+        let usages = collect_asset_usage(&textured.into_untyped(), &graph);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].node, textured_mesh);
+        assert!(usages[0].via.is_none());
+
+        let unused = UntypedResource::new_pending("unused.png".into(), Uuid::default());
+        assert!(collect_asset_usage(&unused, &graph).is_empty());
+    }
+}
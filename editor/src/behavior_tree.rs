@@ -0,0 +1,764 @@
+use crate::{
+    absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage, Mode},
+        node::{AbsmNode, AbsmNodeBuilder},
+        transition::TransitionBuilder,
+        NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
+    },
+    define_command_stack,
+    document::{Document, DocumentInfo, DocumentKind},
+    inspector::editors::make_property_editors_container,
+    menu::create_menu_item,
+    message::MessageSender,
+    send_sync_message,
+    utils::create_file_selector,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::{Resource, ResourceData},
+    core::{futures::executor::block_on, log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        border::BorderBuilder,
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent, MenuItemMessage},
+        message::{MessageDirection, UiMessage},
+        popup::{Placement, PopupBuilder, PopupMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, RcUiNodeHandle, Thickness, UiNode, UserInterface,
+    },
+    resource::behavior_tree::{
+        BehaviorTreeAsset, BehaviorTreeNode, BehaviorTreeNodeKind, BehaviorTreeResource,
+        BlackboardComparison,
+    },
+};
+use std::{fmt::Debug, path::PathBuf, rc::Rc};
+
+#[derive(Debug)]
+pub struct BehaviorTreeEditorContext {}
+
+define_command_stack!(
+    BehaviorTreeCommand,
+    BehaviorTreeCommandStack,
+    BehaviorTreeEditorContext
+);
+
+#[derive(Debug)]
+struct ModifyBehaviorTreeCommand {
+    tree_resource: BehaviorTreeResource,
+    tree: BehaviorTreeAsset,
+}
+
+impl ModifyBehaviorTreeCommand {
+    fn swap(&mut self) {
+        let mut tree = self.tree_resource.data_ref();
+        std::mem::swap(&mut *tree, &mut self.tree);
+        tree.mark_dirty();
+    }
+}
+
+impl BehaviorTreeCommand for ModifyBehaviorTreeCommand {
+    fn name(&mut self, _: &BehaviorTreeEditorContext) -> String {
+        "Modify Behavior Tree".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut BehaviorTreeEditorContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut BehaviorTreeEditorContext) {
+        self.swap();
+    }
+}
+
+struct CanvasContextMenu {
+    add_node: Handle<UiNode>,
+    menu: RcUiNodeHandle,
+}
+
+impl CanvasContextMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let add_node;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                    add_node = create_menu_item("Add Node", vec![], ctx);
+                    add_node
+                }))
+                .build(ctx),
+            )
+            .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self { add_node, menu }
+    }
+}
+
+struct NodeContextMenu {
+    add_child: Handle<UiNode>,
+    set_as_root: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    menu: RcUiNodeHandle,
+    placement_target: Handle<UiNode>,
+}
+
+impl NodeContextMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let add_child;
+        let set_as_root;
+        let remove;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            add_child = create_menu_item("Add Child", vec![], ctx);
+                            add_child
+                        })
+                        .with_child({
+                            set_as_root = create_menu_item("Set As Root Node", vec![], ctx);
+                            set_as_root
+                        })
+                        .with_child({
+                            remove = create_menu_item("Remove", vec![], ctx);
+                            remove
+                        }),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self {
+            add_child,
+            set_as_root,
+            remove,
+            menu,
+            placement_target: Default::default(),
+        }
+    }
+}
+
+struct FileMenu {
+    new: Handle<UiNode>,
+    save: Handle<UiNode>,
+    load: Handle<UiNode>,
+}
+
+struct EditMenu {
+    undo: Handle<UiNode>,
+    redo: Handle<UiNode>,
+}
+
+struct Menu {
+    file: FileMenu,
+    edit: EditMenu,
+}
+
+pub struct BehaviorTreeEditorWindow {
+    window: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    property_editors: Rc<PropertyEditorDefinitionContainer>,
+    canvas_context_menu: CanvasContextMenu,
+    node_context_menu: NodeContextMenu,
+    menu: Menu,
+    load_file_selector: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    tree_resource: Option<BehaviorTreeResource>,
+    command_stack: BehaviorTreeCommandStack,
+    path: PathBuf,
+    selection: Handle<BehaviorTreeNode>,
+}
+
+fn fetch_node_model_handle(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<BehaviorTreeNode> {
+    ui.try_get_node(handle)
+        .and_then(|n| n.query_component::<AbsmNode<BehaviorTreeNode>>())
+        .map(|n| n.model_handle)
+        .unwrap_or_default()
+}
+
+fn node_label(node: &BehaviorTreeNode) -> String {
+    if !node.name.is_empty() {
+        return node.name.clone();
+    }
+    match node.kind {
+        BehaviorTreeNodeKind::Condition {
+            ref key,
+            comparison,
+            value,
+        } => format!("{} {} {}", key, comparison.as_ref(), value),
+        BehaviorTreeNodeKind::SetValue { ref key, value } => format!("{} = {}", key, value),
+        BehaviorTreeNodeKind::Action { ref name } => format!("Action: {}", name),
+        ref kind => kind.as_ref().to_string(),
+    }
+}
+
+// Children of a node are ticked in the order of the list, the editor keeps them sorted from left
+// to right, so the order of execution matches the picture on the canvas.
+fn sort_children(tree: &mut BehaviorTreeAsset) {
+    let positions = tree
+        .nodes()
+        .pair_iter()
+        .map(|(handle, node)| (handle, node.position.x))
+        .collect::<Vec<_>>();
+    let x = |handle: &Handle<BehaviorTreeNode>| {
+        positions
+            .iter()
+            .find_map(|(h, x)| if h == handle { Some(*x) } else { None })
+            .unwrap_or_default()
+    };
+    for node in tree.nodes_mut().iter_mut() {
+        node.children.sort_by(|a, b| x(a).total_cmp(&x(b)));
+    }
+}
+
+impl BehaviorTreeEditorWindow {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let load_file_selector = create_file_selector(ctx, "behavior", FileBrowserMode::Open);
+        let save_file_selector = create_file_selector(
+            ctx,
+            "behavior",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.behavior"),
+            },
+        );
+
+        let property_editors = make_property_editors_container(sender);
+        property_editors.insert(EnumPropertyEditorDefinition::<BehaviorTreeNodeKind>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<BlackboardComparison>::new());
+
+        let canvas_context_menu = CanvasContextMenu::new(ctx);
+        let node_context_menu = NodeContextMenu::new(ctx);
+
+        let canvas;
+        let inspector;
+        let new;
+        let save;
+        let load;
+        let undo;
+        let redo;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(800.0).with_height(500.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            MenuBuilder::new(WidgetBuilder::new())
+                                .with_items(vec![
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("File"))
+                                        .with_items(vec![
+                                            {
+                                                new = create_menu_item("New", vec![], ctx);
+                                                new
+                                            },
+                                            {
+                                                load = create_menu_item("Load", vec![], ctx);
+                                                load
+                                            },
+                                            {
+                                                save = create_menu_item("Save", vec![], ctx);
+                                                save
+                                            },
+                                        ])
+                                        .build(ctx),
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("Edit"))
+                                        .with_items(vec![
+                                            {
+                                                undo = create_menu_item("Undo", vec![], ctx);
+                                                undo
+                                            },
+                                            {
+                                                redo = create_menu_item("Redo", vec![], ctx);
+                                                redo
+                                            },
+                                        ])
+                                        .build(ctx),
+                                ])
+                                .build(ctx),
+                        )
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child(
+                                        BorderBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_child({
+                                                    canvas = AbsmCanvasBuilder::new(
+                                                        WidgetBuilder::new().with_context_menu(
+                                                            canvas_context_menu.menu.clone(),
+                                                        ),
+                                                    )
+                                                    .build(ctx);
+                                                    canvas
+                                                }),
+                                        )
+                                        .build(ctx),
+                                    )
+                                    .with_child(
+                                        ScrollViewerBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_content({
+                                            inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                                .build(ctx);
+                                            inspector
+                                        })
+                                        .build(ctx),
+                                    ),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::stretch())
+                            .add_column(Column::strict(300.0))
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Behavior Tree Editor"))
+            .build(ctx);
+
+        Self {
+            window,
+            canvas,
+            inspector,
+            property_editors: Rc::new(property_editors),
+            canvas_context_menu,
+            node_context_menu,
+            menu: Menu {
+                file: FileMenu { new, save, load },
+                edit: EditMenu { undo, redo },
+            },
+            load_file_selector,
+            save_file_selector,
+            tree_resource: None,
+            command_stack: BehaviorTreeCommandStack::new(false),
+            path: Default::default(),
+            selection: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn set_tree(&mut self, tree: Option<BehaviorTreeResource>, ui: &mut UserInterface) {
+        self.path = tree
+            .as_ref()
+            .map(|t| t.data_ref().path().to_path_buf())
+            .unwrap_or_default();
+        self.tree_resource = tree;
+        self.selection = Handle::NONE;
+        self.command_stack.clear(BehaviorTreeEditorContext {});
+        self.command_stack.mark_saved();
+        self.sync_title(ui);
+        self.sync_to_model(ui);
+        self.sync_inspector(ui, true);
+    }
+
+    fn sync_title(&self, ui: &UserInterface) {
+        let title = if self.tree_resource.is_some() {
+            if self.path == PathBuf::default() {
+                "Behavior Tree Editor - Unnamed Behavior Tree".to_string()
+            } else {
+                format!("Behavior Tree Editor - {}", self.path.display())
+            }
+        } else {
+            "Behavior Tree Editor".to_string()
+        };
+
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
+    }
+
+    fn clear_canvas(&self, ui: &UserInterface) {
+        for &child in ui.node(self.canvas).children() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+    }
+
+    // Behavior trees are small, so the whole graph is rebuilt on every change.
+    fn sync_to_model(&self, ui: &mut UserInterface) {
+        self.clear_canvas(ui);
+
+        let Some(tree_resource) = self.tree_resource.as_ref() else {
+            return;
+        };
+        let tree = tree_resource.data_ref();
+
+        let mut views = Vec::new();
+        for (handle, node) in tree.nodes().pair_iter() {
+            let is_root = handle == tree.root();
+            let view = AbsmNodeBuilder::new(
+                WidgetBuilder::new()
+                    .with_context_menu(self.node_context_menu.menu.clone())
+                    .with_desired_position(node.position),
+            )
+            .with_normal_color(if is_root {
+                NORMAL_ROOT_COLOR
+            } else {
+                NORMAL_BACKGROUND
+            })
+            .with_selected_color(if is_root {
+                SELECTED_ROOT_COLOR
+            } else {
+                SELECTED_BACKGROUND
+            })
+            .with_model_handle(handle)
+            .with_name(node_label(node))
+            .build(&mut ui.build_ctx());
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+            );
+
+            views.push((handle, view));
+        }
+
+        let find_view = |handle: Handle<BehaviorTreeNode>| {
+            views
+                .iter()
+                .find_map(|(h, v)| if *h == handle { Some(*v) } else { None })
+        };
+
+        for (handle, node) in tree.nodes().pair_iter() {
+            for &child in node.children.iter() {
+                if let (Some(source), Some(dest)) = (find_view(handle), find_view(child)) {
+                    let transition = TransitionBuilder::new(WidgetBuilder::new())
+                        .with_source(source)
+                        .with_dest(dest)
+                        .build(Handle::NONE, &mut ui.build_ctx());
+
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::link(transition, MessageDirection::ToWidget, self.canvas),
+                    );
+
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::lowermost(transition, MessageDirection::ToWidget),
+                    );
+                }
+            }
+        }
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::selection_changed(
+                self.canvas,
+                MessageDirection::ToWidget,
+                find_view(self.selection).into_iter().collect(),
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::force_sync_dependent_objects(
+                self.canvas,
+                MessageDirection::ToWidget,
+            ),
+        );
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface, rebuild: bool) {
+        let tree = self.tree_resource.as_ref().map(|t| t.data_ref());
+        let node = tree
+            .as_ref()
+            .and_then(|t| t.nodes().try_borrow(self.selection));
+
+        if rebuild {
+            let context = node
+                .map(|node| {
+                    InspectorContext::from_object(
+                        node,
+                        &mut ui.build_ctx(),
+                        self.property_editors.clone(),
+                        None,
+                        MSG_SYNC_FLAG,
+                        0,
+                        true,
+                        Default::default(),
+                    )
+                })
+                .unwrap_or_default();
+
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        } else if let Some(node) = node {
+            let context = ui
+                .node(self.inspector)
+                .cast::<Inspector>()
+                .expect("Must be Inspector!")
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = context.sync(node, ui, 0, true, Default::default()) {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {:?}", error))
+                }
+            }
+        }
+    }
+
+    // Applies the given modification to a copy of the tree and puts it on the command stack.
+    fn modify<F>(&mut self, ui: &mut UserInterface, func: F)
+    where
+        F: FnOnce(&mut BehaviorTreeAsset),
+    {
+        let Some(tree_resource) = self.tree_resource.clone() else {
+            return;
+        };
+
+        let mut tree = tree_resource.data_ref().clone();
+        func(&mut tree);
+        sort_children(&mut tree);
+
+        self.command_stack.do_command(
+            Box::new(ModifyBehaviorTreeCommand {
+                tree_resource,
+                tree,
+            }),
+            BehaviorTreeEditorContext {},
+        );
+
+        self.sync_to_model(ui);
+        self.sync_inspector(ui, false);
+    }
+
+    fn save(&mut self) {
+        if let Some(tree_resource) = self.tree_resource.as_ref() {
+            let mut tree = tree_resource.data_ref();
+            tree.set_path(self.path.clone());
+            let result = tree.save(&self.path);
+            if result.is_ok() {
+                self.command_stack.mark_saved();
+            }
+            Log::verify(result);
+        }
+    }
+
+    fn open_save_file_dialog(&self, ui: &UserInterface) {
+        ui.send_message(FileSelectorMessage::root(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+
+        ui.send_message(WindowMessage::open_modal(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn handle_canvas_message(&mut self, msg: &AbsmCanvasMessage, ui: &mut UserInterface) {
+        match msg {
+            AbsmCanvasMessage::CommitTransition {
+                source_node,
+                dest_node,
+            } => {
+                let parent = fetch_node_model_handle(*source_node, ui);
+                let child = fetch_node_model_handle(*dest_node, ui);
+                self.modify(ui, |tree| {
+                    if !tree.add_child(parent, child) {
+                        Log::warn("Unable to attach the node, it is already a child or it would create a cycle.");
+                    }
+                });
+            }
+            AbsmCanvasMessage::CommitDrag { entries } => {
+                let positions = entries
+                    .iter()
+                    .map(|e| {
+                        (
+                            fetch_node_model_handle(e.node, ui),
+                            ui.node(e.node).actual_local_position(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.modify(ui, |tree| {
+                    for (handle, position) in positions {
+                        if let Some(node) = tree.nodes_mut().try_borrow_mut(handle) {
+                            node.position = position;
+                        }
+                    }
+                });
+            }
+            AbsmCanvasMessage::SelectionChanged(selection) => {
+                let new_selection = selection
+                    .iter()
+                    .map(|n| fetch_node_model_handle(*n, ui))
+                    .find(|h| h.is_some())
+                    .unwrap_or_default();
+                if new_selection != self.selection {
+                    self.selection = new_selection;
+                    self.sync_inspector(ui, true);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_context_menus(&mut self, message: &UiMessage, ui: &mut UserInterface) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            let target = self.node_context_menu.placement_target;
+            let target_model = fetch_node_model_handle(target, ui);
+            if message.destination() == self.canvas_context_menu.add_node {
+                let screen_position = ui.node(*self.canvas_context_menu.menu).screen_position();
+                let position = ui.node(self.canvas).screen_to_local(screen_position);
+                self.modify(ui, |tree| {
+                    tree.add_node(BehaviorTreeNode {
+                        position,
+                        ..Default::default()
+                    });
+                });
+            } else if message.destination() == self.node_context_menu.add_child {
+                ui.send_message(AbsmCanvasMessage::switch_mode(
+                    self.canvas,
+                    MessageDirection::ToWidget,
+                    Mode::CreateTransition {
+                        source: target,
+                        source_pos: ui.node(target).center(),
+                        dest_pos: ui.node(self.canvas).screen_to_local(ui.cursor_position()),
+                    },
+                ));
+            } else if message.destination() == self.node_context_menu.set_as_root {
+                self.modify(ui, |tree| tree.set_root(target_model));
+            } else if message.destination() == self.node_context_menu.remove {
+                if self.selection == target_model {
+                    self.selection = Handle::NONE;
+                    self.sync_inspector(ui, true);
+                }
+                self.modify(ui, |tree| {
+                    if tree.nodes().is_valid_handle(target_model) {
+                        tree.remove_node(target_model);
+                    }
+                });
+            }
+        } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
+            if message.destination() == *self.node_context_menu.menu {
+                self.node_context_menu.placement_target = *target;
+            }
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+
+        self.handle_context_menus(message, ui);
+
+        if let Some(msg) = message.data::<AbsmCanvasMessage>() {
+            if message.destination() == self.canvas
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.handle_canvas_message(msg, ui);
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let selection = self.selection;
+                let action = PropertyAction::from_field_kind(&args.value);
+                let path = args.path();
+                self.modify(ui, |tree| {
+                    if let Some(node) = tree.nodes_mut().try_borrow_mut(selection) {
+                        action.apply(&path, node, &mut |result| {
+                            Log::verify(result);
+                        });
+                    }
+                });
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.menu.edit.undo {
+                self.command_stack.undo(BehaviorTreeEditorContext {});
+                self.sync_to_model(ui);
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.edit.redo {
+                self.command_stack.redo(BehaviorTreeEditorContext {});
+                self.sync_to_model(ui);
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.file.new {
+                self.set_tree(Some(Resource::new_ok(BehaviorTreeAsset::default())), ui);
+            } else if message.destination() == self.menu.file.load {
+                ui.send_message(FileSelectorMessage::root(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+
+                ui.send_message(WindowMessage::open_modal(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.menu.file.save {
+                if self.path == PathBuf::default() {
+                    self.open_save_file_dialog(ui);
+                } else {
+                    self.save();
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.load_file_selector {
+                if let Ok(tree) =
+                    block_on(engine.resource_manager.request::<BehaviorTreeAsset>(path))
+                {
+                    self.set_tree(Some(tree), ui);
+                }
+            } else if message.destination() == self.save_file_selector {
+                self.path = path.clone();
+                self.save();
+                self.sync_title(ui);
+            }
+        }
+    }
+}
+
+impl Document for BehaviorTreeEditorWindow {
+    fn document_info(&self) -> Option<DocumentInfo> {
+        self.tree_resource.as_ref().map(|_| DocumentInfo {
+            kind: DocumentKind::BehaviorTree,
+            path: (self.path != PathBuf::default()).then(|| self.path.clone()),
+            modified: self.command_stack.is_modified(),
+        })
+    }
+
+    fn save_document(&mut self, _engine: &mut Engine) -> bool {
+        if self.path == PathBuf::default() {
+            false
+        } else {
+            self.save();
+            true
+        }
+    }
+}
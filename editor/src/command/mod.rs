@@ -17,6 +17,9 @@ macro_rules! define_command_stack {
         pub struct $command_stack {
             commands: Vec<Box<dyn $command_trait>>,
             top: Option<usize>,
+            // Position of the top at the moment when the document was saved. `None` if the
+            // saved state cannot be reached by undo/redo anymore.
+            saved_top: Option<Option<usize>>,
             debug: bool,
         }
 
@@ -25,6 +28,7 @@ macro_rules! define_command_stack {
                 Self {
                     commands: Default::default(),
                     top: None,
+                    saved_top: Some(None),
                     debug,
                 }
             }
@@ -44,6 +48,9 @@ macro_rules! define_command_stack {
                     }
                     // Drop everything after top.
                     let top = self.top.unwrap_or(0);
+                    if matches!(self.saved_top, Some(Some(saved_top)) if saved_top >= top) {
+                        self.saved_top = None;
+                    }
                     if top < self.commands.len() {
                         for mut dropped_command in self.commands.drain(top..) {
                             if self.debug {
@@ -108,7 +115,11 @@ macro_rules! define_command_stack {
                 }
             }
 
+            /// Removes every command from the stack. The document stays modified, if it was
+            /// modified before clearing.
             pub fn clear(&mut self, mut context: $context) {
+                self.saved_top = if self.is_modified() { None } else { Some(None) };
+                self.top = None;
                 for mut dropped_command in self.commands.drain(..) {
                     if self.debug {
                         println!("Finalizing command {:?}", dropped_command);
@@ -116,6 +127,17 @@ macro_rules! define_command_stack {
                     dropped_command.finalize(&mut context);
                 }
             }
+
+            /// Remembers current state of the stack as the saved state of a document.
+            pub fn mark_saved(&mut self) {
+                self.saved_top = Some(self.top);
+            }
+
+            /// Returns `true` if the document was changed since it was saved last time. Undoing
+            /// every change back to the saved state makes the document unmodified again.
+            pub fn is_modified(&self) -> bool {
+                self.saved_top != Some(self.top)
+            }
         }
     };
 }
@@ -1,17 +1,24 @@
+//! Project launcher - allows to select a project from the list of recently opened projects, to
+//! create a new project from a template, or to open any other project.
+
 use crate::message::MessageSender;
-use crate::{Engine, Message};
+use crate::{gui::make_dropdown_list_option, Engine, Message};
 use fyrox::gui::text::TextMessage;
 use fyrox::{
     core::{
         algebra::Vector2,
+        color::Color,
+        log::Log,
         pool::Handle,
         scope_profile,
         visitor::{Visit, VisitResult, Visitor},
     },
     gui::{
         border::BorderBuilder,
+        brush::Brush,
         button::{ButtonBuilder, ButtonMessage},
         decorator::DecoratorBuilder,
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
         file_browser::{FileSelectorBuilder, FileSelectorMessage, Filter},
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
@@ -19,15 +26,17 @@ use fyrox::{
         message::{MessageDirection, UiMessage},
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
-        text_box::TextBoxBuilder,
+        text_box::{TextBoxBuilder, TextCommitMode},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, VerticalAlignment,
     },
+    ENGINE_VERSION,
 };
 use std::{
     env,
     path::{Path, PathBuf},
+    process::Command,
 };
 
 #[derive(Default, Eq, PartialEq, Visit)]
@@ -37,11 +46,134 @@ struct HistoryEntry {
 
 pub const HISTORY_PATH: &str = "history.bin";
 
+/// Template of a new project. See `fyrox-template` for more info.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ProjectTemplate {
+    ThreeD,
+    TwoD,
+}
+
+impl ProjectTemplate {
+    pub const ALL: [ProjectTemplate; 2] = [ProjectTemplate::ThreeD, ProjectTemplate::TwoD];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectTemplate::ThreeD => "3D",
+            ProjectTemplate::TwoD => "2D",
+        }
+    }
+
+    fn style(self) -> &'static str {
+        match self {
+            ProjectTemplate::ThreeD => "3d",
+            ProjectTemplate::TwoD => "2d",
+        }
+    }
+}
+
+/// Creates a new project with the given name in the given folder, using `fyrox-template` tool. The
+/// tool must be installed (`cargo install fyrox-template`). Returns a path to the new project.
+pub fn create_project(
+    parent_dir: &Path,
+    name: &str,
+    template: ProjectTemplate,
+) -> Result<PathBuf, String> {
+    if name.is_empty() {
+        return Err("Project name cannot be empty!".to_string());
+    }
+
+    let project_dir = parent_dir.join(name);
+    if project_dir
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some())
+    {
+        return Err(format!(
+            "Non-empty folder {} already exists!",
+            project_dir.display()
+        ));
+    }
+
+    let output = Command::new("fyrox-template")
+        .current_dir(parent_dir)
+        .args(["init", "--name", name, "--style", template.style()])
+        .output()
+        .map_err(|e| {
+            format!(
+                "Unable to run fyrox-template. Make sure it is installed \
+                (cargo install fyrox-template). Reason: {e}"
+            )
+        })?;
+
+    // The tool reports some errors (such as invalid names) in the output only.
+    if output.status.success() && project_dir.join("Cargo.toml").exists() {
+        Ok(project_dir)
+    } else {
+        Err(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+/// Tries to fetch a version of the engine, that is used by a project in the given folder. The version
+/// is taken from `fyrox` dependency from the `Cargo.toml` of the project (workspace dependencies are
+/// checked first). Returns `None` if the version cannot be fetched (for example, if the engine is used
+/// from a local folder or from a git repository).
+pub fn project_engine_version(project_dir: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(project_dir.join("Cargo.toml")).ok()?;
+    let manifest = manifest.parse::<toml::Table>().ok()?;
+    let dependency = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(|dependencies| dependencies.get("fyrox"))
+        .or_else(|| {
+            manifest
+                .get("dependencies")
+                .and_then(|dependencies| dependencies.get("fyrox"))
+        })?;
+    match dependency {
+        toml::Value::String(version) => Some(version.clone()),
+        toml::Value::Table(table) => table
+            .get("version")
+            .and_then(|version| version.as_str())
+            .map(|version| version.to_string()),
+        _ => None,
+    }
+}
+
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().unwrap_or("0").parse().ok()?;
+    Some((major, minor))
+}
+
+/// Checks whether a project that uses the given version of the engine can be opened in the editor.
+/// Follows SemVer rules: while the major version is zero, every minor version is considered breaking.
+pub fn is_compatible_engine_version(version: &str) -> bool {
+    match (major_minor(version), major_minor(ENGINE_VERSION)) {
+        (Some((major, minor)), Some((engine_major, engine_minor))) => {
+            major == engine_major && (major != 0 || minor == engine_minor)
+        }
+        // Give it a try, if the version is unknown.
+        _ => true,
+    }
+}
+
 pub struct Configurator {
     pub window: Handle<UiNode>,
     work_dir_browser: Handle<UiNode>,
     select_work_dir: Handle<UiNode>,
     ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    tb_project_name: Handle<UiNode>,
+    template_selector: Handle<UiNode>,
+    create_project: Handle<UiNode>,
+    project_name: String,
+    template: ProjectTemplate,
     sender: MessageSender,
     work_dir: PathBuf,
     tb_work_dir: Handle<UiNode>,
@@ -50,9 +182,23 @@ pub struct Configurator {
 }
 
 fn make_history_entry_widget(ctx: &mut BuildContext, entry: &HistoryEntry) -> Handle<UiNode> {
+    let (version_text, version_color) = match project_engine_version(&entry.work_dir) {
+        Some(version) if is_compatible_engine_version(&version) => {
+            (format!("Fyrox {version}"), Color::opaque(160, 160, 160))
+        }
+        Some(version) => (
+            format!("Fyrox {version} - incompatible with the editor (Fyrox {ENGINE_VERSION})"),
+            Color::ORANGE,
+        ),
+        None => (
+            "Unknown engine version".to_string(),
+            Color::opaque(160, 160, 160),
+        ),
+    };
+
     DecoratorBuilder::new(BorderBuilder::new(
         WidgetBuilder::new()
-            .with_height(18.0)
+            .with_height(34.0)
             .with_margin(Thickness {
                 left: 1.0,
                 top: 0.0,
@@ -60,10 +206,24 @@ fn make_history_entry_widget(ctx: &mut BuildContext, entry: &HistoryEntry) -> Ha
                 bottom: 1.0,
             })
             .with_child(
-                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(5.0)))
-                    .with_text(format!("{}", entry.work_dir.display(),))
-                    .with_vertical_text_alignment(VerticalAlignment::Center)
-                    .build(ctx),
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::left(5.0))
+                        .with_vertical_alignment(VerticalAlignment::Center)
+                        .with_child(
+                            TextBuilder::new(WidgetBuilder::new())
+                                .with_text(format!("{}", entry.work_dir.display(),))
+                                .build(ctx),
+                        )
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new().with_foreground(Brush::Solid(version_color)),
+                            )
+                            .with_text(version_text)
+                            .build(ctx),
+                        ),
+                )
+                .build(ctx),
             ),
     ))
     .build(ctx)
@@ -73,7 +233,11 @@ impl Configurator {
     pub fn new(sender: MessageSender, ctx: &mut BuildContext) -> Self {
         let select_work_dir;
         let ok;
+        let cancel;
         let tb_work_dir;
+        let tb_project_name;
+        let template_selector;
+        let create_project;
 
         let current_path = env::current_dir().unwrap();
 
@@ -103,16 +267,17 @@ impl Configurator {
 
         let message = "Please select the working directory of \
         your current project. In most cases it will be the root folder \
-        of your project";
+        of your project. A new project will be created in the selected \
+        directory";
 
         let lv_history;
         let window = WindowBuilder::new(
             WidgetBuilder::new()
-                .with_width(370.0)
-                .with_height(250.0)
-                .with_min_size(Vector2::new(370.0, 250.0)),
+                .with_width(450.0)
+                .with_height(400.0)
+                .with_min_size(Vector2::new(450.0, 400.0)),
         )
-        .with_title(WindowTitle::Text("Configure Editor".into()))
+        .with_title(WindowTitle::Text("Project Launcher".into()))
         .open(false)
         .can_close(false)
         .with_content(
@@ -182,7 +347,7 @@ impl Configurator {
                                 .with_margin(Thickness::uniform(5.0))
                                 .on_row(2),
                         )
-                        .with_text("Previous Configurations")
+                        .with_text("Recent Projects")
                         .with_horizontal_text_alignment(HorizontalAlignment::Center)
                         .build(ctx),
                     )
@@ -197,10 +362,72 @@ impl Configurator {
                             .build(ctx);
                         lv_history
                     })
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(5.0))
+                                .on_row(4),
+                        )
+                        .with_text("New Project")
+                        .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(5)
+                                .with_child({
+                                    tb_project_name = TextBoxBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text_commit_mode(TextCommitMode::Immediate)
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .with_text("my_game")
+                                    .build(ctx);
+                                    tb_project_name
+                                })
+                                .with_child({
+                                    template_selector = DropdownListBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_items(
+                                        ProjectTemplate::ALL
+                                            .iter()
+                                            .map(|template| {
+                                                make_dropdown_list_option(ctx, template.name())
+                                            })
+                                            .collect(),
+                                    )
+                                    .with_selected(0)
+                                    .with_close_on_selection(true)
+                                    .build(ctx);
+                                    template_selector
+                                })
+                                .with_child({
+                                    create_project = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(2)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Create")
+                                    .build(ctx);
+                                    create_project
+                                }),
+                        )
+                        .add_row(Row::strict(25.0))
+                        .add_column(Column::stretch())
+                        .add_column(Column::strict(60.0))
+                        .add_column(Column::strict(80.0))
+                        .build(ctx),
+                    )
                     .with_child(
                         StackPanelBuilder::new(
                             WidgetBuilder::new()
-                                .on_row(4)
+                                .on_row(6)
                                 .with_horizontal_alignment(HorizontalAlignment::Right)
                                 .with_vertical_alignment(VerticalAlignment::Bottom)
                                 .with_child({
@@ -214,6 +441,20 @@ impl Configurator {
                                     .with_text("OK")
                                     .build(ctx);
                                     ok
+                                })
+                                .with_child({
+                                    // There's nothing to go back to, until the editor is
+                                    // configured for the first time.
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_enabled(false)
+                                            .with_width(80.0)
+                                            .with_height(25.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
                                 }),
                         )
                         .with_orientation(Orientation::Horizontal)
@@ -223,8 +464,10 @@ impl Configurator {
             .add_row(Row::auto())
             .add_row(Row::auto())
             .add_row(Row::auto())
-            .add_row(Row::strict(80.0))
             .add_row(Row::stretch())
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::strict(30.0))
             .add_column(Column::stretch())
             .build(ctx),
         )
@@ -235,6 +478,12 @@ impl Configurator {
             work_dir_browser: folder_browser,
             select_work_dir,
             ok,
+            cancel,
+            tb_project_name,
+            template_selector,
+            create_project,
+            project_name: "my_game".to_string(),
+            template: ProjectTemplate::ThreeD,
             sender,
             tb_work_dir,
             work_dir: current_path,
@@ -252,6 +501,17 @@ impl Configurator {
         ));
     }
 
+    fn set_work_dir(&mut self, work_dir: PathBuf, engine: &mut Engine) {
+        self.work_dir = work_dir;
+        engine.user_interface.send_message(TextMessage::text(
+            self.tb_work_dir,
+            MessageDirection::ToWidget,
+            self.work_dir.to_string_lossy().to_string(),
+        ));
+
+        self.validate(engine);
+    }
+
     pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
         scope_profile!();
 
@@ -268,30 +528,29 @@ impl Configurator {
             if message.destination() == self.lv_history
                 && message.direction() == MessageDirection::FromWidget
             {
-                let entry = &self.history[index];
-                self.work_dir = entry.work_dir.clone();
-
-                engine.user_interface.send_message(TextMessage::text(
-                    self.tb_work_dir,
-                    MessageDirection::ToWidget,
-                    self.work_dir.to_string_lossy().to_string(),
-                ));
-
-                self.validate(engine);
+                let work_dir = self.history[index].work_dir.clone();
+                self.set_work_dir(work_dir, engine);
+            }
+        } else if let Some(&DropdownListMessage::SelectionChanged(Some(index))) =
+            message.data::<DropdownListMessage>()
+        {
+            if message.destination() == self.template_selector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.template = ProjectTemplate::ALL[index];
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data::<TextMessage>() {
+            if message.destination() == self.tb_project_name
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.project_name = text.clone();
             }
         } else if let Some(FileSelectorMessage::Commit(path)) =
             message.data::<FileSelectorMessage>()
         {
             if message.destination() == self.work_dir_browser {
                 if let Ok(work_dir) = path.clone().canonicalize() {
-                    self.work_dir = work_dir;
-                    engine.user_interface.send_message(TextMessage::text(
-                        self.tb_work_dir,
-                        MessageDirection::ToWidget,
-                        self.work_dir.to_string_lossy().to_string(),
-                    ));
-
-                    self.validate(engine);
+                    self.set_work_dir(work_dir, engine);
                 }
             }
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
@@ -324,6 +583,29 @@ impl Configurator {
                     self.window,
                     MessageDirection::ToWidget,
                 ));
+
+                engine.user_interface.send_message(WidgetMessage::enabled(
+                    self.cancel,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.cancel {
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.create_project {
+                match create_project(&self.work_dir, &self.project_name, self.template) {
+                    Ok(project_dir) => {
+                        Log::info(format!(
+                            "Project {} was created successfully!",
+                            project_dir.display()
+                        ));
+
+                        self.set_work_dir(project_dir, engine);
+                    }
+                    Err(error) => Log::err(format!("Unable to create a project. {error}")),
+                }
             } else if message.destination() == self.select_work_dir {
                 engine
                     .user_interface
@@ -336,3 +618,69 @@ impl Configurator {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn project_launcher_engine_version() {
+        let project_dir = env::temp_dir().join("fyrox_project_launcher_version");
+        let _ = fs::remove_dir_all(&project_dir);
+        fs::create_dir_all(&project_dir).unwrap();
+        assert_eq!(project_engine_version(&project_dir), None);
+
+        let write_manifest = |manifest: &str| {
+            fs::write(project_dir.join("Cargo.toml"), manifest).unwrap();
+            project_engine_version(&project_dir)
+        };
+        // Workspace dependencies go first.
+        assert_eq!(
+            write_manifest(
+                "[workspace.dependencies]\nfyrox = { version = \"0.30\" }\n\
+                [dependencies]\nfyrox = \"0.29\"\n"
+            ),
+            Some("0.30".to_string())
+        );
+        assert_eq!(
+            write_manifest("[dependencies]\nfyrox = \"0.29\"\n"),
+            Some("0.29".to_string())
+        );
+        assert_eq!(
+            write_manifest("[dependencies]\nfyrox = { path = \"../Fyrox\" }\n"),
+            None
+        );
+
+        fs::remove_dir_all(&project_dir).unwrap();
+
+        let (major, minor) = major_minor(ENGINE_VERSION).unwrap();
+        assert!(is_compatible_engine_version(&format!("{major}.{minor}.99")));
+        assert!(is_compatible_engine_version(&format!("^{major}.{minor}")));
+        assert!(!is_compatible_engine_version(&format!("{}.0", major + 1)));
+        // Every minor version is breaking while the major version is zero.
+        assert_eq!(
+            is_compatible_engine_version(&format!("{major}.{}", minor + 1)),
+            major != 0
+        );
+        assert!(is_compatible_engine_version("*"));
+    }
+
+    #[test]
+    fn project_launcher_create_project_checks() {
+        let parent_dir = env::temp_dir().join("fyrox_project_launcher_create");
+        let _ = fs::remove_dir_all(&parent_dir);
+        fs::create_dir_all(parent_dir.join("existing")).unwrap();
+        fs::write(parent_dir.join("existing").join("main.rs"), "").unwrap();
+
+        assert!(create_project(&parent_dir, "", ProjectTemplate::ThreeD).is_err());
+        // Existing projects must not be overwritten.
+        assert!(
+            create_project(&parent_dir, "existing", ProjectTemplate::TwoD)
+                .unwrap_err()
+                .contains("already exists")
+        );
+
+        fs::remove_dir_all(&parent_dir).unwrap();
+    }
+}
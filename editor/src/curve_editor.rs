@@ -1,12 +1,15 @@
 use crate::{
-    define_command_stack, send_sync_message, utils::create_file_selector, MessageBoxButtons,
-    MessageBoxMessage, MSG_SYNC_FLAG,
+    define_command_stack,
+    document::{Document, DocumentInfo, DocumentKind},
+    send_sync_message,
+    utils::create_file_selector,
+    MessageBoxButtons, MessageBoxMessage, MSG_SYNC_FLAG,
 };
 use fyrox::asset::ResourceStateRefMut;
 use fyrox::{
-    asset::{Resource, ResourceData},
+    asset::{io::write_atomically, Resource, ResourceData},
     core::{
-        color::Color, curve::Curve, futures::executor::block_on, pool::Handle, visitor::prelude::*,
+        color::Color, curve::Curve, futures::executor::block_on, log::Log, pool::Handle,
         visitor::Visitor,
     },
     engine::Engine,
@@ -42,7 +45,9 @@ struct ModifyCurveCommand {
 
 impl ModifyCurveCommand {
     fn swap(&mut self) {
-        std::mem::swap(&mut self.curve_resource.data_ref().curve, &mut self.curve);
+        let mut state = self.curve_resource.data_ref();
+        std::mem::swap(&mut state.curve, &mut self.curve);
+        state.rebake();
     }
 }
 
@@ -89,7 +94,6 @@ pub struct CurveEditorWindow {
     path: PathBuf,
     save_changes_message_box: Handle<UiNode>,
     cancel_message_box: Handle<UiNode>,
-    modified: bool,
     backup: Curve,
 }
 
@@ -274,7 +278,6 @@ impl CurveEditorWindow {
             save_file_selector,
             path: Default::default(),
             save_changes_message_box,
-            modified: false,
             backup: Default::default(),
             cancel_message_box,
         }
@@ -310,12 +313,20 @@ impl CurveEditorWindow {
         }
     }
 
-    fn save(&self) {
+    fn save(&mut self) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
             if let ResourceStateRefMut::Ok(state) = curve_resource.state().get_mut() {
                 let mut visitor = Visitor::new();
-                state.curve.visit("Curve", &mut visitor).unwrap();
-                visitor.save_binary(&self.path).unwrap();
+                state.visit_content(&mut visitor).unwrap();
+                if let Err(err) = write_atomically(&self.path, |path| visitor.save_binary(path)) {
+                    Log::err(format!(
+                        "Unable to save {} curve. Reason: {:?}",
+                        self.path.display(),
+                        err
+                    ));
+                    return;
+                }
+                self.command_stack.mark_saved();
             }
         }
     }
@@ -333,9 +344,8 @@ impl CurveEditorWindow {
         self.sync_to_model(ui);
         self.sync_title(ui);
 
-        self.modified = false;
-
         self.command_stack.clear(CurveEditorContext {});
+        self.command_stack.mark_saved();
     }
 
     fn sync_title(&self, ui: &UserInterface) {
@@ -362,6 +372,7 @@ impl CurveEditorWindow {
         self.path = Default::default();
         self.backup = Default::default();
         self.command_stack.clear(CurveEditorContext {});
+        self.command_stack.mark_saved();
         self.curve_resource = None;
         self.sync_title(ui);
         ui.send_message(WidgetMessage::enabled(
@@ -381,7 +392,9 @@ impl CurveEditorWindow {
 
     fn revert(&self) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
-            curve_resource.data_ref().curve = self.backup.clone();
+            let mut state = curve_resource.data_ref();
+            state.curve = self.backup.clone();
+            state.rebake();
         }
     }
 
@@ -404,7 +417,7 @@ impl CurveEditorWindow {
 
         if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.cancel {
-                if self.modified && self.curve_resource.is_some() {
+                if self.command_stack.is_modified() && self.curve_resource.is_some() {
                     ui.send_message(MessageBoxMessage::open(
                         self.cancel_message_box,
                         MessageDirection::ToWidget,
@@ -415,7 +428,7 @@ impl CurveEditorWindow {
                     self.close(ui);
                 }
             } else if message.destination() == self.ok {
-                if self.modified && self.curve_resource.is_some() {
+                if self.command_stack.is_modified() && self.curve_resource.is_some() {
                     if self.path == PathBuf::default() {
                         ui.send_message(MessageBoxMessage::open(
                             self.save_changes_message_box,
@@ -444,8 +457,6 @@ impl CurveEditorWindow {
                         }),
                         CurveEditorContext {},
                     );
-
-                    self.modified = true;
                 }
             }
         } else if let Some(MenuItemMessage::Click) = message.data() {
@@ -518,3 +529,22 @@ impl CurveEditorWindow {
         }
     }
 }
+
+impl Document for CurveEditorWindow {
+    fn document_info(&self) -> Option<DocumentInfo> {
+        self.curve_resource.as_ref().map(|_| DocumentInfo {
+            kind: DocumentKind::Curve,
+            path: (self.path != PathBuf::default()).then(|| self.path.clone()),
+            modified: self.command_stack.is_modified(),
+        })
+    }
+
+    fn save_document(&mut self, _engine: &mut Engine) -> bool {
+        if self.path == PathBuf::default() {
+            false
+        } else {
+            self.save();
+            true
+        }
+    }
+}
@@ -0,0 +1,782 @@
+use crate::{
+    absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage, Mode},
+        node::{AbsmNode, AbsmNodeBuilder},
+        transition::TransitionBuilder,
+        NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
+    },
+    define_command_stack,
+    document::{Document, DocumentInfo, DocumentKind},
+    inspector::editors::make_property_editors_container,
+    menu::create_menu_item,
+    message::MessageSender,
+    send_sync_message,
+    utils::create_file_selector,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::{Resource, ResourceData},
+    core::{futures::executor::block_on, log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        border::BorderBuilder,
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                collection::VecCollectionPropertyEditorDefinition,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent, MenuItemMessage},
+        message::{MessageDirection, UiMessage},
+        popup::{Placement, PopupBuilder, PopupMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, RcUiNodeHandle, Thickness, UiNode, UserInterface,
+    },
+    resource::dialogue::{
+        Dialogue, DialogueAction, DialogueActionKind, DialogueChoice, DialogueComparison,
+        DialogueCondition, DialogueLink, DialogueNode, DialogueResource,
+    },
+};
+use std::{fmt::Debug, path::PathBuf, rc::Rc};
+
+#[derive(Debug)]
+pub struct DialogueEditorContext {}
+
+define_command_stack!(DialogueCommand, DialogueCommandStack, DialogueEditorContext);
+
+#[derive(Debug)]
+struct ModifyDialogueCommand {
+    dialogue_resource: DialogueResource,
+    dialogue: Dialogue,
+}
+
+impl ModifyDialogueCommand {
+    fn swap(&mut self) {
+        let mut dialogue = self.dialogue_resource.data_ref();
+        std::mem::swap(&mut *dialogue, &mut self.dialogue);
+        dialogue.mark_dirty();
+    }
+}
+
+impl DialogueCommand for ModifyDialogueCommand {
+    fn name(&mut self, _: &DialogueEditorContext) -> String {
+        "Modify Dialogue".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut DialogueEditorContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut DialogueEditorContext) {
+        self.swap();
+    }
+}
+
+// Defines what will be created when user connects two nodes on the canvas.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ConnectionKind {
+    Link,
+    Choice,
+}
+
+struct CanvasContextMenu {
+    add_node: Handle<UiNode>,
+    menu: RcUiNodeHandle,
+}
+
+impl CanvasContextMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let add_node;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                    add_node = create_menu_item("Add Node", vec![], ctx);
+                    add_node
+                }))
+                .build(ctx),
+            )
+            .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self { add_node, menu }
+    }
+}
+
+struct NodeContextMenu {
+    add_link: Handle<UiNode>,
+    add_choice: Handle<UiNode>,
+    set_as_entry: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    menu: RcUiNodeHandle,
+    placement_target: Handle<UiNode>,
+}
+
+impl NodeContextMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let add_link;
+        let add_choice;
+        let set_as_entry;
+        let remove;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            add_link = create_menu_item("Add Link", vec![], ctx);
+                            add_link
+                        })
+                        .with_child({
+                            add_choice = create_menu_item("Add Choice", vec![], ctx);
+                            add_choice
+                        })
+                        .with_child({
+                            set_as_entry = create_menu_item("Set As Entry Node", vec![], ctx);
+                            set_as_entry
+                        })
+                        .with_child({
+                            remove = create_menu_item("Remove", vec![], ctx);
+                            remove
+                        }),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self {
+            add_link,
+            add_choice,
+            set_as_entry,
+            remove,
+            menu,
+            placement_target: Default::default(),
+        }
+    }
+}
+
+struct FileMenu {
+    new: Handle<UiNode>,
+    save: Handle<UiNode>,
+    load: Handle<UiNode>,
+}
+
+struct EditMenu {
+    undo: Handle<UiNode>,
+    redo: Handle<UiNode>,
+}
+
+struct Menu {
+    file: FileMenu,
+    edit: EditMenu,
+}
+
+pub struct DialogueEditorWindow {
+    window: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    property_editors: Rc<PropertyEditorDefinitionContainer>,
+    canvas_context_menu: CanvasContextMenu,
+    node_context_menu: NodeContextMenu,
+    menu: Menu,
+    load_file_selector: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    dialogue_resource: Option<DialogueResource>,
+    command_stack: DialogueCommandStack,
+    path: PathBuf,
+    selection: Handle<DialogueNode>,
+    connection_kind: ConnectionKind,
+}
+
+fn fetch_node_model_handle(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<DialogueNode> {
+    ui.try_get_node(handle)
+        .and_then(|n| n.query_component::<AbsmNode<DialogueNode>>())
+        .map(|n| n.model_handle)
+        .unwrap_or_default()
+}
+
+fn node_label(node: &DialogueNode) -> String {
+    if node.name.is_empty() {
+        format!("{}: {}", node.speaker, node.text_key)
+    } else {
+        node.name.clone()
+    }
+}
+
+impl DialogueEditorWindow {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let load_file_selector = create_file_selector(ctx, "dialogue", FileBrowserMode::Open);
+        let save_file_selector = create_file_selector(
+            ctx,
+            "dialogue",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.dialogue"),
+            },
+        );
+
+        let property_editors = make_property_editors_container(sender);
+        property_editors.insert(InspectablePropertyEditorDefinition::<DialogueCondition>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<DialogueAction>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<DialogueChoice>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<DialogueLink>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<Handle<DialogueNode>>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<DialogueCondition>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<DialogueAction>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<DialogueChoice>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<DialogueLink>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<DialogueComparison>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<DialogueActionKind>::new());
+
+        let canvas_context_menu = CanvasContextMenu::new(ctx);
+        let node_context_menu = NodeContextMenu::new(ctx);
+
+        let canvas;
+        let inspector;
+        let new;
+        let save;
+        let load;
+        let undo;
+        let redo;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(800.0).with_height(500.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            MenuBuilder::new(WidgetBuilder::new())
+                                .with_items(vec![
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("File"))
+                                        .with_items(vec![
+                                            {
+                                                new = create_menu_item("New", vec![], ctx);
+                                                new
+                                            },
+                                            {
+                                                load = create_menu_item("Load", vec![], ctx);
+                                                load
+                                            },
+                                            {
+                                                save = create_menu_item("Save", vec![], ctx);
+                                                save
+                                            },
+                                        ])
+                                        .build(ctx),
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("Edit"))
+                                        .with_items(vec![
+                                            {
+                                                undo = create_menu_item("Undo", vec![], ctx);
+                                                undo
+                                            },
+                                            {
+                                                redo = create_menu_item("Redo", vec![], ctx);
+                                                redo
+                                            },
+                                        ])
+                                        .build(ctx),
+                                ])
+                                .build(ctx),
+                        )
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child(
+                                        BorderBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_child({
+                                                    canvas = AbsmCanvasBuilder::new(
+                                                        WidgetBuilder::new().with_context_menu(
+                                                            canvas_context_menu.menu.clone(),
+                                                        ),
+                                                    )
+                                                    .build(ctx);
+                                                    canvas
+                                                }),
+                                        )
+                                        .build(ctx),
+                                    )
+                                    .with_child(
+                                        ScrollViewerBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_content({
+                                            inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                                .build(ctx);
+                                            inspector
+                                        })
+                                        .build(ctx),
+                                    ),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::stretch())
+                            .add_column(Column::strict(300.0))
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Dialogue Editor"))
+            .build(ctx);
+
+        Self {
+            window,
+            canvas,
+            inspector,
+            property_editors: Rc::new(property_editors),
+            canvas_context_menu,
+            node_context_menu,
+            menu: Menu {
+                file: FileMenu { new, save, load },
+                edit: EditMenu { undo, redo },
+            },
+            load_file_selector,
+            save_file_selector,
+            dialogue_resource: None,
+            command_stack: DialogueCommandStack::new(false),
+            path: Default::default(),
+            selection: Default::default(),
+            connection_kind: ConnectionKind::Link,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn set_dialogue(&mut self, dialogue: Option<DialogueResource>, ui: &mut UserInterface) {
+        self.path = dialogue
+            .as_ref()
+            .map(|d| d.data_ref().path().to_path_buf())
+            .unwrap_or_default();
+        self.dialogue_resource = dialogue;
+        self.selection = Handle::NONE;
+        self.command_stack.clear(DialogueEditorContext {});
+        self.command_stack.mark_saved();
+        self.sync_title(ui);
+        self.sync_to_model(ui);
+        self.sync_inspector(ui, true);
+    }
+
+    fn sync_title(&self, ui: &UserInterface) {
+        let title = if self.dialogue_resource.is_some() {
+            if self.path == PathBuf::default() {
+                "Dialogue Editor - Unnamed Dialogue".to_string()
+            } else {
+                format!("Dialogue Editor - {}", self.path.display())
+            }
+        } else {
+            "Dialogue Editor".to_string()
+        };
+
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
+    }
+
+    fn clear_canvas(&self, ui: &UserInterface) {
+        for &child in ui.node(self.canvas).children() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+    }
+
+    // Dialogues are small, so the whole graph is rebuilt on every change.
+    fn sync_to_model(&self, ui: &mut UserInterface) {
+        self.clear_canvas(ui);
+
+        let Some(dialogue_resource) = self.dialogue_resource.as_ref() else {
+            return;
+        };
+        let dialogue = dialogue_resource.data_ref();
+
+        let mut views = Vec::new();
+        for (handle, node) in dialogue.nodes().pair_iter() {
+            let is_entry = handle == dialogue.entry();
+            let view = AbsmNodeBuilder::new(
+                WidgetBuilder::new()
+                    .with_context_menu(self.node_context_menu.menu.clone())
+                    .with_desired_position(node.position),
+            )
+            .with_normal_color(if is_entry {
+                NORMAL_ROOT_COLOR
+            } else {
+                NORMAL_BACKGROUND
+            })
+            .with_selected_color(if is_entry {
+                SELECTED_ROOT_COLOR
+            } else {
+                SELECTED_BACKGROUND
+            })
+            .with_model_handle(handle)
+            .with_name(node_label(node))
+            .build(&mut ui.build_ctx());
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+            );
+
+            views.push((handle, view));
+        }
+
+        let find_view = |handle: Handle<DialogueNode>| {
+            views
+                .iter()
+                .find_map(|(h, v)| if *h == handle { Some(*v) } else { None })
+        };
+
+        for (handle, node) in dialogue.nodes().pair_iter() {
+            let targets = node
+                .choices
+                .iter()
+                .map(|c| c.target)
+                .chain(node.next.iter().map(|l| l.target));
+            for target in targets {
+                if let (Some(source), Some(dest)) = (find_view(handle), find_view(target)) {
+                    let transition = TransitionBuilder::new(WidgetBuilder::new())
+                        .with_source(source)
+                        .with_dest(dest)
+                        .build(Handle::NONE, &mut ui.build_ctx());
+
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::link(transition, MessageDirection::ToWidget, self.canvas),
+                    );
+
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::lowermost(transition, MessageDirection::ToWidget),
+                    );
+                }
+            }
+        }
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::selection_changed(
+                self.canvas,
+                MessageDirection::ToWidget,
+                find_view(self.selection).into_iter().collect(),
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::force_sync_dependent_objects(
+                self.canvas,
+                MessageDirection::ToWidget,
+            ),
+        );
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface, rebuild: bool) {
+        let dialogue = self.dialogue_resource.as_ref().map(|d| d.data_ref());
+        let node = dialogue
+            .as_ref()
+            .and_then(|d| d.nodes().try_borrow(self.selection));
+
+        if rebuild {
+            let context = node
+                .map(|node| {
+                    InspectorContext::from_object(
+                        node,
+                        &mut ui.build_ctx(),
+                        self.property_editors.clone(),
+                        None,
+                        MSG_SYNC_FLAG,
+                        0,
+                        true,
+                        Default::default(),
+                    )
+                })
+                .unwrap_or_default();
+
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        } else if let Some(node) = node {
+            let context = ui
+                .node(self.inspector)
+                .cast::<Inspector>()
+                .expect("Must be Inspector!")
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = context.sync(node, ui, 0, true, Default::default()) {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {:?}", error))
+                }
+            }
+        }
+    }
+
+    // Applies the given modification to a copy of the dialogue and puts it on the command stack.
+    fn modify<F>(&mut self, ui: &mut UserInterface, func: F)
+    where
+        F: FnOnce(&mut Dialogue),
+    {
+        let Some(dialogue_resource) = self.dialogue_resource.clone() else {
+            return;
+        };
+
+        let mut dialogue = dialogue_resource.data_ref().clone();
+        func(&mut dialogue);
+
+        self.command_stack.do_command(
+            Box::new(ModifyDialogueCommand {
+                dialogue_resource,
+                dialogue,
+            }),
+            DialogueEditorContext {},
+        );
+
+        self.sync_to_model(ui);
+        self.sync_inspector(ui, false);
+    }
+
+    fn save(&mut self) {
+        if let Some(dialogue_resource) = self.dialogue_resource.as_ref() {
+            let mut dialogue = dialogue_resource.data_ref();
+            dialogue.set_path(self.path.clone());
+            let result = dialogue.save(&self.path);
+            if result.is_ok() {
+                self.command_stack.mark_saved();
+            }
+            Log::verify(result);
+        }
+    }
+
+    fn open_save_file_dialog(&self, ui: &UserInterface) {
+        ui.send_message(FileSelectorMessage::root(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+
+        ui.send_message(WindowMessage::open_modal(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn handle_canvas_message(&mut self, msg: &AbsmCanvasMessage, ui: &mut UserInterface) {
+        match msg {
+            AbsmCanvasMessage::CommitTransition {
+                source_node,
+                dest_node,
+            } => {
+                let source = fetch_node_model_handle(*source_node, ui);
+                let target = fetch_node_model_handle(*dest_node, ui);
+                let kind = self.connection_kind;
+                self.modify(ui, |dialogue| {
+                    if let Some(node) = dialogue.nodes_mut().try_borrow_mut(source) {
+                        match kind {
+                            ConnectionKind::Link => node.next.push(DialogueLink {
+                                conditions: Default::default(),
+                                target,
+                            }),
+                            ConnectionKind::Choice => node.choices.push(DialogueChoice {
+                                text_key: Default::default(),
+                                conditions: Default::default(),
+                                target,
+                            }),
+                        }
+                    }
+                });
+            }
+            AbsmCanvasMessage::CommitDrag { entries } => {
+                let positions = entries
+                    .iter()
+                    .map(|e| {
+                        (
+                            fetch_node_model_handle(e.node, ui),
+                            ui.node(e.node).actual_local_position(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.modify(ui, |dialogue| {
+                    for (handle, position) in positions {
+                        if let Some(node) = dialogue.nodes_mut().try_borrow_mut(handle) {
+                            node.position = position;
+                        }
+                    }
+                });
+            }
+            AbsmCanvasMessage::SelectionChanged(selection) => {
+                let new_selection = selection
+                    .iter()
+                    .map(|n| fetch_node_model_handle(*n, ui))
+                    .find(|h| h.is_some())
+                    .unwrap_or_default();
+                if new_selection != self.selection {
+                    self.selection = new_selection;
+                    self.sync_inspector(ui, true);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_context_menus(&mut self, message: &UiMessage, ui: &mut UserInterface) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            let target = self.node_context_menu.placement_target;
+            let target_model = fetch_node_model_handle(target, ui);
+            if message.destination() == self.canvas_context_menu.add_node {
+                let screen_position = ui.node(*self.canvas_context_menu.menu).screen_position();
+                let position = ui.node(self.canvas).screen_to_local(screen_position);
+                self.modify(ui, |dialogue| {
+                    dialogue.add_node(DialogueNode {
+                        position,
+                        name: "New Node".to_string(),
+                        ..Default::default()
+                    });
+                });
+            } else if message.destination() == self.node_context_menu.add_link
+                || message.destination() == self.node_context_menu.add_choice
+            {
+                self.connection_kind = if message.destination() == self.node_context_menu.add_link {
+                    ConnectionKind::Link
+                } else {
+                    ConnectionKind::Choice
+                };
+                ui.send_message(AbsmCanvasMessage::switch_mode(
+                    self.canvas,
+                    MessageDirection::ToWidget,
+                    Mode::CreateTransition {
+                        source: target,
+                        source_pos: ui.node(target).center(),
+                        dest_pos: ui.node(self.canvas).screen_to_local(ui.cursor_position()),
+                    },
+                ));
+            } else if message.destination() == self.node_context_menu.set_as_entry {
+                self.modify(ui, |dialogue| dialogue.set_entry(target_model));
+            } else if message.destination() == self.node_context_menu.remove {
+                if self.selection == target_model {
+                    self.selection = Handle::NONE;
+                    self.sync_inspector(ui, true);
+                }
+                self.modify(ui, |dialogue| {
+                    if dialogue.nodes().is_valid_handle(target_model) {
+                        dialogue.remove_node(target_model);
+                    }
+                });
+            }
+        } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
+            if message.destination() == *self.node_context_menu.menu {
+                self.node_context_menu.placement_target = *target;
+            }
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+
+        self.handle_context_menus(message, ui);
+
+        if let Some(msg) = message.data::<AbsmCanvasMessage>() {
+            if message.destination() == self.canvas
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.handle_canvas_message(msg, ui);
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let selection = self.selection;
+                let action = PropertyAction::from_field_kind(&args.value);
+                let path = args.path();
+                self.modify(ui, |dialogue| {
+                    if let Some(node) = dialogue.nodes_mut().try_borrow_mut(selection) {
+                        action.apply(&path, node, &mut |result| {
+                            Log::verify(result);
+                        });
+                    }
+                });
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.menu.edit.undo {
+                self.command_stack.undo(DialogueEditorContext {});
+                self.sync_to_model(ui);
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.edit.redo {
+                self.command_stack.redo(DialogueEditorContext {});
+                self.sync_to_model(ui);
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.file.new {
+                self.set_dialogue(Some(Resource::new_ok(Dialogue::default())), ui);
+            } else if message.destination() == self.menu.file.load {
+                ui.send_message(FileSelectorMessage::root(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+
+                ui.send_message(WindowMessage::open_modal(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.menu.file.save {
+                if self.path == PathBuf::default() {
+                    self.open_save_file_dialog(ui);
+                } else {
+                    self.save();
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.load_file_selector {
+                if let Ok(dialogue) = block_on(engine.resource_manager.request::<Dialogue>(path)) {
+                    self.set_dialogue(Some(dialogue), ui);
+                }
+            } else if message.destination() == self.save_file_selector {
+                self.path = path.clone();
+                self.save();
+                self.sync_title(ui);
+            }
+        }
+    }
+}
+
+impl Document for DialogueEditorWindow {
+    fn document_info(&self) -> Option<DocumentInfo> {
+        self.dialogue_resource.as_ref().map(|_| DocumentInfo {
+            kind: DocumentKind::Dialogue,
+            path: (self.path != PathBuf::default()).then(|| self.path.clone()),
+            modified: self.command_stack.is_modified(),
+        })
+    }
+
+    fn save_document(&mut self, _engine: &mut Engine) -> bool {
+        if self.path == PathBuf::default() {
+            false
+        } else {
+            self.save();
+            true
+        }
+    }
+}
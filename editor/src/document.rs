@@ -0,0 +1,230 @@
+//! Unified model of documents, that could be edited in the editor. Every document has its own
+//! command stack, which is also used to track unsaved changes (see `mark_saved` and `is_modified`
+//! methods of command stacks). Animations, animation blending state machines, and materials are
+//! edited using scene commands, so their changes are tracked by the scene they belong to. Materials
+//! are stored in external files, such files are written when the scene is saved and reverted when
+//! the changes of the scene are discarded (see [`ModifiedResources`]).
+
+use crate::Engine;
+use fyrox::{
+    asset::{manager::ResourceManager, Resource, TypedResourceData},
+    core::log::Log,
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+/// Kind of a document.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DocumentKind {
+    Scene,
+    Curve,
+    Dialogue,
+    BehaviorTree,
+    SoundBank,
+    PostEffects,
+}
+
+impl Display for DocumentKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DocumentKind::Scene => "scene",
+            DocumentKind::Curve => "curve",
+            DocumentKind::Dialogue => "dialogue",
+            DocumentKind::BehaviorTree => "behavior tree",
+            DocumentKind::SoundBank => "sound bank",
+            DocumentKind::PostEffects => "post effects",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Brief information about an opened document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentInfo {
+    pub kind: DocumentKind,
+    /// Path of the document, `None` if the document was never saved.
+    pub path: Option<PathBuf>,
+    /// `true` if the document has unsaved changes.
+    pub modified: bool,
+}
+
+impl Display for DocumentInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.path.as_ref() {
+            Some(path) => write!(f, "{} {}", self.kind, path.display()),
+            None => write!(f, "unnamed {}", self.kind),
+        }
+    }
+}
+
+/// Common interface of editors, that edit a single document at a time.
+pub trait Document {
+    /// Returns information about the document, that is currently opened in the editor. `None` if
+    /// there's no opened document.
+    fn document_info(&self) -> Option<DocumentInfo>;
+
+    /// Saves the document at its current path. Returns `false` if the document has no path yet,
+    /// so it must be saved using `Save As` dialog of the editor.
+    fn save_document(&mut self, engine: &mut Engine) -> bool;
+}
+
+/// Saves every modified document of the given editors. Returns `false` if some of the documents
+/// could not be saved, because they were never saved before and have no path.
+pub fn save_modified_documents(editors: &mut [&mut dyn Document], engine: &mut Engine) -> bool {
+    let mut saved_all = true;
+    for editor in editors.iter_mut() {
+        if let Some(info) = editor.document_info().filter(|info| info.modified) {
+            if !editor.save_document(engine) {
+                Log::err(format!(
+                    "Unable to save {info}, because it has no path. Save it manually and retry."
+                ));
+                saved_all = false;
+            }
+        }
+    }
+    saved_all
+}
+
+/// A set of external resources, that were changed by the commands of a document, but belong to
+/// their own files (for example, materials, that are edited in a scene). Such resources must be
+/// saved together with the document and reverted to their saved state, when the changes of the
+/// document are discarded.
+pub struct ModifiedResources<T>
+where
+    T: TypedResourceData,
+{
+    resources: Vec<Resource<T>>,
+}
+
+impl<T> Default for ModifiedResources<T>
+where
+    T: TypedResourceData,
+{
+    fn default() -> Self {
+        Self {
+            resources: Default::default(),
+        }
+    }
+}
+
+impl<T> ModifiedResources<T>
+where
+    T: TypedResourceData,
+{
+    /// Remembers the given resource as modified and marks its data as dirty. Embedded resources are
+    /// ignored, because they are saved as a part of the document.
+    pub fn add(&mut self, resource: &Resource<T>) {
+        if !resource.is_ok() {
+            return;
+        }
+
+        let mut data = resource.data_ref();
+        if data.is_embedded() {
+            return;
+        }
+        data.mark_dirty();
+        drop(data);
+
+        if !self.resources.contains(resource) {
+            self.resources.push(resource.clone());
+        }
+    }
+
+    /// Returns `true` if there's no modified resources.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Saves every modified resource to its file. Resources, that could not be saved, stay in the
+    /// set and an error message with the reasons is returned.
+    pub fn save(&mut self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        self.resources.retain(|resource| {
+            if !resource.is_ok() {
+                return false;
+            }
+
+            let mut data = resource.data_ref();
+            let path = data.path().to_path_buf();
+            match data.try_save(&path) {
+                Ok(()) => false,
+                Err(err) => {
+                    errors.push(format!("{}: {}", path.display(), err));
+                    true
+                }
+            }
+        });
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Unable to save modified resources:\n{}",
+                errors.join("\n")
+            ))
+        }
+    }
+
+    /// Discards the changes of every modified resource by reloading it from its file.
+    pub fn revert(&mut self, resource_manager: &ResourceManager) {
+        for resource in self.resources.drain(..) {
+            resource.reload(resource_manager);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::{
+        asset::{io::FsResourceIo, ResourceData},
+        core::{color::Color, futures::executor::block_on, sstorage::ImmutableString},
+        material::{shader::Shader, Material, MaterialResource, PropertyValue},
+    };
+    use std::path::Path;
+
+    fn load_material(path: &Path) -> Material {
+        let resource_manager = ResourceManager::new();
+        resource_manager
+            .state()
+            .constructors_container
+            .add::<Shader>();
+        block_on(Material::from_file(path, &FsResourceIo, resource_manager)).unwrap()
+    }
+
+    #[test]
+    fn material_edit_survives_save() {
+        let path = Path::new("material_edit_survives_save.material");
+        let name = ImmutableString::new("diffuseColor");
+        let color = Color::opaque(10, 20, 30);
+
+        Material::standard().save(path).unwrap();
+        let material = MaterialResource::new_ok(load_material(path));
+
+        let mut modified = ModifiedResources::default();
+        material
+            .data_ref()
+            .set_property(&name, PropertyValue::Color(color))
+            .unwrap();
+        modified.add(&material);
+        assert!(!modified.is_empty());
+        assert!(material.data_ref().is_dirty());
+
+        // Saving the scene writes every material, that was changed by its commands.
+        modified.save().unwrap();
+        assert!(modified.is_empty());
+        assert!(!material.data_ref().is_dirty());
+
+        let loaded = load_material(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            loaded
+                .property_ref(&name)
+                .and_then(|value| value.as_color()),
+            Some(color)
+        );
+    }
+}
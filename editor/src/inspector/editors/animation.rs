@@ -2,11 +2,16 @@
 
 use crate::{gui::make_dropdown_list_option_universal, inspector::EditorEnvironment, Message};
 use fyrox::{
-    animation::{machine::Machine, Animation, AnimationContainer},
-    core::pool::Handle,
+    animation::{
+        machine::{expression::ConditionExpression, Machine},
+        Animation, AnimationContainer,
+    },
+    core::{algebra::Vector2, color::Color, pool::Handle},
     gui::{
+        brush::Brush,
         button::{ButtonBuilder, ButtonMessage},
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        formatted_text::WrapMode,
         inspector::{
             editors::{
                 PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
@@ -15,7 +20,11 @@ use fyrox::{
             FieldKind, InspectorError, PropertyChanged,
         },
         message::{MessageDirection, UiMessage},
-        widget::WidgetBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        Thickness, VerticalAlignment, BRUSH_TEXT,
     },
 };
 use std::any::TypeId;
@@ -183,3 +192,114 @@ impl PropertyEditorDefinition for MachinePropertyEditorDefinition {
         None
     }
 }
+
+fn expression_brush(expression: &ConditionExpression) -> Brush {
+    if expression.is_valid() {
+        BRUSH_TEXT
+    } else {
+        Brush::Solid(Color::opaque(255, 80, 80))
+    }
+}
+
+fn expression_error_text(expression: &ConditionExpression) -> String {
+    match expression.error() {
+        Some(error) => match expression.source().get(error.span.clone()) {
+            Some(fragment) if !fragment.trim().is_empty() => format!("{error}: {fragment}"),
+            _ => error.to_string(),
+        },
+        None => Default::default(),
+    }
+}
+
+/// Text editor of transition condition expressions. Invalid expressions are highlighted and the
+/// compilation error is shown right below the expression.
+#[derive(Debug)]
+pub struct ConditionExpressionPropertyEditorDefinition;
+
+impl PropertyEditorDefinition for ConditionExpressionPropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<ConditionExpression>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<ConditionExpression>()?;
+        let editor = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_min_size(Vector2::new(0.0, 17.0))
+                .with_margin(Thickness::uniform(1.0))
+                .with_foreground(expression_brush(value)),
+        )
+        .with_text(value.source())
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx.build_context);
+        let error = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(!value.is_valid())
+                .with_margin(Thickness::uniform(1.0))
+                .with_foreground(Brush::Solid(Color::opaque(255, 80, 80))),
+        )
+        .with_wrap(WrapMode::Word)
+        .with_text(expression_error_text(value))
+        .build(ctx.build_context);
+        Ok(PropertyEditorInstance::Custom {
+            container: StackPanelBuilder::new(
+                WidgetBuilder::new().with_child(editor).with_child(error),
+            )
+            .build(ctx.build_context),
+            editor,
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<ConditionExpression>()?;
+
+        let container = ctx.ui.node(ctx.instance).parent();
+        if let Some(error) = ctx
+            .ui
+            .try_get_node(container)
+            .and_then(|c| c.children().get(1))
+        {
+            let error = *error;
+            ctx.ui.send_message(TextMessage::text(
+                error,
+                MessageDirection::ToWidget,
+                expression_error_text(value),
+            ));
+            ctx.ui.send_message(WidgetMessage::visibility(
+                error,
+                MessageDirection::ToWidget,
+                !value.is_valid(),
+            ));
+        }
+        ctx.ui.send_message(WidgetMessage::foreground(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            expression_brush(value),
+        ));
+
+        Ok(Some(TextMessage::text(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.source().to_string(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(TextMessage::Text(value)) = ctx.message.data() {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(ConditionExpression::new(value.clone())),
+                });
+            }
+        }
+        None
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     inspector::editors::{
         animation::{
             AnimationContainerPropertyEditorDefinition, AnimationPropertyEditorDefinition,
-            MachinePropertyEditorDefinition,
+            ConditionExpressionPropertyEditorDefinition, MachinePropertyEditorDefinition,
         },
         handle::NodeHandlePropertyEditorDefinition,
         material::MaterialPropertyEditorDefinition,
@@ -48,6 +48,8 @@ use fyrox::{
     resource::{
         curve::{CurveResource, CurveResourceState},
         model::{MaterialSearchOptions, Model, ModelResource},
+        post_effects::{PostEffects, PostEffectsResource},
+        spritesheet::{SpriteAnimation, SpriteSheet, SpriteSheetFrame, SpriteSheetResource},
         texture::{
             CompressionOptions, MipFilter, TextureMagnificationFilter, TextureMinificationFilter,
             TextureResource, TextureWrapMode,
@@ -91,8 +93,8 @@ use fyrox::{
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
             },
             reverb::Reverb,
-            Attenuate, AudioBus, Biquad, DistanceModel, Effect, SoundBuffer, SoundBufferResource,
-            Status,
+            Attenuate, AudioBus, Biquad, DistanceModel, Effect, HrirSphereResource,
+            HrirSphereResourceData, LoopPoints, SoundBuffer, SoundBufferResource, Status,
         },
         terrain::{Chunk, Layer},
         transform::Transform,
@@ -204,6 +206,20 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     >::new());
     container.register_inheritable_vec_collection::<Option<SoundBufferResource>>();
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<
+        HrirSphereResourceData,
+    >::new(
+        Rc::new(|resource_manager, path| {
+            resource_manager
+                .try_request::<HrirSphereResourceData>(path)
+                .map(block_on)
+        }),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<HrirSphereResource>,
+    >::new());
+
     container.insert(
         ResourceFieldPropertyEditorDefinition::<CurveResourceState>::new(
             Rc::new(|resource_manager, path| {
@@ -217,6 +233,35 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InheritablePropertyEditorDefinition::<Option<CurveResource>>::new());
     container.register_inheritable_vec_collection::<Option<CurveResource>>();
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<SpriteSheet>::new(
+        Rc::new(|resource_manager, path| {
+            resource_manager
+                .try_request::<SpriteSheet>(path)
+                .map(block_on)
+        }),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<SpriteSheetResource>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<SpriteSheetResource>>();
+    container.register_inheritable_inspectable::<SpriteSheetFrame>();
+    container.register_inheritable_vec_collection::<SpriteSheetFrame>();
+    container.register_inheritable_inspectable::<SpriteAnimation>();
+    container.register_inheritable_vec_collection::<SpriteAnimation>();
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<PostEffects>::new(
+        Rc::new(|resource_manager, path| {
+            resource_manager
+                .try_request::<PostEffects>(path)
+                .map(block_on)
+        }),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<PostEffectsResource>,
+    >::new());
+
     container.insert(ResourceFieldPropertyEditorDefinition::<Shader>::new(
         Rc::new(|resource_manager, path| {
             resource_manager.try_request::<Shader>(path).map(block_on)
@@ -255,6 +300,9 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
 
+    container.insert(EnumPropertyEditorDefinition::<LoopPoints>::new_optional());
+    container.insert(InspectablePropertyEditorDefinition::<LoopPoints>::new());
+
     container.register_inheritable_enum::<Emitter, _>();
 
     container.register_inheritable_inspectable::<Biquad>();
@@ -357,6 +405,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<OrNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<XorNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<NotNode>::new());
+    container.insert(ConditionExpressionPropertyEditorDefinition);
 
     container.insert(InspectablePropertyEditorDefinition::<ParticleSystemRng>::new());
     container.insert(EnumPropertyEditorDefinition::<PolygonFillMode>::new());
@@ -14,11 +14,14 @@ pub mod absm;
 pub mod animation;
 pub mod asset;
 pub mod audio;
+pub mod behavior_tree;
 pub mod build;
 pub mod camera;
 pub mod command;
 pub mod configurator;
 pub mod curve_editor;
+pub mod dialogue;
+pub mod document;
 pub mod gui;
 pub mod inspector;
 pub mod interaction;
@@ -30,10 +33,12 @@ pub mod message;
 pub mod overlay;
 pub mod particle;
 pub mod plugin;
+pub mod post_effects;
 pub mod preview;
 pub mod scene;
 pub mod scene_viewer;
 pub mod settings;
+pub mod sound_bank;
 pub mod utils;
 pub mod world;
 
@@ -42,11 +47,14 @@ use crate::{
     animation::AnimationEditor,
     asset::{item::AssetItem, AssetBrowser},
     audio::{preview::AudioPreviewPanel, AudioPanel},
+    behavior_tree::BehaviorTreeEditorWindow,
     build::BuildWindow,
     camera::panel::CameraPreviewControlPanel,
     command::{panel::CommandStackViewer, Command, CommandStack},
     configurator::Configurator,
     curve_editor::CurveEditorWindow,
+    dialogue::DialogueEditorWindow,
+    document::{save_modified_documents, Document, DocumentInfo, DocumentKind},
     inspector::{editors::handle::HandlePropertyEditorMessage, Inspector},
     interaction::{
         move_mode::MoveInteractionMode,
@@ -65,6 +73,7 @@ use crate::{
     overlay::OverlayRenderPass,
     particle::ParticleSystemPreviewControlPanel,
     plugin::EditorPlugin,
+    post_effects::PostEffectsEditorWindow,
     scene::{
         commands::{
             graph::AddModelCommand, make_delete_selection_command, mesh::SetMeshTextureCommand,
@@ -77,12 +86,18 @@ use crate::{
     },
     scene_viewer::SceneViewer,
     settings::Settings,
+    sound_bank::SoundBankEditorWindow,
+    utils::array::ArrayDuplicateTool,
+    utils::lod::LodGenerator,
     utils::ragdoll::RagdollWizard,
-    utils::{doc::DocWindow, path_fixer::PathFixer},
+    utils::{doc::DocWindow, path_fixer::PathFixer, validation::ProjectValidator},
     world::{graph::selection::GraphSelection, WorldViewer},
 };
 use fyrox::{
-    asset::{io::FsResourceIo, manager::ResourceManager},
+    asset::{
+        io::{write_atomically, FsResourceIo},
+        manager::ResourceManager,
+    },
     core::{
         algebra::{Matrix3, Vector2},
         color::Color,
@@ -461,6 +476,14 @@ impl EditorSceneEntry {
         }
     }
 
+    pub fn document_info(&self) -> DocumentInfo {
+        DocumentInfo {
+            kind: DocumentKind::Scene,
+            path: self.editor_scene.path.clone(),
+            modified: self.editor_scene.need_save(),
+        }
+    }
+
     fn on_drop(&mut self, engine: &mut Engine) {
         for mut interaction_mode in self.interaction_modes.drain(..) {
             interaction_mode.on_drop(engine);
@@ -690,9 +713,14 @@ pub struct Editor {
     pub navmesh_panel: NavmeshPanel,
     pub settings: Settings,
     pub path_fixer: PathFixer,
+    pub project_validator: ProjectValidator,
     pub material_editor: MaterialEditor,
     pub inspector: Inspector,
     pub curve_editor: CurveEditorWindow,
+    pub dialogue_editor: DialogueEditorWindow,
+    pub behavior_tree_editor: BehaviorTreeEditorWindow,
+    pub sound_bank_editor: SoundBankEditorWindow,
+    pub post_effects_editor: PostEffectsEditorWindow,
     pub audio_panel: AudioPanel,
     pub absm_editor: AbsmEditor,
     pub mode: Mode,
@@ -713,6 +741,8 @@ pub struct Editor {
     pub update_loop_state: UpdateLoopState,
     pub is_suspended: bool,
     pub ragdoll_wizard: RagdollWizard,
+    pub lod_generator: LodGenerator,
+    pub array_duplicate_tool: ArrayDuplicateTool,
 }
 
 impl Editor {
@@ -754,6 +784,8 @@ impl Editor {
         let graphics_context_params = GraphicsContextParams {
             window_attributes,
             vsync: true,
+            texture_upload_budget: Default::default(),
+            shader_upload_budget: None,
         };
 
         let serialization_context = Arc::new(SerializationContext::new());
@@ -853,6 +885,8 @@ impl Editor {
         let doc_window = DocWindow::new(ctx);
         let node_removal_dialog = NodeRemovalDialog::new(ctx);
         let ragdoll_wizard = RagdollWizard::new(ctx, message_sender.clone());
+        let lod_generator = LodGenerator::new(ctx, message_sender.clone());
+        let array_duplicate_tool = ArrayDuplicateTool::new(ctx, message_sender.clone());
 
         let docking_manager;
         let root_grid = GridBuilder::new(
@@ -1014,8 +1048,18 @@ impl Editor {
 
         let path_fixer = PathFixer::new(ctx);
 
+        let project_validator = ProjectValidator::new(ctx);
+
         let curve_editor = CurveEditorWindow::new(ctx);
 
+        let dialogue_editor = DialogueEditorWindow::new(ctx, message_sender.clone());
+
+        let behavior_tree_editor = BehaviorTreeEditorWindow::new(ctx, message_sender.clone());
+
+        let sound_bank_editor = SoundBankEditorWindow::new(ctx, message_sender.clone());
+
+        let post_effects_editor = PostEffectsEditorWindow::new(ctx, message_sender.clone());
+
         let save_scene_dialog = SaveSceneConfirmationDialog::new(ctx);
 
         let build_window = BuildWindow::new(ctx);
@@ -1057,9 +1101,14 @@ impl Editor {
             validation_message_box,
             settings,
             path_fixer,
+            project_validator,
             material_editor,
             inspector,
             curve_editor,
+            dialogue_editor,
+            behavior_tree_editor,
+            sound_bank_editor,
+            post_effects_editor,
             audio_panel,
             save_scene_dialog,
             mode: Mode::Edit,
@@ -1085,6 +1134,8 @@ impl Editor {
             update_loop_state: UpdateLoopState::default(),
             is_suspended: false,
             ragdoll_wizard,
+            lod_generator,
+            array_duplicate_tool,
         };
 
         if let Some(data) = startup_data {
@@ -1342,12 +1393,19 @@ impl Editor {
                     audio_panel: self.audio_panel.window,
                     configurator_window: self.configurator.window,
                     path_fixer: self.path_fixer.window,
+                    project_validator: self.project_validator.window,
                     curve_editor: &self.curve_editor,
+                    dialogue_editor: &self.dialogue_editor,
+                    behavior_tree_editor: &self.behavior_tree_editor,
+                    sound_bank_editor: &self.sound_bank_editor,
+                    post_effects_editor: &self.post_effects_editor,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
                     scene_settings: &self.scene_settings,
                     animation_editor: &self.animation_editor,
                     ragdoll_wizard: &self.ragdoll_wizard,
+                    lod_generator: &self.lod_generator,
+                    array_duplicate_tool: &self.array_duplicate_tool,
                 },
                 settings: &mut self.settings,
             },
@@ -1360,12 +1418,22 @@ impl Editor {
             .handle_ui_message(message, engine, self.message_sender.clone());
         self.command_stack_viewer.handle_ui_message(message);
         self.curve_editor.handle_ui_message(message, engine);
+        self.dialogue_editor.handle_ui_message(message, engine);
+        self.behavior_tree_editor.handle_ui_message(message, engine);
+        self.sound_bank_editor.handle_ui_message(message, engine);
+        self.post_effects_editor.handle_ui_message(message, engine);
         self.path_fixer.handle_ui_message(
             message,
             &mut engine.user_interface,
             engine.serialization_context.clone(),
             engine.resource_manager.clone(),
         );
+        self.project_validator.handle_ui_message(
+            message,
+            &engine.user_interface,
+            &engine.resource_manager,
+            &self.message_sender,
+        );
         self.scene_viewer.handle_ui_message(
             message,
             engine,
@@ -1392,6 +1460,21 @@ impl Editor {
                 editor_scene,
                 &self.message_sender,
             );
+            self.lod_generator.handle_ui_message(
+                message,
+                &mut engine.user_interface,
+                &mut engine.scenes[editor_scene.scene].graph,
+                editor_scene,
+                &engine.resource_manager,
+                &self.message_sender,
+            );
+            self.array_duplicate_tool.handle_ui_message(
+                message,
+                &mut engine.user_interface,
+                &mut engine.scenes[editor_scene.scene].graph,
+                editor_scene,
+                &self.message_sender,
+            );
             self.particle_system_control_panel
                 .handle_ui_message(message, editor_scene, engine);
             self.camera_control_panel
@@ -1451,31 +1534,47 @@ impl Editor {
                         self.message_sender.send(Message::Exit { force: true });
                     }
                     MessageBoxResult::Yes => {
-                        if let Some(first_unsaved) = self.scenes.first_unsaved_scene() {
-                            let editor_scene = &first_unsaved.editor_scene;
-                            if editor_scene.need_save() {
-                                if let Some(path) = editor_scene.path.as_ref() {
-                                    self.message_sender.send(Message::SaveScene {
-                                        scene: editor_scene.scene,
-                                        path: path.clone(),
-                                    });
-
-                                    self.message_sender
-                                        .send(Message::CloseScene(editor_scene.scene));
-
-                                    self.message_sender.send(Message::Exit {
-                                        force: self.scenes.unsaved_scene_count() == 1,
-                                    });
-                                } else {
-                                    // Scene wasn't saved yet, open Save As dialog.
-                                    engine
-                                        .user_interface
-                                        .send_message(WindowMessage::open_modal(
-                                            self.save_file_selector,
-                                            MessageDirection::ToWidget,
-                                            true,
-                                        ));
+                        let saved_documents = save_modified_documents(
+                            &mut [
+                                &mut self.curve_editor,
+                                &mut self.dialogue_editor,
+                                &mut self.behavior_tree_editor,
+                                &mut self.sound_bank_editor,
+                                &mut self.post_effects_editor,
+                            ],
+                            engine,
+                        );
+
+                        // Exit is cancelled if some documents must be saved manually.
+                        if saved_documents {
+                            if let Some(first_unsaved) = self.scenes.first_unsaved_scene() {
+                                let editor_scene = &first_unsaved.editor_scene;
+                                if editor_scene.need_save() {
+                                    if let Some(path) = editor_scene.path.as_ref() {
+                                        self.message_sender.send(Message::SaveScene {
+                                            scene: editor_scene.scene,
+                                            path: path.clone(),
+                                        });
+
+                                        self.message_sender
+                                            .send(Message::CloseScene(editor_scene.scene));
+
+                                        self.message_sender.send(Message::Exit {
+                                            force: self.scenes.unsaved_scene_count() == 1,
+                                        });
+                                    } else {
+                                        // Scene wasn't saved yet, open Save As dialog.
+                                        engine.user_interface.send_message(
+                                            WindowMessage::open_modal(
+                                                self.save_file_selector,
+                                                MessageDirection::ToWidget,
+                                                true,
+                                            ),
+                                        );
+                                    }
                                 }
+                            } else {
+                                self.message_sender.send(Message::Exit { force: true });
                             }
                         }
                     }
@@ -1697,7 +1796,7 @@ impl Editor {
                 },
             );
 
-            editor_scene.has_unsaved_changes = true;
+            editor_scene.has_unsaved_changes = current_scene_entry.command_stack.is_modified();
 
             true
         } else {
@@ -1718,7 +1817,7 @@ impl Editor {
                 serialization_context: engine.serialization_context.clone(),
             });
 
-            editor_scene.has_unsaved_changes = true;
+            editor_scene.has_unsaved_changes = current_scene_entry.command_stack.is_modified();
 
             true
         } else {
@@ -1739,7 +1838,7 @@ impl Editor {
                 serialization_context: engine.serialization_context.clone(),
             });
 
-            editor_scene.has_unsaved_changes = true;
+            editor_scene.has_unsaved_changes = current_scene_entry.command_stack.is_modified();
 
             true
         } else {
@@ -1839,6 +1938,7 @@ impl Editor {
                     Log::info(message);
 
                     editor_scene.has_unsaved_changes = false;
+                    entry.command_stack.mark_saved();
                 }
                 Err(message) => {
                     Log::err(message.clone());
@@ -1889,23 +1989,51 @@ impl Editor {
         }
     }
 
+    /// Returns information about every opened document: scenes and documents of specialized
+    /// editors.
+    pub fn documents(&self) -> Vec<DocumentInfo> {
+        let editors: [&dyn Document; 5] = [
+            &self.curve_editor,
+            &self.dialogue_editor,
+            &self.behavior_tree_editor,
+            &self.sound_bank_editor,
+            &self.post_effects_editor,
+        ];
+
+        self.scenes
+            .iter()
+            .map(|entry| entry.document_info())
+            .chain(editors.iter().filter_map(|editor| editor.document_info()))
+            .collect()
+    }
+
     fn exit(&mut self, force: bool) {
-        let engine = &mut self.engine;
         if force {
             self.exit = true;
-        } else if let Some(first_unsaved) = self.scenes.first_unsaved_scene() {
-            engine.user_interface.send_message(MessageBoxMessage::open(
-                self.exit_message_box,
-                MessageDirection::ToWidget,
-                None,
-                Some(format!(
-                    "There are unsaved changes in the {} scene. \
-                    Do you wish to save them before exit?",
-                    first_unsaved.editor_scene.name()
-                )),
-            ));
         } else {
-            self.exit = true;
+            let unsaved_documents = self
+                .documents()
+                .into_iter()
+                .filter(|document| document.modified)
+                .map(|document| document.to_string())
+                .collect::<Vec<_>>();
+
+            if unsaved_documents.is_empty() {
+                self.exit = true;
+            } else {
+                self.engine
+                    .user_interface
+                    .send_message(MessageBoxMessage::open(
+                        self.exit_message_box,
+                        MessageDirection::ToWidget,
+                        None,
+                        Some(format!(
+                            "There are unsaved changes in: {}. \
+                        Do you wish to save them before exit?",
+                            unsaved_documents.join(", ")
+                        )),
+                    ));
+            }
         }
     }
 
@@ -1916,6 +2044,12 @@ impl Editor {
         if let Some(mut editor_scene_entry) = self.scenes.take_scene(scene) {
             engine.scenes.remove(editor_scene_entry.editor_scene.scene);
 
+            // Changes of the scene are discarded at this point, unsaved materials must be reverted.
+            editor_scene_entry
+                .editor_scene
+                .modified_materials
+                .revert(&engine.resource_manager);
+
             // Preview frame has scene frame texture assigned, it must be cleared explicitly,
             // otherwise it will show last rendered frame in preview which is not what we want.
             self.scene_viewer
@@ -1962,7 +2096,16 @@ impl Editor {
     }
 
     fn configure(&mut self, working_directory: PathBuf) {
-        assert!(self.scenes.is_empty());
+        // Scenes of one project cannot be edited in another, so everything that is left from the
+        // previous project must be closed.
+        let scenes = self
+            .scenes
+            .iter()
+            .map(|entry| entry.editor_scene.scene)
+            .collect::<Vec<_>>();
+        for scene in scenes {
+            self.close_scene(scene);
+        }
 
         self.asset_browser.clear_preview(&mut self.engine);
 
@@ -2118,6 +2261,8 @@ impl Editor {
         self.log.update(&mut self.engine);
         self.material_editor.update(&mut self.engine);
         self.asset_browser.update(&mut self.engine);
+        self.project_validator
+            .update(&mut self.engine.user_interface);
 
         if let Some(scene) = self.scenes.current_editor_scene_ref() {
             self.light_panel.update(scene, &mut self.engine);
@@ -2220,8 +2365,15 @@ impl Editor {
                         needs_sync = true;
                     }
                     Message::Configure { working_directory } => {
-                        self.configure(working_directory);
-                        needs_sync = true;
+                        if self.documents().iter().any(|document| document.modified) {
+                            Log::err(
+                                "Unable to open another project, because there are unsaved \
+                                documents! Save or close them first and retry.",
+                            );
+                        } else {
+                            self.configure(working_directory);
+                            needs_sync = true;
+                        }
                     }
                     Message::OpenSettings => {
                         self.menu.file_menu.settings.open(
@@ -2231,6 +2383,27 @@ impl Editor {
                         );
                     }
                     Message::OpenMaterialEditor(material) => self.open_material_editor(material),
+                    Message::OpenDialogueEditor(dialogue) => {
+                        let ui = &mut self.engine.user_interface;
+                        self.dialogue_editor.set_dialogue(Some(dialogue), ui);
+                        self.dialogue_editor.open(ui);
+                    }
+                    Message::OpenBehaviorTreeEditor(tree) => {
+                        let ui = &mut self.engine.user_interface;
+                        self.behavior_tree_editor.set_tree(Some(tree), ui);
+                        self.behavior_tree_editor.open(ui);
+                    }
+                    Message::OpenSoundBankEditor(bank) => {
+                        let ui = &mut self.engine.user_interface;
+                        self.sound_bank_editor.set_bank(Some(bank), ui);
+                        self.sound_bank_editor.open(ui);
+                    }
+                    Message::OpenPostEffectsEditor(post_effects) => {
+                        let ui = &mut self.engine.user_interface;
+                        self.post_effects_editor
+                            .set_post_effects(Some(post_effects), ui);
+                        self.post_effects_editor.open(ui);
+                    }
                     Message::OpenNodeRemovalDialog => {
                         if let Some(editor_scene) = self.scenes.current_editor_scene_ref() {
                             self.node_removal_dialog.open(editor_scene, &self.engine)
@@ -2255,6 +2428,43 @@ impl Editor {
                     Message::SelectObject { type_id, handle } => {
                         self.select_object(type_id, handle);
                     }
+                    Message::SelectNodes(nodes) => {
+                        if let Some(scene) = self.scenes.current_editor_scene_ref() {
+                            let graph = &self.engine.scenes[scene.scene].graph;
+                            let nodes = nodes
+                                .into_iter()
+                                .filter(|node| graph.is_valid_handle(*node))
+                                .collect::<Vec<_>>();
+                            self.message_sender
+                                .do_scene_command(ChangeSelectionCommand::new(
+                                    Selection::Graph(GraphSelection::from_list(nodes)),
+                                    scene.selection.clone(),
+                                ))
+                        }
+                    }
+                    Message::ShowAssetUsage(path) => {
+                        if let Some(scene) = self.scenes.current_editor_scene_ref() {
+                            self.asset_browser.show_asset_usage(
+                                &path,
+                                scene.scene,
+                                &mut self.engine,
+                            );
+                        }
+                    }
+                    Message::DeleteAsset(path) => {
+                        let open_scenes = self
+                            .scenes
+                            .iter()
+                            .map(|entry| (entry.editor_scene.scene, entry.editor_scene.name()))
+                            .collect::<Vec<_>>();
+                        self.asset_browser
+                            .delete_asset(&path, &open_scenes, &mut self.engine);
+                    }
+                    Message::MarkSceneModified(scene) => {
+                        if let Some(entry) = self.scenes.entry_by_scene_handle_mut(scene) {
+                            entry.editor_scene.has_unsaved_changes = true;
+                        }
+                    }
                     Message::FocusObject(handle) => {
                         if let Some(editor_scene) = self.scenes.current_editor_scene_mut() {
                             let scene = &mut self.engine.scenes[editor_scene.scene];
@@ -2425,7 +2635,7 @@ impl Editor {
                         e
                     )),
                     Ok(_) => {
-                        if let Err(e) = visitor.save_binary(&path) {
+                        if let Err(e) = write_atomically(&path, |path| visitor.save_binary(path)) {
                             Log::err(format!(
                                 "Failed to save selection as prefab! Reason: {:?}",
                                 e
@@ -4,7 +4,10 @@ use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        animation::{absm::AnimationBlendingStateMachineBuilder, AnimationPlayerBuilder},
+        animation::{
+            absm::AnimationBlendingStateMachineBuilder, spritesheet::SpriteAnimationPlayerBuilder,
+            AnimationPlayerBuilder,
+        },
         base::BaseBuilder,
         node::Node,
     },
@@ -14,12 +17,14 @@ pub struct AnimationMenu {
     pub menu: Handle<UiNode>,
     create_animation_player: Handle<UiNode>,
     create_absm: Handle<UiNode>,
+    create_sprite_animation_player: Handle<UiNode>,
 }
 
 impl AnimationMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_animation_player;
         let create_absm;
+        let create_sprite_animation_player;
 
         let menu = create_menu_item(
             "Animation",
@@ -32,6 +37,11 @@ impl AnimationMenu {
                     create_absm = create_menu_item("Animation Blending State Machine", vec![], ctx);
                     create_absm
                 },
+                {
+                    create_sprite_animation_player =
+                        create_menu_item("Sprite Animation Player", vec![], ctx);
+                    create_sprite_animation_player
+                },
             ],
             ctx,
         );
@@ -40,6 +50,7 @@ impl AnimationMenu {
             menu,
             create_animation_player,
             create_absm,
+            create_sprite_animation_player,
         }
     }
 
@@ -64,6 +75,12 @@ impl AnimationMenu {
                 .with_machine(machine)
                 .build_node();
                 Some(node)
+            } else if message.destination() == self.create_sprite_animation_player {
+                let node = SpriteAnimationPlayerBuilder::new(
+                    BaseBuilder::new().with_name("Sprite Animation Player"),
+                )
+                .build_node();
+                Some(node)
             } else {
                 None
             }
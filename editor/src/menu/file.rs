@@ -7,7 +7,7 @@ use crate::{
     Engine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{log::Log, pool::Handle},
     gui::{
         file_browser::{FileSelectorBuilder, FileSelectorMessage},
         menu::MenuItemMessage,
@@ -24,6 +24,7 @@ pub struct FileMenu {
     new_scene: Handle<UiNode>,
     pub save: Handle<UiNode>,
     pub save_as: Handle<UiNode>,
+    save_modified_resources: Handle<UiNode>,
     load: Handle<UiNode>,
     pub close_scene: Handle<UiNode>,
     exit: Handle<UiNode>,
@@ -54,6 +55,7 @@ impl FileMenu {
         let new_scene;
         let save;
         let save_as;
+        let save_modified_resources;
         let close_scene;
         let load;
         let open_settings;
@@ -69,7 +71,9 @@ impl FileMenu {
                 .open(false)
                 .with_title(WindowTitle::Text("Warning".to_owned())),
         )
-        .with_text("Cannot reconfigure editor while scene is open! Close scene first and retry.")
+        .with_text(
+            "Cannot open another project while scene has unsaved changes! Save scene and retry.",
+        )
         .with_buttons(MessageBoxButtons::Ok)
         .build(ctx);
 
@@ -91,6 +95,11 @@ impl FileMenu {
                         create_menu_item_shortcut("Save Scene As...", "Ctrl+Shift+S", vec![], ctx);
                     save_as
                 },
+                {
+                    save_modified_resources =
+                        create_menu_item("Save Modified Resources", vec![], ctx);
+                    save_modified_resources
+                },
                 {
                     load = create_menu_item_shortcut("Load Scene...", "Ctrl+L", vec![], ctx);
                     load
@@ -108,7 +117,7 @@ impl FileMenu {
                     open_scene_settings
                 },
                 {
-                    configure = create_menu_item("Configure...", vec![], ctx);
+                    configure = create_menu_item("Open Project...", vec![], ctx);
                     configure
                 },
                 {
@@ -141,6 +150,7 @@ impl FileMenu {
             new_scene,
             save,
             save_as,
+            save_modified_resources,
             close_scene,
             load,
             exit,
@@ -253,6 +263,9 @@ impl FileMenu {
                         MessageDirection::ToWidget,
                         std::env::current_dir().unwrap(),
                     ));
+            } else if message.destination() == self.save_modified_resources {
+                let count = engine.resource_manager.save_all_modified();
+                Log::info(format!("{count} modified resources were saved."));
             } else if message.destination() == self.load {
                 self.open_load_file_selector(&mut engine.user_interface);
             } else if message.destination() == self.close_scene {
@@ -273,7 +286,7 @@ impl FileMenu {
             } else if message.destination() == self.new_scene {
                 sender.send(Message::NewScene);
             } else if message.destination() == self.configure {
-                if editor_scene.is_none() {
+                if !editor_scene.as_ref().is_some_and(|scene| scene.need_save()) {
                     engine
                         .user_interface
                         .send_message(WindowMessage::open_modal(
@@ -8,8 +8,9 @@ use crate::{
     scene::EditorScene,
     send_sync_message,
     settings::Settings,
-    utils::ragdoll::RagdollWizard,
-    AbsmEditor, CurveEditorWindow, Engine, Mode, SceneSettingsWindow,
+    utils::{array::ArrayDuplicateTool, lod::LodGenerator, ragdoll::RagdollWizard},
+    AbsmEditor, BehaviorTreeEditorWindow, CurveEditorWindow, DialogueEditorWindow, Engine, Mode,
+    PostEffectsEditorWindow, SceneSettingsWindow, SoundBankEditorWindow,
 };
 use fyrox::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
@@ -54,11 +55,18 @@ pub struct Panels<'b> {
     pub asset_window: Handle<UiNode>,
     pub configurator_window: Handle<UiNode>,
     pub path_fixer: Handle<UiNode>,
+    pub project_validator: Handle<UiNode>,
     pub curve_editor: &'b CurveEditorWindow,
+    pub dialogue_editor: &'b DialogueEditorWindow,
+    pub behavior_tree_editor: &'b BehaviorTreeEditorWindow,
+    pub sound_bank_editor: &'b SoundBankEditorWindow,
+    pub post_effects_editor: &'b PostEffectsEditorWindow,
     pub absm_editor: &'b AbsmEditor,
     pub scene_settings: &'b SceneSettingsWindow,
     pub animation_editor: &'b AnimationEditor,
     pub ragdoll_wizard: &'b RagdollWizard,
+    pub lod_generator: &'b LodGenerator,
+    pub array_duplicate_tool: &'b ArrayDuplicateTool,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -178,7 +186,7 @@ impl Menu {
         }
 
         self.utils_menu
-            .handle_ui_message(message, &ctx.panels, &ctx.engine.user_interface);
+            .handle_ui_message(message, &ctx.panels, ctx.engine);
         self.file_menu.handle_ui_message(
             message,
             &self.message_sender,
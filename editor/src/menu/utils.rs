@@ -1,30 +1,51 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    Engine,
+};
 use fyrox::{
     asset::core::pool::Handle,
+    engine::GraphicsContext,
     gui::{
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
         window::WindowMessage,
-        BuildContext, UiNode, UserInterface,
+        BuildContext, UiNode,
     },
 };
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
     open_path_fixer: Handle<UiNode>,
+    open_project_validator: Handle<UiNode>,
     open_curve_editor: Handle<UiNode>,
+    open_dialogue_editor: Handle<UiNode>,
+    open_behavior_tree_editor: Handle<UiNode>,
+    open_sound_bank_editor: Handle<UiNode>,
+    open_post_effects_editor: Handle<UiNode>,
     absm_editor: Handle<UiNode>,
     animation_editor: Handle<UiNode>,
     ragdoll_wizard: Handle<UiNode>,
+    lod_generator: Handle<UiNode>,
+    array_duplicate_tool: Handle<UiNode>,
+    capture_frame: Handle<UiNode>,
 }
 
 impl UtilsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let open_path_fixer;
+        let open_project_validator;
         let open_curve_editor;
+        let open_dialogue_editor;
+        let open_behavior_tree_editor;
+        let open_sound_bank_editor;
+        let open_post_effects_editor;
         let absm_editor;
         let animation_editor;
         let ragdoll_wizard;
+        let lod_generator;
+        let array_duplicate_tool;
+        let capture_frame;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -32,10 +53,31 @@ impl UtilsMenu {
                     open_path_fixer = create_menu_item("Path Fixer", vec![], ctx);
                     open_path_fixer
                 },
+                {
+                    open_project_validator = create_menu_item("Validate Project", vec![], ctx);
+                    open_project_validator
+                },
                 {
                     open_curve_editor = create_menu_item("Curve Editor", vec![], ctx);
                     open_curve_editor
                 },
+                {
+                    open_dialogue_editor = create_menu_item("Dialogue Editor", vec![], ctx);
+                    open_dialogue_editor
+                },
+                {
+                    open_behavior_tree_editor =
+                        create_menu_item("Behavior Tree Editor", vec![], ctx);
+                    open_behavior_tree_editor
+                },
+                {
+                    open_sound_bank_editor = create_menu_item("Sound Bank Editor", vec![], ctx);
+                    open_sound_bank_editor
+                },
+                {
+                    open_post_effects_editor = create_menu_item("Post Effects Editor", vec![], ctx);
+                    open_post_effects_editor
+                },
                 {
                     absm_editor = create_menu_item("ABSM Editor", vec![], ctx);
                     absm_editor
@@ -48,6 +90,18 @@ impl UtilsMenu {
                     ragdoll_wizard = create_menu_item("Ragdoll Wizard", vec![], ctx);
                     ragdoll_wizard
                 },
+                {
+                    lod_generator = create_menu_item("LOD Generator", vec![], ctx);
+                    lod_generator
+                },
+                {
+                    array_duplicate_tool = create_menu_item("Array Duplicate", vec![], ctx);
+                    array_duplicate_tool
+                },
+                {
+                    capture_frame = create_menu_item("Capture Frame", vec![], ctx);
+                    capture_frame
+                },
             ],
             ctx,
         );
@@ -55,14 +109,23 @@ impl UtilsMenu {
         Self {
             menu,
             open_path_fixer,
+            open_project_validator,
             open_curve_editor,
+            open_dialogue_editor,
+            open_behavior_tree_editor,
+            open_sound_bank_editor,
+            open_post_effects_editor,
             absm_editor,
             animation_editor,
             ragdoll_wizard,
+            lod_generator,
+            array_duplicate_tool,
+            capture_frame,
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, panels: &Panels, ui: &UserInterface) {
+    pub fn handle_ui_message(&mut self, message: &UiMessage, panels: &Panels, engine: &mut Engine) {
+        let ui = &engine.user_interface;
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.open_path_fixer {
                 ui.send_message(WindowMessage::open_modal(
@@ -70,14 +133,44 @@ impl UtilsMenu {
                     MessageDirection::ToWidget,
                     true,
                 ));
+            } else if message.destination() == self.open_project_validator {
+                ui.send_message(WindowMessage::open(
+                    panels.project_validator,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
             } else if message.destination() == self.open_curve_editor {
                 panels.curve_editor.open(ui);
+            } else if message.destination() == self.open_dialogue_editor {
+                panels.dialogue_editor.open(ui);
+            } else if message.destination() == self.open_behavior_tree_editor {
+                panels.behavior_tree_editor.open(ui);
+            } else if message.destination() == self.open_sound_bank_editor {
+                panels.sound_bank_editor.open(ui);
+            } else if message.destination() == self.open_post_effects_editor {
+                panels.post_effects_editor.open(ui);
             } else if message.destination() == self.absm_editor {
                 panels.absm_editor.open(ui);
             } else if message.destination() == self.animation_editor {
                 panels.animation_editor.open(ui);
             } else if message.destination() == self.ragdoll_wizard {
                 panels.ragdoll_wizard.open(ui);
+            } else if message.destination() == self.lod_generator {
+                panels.lod_generator.open(ui);
+            } else if message.destination() == self.array_duplicate_tool {
+                panels.array_duplicate_tool.open(ui);
+            } else if message.destination() == self.capture_frame {
+                if let GraphicsContext::Initialized(ref graphics_context) = engine.graphics_context
+                {
+                    let timestamp = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs();
+                    graphics_context
+                        .renderer
+                        .frame_capture()
+                        .capture_frame_to_file(format!("screenshot_{timestamp}.png"));
+                }
             }
         }
     }
@@ -9,6 +9,10 @@ use fyrox::{
     },
     gui::UiNode,
     material::MaterialResource,
+    resource::{
+        behavior_tree::BehaviorTreeResource, dialogue::DialogueResource,
+        post_effects::PostEffectsResource, sound_bank::SoundBankResource,
+    },
     scene::{camera::Projection, node::Node, Scene},
 };
 use std::{any::TypeId, path::PathBuf, sync::mpsc::Sender};
@@ -40,6 +44,10 @@ pub enum Message {
     OpenAnimationEditor,
     OpenAbsmEditor,
     OpenMaterialEditor(MaterialResource),
+    OpenDialogueEditor(DialogueResource),
+    OpenBehaviorTreeEditor(BehaviorTreeResource),
+    OpenSoundBankEditor(SoundBankResource),
+    OpenPostEffectsEditor(PostEffectsResource),
     OpenNodeRemovalDialog,
     ShowInAssetBrowser(PathBuf),
     SetWorldViewerFilter(String),
@@ -51,6 +59,10 @@ pub enum Message {
         type_id: TypeId,
         handle: ErasedHandle,
     },
+    SelectNodes(Vec<Handle<Node>>),
+    ShowAssetUsage(PathBuf),
+    DeleteAsset(PathBuf),
+    MarkSceneModified(Handle<Scene>),
     SetCurrentScene(Handle<Scene>),
     FocusObject(Handle<Node>),
     SetEditorCameraProjection(Projection),
@@ -0,0 +1,471 @@
+use crate::{
+    define_command_stack,
+    document::{Document, DocumentInfo, DocumentKind},
+    inspector::editors::make_property_editors_container,
+    menu::create_menu_item,
+    message::MessageSender,
+    utils::create_file_selector,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::{manager::ResourceManager, Resource, ResourceData},
+    core::{futures::executor::block_on, log::Log, make_relative_path, pool::Handle},
+    engine::Engine,
+    gui::{
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent, MenuItemMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    resource::{
+        post_effects::{
+            lut::{CubeLut, LUT_STRIP_HEIGHT, LUT_STRIP_WIDTH},
+            Bloom, ColorGrading, PostEffects, PostEffectsResource, Tonemapping, Vignette,
+        },
+        texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    },
+};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+#[derive(Debug)]
+pub struct PostEffectsEditorContext {}
+
+define_command_stack!(
+    PostEffectsCommand,
+    PostEffectsCommandStack,
+    PostEffectsEditorContext
+);
+
+#[derive(Debug)]
+struct ModifyPostEffectsCommand {
+    post_effects_resource: PostEffectsResource,
+    post_effects: PostEffects,
+}
+
+impl ModifyPostEffectsCommand {
+    fn swap(&mut self) {
+        let mut post_effects = self.post_effects_resource.data_ref();
+        std::mem::swap(&mut *post_effects, &mut self.post_effects);
+        post_effects.mark_dirty();
+    }
+}
+
+impl PostEffectsCommand for ModifyPostEffectsCommand {
+    fn name(&mut self, _: &PostEffectsEditorContext) -> String {
+        "Modify Post Effects".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut PostEffectsEditorContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut PostEffectsEditorContext) {
+        self.swap();
+    }
+}
+
+/// Converts a `.cube` file to an unwrapped look-up table texture, that is saved next to the
+/// `.cube` file with `png` extension.
+fn import_cube_lut(
+    cube_path: &Path,
+    resource_manager: &ResourceManager,
+) -> Result<TextureResource, String> {
+    let source = std::fs::read_to_string(cube_path).map_err(|e| e.to_string())?;
+    let lut = CubeLut::parse(&source).map_err(|e| e.to_string())?;
+
+    let mut texture = Texture::from_bytes(
+        TextureKind::Rectangle {
+            width: LUT_STRIP_WIDTH as u32,
+            height: LUT_STRIP_HEIGHT as u32,
+        },
+        TexturePixelKind::RGB8,
+        lut.to_strip(),
+        false,
+    )
+    .ok_or_else(|| "Unable to create a look-up table texture.".to_string())?;
+    let strip_path = make_relative_path(cube_path.with_extension("png"))
+        .unwrap_or_else(|_| cube_path.with_extension("png"));
+    texture.set_path(strip_path.clone());
+    texture.save().map_err(|e| format!("{e:?}"))?;
+
+    let texture = resource_manager.request::<Texture>(strip_path);
+    // The texture could be imported already, make sure that it has the new content.
+    texture.clone().into_untyped().reload(resource_manager);
+    Ok(texture)
+}
+
+struct FileMenu {
+    new: Handle<UiNode>,
+    save: Handle<UiNode>,
+    load: Handle<UiNode>,
+}
+
+struct EditMenu {
+    undo: Handle<UiNode>,
+    redo: Handle<UiNode>,
+    import_lut: Handle<UiNode>,
+}
+
+struct Menu {
+    file: FileMenu,
+    edit: EditMenu,
+}
+
+/// An editor of post-processing stacks. Every change is applied to the resource right away, so
+/// every scene or camera that use the stack shows the changes immediately.
+pub struct PostEffectsEditorWindow {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    property_editors: Rc<PropertyEditorDefinitionContainer>,
+    menu: Menu,
+    load_file_selector: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    lut_file_selector: Handle<UiNode>,
+    post_effects_resource: Option<PostEffectsResource>,
+    command_stack: PostEffectsCommandStack,
+    path: PathBuf,
+}
+
+impl PostEffectsEditorWindow {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let load_file_selector = create_file_selector(ctx, "posteffects", FileBrowserMode::Open);
+        let save_file_selector = create_file_selector(
+            ctx,
+            "posteffects",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.posteffects"),
+            },
+        );
+        let lut_file_selector = create_file_selector(ctx, "cube", FileBrowserMode::Open);
+
+        let property_editors = make_property_editors_container(sender);
+        property_editors.insert(InspectablePropertyEditorDefinition::<Bloom>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<ColorGrading>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<Vignette>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<Tonemapping>::new());
+
+        let inspector;
+        let new;
+        let save;
+        let load;
+        let undo;
+        let redo;
+        let import_lut;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(500.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            MenuBuilder::new(WidgetBuilder::new())
+                                .with_items(vec![
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("File"))
+                                        .with_items(vec![
+                                            {
+                                                new = create_menu_item("New", vec![], ctx);
+                                                new
+                                            },
+                                            {
+                                                load = create_menu_item("Load", vec![], ctx);
+                                                load
+                                            },
+                                            {
+                                                save = create_menu_item("Save", vec![], ctx);
+                                                save
+                                            },
+                                        ])
+                                        .build(ctx),
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("Edit"))
+                                        .with_items(vec![
+                                            {
+                                                undo = create_menu_item("Undo", vec![], ctx);
+                                                undo
+                                            },
+                                            {
+                                                redo = create_menu_item("Redo", vec![], ctx);
+                                                redo
+                                            },
+                                            {
+                                                import_lut = create_menu_item(
+                                                    "Import Color Grading LUT (.cube)...",
+                                                    vec![],
+                                                    ctx,
+                                                );
+                                                import_lut
+                                            },
+                                        ])
+                                        .build(ctx),
+                                ])
+                                .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Post Effects Editor"))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            property_editors: Rc::new(property_editors),
+            menu: Menu {
+                file: FileMenu { new, save, load },
+                edit: EditMenu {
+                    undo,
+                    redo,
+                    import_lut,
+                },
+            },
+            load_file_selector,
+            save_file_selector,
+            lut_file_selector,
+            post_effects_resource: None,
+            command_stack: PostEffectsCommandStack::new(false),
+            path: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn set_post_effects(
+        &mut self,
+        post_effects: Option<PostEffectsResource>,
+        ui: &mut UserInterface,
+    ) {
+        self.path = post_effects
+            .as_ref()
+            .map(|p| p.data_ref().path().to_path_buf())
+            .unwrap_or_default();
+        self.post_effects_resource = post_effects;
+        self.command_stack.clear(PostEffectsEditorContext {});
+        self.command_stack.mark_saved();
+        self.sync_title(ui);
+        self.sync_inspector(ui, true);
+    }
+
+    fn sync_title(&self, ui: &UserInterface) {
+        let title = if self.post_effects_resource.is_some() {
+            if self.path == PathBuf::default() {
+                "Post Effects Editor - Unnamed Post Effects".to_string()
+            } else {
+                format!("Post Effects Editor - {}", self.path.display())
+            }
+        } else {
+            "Post Effects Editor".to_string()
+        };
+
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface, rebuild: bool) {
+        let post_effects = self.post_effects_resource.as_ref().map(|p| p.data_ref());
+
+        if rebuild {
+            let context = post_effects
+                .as_ref()
+                .map(|post_effects| {
+                    InspectorContext::from_object(
+                        &**post_effects,
+                        &mut ui.build_ctx(),
+                        self.property_editors.clone(),
+                        None,
+                        MSG_SYNC_FLAG,
+                        0,
+                        true,
+                        Default::default(),
+                    )
+                })
+                .unwrap_or_default();
+
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        } else if let Some(post_effects) = post_effects {
+            let context = ui
+                .node(self.inspector)
+                .cast::<Inspector>()
+                .expect("Must be Inspector!")
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = context.sync(&*post_effects, ui, 0, true, Default::default())
+            {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {:?}", error))
+                }
+            }
+        }
+    }
+
+    fn modify<F: FnOnce(&mut PostEffects)>(&mut self, ui: &mut UserInterface, func: F) {
+        if let Some(post_effects_resource) = self.post_effects_resource.clone() {
+            let mut post_effects = post_effects_resource.data_ref().clone();
+            func(&mut post_effects);
+
+            self.command_stack.do_command(
+                Box::new(ModifyPostEffectsCommand {
+                    post_effects_resource,
+                    post_effects,
+                }),
+                PostEffectsEditorContext {},
+            );
+
+            self.sync_inspector(ui, false);
+        }
+    }
+
+    fn save(&mut self) {
+        if let Some(post_effects_resource) = self.post_effects_resource.as_ref() {
+            let mut post_effects = post_effects_resource.data_ref();
+            post_effects.set_path(self.path.clone());
+            let result = post_effects.save(&self.path);
+            if result.is_ok() {
+                self.command_stack.mark_saved();
+            }
+            Log::verify(result);
+        }
+    }
+
+    fn open_file_dialog(&self, file_selector: Handle<UiNode>, ui: &UserInterface) {
+        ui.send_message(FileSelectorMessage::root(
+            file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+
+        ui.send_message(WindowMessage::open_modal(
+            file_selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.modify(ui, |post_effects| {
+                    PropertyAction::from_field_kind(&args.value).apply(
+                        &args.path(),
+                        post_effects,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+                });
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.menu.edit.undo {
+                self.command_stack.undo(PostEffectsEditorContext {});
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.edit.redo {
+                self.command_stack.redo(PostEffectsEditorContext {});
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.edit.import_lut {
+                if self.post_effects_resource.is_some() {
+                    self.open_file_dialog(self.lut_file_selector, ui);
+                }
+            } else if message.destination() == self.menu.file.new {
+                self.set_post_effects(Some(Resource::new_ok(PostEffects::default())), ui);
+            } else if message.destination() == self.menu.file.load {
+                self.open_file_dialog(self.load_file_selector, ui);
+            } else if message.destination() == self.menu.file.save {
+                if self.path == PathBuf::default() {
+                    self.open_file_dialog(self.save_file_selector, ui);
+                } else {
+                    self.save();
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.load_file_selector {
+                if let Ok(post_effects) =
+                    block_on(engine.resource_manager.request::<PostEffects>(path))
+                {
+                    self.set_post_effects(Some(post_effects), ui);
+                }
+            } else if message.destination() == self.save_file_selector {
+                self.path = path.clone();
+                self.save();
+                self.sync_title(ui);
+            } else if message.destination() == self.lut_file_selector {
+                match import_cube_lut(path, &engine.resource_manager) {
+                    Ok(lut) => self.modify(ui, |post_effects| {
+                        post_effects.color_grading.lut = Some(lut);
+                        post_effects.color_grading.enabled = true;
+                    }),
+                    Err(error) => Log::err(format!(
+                        "Unable to import color grading LUT from {}. Reason: {error}",
+                        path.display()
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl Document for PostEffectsEditorWindow {
+    fn document_info(&self) -> Option<DocumentInfo> {
+        self.post_effects_resource.as_ref().map(|_| DocumentInfo {
+            kind: DocumentKind::PostEffects,
+            path: (self.path != PathBuf::default()).then(|| self.path.clone()),
+            modified: self.command_stack.is_modified(),
+        })
+    }
+
+    fn save_document(&mut self, _engine: &mut Engine) -> bool {
+        if self.path == PathBuf::default() {
+            false
+        } else {
+            self.save();
+            true
+        }
+    }
+}
@@ -8,7 +8,7 @@ use fyrox::{
         pool::{Handle, Ticket},
     },
     scene::{
-        base::Base,
+        base::{Base, LodGroup},
         graph::{Graph, SubGraph},
         node::Node,
     },
@@ -466,3 +466,33 @@ impl Command for SetGraphRootCommand {
         self.root = std::mem::replace(&mut context.editor_scene.scene_content_root, self.root);
     }
 }
+
+#[derive(Debug)]
+pub struct SetLodGroupCommand {
+    handle: Handle<Node>,
+    lod_group: Option<LodGroup>,
+}
+
+impl SetLodGroupCommand {
+    pub fn new(handle: Handle<Node>, lod_group: Option<LodGroup>) -> Self {
+        Self { handle, lod_group }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        self.lod_group = context.scene.graph[self.handle].set_lod_group(self.lod_group.take());
+    }
+}
+
+impl Command for SetLodGroupCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Lod Group".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
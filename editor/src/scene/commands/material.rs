@@ -1,7 +1,7 @@
 use crate::{command::Command, scene::commands::SceneContext};
 use fyrox::{
     asset::ResourceData,
-    core::{log::Log, sstorage::ImmutableString},
+    core::sstorage::ImmutableString,
     material::{shader::ShaderResource, Material, MaterialResource, PropertyValue},
 };
 
@@ -21,7 +21,7 @@ impl SetMaterialPropertyValueCommand {
         }
     }
 
-    fn swap(&mut self) {
+    fn swap(&mut self, context: &mut SceneContext) {
         let mut material = self.material.data_ref();
 
         let old_value = material.property_ref(&self.name).unwrap().clone();
@@ -30,10 +30,10 @@ impl SetMaterialPropertyValueCommand {
             .set_property(&self.name, std::mem::replace(&mut self.value, old_value))
             .unwrap();
 
-        if !material.is_embedded() {
-            let path = material.path().to_path_buf();
-            Log::verify(material.save(&path));
-        }
+        drop(material);
+
+        // External material is saved together with the scene.
+        context.editor_scene.modified_materials.add(&self.material);
     }
 }
 
@@ -42,12 +42,12 @@ impl Command for SetMaterialPropertyValueCommand {
         format!("Set Material {} Property Value", self.name)
     }
 
-    fn execute(&mut self, _: &mut SceneContext) {
-        self.swap();
+    fn execute(&mut self, ctx: &mut SceneContext) {
+        self.swap(ctx);
     }
 
-    fn revert(&mut self, _: &mut SceneContext) {
-        self.swap();
+    fn revert(&mut self, ctx: &mut SceneContext) {
+        self.swap(ctx);
     }
 }
 
@@ -74,6 +74,10 @@ impl SetMaterialShaderCommand {
     }
 
     fn swap(&mut self, context: &mut SceneContext) {
+        // A material created from a shader is always embedded, so the resource is registered
+        // both before and after the swap to catch the external data.
+        context.editor_scene.modified_materials.add(&self.material);
+
         match std::mem::replace(&mut self.state, SetMaterialShaderCommandState::Undefined) {
             SetMaterialShaderCommandState::Undefined => {
                 unreachable!()
@@ -81,10 +85,12 @@ impl SetMaterialShaderCommand {
             SetMaterialShaderCommandState::NonExecuted { new_shader } => {
                 let mut material = self.material.data_ref();
 
-                let old_material = std::mem::replace(
-                    &mut *material,
-                    Material::from_shader(new_shader, Some(context.resource_manager.clone())),
-                );
+                let mut new_material =
+                    Material::from_shader(new_shader, Some(context.resource_manager.clone()));
+                // Keep the material bound to its file.
+                new_material.set_path(material.path().to_path_buf());
+
+                let old_material = std::mem::replace(&mut *material, new_material);
 
                 self.state = SetMaterialShaderCommandState::Executed { old_material };
             }
@@ -104,12 +110,7 @@ impl SetMaterialShaderCommand {
             }
         }
 
-        let mut material = self.material.data_ref();
-
-        if !material.is_embedded() {
-            let path = material.path().to_path_buf();
-            Log::verify(material.save(&path));
-        }
+        context.editor_scene.modified_materials.add(&self.material);
     }
 }
 
@@ -1,29 +1,35 @@
 use crate::{
     absm::selection::AbsmSelection, animation::selection::AnimationSelection,
-    audio::AudioBusSelection, camera::CameraController,
+    audio::AudioBusSelection, camera::CameraController, document::ModifiedResources,
     interaction::navmesh::selection::NavmeshSelection, scene::clipboard::Clipboard,
     world::graph::selection::GraphSelection, Settings,
 };
 use fyrox::core::log::Log;
 use fyrox::{
-    core::{color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle, visitor::Visitor},
+    asset::io::write_atomically,
+    core::{
+        algebra::Vector3, color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle,
+        visitor::Visitor,
+    },
     engine::Engine,
+    material::Material,
     scene::{
         base::BaseBuilder,
         camera::Camera,
         debug::{Line, SceneDrawingContext},
+        dim2,
         graph::{Graph, GraphUpdateSwitches},
+        joint::Joint,
         light::{point::PointLight, spot::SpotLight},
         mesh::Mesh,
         navmesh::NavigationalMesh,
         node::Node,
         pivot::PivotBuilder,
+        ragdoll::{Limb, Ragdoll},
         terrain::Terrain,
         Scene,
     },
 };
-use std::fs::File;
-use std::io::Write;
 use std::path::PathBuf;
 
 pub mod clipboard;
@@ -47,6 +53,8 @@ pub struct EditorScene {
     pub camera_controller: CameraController,
     pub preview_camera: Handle<Node>,
     pub graph_switches: GraphUpdateSwitches,
+    /// External materials, that were changed by the commands of the scene.
+    pub modified_materials: ModifiedResources<Material>,
 }
 
 impl EditorScene {
@@ -84,6 +92,7 @@ impl EditorScene {
             clipboard: Default::default(),
             has_unsaved_changes: false,
             preview_camera: Default::default(),
+            modified_materials: Default::default(),
             graph_switches: GraphUpdateSwitches {
                 physics2d: true,
                 physics: true,
@@ -139,16 +148,19 @@ impl EditorScene {
 
             let mut visitor = Visitor::new();
             pure_scene.save("Scene", &mut visitor).unwrap();
-            if let Err(e) = visitor.save_binary(&path) {
+            if let Err(e) = write_atomically(&path, |path| visitor.save_binary(path)) {
                 Err(format!("Failed to save scene! Reason: {}", e))
+            } else if let Err(e) = self.modified_materials.save() {
+                Err(format!(
+                    "Scene was saved, but its materials were not! Reason: {}",
+                    e
+                ))
             } else {
                 if settings.debugging.save_scene_in_text_form {
                     let text = visitor.save_text();
                     let mut path = path.to_path_buf();
                     path.set_extension("txt");
-                    if let Ok(mut file) = File::create(path) {
-                        Log::verify(file.write_all(text.as_bytes()));
-                    }
+                    Log::verify(write_atomically(&path, |path| std::fs::write(path, &text)));
                 }
 
                 Ok(format!("Scene {} was successfully saved!", path.display()))
@@ -283,6 +295,30 @@ impl EditorScene {
                         }
                     }
                 }
+            } else if let Some(ragdoll) = node.cast::<Ragdoll>() {
+                if settings.debugging.show_physics {
+                    draw_limbs_recursively(ragdoll.root_limb(), graph, ctx);
+                }
+            } else if let Some(joint) = node.cast::<Joint>() {
+                if settings.debugging.show_physics {
+                    draw_joint(
+                        node.global_position(),
+                        joint.body1(),
+                        joint.body2(),
+                        graph,
+                        ctx,
+                    );
+                }
+            } else if let Some(joint) = node.cast::<dim2::joint::Joint>() {
+                if settings.debugging.show_physics {
+                    draw_joint(
+                        node.global_position(),
+                        joint.body1(),
+                        joint.body2(),
+                        graph,
+                        ctx,
+                    );
+                }
             } else {
                 node.debug_draw(ctx);
             }
@@ -292,6 +328,43 @@ impl EditorScene {
             }
         }
 
+        // Connects physical bones of limbs (3D or 2D rigid bodies) with their children.
+        fn draw_limbs_recursively(limb: &Limb, graph: &Graph, ctx: &mut SceneDrawingContext) {
+            for child in limb.children.iter() {
+                if let (Some(parent_body), Some(child_body)) = (
+                    graph.try_get(limb.physical_bone),
+                    graph.try_get(child.physical_bone),
+                ) {
+                    ctx.add_line(Line {
+                        begin: parent_body.global_position(),
+                        end: child_body.global_position(),
+                        color: Color::opaque(0, 200, 255),
+                    });
+                }
+
+                draw_limbs_recursively(child, graph, ctx);
+            }
+        }
+
+        // Connects a joint (3D or 2D) with its bodies.
+        fn draw_joint(
+            position: Vector3<f32>,
+            body1: Handle<Node>,
+            body2: Handle<Node>,
+            graph: &Graph,
+            ctx: &mut SceneDrawingContext,
+        ) {
+            for body in [body1, body2] {
+                if let Some(body) = graph.try_get(body) {
+                    ctx.add_line(Line {
+                        begin: position,
+                        end: body.global_position(),
+                        color: Color::opaque(255, 200, 0),
+                    });
+                }
+            }
+        }
+
         // Draw pivots.
         draw_recursively(
             self.scene_content_root,
@@ -26,6 +26,8 @@ use fyrox::{
             physics::{IntegrationParameters, PhysicsWorld},
             Graph, NodePool,
         },
+        prefab_pool::{PrefabWarmUp, PrefabWarmUpList},
+        resource_override::{ResourceOverride, ResourceOverrideTable},
         SceneRenderingOptions,
     },
     utils::lightmap::Lightmap,
@@ -64,6 +66,12 @@ impl SceneSettingsWindow {
         container.register_inheritable_inspectable::<PhysicsWorld>();
         container.register_inheritable_inspectable::<dim2::physics::PhysicsWorld>();
         container.register_inheritable_inspectable::<SceneRenderingOptions>();
+        container.register_inheritable_inspectable::<ResourceOverrideTable>();
+        container.register_inheritable_inspectable::<ResourceOverride>();
+        container.register_inheritable_vec_collection::<ResourceOverride>();
+        container.register_inheritable_inspectable::<PrefabWarmUpList>();
+        container.register_inheritable_inspectable::<PrefabWarmUp>();
+        container.register_inheritable_vec_collection::<PrefabWarmUp>();
         container.insert(EnumPropertyEditorDefinition::<Color>::new_optional());
 
         Self {
@@ -0,0 +1,398 @@
+use crate::{
+    define_command_stack,
+    document::{Document, DocumentInfo, DocumentKind},
+    inspector::editors::make_property_editors_container,
+    menu::create_menu_item,
+    message::MessageSender,
+    utils::create_file_selector,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::{Resource, ResourceData},
+    core::{futures::executor::block_on, log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                collection::VecCollectionPropertyEditorDefinition,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
+            },
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        menu::{MenuBuilder, MenuItemBuilder, MenuItemContent, MenuItemMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    resource::sound_bank::{
+        SoundBank, SoundBankResource, SoundCue, SoundCueSelection, SoundCueVariation,
+    },
+};
+use std::{fmt::Debug, path::PathBuf, rc::Rc};
+
+#[derive(Debug)]
+pub struct SoundBankEditorContext {}
+
+define_command_stack!(
+    SoundBankCommand,
+    SoundBankCommandStack,
+    SoundBankEditorContext
+);
+
+#[derive(Debug)]
+struct ModifySoundBankCommand {
+    bank_resource: SoundBankResource,
+    bank: SoundBank,
+}
+
+impl ModifySoundBankCommand {
+    fn swap(&mut self) {
+        let mut bank = self.bank_resource.data_ref();
+        std::mem::swap(&mut *bank, &mut self.bank);
+        bank.mark_dirty();
+    }
+}
+
+impl SoundBankCommand for ModifySoundBankCommand {
+    fn name(&mut self, _: &SoundBankEditorContext) -> String {
+        "Modify Sound Bank".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut SoundBankEditorContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut SoundBankEditorContext) {
+        self.swap();
+    }
+}
+
+struct FileMenu {
+    new: Handle<UiNode>,
+    save: Handle<UiNode>,
+    load: Handle<UiNode>,
+}
+
+struct EditMenu {
+    undo: Handle<UiNode>,
+    redo: Handle<UiNode>,
+}
+
+struct Menu {
+    file: FileMenu,
+    edit: EditMenu,
+}
+
+pub struct SoundBankEditorWindow {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    property_editors: Rc<PropertyEditorDefinitionContainer>,
+    menu: Menu,
+    load_file_selector: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    bank_resource: Option<SoundBankResource>,
+    command_stack: SoundBankCommandStack,
+    path: PathBuf,
+}
+
+impl SoundBankEditorWindow {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let load_file_selector = create_file_selector(ctx, "soundbank", FileBrowserMode::Open);
+        let save_file_selector = create_file_selector(
+            ctx,
+            "soundbank",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.soundbank"),
+            },
+        );
+
+        let property_editors = make_property_editors_container(sender);
+        property_editors.insert(InspectablePropertyEditorDefinition::<SoundCue>::new());
+        property_editors.insert(InspectablePropertyEditorDefinition::<SoundCueVariation>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<SoundCue>::new());
+        property_editors.insert(VecCollectionPropertyEditorDefinition::<SoundCueVariation>::new());
+        property_editors.insert(EnumPropertyEditorDefinition::<SoundCueSelection>::new());
+
+        let inspector;
+        let new;
+        let save;
+        let load;
+        let undo;
+        let redo;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(500.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            MenuBuilder::new(WidgetBuilder::new())
+                                .with_items(vec![
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("File"))
+                                        .with_items(vec![
+                                            {
+                                                new = create_menu_item("New", vec![], ctx);
+                                                new
+                                            },
+                                            {
+                                                load = create_menu_item("Load", vec![], ctx);
+                                                load
+                                            },
+                                            {
+                                                save = create_menu_item("Save", vec![], ctx);
+                                                save
+                                            },
+                                        ])
+                                        .build(ctx),
+                                    MenuItemBuilder::new(WidgetBuilder::new())
+                                        .with_content(MenuItemContent::text("Edit"))
+                                        .with_items(vec![
+                                            {
+                                                undo = create_menu_item("Undo", vec![], ctx);
+                                                undo
+                                            },
+                                            {
+                                                redo = create_menu_item("Redo", vec![], ctx);
+                                                redo
+                                            },
+                                        ])
+                                        .build(ctx),
+                                ])
+                                .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Sound Bank Editor"))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            property_editors: Rc::new(property_editors),
+            menu: Menu {
+                file: FileMenu { new, save, load },
+                edit: EditMenu { undo, redo },
+            },
+            load_file_selector,
+            save_file_selector,
+            bank_resource: None,
+            command_stack: SoundBankCommandStack::new(false),
+            path: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn set_bank(&mut self, bank: Option<SoundBankResource>, ui: &mut UserInterface) {
+        self.path = bank
+            .as_ref()
+            .map(|b| b.data_ref().path().to_path_buf())
+            .unwrap_or_default();
+        self.bank_resource = bank;
+        self.command_stack.clear(SoundBankEditorContext {});
+        self.command_stack.mark_saved();
+        self.sync_title(ui);
+        self.sync_inspector(ui, true);
+    }
+
+    fn sync_title(&self, ui: &UserInterface) {
+        let title = if self.bank_resource.is_some() {
+            if self.path == PathBuf::default() {
+                "Sound Bank Editor - Unnamed Sound Bank".to_string()
+            } else {
+                format!("Sound Bank Editor - {}", self.path.display())
+            }
+        } else {
+            "Sound Bank Editor".to_string()
+        };
+
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface, rebuild: bool) {
+        let bank = self.bank_resource.as_ref().map(|b| b.data_ref());
+
+        if rebuild {
+            let context = bank
+                .as_ref()
+                .map(|bank| {
+                    InspectorContext::from_object(
+                        &**bank,
+                        &mut ui.build_ctx(),
+                        self.property_editors.clone(),
+                        None,
+                        MSG_SYNC_FLAG,
+                        0,
+                        true,
+                        Default::default(),
+                    )
+                })
+                .unwrap_or_default();
+
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        } else if let Some(bank) = bank {
+            let context = ui
+                .node(self.inspector)
+                .cast::<Inspector>()
+                .expect("Must be Inspector!")
+                .context()
+                .clone();
+
+            if let Err(sync_errors) = context.sync(&*bank, ui, 0, true, Default::default()) {
+                for error in sync_errors {
+                    Log::err(format!("Failed to sync property. Reason: {:?}", error))
+                }
+            }
+        }
+    }
+
+    fn save(&mut self) {
+        if let Some(bank_resource) = self.bank_resource.as_ref() {
+            let mut bank = bank_resource.data_ref();
+            bank.set_path(self.path.clone());
+            let result = bank.save(&self.path);
+            if result.is_ok() {
+                self.command_stack.mark_saved();
+            }
+            Log::verify(result);
+        }
+    }
+
+    fn open_save_file_dialog(&self, ui: &UserInterface) {
+        ui.send_message(FileSelectorMessage::root(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+
+        ui.send_message(WindowMessage::open_modal(
+            self.save_file_selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let ui = &mut engine.user_interface;
+
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(bank_resource) = self.bank_resource.clone() {
+                    let mut bank = bank_resource.data_ref().clone();
+                    PropertyAction::from_field_kind(&args.value).apply(
+                        &args.path(),
+                        &mut bank,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+
+                    self.command_stack.do_command(
+                        Box::new(ModifySoundBankCommand {
+                            bank_resource,
+                            bank,
+                        }),
+                        SoundBankEditorContext {},
+                    );
+
+                    self.sync_inspector(ui, false);
+                }
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.menu.edit.undo {
+                self.command_stack.undo(SoundBankEditorContext {});
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.edit.redo {
+                self.command_stack.redo(SoundBankEditorContext {});
+                self.sync_inspector(ui, true);
+            } else if message.destination() == self.menu.file.new {
+                self.set_bank(Some(Resource::new_ok(SoundBank::default())), ui);
+            } else if message.destination() == self.menu.file.load {
+                ui.send_message(FileSelectorMessage::root(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+
+                ui.send_message(WindowMessage::open_modal(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.menu.file.save {
+                if self.path == PathBuf::default() {
+                    self.open_save_file_dialog(ui);
+                } else {
+                    self.save();
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.load_file_selector {
+                if let Ok(bank) = block_on(engine.resource_manager.request::<SoundBank>(path)) {
+                    self.set_bank(Some(bank), ui);
+                }
+            } else if message.destination() == self.save_file_selector {
+                self.path = path.clone();
+                self.save();
+                self.sync_title(ui);
+            }
+        }
+    }
+}
+
+impl Document for SoundBankEditorWindow {
+    fn document_info(&self) -> Option<DocumentInfo> {
+        self.bank_resource.as_ref().map(|_| DocumentInfo {
+            kind: DocumentKind::SoundBank,
+            path: (self.path != PathBuf::default()).then(|| self.path.clone()),
+            modified: self.command_stack.is_modified(),
+        })
+    }
+
+    fn save_document(&mut self, _engine: &mut Engine) -> bool {
+        if self.path == PathBuf::default() {
+            false
+        } else {
+            self.save();
+            true
+        }
+    }
+}
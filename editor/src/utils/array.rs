@@ -0,0 +1,520 @@
+use crate::{
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::{
+        commands::{graph::AddModelCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::{Point3, UnitQuaternion, Vector3},
+        log::Log,
+        pool::Handle,
+        rand::{rngs::StdRng, Rng, SeedableRng},
+        reflect::prelude::*,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::enumeration::EnumPropertyEditorDefinition, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::rc::Rc;
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum ArrayLayout {
+    /// Copies are placed one after another with a fixed offset.
+    #[default]
+    Linear,
+    /// Copies are placed on a circle, that passes through the source node.
+    Circle,
+    /// Copies are evenly distributed along a smooth path.
+    Path,
+}
+
+#[derive(Reflect, Debug)]
+pub struct ArraySettings {
+    #[reflect(
+        description = "Amount of copies of every selected node.",
+        min_value = 1.0
+    )]
+    count: usize,
+    #[reflect(description = "Defines how the copies are placed.")]
+    layout: ArrayLayout,
+    #[reflect(description = "Linear layout: an offset between two neighbouring copies.")]
+    offset: Vector3<f32>,
+    #[reflect(description = "Circle layout: center of the circle relative to the source node.")]
+    circle_center: Vector3<f32>,
+    #[reflect(description = "Circle layout: an axis of the circle.")]
+    circle_axis: Vector3<f32>,
+    #[reflect(
+        description = "Circle layout: an angle (in degrees) covered by the copies. Full circle \
+        is shared between the source node and its copies.",
+        min_value = -360.0,
+        max_value = 360.0
+    )]
+    circle_angle: f32,
+    #[reflect(
+        description = "Path layout: a node, whose children define control points of the \
+        path in the order of their appearance."
+    )]
+    path: Handle<Node>,
+    #[reflect(
+        description = "Circle and path layouts: whether to rotate the copies to follow \
+        the circle or the path."
+    )]
+    follow_layout: bool,
+    #[reflect(description = "Extra rotation (Euler angles in degrees) of every next copy.")]
+    rotation_step: Vector3<f32>,
+    #[reflect(description = "Extra scale of every next copy.")]
+    scale_step: Vector3<f32>,
+    #[reflect(description = "Maximum random offset of a copy along every axis.")]
+    random_offset: Vector3<f32>,
+    #[reflect(
+        description = "Maximum random rotation (Euler angles in degrees) of a copy around every axis."
+    )]
+    random_rotation: Vector3<f32>,
+    #[reflect(
+        description = "Maximum random deviation of uniform scale of a copy, 0.1 means +-10%.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    random_scale: f32,
+    #[reflect(description = "Seed of the randomization, the same seed gives the same result.")]
+    seed: u64,
+}
+
+impl Default for ArraySettings {
+    fn default() -> Self {
+        Self {
+            count: 5,
+            layout: ArrayLayout::Linear,
+            offset: Vector3::new(1.0, 0.0, 0.0),
+            circle_center: Vector3::new(0.0, 0.0, 5.0),
+            circle_axis: Vector3::y(),
+            circle_angle: 360.0,
+            path: Default::default(),
+            follow_layout: true,
+            rotation_step: Default::default(),
+            scale_step: Default::default(),
+            random_offset: Default::default(),
+            random_rotation: Default::default(),
+            random_scale: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+fn euler_to_rotation(angles: Vector3<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_euler_angles(
+        angles.x.to_radians(),
+        angles.y.to_radians(),
+        angles.z.to_radians(),
+    )
+}
+
+fn random_vector(rng: &mut StdRng, range: Vector3<f32>) -> Vector3<f32> {
+    range.map(|max| {
+        let max = max.abs();
+        if max > 0.0 {
+            rng.gen_range(-max..=max)
+        } else {
+            0.0
+        }
+    })
+}
+
+/// A smooth path (Catmull-Rom spline) through the given points, sampled by the arc length.
+struct Path {
+    points: Vec<Vector3<f32>>,
+    lengths: Vec<f32>,
+}
+
+impl Path {
+    const SAMPLES_PER_SEGMENT: usize = 16;
+
+    fn new(control_points: &[Vector3<f32>]) -> Option<Self> {
+        if control_points.len() < 2 {
+            return None;
+        }
+
+        let point =
+            |i: isize| control_points[i.clamp(0, control_points.len() as isize - 1) as usize];
+        let mut points = vec![control_points[0]];
+        for i in 0..control_points.len() as isize - 1 {
+            let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+            for sample in 1..=Self::SAMPLES_PER_SEGMENT {
+                let t = sample as f32 / Self::SAMPLES_PER_SEGMENT as f32;
+                let t2 = t * t;
+                let t3 = t2 * t;
+                points.push(
+                    (p1 * 2.0
+                        + (p2 - p0) * t
+                        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+                        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+                        * 0.5,
+                );
+            }
+        }
+
+        let mut lengths = vec![0.0];
+        for pair in points.windows(2) {
+            lengths.push(lengths.last().unwrap() + (pair[1] - pair[0]).norm());
+        }
+
+        Some(Self { points, lengths })
+    }
+
+    /// Returns a position and a direction of the path at the given fraction of its length.
+    fn sample(&self, fraction: f32) -> (Vector3<f32>, Vector3<f32>) {
+        let total = *self.lengths.last().unwrap();
+        let distance = fraction.clamp(0.0, 1.0) * total;
+        let index = self
+            .lengths
+            .partition_point(|length| *length < distance)
+            .clamp(1, self.points.len() - 1);
+        let (a, b) = (self.points[index - 1], self.points[index]);
+        let segment = self.lengths[index] - self.lengths[index - 1];
+        let t = if segment > f32::EPSILON {
+            (distance - self.lengths[index - 1]) / segment
+        } else {
+            0.0
+        };
+        (
+            a.lerp(&b, t),
+            (b - a).try_normalize(f32::EPSILON).unwrap_or_default(),
+        )
+    }
+}
+
+impl ArraySettings {
+    /// Calculates local position and rotation of the copy with the given index (starting from
+    /// one), `None` means that the copy should not be created.
+    fn placement(
+        &self,
+        index: usize,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        path: Option<&Path>,
+    ) -> Option<(Vector3<f32>, UnitQuaternion<f32>)> {
+        match self.layout {
+            ArrayLayout::Linear => Some((position + self.offset * index as f32, rotation)),
+            ArrayLayout::Circle => {
+                // Full circle is split evenly between the source node and its copies, otherwise the
+                // last copy is placed at the end of the arc.
+                let steps = if self.circle_angle.abs() >= 360.0 {
+                    self.count + 1
+                } else {
+                    self.count
+                };
+                let angle = self.circle_angle.to_radians() * index as f32 / steps as f32;
+                let axis = self
+                    .circle_axis
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::y);
+                let turn = UnitQuaternion::from_scaled_axis(axis * angle);
+                let center = position + self.circle_center;
+                let position = center + turn * (position - center);
+                let rotation = if self.follow_layout {
+                    turn * rotation
+                } else {
+                    rotation
+                };
+                Some((position, rotation))
+            }
+            ArrayLayout::Path => {
+                let path = path?;
+                let fraction = if self.count > 1 {
+                    (index - 1) as f32 / (self.count - 1) as f32
+                } else {
+                    0.0
+                };
+                let (position, direction) = path.sample(fraction);
+                let rotation = if self.follow_layout && direction != Vector3::zeros() {
+                    let up = if direction.cross(&Vector3::y()).norm() > f32::EPSILON {
+                        Vector3::y()
+                    } else {
+                        Vector3::z()
+                    };
+                    UnitQuaternion::face_towards(&direction, &up) * rotation
+                } else {
+                    rotation
+                };
+                Some((position, rotation))
+            }
+        }
+    }
+
+    fn create_and_send_command(
+        &self,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        let Selection::Graph(ref selection) = editor_scene.selection else {
+            Log::warn("Select one or more nodes to duplicate them.");
+            return;
+        };
+
+        let control_points = if self.layout == ArrayLayout::Path {
+            if !graph.is_valid_handle(self.path) {
+                Log::warn("There's no path node for the path layout.");
+                return;
+            }
+            graph[self.path]
+                .children()
+                .iter()
+                .map(|child| graph[*child].global_position())
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut copies = Vec::new();
+        for &source in selection.nodes() {
+            // The path should not be duplicated into itself.
+            if source == self.path || !graph.is_valid_handle(source) {
+                continue;
+            }
+
+            let parent = graph[source].parent();
+            let parent_inv_transform = graph
+                .try_get(parent)
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_default();
+            let path = Path::new(
+                &control_points
+                    .iter()
+                    .map(|point| {
+                        parent_inv_transform
+                            .transform_point(&Point3::from(*point))
+                            .coords
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            if self.layout == ArrayLayout::Path && path.is_none() {
+                Log::warn("A path must have at least two control points (children of the node).");
+                return;
+            }
+
+            let transform = graph[source].local_transform();
+            let position = **transform.position();
+            let rotation = **transform.rotation();
+            let scale = **transform.scale();
+            let name = graph[source].name().to_owned();
+
+            for index in 1..=self.count {
+                let Some((position, rotation)) =
+                    self.placement(index, position, rotation, path.as_ref())
+                else {
+                    continue;
+                };
+
+                let position = position + random_vector(&mut rng, self.random_offset);
+                let rotation = rotation
+                    * euler_to_rotation(self.rotation_step * index as f32)
+                    * euler_to_rotation(random_vector(&mut rng, self.random_rotation));
+                let random_scale = if self.random_scale > 0.0 {
+                    1.0 + rng.gen_range(-self.random_scale..=self.random_scale)
+                } else {
+                    1.0
+                };
+                let scale = (scale + self.scale_step * index as f32) * random_scale;
+
+                let (copy, _) = graph.copy_node_inplace(source, &mut |_, _| true);
+                graph.link_nodes(copy, parent);
+                let copy_node = &mut graph[copy];
+                copy_node.set_name(format!("{name}_{index}"));
+                copy_node
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation)
+                    .set_scale(scale);
+                copies.push(copy);
+            }
+        }
+
+        if copies.is_empty() {
+            return;
+        }
+
+        // Copies were created in place to calculate their transforms, take them out of the graph
+        // and put them in the commands to make the duplication undoable.
+        let mut group = copies
+            .iter()
+            .map(|copy| {
+                SceneCommand::new(AddModelCommand::new(graph.take_reserve_sub_graph(*copy)))
+            })
+            .collect::<Vec<_>>();
+        group.push(SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::from_list(copies)),
+            editor_scene.selection.clone(),
+        )));
+
+        sender.do_scene_command(CommandGroup::from(group).with_custom_name("Array Duplicate"));
+    }
+}
+
+pub struct ArrayDuplicateTool {
+    pub window: Handle<UiNode>,
+    pub settings: ArraySettings,
+    inspector: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+}
+
+impl ArrayDuplicateTool {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = ArraySettings::default();
+        let container = make_property_editors_container(sender);
+        container.insert(EnumPropertyEditorDefinition::<ArrayLayout>::new());
+        let container = Rc::new(container);
+
+        let inspector;
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(380.0)
+                .with_height(480.0)
+                .with_name("ArrayDuplicateTool"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Array Duplicate"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_context(InspectorContext::from_object(
+                                &settings,
+                                ctx,
+                                container,
+                                None,
+                                MSG_SYNC_FLAG,
+                                0,
+                                true,
+                                Default::default(),
+                            ))
+                            .build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    ok = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Duplicate")
+                                    .build(ctx);
+                                    ok
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            settings,
+            inspector,
+            ok,
+            cancel,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                self.settings
+                    .create_and_send_command(graph, editor_scene, sender);
+
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
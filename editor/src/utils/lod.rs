@@ -0,0 +1,576 @@
+use crate::{
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::{
+        commands::{
+            graph::{AddModelCommand, DeleteSubGraphCommand, SetLodGroupCommand},
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+    MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::manager::ResourceManager,
+    core::{
+        algebra::Matrix4, log::Log, make_relative_path, pool::Handle, reflect::prelude::*,
+        sstorage::ImmutableString,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    material::{shader::SamplerFallback, Material, MaterialResource, PropertyValue},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    scene::{
+        base::{BaseBuilder, LevelOfDetail, LodGroup},
+        graph::Graph,
+        mesh::{
+            surface::{SurfaceBuilder, SurfaceSharedData},
+            Mesh, MeshBuilder,
+        },
+        node::Node,
+        sprite::SpriteBuilder,
+        transform::TransformBuilder,
+    },
+    utils::lod::{bake_impostor, decimate_surface, DecimationOptions, ImpostorSource},
+};
+use std::{path::PathBuf, rc::Rc};
+
+#[derive(Reflect, Debug)]
+pub struct LodSettings {
+    #[reflect(
+        description = "Amount of simplified levels of detail, that will be generated for \
+        every mesh of a model. The original meshes are used as the first level.",
+        min_value = 1.0
+    )]
+    levels: usize,
+    #[reflect(
+        description = "Amount of triangles of every next level relative to the previous one.",
+        min_value = 0.01,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    reduction: f32,
+    #[reflect(
+        description = "Maximum geometric error of a simplified mesh (in local units of the mesh). \
+        Simplification stops when the error is reached even if the desired amount of triangles \
+        is not reached yet.",
+        min_value = 0.0
+    )]
+    max_error: f32,
+    #[reflect(
+        description = "Whether to keep open borders of a mesh (seams, holes, etc.) in place."
+    )]
+    preserve_borders: bool,
+    #[reflect(
+        description = "Length of the distance range of every level in normalized \
+        coordinates, where 0.0 - closest to a camera, 1.0 - farthest from a camera.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.01
+    )]
+    level_distance: f32,
+    #[reflect(
+        description = "Whether to generate a billboard impostor as the farthest level of detail."
+    )]
+    generate_impostor: bool,
+    #[reflect(
+        description = "Width and height of the impostor texture in pixels.",
+        min_value = 16.0,
+        max_value = 4096.0
+    )]
+    impostor_resolution: u32,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        Self {
+            levels: 3,
+            reduction: 0.5,
+            max_error: f32::MAX,
+            preserve_borders: true,
+            level_distance: 0.05,
+            generate_impostor: true,
+            impostor_resolution: 256,
+        }
+    }
+}
+
+fn make_lod_mesh(mesh: &Mesh, options: &DecimationOptions, level: usize) -> Option<Node> {
+    let mut surfaces = Vec::new();
+    for surface in mesh.surfaces() {
+        let data = surface.data();
+        let data = data.lock();
+        match decimate_surface(&data, options) {
+            Ok(decimated) => surfaces.push(
+                SurfaceBuilder::new(SurfaceSharedData::new(decimated))
+                    .with_material(surface.material().clone())
+                    .with_bones(surface.bones().to_vec())
+                    .build(),
+            ),
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to simplify a surface of {} mesh. Reason: {:?}",
+                    mesh.name(),
+                    err
+                ));
+                return None;
+            }
+        }
+    }
+
+    Some(
+        MeshBuilder::new(
+            BaseBuilder::new()
+                .with_name(format!("{}_LOD{}", mesh.name(), level))
+                .with_local_transform(mesh.local_transform().clone()),
+        )
+        .with_surfaces(surfaces)
+        .with_render_path(mesh.render_path())
+        .build_node(),
+    )
+}
+
+/// Impostor textures are derived resources, they're stored next to the source of the model (or next
+/// to the scene, if the model is not instantiated from a resource).
+fn impostor_path(graph: &Graph, root: Handle<Node>, editor_scene: &EditorScene) -> PathBuf {
+    let source = graph[root]
+        .resource()
+        .map(|resource| resource.path())
+        .or_else(|| editor_scene.path.clone())
+        .unwrap_or_else(|| PathBuf::from("unnamed"));
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = graph[root]
+        .name()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    source.with_file_name(format!("{stem}.{name}.impostor.png"))
+}
+
+fn is_generated_lod_object(name: &str) -> bool {
+    name.ends_with("_Impostor")
+        || name
+            .rsplit_once("_LOD")
+            .is_some_and(|(_, level)| level.parse::<usize>().is_ok())
+}
+
+fn save_impostor_texture(
+    path: PathBuf,
+    pixels: Vec<u8>,
+    resolution: u32,
+    resource_manager: &ResourceManager,
+) -> Result<TextureResource, String> {
+    let mut texture = Texture::from_bytes(
+        TextureKind::Rectangle {
+            width: resolution,
+            height: resolution,
+        },
+        TexturePixelKind::RGBA8,
+        pixels,
+        false,
+    )
+    .ok_or_else(|| "Unable to create an impostor texture.".to_string())?;
+    let path = make_relative_path(&path).unwrap_or(path);
+    texture.set_path(path.clone());
+    texture.save().map_err(|e| format!("{e:?}"))?;
+
+    let texture = resource_manager.request::<Texture>(path);
+    // The impostor could be generated before, make sure that the texture has the new content.
+    texture.clone().into_untyped().reload(resource_manager);
+    Ok(texture)
+}
+
+impl LodSettings {
+    /// Generates levels of detail for the model with the given root. New nodes are added to the
+    /// graph right away, they must be taken out of it before sending the commands.
+    fn generate(
+        &self,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        resource_manager: &ResourceManager,
+        new_nodes: &mut Vec<Handle<Node>>,
+        deleted_nodes: &mut Vec<Handle<Node>>,
+    ) -> Option<LodGroup> {
+        // Objects of the previously generated levels must be replaced with the new ones.
+        let previous = graph[root]
+            .lod_group()
+            .map(|group| {
+                group
+                    .levels
+                    .iter()
+                    .skip(1)
+                    .flat_map(|level| level.objects.iter().cloned())
+                    .filter(|object| graph.is_valid_handle(*object))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let previous_sub_graphs = previous
+            .iter()
+            .flat_map(|object| graph.traverse_handle_iter(*object))
+            .collect::<Vec<_>>();
+        let meshes = graph
+            .traverse_handle_iter(root)
+            .filter(|handle| {
+                !previous_sub_graphs.contains(handle) && graph[*handle].cast::<Mesh>().is_some()
+            })
+            .collect::<Vec<_>>();
+
+        if meshes.is_empty() {
+            Log::warn(format!(
+                "{} node does not contain any meshes, levels of detail won't be generated.",
+                graph[root].name()
+            ));
+            return None;
+        }
+
+        let mut levels = vec![meshes.clone()];
+        for level in 1..=self.levels {
+            let options = DecimationOptions {
+                target_ratio: self.reduction.powi(level as i32),
+                max_error: self.max_error,
+                preserve_borders: self.preserve_borders,
+            };
+            let mut objects = Vec::with_capacity(meshes.len());
+            for &mesh in meshes.iter() {
+                let Some(lod_mesh) =
+                    make_lod_mesh(graph[mesh].cast::<Mesh>().unwrap(), &options, level)
+                else {
+                    continue;
+                };
+                let parent = graph[mesh].parent();
+                let handle = graph.add_node(lod_mesh);
+                graph.link_nodes(handle, parent);
+                new_nodes.push(handle);
+                objects.push(handle);
+            }
+            levels.push(objects);
+        }
+
+        if self.generate_impostor {
+            let root_inv_transform = graph[root]
+                .global_transform()
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity);
+            let surfaces = meshes
+                .iter()
+                .flat_map(|handle| {
+                    let mesh = graph[*handle].cast::<Mesh>().unwrap();
+                    let transform = root_inv_transform * mesh.global_transform();
+                    mesh.surfaces()
+                        .iter()
+                        .map(move |surface| (surface.data(), surface.material().clone(), transform))
+                })
+                .collect::<Vec<_>>();
+            let data = surfaces
+                .iter()
+                .map(|(data, _, _)| data.lock())
+                .collect::<Vec<_>>();
+            let sources = surfaces
+                .iter()
+                .zip(data.iter())
+                .map(|((_, material, transform), data)| ImpostorSource {
+                    data,
+                    material,
+                    transform: *transform,
+                })
+                .collect::<Vec<_>>();
+
+            match bake_impostor(&sources, self.impostor_resolution) {
+                Ok(Some(impostor)) => {
+                    match save_impostor_texture(
+                        impostor_path(graph, root, editor_scene),
+                        impostor.pixels,
+                        impostor.resolution,
+                        resource_manager,
+                    ) {
+                        Ok(texture) => {
+                            let mut material = Material::standard_sprite();
+                            Log::verify(material.set_property(
+                                &ImmutableString::new("diffuseTexture"),
+                                PropertyValue::Sampler {
+                                    value: Some(texture),
+                                    fallback: SamplerFallback::White,
+                                },
+                            ));
+
+                            let sprite = SpriteBuilder::new(
+                                BaseBuilder::new()
+                                    .with_name(format!("{}_Impostor", graph[root].name()))
+                                    .with_local_transform(
+                                        TransformBuilder::new()
+                                            .with_local_position(impostor.center)
+                                            .build(),
+                                    ),
+                            )
+                            .with_material(MaterialResource::new_ok(material))
+                            .with_size(impostor.size * 0.5)
+                            .build_node();
+
+                            let handle = graph.add_node(sprite);
+                            graph.link_nodes(handle, root);
+                            new_nodes.push(handle);
+                            levels.push(vec![handle]);
+                        }
+                        Err(err) => {
+                            Log::err(format!("Unable to save an impostor texture. Reason: {err}"))
+                        }
+                    }
+                }
+                Ok(None) => (),
+                Err(err) => Log::err(format!("Unable to bake an impostor. Reason: {err:?}")),
+            }
+        }
+
+        // Hand-made levels are kept as is, only the generated ones are replaced.
+        deleted_nodes.extend(
+            previous
+                .into_iter()
+                .filter(|object| is_generated_lod_object(graph[*object].name())),
+        );
+
+        let count = levels.len();
+        Some(LodGroup {
+            levels: levels
+                .into_iter()
+                .enumerate()
+                .map(|(i, objects)| {
+                    let begin = i as f32 * self.level_distance;
+                    let end = if i + 1 == count {
+                        1.0
+                    } else {
+                        (i + 1) as f32 * self.level_distance
+                    };
+                    LevelOfDetail::new(begin, end, objects)
+                })
+                .collect(),
+        })
+    }
+
+    fn create_and_send_command(
+        &self,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) {
+        let Selection::Graph(ref selection) = editor_scene.selection else {
+            Log::warn("Select one or more models to generate levels of detail for them.");
+            return;
+        };
+
+        let mut new_nodes = Vec::new();
+        let mut deleted_nodes = Vec::new();
+        let mut lod_groups = Vec::new();
+        for &root in selection.nodes() {
+            if let Some(lod_group) = self.generate(
+                root,
+                graph,
+                editor_scene,
+                resource_manager,
+                &mut new_nodes,
+                &mut deleted_nodes,
+            ) {
+                lod_groups.push((root, lod_group));
+            }
+        }
+
+        if lod_groups.is_empty() {
+            return;
+        }
+
+        let mut group = deleted_nodes
+            .into_iter()
+            .map(|node| SceneCommand::new(DeleteSubGraphCommand::new(node)))
+            .collect::<Vec<_>>();
+        for node in new_nodes {
+            // The nodes were added to the graph only to be able to build them in place, put them in
+            // the commands to make the generation undoable.
+            let sub_graph = graph.take_reserve_sub_graph(node);
+            group.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+        }
+        for (root, lod_group) in lod_groups.iter() {
+            group.push(SceneCommand::new(SetLodGroupCommand::new(
+                *root,
+                Some(lod_group.clone()),
+            )));
+        }
+        group.push(SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::from_list(
+                lod_groups.iter().map(|(root, _)| *root).collect(),
+            )),
+            editor_scene.selection.clone(),
+        )));
+
+        sender.do_scene_command(CommandGroup::from(group).with_custom_name("Generate LODs"));
+    }
+}
+
+pub struct LodGenerator {
+    pub window: Handle<UiNode>,
+    pub settings: LodSettings,
+    inspector: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+}
+
+impl LodGenerator {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = LodSettings::default();
+        let container = Rc::new(make_property_editors_container(sender));
+
+        let inspector;
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(350.0)
+                .with_height(300.0)
+                .with_name("LodGenerator"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("LOD Generator"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_context(InspectorContext::from_object(
+                                &settings,
+                                ctx,
+                                container,
+                                None,
+                                MSG_SYNC_FLAG,
+                                0,
+                                true,
+                                Default::default(),
+                            ))
+                            .build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    ok = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Generate")
+                                    .build(ctx);
+                                    ok
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            settings,
+            inspector,
+            ok,
+            cancel,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                self.settings.create_and_send_command(
+                    graph,
+                    editor_scene,
+                    resource_manager,
+                    sender,
+                );
+
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
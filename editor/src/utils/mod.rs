@@ -10,9 +10,12 @@ use fyrox::{
 };
 use std::{fs::File, io::Read, path::Path};
 
+pub mod array;
 pub mod doc;
+pub mod lod;
 pub mod path_fixer;
 pub mod ragdoll;
+pub mod validation;
 
 pub fn is_slice_equal_permutation<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     if a.is_empty() && !b.is_empty() {
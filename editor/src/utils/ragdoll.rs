@@ -10,7 +10,7 @@ use crate::{
 };
 use fyrox::{
     core::{
-        algebra::{UnitQuaternion, Vector3},
+        algebra::{UnitQuaternion, Vector2, Vector3},
         log::Log,
         math::Matrix4Ext,
         pool::Handle,
@@ -31,6 +31,7 @@ use fyrox::{
     scene::{
         base::BaseBuilder,
         collider::{ColliderBuilder, ColliderShape, InteractionGroups},
+        dim2,
         graph::Graph,
         joint::{BallJoint, JointBuilder, JointParams, RevoluteJoint},
         node::Node,
@@ -115,6 +116,11 @@ pub struct RagdollPreset {
     rag doll. It could be used to filter out interactions between character capsule and any part of the rag doll."
     )]
     solver_groups: InteractionGroups,
+    #[reflect(
+        description = "A flag, that defines whether the rag doll will be made of 2D rigid bodies, colliders \
+    and joints instead of 3D ones. 2D bodies rotate around Z axis only, so the bones must be in XY plane."
+    )]
+    use_2d_physics: bool,
 }
 
 impl Default for RagdollPreset {
@@ -146,6 +152,7 @@ impl Default for RagdollPreset {
             can_sleep: true,
             collision_groups: Default::default(),
             solver_groups: Default::default(),
+            use_2d_physics: false,
         }
     }
 }
@@ -164,175 +171,329 @@ struct BallJointLimits {
     z: Range<f32>,
 }
 
-fn try_make_ball_joint(
+/// Shape of a collider of a body part, it is converted to a 3D or a 2D shape.
+enum LimbShape {
+    Ball(f32),
+    /// A capsule, that goes along local Y axis from the origin.
+    Capsule {
+        length: f32,
+        radius: f32,
+    },
+    Cuboid(Vector3<f32>),
+}
+
+enum LimbJoint {
+    Joint(JointParams),
+    Joint2D(dim2::joint::JointParams),
+}
+
+fn basis_rotation(node: &Node) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_matrix_eps(
+        &node.global_transform().basis(),
+        f32::EPSILON,
+        16,
+        Default::default(),
+    )
+}
+
+// 2D bodies and joints can rotate only around Z axis.
+fn project_rotation_2d(rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_axis_angle(&Vector3::z_axis(), rotation.euler_angles().2)
+}
+
+fn make_joint(
     body1: Handle<Node>,
     body2: Handle<Node>,
     name: &str,
-    limits: Option<BallJointLimits>,
-    offset_radius: AxisOffset,
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    params: LimbJoint,
     ragdoll: Handle<Node>,
     graph: &mut Graph,
 ) -> Handle<Node> {
-    if body1.is_some() && body2.is_some() {
-        let mut joint = BallJoint::default();
-
-        if let Some(limits) = limits {
-            joint.x_limits_enabled = true;
-            joint.y_limits_enabled = true;
-            joint.z_limits_enabled = true;
-
-            joint.x_limits_angles = limits.x;
-            joint.y_limits_angles = limits.y;
-            joint.z_limits_angles = limits.z;
-        }
-
-        let body1_ref = &graph[body1];
-
-        let offset = match offset_radius {
-            AxisOffset::None => Default::default(),
-            AxisOffset::X(offset) => body1_ref
-                .side_vector()
-                .try_normalize(f32::EPSILON)
-                .unwrap_or_default()
-                .scale(offset),
-            AxisOffset::Y(offset) => body1_ref
-                .up_vector()
-                .try_normalize(f32::EPSILON)
-                .unwrap_or_default()
-                .scale(offset),
-            AxisOffset::Z(offset) => body1_ref
-                .look_vector()
-                .try_normalize(f32::EPSILON)
-                .unwrap_or_default()
-                .scale(offset),
-        };
-
-        let ball_joint = JointBuilder::new(
-            BaseBuilder::new().with_name(name).with_local_transform(
+    let base_builder = BaseBuilder::new().with_name(name);
+    let joint = match params {
+        LimbJoint::Joint(params) => JointBuilder::new(
+            base_builder.with_local_transform(
                 TransformBuilder::new()
-                    .with_local_position(body1_ref.global_position() - offset)
-                    .with_local_rotation(UnitQuaternion::from_matrix_eps(
-                        &graph[body1].global_transform().basis(),
-                        f32::EPSILON,
-                        16,
-                        Default::default(),
-                    ))
+                    .with_local_position(position)
+                    .with_local_rotation(rotation)
                     .build(),
             ),
         )
-        .with_params(JointParams::BallJoint(joint))
+        .with_params(params)
         .with_body1(body1)
         .with_body2(body2)
         .with_auto_rebinding_enabled(false)
         .with_contacts_enabled(false)
-        .build(graph);
-
-        graph.link_nodes(ball_joint, ragdoll);
-
-        ball_joint
-    } else {
-        Default::default()
-    }
-}
-
-fn try_make_hinge_joint(
-    body1: Handle<Node>,
-    body2: Handle<Node>,
-    name: &str,
-    limits: Option<Range<f32>>,
-    ragdoll: Handle<Node>,
-    graph: &mut Graph,
-) -> Handle<Node> {
-    if body1.is_some() && body2.is_some() {
-        let mut joint = RevoluteJoint::default();
-
-        if let Some(limits) = limits {
-            joint.limits_enabled = true;
-            joint.limits = limits;
-        }
-
-        let hinge_joint = JointBuilder::new(
-            BaseBuilder::new().with_name(name).with_local_transform(
+        .build(graph),
+        LimbJoint::Joint2D(params) => dim2::joint::JointBuilder::new(
+            base_builder.with_local_transform(
                 TransformBuilder::new()
-                    .with_local_position(graph[body1].global_position())
-                    .with_local_rotation(UnitQuaternion::from_matrix_eps(
-                        &graph[body1].global_transform().basis(),
-                        f32::EPSILON,
-                        16,
-                        Default::default(),
-                    ))
+                    .with_local_position(position)
+                    .with_local_rotation(project_rotation_2d(rotation))
                     .build(),
             ),
         )
-        .with_params(JointParams::RevoluteJoint(joint))
+        .with_params(params)
         .with_body1(body1)
         .with_body2(body2)
         .with_auto_rebinding_enabled(false)
         .with_contacts_enabled(false)
-        .build(graph);
+        .build(graph),
+    };
 
-        graph.link_nodes(hinge_joint, ragdoll);
+    graph.link_nodes(joint, ragdoll);
 
-        hinge_joint
-    } else {
-        Default::default()
-    }
+    joint
 }
 
 impl RagdollPreset {
-    fn make_sphere(
+    fn try_make_ball_joint(
         &self,
-        from: Handle<Node>,
-        radius: f32,
-        mass: f32,
+        body1: Handle<Node>,
+        body2: Handle<Node>,
         name: &str,
+        limits: Option<BallJointLimits>,
+        offset_radius: AxisOffset,
         ragdoll: Handle<Node>,
-        apply_offset: bool,
         graph: &mut Graph,
     ) -> Handle<Node> {
-        if let Some(from_ref) = graph.try_get(from) {
-            let offset = if apply_offset {
-                from_ref
+        if body1.is_some() && body2.is_some() {
+            let body1_ref = &graph[body1];
+
+            let offset = match offset_radius {
+                AxisOffset::None => Default::default(),
+                AxisOffset::X(offset) => body1_ref
+                    .side_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default()
+                    .scale(offset),
+                AxisOffset::Y(offset) => body1_ref
                     .up_vector()
                     .try_normalize(f32::EPSILON)
                     .unwrap_or_default()
-                    .scale(radius)
+                    .scale(offset),
+                AxisOffset::Z(offset) => body1_ref
+                    .look_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default()
+                    .scale(offset),
+            };
+
+            let position = body1_ref.global_position() - offset;
+            let rotation = basis_rotation(body1_ref);
+
+            let params = if self.use_2d_physics {
+                // 2D ball joint rotates around Z axis only.
+                let mut joint = dim2::joint::BallJoint::default();
+
+                if let Some(limits) = limits {
+                    joint.limits_enabled = true;
+                    joint.limits_angles = limits.z;
+                }
+
+                LimbJoint::Joint2D(dim2::joint::JointParams::BallJoint(joint))
             } else {
-                Default::default()
+                let mut joint = BallJoint::default();
+
+                if let Some(limits) = limits {
+                    joint.x_limits_enabled = true;
+                    joint.y_limits_enabled = true;
+                    joint.z_limits_enabled = true;
+
+                    joint.x_limits_angles = limits.x;
+                    joint.y_limits_angles = limits.y;
+                    joint.z_limits_angles = limits.z;
+                }
+
+                LimbJoint::Joint(JointParams::BallJoint(joint))
+            };
+
+            make_joint(
+                body1, body2, name, position, rotation, params, ragdoll, graph,
+            )
+        } else {
+            Default::default()
+        }
+    }
+
+    fn try_make_hinge_joint(
+        &self,
+        body1: Handle<Node>,
+        body2: Handle<Node>,
+        name: &str,
+        limits: Option<Range<f32>>,
+        ragdoll: Handle<Node>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        if body1.is_some() && body2.is_some() {
+            let position = graph[body1].global_position();
+            let rotation = basis_rotation(&graph[body1]);
+
+            let params = if self.use_2d_physics {
+                // Every 2D ball joint is a hinge around Z axis.
+                let mut joint = dim2::joint::BallJoint::default();
+
+                if let Some(limits) = limits {
+                    joint.limits_enabled = true;
+                    joint.limits_angles = limits;
+                }
+
+                LimbJoint::Joint2D(dim2::joint::JointParams::BallJoint(joint))
+            } else {
+                let mut joint = RevoluteJoint::default();
+
+                if let Some(limits) = limits {
+                    joint.limits_enabled = true;
+                    joint.limits = limits;
+                }
+
+                LimbJoint::Joint(JointParams::RevoluteJoint(joint))
+            };
+
+            make_joint(
+                body1, body2, name, position, rotation, params, ragdoll, graph,
+            )
+        } else {
+            Default::default()
+        }
+    }
+
+    fn make_body(
+        &self,
+        name: &str,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        shape: LimbShape,
+        mass: f32,
+        ragdoll: Handle<Node>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        let body = if self.use_2d_physics {
+            let (collider_name, shape) = match shape {
+                LimbShape::Ball(radius) => (
+                    "SphereCollider",
+                    dim2::collider::ColliderShape::ball(radius),
+                ),
+                LimbShape::Capsule { length, radius } => (
+                    "CapsuleCollider",
+                    dim2::collider::ColliderShape::capsule(
+                        Vector2::default(),
+                        Vector2::new(0.0, length),
+                        radius,
+                    ),
+                ),
+                LimbShape::Cuboid(half_size) => (
+                    "CuboidCollider",
+                    dim2::collider::ColliderShape::cuboid(half_size.x, half_size.y),
+                ),
             };
 
-            let sphere = RigidBodyBuilder::new(
+            let collider =
+                dim2::collider::ColliderBuilder::new(BaseBuilder::new().with_name(collider_name))
+                    .with_collision_groups(self.collision_groups)
+                    .with_solver_groups(self.solver_groups)
+                    .with_friction(self.friction)
+                    .with_shape(shape)
+                    .build(graph);
+
+            dim2::rigidbody::RigidBodyBuilder::new(
                 BaseBuilder::new()
                     .with_name(name)
                     .with_local_transform(
                         TransformBuilder::new()
-                            .with_local_position(from_ref.global_position() + offset)
-                            .with_local_rotation(UnitQuaternion::from_matrix_eps(
-                                &from_ref.global_transform().basis(),
-                                f32::EPSILON,
-                                16,
-                                Default::default(),
-                            ))
+                            .with_local_position(position)
+                            .with_local_rotation(project_rotation_2d(rotation))
                             .build(),
                     )
-                    .with_children(&[ColliderBuilder::new(
-                        BaseBuilder::new().with_name("SphereCollider"),
+                    .with_children(&[collider]),
+            )
+            .with_mass(mass)
+            .with_can_sleep(self.can_sleep)
+            .with_ccd_enabled(self.use_ccd)
+            .with_body_type(RigidBodyType::KinematicPositionBased)
+            .build(graph)
+        } else {
+            let (collider_name, shape) = match shape {
+                LimbShape::Ball(radius) => ("SphereCollider", ColliderShape::ball(radius)),
+                LimbShape::Capsule { length, radius } => (
+                    "CapsuleCollider",
+                    ColliderShape::capsule(
+                        Vector3::default(),
+                        Vector3::new(0.0, length, 0.0),
+                        radius,
+                    ),
+                ),
+                LimbShape::Cuboid(half_size) => (
+                    "CuboidCollider",
+                    ColliderShape::cuboid(half_size.x, half_size.y, half_size.z),
+                ),
+            };
+
+            let collider = ColliderBuilder::new(BaseBuilder::new().with_name(collider_name))
+                .with_collision_groups(self.collision_groups)
+                .with_solver_groups(self.solver_groups)
+                .with_friction(self.friction)
+                .with_shape(shape)
+                .build(graph);
+
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_name(name)
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .with_local_rotation(rotation)
+                            .build(),
                     )
-                    .with_collision_groups(self.collision_groups)
-                    .with_solver_groups(self.solver_groups)
-                    .with_friction(self.friction)
-                    .with_shape(ColliderShape::ball(radius))
-                    .build(graph)]),
+                    .with_children(&[collider]),
             )
             .with_mass(mass)
             .with_can_sleep(self.can_sleep)
             .with_ccd_enabled(self.use_ccd)
             .with_body_type(RigidBodyType::KinematicPositionBased)
-            .build(graph);
+            .build(graph)
+        };
+
+        graph.link_nodes(body, ragdoll);
 
-            graph.link_nodes(sphere, ragdoll);
+        body
+    }
+
+    fn make_sphere(
+        &self,
+        from: Handle<Node>,
+        radius: f32,
+        mass: f32,
+        name: &str,
+        ragdoll: Handle<Node>,
+        apply_offset: bool,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        if let Some(from_ref) = graph.try_get(from) {
+            let offset = if apply_offset {
+                from_ref
+                    .up_vector()
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default()
+                    .scale(radius)
+            } else {
+                Default::default()
+            };
 
-            sphere
+            let position = from_ref.global_position() + offset;
+            let rotation = basis_rotation(from_ref);
+
+            self.make_body(
+                name,
+                position,
+                rotation,
+                LimbShape::Ball(radius),
+                mass,
+                ragdoll,
+                graph,
+            )
         } else {
             Default::default()
         }
@@ -351,43 +512,20 @@ impl RagdollPreset {
         if let (Some(from_ref), Some(to_ref)) = (graph.try_get(from), graph.try_get(to)) {
             let pos_from = from_ref.global_position();
             let pos_to = to_ref.global_position();
-
-            let capsule = RigidBodyBuilder::new(
-                BaseBuilder::new()
-                    .with_name(name)
-                    .with_local_transform(
-                        TransformBuilder::new()
-                            .with_local_position(pos_from)
-                            .with_local_rotation(UnitQuaternion::from_matrix_eps(
-                                &from_ref.global_transform().basis(),
-                                f32::EPSILON,
-                                16,
-                                Default::default(),
-                            ))
-                            .build(),
-                    )
-                    .with_children(&[ColliderBuilder::new(
-                        BaseBuilder::new().with_name("CapsuleCollider"),
-                    )
-                    .with_shape(ColliderShape::capsule(
-                        Vector3::default(),
-                        Vector3::new(0.0, (pos_to - pos_from).norm() - 2.0 * radius, 0.0),
-                        radius,
-                    ))
-                    .with_collision_groups(self.collision_groups)
-                    .with_solver_groups(self.solver_groups)
-                    .with_friction(self.friction)
-                    .build(graph)]),
+            let rotation = basis_rotation(from_ref);
+
+            self.make_body(
+                name,
+                pos_from,
+                rotation,
+                LimbShape::Capsule {
+                    length: (pos_to - pos_from).norm() - 2.0 * radius,
+                    radius,
+                },
+                mass,
+                ragdoll,
+                graph,
             )
-            .with_mass(mass)
-            .with_can_sleep(self.can_sleep)
-            .with_ccd_enabled(self.use_ccd)
-            .with_body_type(RigidBodyType::KinematicPositionBased)
-            .build(graph);
-
-            graph.link_nodes(capsule, ragdoll);
-
-            capsule
         } else {
             Default::default()
         }
@@ -403,32 +541,17 @@ impl RagdollPreset {
         graph: &mut Graph,
     ) -> Handle<Node> {
         if let Some(from_ref) = graph.try_get(from) {
-            let cuboid = RigidBodyBuilder::new(
-                BaseBuilder::new()
-                    .with_name(name)
-                    .with_local_transform(
-                        TransformBuilder::new()
-                            .with_local_position(from_ref.global_position())
-                            .build(),
-                    )
-                    .with_children(&[ColliderBuilder::new(
-                        BaseBuilder::new().with_name("CuboidCollider"),
-                    )
-                    .with_collision_groups(self.collision_groups)
-                    .with_solver_groups(self.solver_groups)
-                    .with_shape(ColliderShape::cuboid(half_size.x, half_size.y, half_size.z))
-                    .with_friction(self.friction)
-                    .build(graph)]),
+            let position = from_ref.global_position();
+
+            self.make_body(
+                name,
+                position,
+                UnitQuaternion::identity(),
+                LimbShape::Cuboid(half_size),
+                mass,
+                ragdoll,
+                graph,
             )
-            .with_mass(mass)
-            .with_can_sleep(self.can_sleep)
-            .with_ccd_enabled(self.use_ccd)
-            .with_body_type(RigidBodyType::KinematicPositionBased)
-            .build(graph);
-
-            graph.link_nodes(cuboid, ragdoll);
-
-            cuboid
         } else {
             Default::default()
         }
@@ -451,12 +574,8 @@ impl RagdollPreset {
         base_size
     }
 
-    pub fn create_and_send_command(
-        &self,
-        graph: &mut Graph,
-        editor_scene: &EditorScene,
-        sender: &MessageSender,
-    ) {
+    /// Creates a rag doll with its bodies and joints in the given graph and returns its handle.
+    fn create(&self, graph: &mut Graph) -> Handle<Node> {
         let base_size = self.measure_base_size(graph);
         let hand_radius = 0.3 * base_size;
         let head_radius = 0.5 * base_size;
@@ -477,8 +596,6 @@ impl RagdollPreset {
             .with_active(true)
             .build(graph);
 
-        graph.link_nodes(ragdoll, editor_scene.scene_content_root);
-
         let left_up_leg = self.make_oriented_capsule(
             self.left_up_leg,
             self.left_leg,
@@ -681,7 +798,7 @@ impl RagdollPreset {
         graph.update_hierarchical_data();
 
         // Left leg.
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             left_up_leg,
             hips,
             "RagdollLeftUpLegHipsBallJoint",
@@ -694,7 +811,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             left_leg,
             left_up_leg,
             "RagdollLeftLegLeftUpLegHingeJoint",
@@ -702,7 +819,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             left_foot,
             left_leg,
             "RagdollLeftFootLeftLegBallJoint",
@@ -717,7 +834,7 @@ impl RagdollPreset {
         );
 
         // Right leg.
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             right_up_leg,
             hips,
             "RagdollLeftUpLegHipsBallJoint",
@@ -730,7 +847,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             right_leg,
             right_up_leg,
             "RagdollRightLegRightUpLegHingeJoint",
@@ -738,7 +855,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             right_foot,
             right_leg,
             "RagdollRightFootRightLegBallJoint",
@@ -752,7 +869,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             spine,
             hips,
             "RagdollSpineHipsHingeJoint",
@@ -761,7 +878,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             spine1,
             spine,
             "RagdollSpine1SpineHingeJoint",
@@ -770,7 +887,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             spine2,
             spine1,
             "RagdollSpine2Spine1HingeJoint",
@@ -779,7 +896,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             left_shoulder,
             spine2,
             "RagdollSpine2LeftShoulderBallJoint",
@@ -787,7 +904,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             left_arm,
             left_shoulder,
             "RagdollLeftShoulderLeftArmBallJoint",
@@ -796,7 +913,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             left_fore_arm,
             left_arm,
             "RagdollLeftArmLeftForeArmBallJoint",
@@ -804,7 +921,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             left_hand,
             left_fore_arm,
             "RagdollLeftForeArmLeftHandBallJoint",
@@ -818,7 +935,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             right_shoulder,
             spine2,
             "RagdollSpine2RightShoulderBallJoint",
@@ -826,7 +943,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             right_arm,
             right_shoulder,
             "RagdollRightShoulderRightArmBallJoint",
@@ -835,7 +952,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_hinge_joint(
+        self.try_make_hinge_joint(
             right_fore_arm,
             right_arm,
             "RagdollRightArmRightForeArmHingeJoint",
@@ -843,7 +960,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             right_hand,
             right_fore_arm,
             "RagdollRightForeArmRightHandBallJoint",
@@ -857,7 +974,7 @@ impl RagdollPreset {
             graph,
         );
 
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             neck,
             spine2,
             "RagdollNeckSpine2BallJoint",
@@ -866,7 +983,7 @@ impl RagdollPreset {
             ragdoll,
             graph,
         );
-        try_make_ball_joint(
+        self.try_make_ball_joint(
             head,
             neck,
             "RagdollHeadNeckBallJoint",
@@ -966,6 +1083,18 @@ impl RagdollPreset {
             ],
         });
 
+        ragdoll
+    }
+
+    pub fn create_and_send_command(
+        &self,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        let ragdoll = self.create(graph);
+        graph.link_nodes(ragdoll, editor_scene.scene_content_root);
+
         // Immediately after extract if from the scene to subgraph. This is required to not violate
         // the rule of one place of execution, only commands allowed to modify the scene.
         let sub_graph = graph.take_reserve_sub_graph(ragdoll);
@@ -1192,3 +1321,64 @@ impl RagdollWizard {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::scene::{joint::Joint, pivot::PivotBuilder, ragdoll::Ragdoll, rigidbody::RigidBody};
+
+    fn make_bone(position: Vector3<f32>, graph: &mut Graph) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(graph)
+    }
+
+    // Returns the amount of 3D and 2D bodies and joints of a rag doll made of a hips, a spine and a leg.
+    fn count_parts(use_2d_physics: bool) -> [usize; 4] {
+        let mut graph = Graph::new();
+        let preset = RagdollPreset {
+            hips: make_bone(Vector3::new(0.0, 1.0, 0.0), &mut graph),
+            spine: make_bone(Vector3::new(0.0, 1.3, 0.0), &mut graph),
+            left_up_leg: make_bone(Vector3::new(-0.2, 0.9, 0.0), &mut graph),
+            left_leg: make_bone(Vector3::new(-0.2, 0.5, 0.0), &mut graph),
+            left_foot: make_bone(Vector3::new(-0.2, 0.1, 0.0), &mut graph),
+            use_2d_physics,
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+
+        let ragdoll = preset.create(&mut graph);
+
+        let mut parts = [0; 4];
+        for &child in graph[ragdoll].children() {
+            let node = &graph[child];
+            parts[0] += node.cast::<RigidBody>().is_some() as usize;
+            parts[1] += node.cast::<Joint>().is_some() as usize;
+            parts[2] += node.cast::<dim2::rigidbody::RigidBody>().is_some() as usize;
+            parts[3] += node.cast::<dim2::joint::Joint>().is_some() as usize;
+        }
+
+        let root_limb = graph[ragdoll].cast::<Ragdoll>().unwrap().root_limb();
+        assert_eq!(root_limb.bone, preset.hips);
+        assert_eq!(
+            graph[root_limb.physical_bone]
+                .cast::<dim2::rigidbody::RigidBody>()
+                .is_some(),
+            use_2d_physics
+        );
+
+        parts
+    }
+
+    #[test]
+    fn ragdoll_preset_creates_bodies_of_selected_dimension() {
+        // Five bodies (hips, spine and three leg parts) and four joints between them.
+        assert_eq!(count_parts(false), [5, 4, 0, 0]);
+        assert_eq!(count_parts(true), [0, 0, 5, 4]);
+    }
+}
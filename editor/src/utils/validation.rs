@@ -0,0 +1,351 @@
+//! Project validation window. It checks every resource of the project using
+//! [`ResourceManager::validate_project`] and lists found problems, so broken assets could be
+//! found before a build is shipped.
+
+use crate::{message::MessageSender, Message};
+use fyrox::{
+    asset::{manager::ResourceManager, validation::ProjectValidationReport},
+    core::{futures::executor::block_on, pool::Handle},
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        decorator::DecoratorBuilder,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+};
+
+/// A problem of the report and a path of the resource, that should be fixed.
+fn list_problems(report: &ProjectValidationReport) -> Vec<(PathBuf, String)> {
+    let mut problems = Vec::new();
+    for failed in report.failed.iter() {
+        problems.push((
+            failed.path.clone(),
+            format!(
+                "{} failed to load: {}",
+                failed.path.display(),
+                failed.reason
+            ),
+        ));
+    }
+    // A missing resource cannot be shown, the resources that reference it must be fixed.
+    for missing in report.missing.iter() {
+        for referenced_by in missing.referenced_by.iter() {
+            problems.push((
+                referenced_by.clone(),
+                format!(
+                    "{} references missing {}",
+                    referenced_by.display(),
+                    missing.path.display()
+                ),
+            ));
+        }
+    }
+    for mismatch in report.type_mismatches.iter() {
+        problems.push((
+            mismatch.path.clone(),
+            format!(
+                "Type mismatch of {}: expected {:?}, actual {:?}",
+                mismatch.path.display(),
+                mismatch.expected,
+                mismatch.actual
+            ),
+        ));
+    }
+    for cycle in report.cycles.iter() {
+        let Some(first) = cycle.first() else {
+            continue;
+        };
+        let cycle_text = cycle
+            .iter()
+            .chain(Some(first))
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        problems.push((first.clone(), format!("Cyclic dependency: {cycle_text}")));
+    }
+    problems
+}
+
+pub struct ProjectValidator {
+    pub window: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    list: Handle<UiNode>,
+    validate: Handle<UiNode>,
+    close: Handle<UiNode>,
+    /// Paths of the resources of the list items.
+    paths: Vec<PathBuf>,
+    receiver: Option<Receiver<ProjectValidationReport>>,
+}
+
+fn make_problem_item(text: String, ctx: &mut BuildContext) -> Handle<UiNode> {
+    DecoratorBuilder::new(BorderBuilder::new(
+        WidgetBuilder::new().with_child(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                .with_text(text)
+                .with_wrap(WrapMode::Word)
+                .build(ctx),
+        ),
+    ))
+    .build(ctx)
+}
+
+impl ProjectValidator {
+    /// Root folder of the checked resources.
+    const ROOT: &'static str = "./";
+
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let summary;
+        let list;
+        let validate;
+        let close;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(500.0)
+                .with_height(400.0)
+                .with_name("ProjectValidator"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Project Validation"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child({
+                        summary = TextBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_margin(Thickness::uniform(2.0)),
+                        )
+                        .with_wrap(WrapMode::Word)
+                        .build(ctx);
+                        summary
+                    })
+                    .with_child({
+                        list = ListViewBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .build(ctx);
+                        list
+                    })
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(2.0))
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(2)
+                                .with_child({
+                                    validate = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Validate")
+                                    .build(ctx);
+                                    validate
+                                })
+                                .with_child({
+                                    close = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Close")
+                                    .build(ctx);
+                                    close
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::auto())
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            summary,
+            list,
+            validate,
+            close,
+            paths: Default::default(),
+            receiver: None,
+        }
+    }
+
+    fn start(&mut self, resource_manager: &ResourceManager, ui: &UserInterface) {
+        if self.receiver.is_some() {
+            return;
+        }
+
+        // Validation loads every resource of the project, it must not block the editor.
+        let (sender, receiver) = mpsc::channel();
+        let resource_manager = resource_manager.clone();
+        std::thread::spawn(move || {
+            let report = block_on(resource_manager.validate_project(Path::new(Self::ROOT)));
+            let _ = sender.send(report);
+        });
+        self.receiver = Some(receiver);
+
+        self.paths.clear();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            "Validating the project...".to_string(),
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.validate,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    /// Polls the validation, must be called every frame.
+    pub fn update(&mut self, ui: &mut UserInterface) {
+        let Some(report) = self
+            .receiver
+            .as_ref()
+            .and_then(|receiver| receiver.try_recv().ok())
+        else {
+            return;
+        };
+        self.receiver = None;
+
+        let problems = list_problems(&report);
+        let summary = if problems.is_empty() {
+            format!(
+                "{} resource(s) checked, no problems found.",
+                report.checked.len()
+            )
+        } else {
+            format!(
+                "{} resource(s) checked, {} problem(s) found. Select a problem to show the \
+                resource in the asset browser.",
+                report.checked.len(),
+                problems.len()
+            )
+        };
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            summary,
+        ));
+
+        let ctx = &mut ui.build_ctx();
+        let items = problems
+            .iter()
+            .map(|(_, text)| make_problem_item(text.clone(), ctx))
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.validate,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        self.paths = problems.into_iter().map(|(path, _)| path).collect();
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.validate {
+                self.start(resource_manager, ui);
+            }
+        } else if let Some(WindowMessage::Open { .. }) = message.data() {
+            // Every time the window is opened, the project is validated again.
+            if message.destination() == self.window {
+                self.start(resource_manager, ui);
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(path) = self.paths.get(*index) {
+                    sender.send(Message::ShowInAssetBrowser(path.clone()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::asset::validation::{FailedResource, MissingResource};
+
+    #[test]
+    fn project_validator_problems() {
+        let report = ProjectValidationReport {
+            checked: vec!["a.rgs".into(), "b.rgs".into(), "c.mat".into()],
+            failed: vec![FailedResource {
+                path: "c.mat".into(),
+                reason: "Corrupted".to_string(),
+            }],
+            missing: vec![MissingResource {
+                path: "d.png".into(),
+                referenced_by: vec!["a.rgs".into(), "b.rgs".into()],
+            }],
+            type_mismatches: vec![],
+            cycles: vec![vec!["a.rgs".into(), "b.rgs".into()]],
+        };
+
+        assert_eq!(
+            list_problems(&report),
+            [
+                (
+                    PathBuf::from("c.mat"),
+                    "c.mat failed to load: Corrupted".into()
+                ),
+                (
+                    PathBuf::from("a.rgs"),
+                    "a.rgs references missing d.png".into()
+                ),
+                (
+                    PathBuf::from("b.rgs"),
+                    "b.rgs references missing d.png".into()
+                ),
+                (
+                    PathBuf::from("a.rgs"),
+                    "Cyclic dependency: a.rgs -> b.rgs -> a.rgs".into()
+                ),
+            ]
+        );
+    }
+}
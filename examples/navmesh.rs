@@ -255,6 +255,8 @@ fn main() {
         GraphicsContextParams {
             window_attributes: Default::default(),
             vsync: true,
+            texture_upload_budget: Default::default(),
+            shader_upload_budget: None,
         },
     );
     executor.add_plugin_constructor(GameConstructor);
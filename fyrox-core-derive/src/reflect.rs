@@ -20,6 +20,8 @@ pub fn impl_reflect(ty_args: &args::TypeArgs) -> TokenStream2 {
             quote!(None),
             quote!(func(&[])),
             quote!(func(&mut [])),
+            quote!(),
+            quote!(),
             None,
             quote!(func(&[])),
         );
@@ -180,6 +182,18 @@ fn impl_reflect_struct(ty_args: &args::TypeArgs, field_args: &args::Fields) -> T
         ])
     };
 
+    let contents_body = quote! {
+        #(
+            func(#fields as &dyn Reflect);
+        )*
+    };
+
+    let contents_mut_body = quote! {
+        #(
+            func(#field_muts as &mut dyn Reflect);
+        )*
+    };
+
     let set_field_body = self::struct_set_field_body(ty_args);
     self::gen_impl(
         ty_args,
@@ -187,6 +201,8 @@ fn impl_reflect_struct(ty_args: &args::TypeArgs, field_args: &args::Fields) -> T
         field_mut_body,
         fields_body,
         fields_mut_body,
+        contents_body,
+        contents_mut_body,
         set_field_body,
         quote! {
             func(&[#metadata])
@@ -242,6 +258,8 @@ fn struct_set_field_body(ty_args: &args::TypeArgs) -> Option<TokenStream2> {
 fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs]) -> TokenStream2 {
     let mut fields_list = Vec::new();
     let mut fields_list_mut = Vec::new();
+    let mut contents_list = Vec::new();
+    let mut contents_list_mut = Vec::new();
     let mut fields_info = Vec::new();
     let (fields, field_muts): (Vec<_>, Vec<_>) = variant_args
         .iter()
@@ -289,6 +307,22 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
                 )*
             };
 
+            contents_list.push(quote! {
+                #matcher => {
+                    #(
+                        func(#fields as &dyn Reflect);
+                    )*
+                }
+            });
+
+            contents_list_mut.push(quote! {
+                #matcher => {
+                    #(
+                        func(#field_muts as &mut dyn Reflect);
+                    )*
+                }
+            });
+
             let fields = quote! {
                 #(
                     #prop_values => match self {
@@ -330,6 +364,8 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
             quote!(None),
             quote!(func(&[])),
             quote!(func(&mut [])),
+            quote!(),
+            quote!(),
             None,
             quote!(func(&[])),
         )
@@ -370,6 +406,24 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
             }
         };
 
+        let contents_body = quote! {
+            match self {
+                #(
+                    #contents_list
+                )*
+                _ => {}
+            }
+        };
+
+        let contents_mut_body = quote! {
+            match self {
+                #(
+                    #contents_list_mut
+                )*
+                _ => {}
+            }
+        };
+
         let fields_metadata_body = quote! {
             match self {
                 #(
@@ -385,18 +439,23 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
             field_mut_body,
             fields_body,
             fields_mut_body,
+            contents_body,
+            contents_mut_body,
             None,
             fields_metadata_body,
         )
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn gen_impl(
     ty_args: &args::TypeArgs,
     field: TokenStream2,
     field_mut: TokenStream2,
     fields: TokenStream2,
     fields_mut: TokenStream2,
+    contents: TokenStream2,
+    contents_mut: TokenStream2,
     set_field: Option<TokenStream2>,
     metadata: TokenStream2,
 ) -> TokenStream2 {
@@ -407,6 +466,24 @@ fn gen_impl(
     let as_list_impl = ty_args.as_list_impl();
     let as_array_impl = ty_args.as_array_impl();
 
+    // Contents of arrays are accessible through the manual `ReflectArray` impl only.
+    let contents_impl = (!ty_args.impl_as_array).then(|| {
+        quote! {
+            fn contents<'__a>(&'__a self, func: &mut dyn FnMut(&'__a dyn Reflect)) -> bool {
+                #contents
+                true
+            }
+
+            fn contents_mut<'__a>(
+                &'__a mut self,
+                func: &mut dyn FnMut(&'__a mut dyn Reflect),
+            ) -> bool {
+                #contents_mut
+                true
+            }
+        }
+    });
+
     let doc = args::fetch_doc_comment(&ty_args.attrs);
 
     let set_field = set_field.map(|set_field| {
@@ -484,6 +561,8 @@ fn gen_impl(
             #as_array_impl
 
             #as_list_impl
+
+            #contents_impl
         }
     }
 }
@@ -308,13 +308,82 @@ impl Curve {
 
         Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
     }
+
+    /// Samples the curve uniformly from its first key to its last key and creates a lookup table,
+    /// that could be sampled in constant time. See [`BakedCurve`] docs for more info.
+    #[inline]
+    pub fn bake(&self, resolution: usize) -> BakedCurve {
+        BakedCurve::new(self, resolution)
+    }
+}
+
+/// A lookup table of curve values, sampled uniformly between the first and the last keys of a
+/// curve. Unlike [`Curve::value_at`], which uses binary search, sampling of the table takes constant
+/// time, which makes it useful in hot paths, such as particle systems or sound envelopes. Values
+/// between samples are interpolated linearly, so the table is an approximation of the curve and
+/// its precision depends on the amount of samples.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BakedCurve {
+    start: f32,
+    scale: f32,
+    values: Vec<f32>,
+}
+
+impl BakedCurve {
+    /// Creates a new lookup table with the given amount of samples (at least 2). Curves with one
+    /// key (or with all keys at the same location) are baked to a single sample.
+    pub fn new(curve: &Curve, resolution: usize) -> Self {
+        let (Some(first), Some(last)) = (curve.keys.first(), curve.keys.last()) else {
+            return Self::default();
+        };
+
+        let length = last.location - first.location;
+        if length <= 0.0 {
+            return Self {
+                start: first.location,
+                scale: 0.0,
+                values: vec![first.value],
+            };
+        }
+
+        let resolution = resolution.max(2);
+        let step = length / (resolution - 1) as f32;
+        Self {
+            start: first.location,
+            scale: 1.0 / step,
+            values: (0..resolution)
+                .map(|i| curve.value_at(first.location + i as f32 * step))
+                .collect(),
+        }
+    }
+
+    /// Returns an interpolated value at the given location. Locations outside of the baked range
+    /// are clamped.
+    #[inline]
+    pub fn value_at(&self, location: f32) -> f32 {
+        match self.values.len() {
+            0 => 0.0,
+            1 => self.values[0],
+            count => {
+                let t = ((location - self.start) * self.scale).clamp(0.0, (count - 1) as f32);
+                let index = (t as usize).min(count - 2);
+                lerpf(self.values[index], self.values[index + 1], t - index as f32)
+            }
+        }
+    }
+
+    /// Returns the baked samples.
+    #[inline]
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
 }
 
 #[cfg(test)]
 mod test {
     use uuid::Uuid;
 
-    use crate::curve::{Curve, CurveKey, CurveKeyKind};
+    use crate::curve::{BakedCurve, Curve, CurveKey, CurveKeyKind};
 
     #[test]
     fn test_curve_key_insertion_order() {
@@ -486,4 +555,24 @@ mod test {
         assert_eq!(curve.name(), "");
         assert_eq!(curve.keys(), vec![key, key2, key4, key3,]);
     }
+
+    #[test]
+    fn test_baked_curve() {
+        assert_eq!(Curve::default().bake(16).value_at(1.0), 0.0);
+
+        let mut curve = Curve::default();
+        curve.add_key(CurveKey::new(1.0, 2.0, CurveKeyKind::Linear));
+        let baked = curve.bake(16);
+        assert_eq!(baked.values(), [2.0]);
+        assert_eq!(baked.value_at(-10.0), 2.0);
+
+        curve.add_key(CurveKey::new(3.0, 6.0, CurveKeyKind::Linear));
+        let baked = BakedCurve::new(&curve, 5);
+        assert_eq!(baked.values(), [2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(baked.value_at(0.0), 2.0);
+        assert_eq!(baked.value_at(1.25), 2.5);
+        assert_eq!(baked.value_at(2.0), 4.0);
+        assert_eq!(baked.value_at(3.0), 6.0);
+        assert_eq!(baked.value_at(4.0), 6.0);
+    }
 }
@@ -1,4 +1,7 @@
-use std::{io::Error, path::Path};
+use std::{
+    io::{Error, ErrorKind},
+    path::Path,
+};
 
 #[derive(Debug)]
 pub enum FileLoadError {
@@ -6,6 +9,28 @@ pub enum FileLoadError {
     Custom(String),
 }
 
+impl FileLoadError {
+    /// Returns `true` if the error is most likely temporary and the same operation could succeed
+    /// if repeated later (for example, a connection was reset or an operation has timed out). Such
+    /// errors are common for network file systems and other remote storages.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FileLoadError::Io(err) => matches!(
+                err.kind(),
+                ErrorKind::Interrupted
+                    | ErrorKind::TimedOut
+                    | ErrorKind::WouldBlock
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::NotConnected
+                    | ErrorKind::BrokenPipe
+            ),
+            FileLoadError::Custom(_) => false,
+        }
+    }
+}
+
 impl From<std::io::Error> for FileLoadError {
     fn from(e: Error) -> Self {
         Self::Io(e)
@@ -19,6 +19,11 @@
 //! load portions of data into its cache piece by piece, it will be free from any
 //! indirections that might cause cache invalidation. This is the so called cache
 //! friendliness.
+//!
+//! Dangling handles (handles to objects, that were already freed) could be hard to track down, because
+//! a panic on borrowing tells nothing about the code that freed the object. [`Pool::set_handle_diagnostics_enabled`]
+//! turns on a debug mode, in which the pool remembers where the objects were freed and reports it, when a
+//! dangling handle causes a panic.
 
 #![allow(clippy::unneeded_field_pattern)]
 
@@ -28,15 +33,19 @@ use crate::{
     visitor::{Visit, VisitResult, Visitor},
 };
 use arrayvec::ArrayVec;
+use fxhash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::{
+    backtrace::Backtrace,
     fmt::{Debug, Display, Formatter},
     future::Future,
     hash::{Hash, Hasher},
     iter::FromIterator,
     marker::PhantomData,
     ops::{Index, IndexMut},
+    panic::Location,
+    sync::Arc,
 };
 
 const INVALID_GENERATION: u32 = 0;
@@ -103,6 +112,52 @@ where
 {
     records: Vec<PoolRecord<T, P>>,
     free_stack: Vec<u32>,
+    free_info: Option<Box<FxHashMap<u32, FreeInfo>>>,
+}
+
+// Free info is a separate field of the pool, so it could be accessed while the records are borrowed.
+fn find_free_info<T>(
+    free_info: &Option<Box<FxHashMap<u32, FreeInfo>>>,
+    handle: Handle<T>,
+) -> Option<&FreeInfo> {
+    free_info
+        .as_ref()?
+        .get(&handle.index)
+        .filter(|info| info.generation == handle.generation)
+}
+
+// Must be called only on failure paths, the description could be expensive to build.
+fn describe_dangling_handle<T>(
+    free_info: &Option<Box<FxHashMap<u32, FreeInfo>>>,
+    handle: Handle<T>,
+) -> String {
+    match find_free_info(free_info, handle) {
+        Some(info) => format!(" {info}"),
+        None => String::new(),
+    }
+}
+
+/// Information about the place, where an object was freed. It is collected only if handle diagnostics is
+/// enabled for a pool (see [`Pool::set_handle_diagnostics_enabled`]).
+#[derive(Clone, Debug)]
+pub struct FreeInfo {
+    /// Generation of the freed object. Only handles with this generation are described by the info.
+    pub generation: u32,
+    /// Source code location of the call, that freed the object.
+    pub location: &'static Location<'static>,
+    /// Backtrace of the call, that freed the object. It is captured only if `RUST_BACKTRACE` environment
+    /// variable is set (see [`Backtrace::capture`]).
+    pub backtrace: Arc<Backtrace>,
+}
+
+impl Display for FreeInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The object was freed at {}", self.location)?;
+        if let std::backtrace::BacktraceStatus::Captured = self.backtrace.status() {
+            write!(f, ". Backtrace:\n{}", self.backtrace)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T, P> Reflect for Pool<T, P>
@@ -154,6 +209,20 @@ where
     fn as_array_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectArray>)) {
         func(Some(self))
     }
+
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        for item in self.iter() {
+            func(item);
+        }
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        for item in self.iter_mut() {
+            func(item);
+        }
+        true
+    }
 }
 
 impl<T, P> ReflectArray for Pool<T, P>
@@ -480,6 +549,7 @@ impl<T: Clone> Clone for Pool<T> {
         Self {
             records: self.records.clone(),
             free_stack: self.free_stack.clone(),
+            free_info: self.free_info.clone(),
         }
     }
 }
@@ -493,6 +563,7 @@ where
         Pool {
             records: Vec::new(),
             free_stack: Vec::new(),
+            free_info: None,
         }
     }
 
@@ -502,6 +573,62 @@ where
         Pool {
             records: Vec::with_capacity(capacity),
             free_stack: Vec::new(),
+            free_info: None,
+        }
+    }
+
+    /// Enables or disables handle diagnostics. When enabled, the pool remembers where every object was
+    /// freed (see [`FreeInfo`]) and reports it, when a dangling handle to the object is used: panic messages
+    /// of [`Self::borrow`] and similar methods will contain the info. Non-panicking methods (such as
+    /// [`Self::try_borrow`]) do not report anything, because a failure is a normal result for them, use
+    /// [`Self::free_info`] to get the info. It has a performance cost on every freeing and it is meant to
+    /// be used for debugging only. Disabling the diagnostics discards the collected info.
+    ///
+    /// ```
+    /// use fyrox_core::pool::Pool;
+    /// let mut pool = Pool::<u32>::new();
+    /// pool.set_handle_diagnostics_enabled(true);
+    /// let a = pool.spawn(1);
+    /// pool.free(a);
+    /// let info = pool.free_info(a).unwrap();
+    /// assert_eq!(info.location.file(), file!());
+    /// ```
+    #[inline]
+    pub fn set_handle_diagnostics_enabled(&mut self, enabled: bool) {
+        if enabled {
+            if self.free_info.is_none() {
+                self.free_info = Some(Default::default());
+            }
+        } else {
+            self.free_info = None;
+        }
+    }
+
+    /// Returns `true` if handle diagnostics is enabled, `false` - otherwise. See
+    /// [`Self::set_handle_diagnostics_enabled`] for more info.
+    #[inline]
+    pub fn is_handle_diagnostics_enabled(&self) -> bool {
+        self.free_info.is_some()
+    }
+
+    /// Returns information about the place, where an object at the given handle was freed. It is available
+    /// only if the handle diagnostics is enabled (see [`Self::set_handle_diagnostics_enabled`]) and only for
+    /// the last object freed at the handle's index.
+    #[inline]
+    pub fn free_info(&self, handle: Handle<T>) -> Option<&FreeInfo> {
+        find_free_info(&self.free_info, handle)
+    }
+
+    fn record_free(&mut self, handle: Handle<T>, location: &'static Location<'static>) {
+        if let Some(free_info) = self.free_info.as_mut() {
+            free_info.insert(
+                handle.index,
+                FreeInfo {
+                    generation: handle.generation,
+                    location,
+                    backtrace: Arc::new(Backtrace::capture()),
+                },
+            );
         }
     }
 
@@ -741,12 +868,18 @@ where
                 if let Some(payload) = record.payload.as_ref() {
                     payload
                 } else {
-                    panic!("Attempt to borrow destroyed object at {:?} handle.", handle);
+                    panic!(
+                        "Attempt to borrow destroyed object at {:?} handle.{}",
+                        handle,
+                        describe_dangling_handle(&self.free_info, handle)
+                    );
                 }
             } else {
                 panic!(
-                    "Attempt to use dangling handle {:?}. Record has generation {}!",
-                    handle, record.generation
+                    "Attempt to use dangling handle {:?}. Record has generation {}!{}",
+                    handle,
+                    record.generation,
+                    describe_dangling_handle(&self.free_info, handle)
                 );
             }
         } else {
@@ -779,15 +912,27 @@ where
     #[must_use]
     pub fn borrow_mut(&mut self, handle: Handle<T>) -> &mut T {
         let record_count = self.records.len();
-        if let Some(record) = self.records_get_mut(handle.index) {
+        let index = usize::try_from(handle.index).expect("Index overflowed usize");
+        // The records are borrowed directly, so the free info (a disjoint field) is still accessible
+        // to describe the handle on failure.
+        if let Some(record) = self.records.get_mut(index) {
             if record.generation == handle.generation {
                 if let Some(payload) = record.payload.as_mut() {
                     payload
                 } else {
-                    panic!("Attempt to borrow destroyed object at {:?} handle.", handle);
+                    panic!(
+                        "Attempt to borrow destroyed object at {:?} handle.{}",
+                        handle,
+                        describe_dangling_handle(&self.free_info, handle)
+                    );
                 }
             } else {
-                panic!("Attempt to borrow object using dangling handle {:?}. Record has {} generation!", handle, record.generation);
+                panic!(
+                    "Attempt to borrow object using dangling handle {:?}. Record has {} generation!{}",
+                    handle,
+                    record.generation,
+                    describe_dangling_handle(&self.free_info, handle)
+                );
             }
         } else {
             panic!(
@@ -977,7 +1122,24 @@ where
     ///
     /// Panics if the given handle is invalid.
     #[inline]
+    #[track_caller]
     pub fn free(&mut self, handle: Handle<T>) -> T {
+        self.free_with_location(handle, Location::caller())
+    }
+
+    /// Same as [`Self::free`], but the given location is reported as the place where the object was freed,
+    /// if handle diagnostics is enabled (see [`Self::set_handle_diagnostics_enabled`]). It is useful for
+    /// deferred destruction, when an object is freed some time after it was requested to be freed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given handle is invalid.
+    #[inline]
+    pub fn free_with_location(
+        &mut self,
+        handle: Handle<T>,
+        location: &'static Location<'static>,
+    ) -> T {
         let index = usize::try_from(handle.index).expect("index overflowed usize");
         if let Some(record) = self.records.get_mut(index) {
             if record.generation == handle.generation {
@@ -985,14 +1147,21 @@ where
                 self.free_stack.push(handle.index);
                 // Return current payload.
                 if let Some(payload) = record.payload.take() {
+                    self.record_free(handle, location);
                     payload
                 } else {
-                    panic!("Attempt to double free object at handle {:?}!", handle);
+                    panic!(
+                        "Attempt to double free object at handle {:?}!{}",
+                        handle,
+                        describe_dangling_handle(&self.free_info, handle)
+                    );
                 }
             } else {
                 panic!(
-                    "Attempt to free object using dangling handle {:?}! Record generation is {}",
-                    handle, record.generation
+                    "Attempt to free object using dangling handle {:?}! Record generation is {}{}",
+                    handle,
+                    record.generation,
+                    describe_dangling_handle(&self.free_info, handle)
                 );
             }
         } else {
@@ -1004,9 +1173,10 @@ where
     /// is invalid. After object is moved out if the pool, all handles to the object will become
     /// invalid.
     #[inline]
+    #[track_caller]
     pub fn try_free(&mut self, handle: Handle<T>) -> Option<T> {
         let index = usize::try_from(handle.index).expect("index overflowed usize");
-        self.records.get_mut(index).and_then(|record| {
+        let payload = self.records.get_mut(index).and_then(|record| {
             if record.generation == handle.generation {
                 if let Some(payload) = record.payload.take() {
                     self.free_stack.push(handle.index);
@@ -1017,7 +1187,11 @@ where
             } else {
                 None
             }
-        })
+        });
+        if payload.is_some() {
+            self.record_free(handle, Location::caller());
+        }
+        payload
     }
 
     /// Moves an object out of the pool using the given handle with a promise that the object will be returned back.
@@ -1129,6 +1303,9 @@ where
     pub fn clear(&mut self) {
         self.records.clear();
         self.free_stack.clear();
+        if let Some(free_info) = self.free_info.as_mut() {
+            free_info.clear();
+        }
     }
 
     #[inline]
@@ -2178,4 +2355,44 @@ mod test {
         assert_eq!(pool[a], 15);
         assert_eq!(pool[b], 5);
     }
+
+    #[test]
+    fn pool_handle_diagnostics() {
+        let mut pool = Pool::<u32>::new();
+        let a = pool.spawn(1);
+        pool.free(a);
+        assert!(!pool.is_handle_diagnostics_enabled());
+        assert!(pool.free_info(a).is_none());
+
+        pool.set_handle_diagnostics_enabled(true);
+        let b = pool.spawn(2);
+        let line = line!() + 1;
+        assert_eq!(pool.try_free(b), Some(2));
+        let info = pool.free_info(b).unwrap();
+        assert_eq!(info.generation, b.generation);
+        assert_eq!(info.location.file(), file!());
+        assert_eq!(info.location.line(), line);
+        assert!(pool.try_borrow(b).is_none());
+
+        // Info describes only the last freed object at the index.
+        let c = pool.spawn(3);
+        assert_eq!(c.index, b.index);
+        assert!(pool.free_info(b).is_some());
+        pool.free(c);
+        assert!(pool.free_info(b).is_none());
+        assert!(pool.free_info(c).is_some());
+
+        let result = std::panic::catch_unwind(|| *pool.borrow(c));
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("The object was freed at"));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| *pool.borrow_mut(c)));
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("The object was freed at"));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| pool.free(c)));
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("The object was freed at"));
+
+        pool.set_handle_diagnostics_enabled(false);
+        assert!(pool.free_info(c).is_none());
+    }
 }
@@ -2,6 +2,7 @@
 
 mod external_impls;
 mod std_impls;
+pub mod walk;
 
 pub use fyrox_core_derive::Reflect;
 use std::{
@@ -259,6 +260,37 @@ pub trait Reflect: Any + Debug {
     fn as_hash_map_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectHashMap>)) {
         func(None)
     }
+
+    /// Returns `true` if the value gives access to its contents only while a lock (or a borrow)
+    /// is held, for example `Mutex<T>` or `Rc<RefCell<T>>`. References, that are passed to
+    /// reflection callbacks of such values, must not be used after the callbacks have returned.
+    fn is_guarded(&self) -> bool {
+        false
+    }
+
+    /// Passes every value inside this value (items of arrays, keys and values of hash maps, inner
+    /// value of inheritable variables, or fields) to the given function. Unlike [`Self::fields`],
+    /// the references live as long as the value is borrowed, which allows the traversal (see
+    /// [`walk::walk`]) to keep them on its own stack instead of recursing. Returns `false` if the
+    /// method is not implemented, in this case the contents are accessible only through the
+    /// callbacks. The derive macro implements this method automatically. Must be implemented
+    /// together with [`Self::contents_mut`].
+    fn contents<'a>(
+        &'a self,
+        #[allow(unused_variables)] func: &mut dyn FnMut(&'a dyn Reflect),
+    ) -> bool {
+        false
+    }
+
+    /// Same as [`Self::contents`], but gives mutable access to the values. Keys of hash maps are
+    /// not passed, because they cannot be modified without re-hashing. Inheritable variables must
+    /// not be marked as modified.
+    fn contents_mut<'a>(
+        &'a mut self,
+        #[allow(unused_variables)] func: &mut dyn FnMut(&'a mut dyn Reflect),
+    ) -> bool {
+        false
+    }
 }
 
 /// [`Reflect`] sub trait for working with slices.
@@ -1165,6 +1197,18 @@ macro_rules! delegate_reflect {
         fn as_list_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectList>)) {
             self.deref_mut().as_list_mut(func)
         }
+
+        fn is_guarded(&self) -> bool {
+            self.deref().is_guarded()
+        }
+
+        fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+            self.deref().contents(func)
+        }
+
+        fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+            self.deref_mut().contents_mut(func)
+        }
     };
 }
 
@@ -73,6 +73,20 @@ impl<const N: usize, T: Reflect> Reflect for [T; N] {
     fn as_array_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectArray>)) {
         func(Some(self))
     }
+
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        for item in self.iter() {
+            func(item);
+        }
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        for item in self.iter_mut() {
+            func(item);
+        }
+        true
+    }
 }
 
 impl<const N: usize, T: Reflect> ReflectArray for [T; N] {
@@ -97,9 +111,38 @@ impl<const N: usize, T: Reflect> ReflectArray for [T; N] {
     }
 }
 
-impl_reflect! {
-    #[reflect(ReflectList, ReflectArray)]
-    pub struct Vec<T: Reflect + 'static>;
+impl<T: Reflect + 'static> Reflect for Vec<T> {
+    blank_reflect!();
+
+    fn as_array(&self, func: &mut dyn FnMut(Option<&dyn ReflectArray>)) {
+        func(Some(self))
+    }
+
+    fn as_array_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectArray>)) {
+        func(Some(self))
+    }
+
+    fn as_list(&self, func: &mut dyn FnMut(Option<&dyn ReflectList>)) {
+        func(Some(self))
+    }
+
+    fn as_list_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectList>)) {
+        func(Some(self))
+    }
+
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        for item in self.iter() {
+            func(item);
+        }
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        for item in self.iter_mut() {
+            func(item);
+        }
+        true
+    }
 }
 
 impl<T: Reflect + 'static> ReflectArray for Vec<T> {
@@ -164,6 +207,21 @@ where
     fn as_hash_map_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectHashMap>)) {
         func(Some(self))
     }
+
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        for (key, value) in self.iter() {
+            func(key);
+            func(value);
+        }
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        for value in self.values_mut() {
+            func(value);
+        }
+        true
+    }
 }
 
 impl<K, V, S> ReflectHashMap for HashMap<K, V, S>
@@ -399,6 +457,10 @@ macro_rules! impl_reflect_inner_mutability {
             let mut guard = $acquire_lock_guard;
             guard.as_hash_map_mut(func)
         }
+
+        fn is_guarded(&$self) -> bool {
+            true
+        }
     };
 }
 
@@ -0,0 +1,309 @@
+//! Iterative traversal of reflected values, see [`walk`] and [`walk_mut`] docs for more info.
+
+use crate::reflect::Reflect;
+
+/// Tells the traversal what to do with a visited value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WalkAction {
+    /// Visit the contents of the value (items, fields, etc.).
+    Descend,
+    /// Do not look into the value.
+    Skip,
+}
+
+/// Visits the given value and every value inside it (items of arrays, keys and values of hash maps,
+/// contents of inheritable variables and fields) in depth-first order. The traversal uses an
+/// explicit stack for the contents of values, that implement [`Reflect::contents`] (every value,
+/// that derives [`Reflect`], standard containers, etc.), so deep hierarchies won't overflow the
+/// call stack. Contents of the rest of the values are visited by a nested traversal inside the
+/// reflection callbacks. Guarded values (see [`Reflect::is_guarded`]) are visited first, then their
+/// contents are visited as a separate value, because the contents are accessible only while the
+/// lock is held.
+pub fn walk<'a>(root: &'a dyn Reflect, visitor: &mut dyn FnMut(&dyn Reflect) -> WalkAction) {
+    let mut stack = vec![root];
+    while let Some(value) = stack.pop() {
+        if visitor(value) == WalkAction::Skip {
+            continue;
+        }
+
+        if value.is_guarded() {
+            value.as_reflect(&mut |inner| walk(inner, visitor));
+            continue;
+        }
+
+        let first = stack.len();
+        if value.contents(&mut |content| stack.push(content)) {
+            // Contents are pushed in reverse order, so they're visited in the order of declaration.
+            stack[first..].reverse();
+        } else {
+            for_each_content(value, &mut |content| walk(content, visitor));
+        }
+    }
+}
+
+fn for_each_content(value: &dyn Reflect, func: &mut dyn FnMut(&dyn Reflect)) {
+    let mut finished = false;
+
+    value.as_array(&mut |array| {
+        if let Some(array) = array {
+            for i in 0..array.reflect_len() {
+                if let Some(item) = array.reflect_index(i) {
+                    func(item);
+                }
+            }
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.as_inheritable_variable(&mut |inheritable| {
+        if let Some(inheritable) = inheritable {
+            func(inheritable.inner_value_ref());
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.as_hash_map(&mut |hash_map| {
+        if let Some(hash_map) = hash_map {
+            for i in 0..hash_map.reflect_len() {
+                if let Some((key, value)) = hash_map.reflect_get_at(i) {
+                    func(key);
+                    func(value);
+                }
+            }
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.fields(&mut |fields| {
+        for field in fields {
+            func(*field);
+        }
+    })
+}
+
+/// Same as [`walk`], but gives mutable access to the values. Keys of hash maps are not visited,
+/// because they cannot be modified without re-hashing. Inheritable variables are not marked as
+/// modified.
+pub fn walk_mut<'a>(
+    root: &'a mut dyn Reflect,
+    visitor: &mut dyn FnMut(&mut dyn Reflect) -> WalkAction,
+) {
+    let mut stack = vec![root];
+    while let Some(value) = stack.pop() {
+        if visitor(value) == WalkAction::Skip {
+            continue;
+        }
+
+        if value.is_guarded() {
+            value.as_reflect_mut(&mut |inner| walk_mut(inner, visitor));
+            continue;
+        }
+
+        // The contents borrow the value for the whole traversal, so the value cannot be used
+        // after they're pushed. Check if they're accessible first.
+        if value.contents_mut(&mut |_| {}) {
+            let first = stack.len();
+            value.contents_mut(&mut |content| stack.push(content));
+            stack[first..].reverse();
+        } else {
+            for_each_content_mut(value, &mut |content| walk_mut(content, visitor));
+        }
+    }
+}
+
+fn for_each_content_mut(value: &mut dyn Reflect, func: &mut dyn FnMut(&mut dyn Reflect)) {
+    let mut finished = false;
+
+    // Inheritable variables must be checked first, because mutable access to their contents in any
+    // other way marks them as modified.
+    value.as_inheritable_variable_mut(&mut |inheritable| {
+        if let Some(inheritable) = inheritable {
+            func(inheritable.inner_value_mut());
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.as_array_mut(&mut |array| {
+        if let Some(array) = array {
+            for i in 0..array.reflect_len() {
+                if let Some(item) = array.reflect_index_mut(i) {
+                    func(item);
+                }
+            }
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.as_hash_map_mut(&mut |hash_map| {
+        if let Some(hash_map) = hash_map {
+            for i in 0..hash_map.reflect_len() {
+                if let Some(value) = hash_map.reflect_get_nth_value_mut(i) {
+                    func(value);
+                }
+            }
+
+            finished = true;
+        }
+    });
+
+    if finished {
+        return;
+    }
+
+    value.fields_mut(&mut |fields| {
+        for field in fields.iter_mut() {
+            func(&mut **field);
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{blank_reflect, parking_lot::Mutex, reflect::prelude::*};
+    use std::{any::Any, sync::Arc};
+
+    // Recursive types can't derive `Reflect`.
+    #[derive(Debug, Default)]
+    struct Chain {
+        value: u32,
+        next: Vec<Chain>,
+        shared: Option<Arc<Mutex<u32>>>,
+    }
+
+    impl Reflect for Chain {
+        blank_reflect!();
+
+        fn fields(&self, func: &mut dyn FnMut(&[&dyn Reflect])) {
+            func(&[&self.value, &self.next, &self.shared])
+        }
+
+        fn fields_mut(&mut self, func: &mut dyn FnMut(&mut [&mut dyn Reflect])) {
+            func(&mut [&mut self.value, &mut self.next, &mut self.shared])
+        }
+
+        fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+            func(&self.value);
+            func(&self.next);
+            func(&self.shared);
+            true
+        }
+
+        fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+            func(&mut self.value);
+            func(&mut self.next);
+            func(&mut self.shared);
+            true
+        }
+    }
+
+    // Passes a temporary to the callbacks, so its contents must be visited inside the callbacks.
+    #[derive(Debug, Default)]
+    struct Computed {
+        value: u32,
+    }
+
+    impl Reflect for Computed {
+        blank_reflect!();
+
+        fn fields(&self, func: &mut dyn FnMut(&[&dyn Reflect])) {
+            func(&[&vec![self.value, self.value * 2]])
+        }
+    }
+
+    #[test]
+    fn walk_deep_hierarchy() {
+        let mut root = Chain::default();
+        let mut last = &mut root;
+        for i in 1..100_000 {
+            last.next.push(Chain {
+                value: i,
+                ..Default::default()
+            });
+            last = &mut last.next[0];
+        }
+        last.shared = Some(Arc::new(Mutex::new(7)));
+
+        let mut sum = 0u64;
+        walk(&root, &mut |value| {
+            // Guarded values are visited as well, they look like their contents.
+            if value.is_guarded() {
+                return WalkAction::Descend;
+            }
+            value.downcast_ref::<u32>(&mut |value| {
+                if let Some(value) = value {
+                    sum += *value as u64;
+                }
+            });
+            WalkAction::Descend
+        });
+        // Values of the chain and the value behind the lock.
+        assert_eq!(sum, (0..100_000u64).sum::<u64>() + 7);
+
+        walk_mut(&mut root, &mut |value| {
+            let mut action = WalkAction::Descend;
+            value.downcast_mut::<u32>(&mut |value| {
+                if let Some(value) = value {
+                    *value = 42;
+                }
+            });
+            value.downcast_mut::<Vec<Chain>>(&mut |chain| {
+                if chain.is_some() {
+                    action = WalkAction::Skip;
+                }
+            });
+            action
+        });
+        assert_eq!(root.value, 42);
+        // Skipped values are not visited.
+        assert_eq!(root.next[0].value, 1);
+
+        // The default drop is recursive.
+        let mut next = std::mem::take(&mut root.next);
+        while let Some(mut chain) = next.pop() {
+            next.append(&mut chain.next);
+        }
+    }
+
+    #[test]
+    fn walk_values_without_contents() {
+        let value = Computed { value: 3 };
+        assert!(!value.contents(&mut |_| {}));
+
+        let mut values = Vec::new();
+        walk(&value, &mut |value| {
+            value.downcast_ref::<u32>(&mut |value| {
+                if let Some(value) = value {
+                    values.push(*value);
+                }
+            });
+            WalkAction::Descend
+        });
+        assert_eq!(values, [3, 6]);
+    }
+}
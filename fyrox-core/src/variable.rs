@@ -374,6 +374,17 @@ where
     ) {
         func(Some(self))
     }
+
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        func(&self.value);
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        // Direct access, the variable must not be marked as modified.
+        func(&mut self.value);
+        true
+    }
 }
 
 impl<T> ReflectInheritableVariable for InheritableVariable<T>
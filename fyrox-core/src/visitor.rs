@@ -1227,6 +1227,78 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Returns `true` if the current region has a field with the given name.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.nodes
+            .borrow(self.current_node)
+            .fields
+            .iter()
+            .any(|field| field.name == name)
+    }
+
+    /// Returns `true` if the current region has a child region with the given name.
+    pub fn has_region(&self, name: &str) -> bool {
+        self.find_region(name).is_some()
+    }
+
+    fn find_region(&self, name: &str) -> Option<Handle<VisitorNode>> {
+        self.nodes
+            .borrow(self.current_node)
+            .children
+            .iter()
+            .find(|child| self.nodes.borrow(**child).name == name)
+            .cloned()
+    }
+
+    /// Renames a field of the current region. Returns `false` if there's no such field or if the
+    /// new name is already taken. It is meant to be used to upgrade data of older versions before
+    /// reading it.
+    pub fn rename_field(&mut self, old_name: &str, new_name: &str) -> bool {
+        if self.has_field(new_name) {
+            return false;
+        }
+        match self.find_field(old_name) {
+            Some(field) => {
+                field.name = new_name.to_owned();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a field from the current region. Returns `false` if there's no such field.
+    pub fn remove_field(&mut self, name: &str) -> bool {
+        let fields = &mut self.current_node().fields;
+        let count = fields.len();
+        fields.retain(|field| field.name != name);
+        fields.len() != count
+    }
+
+    /// Renames a child region of the current region. Returns `false` if there's no such region or
+    /// if the new name is already taken.
+    pub fn rename_region(&mut self, old_name: &str, new_name: &str) -> bool {
+        if self.has_region(new_name) {
+            return false;
+        }
+        match self.find_region(old_name) {
+            Some(region) => {
+                self.nodes.borrow_mut(region).name = new_name.to_owned();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes a value into the current region even if the visitor is in reading mode. It could be
+    /// used to add missing fields with default values to data of older versions.
+    pub fn insert_field<T: Visit>(&mut self, name: &str, value: &mut T) -> VisitResult {
+        let reading = self.reading;
+        self.reading = false;
+        let result = value.visit(name, self);
+        self.reading = reading;
+        result
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
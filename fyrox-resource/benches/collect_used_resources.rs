@@ -0,0 +1,87 @@
+//! Measures the time needed to collect resources of a large scene-like object graph. Run it with
+//! `cargo bench -p fyrox-resource`.
+
+use fxhash::{FxHashMap, FxHashSet};
+use fyrox_core::{
+    algebra::{Matrix4, Vector2, Vector3},
+    reflect::prelude::*,
+    uuid::Uuid,
+    variable::InheritableVariable,
+};
+use fyrox_resource::{collect_used_resources, untyped::UntypedResource};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Reflect)]
+struct Surface {
+    material: Option<UntypedResource>,
+    positions: Vec<Vector3<f32>>,
+    uvs: Vec<Vector2<f32>>,
+    indices: Vec<u32>,
+}
+
+#[derive(Debug, Reflect)]
+struct Node {
+    name: String,
+    transform: Matrix4<f32>,
+    visible: InheritableVariable<bool>,
+    texture: InheritableVariable<Option<UntypedResource>>,
+    surfaces: Vec<Surface>,
+    properties: FxHashMap<String, f32>,
+    children: Vec<usize>,
+}
+
+#[derive(Debug, Reflect)]
+struct Scene {
+    nodes: Vec<Node>,
+}
+
+fn make_scene(node_count: usize) -> Scene {
+    let uuid = Uuid::new_v4();
+    let resources = (0..64)
+        .map(|i| UntypedResource::new_pending(format!("{i}.res").into(), uuid))
+        .collect::<Vec<_>>();
+
+    Scene {
+        nodes: (0..node_count)
+            .map(|i| Node {
+                name: format!("Node{i}"),
+                transform: Matrix4::identity(),
+                visible: true.into(),
+                texture: Some(resources[i % resources.len()].clone()).into(),
+                surfaces: (0..2)
+                    .map(|j| Surface {
+                        material: Some(resources[(i + j) % resources.len()].clone()),
+                        positions: vec![Vector3::default(); 256],
+                        uvs: vec![Vector2::default(); 256],
+                        indices: (0..384).collect(),
+                    })
+                    .collect(),
+                properties: (0..4).map(|j| (format!("Property{j}"), j as f32)).collect(),
+                children: vec![i + 1, i + 2],
+            })
+            .collect(),
+    }
+}
+
+#[allow(clippy::mutable_key_type)]
+fn main() {
+    for node_count in [1_000, 10_000] {
+        let scene = make_scene(node_count);
+
+        let iterations = 10;
+        let mut total = Duration::default();
+        let mut resource_count = 0;
+        for _ in 0..iterations {
+            let mut resources = FxHashSet::default();
+            let start = Instant::now();
+            collect_used_resources(&scene, &mut resources);
+            total += start.elapsed();
+            resource_count = resources.len();
+        }
+
+        println!(
+            "collect_used_resources: {node_count} nodes, {resource_count} resources - {:?} per scan",
+            total / iterations
+        );
+    }
+}
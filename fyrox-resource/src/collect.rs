@@ -0,0 +1,296 @@
+//! Resource collection and remapping. See [`collect_used_resources`] and [`remap_used_resources`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix2, Matrix3, Matrix4, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        math::TriangleDefinition,
+        parking_lot::RwLock,
+        reflect::{
+            prelude::*,
+            walk::{walk, walk_mut, WalkAction},
+        },
+        sstorage::ImmutableString,
+        uuid::Uuid,
+    },
+    untyped::UntypedResource,
+};
+use fxhash::FxHashSet;
+use std::{
+    any::{Any, TypeId},
+    path::PathBuf,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+fn opaque_types() -> &'static RwLock<FxHashSet<TypeId>> {
+    static OPAQUE_TYPES: OnceLock<RwLock<FxHashSet<TypeId>>> = OnceLock::new();
+    OPAQUE_TYPES.get_or_init(|| {
+        macro_rules! type_ids {
+            ($($ty:ty),* $(,)?) => {
+                [$(TypeId::of::<$ty>(), TypeId::of::<Vec<$ty>>()),*]
+            };
+        }
+
+        RwLock::new(
+            type_ids![
+                f32,
+                f64,
+                usize,
+                u8,
+                u16,
+                u32,
+                u64,
+                isize,
+                i8,
+                i16,
+                i32,
+                i64,
+                bool,
+                String,
+                PathBuf,
+                Duration,
+                Instant,
+                ImmutableString,
+                Uuid,
+                Color,
+                TriangleDefinition,
+                Vector2<f32>,
+                Vector3<f32>,
+                Vector4<f32>,
+                Vector2<f64>,
+                Vector3<f64>,
+                Vector4<f64>,
+                Matrix2<f32>,
+                Matrix3<f32>,
+                Matrix4<f32>,
+                UnitQuaternion<f32>,
+            ]
+            .into_iter()
+            .collect(),
+        )
+    })
+}
+
+/// Registers a type, that cannot contain any resources, so [`collect_used_resources`] and
+/// [`remap_used_resources`] will skip every instance of it without looking into its fields. This
+/// is useful for types with large amounts of plain data (vertex buffers, height maps, etc.), that
+/// would otherwise be inspected element by element. Primitive types, common math types and vectors
+/// of them are registered by default.
+///
+/// Keep in mind, that resources inside instances of an opaque type won't be found, so make sure
+/// the type does not contain any resources.
+pub fn register_opaque_type<T: Reflect>() {
+    opaque_types().write().insert(TypeId::of::<T>());
+}
+
+/// Returns `true` if the given type was registered as opaque (see [`register_opaque_type`]).
+pub fn is_opaque_type<T: Reflect>() -> bool {
+    opaque_types().read().contains(&TypeId::of::<T>())
+}
+
+/// Collects all resources used by a given entity. Internally, it uses reflection to iterate over
+/// each field of every descendant sub-object of the entity (see [`walk`]). This function could be
+/// used to collect all resources used by an object, which could be useful if you're building a
+/// resource dependency analyzer.
+///
+/// The traversal is iterative, so deep hierarchies won't overflow the stack. Shared contents (for
+/// example, `Arc<Mutex<T>>`) are inspected only once, even if they're reachable multiple times.
+/// Instances of opaque types (see [`register_opaque_type`]) are skipped entirely.
+#[allow(clippy::mutable_key_type)]
+pub fn collect_used_resources(
+    entity: &dyn Reflect,
+    resources_collection: &mut FxHashSet<UntypedResource>,
+) {
+    let opaque_types = opaque_types().read();
+    // Addresses of the contents of guarded values, that were inspected already.
+    let mut visited_guarded = FxHashSet::<*const ()>::default();
+    walk(entity, &mut |entity| {
+        let type_id = Any::type_id(entity);
+        if opaque_types.contains(&type_id) {
+            return WalkAction::Skip;
+        }
+
+        if type_id == TypeId::of::<UntypedResource>() {
+            entity.downcast_ref::<UntypedResource>(&mut |resource| {
+                if let Some(resource) = resource {
+                    resources_collection.insert(resource.clone());
+                }
+            });
+            return WalkAction::Skip;
+        }
+
+        // The same object could be reachable multiple times only via shared values, which give
+        // access to their contents through a lock or a borrow.
+        if entity.is_guarded() {
+            let mut action = WalkAction::Skip;
+            entity.as_reflect(&mut |inner| {
+                if visited_guarded.insert(inner as *const dyn Reflect as *const ()) {
+                    action = WalkAction::Descend;
+                }
+            });
+            return action;
+        }
+
+        WalkAction::Descend
+    })
+}
+
+/// Replaces resources used by a given entity. Internally, it uses reflection to iterate over each
+/// field of every descendant sub-object of the entity (in the same manner as
+/// [`collect_used_resources`]). The `remap` closure is called for every found resource and should
+/// return a new resource to replace the current one, or `None` to keep the current one.
+/// Resources are not inspected, so resources used by other resources are left untouched.
+///
+/// Keep in mind, that this function does **not** mark inheritable variables as modified.
+pub fn remap_used_resources(
+    entity: &mut dyn Reflect,
+    remap: &mut dyn FnMut(&UntypedResource) -> Option<UntypedResource>,
+) {
+    let opaque_types = opaque_types().read();
+    walk_mut(entity, &mut |entity| {
+        let type_id = Any::type_id(entity);
+        if opaque_types.contains(&type_id) {
+            return WalkAction::Skip;
+        }
+
+        if type_id == TypeId::of::<UntypedResource>() {
+            entity.downcast_mut::<UntypedResource>(&mut |resource| {
+                if let Some(resource) = resource {
+                    if let Some(new_resource) = remap(resource) {
+                        *resource = new_resource;
+                    }
+                }
+            });
+            return WalkAction::Skip;
+        }
+
+        WalkAction::Descend
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::parking_lot::Mutex;
+    use std::sync::Arc;
+
+    #[derive(Debug, Default, Reflect)]
+    struct Opaque {
+        resource: Option<UntypedResource>,
+    }
+
+    #[derive(Debug, Default, Reflect)]
+    struct Shared {
+        resources: Vec<UntypedResource>,
+    }
+
+    #[derive(Debug, Default, Reflect)]
+    struct Container {
+        opaque: Opaque,
+        a: Arc<Mutex<Shared>>,
+        b: Arc<Mutex<Shared>>,
+        positions: Vec<Vector3<f32>>,
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn collect_used_resources_opaque_and_shared() {
+        let uuid = Uuid::new_v4();
+        let shared = Arc::new(Mutex::new(Shared {
+            resources: vec![UntypedResource::new_pending("a.txt".into(), uuid)],
+        }));
+        let mut container = Container {
+            opaque: Opaque {
+                resource: Some(UntypedResource::new_pending("b.txt".into(), uuid)),
+            },
+            a: shared.clone(),
+            b: shared,
+            positions: vec![Vector3::default(); 16],
+        };
+        assert!(is_opaque_type::<Vec<Vector3<f32>>>());
+
+        let mut resources = FxHashSet::default();
+        collect_used_resources(&container, &mut resources);
+        assert_eq!(resources.len(), 2);
+
+        register_opaque_type::<Opaque>();
+        assert!(is_opaque_type::<Opaque>());
+
+        let mut resources = FxHashSet::default();
+        collect_used_resources(&container, &mut resources);
+        assert_eq!(resources.len(), 1);
+        assert!(resources
+            .iter()
+            .all(|resource| resource.path() == std::path::Path::new("a.txt")));
+
+        let mut remapped = 0;
+        remap_used_resources(&mut container, &mut |_| {
+            remapped += 1;
+            None
+        });
+        // Shared data is remapped through every owner.
+        assert_eq!(remapped, 2);
+    }
+
+    // Recursive types can't derive `Reflect`.
+    #[derive(Debug, Default)]
+    struct Chain {
+        resource: Option<UntypedResource>,
+        next: Vec<Chain>,
+    }
+
+    impl Reflect for Chain {
+        crate::core::blank_reflect!();
+
+        fn fields(&self, func: &mut dyn FnMut(&[&dyn Reflect])) {
+            func(&[&self.resource, &self.next])
+        }
+
+        fn fields_mut(&mut self, func: &mut dyn FnMut(&mut [&mut dyn Reflect])) {
+            func(&mut [&mut self.resource, &mut self.next])
+        }
+
+        fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+            func(&self.resource);
+            func(&self.next);
+            true
+        }
+
+        fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+            func(&mut self.resource);
+            func(&mut self.next);
+            true
+        }
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn collect_used_resources_deep_hierarchy() {
+        let uuid = Uuid::new_v4();
+        let mut root = Chain::default();
+        let mut last = &mut root;
+        for _ in 0..100_000 {
+            last.next.push(Chain::default());
+            last = &mut last.next[0];
+        }
+        last.resource = Some(UntypedResource::new_pending("deep.txt".into(), uuid));
+
+        let mut resources = FxHashSet::default();
+        collect_used_resources(&root, &mut resources);
+        assert_eq!(resources.len(), 1);
+
+        let other = UntypedResource::new_pending("other.txt".into(), uuid);
+        remap_used_resources(&mut root, &mut |_| Some(other.clone()));
+        let mut resources = FxHashSet::default();
+        collect_used_resources(&root, &mut resources);
+        assert!(resources.contains(&other));
+
+        // Drop the chain iteratively, the default drop is recursive.
+        let mut next = std::mem::take(&mut root.next);
+        while let Some(mut chain) = next.pop() {
+            next.append(&mut chain.next);
+        }
+    }
+}
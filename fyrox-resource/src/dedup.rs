@@ -0,0 +1,102 @@
+//! Content hashing for resource deduplication. See
+//! [`crate::manager::ResourceManagerState::set_content_deduplication`] docs for more info.
+
+use crate::{
+    core::{io::FileLoadError, parking_lot::Mutex, uuid::Uuid},
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+};
+use fxhash::FxHasher64;
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A hash of the content of a resource file, that was read by a loading task.
+pub(crate) struct ContentHashReport {
+    pub(crate) path: PathBuf,
+    pub(crate) type_uuid: Uuid,
+    pub(crate) hash: u64,
+}
+
+/// Resource IO, that hashes the content of the resource file, when a loader reads it. The hash is
+/// calculated from the same bytes the loader gets, so the file is read only once. Files read using
+/// [`ResourceIo::file_reader`] are not hashed.
+pub(crate) struct ContentHashingIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) path: PathBuf,
+    pub(crate) type_uuid: Uuid,
+    pub(crate) reports: Arc<Mutex<Vec<ContentHashReport>>>,
+}
+
+impl ResourceIo for ContentHashingIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let data = self.inner.load_file(path).await?;
+            if path == self.path {
+                let mut hasher = FxHasher64::default();
+                data.hash(&mut hasher);
+                self.reports.lock().push(ContentHashReport {
+                    path: self.path.clone(),
+                    type_uuid: self.type_uuid,
+                    hash: hasher.finish(),
+                });
+            }
+            Ok(data)
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        self.inner.file_reader(path)
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        self.inner.file_stamp(path)
+    }
+}
@@ -0,0 +1,78 @@
+//! Registry of resources, that are compiled into the binary. See [`EmbeddedResourceRegistry`] docs
+//! for more info.
+
+use fxhash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// A registry of resources, that are compiled into the binary (usually using `include_bytes!`).
+/// Every resource is registered under a virtual path, which is then used to request the resource
+/// from the resource manager as usual. Resources at registered paths are always loaded from the
+/// embedded data, without any access to the file system, which is useful for default assets of the
+/// engine and games that should be distributed as a single executable.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_resource::manager::ResourceManager;
+///
+/// fn register_embedded_resources(resource_manager: &ResourceManager) {
+///     resource_manager
+///         .state()
+///         .embedded_resources
+///         .register("embedded/logo.png", include_bytes!("../../pics/logo.png"));
+/// }
+/// ```
+#[derive(Default, Debug)]
+pub struct EmbeddedResourceRegistry {
+    files: FxHashMap<PathBuf, &'static [u8]>,
+}
+
+impl EmbeddedResourceRegistry {
+    /// Creates new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given data under the given virtual path. Returns previous data at the path
+    /// (if any).
+    pub fn register(
+        &mut self,
+        path: impl Into<PathBuf>,
+        data: &'static [u8],
+    ) -> Option<&'static [u8]> {
+        self.files.insert(path.into(), data)
+    }
+
+    /// Removes data at the given virtual path and returns it (if any). Resources that were loaded
+    /// from the data are not affected.
+    pub fn unregister(&mut self, path: &Path) -> Option<&'static [u8]> {
+        self.files.remove(path)
+    }
+
+    /// Returns data at the given virtual path (if any).
+    pub fn get(&self, path: &Path) -> Option<&'static [u8]> {
+        self.files.get(path).cloned()
+    }
+
+    /// Returns `true` if there's data at the given virtual path.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Returns an iterator over every virtual path and its data.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &'static [u8])> {
+        self.files
+            .iter()
+            .map(|(path, data)| (path.as_path(), *data))
+    }
+
+    /// Returns total amount of registered resources.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
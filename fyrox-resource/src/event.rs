@@ -3,11 +3,13 @@
 use crate::core::{
     parking_lot::Mutex,
     pool::{Handle, Pool},
+    uuid::Uuid,
 };
-use crate::UntypedResource;
+use crate::{state::ResourceState, UntypedResource};
+use fxhash::FxHashMap;
 use std::{
     path::PathBuf,
-    sync::{mpsc::Sender, Arc},
+    sync::{mpsc::Sender, Arc, Weak},
 };
 
 /// A resource event.
@@ -17,22 +19,116 @@ pub enum ResourceEvent {
     Loaded(UntypedResource),
 
     /// Occurs when a resource was already fully loaded, but was reloaded by an explicit request.
-    Reloaded(UntypedResource),
+    /// Type uuids could be different, if the file was replaced with a file of some other type.
+    Reloaded {
+        /// The reloaded resource.
+        resource: UntypedResource,
+        /// Type uuid of the resource data before the reload.
+        old_type_uuid: Uuid,
+        /// Type uuid of the resource data after the reload.
+        new_type_uuid: Uuid,
+    },
 
     /// Occurs when a resource was just added to a resource container.
     Added(UntypedResource),
 
     /// Occurs when a resource was removed from a resource container.
     Removed(PathBuf),
+
+    /// Occurs when a resource was destroyed by the resource manager, because it was not used
+    /// anymore (see [`crate::manager::ResourceManagerState::update`] and
+    /// [`crate::manager::ResourceManagerState::unload_group`]). The resource itself does not exist
+    /// anymore, so the event contains its [`UntypedResource::key`], that could be used to remove
+    /// respective entries from caches (GPU textures, etc.). This event is sent right after
+    /// [`Self::Removed`]. It is also sent (without [`Self::Removed`]) when the data of a resource
+    /// was dropped by [`UntypedResource::unload`], the resource stays valid in this case.
+    Unloaded {
+        /// Path of the unloaded resource.
+        path: PathBuf,
+        /// Type uuid of the unloaded resource.
+        type_uuid: Uuid,
+        /// Key of the unloaded resource (see [`UntypedResource::key`]).
+        key: usize,
+    },
+
+    /// Occurs when data of a fully loaded resource was replaced in-place (see
+    /// [`UntypedResource::replace_data`]).
+    DataReplaced(UntypedResource),
+
+    /// Occurs when a stage of a streamable resource was loaded and committed (see
+    /// [`crate::streaming::StreamableResourceData`]).
+    StageLoaded {
+        /// The resource, which stage was loaded.
+        resource: UntypedResource,
+        /// Index of the stage.
+        stage: usize,
+    },
+
+    /// Occurs when a stage of a streamable resource has failed to load. The error could be fetched
+    /// using [`crate::manager::ResourceManagerState::stage_states`].
+    StageFailed {
+        /// The resource, which stage has failed to load.
+        resource: UntypedResource,
+        /// Index of the stage.
+        stage: usize,
+    },
+
+    /// Occurs when a stage of a streamable resource was unloaded (see
+    /// [`crate::manager::ResourceManagerState::unload_stages`]).
+    StageUnloaded {
+        /// The resource, which stage was unloaded.
+        resource: UntypedResource,
+        /// Index of the stage.
+        stage: usize,
+    },
 }
 
 /// Type alias for event sender.
 pub type ResourceEventSender = Sender<ResourceEvent>;
 
-/// Event broadcaster is responsible for delivering resource events to "subscribers".
+/// Defines when a resource callback is called.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResourceCallbackKind {
+    /// The callback is called once, when the resource is fully loaded. If the resource is already
+    /// loaded, the callback is called immediately.
+    Loaded,
+    /// The callback is called every time the resource is reloaded.
+    Reloaded,
+    /// The callback is called every time the resource data is replaced in-place (see
+    /// [`UntypedResource::replace_data`]).
+    DataReplaced,
+}
+
+/// Type alias for a resource callback.
+pub type ResourceCallback = Box<dyn FnMut(&UntypedResource) + Send>;
+
+/// A handle of a resource callback, that could be used to remove the callback (see
+/// [`ResourceEventBroadcaster::remove_resource_callback`]).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ResourceCallbackHandle(u64);
+
+struct ResourceCallbackEntry {
+    id: u64,
+    resource: Weak<Mutex<ResourceState>>,
+    kind: ResourceCallbackKind,
+    callback: ResourceCallback,
+}
+
+#[derive(Default)]
+struct ResourceCallbacks {
+    next_id: u64,
+    entries: Vec<ResourceCallbackEntry>,
+}
+
+/// Event broadcaster is responsible for delivering resource events to "subscribers". Besides
+/// general subscribers, that receive events of every resource, it could also call callbacks
+/// attached to specific resources (see [`Self::add_resource_callback`]).
 #[derive(Clone)]
 pub struct ResourceEventBroadcaster {
     container: Arc<Mutex<Pool<ResourceEventSender>>>,
+    callbacks: Arc<Mutex<ResourceCallbacks>>,
+    // Type uuids of resources, that are being reloaded. Keys are resource keys.
+    reloads: Arc<Mutex<FxHashMap<usize, Uuid>>>,
 }
 
 impl Default for ResourceEventBroadcaster {
@@ -46,6 +142,8 @@ impl ResourceEventBroadcaster {
     pub fn new() -> Self {
         Self {
             container: Arc::new(Default::default()),
+            callbacks: Arc::new(Default::default()),
+            reloads: Arc::new(Default::default()),
         }
     }
 
@@ -59,11 +157,108 @@ impl ResourceEventBroadcaster {
         self.container.lock().free(handle)
     }
 
-    /// Sends an event to all "subscribers" in the broadcaster.
+    /// Sends an event to all "subscribers" in the broadcaster and calls respective callbacks of the
+    /// resource (if any).
     pub fn broadcast(&self, event: ResourceEvent) {
-        let container = self.container.lock();
-        for sender in container.iter() {
-            let _ = sender.send(event.clone());
+        {
+            let container = self.container.lock();
+            for sender in container.iter() {
+                let _ = sender.send(event.clone());
+            }
+        }
+
+        match event {
+            ResourceEvent::Loaded(resource) => {
+                self.call_resource_callbacks(&resource, ResourceCallbackKind::Loaded)
+            }
+            ResourceEvent::Reloaded { resource, .. } => {
+                self.call_resource_callbacks(&resource, ResourceCallbackKind::Reloaded)
+            }
+            ResourceEvent::DataReplaced(resource) => {
+                self.call_resource_callbacks(&resource, ResourceCallbackKind::DataReplaced)
+            }
+            _ => (),
+        }
+    }
+
+    /// Attaches a callback to the given resource. The callback is called on a thread, that loads
+    /// the resource, after the resource data is committed. See [`ResourceCallbackKind`] docs for
+    /// more info about when the callback is called. The callback does not prevent the resource from
+    /// being destroyed, callbacks of destroyed resources are removed automatically.
+    pub fn add_resource_callback<F>(
+        &self,
+        resource: &UntypedResource,
+        kind: ResourceCallbackKind,
+        callback: F,
+    ) -> ResourceCallbackHandle
+    where
+        F: FnMut(&UntypedResource) + Send + 'static,
+    {
+        let id = {
+            let mut callbacks = self.callbacks.lock();
+            let id = callbacks.next_id;
+            callbacks.next_id += 1;
+            callbacks.entries.push(ResourceCallbackEntry {
+                id,
+                resource: Arc::downgrade(&resource.0),
+                kind,
+                callback: Box::new(callback),
+            });
+            id
+        };
+
+        // The loading could be finished before the callback was added, call the callback right away
+        // in this case (unless it was already called by the loader).
+        if kind == ResourceCallbackKind::Loaded && matches!(*resource.lock(), ResourceState::Ok(_))
+        {
+            let entry = self.take_resource_callback(id);
+            if let Some(mut entry) = entry {
+                (entry.callback)(resource);
+            }
+        }
+
+        ResourceCallbackHandle(id)
+    }
+
+    /// Removes a resource callback by its handle. Returns `true` if the callback was removed, `false`
+    /// if there's no such callback (for example, if it was already called).
+    pub fn remove_resource_callback(&self, handle: ResourceCallbackHandle) -> bool {
+        self.take_resource_callback(handle.0).is_some()
+    }
+
+    fn take_resource_callback(&self, id: u64) -> Option<ResourceCallbackEntry> {
+        let mut callbacks = self.callbacks.lock();
+        let index = callbacks.entries.iter().position(|entry| entry.id == id)?;
+        Some(callbacks.entries.remove(index))
+    }
+
+    fn call_resource_callbacks(&self, resource: &UntypedResource, kind: ResourceCallbackKind) {
+        let mut matching = Vec::new();
+        {
+            let mut callbacks = self.callbacks.lock();
+            // Remove callbacks of destroyed resources first.
+            callbacks
+                .entries
+                .retain(|entry| entry.resource.strong_count() > 0);
+            let mut i = 0;
+            while i < callbacks.entries.len() {
+                let entry = &callbacks.entries[i];
+                if entry.kind == kind && entry.resource.as_ptr() == Arc::as_ptr(&resource.0) {
+                    matching.push(callbacks.entries.remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        // Callbacks are called without the lock, so they can add or remove other callbacks.
+        for entry in matching.iter_mut() {
+            (entry.callback)(resource);
+        }
+
+        // Loaded callbacks are called only once.
+        if kind != ResourceCallbackKind::Loaded {
+            self.callbacks.lock().entries.extend(matching);
         }
     }
 
@@ -76,16 +271,45 @@ impl ResourceEventBroadcaster {
     /// "subscribers" in the broadcaster depending on the `reload` parameter.
     pub fn broadcast_loaded_or_reloaded(&self, resource: UntypedResource, reload: bool) {
         self.broadcast(if reload {
-            ResourceEvent::Reloaded(resource)
+            let new_type_uuid = resource.type_uuid();
+            let old_type_uuid = self
+                .reloads
+                .lock()
+                .remove(&resource.key())
+                .unwrap_or(new_type_uuid);
+            ResourceEvent::Reloaded {
+                resource,
+                old_type_uuid,
+                new_type_uuid,
+            }
         } else {
             ResourceEvent::Loaded(resource)
         })
     }
+
+    /// Remembers the type of a resource (identified by its key) before it is reloaded, so it could
+    /// be passed to [`ResourceEvent::Reloaded`] event.
+    pub(crate) fn on_reload_started(&self, resource_key: usize, type_uuid: Uuid) {
+        self.reloads.lock().insert(resource_key, type_uuid);
+    }
+
+    /// Sends [`ResourceEvent::Removed`] and [`ResourceEvent::Unloaded`] events for a resource, that
+    /// is about to be destroyed.
+    pub(crate) fn broadcast_unloaded(&self, resource: &UntypedResource) {
+        let path = resource.path();
+        self.broadcast(ResourceEvent::Removed(path.clone()));
+        self.reloads.lock().remove(&resource.key());
+        self.broadcast(ResourceEvent::Unloaded {
+            path,
+            type_uuid: resource.type_uuid(),
+            key: resource.key(),
+        });
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::sync::mpsc::channel;
+    use std::{path::Path, sync::mpsc::channel};
 
     use super::*;
 
@@ -138,10 +362,87 @@ mod test {
         broadcaster.broadcast_loaded_or_reloaded(UntypedResource::default(), true);
         assert!(matches!(
             receiver.recv(),
-            Ok(ResourceEvent::Reloaded(UntypedResource(_)))
+            Ok(ResourceEvent::Reloaded {
+                resource: UntypedResource(_),
+                ..
+            })
         ));
     }
 
+    #[test]
+    fn resource_event_broadcaster_reloaded_type_change() {
+        let broadcaster = ResourceEventBroadcaster::default();
+        let (sender, receiver) = channel();
+        broadcaster.add(sender);
+
+        let old_type_uuid = Uuid::new_v4();
+        let new_type_uuid = Uuid::new_v4();
+        let resource = UntypedResource::new_pending("foo".into(), old_type_uuid);
+        broadcaster.on_reload_started(resource.key(), old_type_uuid);
+        *resource.lock() = ResourceState::LoadError {
+            path: "foo".into(),
+            error: Default::default(),
+            type_uuid: new_type_uuid,
+        };
+
+        broadcaster.broadcast_loaded_or_reloaded(resource.clone(), true);
+        assert!(matches!(
+            receiver.recv(),
+            Ok(ResourceEvent::Reloaded { resource: r, old_type_uuid: old, new_type_uuid: new })
+                if r == resource && old == old_type_uuid && new == new_type_uuid
+        ));
+
+        broadcaster.broadcast_unloaded(&resource);
+        assert!(
+            matches!(receiver.recv(), Ok(ResourceEvent::Removed(path)) if path == Path::new("foo"))
+        );
+        assert!(matches!(
+            receiver.recv(),
+            Ok(ResourceEvent::Unloaded { type_uuid, key, .. })
+                if type_uuid == new_type_uuid && key == resource.key()
+        ));
+    }
+
+    #[test]
+    fn resource_event_broadcaster_resource_callbacks() {
+        let broadcaster = ResourceEventBroadcaster::default();
+        let resource = UntypedResource::new_pending("foo".into(), Default::default());
+        let other = UntypedResource::new_pending("bar".into(), Default::default());
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let loaded_calls = calls.clone();
+        broadcaster.add_resource_callback(&resource, ResourceCallbackKind::Loaded, move |_| {
+            loaded_calls.lock().push(ResourceCallbackKind::Loaded)
+        });
+        let reloaded_calls = calls.clone();
+        let reloaded = broadcaster.add_resource_callback(
+            &resource,
+            ResourceCallbackKind::Reloaded,
+            move |_| reloaded_calls.lock().push(ResourceCallbackKind::Reloaded),
+        );
+
+        broadcaster.broadcast_loaded(other.clone());
+        assert!(calls.lock().is_empty());
+
+        broadcaster.broadcast_loaded(resource.clone());
+        broadcaster.broadcast_loaded(resource.clone());
+        broadcaster.broadcast_loaded_or_reloaded(resource.clone(), true);
+        broadcaster.broadcast_loaded_or_reloaded(resource.clone(), true);
+        assert_eq!(
+            *calls.lock(),
+            [
+                ResourceCallbackKind::Loaded,
+                ResourceCallbackKind::Reloaded,
+                ResourceCallbackKind::Reloaded
+            ]
+        );
+
+        assert!(broadcaster.remove_resource_callback(reloaded));
+        assert!(!broadcaster.remove_resource_callback(reloaded));
+        broadcaster.broadcast_loaded_or_reloaded(resource, true);
+        assert_eq!(calls.lock().len(), 3);
+    }
+
     #[test]
     fn resource_event_broadcaster_clone() {
         let broadcaster = ResourceEventBroadcaster::new();
@@ -20,7 +20,7 @@ impl ResourceGraphNode {
         // Look for dependent resources.
         let mut dependent_resources = FxHashSet::default();
 
-        let resource_state = resource.0.lock();
+        let resource_state = resource.lock();
         if let ResourceState::Ok(resource_data) = &*resource_state {
             (**resource_data).as_reflect(&mut |entity| {
                 collect_used_resources(entity, &mut dependent_resources);
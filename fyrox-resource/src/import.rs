@@ -0,0 +1,550 @@
+//! Asset import pipeline with derived data cache. See [`ResourceImporter`] and [`DerivedDataCache`]
+//! docs for more info.
+
+use crate::{
+    core::{append_extension, io::FileLoadError, log::Log, uuid::Uuid},
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+    options::OPTIONS_EXTENSION,
+};
+use fxhash::FxHasher64;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Source data of an asset, that should be converted by a [`ResourceImporter`].
+pub struct ImportContext<'a> {
+    /// Path of the source file.
+    pub path: &'a Path,
+    /// Content of the source file.
+    pub source: &'a [u8],
+    /// Content of the import options file of the source file, or default options of the importer
+    /// (see [`ResourceImporter::default_options`]) if there's no options file. See
+    /// [`crate::options`] module docs for more info.
+    pub options: Option<&'a [u8]>,
+}
+
+/// Resource importer converts source files of some format (PNG, WAV, FBX, etc.) into an
+/// engine-optimized representation (derived data), that is much faster to load. For example,
+/// a texture importer could decode and compress a PNG image and generate its mip levels, so the
+/// texture loader only needs to copy the data.
+///
+/// Derived data is produced once and then stored in [`DerivedDataCache`], keyed by a hash of the
+/// source file content, the import options and the importer itself, so it is produced again only
+/// when any of them changes. The cache also remembers stamps of the source file and its options
+/// file (see [`FileStamp`]), so when the files weren't modified since the last import, the entry
+/// is found without reading the source file. Resource loaders never see the source data of imported files: when a
+/// loader reads the source file, it receives the derived data instead. This means, that the loader
+/// of the source extension must understand the format of the derived data.
+///
+/// Importers are registered in [`ResourceImportersContainer`] of the resource manager.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{
+///     core::uuid::{uuid, Uuid},
+///     import::{ImportContext, ResourceImporter},
+///     manager::ResourceManager,
+/// };
+///
+/// /// Converts text files to upper case.
+/// struct UpperCaseImporter;
+///
+/// impl ResourceImporter for UpperCaseImporter {
+///     fn extensions(&self) -> &[&str] {
+///         &["txt"]
+///     }
+///
+///     fn importer_uuid(&self) -> Uuid {
+///         uuid!("c2a0ef1f-1ad6-4b3f-8a39-55de4c3b9e2a")
+///     }
+///
+///     fn import(&self, context: ImportContext) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+///         Ok(context.source.to_ascii_uppercase())
+///     }
+/// }
+///
+/// let resource_manager = ResourceManager::new();
+/// resource_manager.state().importers.add(UpperCaseImporter);
+/// ```
+pub trait ResourceImporter: Send + Sync + 'static {
+    /// Returns a list of extensions of source files, that could be imported by this importer.
+    fn extensions(&self) -> &[&str];
+
+    /// Checks if the given extension is supported by this importer. Comparison is
+    /// case-insensitive.
+    fn supports_extension(&self, ext: &str) -> bool {
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(ext))
+    }
+
+    /// Returns a unique id of the importer. It is a part of the key of derived data, so different
+    /// importers of the same source file never share their derived data.
+    fn importer_uuid(&self) -> Uuid;
+
+    /// Returns a version of the derived data format. It is a part of the key of derived data, so
+    /// changing the version invalidates every derived data produced by previous versions of the
+    /// importer.
+    fn version(&self) -> u32 {
+        0
+    }
+
+    /// Returns import options of the given source file, that are used when the file has no import
+    /// options file (for example, registered default import options, see
+    /// [`crate::options::register_default_import_options`]). The options are passed to
+    /// [`Self::import`] instead of the content of the options file, so they're a part of the key
+    /// of derived data as well.
+    ///
+    /// Default implementation returns `None`.
+    fn default_options(&self, #[allow(unused)] path: &Path) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Converts the source data into derived data.
+    fn import(&self, context: ImportContext) -> Result<Vec<u8>, Box<dyn std::error::Error>>;
+}
+
+/// A set of resource importers. See [`ResourceImporter`] docs for more info.
+#[derive(Default)]
+pub struct ResourceImportersContainer {
+    importers: Vec<Arc<dyn ResourceImporter>>,
+}
+
+impl ResourceImportersContainer {
+    /// Creates new empty importers container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new importer. If there are multiple importers for the same extension, the one that
+    /// was added first is used.
+    pub fn add<T: ResourceImporter>(&mut self, importer: T) {
+        self.importers.push(Arc::new(importer));
+    }
+
+    /// Tries to find an importer for the given extension.
+    pub fn find_for_extension(&self, ext: &str) -> Option<&Arc<dyn ResourceImporter>> {
+        self.importers
+            .iter()
+            .find(|importer| importer.supports_extension(ext))
+    }
+
+    /// Tries to find an importer for the given path using its extension.
+    pub fn find_for_path(&self, path: &Path) -> Option<&Arc<dyn ResourceImporter>> {
+        self.find_for_extension(path.extension()?.to_str()?)
+    }
+
+    /// Removes every importer.
+    pub fn clear(&mut self) {
+        self.importers.clear();
+    }
+
+    /// Returns total amount of importers.
+    pub fn len(&self) -> usize {
+        self.importers.len()
+    }
+
+    /// Returns `true` if there's no importers.
+    pub fn is_empty(&self) -> bool {
+        self.importers.is_empty()
+    }
+
+    /// Returns an iterator over every importer.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ResourceImporter> {
+        self.importers.iter().map(|importer| &**importer)
+    }
+}
+
+/// A key of derived data in [`DerivedDataCache`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivedDataKey(pub u64);
+
+impl DerivedDataKey {
+    /// Calculates a key of derived data for the given importer and its input.
+    pub fn new(importer: &dyn ResourceImporter, source: &[u8], options: Option<&[u8]>) -> Self {
+        let mut hasher = FxHasher64::default();
+        importer.importer_uuid().hash(&mut hasher);
+        importer.version().hash(&mut hasher);
+        source.hash(&mut hasher);
+        options.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+impl Display for DerivedDataKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// A folder with derived data, produced by resource importers. See [`ResourceImporter`] docs for
+/// more info. Every entry is stored in a separate file named by its key, stamps of source files
+/// are stored in [`Self::STAMPS_FOLDER`]. The cache uses the file
+/// system directly (not the resource IO of the resource manager), because it is a local storage,
+/// that should never be shipped with a game. Errors of the file system are never fatal: derived
+/// data is then produced again on every load.
+///
+/// The cache is never cleaned up automatically, the entries of modified source files stay there
+/// until [`Self::clear`] is called (or the folder is deleted).
+#[derive(Clone, Debug)]
+pub struct DerivedDataCache {
+    root: PathBuf,
+    enabled: bool,
+    read_only: bool,
+}
+
+impl Default for DerivedDataCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ROOT)
+    }
+}
+
+impl DerivedDataCache {
+    /// Default root folder of the cache.
+    pub const DEFAULT_ROOT: &'static str = ".cache";
+
+    /// A folder inside the root folder, that contains stamps of source files.
+    pub const STAMPS_FOLDER: &'static str = "stamps";
+
+    /// Creates a new cache at the given root folder. The folder is created on the first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            enabled: true,
+            read_only: false,
+        }
+    }
+
+    /// Returns the root folder of the cache.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Enables or disables the cache. Disabled cache is never read or written, derived data is
+    /// produced on every load.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the cache is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables read-only mode of the cache. Read-only cache is still read, but nothing
+    /// is written to it. The mode is set by [`crate::manager::ResourceManagerState::set_read_only`].
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Returns `true` if the cache is in read-only mode.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns a path of the file of an entry with the given key.
+    pub fn entry_path(&self, key: DerivedDataKey) -> PathBuf {
+        self.root.join(key.to_string())
+    }
+
+    /// Tries to read an entry with the given key.
+    pub fn load(&self, key: DerivedDataKey) -> Option<Vec<u8>> {
+        if !self.enabled {
+            return None;
+        }
+        std::fs::read(self.entry_path(key)).ok()
+    }
+
+    /// Writes an entry with the given key. The data is written to a temporary file first, so
+    /// a reader never sees partially written data. Nothing is written in read-only mode (see
+    /// [`Self::set_read_only`]).
+    pub fn store(&self, key: DerivedDataKey, data: &[u8]) -> std::io::Result<()> {
+        if !self.enabled || self.read_only {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.entry_path(key);
+        let temp_path = append_extension(&path, "tmp");
+        std::fs::write(&temp_path, data)?;
+        std::fs::rename(temp_path, path)
+    }
+
+    fn stamps_path(&self, importer: &dyn ResourceImporter, source_path: &Path) -> PathBuf {
+        let mut hasher = FxHasher64::default();
+        importer.importer_uuid().hash(&mut hasher);
+        importer.version().hash(&mut hasher);
+        source_path.hash(&mut hasher);
+        self.root
+            .join(Self::STAMPS_FOLDER)
+            .join(format!("{:016x}", hasher.finish()))
+    }
+
+    /// Tries to find a key of derived data of the given source file using the stamps of the
+    /// source file and its options file, that were stored by [`Self::store_key`]. The key is
+    /// returned only if the stamps are the same.
+    pub fn find_key(
+        &self,
+        importer: &dyn ResourceImporter,
+        source_path: &Path,
+        stamps: &SourceStamps,
+    ) -> Option<DerivedDataKey> {
+        if !self.enabled {
+            return None;
+        }
+        let data = std::fs::read(self.stamps_path(importer, source_path)).ok()?;
+        let record = ron::de::from_bytes::<StampRecord>(&data).ok()?;
+        (record.stamps == *stamps).then_some(DerivedDataKey(record.key))
+    }
+
+    /// Remembers a key of derived data of the given source file with the given stamps, so the
+    /// key could be found by [`Self::find_key`] without reading the source file. Nothing is
+    /// written in read-only mode (see [`Self::set_read_only`]).
+    pub fn store_key(
+        &self,
+        importer: &dyn ResourceImporter,
+        source_path: &Path,
+        stamps: &SourceStamps,
+        key: DerivedDataKey,
+    ) -> std::io::Result<()> {
+        if !self.enabled || self.read_only {
+            return Ok(());
+        }
+        let path = self.stamps_path(importer, source_path);
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        let record = StampRecord {
+            stamps: stamps.clone(),
+            key: key.0,
+        };
+        let data = ron::to_string(&record)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        let temp_path = append_extension(&path, "tmp");
+        std::fs::write(&temp_path, data)?;
+        std::fs::rename(temp_path, path)
+    }
+
+    /// Removes every entry of the cache.
+    pub fn clear(&self) -> std::io::Result<()> {
+        match std::fs::remove_dir_all(&self.root) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Stamps of a source file and its options file, see [`DerivedDataCache::find_key`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceStamps {
+    /// Stamp of the source file.
+    pub source: FileStamp,
+    /// Stamp of the options file of the source file, `None` if there's no options file.
+    pub options: Option<FileStamp>,
+    /// Hash of default options of the importer (see [`ResourceImporter::default_options`]),
+    /// `None` if there's an options file or the importer has no default options.
+    pub default_options: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StampRecord {
+    stamps: SourceStamps,
+    key: u64,
+}
+
+/// Resource IO wrapper, that provides derived data instead of the content of a source file.
+pub(crate) struct ImportingResourceIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) source_path: PathBuf,
+    pub(crate) importer: Arc<dyn ResourceImporter>,
+    pub(crate) cache: DerivedDataCache,
+}
+
+impl ImportingResourceIo {
+    async fn source_stamps(
+        &self,
+        options_path: &Path,
+        has_options: bool,
+        default_options: Option<&[u8]>,
+    ) -> Option<SourceStamps> {
+        let source = self.inner.file_stamp(&self.source_path).await?;
+        let options = if has_options {
+            // Options file without a stamp could be modified unnoticed.
+            Some(self.inner.file_stamp(options_path).await?)
+        } else {
+            None
+        };
+        let default_options = default_options.map(|options| {
+            let mut hasher = FxHasher64::default();
+            options.hash(&mut hasher);
+            hasher.finish()
+        });
+        Some(SourceStamps {
+            source,
+            options,
+            default_options,
+        })
+    }
+
+    async fn load_derived_data(&self) -> Result<Vec<u8>, FileLoadError> {
+        let options_path = append_extension(&self.source_path, OPTIONS_EXTENSION);
+        let has_options = self.inner.exists(&options_path).await;
+        let default_options = if has_options {
+            None
+        } else {
+            self.importer.default_options(&self.source_path)
+        };
+
+        // Stamps must be taken before reading the files, otherwise a modification between the
+        // reads could be missed.
+        let stamps = self
+            .source_stamps(&options_path, has_options, default_options.as_deref())
+            .await;
+        if let Some(ref stamps) = stamps {
+            if let Some(data) = self
+                .cache
+                .find_key(&*self.importer, &self.source_path, stamps)
+                .and_then(|key| self.cache.load(key))
+            {
+                return Ok(data);
+            }
+        }
+
+        let source = self.inner.load_file(&self.source_path).await?;
+        let options = if has_options {
+            self.inner.load_file(&options_path).await.ok()
+        } else {
+            default_options
+        };
+
+        let key = DerivedDataKey::new(&*self.importer, &source, options.as_deref());
+        let data = match self.cache.load(key) {
+            Some(data) => data,
+            None => self.import(key, &source, options.as_deref())?,
+        };
+
+        if let Some(stamps) = stamps {
+            if let Err(err) = self
+                .cache
+                .store_key(&*self.importer, &self.source_path, &stamps, key)
+            {
+                Log::warn(format!(
+                    "Unable to write stamps of {} to the cache. Reason: {:?}",
+                    self.source_path.display(),
+                    err
+                ));
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn import(
+        &self,
+        key: DerivedDataKey,
+        source: &[u8],
+        options: Option<&[u8]>,
+    ) -> Result<Vec<u8>, FileLoadError> {
+        let data = self
+            .importer
+            .import(ImportContext {
+                path: &self.source_path,
+                source,
+                options,
+            })
+            .map_err(|err| {
+                FileLoadError::Custom(format!(
+                    "Unable to import {}. Reason: {}",
+                    self.source_path.display(),
+                    err
+                ))
+            })?;
+
+        if let Err(err) = self.cache.store(key, &data) {
+            Log::warn(format!(
+                "Unable to write derived data of {} to the cache. Reason: {:?}",
+                self.source_path.display(),
+                err
+            ));
+        }
+
+        Ok(data)
+    }
+}
+
+impl ResourceIo for ImportingResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        if path == self.source_path {
+            Box::pin(self.load_derived_data())
+        } else {
+            self.inner.load_file(path)
+        }
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        if path == self.source_path {
+            Box::pin(async move {
+                let reader: Box<dyn FileReader> =
+                    Box::new(std::io::Cursor::new(self.load_derived_data().await?));
+                Ok(reader)
+            })
+        } else {
+            self.inner.file_reader(path)
+        }
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        self.inner.file_stamp(path)
+    }
+}
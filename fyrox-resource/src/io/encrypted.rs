@@ -0,0 +1,285 @@
+//! Encrypting resource IO layer. See [`EncryptedResourceIo`] docs for more info.
+
+use crate::{
+    core::{
+        io::FileLoadError,
+        rand::{thread_rng, RngCore},
+    },
+    io::{FileStamp, ResourceIo, ResourceIoFuture},
+};
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Magic sequence that is written at the beginning of every encrypted file.
+pub const ENCRYPTED_FILE_MAGIC: &[u8; 8] = b"FYRXENC1";
+
+/// Size of an encryption key in bytes.
+pub const ENCRYPTION_KEY_SIZE: usize = 32;
+
+const NONCE_SIZE: usize = 12;
+
+/// Resource IO layer that decrypts files produced by [`EncryptedResourceIo::encrypt`]. It wraps any
+/// other resource IO (file system, archive, etc.) and transparently decrypts the data it provides,
+/// so shipped assets can't be trivially extracted. AES-256-GCM is used, which means that the data
+/// is also authenticated - an attempt to load a tampered file will result in an error.
+///
+/// File layout is the following: [`ENCRYPTED_FILE_MAGIC`], 12-byte nonce, ciphertext with the
+/// authentication tag. By default, files without the magic sequence are rejected, otherwise an
+/// encrypted asset could be replaced with an unencrypted file. If you want to encrypt only a subset
+/// of your assets, enable plaintext passthrough (see [`Self::with_plaintext_passthrough`]).
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{
+///     io::{encrypted::EncryptedResourceIo, FsResourceIo},
+///     manager::ResourceManager,
+/// };
+/// use std::sync::Arc;
+///
+/// // The key must be provided by your game, ideally it should be obfuscated somehow.
+/// let key = [42u8; 32];
+///
+/// let resource_manager = ResourceManager::new();
+/// resource_manager
+///     .state()
+///     .set_resource_io(Arc::new(EncryptedResourceIo::new(Arc::new(FsResourceIo), &key)));
+/// ```
+pub struct EncryptedResourceIo {
+    inner: Arc<dyn ResourceIo>,
+    cipher: Aes256Gcm,
+    plaintext_passthrough: bool,
+}
+
+impl Debug for EncryptedResourceIo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Never expose the key.
+        write!(f, "EncryptedResourceIo")
+    }
+}
+
+impl EncryptedResourceIo {
+    /// Creates new encrypting layer on top of the given resource IO using the specified key.
+    pub fn new(inner: Arc<dyn ResourceIo>, key: &[u8; ENCRYPTION_KEY_SIZE]) -> Self {
+        Self {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+            plaintext_passthrough: false,
+        }
+    }
+
+    /// Allows or forbids loading of files without [`ENCRYPTED_FILE_MAGIC`]. When allowed, such files
+    /// are passed through as is, which allows you to encrypt only a subset of your assets, but
+    /// anyone could replace an encrypted asset with an unencrypted file. Default is `false`.
+    pub fn with_plaintext_passthrough(mut self, enabled: bool) -> Self {
+        self.plaintext_passthrough = enabled;
+        self
+    }
+
+    /// Returns `true` if files without [`ENCRYPTED_FILE_MAGIC`] are passed through as is. See
+    /// [`Self::with_plaintext_passthrough`] for more info.
+    pub fn is_plaintext_passthrough_enabled(&self) -> bool {
+        self.plaintext_passthrough
+    }
+
+    /// Returns a reference to the inner resource IO.
+    pub fn inner(&self) -> &Arc<dyn ResourceIo> {
+        &self.inner
+    }
+
+    /// Checks whether the given data was produced by [`Self::encrypt`] or not.
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.starts_with(ENCRYPTED_FILE_MAGIC)
+    }
+
+    /// Encrypts the given data using a random nonce. The result could be written to a file and later
+    /// loaded by this resource IO. This method is meant to be used by asset packaging tools.
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, FileLoadError> {
+        let mut nonce = [0u8; NONCE_SIZE];
+        thread_rng().fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), data)
+            .map_err(|_| FileLoadError::Custom("Unable to encrypt the data!".to_string()))?;
+
+        let mut output =
+            Vec::with_capacity(ENCRYPTED_FILE_MAGIC.len() + NONCE_SIZE + ciphertext.len());
+        output.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+        output.extend_from_slice(&nonce);
+        output.extend_from_slice(&ciphertext);
+        Ok(output)
+    }
+
+    /// Decrypts the given data. Data without [`ENCRYPTED_FILE_MAGIC`] is rejected, unless plaintext
+    /// passthrough is enabled (see [`Self::with_plaintext_passthrough`]), in this case it is returned
+    /// as is.
+    pub fn decrypt(&self, data: Vec<u8>) -> Result<Vec<u8>, FileLoadError> {
+        if !Self::is_encrypted(&data) {
+            return if self.plaintext_passthrough {
+                Ok(data)
+            } else {
+                Err(FileLoadError::Custom(
+                    "The data is not encrypted!".to_string(),
+                ))
+            };
+        }
+
+        let payload = &data[ENCRYPTED_FILE_MAGIC.len()..];
+        if payload.len() < NONCE_SIZE {
+            return Err(FileLoadError::Custom(
+                "Encrypted data is truncated!".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_SIZE);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                FileLoadError::Custom(
+                    "Unable to decrypt the data! The key is invalid or the data is corrupted."
+                        .to_string(),
+                )
+            })
+    }
+}
+
+impl ResourceIo for EncryptedResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let data = self.inner.load_file(path).await?;
+            self.decrypt(data).map_err(|err| match err {
+                FileLoadError::Custom(msg) => {
+                    FileLoadError::Custom(format!("{}: {}", path.display(), msg))
+                }
+                err => err,
+            })
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        self.inner.file_stamp(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::executor::block_on, io::FsResourceIo};
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[1u8; ENCRYPTION_KEY_SIZE]);
+        let data = b"Some resource data".to_vec();
+
+        let encrypted = io.encrypt(&data).unwrap();
+        assert!(EncryptedResourceIo::is_encrypted(&encrypted));
+        assert_ne!(&encrypted[ENCRYPTED_FILE_MAGIC.len()..], data.as_slice());
+        assert_eq!(io.decrypt(encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypt_rejects_plain_data_by_default() {
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[1u8; ENCRYPTION_KEY_SIZE]);
+        assert!(!io.is_plaintext_passthrough_enabled());
+
+        assert!(io.decrypt(b"Plain data".to_vec()).is_err());
+    }
+
+    #[test]
+    fn decrypt_passes_plain_data_through_when_enabled() {
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[1u8; ENCRYPTION_KEY_SIZE])
+            .with_plaintext_passthrough(true);
+        let data = b"Plain data".to_vec();
+
+        assert_eq!(io.decrypt(data.clone()).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypt_with_wrong_key_fails() {
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[1u8; ENCRYPTION_KEY_SIZE]);
+        let other = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[2u8; ENCRYPTION_KEY_SIZE]);
+
+        let encrypted = io.encrypt(b"Secret").unwrap();
+        assert!(other.decrypt(encrypted.clone()).is_err());
+
+        let mut truncated = encrypted;
+        truncated.truncate(ENCRYPTED_FILE_MAGIC.len() + 4);
+        assert!(io.decrypt(truncated).is_err());
+    }
+
+    #[test]
+    fn encrypted_resource_io_load_file() {
+        let path = Path::new("encrypted_resource_io_load_file.bin");
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[3u8; ENCRYPTION_KEY_SIZE]);
+        std::fs::write(path, io.encrypt(b"Resource").unwrap()).unwrap();
+
+        let loaded = block_on(io.load_file(path));
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.unwrap(), b"Resource".to_vec());
+    }
+
+    #[test]
+    fn encrypted_resource_io_rejects_unencrypted_file() {
+        let path = Path::new("encrypted_resource_io_rejects_unencrypted_file.bin");
+        let io = EncryptedResourceIo::new(Arc::new(FsResourceIo), &[4u8; ENCRYPTION_KEY_SIZE]);
+        // A replacement of an encrypted asset with a plain file must not load.
+        std::fs::write(path, b"Tampered resource").unwrap();
+
+        let loaded = block_on(io.load_file(path));
+        std::fs::remove_file(path).unwrap();
+
+        assert!(loaded.is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Resource IO that serves files from memory. See [`MemoryResourceIo`] docs for more info.
+
+use crate::{
+    core::{io::FileLoadError, parking_lot::RwLock},
+    io::{PathIter, ResourceIo, ResourceIoFuture},
+};
+use fxhash::FxHashMap;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Resource IO that serves files from memory. Files that are not stored in memory are requested
+/// from an optional fallback resource IO. It is useful for downloaded or procedurally generated
+/// content and for tests that should not touch the file system.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::io::{memory::MemoryResourceIo, FsResourceIo};
+/// use std::sync::Arc;
+///
+/// let io = MemoryResourceIo::new().with_fallback(Arc::new(FsResourceIo));
+/// io.add_file("generated/level.rgs", vec![1, 2, 3]);
+/// ```
+#[derive(Default)]
+pub struct MemoryResourceIo {
+    files: RwLock<FxHashMap<PathBuf, Vec<u8>>>,
+    fallback: Option<Arc<dyn ResourceIo>>,
+}
+
+impl MemoryResourceIo {
+    /// Creates new resource IO without any files and without fallback.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a resource IO, that will be used to access files that are not stored in memory.
+    pub fn with_fallback(mut self, fallback: Arc<dyn ResourceIo>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Adds a file with the given content. Replaces the previous content of the file (if any).
+    pub fn add_file(&self, path: impl Into<PathBuf>, data: Vec<u8>) {
+        self.files.write().insert(path.into(), data);
+    }
+
+    /// Removes a file with the given path and returns its content (if any).
+    pub fn remove_file(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files.write().remove(path)
+    }
+
+    fn has_file(&self, path: &Path) -> bool {
+        self.files.read().contains_key(path)
+    }
+}
+
+impl ResourceIo for MemoryResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let data = self.files.read().get(path).cloned();
+            match (data, self.fallback.as_ref()) {
+                (Some(data), _) => Ok(data),
+                (None, Some(fallback)) => fallback.load_file(path).await,
+                (None, None) => Err(FileLoadError::Custom(format!(
+                    "File {} does not exist in memory!",
+                    path.display()
+                ))),
+            }
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            if let Some(data) = self.remove_file(source) {
+                self.add_file(dest, data);
+                Ok(())
+            } else if let Some(fallback) = self.fallback.as_ref() {
+                fallback.move_file(source, dest).await
+            } else {
+                Err(FileLoadError::Custom(format!(
+                    "File {} does not exist in memory!",
+                    source.display()
+                )))
+            }
+        })
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move {
+            let mut paths = self
+                .files
+                .read()
+                .keys()
+                .filter(|file| file.starts_with(path))
+                .cloned()
+                .collect::<Vec<_>>();
+            if let Some(fallback) = self.fallback.as_ref() {
+                if let Ok(fallback_paths) = fallback.walk_directory(path).await {
+                    paths.extend(fallback_paths.filter(|file| !self.has_file(file)));
+                }
+            }
+            paths.sort();
+            let iter: PathIter = Box::new(paths.into_iter());
+            Ok(iter)
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            if self.has_file(path) {
+                return true;
+            }
+            match self.fallback.as_ref() {
+                Some(fallback) => fallback.exists(path).await,
+                None => false,
+            }
+        })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            if self.has_file(path) {
+                return true;
+            }
+            match self.fallback.as_ref() {
+                Some(fallback) => fallback.is_file(path).await,
+                None => false,
+            }
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            match self.fallback.as_ref() {
+                Some(fallback) => fallback.is_dir(path).await,
+                None => false,
+            }
+        })
+    }
+}
@@ -0,0 +1,472 @@
+//! Provides an interface for IO operations that a resource loader will use, this facilliates
+//! things such as loading assets within archive files
+
+use fyrox_core::{io::FileLoadError, log::Log, parking_lot::RwLock};
+use serde::{Deserialize, Serialize};
+use std::future::{ready, Future};
+use std::iter::empty;
+use std::pin::Pin;
+use std::{
+    ffi::OsString,
+    fmt::Debug,
+    io::{Cursor, Read, Seek},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+pub mod encrypted;
+pub mod memory;
+pub mod vfs;
+
+/// Options of atomic file writing, see [`write_atomically`] for more info.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AtomicWriteOptions {
+    /// If `true`, the previous version of a file is kept next to it (see [`backup_path`]).
+    pub keep_backup: bool,
+}
+
+fn atomic_write_options() -> &'static RwLock<AtomicWriteOptions> {
+    static OPTIONS: OnceLock<RwLock<AtomicWriteOptions>> = OnceLock::new();
+    OPTIONS.get_or_init(Default::default)
+}
+
+/// Sets project-wide options, that are used by [`write_atomically`]. For example, the editor could
+/// enable backups to let users restore previous versions of their assets.
+pub fn set_default_atomic_write_options(options: AtomicWriteOptions) {
+    *atomic_write_options().write() = options;
+}
+
+/// Returns project-wide options, that are used by [`write_atomically`].
+pub fn default_atomic_write_options() -> AtomicWriteOptions {
+    atomic_write_options().read().clone()
+}
+
+/// Returns a unique path of a temporary file, that is used to write the file at the given path
+/// atomically. The temporary file is placed in the same folder (renaming across file systems is not
+/// atomic) and keeps the extension of the file (savers usually pick a format by the extension of
+/// the path), for example `foo.png` is written as `.foo.tmp-<pid>-<n>.png` first, where `<pid>` is
+/// the id of the current process and `<n>` is a number, that is unique within the process. So
+/// concurrent writes of the same file never share a temporary file. The file is hidden, so file
+/// watchers and asset browsers could ignore it (see [`is_temp_path`]).
+pub fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut name = OsString::from(".");
+    name.push(path.file_stem().unwrap_or_default());
+    name.push(format!(
+        ".tmp-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    path.with_file_name(name)
+}
+
+/// Checks whether the given path is a path of a temporary file (see [`temp_path`]).
+pub fn is_temp_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+        return false;
+    };
+    let Some(name) = name.strip_prefix('.') else {
+        return false;
+    };
+    // The extension can't contain dots, so the last marker is the one added by `temp_path`.
+    let Some((stem, suffix)) = name.rsplit_once(".tmp-") else {
+        return false;
+    };
+    let (numbers, extension) = match suffix.split_once('.') {
+        Some((numbers, extension)) => (numbers, Some(extension)),
+        None => (suffix, None),
+    };
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    !stem.is_empty()
+        && numbers
+            .split_once('-')
+            .is_some_and(|(pid, n)| is_number(pid) && is_number(n))
+        && !matches!(extension, Some(extension) if extension.is_empty() || extension.contains('.'))
+}
+
+/// Returns a path of a backup of the file at the given path, for example `foo.png.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Writes a file atomically using project-wide options (see [`set_default_atomic_write_options`]).
+/// The `write` closure must write the whole file to the given (temporary) path, then the temporary
+/// file replaces the file at `path`. If the closure fails, the temporary file is removed and the
+/// file at `path` stays intact, so a crash or an error in the middle of saving can't corrupt
+/// existing assets. All standard resource savers use this function.
+pub fn write_atomically<F, E>(path: &Path, write: F) -> Result<(), E>
+where
+    F: FnOnce(&Path) -> Result<(), E>,
+    E: From<std::io::Error>,
+{
+    write_atomically_with_options(path, &default_atomic_write_options(), write)
+}
+
+/// Same as [`write_atomically`], but with the given options.
+pub fn write_atomically_with_options<F, E>(
+    path: &Path,
+    options: &AtomicWriteOptions,
+    write: F,
+) -> Result<(), E>
+where
+    F: FnOnce(&Path) -> Result<(), E>,
+    E: From<std::io::Error>,
+{
+    let temp_path = temp_path(path);
+    if let Err(err) = write(&temp_path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(err);
+    }
+
+    // The data must reach the disk before the rename, otherwise a crash right after the rename
+    // could leave an empty or partially written file at the path.
+    if let Err(err) = std::fs::OpenOptions::new()
+        .write(true)
+        .open(&temp_path)
+        .and_then(|file| file.sync_all())
+    {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(err.into());
+    }
+
+    if options.keep_backup && path.exists() {
+        // Copy instead of rename, so the file at the path exists all the time.
+        if let Err(err) = std::fs::copy(path, backup_path(path)) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(err.into());
+        }
+    }
+
+    if let Err(err) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(err.into());
+    }
+
+    // Persist the rename itself. Directories can't be opened as files on Windows, where renames
+    // are flushed by the file system. The file is already replaced at this point, so a failure is
+    // not an error of the write.
+    #[cfg(unix)]
+    {
+        let folder = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        if let Err(err) = std::fs::File::open(folder).and_then(|folder| folder.sync_all()) {
+            Log::warn(format!(
+                "Unable to flush the folder {} after writing {}. Reason: {err}",
+                folder.display(),
+                path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Metadata of a file, that changes when the file is modified. It is used to detect changes of
+/// files without reading them. Modifications, that keep the size of a file and happen within the
+/// resolution of the modification time of the file system, are not detected.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FileStamp {
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Modification time of the file in nanoseconds since Unix epoch.
+    pub modified: u64,
+}
+
+/// Trait for files readers ensuring they implement the required traits
+pub trait FileReader: Debug + Send + Read + Seek + 'static {}
+
+impl<F> FileReader for F where F: Debug + Send + Read + Seek + 'static {}
+
+/// Interface wrapping IO operations for doing this like loading files
+/// for resources
+pub trait ResourceIo: Send + Sync + 'static {
+    /// Attempts to load the file at the provided path returning
+    /// the entire byte contents of the file or an error
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>>;
+
+    /// Attempts to move a file at the given `source` path to the given `dest` path.
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>>;
+
+    /// Tries to convert the path to its canonical form (normalize it in other terms). This method
+    /// should guarantee correct behaviour for relative paths. Symlinks aren't mandatory to
+    /// follow.
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(ready(Ok(path.to_owned())))
+    }
+
+    /// Provides an iterator over the paths present in the provided
+    /// path, this should only provide paths immediately within the directory
+    ///
+    /// Default implementation is no-op returning an empty iterator
+    fn read_directory<'a>(
+        &'a self,
+        #[allow(unused)] path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        let iter: Box<dyn Iterator<Item = PathBuf> + Send> = Box::new(empty());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    /// Provides an iterator over the paths present in the provided
+    /// path directory this implementation should walk the directory paths
+    ///
+    /// Default implementation is no-op returning an empty iterator
+    fn walk_directory<'a>(
+        &'a self,
+        #[allow(unused)] path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        let iter: Box<dyn Iterator<Item = PathBuf> + Send> = Box::new(empty());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    /// Attempts to open a file reader to the proivded path for
+    /// reading its bytes
+    ///
+    /// Default implementation loads the entire file contents from `load_file`
+    /// then uses a cursor as the reader
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            let bytes = self.load_file(path).await?;
+            let read: Box<dyn FileReader> = Box::new(Cursor::new(bytes));
+            Ok(read)
+        })
+    }
+
+    /// Used to check whether a path exists
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool>;
+
+    /// Used to check whether a path is a file
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool>;
+
+    /// Used to check whether a path is a dir
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool>;
+
+    /// Returns a stamp of the file at the given path (see [`FileStamp`]). `None` means that the
+    /// file does not exist or the IO cannot provide stamps, changes of the file can then be
+    /// detected only by reading it.
+    ///
+    /// Default implementation returns `None`
+    fn file_stamp<'a>(
+        &'a self,
+        #[allow(unused)] path: &'a Path,
+    ) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        Box::pin(ready(None))
+    }
+}
+
+/// Standard resource IO provider that uses the file system to
+/// load the file bytes
+#[derive(Default)]
+pub struct FsResourceIo;
+
+/// Future for resource io loading
+#[cfg(target_arch = "wasm32")]
+pub type ResourceIoFuture<'a, V> = Pin<Box<dyn Future<Output = V> + 'a>>;
+/// Future for resource io loading
+#[cfg(not(target_arch = "wasm32"))]
+pub type ResourceIoFuture<'a, V> = Pin<Box<dyn Future<Output = V> + Send + 'a>>;
+
+/// Iterator of paths
+#[cfg(target_arch = "wasm32")]
+pub type PathIter = Box<dyn Iterator<Item = PathBuf>>;
+/// Iterator of paths
+#[cfg(not(target_arch = "wasm32"))]
+pub type PathIter = Box<dyn Iterator<Item = PathBuf> + Send>;
+
+impl ResourceIo for FsResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(fyrox_core::io::load_file(path))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            std::fs::rename(source, dest)?;
+            Ok(())
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(async move { Ok(std::fs::canonicalize(path)?) })
+    }
+
+    /// wasm should fallback to the default no-op impl as im not sure if they
+    /// can directly read a directory
+    ///
+    /// Note: Android directory reading should be possible just I have not created
+    /// an implementation for this yet
+    #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+    fn read_directory<'a>(
+        &'a self,
+        #[allow(unused)] path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move {
+            let iter = std::fs::read_dir(path)?.flatten().map(|entry| entry.path());
+            let iter: PathIter = Box::new(iter);
+            Ok(iter)
+        })
+    }
+
+    /// Android and wasm should fallback to the default no-op impl as they cant be
+    /// walked with WalkDir
+    #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move {
+            use walkdir::WalkDir;
+
+            let iter = WalkDir::new(path)
+                .into_iter()
+                .flatten()
+                .map(|value| value.into_path());
+
+            let iter: PathIter = Box::new(iter);
+
+            Ok(iter)
+        })
+    }
+
+    /// Only use file reader when not targetting android or wasm
+    ///
+    /// Note: Might be possible to use the Android Asset struct for reading as
+    /// long as its Send + Sync + 'static (It already implements Debug + Read + Seek)
+    #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            let file = match std::fs::File::open(path) {
+                Ok(file) => file,
+                Err(e) => return Err(FileLoadError::Io(e)),
+            };
+
+            let read: Box<dyn FileReader> = Box::new(std::io::BufReader::new(file));
+            Ok(read)
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(fyrox_core::io::exists(path))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(fyrox_core::io::is_file(path))
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(fyrox_core::io::is_dir(path))
+    }
+
+    /// Android and wasm should fallback to the default impl, their files are not accessible
+    /// using the standard file system API
+    #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        Box::pin(async move {
+            let metadata = std::fs::metadata(path).ok()?;
+            let modified = metadata
+                .modified()
+                .ok()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()?;
+            Some(FileStamp {
+                size: metadata.len(),
+                modified: modified.as_nanos() as u64,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn atomic_write_paths() {
+        let temp = temp_path(Path::new("a/foo.png"));
+        assert_eq!(temp.parent(), Some(Path::new("a")));
+        assert_eq!(temp.extension(), Some(OsStr::new("png")));
+        assert_ne!(temp, temp_path(Path::new("a/foo.png")));
+        assert!(is_temp_path(&temp));
+        assert!(is_temp_path(&temp_path(Path::new("a/foo"))));
+        assert!(is_temp_path(&temp_path(Path::new("a/foo.tmp-1-2.png"))));
+        assert!(is_temp_path(Path::new("a/.foo.tmp-12-3.png")));
+        assert!(!is_temp_path(Path::new("a/foo.tmp-12-3.png")));
+        assert!(!is_temp_path(Path::new("a/.foo.tmp.png")));
+        assert!(!is_temp_path(Path::new("a/.foo.tmp-12.png")));
+        assert!(!is_temp_path(Path::new("a/.foo.tmp-12-x.png")));
+        assert!(!is_temp_path(Path::new("a/.tmp-12-3.png")));
+        assert!(!is_temp_path(Path::new("a/.foo.png")));
+        assert!(!is_temp_path(Path::new("a/.gitignore")));
+        assert_eq!(
+            backup_path(Path::new("a/foo.png")),
+            Path::new("a/foo.png.bak")
+        );
+    }
+
+    #[test]
+    fn atomic_write() {
+        let folder = std::env::temp_dir().join("fyrox_resource_atomic_write");
+        let _ = std::fs::remove_dir_all(&folder);
+        std::fs::create_dir_all(&folder).unwrap();
+        let path = folder.join("data.bin");
+        let options = AtomicWriteOptions { keep_backup: true };
+
+        write_atomically_with_options(&path, &options, |path| std::fs::write(path, [1])).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), [1]);
+        assert!(!backup_path(&path).exists());
+
+        write_atomically_with_options(&path, &options, |path| std::fs::write(path, [2])).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), [2]);
+        assert_eq!(std::fs::read(backup_path(&path)).unwrap(), [1]);
+
+        // A failed write must keep the file intact and remove the temporary file.
+        let result = write_atomically_with_options(&path, &options, |path| {
+            std::fs::write(path, [3, 3])?;
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "Failed"))
+        });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), [2]);
+        assert!(std::fs::read_dir(&folder)
+            .unwrap()
+            .all(|entry| !is_temp_path(&entry.unwrap().path())));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,346 @@
+//! Virtual file system with mount points and overlays. See [`VirtualFileSystem`] docs for more
+//! info.
+
+use crate::{
+    core::{io::FileLoadError, parking_lot::RwLock},
+    io::{FsResourceIo, ResourceIo, ResourceIoFuture},
+};
+use fxhash::FxHashSet;
+use std::{
+    cmp::Reverse,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// A single mount point of [`VirtualFileSystem`].
+#[derive(Clone)]
+pub struct MountPoint {
+    /// Virtual prefix under which the root is visible. Empty prefix means that the root is mounted
+    /// at the top level of the virtual file system.
+    pub prefix: PathBuf,
+    /// Real root path, that will be passed to the resource IO of the mount point.
+    pub root: PathBuf,
+    /// Mount points with higher priority override the content of mount points with lower priority.
+    pub priority: i32,
+    /// Resource IO that is used to access the content of the root.
+    pub io: Arc<dyn ResourceIo>,
+}
+
+impl MountPoint {
+    fn to_real_path(&self, virtual_path: &Path) -> Option<PathBuf> {
+        virtual_path
+            .strip_prefix(&self.prefix)
+            .ok()
+            .map(|relative| self.root.join(relative))
+    }
+
+    fn to_virtual_path(&self, real_path: &Path) -> Option<PathBuf> {
+        real_path
+            .strip_prefix(&self.root)
+            .ok()
+            .map(|relative| self.prefix.join(relative))
+    }
+}
+
+/// Virtual file system allows you to mount multiple roots under virtual prefixes. Every path
+/// requested from the file system is resolved against the mount points in order of their priority,
+/// the first mount point that has the requested file wins. This way you can override content of
+/// your game without touching the game code - for example, mount `mods/` directory with higher
+/// priority than `data/` directory and every file in `mods/` will override a file with the same
+/// path in `data/`. Mount points with the same priority are checked in the order of mounting.
+///
+/// Every mount point has its own resource IO, which means that you can mix the file system with
+/// archives, encrypted packages, etc.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{io::vfs::VirtualFileSystem, manager::ResourceManager};
+/// use std::sync::Arc;
+///
+/// let vfs = VirtualFileSystem::new();
+/// vfs.mount_dir("data", "data", 0);
+/// // Everything in `mods/data` overrides the content of `data`.
+/// vfs.mount_dir("data", "mods/data", 1);
+///
+/// let resource_manager = ResourceManager::new();
+/// resource_manager.state().set_resource_io(Arc::new(vfs));
+/// ```
+#[derive(Default)]
+pub struct VirtualFileSystem {
+    mounts: RwLock<Vec<MountPoint>>,
+}
+
+impl VirtualFileSystem {
+    /// Creates new virtual file system without any mount points.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts the given root under the specified virtual prefix. The root is accessed using the
+    /// given resource IO.
+    pub fn mount(
+        &self,
+        prefix: impl AsRef<Path>,
+        root: impl AsRef<Path>,
+        priority: i32,
+        io: Arc<dyn ResourceIo>,
+    ) {
+        let mut mounts = self.mounts.write();
+        mounts.push(MountPoint {
+            prefix: normalize_path(prefix.as_ref()),
+            root: root.as_ref().to_path_buf(),
+            priority,
+            io,
+        });
+        // Stable sort keeps the order of mounting for mount points with the same priority.
+        mounts.sort_by_key(|m| Reverse(m.priority));
+    }
+
+    /// Mounts a directory of the file system under the specified virtual prefix.
+    pub fn mount_dir(&self, prefix: impl AsRef<Path>, root: impl AsRef<Path>, priority: i32) {
+        self.mount(prefix, root, priority, Arc::new(FsResourceIo))
+    }
+
+    /// Removes every mount point with the given prefix and root. Returns `true` if at least one
+    /// mount point was removed.
+    pub fn unmount(&self, prefix: impl AsRef<Path>, root: impl AsRef<Path>) -> bool {
+        let prefix = normalize_path(prefix.as_ref());
+        let mut mounts = self.mounts.write();
+        let count = mounts.len();
+        mounts.retain(|m| m.prefix != prefix || m.root != root.as_ref());
+        mounts.len() != count
+    }
+
+    /// Returns a copy of the current mount points, sorted by their priority (highest first).
+    pub fn mount_points(&self) -> Vec<MountPoint> {
+        self.mounts.read().clone()
+    }
+
+    /// Returns a list of real locations of the given virtual path in all mount points, sorted by
+    /// priority. The list is not checked for existence of the files.
+    fn candidates(&self, path: &Path) -> Vec<(MountPoint, PathBuf)> {
+        let path = normalize_path(path);
+        self.mounts
+            .read()
+            .iter()
+            .filter_map(|m| m.to_real_path(&path).map(|real| (m.clone(), real)))
+            .collect()
+    }
+
+    /// Searches for a mount point, that contains the given virtual path. Returns the mount point
+    /// and the real path of the file.
+    pub async fn resolve(&self, path: &Path) -> Option<(MountPoint, PathBuf)> {
+        for (mount, real_path) in self.candidates(path) {
+            if mount.io.exists(&real_path).await {
+                return Some((mount, real_path));
+            }
+        }
+        None
+    }
+
+    async fn collect_paths(&self, path: &Path, walk: bool) -> Result<Vec<PathBuf>, FileLoadError> {
+        let mut found = false;
+        let mut seen = FxHashSet::default();
+        let mut paths = Vec::new();
+        for (mount, real_path) in self.candidates(path) {
+            let iter = if walk {
+                mount.io.walk_directory(&real_path).await
+            } else {
+                mount.io.read_directory(&real_path).await
+            };
+            if let Ok(iter) = iter {
+                found = true;
+                for virtual_path in iter.filter_map(|p| mount.to_virtual_path(&p)) {
+                    if seen.insert(virtual_path.clone()) {
+                        paths.push(virtual_path);
+                    }
+                }
+            }
+        }
+        if found {
+            Ok(paths)
+        } else {
+            Err(FileLoadError::Custom(format!(
+                "Directory {} does not exist in any mount point!",
+                path.display()
+            )))
+        }
+    }
+}
+
+/// Lexically normalizes the given path by removing `.` and resolving `..` components. Virtual
+/// paths does not exist in the real file system, so they can't be canonicalized in a usual way.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+impl ResourceIo for VirtualFileSystem {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            match self.resolve(path).await {
+                Some((mount, real_path)) => mount.io.load_file(&real_path).await,
+                None => Err(FileLoadError::Custom(format!(
+                    "File {} does not exist in any mount point!",
+                    path.display()
+                ))),
+            }
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            let (mount, real_source) = self.resolve(source).await.ok_or_else(|| {
+                FileLoadError::Custom(format!(
+                    "File {} does not exist in any mount point!",
+                    source.display()
+                ))
+            })?;
+            // Files can be moved only within the mount point in which they're located.
+            let real_dest = mount.to_real_path(&normalize_path(dest)).ok_or_else(|| {
+                FileLoadError::Custom(format!(
+                    "Unable to move {} to {}, because the destination is outside of the mount \
+                        point of the source!",
+                    source.display(),
+                    dest.display()
+                ))
+            })?;
+            mount.io.move_file(&real_source, &real_dest).await
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(async move { Ok(normalize_path(path)) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(async move {
+            let paths = self.collect_paths(path, false).await?;
+            let iter: Box<dyn Iterator<Item = PathBuf> + Send> = Box::new(paths.into_iter());
+            Ok(iter)
+        })
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(async move {
+            let paths = self.collect_paths(path, true).await?;
+            let iter: Box<dyn Iterator<Item = PathBuf> + Send> = Box::new(paths.into_iter());
+            Ok(iter)
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.resolve(path).await.is_some() })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            match self.resolve(path).await {
+                Some((mount, real_path)) => mount.io.is_file(&real_path).await,
+                None => false,
+            }
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for (mount, real_path) in self.candidates(path) {
+                if mount.io.is_dir(&real_path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::futures::executor::block_on;
+
+    fn make_roots(name: &str) -> (PathBuf, PathBuf) {
+        let base = std::env::temp_dir().join(name);
+        let data = base.join("data");
+        let mods = base.join("mods");
+        let _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&data).unwrap();
+        std::fs::create_dir_all(&mods).unwrap();
+        std::fs::write(data.join("a.txt"), "data a").unwrap();
+        std::fs::write(data.join("b.txt"), "data b").unwrap();
+        std::fs::write(mods.join("a.txt"), "mods a").unwrap();
+        (data, mods)
+    }
+
+    #[test]
+    fn normalize_path_removes_dots() {
+        assert_eq!(
+            normalize_path(Path::new("./foo/bar/../baz.txt")),
+            PathBuf::from("foo/baz.txt")
+        );
+    }
+
+    #[test]
+    fn vfs_overlay_resolution() {
+        let (data, mods) = make_roots("fyrox_vfs_overlay_resolution");
+        let vfs = VirtualFileSystem::new();
+        vfs.mount_dir("assets", &data, 0);
+        vfs.mount_dir("assets", &mods, 1);
+
+        assert_eq!(
+            block_on(vfs.load_file(Path::new("assets/a.txt"))).unwrap(),
+            b"mods a"
+        );
+        assert_eq!(
+            block_on(vfs.load_file(Path::new("assets/b.txt"))).unwrap(),
+            b"data b"
+        );
+        assert!(block_on(vfs.load_file(Path::new("assets/c.txt"))).is_err());
+        assert!(block_on(vfs.exists(Path::new("assets/./b.txt"))));
+        assert!(block_on(vfs.is_file(Path::new("assets/a.txt"))));
+        assert!(block_on(vfs.is_dir(Path::new("assets"))));
+        assert!(!block_on(vfs.exists(Path::new("other/a.txt"))));
+
+        let mut entries = block_on(vfs.read_directory(Path::new("assets")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![PathBuf::from("assets/a.txt"), PathBuf::from("assets/b.txt")]
+        );
+
+        assert!(vfs.unmount("assets", &mods));
+        assert!(!vfs.unmount("assets", &mods));
+        assert_eq!(
+            block_on(vfs.load_file(Path::new("assets/a.txt"))).unwrap(),
+            b"data a"
+        );
+    }
+}
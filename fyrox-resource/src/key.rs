@@ -0,0 +1,154 @@
+//! Normalized resource path keys. See [`ResourcePathKey`] docs for more info.
+
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Normalized form of a resource path, that is used by the resource manager to identify
+/// resources. Normalization makes different spellings of the same path equal:
+///
+/// - Both `/` and `\` are treated as separators, the key always uses `/`.
+/// - Empty and `.` components are removed, so `a//./b` is the same as `a/b`.
+/// - Optionally, the key could be case-insensitive, so `Textures/foo.png` is the same as
+///   `textures/FOO.PNG`. This is useful on platforms with case-insensitive file systems (such as
+///   Windows), where both paths point to the same file.
+///
+/// Normalization is purely lexical, it never accesses the file system, so `..` components are
+/// kept as is.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::key::ResourcePathKey;
+/// use std::path::Path;
+///
+/// let key = ResourcePathKey::new(Path::new("Textures/foo.png"), true);
+/// assert_eq!(key, ResourcePathKey::new(Path::new("textures\\FOO.PNG"), true));
+/// assert_eq!(key.as_str(), "textures/foo.png");
+/// assert!(key.matches(Path::new("./TEXTURES//foo.png")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourcePathKey {
+    key: String,
+    case_insensitive: bool,
+}
+
+fn components(path: &str) -> impl Iterator<Item = &str> {
+    path.split(['/', '\\'])
+        .filter(|component| !component.is_empty() && *component != ".")
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with(['/', '\\'])
+}
+
+impl ResourcePathKey {
+    /// Creates a new key for the given path.
+    pub fn new(path: &Path, case_insensitive: bool) -> Self {
+        let path = path.to_string_lossy();
+        let mut key = String::with_capacity(path.len());
+        if is_absolute(&path) {
+            key.push('/');
+        }
+        for (i, component) in components(&path).enumerate() {
+            if i > 0 {
+                key.push('/');
+            }
+            if case_insensitive {
+                key.extend(component.chars().flat_map(char::to_lowercase));
+            } else {
+                key.push_str(component);
+            }
+        }
+        Self {
+            key,
+            case_insensitive,
+        }
+    }
+
+    /// Returns the normalized path as a string.
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns `true` if the key is case-insensitive.
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Checks whether the given path has the same key. Unlike comparison of two keys, this method
+    /// does not allocate.
+    pub fn matches(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy();
+        if is_absolute(&path) != is_absolute(&self.key) {
+            return false;
+        }
+        let mut key_components = components(&self.key);
+        let mut path_components = components(&path);
+        loop {
+            match (key_components.next(), path_components.next()) {
+                (None, None) => return true,
+                (Some(key), Some(component)) => {
+                    let equal = if self.case_insensitive {
+                        key.chars()
+                            .eq(component.chars().flat_map(char::to_lowercase))
+                    } else {
+                        key == component
+                    };
+                    if !equal {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    /// Checks whether the path of this key starts with the path of the given key. Only whole
+    /// components are compared, so `a/bc` does not start with `a/b`.
+    pub fn starts_with(&self, prefix: &ResourcePathKey) -> bool {
+        if is_absolute(&self.key) != is_absolute(&prefix.key) {
+            return false;
+        }
+        let mut key_components = components(&self.key);
+        components(&prefix.key).all(|component| key_components.next() == Some(component))
+    }
+}
+
+impl Display for ResourcePathKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resource_path_key() {
+        let key =
+            |path: &str, case_insensitive| ResourcePathKey::new(Path::new(path), case_insensitive);
+
+        assert_eq!(key("a\\b//./c.png", false).as_str(), "a/b/c.png");
+        assert_eq!(key("/a/b", false).as_str(), "/a/b");
+        assert_eq!(key("./a/../b", false).as_str(), "a/../b");
+        assert_ne!(key("A/b", false), key("a/b", false));
+        assert_eq!(key("A\\B", true), key("a/b", true));
+        assert_eq!(key("Ä/b", true).as_str(), "ä/b");
+
+        assert!(key("a/b", false).matches(Path::new("a\\b")));
+        assert!(!key("a/b", false).matches(Path::new("a/B")));
+        assert!(key("a/b", true).matches(Path::new("./A//B")));
+        assert!(!key("a/b", true).matches(Path::new("a/b/c")));
+        assert!(!key("a/b", true).matches(Path::new("/a/b")));
+
+        assert!(key("a/b/c.png", false).starts_with(&key("a\\b", false)));
+        assert!(key("a/b", false).starts_with(&key("a/b", false)));
+        assert!(key("a/b", false).starts_with(&key("", false)));
+        assert!(!key("a/bc", false).starts_with(&key("a/b", false)));
+        assert!(!key("a", false).starts_with(&key("a/b", false)));
+        assert!(!key("/a/b", false).starts_with(&key("a", false)));
+    }
+}
@@ -0,0 +1,298 @@
+//! Lazy resource is a resource handle, that starts loading on first access. See [`LazyResource`]
+//! docs for more info.
+
+use crate::{
+    core::{parking_lot::Mutex, reflect::prelude::*, visitor::prelude::*},
+    manager::ResourceManager,
+    Resource, TypedResourceData,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    path::{Path, PathBuf},
+};
+
+/// Lazy resource is a typed resource handle, that stores only a path of a resource and starts
+/// loading the resource the first time it is accessed using [`Self::request`]. Unlike
+/// [`Resource`], lazy resources are not requested on deserialization, so they are not loaded
+/// (and not waited for) together with a scene or any other object that holds them. This is useful
+/// for optional assets, that are rarely needed: rare visual effects, content of secret areas, etc.
+///
+/// Serialized form of lazy resource contains only the path, so the loaded resource is never
+/// saved. Reflection exposes only the path as well, changing the path makes the lazy resource
+/// to request the resource at the new path on next access.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{lazy::LazyResource, manager::ResourceManager, TypedResourceData};
+///
+/// fn on_secret_area_entered<T: TypedResourceData>(
+///     effect: &LazyResource<T>,
+///     resource_manager: &ResourceManager,
+/// ) {
+///     // The effect starts loading only now.
+///     if let Some(effect) = effect.request(resource_manager) {
+///         // Use the effect.
+///     }
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    path: PathBuf,
+    #[reflect(hidden)]
+    resource: Mutex<Option<Resource<T>>>,
+}
+
+impl<T> Default for LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    fn default() -> Self {
+        Self {
+            path: Default::default(),
+            resource: Default::default(),
+        }
+    }
+}
+
+impl<T> Clone for LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            resource: Mutex::new(self.resource.lock().clone()),
+        }
+    }
+}
+
+impl<T> Debug for LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LazyResource({}, requested: {})",
+            self.path.display(),
+            self.is_requested()
+        )
+    }
+}
+
+impl<T> PartialEq for LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<T> Visit for LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.path.visit("Path", &mut region)?;
+
+        if region.is_reading() {
+            *self.resource.get_mut() = None;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> LazyResource<T>
+where
+    T: TypedResourceData,
+{
+    /// Creates new lazy resource with the given path. The resource won't be loaded until it is
+    /// requested.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            resource: Default::default(),
+        }
+    }
+
+    /// Returns path of the resource.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Sets new path of the resource. Previously requested resource (if any) is released.
+    pub fn set_path(&mut self, path: impl Into<PathBuf>) {
+        self.path = path.into();
+        *self.resource.get_mut() = None;
+    }
+
+    /// Returns the resource, requesting it from the given resource manager on first access. Every
+    /// next call returns the same resource without accessing the resource manager. Returns `None`
+    /// if the path is empty or the actual type of the resource does not match `T`.
+    pub fn request(&self, resource_manager: &ResourceManager) -> Option<Resource<T>> {
+        if self.path.as_os_str().is_empty() {
+            return None;
+        }
+
+        let mut resource = self.resource.lock();
+        if let Some(resource) = resource.as_ref() {
+            // The path could be changed using reflection.
+            if resource.path() == self.path {
+                return Some(resource.clone());
+            }
+        }
+
+        *resource = resource_manager.try_request::<T>(&self.path);
+        resource.clone()
+    }
+
+    /// Returns the resource if it was requested previously, `None` - otherwise. This method never
+    /// starts loading.
+    pub fn get(&self) -> Option<Resource<T>> {
+        self.resource.lock().clone()
+    }
+
+    /// Returns `true` if the resource was requested.
+    pub fn is_requested(&self) -> bool {
+        self.resource.lock().is_some()
+    }
+
+    /// Releases the requested resource (if any), so it could be unloaded by the resource manager
+    /// when it is not used anymore. Next access will request the resource again.
+    pub fn release(&mut self) {
+        *self.resource.get_mut() = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{
+            futures::executor::block_on,
+            uuid::{uuid, Uuid},
+            TypeUuidProvider,
+        },
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        untyped::UntypedResource,
+        ResourceData,
+    };
+    use std::{any::Any, error::Error, sync::Arc};
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct LazyStub {
+        path: PathBuf,
+    }
+
+    impl TypeUuidProvider for LazyStub {
+        fn type_uuid() -> Uuid {
+            uuid!("8f1d2c3b-4a5e-4f60-9b7c-d8e9f0a1b2c3")
+        }
+    }
+
+    impl ResourceData for LazyStub {
+        fn path(&self) -> &Path {
+            &self.path
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+    }
+
+    struct LazyStubLoader;
+
+    impl ResourceLoader for LazyStubLoader {
+        fn extensions(&self) -> &[&str] {
+            &["lazy"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <LazyStub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            _event_broadcaster: ResourceEventBroadcaster,
+            _reload: bool,
+            _io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                let path = resource.path();
+                resource.commit_ok(LazyStub { path });
+            })
+        }
+    }
+
+    #[test]
+    fn lazy_resource_request() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(LazyStubLoader);
+
+        let lazy = LazyResource::<LazyStub>::new("effects/rare.lazy");
+        assert!(!lazy.is_requested());
+        assert!(lazy.get().is_none());
+        assert_eq!(manager.state().len(), 0);
+
+        let resource = lazy.request(&manager).unwrap();
+        assert!(lazy.is_requested());
+        assert_eq!(lazy.request(&manager), Some(resource.clone()));
+        assert_eq!(lazy.get(), Some(resource.clone()));
+        assert!(block_on(resource).is_ok());
+
+        assert!(LazyResource::<LazyStub>::default()
+            .request(&manager)
+            .is_none());
+    }
+
+    #[test]
+    fn lazy_resource_visit() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(LazyStubLoader);
+
+        let mut lazy = LazyResource::<LazyStub>::new("effects/rare.lazy");
+        lazy.request(&manager);
+
+        let mut visitor = Visitor::new();
+        lazy.visit("Lazy", &mut visitor).unwrap();
+        let mut data = Vec::new();
+        visitor.save_binary_to_memory(&mut data).unwrap();
+
+        let mut loaded = LazyResource::<LazyStub>::default();
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        loaded.visit("Lazy", &mut visitor).unwrap();
+        assert_eq!(loaded.path(), Path::new("effects/rare.lazy"));
+        assert!(!loaded.is_requested());
+    }
+}
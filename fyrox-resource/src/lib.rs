@@ -5,20 +5,23 @@
 
 use crate::{
     core::{
-        parking_lot::MutexGuard,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
         visitor::prelude::*,
         TypeUuidProvider,
     },
+    event::{ResourceCallbackHandle, ResourceCallbackKind},
+    lock::LockedState,
+    manager::ResourceManager,
+    protection::ReadOnlyError,
     state::ResourceState,
+    streaming::StreamableResourceData,
     untyped::UntypedResource,
 };
-use fxhash::FxHashSet;
 use std::{
     any::Any,
     error::Error,
-    fmt::{Debug, Formatter},
+    fmt::{Debug, Display, Formatter},
     future::Future,
     hash::{Hash, Hasher},
     marker::PhantomData,
@@ -26,22 +29,45 @@ use std::{
     path::{Path, PathBuf},
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use crate::state::LoadError;
+pub use collect::{collect_used_resources, remap_used_resources};
 pub use fyrox_core as core;
 
+pub mod collect;
 pub mod constructor;
+mod dedup;
+pub mod embedded;
 pub mod entry;
 pub mod event;
 pub mod graph;
+pub mod import;
 pub mod io;
+pub mod key;
+pub mod lazy;
 pub mod loader;
+pub mod locale;
+pub mod lock;
 pub mod manager;
+pub mod manifest;
+pub mod migration;
 pub mod options;
+pub mod platform;
+pub mod prefetch;
+pub mod protection;
+pub mod quota;
+pub mod registry;
+pub mod retry;
+pub mod saver;
 pub mod state;
-mod task;
+pub mod stats;
+pub mod streaming;
+pub mod task;
+pub mod tracker;
 pub mod untyped;
+pub mod validation;
 
 /// Type UUID of texture resource. It is defined here to load old versions of resources.
 pub const TEXTURE_RESOURCE_UUID: Uuid = uuid!("02c23a44-55fa-411a-bc39-eb7a5eadf15c");
@@ -74,15 +100,62 @@ pub trait ResourceData: 'static + Debug + Visit + Send + Reflect {
     /// Returns true if the resource data was generated procedurally, not taken from a file.
     fn is_embedded(&self) -> bool;
 
-    /// Saves the resource data a file at the specified path. By default, this method returns an
-    /// error that tells that saving functionality is not implemented. This method is free to
-    /// decide how the resource data is saved. This is needed, because there are multiple formats
-    /// that defines various kinds of resources. For example, a rectangular texture could be saved
-    /// into a whole bunch of formats, such as png, bmp, tga, jpg etc, but in the engine it is single
-    /// Texture resource. In any case, produced file should be compatible with a respective resource
-    /// loader.
-    fn save(&mut self, #[allow(unused_variables)] path: &Path) -> Result<(), Box<dyn Error>> {
-        Err("Saving is not supported!".to_string().into())
+    /// Saves the resource data a file at the specified path. By default, this method uses a saver
+    /// registered for the data type and the extension of the path (see
+    /// [`saver::ResourceSaversContainer`]) and returns an error that tells that saving functionality
+    /// is not implemented if there's no such saver. This method is free to decide how the resource
+    /// data is saved. This is needed, because there are multiple formats that defines various kinds
+    /// of resources. For example, a rectangular texture could be saved into a whole bunch of formats,
+    /// such as png, bmp, tga, jpg etc, but in the engine it is single Texture resource. In any case,
+    /// produced file should be compatible with a respective resource loader. The default
+    /// implementation returns [`protection::ReadOnlyError`] if the data belongs to a resource with
+    /// the read-only flag (see [`UntypedResource::is_read_only`]). Custom implementations may skip
+    /// this check, so use [`ResourceDataRef::try_save`] or
+    /// [`manager::ResourceManager::save_resource`] to save shared content.
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if protection::has_read_only_flag_by_path(self.path()) {
+            return Err(Box::new(ReadOnlyError {
+                path: self.path().to_path_buf(),
+                read_only_mode: false,
+            }));
+        }
+        // Savers could save other resources, so the lock must be recursive.
+        saver::resource_savers().read_recursive().save(
+            self.type_uuid(),
+            ResourceData::as_any(self),
+            path,
+        )
+    }
+
+    /// Marks the resource data as modified, which means that it differs from its file and must be
+    /// saved to keep the changes (see [`manager::ResourceManager::save_all_modified`]). By default,
+    /// this method does nothing, which means that the resource data does not track its modifications.
+    /// Implementations should reset the flag in [`Self::save`].
+    fn mark_dirty(&mut self) {}
+
+    /// Returns `true` if the resource data was marked as modified (see [`Self::mark_dirty`]) and
+    /// wasn't saved since then. By default, this method always returns `false`.
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    /// Returns estimated amount of memory (in bytes) used by the resource data. The estimation does
+    /// not need to be precise, it should include only large allocations (pixels of a texture,
+    /// samples of a sound, etc.), since it is used only for memory usage reports (see
+    /// [`stats::ResourceMemoryReport`]). By default, this method returns size of the data type.
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
+    /// Returns `self` as streamable resource data, if the data could be loaded in stages (see
+    /// [`StreamableResourceData`] docs for more info). By default, this method returns `None`.
+    fn as_streamable(&self) -> Option<&dyn StreamableResourceData> {
+        None
+    }
+
+    /// Returns `self` as mutable streamable resource data. See [`Self::as_streamable`] for more info.
+    fn as_streamable_mut(&mut self) -> Option<&mut dyn StreamableResourceData> {
+        None
     }
 }
 
@@ -95,16 +168,92 @@ pub trait TypedResourceData: ResourceData + Default + TypeUuidProvider {}
 impl<T> TypedResourceData for T where T: ResourceData + Default + TypeUuidProvider {}
 
 /// A trait for resource load error.
-pub trait ResourceLoadError: 'static + Debug + Send + Sync {}
+pub trait ResourceLoadError: 'static + Debug + Send + Sync {
+    /// Returns the error as [`Any`], it could be used to find out the actual type of the error.
+    /// Every sized error type has an automatic implementation, the default one (used by unsized
+    /// types) returns the unit type.
+    fn error_as_any(&self) -> &dyn Any {
+        &()
+    }
+}
+
+impl<T> ResourceLoadError for T
+where
+    T: 'static + Debug + Send + Sync,
+{
+    fn error_as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// An error that may occur during asynchronous saving of a resource (see [`Resource::save_async`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceSaveError {
+    /// The resource is not loaded (it is either still loading or failed to load), so there's
+    /// nothing to save.
+    NotLoaded,
+    /// The resource data failed to save itself. Contains textual description of the error.
+    Save(String),
+    /// The worker thread has stopped before the saving was finished.
+    Interrupted,
+    /// The resource is read-only (see [`protection::ReadOnlyResources`]) or resources are in
+    /// read-only mode (see [`manager::ResourceManagerState::set_read_only`]).
+    ReadOnly,
+}
+
+impl Display for ResourceSaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceSaveError::NotLoaded => {
+                write!(f, "The resource is not loaded and cannot be saved.")
+            }
+            ResourceSaveError::Save(v) => {
+                write!(f, "Unable to save the resource. Reason: {v}")
+            }
+            ResourceSaveError::Interrupted => {
+                write!(f, "Resource saving was interrupted.")
+            }
+            ResourceSaveError::ReadOnly => {
+                write!(f, "The resource is read-only and cannot be saved.")
+            }
+        }
+    }
+}
+
+impl Error for ResourceSaveError {}
+
+/// An error that may occur when an untyped resource is cast to a typed resource of a wrong type (see
+/// [`UntypedResource::cast`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceCastError {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// Type UUID of the requested resource type.
+    pub expected: Uuid,
+    /// Actual type UUID of the resource.
+    pub actual: Uuid,
+}
+
+impl Display for ResourceCastError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unable to cast resource {} to type {}, because its actual type is {}.",
+            self.path.display(),
+            self.expected,
+            self.actual
+        )
+    }
+}
 
-impl<T> ResourceLoadError for T where T: 'static + Debug + Send + Sync {}
+impl Error for ResourceCastError {}
 
 /// Provides typed access to a resource state.
 pub struct ResourceStateGuard<'a, T>
 where
     T: TypedResourceData,
 {
-    guard: MutexGuard<'a, ResourceState>,
+    guard: LockedState<'a>,
     phantom: PhantomData<T>,
 }
 
@@ -314,7 +463,8 @@ where
         self.untyped
     }
 
-    /// Locks internal mutex provides access to the state.
+    /// Locks internal mutex provides access to the state. See [`lock::LockDiagnostics`] docs if the
+    /// call blocks for too long.
     #[inline]
     pub fn state(&self) -> ResourceStateGuard<'_, T> {
         ResourceStateGuard {
@@ -326,20 +476,31 @@ where
     /// Tries to lock internal mutex provides access to the state.
     #[inline]
     pub fn try_acquire_state(&self) -> Option<ResourceStateGuard<'_, T>> {
-        self.untyped.0.try_lock().map(|guard| ResourceStateGuard {
+        lock::try_lock_state_for(&self.untyped.0, Duration::ZERO).map(|guard| ResourceStateGuard {
             guard,
             phantom: Default::default(),
         })
     }
 
-    fn state_inner(&self) -> MutexGuard<'_, ResourceState> {
-        self.untyped.0.lock()
+    /// Tries to lock internal mutex for the given amount of time and provides access to the state.
+    /// Returns `None` if the mutex is still locked after the timeout.
+    #[inline]
+    pub fn try_acquire_state_for(&self, timeout: Duration) -> Option<ResourceStateGuard<'_, T>> {
+        lock::try_lock_state_for(&self.untyped.0, timeout).map(|guard| ResourceStateGuard {
+            guard,
+            phantom: Default::default(),
+        })
     }
 
-    /// Returns true if the resource is still loading.
+    fn state_inner(&self) -> LockedState<'_> {
+        lock::lock_state(&self.untyped.0)
+    }
+
+    /// Returns true if the resource is still loading. See [`UntypedResource::is_loading`] for more
+    /// info.
     #[inline]
     pub fn is_loading(&self) -> bool {
-        matches!(*self.state_inner(), ResourceState::Pending { .. })
+        self.untyped.is_loading()
     }
 
     /// Returns true if the resource is fully loaded and ready for use.
@@ -369,7 +530,7 @@ where
     /// Returns path of the resource.
     #[inline]
     pub fn path(&self) -> PathBuf {
-        self.untyped.0.lock().path().to_path_buf()
+        self.untyped.lock().path().to_path_buf()
     }
 
     /// Sets a new path of the resource.
@@ -387,6 +548,10 @@ where
     /// like this `resource.await?.data_ref()`. Every resource implements Future trait
     /// and it returns Result, so if you'll await future then you'll get Result, so
     /// call to `data_ref` will be fine.
+    ///
+    /// Mutable access to the data via `DerefMut` does not check the read-only protection, use
+    /// [`ResourceDataRef::try_data_mut`] and [`ResourceDataRef::try_save`] to modify and save
+    /// shared content (see [`protection::ReadOnlyResources`]).
     #[inline]
     pub fn data_ref(&self) -> ResourceDataRef<'_, T> {
         ResourceDataRef {
@@ -394,6 +559,152 @@ where
             phantom: Default::default(),
         }
     }
+
+    /// Attaches a callback, that is called once when the resource is fully loaded (or right away,
+    /// if the resource is already loaded). It is useful to do some work (for example, build
+    /// derived data) as soon as the resource is loaded, without polling [`Self::is_ok`]. The
+    /// callback is called on a thread, that loads the resource. See
+    /// [`event::ResourceEventBroadcaster::add_resource_callback`] for more info.
+    pub fn on_loaded<F>(
+        &self,
+        resource_manager: &ResourceManager,
+        mut callback: F,
+    ) -> ResourceCallbackHandle
+    where
+        F: FnMut(&Resource<T>) + Send + 'static,
+    {
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        event_broadcaster.add_resource_callback(
+            &self.untyped,
+            ResourceCallbackKind::Loaded,
+            move |resource| callback(&Resource::from(resource.clone())),
+        )
+    }
+
+    /// Attaches a callback, that is called every time the resource is reloaded (for example, when
+    /// its file was changed and hot reloading is enabled). It is useful to re-upload data to GPU or
+    /// to rebuild derived data. The callback is called on a thread, that loads the resource. See
+    /// [`event::ResourceEventBroadcaster::add_resource_callback`] for more info.
+    pub fn on_reloaded<F>(
+        &self,
+        resource_manager: &ResourceManager,
+        mut callback: F,
+    ) -> ResourceCallbackHandle
+    where
+        F: FnMut(&Resource<T>) + Send + 'static,
+    {
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        event_broadcaster.add_resource_callback(
+            &self.untyped,
+            ResourceCallbackKind::Reloaded,
+            move |resource| callback(&Resource::from(resource.clone())),
+        )
+    }
+
+    /// Attaches a callback, that is called every time the resource data is replaced in-place (see
+    /// [`Self::replace_data`]). See [`event::ResourceEventBroadcaster::add_resource_callback`] for
+    /// more info.
+    pub fn on_data_replaced<F>(
+        &self,
+        resource_manager: &ResourceManager,
+        mut callback: F,
+    ) -> ResourceCallbackHandle
+    where
+        F: FnMut(&Resource<T>) + Send + 'static,
+    {
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        event_broadcaster.add_resource_callback(
+            &self.untyped,
+            ResourceCallbackKind::DataReplaced,
+            move |resource| callback(&Resource::from(resource.clone())),
+        )
+    }
+
+    /// Replaces the resource data in-place and returns the old data, every user of the resource
+    /// will see the new data. The new data is returned back, if the resource is not fully loaded.
+    /// See [`UntypedResource::replace_data`] for more info.
+    pub fn replace_data(&self, new_data: T, resource_manager: &ResourceManager) -> Result<T, T> {
+        self.untyped
+            .replace_data(new_data, resource_manager)
+            .map(|old_data| {
+                *Reflect::into_any(old_data)
+                    .downcast::<T>()
+                    .expect("Type mismatch!")
+            })
+    }
+
+    /// Reloads the resource from its source. See [`UntypedResource::reload`] for more info.
+    pub fn reload(&self, resource_manager: &ResourceManager) {
+        self.untyped.reload(resource_manager)
+    }
+
+    /// Drops the resource data, while keeping the resource valid. Returns `true` if the data was
+    /// dropped. See [`UntypedResource::unload`] for more info.
+    pub fn unload(&self, resource_manager: &ResourceManager) -> bool {
+        self.untyped.unload(resource_manager)
+    }
+
+    /// Returns `true` if the resource has the read-only flag. See [`UntypedResource::is_read_only`]
+    /// for more info.
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.untyped.is_read_only()
+    }
+
+    /// Saves the resource data to the given path on a worker thread and returns a future that
+    /// resolves when the saving is finished. It is useful to save huge resources (such as lightmaps
+    /// or terrains) without blocking the calling thread. The data is copied while the resource is
+    /// locked, then the copy is saved without holding the lock, so other threads could keep using
+    /// the resource. The resource itself is not marked as saved (see [`ResourceData::is_dirty`]),
+    /// because it could be modified while the copy is being written. On WebAssembly there's no
+    /// worker threads, so the data is saved immediately. The future resolves to
+    /// [`ResourceSaveError::ReadOnly`] if the resource cannot be saved, see
+    /// [`manager::ResourceManagerState::check_writable`].
+    pub fn save_async(
+        &self,
+        path: impl Into<PathBuf>,
+        resource_manager: &ResourceManager,
+    ) -> impl Future<Output = Result<(), ResourceSaveError>>
+    where
+        T: Clone,
+    {
+        let path = path.into();
+        let writable = !self.untyped.is_read_only()
+            && resource_manager
+                .state()
+                .check_writable(&self.untyped)
+                .is_ok();
+        let snapshot = match &*self.untyped.lock() {
+            ResourceState::Ok(_) if !writable => Err(ResourceSaveError::ReadOnly),
+            ResourceState::Ok(data) => Ok(ResourceData::as_any(&**data)
+                .downcast_ref::<T>()
+                .expect("Type mismatch!")
+                .clone()),
+            _ => Err(ResourceSaveError::NotLoaded),
+        };
+        let (sender, receiver) = core::futures::channel::oneshot::channel();
+
+        let job = move || {
+            let result = snapshot.and_then(|mut data| {
+                data.save(&path)
+                    .map_err(|e| ResourceSaveError::Save(e.to_string()))
+            });
+            // The receiver could be dropped if nobody is interested in the result.
+            let _ = sender.send(result);
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(job);
+
+        #[cfg(target_arch = "wasm32")]
+        job();
+
+        async move {
+            receiver
+                .await
+                .unwrap_or(Err(ResourceSaveError::Interrupted))
+        }
+    }
 }
 
 impl<T> Default for Resource<T>
@@ -426,6 +737,12 @@ impl<T> From<UntypedResource> for Resource<T>
 where
     T: TypedResourceData,
 {
+    /// Converts an untyped resource to a typed one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type of the resource does not match `T`. Use [`UntypedResource::try_cast`] or
+    /// [`UntypedResource::cast`] to convert resources of an unknown type.
     #[inline]
     fn from(untyped: UntypedResource) -> Self {
         assert_eq!(untyped.type_uuid(), <T as TypeUuidProvider>::type_uuid());
@@ -461,12 +778,12 @@ where
     }
 }
 
-#[doc(hidden)]
+/// A locked reference to the data of a resource, see [`Resource::data_ref`].
 pub struct ResourceDataRef<'a, T>
 where
     T: TypedResourceData,
 {
-    guard: MutexGuard<'a, ResourceState>,
+    guard: LockedState<'a>,
     phantom: PhantomData<T>,
 }
 
@@ -522,11 +839,57 @@ where
     }
 }
 
+impl<'a, T> ResourceDataRef<'a, T>
+where
+    T: TypedResourceData,
+{
+    fn check_writable(&self) -> Result<(), ReadOnlyError> {
+        if protection::has_read_only_flag(self.guard.key()) {
+            Err(ReadOnlyError {
+                path: self.guard.path().to_path_buf(),
+                read_only_mode: false,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns mutable reference to the resource data or an error if the resource has the
+    /// read-only flag (see [`UntypedResource::is_read_only`]). Unlike `DerefMut` implementation,
+    /// that panics in this case, this method should be used by code, that could modify shared
+    /// content (editors, tools, etc.).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the resource is not loaded, just like `Deref` implementation.
+    pub fn try_data_mut(&mut self) -> Result<&mut T, ReadOnlyError> {
+        self.check_writable()?;
+        Ok(&mut **self)
+    }
+
+    /// Saves the resource data to the given path. Unlike [`ResourceData::save`], it returns
+    /// [`ResourceSaveError::ReadOnly`] if the resource has the read-only flag (see
+    /// [`UntypedResource::is_read_only`]) regardless of the implementation of the data.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the resource is not loaded, just like `Deref` implementation.
+    pub fn try_save(&mut self, path: &Path) -> Result<(), ResourceSaveError> {
+        self.check_writable()
+            .map_err(|_| ResourceSaveError::ReadOnly)?;
+        ResourceData::save(&mut **self, path)
+            .map_err(|err| ResourceSaveError::Save(err.to_string()))
+    }
+}
+
 impl<'a, T> DerefMut for ResourceDataRef<'a, T>
 where
     T: TypedResourceData,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        if let Err(err) = self.check_writable() {
+            panic!("{err} Use `try_data_mut` to handle the protection.")
+        }
         match *self.guard {
             ResourceState::Pending { ref path, .. } => {
                 panic!(
@@ -547,100 +910,215 @@ where
     }
 }
 
-/// Collects all resources used by a given entity. Internally, it uses reflection to iterate over
-/// each field of every descendant sub-object of the entity. This function could be used to collect
-/// all resources used by an object, which could be useful if you're building a resource dependency
-/// analyzer.
-pub fn collect_used_resources(
-    entity: &dyn Reflect,
-    resources_collection: &mut FxHashSet<UntypedResource>,
-) {
-    #[inline(always)]
-    fn type_is<T: Reflect>(entity: &dyn Reflect) -> bool {
-        let mut types_match = false;
-        entity.downcast_ref::<T>(&mut |v| {
-            types_match = v.is_some();
-        });
-        types_match
-    }
-
-    // Skip potentially large chunks of numeric data, that definitely cannot contain any resources.
-    // TODO: This is a brute-force solution which does not include all potential types with plain
-    // data.
-    let mut finished = type_is::<Vec<u8>>(entity)
-        || type_is::<Vec<u16>>(entity)
-        || type_is::<Vec<u32>>(entity)
-        || type_is::<Vec<u64>>(entity)
-        || type_is::<Vec<i8>>(entity)
-        || type_is::<Vec<i16>>(entity)
-        || type_is::<Vec<i32>>(entity)
-        || type_is::<Vec<i64>>(entity)
-        || type_is::<Vec<f32>>(entity)
-        || type_is::<Vec<f64>>(entity);
-
-    if finished {
-        return;
-    }
-
-    entity.downcast_ref::<UntypedResource>(&mut |v| {
-        if let Some(resource) = v {
-            resources_collection.insert(resource.clone());
-            finished = true;
-        }
-    });
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::futures::executor::block_on;
 
-    if finished {
-        return;
+    #[derive(Debug, Default, Clone, Reflect, Visit)]
+    struct SaveStub {
+        saved_to: Option<PathBuf>,
     }
 
-    entity.as_array(&mut |array| {
-        if let Some(array) = array {
-            for i in 0..array.reflect_len() {
-                if let Some(item) = array.reflect_index(i) {
-                    collect_used_resources(item, resources_collection)
-                }
-            }
+    impl TypeUuidProvider for SaveStub {
+        fn type_uuid() -> Uuid {
+            uuid!("5e0f7a2c-3b9d-4c61-8a47-d2e91f6b0c38")
+        }
+    }
 
-            finished = true;
+    impl ResourceData for SaveStub {
+        fn path(&self) -> &Path {
+            Path::new("stub.txt")
         }
-    });
 
-    if finished {
-        return;
-    }
+        fn set_path(&mut self, _path: PathBuf) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
 
-    entity.as_inheritable_variable(&mut |inheritable| {
-        if let Some(inheritable) = inheritable {
-            collect_used_resources(inheritable.inner_value_ref(), resources_collection);
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
 
-            finished = true;
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
         }
-    });
 
-    if finished {
-        return;
-    }
+        fn is_embedded(&self) -> bool {
+            false
+        }
 
-    entity.as_hash_map(&mut |hash_map| {
-        if let Some(hash_map) = hash_map {
-            for i in 0..hash_map.reflect_len() {
-                if let Some((key, value)) = hash_map.reflect_get_at(i) {
-                    collect_used_resources(key, resources_collection);
-                    collect_used_resources(value, resources_collection);
-                }
+        fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+            if path.as_os_str().is_empty() {
+                return Err("Empty path!".to_string().into());
             }
-
-            finished = true;
+            self.saved_to = Some(path.to_path_buf());
+            Ok(())
         }
-    });
+    }
 
-    if finished {
-        return;
+    #[test]
+    fn resource_save_async() {
+        let resource_manager = ResourceManager::new();
+        let resource = Resource::new_ok(SaveStub::default());
+        assert_eq!(
+            block_on(resource.save_async("foo.txt", &resource_manager)),
+            Ok(())
+        );
+        // A copy of the data is saved.
+        assert!(resource.data_ref().saved_to.is_none());
+
+        assert!(matches!(
+            block_on(resource.save_async("", &resource_manager)),
+            Err(ResourceSaveError::Save(_))
+        ));
+
+        let pending = Resource::<SaveStub>::from(UntypedResource::new_pending(
+            "bar.txt".into(),
+            <SaveStub as TypeUuidProvider>::type_uuid(),
+        ));
+        assert_eq!(
+            block_on(pending.save_async("bar.txt", &resource_manager)),
+            Err(ResourceSaveError::NotLoaded)
+        );
     }
 
-    entity.fields(&mut |fields| {
-        for field in fields {
-            collect_used_resources(*field, resources_collection);
-        }
-    })
+    #[test]
+    fn resource_replace_data() {
+        let resource_manager = ResourceManager::new();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        resource_manager.state().event_broadcaster.add(sender);
+
+        let resource = Resource::new_ok(SaveStub::default());
+        let user = resource.clone();
+
+        let replaced = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = replaced.clone();
+        resource.on_data_replaced(&resource_manager, move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        let new_data = SaveStub {
+            saved_to: Some("bar.txt".into()),
+        };
+        let old_data = resource.replace_data(new_data, &resource_manager).unwrap();
+        assert_eq!(old_data.saved_to, None);
+        assert_eq!(
+            user.data_ref().saved_to.as_deref(),
+            Some(Path::new("bar.txt"))
+        );
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(event::ResourceEvent::DataReplaced(r)) if r == resource.clone().into_untyped()
+        ));
+
+        resource
+            .replace_data(SaveStub::default(), &resource_manager)
+            .unwrap();
+        assert_eq!(replaced.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(receiver.try_recv().is_ok());
+
+        let pending = Resource::<SaveStub>::from(UntypedResource::new_pending(
+            "baz.txt".into(),
+            <SaveStub as TypeUuidProvider>::type_uuid(),
+        ));
+        assert!(pending
+            .replace_data(SaveStub::default(), &resource_manager)
+            .is_err());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn read_only_resource() {
+        let resource_manager = ResourceManager::new();
+        let other = ResourceManager::new();
+        let resource = Resource::new_ok(SaveStub::default());
+        let untyped = resource.clone().into_untyped();
+        resource_manager
+            .state()
+            .read_only_resources_mut()
+            .protect(&untyped);
+        assert!(resource_manager.state().check_writable(&untyped).is_err());
+        // The protection belongs to the manager.
+        assert!(other.state().check_writable(&untyped).is_ok());
+        // The manager flags the resource, so the resource rejects writes on its own.
+        assert!(resource.is_read_only());
+        assert!(resource.data_ref().try_data_mut().is_err());
+        assert_eq!(
+            resource.data_ref().try_save(Path::new("foo.txt")),
+            Err(ResourceSaveError::ReadOnly)
+        );
+        assert_eq!(
+            untyped.try_save(Path::new("foo.txt")),
+            Err(ResourceSaveError::ReadOnly)
+        );
+
+        assert_eq!(
+            block_on(resource.save_async("foo.txt", &resource_manager)),
+            Err(ResourceSaveError::ReadOnly)
+        );
+        assert!(resource
+            .replace_data(SaveStub::default(), &resource_manager)
+            .is_err());
+        assert_eq!(
+            resource_manager.save_resource(&untyped),
+            Err(ResourceSaveError::ReadOnly)
+        );
+        assert!(resource.data_ref().saved_to.is_none());
+        // Plain mutable access panics.
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            resource.data_ref().saved_to = None;
+        }))
+        .is_err());
+
+        resource_manager
+            .state()
+            .read_only_resources_mut()
+            .unprotect(&untyped);
+        assert!(resource_manager.state().check_writable(&untyped).is_ok());
+        assert!(!resource.is_read_only());
+        resource.data_ref().try_data_mut().unwrap().saved_to = None;
+        assert_eq!(resource.data_ref().try_save(Path::new("baz.txt")), Ok(()));
+        assert_eq!(
+            resource.data_ref().saved_to.as_deref(),
+            Some(Path::new("baz.txt"))
+        );
+        assert!(resource_manager.save_resource(&untyped).is_ok());
+        assert_eq!(
+            resource.data_ref().saved_to.as_deref(),
+            Some(Path::new("stub.txt"))
+        );
+        assert_eq!(
+            block_on(resource.save_async("bar.txt", &resource_manager)),
+            Ok(())
+        );
+    }
+
+    #[derive(Debug, Default, Reflect)]
+    struct RemapStub {
+        resource: Option<UntypedResource>,
+        resources: Vec<Resource<SaveStub>>,
+    }
+
+    #[test]
+    fn remap_used_resources_replaces_matching() {
+        let uuid = <SaveStub as TypeUuidProvider>::type_uuid();
+        let mut stub = RemapStub {
+            resource: Some(UntypedResource::new_pending("a.txt".into(), uuid)),
+            resources: vec![
+                Resource::from(UntypedResource::new_pending("a.txt".into(), uuid)),
+                Resource::from(UntypedResource::new_pending("b.txt".into(), uuid)),
+            ],
+        };
+
+        let replacement = UntypedResource::new_pending("c.txt".into(), uuid);
+        remap_used_resources(&mut stub, &mut |resource| {
+            (resource.path() == Path::new("a.txt")).then(|| replacement.clone())
+        });
+
+        assert_eq!(stub.resource, Some(replacement.clone()));
+        assert_eq!(stub.resources[0].path(), Path::new("c.txt"));
+        assert_eq!(stub.resources[1].path(), Path::new("b.txt"));
+    }
 }
@@ -2,9 +2,18 @@
 
 use crate::{
     core::uuid::Uuid, event::ResourceEventBroadcaster, io::ResourceIo, options::BaseImportOptions,
-    UntypedResource,
+    state::ResourceState, UntypedResource,
+};
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::VecDeque,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
 };
-use std::{any::Any, future::Future, path::PathBuf, pin::Pin, sync::Arc};
 
 #[cfg(target_arch = "wasm32")]
 #[doc(hidden)]
@@ -98,10 +107,22 @@ pub type BoxedLoaderFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 pub type BoxedImportOptionsLoaderFuture =
     Pin<Box<dyn Future<Output = Option<Box<dyn BaseImportOptions>>>>>;
 
+struct LoaderEntry {
+    loader: Box<dyn ResourceLoader>,
+    priority: i32,
+}
+
 /// Container for resource loaders.
+///
+/// There could be multiple loaders for the same file extension, this is useful when a single
+/// extension (for example `.json` or `.bin`) could contain various kinds of assets. Every loader has
+/// a priority and the resource manager tries the loaders in the order of their priorities (higher
+/// priority goes first, loaders with the same priority are tried in registration order). If a
+/// loader fails to load a resource, the next one is tried and only the error of the last loader is
+/// committed to the resource. See [`Self::set_with_priority`] for more info.
 #[derive(Default)]
 pub struct ResourceLoadersContainer {
-    loaders: Vec<Box<dyn ResourceLoader>>,
+    loaders: Vec<LoaderEntry>,
 }
 
 impl ResourceLoadersContainer {
@@ -112,35 +133,71 @@ impl ResourceLoadersContainer {
 
     /// Adds new resource loader or replaces existing. There could be only one loader of a given type
     /// at the same time. You can use this method to replace resource loaders with your own loaders.
+    /// New loaders have zero priority, replaced loaders keep their priority.
     pub fn set<T>(&mut self, loader: T) -> Option<T>
     where
         T: ResourceLoader,
     {
-        if let Some(existing_loader) = self
-            .loaders
-            .iter_mut()
-            .find_map(|l| (**l).as_any_mut().downcast_mut::<T>())
-        {
+        if let Some(existing_loader) = self.find_mut::<T>() {
             Some(std::mem::replace(existing_loader, loader))
         } else {
-            self.loaders.push(Box::new(loader));
+            self.insert(Box::new(loader), 0);
             None
         }
     }
 
+    /// Same as [`Self::set`], but also sets the priority of the loader. Loaders with higher
+    /// priority are tried first when there are multiple loaders for the same extension. If the
+    /// loader with the highest priority fails to load a resource, the next one is tried and so on.
+    pub fn set_with_priority<T>(&mut self, loader: T, priority: i32) -> Option<T>
+    where
+        T: ResourceLoader,
+    {
+        let prev = self.position::<T>().map(|pos| self.loaders.remove(pos));
+        self.insert(Box::new(loader), priority);
+        prev.and_then(|entry| entry.loader.into_any().downcast::<T>().ok())
+            .map(|boxed| *boxed)
+    }
+
+    /// Returns the priority of a resource loader of the given type `T`, or `None` if there's no
+    /// such loader.
+    pub fn priority<T>(&self) -> Option<i32>
+    where
+        T: ResourceLoader,
+    {
+        self.position::<T>().map(|pos| self.loaders[pos].priority)
+    }
+
+    fn position<T>(&self) -> Option<usize>
+    where
+        T: ResourceLoader,
+    {
+        self.loaders
+            .iter()
+            .position(|entry| (*entry.loader).as_any().is::<T>())
+    }
+
+    fn insert(&mut self, loader: Box<dyn ResourceLoader>, priority: i32) {
+        // Keep the loaders sorted by priority, loaders with the same priority are kept in the
+        // registration order.
+        let pos = self
+            .loaders
+            .iter()
+            .position(|entry| entry.priority < priority)
+            .unwrap_or(self.loaders.len());
+        self.loaders.insert(pos, LoaderEntry { loader, priority });
+    }
+
     /// Searches for an instance of a resource loader of type `Prev` and replaces it with an other instance
-    /// of a type `New`.
+    /// of a type `New`. The new loader keeps the priority of the previous one.
     pub fn try_replace<Prev, New>(&mut self, new_loader: New) -> Option<Prev>
     where
         Prev: ResourceLoader,
         New: ResourceLoader,
     {
-        if let Some(pos) = self
-            .loaders
-            .iter()
-            .position(|l| (**l).as_any().is::<Prev>())
-        {
-            let prev_untyped = std::mem::replace(&mut self.loaders[pos], Box::new(new_loader));
+        if let Some(pos) = self.position::<Prev>() {
+            let prev_untyped =
+                std::mem::replace(&mut self.loaders[pos].loader, Box::new(new_loader));
             prev_untyped
                 .into_any()
                 .downcast::<Prev>()
@@ -158,7 +215,7 @@ impl ResourceLoadersContainer {
     {
         self.loaders
             .iter()
-            .find_map(|loader| (**loader).as_any().downcast_ref())
+            .find_map(|entry| (*entry.loader).as_any().downcast_ref())
     }
 
     /// Tries to find an instance of a resource loader of the given type `T.
@@ -168,7 +225,7 @@ impl ResourceLoadersContainer {
     {
         self.loaders
             .iter_mut()
-            .find_map(|loader| (**loader).as_any_mut().downcast_mut())
+            .find_map(|entry| (*entry.loader).as_any_mut().downcast_mut())
     }
 
     /// Returns total amount of resource loaders in the container.
@@ -181,14 +238,99 @@ impl ResourceLoadersContainer {
         self.loaders.is_empty()
     }
 
-    /// Returns an iterator yielding shared references to "untyped" resource loaders.
+    /// Returns an iterator yielding shared references to "untyped" resource loaders. Loaders are
+    /// yielded in the order of their priorities.
     pub fn iter(&self) -> impl Iterator<Item = &dyn ResourceLoader> {
-        self.loaders.iter().map(|boxed| &**boxed)
+        self.loaders.iter().map(|entry| &*entry.loader)
     }
 
-    /// Returns an iterator yielding mutable references to "untyped" resource loaders.
+    /// Returns an iterator yielding mutable references to "untyped" resource loaders. Loaders are
+    /// yielded in the order of their priorities.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut dyn ResourceLoader> {
-        self.loaders.iter_mut().map(|boxed| &mut **boxed)
+        self.loaders.iter_mut().map(|entry| &mut *entry.loader)
+    }
+
+    /// Returns an iterator yielding every resource loader, that supports the given extension, in
+    /// the order in which the loaders will be tried by the resource manager.
+    pub fn iter_for_extension(&self, ext: &str) -> impl Iterator<Item = &dyn ResourceLoader> {
+        let ext = ext.to_owned();
+        self.iter()
+            .filter(move |loader| loader.supports_extension(&ext))
+    }
+}
+
+thread_local! {
+    static CURRENT_CHAIN: Cell<Option<ChainProbe>> = const { Cell::new(None) };
+}
+
+#[derive(Copy, Clone)]
+struct ChainProbe {
+    resource_key: usize,
+    failed: bool,
+}
+
+/// Checks whether a load error of the given resource must be deferred, because there are other
+/// loaders to try. Marks the current loader as failed if so.
+pub(crate) fn defer_load_error(resource_key: usize) -> bool {
+    CURRENT_CHAIN.with(|chain| match chain.get() {
+        Some(probe) if probe.resource_key == resource_key => {
+            chain.set(Some(ChainProbe {
+                failed: true,
+                ..probe
+            }));
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Loader future, that tries a number of loaders one-by-one until one of them loads the resource
+/// successfully. Errors of every loader except the last one are not committed to the resource.
+pub(crate) struct ChainedLoaderFuture {
+    pub(crate) resource: UntypedResource,
+    pub(crate) current: BoxedLoaderFuture,
+    pub(crate) failed: bool,
+    /// Type uuids and futures of the next loaders to try.
+    pub(crate) fallbacks: VecDeque<(Uuid, BoxedLoaderFuture)>,
+}
+
+impl Future for ChainedLoaderFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let probe = (!this.fallbacks.is_empty()).then_some(ChainProbe {
+                resource_key: this.resource.key(),
+                failed: false,
+            });
+            let previous = CURRENT_CHAIN.with(|chain| chain.replace(probe));
+            let poll = this.current.as_mut().poll(cx);
+            if let Some(probe) = CURRENT_CHAIN.with(|chain| chain.replace(previous)) {
+                this.failed |= probe.failed;
+            }
+
+            if poll.is_pending() {
+                return Poll::Pending;
+            }
+
+            if !std::mem::take(&mut this.failed) {
+                return Poll::Ready(());
+            }
+
+            let Some((type_uuid, next)) = this.fallbacks.pop_front() else {
+                return Poll::Ready(());
+            };
+            if let ResourceState::Pending {
+                type_uuid: ref mut current_type_uuid,
+                ..
+            } = *this.resource.lock()
+            {
+                *current_type_uuid = type_uuid;
+            }
+            this.current = next;
+        }
     }
 }
 
@@ -265,6 +407,81 @@ mod test {
         assert_eq!(res, Some(&mut MyResourceLoader));
     }
 
+    struct OtherResourceLoader;
+
+    impl ResourceLoader for OtherResourceLoader {
+        fn extensions(&self) -> &[&str] {
+            &["json"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            Default::default()
+        }
+
+        fn load(
+            &self,
+            _resource: UntypedResource,
+            _event_broadcaster: ResourceEventBroadcaster,
+            _reload: bool,
+            _io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            todo!()
+        }
+    }
+
+    struct AnotherResourceLoader;
+
+    impl ResourceLoader for AnotherResourceLoader {
+        fn extensions(&self) -> &[&str] {
+            &["json", "bin"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            Default::default()
+        }
+
+        fn load(
+            &self,
+            _resource: UntypedResource,
+            _event_broadcaster: ResourceEventBroadcaster,
+            _reload: bool,
+            _io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            todo!()
+        }
+    }
+
+    #[test]
+    fn resource_loader_container_priority() {
+        let mut container = ResourceLoadersContainer::new();
+        container.set(MyResourceLoader);
+        container.set(OtherResourceLoader);
+        container.set_with_priority(AnotherResourceLoader, 1);
+        assert_eq!(container.priority::<OtherResourceLoader>(), Some(0));
+        assert_eq!(container.priority::<AnotherResourceLoader>(), Some(1));
+
+        let is_first = |container: &ResourceLoadersContainer, ext| {
+            container
+                .iter_for_extension(ext)
+                .next()
+                .unwrap()
+                .as_any()
+                .is::<AnotherResourceLoader>()
+        };
+        assert_eq!(container.iter_for_extension("JSON").count(), 2);
+        assert!(is_first(&container, "json"));
+
+        // Replacing a loader keeps its priority, changing the priority changes the order.
+        container.set(AnotherResourceLoader);
+        assert!(is_first(&container, "json"));
+        assert!(container
+            .set_with_priority(AnotherResourceLoader, -1)
+            .is_some());
+        assert!(!is_first(&container, "json"));
+        assert_eq!(container.iter_for_extension("bin").count(), 1);
+        assert_eq!(container.len(), 3);
+    }
+
     #[test]
     fn resource_loader_container_getters() {
         let mut container = ResourceLoadersContainer::new();
@@ -0,0 +1,263 @@
+//! Locale-aware resource variants. See [`LocaleSettings`] docs for more info.
+
+use crate::{
+    core::io::FileLoadError,
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+};
+use fxhash::FxHashMap;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Locale settings of a resource manager. When a locale is set, every resource is loaded from its
+/// localized variant (if there's one), while the path of the resource stays the same. This way
+/// scenes and other resources keep referencing the logical path (for example `sounds/voice.ogg`),
+/// and the actual content depends on the active locale. A variant is looked up by a suffix or by a
+/// folder with the name of the locale, for `sounds/voice.ogg` and `pt-BR` locale the candidates are:
+///
+/// 1. `sounds/voice_pt-BR.ogg`
+/// 2. `sounds/pt-BR/voice.ogg`
+/// 3. `sounds/voice_pt.ogg`
+/// 4. `sounds/pt/voice.ogg`
+///
+/// The same candidates of the fallback locale (if any) are checked next, and the original file is
+/// used if there's no variant at all. Explicit path mappings (see [`Self::paths`]) are checked
+/// before everything else. See [`crate::manager::ResourceManager::set_locale_settings`] for the way
+/// to switch the locale at runtime.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocaleSettings {
+    /// Active locale (for example `en`, `de` or `pt-BR`). `None` disables localized variants.
+    pub locale: Option<String>,
+    /// A locale, that is used when there's no variant for the active locale.
+    pub fallback: Option<String>,
+    /// A map of `Original Path -> Localized Path`, that overrides the lookup by suffix or folder.
+    /// It is used only if there's an active locale.
+    pub paths: FxHashMap<PathBuf, PathBuf>,
+}
+
+impl LocaleSettings {
+    /// Creates new settings with the given active locale and without fallback locale.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: Some(locale.into()),
+            fallback: None,
+            paths: Default::default(),
+        }
+    }
+
+    /// Sets the fallback locale.
+    pub fn with_fallback(mut self, fallback: impl Into<String>) -> Self {
+        self.fallback = Some(fallback.into());
+        self
+    }
+
+    /// Adds an explicit localized path for the given original path (see [`Self::paths`]).
+    pub fn with_path(mut self, path: impl Into<PathBuf>, localized: impl Into<PathBuf>) -> Self {
+        self.paths.insert(path.into(), localized.into());
+        self
+    }
+
+    /// Returns `true` if there's an active locale.
+    pub fn is_enabled(&self) -> bool {
+        self.locale.is_some()
+    }
+
+    /// Returns a list of paths of possible localized variants of the given path, in the order in
+    /// which they should be checked. The list does not include the path itself.
+    pub fn variant_candidates(&self, path: &Path) -> Vec<PathBuf> {
+        let Some(locale) = self.locale.as_deref() else {
+            return Vec::new();
+        };
+
+        let mut tags = Vec::new();
+        for tag in std::iter::once(locale).chain(self.fallback.as_deref()) {
+            for tag in [tag, language(tag)] {
+                if !tag.is_empty() && !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+
+        let (Some(file_name), Some(stem)) = (path.file_name(), path.file_stem()) else {
+            return Vec::new();
+        };
+        let parent = path.parent().unwrap_or(Path::new(""));
+
+        let mut candidates = self
+            .paths
+            .get(path)
+            .cloned()
+            .into_iter()
+            .collect::<Vec<_>>();
+        for tag in tags {
+            let mut suffixed = stem.to_os_string();
+            suffixed.push("_");
+            suffixed.push(tag);
+            if let Some(extension) = path.extension() {
+                suffixed.push(".");
+                suffixed.push(extension);
+            }
+            candidates.push(parent.join(suffixed));
+            candidates.push(parent.join(tag).join(file_name));
+        }
+        candidates
+    }
+
+    /// Searches for the first existing localized variant of the given path. Returns `None` if
+    /// there's no variant.
+    pub async fn resolve(&self, path: &Path, io: &dyn ResourceIo) -> Option<PathBuf> {
+        for candidate in self.variant_candidates(path) {
+            if io.is_file(&candidate).await {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the language part of the given locale (`pt` for `pt-BR`).
+pub fn language(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
+
+/// Resource IO, that substitutes the content of the file at the given path with the content of its
+/// localized variant.
+pub(crate) struct LocalizedResourceIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) path: PathBuf,
+    pub(crate) settings: LocaleSettings,
+}
+
+impl LocalizedResourceIo {
+    async fn actual_path(&self, path: &Path) -> PathBuf {
+        if path == self.path {
+            if let Some(variant) = self.settings.resolve(path, &*self.inner).await {
+                return variant;
+            }
+        }
+        path.to_path_buf()
+    }
+}
+
+impl ResourceIo for LocalizedResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.load_file(&path).await
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.file_reader(&path).await
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.file_stamp(&path).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::executor::block_on, io::memory::MemoryResourceIo};
+
+    #[test]
+    fn locale_variant_candidates() {
+        assert!(LocaleSettings::default()
+            .variant_candidates(Path::new("sounds/voice.ogg"))
+            .is_empty());
+
+        let settings = LocaleSettings::new("pt-BR")
+            .with_fallback("en")
+            .with_path("sounds/voice.ogg", "sounds/br/greeting.ogg");
+        assert_eq!(
+            settings.variant_candidates(Path::new("sounds/voice.ogg")),
+            [
+                "sounds/br/greeting.ogg",
+                "sounds/voice_pt-BR.ogg",
+                "sounds/pt-BR/voice.ogg",
+                "sounds/voice_pt.ogg",
+                "sounds/pt/voice.ogg",
+                "sounds/voice_en.ogg",
+                "sounds/en/voice.ogg",
+            ]
+            .map(PathBuf::from)
+        );
+        assert_eq!(language("pt-BR"), "pt",);
+        assert_eq!(language("en"), "en");
+    }
+
+    #[test]
+    fn localized_resource_io() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file(Path::new("voice.ogg"), vec![0]);
+        io.add_file(Path::new("de/voice.ogg"), vec![1]);
+        io.add_file(Path::new("voice_en.ogg"), vec![2]);
+
+        let load = |settings: LocaleSettings| {
+            let io = LocalizedResourceIo {
+                inner: io.clone(),
+                path: "voice.ogg".into(),
+                settings,
+            };
+            block_on(io.load_file(Path::new("voice.ogg"))).unwrap()
+        };
+
+        assert_eq!(load(LocaleSettings::new("de-AT")), [1]);
+        assert_eq!(load(LocaleSettings::new("fr").with_fallback("en")), [2]);
+        assert_eq!(load(LocaleSettings::new("fr")), [0]);
+    }
+}
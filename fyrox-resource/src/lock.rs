@@ -0,0 +1,295 @@
+//! Diagnostics of resource state locks. See [`LockDiagnostics`] docs for more info.
+
+use crate::{
+    core::{
+        instant::Instant,
+        log::Log,
+        parking_lot::{Mutex, MutexGuard},
+    },
+    state::ResourceState,
+    untyped::UntypedResource,
+};
+use fxhash::FxHashMap;
+use std::{
+    backtrace::Backtrace,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+
+/// Information about a thread, that holds a lock of a resource state.
+#[derive(Clone, Debug)]
+pub struct LockHolder {
+    /// Path of the resource at the moment when the lock was acquired.
+    pub path: PathBuf,
+    /// Name (or id, if the thread is unnamed) of the thread, that holds the lock.
+    pub thread: String,
+    /// Backtrace of the call, that acquired the lock.
+    pub backtrace: Arc<Backtrace>,
+    /// Time when the lock was acquired.
+    pub acquired: Instant,
+}
+
+/// Lock diagnostics helps to find deadlocks on resource states. Every resource keeps its state
+/// behind a mutex, and [`crate::Resource::state`] (as well as [`crate::Resource::data_ref`]) blocks
+/// until the mutex is unlocked. A guard, that is held for too long (or a second lock of the same
+/// resource on the same thread) stalls the engine without any clue about the culprit.
+///
+/// When the diagnostics is enabled, every lock of a resource (see [`UntypedResource::lock`])
+/// records its holder (thread and backtrace, see [`LockHolder`]), and every wait, that exceeds
+/// [`Self::wait_threshold`], is reported to the log with the backtrace of the current holder. The
+/// diagnostics is disabled by default, because capturing a backtrace on every lock is slow. The
+/// diagnostics is global (see [`lock_diagnostics`]), because resources could be locked from
+/// anywhere. Direct locking of the inner mutex of [`UntypedResource`] bypasses the diagnostics.
+///
+/// Use [`crate::Resource::try_acquire_state_for`] to wait for a lock with a timeout instead of
+/// blocking forever.
+///
+/// ```rust
+/// # use fyrox_resource::lock::lock_diagnostics;
+/// # use std::time::Duration;
+/// lock_diagnostics().set_wait_threshold(Duration::from_secs(2));
+/// lock_diagnostics().set_enabled(true);
+/// ```
+#[derive(Debug)]
+pub struct LockDiagnostics {
+    enabled: AtomicBool,
+    wait_threshold_us: AtomicU64,
+    holders: Mutex<FxHashMap<usize, LockHolder>>,
+}
+
+impl Default for LockDiagnostics {
+    fn default() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            wait_threshold_us: AtomicU64::new(1_000_000),
+            holders: Default::default(),
+        }
+    }
+}
+
+impl LockDiagnostics {
+    /// Enables or disables the diagnostics. Holders of the locks, that were acquired while the
+    /// diagnostics was disabled, are unknown.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.holders.lock().clear();
+        }
+    }
+
+    /// Returns `true` if the diagnostics is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum time of waiting for a lock, after which the wait is reported. Default is
+    /// one second.
+    pub fn set_wait_threshold(&self, threshold: Duration) {
+        self.wait_threshold_us.store(
+            threshold.as_micros().min(u64::MAX as u128) as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Returns the maximum time of waiting for a lock, after which the wait is reported.
+    pub fn wait_threshold(&self) -> Duration {
+        Duration::from_micros(self.wait_threshold_us.load(Ordering::Relaxed))
+    }
+
+    /// Returns the current holder of the state lock of the given resource, if any.
+    pub fn holder(&self, resource: &UntypedResource) -> Option<LockHolder> {
+        self.holders.lock().get(&resource.key()).cloned()
+    }
+
+    fn lock<'a>(&'a self, mutex: &'a Mutex<ResourceState>) -> LockedState<'a> {
+        if !self.is_enabled() {
+            return LockedState {
+                _holder: None,
+                guard: mutex.lock(),
+            };
+        }
+
+        let key = mutex as *const _ as usize;
+        let threshold = self.wait_threshold();
+        let guard = match mutex.try_lock_for(threshold) {
+            Some(guard) => guard,
+            None => {
+                self.report_wait(key, threshold);
+                mutex.lock()
+            }
+        };
+        self.acquired(key, guard)
+    }
+
+    fn try_lock_for<'a>(
+        &'a self,
+        mutex: &'a Mutex<ResourceState>,
+        timeout: Duration,
+    ) -> Option<LockedState<'a>> {
+        let guard = mutex.try_lock_for(timeout)?;
+        if self.is_enabled() {
+            Some(self.acquired(mutex as *const _ as usize, guard))
+        } else {
+            Some(LockedState {
+                _holder: None,
+                guard,
+            })
+        }
+    }
+
+    fn acquired<'a>(&'a self, key: usize, guard: MutexGuard<'a, ResourceState>) -> LockedState<'a> {
+        let current = std::thread::current();
+        self.holders.lock().insert(
+            key,
+            LockHolder {
+                path: guard.path().to_path_buf(),
+                thread: current
+                    .name()
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| format!("{:?}", current.id())),
+                backtrace: Arc::new(Backtrace::force_capture()),
+                acquired: Instant::now(),
+            },
+        );
+        LockedState {
+            _holder: Some(HolderRecord {
+                diagnostics: self,
+                key,
+            }),
+            guard,
+        }
+    }
+
+    fn report_wait(&self, key: usize, threshold: Duration) {
+        match self.holders.lock().get(&key) {
+            Some(holder) => Log::warn(format!(
+                "Thread {:?} is waiting for a lock of {} resource for more than {:.2} s. The lock \
+                is held by {} thread for {:.2} s, the lock was acquired at:\n{}",
+                std::thread::current().id(),
+                holder.path.display(),
+                threshold.as_secs_f32(),
+                holder.thread,
+                holder.acquired.elapsed().as_secs_f32(),
+                holder.backtrace
+            )),
+            None => Log::warn(format!(
+                "Thread {:?} is waiting for a lock of a resource for more than {:.2} s. The \
+                holder of the lock is unknown.",
+                std::thread::current().id(),
+                threshold.as_secs_f32(),
+            )),
+        }
+    }
+}
+
+/// Returns the global lock diagnostics. See [`LockDiagnostics`] docs for more info.
+pub fn lock_diagnostics() -> &'static LockDiagnostics {
+    static DIAGNOSTICS: OnceLock<LockDiagnostics> = OnceLock::new();
+    DIAGNOSTICS.get_or_init(Default::default)
+}
+
+struct HolderRecord<'a> {
+    diagnostics: &'a LockDiagnostics,
+    key: usize,
+}
+
+impl Drop for HolderRecord<'_> {
+    fn drop(&mut self) {
+        self.diagnostics.holders.lock().remove(&self.key);
+    }
+}
+
+/// A guard of a resource state, that removes its holder record when dropped.
+pub struct LockedState<'a> {
+    // The record must be removed before the mutex is unlocked, fields are dropped in declaration
+    // order.
+    _holder: Option<HolderRecord<'a>>,
+    guard: MutexGuard<'a, ResourceState>,
+}
+
+impl LockedState<'_> {
+    /// Returns a pointer to the locked mutex as numeric value, see [`UntypedResource::key`].
+    pub(crate) fn key(&self) -> usize {
+        MutexGuard::mutex(&self.guard) as *const _ as usize
+    }
+}
+
+impl Deref for LockedState<'_> {
+    type Target = ResourceState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl DerefMut for LockedState<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+/// Locks the given resource state, recording the holder of the lock if the diagnostics is enabled.
+pub(crate) fn lock_state(mutex: &Mutex<ResourceState>) -> LockedState<'_> {
+    lock_diagnostics().lock(mutex)
+}
+
+/// Tries to lock the given resource state for the given amount of time.
+pub(crate) fn try_lock_state_for(
+    mutex: &Mutex<ResourceState>,
+    timeout: Duration,
+) -> Option<LockedState<'_>> {
+    lock_diagnostics().try_lock_for(mutex, timeout)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lock_diagnostics_holder() {
+        let diagnostics = LockDiagnostics::default();
+        let resource = UntypedResource::new_pending("foo.bin".into(), Default::default());
+
+        // Disabled diagnostics does not record anything.
+        drop(diagnostics.lock(&resource.0));
+        assert!(diagnostics.holder(&resource).is_none());
+
+        diagnostics.set_enabled(true);
+        diagnostics.set_wait_threshold(Duration::from_millis(10));
+        assert_eq!(diagnostics.wait_threshold(), Duration::from_millis(10));
+
+        let guard = diagnostics.lock(&resource.0);
+        assert_eq!(guard.key(), resource.key());
+        let holder = diagnostics.holder(&resource).unwrap();
+        assert_eq!(holder.path, PathBuf::from("foo.bin"));
+        assert_eq!(holder.thread, std::thread::current().name().unwrap());
+
+        // The lock is held by this thread, so the timed lock must fail.
+        assert!(diagnostics
+            .try_lock_for(&resource.0, Duration::from_millis(10))
+            .is_none());
+
+        drop(guard);
+        assert!(diagnostics.holder(&resource).is_none());
+        assert!(diagnostics
+            .try_lock_for(&resource.0, Duration::from_millis(10))
+            .is_some());
+        assert!(diagnostics.holder(&resource).is_none());
+    }
+
+    #[test]
+    fn untyped_resource_lock_diagnostics() {
+        let resource = UntypedResource::new_pending("bar.bin".into(), Default::default());
+        lock_diagnostics().set_enabled(true);
+        let guard = resource.lock();
+        let holder = lock_diagnostics().holder(&resource);
+        drop(guard);
+        lock_diagnostics().set_enabled(false);
+        assert_eq!(holder.unwrap().path, PathBuf::from("bar.bin"));
+    }
+}
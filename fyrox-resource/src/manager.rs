@@ -1,34 +1,65 @@
 //! Resource manager controls loading and lifetime of resource in the engine.
 
 use crate::{
+    collect::remap_used_resources,
     collect_used_resources,
     constructor::ResourceConstructorContainer,
     core::{
         append_extension,
-        futures::future::join_all,
+        futures::future::{join_all, JoinAll},
         io::FileLoadError,
         log::Log,
         make_relative_path, notify,
         parking_lot::{Mutex, MutexGuard},
+        reflect::Reflect,
+        uuid::Uuid,
         watcher::FileSystemWatcher,
         TypeUuidProvider,
     },
+    dedup::{ContentHashReport, ContentHashingIo},
+    embedded::EmbeddedResourceRegistry,
     entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
     event::{ResourceEvent, ResourceEventBroadcaster},
-    io::{FsResourceIo, ResourceIo},
-    loader::{ResourceLoader, ResourceLoadersContainer},
-    options::OPTIONS_EXTENSION,
+    import::{DerivedDataCache, ImportingResourceIo, ResourceImportersContainer},
+    io::{is_temp_path, memory::MemoryResourceIo, FsResourceIo, ResourceIo},
+    key::ResourcePathKey,
+    loader::{ChainedLoaderFuture, ResourceLoader, ResourceLoadersContainer},
+    locale::{LocaleSettings, LocalizedResourceIo},
+    manifest::{LoadPriority, PreloadManifest, PreloadRequest},
+    migration::ResourceMigrationRegistry,
+    options::{BaseImportOptions, OPTIONS_EXTENSION},
+    platform::{PlatformResourceIo, PlatformSettings},
+    prefetch::{PrefetchSettings, Prefetcher},
+    protection::{self, ReadOnlyError, ReadOnlyResources},
+    quota::{ResourceQuota, ResourceTagReport, ResourceTagUsage, ResourceTags},
+    registry::{read_options_hash, RegistryEntry, RegistrySnapshot, RegistrySnapshotError},
+    retry::{Retrier, RetryPolicy, TransientErrorMonitorIo},
     state::{LoadError, ResourceState},
-    task::TaskPool,
-    Resource, ResourceData, TypedResourceData, UntypedResource,
+    stats::{
+        ResourceLoadStatistics, ResourceLoadTiming, ResourceMemoryReport, ResourceTypeMemoryUsage,
+        TimedLoaderFuture, TimedResourceIo,
+    },
+    streaming::{ResourceStreamer, StageState},
+    task::{TaskExecutor, TaskPool},
+    untyped,
+    validation::{
+        find_cycles, FailedResource, MissingResource, ProjectValidationReport, TypeMismatch,
+    },
+    Resource, ResourceData, ResourceSaveError, TypedResourceData, UntypedResource,
 };
 use fxhash::{FxHashMap, FxHashSet};
 use rayon::prelude::*;
 use std::{
+    cmp::Reverse,
+    collections::VecDeque,
     fmt::{Debug, Display, Formatter},
+    future::Future,
     marker::PhantomData,
     path::{Path, PathBuf},
-    sync::Arc,
+    pin::Pin,
+    sync::{atomic::AtomicBool, Arc},
+    task::{Context, Poll, Waker},
+    time::Duration,
 };
 
 /// A set of resources that can be waited for.
@@ -44,7 +75,7 @@ impl ResourceWaitContext {
     pub fn is_all_loaded(&self) -> bool {
         let mut loaded_count = 0;
         for resource in self.resources.iter() {
-            if !matches!(*resource.0.lock(), ResourceState::Pending { .. }) {
+            if !resource.is_loading() {
                 loaded_count += 1;
             }
         }
@@ -52,6 +83,116 @@ impl ResourceWaitContext {
     }
 }
 
+/// An error, that is returned when waiting for pending resources took longer than the given
+/// timeout. See [`ResourceManager::wait_for_all_pending`] for more info.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaitTimeoutError {
+    /// Paths of the resources, that were still loading when the timeout expired.
+    pub pending: Vec<PathBuf>,
+}
+
+impl Display for WaitTimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Timed out waiting for {} pending resource(s):",
+            self.pending.len()
+        )?;
+        for path in self.pending.iter() {
+            write!(f, " {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// A future, that resolves once every resource, that was pending when the future was created, is
+/// loaded (or failed to load). See [`ResourceManager::wait_for_all_pending`] for more info.
+#[must_use]
+pub struct PendingResourcesFuture {
+    resource_manager: ResourceManager,
+    resources: Vec<UntypedResource>,
+    deadline: Option<crate::core::instant::Instant>,
+    // The waker is shared with a timer thread, that wakes the future when the deadline is reached.
+    timer_waker: Option<Arc<Mutex<Waker>>>,
+    // The time, when the earliest started retry timer wakes the future.
+    retry_timer: Option<crate::core::instant::Instant>,
+}
+
+impl Future for PendingResourcesFuture {
+    type Output = Result<(), WaitTimeoutError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Queued preload requests and due retries must be started, otherwise the wait will never
+        // finish if the resource manager is not updated while waiting (when blocking the main
+        // thread, for example).
+        let next_retry = {
+            let mut state = self.resource_manager.state();
+            state.process_preload_queue();
+            state.process_retries();
+            state.retrier.next_due()
+        };
+
+        self.resources.retain(|resource| {
+            let mut resource = resource.clone();
+            Pin::new(&mut resource).poll(cx).is_pending()
+        });
+
+        if self.resources.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // There are no threads on WebAssembly, retries are started only when the future is
+        // polled again.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(next_retry) = next_retry {
+            let now = crate::core::instant::Instant::now();
+            if self
+                .retry_timer
+                .map_or(true, |timer| timer <= now || timer > next_retry)
+            {
+                self.retry_timer = Some(next_retry);
+                let waker = cx.waker().clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(next_retry.saturating_duration_since(now));
+                    waker.wake();
+                });
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = next_retry;
+
+        if let Some(deadline) = self.deadline {
+            let now = crate::core::instant::Instant::now();
+            if now >= deadline {
+                return Poll::Ready(Err(WaitTimeoutError {
+                    pending: self
+                        .resources
+                        .iter()
+                        .map(|resource| resource.path())
+                        .collect(),
+                }));
+            }
+
+            match self.timer_waker.as_ref() {
+                Some(waker) => *waker.lock() = cx.waker().clone(),
+                None => {
+                    let waker = Arc::new(Mutex::new(cx.waker().clone()));
+                    self.timer_waker = Some(waker.clone());
+                    // There are no threads on WebAssembly, so the timeout is checked only when
+                    // some of the resources is loaded.
+                    #[cfg(not(target_arch = "wasm32"))]
+                    std::thread::spawn(move || {
+                        std::thread::sleep(deadline - now);
+                        waker.lock().wake_by_ref();
+                    });
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
 /// See module docs.
 pub struct ResourceManagerState {
     /// A set of resource loaders. Use this field to register your own resource loader.
@@ -64,10 +205,50 @@ pub struct ResourceManagerState {
     pub built_in_resources: FxHashMap<PathBuf, UntypedResource>,
     /// The resource acccess interface
     pub resource_io: Arc<dyn ResourceIo>,
+    /// A set of migration functions, that are used to upgrade resources of older versions on load.
+    pub migrations: ResourceMigrationRegistry,
+    /// A set of resources, that are compiled into the binary. Resources at the paths of the
+    /// registry are loaded from memory, instead of the resource IO.
+    pub embedded_resources: EmbeddedResourceRegistry,
+    /// A set of resource importers, that convert source files into derived data. See
+    /// [`crate::import::ResourceImporter`] docs for more info.
+    pub importers: ResourceImportersContainer,
+    /// A cache of derived data, produced by the importers.
+    pub derived_data_cache: DerivedDataCache,
 
     resources: Vec<TimedEntry<UntypedResource>>,
-    task_pool: Arc<TaskPool>,
+    task_pool: TaskPool,
+    statistics: Arc<Mutex<ResourceLoadStatistics>>,
     watcher: Option<FileSystemWatcher>,
+    groups: FxHashMap<String, Vec<UntypedResource>>,
+    case_insensitive_paths: bool,
+    content_deduplication: bool,
+    // Content hash and type of a resource -> path of the first resource with such content.
+    content_hashes: FxHashMap<(u64, Uuid), PathBuf>,
+    // Path of a duplicate -> path of the first resource with the same content.
+    content_duplicates: FxHashMap<ResourcePathKey, PathBuf>,
+    // Content hashes of the files read by loading tasks, processed in `update`.
+    content_reports: Arc<Mutex<Vec<ContentHashReport>>>,
+    // Preload requests, that are not started yet. Sorted by priority (highest first).
+    preload_queue: VecDeque<(LoadPriority, UntypedResource)>,
+    // Preload requests, that are started and not finished yet.
+    active_preloads: Vec<UntypedResource>,
+    max_concurrent_preloads: usize,
+    // Resource -> paths of the resources, that were reported as its dependencies by its loader.
+    dependencies: FxHashMap<ResourcePathKey, Vec<PathBuf>>,
+    // Resource -> paths of plain files (not resources), that were used to produce its data.
+    file_dependencies: FxHashMap<ResourcePathKey, Vec<PathBuf>>,
+    streamer: ResourceStreamer,
+    // Old path of a renamed resource -> its new path.
+    redirects: FxHashMap<ResourcePathKey, PathBuf>,
+    locale: LocaleSettings,
+    platform: PlatformSettings,
+    prefetcher: Prefetcher,
+    registry: Option<RegistrySnapshot>,
+    retrier: Retrier,
+    tags: ResourceTags,
+    read_only: bool,
+    read_only_resources: ReadOnlyResources,
 }
 
 /// See module docs.
@@ -109,6 +290,32 @@ impl Display for ResourceRegistrationError {
     }
 }
 
+/// An error that may occur during resource renaming. See [`ResourceManager::rename_resource`] for
+/// more info.
+#[derive(Debug)]
+pub enum ResourceRenameError {
+    /// There's a file at the new path already.
+    AlreadyExists(PathBuf),
+    /// The file could not be moved.
+    Io(FileLoadError),
+    /// Resources are in read-only mode (see [`ResourceManagerState::set_read_only`]).
+    ReadOnly(ReadOnlyError),
+}
+
+impl Display for ResourceRenameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceRenameError::AlreadyExists(path) => {
+                write!(f, "A file {} already exists!", path.display())
+            }
+            ResourceRenameError::Io(err) => {
+                write!(f, "Unable to move the resource file. Reason: {err:?}")
+            }
+            ResourceRenameError::ReadOnly(err) => Display::fmt(err, f),
+        }
+    }
+}
+
 impl ResourceManager {
     /// Creates a resource manager with default settings and loaders.
     pub fn new() -> Self {
@@ -122,12 +329,23 @@ impl ResourceManager {
         self.state.lock()
     }
 
+    /// Returns `true` if both handles point to the same resource manager.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+
     /// Returns the ResourceIo used by this resource manager
     pub fn resource_io(&self) -> Arc<dyn ResourceIo> {
         let state = self.state();
         state.resource_io.clone()
     }
 
+    /// Returns a copy of current resource loading statistics. See [`ResourceLoadStatistics`] docs
+    /// for more info.
+    pub fn statistics(&self) -> ResourceLoadStatistics {
+        self.state().statistics()
+    }
+
     /// Requests a resource of the given type located at the given path. This method is non-blocking, instead
     /// it immediately returns the typed resource wrapper. Loading of the resource is managed automatically in
     /// a separate thread (or thread pool) on PC, and JS micro-task (the same thread) on WebAssembly.
@@ -163,7 +381,9 @@ impl ResourceManager {
     where
         T: TypedResourceData,
     {
-        let untyped = self.state().request(path);
+        let untyped = self
+            .state()
+            .request_of_type(path, <T as TypeUuidProvider>::type_uuid());
         let actual_type_uuid = untyped.type_uuid();
         assert_eq!(actual_type_uuid, <T as TypeUuidProvider>::type_uuid());
         Resource {
@@ -182,7 +402,9 @@ impl ResourceManager {
     where
         T: TypedResourceData,
     {
-        let untyped = self.state().request(path);
+        let untyped = self
+            .state()
+            .request_of_type(path, <T as TypeUuidProvider>::type_uuid());
         let actual_type_uuid = untyped.type_uuid();
         if actual_type_uuid == <T as TypeUuidProvider>::type_uuid() {
             Some(Resource {
@@ -202,6 +424,416 @@ impl ResourceManager {
         self.state().request(path)
     }
 
+    /// Loads a resource of the given type from the given bytes, using a resource loader that is
+    /// registered for the extension of the virtual path. The bytes are treated as the content of a
+    /// file at the virtual path, every other file (for example, import options or dependencies of
+    /// the resource) is loaded as usual. The resource is registered at the virtual path, so it could
+    /// be requested later using [`Self::request`]. If there's a resource at the virtual path already,
+    /// it will be reloaded from the given bytes.
+    ///
+    /// This method is useful to load downloaded or procedurally generated content and in tests, that
+    /// should not touch the file system.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn load_from_memory<T>(
+        &self,
+        bytes: impl Into<Vec<u8>>,
+        virtual_path: impl AsRef<Path>,
+    ) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let untyped = self.state().load_from_memory_internal(
+            bytes.into(),
+            virtual_path.as_ref(),
+            Some(<T as TypeUuidProvider>::type_uuid()),
+        );
+        let actual_type_uuid = untyped.type_uuid();
+        assert_eq!(actual_type_uuid, <T as TypeUuidProvider>::type_uuid());
+        Resource {
+            untyped,
+            phantom: PhantomData::<T>,
+        }
+    }
+
+    /// Same as [`Self::request`], but blocks the calling thread until the resource is fully loaded
+    /// (or failed to load). This method is meant to be used in tools, tests, asset bakers and so on,
+    /// where async plumbing is pure overhead.
+    ///
+    /// ## Important notes
+    ///
+    /// Never call this method from the main game loop, it will stall the entire game until the
+    /// resource is loaded. This method is not available on WebAssembly, because it is impossible to
+    /// block the main thread there.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn request_sync<T>(&self, path: impl AsRef<Path>) -> Result<Resource<T>, LoadError>
+    where
+        T: TypedResourceData,
+    {
+        // The state lock must not be held here, loaders are free to request other resources.
+        self.block_on_driven(self.request::<T>(path))
+    }
+
+    /// Same as [`Self::request_sync`], but returns untyped resource.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn request_untyped_sync<P>(&self, path: P) -> Result<UntypedResource, LoadError>
+    where
+        P: AsRef<Path>,
+    {
+        self.block_on_driven(self.request_untyped(path))
+    }
+
+    /// Same as [`Self::request`], but also adds the resource to the given group. Groups allow you to
+    /// wait for or unload an entire set of resources at once, without tracking every resource
+    /// manually. For example, you can put every resource of a game level in a group and unload the
+    /// group when the level is unloaded. See [`ResourceManagerState::unload_group`] and
+    /// [`Self::wait_for_group`] for more info.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn request_in_group<T>(&self, path: impl AsRef<Path>, group: &str) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let resource = self.request::<T>(path);
+        self.state()
+            .add_to_group(resource.clone().into_untyped(), group);
+        resource
+    }
+
+    /// Same as [`Self::request_in_group`], but returns untyped resource.
+    pub fn request_untyped_in_group<P>(&self, path: P, group: &str) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        let mut state = self.state();
+        let resource = state.request(path);
+        state.add_to_group(resource.clone(), group);
+        resource
+    }
+
+    /// Same as [`Self::request`], but also attributes the resource to the given requester tag
+    /// (plugin name, subsystem, etc.). Tags are used to report resource usage per requester and to
+    /// check quotas, see [`ResourceTagReport`] docs for more info.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn request_tagged<T>(&self, path: impl AsRef<Path>, tag: &str) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let resource = self.request::<T>(path);
+        self.state().add_tag(&resource.clone().into_untyped(), tag);
+        resource
+    }
+
+    /// Same as [`Self::request_tagged`], but returns untyped resource.
+    pub fn request_untyped_tagged<P>(&self, path: P, tag: &str) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        let mut state = self.state();
+        let resource = state.request(path);
+        state.add_tag(&resource, tag);
+        resource
+    }
+
+    /// Waits until every resource of the given group is loaded (or failed to load). Returns a list
+    /// of load results in the same order as the resources were added to the group.
+    pub async fn wait_for_group(&self, group: &str) -> Vec<Result<UntypedResource, LoadError>> {
+        let resources = self.state().group(group).to_vec();
+        join_all(resources).await
+    }
+
+    /// Returns a future, that resolves once every resource, that is currently pending, is loaded or
+    /// failed to load. Resources, that are requested after the call, are not waited for. If the
+    /// timeout is reached before that, the future resolves with an error, that contains the
+    /// paths of the resources, that are still loading. It is useful for level transitions and tests:
+    ///
+    /// ```rust,no_run
+    /// # use fyrox_resource::{core::futures::executor::block_on, manager::ResourceManager};
+    /// # use std::time::Duration;
+    /// # let resource_manager = ResourceManager::new();
+    /// resource_manager.request_untyped("data/level2.rgs");
+    /// if let Err(err) =
+    ///     block_on(resource_manager.wait_for_all_pending(Some(Duration::from_secs(30))))
+    /// {
+    ///     eprintln!("{err}");
+    /// }
+    /// ```
+    pub fn wait_for_all_pending(&self, timeout: Option<Duration>) -> PendingResourcesFuture {
+        let resources = self
+            .state()
+            .resources
+            .iter()
+            .filter(|entry| entry.value.is_loading())
+            .map(|entry| entry.value.clone())
+            .collect();
+        PendingResourcesFuture {
+            resource_manager: self.clone(),
+            resources,
+            deadline: timeout.map(|timeout| crate::core::instant::Instant::now() + timeout),
+            timer_waker: None,
+            retry_timer: None,
+        }
+    }
+
+    /// Blocking variant of [`Self::wait_for_all_pending`]. It blocks the current thread, so it
+    /// must not be called from a resource loader or any other task of the resource manager.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn wait_for_all_pending_blocking(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), WaitTimeoutError> {
+        self.block_on_driven(self.wait_for_all_pending(timeout))
+    }
+
+    /// Blocks the current thread until the given future is finished. Unlike a plain `block_on`, it
+    /// keeps the resource manager running while waiting: queued preload requests and due retries are
+    /// started and the task executor is updated, so executors, that run tasks only when updated (such as
+    /// [`crate::task::CooperativeTaskExecutor`]), won't deadlock the wait.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn block_on_driven<F: Future>(&self, future: F) -> F::Output {
+        use crate::core::futures::task::{waker, ArcWake};
+
+        struct ThreadWaker(std::thread::Thread);
+
+        impl ArcWake for ThreadWaker {
+            fn wake_by_ref(arc_self: &Arc<Self>) {
+                arc_self.0.unpark();
+            }
+        }
+
+        let waker = waker(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+
+            let executor = {
+                let mut state = self.state();
+                state.process_preload_queue();
+                state.process_retries();
+                state.task_pool.executor().clone()
+            };
+            // The state lock must not be held while running tasks, they could lock it.
+            executor.update();
+
+            // Executors, that are not updated manually, wake the future when a task is done, the
+            // timeout is for the ones, that must be updated to make progress.
+            std::thread::park_timeout(Duration::from_millis(1));
+        }
+    }
+
+    /// Reads a preload manifest at the given path and starts loading every resource listed in it
+    /// with the priority of the manifest. The returned request could be used to track progress of
+    /// loading (on a loading screen, for example) or to wait until every resource is loaded. See
+    /// [`PreloadManifest`] docs for more info.
+    pub async fn preload_manifest(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<PreloadRequest, LoadError> {
+        let path = path.as_ref();
+        let bytes = self
+            .resource_io()
+            .load_file(path)
+            .await
+            .map_err(LoadError::new)?;
+        let manifest = PreloadManifest::from_bytes(&bytes)?;
+        Ok(self.state().preload(&manifest))
+    }
+
+    /// Records the given resources as dependencies of a resource, that is being loaded, and returns
+    /// a future, that resolves when every dependency is loaded (or failed to load). Resource loaders
+    /// should await the future before committing the data of a resource, so the resource never
+    /// becomes fully loaded while its dependencies are still loading. Dependencies, that depend
+    /// (directly or indirectly) on the resource, are not waited for, otherwise cyclic dependencies
+    /// would never finish loading.
+    pub fn wait_for_dependencies(
+        &self,
+        resource: &UntypedResource,
+        dependencies: impl IntoIterator<Item = UntypedResource>,
+    ) -> JoinAll<UntypedResource> {
+        join_all(self.state().register_dependencies(resource, dependencies))
+    }
+
+    /// Same as [`Self::wait_for_dependencies`], but collects the dependencies from the given data
+    /// of the resource (using [`collect_used_resources`]).
+    #[allow(clippy::mutable_key_type)]
+    pub fn wait_for_data_dependencies(
+        &self,
+        resource: &UntypedResource,
+        data: &dyn Reflect,
+    ) -> JoinAll<UntypedResource> {
+        let mut dependencies = FxHashSet::default();
+        collect_used_resources(data, &mut dependencies);
+        self.wait_for_dependencies(resource, dependencies)
+    }
+
+    /// Records the given files as file dependencies of a resource. File dependencies are plain files
+    /// (not resources), that were read by the loader of the resource to produce its data, for
+    /// example, files included by a shader. The resource is reloaded when any of the files is
+    /// changed, see [`ResourceManagerState::try_reload_resource_from_path`]. Previously recorded
+    /// file dependencies of the resource are replaced.
+    pub fn set_file_dependencies(
+        &self,
+        resource: &UntypedResource,
+        files: impl IntoIterator<Item = PathBuf>,
+    ) {
+        self.state().set_file_dependencies(resource, files)
+    }
+
+    /// Checks every resource in the given folder (recursively) and returns a report with every
+    /// found problem: resources that fail to load, references to missing files, type mismatches and
+    /// cyclic dependencies. Every file, that has a loader registered for its extension, is loaded
+    /// (if it is not loaded already). See [`ProjectValidationReport`] docs for more info.
+    #[allow(clippy::mutable_key_type)]
+    pub async fn validate_project(&self, root: impl AsRef<Path>) -> ProjectValidationReport {
+        let root = root.as_ref();
+        let io = self.state().resource_io.clone();
+        let mut report = ProjectValidationReport::default();
+
+        let mut paths = match io.walk_directory(root).await {
+            Ok(paths) => paths.collect::<Vec<_>>(),
+            Err(error) => {
+                report.failed.push(FailedResource {
+                    path: root.to_path_buf(),
+                    reason: format!("{:?}", error),
+                });
+                return report;
+            }
+        };
+        {
+            let state = self.state();
+            paths.retain(|path| {
+                path.extension().is_some_and(|extension| {
+                    state
+                        .loaders
+                        .iter_for_extension(&extension.to_string_lossy())
+                        .next()
+                        .is_some()
+                })
+            });
+        }
+        paths.sort();
+
+        // Every handle of a path is checked, because the same path could be used by resources of
+        // different types.
+        let mut handles = FxHashMap::<PathBuf, Vec<UntypedResource>>::default();
+        let mut referenced_by = FxHashMap::<PathBuf, Vec<PathBuf>>::default();
+        let mut graph = FxHashMap::<PathBuf, Vec<PathBuf>>::default();
+
+        for path in paths.iter() {
+            let resource = self.request_untyped(path);
+            handles
+                .entry(path.clone())
+                .or_default()
+                .push(resource.clone());
+
+            if let Err(error) = resource.clone().await {
+                report.failed.push(FailedResource {
+                    path: path.clone(),
+                    reason: format!("{:?}", error),
+                });
+                continue;
+            }
+
+            let mut dependencies = FxHashSet::default();
+            if let ResourceState::Ok(ref data) = *resource.lock() {
+                data.as_reflect(&mut |entity| collect_used_resources(entity, &mut dependencies));
+            }
+
+            let mut edges = Vec::new();
+            for dependency in dependencies {
+                let dependency_path = dependency.path();
+                if dependency.is_embedded() || dependency_path == PathBuf::default() {
+                    continue;
+                }
+                referenced_by
+                    .entry(dependency_path.clone())
+                    .or_default()
+                    .push(path.clone());
+                let path_handles = handles.entry(dependency_path.clone()).or_default();
+                if !path_handles.contains(&dependency) {
+                    path_handles.push(dependency);
+                }
+                edges.push(dependency_path);
+            }
+            edges.sort();
+            edges.dedup();
+            graph.insert(path.clone(), edges);
+        }
+
+        let mut handles = handles.into_iter().collect::<Vec<_>>();
+        handles.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (path, path_handles) in handles {
+            if !io.exists(&path).await {
+                if let Some(mut referenced_by) = referenced_by.remove(&path) {
+                    referenced_by.sort();
+                    referenced_by.dedup();
+                    report.missing.push(MissingResource {
+                        path,
+                        referenced_by,
+                    });
+                }
+                continue;
+            }
+
+            let mut actual = Vec::new();
+            for handle in path_handles {
+                if let Err(error) = handle.clone().await {
+                    if !report.failed.iter().any(|failed| failed.path == path) {
+                        report.failed.push(FailedResource {
+                            path: path.clone(),
+                            reason: format!("{:?}", error),
+                        });
+                    }
+                }
+                let type_uuid = handle.type_uuid();
+                if !actual.contains(&type_uuid) {
+                    actual.push(type_uuid);
+                }
+            }
+
+            let mut expected = Vec::new();
+            if let Some(extension) = path.extension() {
+                let state = self.state();
+                for loader in state
+                    .loaders
+                    .iter_for_extension(&extension.to_string_lossy())
+                {
+                    if !expected.contains(&loader.data_type_uuid()) {
+                        expected.push(loader.data_type_uuid());
+                    }
+                }
+            }
+
+            if !expected.is_empty()
+                && (actual.len() > 1 || actual.iter().any(|uuid| !expected.contains(uuid)))
+            {
+                report.type_mismatches.push(TypeMismatch {
+                    path,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        report.cycles = find_cycles(&graph);
+        report.checked = paths;
+        report
+    }
+
     /// Saves given resources in the specified path and registers it in resource manager, so
     /// it will be accessible through it later.
     pub fn register<P, F>(
@@ -218,7 +850,7 @@ impl ResourceManager {
         if state.find(path.as_ref()).is_some() {
             Err(ResourceRegistrationError::AlreadyRegistered)
         } else {
-            let mut texture_state = resource.0.lock();
+            let mut texture_state = resource.lock();
             match &mut *texture_state {
                 ResourceState::Ok(data) => {
                     data.set_path(path.as_ref().to_path_buf());
@@ -226,7 +858,8 @@ impl ResourceManager {
                         Err(ResourceRegistrationError::UnableToRegister)
                     } else {
                         std::mem::drop(texture_state);
-                        state.push(resource);
+                        state.push(resource.clone());
+                        state.sync_registry_entry(&resource);
                         Ok(())
                     }
                 }
@@ -247,6 +880,16 @@ impl ResourceManager {
         let io = self.state().resource_io.clone();
         let existing_path = resource.path();
 
+        if self.state().is_read_only() {
+            return Err(FileLoadError::Custom(
+                ReadOnlyError {
+                    path: existing_path,
+                    read_only_mode: true,
+                }
+                .to_string(),
+            ));
+        }
+
         let canonical_existing_path = io.canonicalize_path(&existing_path).await?;
 
         // Collect all resources referencing the resource.
@@ -267,7 +910,7 @@ impl ResourceManager {
         let mut pairs = resources_to_fix
             .par_iter()
             .filter_map(|loaded_resource| {
-                let mut guard = loaded_resource.0.lock();
+                let mut guard = loaded_resource.lock();
                 if let ResourceState::Ok(data) = &mut *guard {
                     let mut used_resources = FxHashSet::default();
                     (**data).as_reflect(&mut |reflect| {
@@ -303,22 +946,20 @@ impl ResourceManager {
                     resource.set_path(new_path.clone());
                 }
 
-                let mut guard = loaded_resource.0.lock();
-                if let ResourceState::Ok(data) = &mut *guard {
-                    // Save the resource back.
-                    let loaded_resource_path = data.path().to_owned();
-                    match data.save(&loaded_resource_path) {
-                        Ok(_) => Log::info(format!(
-                            "Resource {} was saved successfully!",
-                            data.path().display()
-                        )),
-                        Err(err) => Log::err(format!(
-                            "Unable to save {} resource. Reason: {:?}",
-                            data.path().display(),
-                            err
-                        )),
-                    };
-                }
+                // Save the resource back. Read-only resources are not saved, they still could find
+                // the moved resource via the redirect.
+                let loaded_resource_path = loaded_resource.path();
+                match self.save_resource(loaded_resource) {
+                    Ok(_) => Log::info(format!(
+                        "Resource {} was saved successfully!",
+                        loaded_resource_path.display()
+                    )),
+                    Err(err) => Log::err(format!(
+                        "Unable to save {} resource. Reason: {:?}",
+                        loaded_resource_path.display(),
+                        err
+                    )),
+                };
             }
         }
 
@@ -333,646 +974,3792 @@ impl ResourceManager {
         Ok(())
     }
 
-    /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
-    /// method! This method is asynchronous, it uses all available CPU power to reload resources as
-    /// fast as possible.
-    pub async fn reload_resources(&self) {
-        let resources = self.state().reload_resources();
-        join_all(resources).await;
-    }
-}
-
-impl ResourceManagerState {
-    pub(crate) fn new() -> Self {
-        Self {
-            resources: Default::default(),
-            task_pool: Arc::new(Default::default()),
-            loaders: Default::default(),
-            event_broadcaster: Default::default(),
-            constructors_container: Default::default(),
-            watcher: None,
-            built_in_resources: Default::default(),
-            // Use the file system resource io by default
-            resource_io: Arc::new(FsResourceIo),
+    /// Walks the given directory recursively and collects paths of every file, that could be loaded
+    /// by one of the registered resource loaders. This method is useful for asset processing tools,
+    /// that need to handle every resource of a project. If the directory is covered by the registry
+    /// snapshot of the manager (see [`RegistrySnapshot`] docs), the paths are taken from the snapshot
+    /// and the directory is not walked.
+    pub async fn find_loadable_resources(
+        &self,
+        directory: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>, FileLoadError> {
+        let directory = directory.as_ref();
+        {
+            let state = self.state();
+            if let Some(registry) = state.registry.as_ref().filter(|r| r.covers(directory)) {
+                return Ok(registry
+                    .paths_in(directory)
+                    .filter(|path| state.can_load(path))
+                    .map(|path| path.to_path_buf())
+                    .collect());
+            }
         }
-    }
 
-    /// Set the IO source that the resource manager should use when
-    /// loading assets
-    pub fn set_resource_io(&mut self, resource_io: Arc<dyn ResourceIo>) {
-        self.resource_io = resource_io;
+        let io = self.state().resource_io.clone();
+        let mut paths = Vec::new();
+        for path in io.walk_directory(directory).await? {
+            if io.is_file(&path).await && self.state().can_load(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
     }
 
-    /// Sets resource watcher which will track any modifications in file system and forcing
-    /// the manager to reload changed resources. By default there is no watcher, since it
-    /// may be an undesired effect to reload resources at runtime. This is very useful thing
-    /// for fast iterative development.
-    pub fn set_watcher(&mut self, watcher: Option<FileSystemWatcher>) {
-        self.watcher = watcher;
+    /// Walks the given directory recursively and builds a registry snapshot of every loadable file in
+    /// it: its type, known dependencies and the hash of its import options. The snapshot could be
+    /// saved and then loaded on the next launch with [`Self::load_registry_snapshot`], which avoids
+    /// the walk. See [`RegistrySnapshot`] docs for more info.
+    ///
+    /// Dependencies are known only for the resources, that were loaded by the manager (or for the
+    /// entries of the current snapshot of the manager).
+    pub async fn build_registry_snapshot(
+        &self,
+        root: impl AsRef<Path>,
+    ) -> Result<RegistrySnapshot, FileLoadError> {
+        let root = root.as_ref();
+        let io = self.state().resource_io.clone();
+        let mut snapshot = RegistrySnapshot::new(root);
+        for path in io.walk_directory(root).await? {
+            if !io.is_file(&path).await {
+                continue;
+            }
+            let Some(mut entry) = self.state().known_registry_entry(&path) else {
+                continue;
+            };
+            entry.options_hash = read_options_hash(&path, &*io).await;
+            snapshot.add_entry(entry);
+        }
+        Ok(snapshot)
     }
 
-    /// Returns total amount of registered resources.
-    pub fn count_registered_resources(&self) -> usize {
-        self.resources.len()
+    /// Loads a registry snapshot from the given file and makes it the current snapshot of the manager
+    /// (see [`ResourceManagerState::set_registry_snapshot`]). If the snapshot could not be loaded
+    /// (it does not exist or it was made by a different version of the engine), the manager keeps
+    /// working without it and the snapshot should be rebuilt with [`Self::build_registry_snapshot`].
+    ///
+    /// ```rust,no_run
+    /// # use fyrox_resource::manager::ResourceManager;
+    /// # async fn startup(resource_manager: ResourceManager) {
+    /// if resource_manager
+    ///     .load_registry_snapshot("data.registry")
+    ///     .await
+    ///     .is_err()
+    /// {
+    ///     let snapshot = resource_manager.build_registry_snapshot("data").await.unwrap();
+    ///     snapshot.save("data.registry".as_ref()).unwrap();
+    ///     resource_manager.state().set_registry_snapshot(Some(snapshot));
+    /// }
+    /// # }
+    /// ```
+    pub async fn load_registry_snapshot(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), RegistrySnapshotError> {
+        let io = self.state().resource_io.clone();
+        let snapshot = RegistrySnapshot::load(path.as_ref(), &*io).await?;
+        self.state().set_registry_snapshot(Some(snapshot));
+        Ok(())
     }
 
-    /// Returns percentage of loading progress. This method is useful to show progress on
-    /// loading screen in your game. This method could be used alone if your game depends
-    /// only on external resources, or if your game doing some heavy calculations this value
-    /// can be combined with progress of your tasks.
-    pub fn loading_progress(&self) -> usize {
-        let registered = self.count_registered_resources();
-        if registered > 0 {
-            self.count_loaded_resources() * 100 / registered
-        } else {
-            100
+    /// Sets new locale settings (see [`LocaleSettings`] docs for more info). Every resource, that
+    /// is requested after this call, is loaded from its variant for the new locale. Every loaded
+    /// resource, that has a different variant for the new locale, is reloaded in the background,
+    /// so the content is swapped without changing the paths of the resources.
+    ///
+    /// ```rust,no_run
+    /// # use fyrox_resource::{locale::LocaleSettings, manager::ResourceManager};
+    /// # let resource_manager = ResourceManager::new();
+    /// resource_manager.set_locale_settings(LocaleSettings::new("de").with_fallback("en"));
+    /// ```
+    pub fn set_locale_settings(&self, settings: LocaleSettings) {
+        let mut state = self.state();
+        if state.locale == settings {
+            return;
         }
+        let old_settings = std::mem::replace(&mut state.locale, settings.clone());
+        state.prefetcher.clear_cache();
+        let io = state.resource_io.clone();
+        let resources = state
+            .resources()
+            .into_iter()
+            .filter(|resource| {
+                !resource.is_loading() && !state.embedded_resources.contains(&resource.path())
+            })
+            .collect::<Vec<_>>();
+        let resource_manager = self.clone();
+        state.task_pool.spawn_task(async move {
+            for resource in resources {
+                let path = resource.path();
+                let old_variant = old_settings.resolve(&path, &*io).await;
+                let new_variant = settings.resolve(&path, &*io).await;
+                if old_variant != new_variant {
+                    resource_manager.state().reload_resource(resource);
+                }
+            }
+        });
     }
 
-    /// Update resource containers and do hot-reloading.
-    ///
-    /// Resources are removed if they're not used
-    /// or reloaded if they have changed in disk.
+    /// Sets new platform settings (see [`PlatformSettings`] docs for more info). Every resource, that
+    /// is requested after this call, is loaded from its variant for the new platform tag. Every
+    /// loaded resource, that has a different variant for the new tag, is reloaded in the background.
     ///
-    /// Normally, this is called from `Engine::update()`.
-    /// You should only call this manually if you don't use that method.
-    pub fn update(&mut self, dt: f32) {
-        self.resources.retain_mut(|resource| {
-            // One usage means that the resource has single owner, and that owner
-            // is this container. Such resources have limited life time, if the time
-            // runs out before it gets shared again, the resource will be deleted.
-            if resource.value.use_count() <= 1 {
-                resource.time_to_live -= dt;
-                if resource.time_to_live <= 0.0 {
-                    let path = resource.0.lock().path().to_path_buf();
+    /// ```rust,no_run
+    /// # use fyrox_resource::{manager::ResourceManager, platform::PlatformSettings};
+    /// # let resource_manager = ResourceManager::new();
+    /// resource_manager.set_platform_settings(PlatformSettings::current());
+    /// ```
+    pub fn set_platform_settings(&self, settings: PlatformSettings) {
+        let mut state = self.state();
+        if state.platform == settings {
+            return;
+        }
+        let old_settings = std::mem::replace(&mut state.platform, settings.clone());
+        state.prefetcher.clear_cache();
+        let io = state.resource_io.clone();
+        let resources = state
+            .resources()
+            .into_iter()
+            .filter(|resource| {
+                !resource.is_loading() && !state.embedded_resources.contains(&resource.path())
+            })
+            .map(|resource| {
+                let loaders = state.find_loaders(&resource.path(), Some(resource.type_uuid()));
+                let extensions = variant_extensions(&loaders);
+                (resource, extensions)
+            })
+            .collect::<Vec<_>>();
+        let resource_manager = self.clone();
+        state.task_pool.spawn_task(async move {
+            for (resource, extensions) in resources {
+                let path = resource.path();
+                let old_variant = old_settings.resolve(&path, &extensions, &*io).await;
+                let new_variant = settings.resolve(&path, &extensions, &*io).await;
+                if old_variant != new_variant {
+                    resource_manager.state().reload_resource(resource);
+                }
+            }
+        });
+    }
 
-                    Log::info(format!(
-                        "Resource {} destroyed because it is not used anymore!",
-                        path.display()
+    /// Saves every loaded resource, that was modified (see [`ResourceData::is_dirty`]) and is
+    /// neither embedded nor read-only, to its file. Returns the amount of saved resources, every
+    /// failed attempt to save a resource is written to the log. Nothing is saved in read-only mode
+    /// (see [`ResourceManagerState::set_read_only`]).
+    pub fn save_all_modified(&self) -> usize {
+        let state = self.state();
+        let resources = state.resources();
+        if state.is_read_only() {
+            let modified = resources
+                .iter()
+                .filter(|resource| {
+                    matches!(&*resource.lock(), ResourceState::Ok(data) if data.is_dirty())
+                })
+                .count();
+            if modified > 0 {
+                Log::err(format!(
+                    "Unable to save {modified} modified resource(s), because resources are in \
+                    read-only mode."
+                ));
+            }
+            return 0;
+        }
+        // Filter out read-only resources while the state is locked, but save the rest without it,
+        // so the saving does not block the manager.
+        let resources = resources
+            .into_iter()
+            .filter(|resource| match state.check_writable(resource) {
+                Ok(()) => true,
+                Err(err) => {
+                    if matches!(&*resource.lock(), ResourceState::Ok(data) if data.is_dirty()) {
+                        Log::err(format!("Unable to save modified resource. Reason: {err}"));
+                    }
+                    false
+                }
+            })
+            .collect::<Vec<_>>();
+        drop(state);
+
+        let mut saved = 0;
+        for resource in resources {
+            let mut state = resource.lock();
+            if let ResourceState::Ok(ref mut data) = *state {
+                if data.is_dirty() && !data.is_embedded() {
+                    let path = data.path().to_path_buf();
+                    match data.save(&path) {
+                        Ok(()) => saved += 1,
+                        Err(err) => Log::err(format!(
+                            "Unable to save modified resource {}. Reason: {:?}",
+                            path.display(),
+                            err
+                        )),
+                    }
+                }
+            }
+        }
+        saved
+    }
+
+    /// Saves the given resource to its file. Unlike [`ResourceData::save`], it returns an error,
+    /// if resources are in read-only mode (see [`ResourceManagerState::set_read_only`]), the
+    /// resource is read-only (see [`crate::protection::ReadOnlyResources`]), embedded or not loaded.
+    pub fn save_resource(&self, resource: &UntypedResource) -> Result<(), ResourceSaveError> {
+        self.state()
+            .check_writable(resource)
+            .map_err(|_| ResourceSaveError::ReadOnly)?;
+        match *resource.lock() {
+            ResourceState::Ok(ref mut data) => {
+                if data.is_embedded() {
+                    return Err(ResourceSaveError::Save(
+                        "Embedded resources cannot be saved to a file.".to_string(),
                     ));
+                }
+                let path = data.path().to_path_buf();
+                data.save(&path)
+                    .map_err(|err| ResourceSaveError::Save(err.to_string()))
+            }
+            _ => Err(ResourceSaveError::NotLoaded),
+        }
+    }
 
-                    self.event_broadcaster
-                        .broadcast(ResourceEvent::Removed(path));
+    /// Saves the given import options of a resource at the given path to its import options file.
+    /// Returns `false` if the options could not be saved or resources are in read-only mode (see
+    /// [`ResourceManagerState::set_read_only`]).
+    pub fn save_import_options(
+        &self,
+        options: &dyn BaseImportOptions,
+        resource_path: impl AsRef<Path>,
+    ) -> bool {
+        let path = append_extension(resource_path.as_ref(), OPTIONS_EXTENSION);
+        if self.state().is_read_only() {
+            Log::err(format!(
+                "Unable to save import options to {}, because resources are in read-only mode.",
+                path.display()
+            ));
+            return false;
+        }
+        options.save(&path)
+    }
 
-                    false
-                } else {
-                    // Keep resource alive for short period of time.
-                    true
+    /// Renames (moves) a resource file and updates every reference to it. The file is moved using
+    /// the resource IO of the manager (along with its import options file, if any), then
+    /// [`ResourceManagerState::apply_rename`] is used to update the registry: the loaded resource
+    /// gets the new path, every loaded resource, that references it, is patched and marked as
+    /// modified, and a redirect from the old path to the new one is recorded. The redirect makes
+    /// requests of the old path (from scenes, that were saved before the renaming, for example)
+    /// return the renamed resource.
+    ///
+    /// ```rust,no_run
+    /// # use fyrox_resource::{core::futures::executor::block_on, manager::ResourceManager};
+    /// # let resource_manager = ResourceManager::new();
+    /// block_on(resource_manager.rename_resource("data/rock.png", "data/textures/rock.png")).unwrap();
+    /// // Persist the new path in every scene, that uses the texture.
+    /// resource_manager.save_all_modified();
+    /// ```
+    pub async fn rename_resource(
+        &self,
+        old_path: impl AsRef<Path>,
+        new_path: impl AsRef<Path>,
+    ) -> Result<(), ResourceRenameError> {
+        let old_path = old_path.as_ref();
+        let new_path = new_path.as_ref();
+
+        let (io, same_key) = {
+            let state = self.state();
+            if state.is_read_only() {
+                return Err(ResourceRenameError::ReadOnly(ReadOnlyError {
+                    path: old_path.to_path_buf(),
+                    read_only_mode: true,
+                }));
+            }
+            (
+                state.resource_io.clone(),
+                state.path_key(old_path) == state.path_key(new_path),
+            )
+        };
+
+        // Changing the case of a path on a case-insensitive file system is not a conflict.
+        if !same_key && io.exists(new_path).await {
+            return Err(ResourceRenameError::AlreadyExists(new_path.to_path_buf()));
+        }
+
+        io.move_file(old_path, new_path)
+            .await
+            .map_err(ResourceRenameError::Io)?;
+
+        let old_options_path = append_extension(old_path, OPTIONS_EXTENSION);
+        if io.exists(&old_options_path).await {
+            let new_options_path = append_extension(new_path, OPTIONS_EXTENSION);
+            if let Err(err) = io.move_file(&old_options_path, &new_options_path).await {
+                Log::warn(format!(
+                    "Unable to move import options file {}. Reason: {:?}",
+                    old_options_path.display(),
+                    err
+                ));
+            }
+        }
+
+        self.state().apply_rename(old_path, new_path);
+
+        Ok(())
+    }
+
+    /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
+    /// method! This method is asynchronous, it uses all available CPU power to reload resources as
+    /// fast as possible.
+    pub async fn reload_resources(&self) {
+        let resources = self.state().reload_resources();
+        join_all(resources).await;
+    }
+}
+
+impl ResourceManagerState {
+    pub(crate) fn new() -> Self {
+        Self {
+            resources: Default::default(),
+            task_pool: Default::default(),
+            statistics: Default::default(),
+            loaders: Default::default(),
+            event_broadcaster: Default::default(),
+            constructors_container: Default::default(),
+            watcher: None,
+            groups: Default::default(),
+            built_in_resources: Default::default(),
+            // Use the file system resource io by default
+            resource_io: Arc::new(FsResourceIo),
+            migrations: Default::default(),
+            embedded_resources: Default::default(),
+            importers: Default::default(),
+            derived_data_cache: Default::default(),
+            case_insensitive_paths: cfg!(windows),
+            content_deduplication: false,
+            content_hashes: Default::default(),
+            content_duplicates: Default::default(),
+            content_reports: Default::default(),
+            preload_queue: Default::default(),
+            active_preloads: Default::default(),
+            max_concurrent_preloads: 8,
+            dependencies: Default::default(),
+            file_dependencies: Default::default(),
+            streamer: Default::default(),
+            redirects: Default::default(),
+            locale: Default::default(),
+            platform: Default::default(),
+            prefetcher: Default::default(),
+            registry: None,
+            retrier: Default::default(),
+            tags: Default::default(),
+            read_only: false,
+            read_only_resources: Default::default(),
+        }
+    }
+
+    /// Returns current locale settings. See [`LocaleSettings`] docs for more info.
+    pub fn locale_settings(&self) -> &LocaleSettings {
+        &self.locale
+    }
+
+    /// Returns current platform settings. See [`PlatformSettings`] docs for more info.
+    pub fn platform_settings(&self) -> &PlatformSettings {
+        &self.platform
+    }
+
+    /// Sets whether resource paths are case-insensitive or not. Resources are identified by
+    /// normalized paths (see [`ResourcePathKey`] docs for more info), so `textures/foo.png` and
+    /// `textures\foo.png` are always the same resource. If case-insensitivity is enabled, then
+    /// `Textures/FOO.PNG` is the same resource as well. By default, paths are case-insensitive on
+    /// Windows only, because its file system is case-insensitive.
+    pub fn set_case_insensitive_paths(&mut self, case_insensitive: bool) {
+        self.case_insensitive_paths = case_insensitive;
+        self.read_only_resources
+            .set_case_insensitive_paths(case_insensitive);
+    }
+
+    /// Returns `true` if resource paths are case-insensitive, `false` - otherwise. See
+    /// [`Self::set_case_insensitive_paths`] for more info.
+    pub fn is_case_insensitive_paths(&self) -> bool {
+        self.case_insensitive_paths
+    }
+
+    /// Enables or disables detection of duplicated content. When enabled, the content of every
+    /// loaded file is hashed by its loading task and if a resource of the same type with the same
+    /// content is registered already (a copy of the same texture under a different path, for
+    /// example), then the duplicate is reported on the next [`Self::update`], see
+    /// [`Self::content_duplicates`]. The duplicate stays a separate resource, because its handles
+    /// cannot be redirected to the existing resource and its users must keep its path. The
+    /// reported copies could be removed from the project and their users could be remapped to
+    /// the existing resources (see [`crate::collect::remap_used_resources`]) to reduce memory
+    /// usage.
+    ///
+    /// Detection is disabled by default, because every loaded file must be hashed. Only the files
+    /// read using [`ResourceIo::load_file`] are hashed.
+    pub fn set_content_deduplication(&mut self, enabled: bool) {
+        self.content_deduplication = enabled;
+        if !enabled {
+            self.content_hashes.clear();
+            self.content_duplicates.clear();
+            self.content_reports.lock().clear();
+        }
+    }
+
+    /// Returns `true` if content deduplication is enabled. See [`Self::set_content_deduplication`]
+    /// for more info.
+    pub fn is_content_deduplication_enabled(&self) -> bool {
+        self.content_deduplication
+    }
+
+    /// Returns an iterator over paths of loaded duplicates and the paths of the first loaded
+    /// resources with the same content. See [`Self::set_content_deduplication`] for more info.
+    pub fn content_duplicates(&self) -> impl Iterator<Item = (&ResourcePathKey, &Path)> {
+        self.content_duplicates
+            .iter()
+            .map(|(duplicate, path)| (duplicate, path.as_path()))
+    }
+
+    /// Sets the maximum amount of preload requests with [`LoadPriority::Normal`] and
+    /// [`LoadPriority::Low`] priorities, that could be loading at the same time. Other requests
+    /// wait in a queue, so they do not delay explicit requests. Default value is 8.
+    pub fn set_max_concurrent_preloads(&mut self, max: usize) {
+        self.max_concurrent_preloads = max.max(1);
+    }
+
+    /// Returns the maximum amount of preload requests, that could be loading at the same time. See
+    /// [`Self::set_max_concurrent_preloads`] for more info.
+    pub fn max_concurrent_preloads(&self) -> usize {
+        self.max_concurrent_preloads
+    }
+
+    /// Adds the file of a resource at the given path to the prefetch queue. The file is read in
+    /// background at low priority and its content is kept in memory, so a later request of the
+    /// resource skips reading the file. Nothing happens if the resource is loaded already, or if
+    /// there's no loader for it. See [`PrefetchSettings`] docs for more info.
+    pub fn prefetch(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if self.find(path).is_some()
+            || self.embedded_resources.contains(path)
+            || !self.can_load(path)
+        {
+            return;
+        }
+        let key = self.path_key(path);
+        self.prefetcher.enqueue(key, path.to_path_buf());
+    }
+
+    /// Sets new prefetch settings. See [`PrefetchSettings`] docs for more info.
+    pub fn set_prefetch_settings(&mut self, settings: PrefetchSettings) {
+        self.prefetcher.settings = settings;
+    }
+
+    /// Returns current prefetch settings.
+    pub fn prefetch_settings(&self) -> &PrefetchSettings {
+        &self.prefetcher.settings
+    }
+
+    /// Sets new retry policy for loads, that failed because of transient errors. See
+    /// [`RetryPolicy`] docs for more info.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retrier.policy = policy;
+    }
+
+    /// Returns current retry policy.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retrier.policy
+    }
+
+    /// Returns the amount of loads, that have failed because of transient errors and are waiting
+    /// for a retry.
+    pub fn count_scheduled_retries(&self) -> usize {
+        self.retrier.scheduled()
+    }
+
+    /// Returns the amount of files, that are waiting in the prefetch queue.
+    pub fn count_queued_prefetches(&self) -> usize {
+        self.prefetcher.queued()
+    }
+
+    /// Returns `true` if the file at the given path is prefetched and not requested yet.
+    pub fn is_prefetched(&self, path: impl AsRef<Path>) -> bool {
+        self.prefetcher.contains(&self.path_key(path.as_ref()))
+    }
+
+    /// Returns the total size of prefetched data in bytes.
+    pub fn prefetched_size(&self) -> usize {
+        self.prefetcher.cached_size()
+    }
+
+    /// Clears the prefetch queue and drops every prefetched data.
+    pub fn clear_prefetched(&mut self) {
+        self.prefetcher.clear();
+    }
+
+    /// Returns the amount of preload requests, that are waiting in the queue.
+    pub fn count_queued_preloads(&self) -> usize {
+        self.preload_queue.len()
+    }
+
+    /// Requests every stage of the given streamable resource up to the given stage (inclusive) to be
+    /// loaded. Stages are loaded one at a time in background, [`ResourceEvent::StageLoaded`] event is
+    /// sent when a stage is loaded. Returns `false` if the resource is not loaded yet or if it is not
+    /// streamable. See [`crate::streaming::StreamableResourceData`] docs for more info.
+    pub fn request_stages(&mut self, resource: &UntypedResource, stage: usize) -> bool {
+        self.streamer.request_stages(resource, stage)
+    }
+
+    /// Unloads every stage of the given streamable resource after the given stage, that supports
+    /// unloading. Stages that are loading at the moment are not affected. Returns the amount of
+    /// unloaded stages.
+    pub fn unload_stages(&mut self, resource: &UntypedResource, keep: usize) -> usize {
+        self.streamer
+            .unload_stages(resource, keep, &self.event_broadcaster)
+    }
+
+    /// Returns states of every stage of the given streamable resource. The list is empty if no stages
+    /// of the resource were requested (see [`Self::request_stages`]).
+    pub fn stage_states(&self, resource: &UntypedResource) -> Vec<StageState> {
+        self.streamer.stage_states(resource)
+    }
+
+    /// Starts loading every resource of the given manifest with the priority of the manifest. See
+    /// [`PreloadManifest`] docs for more info.
+    pub fn preload(&mut self, manifest: &PreloadManifest) -> PreloadRequest {
+        let resources = manifest
+            .resources
+            .iter()
+            .map(|path| {
+                let resource = self.request_internal(path, None, Some(manifest.priority));
+                if let Some(group) = manifest.group.as_ref() {
+                    self.add_to_group(resource.clone(), group);
                 }
+                resource
+            })
+            .collect();
+        self.process_preload_queue();
+        PreloadRequest::new(resources)
+    }
+
+    fn enqueue_preload(&mut self, resource: UntypedResource, priority: LoadPriority) {
+        let index = self
+            .preload_queue
+            .partition_point(|(queued_priority, _)| *queued_priority >= priority);
+        self.preload_queue.insert(index, (priority, resource));
+    }
+
+    /// Starts reading of the next file from the prefetch queue, if the throughput budget allows it.
+    fn process_prefetch_queue(&mut self, dt: f32) {
+        let is_loading = self.prefetcher.queued() > 0
+            && self.resources.iter().any(|entry| entry.value.is_loading());
+        if let Some((path, completion)) = self.prefetcher.update(dt, is_loading) {
+            // The resource could be requested while it was waiting in the queue.
+            if self.find(&path).is_some() {
+                completion.complete(None);
+                return;
+            }
+            let io = self.source_io(&path, &self.find_loaders(&path, None));
+            self.task_pool.spawn_task(async move {
+                completion.complete(io.load_file(&path).await.ok());
+            });
+        }
+    }
+
+    /// Starts loads, that have failed because of transient errors, once their delay runs out.
+    fn process_retries(&mut self) {
+        for retry in self.retrier.take_due() {
+            // Deferred errors keep the resource in the pending state, it could be committed from
+            // elsewhere in the meantime.
+            if !retry.resource.is_loading() {
+                continue;
+            }
+            let path = retry.resource.path();
+            let loaders = self.find_reload_loaders(&path, retry.resource.type_uuid());
+            if loaders.is_empty() {
+                let msg = format!(
+                    "There's no resource loader for {} resource!",
+                    path.display()
+                );
+                retry.resource.commit_error(path, msg);
             } else {
-                // Make sure to reset timer if a resource is used by more than one owner.
-                resource.time_to_live = DEFAULT_RESOURCE_LIFETIME;
+                self.spawn_loading_attempt(&loaders, retry.resource, retry.reload, retry.attempt);
+            }
+        }
+    }
 
-                // Keep resource alive while it has more than one owner.
-                true
+    /// Starts queued preload requests, while there's free preload slots.
+    fn process_preload_queue(&mut self) {
+        self.active_preloads
+            .retain(|resource| resource.is_loading());
+        while self.active_preloads.len() < self.max_concurrent_preloads {
+            let Some((_, resource)) = self.preload_queue.pop_front() else {
+                break;
+            };
+            self.start_preload(resource.clone());
+            self.active_preloads.push(resource);
+        }
+    }
+
+    fn start_preload(&self, resource: UntypedResource) {
+        let loaders = self.find_loaders(&resource.path(), Some(resource.type_uuid()));
+        self.spawn_loading_task(&loaders, resource, false);
+    }
+
+    /// Starts loading of the given resource immediately, if it is waiting in the preload queue.
+    fn promote_preload(&mut self, resource: &UntypedResource) {
+        if let Some(index) = self
+            .preload_queue
+            .iter()
+            .position(|(_, queued)| queued == resource)
+        {
+            if let Some((_, resource)) = self.preload_queue.remove(index) {
+                self.start_preload(resource);
             }
-        });
+        }
+    }
 
-        if let Some(watcher) = self.watcher.as_ref() {
-            if let Some(evt) = watcher.try_get_event() {
-                if let notify::EventKind::Modify(_) = evt.kind {
-                    for path in evt.paths {
-                        if let Ok(relative_path) = make_relative_path(path) {
-                            if self.try_reload_resource_from_path(&relative_path) {
-                                Log::info(format!(
-                                        "File {} was changed, trying to reload a respective resource...",
-                                        relative_path.display()
-                                    ));
+    /// Returns paths of the dependencies of a resource at the given path, that were reported by
+    /// the loader of the resource. See [`ResourceManager::wait_for_dependencies`] for more info.
+    pub fn dependencies_of(&self, path: &Path) -> &[PathBuf] {
+        self.dependencies
+            .get(&self.path_key(path))
+            .map(|dependencies| dependencies.as_slice())
+            .unwrap_or_default()
+    }
 
-                                break;
-                            }
-                        }
-                    }
+    /// Returns paths of the file dependencies of a resource at the given path. See
+    /// [`ResourceManager::set_file_dependencies`] for more info.
+    pub fn file_dependencies_of(&self, path: &Path) -> &[PathBuf] {
+        self.file_dependencies
+            .get(&self.path_key(path))
+            .map(|files| files.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Records the given files as file dependencies of a resource. See
+    /// [`ResourceManager::set_file_dependencies`] for more info.
+    pub fn set_file_dependencies(
+        &mut self,
+        resource: &UntypedResource,
+        files: impl IntoIterator<Item = PathBuf>,
+    ) {
+        let key = self.path_key(&resource.path());
+        let files = files.into_iter().collect::<Vec<_>>();
+        if files.is_empty() {
+            self.file_dependencies.remove(&key);
+        } else {
+            self.file_dependencies.insert(key, files);
+        }
+        self.sync_registry_entry(resource);
+    }
+
+    /// Returns the current registry snapshot of the manager (if any). The snapshot is kept
+    /// up-to-date with the resources loaded or renamed by the manager, so it could be saved on exit
+    /// to be used on the next launch. See [`RegistrySnapshot`] docs for more info.
+    pub fn registry_snapshot(&self) -> Option<&RegistrySnapshot> {
+        self.registry.as_ref()
+    }
+
+    /// Sets a new registry snapshot, `None` disables the use of a snapshot. Dependency edges of the
+    /// snapshot are registered for every resource, that has no dependencies recorded yet, so
+    /// dependency-aware reloading works before the resources are loaded. See [`RegistrySnapshot`]
+    /// docs for more info.
+    pub fn set_registry_snapshot(&mut self, snapshot: Option<RegistrySnapshot>) {
+        if let Some(snapshot) = snapshot.as_ref() {
+            for entry in snapshot.entries() {
+                let key = ResourcePathKey::new(&entry.path, self.case_insensitive_paths);
+                if !entry.dependencies.is_empty() {
+                    self.dependencies
+                        .entry(key.clone())
+                        .or_insert_with(|| entry.dependencies.clone());
+                }
+                if !entry.file_dependencies.is_empty() {
+                    self.file_dependencies
+                        .entry(key)
+                        .or_insert_with(|| entry.file_dependencies.clone());
                 }
             }
         }
+        self.registry = snapshot;
     }
 
-    /// Adds a new resource in the container.
-    pub fn push(&mut self, resource: UntypedResource) {
-        self.event_broadcaster
-            .broadcast(ResourceEvent::Added(resource.clone()));
+    /// Creates a registry entry for a file at the given path from the data known to the manager,
+    /// `None` if the file could not be loaded. Import options hash is taken from the current
+    /// snapshot (if any).
+    fn known_registry_entry(&self, path: &Path) -> Option<RegistryEntry> {
+        let type_uuid = self.find_loaders(path, None).first()?.data_type_uuid();
+        let known = self
+            .registry
+            .as_ref()
+            .and_then(|registry| registry.entry(path));
+        let dependencies = self.dependencies_of(path);
+        let file_dependencies = self.file_dependencies_of(path);
+        Some(RegistryEntry {
+            path: path.to_path_buf(),
+            type_uuid,
+            dependencies: match known {
+                Some(known) if dependencies.is_empty() => known.dependencies.clone(),
+                _ => dependencies.to_vec(),
+            },
+            file_dependencies: match known {
+                Some(known) if file_dependencies.is_empty() => known.file_dependencies.clone(),
+                _ => file_dependencies.to_vec(),
+            },
+            options_hash: known.and_then(|known| known.options_hash),
+        })
+    }
 
-        self.resources.push(TimedEntry {
-            value: resource,
-            time_to_live: DEFAULT_RESOURCE_LIFETIME,
-        });
+    /// Updates the entry of the given resource in the current registry snapshot, so the snapshot
+    /// has the latest dependencies of the resource.
+    fn sync_registry_entry(&mut self, resource: &UntypedResource) {
+        let path = resource.path();
+        let is_covered = self
+            .registry
+            .as_ref()
+            .is_some_and(|registry| registry.covers(&path));
+        if !is_covered || resource.is_embedded() {
+            return;
+        }
+        let dependencies = self.dependencies_of(&path).to_vec();
+        let file_dependencies = self.file_dependencies_of(&path).to_vec();
+        let type_uuid = resource.type_uuid();
+        let Some(registry) = self.registry.as_mut() else {
+            return;
+        };
+        match registry.entry_mut(&path) {
+            Some(entry) => {
+                entry.dependencies = dependencies;
+                entry.file_dependencies = file_dependencies;
+            }
+            None => registry.add_entry(RegistryEntry {
+                path,
+                type_uuid,
+                dependencies,
+                file_dependencies,
+                options_hash: None,
+            }),
+        }
     }
 
-    /// Tries to find a resources by its path. Returns None if no resource was found.
-    ///
-    /// # Complexity
+    /// Records dependencies of a resource and returns the dependencies, that could be waited for
+    /// without a deadlock.
+    fn register_dependencies(
+        &mut self,
+        resource: &UntypedResource,
+        dependencies: impl IntoIterator<Item = UntypedResource>,
+    ) -> Vec<UntypedResource> {
+        let dependencies = dependencies
+            .into_iter()
+            .filter(|dependency| dependency != resource && !dependency.is_embedded())
+            .collect::<Vec<_>>();
+        let key = self.path_key(&resource.path());
+        self.dependencies.insert(
+            key.clone(),
+            dependencies
+                .iter()
+                .map(|dependency| dependency.path())
+                .collect(),
+        );
+        self.sync_registry_entry(resource);
+        dependencies
+            .into_iter()
+            .filter(|dependency| !self.depends_on(&dependency.path(), &key))
+            .collect()
+    }
+
+    /// Checks whether a resource at the given path depends (directly or indirectly) on a resource
+    /// with the given key.
+    fn depends_on(&self, path: &Path, target: &ResourcePathKey) -> bool {
+        let mut visited = FxHashSet::default();
+        let mut stack = vec![self.path_key(path)];
+        while let Some(key) = stack.pop() {
+            if &key == target {
+                return true;
+            }
+            if let Some(dependencies) = self.dependencies.get(&key) {
+                for dependency in dependencies {
+                    let dependency = self.path_key(dependency);
+                    if visited.insert(dependency.clone()) {
+                        stack.push(dependency);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Updates the registry after a resource file was renamed (moved) from `old_path` to `new_path`.
+    /// This method does not touch any files, it should be used directly only if the file was moved
+    /// by some other means, otherwise use [`ResourceManager::rename_resource`].
     ///
-    /// O(n)
-    pub fn find<P: AsRef<Path>>(&self, path: P) -> Option<&UntypedResource> {
-        for resource in self.resources.iter() {
-            if resource.0.lock().path() == path.as_ref() {
-                return Some(&resource.value);
+    /// The loaded resource (if any) gets the new path. Every loaded resource, that references the
+    /// renamed one and is not read-only, is patched (using [`remap_used_resources`]) and marked as
+    /// modified, so [`ResourceManager::save_all_modified`] will save the new path. Finally, a
+    /// redirect from the old path to the new one is recorded, see [`Self::add_redirect`] for more
+    /// info.
+    pub fn apply_rename(&mut self, old_path: &Path, new_path: &Path) {
+        let old_key = self.path_key(old_path);
+        let new_key = self.path_key(new_path);
+
+        let renamed = self.find_by_key(&old_key).cloned();
+        if let Some(renamed) = renamed.as_ref() {
+            renamed.set_path(new_path.to_path_buf());
+        }
+
+        for entry in self.resources.iter() {
+            let resource = &entry.value;
+            if Some(resource) == renamed.as_ref() {
+                continue;
+            }
+
+            let key = resource.key();
+            let mut state = resource.lock();
+            let ResourceState::Ok(ref mut data) = *state else {
+                continue;
+            };
+            // Read-only resources are not patched, they find the renamed resource via the redirect.
+            if self
+                .read_only_resources
+                .is_read_only_by_key(key, data.path())
+            {
+                continue;
+            }
+
+            let mut referenced = false;
+            data.as_reflect_mut(&mut |entity| {
+                remap_used_resources(entity, &mut |used| {
+                    if used == resource {
+                        // Self-references could not be checked, the resource is locked already.
+                        None
+                    } else if Some(used) == renamed.as_ref() {
+                        referenced = true;
+                        None
+                    } else if old_key.matches(&used.path()) {
+                        // A separate instance of the renamed resource (a resource, that failed to
+                        // load, for example).
+                        referenced = true;
+                        match renamed.as_ref() {
+                            Some(renamed) => Some(renamed.clone()),
+                            None => {
+                                used.set_path(new_path.to_path_buf());
+                                None
+                            }
+                        }
+                    } else {
+                        None
+                    }
+                })
+            });
+
+            if referenced {
+                data.mark_dirty();
+            }
+        }
+
+        if let Some(dependencies) = self.dependencies.remove(&old_key) {
+            self.dependencies.insert(new_key.clone(), dependencies);
+        }
+        if let Some(files) = self.file_dependencies.remove(&old_key) {
+            self.file_dependencies.insert(new_key.clone(), files);
+        }
+        if let Some(original) = self.content_duplicates.remove(&old_key) {
+            self.content_duplicates.insert(new_key.clone(), original);
+        }
+        let paths = self
+            .dependencies
+            .values_mut()
+            .flatten()
+            .chain(self.file_dependencies.values_mut().flatten())
+            .chain(self.content_hashes.values_mut())
+            .chain(self.content_duplicates.values_mut())
+            .chain(self.redirects.values_mut());
+        for path in paths {
+            if old_key.matches(path) {
+                *path = new_path.to_path_buf();
             }
         }
-        None
+        if let Some(registry) = self.registry.as_mut() {
+            registry.remap_paths(|path| {
+                if old_key.matches(path) {
+                    *path = new_path.to_path_buf();
+                }
+            });
+        }
+
+        if old_key != new_key {
+            self.add_redirect(old_path, new_path);
+        }
+    }
+
+    /// Adds a redirect from the given old path to the given new path. Every request of a resource
+    /// at the old path will return the resource at the new path instead. Redirects are
+    /// recorded automatically when a resource is renamed, so scenes, that were saved with the old
+    /// path, still load. Redirects are not persistent, they live as long as the resource manager.
+    pub fn add_redirect(&mut self, old_path: &Path, new_path: &Path) {
+        let new_key = self.path_key(new_path);
+        // The new path becomes a real resource path again.
+        self.redirects.remove(&new_key);
+        self.redirects
+            .insert(self.path_key(old_path), new_path.to_path_buf());
+    }
+
+    /// Removes a redirect from the given old path. Returns the path, the redirect pointed to, if any.
+    pub fn remove_redirect(&mut self, old_path: &Path) -> Option<PathBuf> {
+        self.redirects.remove(&self.path_key(old_path))
+    }
+
+    /// Returns an iterator over every redirect: old paths (in their normalized form) and the paths
+    /// they point to. See [`Self::add_redirect`] for more info.
+    pub fn redirects(&self) -> impl Iterator<Item = (&ResourcePathKey, &Path)> {
+        self.redirects
+            .iter()
+            .map(|(old_path, new_path)| (old_path, new_path.as_path()))
+    }
+
+    /// Follows redirects of the given path (including chains of them) and returns the final path,
+    /// or `None` if there's no redirect for the path.
+    pub fn resolve_redirect(&self, path: &Path) -> Option<PathBuf> {
+        // Chains could only appear if redirects are added manually, renaming updates existing
+        // redirects. The limit protects from cycles.
+        const MAX_REDIRECTS: usize = 16;
+
+        let mut resolved = self.redirects.get(&self.path_key(path))?;
+        for _ in 0..MAX_REDIRECTS {
+            match self.redirects.get(&self.path_key(resolved)) {
+                Some(next) => resolved = next,
+                None => break,
+            }
+        }
+        Some(resolved.clone())
+    }
+
+    /// Returns a normalized key for the given path, that is used to identify resources.
+    pub fn path_key(&self, path: &Path) -> ResourcePathKey {
+        ResourcePathKey::new(path, self.case_insensitive_paths)
+    }
+
+    /// Returns a copy of current resource loading statistics. See [`ResourceLoadStatistics`] docs
+    /// for more info.
+    pub fn statistics(&self) -> ResourceLoadStatistics {
+        self.statistics.lock().clone()
+    }
+
+    /// Removes all collected resource loading statistics.
+    pub fn clear_statistics(&mut self) {
+        self.statistics.lock().timings.clear();
+    }
+
+    /// Set the IO source that the resource manager should use when
+    /// loading assets
+    pub fn set_resource_io(&mut self, resource_io: Arc<dyn ResourceIo>) {
+        self.resource_io = resource_io;
+    }
+
+    /// Sets the executor, that will be used to run resource loading tasks. Tasks, that were spawned
+    /// before the call, stay on the previous executor. See [`TaskExecutor`] docs for more info.
+    pub fn set_task_executor(&mut self, executor: Arc<dyn TaskExecutor>) {
+        self.task_pool.set_executor(executor);
+    }
+
+    /// Returns the executor, that is used to run resource loading tasks.
+    pub fn task_executor(&self) -> &Arc<dyn TaskExecutor> {
+        self.task_pool.executor()
+    }
+
+    /// Sets resource watcher which will track any modifications in file system and forcing
+    /// the manager to reload changed resources. By default there is no watcher, since it
+    /// may be an undesired effect to reload resources at runtime. This is very useful thing
+    /// for fast iterative development.
+    ///
+    /// The watcher is ignored in read-only mode (see [`Self::set_read_only`]).
+    pub fn set_watcher(&mut self, watcher: Option<FileSystemWatcher>) {
+        if watcher.is_some() && self.is_read_only() {
+            Log::warn("File system watcher is ignored, because resources are in read-only mode.");
+            return;
+        }
+        self.watcher = watcher;
+    }
+
+    /// Enables or disables read-only mode, that is meant for shipped games running from read-only
+    /// install locations. In this mode every save or write operation of the manager (saving
+    /// resources, moving and renaming resource files, writing import options and derived data)
+    /// fails fast with [`crate::protection::ReadOnlyError`] (or skips the write), and file watching
+    /// is disabled. The mode belongs to this manager only, other managers in the same process are
+    /// not affected. Keep in mind, that the mode is not checked when resource data is saved
+    /// directly (for example, via [`crate::ResourceData::save`]).
+    ///
+    /// ```rust
+    /// # use fyrox_resource::manager::ResourceManager;
+    /// let resource_manager = ResourceManager::new();
+    /// if !cfg!(debug_assertions) {
+    ///     resource_manager.state().set_read_only(true);
+    /// }
+    /// ```
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.derived_data_cache.set_read_only(read_only);
+        if read_only {
+            self.watcher = None;
+        }
+    }
+
+    /// Returns `true` if read-only mode is enabled. See [`Self::set_read_only`] for more info.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns a reference to the set of read-only resources of this manager. See
+    /// [`ReadOnlyResources`] docs for more info.
+    pub fn read_only_resources(&self) -> &ReadOnlyResources {
+        &self.read_only_resources
+    }
+
+    /// Returns a reference to the set of read-only resources of this manager, it could be used to
+    /// protect resources and folders. See [`ReadOnlyResources`] docs for more info.
+    pub fn read_only_resources_mut(&mut self) -> &mut ReadOnlyResources {
+        &mut self.read_only_resources
+    }
+
+    /// Updates the read-only flags (see [`UntypedResource::is_read_only`]) of every registered
+    /// resource according to the set of read-only resources of this manager. Call this method after
+    /// read-only roots are added or removed, resources, that are registered later, are flagged
+    /// automatically. This method locks every registered resource.
+    pub fn sync_read_only_flags(&self) {
+        for entry in self.resources.iter() {
+            let resource = &entry.value;
+            protection::set_read_only_flag(
+                resource,
+                self.read_only_resources.is_read_only(resource),
+            );
+        }
+    }
+
+    /// Returns [`ReadOnlyError`] if the given resource cannot be modified or saved, because it is
+    /// read-only (see [`Self::read_only_resources_mut`]) or resources are in read-only mode (see
+    /// [`Self::set_read_only`]). Unlike [`crate::ResourceDataRef::try_data_mut`], this method also
+    /// checks the read-only mode. This method locks the resource.
+    pub fn check_writable(&self, resource: &UntypedResource) -> Result<(), ReadOnlyError> {
+        if self.read_only {
+            return Err(ReadOnlyError {
+                path: resource.path(),
+                read_only_mode: true,
+            });
+        }
+        self.read_only_resources
+            .check(resource.key(), &resource.path())
+    }
+
+    /// Returns total amount of registered resources.
+    pub fn count_registered_resources(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns percentage of loading progress. This method is useful to show progress on
+    /// loading screen in your game. This method could be used alone if your game depends
+    /// only on external resources, or if your game doing some heavy calculations this value
+    /// can be combined with progress of your tasks.
+    pub fn loading_progress(&self) -> usize {
+        let registered = self.count_registered_resources();
+        if registered > 0 {
+            self.count_loaded_resources() * 100 / registered
+        } else {
+            100
+        }
+    }
+
+    /// Update resource containers and do hot-reloading.
+    ///
+    /// Resources are removed if they're not used
+    /// or reloaded if they have changed in disk.
+    ///
+    /// Normally, this is called from `Engine::update()`.
+    /// You should only call this manually if you don't use that method.
+    pub fn update(&mut self, dt: f32) {
+        let mut destroyed = Vec::new();
+        self.resources.retain_mut(|resource| {
+            // One usage means that the resource has single owner, and that owner
+            // is this container. Such resources have limited life time, if the time
+            // runs out before it gets shared again, the resource will be deleted.
+            if resource.value.use_count() <= 1 {
+                resource.time_to_live -= dt;
+                if resource.time_to_live <= 0.0 {
+                    let path = resource.lock().path().to_path_buf();
+
+                    Log::info(format!(
+                        "Resource {} destroyed because it is not used anymore!",
+                        path.display()
+                    ));
+
+                    let key = ResourcePathKey::new(&path, self.case_insensitive_paths);
+                    self.dependencies.remove(&key);
+                    self.file_dependencies.remove(&key);
+                    destroyed.push(key);
+
+                    self.event_broadcaster.broadcast_unloaded(&resource.value);
+
+                    false
+                } else {
+                    // Keep resource alive for short period of time.
+                    true
+                }
+            } else {
+                // Make sure to reset timer if a resource is used by more than one owner.
+                resource.time_to_live = DEFAULT_RESOURCE_LIFETIME;
+
+                // Keep resource alive while it has more than one owner.
+                true
+            }
+        });
+
+        self.prune_content_hashes(&destroyed);
+        self.process_content_hashes();
+        self.process_preload_queue();
+        self.process_prefetch_queue(dt);
+        self.process_retries();
+
+        self.streamer
+            .update(&self.task_pool, &self.resource_io, &self.event_broadcaster);
+
+        self.task_pool.executor().update();
+
+        if let Some(watcher) = self.watcher.as_ref() {
+            if let Some(evt) = watcher.try_get_event() {
+                if let notify::EventKind::Modify(_) = evt.kind {
+                    // Temporary files of atomic writes are renamed right away.
+                    for path in evt.paths.into_iter().filter(|path| !is_temp_path(path)) {
+                        if let Ok(relative_path) = make_relative_path(path) {
+                            if self.try_reload_resource_from_path(&relative_path) {
+                                Log::info(format!(
+                                        "File {} was changed, trying to reload a respective resource...",
+                                        relative_path.display()
+                                    ));
+
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds a new resource in the container. The resource gets the read-only flag, if it is located
+    /// in a read-only root (see [`ReadOnlyResources::add_root`]).
+    pub fn push(&mut self, resource: UntypedResource) {
+        if self.read_only_resources.is_path_read_only(&resource.path()) {
+            protection::set_read_only_flag(&resource, true);
+        }
+
+        self.event_broadcaster
+            .broadcast(ResourceEvent::Added(resource.clone()));
+
+        self.resources.push(TimedEntry {
+            value: resource,
+            time_to_live: DEFAULT_RESOURCE_LIFETIME,
+        });
+    }
+
+    /// Tries to find a resources by its path. Returns None if no resource was found. Paths are
+    /// compared using their normalized form, see [`Self::set_case_insensitive_paths`] for more info.
+    ///
+    /// # Complexity
+    ///
+    /// O(n)
+    pub fn find<P: AsRef<Path>>(&self, path: P) -> Option<&UntypedResource> {
+        let key = self.path_key(path.as_ref());
+        self.find_by_key(&key).or_else(|| {
+            let redirected = self.resolve_redirect(path.as_ref())?;
+            self.find_by_key(&self.path_key(&redirected))
+        })
+    }
+
+    fn find_by_key(&self, key: &ResourcePathKey) -> Option<&UntypedResource> {
+        self.resources
+            .iter()
+            .find(|resource| key.matches(resource.lock().path()))
+            .map(|resource| &resource.value)
+    }
+
+    /// Returns total amount of resources in the container.
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns true if the resource manager has no resources.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Creates an iterator over resources in the manager.
+    pub fn iter(&self) -> impl Iterator<Item = &UntypedResource> {
+        self.resources.iter().map(|entry| &entry.value)
+    }
+
+    /// Immediately destroys all resources in the manager that are not used anywhere else. Sends
+    /// [`ResourceEvent::Unloaded`] event for every destroyed resource, so its respective caches
+    /// (GPU textures, etc.) could be freed.
+    pub fn destroy_unused_resources(&mut self) {
+        let mut destroyed = Vec::new();
+        self.resources.retain(|resource| {
+            if resource.value.use_count() > 1 {
+                return true;
+            }
+
+            let key = ResourcePathKey::new(&resource.value.path(), self.case_insensitive_paths);
+            self.dependencies.remove(&key);
+            self.file_dependencies.remove(&key);
+            destroyed.push(key);
+
+            self.event_broadcaster.broadcast_unloaded(&resource.value);
+
+            false
+        });
+        self.prune_content_hashes(&destroyed);
+    }
+
+    /// Returns total amount of resources that still loading.
+    pub fn count_pending_resources(&self) -> usize {
+        self.resources.iter().fold(0, |counter, resource| {
+            if resource.value.is_loading() {
+                counter + 1
+            } else {
+                counter
+            }
+        })
+    }
+
+    /// Returns total amount of completely loaded resources.
+    pub fn count_loaded_resources(&self) -> usize {
+        self.resources.iter().fold(0, |counter, resource| {
+            if let ResourceState::Ok(_) = *resource.lock() {
+                counter + 1
+            } else {
+                counter
+            }
+        })
+    }
+
+    /// Creates a report with estimated memory usage of every loaded resource grouped by resource
+    /// type. See [`ResourceMemoryReport`] docs for more info.
+    pub fn memory_usage_report(&self) -> ResourceMemoryReport {
+        let mut types = FxHashMap::<Uuid, ResourceTypeMemoryUsage>::default();
+        for resource in self.resources.iter() {
+            if let ResourceState::Ok(ref data) = *resource.lock() {
+                let type_uuid = data.type_uuid();
+                let usage = types
+                    .entry(type_uuid)
+                    .or_insert_with(|| ResourceTypeMemoryUsage {
+                        type_uuid,
+                        ..Default::default()
+                    });
+                usage.count += 1;
+                usage.memory += data.memory_usage();
+            }
+        }
+
+        let constructors = self.constructors_container.map.lock();
+        let mut types = types
+            .into_values()
+            .map(|mut usage| {
+                if let Some(constructor) = constructors.get(&usage.type_uuid) {
+                    usage.type_name = constructor.type_name.clone();
+                }
+                usage
+            })
+            .collect::<Vec<_>>();
+        types.sort_by_key(|usage| Reverse(usage.memory));
+
+        ResourceMemoryReport { types }
+    }
+
+    /// Returns a set of resource handled by this container.
+    pub fn resources(&self) -> Vec<UntypedResource> {
+        self.resources.iter().map(|t| t.value.clone()).collect()
+    }
+
+    /// Tries to load a resources at a given path. If there are multiple resource loaders for the
+    /// extension of the path, the loaders are tried in the order of their priorities (see
+    /// [`ResourceLoadersContainer`] docs for more info).
+    pub fn request<P>(&mut self, path: P) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.request_internal(path.as_ref(), None, None)
+    }
+
+    /// Same as [`Self::request`], but uses only the resource loaders, that load resources of the
+    /// given type. This method does not check the type of an existing resource.
+    pub fn request_of_type<P>(&mut self, path: P, type_uuid: Uuid) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.request_internal(path.as_ref(), Some(type_uuid), None)
+    }
+
+    /// Requests a resource. Loading of resources requested with [`LoadPriority::Normal`] or
+    /// [`LoadPriority::Low`] priority is queued, explicit requests (without priority) start
+    /// immediately.
+    fn request_internal(
+        &mut self,
+        path: &Path,
+        type_uuid: Option<Uuid>,
+        priority: Option<LoadPriority>,
+    ) -> UntypedResource {
+        let redirected = self.resolve_redirect(path);
+        let path = redirected.as_deref().unwrap_or(path);
+
+        let built_in_resource = self.built_in_resources.get(path).or_else(|| {
+            let key = self.path_key(path);
+            self.built_in_resources
+                .iter()
+                .find_map(|(built_in_path, resource)| {
+                    key.matches(built_in_path).then_some(resource)
+                })
+        });
+        if let Some(built_in_resource) = built_in_resource {
+            return built_in_resource.clone();
+        }
+
+        match self.find(path).cloned() {
+            Some(existing) => {
+                if existing.is_unloaded() {
+                    self.reload_resource(existing.clone());
+                } else if priority.is_none() {
+                    self.promote_preload(&existing);
+                }
+                existing
+            }
+            None => {
+                let loaders = self.find_loaders(path, type_uuid);
+                if let Some(loader) = loaders.first() {
+                    let type_uuid = loader.data_type_uuid();
+                    let resource = UntypedResource::new_pending(path.to_owned(), type_uuid);
+
+                    match priority {
+                        Some(priority @ (LoadPriority::Normal | LoadPriority::Low)) => {
+                            self.enqueue_preload(resource.clone(), priority)
+                        }
+                        _ => self.spawn_loading_task(&loaders, resource.clone(), false),
+                    }
+
+                    self.push(resource.clone());
+
+                    resource
+                } else {
+                    UntypedResource::new_load_error(
+                        path.to_owned(),
+                        LoadError::new(format!(
+                            "There's no resource loader for {} resource!",
+                            path.display()
+                        )),
+                        Default::default(),
+                    )
+                }
+            }
+        }
+    }
+
+    fn process_content_hashes(&mut self) {
+        let reports = std::mem::take(&mut *self.content_reports.lock());
+        for report in reports {
+            let content_key = (report.hash, report.type_uuid);
+            let original = self
+                .content_hashes
+                .get(&content_key)
+                .filter(|original| self.find(original).is_some())
+                .cloned();
+            let Some(original) = original else {
+                self.content_hashes.insert(content_key, report.path);
+                continue;
+            };
+            let duplicate = self.path_key(&report.path);
+            if duplicate.matches(&original) {
+                continue;
+            }
+            Log::info(format!(
+                "Resource {} has the same content as {}.",
+                report.path.display(),
+                original.display()
+            ));
+            self.content_duplicates.insert(duplicate, original);
+        }
+    }
+
+    /// Removes content hashes and duplicates of the destroyed resources.
+    fn prune_content_hashes(&mut self, destroyed: &[ResourcePathKey]) {
+        if destroyed.is_empty() {
+            return;
+        }
+        let is_destroyed = |path: &Path| destroyed.iter().any(|key| key.matches(path));
+        self.content_hashes.retain(|_, path| !is_destroyed(path));
+        self.content_duplicates.retain(|duplicate, original| {
+            !destroyed.contains(duplicate) && !is_destroyed(original)
+        });
+    }
+
+    /// Loads a resource from the given bytes. See [`ResourceManager::load_from_memory`] for more info.
+    pub fn load_from_memory(&mut self, bytes: Vec<u8>, virtual_path: &Path) -> UntypedResource {
+        self.load_from_memory_internal(bytes, virtual_path, None)
+    }
+
+    fn load_from_memory_internal(
+        &mut self,
+        bytes: Vec<u8>,
+        virtual_path: &Path,
+        type_uuid: Option<Uuid>,
+    ) -> UntypedResource {
+        let existing = self.find(virtual_path).cloned();
+        let loaders = match existing {
+            Some(ref existing) => self.find_reload_loaders(virtual_path, existing.type_uuid()),
+            None => self.find_loaders(virtual_path, type_uuid),
+        };
+        let Some(loader) = loaders.first() else {
+            return UntypedResource::new_load_error(
+                virtual_path.to_owned(),
+                LoadError::new(format!(
+                    "There's no resource loader for {} resource!",
+                    virtual_path.display()
+                )),
+                Default::default(),
+            );
+        };
+
+        let io = self.memory_io(virtual_path, bytes);
+
+        if let Some(existing) = existing {
+            let mut state = existing.lock();
+            if state.is_loading() {
+                drop(state);
+                Log::warn(format!(
+                    "Unable to load {} from memory, because it is loading already!",
+                    virtual_path.display()
+                ));
+            } else {
+                self.event_broadcaster
+                    .on_reload_started(existing.key(), state.type_uuid());
+                state.switch_to_pending_state();
+                drop(state);
+                self.spawn_loading_task_with_io(&loaders, existing.clone(), true, io, None);
+            }
+            existing
+        } else {
+            let resource =
+                UntypedResource::new_pending(virtual_path.to_owned(), loader.data_type_uuid());
+            self.spawn_loading_task_with_io(&loaders, resource.clone(), false, io, None);
+            self.push(resource.clone());
+            resource
+        }
+    }
+
+    /// Checks whether there's a resource loader, that can load a resource at the given path.
+    pub fn can_load(&self, path: &Path) -> bool {
+        !self.find_loaders(path, None).is_empty()
+    }
+
+    /// Returns every resource loader for the given path (optionally filtered by type uuid of the
+    /// resources they load) in the order in which the loaders must be tried.
+    fn find_loaders(&self, path: &Path, type_uuid: Option<Uuid>) -> Vec<&dyn ResourceLoader> {
+        let Some(extension) = path.extension() else {
+            return Vec::new();
+        };
+        self.loaders
+            .iter_for_extension(&extension.to_string_lossy())
+            .filter(|loader| type_uuid.map_or(true, |uuid| loader.data_type_uuid() == uuid))
+            .collect()
+    }
+
+    /// Same as [`Self::find_loaders`], but prefers the loaders of the given type, so a reloaded
+    /// resource keeps its type (otherwise typed handles to it will be invalidated). Every other
+    /// loader is used only if there's no loader of the given type.
+    fn find_reload_loaders(&self, path: &Path, type_uuid: Uuid) -> Vec<&dyn ResourceLoader> {
+        let loaders = self.find_loaders(path, Some(type_uuid));
+        if loaders.is_empty() {
+            self.find_loaders(path, None)
+        } else {
+            loaders
+        }
+    }
+
+    fn spawn_loading_task(
+        &self,
+        loaders: &[&dyn ResourceLoader],
+        resource: UntypedResource,
+        reload: bool,
+    ) {
+        self.spawn_loading_attempt(loaders, resource, reload, 0)
+    }
+
+    /// Spawns a loading task, that performs the given attempt (starting from zero) of loading of
+    /// the resource. See [`RetryPolicy`] docs for more info about retries.
+    fn spawn_loading_attempt(
+        &self,
+        loaders: &[&dyn ResourceLoader],
+        resource: UntypedResource,
+        reload: bool,
+        attempt: u32,
+    ) {
+        // Embedded resources must be loaded from memory, every other file (import options, etc.)
+        // is still accessed using the resource IO.
+        let path = resource.path();
+        let io = match self.embedded_resources.get(&path) {
+            Some(data) => self.memory_io(&path, data.to_vec()),
+            None => match self.prefetcher.take(&self.path_key(&path)) {
+                // Prefetched data is read through the same IO, so it is a proper variant already.
+                Some(data) if !reload => self.memory_io(&path, data),
+                _ => self.source_io(&path, loaders),
+            },
+        };
+        self.spawn_loading_task_with_io(loaders, resource, reload, io, Some(attempt))
+    }
+
+    /// Returns resource IO, that reads the file of a resource at the given path (respecting locale
+    /// and platform variants).
+    fn source_io(&self, path: &Path, loaders: &[&dyn ResourceLoader]) -> Arc<dyn ResourceIo> {
+        let mut io = self.resource_io.clone();
+        if self.locale.is_enabled() {
+            io = Arc::new(LocalizedResourceIo {
+                inner: io,
+                path: path.to_path_buf(),
+                settings: self.locale.clone(),
+            });
+        }
+        if self.platform.is_enabled() {
+            io = Arc::new(PlatformResourceIo {
+                inner: io,
+                path: path.to_path_buf(),
+                extensions: variant_extensions(loaders),
+                settings: self.platform.clone(),
+            });
+        }
+        io
+    }
+
+    fn memory_io(&self, path: &Path, bytes: Vec<u8>) -> Arc<dyn ResourceIo> {
+        let io = MemoryResourceIo::new().with_fallback(self.resource_io.clone());
+        io.add_file(path, bytes);
+        Arc::new(io)
+    }
+
+    /// Spawns a loading task, that reads the resource using the given IO. `attempt` is an index of
+    /// the loading attempt, `None` means that the load must not be retried.
+    fn spawn_loading_task_with_io(
+        &self,
+        loaders: &[&dyn ResourceLoader],
+        resource: UntypedResource,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+        attempt: Option<u32>,
+    ) {
+        let io_failed = Arc::new(AtomicBool::new(false));
+        let io_spent = Arc::new(Mutex::new(Default::default()));
+        let mut io: Arc<dyn ResourceIo> = Arc::new(TimedResourceIo {
+            inner: Arc::new(TransientErrorMonitorIo {
+                inner: io,
+                failed: io_failed.clone(),
+            }),
+            spent: io_spent.clone(),
+        });
+        let path = resource.path();
+        if self.content_deduplication && !reload && !self.embedded_resources.contains(&path) {
+            io = Arc::new(ContentHashingIo {
+                inner: io,
+                path: path.clone(),
+                type_uuid: resource.type_uuid(),
+                reports: self.content_reports.clone(),
+            });
+        }
+        if let Some(importer) = self.importers.find_for_path(&path) {
+            // Loaders should read derived data instead of the source file.
+            io = Arc::new(ImportingResourceIo {
+                inner: io,
+                source_path: path,
+                importer: importer.clone(),
+                cache: self.derived_data_cache.clone(),
+            });
+        }
+        let timing = ResourceLoadTiming {
+            path: resource.path(),
+            type_uuid: resource.type_uuid(),
+            reload,
+            ..Default::default()
+        };
+        let resource_key = resource.key();
+        let mut futures = loaders
+            .iter()
+            .map(|loader| {
+                let future = loader.load(
+                    resource.clone(),
+                    self.event_broadcaster.clone(),
+                    reload,
+                    io.clone(),
+                );
+                (loader.data_type_uuid(), future)
+            })
+            .collect::<VecDeque<_>>();
+        let Some((_, current)) = futures.pop_front() else {
+            return;
+        };
+        let mut inner = if futures.is_empty() {
+            current
+        } else {
+            Box::pin(ChainedLoaderFuture {
+                resource: resource.clone(),
+                current,
+                failed: false,
+                fallbacks: futures,
+            })
+        };
+        if let Some(attempt) = attempt {
+            inner = self
+                .retrier
+                .wrap(inner, resource, reload, attempt, io_failed);
+        }
+        self.task_pool.spawn_task(TimedLoaderFuture {
+            inner,
+            timing,
+            resource_key,
+            io_spent,
+            statistics: self.statistics.clone(),
+            start: None,
+            finalization_start: None,
+        });
+    }
+
+    /// Reloads a single resource.
+    pub fn reload_resource(&mut self, resource: UntypedResource) {
+        // Unloaded resources are pending, but nothing loads them.
+        let unloaded = untyped::set_unloaded_flag(&resource, false);
+        let mut state = resource.lock();
+
+        if !state.is_loading() || unloaded {
+            // Reloaded data starts from its first stage again.
+            self.streamer.reset(&resource);
+
+            let path = state.path().to_path_buf();
+            let loaders = self.find_reload_loaders(&path, state.type_uuid());
+            if !loaders.is_empty() {
+                self.event_broadcaster
+                    .on_reload_started(resource.key(), state.type_uuid());
+                state.switch_to_pending_state();
+                drop(state);
+
+                self.spawn_loading_task(&loaders, resource, true);
+            } else {
+                let msg = format!(
+                    "There's no resource loader for {} resource!",
+                    path.display()
+                );
+                drop(state);
+                resource.commit_error(path, msg)
+            }
+        }
+    }
+
+    /// Reloads all resources in the container. Returns a list of resources that will be reloaded.
+    /// You can use the list to wait until all resources are loading.
+    pub fn reload_resources(&mut self) -> Vec<UntypedResource> {
+        let resources = self
+            .resources
+            .iter()
+            .map(|r| r.value.clone())
+            .collect::<Vec<_>>();
+
+        for resource in resources.iter().cloned() {
+            self.reload_resource(resource);
+        }
+
+        resources
+    }
+
+    /// Wait until all resources are loaded (or failed to load).
+    pub fn get_wait_context(&self) -> ResourceWaitContext {
+        ResourceWaitContext {
+            resources: self
+                .resources
+                .iter()
+                .map(|e| e.value.clone())
+                .collect::<Vec<_>>(),
+        }
+    }
+
+    /// Adds the given resource to the group with the given name. The group holds a strong reference
+    /// to the resource, so the resource won't be destroyed until the group is unloaded. The group
+    /// is created automatically, if it does not exist.
+    pub fn add_to_group(&mut self, resource: UntypedResource, group: &str) {
+        let resources = self.groups.entry(group.to_owned()).or_default();
+        if !resources.contains(&resource) {
+            resources.push(resource);
+        }
+    }
+
+    /// Returns a list of resources of the given group. The list is empty if there's no such group.
+    pub fn group(&self, group: &str) -> &[UntypedResource] {
+        self.groups
+            .get(group)
+            .map(|r| r.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the names of every group.
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.groups.keys().map(|k| k.as_str())
+    }
+
+    /// Wait until all resources of the given group are loaded (or failed to load).
+    pub fn get_group_wait_context(&self, group: &str) -> ResourceWaitContext {
+        ResourceWaitContext {
+            resources: self.group(group).to_vec(),
+        }
+    }
+
+    /// Removes the group with the given name and immediately destroys every resource of the group,
+    /// that is not used anywhere else (including other groups). Resources that are still in use are
+    /// kept alive and will be destroyed as usual, when they're not used anymore. Returns the amount
+    /// of destroyed resources.
+    pub fn unload_group(&mut self, group: &str) -> usize {
+        let Some(resources) = self.groups.remove(group) else {
+            return 0;
+        };
+
+        let mut destroyed = 0;
+        for resource in resources {
+            // Two users means that the resource is used only by the group and the manager.
+            if resource.use_count() <= 2 {
+                if let Some(position) = self.resources.iter().position(|r| r.value == resource) {
+                    self.resources.remove(position);
+                    self.event_broadcaster.broadcast_unloaded(&resource);
+                    destroyed += 1;
+                }
+            }
+        }
+        destroyed
+    }
+
+    /// Attributes the given resource to the given requester tag. A resource could have any number
+    /// of tags. Unlike groups, tags do not hold the resource, they're bound to the path of the
+    /// resource. See [`ResourceTagReport`] docs for more info.
+    pub fn add_tag(&mut self, resource: &UntypedResource, tag: &str) {
+        let key = self.path_key(&resource.path());
+        self.tags.add(key, tag);
+    }
+
+    /// Removes the given tag from the given resource. Returns `true` if the resource had the tag.
+    pub fn remove_tag(&mut self, resource: &UntypedResource, tag: &str) -> bool {
+        let key = self.path_key(&resource.path());
+        self.tags.remove(&key, tag)
+    }
+
+    /// Returns every tag of the given resource.
+    pub fn tags_of(&self, resource: &UntypedResource) -> &[String] {
+        self.tags.get(&self.path_key(&resource.path()))
+    }
+
+    /// Sets a quota of the given tag. Quotas are not enforced, exceeded quotas are only reported
+    /// (see [`Self::tag_usage_report`] and [`Self::check_quotas`]).
+    pub fn set_quota(&mut self, tag: &str, quota: ResourceQuota) {
+        self.tags.quotas.insert(tag.to_owned(), quota);
+    }
+
+    /// Returns a quota of the given tag. Tags without a quota are unlimited.
+    pub fn quota(&self, tag: &str) -> ResourceQuota {
+        self.tags.quotas.get(tag).cloned().unwrap_or_default()
+    }
+
+    /// Creates a report with resource usage (memory and loading time) of every requester tag. See
+    /// [`ResourceTagReport`] docs for more info.
+    pub fn tag_usage_report(&self) -> ResourceTagReport {
+        // Every tag owns its resources and their dependencies.
+        let mut tag_resources = FxHashMap::<&str, FxHashSet<ResourcePathKey>>::default();
+        for (key, tags) in self.tags.tags.iter() {
+            let mut stack = vec![key.clone()];
+            let mut visited = FxHashSet::default();
+            while let Some(key) = stack.pop() {
+                if let Some(dependencies) = self.dependencies.get(&key) {
+                    stack.extend(
+                        dependencies
+                            .iter()
+                            .map(|path| self.path_key(path))
+                            .filter(|dependency| !visited.contains(dependency)),
+                    );
+                }
+                visited.insert(key);
+            }
+            for tag in tags {
+                tag_resources
+                    .entry(tag.as_str())
+                    .or_default()
+                    .extend(visited.iter().cloned());
+            }
+        }
+
+        let mut memory = FxHashMap::<ResourcePathKey, usize>::default();
+        for resource in self.resources.iter() {
+            let state = resource.lock();
+            if let ResourceState::Ok(ref data) = *state {
+                memory.insert(self.path_key(state.path()), data.memory_usage());
+            }
+        }
+
+        let mut load_time = FxHashMap::<ResourcePathKey, Duration>::default();
+        for timing in self.statistics.lock().timings.iter() {
+            *load_time.entry(self.path_key(&timing.path)).or_default() += timing.total();
+        }
+
+        let mut tags = self
+            .tags
+            .quotas
+            .keys()
+            .map(|tag| tag.as_str())
+            .chain(tag_resources.keys().cloned())
+            .collect::<FxHashSet<_>>()
+            .into_iter()
+            .map(|tag| {
+                let mut usage = ResourceTagUsage {
+                    tag: tag.to_owned(),
+                    quota: self.quota(tag),
+                    ..Default::default()
+                };
+                for key in tag_resources.get(tag).into_iter().flatten() {
+                    if let Some(memory) = memory.get(key) {
+                        usage.count += 1;
+                        usage.memory += memory;
+                    }
+                    usage.load_time += load_time.get(key).cloned().unwrap_or_default();
+                }
+                usage
+            })
+            .collect::<Vec<_>>();
+        tags.sort_by(|a, b| b.memory.cmp(&a.memory).then_with(|| a.tag.cmp(&b.tag)));
+
+        ResourceTagReport { tags }
+    }
+
+    /// Checks quotas of every tag and writes a warning to the log for each exceeded quota. Returns
+    /// the usage of every tag, that has exceeded its quota. This method is relatively slow, it
+    /// should not be called every frame.
+    pub fn check_quotas(&self) -> Vec<ResourceTagUsage> {
+        let over_quota = self
+            .tag_usage_report()
+            .over_quota()
+            .cloned()
+            .collect::<Vec<_>>();
+        for usage in over_quota.iter() {
+            Log::warn(format!("Resource quota is exceeded. {usage}"));
+        }
+        over_quota
+    }
+
+    /// Tries to reload a resource at the given path. Every resource, that has the file at the given
+    /// path as its file dependency (see [`ResourceManager::set_file_dependencies`]), is reloaded as
+    /// well.
+    pub fn try_reload_resource_from_path(&mut self, path: &Path) -> bool {
+        let key = self.path_key(path);
+
+        // Prefetched content is outdated.
+        self.prefetcher.take(&key);
+
+        let dependents = self
+            .file_dependencies
+            .iter()
+            .filter(|(_, files)| files.iter().any(|file| key.matches(file)))
+            .filter_map(|(dependent, _)| self.find_by_key(dependent).cloned())
+            .collect::<Vec<_>>();
+        let reloaded_dependents = !dependents.is_empty();
+        for dependent in dependents {
+            self.reload_resource(dependent);
+        }
+
+        // The content has changed, so the resource is not known to be a duplicate anymore.
+        self.content_duplicates.remove(&key);
+
+        if let Some(resource) = self.find(path).cloned() {
+            self.reload_resource(resource);
+            true
+        } else {
+            reloaded_dependents
+        }
+    }
+
+    /// Forgets that a resource at the given path was ever loaded, thus making it possible to reload it
+    /// again as a new instance.
+    pub fn unregister(&mut self, path: &Path) {
+        let key = self.path_key(path);
+        self.content_duplicates.remove(&key);
+        self.dependencies.remove(&key);
+        if let Some(position) = self
+            .resources
+            .iter()
+            .position(|r| key.matches(r.lock().path()))
+        {
+            self.resources.remove(position);
+        }
+    }
+}
+
+/// Returns every extension, that is supported by the given loaders. Platform variants of a resource
+/// could use any of them.
+fn variant_extensions(loaders: &[&dyn ResourceLoader]) -> Vec<String> {
+    let mut extensions = Vec::<String>::new();
+    for extension in loaders.iter().flat_map(|loader| loader.extensions()) {
+        if !extensions.iter().any(|e| e.eq_ignore_ascii_case(extension)) {
+            extensions.push(extension.to_string());
+        }
+    }
+    extensions
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, time::Duration};
+
+    use crate::{
+        io::FileStamp,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+    };
+
+    use super::*;
+
+    use fyrox_core::uuid::{uuid, Uuid};
+    use fyrox_core::{
+        reflect::{FieldInfo, Reflect},
+        visitor::{Visit, VisitResult, Visitor},
+        TypeUuidProvider,
+    };
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Stub {}
+
+    impl TypeUuidProvider for Stub {
+        fn type_uuid() -> Uuid {
+            uuid!("9d873ff4-3126-47e1-a492-7cd8e7168239")
+        }
+    }
+
+    impl ResourceData for Stub {
+        fn path(&self) -> &std::path::Path {
+            Path::new("test.txt")
+        }
+
+        fn set_path(&mut self, _path: std::path::PathBuf) {}
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            unimplemented!()
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            unimplemented!()
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+    }
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct DirtyStub {
+        dirty: bool,
+        saves: usize,
+    }
+
+    impl TypeUuidProvider for DirtyStub {
+        fn type_uuid() -> Uuid {
+            uuid!("0b6c5a2e-7d41-4c8f-9e3a-16f2d8b4c750")
+        }
+    }
+
+    impl ResourceData for DirtyStub {
+        fn path(&self) -> &std::path::Path {
+            Path::new("dirty.txt")
+        }
+
+        fn set_path(&mut self, _path: std::path::PathBuf) {}
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+            self.saves += 1;
+            self.dirty = false;
+            Ok(())
+        }
+
+        fn mark_dirty(&mut self) {
+            self.dirty = true;
+        }
+
+        fn is_dirty(&self) -> bool {
+            self.dirty
+        }
+    }
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct BytesStub {
+        path: PathBuf,
+        bytes: Vec<u8>,
+    }
+
+    impl TypeUuidProvider for BytesStub {
+        fn type_uuid() -> Uuid {
+            uuid!("4f1d7c3e-8a26-4b59-b0e7-2c9d5a6f8e13")
+        }
+    }
+
+    impl ResourceData for BytesStub {
+        fn path(&self) -> &std::path::Path {
+            &self.path
+        }
+
+        fn set_path(&mut self, path: std::path::PathBuf) {
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+
+        fn memory_usage(&self) -> usize {
+            self.bytes.len()
+        }
+    }
+
+    struct BytesStubLoader;
+
+    impl ResourceLoader for BytesStubLoader {
+        fn extensions(&self) -> &[&str] {
+            &["bin"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <BytesStub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            event_broadcaster: ResourceEventBroadcaster,
+            reload: bool,
+            io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                let path = resource.path();
+                match io.load_file(&path).await {
+                    Ok(bytes) => {
+                        resource.commit_ok(BytesStub {
+                            path: path.clone(),
+                            bytes,
+                        });
+                        event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+                    }
+                    Err(err) => resource.commit_error(path, err),
+                }
+            })
+        }
+    }
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct LinkStub {
+        path: PathBuf,
+        dependencies: Vec<UntypedResource>,
+    }
+
+    impl TypeUuidProvider for LinkStub {
+        fn type_uuid() -> Uuid {
+            uuid!("b4a1f3c2-6d5e-4f87-9a0b-8c7d6e5f4a31")
+        }
+    }
+
+    impl ResourceData for LinkStub {
+        fn path(&self) -> &std::path::Path {
+            &self.path
+        }
+
+        fn set_path(&mut self, path: std::path::PathBuf) {
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+    }
+
+    /// Loads `.link` files, that contain a list of paths of dependencies (one path per line).
+    struct LinkStubLoader(ResourceManager);
+
+    impl ResourceLoader for LinkStubLoader {
+        fn extensions(&self) -> &[&str] {
+            &["link"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <LinkStub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            event_broadcaster: ResourceEventBroadcaster,
+            reload: bool,
+            io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            let resource_manager = self.0.clone();
+            Box::pin(async move {
+                let path = resource.path();
+                match io.load_file(&path).await {
+                    Ok(bytes) => {
+                        let link = LinkStub {
+                            path: path.clone(),
+                            dependencies: String::from_utf8(bytes)
+                                .unwrap()
+                                .lines()
+                                .map(|dependency| resource_manager.request_untyped(dependency))
+                                .collect(),
+                        };
+                        resource_manager
+                            .wait_for_data_dependencies(&resource, &link)
+                            .await;
+                        resource.commit_ok(link);
+                        event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+                    }
+                    Err(err) => resource.commit_error(path, err),
+                }
+            })
+        }
+    }
+
+    /// Loads `.odd` files, but declares a wrong data type.
+    struct MislabeledStubLoader;
+
+    impl ResourceLoader for MislabeledStubLoader {
+        fn extensions(&self) -> &[&str] {
+            &["odd"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <LinkStub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            event_broadcaster: ResourceEventBroadcaster,
+            reload: bool,
+            io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            BytesStubLoader.load(resource, event_broadcaster, reload, io)
+        }
+    }
+
+    /// Loads `.bin` files, that start with a magic header.
+    struct MagicStubLoader;
+
+    impl ResourceLoader for MagicStubLoader {
+        fn extensions(&self) -> &[&str] {
+            &["bin"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <DirtyStub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            event_broadcaster: ResourceEventBroadcaster,
+            reload: bool,
+            io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                let path = resource.path();
+                match io.load_file(&path).await {
+                    Ok(bytes) if bytes.starts_with(b"MAGIC") => {
+                        resource.commit_ok(DirtyStub::default());
+                        event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+                    }
+                    Ok(_) => resource.commit_error(path, "no magic header"),
+                    Err(err) => resource.commit_error(path, err),
+                }
+            })
+        }
+    }
+
+    impl ResourceLoader for Stub {
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            <Stub as TypeUuidProvider>::type_uuid()
+        }
+
+        fn load(
+            &self,
+            resource: UntypedResource,
+            event_broadcaster: ResourceEventBroadcaster,
+            reload: bool,
+            _io: Arc<dyn ResourceIo>,
+        ) -> BoxedLoaderFuture {
+            Box::pin(async move {
+                resource.commit_ok(Stub::default());
+                event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+            })
+        }
+    }
+
+    #[test]
+    fn resource_wait_context_is_all_loaded() {
+        assert!(ResourceWaitContext::default().is_all_loaded());
+
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+
+        let cx = ResourceWaitContext {
+            resources: vec![
+                UntypedResource::new_pending(path.clone(), type_uuid),
+                UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid),
+            ],
+        };
+        assert!(!cx.is_all_loaded());
+    }
+
+    #[test]
+    fn resource_manager_state_new() {
+        let state = ResourceManagerState::new();
+
+        assert!(state.resources.is_empty());
+        assert!(state.loaders.is_empty());
+        assert!(state.built_in_resources.is_empty());
+        assert!(state.constructors_container.is_empty());
+        assert!(state.watcher.is_none());
+        assert!(state.is_empty());
+    }
+
+    #[test]
+    fn resource_manager_state_set_watcher() {
+        let mut state = ResourceManagerState::new();
+        assert!(state.watcher.is_none());
+
+        let path = PathBuf::from("test.txt");
+        if File::create(path.clone()).is_ok() {
+            let watcher = FileSystemWatcher::new(path.clone(), Duration::from_secs(1));
+            state.set_watcher(watcher.ok());
+            assert!(state.watcher.is_some());
+        }
+    }
+
+    #[test]
+    fn resource_manager_state_push() {
+        let mut state = ResourceManagerState::new();
+
+        assert_eq!(state.count_loaded_resources(), 0);
+        assert_eq!(state.count_pending_resources(), 0);
+        assert_eq!(state.count_registered_resources(), 0);
+        assert_eq!(state.len(), 0);
+
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+        state.push(UntypedResource::new_pending(path.clone(), type_uuid));
+        state.push(UntypedResource::new_load_error(
+            path.clone(),
+            Default::default(),
+            type_uuid,
+        ));
+        state.push(UntypedResource::new_ok(Stub {}));
+
+        assert_eq!(state.count_loaded_resources(), 1);
+        assert_eq!(state.count_pending_resources(), 1);
+        assert_eq!(state.count_registered_resources(), 3);
+        assert_eq!(state.len(), 3);
+    }
+
+    #[test]
+    fn resource_manager_state_loading_progress() {
+        let mut state = ResourceManagerState::new();
+
+        assert_eq!(state.loading_progress(), 100);
+
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+        state.push(UntypedResource::new_pending(path.clone(), type_uuid));
+        state.push(UntypedResource::new_load_error(
+            path.clone(),
+            Default::default(),
+            type_uuid,
+        ));
+        state.push(UntypedResource::new_ok(Stub {}));
+
+        assert_eq!(state.loading_progress(), 33);
+    }
+
+    #[test]
+    fn resource_manager_state_find() {
+        let mut state = ResourceManagerState::new();
+
+        assert!(state.find(Path::new("foo.txt")).is_none());
+
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+        let resource = UntypedResource::new_pending(path.clone(), type_uuid);
+        state.push(resource.clone());
+
+        assert_eq!(state.find(path), Some(&resource));
+    }
+
+    #[test]
+    fn resource_manager_state_find_normalized() {
+        let mut state = ResourceManagerState::new();
+        state.loaders.set(Stub {});
+        state.set_case_insensitive_paths(false);
+
+        let resource = state.request("Textures/foo.txt");
+        assert_eq!(state.request("./Textures\\foo.txt"), resource);
+        assert_ne!(state.request("textures/FOO.txt"), resource);
+        assert_eq!(state.len(), 2);
+
+        state.set_case_insensitive_paths(true);
+        assert_eq!(state.find("textures\\FOO.TXT"), Some(&resource));
+        // The resource keeps the path it was requested with.
+        assert_eq!(resource.path(), Path::new("Textures/foo.txt"));
+
+        state.unregister(Path::new("TEXTURES/foo.txt"));
+        assert_eq!(state.len(), 1);
+    }
+
+    #[test]
+    fn resource_manager_state_resources() {
+        let mut state = ResourceManagerState::new();
+
+        assert_eq!(state.resources(), Vec::new());
+
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+        let r1 = UntypedResource::new_pending(path.clone(), type_uuid);
+        let r2 = UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid);
+        let r3 = UntypedResource::new_ok(Stub {});
+        state.push(r1.clone());
+        state.push(r2.clone());
+        state.push(r3.clone());
+
+        assert_eq!(state.resources(), vec![r1.clone(), r2.clone(), r3.clone()]);
+        assert!(state.iter().eq([&r1, &r2, &r3]));
+    }
+
+    #[test]
+    fn resource_manager_state_destroy_unused_resources() {
+        let mut state = ResourceManagerState::new();
+
+        let unused = UntypedResource::new_pending(PathBuf::from("test.txt"), Uuid::default());
+        let unused_key = unused.key();
+        state.push(unused);
+        let used = UntypedResource::new_pending(PathBuf::from("used.txt"), Uuid::default());
+        state.push(used.clone());
+        assert_eq!(state.len(), 2);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        state.event_broadcaster.add(sender);
+
+        state.destroy_unused_resources();
+        assert_eq!(state.resources(), vec![used]);
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResourceEvent::Removed(path)) if path == Path::new("test.txt")
+        ));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResourceEvent::Unloaded { key, .. }) if key == unused_key
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn resource_manager_state_request() {
+        let mut state = ResourceManagerState::new();
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+
+        let resource = UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid);
+        state.push(resource.clone());
+
+        let res = state.request(path);
+        assert_eq!(res, resource);
+
+        let path = PathBuf::from("foo.txt");
+        let res = state.request(path.clone());
+
+        assert_eq!(res.path(), path.clone());
+        assert_eq!(res.type_uuid(), type_uuid);
+        assert!(!res.is_loading());
+    }
+
+    #[test]
+    fn resource_manager_state_reload_resource() {
+        let mut state = ResourceManagerState::new();
+        state.loaders.set(Stub {});
+
+        let resource = UntypedResource::new_ok(Stub {});
+        state.push(resource.clone());
+        state.reload_resource(resource.clone());
+
+        assert!(resource.is_loading());
+    }
+
+    #[test]
+    fn resource_manager_state_reload_resources() {
+        let mut state = ResourceManagerState::new();
+        state.loaders.set(Stub {});
+
+        let resource = UntypedResource::new_ok(Stub {});
+        state.push(resource.clone());
+        let res = state.reload_resources();
+
+        assert_eq!(res.len(), 1);
+        assert!(res[0].is_loading());
+    }
+
+    #[test]
+    fn resource_manager_state_try_reload_resource_from_path() {
+        let mut state = ResourceManagerState::new();
+        state.loaders.set(Stub {});
+
+        let resource = UntypedResource::new_load_error(
+            PathBuf::from("test.txt"),
+            Default::default(),
+            Uuid::default(),
+        );
+        state.push(resource.clone());
+
+        assert!(!state.try_reload_resource_from_path(Path::new("foo.txt")));
+
+        assert!(state.try_reload_resource_from_path(Path::new("test.txt")));
+        assert!(resource.is_loading());
+    }
+
+    #[test]
+    fn resource_manager_state_reload_file_dependents() {
+        let mut state = ResourceManagerState::new();
+        state.loaders.set(Stub {});
+
+        let resource = UntypedResource::new_load_error(
+            PathBuf::from("test.txt"),
+            Default::default(),
+            Uuid::default(),
+        );
+        state.push(resource.clone());
+        state.set_file_dependencies(&resource, [PathBuf::from("shared/common.glsl")]);
+        assert_eq!(
+            state.file_dependencies_of(Path::new("test.txt")),
+            [PathBuf::from("shared/common.glsl")]
+        );
+
+        assert!(!state.try_reload_resource_from_path(Path::new("shared/other.glsl")));
+        assert!(state.try_reload_resource_from_path(Path::new("shared/common.glsl")));
+        assert!(resource.is_loading());
+
+        state.set_file_dependencies(&resource, []);
+        assert!(state.file_dependencies_of(Path::new("test.txt")).is_empty());
+    }
+
+    #[test]
+    fn resource_manager_state_get_wait_context() {
+        let mut state = ResourceManagerState::new();
+
+        let resource = UntypedResource::new_ok(Stub {});
+        state.push(resource.clone());
+        let cx = state.get_wait_context();
+
+        assert!(cx.resources.eq(&vec![resource]));
+    }
+
+    #[test]
+    fn resource_manager_new() {
+        let manager = ResourceManager::new();
+
+        assert!(manager.state.lock().is_empty());
+        assert!(manager.state().is_empty());
+    }
+
+    #[test]
+    fn resource_manager_register() {
+        let manager = ResourceManager::default();
+        let path = PathBuf::from("test.txt");
+        let type_uuid = Uuid::default();
+
+        let resource = UntypedResource::new_pending(path.clone(), type_uuid);
+        let res = manager.register(resource.clone(), path.clone(), |_, __| true);
+        assert!(res.is_err());
+
+        let resource = UntypedResource::new_ok(Stub {});
+        let res = manager.register(resource.clone(), path.clone(), |_, __| true);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn resource_manager_request() {
+        let manager = ResourceManager::new();
+        let untyped = UntypedResource::new_ok(Stub {});
+        let res = manager.register(untyped.clone(), PathBuf::from("foo.txt"), |_, __| true);
+        assert!(res.is_ok());
+
+        let res: Resource<Stub> = manager.request(Path::new("test.txt"));
+        assert_eq!(
+            res,
+            Resource {
+                untyped,
+                phantom: PhantomData::<Stub>
+            }
+        );
+    }
+
+    #[test]
+    fn resource_manager_request_untyped() {
+        let manager = ResourceManager::new();
+        let resource = UntypedResource::new_ok(Stub {});
+        let res = manager.register(resource.clone(), PathBuf::from("foo.txt"), |_, __| true);
+        assert!(res.is_ok());
+
+        let res = manager.request_untyped(Path::new("test.txt"));
+        assert_eq!(res, resource);
     }
 
-    /// Returns total amount of resources in the container.
-    pub fn len(&self) -> usize {
-        self.resources.len()
-    }
+    #[test]
+    fn resource_manager_reload_and_unload() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(Stub {});
+
+        let resource = manager.request_untyped(Path::new("test.txt"));
+        assert!(!resource.unload(&manager));
+        let resource = crate::core::futures::executor::block_on(resource).unwrap();
+        let user = resource.clone();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        manager.state().event_broadcaster.add(sender);
+        assert!(resource.unload(&manager));
+        assert!(matches!(*user.lock(), ResourceState::Pending { .. }));
+        assert!(user.is_unloaded());
+        assert!(!user.is_loading());
+        assert!(manager.state().get_wait_context().is_all_loaded());
+        assert_eq!(user.path(), Path::new("test.txt"));
+        assert_eq!(user.type_uuid(), <Stub as TypeUuidProvider>::type_uuid());
+        assert!(!resource.unload(&manager));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResourceEvent::Unloaded { key, .. }) if key == resource.key()
+        ));
 
-    /// Returns true if the resource manager has no resources.
-    pub fn is_empty(&self) -> bool {
-        self.resources.is_empty()
+        user.reload(&manager);
+        assert!(!user.is_unloaded());
+        assert!(crate::core::futures::executor::block_on(resource.clone()).is_ok());
+        assert!(matches!(*user.lock(), ResourceState::Ok(_)));
+        assert!(matches!(
+            receiver.recv_timeout(Duration::from_secs(5)),
+            Ok(ResourceEvent::Reloaded { resource: r, .. }) if r == resource
+        ));
     }
 
-    /// Creates an iterator over resources in the manager.
-    pub fn iter(&self) -> impl Iterator<Item = &UntypedResource> {
-        self.resources.iter().map(|entry| &entry.value)
+    #[test]
+    fn resource_manager_state_can_load() {
+        let mut state = ResourceManagerState::new();
+        assert!(!state.can_load(Path::new("foo.txt")));
+
+        state.loaders.set(Stub {});
+        assert!(state.can_load(Path::new("foo.txt")));
+        assert!(state.can_load(Path::new("foo.TXT")));
+        assert!(!state.can_load(Path::new("foo.bar")));
+        assert!(!state.can_load(Path::new("foo")));
     }
 
-    /// Immediately destroys all resources in the manager that are not used anywhere else.
-    pub fn destroy_unused_resources(&mut self) {
-        self.resources
-            .retain(|resource| resource.value.use_count() > 1);
+    #[test]
+    fn resource_manager_state_groups() {
+        let mut state = ResourceManagerState::new();
+
+        let r1 = UntypedResource::new_pending(PathBuf::from("foo.txt"), Uuid::default());
+        let r2 = UntypedResource::new_ok(Stub {});
+        state.push(r1.clone());
+        state.push(r2.clone());
+        state.add_to_group(r1.clone(), "level");
+        state.add_to_group(r1.clone(), "level");
+        state.add_to_group(r2.clone(), "level");
+        state.add_to_group(r2.clone(), "common");
+
+        assert_eq!(state.group("level"), &[r1.clone(), r2.clone()]);
+        assert!(state.group("foo").is_empty());
+        assert_eq!(state.groups().count(), 2);
+        assert!(!state.get_group_wait_context("level").is_all_loaded());
+        assert!(state.get_group_wait_context("common").is_all_loaded());
+
+        drop(r1);
+        // The second resource is still used by the other group.
+        assert_eq!(state.unload_group("level"), 1);
+        assert_eq!(state.len(), 1);
+        assert_eq!(state.unload_group("level"), 0);
+
+        drop(r2);
+        assert_eq!(state.unload_group("common"), 1);
+        assert!(state.is_empty());
     }
 
-    /// Returns total amount of resources that still loading.
-    pub fn count_pending_resources(&self) -> usize {
-        self.resources.iter().fold(0, |counter, resource| {
-            if let ResourceState::Pending { .. } = *resource.0.lock() {
-                counter + 1
-            } else {
-                counter
-            }
-        })
+    #[test]
+    fn resource_manager_state_tags() {
+        let mut state = ResourceManagerState::new();
+
+        let level = UntypedResource::new_ok(Stub {});
+        let dependency = UntypedResource::new_ok(DirtyStub::default());
+        state.push(level.clone());
+        state.push(dependency.clone());
+        state.register_dependencies(&level, [dependency.clone()]);
+        state.add_tag(&level, "Level");
+        state.add_tag(&level, "Level");
+        state.add_tag(&dependency, "UI");
+        state.set_quota("Level", ResourceQuota::memory(1));
+        state.set_quota("Audio", ResourceQuota::memory(1));
+        assert_eq!(state.tags_of(&level), ["Level"]);
+
+        let report = state.tag_usage_report();
+        assert_eq!(report.tags.len(), 3);
+        // Dependencies are attributed to the tag of the resource.
+        let usage = report.find("Level").unwrap();
+        assert_eq!(usage.count, 2);
+        assert_eq!(usage.memory, std::mem::size_of::<DirtyStub>());
+        assert!(usage.is_over_quota());
+        assert_eq!(report.find("UI").unwrap().count, 1);
+        assert_eq!(report.find("Audio").unwrap().count, 0);
+        assert_eq!(
+            state
+                .check_quotas()
+                .iter()
+                .map(|usage| usage.tag.as_str())
+                .collect::<Vec<_>>(),
+            ["Level"]
+        );
+
+        assert!(state.remove_tag(&level, "Level"));
+        assert!(state.tags_of(&level).is_empty());
+        assert_eq!(state.tag_usage_report().find("Level").unwrap().count, 0);
     }
 
-    /// Returns total amount of completely loaded resources.
-    pub fn count_loaded_resources(&self) -> usize {
-        self.resources.iter().fold(0, |counter, resource| {
-            if let ResourceState::Ok(_) = *resource.0.lock() {
-                counter + 1
-            } else {
-                counter
-            }
-        })
+    #[test]
+    fn resource_manager_request_in_group() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(Stub {});
+
+        let resource = manager.request_in_group::<Stub>(Path::new("foo.txt"), "level");
+        let untyped = manager.request_untyped_in_group(Path::new("bar.txt"), "level");
+        assert_eq!(
+            manager.state().group("level"),
+            &[resource.clone().into_untyped(), untyped]
+        );
+
+        let results = crate::core::futures::executor::block_on(manager.wait_for_group("level"));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.is_ok()));
     }
 
-    /// Returns a set of resource handled by this container.
-    pub fn resources(&self) -> Vec<UntypedResource> {
-        self.resources.iter().map(|t| t.value.clone()).collect()
+    #[test]
+    fn resource_manager_request_sync() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(Stub {});
+
+        let res = manager.request_sync::<Stub>(Path::new("foo.txt"));
+        assert!(res.unwrap().is_ok());
+
+        let res = manager.request_untyped_sync(Path::new("foo.bar"));
+        assert!(res.is_err());
     }
 
-    /// Tries to load a resources at a given path.
-    pub fn request<P>(&mut self, path: P) -> UntypedResource
-    where
-        P: AsRef<Path>,
-    {
-        if let Some(built_in_resource) = self.built_in_resources.get(path.as_ref()) {
-            return built_in_resource.clone();
+    /// Reverses bytes and counts the amount of imports.
+    struct ReverseImporter(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl crate::import::ResourceImporter for ReverseImporter {
+        fn extensions(&self) -> &[&str] {
+            &["bin"]
         }
 
-        match self.find(path.as_ref()) {
-            Some(existing) => existing.clone(),
-            None => {
-                if let Some(loader) = self.find_loader(path.as_ref()) {
-                    let resource = UntypedResource::new_pending(
-                        path.as_ref().to_owned(),
-                        loader.data_type_uuid(),
-                    );
+        fn importer_uuid(&self) -> Uuid {
+            uuid!("9d3e2b71-58c4-4f0a-a6e2-7b1c0d4f9a38")
+        }
 
-                    self.spawn_loading_task(loader, resource.clone(), false);
+        fn import(
+            &self,
+            context: crate::import::ImportContext,
+        ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(context.source.iter().rev().cloned().collect())
+        }
+    }
 
-                    self.push(resource.clone());
+    #[test]
+    fn resource_manager_import_with_derived_data_cache() {
+        let cache_root = std::env::temp_dir().join(format!("fyrox-ddc-{}", Uuid::new_v4()));
+        let imports = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1, 2, 3]);
+        io.add_file("b.bin", vec![1, 2, 3]);
 
-                    resource
-                } else {
-                    UntypedResource::new_load_error(
-                        path.as_ref().to_owned(),
-                        LoadError::new(format!(
-                            "There's no resource loader for {} resource!",
-                            path.as_ref().display()
-                        )),
-                        Default::default(),
-                    )
-                }
-            }
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.importers.add(ReverseImporter(imports.clone()));
+            state.derived_data_cache = DerivedDataCache::new(&cache_root);
         }
-    }
 
-    fn find_loader(&self, path: &Path) -> Option<&dyn ResourceLoader> {
-        path.extension().and_then(|extension| {
-            self.loaders
-                .iter()
-                .find(|loader| loader.supports_extension(&extension.to_string_lossy()))
-        })
-    }
+        let a = manager.request_sync::<BytesStub>("a.bin").unwrap();
+        assert_eq!(a.data_ref().bytes, [3, 2, 1]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 1);
 
-    fn spawn_loading_task(
-        &self,
-        loader: &dyn ResourceLoader,
-        resource: UntypedResource,
-        reload: bool,
-    ) {
-        self.task_pool.spawn_task(loader.load(
-            resource,
-            self.event_broadcaster.clone(),
-            reload,
-            self.resource_io.clone(),
-        ));
-    }
+        // Identical content is imported only once.
+        let b = manager.request_sync::<BytesStub>("b.bin").unwrap();
+        assert_eq!(b.data_ref().bytes, [3, 2, 1]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 1);
 
-    /// Reloads a single resource.
-    pub fn reload_resource(&mut self, resource: UntypedResource) {
-        let mut state = resource.0.lock();
+        // Changed source is imported again.
+        io.add_file("a.bin", vec![4, 5]);
+        a.reload(&manager);
+        let a = crate::core::futures::executor::block_on(a).unwrap();
+        assert_eq!(a.data_ref().bytes, [5, 4]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 2);
 
-        if !state.is_loading() {
-            let path = state.path().to_path_buf();
-            if let Some(loader) = self.find_loader(&path) {
-                state.switch_to_pending_state();
-                drop(state);
+        manager.state().derived_data_cache.clear().unwrap();
+        assert!(!cache_root.exists());
+    }
 
-                self.spawn_loading_task(loader, resource, true);
-            } else {
-                let msg = format!(
-                    "There's no resource loader for {} resource!",
-                    path.display()
-                );
-                resource.commit_error(path, msg)
+    /// Resource IO, that provides the given stamp for every file and counts reads of the given
+    /// file.
+    struct StampedResourceIo {
+        inner: MemoryResourceIo,
+        stamp: Mutex<FileStamp>,
+        path: PathBuf,
+        reads: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ResourceIo for StampedResourceIo {
+        fn load_file<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> crate::io::ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+            if path == self.path {
+                self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
             }
+            self.inner.load_file(path)
         }
-    }
 
-    /// Reloads all resources in the container. Returns a list of resources that will be reloaded.
-    /// You can use the list to wait until all resources are loading.
-    pub fn reload_resources(&mut self) -> Vec<UntypedResource> {
-        let resources = self
-            .resources
-            .iter()
-            .map(|r| r.value.clone())
-            .collect::<Vec<_>>();
+        fn move_file<'a>(
+            &'a self,
+            source: &'a Path,
+            dest: &'a Path,
+        ) -> crate::io::ResourceIoFuture<'a, Result<(), FileLoadError>> {
+            self.inner.move_file(source, dest)
+        }
 
-        for resource in resources.iter().cloned() {
-            self.reload_resource(resource);
+        fn exists<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.exists(path)
         }
 
-        resources
-    }
+        fn is_file<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.is_file(path)
+        }
 
-    /// Wait until all resources are loaded (or failed to load).
-    pub fn get_wait_context(&self) -> ResourceWaitContext {
-        ResourceWaitContext {
-            resources: self
-                .resources
-                .iter()
-                .map(|e| e.value.clone())
-                .collect::<Vec<_>>(),
+        fn is_dir<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.is_dir(path)
         }
-    }
 
-    /// Tries to reload a resource at the given path.
-    pub fn try_reload_resource_from_path(&mut self, path: &Path) -> bool {
-        if let Some(resource) = self.find(path).cloned() {
-            self.reload_resource(resource);
-            true
-        } else {
-            false
+        fn file_stamp<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> crate::io::ResourceIoFuture<'a, Option<FileStamp>> {
+            Box::pin(std::future::ready(Some(*self.stamp.lock())))
         }
     }
 
-    /// Forgets that a resource at the given path was ever loaded, thus making it possible to reload it
-    /// again as a new instance.
-    pub fn unregister(&mut self, path: &Path) {
-        if let Some(position) = self.resources.iter().position(|r| r.path() == path) {
-            self.resources.remove(position);
+    #[test]
+    fn resource_manager_import_cache_hit_by_stamps() {
+        let cache_root = std::env::temp_dir().join(format!("fyrox-ddc-{}", Uuid::new_v4()));
+        let imports = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let inner = MemoryResourceIo::new();
+        inner.add_file("a.bin", vec![1, 2, 3]);
+        let io = Arc::new(StampedResourceIo {
+            inner,
+            stamp: Mutex::new(FileStamp {
+                size: 3,
+                modified: 1,
+            }),
+            path: PathBuf::from("a.bin"),
+            reads: Default::default(),
+        });
+
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.importers.add(ReverseImporter(imports.clone()));
+            state.derived_data_cache = DerivedDataCache::new(&cache_root);
         }
+        let reads = || io.reads.load(std::sync::atomic::Ordering::SeqCst);
+
+        let a = manager.request_sync::<BytesStub>("a.bin").unwrap();
+        assert_eq!(a.data_ref().bytes, [3, 2, 1]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(reads(), 1);
+
+        // Unmodified source is not read again.
+        a.reload(&manager);
+        let a = crate::core::futures::executor::block_on(a).unwrap();
+        assert_eq!(a.data_ref().bytes, [3, 2, 1]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(reads(), 1);
+
+        // Modified source is read and imported again.
+        io.inner.add_file("a.bin", vec![4, 5]);
+        *io.stamp.lock() = FileStamp {
+            size: 2,
+            modified: 2,
+        };
+        a.reload(&manager);
+        let a = crate::core::futures::executor::block_on(a).unwrap();
+        assert_eq!(a.data_ref().bytes, [5, 4]);
+        assert_eq!(imports.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(reads(), 2);
+
+        manager.state().derived_data_cache.clear().unwrap();
+        assert!(!cache_root.exists());
     }
-}
 
-#[cfg(test)]
-mod test {
-    use std::{fs::File, time::Duration};
+    #[test]
+    fn resource_manager_locale_variants() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("voice.bin", vec![0]);
+        io.add_file("voice_de.bin", vec![1]);
+        io.add_file("en/voice.bin", vec![2]);
 
-    use crate::loader::{BoxedLoaderFuture, ResourceLoader};
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+        }
+        manager.set_locale_settings(LocaleSettings::new("de"));
+
+        let voice = manager.request_sync::<BytesStub>("voice.bin").unwrap();
+        assert_eq!(voice.data_ref().bytes, [1]);
+        assert_eq!(voice.path(), Path::new("voice.bin"));
+
+        // Loaded resources are swapped in the background.
+        manager.set_locale_settings(LocaleSettings::new("en-US"));
+        let start = std::time::Instant::now();
+        while voice.is_loading() || voice.data_ref().bytes != [2] {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(voice.path(), Path::new("voice.bin"));
+    }
 
-    use super::*;
+    #[test]
+    fn resource_manager_platform_variants() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("texture.bin", vec![0]);
+        io.add_file("texture.mobile.bin", vec![1]);
 
-    use fyrox_core::uuid::{uuid, Uuid};
-    use fyrox_core::{
-        reflect::{FieldInfo, Reflect},
-        visitor::{Visit, VisitResult, Visitor},
-        TypeUuidProvider,
-    };
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+        }
+        manager.set_platform_settings(PlatformSettings::new("mobile"));
 
-    #[derive(Debug, Default, Reflect, Visit)]
-    struct Stub {}
+        let texture = manager.request_sync::<BytesStub>("texture.bin").unwrap();
+        assert_eq!(texture.data_ref().bytes, [1]);
+        assert_eq!(texture.path(), Path::new("texture.bin"));
 
-    impl TypeUuidProvider for Stub {
-        fn type_uuid() -> Uuid {
-            uuid!("9d873ff4-3126-47e1-a492-7cd8e7168239")
+        manager.set_platform_settings(PlatformSettings::new("desktop"));
+        let start = std::time::Instant::now();
+        while texture.is_loading() || texture.data_ref().bytes != [0] {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            std::thread::sleep(std::time::Duration::from_millis(1));
         }
     }
 
-    impl ResourceData for Stub {
-        fn path(&self) -> &std::path::Path {
-            Path::new("test.txt")
+    #[test]
+    fn resource_manager_prefetch() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("level.bin", vec![1, 2, 3]);
+
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.set_prefetch_settings(PrefetchSettings {
+                max_bytes_per_second: None,
+                ..Default::default()
+            });
+            state.prefetch("level.bin");
+            // Files without a loader are ignored.
+            state.prefetch("level.txt");
+            assert_eq!(state.count_queued_prefetches(), 1);
         }
 
-        fn set_path(&mut self, _path: std::path::PathBuf) {}
+        let start = std::time::Instant::now();
+        while !manager.state().is_prefetched("level.bin") {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            manager.state().update(0.016);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(manager.state().prefetched_size(), 3);
+
+        // The file is not read again, the prefetched content is used instead.
+        io.remove_file(Path::new("level.bin"));
+        let level = manager.request_sync::<BytesStub>("level.bin").unwrap();
+        assert_eq!(level.data_ref().bytes, [1, 2, 3]);
+        assert!(!manager.state().is_prefetched("level.bin"));
+        assert_eq!(manager.state().prefetched_size(), 0);
+    }
 
-        fn as_any(&self) -> &dyn std::any::Any {
-            unimplemented!()
+    /// Resource IO, that fails to load files with a timeout error the given amount of times.
+    struct FlakyResourceIo {
+        inner: MemoryResourceIo,
+        failures: Mutex<usize>,
+    }
+
+    impl ResourceIo for FlakyResourceIo {
+        fn load_file<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> crate::io::ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+            let mut failures = self.failures.lock();
+            if *failures > 0 {
+                *failures -= 1;
+                return Box::pin(std::future::ready(Err(FileLoadError::Io(
+                    std::io::ErrorKind::TimedOut.into(),
+                ))));
+            }
+            self.inner.load_file(path)
         }
 
-        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
-            unimplemented!()
+        fn move_file<'a>(
+            &'a self,
+            source: &'a Path,
+            dest: &'a Path,
+        ) -> crate::io::ResourceIoFuture<'a, Result<(), FileLoadError>> {
+            self.inner.move_file(source, dest)
         }
 
-        fn type_uuid(&self) -> Uuid {
-            <Self as TypeUuidProvider>::type_uuid()
+        fn exists<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.exists(path)
         }
 
-        fn is_embedded(&self) -> bool {
-            unimplemented!()
+        fn is_file<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.is_file(path)
         }
-    }
 
-    impl ResourceLoader for Stub {
-        fn extensions(&self) -> &[&str] {
-            &["txt"]
+        fn is_dir<'a>(&'a self, path: &'a Path) -> crate::io::ResourceIoFuture<'a, bool> {
+            self.inner.is_dir(path)
         }
+    }
 
-        fn data_type_uuid(&self) -> Uuid {
-            <Stub as TypeUuidProvider>::type_uuid()
+    #[test]
+    fn resource_manager_retry_transient_errors() {
+        let inner = MemoryResourceIo::new();
+        inner.add_file("remote.bin", vec![1, 2, 3]);
+        inner.add_file("other.bin", vec![4]);
+        let io = Arc::new(FlakyResourceIo {
+            inner,
+            failures: Mutex::new(2),
+        });
+
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.set_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_delay: Duration::from_millis(10),
+                ..Default::default()
+            });
         }
 
-        fn load(
-            &self,
-            resource: UntypedResource,
-            event_broadcaster: ResourceEventBroadcaster,
-            reload: bool,
-            _io: Arc<dyn ResourceIo>,
-        ) -> BoxedLoaderFuture {
-            Box::pin(async move {
-                resource.commit_ok(Stub::default());
-                event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
-            })
+        let wait = |resource: &Resource<BytesStub>| {
+            let start = std::time::Instant::now();
+            while resource.is_loading() {
+                assert!(start.elapsed() < Duration::from_secs(10));
+                manager.state().update(0.016);
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        };
+
+        // Two failures are covered by two retries.
+        let remote = manager.request::<BytesStub>("remote.bin");
+        wait(&remote);
+        assert!(remote.is_ok());
+        assert_eq!(remote.data_ref().bytes, [1, 2, 3]);
+        assert_eq!(manager.state().count_scheduled_retries(), 0);
+
+        // Retries are exhausted, the error is committed.
+        *io.failures.lock() = 3;
+        let other = manager.request::<BytesStub>("other.bin");
+        wait(&other);
+        assert!(other.is_failed_to_load());
+        assert!(matches!(
+            *other.untyped.lock(),
+            ResourceState::LoadError { ref error, .. } if error.is_transient()
+        ));
+
+        // Non-transient errors are committed right away.
+        let missing = manager.request::<BytesStub>("missing.bin");
+        wait(&missing);
+        assert!(missing.is_failed_to_load());
+        assert_eq!(*io.failures.lock(), 0);
+    }
+
+    #[test]
+    fn resource_manager_retry_while_waiting() {
+        let inner = MemoryResourceIo::new();
+        inner.add_file("remote.bin", vec![1, 2, 3]);
+        inner.add_file("other.bin", vec![4]);
+        let io = Arc::new(FlakyResourceIo {
+            inner,
+            failures: Mutex::new(2),
+        });
+
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.set_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_delay: Duration::from_millis(10),
+                ..Default::default()
+            });
         }
+
+        // The manager is never updated here, blocking waits must start the retries.
+        let remote = manager.request_sync::<BytesStub>("remote.bin").unwrap();
+        assert_eq!(remote.data_ref().bytes, [1, 2, 3]);
+
+        *io.failures.lock() = 1;
+        let other = manager.request::<BytesStub>("other.bin");
+        crate::core::futures::executor::block_on(
+            manager.wait_for_all_pending(Some(Duration::from_secs(10))),
+        )
+        .unwrap();
+        assert!(other.is_ok());
+        assert_eq!(manager.state().count_scheduled_retries(), 0);
     }
 
     #[test]
-    fn resource_wait_context_is_all_loaded() {
-        assert!(ResourceWaitContext::default().is_all_loaded());
+    fn resource_manager_load_from_memory() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(BytesStubLoader);
+
+        let path = Path::new("virtual/data.bin");
+        manager.load_from_memory::<BytesStub>(vec![1, 2, 3], path);
+        let resource = manager.request_sync::<BytesStub>(path).unwrap();
+        assert_eq!(resource.data_ref().bytes, [1, 2, 3]);
+        // The resource must not exist on disk.
+        assert!(!path.exists());
+
+        // Loading at the same path again reloads the existing resource.
+        let reloaded = manager.load_from_memory::<BytesStub>(vec![4, 5], path);
+        assert_eq!(reloaded, resource);
+        let reloaded = crate::core::futures::executor::block_on(reloaded).unwrap();
+        assert_eq!(reloaded.data_ref().bytes, [4, 5]);
+
+        let res = manager
+            .state()
+            .load_from_memory(vec![], Path::new("foo.bar"));
+        assert!(!res.is_loading());
+    }
 
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
+    #[test]
+    fn resource_manager_content_deduplication() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1, 2, 3]);
+        io.add_file("copy/b.bin", vec![1, 2, 3]);
+        io.add_file("c.bin", vec![3, 2, 1]);
 
-        let cx = ResourceWaitContext {
-            resources: vec![
-                UntypedResource::new_pending(path.clone(), type_uuid),
-                UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid),
-            ],
-        };
-        assert!(!cx.is_all_loaded());
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.set_content_deduplication(true);
+        }
+
+        let a = manager.request_sync::<BytesStub>("a.bin").unwrap();
+        // The duplicate is detected by its loading task, it is loaded as usual.
+        let b = manager.request_sync::<BytesStub>("copy/b.bin").unwrap();
+        let c = manager.request_sync::<BytesStub>("c.bin").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(b.data_ref().bytes, [1, 2, 3]);
+
+        manager.state().update(0.0);
+        // The duplicate stays registered, so its users keep it and its path.
+        assert_eq!(manager.request_sync::<BytesStub>("copy/b.bin").unwrap(), b);
+        assert_eq!(b.path(), Path::new("copy/b.bin"));
+
+        {
+            let state = manager.state();
+            assert_eq!(state.len(), 3);
+            let duplicates = state.content_duplicates().collect::<Vec<_>>();
+            assert_eq!(duplicates.len(), 1);
+            assert!(duplicates[0].0.matches(Path::new("copy/b.bin")));
+            assert_eq!(duplicates[0].1, Path::new("a.bin"));
+        }
+
+        // Hashes and duplicates of destroyed resources are removed.
+        drop((a, b, c));
+        manager.state().update(DEFAULT_RESOURCE_LIFETIME + 1.0);
+        let state = manager.state();
+        assert_eq!(state.len(), 0);
+        assert_eq!(state.content_duplicates().count(), 0);
+        assert!(state.content_hashes.is_empty());
     }
 
     #[test]
-    fn resource_manager_state_new() {
-        let state = ResourceManagerState::new();
+    fn resource_manager_preload_manifest() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1]);
+        io.add_file("b.bin", vec![2]);
+        io.add_file("c.bin", vec![3]);
+        let manifest = PreloadManifest {
+            priority: LoadPriority::Normal,
+            group: Some("Level".to_string()),
+            resources: vec!["a.bin".into(), "b.bin".into(), "c.bin".into()],
+        };
+        io.add_file(
+            "level.manifest",
+            manifest.to_string_pretty().unwrap().into_bytes(),
+        );
 
-        assert!(state.resources.is_empty());
-        assert!(state.loaders.is_empty());
-        assert!(state.built_in_resources.is_empty());
-        assert!(state.constructors_container.is_empty());
-        assert!(state.watcher.is_none());
-        assert!(state.is_empty());
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.set_max_concurrent_preloads(1);
+        }
+
+        let request =
+            crate::core::futures::executor::block_on(manager.preload_manifest("level.manifest"))
+                .unwrap();
+        assert_eq!(request.resources().len(), 3);
+        assert_eq!(manager.state().group("Level").len(), 3);
+        assert_eq!(manager.state().count_queued_preloads(), 2);
+
+        // Explicit requests must not wait in the queue.
+        let c = manager.request_sync::<BytesStub>("c.bin").unwrap();
+        assert_eq!(c.data_ref().bytes, [3]);
+        assert_eq!(manager.state().count_queued_preloads(), 1);
+
+        crate::core::futures::executor::block_on(request.resources()[0].clone()).unwrap();
+        manager.state().update(0.0);
+        assert_eq!(manager.state().count_queued_preloads(), 0);
+
+        let results = crate::core::futures::executor::block_on(request.wait());
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert!(request.is_finished());
+        assert_eq!(request.progress(), 1.0);
     }
 
     #[test]
-    fn resource_manager_state_set_watcher() {
-        let mut state = ResourceManagerState::new();
-        assert!(state.watcher.is_none());
+    fn resource_manager_dependency_aware_loading() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.link", b"b.link\ndata.bin".to_vec());
+        // Cyclic dependency must not block loading.
+        io.add_file("b.link", b"a.link".to_vec());
+        io.add_file("data.bin", vec![1, 2, 3]);
 
-        let path = PathBuf::from("test.txt");
-        if File::create(path.clone()).is_ok() {
-            let watcher = FileSystemWatcher::new(path.clone(), Duration::from_secs(1));
-            state.set_watcher(watcher.ok());
-            assert!(state.watcher.is_some());
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.loaders.set(LinkStubLoader(manager.clone()));
         }
+
+        let a = manager.request_untyped_sync("a.link").unwrap();
+        // Dependencies are never loading when a dependent resource is loaded.
+        let data = manager.request_untyped("data.bin");
+        assert!(!data.is_loading());
+        assert!(!a.is_loading());
+
+        let b = manager.request_untyped_sync("b.link").unwrap();
+        assert!(!b.is_loading());
+
+        let state = manager.state();
+        let mut dependencies = state.dependencies_of(Path::new("a.link")).to_vec();
+        dependencies.sort();
+        assert_eq!(
+            dependencies,
+            [PathBuf::from("b.link"), PathBuf::from("data.bin")]
+        );
+        assert_eq!(
+            state.dependencies_of(Path::new("b.link")),
+            [PathBuf::from("a.link")]
+        );
+        assert!(state.dependencies_of(Path::new("data.bin")).is_empty());
     }
 
     #[test]
-    fn resource_manager_state_push() {
-        let mut state = ResourceManagerState::new();
+    fn resource_manager_registry_snapshot() {
+        use crate::core::futures::executor::block_on;
 
-        assert_eq!(state.count_loaded_resources(), 0);
-        assert_eq!(state.count_pending_resources(), 0);
-        assert_eq!(state.count_registered_resources(), 0);
-        assert_eq!(state.len(), 0);
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("data/a.link", b"data/b.bin".to_vec());
+        io.add_file("data/b.bin", vec![1, 2, 3]);
+        io.add_file("data/b.bin.options", b"()".to_vec());
+        io.add_file("data/notes.txt", vec![0]);
 
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
-        state.push(UntypedResource::new_pending(path.clone(), type_uuid));
-        state.push(UntypedResource::new_load_error(
-            path.clone(),
-            Default::default(),
-            type_uuid,
-        ));
-        state.push(UntypedResource::new_ok(Stub {}));
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.loaders.set(LinkStubLoader(manager.clone()));
+        }
+        manager.request_untyped_sync("data/a.link").unwrap();
+
+        let snapshot = block_on(manager.build_registry_snapshot("data")).unwrap();
+        assert_eq!(snapshot.len(), 2);
+        let link = snapshot.entry(Path::new("data/a.link")).unwrap();
+        assert_eq!(link.type_uuid, <LinkStub as TypeUuidProvider>::type_uuid());
+        assert_eq!(link.dependencies, [PathBuf::from("data/b.bin")]);
+        assert_eq!(link.options_hash, None);
+        let bin = snapshot.entry(Path::new("data/b.bin")).unwrap();
+        assert_eq!(bin.type_uuid, <BytesStub as TypeUuidProvider>::type_uuid());
+        assert!(bin.options_hash.is_some());
+
+        // A fresh manager takes everything from the snapshot without walking the directory.
+        let bytes = snapshot.to_bytes().unwrap();
+        io.add_file("data.registry", bytes);
+        io.add_file("data/unknown.link", b"data/b.bin".to_vec());
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.loaders.set(LinkStubLoader(manager.clone()));
+        }
+        block_on(manager.load_registry_snapshot("data.registry")).unwrap();
+        assert_eq!(
+            manager.state().dependencies_of(Path::new("data/a.link")),
+            [PathBuf::from("data/b.bin")]
+        );
+        let mut paths = block_on(manager.find_loadable_resources("data")).unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [PathBuf::from("data/a.link"), PathBuf::from("data/b.bin")]
+        );
 
-        assert_eq!(state.count_loaded_resources(), 1);
-        assert_eq!(state.count_pending_resources(), 1);
-        assert_eq!(state.count_registered_resources(), 3);
-        assert_eq!(state.len(), 3);
+        // Loaded and renamed resources are kept in the snapshot.
+        manager.request_untyped_sync("data/unknown.link").unwrap();
+        block_on(manager.rename_resource("data/b.bin", "data/moved.bin")).unwrap();
+        let state = manager.state();
+        let registry = state.registry_snapshot().unwrap();
+        assert!(registry.entry(Path::new("data/unknown.link")).is_some());
+        assert!(registry.entry(Path::new("data/b.bin")).is_none());
+        assert!(registry.entry(Path::new("data/moved.bin")).is_some());
+        assert_eq!(
+            registry
+                .entry(Path::new("data/a.link"))
+                .unwrap()
+                .dependencies,
+            [PathBuf::from("data/moved.bin")]
+        );
     }
 
     #[test]
-    fn resource_manager_state_loading_progress() {
-        let mut state = ResourceManagerState::new();
+    fn resource_manager_validate_project() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("data/a.link", b"data/b.link\ndata/missing.bin".to_vec());
+        io.add_file("data/b.link", b"data/a.link\ndata/c.odd".to_vec());
+        io.add_file("data/c.odd", vec![1, 2, 3]);
+        io.add_file("data/ok.link", b"data/c.bin".to_vec());
+        io.add_file("data/c.bin", vec![1, 2, 3]);
+        io.add_file("data/unknown.txt", vec![]);
+        io.add_file("other/d.bin", vec![]);
 
-        assert_eq!(state.loading_progress(), 100);
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.loaders.set(MislabeledStubLoader);
+            state.loaders.set(LinkStubLoader(manager.clone()));
+        }
 
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
-        state.push(UntypedResource::new_pending(path.clone(), type_uuid));
-        state.push(UntypedResource::new_load_error(
-            path.clone(),
-            Default::default(),
-            type_uuid,
-        ));
-        state.push(UntypedResource::new_ok(Stub {}));
+        let report = crate::core::futures::executor::block_on(manager.validate_project("data"));
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.checked,
+            ["a.link", "b.link", "c.bin", "c.odd", "ok.link"]
+                .map(|name| Path::new("data").join(name))
+        );
+        assert_eq!(
+            report.missing,
+            [MissingResource {
+                path: "data/missing.bin".into(),
+                referenced_by: vec!["data/a.link".into()],
+            }]
+        );
+        assert_eq!(report.type_mismatches.len(), 1);
+        assert_eq!(report.type_mismatches[0].path, Path::new("data/c.odd"));
+        assert_eq!(
+            report.type_mismatches[0].actual,
+            [<BytesStub as TypeUuidProvider>::type_uuid()]
+        );
+        assert_eq!(
+            report.cycles,
+            [vec![
+                PathBuf::from("data/a.link"),
+                PathBuf::from("data/b.link")
+            ]]
+        );
+        assert!(report.failed.is_empty());
 
-        assert_eq!(state.loading_progress(), 33);
+        let report = crate::core::futures::executor::block_on(manager.validate_project("other"));
+        assert!(report.is_ok());
+        assert_eq!(report.checked, [PathBuf::from("other/d.bin")]);
     }
 
     #[test]
-    fn resource_manager_state_find() {
-        let mut state = ResourceManagerState::new();
+    fn resource_manager_loader_fallback() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(BytesStubLoader);
+        manager
+            .state()
+            .loaders
+            .set_with_priority(MagicStubLoader, 1);
+
+        let load = |bytes: &[u8], path: &str| {
+            let resource = manager
+                .state()
+                .load_from_memory(bytes.to_vec(), Path::new(path));
+            crate::core::futures::executor::block_on(resource).unwrap()
+        };
 
-        assert!(state.find(Path::new("foo.txt")).is_none());
+        // The loader with the highest priority goes first.
+        let magic = load(b"MAGIC", "magic.bin");
+        assert_eq!(
+            magic.type_uuid(),
+            <DirtyStub as TypeUuidProvider>::type_uuid()
+        );
 
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
-        let resource = UntypedResource::new_pending(path.clone(), type_uuid);
-        state.push(resource.clone());
+        // If it fails, the next one is tried.
+        let plain = load(b"plain", "plain.bin");
+        assert_eq!(
+            plain.type_uuid(),
+            <BytesStub as TypeUuidProvider>::type_uuid()
+        );
 
-        assert_eq!(state.find(path), Some(&resource));
+        // Typed requests use only the loaders of the requested type.
+        let typed = manager.load_from_memory::<BytesStub>(b"MAGIC".to_vec(), "typed.bin");
+        let typed = crate::core::futures::executor::block_on(typed).unwrap();
+        assert_eq!(typed.data_ref().bytes, b"MAGIC");
+
+        // The error of the last loader is committed, if every loader failed.
+        let missing = manager.state().request("missing.bin");
+        assert!(crate::core::futures::executor::block_on(missing.clone()).is_err());
+        assert_eq!(
+            missing.type_uuid(),
+            <BytesStub as TypeUuidProvider>::type_uuid()
+        );
     }
 
     #[test]
-    fn resource_manager_state_resources() {
-        let mut state = ResourceManagerState::new();
-
-        assert_eq!(state.resources(), Vec::new());
+    fn resource_manager_request_embedded() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(BytesStubLoader);
+        let path = Path::new("embedded/data.bin");
+        manager
+            .state()
+            .embedded_resources
+            .register(path, b"embedded");
+
+        let resource = manager.request_sync::<BytesStub>(path).unwrap();
+        assert_eq!(resource.data_ref().bytes, b"embedded");
+        assert!(!path.exists());
+
+        manager
+            .state()
+            .reload_resource(resource.clone().into_untyped());
+        let resource = crate::core::futures::executor::block_on(resource).unwrap();
+        assert_eq!(resource.data_ref().bytes, b"embedded");
+    }
 
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
-        let r1 = UntypedResource::new_pending(path.clone(), type_uuid);
-        let r2 = UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid);
-        let r3 = UntypedResource::new_ok(Stub {});
-        state.push(r1.clone());
-        state.push(r2.clone());
-        state.push(r3.clone());
+    #[test]
+    fn resource_manager_statistics() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(BytesStubLoader);
+
+        let path = Path::new("virtual/stats.bin");
+        let resource = manager.load_from_memory::<BytesStub>(vec![1, 2, 3], path);
+        crate::core::futures::executor::block_on(resource).unwrap();
+
+        // Timings are written when the loading task is finished, which could happen a bit later
+        // than the resource is committed.
+        let mut timing = None;
+        for _ in 0..1000 {
+            timing = manager.statistics().find(path).cloned();
+            if timing.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let timing = timing.unwrap();
+        assert_eq!(
+            timing.type_uuid,
+            <BytesStub as TypeUuidProvider>::type_uuid()
+        );
+        assert!(!timing.reload);
 
-        assert_eq!(state.resources(), vec![r1.clone(), r2.clone(), r3.clone()]);
-        assert!(state.iter().eq([&r1, &r2, &r3]));
+        manager.state().clear_statistics();
+        assert!(manager.statistics().timings.is_empty());
     }
 
     #[test]
-    fn resource_manager_state_destroy_unused_resources() {
-        let mut state = ResourceManagerState::new();
+    fn resource_manager_resource_callbacks() {
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(BytesStubLoader);
+
+        let resource =
+            manager.load_from_memory::<BytesStub>(vec![1, 2, 3], Path::new("virtual/cb.bin"));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let loaded_sender = sender.clone();
+        resource.on_loaded(&manager, move |resource| {
+            loaded_sender
+                .send(("loaded", resource.data_ref().bytes.len()))
+                .unwrap()
+        });
+        assert_eq!(
+            receiver.recv_timeout(std::time::Duration::from_secs(10)),
+            Ok(("loaded", 3))
+        );
+
+        // Already loaded resource calls the callback immediately.
+        resource.on_loaded(&manager, move |_| sender.send(("loaded", 0)).unwrap());
+        assert_eq!(receiver.try_recv(), Ok(("loaded", 0)));
+    }
 
+    #[test]
+    fn resource_manager_memory_usage_report() {
+        let manager = ResourceManager::new();
+        manager.state().constructors_container.add::<BytesStub>();
+
+        let mut state = manager.state();
+        for size in [10, 20] {
+            state.push(UntypedResource::new_ok(BytesStub {
+                path: Default::default(),
+                bytes: vec![0; size],
+            }));
+        }
+        state.push(UntypedResource::new_ok(Stub {}));
         state.push(UntypedResource::new_pending(
-            PathBuf::from("test.txt"),
-            Uuid::default(),
+            "pending.bin".into(),
+            <BytesStub as TypeUuidProvider>::type_uuid(),
         ));
-        assert_eq!(state.len(), 1);
 
-        state.destroy_unused_resources();
-        assert_eq!(state.len(), 0);
+        let report = state.memory_usage_report();
+        assert_eq!(report.total_count(), 3);
+        let bytes = report
+            .find(<BytesStub as TypeUuidProvider>::type_uuid())
+            .unwrap();
+        assert_eq!(bytes.count, 2);
+        assert_eq!(bytes.memory, 30);
+        assert_eq!(bytes.type_name, std::any::type_name::<BytesStub>());
+        let stub = report
+            .find(<Stub as TypeUuidProvider>::type_uuid())
+            .unwrap();
+        assert_eq!(stub.count, 1);
+        assert!(stub.type_name.is_empty());
+        assert_eq!(report.types[0].type_uuid, bytes.type_uuid);
     }
 
     #[test]
-    fn resource_manager_state_request() {
-        let mut state = ResourceManagerState::new();
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
+    fn resource_manager_save_all_modified() {
+        let manager = ResourceManager::new();
 
-        let resource = UntypedResource::new_load_error(path.clone(), Default::default(), type_uuid);
-        state.push(resource.clone());
+        let mut data = DirtyStub::default();
+        data.mark_dirty();
+        let dirty = UntypedResource::new_ok(data);
+        manager.state().push(dirty.clone());
+        manager
+            .state()
+            .push(UntypedResource::new_ok(DirtyStub::default()));
+        manager.state().push(UntypedResource::new_ok(Stub {}));
+
+        assert_eq!(manager.save_all_modified(), 1);
+        assert_eq!(manager.save_all_modified(), 0);
+
+        let dirty = dirty.try_cast::<DirtyStub>().unwrap();
+        assert_eq!(dirty.data_ref().saves, 1);
+        assert!(!dirty.data_ref().is_dirty());
+    }
 
-        let res = state.request(path);
-        assert_eq!(res, resource);
+    #[test]
+    fn resource_manager_read_only_mode() {
+        let manager = ResourceManager::new();
+        let other = ResourceManager::new();
 
-        let path = PathBuf::from("foo.txt");
-        let res = state.request(path.clone());
+        let mut data = DirtyStub::default();
+        data.mark_dirty();
+        let dirty = UntypedResource::new_ok(data);
+        manager.state().push(dirty.clone());
 
-        assert_eq!(res.path(), path.clone());
-        assert_eq!(res.type_uuid(), type_uuid);
-        assert!(!res.is_loading());
+        manager.state().set_read_only(true);
+        assert!(manager.state().is_read_only());
+        assert!(manager.state().derived_data_cache.is_read_only());
+        // The mode belongs to the manager, another one is still writable.
+        assert!(!other.state().is_read_only());
+
+        assert_eq!(
+            manager.save_resource(&dirty),
+            Err(ResourceSaveError::ReadOnly)
+        );
+        assert_eq!(manager.save_all_modified(), 0);
+
+        manager.state().set_read_only(false);
+        assert_eq!(manager.save_resource(&dirty), Ok(()));
+        let dirty = dirty.try_cast::<DirtyStub>().unwrap();
+        assert_eq!(dirty.data_ref().saves, 1);
+        assert!(!dirty.data_ref().is_dirty());
     }
 
     #[test]
-    fn resource_manager_state_reload_resource() {
+    fn resource_manager_read_only_root_flags() {
         let mut state = ResourceManagerState::new();
-        state.loaders.set(Stub {});
-
-        let resource = UntypedResource::new_ok(Stub {});
-        state.push(resource.clone());
-        state.reload_resource(resource.clone());
+        let registered = UntypedResource::new_pending("dlc/a.bin".into(), Uuid::default());
+        state.push(registered.clone());
+        assert!(!registered.is_read_only());
+
+        state.read_only_resources_mut().add_root("dlc");
+        let pushed = UntypedResource::new_pending("dlc/b.bin".into(), Uuid::default());
+        state.push(pushed.clone());
+        assert!(pushed.is_read_only());
+        assert!(!registered.is_read_only());
+
+        state.sync_read_only_flags();
+        assert!(registered.is_read_only());
+        assert_eq!(
+            registered.try_save(Path::new("dlc/a.bin")),
+            Err(ResourceSaveError::ReadOnly)
+        );
 
-        assert!(resource.is_loading());
+        state.read_only_resources_mut().remove_root("dlc");
+        state.sync_read_only_flags();
+        assert!(!registered.is_read_only());
+        assert!(!pushed.is_read_only());
     }
 
     #[test]
-    fn resource_manager_state_reload_resources() {
-        let mut state = ResourceManagerState::new();
-        state.loaders.set(Stub {});
+    fn resource_manager_wait_for_all_pending() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1]);
+        io.add_file("b.bin", vec![2]);
 
-        let resource = UntypedResource::new_ok(Stub {});
-        state.push(resource.clone());
-        let res = state.reload_resources();
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io);
+            state.loaders.set(BytesStubLoader);
+        }
 
-        assert_eq!(res.len(), 1);
-        assert!(res[0].is_loading());
+        // Only one preload could be active, the other one is queued and must be started by the
+        // wait itself.
+        manager.state().set_max_concurrent_preloads(1);
+        let request = manager.state().preload(&PreloadManifest {
+            priority: LoadPriority::Low,
+            group: None,
+            resources: vec!["a.bin".into(), "b.bin".into()],
+        });
+        assert_eq!(manager.wait_for_all_pending_blocking(None), Ok(()));
+        assert!(request.is_finished());
+
+        // A resource, that is never loaded.
+        let stuck = UntypedResource::new_pending("stuck.bin".into(), Default::default());
+        manager.state().push(stuck.clone());
+        assert_eq!(
+            manager.wait_for_all_pending_blocking(Some(Duration::from_millis(50))),
+            Err(WaitTimeoutError {
+                pending: vec![PathBuf::from("stuck.bin")]
+            })
+        );
+        // Wakers of the timed out wait must be released before the resource is dropped.
+        stuck.commit_error(PathBuf::from("stuck.bin"), "Never loaded".to_string());
     }
 
     #[test]
-    fn resource_manager_state_try_reload_resource_from_path() {
-        let mut state = ResourceManagerState::new();
-        state.loaders.set(Stub {});
+    fn resource_manager_rename_resource() {
+        use crate::core::futures::executor::block_on;
 
-        let resource = UntypedResource::new_load_error(
-            PathBuf::from("test.txt"),
-            Default::default(),
-            Uuid::default(),
-        );
-        state.push(resource.clone());
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("data.bin", vec![1, 2, 3]);
+        io.add_file("other.bin", vec![4]);
+        io.add_file("a.link", b"data.bin".to_vec());
 
-        assert!(!state.try_reload_resource_from_path(Path::new("foo.txt")));
+        let manager = ResourceManager::new();
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io.clone());
+            state.loaders.set(BytesStubLoader);
+            state.loaders.set(LinkStubLoader(manager.clone()));
+        }
 
-        assert!(state.try_reload_resource_from_path(Path::new("test.txt")));
-        assert!(resource.is_loading());
-    }
+        let data = manager.request_untyped_sync("data.bin").unwrap();
+        let link = manager.request_untyped_sync("a.link").unwrap();
+        // A detached instance, that refers to the same path, must be replaced.
+        let detached = UntypedResource::new_load_error(
+            "data.bin".into(),
+            LoadError::new("Detached"),
+            Default::default(),
+        );
+        let detached_link = UntypedResource::new_ok(LinkStub {
+            path: "b.link".into(),
+            dependencies: vec![detached],
+        });
+        manager.state().push(detached_link.clone());
 
-    #[test]
-    fn resource_manager_state_get_wait_context() {
-        let mut state = ResourceManagerState::new();
+        assert!(matches!(
+            block_on(manager.rename_resource("data.bin", "other.bin")),
+            Err(ResourceRenameError::AlreadyExists(_))
+        ));
 
-        let resource = UntypedResource::new_ok(Stub {});
-        state.push(resource.clone());
-        let cx = state.get_wait_context();
+        block_on(manager.rename_resource("data.bin", "moved/data.bin")).unwrap();
+        assert_eq!(data.path(), Path::new("moved/data.bin"));
+        assert!(!block_on(io.exists(Path::new("data.bin"))));
+        assert!(block_on(io.exists(Path::new("moved/data.bin"))));
 
-        assert!(cx.resources.eq(&vec![resource]));
-    }
+        let link = link.try_cast::<LinkStub>().unwrap();
+        assert_eq!(link.data_ref().dependencies[0], data);
+        let detached_link = detached_link.try_cast::<LinkStub>().unwrap();
+        assert_eq!(detached_link.data_ref().dependencies[0], data);
+        assert_eq!(
+            manager.state().dependencies_of(Path::new("a.link")),
+            [PathBuf::from("moved/data.bin")]
+        );
 
-    #[test]
-    fn resource_manager_new() {
-        let manager = ResourceManager::new();
+        // Old path is redirected to the new one.
+        assert_eq!(manager.request_untyped("data.bin"), data);
+        assert_eq!(
+            manager.state().resolve_redirect(Path::new("data.bin")),
+            Some(PathBuf::from("moved/data.bin"))
+        );
 
-        assert!(manager.state.lock().is_empty());
-        assert!(manager.state().is_empty());
+        // Renaming it again updates the existing redirect.
+        block_on(manager.rename_resource("moved/data.bin", "final.bin")).unwrap();
+        assert_eq!(
+            manager.state().resolve_redirect(Path::new("data.bin")),
+            Some(PathBuf::from("final.bin"))
+        );
+        assert_eq!(manager.request_untyped("moved/data.bin"), data);
     }
 
     #[test]
-    fn resource_manager_register() {
-        let manager = ResourceManager::default();
-        let path = PathBuf::from("test.txt");
-        let type_uuid = Uuid::default();
+    fn resource_manager_custom_task_executor() {
+        use crate::task::{TaskExecutor, TaskFuture};
+        use std::sync::atomic::{AtomicUsize, Ordering};
 
-        let resource = UntypedResource::new_pending(path.clone(), type_uuid);
-        let res = manager.register(resource.clone(), path.clone(), |_, __| true);
-        assert!(res.is_err());
+        #[derive(Default)]
+        struct CountingExecutor {
+            spawned: AtomicUsize,
+        }
 
-        let resource = UntypedResource::new_ok(Stub {});
-        let res = manager.register(resource.clone(), path.clone(), |_, __| true);
-        assert!(res.is_ok());
-    }
+        impl TaskExecutor for CountingExecutor {
+            fn spawn(&self, future: TaskFuture) {
+                self.spawned.fetch_add(1, Ordering::SeqCst);
+                crate::core::futures::executor::block_on(future);
+            }
+        }
 
-    #[test]
-    fn resource_manager_request() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1, 2, 3]);
+        let executor = Arc::new(CountingExecutor::default());
         let manager = ResourceManager::new();
-        let untyped = UntypedResource::new_ok(Stub {});
-        let res = manager.register(untyped.clone(), PathBuf::from("foo.txt"), |_, __| true);
-        assert!(res.is_ok());
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io);
+            state.loaders.set(BytesStubLoader);
+            state.set_task_executor(executor.clone());
+        }
 
-        let res: Resource<Stub> = manager.request(Path::new("test.txt"));
-        assert_eq!(
-            res,
-            Resource {
-                untyped,
-                phantom: PhantomData::<Stub>
-            }
-        );
+        // The executor above runs tasks in place, so the resource is loaded immediately.
+        let resource = manager.request::<BytesStub>("a.bin");
+        assert!(resource.is_ok());
+        assert_eq!(resource.data_ref().bytes, [1, 2, 3]);
+        assert_eq!(executor.spawned.load(Ordering::SeqCst), 1);
     }
 
     #[test]
-    fn resource_manager_request_untyped() {
+    fn resource_manager_blocking_wait_with_cooperative_executor() {
+        use crate::task::CooperativeTaskExecutor;
+
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file("a.bin", vec![1, 2, 3]);
+        io.add_file("b.bin", vec![4, 5, 6]);
+        let executor = Arc::new(CooperativeTaskExecutor::new(Duration::from_secs(10)));
         let manager = ResourceManager::new();
-        let resource = UntypedResource::new_ok(Stub {});
-        let res = manager.register(resource.clone(), PathBuf::from("foo.txt"), |_, __| true);
-        assert!(res.is_ok());
+        {
+            let mut state = manager.state();
+            state.set_resource_io(io);
+            state.loaders.set(BytesStubLoader);
+            state.set_task_executor(executor.clone());
+        }
 
-        let res = manager.request_untyped(Path::new("test.txt"));
-        assert_eq!(res, resource);
+        // Tasks of the executor run only when it is updated, blocking waits must do it.
+        let a = manager.request_sync::<BytesStub>("a.bin").unwrap();
+        assert_eq!(a.data_ref().bytes, [1, 2, 3]);
+
+        // A task spawned from another thread is run by the waiting thread.
+        let other = manager.clone();
+        let b = std::thread::spawn(move || other.request::<BytesStub>("b.bin"))
+            .join()
+            .unwrap();
+        assert!(b.is_loading());
+        manager
+            .wait_for_all_pending_blocking(Some(Duration::from_secs(10)))
+            .unwrap();
+        assert_eq!(b.data_ref().bytes, [4, 5, 6]);
+        assert_eq!(executor.pending_tasks(), 0);
     }
 
     #[test]
@@ -0,0 +1,131 @@
+//! Preload manifests are lists of resources, that should be loaded in advance (at startup or before
+//! a game level is shown). See [`PreloadManifest`] docs for more info.
+
+use crate::{
+    core::futures::future::join_all, state::LoadError, state::ResourceState, UntypedResource,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Extension of preload manifest files.
+pub const MANIFEST_EXTENSION: &str = "manifest";
+
+/// Priority of preloading requests. Requests with higher priority are started first.
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum LoadPriority {
+    /// Requests are started only when there's no queued requests of higher priorities. Use this
+    /// priority for resources, that will be needed eventually, but not right away.
+    Low,
+    /// Requests are queued and started gradually, so they do not occupy every loading thread.
+    #[default]
+    Normal,
+    /// Requests are started immediately, just like normal [`crate::manager::ResourceManager::request`]
+    /// calls.
+    High,
+}
+
+/// Preload manifest is a list of resources, that should be loaded in advance, so they're ready
+/// when they are actually needed. For example, there could be a manifest for every game level,
+/// that lists every texture, model and sound of the level, and the game shows a loading screen
+/// while the resources are loading. Manifests are stored in RON format:
+///
+/// ```text
+/// (
+///     priority: High,
+///     group: Some("Level1"),
+///     resources: [
+///         "data/textures/wall.png",
+///         "data/models/door.rgs",
+///     ],
+/// )
+/// ```
+///
+/// Use [`crate::manager::ResourceManager::preload_manifest`] to load a manifest and start loading
+/// the resources listed in it.
+///
+/// Queued (not yet started) requests are started by [`crate::manager::ResourceManagerState::update`],
+/// a few at a time (see [`crate::manager::ResourceManagerState::set_max_concurrent_preloads`]). If a
+/// queued resource is requested explicitly, its loading starts immediately.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreloadManifest {
+    /// Priority of every request of the manifest.
+    #[serde(default)]
+    pub priority: LoadPriority,
+    /// Name of a resource group, that will contain every resource of the manifest (if any). See
+    /// [`crate::manager::ResourceManager::request_in_group`] for more info about groups.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Paths of the resources, that should be preloaded.
+    pub resources: Vec<PathBuf>,
+}
+
+impl PreloadManifest {
+    /// Tries to read a manifest from the given bytes in RON format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadError> {
+        ron::de::from_bytes(bytes).map_err(LoadError::new)
+    }
+
+    /// Writes the manifest in RON format.
+    pub fn to_string_pretty(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+}
+
+/// A set of resources of a preload manifest, that are being loaded. It could be used to show
+/// progress on a loading screen or to wait until every resource is loaded. The resources are kept
+/// alive while the request exists.
+#[derive(Default, Debug, Clone)]
+pub struct PreloadRequest {
+    resources: Vec<UntypedResource>,
+}
+
+impl PreloadRequest {
+    pub(crate) fn new(resources: Vec<UntypedResource>) -> Self {
+        Self { resources }
+    }
+
+    /// Returns a list of every resource of the request.
+    pub fn resources(&self) -> &[UntypedResource] {
+        &self.resources
+    }
+
+    /// Returns the amount of resources, that are loaded (or failed to load).
+    pub fn count_finished(&self) -> usize {
+        self.resources
+            .iter()
+            .filter(|resource| !matches!(*resource.lock(), ResourceState::Pending { .. }))
+            .count()
+    }
+
+    /// Returns the amount of resources, that are failed to load.
+    pub fn count_failed(&self) -> usize {
+        self.resources
+            .iter()
+            .filter(|resource| matches!(*resource.lock(), ResourceState::LoadError { .. }))
+            .count()
+    }
+
+    /// Returns loading progress in `[0.0; 1.0]` range.
+    pub fn progress(&self) -> f32 {
+        if self.resources.is_empty() {
+            1.0
+        } else {
+            self.count_finished() as f32 / self.resources.len() as f32
+        }
+    }
+
+    /// Returns `true` if every resource is loaded (or failed to load).
+    pub fn is_finished(&self) -> bool {
+        self.count_finished() == self.resources.len()
+    }
+
+    /// Waits until every resource is loaded (or failed to load). Returns a list of load results in
+    /// the same order as the resources were listed in the manifest. Keep in mind, that queued
+    /// requests are started by [`crate::manager::ResourceManagerState::update`], which must be
+    /// called while waiting (the engine does it every frame).
+    pub async fn wait(&self) -> Vec<Result<UntypedResource, LoadError>> {
+        join_all(self.resources.iter().cloned()).await
+    }
+}
@@ -0,0 +1,200 @@
+//! Resource format versioning. Every resource that is stored using [`Visitor`] could write its format
+//! version into the file (see [`write_version`]). When the format of a resource changes, the version
+//! should be bumped and a migration function should be registered in [`ResourceMigrationRegistry`],
+//! so files of older versions will be upgraded automatically on load.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use fyrox_resource::{core::uuid::uuid, migration::ResourceMigrationRegistry};
+//!
+//! let registry = ResourceMigrationRegistry::new();
+//! // Version 1 of the format has renamed `Speed` field into `MaxSpeed`.
+//! registry.add(
+//!     uuid!("6a2b3c4d-1e2f-4a5b-8c6d-7e8f9a0b1c2d"),
+//!     0,
+//!     |visitor| {
+//!         let mut region = visitor.enter_region("Vehicle")?;
+//!         region.rename_field("Speed", "MaxSpeed");
+//!         Ok(())
+//!     },
+//! );
+//! ```
+
+use crate::core::{
+    parking_lot::Mutex,
+    uuid::Uuid,
+    visitor::{Visit, VisitError, VisitResult, Visitor},
+};
+use fxhash::FxHashMap;
+
+/// Name of a field in the root region of a file, that holds format version of a resource.
+pub const RESOURCE_VERSION_FIELD: &str = "ResourceVersion";
+
+/// A boxed migration function, that upgrades data of a resource from one version to the next one.
+/// The visitor is in reading mode and it is positioned at the root region.
+pub type ResourceMigration = Box<dyn Fn(&mut Visitor) -> VisitResult + Send>;
+
+/// Writes format version into the root region of the given visitor. It must be called before
+/// saving a resource.
+pub fn write_version(visitor: &mut Visitor, mut version: u32) -> VisitResult {
+    version.visit(RESOURCE_VERSION_FIELD, visitor)
+}
+
+/// Reads format version from the root region of the given visitor. Files without the version field
+/// are treated as version `0`.
+pub fn read_version(visitor: &mut Visitor) -> u32 {
+    let mut version = 0u32;
+    if visitor.has_field(RESOURCE_VERSION_FIELD) {
+        let _ = version.visit(RESOURCE_VERSION_FIELD, visitor);
+    }
+    version
+}
+
+/// A container of migration functions for every resource type. Migrations are stored per type
+/// UUID, every migration upgrades data from some version to the next one, so a file of any older
+/// version could be upgraded by running a chain of migrations.
+#[derive(Default)]
+pub struct ResourceMigrationRegistry {
+    /// Map of `Type UUID -> (Version -> Migration)`
+    pub map: Mutex<FxHashMap<Uuid, FxHashMap<u32, ResourceMigration>>>,
+}
+
+impl ResourceMigrationRegistry {
+    /// Creates an empty migration registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a migration function, that upgrades data of a resource with the given type UUID
+    /// from `from_version` to `from_version + 1`. Replaces previous migration for the version (if
+    /// any).
+    pub fn add<F>(&self, type_uuid: Uuid, from_version: u32, migration: F)
+    where
+        F: Fn(&mut Visitor) -> VisitResult + Send + 'static,
+    {
+        self.map
+            .lock()
+            .entry(type_uuid)
+            .or_default()
+            .insert(from_version, Box::new(migration));
+    }
+
+    /// Returns `true` if there's a migration of the given type from the given version.
+    pub fn has_migration(&self, type_uuid: Uuid, from_version: u32) -> bool {
+        self.map
+            .lock()
+            .get(&type_uuid)
+            .is_some_and(|migrations| migrations.contains_key(&from_version))
+    }
+
+    /// Upgrades data of a resource with the given type UUID to the given version. It must be called
+    /// right after the data was loaded into the visitor and before visiting the resource itself.
+    /// Returns the version of the data before migration.
+    ///
+    /// # Important notes
+    ///
+    /// The registry is locked while the migrations are running, so migration functions must not
+    /// use the registry.
+    pub fn migrate(
+        &self,
+        type_uuid: Uuid,
+        current_version: u32,
+        visitor: &mut Visitor,
+    ) -> Result<u32, VisitError> {
+        let file_version = read_version(visitor);
+        if file_version > current_version {
+            return Err(VisitError::User(format!(
+                "Resource of type {type_uuid} has version {file_version}, which is newer than \
+                the supported version {current_version}. Update the engine to load it."
+            )));
+        }
+
+        let map = self.map.lock();
+        for version in file_version..current_version {
+            let migration = map
+                .get(&type_uuid)
+                .and_then(|migrations| migrations.get(&version))
+                .ok_or_else(|| {
+                    VisitError::User(format!(
+                        "Unable to upgrade resource of type {type_uuid} from version {version} \
+                        to version {}, there's no migration registered.",
+                        version + 1
+                    ))
+                })?;
+            migration(visitor).map_err(|err| {
+                VisitError::User(format!(
+                    "Failed to upgrade resource of type {type_uuid} from version {version} to \
+                    version {}. Reason: {err}",
+                    version + 1
+                ))
+            })?;
+        }
+
+        Ok(file_version)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::uuid::uuid;
+
+    const TYPE: Uuid = uuid!("0b6e9f4a-2c17-4d38-9e51-a7c3f0d2b846");
+
+    fn old_file() -> Visitor {
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Data").unwrap();
+            let mut speed = 10.0f32;
+            speed.visit("Speed", &mut region).unwrap();
+        }
+        Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn resource_migration_chain() {
+        let registry = ResourceMigrationRegistry::new();
+        registry.add(TYPE, 0, |visitor| {
+            let mut region = visitor.enter_region("Data")?;
+            assert!(region.rename_field("Speed", "MaxSpeed"));
+            Ok(())
+        });
+        registry.add(TYPE, 1, |visitor| {
+            let mut region = visitor.enter_region("Data")?;
+            region.insert_field("Acceleration", &mut 2.0f32)
+        });
+
+        let mut visitor = old_file();
+        assert_eq!(registry.migrate(TYPE, 2, &mut visitor).unwrap(), 0);
+
+        let mut region = visitor.enter_region("Data").unwrap();
+        let mut max_speed = 0.0f32;
+        max_speed.visit("MaxSpeed", &mut region).unwrap();
+        let mut acceleration = 0.0f32;
+        acceleration.visit("Acceleration", &mut region).unwrap();
+        assert_eq!((max_speed, acceleration), (10.0, 2.0));
+        assert!(!region.has_field("Speed"));
+    }
+
+    #[test]
+    fn resource_migration_errors() {
+        let registry = ResourceMigrationRegistry::new();
+
+        // Nothing to do for up-to-date files.
+        let mut visitor = old_file();
+        assert_eq!(registry.migrate(TYPE, 0, &mut visitor).unwrap(), 0);
+
+        // Missing migration.
+        let mut visitor = old_file();
+        assert!(registry.migrate(TYPE, 1, &mut visitor).is_err());
+
+        // Files from the future.
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, 3).unwrap();
+        let mut visitor =
+            Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+        assert_eq!(read_version(&mut visitor), 3);
+        assert!(registry.migrate(TYPE, 2, &mut visitor).is_err());
+    }
+}
@@ -1,12 +1,18 @@
-//! Resource import options common traits.
+//! Resource import options common traits and project-wide default import options (see
+//! [`register_default_import_options`]).
 
 use crate::{
-    core::{append_extension, log::Log, reflect::Reflect},
-    io::ResourceIo,
+    core::{append_extension, log::Log, parking_lot::RwLock, reflect::Reflect},
+    io::{write_atomically, ResourceIo},
 };
 use ron::ser::PrettyConfig;
 use serde::{de::DeserializeOwned, Serialize};
-use std::{any::Any, fs::File, path::Path};
+use std::{
+    any::Any,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 /// Extension of import options file.
 pub const OPTIONS_EXTENSION: &str = "options";
@@ -26,14 +32,16 @@ pub trait BaseImportOptions: Reflect {
 pub trait ImportOptions:
     BaseImportOptions + Serialize + DeserializeOwned + Default + Clone
 {
-    /// Saves import options into a specified file.
+    /// Saves import options into a specified file. Use
+    /// [`crate::manager::ResourceManager::save_import_options`] to respect read-only mode of the
+    /// resource manager.
     fn save_internal(&self, path: &Path) -> bool {
-        if let Ok(file) = File::create(path) {
-            if ron::ser::to_writer_pretty(file, self, PrettyConfig::default()).is_ok() {
-                return true;
-            }
-        }
-        false
+        write_atomically(path, |path| {
+            let file = File::create(path)?;
+            ron::ser::to_writer_pretty(file, self, PrettyConfig::default())
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))
+        })
+        .is_ok()
     }
 }
 
@@ -54,14 +62,125 @@ where
     }
 }
 
-/// Tries to load import settings for a resource. It is not part of ImportOptions trait because
-/// `async fn` is not yet supported for traits.
+struct DefaultImportOptionsEntry {
+    extension: String,
+    folder: Option<PathBuf>,
+    options: Box<dyn Any + Send + Sync>,
+    clone_opaque: fn(&dyn Any) -> Option<Box<dyn BaseImportOptions>>,
+}
+
+impl DefaultImportOptionsEntry {
+    fn matches(&self, resource_path: &Path) -> bool {
+        resource_path.extension().is_some_and(|extension| {
+            extension
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&self.extension)
+        }) && self
+            .folder
+            .as_ref()
+            .map_or(true, |folder| resource_path.starts_with(folder))
+    }
+
+    fn specificity(&self) -> usize {
+        self.folder
+            .as_ref()
+            .map_or(0, |folder| folder.components().count() + 1)
+    }
+}
+
+fn default_import_options_registry() -> &'static RwLock<Vec<DefaultImportOptionsEntry>> {
+    static REGISTRY: OnceLock<RwLock<Vec<DefaultImportOptionsEntry>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Registers default import options for every resource with the given extension, optionally
+/// limited to resources in the given folder (and its sub-folders). The options are used when a
+/// resource has no import options file, instead of the defaults of its loader. For example, every
+/// `.png` texture in `data/ui` folder could be imported without mip-maps, while the rest of the
+/// textures use the defaults of the texture loader. When multiple registered options match a
+/// resource, the ones with the most specific folder are used. Registering options for the same
+/// extension, folder and type replaces the previous options.
+pub fn register_default_import_options<T>(extension: &str, folder: Option<&Path>, options: T)
+where
+    T: ImportOptions + Send + Sync,
+{
+    let mut registry = default_import_options_registry().write();
+    registry.retain(|entry| {
+        !(entry.extension.eq_ignore_ascii_case(extension)
+            && entry.folder.as_deref() == folder
+            && entry.options.is::<T>())
+    });
+    registry.push(DefaultImportOptionsEntry {
+        extension: extension.to_string(),
+        folder: folder.map(|folder| folder.to_path_buf()),
+        options: Box::new(options),
+        clone_opaque: |options| {
+            options
+                .downcast_ref::<T>()
+                .map(|options| Box::new(options.clone()) as Box<dyn BaseImportOptions>)
+        },
+    });
+}
+
+/// Removes default import options, that were registered using [`register_default_import_options`]
+/// with the same extension, folder and type. Returns the removed options (if any).
+pub fn unregister_default_import_options<T>(extension: &str, folder: Option<&Path>) -> Option<T>
+where
+    T: ImportOptions + Send + Sync,
+{
+    let mut registry = default_import_options_registry().write();
+    let index = registry.iter().position(|entry| {
+        entry.extension.eq_ignore_ascii_case(extension)
+            && entry.folder.as_deref() == folder
+            && entry.options.is::<T>()
+    })?;
+    registry
+        .remove(index)
+        .options
+        .downcast::<T>()
+        .ok()
+        .map(|options| *options)
+}
+
+/// Returns registered default import options of the given type (see [`register_default_import_options`])
+/// for a resource at the given path.
+pub fn default_import_options<T>(resource_path: &Path) -> Option<T>
+where
+    T: ImportOptions,
+{
+    default_import_options_registry()
+        .read()
+        .iter()
+        .filter(|entry| entry.options.is::<T>() && entry.matches(resource_path))
+        .max_by_key(|entry| entry.specificity())
+        .and_then(|entry| entry.options.downcast_ref::<T>().cloned())
+}
+
+/// Same as [`default_import_options`], but returns opaque import options of any type.
+pub fn default_import_options_opaque(resource_path: &Path) -> Option<Box<dyn BaseImportOptions>> {
+    default_import_options_registry()
+        .read()
+        .iter()
+        .filter(|entry| entry.matches(resource_path))
+        .max_by_key(|entry| entry.specificity())
+        .and_then(|entry| (entry.clone_opaque)(&*entry.options))
+}
+
+/// Tries to load import settings for a resource. If there's no import options file for the resource,
+/// registered default import options are used (see [`register_default_import_options`]). It is not
+/// part of ImportOptions trait because `async fn` is not yet supported for traits.
 pub async fn try_get_import_settings<T>(resource_path: &Path, io: &dyn ResourceIo) -> Option<T>
 where
     T: ImportOptions,
 {
     let settings_path = append_extension(resource_path, OPTIONS_EXTENSION);
 
+    if !io.exists(&settings_path).await {
+        if let Some(options) = default_import_options::<T>(resource_path) {
+            return Some(options);
+        }
+    }
+
     match io.load_file(settings_path.as_ref()).await {
         Ok(bytes) => match ron::de::from_bytes::<T>(&bytes) {
             Ok(options) => Some(options),
@@ -101,3 +220,85 @@ where
         .await
         .map(|options| Box::new(options) as Box<dyn BaseImportOptions>)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{futures::executor::block_on, reflect::prelude::*},
+        io::memory::MemoryResourceIo,
+    };
+    use serde::Deserialize;
+
+    #[derive(Reflect, Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+    struct TestImportOptions {
+        mip_maps: bool,
+    }
+
+    impl ImportOptions for TestImportOptions {}
+
+    #[test]
+    fn default_import_options_per_extension() {
+        let io = MemoryResourceIo::new();
+        io.add_file(
+            "data/ui/button.tstopt.options",
+            ron::ser::to_string(&TestImportOptions { mip_maps: true })
+                .unwrap()
+                .into_bytes(),
+        );
+
+        assert_eq!(
+            block_on(try_get_import_settings::<TestImportOptions>(
+                Path::new("data/foo.tstopt"),
+                &io
+            )),
+            None
+        );
+
+        register_default_import_options("tstopt", None, TestImportOptions { mip_maps: true });
+        register_default_import_options(
+            "tstopt",
+            Some(Path::new("data/ui")),
+            TestImportOptions { mip_maps: false },
+        );
+
+        let get = |path: &str| {
+            block_on(try_get_import_settings::<TestImportOptions>(
+                Path::new(path),
+                &io,
+            ))
+        };
+
+        // Generic extension rule.
+        assert_eq!(
+            get("data/foo.TSTOPT"),
+            Some(TestImportOptions { mip_maps: true })
+        );
+        // Folder-specific rule has higher priority.
+        assert_eq!(
+            get("data/ui/icons/close.tstopt"),
+            Some(TestImportOptions { mip_maps: false })
+        );
+        // Existing options file has the highest priority.
+        assert_eq!(
+            get("data/ui/button.tstopt"),
+            Some(TestImportOptions { mip_maps: true })
+        );
+        assert!(default_import_options_opaque(Path::new("data/ui/close.tstopt")).is_some());
+        assert_eq!(get("data/ui/close.other"), None);
+
+        assert_eq!(
+            unregister_default_import_options::<TestImportOptions>(
+                "tstopt",
+                Some(Path::new("data/ui"))
+            ),
+            Some(TestImportOptions { mip_maps: false })
+        );
+        assert_eq!(
+            get("data/ui/icons/close.tstopt"),
+            Some(TestImportOptions { mip_maps: true })
+        );
+        unregister_default_import_options::<TestImportOptions>("tstopt", None);
+        assert_eq!(get("data/foo.tstopt"), None);
+    }
+}
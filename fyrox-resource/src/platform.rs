@@ -0,0 +1,244 @@
+//! Platform-specific resource variants. See [`PlatformSettings`] docs for more info.
+
+use crate::{
+    core::io::FileLoadError,
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Platform settings of a resource manager. When a platform tag is set, every resource is loaded
+/// from its platform-specific variant (if there's one), while the path of the resource stays the
+/// same. It allows to keep a single logical path (for example `textures/rock.png`) in scenes and
+/// other resources, and to ship platform-optimized data for it. A variant is a file with the
+/// platform tag before the extension, the extension of the variant could differ from the
+/// extension of the logical path, but it must be supported by a loader of the resource. For
+/// `textures/rock.png` and `mobile` tag, the candidates are:
+///
+/// 1. `textures/rock.mobile.png`
+/// 2. `textures/rock.mobile.<ext>` - for every other extension, supported by the loaders of `png`
+///    files (`textures/rock.mobile.dds`, for example).
+///
+/// The original file is used if there's no variant. Platform variants take precedence over
+/// localized variants (see [`crate::locale::LocaleSettings`]). See
+/// [`crate::manager::ResourceManager::set_platform_settings`] for the way to change the platform tag.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlatformSettings {
+    /// Active platform tag (for example `desktop`, `mobile` or `web`). `None` disables platform
+    /// variants.
+    pub tag: Option<String>,
+}
+
+impl PlatformSettings {
+    /// Creates new settings with the given platform tag.
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: Some(tag.into()),
+        }
+    }
+
+    /// Creates new settings with the tag of the current target platform: `mobile` for Android and
+    /// iOS, `web` for WebAssembly and `desktop` for everything else.
+    pub fn current() -> Self {
+        let tag = if cfg!(any(target_os = "android", target_os = "ios")) {
+            "mobile"
+        } else if cfg!(target_arch = "wasm32") {
+            "web"
+        } else {
+            "desktop"
+        };
+        Self::new(tag)
+    }
+
+    /// Returns `true` if there's an active platform tag.
+    pub fn is_enabled(&self) -> bool {
+        self.tag.is_some()
+    }
+
+    /// Returns a list of paths of possible platform variants of the given path, in the order in
+    /// which they should be checked. `extensions` is a list of extensions, that could be used by
+    /// the variants in addition to the extension of the path itself.
+    pub fn variant_candidates(&self, path: &Path, extensions: &[String]) -> Vec<PathBuf> {
+        let (Some(tag), Some(stem)) = (self.tag.as_deref(), path.file_stem()) else {
+            return Vec::new();
+        };
+
+        let own_extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string());
+        let mut candidate_extensions = Vec::new();
+        for extension in own_extension.iter().chain(extensions) {
+            if !candidate_extensions
+                .iter()
+                .any(|e: &&String| e.eq_ignore_ascii_case(extension))
+            {
+                candidate_extensions.push(extension);
+            }
+        }
+
+        candidate_extensions
+            .into_iter()
+            .map(|extension| {
+                let mut file_name = stem.to_os_string();
+                file_name.push(".");
+                file_name.push(tag);
+                file_name.push(".");
+                file_name.push(extension);
+                path.with_file_name(file_name)
+            })
+            .collect()
+    }
+
+    /// Searches for the first existing platform variant of the given path. Returns `None` if
+    /// there's no variant.
+    pub async fn resolve(
+        &self,
+        path: &Path,
+        extensions: &[String],
+        io: &dyn ResourceIo,
+    ) -> Option<PathBuf> {
+        for candidate in self.variant_candidates(path, extensions) {
+            if io.is_file(&candidate).await {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+/// Resource IO, that substitutes the content of the file at the given path with the content of its
+/// platform variant.
+pub(crate) struct PlatformResourceIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) path: PathBuf,
+    pub(crate) extensions: Vec<String>,
+    pub(crate) settings: PlatformSettings,
+}
+
+impl PlatformResourceIo {
+    async fn actual_path(&self, path: &Path) -> PathBuf {
+        if path == self.path {
+            if let Some(variant) = self
+                .settings
+                .resolve(path, &self.extensions, &*self.inner)
+                .await
+            {
+                return variant;
+            }
+        }
+        path.to_path_buf()
+    }
+}
+
+impl ResourceIo for PlatformResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.load_file(&path).await
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        self.inner.move_file(source, dest)
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        self.inner.canonicalize_path(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        self.inner.walk_directory(path)
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.file_reader(&path).await
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        Box::pin(async move {
+            let path = self.actual_path(path).await;
+            self.inner.file_stamp(&path).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::executor::block_on, io::memory::MemoryResourceIo};
+
+    #[test]
+    fn platform_variant_candidates() {
+        let extensions = ["png".to_string(), "dds".to_string()];
+        assert!(PlatformSettings::default()
+            .variant_candidates(Path::new("textures/rock.png"), &extensions)
+            .is_empty());
+
+        assert_eq!(
+            PlatformSettings::new("mobile")
+                .variant_candidates(Path::new("textures/rock.png"), &extensions),
+            ["textures/rock.mobile.png", "textures/rock.mobile.dds"].map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn platform_resource_io() {
+        let io = Arc::new(MemoryResourceIo::new());
+        io.add_file(Path::new("rock.png"), vec![0]);
+        io.add_file(Path::new("rock.desktop.dds"), vec![1]);
+        io.add_file(Path::new("rock.mobile.png"), vec![2]);
+
+        let load = |settings: PlatformSettings| {
+            let io = PlatformResourceIo {
+                inner: io.clone(),
+                path: "rock.png".into(),
+                extensions: vec!["png".to_string(), "dds".to_string()],
+                settings,
+            };
+            block_on(io.load_file(Path::new("rock.png"))).unwrap()
+        };
+
+        assert_eq!(load(PlatformSettings::new("desktop")), [1]);
+        assert_eq!(load(PlatformSettings::new("mobile")), [2]);
+        assert_eq!(load(PlatformSettings::new("web")), [0]);
+    }
+}
@@ -0,0 +1,219 @@
+//! Speculative background reading of resource files. See [`PrefetchSettings`] docs for more info.
+
+use crate::{core::parking_lot::Mutex, key::ResourcePathKey};
+use fxhash::FxHashMap;
+use std::{collections::VecDeque, path::PathBuf, sync::Arc};
+
+/// Settings of resource prefetching. Prefetching reads files of resources, that will probably be
+/// needed soon, in background and keeps their content in memory, so a later request of such a
+/// resource does not wait for IO (which is the slowest part of loading on slow storage, or when
+/// the data is downloaded). Unlike preloading (see [`crate::manifest::PreloadManifest`]), prefetching
+/// does not decode resources and never competes with regular loading:
+///
+/// - Files are read one at a time, and the reading is throttled to
+///   [`Self::max_bytes_per_second`].
+/// - Reading is paused while there are resources being loaded, if
+///   [`Self::pause_while_loading`] is set.
+/// - The total size of the prefetched data is limited by [`Self::max_cache_size`], the oldest data
+///   is dropped first.
+///
+/// See [`crate::manager::ResourceManagerState::prefetch`] for the way to prefetch a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrefetchSettings {
+    /// Maximum throughput of prefetching in bytes per second. `None` means no limit. Default is
+    /// 4 MiB per second.
+    pub max_bytes_per_second: Option<usize>,
+    /// Maximum total size of prefetched data (in bytes). Default is 64 MiB.
+    pub max_cache_size: usize,
+    /// Whether to pause prefetching while there are resources being loaded. Default is `true`.
+    pub pause_while_loading: bool,
+}
+
+impl Default for PrefetchSettings {
+    fn default() -> Self {
+        Self {
+            max_bytes_per_second: Some(4 * 1024 * 1024),
+            max_cache_size: 64 * 1024 * 1024,
+            pause_while_loading: true,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PrefetchCache {
+    files: FxHashMap<ResourcePathKey, Vec<u8>>,
+    order: VecDeque<ResourcePathKey>,
+    size: usize,
+    in_flight: bool,
+    // Bytes read since the last update, they're subtracted from the throughput budget.
+    consumed: usize,
+}
+
+impl PrefetchCache {
+    fn remove(&mut self, key: &ResourcePathKey) -> Option<Vec<u8>> {
+        let data = self.files.remove(key)?;
+        self.order.retain(|k| k != key);
+        self.size -= data.len();
+        Some(data)
+    }
+}
+
+/// A handle, that is used by a prefetching task to put the read data in the cache.
+pub(crate) struct PrefetchCompletion {
+    cache: Arc<Mutex<PrefetchCache>>,
+    key: ResourcePathKey,
+    max_cache_size: usize,
+}
+
+impl PrefetchCompletion {
+    /// Stores the content of the prefetched file, `None` means that the file could not be read.
+    pub(crate) fn complete(self, data: Option<Vec<u8>>) {
+        let mut cache = self.cache.lock();
+        cache.in_flight = false;
+        let Some(data) = data else {
+            return;
+        };
+        cache.consumed += data.len();
+        if data.len() > self.max_cache_size {
+            return;
+        }
+        cache.remove(&self.key);
+        cache.size += data.len();
+        cache.files.insert(self.key.clone(), data);
+        cache.order.push_back(self.key);
+        while cache.size > self.max_cache_size {
+            let Some(oldest) = cache.order.front().cloned() else {
+                break;
+            };
+            cache.remove(&oldest);
+        }
+    }
+}
+
+/// A queue of files to prefetch and the prefetched data.
+#[derive(Default)]
+pub(crate) struct Prefetcher {
+    pub(crate) settings: PrefetchSettings,
+    queue: VecDeque<(ResourcePathKey, PathBuf)>,
+    cache: Arc<Mutex<PrefetchCache>>,
+    budget: f64,
+}
+
+impl Prefetcher {
+    pub(crate) fn enqueue(&mut self, key: ResourcePathKey, path: PathBuf) {
+        if !self.contains(&key) && !self.queue.iter().any(|(k, _)| k == &key) {
+            self.queue.push_back((key, path));
+        }
+    }
+
+    pub(crate) fn contains(&self, key: &ResourcePathKey) -> bool {
+        self.cache.lock().files.contains_key(key)
+    }
+
+    pub(crate) fn take(&self, key: &ResourcePathKey) -> Option<Vec<u8>> {
+        self.cache.lock().remove(key)
+    }
+
+    pub(crate) fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub(crate) fn cached_size(&self) -> usize {
+        self.cache.lock().size
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.queue.clear();
+        self.clear_cache();
+    }
+
+    pub(crate) fn clear_cache(&mut self) {
+        let mut cache = self.cache.lock();
+        cache.files.clear();
+        cache.order.clear();
+        cache.size = 0;
+    }
+
+    /// Updates the throughput budget and returns the next file to read, if prefetching could
+    /// continue.
+    pub(crate) fn update(
+        &mut self,
+        dt: f32,
+        is_loading: bool,
+    ) -> Option<(PathBuf, PrefetchCompletion)> {
+        let mut cache = self.cache.lock();
+        match self.settings.max_bytes_per_second {
+            Some(rate) => {
+                let rate = rate as f64;
+                self.budget = (self.budget + rate * dt as f64 - cache.consumed as f64).min(rate);
+            }
+            None => self.budget = 0.0,
+        }
+        cache.consumed = 0;
+
+        let throttled = self.settings.max_bytes_per_second.is_some() && self.budget <= 0.0;
+        if cache.in_flight || throttled || (is_loading && self.settings.pause_while_loading) {
+            return None;
+        }
+
+        let (key, path) = self.queue.pop_front()?;
+        cache.in_flight = true;
+        Some((
+            path,
+            PrefetchCompletion {
+                cache: self.cache.clone(),
+                key,
+                max_cache_size: self.settings.max_cache_size,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::Path;
+
+    fn key(path: &str) -> ResourcePathKey {
+        ResourcePathKey::new(Path::new(path), false)
+    }
+
+    #[test]
+    fn prefetcher_throttling() {
+        let mut prefetcher = Prefetcher {
+            settings: PrefetchSettings {
+                max_bytes_per_second: Some(100),
+                max_cache_size: 250,
+                pause_while_loading: true,
+            },
+            ..Default::default()
+        };
+        prefetcher.enqueue(key("a"), "a".into());
+        prefetcher.enqueue(key("a"), "a".into());
+        prefetcher.enqueue(key("b"), "b".into());
+        prefetcher.enqueue(key("c"), "c".into());
+        assert_eq!(prefetcher.queued(), 3);
+
+        // Foreground loading pauses prefetching.
+        assert!(prefetcher.update(0.5, true).is_none());
+
+        let (path, completion) = prefetcher.update(0.5, false).unwrap();
+        assert_eq!(path, Path::new("a"));
+        // Only one file is read at a time.
+        assert!(prefetcher.update(0.5, false).is_none());
+        completion.complete(Some(vec![0; 200]));
+        assert!(prefetcher.contains(&key("a")));
+
+        // 200 bytes were read, so the budget must be refilled first.
+        assert!(prefetcher.update(0.5, false).is_none());
+        let (path, completion) = prefetcher.update(1.0, false).unwrap();
+        assert_eq!(path, Path::new("b"));
+        completion.complete(Some(vec![0; 100]));
+
+        // The oldest data is dropped when the cache is full.
+        assert!(!prefetcher.contains(&key("a")));
+        assert_eq!(prefetcher.cached_size(), 100);
+        assert_eq!(prefetcher.take(&key("b")), Some(vec![0; 100]));
+        assert_eq!(prefetcher.cached_size(), 0);
+    }
+}
@@ -0,0 +1,352 @@
+//! Read-only protection of resources. See [`ReadOnlyResources`] docs for more info.
+
+use crate::{
+    core::parking_lot::Mutex, key::ResourcePathKey, state::ResourceState, untyped::UntypedResource,
+};
+use fxhash::FxHashMap;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock, Weak},
+};
+
+/// An error, that is returned on an attempt to modify or to save a read-only resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOnlyError {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// `true` if the error is caused by the read-only mode of a resource manager (see
+    /// [`crate::manager::ResourceManagerState::set_read_only`]), rather than by protection of the
+    /// resource itself.
+    pub read_only_mode: bool,
+}
+
+impl Display for ReadOnlyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.read_only_mode {
+            write!(
+                f,
+                "Unable to write {}, because resources are in read-only mode.",
+                self.path.display()
+            )
+        } else {
+            write!(
+                f,
+                "Resource {} is read-only and cannot be modified or saved.",
+                self.path.display()
+            )
+        }
+    }
+}
+
+impl Error for ReadOnlyError {}
+
+/// A set of resources, that are protected from modification. A resource could be marked read-only
+/// individually (see [`Self::protect`]) or by its location - every resource in a read-only root
+/// folder (see [`Self::add_root`]) is read-only too. It is useful to protect shared built-in assets
+/// of the engine or DLC content from accidental changes.
+///
+/// Every resource manager has its own set (see
+/// [`crate::manager::ResourceManagerState::read_only_resources_mut`]), so the protection of one
+/// manager does not affect the resources of another one. The protection is checked by the APIs, that
+/// have access to the manager: [`crate::manager::ResourceManagerState::check_writable`],
+/// [`crate::manager::ResourceManager::save_resource`],
+/// [`crate::manager::ResourceManager::save_all_modified`], [`crate::Resource::save_async`],
+/// [`UntypedResource::replace_data`] and the renaming and moving of resources return errors or skip
+/// read-only resources.
+///
+/// The manager also sets the read-only flag of its protected resources (see
+/// [`UntypedResource::is_read_only`]), so the resources enforce the protection on their own:
+/// [`crate::ResourceDataRef::try_data_mut`], [`crate::ResourceDataRef::try_save`] and the default
+/// implementation of [`crate::ResourceData::save`] return [`ReadOnlyError`] for flagged resources,
+/// `DerefMut` of [`crate::Resource::data_ref`] panics. Tools, that modify shared content, should use
+/// the methods, that return errors. Resources in a read-only root are flagged when they are
+/// registered in the manager, call [`crate::manager::ResourceManagerState::sync_read_only_flags`]
+/// after the roots of a manager with registered resources are changed.
+///
+/// ```rust
+/// # use fyrox_resource::{manager::ResourceManager, untyped::UntypedResource};
+/// let resource_manager = ResourceManager::new();
+/// let mut state = resource_manager.state();
+/// state.read_only_resources_mut().add_root("data/dlc");
+/// assert!(state
+///     .read_only_resources()
+///     .is_path_read_only("data/dlc/level.rgs".as_ref()));
+///
+/// let resource = UntypedResource::default();
+/// state.read_only_resources_mut().protect(&resource);
+/// assert!(state.check_writable(&resource).is_err());
+/// assert!(resource.is_read_only());
+/// ```
+///
+/// To forbid saving of every resource of a resource manager, use its read-only mode instead (see
+/// [`crate::manager::ResourceManagerState::set_read_only`]).
+#[derive(Debug)]
+pub struct ReadOnlyResources {
+    resources: FxHashMap<usize, Weak<Mutex<ResourceState>>>,
+    roots: Vec<PathBuf>,
+    case_insensitive_paths: bool,
+}
+
+// Not derivable on Windows, where paths are case-insensitive by default.
+#[allow(clippy::derivable_impls)]
+impl Default for ReadOnlyResources {
+    fn default() -> Self {
+        Self {
+            resources: Default::default(),
+            roots: Default::default(),
+            case_insensitive_paths: cfg!(windows),
+        }
+    }
+}
+
+impl ReadOnlyResources {
+    /// Marks the resource read-only and sets its read-only flag (see
+    /// [`UntypedResource::is_read_only`]). The resource is not held by the set, it is removed from
+    /// the set automatically when it is destroyed.
+    pub fn protect(&mut self, resource: &UntypedResource) {
+        self.resources.retain(|_, weak| weak.strong_count() > 0);
+        self.resources
+            .insert(resource.key(), Arc::downgrade(&resource.0));
+        set_read_only_flag(resource, true);
+    }
+
+    /// Removes the read-only mark of the resource. Keep in mind, that the resource is still
+    /// read-only (and keeps its read-only flag) if it is located in a read-only root. Returns `true`
+    /// if the resource was marked. This method locks the resource.
+    pub fn unprotect(&mut self, resource: &UntypedResource) -> bool {
+        let removed = self.resources.remove(&resource.key()).is_some();
+        if removed {
+            set_read_only_flag(resource, self.is_path_read_only(&resource.path()));
+        }
+        removed
+    }
+
+    /// Sets whether paths are compared case-insensitively. The resource manager keeps it in sync
+    /// with its own setting, see
+    /// [`crate::manager::ResourceManagerState::set_case_insensitive_paths`].
+    pub fn set_case_insensitive_paths(&mut self, case_insensitive: bool) {
+        self.case_insensitive_paths = case_insensitive;
+    }
+
+    fn path_key(&self, path: &Path) -> ResourcePathKey {
+        ResourcePathKey::new(path, self.case_insensitive_paths)
+    }
+
+    /// Marks every resource in the given folder (and its sub-folders) read-only. The root is compared
+    /// with resource paths, so when [`crate::io::vfs::VirtualFileSystem`] is used, the root should be
+    /// a prefix of a mount point (for example, a prefix of mounted DLC content). Paths are compared
+    /// using their normalized form (see [`ResourcePathKey`]).
+    pub fn add_root(&mut self, root: impl AsRef<Path>) {
+        let key = self.path_key(root.as_ref());
+        if !self.roots.iter().any(|r| key.matches(r)) {
+            self.roots.push(root.as_ref().to_path_buf());
+        }
+    }
+
+    /// Removes the given read-only root. Returns `true` if the root was registered.
+    pub fn remove_root(&mut self, root: impl AsRef<Path>) -> bool {
+        let key = self.path_key(root.as_ref());
+        let count = self.roots.len();
+        self.roots.retain(|r| !key.matches(r));
+        self.roots.len() != count
+    }
+
+    /// Returns a list of read-only roots.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Checks whether the given path is located in one of the read-only roots. Paths are compared
+    /// using their normalized form (see [`ResourcePathKey`]), so `DLC\\Level.rgs` is read-only if
+    /// `dlc` is a read-only root and paths are case-insensitive.
+    pub fn is_path_read_only(&self, path: &Path) -> bool {
+        if self.roots.is_empty() {
+            return false;
+        }
+        let key = self.path_key(path);
+        self.roots
+            .iter()
+            .any(|root| key.starts_with(&self.path_key(root)))
+    }
+
+    /// Checks whether the given resource is read-only. This method locks the resource, use
+    /// [`Self::is_read_only_by_key`] if the resource is locked already.
+    pub fn is_read_only(&self, resource: &UntypedResource) -> bool {
+        self.is_read_only_by_key(resource.key(), &resource.path())
+    }
+
+    /// Checks whether a resource with the given key (see [`UntypedResource::key`]) and the given
+    /// path is read-only.
+    pub fn is_read_only_by_key(&self, key: usize, path: &Path) -> bool {
+        self.resources
+            .get(&key)
+            .is_some_and(|weak| weak.strong_count() > 0)
+            || self.is_path_read_only(path)
+    }
+
+    /// Checks whether a resource with the given key and the given path is read-only and returns
+    /// [`ReadOnlyError`] if so.
+    pub fn check(&self, key: usize, path: &Path) -> Result<(), ReadOnlyError> {
+        if self.is_read_only_by_key(key, path) {
+            Err(ReadOnlyError {
+                path: path.to_path_buf(),
+                read_only_mode: false,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Read-only flags of resources. The state of a resource is shared with the UI as is (see
+// `SharedTexture`), so the flag cannot be stored next to it and lives in this table instead. Weak
+// references keep the memory of destroyed resources, so their keys cannot be reused while they're
+// in the table.
+// The path of a resource is stored with its flag, so the resource data, that doesn't know its
+// resource, could check the flag (see `ResourceData::save`).
+struct ReadOnlyFlag {
+    resource: Weak<Mutex<ResourceState>>,
+    path: PathBuf,
+}
+
+fn read_only_flags() -> &'static Mutex<FxHashMap<usize, ReadOnlyFlag>> {
+    static FLAGS: OnceLock<Mutex<FxHashMap<usize, ReadOnlyFlag>>> = OnceLock::new();
+    FLAGS.get_or_init(Default::default)
+}
+
+/// Sets or removes the read-only flag of the resource. The flag is managed by resource managers,
+/// see [`ReadOnlyResources`] docs for more info. This method locks the resource.
+pub(crate) fn set_read_only_flag(resource: &UntypedResource, read_only: bool) {
+    let path = resource.path();
+    let mut flags = read_only_flags().lock();
+    flags.retain(|_, flag| flag.resource.strong_count() > 0);
+    if read_only {
+        flags.insert(
+            resource.key(),
+            ReadOnlyFlag {
+                resource: Arc::downgrade(&resource.0),
+                path,
+            },
+        );
+    } else {
+        flags.remove(&resource.key());
+    }
+}
+
+/// Returns `true` if a resource with the given key (see [`UntypedResource::key`]) has the
+/// read-only flag.
+pub(crate) fn has_read_only_flag(key: usize) -> bool {
+    read_only_flags()
+        .lock()
+        .get(&key)
+        .is_some_and(|flag| flag.resource.strong_count() > 0)
+}
+
+/// Returns `true` if a resource with the given path has the read-only flag. Embedded resources
+/// have no path, so they're never found by this function.
+pub(crate) fn has_read_only_flag_by_path(path: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && read_only_flags()
+            .lock()
+            .values()
+            .any(|flag| flag.resource.strong_count() > 0 && flag.path == path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+        ResourceData,
+    };
+    use std::any::Any;
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct ProtectedStub;
+
+    impl ResourceData for ProtectedStub {
+        fn path(&self) -> &Path {
+            Path::new("protected_stub.txt")
+        }
+
+        fn set_path(&mut self, _path: PathBuf) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            Uuid::default()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn read_only_resource_data_save() {
+        let mut set = ReadOnlyResources::default();
+        let resource = UntypedResource::new_ok(ProtectedStub);
+        set.protect(&resource);
+
+        let mut state = resource.lock();
+        let ResourceState::Ok(data) = &mut *state else {
+            unreachable!()
+        };
+        let err = data.save(Path::new("foo.txt")).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ReadOnlyError>()
+                .map(|e| e.path.as_path()),
+            Some(Path::new("protected_stub.txt"))
+        );
+    }
+
+    #[test]
+    fn read_only_resources_protection() {
+        let mut set = ReadOnlyResources::default();
+
+        let resource = UntypedResource::default();
+        assert!(!set.is_read_only(&resource));
+        set.protect(&resource);
+        assert!(set.is_read_only(&resource));
+        assert!(set
+            .check(resource.key(), Path::new("foo"))
+            .is_err_and(|e| e.path == Path::new("foo")));
+        assert!(resource.is_read_only());
+        assert!(set.unprotect(&resource));
+        assert!(!set.is_read_only(&resource));
+        assert!(!resource.is_read_only());
+
+        // Destroyed resources must not affect new ones.
+        set.protect(&resource);
+        let key = resource.key();
+        drop(resource);
+        assert!(!set.is_read_only_by_key(key, Path::new("")));
+
+        set.add_root("data/builtin");
+        set.add_root("data/builtin");
+        assert_eq!(set.roots().len(), 1);
+        assert!(set.is_path_read_only(Path::new("data/builtin/shaders/standard.shader")));
+        assert!(!set.is_path_read_only(Path::new("data/builtin_extra/a.png")));
+        assert!(set.remove_root("data/builtin"));
+        assert!(!set.remove_root("data/builtin"));
+
+        // Roots are compared with normalized paths.
+        set.set_case_insensitive_paths(true);
+        set.add_root("./Data\\DLC/");
+        assert!(set.is_path_read_only(Path::new("data/dlc/level.rgs")));
+        assert!(set.is_path_read_only(Path::new("DATA\\dlc\\level.rgs")));
+        assert!(!set.is_path_read_only(Path::new("data/dlc2/level.rgs")));
+        set.set_case_insensitive_paths(false);
+        assert!(!set.is_path_read_only(Path::new("data/dlc/level.rgs")));
+        assert!(set.is_path_read_only(Path::new("Data/DLC/level.rgs")));
+        assert!(set.remove_root("Data/DLC"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Resource usage attribution and quotas. See [`ResourceTagReport`] docs for more info.
+
+use crate::key::ResourcePathKey;
+use fxhash::FxHashMap;
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+/// Optional limits of resource usage of a single tag. See [`ResourceTagReport`] docs for more info.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceQuota {
+    /// Maximum estimated amount of memory (in bytes) used by the resources of the tag. `None` means
+    /// that the memory usage is unlimited.
+    pub max_memory: Option<usize>,
+    /// Maximum total time spent to load the resources of the tag. `None` means that the loading
+    /// time is unlimited.
+    pub max_load_time: Option<Duration>,
+}
+
+impl ResourceQuota {
+    /// Creates a quota, that limits memory usage only.
+    pub fn memory(max_memory: usize) -> Self {
+        Self {
+            max_memory: Some(max_memory),
+            max_load_time: None,
+        }
+    }
+
+    /// Sets the maximum total loading time of the quota.
+    pub fn with_max_load_time(mut self, max_load_time: Duration) -> Self {
+        self.max_load_time = Some(max_load_time);
+        self
+    }
+}
+
+/// Resource usage of a single tag.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceTagUsage {
+    /// Name of the tag.
+    pub tag: String,
+    /// Amount of loaded resources attributed to the tag.
+    pub count: usize,
+    /// Estimated amount of memory (in bytes) used by the resources of the tag. See
+    /// [`crate::ResourceData::memory_usage`] for more info.
+    pub memory: usize,
+    /// Total time spent to load (and reload) the resources of the tag. See
+    /// [`crate::stats::ResourceLoadTiming`] for more info.
+    pub load_time: Duration,
+    /// Quota of the tag.
+    pub quota: ResourceQuota,
+}
+
+impl ResourceTagUsage {
+    /// Returns `true` if the memory usage of the tag exceeds its quota.
+    pub fn is_memory_exceeded(&self) -> bool {
+        self.quota
+            .max_memory
+            .is_some_and(|max_memory| self.memory > max_memory)
+    }
+
+    /// Returns `true` if the loading time of the tag exceeds its quota.
+    pub fn is_load_time_exceeded(&self) -> bool {
+        self.quota
+            .max_load_time
+            .is_some_and(|max_load_time| self.load_time > max_load_time)
+    }
+
+    /// Returns `true` if any limit of the quota of the tag is exceeded.
+    pub fn is_over_quota(&self) -> bool {
+        self.is_memory_exceeded() || self.is_load_time_exceeded()
+    }
+}
+
+impl Display for ResourceTagUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} resource(s), {:.2} MiB",
+            self.tag,
+            self.count,
+            self.memory as f64 / (1024.0 * 1024.0)
+        )?;
+        if let Some(max_memory) = self.quota.max_memory {
+            write!(f, " of {:.2} MiB", max_memory as f64 / (1024.0 * 1024.0))?;
+        }
+        write!(f, ", {:.2} ms", self.load_time.as_secs_f64() * 1000.0)?;
+        if let Some(max_load_time) = self.quota.max_load_time {
+            write!(f, " of {:.2} ms", max_load_time.as_secs_f64() * 1000.0)?;
+        }
+        if self.is_over_quota() {
+            write!(f, " (over quota)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Tag report contains resource usage of every requester tag. A tag is an arbitrary name of a
+/// system, that requests resources - a plugin, a subsystem (terrain, UI, audio), a game level and
+/// so on. Resources are attributed to tags using
+/// [`crate::manager::ResourceManager::request_tagged`] or
+/// [`crate::manager::ResourceManagerState::add_tag`]. Dependencies of a tagged resource (as
+/// reported by its loader) are attributed to the same tag.
+///
+/// A resource could be attributed to any number of tags and it is counted in full in every tag,
+/// so the sum of memory usage of all tags could be larger than the actual memory usage.
+///
+/// Every tag could have optional quota (see [`ResourceQuota`]), the report shows which tags have
+/// exceeded their quotas. Use [`crate::manager::ResourceManagerState::tag_usage_report`] to
+/// create the report.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{manager::ResourceManager, quota::ResourceQuota};
+///
+/// fn setup(resource_manager: &ResourceManager) {
+///     resource_manager
+///         .state()
+///         .set_quota("Terrain", ResourceQuota::memory(512 * 1024 * 1024));
+///     resource_manager.request_untyped_tagged("data/terrain/height_map.png", "Terrain");
+/// }
+///
+/// fn print_usage(resource_manager: &ResourceManager) {
+///     let report = resource_manager.state().tag_usage_report();
+///     println!("{report}");
+///     for usage in report.over_quota() {
+///         println!("{} is over its budget!", usage.tag);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceTagReport {
+    /// Resource usage of every tag, sorted by used memory in descending order.
+    pub tags: Vec<ResourceTagUsage>,
+}
+
+impl ResourceTagReport {
+    /// Returns resource usage of the given tag (if any).
+    pub fn find(&self, tag: &str) -> Option<&ResourceTagUsage> {
+        self.tags.iter().find(|usage| usage.tag == tag)
+    }
+
+    /// Returns an iterator over every tag, that has exceeded its quota.
+    pub fn over_quota(&self) -> impl Iterator<Item = &ResourceTagUsage> {
+        self.tags.iter().filter(|usage| usage.is_over_quota())
+    }
+}
+
+impl Display for ResourceTagReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Resource Tags: {}", self.tags.len())?;
+        for usage in self.tags.iter() {
+            writeln!(f, "\t{usage}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Attribution of resources to tags and quotas of the tags.
+#[derive(Default)]
+pub(crate) struct ResourceTags {
+    // Path of a resource -> tags of the resource.
+    pub(crate) tags: FxHashMap<ResourcePathKey, Vec<String>>,
+    pub(crate) quotas: FxHashMap<String, ResourceQuota>,
+}
+
+impl ResourceTags {
+    pub(crate) fn add(&mut self, key: ResourcePathKey, tag: &str) {
+        let tags = self.tags.entry(key).or_default();
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_owned());
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: &ResourcePathKey, tag: &str) -> bool {
+        let Some(tags) = self.tags.get_mut(key) else {
+            return false;
+        };
+        let len = tags.len();
+        tags.retain(|t| t != tag);
+        let removed = tags.len() != len;
+        if tags.is_empty() {
+            self.tags.remove(key);
+        }
+        removed
+    }
+
+    pub(crate) fn get(&self, key: &ResourcePathKey) -> &[String] {
+        self.tags.get(key).map(|t| t.as_slice()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resource_tag_usage_quota() {
+        let mut usage = ResourceTagUsage {
+            tag: "Terrain".to_string(),
+            count: 2,
+            memory: 1024,
+            load_time: Duration::from_millis(5),
+            quota: Default::default(),
+        };
+        assert!(!usage.is_over_quota());
+
+        usage.quota = ResourceQuota::memory(2048).with_max_load_time(Duration::from_millis(4));
+        assert!(!usage.is_memory_exceeded());
+        assert!(usage.is_load_time_exceeded());
+        assert!(usage.is_over_quota());
+
+        let report = ResourceTagReport {
+            tags: vec![usage.clone()],
+        };
+        assert_eq!(report.find("Terrain"), Some(&usage));
+        assert_eq!(report.over_quota().count(), 1);
+    }
+}
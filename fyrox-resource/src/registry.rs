@@ -0,0 +1,371 @@
+//! Registry snapshots allow to skip the scan of project files on startup. See [`RegistrySnapshot`]
+//! docs for more info.
+
+use crate::{
+    core::{append_extension, io::FileLoadError, uuid::Uuid, visitor::prelude::*},
+    io::{write_atomically, ResourceIo},
+    options::OPTIONS_EXTENSION,
+};
+use fxhash::{FxHashMap, FxHasher64};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// Extension of registry snapshot files.
+pub const REGISTRY_SNAPSHOT_EXTENSION: &str = "registry";
+
+/// An error that may occur during registry snapshot loading or saving.
+#[derive(Debug)]
+pub enum RegistrySnapshotError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+    /// The snapshot is corrupted or could not be serialized.
+    Visit(VisitError),
+    /// The snapshot was created by a different version of the engine, it must be rebuilt.
+    UnsupportedVersion(u32),
+}
+
+impl Display for RegistrySnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrySnapshotError::Io(v) => write!(f, "A file load error has occurred {v:?}"),
+            RegistrySnapshotError::Visit(v) => {
+                write!(f, "Unable to (de)serialize the registry snapshot. {v:?}")
+            }
+            RegistrySnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Registry snapshot version {version} is not supported, expected {}.",
+                RegistrySnapshot::VERSION
+            ),
+        }
+    }
+}
+
+impl Error for RegistrySnapshotError {}
+
+impl From<FileLoadError> for RegistrySnapshotError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for RegistrySnapshotError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+impl From<std::io::Error> for RegistrySnapshotError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(FileLoadError::Io(e))
+    }
+}
+
+/// Calculates a hash of the content of an import options file, that is stored in
+/// [`RegistryEntry::options_hash`].
+pub fn options_hash(options: &[u8]) -> u64 {
+    let mut hasher = FxHasher64::default();
+    options.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Everything the registry knows about a single resource file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Visit)]
+pub struct RegistryEntry {
+    /// Path of the resource file.
+    pub path: PathBuf,
+    /// Type uuid of the resource data, that is produced by the loader of the file.
+    pub type_uuid: Uuid,
+    /// Paths of the resources, that are used by the resource.
+    pub dependencies: Vec<PathBuf>,
+    /// Paths of the plain files, that are used to produce the data of the resource. See
+    /// [`crate::manager::ResourceManager::set_file_dependencies`] for more info.
+    pub file_dependencies: Vec<PathBuf>,
+    /// Hash of the import options file of the resource (see [`options_hash`]), `None` if there's
+    /// no such file.
+    pub options_hash: Option<u64>,
+}
+
+/// Registry snapshot is a serialized list of every loadable resource file in a directory along
+/// with the dependency edges between the resources and hashes of their import options. Big
+/// projects contain tens of thousands of files and scanning them (and reading the import options)
+/// on every launch takes a noticeable time. Instead, the snapshot could be built once (see
+/// [`crate::manager::ResourceManager::build_registry_snapshot`]), saved to a single file and then
+/// loaded on startup (see [`crate::manager::ResourceManager::load_registry_snapshot`]).
+///
+/// When a snapshot is used by the resource manager:
+///
+/// - [`crate::manager::ResourceManager::find_loadable_resources`] takes the paths from the
+///   snapshot instead of walking the directories, that are covered by the snapshot.
+/// - Dependency edges of the snapshot are known before the resources are loaded, which is used for
+///   dependency-aware reloading.
+/// - The entries are updated when the resources are loaded (with the dependencies reported by
+///   their loaders), registered or renamed, so saving the snapshot of the manager (see
+///   [`crate::manager::ResourceManagerState::registry_snapshot`]) on exit keeps it up-to-date
+///   without a rescan.
+///
+/// Files, that were added or removed outside of the engine are not tracked, [`Self::find_outdated`]
+/// could be used to find the entries that are not valid anymore. If in doubt, rebuild the snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Visit)]
+pub struct RegistrySnapshot {
+    version: u32,
+    root: PathBuf,
+    entries: FxHashMap<PathBuf, RegistryEntry>,
+}
+
+impl RegistrySnapshot {
+    /// Current version of the format of snapshot files. Snapshots of other versions are rejected
+    /// on load, which means that they must be rebuilt.
+    pub const VERSION: u32 = 1;
+
+    /// Creates a new empty snapshot of the given root directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            version: Self::VERSION,
+            root: root.into(),
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns the root directory of the snapshot.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Checks whether the given directory is in the root directory of the snapshot, which means
+    /// that the snapshot knows every resource file of the directory.
+    pub fn covers(&self, directory: &Path) -> bool {
+        directory.starts_with(&self.root)
+    }
+
+    /// Returns every entry of the snapshot.
+    pub fn entries(&self) -> impl Iterator<Item = &RegistryEntry> {
+        self.entries.values()
+    }
+
+    /// Returns the amount of entries in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the snapshot has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an entry for the given path (if any).
+    pub fn entry(&self, path: &Path) -> Option<&RegistryEntry> {
+        self.entries.get(path)
+    }
+
+    /// Returns a mutable reference to an entry for the given path (if any).
+    pub fn entry_mut(&mut self, path: &Path) -> Option<&mut RegistryEntry> {
+        self.entries.get_mut(path)
+    }
+
+    /// Adds a new entry to the snapshot. An entry with the same path is replaced.
+    pub fn add_entry(&mut self, entry: RegistryEntry) {
+        self.entries.insert(entry.path.clone(), entry);
+    }
+
+    /// Removes an entry for the given path and returns it (if any).
+    pub fn remove_entry(&mut self, path: &Path) -> Option<RegistryEntry> {
+        self.entries.remove(path)
+    }
+
+    /// Returns paths of every resource file in the given directory (recursively).
+    pub fn paths_in<'a>(&'a self, directory: &'a Path) -> impl Iterator<Item = &'a Path> + 'a {
+        self.entries
+            .keys()
+            .map(|path| path.as_path())
+            .filter(move |path| path.starts_with(directory))
+    }
+
+    /// Applies the given function to every path of the snapshot (paths of the entries and the
+    /// paths of their dependencies).
+    pub(crate) fn remap_paths(&mut self, mut func: impl FnMut(&mut PathBuf)) {
+        self.entries = std::mem::take(&mut self.entries)
+            .into_values()
+            .map(|mut entry| {
+                func(&mut entry.path);
+                for path in entry
+                    .dependencies
+                    .iter_mut()
+                    .chain(entry.file_dependencies.iter_mut())
+                {
+                    func(path);
+                }
+                (entry.path.clone(), entry)
+            })
+            .collect();
+    }
+
+    /// Serializes the snapshot into a binary representation.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        self.clone().visit("RegistrySnapshot", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    /// Deserializes a snapshot from its binary representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RegistrySnapshotError> {
+        let mut visitor = Visitor::load_from_memory(bytes)?;
+        let mut snapshot = Self::default();
+        snapshot.visit("RegistrySnapshot", &mut visitor)?;
+        if snapshot.version != Self::VERSION {
+            return Err(RegistrySnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        Ok(snapshot)
+    }
+
+    /// Loads a snapshot from the given file.
+    pub async fn load(path: &Path, io: &dyn ResourceIo) -> Result<Self, RegistrySnapshotError> {
+        let bytes = io.load_file(path).await?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Saves the snapshot to the given file. The file is replaced atomically, so a crash during
+    /// saving does not leave a corrupted snapshot.
+    pub fn save(&self, path: &Path) -> Result<(), RegistrySnapshotError> {
+        let bytes = self.to_bytes()?;
+        write_atomically(path, |path| std::fs::write(path, &bytes))?;
+        Ok(())
+    }
+
+    /// Returns paths of the entries, that are not valid anymore: their files do not exist or their
+    /// import options were changed since the snapshot was made. This method reads import options
+    /// of every entry, so it should be used only when it is known that the files could be changed
+    /// outside of the engine (after a version control update, for example).
+    pub async fn find_outdated(&self, io: &dyn ResourceIo) -> Vec<PathBuf> {
+        let mut outdated = Vec::new();
+        for entry in self.entries.values() {
+            if !io.is_file(&entry.path).await
+                || read_options_hash(&entry.path, io).await != entry.options_hash
+            {
+                outdated.push(entry.path.clone());
+            }
+        }
+        outdated.sort();
+        outdated
+    }
+}
+
+/// Reads the import options file of a resource at the given path and returns its hash, `None` if
+/// the resource has no import options file.
+pub async fn read_options_hash(path: &Path, io: &dyn ResourceIo) -> Option<u64> {
+    let options_path = append_extension(path, OPTIONS_EXTENSION);
+    if !io.exists(&options_path).await {
+        return None;
+    }
+    io.load_file(&options_path)
+        .await
+        .ok()
+        .map(|options| options_hash(&options))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::executor::block_on, io::memory::MemoryResourceIo};
+
+    fn entry(path: &str) -> RegistryEntry {
+        RegistryEntry {
+            path: path.into(),
+            type_uuid: Uuid::new_v4(),
+            dependencies: vec!["data/dependency.bin".into()],
+            file_dependencies: vec!["data/include.glsl".into()],
+            options_hash: Some(options_hash(b"(mip_maps: true)")),
+        }
+    }
+
+    #[test]
+    fn registry_snapshot_entries() {
+        let mut snapshot = RegistrySnapshot::new("data");
+        snapshot.add_entry(entry("data/a.bin"));
+        snapshot.add_entry(entry("data/sub/b.bin"));
+        assert_eq!(snapshot.len(), 2);
+
+        let mut replacement = entry("data/a.bin");
+        replacement.options_hash = None;
+        snapshot.add_entry(replacement.clone());
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.entry(Path::new("data/a.bin")), Some(&replacement));
+
+        assert!(snapshot.covers(Path::new("data/sub")));
+        assert!(!snapshot.covers(Path::new("other")));
+        assert_eq!(
+            snapshot.paths_in(Path::new("data/sub")).collect::<Vec<_>>(),
+            vec![Path::new("data/sub/b.bin")]
+        );
+
+        assert_eq!(
+            snapshot.remove_entry(Path::new("data/a.bin")),
+            Some(replacement)
+        );
+        assert!(snapshot.entry(Path::new("data/a.bin")).is_none());
+
+        snapshot.remap_paths(|path| {
+            if let Ok(relative) = path.strip_prefix("data/sub") {
+                *path = Path::new("data/moved").join(relative);
+            }
+        });
+        assert!(snapshot.entry(Path::new("data/sub/b.bin")).is_none());
+        assert!(snapshot.entry(Path::new("data/moved/b.bin")).is_some());
+    }
+
+    #[test]
+    fn registry_snapshot_serialization() {
+        let mut snapshot = RegistrySnapshot::new("data");
+        snapshot.add_entry(entry("data/a.bin"));
+        snapshot.add_entry(entry("data/b.bin"));
+
+        let bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(RegistrySnapshot::from_bytes(&bytes).unwrap(), snapshot);
+
+        let mut old = snapshot.clone();
+        old.version = 0;
+        assert!(matches!(
+            RegistrySnapshot::from_bytes(&old.to_bytes().unwrap()),
+            Err(RegistrySnapshotError::UnsupportedVersion(0))
+        ));
+        assert!(RegistrySnapshot::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn registry_snapshot_find_outdated() {
+        let io = MemoryResourceIo::new();
+        io.add_file("data/a.bin", vec![0]);
+        io.add_file("data/b.bin", vec![0]);
+        io.add_file("data/b.bin.options", b"(mip_maps: true)".to_vec());
+        io.add_file("data/c.bin", vec![0]);
+
+        let mut snapshot = RegistrySnapshot::new("data");
+        for path in ["data/a.bin", "data/b.bin", "data/c.bin", "data/removed.bin"] {
+            let path = Path::new(path);
+            snapshot.add_entry(RegistryEntry {
+                path: path.to_path_buf(),
+                options_hash: block_on(read_options_hash(path, &io)),
+                ..Default::default()
+            });
+        }
+        assert!(snapshot
+            .entry(Path::new("data/b.bin"))
+            .unwrap()
+            .options_hash
+            .is_some());
+
+        io.add_file("data/b.bin.options", b"(mip_maps: false)".to_vec());
+        io.add_file("data/c.bin.options", b"(mip_maps: false)".to_vec());
+        assert_eq!(
+            block_on(snapshot.find_outdated(&io)),
+            vec![
+                PathBuf::from("data/b.bin"),
+                PathBuf::from("data/c.bin"),
+                PathBuf::from("data/removed.bin")
+            ]
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Automatic retrying of resource loads, that failed because of transient errors. See
+//! [`RetryPolicy`] docs for more info.
+
+use crate::{
+    core::{instant::Instant, io::FileLoadError, log::Log, parking_lot::Mutex},
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+    loader::BoxedLoaderFuture,
+    ResourceLoadError, UntypedResource,
+};
+use std::{
+    cell::RefCell,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Retry policy defines what the resource manager should do when a resource has failed to load
+/// because of a transient error (see [`crate::state::LoadError::is_transient`]), such as a timed
+/// out or reset connection of a network resource IO. Instead of settling in the error state right
+/// away, the resource stays in the pending state and its loading is re-queued after a delay, that
+/// grows exponentially with every attempt. Once the retries are exhausted (or the error is not
+/// transient), the error is committed as usual.
+///
+/// Due retries are started by [`crate::manager::ResourceManagerState::update`] and while waiting
+/// for resources (see [`crate::manager::ResourceManager::wait_for_all_pending`] and
+/// [`crate::manager::ResourceManager::request_sync`]), so the waits won't hang if the manager is
+/// not updated.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{manager::ResourceManager, retry::RetryPolicy};
+/// use std::time::Duration;
+///
+/// fn enable_retries(resource_manager: &ResourceManager) {
+///     resource_manager.state().set_retry_policy(RetryPolicy {
+///         max_retries: 3,
+///         initial_delay: Duration::from_millis(250),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum amount of retries of a single load. Zero disables retrying, it is the default
+    /// value.
+    pub max_retries: u32,
+    /// Delay before the first retry. Default is half a second.
+    pub initial_delay: Duration,
+    /// A multiplier, that is applied to the delay after every retry. Default is `2.0`.
+    pub backoff_factor: f32,
+    /// Maximum delay between two attempts. Default is 10 seconds.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::from_millis(500),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a delay before the retry with the given index (starting from zero).
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self
+            .backoff_factor
+            .max(1.0)
+            .powi(retry.min(i32::MAX as u32) as i32);
+        self.initial_delay
+            .mul_f32(factor.min(u32::MAX as f32))
+            .min(self.max_delay)
+    }
+}
+
+/// A wrapper, that marks an arbitrary load error as transient. Resource loaders (or custom resource
+/// IO implementations) could use it to tell the resource manager, that the load could succeed if
+/// repeated later. See [`RetryPolicy`] docs for more info.
+#[derive(Debug)]
+pub struct TransientLoadError(pub Box<dyn ResourceLoadError>);
+
+impl TransientLoadError {
+    /// Wraps the given error.
+    pub fn new<E: ResourceLoadError>(error: E) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+/// Checks whether the given error is transient. See [`crate::state::LoadError::is_transient`].
+pub(crate) fn is_transient_error(error: &dyn ResourceLoadError) -> bool {
+    let error = error.error_as_any();
+    error.is::<TransientLoadError>()
+        || error
+            .downcast_ref::<FileLoadError>()
+            .is_some_and(|error| error.is_transient())
+}
+
+struct RetryProbe {
+    resource_key: usize,
+    io_failed: Arc<AtomicBool>,
+    deferred: Option<String>,
+}
+
+thread_local! {
+    static CURRENT_RETRY: RefCell<Option<RetryProbe>> = const { RefCell::new(None) };
+}
+
+/// Checks whether a load error of the given resource must be deferred, because the load will be
+/// retried. An error is deferred if it is transient itself, or if the resource IO has reported a
+/// transient error while loading (loaders usually wrap IO errors in their own error types).
+pub(crate) fn defer_transient_error(resource_key: usize, error: &dyn ResourceLoadError) -> bool {
+    CURRENT_RETRY.with(|probe| match probe.borrow_mut().as_mut() {
+        Some(probe)
+            if probe.resource_key == resource_key
+                && (is_transient_error(error) || probe.io_failed.load(Ordering::Relaxed)) =>
+        {
+            probe.deferred = Some(format!("{error:?}"));
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Resource IO wrapper, that remembers whether any of the operations has failed with a transient
+/// error.
+pub(crate) struct TransientErrorMonitorIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) failed: Arc<AtomicBool>,
+}
+
+impl TransientErrorMonitorIo {
+    async fn monitor<T, F>(&self, future: F) -> Result<T, FileLoadError>
+    where
+        F: Future<Output = Result<T, FileLoadError>>,
+    {
+        let result = future.await;
+        if let Err(ref err) = result {
+            if err.is_transient() {
+                self.failed.store(true, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+}
+
+impl ResourceIo for TransientErrorMonitorIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(self.monitor(self.inner.load_file(path)))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(self.monitor(self.inner.move_file(source, dest)))
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(self.monitor(self.inner.canonicalize_path(path)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(self.monitor(self.inner.read_directory(path)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(self.monitor(self.inner.walk_directory(path)))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(self.monitor(self.inner.file_reader(path)))
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.exists(path)
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_file(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.inner.is_dir(path)
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        self.inner.file_stamp(path)
+    }
+}
+
+/// A load, that should be started again once its delay runs out.
+pub(crate) struct ScheduledRetry {
+    pub(crate) resource: UntypedResource,
+    pub(crate) reload: bool,
+    /// Index of the next attempt, the first load has index zero.
+    pub(crate) attempt: u32,
+    delay: Duration,
+    due: Option<Instant>,
+}
+
+/// Loader future wrapper, that defers transient errors and schedules a retry instead.
+struct RetryLoaderFuture {
+    inner: BoxedLoaderFuture,
+    retry: Option<ScheduledRetry>,
+    max_retries: u32,
+    io_failed: Arc<AtomicBool>,
+    scheduled: Arc<Mutex<Vec<ScheduledRetry>>>,
+    deferred: Option<String>,
+}
+
+impl Future for RetryLoaderFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let Some(resource_key) = this.retry.as_ref().map(|retry| retry.resource.key()) else {
+            return Poll::Ready(());
+        };
+        let previous = CURRENT_RETRY.with(|probe| {
+            probe.replace(Some(RetryProbe {
+                resource_key,
+                io_failed: this.io_failed.clone(),
+                deferred: None,
+            }))
+        });
+        let poll = this.inner.as_mut().poll(cx);
+        if let Some(probe) = CURRENT_RETRY.with(|probe| probe.replace(previous)) {
+            if probe.deferred.is_some() {
+                this.deferred = probe.deferred;
+            }
+        }
+
+        if poll.is_pending() {
+            return Poll::Pending;
+        }
+
+        if let (Some(reason), Some(mut retry)) = (this.deferred.take(), this.retry.take()) {
+            Log::warn(format!(
+                "Unable to load {} resource (attempt {} of {}), retrying in {:.2} s. Reason: {}",
+                retry.resource.path().display(),
+                retry.attempt,
+                this.max_retries + 1,
+                retry.delay.as_secs_f32(),
+                reason
+            ));
+            retry.due = Some(Instant::now() + retry.delay);
+            this.scheduled.lock().push(retry);
+        }
+
+        Poll::Ready(())
+    }
+}
+
+/// Retry policy and the loads, that are waiting for a retry.
+#[derive(Default)]
+pub(crate) struct Retrier {
+    pub(crate) policy: RetryPolicy,
+    scheduled: Arc<Mutex<Vec<ScheduledRetry>>>,
+}
+
+impl Retrier {
+    /// Wraps the given loader future (that performs the given attempt of loading of the resource),
+    /// so its transient errors will be deferred and the load will be scheduled for a retry. IO of
+    /// the future must be wrapped in [`TransientErrorMonitorIo`] with the given flag.
+    pub(crate) fn wrap(
+        &self,
+        inner: BoxedLoaderFuture,
+        resource: UntypedResource,
+        reload: bool,
+        attempt: u32,
+        io_failed: Arc<AtomicBool>,
+    ) -> BoxedLoaderFuture {
+        if attempt >= self.policy.max_retries {
+            return inner;
+        }
+        Box::pin(RetryLoaderFuture {
+            inner,
+            retry: Some(ScheduledRetry {
+                resource,
+                reload,
+                attempt: attempt + 1,
+                delay: self.policy.delay(attempt),
+                due: None,
+            }),
+            max_retries: self.policy.max_retries,
+            io_failed,
+            scheduled: self.scheduled.clone(),
+            deferred: None,
+        })
+    }
+
+    /// Returns the loads, which delays have run out and that should be started again.
+    pub(crate) fn take_due(&self) -> Vec<ScheduledRetry> {
+        let now = Instant::now();
+        let mut scheduled = self.scheduled.lock();
+        let mut due = Vec::new();
+        let mut i = 0;
+        while i < scheduled.len() {
+            if scheduled[i].due.map_or(true, |due| due <= now) {
+                due.push(scheduled.swap_remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        due
+    }
+
+    /// Returns the time, when the earliest scheduled load should be started again.
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        self.scheduled
+            .lock()
+            .iter()
+            .map(|retry| retry.due.unwrap_or_else(Instant::now))
+            .min()
+    }
+
+    /// Returns the amount of loads, that are waiting for a retry.
+    pub(crate) fn scheduled(&self) -> usize {
+        self.scheduled.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::LoadError;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn retry_policy_delay() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_delay: Duration::from_secs(1),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(5),
+        };
+        assert_eq!(policy.delay(0), Duration::from_secs(1));
+        assert_eq!(policy.delay(1), Duration::from_secs(2));
+        assert_eq!(policy.delay(2), Duration::from_secs(4));
+        assert_eq!(policy.delay(3), Duration::from_secs(5));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn load_error_is_transient() {
+        assert!(LoadError::new(FileLoadError::Io(Error::from(ErrorKind::TimedOut))).is_transient());
+        assert!(
+            !LoadError::new(FileLoadError::Io(Error::from(ErrorKind::NotFound))).is_transient()
+        );
+        assert!(LoadError::new(TransientLoadError::new("Server is busy")).is_transient());
+        assert!(!LoadError::new("Invalid data").is_transient());
+        assert!(!LoadError::default().is_transient());
+    }
+}
@@ -0,0 +1,256 @@
+//! Resource savers. They're used to save resource data types, that do not implement saving by
+//! themselves. See [`ResourceSaversContainer`] docs for more info.
+
+use crate::core::{parking_lot::RwLock, uuid::Uuid};
+use std::{any::Any, error::Error, path::Path, sync::OnceLock};
+
+/// A simple type-casting trait that has auto-impl.
+pub trait ResourceSaverTypeTrait: Send + Sync + 'static {
+    /// Converts `self` into boxed `Any`.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    /// Returns `self` as `&dyn Any`. It is useful for downcasting to a particular type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns `self` as `&mut dyn Any`. It is useful for downcasting to a particular type.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> ResourceSaverTypeTrait for T
+where
+    T: ResourceSaver,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Trait for resource saving. A saver is responsible for a single resource data type (see
+/// [`Self::data_type_uuid`]) and could save it in one or more formats (see [`Self::extensions`]).
+pub trait ResourceSaver: ResourceSaverTypeTrait {
+    /// Returns a list of file extensions supported by the saver. The extension of the path, that
+    /// is passed to [`crate::ResourceData::save`], is used to pick a saver.
+    fn extensions(&self) -> &[&str];
+
+    /// Checks if the given extension is supported by this saver. Comparison is case-insensitive.
+    fn supports_extension(&self, ext: &str) -> bool {
+        self.extensions()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(ext))
+    }
+
+    /// Must return a type uuid of the resource data type.
+    fn data_type_uuid(&self) -> Uuid;
+
+    /// Saves the resource data to a file at the given path. The data is guaranteed to have the type
+    /// defined by [`Self::data_type_uuid`], so it is safe to downcast it to the concrete type.
+    fn save(&self, data: &dyn Any, path: &Path) -> Result<(), Box<dyn Error>>;
+}
+
+/// Container for resource savers. It is used by the default implementation of
+/// [`crate::ResourceData::save`], so resource data types, that do not implement saving by themselves,
+/// could still be saved if there's a saver for their type and the extension of the path. Savers
+/// are stored in a global container, because resource data could be saved from anywhere (see
+/// [`resource_savers`]).
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_resource::{core::uuid::{uuid, Uuid}, saver::{resource_savers, ResourceSaver}};
+/// # use std::{any::Any, error::Error, path::Path};
+/// struct MyData {
+///     text: String,
+/// }
+///
+/// struct MyDataSaver;
+///
+/// impl ResourceSaver for MyDataSaver {
+///     fn extensions(&self) -> &[&str] {
+///         &["txt"]
+///     }
+///
+///     fn data_type_uuid(&self) -> Uuid {
+///         uuid!("a0e5bb57-e6a6-4174-b370-2b5d8b8b4fe8")
+///     }
+///
+///     fn save(&self, data: &dyn Any, path: &Path) -> Result<(), Box<dyn Error>> {
+///         let data = data.downcast_ref::<MyData>().ok_or("Invalid data type!")?;
+///         std::fs::write(path, &data.text)?;
+///         Ok(())
+///     }
+/// }
+///
+/// resource_savers().write().set(MyDataSaver);
+/// ```
+#[derive(Default)]
+pub struct ResourceSaversContainer {
+    savers: Vec<Box<dyn ResourceSaver>>,
+}
+
+impl ResourceSaversContainer {
+    /// Creates new empty resource savers container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds new resource saver or replaces existing. There could be only one saver of a given type
+    /// at the same time.
+    pub fn set<T>(&mut self, saver: T) -> Option<T>
+    where
+        T: ResourceSaver,
+    {
+        if let Some(existing_saver) = self.find_mut::<T>() {
+            Some(std::mem::replace(existing_saver, saver))
+        } else {
+            self.savers.push(Box::new(saver));
+            None
+        }
+    }
+
+    /// Removes a resource saver of the given type `T`.
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: ResourceSaver,
+    {
+        let pos = self
+            .savers
+            .iter()
+            .position(|saver| (**saver).as_any().is::<T>())?;
+        self.savers
+            .remove(pos)
+            .into_any()
+            .downcast::<T>()
+            .ok()
+            .map(|boxed| *boxed)
+    }
+
+    /// Tries to find an instance of a resource saver of the given type `T`.
+    pub fn find<T>(&self) -> Option<&T>
+    where
+        T: ResourceSaver,
+    {
+        self.savers
+            .iter()
+            .find_map(|saver| (**saver).as_any().downcast_ref())
+    }
+
+    /// Tries to find an instance of a resource saver of the given type `T`.
+    pub fn find_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: ResourceSaver,
+    {
+        self.savers
+            .iter_mut()
+            .find_map(|saver| (**saver).as_any_mut().downcast_mut())
+    }
+
+    /// Searches for a saver, that could save the data of the given type to a file at the given path.
+    pub fn find_for(&self, data_type_uuid: Uuid, path: &Path) -> Option<&dyn ResourceSaver> {
+        let ext = path.extension()?.to_string_lossy();
+        self.iter().find(|saver| {
+            saver.data_type_uuid() == data_type_uuid && saver.supports_extension(&ext)
+        })
+    }
+
+    /// Returns total amount of savers in the container.
+    pub fn len(&self) -> usize {
+        self.savers.len()
+    }
+
+    /// Return `true` if the container contains no savers.
+    pub fn is_empty(&self) -> bool {
+        self.savers.is_empty()
+    }
+
+    /// Returns an iterator yielding shared references to savers.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn ResourceSaver> {
+        self.savers.iter().map(|saver| &**saver)
+    }
+
+    /// Saves resource data of the given type using a respective saver (see [`Self::find_for`]).
+    pub fn save(
+        &self,
+        data_type_uuid: Uuid,
+        data: &dyn Any,
+        path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        match self.find_for(data_type_uuid, path) {
+            Some(saver) => saver.save(data, path),
+            None => Err("Saving is not supported!".to_string().into()),
+        }
+    }
+}
+
+/// Returns the global container of resource savers. See [`ResourceSaversContainer`] docs for more
+/// info.
+pub fn resource_savers() -> &'static RwLock<ResourceSaversContainer> {
+    static SAVERS: OnceLock<RwLock<ResourceSaversContainer>> = OnceLock::new();
+    SAVERS.get_or_init(Default::default)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{parking_lot::Mutex, uuid::uuid};
+    use std::path::PathBuf;
+
+    const STUB_UUID: Uuid = uuid!("7f1bd1d6-0ce5-4b8b-9d1b-0a2b622a7c60");
+
+    struct Stub(u32);
+
+    #[derive(Default)]
+    struct StubSaver {
+        saved: Mutex<Vec<(u32, PathBuf)>>,
+    }
+
+    impl ResourceSaver for StubSaver {
+        fn extensions(&self) -> &[&str] {
+            &["stub"]
+        }
+
+        fn data_type_uuid(&self) -> Uuid {
+            STUB_UUID
+        }
+
+        fn save(&self, data: &dyn Any, path: &Path) -> Result<(), Box<dyn Error>> {
+            let data = data.downcast_ref::<Stub>().ok_or("Invalid type!")?;
+            self.saved.lock().push((data.0, path.to_path_buf()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn resource_savers_container() {
+        let mut savers = ResourceSaversContainer::new();
+        assert!(savers.is_empty());
+        assert!(savers.set(StubSaver::default()).is_none());
+        assert!(savers.set(StubSaver::default()).is_some());
+        assert_eq!(savers.len(), 1);
+
+        assert!(savers.find_for(STUB_UUID, Path::new("a.STUB")).is_some());
+        assert!(savers.find_for(STUB_UUID, Path::new("a.txt")).is_none());
+        assert!(savers.find_for(Uuid::nil(), Path::new("a.stub")).is_none());
+
+        assert!(savers
+            .save(STUB_UUID, &Stub(3), Path::new("a.stub"))
+            .is_ok());
+        assert!(savers
+            .save(STUB_UUID, &Stub(3), Path::new("a.txt"))
+            .is_err());
+        assert_eq!(
+            *savers.find::<StubSaver>().unwrap().saved.lock(),
+            [(3, PathBuf::from("a.stub"))]
+        );
+
+        assert!(savers.remove::<StubSaver>().is_some());
+        assert!(savers.is_empty());
+    }
+}
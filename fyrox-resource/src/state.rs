@@ -9,7 +9,7 @@ use crate::{
         visitor::{prelude::*, RegionGuard},
     },
     manager::ResourceManager,
-    ResourceData, ResourceLoadError, CURVE_RESOURCE_UUID, MODEL_RESOURCE_UUID,
+    retry, ResourceData, ResourceLoadError, CURVE_RESOURCE_UUID, MODEL_RESOURCE_UUID,
     SHADER_RESOURCE_UUID, SOUND_BUFFER_RESOURCE_UUID, TEXTURE_RESOURCE_UUID,
 };
 use std::{
@@ -87,6 +87,14 @@ impl LoadError {
     pub fn new<T: ResourceLoadError>(value: T) -> Self {
         Self(Some(Arc::new(value)))
     }
+
+    /// Returns `true` if the error is most likely temporary, and the load could succeed if repeated
+    /// later. It is `true` for transient [`crate::core::io::FileLoadError`]s and for errors wrapped
+    /// in [`crate::retry::TransientLoadError`]. See [`crate::retry::RetryPolicy`] for the way to
+    /// retry such loads automatically.
+    pub fn is_transient(&self) -> bool {
+        self.0.as_deref().is_some_and(retry::is_transient_error)
+    }
 }
 
 /// Resource could be in three possible states:
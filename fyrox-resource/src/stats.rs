@@ -0,0 +1,405 @@
+//! Resource loading statistics. See [`ResourceLoadStatistics`] docs for more info.
+
+use crate::{
+    core::{instant::Instant, io::FileLoadError, parking_lot::Mutex, uuid::Uuid},
+    io::{FileReader, FileStamp, ResourceIo, ResourceIoFuture},
+    loader::BoxedLoaderFuture,
+};
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    fmt::{Display, Formatter},
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Time spent to load a single resource. Every loading stage is measured using wall-clock time, so
+/// the timings include time spent waiting for other tasks (for example, if there are more loading
+/// tasks than worker threads).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLoadTiming {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// Type uuid of the resource.
+    pub type_uuid: Uuid,
+    /// `true` if the resource was reloaded, `false` - if it was loaded for the first time.
+    pub reload: bool,
+    /// Time spent in the resource IO (reading files, checking their existence, etc.).
+    pub io: Duration,
+    /// Time spent by the loader to decode the data (excluding the IO time), until the finalization
+    /// is started or the resource is committed.
+    pub decoding: Duration,
+    /// Time spent by the loader after the finalization was started (see
+    /// [`mark_finalization_started`]) or after the resource was committed. It usually includes
+    /// waiting for dependencies, resolving and broadcasting events.
+    pub finalization: Duration,
+}
+
+impl ResourceLoadTiming {
+    /// Returns total time spent to load the resource.
+    pub fn total(&self) -> Duration {
+        self.io + self.decoding + self.finalization
+    }
+}
+
+impl Display for ResourceLoadTiming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.2} ms (IO: {:.2} ms, Decoding: {:.2} ms, Finalization: {:.2} ms)",
+            self.path.display(),
+            self.total().as_secs_f64() * 1000.0,
+            self.io.as_secs_f64() * 1000.0,
+            self.decoding.as_secs_f64() * 1000.0,
+            self.finalization.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Resource loading statistics contains timings of every resource loaded (or reloaded) by a
+/// resource manager. It could be used to find which resources dominate loading time of your game.
+/// Use [`crate::manager::ResourceManager::statistics`] to get the statistics.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::manager::ResourceManager;
+///
+/// fn print_slowest_resources(resource_manager: &ResourceManager) {
+///     for timing in resource_manager.statistics().slowest(10) {
+///         println!("{timing}");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResourceLoadStatistics {
+    /// Timings of every loaded resource in the order of loading completion.
+    pub timings: Vec<ResourceLoadTiming>,
+}
+
+impl ResourceLoadStatistics {
+    /// Returns the most recent timing of a resource with the given path.
+    pub fn find(&self, path: &Path) -> Option<&ResourceLoadTiming> {
+        self.timings.iter().rev().find(|timing| timing.path == path)
+    }
+
+    /// Returns at most `count` timings sorted by total loading time in descending order.
+    pub fn slowest(&self, count: usize) -> Vec<&ResourceLoadTiming> {
+        let mut timings = self.timings.iter().collect::<Vec<_>>();
+        timings.sort_by_key(|timing| Reverse(timing.total()));
+        timings.truncate(count);
+        timings
+    }
+
+    /// Returns total time spent in the resource IO by all resources.
+    pub fn total_io(&self) -> Duration {
+        self.timings.iter().map(|timing| timing.io).sum()
+    }
+
+    /// Returns total time spent to load all resources. Keep in mind, that resources are loaded
+    /// in parallel, so the value is usually larger than actual elapsed time.
+    pub fn total(&self) -> Duration {
+        self.timings.iter().map(|timing| timing.total()).sum()
+    }
+}
+
+impl Display for ResourceLoadStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Resources Loaded: {}\n\
+            Total Time: {:.2} ms\n\
+            Total IO Time: {:.2} ms",
+            self.timings.len(),
+            self.total().as_secs_f64() * 1000.0,
+            self.total_io().as_secs_f64() * 1000.0
+        )?;
+        for timing in self.timings.iter() {
+            writeln!(f, "\t{timing}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Memory usage of all loaded resources of a single type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceTypeMemoryUsage {
+    /// Type uuid of the resources.
+    pub type_uuid: Uuid,
+    /// Type name of the resources. It is taken from the resource constructors container, empty
+    /// if there's no constructor for the type.
+    pub type_name: String,
+    /// Amount of loaded resources of the type.
+    pub count: usize,
+    /// Estimated amount of memory (in bytes) used by the resources of the type. See
+    /// [`crate::ResourceData::memory_usage`] for more info.
+    pub memory: usize,
+}
+
+impl Display for ResourceTypeMemoryUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = if self.type_name.is_empty() {
+            self.type_uuid.to_string()
+        } else {
+            self.type_name.clone()
+        };
+        write!(
+            f,
+            "{}: {} resource(s), {:.2} MiB",
+            name,
+            self.count,
+            self.memory as f64 / (1024.0 * 1024.0)
+        )
+    }
+}
+
+/// Memory usage report contains estimated memory usage of loaded resources grouped by their type.
+/// Pending resources and resources that failed to load are not included in the report. Use
+/// [`crate::manager::ResourceManagerState::memory_usage_report`] to create the report.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::manager::ResourceManager;
+///
+/// fn print_memory_usage(resource_manager: &ResourceManager) {
+///     println!("{}", resource_manager.state().memory_usage_report());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceMemoryReport {
+    /// Memory usage of every resource type, sorted by used memory in descending order.
+    pub types: Vec<ResourceTypeMemoryUsage>,
+}
+
+impl ResourceMemoryReport {
+    /// Returns memory usage of resources of the given type (if any).
+    pub fn find(&self, type_uuid: Uuid) -> Option<&ResourceTypeMemoryUsage> {
+        self.types.iter().find(|usage| usage.type_uuid == type_uuid)
+    }
+
+    /// Returns total amount of loaded resources.
+    pub fn total_count(&self) -> usize {
+        self.types.iter().map(|usage| usage.count).sum()
+    }
+
+    /// Returns total estimated amount of memory (in bytes) used by all loaded resources.
+    pub fn total_memory(&self) -> usize {
+        self.types.iter().map(|usage| usage.memory).sum()
+    }
+}
+
+impl Display for ResourceMemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Loaded Resources: {}\n\
+            Total Memory: {:.2} MiB",
+            self.total_count(),
+            self.total_memory() as f64 / (1024.0 * 1024.0)
+        )?;
+        for usage in self.types.iter() {
+            writeln!(f, "\t{usage}")?;
+        }
+        Ok(())
+    }
+}
+
+struct LoadProbe {
+    resource_key: usize,
+    finalization_start: Option<Instant>,
+}
+
+thread_local! {
+    static CURRENT_PROBE: RefCell<Option<LoadProbe>> = const { RefCell::new(None) };
+}
+
+/// Marks the beginning of the finalization stage of a resource that is being loaded by the current
+/// task. Resource loaders could call this method to separate decoding of the data from other work
+/// (waiting for dependencies, resolving, etc.). If the method is not called, the finalization stage
+/// starts when the resource is committed. Does nothing if called outside of a loading task.
+pub fn mark_finalization_started() {
+    CURRENT_PROBE.with(|probe| {
+        if let Some(probe) = probe.borrow_mut().as_mut() {
+            probe.finalization_start.get_or_insert_with(Instant::now);
+        }
+    })
+}
+
+pub(crate) fn on_resource_committed(resource_key: usize) {
+    CURRENT_PROBE.with(|probe| {
+        if let Some(probe) = probe.borrow_mut().as_mut() {
+            if probe.resource_key == resource_key {
+                probe.finalization_start.get_or_insert_with(Instant::now);
+            }
+        }
+    })
+}
+
+/// Resource IO wrapper, that measures time spent in the IO.
+pub(crate) struct TimedResourceIo {
+    pub(crate) inner: Arc<dyn ResourceIo>,
+    pub(crate) spent: Arc<Mutex<Duration>>,
+}
+
+impl TimedResourceIo {
+    async fn measure<F: Future>(&self, future: F) -> F::Output {
+        let start = Instant::now();
+        let result = future.await;
+        *self.spent.lock() += start.elapsed();
+        result
+    }
+}
+
+impl ResourceIo for TimedResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(self.measure(self.inner.load_file(path)))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(self.measure(self.inner.move_file(source, dest)))
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(self.measure(self.inner.canonicalize_path(path)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(self.measure(self.inner.read_directory(path)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn Iterator<Item = PathBuf> + Send>, FileLoadError>> {
+        Box::pin(self.measure(self.inner.walk_directory(path)))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(self.measure(self.inner.file_reader(path)))
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(self.measure(self.inner.exists(path)))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(self.measure(self.inner.is_file(path)))
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(self.measure(self.inner.is_dir(path)))
+    }
+    fn file_stamp<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, Option<FileStamp>> {
+        Box::pin(self.measure(self.inner.file_stamp(path)))
+    }
+}
+
+/// Loader future wrapper, that measures loading time of a resource and writes it to the given
+/// statistics when the loading is finished.
+pub(crate) struct TimedLoaderFuture {
+    pub(crate) inner: BoxedLoaderFuture,
+    pub(crate) timing: ResourceLoadTiming,
+    pub(crate) resource_key: usize,
+    pub(crate) io_spent: Arc<Mutex<Duration>>,
+    pub(crate) statistics: Arc<Mutex<ResourceLoadStatistics>>,
+    pub(crate) start: Option<Instant>,
+    pub(crate) finalization_start: Option<Instant>,
+}
+
+impl Future for TimedLoaderFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        this.start.get_or_insert_with(Instant::now);
+
+        // Loading tasks are never nested on the same thread, but restore the previous probe anyway
+        // to be on the safe side.
+        let previous = CURRENT_PROBE.with(|probe| {
+            probe.replace(Some(LoadProbe {
+                resource_key: this.resource_key,
+                finalization_start: this.finalization_start,
+            }))
+        });
+        let poll = this.inner.as_mut().poll(cx);
+        if let Some(probe) = CURRENT_PROBE.with(|probe| probe.replace(previous)) {
+            this.finalization_start = probe.finalization_start;
+        }
+
+        if poll.is_ready() {
+            let end = Instant::now();
+            let start = this.start.unwrap_or(end);
+            let finalization_start = this.finalization_start.unwrap_or(end);
+            let io = *this.io_spent.lock();
+
+            let mut timing = std::mem::take(&mut this.timing);
+            timing.io = io;
+            timing.decoding = finalization_start
+                .saturating_duration_since(start)
+                .saturating_sub(io);
+            timing.finalization = end.saturating_duration_since(finalization_start);
+
+            this.statistics.lock().timings.push(timing);
+        }
+
+        poll
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timing(path: &str, millis: u64) -> ResourceLoadTiming {
+        ResourceLoadTiming {
+            path: path.into(),
+            decoding: Duration::from_millis(millis),
+            io: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resource_load_statistics_queries() {
+        let statistics = ResourceLoadStatistics {
+            timings: vec![timing("a.png", 5), timing("b.png", 10), timing("a.png", 2)],
+        };
+
+        assert_eq!(
+            statistics.find(Path::new("a.png")).map(|t| t.decoding),
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(
+            statistics
+                .slowest(2)
+                .iter()
+                .map(|t| t.total())
+                .collect::<Vec<_>>(),
+            vec![Duration::from_millis(11), Duration::from_millis(6)]
+        );
+        assert_eq!(statistics.total_io(), Duration::from_millis(3));
+        assert_eq!(statistics.total(), Duration::from_millis(20));
+    }
+}
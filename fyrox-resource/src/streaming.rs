@@ -0,0 +1,475 @@
+//! Streaming of resources, that are loaded in stages. See [`StreamableResourceData`] docs for more
+//! info.
+
+use crate::{
+    core::{log::Log, parking_lot::Mutex},
+    event::{ResourceEvent, ResourceEventBroadcaster},
+    io::ResourceIo,
+    state::{LoadError, ResourceState},
+    task::TaskPool,
+    ResourceData, UntypedResource,
+};
+use std::{
+    any::Any,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+/// Data of a single stage of a streamable resource, produced by [`StreamableResourceData::load_stage`]
+/// and consumed by [`StreamableResourceData::commit_stage`].
+pub type StageData = Box<dyn Any + Send>;
+
+/// Future type for stage loading. See [`StreamableResourceData::load_stage`] for more info.
+#[cfg(target_arch = "wasm32")]
+pub type BoxedStageFuture = Pin<Box<dyn Future<Output = Result<StageData, LoadError>>>>;
+
+/// Future type for stage loading. See [`StreamableResourceData::load_stage`] for more info.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxedStageFuture = Pin<Box<dyn Future<Output = Result<StageData, LoadError>> + Send>>;
+
+/// Streamable resource data is a resource data, that could be loaded in stages. For example, a texture
+/// could load its smallest mip levels first and then load larger ones, or a sound could load its
+/// samples chunk-by-chunk. This allows large projects (open worlds in particular) to have every resource
+/// available almost immediately and load the rest of it when it is actually needed.
+///
+/// The first stage (with index 0) is loaded by a regular resource loader, so the resource is usable as
+/// soon as it is loaded. Every other stage must be requested explicitly using
+/// [`crate::manager::ResourceManagerState::request_stages`]. Stages are loaded one at a time and
+/// strictly in order, so the data could rely on every previous stage being committed. Loading of a stage
+/// is done in two steps:
+///
+/// 1. [`Self::load_stage`] creates a future, that loads the stage data. The future is executed on a
+///    separate thread (or as a micro-task on WebAssembly) without the resource being locked, so the
+///    resource stays usable while the stage is loading.
+/// 2. [`Self::commit_stage`] puts the loaded data in the resource. It is called on the same thread
+///    with the resource locked, so it should be as fast as possible.
+///
+/// State of every stage could be fetched using [`crate::manager::ResourceManagerState::stage_states`].
+/// Every committed stage produces [`ResourceEvent::StageLoaded`] event, failed stages produce
+/// [`ResourceEvent::StageFailed`] event.
+///
+/// To make the resource manager aware of streaming capabilities of a resource data, its
+/// [`ResourceData::as_streamable`] and [`ResourceData::as_streamable_mut`] methods must return
+/// `Some(self)`.
+pub trait StreamableResourceData: ResourceData {
+    /// Returns total amount of stages of the resource, including the first stage, that is loaded by
+    /// a resource loader.
+    fn stage_count(&self) -> usize;
+
+    /// Creates a future, that loads data of the given stage (which is always greater than zero) from
+    /// the given resource IO. The future must not access the resource itself, everything it needs
+    /// must be captured by value.
+    fn load_stage(&self, stage: usize, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedStageFuture;
+
+    /// Puts the data, loaded by the future created by [`Self::load_stage`], in the resource.
+    fn commit_stage(&mut self, stage: usize, data: StageData) -> Result<(), LoadError>;
+
+    /// Removes data of the given stage from the resource to free some memory. Returns `true` if the
+    /// stage was unloaded and could be loaded again later. By default, this method does nothing and
+    /// returns `false`, which means that the resource does not support unloading of its stages.
+    fn unload_stage(&mut self, #[allow(unused_variables)] stage: usize) -> bool {
+        false
+    }
+}
+
+/// State of a single stage of a streamable resource.
+#[derive(Clone, Debug, Default)]
+pub enum StageState {
+    /// The stage is not loaded and wasn't requested.
+    #[default]
+    NotLoaded,
+    /// The stage is requested and it is loading (or waiting for the previous stages to load).
+    Loading,
+    /// The stage is loaded and committed.
+    Loaded,
+    /// The stage failed to load. Stages after the failed one won't be loaded, until the stage is
+    /// requested again.
+    Failed(LoadError),
+}
+
+impl StageState {
+    /// Returns `true` if the stage is loaded and committed, `false` - otherwise.
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, StageState::Loaded)
+    }
+}
+
+struct StreamingEntry {
+    resource: Weak<Mutex<ResourceState>>,
+    // Shared with the loading task of the current stage.
+    stages: Arc<Mutex<Vec<StageState>>>,
+    target: usize,
+}
+
+impl StreamingEntry {
+    fn is(&self, resource: &UntypedResource) -> bool {
+        self.resource.as_ptr() == Arc::as_ptr(&resource.0)
+    }
+}
+
+/// Tracks stages of streamable resources and spawns loading tasks for the requested stages.
+#[derive(Default)]
+pub(crate) struct ResourceStreamer {
+    entries: Vec<StreamingEntry>,
+}
+
+impl ResourceStreamer {
+    fn entry(&self, resource: &UntypedResource) -> Option<&StreamingEntry> {
+        self.entries.iter().find(|entry| entry.is(resource))
+    }
+
+    pub(crate) fn request_stages(&mut self, resource: &UntypedResource, stage: usize) -> bool {
+        let stage_count = match &*resource.lock() {
+            ResourceState::Ok(data) => match data.as_streamable() {
+                Some(streamable) => streamable.stage_count(),
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        let target = stage.min(stage_count.saturating_sub(1));
+
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.is(resource)) {
+            entry.target = entry.target.max(target);
+            // Failed stages are retried on explicit request.
+            for state in entry.stages.lock().iter_mut().take(target + 1) {
+                if let StageState::Failed(_) = state {
+                    *state = StageState::NotLoaded;
+                }
+            }
+        } else {
+            let stages = (0..stage_count)
+                .map(|stage| {
+                    if stage == 0 {
+                        StageState::Loaded
+                    } else {
+                        StageState::NotLoaded
+                    }
+                })
+                .collect();
+            self.entries.push(StreamingEntry {
+                resource: Arc::downgrade(&resource.0),
+                stages: Arc::new(Mutex::new(stages)),
+                target,
+            });
+        }
+
+        true
+    }
+
+    pub(crate) fn unload_stages(
+        &mut self,
+        resource: &UntypedResource,
+        keep: usize,
+        event_broadcaster: &ResourceEventBroadcaster,
+    ) -> usize {
+        let Some(entry) = self.entries.iter_mut().find(|entry| entry.is(resource)) else {
+            return 0;
+        };
+
+        entry.target = entry.target.min(keep);
+
+        let mut unloaded = Vec::new();
+        if let ResourceState::Ok(data) = &mut *resource.lock() {
+            if let Some(streamable) = data.as_streamable_mut() {
+                let mut stages = entry.stages.lock();
+                for stage in (keep + 1..stages.len()).rev() {
+                    if stages[stage].is_loaded() && streamable.unload_stage(stage) {
+                        stages[stage] = StageState::NotLoaded;
+                        unloaded.push(stage);
+                    }
+                }
+            }
+        }
+
+        // Events are sent without the resource being locked, so subscribers can access it.
+        for &stage in unloaded.iter() {
+            event_broadcaster.broadcast(ResourceEvent::StageUnloaded {
+                resource: resource.clone(),
+                stage,
+            });
+        }
+
+        unloaded.len()
+    }
+
+    pub(crate) fn stage_states(&self, resource: &UntypedResource) -> Vec<StageState> {
+        self.entry(resource)
+            .map(|entry| entry.stages.lock().clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn reset(&mut self, resource: &UntypedResource) {
+        self.entries.retain(|entry| !entry.is(resource));
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        task_pool: &TaskPool,
+        io: &Arc<dyn ResourceIo>,
+        event_broadcaster: &ResourceEventBroadcaster,
+    ) {
+        self.entries
+            .retain(|entry| entry.resource.strong_count() > 0);
+
+        for entry in self.entries.iter() {
+            let mut stages = entry.stages.lock();
+
+            if stages
+                .iter()
+                .any(|state| matches!(state, StageState::Loading))
+            {
+                continue;
+            }
+
+            // Stages are committed strictly in order, a failed stage blocks every next stage.
+            let Some(stage) = stages
+                .iter()
+                .take(entry.target + 1)
+                .position(|state| !state.is_loaded())
+            else {
+                continue;
+            };
+            if let StageState::Failed(_) = stages[stage] {
+                continue;
+            }
+
+            let Some(resource) = entry.resource.upgrade().map(UntypedResource) else {
+                continue;
+            };
+
+            let future = match &*resource.lock() {
+                ResourceState::Ok(data) => match data.as_streamable() {
+                    Some(streamable) => {
+                        streamable.load_stage(stage, data.path().to_path_buf(), io.clone())
+                    }
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            stages[stage] = StageState::Loading;
+
+            let stages = Arc::downgrade(&entry.stages);
+            let event_broadcaster = event_broadcaster.clone();
+            task_pool.spawn_task(async move {
+                let result = future.await;
+
+                // The streaming state was reset (for example, because the resource was reloaded), so
+                // the data is most likely outdated.
+                let Some(stages) = stages.upgrade() else {
+                    return;
+                };
+
+                let result = result.and_then(|data| match &mut *resource.lock() {
+                    ResourceState::Ok(resource_data) => match resource_data.as_streamable_mut() {
+                        Some(streamable) => streamable.commit_stage(stage, data),
+                        None => Err(LoadError::new("The resource is not streamable anymore.")),
+                    },
+                    _ => Err(LoadError::new("The resource is not loaded.")),
+                });
+
+                match result {
+                    Ok(()) => {
+                        stages.lock()[stage] = StageState::Loaded;
+                        event_broadcaster.broadcast(ResourceEvent::StageLoaded { resource, stage });
+                    }
+                    Err(error) => {
+                        Log::err(format!(
+                            "Unable to load stage {} of {} resource. Reason: {:?}",
+                            stage,
+                            resource.path().display(),
+                            error
+                        ));
+                        stages.lock()[stage] = StageState::Failed(error);
+                        event_broadcaster.broadcast(ResourceEvent::StageFailed { resource, stage });
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{
+            reflect::prelude::*,
+            uuid::{uuid, Uuid},
+            visitor::prelude::*,
+            TypeUuidProvider,
+        },
+        io::memory::MemoryResourceIo,
+        manager::ResourceManagerState,
+    };
+    use std::{
+        path::Path,
+        sync::mpsc::channel,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Chunked {
+        path: PathBuf,
+        stage_count: usize,
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl TypeUuidProvider for Chunked {
+        fn type_uuid() -> Uuid {
+            uuid!("5a0f7c3e-2b9d-4e61-8c47-d3a1f60e9b25")
+        }
+    }
+
+    impl ResourceData for Chunked {
+        fn path(&self) -> &Path {
+            &self.path
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+
+        fn as_streamable(&self) -> Option<&dyn StreamableResourceData> {
+            Some(self)
+        }
+
+        fn as_streamable_mut(&mut self) -> Option<&mut dyn StreamableResourceData> {
+            Some(self)
+        }
+    }
+
+    impl StreamableResourceData for Chunked {
+        fn stage_count(&self) -> usize {
+            self.stage_count
+        }
+
+        fn load_stage(
+            &self,
+            stage: usize,
+            path: PathBuf,
+            io: Arc<dyn ResourceIo>,
+        ) -> BoxedStageFuture {
+            Box::pin(async move {
+                let chunk_path = path.with_extension(format!("{stage}"));
+                let data = io.load_file(&chunk_path).await.map_err(LoadError::new)?;
+                Ok(Box::new(data) as StageData)
+            })
+        }
+
+        fn commit_stage(&mut self, stage: usize, data: StageData) -> Result<(), LoadError> {
+            assert_eq!(self.chunks.len(), stage);
+            let data = data
+                .downcast::<Vec<u8>>()
+                .map_err(|_| LoadError::new("Invalid stage data."))?;
+            self.chunks.push(*data);
+            Ok(())
+        }
+
+        fn unload_stage(&mut self, stage: usize) -> bool {
+            if stage + 1 == self.chunks.len() {
+                self.chunks.pop();
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    fn wait_for_stages<F>(state: &mut ResourceManagerState, resource: &UntypedResource, done: F)
+    where
+        F: Fn(&[StageState]) -> bool,
+    {
+        let start = Instant::now();
+        while !done(&state.stage_states(resource)) && start.elapsed() < Duration::from_secs(5) {
+            state.update(0.0);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn resource_streaming() {
+        let io = MemoryResourceIo::new();
+        io.add_file("data/sound.1", vec![1]);
+        io.add_file("data/sound.2", vec![2, 2]);
+
+        let mut state = ResourceManagerState::new();
+        state.set_resource_io(Arc::new(io));
+        let (sender, receiver) = channel();
+        state.event_broadcaster.add(sender);
+
+        let resource = UntypedResource::new_ok(Chunked {
+            path: "data/sound.bin".into(),
+            stage_count: 4,
+            chunks: vec![vec![0]],
+        });
+
+        assert!(state.request_stages(&resource, 2));
+        wait_for_stages(&mut state, &resource, |stages| stages[2].is_loaded());
+
+        let stages = state.stage_states(&resource);
+        assert_eq!(stages.len(), 4);
+        assert!(stages[..3].iter().all(|stage| stage.is_loaded()));
+        assert!(matches!(stages[3], StageState::NotLoaded));
+        let events = receiver
+            .try_iter()
+            .filter_map(|event| match event {
+                ResourceEvent::StageLoaded { stage, .. } => Some(stage),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(events, [1, 2]);
+        if let ResourceState::Ok(data) = &*resource.lock() {
+            let data = ResourceData::as_any(&**data)
+                .downcast_ref::<Chunked>()
+                .unwrap();
+            assert_eq!(data.chunks, [vec![0], vec![1], vec![2, 2]]);
+        }
+
+        // There's no file for the last stage.
+        assert!(state.request_stages(&resource, 3));
+        wait_for_stages(&mut state, &resource, |stages| {
+            matches!(stages[3], StageState::Failed(_))
+        });
+        assert!(matches!(
+            state.stage_states(&resource)[3],
+            StageState::Failed(_)
+        ));
+        assert!(receiver
+            .try_iter()
+            .any(|event| matches!(event, ResourceEvent::StageFailed { stage: 3, .. })));
+
+        assert_eq!(state.unload_stages(&resource, 0), 2);
+        let stages = state.stage_states(&resource);
+        assert!(stages[0].is_loaded());
+        assert!(matches!(stages[1], StageState::NotLoaded));
+        assert!(matches!(stages[2], StageState::NotLoaded));
+    }
+
+    #[test]
+    fn non_streamable_resource() {
+        let mut state = ResourceManagerState::new();
+        let resource = UntypedResource::new_pending("foo.bin".into(), Default::default());
+        assert!(!state.request_stages(&resource, 1));
+        assert!(state.stage_states(&resource).is_empty());
+    }
+}
@@ -1,12 +1,317 @@
+//! Asynchronous task execution. Resources are loaded by futures, that are spawned on a
+//! [`TaskExecutor`]. See its docs for more info. CPU-heavy parts of loading (decoding, etc.) could
+//! be moved to background workers using [`run_offloaded`].
+
 #[cfg(not(target_arch = "wasm32"))]
 use crate::core::futures::executor::ThreadPool;
-use std::future::Future;
+use crate::core::{
+    futures::{
+        channel::oneshot,
+        task::{waker, ArcWake},
+    },
+    instant::Instant,
+    parking_lot::{Mutex, RwLock},
+};
+use fxhash::FxHashMap;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    task::Context,
+    time::Duration,
+};
 
-pub struct TaskPool {
-    #[cfg(not(target_arch = "wasm32"))]
+/// A future of a task, that is spawned on a [`TaskExecutor`].
+#[cfg(target_arch = "wasm32")]
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// A future of a task, that is spawned on a [`TaskExecutor`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Task executor is an abstraction over async runtimes, that is used by the resource manager to run
+/// resource loading futures. By default, the resource manager uses [`ThreadPoolTaskExecutor`] on
+/// every platform except WebAssembly, where `LocalTaskExecutor` is used. A custom executor could
+/// be used to run loading on the same runtime as the rest of the game (tokio, async-std, a custom job
+/// system, etc.), see [`crate::manager::ResourceManagerState::set_task_executor`].
+///
+/// An executor must drive every spawned future to completion, otherwise respective resources will
+/// stay in pending state forever.
+///
+/// ## Example
+///
+/// The following executor runs every task on a separate thread. For tokio, it would be just
+/// `self.runtime.spawn(future);` where `runtime` is a `tokio::runtime::Handle`.
+///
+/// ```rust
+/// # use fyrox_resource::{
+/// #     core::futures::executor::block_on,
+/// #     manager::ResourceManager,
+/// #     task::{TaskExecutor, TaskFuture},
+/// # };
+/// # use std::sync::Arc;
+/// struct ThreadPerTaskExecutor;
+///
+/// impl TaskExecutor for ThreadPerTaskExecutor {
+///     fn spawn(&self, future: TaskFuture) {
+///         std::thread::spawn(move || block_on(future));
+///     }
+/// }
+///
+/// let resource_manager = ResourceManager::new();
+/// resource_manager
+///     .state()
+///     .set_task_executor(Arc::new(ThreadPerTaskExecutor));
+/// ```
+pub trait TaskExecutor: Send + Sync {
+    /// Spawns a new task, that will be executed in background.
+    fn spawn(&self, future: TaskFuture);
+
+    /// Called once per frame by [`crate::manager::ResourceManagerState::update`]. Executors, that
+    /// run tasks on the main thread, could use this method to make progress. Does nothing by
+    /// default.
+    fn update(&self) {}
+}
+
+/// Default executor, that runs tasks on a thread pool with a thread per CPU core.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ThreadPoolTaskExecutor {
     thread_pool: ThreadPool,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for ThreadPoolTaskExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ThreadPoolTaskExecutor {
+    /// Creates a new thread pool executor.
+    pub fn new() -> Self {
+        Self {
+            thread_pool: ThreadPool::new().unwrap(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TaskExecutor for ThreadPoolTaskExecutor {
+    fn spawn(&self, future: TaskFuture) {
+        self.thread_pool.spawn_ok(future);
+    }
+}
+
+/// An executor, that runs tasks on the current thread using `wasm_bindgen_futures::spawn_local`.
+/// Tasks are polled as soon as they're woken up, so long loading tasks could block the browser
+/// for a few frames. Use [`CooperativeTaskExecutor`] to limit the time spent on loading per frame.
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub struct LocalTaskExecutor;
+
+#[cfg(target_arch = "wasm32")]
+impl TaskExecutor for LocalTaskExecutor {
+    fn spawn(&self, future: TaskFuture) {
+        crate::core::wasm_bindgen_futures::spawn_local(future);
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+#[derive(Default)]
+struct CooperativeState {
+    tasks: Mutex<FxHashMap<u64, TaskFuture>>,
+    next_task: AtomicU64,
+    ready: Mutex<VecDeque<u64>>,
+}
+
+// SAFETY: Futures are not `Send` on WebAssembly, but without threads support there's a single
+// thread, so neither the futures nor the state are ever accessed from another thread.
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Send for CooperativeState {}
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Sync for CooperativeState {}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+struct CooperativeWaker {
+    state: Arc<CooperativeState>,
+    task: u64,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+impl ArcWake for CooperativeWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.state.ready.lock().push_back(arc_self.task);
+    }
+}
+
+/// An executor, that runs tasks within a time budget per frame (see [`Self::run`]), on the thread,
+/// that calls [`Self::run`]. It is meant for environments, where loading would otherwise take the
+/// whole frame and make the application unresponsive. Tasks are polled one-by-one, so a task that
+/// does a lot of work without yielding could still exceed the budget. Use [`run_offloaded`] to move
+/// heavy work from such tasks to background workers, if the platform has them.
+///
+/// Tasks could be spawned from any thread, the resource manager calls [`Self::run`] using
+/// [`TaskExecutor::update`] every frame (and while blocking waits, such as
+/// [`crate::manager::ResourceManager::request_sync`]). On WebAssembly, the executor is available
+/// only without threads support, because loading futures are not `Send` there.
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+pub struct CooperativeTaskExecutor {
+    state: Arc<CooperativeState>,
+    budget: Duration,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+impl Default for CooperativeTaskExecutor {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(4))
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+impl CooperativeTaskExecutor {
+    /// Creates a new executor with the given time budget per frame.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            state: Default::default(),
+            budget,
+        }
+    }
+
+    /// Returns the time budget per frame.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Polls tasks, that are ready to make progress, until there's no such tasks or the given
+    /// time budget is exceeded. Returns the amount of polls.
+    pub fn run(&self, budget: Duration) -> usize {
+        let start = Instant::now();
+        let mut polls = 0;
+        loop {
+            let Some(task) = self.state.ready.lock().pop_front() else {
+                break;
+            };
+
+            // The task could be woken multiple times, finished already or it could be polled by
+            // another thread right now.
+            let Some(mut future) = self.state.tasks.lock().remove(&task) else {
+                continue;
+            };
+
+            let waker = waker(Arc::new(CooperativeWaker {
+                state: self.state.clone(),
+                task,
+            }));
+            if future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_pending()
+            {
+                self.state.tasks.lock().insert(task, future);
+            }
+            polls += 1;
+
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        polls
+    }
+
+    /// Returns the amount of unfinished tasks, that are not being polled right now.
+    pub fn pending_tasks(&self) -> usize {
+        self.state.tasks.lock().len()
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
+impl TaskExecutor for CooperativeTaskExecutor {
+    fn spawn(&self, future: TaskFuture) {
+        let task = self.state.next_task.fetch_add(1, Ordering::Relaxed);
+        self.state.tasks.lock().insert(task, future);
+        self.state.ready.lock().push_back(task);
+    }
+
+    fn update(&self) {
+        self.run(self.budget);
+    }
+}
+
+/// A function, that runs jobs on background workers. See [`set_job_spawner`] for more info.
+pub type JobSpawner = Arc<dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync>;
+
+fn job_spawner_slot() -> &'static RwLock<Option<JobSpawner>> {
+    static SPAWNER: OnceLock<RwLock<Option<JobSpawner>>> = OnceLock::new();
+    SPAWNER.get_or_init(Default::default)
+}
+
+/// Sets a function, that is used by [`run_offloaded`] to run jobs on background workers. It is
+/// mostly useful on WebAssembly with threads support (when `atomics` target feature is enabled),
+/// where the standard library could not spawn threads by itself, and the spawner could post jobs
+/// to a pool of web workers. The spawner must run every job exactly once, jobs that were dropped
+/// without running are executed on the calling thread.
+pub fn set_job_spawner(spawner: Option<JobSpawner>) {
+    *job_spawner_slot().write() = spawner;
+}
+
+/// Returns current job spawner (if any). See [`set_job_spawner`] for more info.
+pub fn job_spawner() -> Option<JobSpawner> {
+    job_spawner_slot().read().clone()
+}
+
+/// An error, that is returned by [`run_offloaded`] when a background worker started a job, but
+/// did not finish it (the job has panicked, for example).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OffloadError;
+
+impl Display for OffloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "An offloaded job was started, but did not finish!")
+    }
+}
+
+impl Error for OffloadError {}
+
+/// Runs CPU-heavy part of a loading task (decoding, mesh processing, etc.) on a background worker,
+/// using the job spawner (see [`set_job_spawner`]). If there's no job spawner, the job is executed
+/// on the current thread right away. Loading tasks already run on a thread pool on every platform
+/// except WebAssembly, so resource loaders could use this function unconditionally.
+pub async fn run_offloaded<F, R>(job: F) -> Result<R, OffloadError>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let Some(spawner) = job_spawner() else {
+        return Ok(job());
+    };
+
+    let job = Arc::new(Mutex::new(Some(job)));
+    let (sender, receiver) = oneshot::channel();
+    let worker_job = job.clone();
+    spawner(Box::new(move || {
+        if let Some(job) = worker_job.lock().take() {
+            let _ = sender.send(job());
+        }
+    }));
+
+    match receiver.await {
+        Ok(result) => Ok(result),
+        // The job was dropped without running, it is executed here.
+        Err(_) => job.lock().take().map(|job| job()).ok_or(OffloadError),
+    }
+}
+
+pub(crate) struct TaskPool {
+    executor: Arc<dyn TaskExecutor>,
+}
+
 impl Default for TaskPool {
     fn default() -> Self {
         Self::new()
@@ -17,16 +322,26 @@ impl TaskPool {
     pub fn new() -> Self {
         Self {
             #[cfg(not(target_arch = "wasm32"))]
-            thread_pool: ThreadPool::new().unwrap(),
+            executor: Arc::new(ThreadPoolTaskExecutor::new()),
+            #[cfg(target_arch = "wasm32")]
+            executor: Arc::new(LocalTaskExecutor),
         }
     }
 
+    pub fn executor(&self) -> &Arc<dyn TaskExecutor> {
+        &self.executor
+    }
+
+    pub fn set_executor(&mut self, executor: Arc<dyn TaskExecutor>) {
+        self.executor = executor;
+    }
+
     #[cfg(target_arch = "wasm32")]
     pub fn spawn_task<F>(&self, future: F)
     where
         F: Future<Output = ()> + 'static,
     {
-        crate::core::wasm_bindgen_futures::spawn_local(future);
+        self.executor.spawn(Box::pin(future));
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -34,6 +349,88 @@ impl TaskPool {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        self.thread_pool.spawn_ok(future);
+        self.executor.spawn(Box::pin(future));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::futures::executor::block_on;
+
+    #[test]
+    fn cooperative_task_executor() {
+        let executor = CooperativeTaskExecutor::new(Duration::from_secs(10));
+        let (sender, receiver) = oneshot::channel::<u32>();
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        executor.spawn(Box::pin(async move {
+            *task_result.lock() = receiver.await.ok();
+        }));
+        executor.spawn(Box::pin(async {}));
+
+        // Nothing is executed until the executor is run.
+        assert_eq!(executor.pending_tasks(), 2);
+        assert_eq!(executor.run(Duration::from_secs(10)), 2);
+        assert_eq!(executor.pending_tasks(), 1);
+        assert_eq!(executor.run(Duration::from_secs(10)), 0);
+
+        sender.send(42).unwrap();
+        executor.update();
+        assert_eq!(executor.pending_tasks(), 0);
+        assert_eq!(*result.lock(), Some(42));
+
+        // Zero budget allows a single poll per run.
+        executor.spawn(Box::pin(async {}));
+        executor.spawn(Box::pin(async {}));
+        assert_eq!(executor.run(Duration::ZERO), 1);
+        assert_eq!(executor.run(Duration::ZERO), 1);
+    }
+
+    #[test]
+    fn cooperative_task_executor_spawn_from_other_thread() {
+        let executor = Arc::new(CooperativeTaskExecutor::new(Duration::from_secs(10)));
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        let spawner = executor.clone();
+        std::thread::spawn(move || {
+            spawner.spawn(Box::pin(async move {
+                *task_result.lock() = Some(42);
+            }));
+        })
+        .join()
+        .unwrap();
+
+        // Tasks of other threads are polled by the thread, that runs the executor.
+        assert_eq!(executor.pending_tasks(), 1);
+        assert_eq!(executor.run(Duration::from_secs(10)), 1);
+        assert_eq!(executor.pending_tasks(), 0);
+        assert_eq!(*result.lock(), Some(42));
+    }
+
+    #[test]
+    fn offloaded_job() {
+        assert_eq!(block_on(run_offloaded(|| 2 + 2)), Ok(4));
+
+        set_job_spawner(Some(Arc::new(|job| {
+            std::thread::spawn(job);
+        })));
+        assert_eq!(block_on(run_offloaded(|| 3 + 3)), Ok(6));
+
+        // Dropped jobs are executed on the calling thread.
+        set_job_spawner(Some(Arc::new(|_| {})));
+        assert_eq!(block_on(run_offloaded(|| 4 + 4)), Ok(8));
+
+        // A job, that was started, but did not finish.
+        set_job_spawner(Some(Arc::new(|job| {
+            let _ = std::thread::spawn(job).join();
+        })));
+        assert_eq!(
+            block_on(run_offloaded(|| -> i32 { panic!("Job failed") })),
+            Err(OffloadError)
+        );
+
+        set_job_spawner(None);
+        assert!(job_spawner().is_none());
     }
 }
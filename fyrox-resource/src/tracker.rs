@@ -0,0 +1,254 @@
+//! Progress tracking of a set of resource requests. See [`LoadingTracker`] docs for more info.
+
+use crate::{
+    core::pool::Handle,
+    event::{ResourceEvent, ResourceEventBroadcaster, ResourceEventSender},
+    manager::ResourceManager,
+    state::ResourceState,
+    Resource, TypedResourceData, UntypedResource,
+};
+use fxhash::FxHashSet;
+use std::{
+    cell::RefCell,
+    path::Path,
+    sync::mpsc::{self, Receiver},
+};
+
+struct TrackedResource {
+    resource: UntypedResource,
+    weight: f32,
+}
+
+/// Loading tracker aggregates loading progress of a set of resources, which is exactly what is
+/// needed for loading screens. The tracker subscribes to the events of a resource manager, so
+/// resources, that are reported as loaded, are not checked again. Resources, that have failed to
+/// load, are counted as complete too, but they're also reported by [`Self::failed`].
+///
+/// Every resource could have its own weight (see [`Self::add_weighted`]), which allows to make
+/// heavy resources (levels, large textures, etc.) to take bigger part of the progress. Default
+/// weight is `1.0`.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{manager::ResourceManager, tracker::LoadingTracker};
+///
+/// fn start_loading(resource_manager: &ResourceManager) -> LoadingTracker {
+///     let mut tracker = LoadingTracker::new(resource_manager);
+///     tracker.add_weighted(resource_manager.request_untyped("data/level.rgs"), 10.0);
+///     tracker.add(resource_manager.request_untyped("data/music.ogg"));
+///     tracker
+/// }
+///
+/// fn update_loading_screen(tracker: &LoadingTracker) {
+///     println!("Loading... {:.0}%", tracker.fraction_complete() * 100.0);
+///
+///     if tracker.finished() {
+///         for resource in tracker.failed() {
+///             println!("Unable to load {}", resource.path().display());
+///         }
+///     }
+/// }
+/// ```
+pub struct LoadingTracker {
+    resource_manager: ResourceManager,
+    event_broadcaster: ResourceEventBroadcaster,
+    subscription: Handle<ResourceEventSender>,
+    receiver: Receiver<ResourceEvent>,
+    resources: Vec<TrackedResource>,
+    // Keys of resources, that were reported as loaded by the events.
+    loaded: RefCell<FxHashSet<usize>>,
+}
+
+impl LoadingTracker {
+    /// Creates a new tracker, that listens to the events of the given resource manager.
+    pub fn new(resource_manager: &ResourceManager) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        let subscription = event_broadcaster.add(sender);
+        Self {
+            resource_manager: resource_manager.clone(),
+            event_broadcaster,
+            subscription,
+            receiver,
+            resources: Default::default(),
+            loaded: Default::default(),
+        }
+    }
+
+    /// Starts tracking of the given resource with default weight.
+    pub fn add(&mut self, resource: impl Into<UntypedResource>) {
+        self.add_weighted(resource, 1.0)
+    }
+
+    /// Starts tracking of the given resource with the given weight, which defines how much of the
+    /// total progress the resource takes. Negative weights are treated as zero.
+    pub fn add_weighted(&mut self, resource: impl Into<UntypedResource>, weight: f32) {
+        let resource = resource.into();
+        if let Some(tracked) = self
+            .resources
+            .iter_mut()
+            .find(|tracked| tracked.resource == resource)
+        {
+            tracked.weight = weight.max(0.0);
+        } else {
+            self.resources.push(TrackedResource {
+                resource,
+                weight: weight.max(0.0),
+            });
+        }
+    }
+
+    /// Requests a resource at the given path using the resource manager of the tracker and starts
+    /// tracking it.
+    pub fn request<T>(&mut self, path: impl AsRef<Path>) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let resource = self.resource_manager.request::<T>(path);
+        self.add(resource.clone());
+        resource
+    }
+
+    /// Returns the amount of tracked resources.
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns `true` if there's no tracked resources.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Stops tracking of every resource.
+    pub fn clear(&mut self) {
+        self.resources.clear();
+        self.loaded.get_mut().clear();
+    }
+
+    fn process_events(&self) {
+        let mut loaded = self.loaded.borrow_mut();
+        for event in self.receiver.try_iter() {
+            match event {
+                ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded { resource, .. } => {
+                    loaded.insert(resource.key());
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn is_complete(&self, resource: &UntypedResource) -> bool {
+        self.loaded.borrow().contains(&resource.key()) || !resource.is_loading()
+    }
+
+    /// Returns a fraction (in `[0; 1]` range) of the total weight of the resources, that have
+    /// finished loading (either successfully or not). It is `1.0` if there's nothing to track.
+    pub fn fraction_complete(&self) -> f32 {
+        self.process_events();
+        let mut total = 0.0;
+        let mut complete = 0.0;
+        for tracked in self.resources.iter() {
+            total += tracked.weight;
+            if self.is_complete(&tracked.resource) {
+                complete += tracked.weight;
+            }
+        }
+        if total > 0.0 {
+            (complete / total).min(1.0)
+        } else if self.finished() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns every tracked resource, that has failed to load.
+    pub fn failed(&self) -> Vec<UntypedResource> {
+        self.resources
+            .iter()
+            .filter(|tracked| matches!(*tracked.resource.lock(), ResourceState::LoadError { .. }))
+            .map(|tracked| tracked.resource.clone())
+            .collect()
+    }
+
+    /// Returns `true` if every tracked resource has finished loading (either successfully or not).
+    pub fn finished(&self) -> bool {
+        self.process_events();
+        self.resources
+            .iter()
+            .all(|tracked| self.is_complete(&tracked.resource))
+    }
+}
+
+impl Drop for LoadingTracker {
+    fn drop(&mut self) {
+        self.event_broadcaster.remove(self.subscription);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+        ResourceData,
+    };
+    use std::{any::Any, path::PathBuf};
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Stub;
+
+    impl ResourceData for Stub {
+        fn path(&self) -> &Path {
+            Path::new("stub")
+        }
+
+        fn set_path(&mut self, _path: PathBuf) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            Uuid::default()
+        }
+
+        fn is_embedded(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn loading_tracker_progress() {
+        let manager = ResourceManager::new();
+        let mut tracker = LoadingTracker::new(&manager);
+        assert!(tracker.finished());
+        assert_eq!(tracker.fraction_complete(), 1.0);
+
+        let level = UntypedResource::new_pending("level.rgs".into(), Uuid::default());
+        let music = UntypedResource::new_pending("music.ogg".into(), Uuid::default());
+        tracker.add_weighted(level.clone(), 3.0);
+        tracker.add(music.clone());
+        assert_eq!(tracker.len(), 2);
+        assert_eq!(tracker.fraction_complete(), 0.0);
+        assert!(!tracker.finished());
+
+        level.commit_ok(Stub);
+        manager
+            .state()
+            .event_broadcaster
+            .broadcast_loaded(level.clone());
+        assert_eq!(tracker.fraction_complete(), 0.75);
+        assert!(tracker.failed().is_empty());
+
+        music.commit_error("music.ogg".into(), "Corrupted file");
+        assert_eq!(tracker.fraction_complete(), 1.0);
+        assert!(tracker.finished());
+        assert_eq!(tracker.failed(), vec![music]);
+    }
+}
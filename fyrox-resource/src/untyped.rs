@@ -4,18 +4,24 @@ use crate::{
     core::{
         parking_lot::Mutex, reflect::prelude::*, uuid::Uuid, visitor::prelude::*, TypeUuidProvider,
     },
+    event::ResourceEvent,
+    loader,
+    lock::{self, LockedState},
     manager::ResourceManager,
+    protection, retry,
     state::{LoadError, ResourceState},
-    Resource, ResourceData, ResourceLoadError, TypedResourceData,
+    stats, Resource, ResourceCastError, ResourceData, ResourceLoadError, ResourceSaveError,
+    TypedResourceData,
 };
+use fxhash::FxHashMap;
 use std::{
     fmt::{Debug, Formatter},
     future::Future,
     hash::{Hash, Hasher},
     marker::PhantomData,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, OnceLock, Weak},
     task::{Context, Poll},
 };
 
@@ -104,20 +110,38 @@ impl UntypedResource {
         ))))
     }
 
+    /// Locks the state of the resource. Unlike direct locking of the inner mutex, the lock is
+    /// visible to the lock diagnostics, see [`lock::LockDiagnostics`] docs if the call blocks for
+    /// too long.
+    #[inline]
+    pub fn lock(&self) -> LockedState<'_> {
+        lock::lock_state(&self.0)
+    }
+
     /// Returns actual unique type id of underlying resource data.
     pub fn type_uuid(&self) -> Uuid {
-        self.0.lock().type_uuid()
+        self.lock().type_uuid()
     }
 
-    /// Returns true if the resource is still loading.
+    /// Returns true if the resource is still loading. Unloaded resources (see [`Self::unload`])
+    /// are pending, but not loading.
     pub fn is_loading(&self) -> bool {
-        matches!(*self.0.lock(), ResourceState::Pending { .. })
+        matches!(*self.lock(), ResourceState::Pending { .. }) && !self.is_unloaded()
+    }
+
+    /// Returns true if the data of the resource was dropped by [`Self::unload`] and the resource
+    /// wasn't reloaded since then.
+    pub fn is_unloaded(&self) -> bool {
+        unloaded_flags()
+            .lock()
+            .get(&self.key())
+            .is_some_and(|weak| weak.strong_count() > 0)
     }
 
     /// Returns true if the resource is procedural (its data is generated at runtime, not stored in an external
     /// file).
     pub fn is_embedded(&self) -> bool {
-        match *self.0.lock() {
+        match *self.lock() {
             ResourceState::Ok(ref data) => data.is_embedded(),
             // Procedural resources must always be in Ok state.
             _ => false,
@@ -136,9 +160,32 @@ impl UntypedResource {
         (&*self.0 as *const _) as usize
     }
 
+    /// Returns `true` if the resource has the read-only flag. The flag is set by a resource manager
+    /// for the resources it protects, see [`crate::protection::ReadOnlyResources`] docs for more
+    /// info.
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        protection::has_read_only_flag(self.key())
+    }
+
+    /// Saves the resource data to the given path. Unlike [`ResourceData::save`], it returns
+    /// [`ResourceSaveError::ReadOnly`] if the resource has the read-only flag (see
+    /// [`Self::is_read_only`]) and [`ResourceSaveError::NotLoaded`] if the resource is not loaded.
+    pub fn try_save(&self, path: &Path) -> Result<(), ResourceSaveError> {
+        if self.is_read_only() {
+            return Err(ResourceSaveError::ReadOnly);
+        }
+        match *self.lock() {
+            ResourceState::Ok(ref mut data) => data
+                .save(path)
+                .map_err(|err| ResourceSaveError::Save(err.to_string())),
+            _ => Err(ResourceSaveError::NotLoaded),
+        }
+    }
+
     /// Returns path of the untyped resource.
     pub fn path(&self) -> PathBuf {
-        match &*self.0.lock() {
+        match &*self.lock() {
             ResourceState::Pending { path, .. } => path.clone(),
             ResourceState::LoadError { path, .. } => path.clone(),
             ResourceState::Ok(data) => data.path().to_path_buf(),
@@ -147,7 +194,7 @@ impl UntypedResource {
 
     /// Set a new path for the untyped resource.
     pub fn set_path(&self, new_path: PathBuf) {
-        match &mut *self.0.lock() {
+        match &mut *self.lock() {
             ResourceState::Pending { path, .. } => {
                 *path = new_path;
             }
@@ -160,18 +207,35 @@ impl UntypedResource {
         }
     }
 
-    /// Tries to cast untyped resource to a particular type.
+    /// Tries to cast untyped resource to a particular type. Returns `None` if the type of the resource
+    /// does not match `T`. Keep in mind, that the type of a pending resource is the type it was requested
+    /// with. See also [`Self::cast`].
     pub fn try_cast<T>(&self) -> Option<Resource<T>>
     where
         T: TypedResourceData,
     {
-        if self.type_uuid() == <T as TypeUuidProvider>::type_uuid() {
-            Some(Resource {
+        self.cast().ok()
+    }
+
+    /// Same as [`Self::try_cast`], but returns an error, that contains the actual type of the resource,
+    /// if the type of the resource does not match `T`.
+    pub fn cast<T>(&self) -> Result<Resource<T>, ResourceCastError>
+    where
+        T: TypedResourceData,
+    {
+        let expected = <T as TypeUuidProvider>::type_uuid();
+        let actual = self.type_uuid();
+        if actual == expected {
+            Ok(Resource {
                 untyped: self.clone(),
                 phantom: PhantomData::<T>,
             })
         } else {
-            None
+            Err(ResourceCastError {
+                path: self.path(),
+                expected,
+                actual,
+            })
         }
     }
 
@@ -179,17 +243,139 @@ impl UntypedResource {
     /// Additionally it wakes all futures.
     #[inline]
     pub fn commit(&self, state: ResourceState) {
-        self.0.lock().commit(state);
+        if let ResourceState::LoadError { ref error, .. } = state {
+            if loader::defer_load_error(self.key())
+                || error
+                    .0
+                    .as_deref()
+                    .is_some_and(|error| retry::defer_transient_error(self.key(), error))
+            {
+                return;
+            }
+        }
+        self.lock().commit(state);
+        stats::on_resource_committed(self.key());
     }
 
     /// Changes internal state to [`ResourceState::Ok`]
     pub fn commit_ok<T: ResourceData>(&self, data: T) {
-        self.0.lock().commit_ok(data);
+        self.lock().commit_ok(data);
+        stats::on_resource_committed(self.key());
+    }
+
+    /// Replaces data of a fully loaded resource with the given data in-place and returns the old data.
+    /// The new data gets the path of the old data. Every user of the resource will see the new data
+    /// immediately, since they share the same state. After the swap, [`ResourceEvent::DataReplaced`]
+    /// event is sent through the event broadcaster of the given resource manager (and respective
+    /// resource callbacks are called), so systems, that keep derived data (such as GPU buffers), could
+    /// update it. It is useful for procedural systems, that regenerate assets at runtime.
+    ///
+    /// The data is not replaced and returned back, if the resource is not fully loaded, is read-only
+    /// (see [`crate::manager::ResourceManagerState::check_writable`]) or the new data has a different
+    /// type. Keep in mind, that this method locks the state of the resource manager, so it must not
+    /// be called while the state is locked.
+    pub fn replace_data<T: ResourceData>(
+        &self,
+        mut new_data: T,
+        resource_manager: &ResourceManager,
+    ) -> Result<Box<dyn ResourceData>, T> {
+        if self.is_read_only() || resource_manager.state().check_writable(self).is_err() {
+            return Err(new_data);
+        }
+
+        let old_data = {
+            let mut state = self.lock();
+            let ResourceState::Ok(ref mut data) = *state else {
+                return Err(new_data);
+            };
+            if data.type_uuid() != new_data.type_uuid() {
+                return Err(new_data);
+            }
+            new_data.set_path(data.path().to_path_buf());
+            std::mem::replace(data, Box::new(new_data))
+        };
+
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        event_broadcaster.broadcast(ResourceEvent::DataReplaced(self.clone()));
+
+        Ok(old_data)
+    }
+
+    /// Reloads the resource from its source using the given resource manager. The resource is
+    /// switched back to [`ResourceState::Pending`] and a new loading task is spawned, every user of
+    /// the resource will see the new data when the loading is finished. Does nothing, if the
+    /// resource is already loading. Keep in mind, that this method locks the state of the resource
+    /// manager, so it must not be called while the state is locked.
+    pub fn reload(&self, resource_manager: &ResourceManager) {
+        resource_manager.state().reload_resource(self.clone())
+    }
+
+    /// Drops the data of a fully loaded resource, but keeps the resource itself valid, so every
+    /// handle to it stays usable. The resource is switched back to [`ResourceState::Pending`] state
+    /// and stays there until it is loaded again using [`Self::reload`] or requested from the
+    /// resource manager. [`ResourceEvent::Unloaded`] event is sent, so respective caches (GPU
+    /// textures, etc.) could be freed. Returns `true` if the data was dropped. Embedded resources
+    /// are never unloaded, because they cannot be loaded again.
+    pub fn unload(&self, resource_manager: &ResourceManager) -> bool {
+        let (old_state, path, type_uuid) = {
+            let mut state = self.lock();
+            let ResourceState::Ok(ref data) = *state else {
+                return false;
+            };
+            if data.is_embedded() {
+                return false;
+            }
+            let path = data.path().to_path_buf();
+            let type_uuid = data.type_uuid();
+            set_unloaded_flag(self, true);
+            let old_state = std::mem::replace(
+                &mut *state,
+                ResourceState::new_pending(path.clone(), type_uuid),
+            );
+            (old_state, path, type_uuid)
+        };
+        // The data could be heavy, so it is dropped when the lock is released.
+        drop(old_state);
+
+        let event_broadcaster = resource_manager.state().event_broadcaster.clone();
+        event_broadcaster.broadcast(ResourceEvent::Unloaded {
+            path,
+            type_uuid,
+            key: self.key(),
+        });
+        true
     }
 
     /// Changes internal state to [`ResourceState::LoadError`].
+    ///
+    /// The error is not committed, if there are other resource loaders to try (see
+    /// [`crate::loader::ResourceLoadersContainer`] for more info).
     pub fn commit_error<E: ResourceLoadError>(&self, path: PathBuf, error: E) {
-        self.0.lock().commit_error(path, error);
+        if loader::defer_load_error(self.key()) || retry::defer_transient_error(self.key(), &error)
+        {
+            return;
+        }
+        self.lock().commit_error(path, error);
+        stats::on_resource_committed(self.key());
+    }
+}
+
+fn unloaded_flags() -> &'static Mutex<FxHashMap<usize, Weak<Mutex<ResourceState>>>> {
+    static FLAGS: OnceLock<Mutex<FxHashMap<usize, Weak<Mutex<ResourceState>>>>> = OnceLock::new();
+    FLAGS.get_or_init(Default::default)
+}
+
+/// Sets or removes the flag, that tells that the resource was unloaded (see
+/// [`UntypedResource::unload`]). Returns `true` if the flag was set before.
+pub(crate) fn set_unloaded_flag(resource: &UntypedResource, unloaded: bool) -> bool {
+    let mut flags = unloaded_flags().lock();
+    flags.retain(|_, weak| weak.strong_count() > 0);
+    if unloaded {
+        flags
+            .insert(resource.key(), Arc::downgrade(&resource.0))
+            .is_some()
+    } else {
+        flags.remove(&resource.key()).is_some()
     }
 }
 
@@ -285,8 +471,8 @@ mod test {
     fn untyped_resource_new_pending() {
         let r = UntypedResource::new_pending(PathBuf::from("/foo"), Uuid::default());
 
-        assert_eq!(r.0.lock().type_uuid(), Uuid::default());
-        assert_eq!(r.0.lock().path(), PathBuf::from("/foo"));
+        assert_eq!(r.lock().type_uuid(), Uuid::default());
+        assert_eq!(r.lock().path(), PathBuf::from("/foo"));
     }
 
     #[test]
@@ -297,8 +483,8 @@ mod test {
             Uuid::default(),
         );
 
-        assert_eq!(r.0.lock().type_uuid(), Uuid::default());
-        assert_eq!(r.0.lock().path(), PathBuf::from("/foo"));
+        assert_eq!(r.lock().type_uuid(), Uuid::default());
+        assert_eq!(r.lock().path(), PathBuf::from("/foo"));
     }
 
     #[test]
@@ -306,8 +492,8 @@ mod test {
         let s = Stub {};
         let r = UntypedResource::new_ok(s);
 
-        assert_eq!(r.0.lock().type_uuid(), s.type_uuid());
-        assert_eq!(r.0.lock().path(), s.path());
+        assert_eq!(r.lock().type_uuid(), s.type_uuid());
+        assert_eq!(r.lock().path(), s.path());
     }
 
     #[test]
@@ -382,6 +568,16 @@ mod test {
 
         assert!(r.try_cast::<Stub>().is_some());
         assert!(r2.try_cast::<Stub>().is_none());
+
+        assert!(r.cast::<Stub>().is_ok());
+        assert_eq!(
+            r2.cast::<Stub>().unwrap_err(),
+            ResourceCastError {
+                path: PathBuf::from("/foo"),
+                expected: Uuid::default(),
+                actual: Uuid::from_u128(0xa1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8u128),
+            }
+        );
     }
 
     #[test]
@@ -390,12 +586,12 @@ mod test {
         let stub = Stub {};
 
         let r = UntypedResource::new_pending(path.clone(), Default::default());
-        assert_eq!(r.0.lock().path(), path);
-        assert_ne!(r.0.lock().path(), stub.path());
+        assert_eq!(r.lock().path(), path);
+        assert_ne!(r.lock().path(), stub.path());
 
         r.commit(ResourceState::Ok(Box::new(stub)));
-        assert_ne!(r.0.lock().path(), path);
-        assert_eq!(r.0.lock().path(), stub.path());
+        assert_ne!(r.lock().path(), path);
+        assert_eq!(r.lock().path(), stub.path());
     }
 
     #[test]
@@ -404,12 +600,12 @@ mod test {
         let stub = Stub {};
 
         let r = UntypedResource::new_pending(path.clone(), Default::default());
-        assert_eq!(r.0.lock().path(), path);
-        assert_ne!(r.0.lock().path(), stub.path());
+        assert_eq!(r.lock().path(), path);
+        assert_ne!(r.lock().path(), stub.path());
 
         r.commit_ok(stub);
-        assert_ne!(r.0.lock().path(), path);
-        assert_eq!(r.0.lock().path(), stub.path());
+        assert_ne!(r.lock().path(), path);
+        assert_eq!(r.lock().path(), stub.path());
     }
 
     #[test]
@@ -418,12 +614,12 @@ mod test {
         let path2 = PathBuf::from("/bar");
 
         let r = UntypedResource::new_pending(path.clone(), Default::default());
-        assert_eq!(r.0.lock().path(), path);
-        assert_ne!(r.0.lock().path(), path2);
+        assert_eq!(r.lock().path(), path);
+        assert_ne!(r.lock().path(), path2);
 
         r.commit_error(path2.clone(), "error");
-        assert_ne!(r.0.lock().path(), path);
-        assert_eq!(r.0.lock().path(), path2);
+        assert_ne!(r.lock().path(), path);
+        assert_eq!(r.lock().path(), path2);
     }
 
     #[test]
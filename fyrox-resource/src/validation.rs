@@ -0,0 +1,178 @@
+//! Project-wide resource validation. See [`ProjectValidationReport`] docs for more info.
+
+use crate::core::uuid::Uuid;
+use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+/// A resource, that is referenced by other resources, but does not exist.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingResource {
+    /// Path of the missing resource.
+    pub path: PathBuf,
+    /// Paths of the resources, that reference the missing resource.
+    pub referenced_by: Vec<PathBuf>,
+}
+
+/// A resource, which data type does not match the type expected for it. It happens when a resource
+/// is loaded by a loader, that produces data of some other type than it declares, or when the same
+/// path is used for resources of different types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeMismatch {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// Type UUIDs, that are expected for the resource (declared by the loaders of its extension).
+    pub expected: Vec<Uuid>,
+    /// Every type UUID, the resource is used with.
+    pub actual: Vec<Uuid>,
+}
+
+/// A resource, that failed to load.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailedResource {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// Reason of the failure.
+    pub reason: String,
+}
+
+/// A result of [`crate::manager::ResourceManager::validate_project`]. The validation loads every
+/// resource of a project (every file, that has a loader registered for its extension), collects
+/// dependencies of a resource using [`crate::collect_used_resources`] and checks them. The report
+/// could be used by CI to catch broken assets before a build is shipped, or it could be shown
+/// in the editor:
+///
+/// ```rust,no_run
+/// # use fyrox_resource::manager::ResourceManager;
+/// # use fyrox_resource::core::futures::executor::block_on;
+/// # let resource_manager = ResourceManager::new();
+/// let report = block_on(resource_manager.validate_project("data"));
+/// if !report.is_ok() {
+///     eprintln!("{report}");
+///     std::process::exit(1);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectValidationReport {
+    /// Paths of every resource, that was checked.
+    pub checked: Vec<PathBuf>,
+    /// Resources, that failed to load.
+    pub failed: Vec<FailedResource>,
+    /// Resources, that are referenced, but do not exist.
+    pub missing: Vec<MissingResource>,
+    /// Resources, which types do not match the expected types.
+    pub type_mismatches: Vec<TypeMismatch>,
+    /// Cyclic dependencies. Every cycle is a list of paths, where each resource depends on the next
+    /// one and the last one depends on the first one.
+    pub cycles: Vec<Vec<PathBuf>>,
+}
+
+impl ProjectValidationReport {
+    /// Returns `true` if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.problem_count() == 0
+    }
+
+    /// Returns total amount of found problems.
+    pub fn problem_count(&self) -> usize {
+        self.failed.len() + self.missing.len() + self.type_mismatches.len() + self.cycles.len()
+    }
+}
+
+impl Display for ProjectValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Checked {} resources.", self.checked.len())?;
+        for failed in self.failed.iter() {
+            writeln!(
+                f,
+                "Failed to load {}: {}",
+                failed.path.display(),
+                failed.reason
+            )?;
+        }
+        for missing in self.missing.iter() {
+            let referenced_by = missing
+                .referenced_by
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "Missing {}, referenced by: {}",
+                missing.path.display(),
+                referenced_by.join(", ")
+            )?;
+        }
+        for mismatch in self.type_mismatches.iter() {
+            writeln!(
+                f,
+                "Type mismatch of {}: expected {:?}, actual {:?}",
+                mismatch.path.display(),
+                mismatch.expected,
+                mismatch.actual
+            )?;
+        }
+        for cycle in self.cycles.iter() {
+            let cycle = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            writeln!(f, "Cyclic dependency: {}", cycle.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds cyclic dependencies using a depth-first search. Every group of mutually dependent resources
+/// is reported at least once. Every cycle is rotated so it starts from its smallest path, which
+/// makes the result independent of the search order.
+pub(crate) fn find_cycles(graph: &FxHashMap<PathBuf, Vec<PathBuf>>) -> Vec<Vec<PathBuf>> {
+    fn visit<'a>(
+        node: &'a PathBuf,
+        graph: &'a FxHashMap<PathBuf, Vec<PathBuf>>,
+        stack: &mut Vec<&'a PathBuf>,
+        finished: &mut FxHashSet<&'a PathBuf>,
+        cycles: &mut FxHashSet<Vec<PathBuf>>,
+    ) {
+        if let Some(position) = stack.iter().position(|visited| *visited == node) {
+            let mut cycle = stack[position..]
+                .iter()
+                .map(|path| (*path).clone())
+                .collect::<Vec<_>>();
+            let smallest = (0..cycle.len())
+                .min_by_key(|i| &cycle[*i])
+                .unwrap_or_default();
+            cycle.rotate_left(smallest);
+            cycles.insert(cycle);
+            return;
+        }
+
+        if finished.contains(node) {
+            return;
+        }
+
+        stack.push(node);
+        for dependency in graph.get(node).into_iter().flatten() {
+            visit(dependency, graph, stack, finished, cycles);
+        }
+        stack.pop();
+
+        finished.insert(node);
+    }
+
+    let mut roots = graph.keys().collect::<Vec<_>>();
+    roots.sort();
+
+    let mut finished = FxHashSet::default();
+    let mut cycles = FxHashSet::default();
+    for root in roots {
+        visit(root, graph, &mut Vec::new(), &mut finished, &mut cycles);
+    }
+
+    let mut cycles = cycles.into_iter().collect::<Vec<_>>();
+    cycles.sort();
+    cycles
+}
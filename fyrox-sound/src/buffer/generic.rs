@@ -25,8 +25,41 @@
 
 use crate::{buffer::DataSource, decoder::Decoder};
 use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use serde::{Deserialize, Serialize};
 use std::{path::Path, path::PathBuf, time::Duration};
 
+/// A region of a buffer, that is repeated when a looping sound reaches its end. It allows music with
+/// an intro to loop seamlessly: the intro (everything before [`Self::start`]) is played only once
+/// and then the playback jumps from [`Self::end`] to [`Self::start`].
+///
+/// Both positions are given in samples per channel (frames).
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Visit, Reflect, Serialize, Deserialize,
+)]
+pub struct LoopPoints {
+    /// Position of the first sample of the loop region.
+    pub start: usize,
+    /// Position right after the last sample of the loop region (exclusive).
+    pub end: usize,
+}
+
+impl LoopPoints {
+    /// Creates new loop region.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the length of the loop region in samples per channel.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns `true` if the loop region has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Generic sound buffer that contains decoded samples and allows random access.
 #[derive(Debug, Default, Visit, Reflect)]
 pub struct GenericBuffer {
@@ -46,6 +79,8 @@ pub struct GenericBuffer {
     #[visit(optional)]
     #[visit(rename = "IsProcedural")]
     pub(crate) is_embedded: bool,
+    #[visit(optional)]
+    pub(crate) loop_points: Option<LoopPoints>,
 }
 
 impl GenericBuffer {
@@ -80,6 +115,7 @@ impl GenericBuffer {
                         sample_rate,
                         external_source_path: Default::default(),
                         is_embedded: true,
+                        loop_points: None,
                     })
                 }
             }
@@ -115,14 +151,18 @@ impl GenericBuffer {
                     }
                 }
 
-                Ok(Self {
+                let mut buffer = Self {
                     sample_rate: decoder.get_sample_rate(),
                     channel_count: decoder.get_channel_count(),
                     channel_duration_in_samples: decoder.channel_duration_in_samples(),
-                    samples: decoder.into_samples(),
+                    loop_points: None,
                     external_source_path,
                     is_embedded,
-                })
+                    samples: Default::default(),
+                };
+                buffer.set_loop_points(decoder.loop_points());
+                buffer.samples = decoder.into_samples();
+                Ok(buffer)
             }
         }
     }
@@ -183,4 +223,25 @@ impl GenericBuffer {
     pub fn channel_duration_in_samples(&self) -> usize {
         self.channel_duration_in_samples
     }
+
+    /// Sets new loop region of the buffer. Looping sound sources, that use the buffer, will jump
+    /// from the end of the region to its start instead of restarting from the beginning. The end of
+    /// the region is clamped to the length of the buffer, empty regions are discarded. Loop points
+    /// of a buffer, that is loaded from a file, are taken from the file (`smpl` chunk of WAV files,
+    /// `LOOPSTART`/`LOOPLENGTH`/`LOOPEND` comments of Ogg/Vorbis files) or from the import options
+    /// of the buffer.
+    pub fn set_loop_points(&mut self, loop_points: Option<LoopPoints>) {
+        self.loop_points = loop_points
+            .map(|points| LoopPoints {
+                start: points.start,
+                end: points.end.min(self.channel_duration_in_samples),
+            })
+            .filter(|points| !points.is_empty());
+    }
+
+    /// Returns current loop region of the buffer, if any.
+    #[inline]
+    pub fn loop_points(&self) -> Option<LoopPoints> {
+        self.loop_points
+    }
 }
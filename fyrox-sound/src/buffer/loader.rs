@@ -1,6 +1,6 @@
 //! Sound buffer loader.
 
-use crate::buffer::{DataSource, SoundBuffer, SoundBufferResourceLoadError};
+use crate::buffer::{generic::LoopPoints, DataSource, SoundBuffer, SoundBufferResourceLoadError};
 use fyrox_core::{log::Log, reflect::prelude::*, uuid::Uuid, TypeUuidProvider};
 use fyrox_resource::options::BaseImportOptions;
 use fyrox_resource::{
@@ -18,6 +18,10 @@ use std::{path::PathBuf, sync::Arc};
 pub struct SoundBufferImportOptions {
     /// Whether the buffer is streaming or not.
     pub stream: bool,
+    /// Loop region of the buffer (in samples per channel). Overrides loop points, that are stored
+    /// in the file itself. See [`LoopPoints`] docs for more info.
+    #[serde(default)]
+    pub loop_points: Option<LoopPoints>,
 }
 
 impl ImportOptions for SoundBufferImportOptions {}
@@ -63,7 +67,11 @@ impl ResourceLoader for SoundBufferLoader {
                         SoundBuffer::raw_generic(source)
                     };
                     match buffer {
-                        Ok(sound_buffer) => {
+                        Ok(mut sound_buffer) => {
+                            if import_options.loop_points.is_some() {
+                                sound_buffer.set_loop_points(import_options.loop_points);
+                            }
+
                             resource.commit_ok(sound_buffer);
 
                             event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
@@ -268,4 +268,9 @@ impl ResourceData for SoundBuffer {
     fn is_embedded(&self) -> bool {
         self.is_embedded
     }
+
+    fn memory_usage(&self) -> usize {
+        // Streaming buffers hold only a small portion of decoded samples at a time.
+        std::mem::size_of::<Self>() + std::mem::size_of_val(self.samples.as_slice())
+    }
 }
@@ -28,7 +28,10 @@
 //! buffer that already in use you'll get error.
 
 use crate::{
-    buffer::{generic::GenericBuffer, DataSource, RawStreamingDataSource},
+    buffer::{
+        generic::{GenericBuffer, LoopPoints},
+        DataSource, RawStreamingDataSource,
+    },
     decoder::Decoder,
     error::SoundError,
 };
@@ -104,6 +107,13 @@ impl StreamingSource {
         }
     }
 
+    fn loop_points(&self) -> Option<LoopPoints> {
+        match self {
+            StreamingSource::Decoder(decoder) => decoder.loop_points(),
+            _ => None,
+        }
+    }
+
     fn rewind(&mut self) -> Result<(), SoundError> {
         match self {
             StreamingSource::Null => Ok(()),
@@ -176,15 +186,19 @@ impl StreamingBuffer {
         streaming_source.read_next_samples_block_into(&mut samples);
         debug_assert_eq!(samples.len() % channel_count, 0);
 
+        let mut generic = GenericBuffer {
+            samples,
+            sample_rate: streaming_source.sample_rate(),
+            channel_count: streaming_source.channel_count(),
+            channel_duration_in_samples: streaming_source.channel_duration_in_samples(),
+            external_source_path,
+            is_embedded,
+            loop_points: None,
+        };
+        generic.set_loop_points(streaming_source.loop_points());
+
         Ok(Self {
-            generic: GenericBuffer {
-                samples,
-                sample_rate: streaming_source.sample_rate(),
-                channel_count: streaming_source.channel_count(),
-                channel_duration_in_samples: streaming_source.channel_duration_in_samples(),
-                external_source_path,
-                is_embedded,
-            },
+            generic,
             use_count: 0,
             streaming_source,
         })
@@ -205,6 +219,17 @@ impl StreamingBuffer {
     pub(crate) fn time_seek(&mut self, location: Duration) {
         self.streaming_source.time_seek(location);
     }
+
+    /// Moves the decoder to the given sample (per channel). Precision of the seek depends on the
+    /// decoder: WAV files are sample-exact, Ogg/Vorbis files can only be seeked to a page boundary.
+    #[inline]
+    pub(crate) fn sample_seek(&mut self, sample: usize) {
+        // Aim at the middle of the sample, so the decoder won't end up at the previous sample
+        // because of rounding errors.
+        self.time_seek(Duration::from_secs_f64(
+            (sample as f64 + 0.5) / self.generic.sample_rate.max(1) as f64,
+        ));
+    }
 }
 
 impl Deref for StreamingBuffer {
@@ -264,6 +264,10 @@ impl State {
 
             self.bus_graph.begin_render(output_device_buffer.len());
 
+            if let Renderer::HrtfRenderer(ref mut hrtf_renderer) = self.renderer {
+                hrtf_renderer.begin_frame(&self.listener);
+            }
+
             // Render sounds to respective audio buses.
             for source in self
                 .sources
@@ -1,5 +1,5 @@
 use crate::{
-    buffer::DataSource,
+    buffer::{generic::LoopPoints, DataSource},
     decoder::{vorbis::OggDecoder, wav::WavDecoder},
     error::SoundError,
 };
@@ -78,4 +78,11 @@ impl Decoder {
             Decoder::Ogg(ogg) => ogg.channel_duration_in_samples(),
         }
     }
+
+    pub fn loop_points(&self) -> Option<LoopPoints> {
+        match self {
+            Decoder::Wav(wav) => wav.loop_points,
+            Decoder::Ogg(ogg) => ogg.loop_points,
+        }
+    }
 }
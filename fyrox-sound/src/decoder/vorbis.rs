@@ -1,4 +1,7 @@
-use crate::{buffer::DataSource, error::SoundError};
+use crate::{
+    buffer::{generic::LoopPoints, DataSource},
+    error::SoundError,
+};
 use lewton::{inside_ogg::read_headers, inside_ogg::OggStreamReader, samples::InterleavedSamples};
 use ogg::PacketReader;
 use std::{
@@ -17,6 +20,7 @@ pub struct OggDecoder {
     pub channel_count: usize,
     pub sample_rate: usize,
     pub channel_duration_in_samples: usize,
+    pub loop_points: Option<LoopPoints>,
 }
 
 impl Debug for OggDecoder {
@@ -81,6 +85,24 @@ fn total_duration_in_samples(source: &mut DataSource) -> usize {
     }
 }
 
+// Reads loop points from `LOOPSTART` and `LOOPLENGTH` (or `LOOPEND`) comments. This is de-facto
+// standard of many game engines and audio editors. Loop end is the end of the file, if there's
+// only `LOOPSTART` comment.
+fn read_loop_points(comments: &[(String, String)], total: usize) -> Option<LoopPoints> {
+    let find = |name: &str| {
+        comments
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+    };
+    let start = find("LOOPSTART")?;
+    let end = find("LOOPLENGTH")
+        .map(|length| start + length)
+        .or_else(|| find("LOOPEND"))
+        .unwrap_or(total);
+    Some(LoopPoints::new(start, end))
+}
+
 impl OggDecoder {
     pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
         if is_vorbis_ogg(&mut source) {
@@ -97,6 +119,10 @@ impl OggDecoder {
             };
 
             Ok(Self {
+                loop_points: read_loop_points(
+                    &reader.comment_hdr.comment_list,
+                    channel_duration_in_samples,
+                ),
                 samples,
                 channel_count: reader.ident_hdr.audio_channels as usize,
                 sample_rate: reader.ident_hdr.audio_sample_rate as usize,
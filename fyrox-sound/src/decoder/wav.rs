@@ -1,4 +1,7 @@
-use crate::{buffer::DataSource, error::SoundError};
+use crate::{
+    buffer::{generic::LoopPoints, DataSource},
+    error::SoundError,
+};
 use hound::WavReader;
 use std::{
     fmt::{Debug, Formatter},
@@ -10,6 +13,7 @@ use std::{
 /// Wav decoder
 pub(crate) struct WavDecoder {
     reader: WavReader<DataSource>,
+    pub loop_points: Option<LoopPoints>,
 }
 
 impl Debug for WavDecoder {
@@ -44,9 +48,61 @@ impl Seek for WrappedDataSource {
     }
 }
 
+fn read_u32(source: &mut impl Read) -> Option<u32> {
+    let mut bytes = [0; 4];
+    source.read_exact(&mut bytes).ok()?;
+    Some(u32::from_le_bytes(bytes))
+}
+
+// Reads the first loop of the `smpl` chunk (if any). `hound` skips unknown chunks, so the chunks
+// are scanned manually. The position of the source is restored afterwards.
+fn read_loop_points(source: &mut DataSource) -> Option<LoopPoints> {
+    let pos = source.stream_position().ok()?;
+    let result = (|| {
+        let mut tag = [0; 4];
+        source.read_exact(&mut tag).ok()?;
+        if &tag != b"RIFF" {
+            return None;
+        }
+        read_u32(source)?;
+        source.read_exact(&mut tag).ok()?;
+        if &tag != b"WAVE" {
+            return None;
+        }
+        loop {
+            source.read_exact(&mut tag).ok()?;
+            let size = read_u32(source)?;
+            if &tag == b"smpl" && size >= 36 {
+                // Skip manufacturer, product, sample period, MIDI unity note, MIDI pitch fraction,
+                // SMPTE format and SMPTE offset.
+                source.seek(SeekFrom::Current(28)).ok()?;
+                let loop_count = read_u32(source)?;
+                // Sampler data.
+                read_u32(source)?;
+                if loop_count == 0 || size < 36 + 24 {
+                    return None;
+                }
+                // Cue point id and loop type.
+                source.seek(SeekFrom::Current(8)).ok()?;
+                let start = read_u32(source)? as usize;
+                // End of a loop is inclusive in WAV files.
+                let end = read_u32(source)? as usize + 1;
+                return Some(LoopPoints::new(start, end));
+            }
+            // Chunks are padded to an even size.
+            source
+                .seek(SeekFrom::Current((size as i64 + 1) & !1))
+                .ok()?;
+        }
+    })();
+    source.seek(SeekFrom::Start(pos)).ok()?;
+    result
+}
+
 impl WavDecoder {
     pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
         let pos = source.stream_position().unwrap();
+        let loop_points = read_loop_points(&mut source);
         let mut wrapped_source = WrappedDataSource {
             data_source: Arc::new(Mutex::new(source)),
         };
@@ -66,7 +122,10 @@ impl WavDecoder {
             }
         };
 
-        Ok(Self { reader })
+        Ok(Self {
+            reader,
+            loop_points,
+        })
     }
 
     pub fn rewind(&mut self) -> Result<(), SoundError> {
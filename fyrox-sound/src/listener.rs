@@ -5,6 +5,7 @@
 //! Engine has only one listener which can be positioned and oriented in space. Listener defined as coordinate
 //! system which is used to compute spatial properties of sound sources.
 
+use crate::renderer::hrtf::HrirSphereResource;
 use fyrox_core::{
     algebra::{Matrix3, Vector3},
     math::Matrix3Ext,
@@ -17,6 +18,8 @@ use fyrox_core::{
 pub struct Listener {
     basis: Matrix3<f32>,
     position: Vector3<f32>,
+    #[visit(optional)]
+    hrir_sphere: Option<HrirSphereResource>,
 }
 
 impl Default for Listener {
@@ -30,9 +33,22 @@ impl Listener {
         Self {
             basis: Matrix3::identity(),
             position: Vector3::new(0.0, 0.0, 0.0),
+            hrir_sphere: None,
         }
     }
 
+    /// Sets a HRIR sphere, that will be used to render sounds for this listener, instead of the HRIR sphere of the
+    /// HRTF renderer. It allows to use a sphere, that suits the player the best. It has no effect if the context does
+    /// not use the HRTF renderer. See [`crate::renderer::hrtf`] module docs for more info.
+    pub fn set_hrir_sphere_resource(&mut self, resource: Option<HrirSphereResource>) {
+        self.hrir_sphere = resource;
+    }
+
+    /// Returns a HRIR sphere of the listener (if any).
+    pub fn hrir_sphere_resource(&self) -> Option<&HrirSphereResource> {
+        self.hrir_sphere.as_ref()
+    }
+
     /// Sets new basis from given vectors in left-handed coordinate system.
     /// See `set_basis` for more info.
     pub fn set_orientation_lh(&mut self, look: Vector3<f32>, up: Vector3<f32>) {
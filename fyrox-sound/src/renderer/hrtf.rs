@@ -51,6 +51,20 @@
 //! [here](http://csoundjournal.com/issue9/newHRTFOpcodes.html)
 //!
 //! Clicks can be reproduced by using clean sine wave of 440 Hz on some source moving around listener.
+//!
+//! # Switching HRIR spheres
+//!
+//! Since HRTF is very specific to each person, a game could let players choose a HRIR sphere that suits them best.
+//! A sphere could be changed at runtime either for the renderer ([`HrtfRenderer::set_hrir_sphere_resource`]) or
+//! for the listener ([`Listener::set_hrir_sphere_resource`]), the latter has priority over the former. The renderer
+//! crossfades between the old and the new sphere within a single frame to prevent clicks.
+//!
+//! # Custom HRIR data
+//!
+//! HRIR data is often distributed in other formats, such as [SOFA](https://www.sofaconventions.org). Such data could
+//! be converted to a HRIR sphere using [`hrir_sphere_from_measurements`], all that is needed is a list of measured
+//! impulse responses for both ears and a direction of each measurement. [`HrirMeasurementsLoader`] allows the resource
+//! manager to load such files directly, using a decoder provided by the user.
 
 use crate::{
     context::{self, DistanceModel, SoundContext},
@@ -59,6 +73,8 @@ use crate::{
     source::SoundSource,
 };
 use fyrox_core::{
+    algebra::Vector3,
+    byteorder::{LittleEndian, WriteBytesExt},
     log::Log,
     reflect::prelude::*,
     uuid::{uuid, Uuid},
@@ -72,11 +88,12 @@ use fyrox_resource::{
     untyped::UntypedResource,
     Resource, ResourceData, ResourceStateRef,
 };
-use hrtf::HrirSphere;
+use hrtf::{HrirSphere, HrtfError};
 use std::{
     any::Any,
     fmt::Debug,
     fmt::Formatter,
+    io::Cursor,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -87,6 +104,16 @@ pub struct HrtfRenderer {
     hrir_resource: Option<HrirSphereResource>,
     #[reflect(hidden)]
     processor: Option<hrtf::HrtfProcessor>,
+    /// A resource, that was used to create current processor.
+    #[reflect(hidden)]
+    active_resource: Option<HrirSphereResource>,
+    /// A processor of previously used HRIR sphere, that is being faded out during current frame.
+    #[reflect(hidden)]
+    fading_processor: Option<hrtf::HrtfProcessor>,
+    #[reflect(hidden)]
+    fade_out_buffer: Vec<(f32, f32)>,
+    #[reflect(hidden)]
+    fade_in_buffer: Vec<(f32, f32)>,
 }
 
 impl Visit for HrtfRenderer {
@@ -111,15 +138,19 @@ impl HrtfRenderer {
                 SoundContext::HRTF_INTERPOLATION_STEPS,
                 SoundContext::HRTF_BLOCK_LEN,
             )),
+            active_resource: Some(hrir_sphere_resource.clone()),
             hrir_resource: Some(hrir_sphere_resource),
+            fading_processor: None,
+            fade_out_buffer: Default::default(),
+            fade_in_buffer: Default::default(),
         }
     }
 
-    /// Sets a desired HRIR sphere resource. Current state of the renderer will be reset and then it will be recreated
-    /// on the next render call only if the resource is fully loaded.
+    /// Sets a desired HRIR sphere resource. The renderer will switch to the new sphere on the next render call, when
+    /// the resource is fully loaded, and will crossfade from the previous sphere. Keep in mind, that a HRIR sphere of
+    /// the listener (if any) has priority over the sphere of the renderer (see [`Listener::set_hrir_sphere_resource`]).
     pub fn set_hrir_sphere_resource(&mut self, resource: Option<HrirSphereResource>) {
         self.hrir_resource = resource;
-        self.processor = None;
     }
 
     /// Returns current HRIR sphere resource (if any).
@@ -127,6 +158,50 @@ impl HrtfRenderer {
         self.hrir_resource.clone()
     }
 
+    /// Returns `true` if the renderer is crossfading between two HRIR spheres during current frame.
+    pub fn is_crossfading(&self) -> bool {
+        self.fading_processor.is_some()
+    }
+
+    /// Must be called once per frame before rendering any source. Switches the processor of the renderer when the
+    /// desired HRIR sphere has changed.
+    pub(crate) fn begin_frame(&mut self, listener: &Listener) {
+        // Crossfade lasts a single frame.
+        self.fading_processor = None;
+
+        let desired = listener
+            .hrir_sphere_resource()
+            .or(self.hrir_resource.as_ref());
+
+        if desired == self.active_resource.as_ref() && self.processor.is_some() {
+            return;
+        }
+
+        match desired {
+            Some(resource) => {
+                // Re-create HRTF processor on the fly only when a respective HRIR sphere resource is fully loaded.
+                // This is a poor-man's async support for crippled OSes such as WebAssembly. Previous processor
+                // is used until then.
+                let state = resource.state();
+                if let ResourceStateRef::Ok(hrir) = state.get() {
+                    if let Some(sphere) = hrir.hrir_sphere.clone() {
+                        let processor = hrtf::HrtfProcessor::new(
+                            sphere,
+                            SoundContext::HRTF_INTERPOLATION_STEPS,
+                            SoundContext::HRTF_BLOCK_LEN,
+                        );
+                        self.fading_processor = self.processor.replace(processor);
+                        self.active_resource = Some(resource.clone());
+                    }
+                }
+            }
+            None => {
+                self.fading_processor = self.processor.take();
+                self.active_resource = None;
+            }
+        }
+    }
+
     pub(crate) fn render_source(
         &mut self,
         source: &mut SoundSource,
@@ -134,21 +209,6 @@ impl HrtfRenderer {
         distance_model: DistanceModel,
         out_buf: &mut [(f32, f32)],
     ) {
-        // Re-create HRTF processor on the fly only when a respective HRIR sphere resource is fully loaded.
-        // This is a poor-man's async support for crippled OSes such as WebAssembly.
-        if self.processor.is_none() {
-            if let Some(resource) = self.hrir_resource.as_ref() {
-                let state = resource.state();
-                if let ResourceStateRef::Ok(hrir) = state.get() {
-                    self.processor = Some(hrtf::HrtfProcessor::new(
-                        hrir.hrir_sphere.clone().unwrap(),
-                        SoundContext::HRTF_INTERPOLATION_STEPS,
-                        SoundContext::HRTF_BLOCK_LEN,
-                    ));
-                }
-            }
-        }
-
         // Render as 2D first with k = (1.0 - spatial_blend).
         render_source_2d_only(source, out_buf);
 
@@ -157,24 +217,74 @@ impl HrtfRenderer {
             * source.spatial_blend()
             * source.calculate_distance_gain(listener, distance_model);
         let new_sampling_vector = source.calculate_sampling_vector(listener);
+        let new_sample_vector = hrtf::Vec3::new(
+            new_sampling_vector.x,
+            new_sampling_vector.y,
+            new_sampling_vector.z,
+        );
+        let prev_sample_vector = hrtf::Vec3::new(
+            source.prev_sampling_vector.x,
+            source.prev_sampling_vector.y,
+            source.prev_sampling_vector.z,
+        );
+        let prev_distance_gain = source.prev_distance_gain.unwrap_or(new_distance_gain);
+
+        if let Some(fading_processor) = self.fading_processor.as_mut() {
+            let fade_out = &mut self.fade_out_buffer;
+            let fade_in = &mut self.fade_in_buffer;
+            for buffer in [&mut *fade_out, &mut *fade_in] {
+                buffer.clear();
+                buffer.resize(out_buf.len(), (0.0, 0.0));
+            }
+
+            // History samples of the source belong to the new processor from now on, so the fading processor
+            // uses a copy of them.
+            let mut prev_left_samples = source.prev_left_samples.clone();
+            let mut prev_right_samples = source.prev_right_samples.clone();
+            fading_processor.process_samples(hrtf::HrtfContext {
+                source: &source.frame_samples,
+                output: fade_out,
+                new_sample_vector,
+                prev_sample_vector,
+                prev_left_samples: &mut prev_left_samples,
+                prev_right_samples: &mut prev_right_samples,
+                prev_distance_gain,
+                new_distance_gain,
+            });
+
+            if let Some(processor) = self.processor.as_mut() {
+                processor.process_samples(hrtf::HrtfContext {
+                    source: &source.frame_samples,
+                    output: fade_in,
+                    new_sample_vector,
+                    prev_sample_vector,
+                    prev_left_samples: &mut source.prev_left_samples,
+                    prev_right_samples: &mut source.prev_right_samples,
+                    prev_distance_gain,
+                    new_distance_gain,
+                });
+            }
 
-        if let Some(processor) = self.processor.as_mut() {
+            let step = 1.0 / out_buf.len() as f32;
+            for (i, ((out_left, out_right), (&(old_left, old_right), &(new_left, new_right)))) in
+                out_buf
+                    .iter_mut()
+                    .zip(fade_out.iter().zip(fade_in.iter()))
+                    .enumerate()
+            {
+                let t = i as f32 * step;
+                *out_left += fyrox_core::math::lerpf(old_left, new_left, t);
+                *out_right += fyrox_core::math::lerpf(old_right, new_right, t);
+            }
+        } else if let Some(processor) = self.processor.as_mut() {
             processor.process_samples(hrtf::HrtfContext {
                 source: &source.frame_samples,
                 output: out_buf,
-                new_sample_vector: hrtf::Vec3::new(
-                    new_sampling_vector.x,
-                    new_sampling_vector.y,
-                    new_sampling_vector.z,
-                ),
-                prev_sample_vector: hrtf::Vec3::new(
-                    source.prev_sampling_vector.x,
-                    source.prev_sampling_vector.y,
-                    source.prev_sampling_vector.z,
-                ),
+                new_sample_vector,
+                prev_sample_vector,
                 prev_left_samples: &mut source.prev_left_samples,
                 prev_right_samples: &mut source.prev_right_samples,
-                prev_distance_gain: source.prev_distance_gain.unwrap_or(new_distance_gain),
+                prev_distance_gain,
                 new_distance_gain,
             });
         }
@@ -184,6 +294,124 @@ impl HrtfRenderer {
     }
 }
 
+/// A single measurement of a head-related impulse response.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HrirMeasurement {
+    /// Direction from the center of the listener's head to the sound source, that was used for the measurement. It is
+    /// expected to be in the right-handed coordinate system, its length does not matter.
+    pub direction: Vector3<f32>,
+    /// Impulse response for the left ear.
+    pub left: Vec<f32>,
+    /// Impulse response for the right ear.
+    pub right: Vec<f32>,
+}
+
+/// Creates a HRIR sphere from the given measurements of impulse responses, that were made at the given sample rate.
+/// Every impulse response must have the same length and the measurements must cover the whole sphere around the
+/// listener, otherwise sounds from uncovered directions will be silent. Impulse responses are resampled to the
+/// sample rate of the engine ([`context::SAMPLE_RATE`]), if needed.
+pub fn hrir_sphere_from_measurements(
+    measurements: &[HrirMeasurement],
+    sample_rate: u32,
+) -> Result<HrirSphere, HrtfError> {
+    let length = measurements.first().map_or(0, |m| m.left.len());
+    if length == 0 {
+        return Err(HrtfError::InvalidLength(length));
+    }
+    if let Some(invalid) = measurements
+        .iter()
+        .find(|m| m.left.len() != length || m.right.len() != length)
+    {
+        return Err(HrtfError::InvalidLength(if invalid.left.len() != length {
+            invalid.left.len()
+        } else {
+            invalid.right.len()
+        }));
+    }
+
+    let directions = measurements.iter().map(|m| m.direction).collect::<Vec<_>>();
+    let faces = convex_hull(&directions).ok_or(HrtfError::InvalidFileFormat)?;
+
+    // Write the data in the format of HRIR sphere files and parse it back, because HRIR spheres cannot be
+    // constructed directly.
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"HRIR");
+    bytes.write_u32::<LittleEndian>(sample_rate)?;
+    bytes.write_u32::<LittleEndian>(length as u32)?;
+    bytes.write_u32::<LittleEndian>(measurements.len() as u32)?;
+    bytes.write_u32::<LittleEndian>(faces.len() as u32 * 3)?;
+    for index in faces.iter().flatten() {
+        bytes.write_u32::<LittleEndian>(*index as u32)?;
+    }
+    for measurement in measurements {
+        for component in measurement.direction.iter() {
+            bytes.write_f32::<LittleEndian>(*component)?;
+        }
+        for sample in measurement.left.iter().chain(measurement.right.iter()) {
+            bytes.write_f32::<LittleEndian>(*sample)?;
+        }
+    }
+
+    HrirSphere::new(Cursor::new(bytes), context::SAMPLE_RATE)
+}
+
+/// Builds a convex hull of the given points using simple incremental algorithm. Returns triangles of the hull with
+/// outward winding or `None` if the points are coplanar.
+fn convex_hull(points: &[Vector3<f32>]) -> Option<Vec<[usize; 3]>> {
+    let scale = points.iter().map(|p| p.norm()).fold(0.0f32, f32::max);
+    let eps = scale * 1.0e-5;
+
+    // Find a non-degenerate tetrahedron first.
+    let a = 0;
+    let b = (0..points.len()).find(|&i| (points[i] - points[a]).norm() > eps)?;
+    let c = (0..points.len()).find(|&i| {
+        (points[b] - points[a])
+            .cross(&(points[i] - points[a]))
+            .norm()
+            > eps * scale
+    })?;
+    let normal = (points[b] - points[a]).cross(&(points[c] - points[a]));
+    let d = (0..points.len())
+        .find(|&i| normal.dot(&(points[i] - points[a])).abs() > eps * scale * scale)?;
+
+    let mut faces = if normal.dot(&(points[d] - points[a])) > 0.0 {
+        vec![[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+    } else {
+        vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+    };
+
+    let is_visible = |face: &[usize; 3], point: &Vector3<f32>| {
+        let [a, b, c] = face.map(|i| points[i]);
+        (b - a).cross(&(c - a)).dot(&(point - a)) > eps * scale * scale
+    };
+
+    for (i, point) in points.iter().enumerate() {
+        if i == a || i == b || i == c || i == d {
+            continue;
+        }
+
+        let (visible, hidden): (Vec<_>, Vec<_>) =
+            faces.into_iter().partition(|face| is_visible(face, point));
+        faces = hidden;
+
+        // Connect the point with every edge of the horizon - edges of visible faces, that are not shared
+        // with other visible faces.
+        for face in visible.iter() {
+            for k in 0..3 {
+                let edge = (face[k], face[(k + 1) % 3]);
+                let shared = visible.iter().any(|other| {
+                    (0..3).any(|m| (other[m], other[(m + 1) % 3]) == (edge.1, edge.0))
+                });
+                if !shared {
+                    faces.push([edge.0, edge.1, i]);
+                }
+            }
+        }
+    }
+
+    Some(faces)
+}
+
 /// Wrapper for [`HrirSphere`] to be able to use it in the resource manager, that will handle async resource
 /// loading automatically.
 #[derive(Reflect, Default)]
@@ -299,6 +527,98 @@ impl ResourceLoader for HrirSphereLoader {
     }
 }
 
+/// A decoder of custom HRIR data, that is used by [`HrirMeasurementsLoader`]. It takes the content of a file and
+/// returns a list of measurements with their sample rate.
+pub type HrirMeasurementsDecoder =
+    dyn Fn(&[u8]) -> Result<(Vec<HrirMeasurement>, u32), String> + Send + Sync;
+
+/// Resource loader for [`HrirSphereResource`], that loads HRIR spheres from files of a custom format using the given
+/// decoder. It could be used to load SOFA files, for example:
+///
+/// ```no_run
+/// # use fyrox_resource::manager::ResourceManager;
+/// # use fyrox_sound::renderer::hrtf::{HrirMeasurement, HrirMeasurementsLoader};
+/// fn decode_sofa(bytes: &[u8]) -> Result<(Vec<HrirMeasurement>, u32), String> {
+///     // Use any SOFA reader here.
+///     # unimplemented!()
+/// }
+///
+/// # let resource_manager = ResourceManager::new();
+/// resource_manager
+///     .state()
+///     .loaders
+///     .set(HrirMeasurementsLoader::new(&["sofa"], decode_sofa));
+/// ```
+pub struct HrirMeasurementsLoader {
+    extensions: Vec<&'static str>,
+    decoder: Arc<HrirMeasurementsDecoder>,
+}
+
+impl HrirMeasurementsLoader {
+    /// Creates a new loader for files with the given extensions.
+    pub fn new<F>(extensions: &[&'static str], decoder: F) -> Self
+    where
+        F: Fn(&[u8]) -> Result<(Vec<HrirMeasurement>, u32), String> + Send + Sync + 'static,
+    {
+        Self {
+            extensions: extensions.to_vec(),
+            decoder: Arc::new(decoder),
+        }
+    }
+}
+
+impl ResourceLoader for HrirMeasurementsLoader {
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <HrirSphereResourceData as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(
+        &self,
+        hrir_sphere: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let decoder = self.decoder.clone();
+        Box::pin(async move {
+            let path = hrir_sphere.path().to_path_buf();
+
+            let result = match io.load_file(&path).await {
+                Ok(bytes) => decoder(&bytes).and_then(|(measurements, sample_rate)| {
+                    hrir_sphere_from_measurements(&measurements, sample_rate)
+                        .map_err(|e| format!("{e:?}"))
+                }),
+                Err(e) => Err(format!("{e:?}")),
+            };
+
+            match result {
+                Ok(sphere) => {
+                    Log::info(format!("HRIR sphere {:?} is loaded!", path));
+
+                    hrir_sphere.commit_ok(HrirSphereResourceData {
+                        hrir_sphere: Some(sphere),
+                        path,
+                    });
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(hrir_sphere, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load HRIR sphere from {:?}! Reason {}",
+                        path, error
+                    ));
+
+                    hrir_sphere.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
+
 /// An alias to `Resource<HrirSphereResourceData>`.
 pub type HrirSphereResource = Resource<HrirSphereResourceData>;
 
@@ -317,3 +637,57 @@ impl HrirSphereResourceExt for HrirSphereResource {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context,
+        renderer::hrtf::{convex_hull, hrir_sphere_from_measurements, HrirMeasurement},
+    };
+    use fyrox_core::algebra::Vector3;
+
+    fn octahedron() -> Vec<Vector3<f32>> {
+        vec![
+            Vector3::x(),
+            -Vector3::x(),
+            Vector3::y(),
+            Vector3::z(),
+            -Vector3::y(),
+            -Vector3::z(),
+        ]
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let points = octahedron();
+        let faces = convex_hull(&points).unwrap();
+        assert_eq!(faces.len(), 8);
+        for face in faces.iter() {
+            let [a, b, c] = face.map(|i| points[i]);
+            // Every face of the octahedron must face outwards.
+            assert!((b - a).cross(&(c - a)).dot(&(a + b + c)) > 0.0);
+        }
+
+        assert!(convex_hull(&[Vector3::x(), Vector3::y(), -Vector3::x()]).is_none());
+    }
+
+    #[test]
+    fn test_hrir_sphere_from_measurements() {
+        let measurements = octahedron()
+            .into_iter()
+            .map(|direction| HrirMeasurement {
+                direction,
+                left: vec![1.0, 0.5, 0.25],
+                right: vec![0.5, 0.25, 0.125],
+            })
+            .collect::<Vec<_>>();
+
+        let sphere = hrir_sphere_from_measurements(&measurements, context::SAMPLE_RATE).unwrap();
+        assert_eq!(sphere.points().len(), 6);
+        assert_eq!(sphere.points()[2].left_hrir(), &[1.0, 0.5, 0.25]);
+
+        let mut invalid = measurements;
+        invalid[3].right.pop();
+        assert!(hrir_sphere_from_measurements(&invalid, context::SAMPLE_RATE).is_err());
+    }
+}
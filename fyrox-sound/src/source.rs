@@ -29,7 +29,11 @@
 #![allow(clippy::float_cmp)]
 
 use crate::{
-    buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
+    buffer::{
+        generic::{GenericBuffer, LoopPoints},
+        streaming::StreamingBuffer,
+        SoundBuffer, SoundBufferResource,
+    },
     bus::AudioBusGraph,
     context::DistanceModel,
     error::SoundError,
@@ -309,7 +313,8 @@ impl SoundSource {
     }
 
     /// Enabled or disables sound looping. Looping sound will never stop by itself, but can be stopped or paused
-    /// by calling `stop` or `pause` methods. Useful for music, ambient sounds, etc.
+    /// by calling `stop` or `pause` methods. Useful for music, ambient sounds, etc. If the buffer has loop points
+    /// (see [`GenericBuffer::set_loop_points`]), looping sound repeats only the loop region of the buffer.
     pub fn set_looping(&mut self, looping: bool) -> &mut Self {
         self.looping = looping;
         self
@@ -510,11 +515,23 @@ impl SoundSource {
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
         let mut count = 0;
         loop {
-            count += self.render_until_block_end(buffer, amount - count);
+            let loop_points = buffer.loop_points().filter(|_| self.looping);
+            let (block_end, loop_end_reached) = self.block_end(buffer, loop_points);
+            count += self.render_until_block_end(buffer, amount - count, block_end);
             if count == amount {
                 break;
             }
 
+            if let Some(loop_points) = loop_points {
+                if loop_end_reached || is_last_block(buffer) {
+                    if !self.jump_to_loop_start(buffer, loop_points, block_end) {
+                        self.status = Status::Stopped;
+                        return;
+                    }
+                    continue;
+                }
+            }
+
             let channel_count = buffer.channel_count();
             let len = buffer.samples().len();
             let mut end_reached = true;
@@ -541,9 +558,56 @@ impl SoundSource {
         }
     }
 
+    // Returns the end of the current block (in samples per channel) and a flag, that indicates
+    // whether the end of the block is the end of the loop region or not.
+    fn block_end(&self, buffer: &SoundBuffer, loop_points: Option<LoopPoints>) -> (usize, bool) {
+        let len = buffer.samples().len() / buffer.channel_count();
+        if let Some(loop_points) = loop_points {
+            // Generic buffers always start at zero, streaming buffers contain only a part of the
+            // data and the position of the part is defined by the difference of the positions.
+            let block_start = self.playback_pos - self.buf_read_pos;
+            let loop_end = (loop_points.end as f64 - block_start).round();
+            if loop_end >= 1.0 && loop_end > self.buf_read_pos && loop_end <= len as f64 {
+                return (loop_end as usize, true);
+            }
+        }
+        (len, false)
+    }
+
+    // Moves the read position from the end of the block back to the start of the loop region. The
+    // fractional part of the read position is kept, so the transition is seamless even when
+    // resampling. Returns `false` if there's no data to play after the jump.
+    fn jump_to_loop_start(
+        &mut self,
+        buffer: &mut SoundBuffer,
+        loop_points: LoopPoints,
+        block_end: usize,
+    ) -> bool {
+        // Interpolation between the last sample of the loop region and the first one.
+        self.prev_buffer_sample = get_sample(buffer, block_end.saturating_sub(1));
+        let overshoot = self.buf_read_pos - block_end as f64;
+        self.playback_pos = loop_points.start as f64 + overshoot;
+        match buffer {
+            SoundBuffer::Streaming(streaming) => {
+                streaming.sample_seek(loop_points.start);
+                streaming.read_next_block();
+                self.buf_read_pos = overshoot;
+            }
+            SoundBuffer::Generic(_) => {
+                self.buf_read_pos = self.playback_pos;
+            }
+        }
+        !buffer.is_empty()
+    }
+
     // Renders until the end of the block or until amount samples is written and returns
     // the number of written samples.
-    fn render_until_block_end(&mut self, buffer: &mut SoundBuffer, mut amount: usize) -> usize {
+    fn render_until_block_end(
+        &mut self,
+        buffer: &mut SoundBuffer,
+        mut amount: usize,
+        block_end: usize,
+    ) -> usize {
         let step = self.pitch * self.resampling_multiplier;
         if step == 1.0 {
             if self.buf_read_pos < 0.0 {
@@ -554,8 +618,7 @@ impl SoundSource {
             }
             // Fast-path for common case when there is no resampling and no pitch change.
             let from = self.buf_read_pos as usize;
-            let buffer_len = buffer.samples.len() / buffer.channel_count;
-            let rendered = (buffer_len - from).min(amount);
+            let rendered = block_end.saturating_sub(from).min(amount);
             if buffer.channel_count == 2 {
                 for i in from..from + rendered {
                     self.frame_samples
@@ -571,7 +634,7 @@ impl SoundSource {
             self.playback_pos += rendered as f64;
             rendered
         } else {
-            self.render_until_block_end_resample(buffer, amount, step)
+            self.render_until_block_end_resample(buffer, amount, step, block_end)
         }
     }
 
@@ -581,6 +644,7 @@ impl SoundSource {
         buffer: &mut SoundBuffer,
         amount: usize,
         step: f64,
+        block_end: usize,
     ) -> usize {
         let mut rendered = 0;
 
@@ -611,7 +675,7 @@ impl SoundSource {
         let rel_step = step as f32;
         // We skip one last element because the hot loop resampling between current and next
         // element. Last elements are appended after the hot loop.
-        let buffer_last = buffer.samples.len() / buffer.channel_count - 1;
+        let buffer_last = block_end.saturating_sub(1);
         if buffer.channel_count == 2 {
             while rendered < amount {
                 let (idx, w) = {
@@ -656,6 +720,30 @@ impl SoundSource {
     }
 }
 
+fn get_sample(buffer: &GenericBuffer, index: usize) -> (f32, f32) {
+    if buffer.channel_count == 2 {
+        match buffer.samples.get(index * 2..index * 2 + 2) {
+            Some(&[l, r]) => (l, r),
+            _ => (0.0, 0.0),
+        }
+    } else {
+        let sample = buffer.samples.get(index).cloned().unwrap_or_default();
+        (sample, sample)
+    }
+}
+
+// Returns `true` if there's no more data in the buffer after its current block.
+fn is_last_block(buffer: &SoundBuffer) -> bool {
+    match buffer {
+        // Means that this is the last available block.
+        SoundBuffer::Streaming(streaming) => {
+            streaming.samples.len()
+                != streaming.channel_count * StreamingBuffer::STREAM_SAMPLE_COUNT
+        }
+        SoundBuffer::Generic(_) => true,
+    }
+}
+
 fn get_last_sample(buffer: &StreamingBuffer) -> (f32, f32) {
     let len = buffer.samples.len();
     if len == 0 {
@@ -871,3 +959,63 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{
+            generic::LoopPoints, DataSource, SoundBufferResource, SoundBufferResourceExtension,
+        },
+        context::SAMPLE_RATE,
+        source::{SoundSourceBuilder, Status},
+    };
+
+    #[test]
+    fn test_loop_points() {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: (0..10).map(|i| i as f32).collect(),
+        })
+        .unwrap();
+        buffer
+            .data_ref()
+            .set_loop_points(Some(LoopPoints::new(4, 8)));
+
+        let mut source = SoundSourceBuilder::new()
+            .with_buffer(buffer.clone())
+            .with_looping(true)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+
+        source.render(14);
+        let rendered = source
+            .frame_samples()
+            .iter()
+            .map(|(l, _)| *l)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 4.0, 5.0, 6.0, 7.0, 4.0, 5.0]
+        );
+
+        // Loop region is ignored if the source is not looping.
+        source.set_looping(false);
+        source.render(8);
+        assert_eq!(source.status(), Status::Stopped);
+
+        // Loop end is clamped to the length of the buffer, empty regions are discarded.
+        buffer
+            .data_ref()
+            .set_loop_points(Some(LoopPoints::new(2, 20)));
+        assert_eq!(
+            buffer.data_ref().loop_points(),
+            Some(LoopPoints::new(2, 10))
+        );
+        buffer
+            .data_ref()
+            .set_loop_points(Some(LoopPoints::new(12, 20)));
+        assert_eq!(buffer.data_ref().loop_points(), None);
+    }
+}
@@ -0,0 +1,357 @@
+//! Accessibility validation allows you to find common accessibility issues of a user interface during
+//! development. See [`AccessibilityReport`] docs for more info.
+//!
+//! Validation works with interactive widgets (see [`crate::Control::is_interactive`]), such as buttons,
+//! check boxes, text boxes, etc. Every visible interactive widget should:
+//!
+//! - Have a unique position in keyboard focus order (see [`crate::widget::Widget::tab_index`]).
+//! - Be reachable by a user: it must be hit-test visible, have non-zero size and be on screen.
+//! - Have a name, that describes its purpose: either an accessible name (see
+//!   [`crate::widget::Widget::accessible_name`]) or a text content (for example, a text of a
+//!   button).
+//!
+//! Interactive widgets that are parts of other interactive widgets (for example, buttons of a
+//! numeric field) are not validated, since a user interacts with the outer widget as a whole.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{math::Rect, pool::Handle},
+    text::Text,
+    BaseControl, UiNode, UserInterface,
+};
+use fxhash::FxHashMap;
+use std::fmt::{Display, Formatter};
+
+/// A reason why an interactive widget cannot be reached by a user.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnreachableReason {
+    /// The widget or one of its ancestors is not hit-test visible, so the widget cannot be picked
+    /// by mouse or touch.
+    HitTestInvisible {
+        /// A widget, that blocks hit-testing (either the widget itself or one of its ancestors).
+        blocker: Handle<UiNode>,
+    },
+    /// The widget has zero width or height.
+    ZeroSize,
+    /// The widget is outside of the screen bounds.
+    OffScreen,
+}
+
+/// Kind of an accessibility issue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessibilityIssueKind {
+    /// An interactive widget has no position in keyboard focus order.
+    MissingTabIndex,
+    /// An interactive widget has the same position in keyboard focus order as some other widget.
+    DuplicateTabIndex {
+        /// Tab index of the widget.
+        tab_index: usize,
+        /// A widget, that has the same tab index.
+        other: Handle<UiNode>,
+    },
+    /// An interactive widget cannot be reached by a user.
+    Unreachable(UnreachableReason),
+    /// An interactive widget has neither an accessible name nor text content.
+    MissingAccessibleName,
+}
+
+/// A single accessibility issue of a widget.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessibilityIssue {
+    /// A handle of the widget with the issue.
+    pub widget: Handle<UiNode>,
+    /// Name of the widget (see [`crate::widget::Widget::name`]).
+    pub name: String,
+    /// Type name of the widget.
+    pub type_name: &'static str,
+    /// Kind of the issue.
+    pub kind: AccessibilityIssueKind,
+}
+
+impl Display for AccessibilityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} \"{}\" {}: ", self.type_name, self.name, self.widget)?;
+        match self.kind {
+            AccessibilityIssueKind::MissingTabIndex => {
+                write!(f, "the widget is not a part of keyboard focus order.")
+            }
+            AccessibilityIssueKind::DuplicateTabIndex { tab_index, other } => {
+                write!(
+                    f,
+                    "the widget has the same tab index {tab_index} as {other}."
+                )
+            }
+            AccessibilityIssueKind::Unreachable(reason) => match reason {
+                UnreachableReason::HitTestInvisible { blocker } => write!(
+                    f,
+                    "the widget cannot be reached, because {blocker} is not hit-test visible."
+                ),
+                UnreachableReason::ZeroSize => {
+                    write!(f, "the widget cannot be reached, because it has zero size.")
+                }
+                UnreachableReason::OffScreen => {
+                    write!(f, "the widget cannot be reached, because it is off-screen.")
+                }
+            },
+            AccessibilityIssueKind::MissingAccessibleName => {
+                write!(f, "the widget has no accessible name and no text content.")
+            }
+        }
+    }
+}
+
+/// Accessibility report contains accessibility issues of every visible interactive widget of a user
+/// interface. See [module docs](self) for the list of checks.
+///
+/// Validation uses the results of the layout pass, so make sure to call [`UserInterface::update`]
+/// before the validation.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::{accessibility::AccessibilityReport, UserInterface};
+///
+/// fn validate_ui(ui: &UserInterface) {
+///     let report = AccessibilityReport::validate(ui);
+///     if !report.is_empty() {
+///         println!("{report}");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessibilityReport {
+    /// A list of found issues in tree order.
+    pub issues: Vec<AccessibilityIssue>,
+}
+
+struct Entry {
+    handle: Handle<UiNode>,
+    hit_test_blocker: Handle<UiNode>,
+    enabled: bool,
+    inside_interactive: bool,
+}
+
+impl AccessibilityReport {
+    /// Walks the widget tree of the given user interface and collects accessibility issues of every
+    /// visible interactive widget.
+    pub fn validate(ui: &UserInterface) -> Self {
+        let screen = Rect::new(0.0, 0.0, ui.screen_size().x, ui.screen_size().y);
+        let mut issues = Vec::new();
+        let mut tab_indices = FxHashMap::<usize, Handle<UiNode>>::default();
+
+        let mut stack = vec![Entry {
+            handle: ui.root(),
+            hit_test_blocker: Handle::NONE,
+            enabled: true,
+            inside_interactive: false,
+        }];
+        while let Some(entry) = stack.pop() {
+            let Some(node) = ui.try_get_node(entry.handle) else {
+                continue;
+            };
+
+            // Hidden widgets (and their descendants) cannot be interacted with at all.
+            if !node.visibility() {
+                continue;
+            }
+
+            let hit_test_blocker =
+                if entry.hit_test_blocker.is_none() && !node.is_hit_test_visible() {
+                    entry.handle
+                } else {
+                    entry.hit_test_blocker
+                };
+            let enabled = entry.enabled && node.enabled();
+            let is_interactive = node.is_interactive();
+
+            if is_interactive && !entry.inside_interactive {
+                let mut add_issue = |kind| {
+                    issues.push(AccessibilityIssue {
+                        widget: entry.handle,
+                        name: node.name().to_owned(),
+                        type_name: BaseControl::type_name(&*node.0),
+                        kind,
+                    })
+                };
+
+                match node.tab_index() {
+                    None => add_issue(AccessibilityIssueKind::MissingTabIndex),
+                    Some(tab_index) => {
+                        if let Some(other) = tab_indices.get(&tab_index) {
+                            add_issue(AccessibilityIssueKind::DuplicateTabIndex {
+                                tab_index,
+                                other: *other,
+                            });
+                        } else {
+                            tab_indices.insert(tab_index, entry.handle);
+                        }
+                    }
+                }
+
+                // Disabled widgets are unreachable intentionally.
+                if enabled {
+                    let size = node.actual_local_size();
+                    if hit_test_blocker.is_some() {
+                        add_issue(AccessibilityIssueKind::Unreachable(
+                            UnreachableReason::HitTestInvisible {
+                                blocker: hit_test_blocker,
+                            },
+                        ));
+                    } else if size.x <= 0.0 || size.y <= 0.0 {
+                        add_issue(AccessibilityIssueKind::Unreachable(
+                            UnreachableReason::ZeroSize,
+                        ));
+                    } else if !node.clip_bounds().intersects(screen) {
+                        add_issue(AccessibilityIssueKind::Unreachable(
+                            UnreachableReason::OffScreen,
+                        ));
+                    }
+                }
+
+                if !node.accessible_name().is_some_and(|name| !name.is_empty())
+                    && !has_text_content(ui, entry.handle)
+                {
+                    add_issue(AccessibilityIssueKind::MissingAccessibleName);
+                }
+            }
+
+            // Push children in reverse order to keep tree order of the issues.
+            for child in node.children().iter().rev() {
+                stack.push(Entry {
+                    handle: *child,
+                    hit_test_blocker,
+                    enabled,
+                    inside_interactive: entry.inside_interactive || is_interactive,
+                });
+            }
+        }
+
+        Self { issues }
+    }
+
+    /// Returns `true` if there are no issues.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns an iterator over issues of the given widget.
+    pub fn issues_of(&self, widget: Handle<UiNode>) -> impl Iterator<Item = &AccessibilityIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.widget == widget)
+    }
+}
+
+impl Display for AccessibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Accessibility Issues: {}", self.issues.len())?;
+        for issue in self.issues.iter() {
+            writeln!(f, "\t{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+fn has_text_content(ui: &UserInterface, handle: Handle<UiNode>) -> bool {
+    let mut stack = vec![handle];
+    while let Some(handle) = stack.pop() {
+        if let Some(node) = ui.try_get_node(handle) {
+            if let Some(text) = node.cast::<Text>() {
+                if !text.text().trim().is_empty() {
+                    return true;
+                }
+            }
+            stack.extend_from_slice(node.children());
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        button::ButtonBuilder, check_box::CheckBoxBuilder, core::algebra::Vector2,
+        stack_panel::StackPanelBuilder, widget::WidgetBuilder,
+    };
+
+    #[test]
+    fn accessibility_report() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let ctx = &mut ui.build_ctx();
+
+        let ok = ButtonBuilder::new(WidgetBuilder::new().with_tab_index(0))
+            .with_text("Ok")
+            .build(ctx);
+        let duplicate = ButtonBuilder::new(WidgetBuilder::new().with_tab_index(0))
+            .with_text("Cancel")
+            .build(ctx);
+        let unnamed = CheckBoxBuilder::new(WidgetBuilder::new().with_tab_index(1)).build(ctx);
+        let named = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_tab_index(2)
+                .with_accessible_name("Fullscreen"),
+        )
+        .build(ctx);
+        let no_tab_index = ButtonBuilder::new(WidgetBuilder::new())
+            .with_text("Apply")
+            .build(ctx);
+        let hidden = ButtonBuilder::new(WidgetBuilder::new().with_visibility(false)).build(ctx);
+        let passive = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_tab_index(3)
+                .with_hit_test_visibility(false),
+        )
+        .with_text("Passive")
+        .build(ctx);
+        let panel = StackPanelBuilder::new(WidgetBuilder::new().with_children([
+            ok,
+            duplicate,
+            unnamed,
+            named,
+            no_tab_index,
+            hidden,
+            passive,
+        ]))
+        .build(ctx);
+
+        ui.update(screen_size, 0.0);
+
+        let report = AccessibilityReport::validate(&ui);
+        let kinds = |widget| {
+            report
+                .issues_of(widget)
+                .map(|issue| issue.kind.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(kinds(ok).is_empty());
+        assert_eq!(
+            kinds(duplicate),
+            vec![AccessibilityIssueKind::DuplicateTabIndex {
+                tab_index: 0,
+                other: ok
+            }]
+        );
+        assert_eq!(
+            kinds(unnamed),
+            vec![AccessibilityIssueKind::MissingAccessibleName]
+        );
+        assert!(kinds(named).is_empty());
+        assert_eq!(
+            kinds(no_tab_index),
+            vec![AccessibilityIssueKind::MissingTabIndex]
+        );
+        assert!(kinds(hidden).is_empty());
+        assert_eq!(
+            kinds(passive),
+            vec![AccessibilityIssueKind::Unreachable(
+                UnreachableReason::HitTestInvisible { blocker: passive }
+            )]
+        );
+        assert!(kinds(panel).is_empty());
+        assert_eq!(report.issues.len(), 4);
+    }
+}
@@ -1,5 +1,6 @@
 //! Alignment defines relative location of the widget to its parent widget. There are two kinds of alignment:
-//! [`HorizontalAlignment`] and [`VerticalAlignment`]. Check the docs for them for more info.
+//! [`HorizontalAlignment`] and [`VerticalAlignment`]. Check the docs for them for more info. [`FlowDirection`] defines
+//! the direction in which the content of a widget is laid out (left-to-right or right-to-left).
 
 use crate::core::{reflect::prelude::*, visitor::prelude::*};
 
@@ -40,3 +41,52 @@ pub enum VerticalAlignment {
     /// possible (shrink-to-fit).
     Bottom,
 }
+
+/// Flow direction defines the direction in which the content of a widget is laid out horizontally.
+/// Right-to-left direction mirrors the arrangement of children of a widget: every child is placed at
+/// the mirrored position along the horizontal axis of its parent, which means that stack panels,
+/// grids and docks are laid out from right to left, [`HorizontalAlignment::Left`] puts a widget at
+/// the right side of its parent (and vice versa) and left margins become right margins. This allows
+/// to use the same layout for left-to-right and right-to-left languages (such as Arabic or Hebrew).
+///
+/// Mirroring happens at the level of visual transform, so the results of layout
+/// ([`crate::widget::Widget::actual_local_position`]) are always in the left-to-right space.
+/// Children of the root canvas of the user interface are never mirrored, because they're placed
+/// at explicit screen positions (windows, popups, tooltips, etc.).
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Default, Reflect, Visit)]
+pub enum FlowDirection {
+    /// Takes flow direction of the parent widget. Widgets without parent (or the children of the root
+    /// canvas) take the direction of the user interface (see
+    /// [`crate::UserInterface::set_flow_direction`]). This is default flow direction for all widgets.
+    #[default]
+    Inherit,
+    /// Content is laid out from left to right.
+    LeftToRight,
+    /// Content is laid out from right to left.
+    RightToLeft,
+}
+
+impl FlowDirection {
+    /// Returns a flow direction of the given locale (for example `ar`, `he-IL` or `en-US`). Locales
+    /// of right-to-left scripts (Arabic, Hebrew, Persian, Urdu, etc.) yield [`Self::RightToLeft`],
+    /// every other locale yields [`Self::LeftToRight`].
+    pub fn from_locale(locale: &str) -> Self {
+        const RTL_LANGUAGES: [&str; 12] = [
+            "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur", "yi",
+        ];
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        if RTL_LANGUAGES
+            .iter()
+            .any(|rtl| rtl.eq_ignore_ascii_case(language))
+        {
+            Self::RightToLeft
+        } else {
+            Self::LeftToRight
+        }
+    }
+
+    /// Returns `true` if the direction is [`Self::RightToLeft`].
+    pub fn is_right_to_left(self) -> bool {
+        self == Self::RightToLeft
+    }
+}
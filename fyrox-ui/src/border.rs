@@ -11,6 +11,7 @@ use crate::{
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
+    stroke::StrokeStyle,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, BRUSH_PRIMARY,
 };
@@ -81,12 +82,33 @@ use std::{
 /// .with_stroke_thickness(Thickness {left: 2.0, right: 2.0, top: 2.0, bottom: 2.0})
 /// .build(&mut ui.build_ctx());
 /// ```
+///
+/// The border could also be dashed or dotted, which is useful for selection marquees and focus outlines. See
+/// [`StrokeStyle`] docs for more info.
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     widget::WidgetBuilder,
+/// #     border::BorderBuilder,
+/// #     stroke::StrokeStyle,
+/// #     UserInterface,
+/// #     Thickness,
+/// # };
+/// # let mut ui = UserInterface::new(Default::default());
+/// BorderBuilder::new(WidgetBuilder::new())
+///     .with_stroke_thickness(Thickness::uniform(1.0))
+///     .with_stroke_style(StrokeStyle::dashed(4.0, 2.0))
+///     .build(&mut ui.build_ctx());
+/// ```
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct Border {
     /// Base widget of the border. See [`Widget`] docs for more info.
     pub widget: Widget,
     /// Stroke thickness for each side of the border.
     pub stroke_thickness: Thickness,
+    /// Stroke style (dash pattern, caps and joins) of the border.
+    #[visit(optional)]
+    pub stroke_style: StrokeStyle,
 }
 
 crate::define_widget_deref!(Border);
@@ -96,6 +118,8 @@ crate::define_widget_deref!(Border);
 pub enum BorderMessage {
     /// Allows you to set stroke thickness at runtime. See [`Self::stroke_thickness`] docs for more.
     StrokeThickness(Thickness),
+    /// Allows you to set stroke style at runtime. See [`Border::stroke_style`] docs for more.
+    StrokeStyle(StrokeStyle),
 }
 
 impl BorderMessage {
@@ -103,6 +127,10 @@ impl BorderMessage {
         /// Creates a new [Self::StrokeThickness] message.
         BorderMessage:StrokeThickness => fn stroke_thickness(Thickness), layout: false
     );
+    define_constructor!(
+        /// Creates a new [Self::StrokeStyle] message.
+        BorderMessage:StrokeStyle => fn stroke_style(StrokeStyle), layout: false
+    );
 }
 
 impl Control for Border {
@@ -168,7 +196,7 @@ impl Control for Border {
             None,
         );
 
-        drawing_context.push_rect_vary(&bounds, self.stroke_thickness);
+        drawing_context.push_rect_vary_styled(&bounds, self.stroke_thickness, &self.stroke_style);
         drawing_context.commit(
             self.clip_bounds(),
             self.widget.foreground(),
@@ -183,12 +211,21 @@ impl Control for Border {
         if message.destination() == self.handle()
             && message.direction() == MessageDirection::ToWidget
         {
-            if let Some(BorderMessage::StrokeThickness(thickness)) = message.data() {
-                if *thickness != self.stroke_thickness {
+            match message.data() {
+                Some(BorderMessage::StrokeThickness(thickness))
+                    if *thickness != self.stroke_thickness =>
+                {
                     self.stroke_thickness = *thickness;
                     ui.send_message(message.reverse());
                     self.invalidate_layout();
                 }
+                Some(BorderMessage::StrokeStyle(stroke_style))
+                    if *stroke_style != self.stroke_style =>
+                {
+                    self.stroke_style = stroke_style.clone();
+                    ui.send_message(message.reverse());
+                }
+                _ => (),
             }
         }
     }
@@ -200,6 +237,8 @@ pub struct BorderBuilder {
     pub widget_builder: WidgetBuilder,
     /// Stroke thickness for each side of the border. Default is 1px wide border for each side.
     pub stroke_thickness: Thickness,
+    /// Stroke style of the border. Default is solid stroke.
+    pub stroke_style: StrokeStyle,
 }
 
 impl BorderBuilder {
@@ -208,6 +247,7 @@ impl BorderBuilder {
         Self {
             widget_builder,
             stroke_thickness: Thickness::uniform(1.0),
+            stroke_style: Default::default(),
         }
     }
 
@@ -217,6 +257,12 @@ impl BorderBuilder {
         self
     }
 
+    /// Sets the desired stroke style of the border. See [`StrokeStyle`] docs for more info.
+    pub fn with_stroke_style(mut self, stroke_style: StrokeStyle) -> Self {
+        self.stroke_style = stroke_style;
+        self
+    }
+
     /// Creates a [`Border`] widget, but does not add it to the user interface. Also see [`Self::build`] docs.
     pub fn build_border(mut self) -> Border {
         if self.widget_builder.foreground.is_none() {
@@ -225,6 +271,7 @@ impl BorderBuilder {
         Border {
             widget: self.widget_builder.build(),
             stroke_thickness: self.stroke_thickness,
+            stroke_style: self.stroke_style,
         }
     }
 
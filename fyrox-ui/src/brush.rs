@@ -1,8 +1,11 @@
 //! Brush defines a way to fill an arbitrary surface. See [`Brush`] docs for more info and usage examples.
+//! Brushes of widgets could be animated, see [`BrushAnimation`] docs for more info.
 
 #![warn(missing_docs)]
 
-use crate::core::{algebra::Vector2, color::Color, reflect::prelude::*, visitor::prelude::*};
+use crate::core::{
+    algebra::Vector2, color::Color, math::lerpf, reflect::prelude::*, visitor::prelude::*,
+};
 
 /// Gradient point defines a point on a surface with a color.
 #[derive(Clone, Debug, PartialEq, Reflect, Visit, Default)]
@@ -13,28 +16,81 @@ pub struct GradientPoint {
     pub color: Color,
 }
 
+impl GradientPoint {
+    /// Interpolates both the stop and the color of the point.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            stop: lerpf(self.stop, other.stop, t),
+            color: self.color.lerp(other.color, t),
+        }
+    }
+}
+
+fn lerp_stops(a: &[GradientPoint], b: &[GradientPoint], t: f32) -> Vec<GradientPoint> {
+    if a.is_empty() || b.is_empty() {
+        return if t < 0.5 { a.to_vec() } else { b.to_vec() };
+    }
+    // The shorter set of stops is padded with its last stop, so added stops "grow" out of it.
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            let a = a.get(i).unwrap_or(&a[a.len() - 1]);
+            let b = b.get(i).unwrap_or(&b[b.len() - 1]);
+            a.lerp(b, t)
+        })
+        .collect()
+}
+
+/// Coordinate space of a gradient brush. It defines how the points of a gradient map to a surface, that is
+/// filled with the brush.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect, Visit)]
+pub enum GradientSpace {
+    /// Coordinates are normalized to the bounds of a surface: `(0, 0)` is the top-left corner of the
+    /// surface and `(1, 1)` is its bottom-right corner. The gradient is stretched over every surface
+    /// it fills. This is the default mode.
+    #[default]
+    Normalized,
+    /// Coordinates are in pixels relative to the top-left corner of a surface. The gradient keeps its
+    /// size regardless of the size of the surface.
+    Local,
+    /// Coordinates are in pixels relative to the top-left corner of the screen. Every surface shows its
+    /// part of the same gradient, so a single gradient flows continuously across multiple adjacent
+    /// widgets.
+    Screen,
+}
+
 /// Brush defines a way to fill an arbitrary surface.
 #[derive(Clone, Debug, PartialEq, Reflect, Visit)]
 pub enum Brush {
     /// A brush, that fills a surface with a solid color.
     Solid(Color),
-    /// A brush, that fills a surface with a linear gradient, which is defined by two points in local coordinates
-    /// and a set of stop points. See [`GradientPoint`] for more info.
+    /// A brush, that fills a surface with a linear gradient, which is defined by two points in the coordinate space
+    /// of the gradient and a set of stop points. See [`GradientPoint`] and [`GradientSpace`] for more info.
     LinearGradient {
-        /// Beginning of the gradient in local coordinates.
+        /// Beginning of the gradient.
         from: Vector2<f32>,
-        /// End of the gradient in local coordinates.
+        /// End of the gradient.
         to: Vector2<f32>,
         /// Stops of the gradient.
         stops: Vec<GradientPoint>,
+        /// Coordinate space of the gradient points.
+        #[visit(optional)]
+        space: GradientSpace,
     },
-    /// A brush, that fills a surface with a radial gradient, which is defined by a center point in local coordinates
-    /// and a set of stop points. See [`GradientPoint`] for more info.
+    /// A brush, that fills a surface with a radial gradient, which is defined by a center point in the coordinate
+    /// space of the gradient and a set of stop points. See [`GradientPoint`] and [`GradientSpace`] for more info.
     RadialGradient {
-        /// Center of the gradient in local coordinates.
+        /// Center of the gradient.
         center: Vector2<f32>,
         /// Stops of the gradient.
         stops: Vec<GradientPoint>,
+        /// Radius of the gradient (a distance at which the last stop is reached) in the coordinate space of the
+        /// gradient. `None` means `1.0`, which covers a whole surface in [`GradientSpace::Normalized`] space, but
+        /// it is just a single pixel in other spaces, so the radius should be set explicitly for them.
+        #[visit(optional)]
+        radius: Option<f32>,
+        /// Coordinate space of the gradient center and radius.
+        #[visit(optional)]
+        space: GradientSpace,
     },
 }
 
@@ -43,3 +99,377 @@ impl Default for Brush {
         Self::Solid(Color::WHITE)
     }
 }
+
+impl Brush {
+    /// Returns coordinate space of the brush. Solid brush is always in [`GradientSpace::Normalized`] space.
+    pub fn space(&self) -> GradientSpace {
+        match self {
+            Brush::Solid(_) => GradientSpace::Normalized,
+            Brush::LinearGradient { space, .. } | Brush::RadialGradient { space, .. } => *space,
+        }
+    }
+
+    /// Returns a copy of the brush with every color replaced by the given one.
+    fn with_color(&self, color: Color) -> Brush {
+        let mut brush = self.clone();
+        match &mut brush {
+            Brush::Solid(solid) => *solid = color,
+            Brush::LinearGradient { stops, .. } | Brush::RadialGradient { stops, .. } => {
+                for stop in stops {
+                    stop.color = color;
+                }
+            }
+        }
+        brush
+    }
+
+    /// Interpolates the brush with the other one. Brushes of the same kind are interpolated
+    /// completely: colors, gradient points, stops and radius. If the amount of stops is different,
+    /// the extra stops are interpolated from the last stop of the other brush. A solid brush is
+    /// interpolated with a gradient as a gradient of the same shape filled with a single color.
+    /// Properties, that cannot be interpolated (coordinate space, kind of the gradient), switch at
+    /// the middle.
+    pub fn lerp(&self, other: &Brush, t: f32) -> Brush {
+        match (self, other) {
+            (Brush::Solid(a), Brush::Solid(b)) => Brush::Solid(a.lerp(*b, t)),
+            (
+                Brush::LinearGradient {
+                    from: a_from,
+                    to: a_to,
+                    stops: a_stops,
+                    space: a_space,
+                },
+                Brush::LinearGradient {
+                    from: b_from,
+                    to: b_to,
+                    stops: b_stops,
+                    space: b_space,
+                },
+            ) => Brush::LinearGradient {
+                from: a_from.lerp(b_from, t),
+                to: a_to.lerp(b_to, t),
+                stops: lerp_stops(a_stops, b_stops, t),
+                space: if t < 0.5 { *a_space } else { *b_space },
+            },
+            (
+                Brush::RadialGradient {
+                    center: a_center,
+                    stops: a_stops,
+                    radius: a_radius,
+                    space: a_space,
+                },
+                Brush::RadialGradient {
+                    center: b_center,
+                    stops: b_stops,
+                    radius: b_radius,
+                    space: b_space,
+                },
+            ) => Brush::RadialGradient {
+                center: a_center.lerp(b_center, t),
+                stops: lerp_stops(a_stops, b_stops, t),
+                radius: if a_radius.is_none() && b_radius.is_none() {
+                    None
+                } else {
+                    Some(lerpf(a_radius.unwrap_or(1.0), b_radius.unwrap_or(1.0), t))
+                },
+                space: if t < 0.5 { *a_space } else { *b_space },
+            },
+            (Brush::Solid(color), gradient) => gradient.with_color(*color).lerp(gradient, t),
+            (gradient, Brush::Solid(color)) => gradient.lerp(&gradient.with_color(*color), t),
+            _ => {
+                if t < 0.5 {
+                    self.clone()
+                } else {
+                    other.clone()
+                }
+            }
+        }
+    }
+}
+
+/// Defines which brush of a widget is changed by a [`BrushAnimation`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect, Visit)]
+pub enum BrushTarget {
+    /// Background brush of a widget.
+    #[default]
+    Background,
+    /// Foreground brush of a widget.
+    Foreground,
+}
+
+/// A key frame of a [`BrushAnimation`].
+#[derive(Clone, Debug, PartialEq, Default, Reflect, Visit)]
+pub struct BrushKey {
+    /// Time (in seconds) of the key.
+    pub time: f32,
+    /// Brush at the time of the key.
+    pub brush: Brush,
+}
+
+/// Brush animation changes a brush of a widget over time by interpolating between a set of key
+/// frames (see [`Brush::lerp`]). It animates everything a brush consists of: colors, gradient points
+/// and positions of gradient stops, so it could be used to make animated progress gradients, shimmering
+/// highlights, pulsing borders and so on without any code. Animations are stored in widgets (see
+/// [`crate::widget::Widget::brush_animations`]) and updated by the user interface every frame, the
+/// animated brush overwrites the respective brush of the widget.
+///
+/// ## Example
+///
+/// The following example creates a border with a highlight, that runs from left to right over and over
+/// again.
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     border::BorderBuilder,
+/// #     brush::{Brush, BrushAnimation, BrushTarget, GradientPoint, GradientSpace},
+/// #     core::{algebra::Vector2, color::Color, pool::Handle},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn shimmer(position: f32) -> Brush {
+///     let point = |stop: f32, color| GradientPoint { stop, color };
+///     Brush::LinearGradient {
+///         from: Vector2::new(0.0, 0.0),
+///         to: Vector2::new(1.0, 0.0),
+///         stops: vec![
+///             point(position - 0.2, Color::opaque(60, 60, 60)),
+///             point(position, Color::opaque(200, 200, 200)),
+///             point(position + 0.2, Color::opaque(60, 60, 60)),
+///         ],
+///         space: GradientSpace::Normalized,
+///     }
+/// }
+///
+/// fn create_shimmering_border(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     BorderBuilder::new(
+///         WidgetBuilder::new().with_brush_animation(
+///             BrushAnimation::new(BrushTarget::Background)
+///                 .with_key(0.0, shimmer(-0.2))
+///                 .with_key(1.5, shimmer(1.2))
+///                 .with_looping(true),
+///         ),
+///     )
+///     .build(ctx)
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Default, Reflect, Visit)]
+pub struct BrushAnimation {
+    /// A brush of a widget, that is animated.
+    pub target: BrushTarget,
+    /// Key frames of the animation, sorted by time.
+    pub keys: Vec<BrushKey>,
+    /// Whether the animation starts over when it ends or not.
+    pub looping: bool,
+    /// Current time (in seconds) of the animation.
+    pub time: f32,
+}
+
+impl BrushAnimation {
+    /// Creates a new animation without keys for the given brush of a widget.
+    pub fn new(target: BrushTarget) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new key frame to the animation, keeping the keys sorted by time.
+    pub fn with_key(mut self, time: f32, brush: Brush) -> Self {
+        let index = self.keys.partition_point(|key| key.time <= time);
+        self.keys.insert(index, BrushKey { time, brush });
+        self
+    }
+
+    /// Sets whether the animation starts over when it ends or not.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Returns the duration of the animation, which is the time of the last key.
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |key| key.time)
+    }
+
+    /// Returns `true` if the animation is not looping and it has reached its end.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.time >= self.duration()
+    }
+
+    /// Returns the brush of the animation at the given time. `None` means that the animation has
+    /// no keys.
+    pub fn sample(&self, time: f32) -> Option<Brush> {
+        let first = self.keys.first()?;
+        let index = self.keys.partition_point(|key| key.time <= time);
+        if index == 0 {
+            return Some(first.brush.clone());
+        }
+        let left = &self.keys[index - 1];
+        match self.keys.get(index) {
+            Some(right) => {
+                let t = (time - left.time) / (right.time - left.time);
+                Some(left.brush.lerp(&right.brush, t))
+            }
+            None => Some(left.brush.clone()),
+        }
+    }
+
+    /// Advances the animation by the given time step and returns the new brush. A finished
+    /// animation returns `None`, so it does not overwrite the brush anymore. An animation with zero
+    /// duration (with a single key, for example) is finished from the start.
+    pub fn update(&mut self, dt: f32) -> Option<Brush> {
+        if self.keys.is_empty() || self.is_finished() {
+            return None;
+        }
+        let duration = self.duration();
+        self.time += dt;
+        if self.time >= duration {
+            if self.looping && duration > 0.0 {
+                self.time %= duration;
+            } else {
+                self.time = duration;
+            }
+        }
+        self.sample(self.time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Visit)]
+    #[allow(dead_code)]
+    enum LegacyBrush {
+        Solid(Color),
+        LinearGradient {
+            from: Vector2<f32>,
+            to: Vector2<f32>,
+            stops: Vec<GradientPoint>,
+        },
+        RadialGradient {
+            center: Vector2<f32>,
+            stops: Vec<GradientPoint>,
+        },
+    }
+
+    fn save<T: Visit>(mut value: T) -> Vec<u8> {
+        let mut visitor = Visitor::new();
+        value.visit("Brush", &mut visitor).unwrap();
+        visitor.save_binary_to_vec().unwrap()
+    }
+
+    fn load(data: &[u8]) -> Brush {
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut brush = Brush::default();
+        brush.visit("Brush", &mut visitor).unwrap();
+        brush
+    }
+
+    #[test]
+    fn brush_gradient_space_visit() {
+        let legacy = LegacyBrush::RadialGradient {
+            center: Vector2::new(0.5, 0.5),
+            stops: vec![],
+        };
+        assert_eq!(
+            load(&save(legacy)),
+            Brush::RadialGradient {
+                center: Vector2::new(0.5, 0.5),
+                stops: vec![],
+                radius: None,
+                space: GradientSpace::Normalized,
+            }
+        );
+
+        let brush = Brush::LinearGradient {
+            from: Vector2::new(0.0, 0.0),
+            to: Vector2::new(800.0, 0.0),
+            stops: vec![GradientPoint {
+                stop: 1.0,
+                color: Color::RED,
+            }],
+            space: GradientSpace::Screen,
+        };
+        assert_eq!(load(&save(brush.clone())), brush);
+        assert_eq!(brush.space(), GradientSpace::Screen);
+    }
+
+    #[test]
+    fn brush_lerp() {
+        let point = |stop: f32, color| GradientPoint { stop, color };
+        let a = Brush::LinearGradient {
+            from: Vector2::new(0.0, 0.0),
+            to: Vector2::new(1.0, 0.0),
+            stops: vec![point(0.0, Color::BLACK)],
+            space: GradientSpace::Normalized,
+        };
+        let b = Brush::LinearGradient {
+            from: Vector2::new(0.0, 1.0),
+            to: Vector2::new(1.0, 0.0),
+            stops: vec![point(0.5, Color::WHITE), point(1.0, Color::RED)],
+            space: GradientSpace::Local,
+        };
+        assert_eq!(
+            a.lerp(&b, 0.5),
+            Brush::LinearGradient {
+                from: Vector2::new(0.0, 0.5),
+                to: Vector2::new(1.0, 0.0),
+                stops: vec![
+                    point(0.25, Color::opaque(127, 127, 127)),
+                    point(0.5, Color::opaque(127, 0, 0))
+                ],
+                space: GradientSpace::Local,
+            }
+        );
+        assert_eq!(a.lerp(&b, 1.0), {
+            let mut b = b.clone();
+            if let Brush::LinearGradient { ref mut stops, .. } = b {
+                stops[0] = point(0.5, Color::WHITE);
+            }
+            b
+        });
+
+        // Solid brush turns into a single-colored gradient.
+        assert_eq!(
+            Brush::Solid(Color::WHITE).lerp(&a, 0.0),
+            a.with_color(Color::WHITE)
+        );
+        assert_eq!(
+            Brush::Solid(Color::BLACK).lerp(&Brush::Solid(Color::WHITE), 0.5),
+            Brush::Solid(Color::opaque(127, 127, 127))
+        );
+    }
+
+    #[test]
+    fn brush_animation_update() {
+        let mut animation = BrushAnimation::new(BrushTarget::Foreground)
+            .with_key(1.0, Brush::Solid(Color::WHITE))
+            .with_key(0.0, Brush::Solid(Color::BLACK));
+        assert_eq!(animation.duration(), 1.0);
+        assert_eq!(
+            animation.update(0.5),
+            Some(Brush::Solid(Color::opaque(127, 127, 127)))
+        );
+        assert_eq!(animation.update(1.0), Some(Brush::Solid(Color::WHITE)));
+        assert!(animation.is_finished());
+        assert_eq!(animation.update(0.1), None);
+
+        let mut animation = animation.with_looping(true);
+        animation.time = 0.0;
+        animation.update(1.25);
+        assert_eq!(animation.time, 0.25);
+        assert!(!animation.is_finished());
+
+        let mut ui = crate::UserInterface::new(Vector2::new(100.0, 100.0));
+        let widget = crate::border::BorderBuilder::new(
+            crate::widget::WidgetBuilder::new().with_brush_animation(animation),
+        )
+        .build(&mut ui.build_ctx());
+        ui.update(Vector2::new(100.0, 100.0), 0.25);
+        assert_eq!(
+            ui.node(widget).foreground(),
+            Brush::Solid(Color::opaque(127, 127, 127))
+        );
+    }
+}
@@ -2,8 +2,8 @@
 //! more info.
 
 use crate::{
-    core::pool::Handle, message::UiMessage, ttf::SharedFont, RestrictionEntry, UiNode,
-    UserInterface,
+    core::pool::Handle, icon::IconRegistry, message::UiMessage, number_format::NumberFormat,
+    ttf::SharedFont, RestrictionEntry, UiNode, UserInterface,
 };
 use std::{
     ops::{Index, IndexMut},
@@ -95,6 +95,17 @@ impl<'a> BuildContext<'a> {
         self.ui.default_font.clone()
     }
 
+    /// Returns current number format of the UI. See [`NumberFormat`] docs for
+    /// more info.
+    pub fn number_format(&self) -> &NumberFormat {
+        self.ui.number_format()
+    }
+
+    /// Returns the icon registry of the UI. See [`IconRegistry`] docs for more info.
+    pub fn icons(&self) -> &IconRegistry {
+        self.ui.icons()
+    }
+
     /// Returns current message sender of the UI, that is used for message passing mechanism. You can
     /// send messages for your widgets inside your builders, however this has limited use and should
     /// be avoided in the favor of explicit state modification to not overload message pipeline.
@@ -102,6 +102,10 @@ impl Control for Button {
         node_map.resolve(&mut self.decorator);
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
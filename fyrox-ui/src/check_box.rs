@@ -160,6 +160,10 @@ impl Control for CheckBox {
         node_map.resolve(&mut self.undefined_mark);
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
@@ -1,6 +1,7 @@
 use crate::{
     core::{
-        algebra::Vector2, pool::Handle, reflect::Reflect, scope_profile, uuid::Uuid, visitor::Visit,
+        algebra::Vector2, math::Rect, pool::Handle, reflect::Reflect, scope_profile, uuid::Uuid,
+        visitor::Visit,
     },
     draw::DrawingContext,
     message::{OsEvent, UiMessage},
@@ -306,6 +307,14 @@ pub trait Control: BaseControl + Deref<Target = Widget> + DerefMut + Reflect + V
     ) {
     }
 
+    /// Returns screen-space area of the text cursor of the widget, if the widget accepts text input
+    /// at the moment. It is called only for the widget with keyboard focus, the area is used to
+    /// enable input method editor (IME) and to place its candidate window near the cursor. See
+    /// [`UserInterface::ime_cursor_area`] for more info.
+    fn ime_cursor_area(&self) -> Option<Rect<f32>> {
+        None
+    }
+
     /// Performs event-specific actions. Must call widget.handle_message()!
     ///
     /// # Notes
@@ -358,6 +367,13 @@ pub trait Control: BaseControl + Deref<Target = Widget> + DerefMut + Reflect + V
     ) {
     }
 
+    /// Returns `true` if a user could interact with the widget directly (buttons, check boxes, text
+    /// boxes, etc.). It is used to validate accessibility of user interfaces, see
+    /// [`crate::accessibility`] docs for more info. Default implementation returns `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
     fn id(&self) -> Uuid {
         // TODO: This must be implemented on per-widget basis, but since there's 60+ widgets it
         // is hard to implement in one go, so leaving this default invalid impl.
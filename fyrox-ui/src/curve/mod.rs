@@ -44,6 +44,10 @@ pub enum CurveEditorMessage {
         after_layout: bool,
     },
     HighlightZones(Vec<HighlightZone>),
+    /// Selects keys with the given ids, ids of keys that are not in the curve are ignored. The
+    /// editor sends this message back (with [`MessageDirection::FromWidget`]) every time when
+    /// the selection of keys was changed by a user.
+    SelectKeys(Vec<Uuid>),
 
     // Internal messages. Use only when you know what you're doing.
     // These are internal because you must use Sync message to request changes
@@ -62,6 +66,7 @@ impl CurveEditorMessage {
     define_constructor!(CurveEditorMessage:Zoom => fn zoom(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:ZoomToFit => fn zoom_to_fit(after_layout: bool), layout: true);
     define_constructor!(CurveEditorMessage:HighlightZones => fn hightlight_zones(Vec<HighlightZone>), layout: false);
+    define_constructor!(CurveEditorMessage:SelectKeys => fn select_keys(Vec<Uuid>), layout: false);
     // Internal. Use only when you know what you're doing.
     define_constructor!(CurveEditorMessage:RemoveSelection => fn remove_selection(), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysKind => fn change_selected_keys_kind(CurveKeyKind), layout: false);
@@ -530,6 +535,22 @@ impl Control for CurveEditor {
                         CurveEditorMessage::HighlightZones(zones) => {
                             self.highlight_zones = zones.clone();
                         }
+                        CurveEditorMessage::SelectKeys(ids) => {
+                            let keys = ids
+                                .iter()
+                                .filter(|id| self.key_container.key_ref(**id).is_some())
+                                .cloned()
+                                .collect::<FxHashSet<_>>();
+                            let selection = if keys.is_empty() {
+                                None
+                            } else {
+                                Some(Selection::Keys { keys })
+                            };
+                            // Do not send the selection back, it was set by the user of the
+                            // editor.
+                            self.selection = selection;
+                            self.sync_selection_state(ui);
+                        }
                     }
                 }
             }
@@ -739,9 +760,32 @@ impl CurveEditor {
         self.key_container.sort_keys();
     }
 
+    /// Returns current zoom of the editor.
+    pub fn zoom(&self) -> Vector2<f32> {
+        self.zoom
+    }
+
+    /// Returns current position of the view of the editor.
+    pub fn view_position(&self) -> Vector2<f32> {
+        self.view_position
+    }
+
     fn set_selection(&mut self, selection: Option<Selection>, ui: &UserInterface) {
         self.selection = selection;
+        self.sync_selection_state(ui);
+
+        let keys = match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => keys.iter().cloned().collect(),
+            _ => Vec::new(),
+        };
+        ui.send_message(CurveEditorMessage::select_keys(
+            self.handle,
+            MessageDirection::FromWidget,
+            keys,
+        ));
+    }
 
+    fn sync_selection_state(&self, ui: &UserInterface) {
         ui.send_message(WidgetMessage::enabled(
             self.context_menu.remove,
             MessageDirection::ToWidget,
@@ -0,0 +1,381 @@
+//! Typed custom data slots of widgets. See [`CustomData`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::core::{
+    log::Log,
+    reflect::Reflect,
+    uuid::Uuid,
+    visitor::{BinaryBlob, Visit, VisitError, VisitResult, Visitor},
+    TypeUuidProvider,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Formatter},
+};
+
+trait Slot: Any + Debug {
+    fn clone_box(&self) -> Box<dyn Slot>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
+    // Returns serialized data of the slot, `None` if the slot lives in memory only.
+    fn serialize(&mut self) -> Option<Result<(Uuid, Vec<u8>), VisitError>>;
+}
+
+#[derive(Clone, Debug)]
+struct TransientSlot<T>(T);
+
+impl<T> Slot for TransientSlot<T>
+where
+    T: Any + Clone + Debug,
+{
+    fn clone_box(&self) -> Box<dyn Slot> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        &self.0
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        &mut self.0
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        Box::new(self.0)
+    }
+
+    fn serialize(&mut self) -> Option<Result<(Uuid, Vec<u8>), VisitError>> {
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SerializableSlot<T>(T);
+
+impl<T> Slot for SerializableSlot<T>
+where
+    T: Reflect + Visit + Default + Clone + TypeUuidProvider,
+{
+    fn clone_box(&self) -> Box<dyn Slot> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        &self.0
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        &mut self.0
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        Box::new(self.0)
+    }
+
+    fn serialize(&mut self) -> Option<Result<(Uuid, Vec<u8>), VisitError>> {
+        let mut visitor = Visitor::new();
+        Some(
+            self.0
+                .visit("Data", &mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+                .map(|data| (T::type_uuid(), data)),
+        )
+    }
+}
+
+// Serialized data of a single slot, it is decoded on the first typed access.
+#[derive(Default, Clone, Debug)]
+struct RawSlot {
+    type_uuid: Uuid,
+    data: Vec<u8>,
+}
+
+impl Visit for RawSlot {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+        self.type_uuid.visit("TypeUuid", &mut region)?;
+        BinaryBlob {
+            vec: &mut self.data,
+        }
+        .visit("Data", &mut region)
+    }
+}
+
+/// A set of values of arbitrary types, that could be attached to a widget (see
+/// [`crate::widget::Widget::custom_data`]). There could be only one value of a type, values are
+/// accessed by their type. It allows you to associate application data (for example, a view-model
+/// of a list item) with a widget directly, without maintaining a separate hash map keyed by widget
+/// handles, that must be kept in sync when widgets are removed.
+///
+/// Values added using [`Self::insert`] live in memory only. Values added using
+/// [`Self::insert_serializable`] are saved together with the widget. Since the type of a saved
+/// value is unknown when the widget is loaded, loaded values are kept in serialized form until
+/// they're accessed using [`Self::get_serializable`] (or replaced). Serialized values, that were
+/// never accessed, are saved back as is.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::{
+///     core::{
+///         pool::Handle, reflect::prelude::*, uuid::{uuid, Uuid}, visitor::prelude::*,
+///         TypeUuidProvider,
+///     },
+///     text::TextBuilder,
+///     widget::WidgetBuilder,
+///     BuildContext, UiNode, UserInterface,
+/// };
+///
+/// #[derive(Default, Clone, Debug, Reflect, Visit)]
+/// struct InventoryItem {
+///     item_id: u32,
+///     amount: u32,
+/// }
+///
+/// impl TypeUuidProvider for InventoryItem {
+///     fn type_uuid() -> Uuid {
+///         uuid!("b7b5a6ed-1e4a-4933-9d53-2a6b2f4b9a1b")
+///     }
+/// }
+///
+/// // Runtime-only data, it won't be saved.
+/// #[derive(Clone, Debug)]
+/// struct Selected;
+///
+/// fn create_slot(ctx: &mut BuildContext, item: InventoryItem) -> Handle<UiNode> {
+///     TextBuilder::new(WidgetBuilder::new().with_serializable_custom_data(item))
+///         .with_text("Item")
+///         .build(ctx)
+/// }
+///
+/// fn item_of(ui: &mut UserInterface, slot: Handle<UiNode>) -> Option<u32> {
+///     ui.node_mut(slot)
+///         .custom_data
+///         .get_serializable::<InventoryItem>()
+///         .map(|item| item.item_id)
+/// }
+///
+/// fn select(ui: &mut UserInterface, slot: Handle<UiNode>) {
+///     ui.node_mut(slot).custom_data.insert(Selected);
+/// }
+/// ```
+#[derive(Default)]
+pub struct CustomData {
+    slots: FxHashMap<TypeId, Box<dyn Slot>>,
+    raw: FxHashMap<Uuid, RawSlot>,
+}
+
+impl Debug for CustomData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomData")
+            .field("slots", &self.slots.values().collect::<Vec<_>>())
+            .field("raw", &self.raw.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Clone for CustomData {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self
+                .slots
+                .iter()
+                .map(|(type_id, slot)| (*type_id, slot.clone_box()))
+                .collect(),
+            raw: self.raw.clone(),
+        }
+    }
+}
+
+impl CustomData {
+    /// Adds a value, that lives in memory only. Returns a previous value of the same type (if any).
+    pub fn insert<T>(&mut self, value: T) -> Option<T>
+    where
+        T: Any + Clone + Debug,
+    {
+        self.insert_slot(Box::new(TransientSlot(value)))
+    }
+
+    /// Adds a value, that will be saved together with the widget. Returns a previous value of the
+    /// same type (if any).
+    pub fn insert_serializable<T>(&mut self, value: T) -> Option<T>
+    where
+        T: Reflect + Visit + Default + Clone + TypeUuidProvider,
+    {
+        self.raw.remove(&T::type_uuid());
+        self.insert_slot(Box::new(SerializableSlot(value)))
+    }
+
+    fn insert_slot<T: Any>(&mut self, slot: Box<dyn Slot>) -> Option<T> {
+        self.slots
+            .insert(TypeId::of::<T>(), slot)
+            .and_then(|previous| previous.into_any().downcast::<T>().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Returns a reference to the value of the given type (if any). Values, that are still in
+    /// serialized form, are not returned, use [`Self::get_serializable`] for them.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.slots
+            .get(&TypeId::of::<T>())
+            .and_then(|slot| slot.as_any().downcast_ref::<T>())
+    }
+
+    /// Returns a mutable reference to the value of the given type (if any). Values, that are still
+    /// in serialized form, are not returned, use [`Self::get_serializable`] for them.
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.slots
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|slot| slot.as_any_mut().downcast_mut::<T>())
+    }
+
+    /// Returns a mutable reference to the value of the given type, decoding the value first if it
+    /// was loaded together with the widget.
+    pub fn get_serializable<T>(&mut self) -> Option<&mut T>
+    where
+        T: Reflect + Visit + Default + Clone + TypeUuidProvider,
+    {
+        if let Some(raw) = self.raw.remove(&T::type_uuid()) {
+            let mut value = T::default();
+            match Visitor::load_from_memory(&raw.data)
+                .and_then(|mut visitor| value.visit("Data", &mut visitor))
+            {
+                Ok(()) => {
+                    self.insert_serializable(value);
+                }
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to decode custom data of type {}. Reason: {err:?}",
+                        std::any::type_name::<T>()
+                    ));
+                }
+            }
+        }
+
+        self.get_mut::<T>()
+    }
+
+    /// Removes the value of the given type and returns it (if any).
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        self.slots
+            .remove(&TypeId::of::<T>())
+            .and_then(|slot| slot.into_any().downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    /// Returns `true` if there's a value of the given type in memory.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.slots.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns `true` if there's no values (in memory and serialized).
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty() && self.raw.is_empty()
+    }
+
+    /// Removes every value.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.raw.clear();
+    }
+}
+
+impl Visit for CustomData {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut raw = if region.is_reading() {
+            Vec::new()
+        } else {
+            let mut raw = self.raw.values().cloned().collect::<Vec<_>>();
+            for slot in self.slots.values_mut() {
+                if let Some(result) = slot.serialize() {
+                    let (type_uuid, data) = result?;
+                    raw.push(RawSlot { type_uuid, data });
+                }
+            }
+            raw
+        };
+
+        raw.visit("Slots", &mut region)?;
+
+        if region.is_reading() {
+            self.slots.clear();
+            self.raw = raw.into_iter().map(|raw| (raw.type_uuid, raw)).collect();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{reflect::prelude::*, uuid::uuid};
+
+    #[derive(Default, Clone, Debug, PartialEq, Reflect, Visit)]
+    struct ViewModel {
+        id: u32,
+        title: String,
+    }
+
+    impl TypeUuidProvider for ViewModel {
+        fn type_uuid() -> Uuid {
+            uuid!("6b3c7e1a-53fd-4c1f-9bb4-cf5cf4d5a8e2")
+        }
+    }
+
+    #[test]
+    fn custom_data_slots() {
+        let mut data = CustomData::default();
+        assert!(data.is_empty());
+        assert_eq!(data.insert(5u32), None);
+        assert_eq!(data.insert(7u32), Some(5));
+        *data.get_mut::<u32>().unwrap() += 1;
+        assert_eq!(data.get::<u32>(), Some(&8));
+        assert_eq!(data.get::<i32>(), None);
+
+        let model = ViewModel {
+            id: 42,
+            title: "Sword".to_string(),
+        };
+        data.insert_serializable(model.clone());
+        assert_eq!(data.get::<ViewModel>(), Some(&model));
+
+        let clone = data.clone();
+        assert_eq!(clone.get::<u32>(), Some(&8));
+
+        // Only serializable values are saved.
+        let mut visitor = Visitor::new();
+        data.visit("Data", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+
+        let mut loaded = CustomData::default();
+        let mut visitor = Visitor::load_from_memory(&bytes).unwrap();
+        loaded.visit("Data", &mut visitor).unwrap();
+        assert!(!loaded.contains::<u32>());
+        assert_eq!(loaded.get::<ViewModel>(), None);
+
+        // Values that weren't accessed survive saving.
+        let mut visitor = Visitor::new();
+        loaded.visit("Data", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+        let mut loaded = CustomData::default();
+        let mut visitor = Visitor::load_from_memory(&bytes).unwrap();
+        loaded.visit("Data", &mut visitor).unwrap();
+
+        assert_eq!(
+            loaded.get_serializable::<ViewModel>(),
+            Some(&mut model.clone())
+        );
+        assert_eq!(loaded.remove::<ViewModel>(), Some(model));
+        assert!(loaded.is_empty());
+    }
+}
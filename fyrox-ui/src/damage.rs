@@ -0,0 +1,357 @@
+//! Damage tracking for partial redraws of user interfaces. See [`DamageRegion`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::{Brush, GradientPoint},
+    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    draw::{Command, CommandTexture, DrawingContext, Vertex},
+    UiNode,
+};
+use fxhash::{FxHashMap, FxHasher64};
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// Maximum amount of separate rectangles in a damage region, regions with more rectangles are
+/// collapsed into a single bounding rectangle.
+const MAX_DAMAGE_RECTS: usize = 16;
+
+/// Damage region is a set of screen-space rectangles, that have changed since the previous
+/// [`crate::UserInterface::draw`] call. It allows a host application, that displays a user
+/// interface on its own (tools built on fyrox-ui, for example), to skip presenting a frame
+/// entirely if nothing has changed, or to re-render only the damaged parts of the screen (using
+/// scissor rectangles, for example), if the content of the previous frame is preserved. This saves
+/// a lot of CPU/GPU time (and battery) for mostly static user interfaces.
+///
+/// Damage tracking is disabled by default (see [`crate::UserInterface::set_damage_tracking`]),
+/// in this case the damage region always covers the whole screen.
+///
+/// ## How it works
+///
+/// After every draw, a snapshot of the visuals of every widget is made: a hash of every draw
+/// command it has produced (geometry, brush, texture, clipping, opacity) and the screen-space
+/// bounds of the commands. On the next draw, the snapshots are compared and the old and the new
+/// bounds of every changed, added or removed widget are added to the damage region. If the
+/// drawing order of widgets has changed (a window was brought to front, for example), the whole
+/// screen is damaged.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::{core::algebra::Vector2, UserInterface};
+///
+/// fn render_frame(ui: &mut UserInterface) {
+///     ui.update(Vector2::new(800.0, 600.0), 1.0 / 60.0);
+///     ui.draw();
+///     let damage = ui.damage();
+///     if damage.is_empty() {
+///         // Nothing has changed, the previous frame could be presented again.
+///         return;
+///     }
+///     for rect in damage.rects() {
+///         // Render the drawing context with a scissor rectangle `rect`.
+///     }
+/// }
+///
+/// let mut ui = UserInterface::new(Vector2::new(800.0, 600.0));
+/// ui.set_damage_tracking(true);
+/// render_frame(&mut ui);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DamageRegion {
+    rects: Vec<Rect<f32>>,
+    full: bool,
+}
+
+impl DamageRegion {
+    /// Creates a damage region, that covers the whole screen of the given size.
+    pub fn full(screen_size: Vector2<f32>) -> Self {
+        Self {
+            rects: vec![Rect::new(0.0, 0.0, screen_size.x, screen_size.y)],
+            full: true,
+        }
+    }
+
+    /// Returns `true` if nothing has changed.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns `true` if the whole screen is damaged.
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Returns a list of non-overlapping damaged rectangles in screen space.
+    pub fn rects(&self) -> &[Rect<f32>] {
+        &self.rects
+    }
+
+    /// Returns a bounding rectangle of every damaged rectangle (if any).
+    pub fn bounds(&self) -> Option<Rect<f32>> {
+        let (first, rest) = self.rects.split_first()?;
+        Some(
+            rest.iter()
+                .fold(*first, |bounds, rect| union(bounds, *rect)),
+        )
+    }
+
+    /// Checks whether the given rectangle intersects the damage region.
+    pub fn intersects(&self, rect: Rect<f32>) -> bool {
+        self.rects.iter().any(|damaged| damaged.intersects(rect))
+    }
+
+    fn add(&mut self, rect: Rect<f32>) {
+        if rect.w() <= 0.0 || rect.h() <= 0.0 {
+            return;
+        }
+
+        // Merge every rectangle, that overlaps the new one, so the rectangles never overlap.
+        let mut rect = rect;
+        let mut i = 0;
+        while i < self.rects.len() {
+            if self.rects[i].intersects(rect) {
+                rect = union(rect, self.rects.swap_remove(i));
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        self.rects.push(rect);
+
+        if self.rects.len() > MAX_DAMAGE_RECTS {
+            let bounds = self.bounds();
+            self.rects.clear();
+            self.rects.extend(bounds);
+        }
+    }
+}
+
+fn union(a: Rect<f32>, b: Rect<f32>) -> Rect<f32> {
+    let left_top = a.left_top_corner().inf(&b.left_top_corner());
+    let right_bottom = a.right_bottom_corner().sup(&b.right_bottom_corner());
+    Rect::new(
+        left_top.x,
+        left_top.y,
+        right_bottom.x - left_top.x,
+        right_bottom.y - left_top.y,
+    )
+}
+
+#[derive(Debug)]
+struct VisualSnapshot {
+    hash: u64,
+    bounds: Rect<f32>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DamageTracker {
+    enabled: bool,
+    invalidated: bool,
+    screen_size: Vector2<f32>,
+    snapshots: FxHashMap<Handle<UiNode>, VisualSnapshot>,
+    order: Vec<Handle<UiNode>>,
+    region: DamageRegion,
+}
+
+fn hash_f32(value: f32, hasher: &mut FxHasher64) {
+    value.to_bits().hash(hasher)
+}
+
+fn hash_vector(value: Vector2<f32>, hasher: &mut FxHasher64) {
+    hash_f32(value.x, hasher);
+    hash_f32(value.y, hasher);
+}
+
+fn hash_rect(value: &Rect<f32>, hasher: &mut FxHasher64) {
+    hash_vector(value.position, hasher);
+    hash_vector(value.size, hasher);
+}
+
+fn hash_color(value: Color, hasher: &mut FxHasher64) {
+    [value.r, value.g, value.b, value.a].hash(hasher)
+}
+
+fn hash_vertex(vertex: &Vertex, hasher: &mut FxHasher64) {
+    hash_vector(vertex.pos, hasher);
+    hash_vector(vertex.tex_coord, hasher);
+    hash_color(vertex.color, hasher);
+}
+
+fn hash_stops(stops: &[GradientPoint], hasher: &mut FxHasher64) {
+    for stop in stops {
+        hash_f32(stop.stop, hasher);
+        hash_color(stop.color, hasher);
+    }
+}
+
+fn hash_brush(brush: &Brush, hasher: &mut FxHasher64) {
+    std::mem::discriminant(brush).hash(hasher);
+    match brush {
+        Brush::Solid(color) => hash_color(*color, hasher),
+        Brush::LinearGradient {
+            from,
+            to,
+            stops,
+            space,
+        } => {
+            hash_vector(*from, hasher);
+            hash_vector(*to, hasher);
+            hash_stops(stops, hasher);
+            std::mem::discriminant(space).hash(hasher);
+        }
+        Brush::RadialGradient {
+            center,
+            stops,
+            radius,
+            space,
+        } => {
+            hash_vector(*center, hasher);
+            hash_stops(stops, hasher);
+            radius.map(f32::to_bits).hash(hasher);
+            std::mem::discriminant(space).hash(hasher);
+        }
+    }
+}
+
+fn hash_command(command: &Command, drawing_context: &DrawingContext, hasher: &mut FxHasher64) {
+    hash_rect(&command.clip_bounds, hasher);
+    hash_brush(&command.brush, hasher);
+    match &command.texture {
+        CommandTexture::None => 0usize.hash(hasher),
+        CommandTexture::Texture(texture) => {
+            (Arc::as_ptr(&texture.0) as *const () as usize).hash(hasher)
+        }
+        CommandTexture::Font(font) => (Arc::as_ptr(&font.0) as *const () as usize).hash(hasher),
+    }
+    hash_f32(command.opacity, hasher);
+
+    // Triangles store absolute indices of vertices, that are shifted every time when some widget
+    // earlier in the drawing order changes its geometry, so only the vertices are hashed.
+    let vertices = drawing_context.get_vertices();
+    for triangle in &drawing_context.get_triangles()[command.triangles.clone()] {
+        for index in triangle.0 {
+            if let Some(vertex) = vertices.get(index as usize) {
+                hash_vertex(vertex, hasher);
+            }
+        }
+    }
+
+    if let Some(clipping_geometry) = command.clipping_geometry.as_ref() {
+        for triangle in clipping_geometry.triangle_buffer.iter() {
+            if let Some((a, b, c)) = clipping_geometry.triangle_points(triangle) {
+                hash_vertex(a, hasher);
+                hash_vertex(b, hasher);
+                hash_vertex(c, hasher);
+            }
+        }
+    }
+}
+
+impl DamageTracker {
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            self.invalidate();
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        self.invalidated = true;
+        self.snapshots.clear();
+        self.order.clear();
+    }
+
+    pub(crate) fn region(&self) -> &DamageRegion {
+        &self.region
+    }
+
+    /// Compares visual snapshots of every widget with the snapshots of the previous frame and
+    /// collects damaged regions. `drawn` must yield widgets in drawing order.
+    pub(crate) fn update<'a>(
+        &mut self,
+        screen_size: Vector2<f32>,
+        drawing_context: &DrawingContext,
+        drawn: impl Iterator<Item = (Handle<UiNode>, &'a [usize])>,
+    ) {
+        if !self.enabled {
+            self.region = DamageRegion::full(screen_size);
+            return;
+        }
+
+        let mut full = std::mem::take(&mut self.invalidated) || self.screen_size != screen_size;
+        self.screen_size = screen_size;
+
+        let commands = drawing_context.get_commands();
+        let mut snapshots =
+            FxHashMap::with_capacity_and_hasher(self.snapshots.len(), Default::default());
+        let mut order = Vec::with_capacity(self.order.len());
+        for (handle, command_indices) in drawn {
+            let mut hasher = FxHasher64::default();
+            let mut bounds: Option<Rect<f32>> = None;
+            for command in command_indices.iter().filter_map(|i| commands.get(*i)) {
+                hash_command(command, drawing_context, &mut hasher);
+                let command_bounds = command.bounds.clip_by(command.clip_bounds);
+                bounds = Some(match bounds {
+                    Some(bounds) => union(bounds, command_bounds),
+                    None => command_bounds,
+                });
+            }
+            if let Some(bounds) = bounds {
+                let hash = hasher.finish();
+                snapshots.insert(handle, VisualSnapshot { hash, bounds });
+                order.push(handle);
+            }
+        }
+
+        // Drawing order of the widgets, that are drawn in both frames, must stay the same,
+        // otherwise overlapping widgets could change their visibility without any changes in their
+        // visuals.
+        if !full {
+            let old_order = self
+                .order
+                .iter()
+                .filter(|handle| snapshots.contains_key(*handle));
+            let new_order = order
+                .iter()
+                .filter(|handle| self.snapshots.contains_key(*handle));
+            full = !old_order.eq(new_order);
+        }
+
+        let mut region = DamageRegion::default();
+        if !full {
+            for (handle, snapshot) in snapshots.iter() {
+                match self.snapshots.get(handle) {
+                    Some(old) => {
+                        if old.hash != snapshot.hash || old.bounds != snapshot.bounds {
+                            region.add(old.bounds);
+                            region.add(snapshot.bounds);
+                        }
+                    }
+                    None => region.add(snapshot.bounds),
+                }
+            }
+
+            // Widgets, that were removed or are not drawn anymore.
+            for (handle, old) in self.snapshots.iter() {
+                if !snapshots.contains_key(handle) {
+                    region.add(old.bounds);
+                }
+            }
+        }
+
+        self.snapshots = snapshots;
+        self.order = order;
+        self.region = if full {
+            DamageRegion::full(screen_size)
+        } else {
+            region
+        };
+    }
+}
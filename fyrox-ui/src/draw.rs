@@ -6,6 +6,7 @@ use crate::{
         math::{self, Rect, TriangleDefinition},
     },
     formatted_text::FormattedText,
+    stroke::{LineCap, LineJoin, StrokeStyle},
     ttf::SharedFont,
     Thickness,
 };
@@ -468,6 +469,110 @@ pub trait Draw {
             prev = next;
         }
     }
+
+    /// Pushes a polyline with the given stroke style. If `closed` is `true`, the last point is
+    /// connected to the first one. See [`StrokeStyle`] docs for more info.
+    fn push_polyline_styled(
+        &mut self,
+        points: &[Vector2<f32>],
+        closed: bool,
+        thickness: f32,
+        style: &StrokeStyle,
+    ) {
+        if style.is_solid() {
+            push_stroke(self, points, closed, thickness, style);
+        } else {
+            for dash in style.dashes(points, closed) {
+                push_stroke(self, &dash, false, thickness, style);
+            }
+        }
+    }
+
+    /// Pushes a line with the given stroke style. See [`StrokeStyle`] docs for more info.
+    fn push_line_styled(
+        &mut self,
+        a: Vector2<f32>,
+        b: Vector2<f32>,
+        thickness: f32,
+        style: &StrokeStyle,
+    ) {
+        self.push_polyline_styled(&[a, b], false, thickness, style)
+    }
+
+    /// Pushes an outline of the given rectangle with the given stroke style. The outline is placed
+    /// inside of the rectangle, the same as with [`Self::push_rect`].
+    fn push_rect_styled(&mut self, rect: &Rect<f32>, thickness: f32, style: &StrokeStyle) {
+        let offset = thickness * 0.5;
+        let left = rect.x() + offset;
+        let top = rect.y() + offset;
+        let right = rect.x() + rect.w() - offset;
+        let bottom = rect.y() + rect.h() - offset;
+        self.push_polyline_styled(
+            &[
+                Vector2::new(left, top),
+                Vector2::new(right, top),
+                Vector2::new(right, bottom),
+                Vector2::new(left, bottom),
+            ],
+            true,
+            thickness,
+            style,
+        );
+    }
+
+    /// Pushes an outline of the given rectangle with different thickness for each side and the
+    /// given stroke style. Solid style with miter joins gives exactly the same result as
+    /// [`Self::push_rect_vary`]. If the thickness differs for some sides, every side is drawn as
+    /// a separate line, but the dash pattern still continues over the corners.
+    fn push_rect_vary_styled(
+        &mut self,
+        rect: &Rect<f32>,
+        thickness: Thickness,
+        style: &StrokeStyle,
+    ) {
+        if style.is_solid() && style.join == LineJoin::Miter {
+            self.push_rect_vary(rect, thickness);
+        } else if thickness.left == thickness.top
+            && thickness.left == thickness.right
+            && thickness.left == thickness.bottom
+        {
+            self.push_rect_styled(rect, thickness.left, style);
+        } else {
+            let left = rect.x() + thickness.left * 0.5;
+            let top = rect.y() + thickness.top * 0.5;
+            let right = rect.x() + rect.w() - thickness.right * 0.5;
+            let bottom = rect.y() + rect.h() - thickness.bottom * 0.5;
+            let sides = [
+                (
+                    Vector2::new(rect.x(), top),
+                    Vector2::new(rect.x() + rect.w(), top),
+                    thickness.top,
+                ),
+                (
+                    Vector2::new(right, rect.y()),
+                    Vector2::new(right, rect.y() + rect.h()),
+                    thickness.right,
+                ),
+                (
+                    Vector2::new(rect.x() + rect.w(), bottom),
+                    Vector2::new(rect.x(), bottom),
+                    thickness.bottom,
+                ),
+                (
+                    Vector2::new(left, rect.y() + rect.h()),
+                    Vector2::new(left, rect.y()),
+                    thickness.left,
+                ),
+            ];
+            let mut style = style.clone();
+            for (a, b, thickness) in sides {
+                if thickness > 0.0 {
+                    self.push_line_styled(a, b, thickness, &style);
+                }
+                style.dash_offset += (b - a).norm();
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -516,6 +621,181 @@ pub struct DrawingContext {
     triangles_to_commit: usize,
 }
 
+fn push_fan<D: Draw + ?Sized>(
+    ctx: &mut D,
+    center: Vector2<f32>,
+    radius: f32,
+    start_angle: f32,
+    sweep: f32,
+) {
+    let steps = ((sweep.abs() / (std::f32::consts::PI / 8.0)).ceil() as u32).max(1);
+    let index = ctx.last_vertex_index();
+    ctx.push_vertex(center, Vector2::default());
+    for i in 0..=steps {
+        let angle = start_angle + sweep * i as f32 / steps as f32;
+        ctx.push_vertex(
+            center + Vector2::new(angle.cos(), angle.sin()).scale(radius),
+            Vector2::default(),
+        );
+    }
+    for i in 0..steps {
+        ctx.push_triangle(index, index + i + 1, index + i + 2);
+    }
+}
+
+fn push_join<D: Draw + ?Sized>(
+    ctx: &mut D,
+    prev: Vector2<f32>,
+    point: Vector2<f32>,
+    next: Vector2<f32>,
+    half_thickness: f32,
+    style: &StrokeStyle,
+) {
+    let (Some(d0), Some(d1)) = (
+        (point - prev).try_normalize(f32::EPSILON),
+        (next - point).try_normalize(f32::EPSILON),
+    ) else {
+        return;
+    };
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    if cross.abs() <= f32::EPSILON && d0.dot(&d1) > 0.0 {
+        // Collinear segments, nothing to join.
+        return;
+    }
+
+    // Offsets to the outer side of the corner, perpendiculars are the same as for `push_line`.
+    let side = if cross > 0.0 { 1.0 } else { -1.0 };
+    let n0 = Vector2::new(d0.y, -d0.x).scale(side);
+    let n1 = Vector2::new(d1.y, -d1.x).scale(side);
+
+    let mut join = style.join;
+    if join == LineJoin::Miter {
+        let miter = (n0 + n1)
+            .try_normalize(f32::EPSILON)
+            .map(|miter| (miter, 1.0 / miter.dot(&n0)));
+        match miter {
+            Some((miter, ratio)) if ratio.is_finite() && ratio <= style.miter_limit => {
+                let tip = point + miter.scale(half_thickness * ratio);
+                let a = point + n0.scale(half_thickness);
+                let b = point + n1.scale(half_thickness);
+                ctx.push_triangle_filled([point, a, tip]);
+                ctx.push_triangle_filled([point, tip, b]);
+                return;
+            }
+            _ => join = LineJoin::Bevel,
+        }
+    }
+
+    match join {
+        LineJoin::Round => {
+            let start_angle = n0.y.atan2(n0.x);
+            let sweep = math::wrap_angle(n1.y.atan2(n1.x) - start_angle);
+            let sweep = if sweep > std::f32::consts::PI {
+                sweep - 2.0 * std::f32::consts::PI
+            } else {
+                sweep
+            };
+            push_fan(ctx, point, half_thickness, start_angle, sweep);
+        }
+        _ => ctx.push_triangle_filled([
+            point,
+            point + n0.scale(half_thickness),
+            point + n1.scale(half_thickness),
+        ]),
+    }
+}
+
+fn push_stroke<D: Draw + ?Sized>(
+    ctx: &mut D,
+    points: &[Vector2<f32>],
+    closed: bool,
+    thickness: f32,
+    style: &StrokeStyle,
+) {
+    let half_thickness = thickness * 0.5;
+
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let closed = closed && points.len() > 2;
+
+    match points.as_slice() {
+        [] => return,
+        [point] => {
+            match style.cap {
+                LineCap::Butt => (),
+                LineCap::Square => ctx.push_rect_filled(
+                    &Rect::new(
+                        point.x - half_thickness,
+                        point.y - half_thickness,
+                        thickness,
+                        thickness,
+                    ),
+                    None,
+                ),
+                LineCap::Round => {
+                    push_fan(ctx, *point, half_thickness, 0.0, 2.0 * std::f32::consts::PI)
+                }
+            }
+            return;
+        }
+        _ => (),
+    }
+
+    let count = points.len();
+    let segment_count = if closed { count } else { count - 1 };
+    for i in 0..segment_count {
+        let mut a = points[i];
+        let mut b = points[(i + 1) % count];
+        if !closed && style.cap == LineCap::Square {
+            let extension = (b - a)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default()
+                .scale(half_thickness);
+            if i == 0 {
+                a -= extension;
+            }
+            if i == segment_count - 1 {
+                b += extension;
+            }
+        }
+        ctx.push_line(a, b, thickness);
+    }
+
+    let joints = if closed { 0..count } else { 1..count - 1 };
+    for i in joints {
+        push_join(
+            ctx,
+            points[(i + count - 1) % count],
+            points[i],
+            points[(i + 1) % count],
+            half_thickness,
+            style,
+        );
+    }
+
+    if !closed && style.cap == LineCap::Round {
+        for (end, towards) in [
+            (points[0], points[1]),
+            (points[count - 1], points[count - 2]),
+        ] {
+            if let Some(dir) = (end - towards).try_normalize(f32::EPSILON) {
+                // Half circle from one side of the line to other, through the direction of the end.
+                let n = Vector2::new(dir.y, -dir.x);
+                push_fan(
+                    ctx,
+                    end,
+                    half_thickness,
+                    n.y.atan2(n.x),
+                    std::f32::consts::PI,
+                );
+            }
+        }
+    }
+}
+
 fn get_line_thickness_vector(a: Vector2<f32>, b: Vector2<f32>, thickness: f32) -> Vector2<f32> {
     if let Some(dir) = (b - a).try_normalize(f32::EPSILON) {
         Vector2::new(dir.y, -dir.x).scale(thickness * 0.5)
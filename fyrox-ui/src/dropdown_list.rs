@@ -80,6 +80,10 @@ impl Control for DropdownList {
         node_map.resolve_slice(&mut self.items);
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
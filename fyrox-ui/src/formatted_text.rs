@@ -102,8 +102,12 @@ pub struct FormattedText {
     // when we changing text too frequently, here we sacrifice some memory in order to get
     // more performance.
     lines: Vec<TextLine>,
-    // Final glyphs for draw buffer.
+    // Final glyphs for draw buffer. It contains glyphs only for `glyph_lines`.
     glyphs: Vec<TextGlyph>,
+    // Vertical range (in local coordinates), that is visible. `None` - the whole text is visible.
+    visible_range: Option<Range<f32>>,
+    // Range of lines, that have their glyphs generated.
+    glyph_lines: Range<usize>,
     vertical_alignment: VerticalAlignment,
     horizontal_alignment: HorizontalAlignment,
     brush: Brush,
@@ -114,6 +118,9 @@ pub struct FormattedText {
     pub shadow_brush: Brush,
     pub shadow_dilation: f32,
     pub shadow_offset: Vector2<f32>,
+    // Size, that was returned by the last build. `None` - the layout must be rebuilt.
+    built_size: Option<Vector2<f32>>,
+    content_size: Vector2<f32>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -133,6 +140,7 @@ impl FormattedText {
 
     pub fn set_font(&mut self, font: SharedFont) -> &mut Self {
         self.font = font;
+        self.built_size = None;
         self
     }
 
@@ -140,8 +148,100 @@ impl FormattedText {
         &self.lines
     }
 
+    /// Sets a vertical range (in local coordinates of the text), that is visible. Glyphs are
+    /// generated (and drawn) only for the lines, that intersect the range, which makes the cost of
+    /// very large texts (documents with tens of thousands of lines, logs, etc.) proportional to the
+    /// amount of visible lines. Layout of the lines is still computed for the whole text. `None`
+    /// (default) means that the whole text is visible.
+    ///
+    /// Glyphs are regenerated right away only if the set of visible lines has changed, so the
+    /// method could be called every frame (when the text is scrolled, for example).
+    pub fn set_visible_range(&mut self, range: Option<Range<f32>>) -> &mut Self {
+        self.visible_range = range;
+        if self.visible_lines() != self.glyph_lines {
+            self.generate_glyphs();
+        }
+        self
+    }
+
+    /// Returns current visible vertical range of the text. See [`Self::set_visible_range`] for more
+    /// info.
+    pub fn visible_range(&self) -> Option<Range<f32>> {
+        self.visible_range.clone()
+    }
+
+    /// Returns a range of lines, that have their glyphs generated (see [`Self::get_glyphs`] and
+    /// [`Self::set_visible_range`]).
+    pub fn glyph_lines(&self) -> Range<usize> {
+        self.glyph_lines.clone()
+    }
+
+    /// Returns a range of lines, that intersect the visible range.
+    fn visible_lines(&self) -> Range<usize> {
+        match self.visible_range.as_ref() {
+            Some(range) => {
+                let begin = self
+                    .lines
+                    .partition_point(|line| line.y_offset + line.height <= range.start);
+                let end = self.lines.partition_point(|line| line.y_offset < range.end);
+                begin..end.max(begin)
+            }
+            None => 0..self.lines.len(),
+        }
+    }
+
+    fn generate_glyphs(&mut self) {
+        let font = self.font.0.lock();
+
+        self.glyphs.clear();
+        self.glyph_lines = self.visible_lines();
+
+        for line in self.lines[self.glyph_lines.clone()].iter() {
+            let mut cursor = Vector2::new(line.x_offset, line.y_offset);
+            let end = line.end.min(self.text.len());
+            for &character in self.text[line.begin.min(end)..end].iter() {
+                let character = self.mask_char.unwrap_or(character);
+                match font.glyphs().get(character.glyph_index as usize) {
+                    Some(glyph) => {
+                        // Insert glyph
+                        let rect = Rect::new(
+                            cursor.x + glyph.left.floor(),
+                            cursor.y + font.ascender().floor()
+                                - glyph.top.floor()
+                                - glyph.bitmap_height as f32,
+                            glyph.bitmap_width as f32,
+                            glyph.bitmap_height as f32,
+                        );
+                        let text_glyph = TextGlyph {
+                            bounds: rect,
+                            tex_coords: glyph.tex_coords,
+                        };
+                        self.glyphs.push(text_glyph);
+
+                        cursor.x += glyph.advance;
+                    }
+                    None => {
+                        // Insert invalid symbol
+                        let rect = Rect::new(
+                            cursor.x,
+                            cursor.y + font.ascender(),
+                            font.height(),
+                            font.height(),
+                        );
+                        self.glyphs.push(TextGlyph {
+                            bounds: rect,
+                            tex_coords: [Vector2::default(); 4],
+                        });
+                        cursor.x += rect.w();
+                    }
+                }
+            }
+        }
+    }
+
     pub fn set_vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
         self.vertical_alignment = vertical_alignment;
+        self.built_size = None;
         self
     }
 
@@ -154,6 +254,7 @@ impl FormattedText {
         horizontal_alignment: HorizontalAlignment,
     ) -> &mut Self {
         self.horizontal_alignment = horizontal_alignment;
+        self.built_size = None;
         self
     }
 
@@ -171,7 +272,10 @@ impl FormattedText {
     }
 
     pub fn set_constraint(&mut self, constraint: Vector2<f32>) -> &mut Self {
-        self.constraint = constraint;
+        if self.constraint != constraint {
+            self.constraint = constraint;
+            self.built_size = None;
+        }
         self
     }
 
@@ -210,11 +314,13 @@ impl FormattedText {
 
         drop(font);
 
+        self.built_size = None;
         self
     }
 
     pub fn set_wrap(&mut self, wrap: WrapMode) -> &mut Self {
         self.wrap = wrap;
+        self.built_size = None;
         self
     }
 
@@ -255,34 +361,55 @@ impl FormattedText {
 
         drop(font);
 
+        self.built_size = None;
         self
     }
 
     pub fn insert_str(&mut self, str: &str, position: usize) -> &mut Self {
         let font = self.font.0.lock();
 
-        for (i, code) in str.chars().enumerate() {
-            self.text.insert(
-                position + i,
-                Character::from_char_with_font(code as u32, &font),
-            );
-        }
+        // Insert everything at once, inserting character by character shifts the rest of the
+        // text every time.
+        self.text.splice(
+            position..position,
+            str.chars()
+                .map(|code| Character::from_char_with_font(code as u32, &font)),
+        );
 
         drop(font);
 
+        self.built_size = None;
         self
     }
 
     pub fn remove_range(&mut self, range: Range<usize>) -> &mut Self {
         self.text.drain(range);
+        self.built_size = None;
         self
     }
 
     pub fn remove_at(&mut self, index: usize) -> &mut Self {
         self.text.remove(index);
+        self.built_size = None;
         self
     }
 
+    /// Returns the size of the area, that is occupied by the lines of the text. Unlike the size
+    /// returned by [`Self::build`], it includes offsets of the lines. It is valid only after the
+    /// text was built.
+    pub fn content_size(&self) -> Vector2<f32> {
+        self.content_size
+    }
+
+    /// Same as [`Self::build`], but does nothing if the text was already built and nothing, that
+    /// affects the layout (text, font, constraint, alignment or wrapping), was changed since then.
+    pub fn build_if_needed(&mut self) -> Vector2<f32> {
+        match self.built_size {
+            Some(size) => size,
+            None => self.build(),
+        }
+    }
+
     pub fn build(&mut self) -> Vector2<f32> {
         let font = self.font.0.lock();
 
@@ -428,9 +555,6 @@ impl FormattedText {
             }
         }
 
-        // Generate glyphs for each text line.
-        self.glyphs.clear();
-
         let cursor_y_start = match self.vertical_alignment {
             VerticalAlignment::Top => 0.0,
             VerticalAlignment::Center => {
@@ -450,55 +574,11 @@ impl FormattedText {
             VerticalAlignment::Stretch => 0.0,
         };
 
-        let cursor_x_start = if self.constraint.x.is_infinite() {
-            0.0
-        } else {
-            self.constraint.x
-        };
-
-        let mut cursor = Vector2::new(cursor_x_start, cursor_y_start);
+        let mut cursor_y = cursor_y_start;
         for line in self.lines.iter_mut() {
-            cursor.x = line.x_offset;
-
-            for &character in text.iter().take(line.end).skip(line.begin) {
-                match font.glyphs().get(character.glyph_index as usize) {
-                    Some(glyph) => {
-                        // Insert glyph
-                        let rect = Rect::new(
-                            cursor.x + glyph.left.floor(),
-                            cursor.y + font.ascender().floor()
-                                - glyph.top.floor()
-                                - glyph.bitmap_height as f32,
-                            glyph.bitmap_width as f32,
-                            glyph.bitmap_height as f32,
-                        );
-                        let text_glyph = TextGlyph {
-                            bounds: rect,
-                            tex_coords: glyph.tex_coords,
-                        };
-                        self.glyphs.push(text_glyph);
-
-                        cursor.x += glyph.advance;
-                    }
-                    None => {
-                        // Insert invalid symbol
-                        let rect = Rect::new(
-                            cursor.x,
-                            cursor.y + font.ascender(),
-                            font.height(),
-                            font.height(),
-                        );
-                        self.glyphs.push(TextGlyph {
-                            bounds: rect,
-                            tex_coords: [Vector2::default(); 4],
-                        });
-                        cursor.x += rect.w();
-                    }
-                }
-            }
             line.height = font.ascender();
-            line.y_offset = cursor.y;
-            cursor.y += font.ascender();
+            line.y_offset = cursor_y;
+            cursor_y += font.ascender();
         }
 
         // Minus here is because descender has negative value.
@@ -506,6 +586,19 @@ impl FormattedText {
         for line in self.lines.iter() {
             full_size.x = line.width.max(full_size.x);
         }
+
+        self.content_size = Vector2::default();
+        for line in self.lines.iter() {
+            self.content_size.x = self.content_size.x.max(line.x_offset + line.width);
+            self.content_size.y = self.content_size.y.max(line.y_offset + line.height);
+        }
+        self.built_size = Some(full_size);
+
+        drop(font);
+
+        // Generate glyphs for each visible text line.
+        self.generate_glyphs();
+
         full_size
     }
 }
@@ -614,6 +707,8 @@ impl FormattedTextBuilder {
                 .collect(),
             lines: Vec::new(),
             glyphs: Vec::new(),
+            visible_range: None,
+            glyph_lines: 0..0,
             vertical_alignment: self.vertical_alignment,
             horizontal_alignment: self.horizontal_alignment,
             brush: self.brush,
@@ -630,6 +725,8 @@ impl FormattedTextBuilder {
             },
             shadow_dilation: self.shadow_dilation,
             shadow_offset: self.shadow_offset,
+            built_size: None,
+            content_size: Vector2::default(),
         }
     }
 }
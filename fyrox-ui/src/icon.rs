@@ -0,0 +1,720 @@
+//! Standard icon set and the widget to display icons. See [`IconRegistry`] and [`Icon`] docs for
+//! more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{
+        algebra::Vector2, color::Color, math::Rect, parking_lot::Mutex, pool::Handle,
+        reflect::prelude::*, visitor::prelude::*,
+    },
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext, SharedTexture},
+    message::{MessageDirection, UiMessage},
+    utils::{make_arrow_primitives, ArrowDirection},
+    vector_image::Primitive,
+    widget::{Widget, WidgetBuilder},
+    BuildContext, Control, UiNode, UserInterface, COLOR_BRIGHT_BLUE, COLOR_LIGHTEST, COLOR_TEXT,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Semantic role of an icon, that defines its color in an [`IconTheme`].
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum IconRole {
+    /// Regular icon.
+    #[default]
+    Normal,
+    /// Highlighted icon (active tool, selected item, etc.).
+    Accent,
+    /// Icon, that indicates successful result.
+    Success,
+    /// Icon, that indicates a warning.
+    Warning,
+    /// Icon, that indicates an error.
+    Error,
+    /// Icon of a disabled control.
+    Disabled,
+}
+
+/// A set of colors of icons for every [`IconRole`]. Theme is stored in an [`IconRegistry`], so every
+/// icon, that uses a role tint (see [`IconTint::Role`]), changes its color immediately when the
+/// theme is changed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IconTheme {
+    /// Color of [`IconRole::Normal`] icons.
+    pub normal: Color,
+    /// Color of [`IconRole::Accent`] icons.
+    pub accent: Color,
+    /// Color of [`IconRole::Success`] icons.
+    pub success: Color,
+    /// Color of [`IconRole::Warning`] icons.
+    pub warning: Color,
+    /// Color of [`IconRole::Error`] icons.
+    pub error: Color,
+    /// Color of [`IconRole::Disabled`] icons.
+    pub disabled: Color,
+}
+
+impl Default for IconTheme {
+    fn default() -> Self {
+        Self {
+            normal: COLOR_TEXT,
+            accent: COLOR_BRIGHT_BLUE,
+            success: Color::opaque(90, 180, 90),
+            warning: Color::opaque(220, 170, 60),
+            error: Color::opaque(210, 70, 70),
+            disabled: COLOR_LIGHTEST,
+        }
+    }
+}
+
+impl IconTheme {
+    /// Returns a color of the given role.
+    pub fn color(&self, role: IconRole) -> Color {
+        match role {
+            IconRole::Normal => self.normal,
+            IconRole::Accent => self.accent,
+            IconRole::Success => self.success,
+            IconRole::Warning => self.warning,
+            IconRole::Error => self.error,
+            IconRole::Disabled => self.disabled,
+        }
+    }
+}
+
+/// A single resolution of an icon in a texture atlas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasIcon {
+    /// Texture atlas, that contains the icon.
+    pub texture: SharedTexture,
+    /// Portion of the texture (in normalized coordinates), that contains the icon.
+    pub uv_rect: Rect<f32>,
+    /// Size of the icon in the texture in pixels. It is used to select the best matching
+    /// resolution for the actual size of an icon on screen.
+    pub pixel_size: f32,
+}
+
+/// Image of an icon.
+#[derive(Clone, Debug)]
+pub enum IconImage {
+    /// Vector icon, that consists of a set of primitives in normalized coordinates (`[0; 1]` range
+    /// on both axes, including the sizes of lines and circles). Vector icons are scaled to the size
+    /// of the icon widget, so they're crisp on any resolution.
+    Vector(Vec<Primitive>),
+    /// Bitmap icon in a texture atlas. There could be multiple resolutions of the same icon (`1x`,
+    /// `2x`, etc.), the icon widget selects the smallest one, that is not smaller than the size of
+    /// the widget on screen (in physical pixels). This way icons stay sharp on high-DPI screens.
+    Atlas(Vec<AtlasIcon>),
+}
+
+impl IconImage {
+    /// Returns the best matching atlas icon for the given size in physical pixels. Returns `None`
+    /// for vector icons.
+    pub fn atlas_icon(&self, pixel_size: f32) -> Option<&AtlasIcon> {
+        let IconImage::Atlas(icons) = self else {
+            return None;
+        };
+        icons
+            .iter()
+            .filter(|icon| icon.pixel_size >= pixel_size)
+            .min_by(|a, b| a.pixel_size.total_cmp(&b.pixel_size))
+            .or_else(|| {
+                icons
+                    .iter()
+                    .max_by(|a, b| a.pixel_size.total_cmp(&b.pixel_size))
+            })
+    }
+}
+
+/// Names of the icons, that are registered in every [`IconRegistry`] created by
+/// [`IconRegistry::with_standard_icons`]. The name of an icon is the name of the variant, for
+/// example `Close`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, AsRefStr, EnumString, EnumVariantNames)]
+pub enum StandardIcon {
+    /// Diagonal cross.
+    Close,
+    /// Plus sign.
+    Add,
+    /// Minus sign.
+    Remove,
+    /// Check mark.
+    Check,
+    /// Filled triangle, that points up.
+    ArrowUp,
+    /// Filled triangle, that points down.
+    ArrowDown,
+    /// Filled triangle, that points left.
+    ArrowLeft,
+    /// Filled triangle, that points right.
+    ArrowRight,
+    /// Chevron, that points up.
+    ChevronUp,
+    /// Chevron, that points down.
+    ChevronDown,
+    /// Chevron, that points left.
+    ChevronLeft,
+    /// Chevron, that points right.
+    ChevronRight,
+    /// Three horizontal bars.
+    Menu,
+    /// Three dots.
+    More,
+    /// Play button.
+    Play,
+    /// Pause button.
+    Pause,
+    /// Stop button.
+    Stop,
+    /// Record button.
+    Record,
+    /// Rectangle outline.
+    Maximize,
+}
+
+const STROKE: f32 = 0.12;
+
+fn line(begin: (f32, f32), end: (f32, f32)) -> Primitive {
+    Primitive::Line {
+        begin: Vector2::new(begin.0, begin.1),
+        end: Vector2::new(end.0, end.1),
+        thickness: STROKE,
+    }
+}
+
+fn filled_rect(x: f32, y: f32, w: f32, h: f32) -> Primitive {
+    Primitive::RectangleFilled {
+        rect: Rect::new(x, y, w, h),
+    }
+}
+
+fn circle(x: f32, y: f32, radius: f32) -> Primitive {
+    Primitive::Circle {
+        center: Vector2::new(x, y),
+        radius,
+        segments: 24,
+    }
+}
+
+impl StandardIcon {
+    /// Returns vector primitives of the icon in normalized coordinates.
+    pub fn primitives(self) -> Vec<Primitive> {
+        match self {
+            StandardIcon::Close => vec![line((0.2, 0.2), (0.8, 0.8)), line((0.8, 0.2), (0.2, 0.8))],
+            StandardIcon::Add => vec![
+                line((0.5, 0.15), (0.5, 0.85)),
+                line((0.15, 0.5), (0.85, 0.5)),
+            ],
+            StandardIcon::Remove => vec![line((0.15, 0.5), (0.85, 0.5))],
+            StandardIcon::Check => vec![
+                line((0.15, 0.55), (0.4, 0.8)),
+                line((0.4, 0.8), (0.85, 0.25)),
+            ],
+            StandardIcon::ArrowUp => make_arrow_primitives(ArrowDirection::Top, 1.0),
+            StandardIcon::ArrowDown => make_arrow_primitives(ArrowDirection::Bottom, 1.0),
+            StandardIcon::ArrowLeft => make_arrow_primitives(ArrowDirection::Left, 1.0),
+            StandardIcon::ArrowRight => make_arrow_primitives(ArrowDirection::Right, 1.0),
+            StandardIcon::ChevronUp => {
+                vec![line((0.15, 0.7), (0.5, 0.3)), line((0.5, 0.3), (0.85, 0.7))]
+            }
+            StandardIcon::ChevronDown => {
+                vec![line((0.15, 0.3), (0.5, 0.7)), line((0.5, 0.7), (0.85, 0.3))]
+            }
+            StandardIcon::ChevronLeft => {
+                vec![line((0.7, 0.15), (0.3, 0.5)), line((0.3, 0.5), (0.7, 0.85))]
+            }
+            StandardIcon::ChevronRight => {
+                vec![line((0.3, 0.15), (0.7, 0.5)), line((0.7, 0.5), (0.3, 0.85))]
+            }
+            StandardIcon::Menu => vec![
+                filled_rect(0.15, 0.2, 0.7, STROKE),
+                filled_rect(0.15, 0.44, 0.7, STROKE),
+                filled_rect(0.15, 0.68, 0.7, STROKE),
+            ],
+            StandardIcon::More => vec![
+                circle(0.2, 0.5, 0.08),
+                circle(0.5, 0.5, 0.08),
+                circle(0.8, 0.5, 0.08),
+            ],
+            StandardIcon::Play => vec![Primitive::Triangle {
+                points: [
+                    Vector2::new(0.25, 0.15),
+                    Vector2::new(0.85, 0.5),
+                    Vector2::new(0.25, 0.85),
+                ],
+            }],
+            StandardIcon::Pause => vec![
+                filled_rect(0.25, 0.15, 0.15, 0.7),
+                filled_rect(0.6, 0.15, 0.15, 0.7),
+            ],
+            StandardIcon::Stop => vec![filled_rect(0.2, 0.2, 0.6, 0.6)],
+            StandardIcon::Record => vec![circle(0.5, 0.5, 0.35)],
+            StandardIcon::Maximize => vec![Primitive::Rectangle {
+                rect: Rect::new(0.2, 0.2, 0.6, 0.6),
+                thickness: STROKE,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct IconRegistryState {
+    icons: FxHashMap<String, Arc<IconImage>>,
+    theme: IconTheme,
+}
+
+/// Icon registry is a shared set of named icons and the theme, that defines their colors. Every
+/// user interface has its own registry (see [`UserInterface::icons`]) with all the
+/// [`StandardIcon`]s registered. Editor plugins and games could register their own icons (both
+/// vector and bitmap, see [`IconImage`]) and use them by name with the [`Icon`] widget, which
+/// gives consistent iconography across the whole user interface.
+///
+/// The registry is a shared handle: clones of the registry refer to the same set of icons, so
+/// every change (new icons, new theme) is visible to every [`Icon`] widget immediately.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::{algebra::Vector2, color::Color},
+/// #     icon::{IconImage, IconTheme},
+/// #     vector_image::Primitive,
+/// #     UserInterface,
+/// # };
+/// fn setup_icons(ui: &UserInterface) {
+///     // A custom vector icon - a dot in the center.
+///     ui.icons().register(
+///         "MyPlugin.Dot",
+///         IconImage::Vector(vec![Primitive::Circle {
+///             center: Vector2::new(0.5, 0.5),
+///             radius: 0.25,
+///             segments: 16,
+///         }]),
+///     );
+///
+///     // Light theme.
+///     ui.icons().set_theme(IconTheme {
+///         normal: Color::opaque(40, 40, 40),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IconRegistry(Arc<Mutex<IconRegistryState>>);
+
+impl PartialEq for IconRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl IconRegistry {
+    /// Creates new icon registry with every [`StandardIcon`] registered.
+    pub fn with_standard_icons() -> Self {
+        let registry = Self::default();
+        for icon in [
+            StandardIcon::Close,
+            StandardIcon::Add,
+            StandardIcon::Remove,
+            StandardIcon::Check,
+            StandardIcon::ArrowUp,
+            StandardIcon::ArrowDown,
+            StandardIcon::ArrowLeft,
+            StandardIcon::ArrowRight,
+            StandardIcon::ChevronUp,
+            StandardIcon::ChevronDown,
+            StandardIcon::ChevronLeft,
+            StandardIcon::ChevronRight,
+            StandardIcon::Menu,
+            StandardIcon::More,
+            StandardIcon::Play,
+            StandardIcon::Pause,
+            StandardIcon::Stop,
+            StandardIcon::Record,
+            StandardIcon::Maximize,
+        ] {
+            registry.register(icon, IconImage::Vector(icon.primitives()));
+        }
+        registry
+    }
+
+    /// Registers an icon with the given name. Existing icon with the same name is replaced, which
+    /// could be used to override standard icons.
+    pub fn register(&self, name: impl AsRef<str>, image: IconImage) {
+        self.0
+            .lock()
+            .icons
+            .insert(name.as_ref().to_owned(), Arc::new(image));
+    }
+
+    /// Removes an icon with the given name. Returns `true` if the icon was registered.
+    pub fn unregister(&self, name: impl AsRef<str>) -> bool {
+        self.0.lock().icons.remove(name.as_ref()).is_some()
+    }
+
+    /// Returns an image of an icon with the given name.
+    pub fn get(&self, name: impl AsRef<str>) -> Option<Arc<IconImage>> {
+        self.0.lock().icons.get(name.as_ref()).cloned()
+    }
+
+    /// Returns `true` if there's an icon with the given name.
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        self.0.lock().icons.contains_key(name.as_ref())
+    }
+
+    /// Returns sorted names of every registered icon.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.0.lock().icons.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Sets new theme of icons.
+    pub fn set_theme(&self, theme: IconTheme) {
+        self.0.lock().theme = theme;
+    }
+
+    /// Returns current theme of icons.
+    pub fn theme(&self) -> IconTheme {
+        self.0.lock().theme
+    }
+}
+
+/// Defines how an [`Icon`] is colored.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Reflect)]
+pub enum IconTint {
+    /// The color is taken from the theme of the icon registry (see [`IconTheme`]).
+    Role(IconRole),
+    /// The foreground brush of the widget is used.
+    Foreground,
+    /// Custom color.
+    Color(Color),
+    /// No tint, bitmap icons are drawn with their own colors. Vector icons are white.
+    None,
+}
+
+impl Default for IconTint {
+    fn default() -> Self {
+        Self::Role(IconRole::Normal)
+    }
+}
+
+/// A set of messages, that could be used to modify [`Icon`] widget state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IconMessage {
+    /// Sets a new name of the icon.
+    Name(String),
+    /// Sets a new tint of the icon.
+    Tint(IconTint),
+}
+
+impl IconMessage {
+    define_constructor!(
+        /// Creates [`IconMessage::Name`] message.
+        IconMessage:Name => fn name(String), layout: false
+    );
+
+    define_constructor!(
+        /// Creates [`IconMessage::Tint`] message.
+        IconMessage:Tint => fn tint(IconTint), layout: false
+    );
+}
+
+/// Icon widget displays an icon from an [`IconRegistry`] by its name. The icon is scaled to fit the
+/// bounds of the widget (keeping the aspect ratio) and centered. The default size of the widget is
+/// defined by [`IconBuilder::with_size`], it could be overridden by explicit width and height of
+/// the widget. Unknown icons are not drawn.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     icon::{IconBuilder, IconRole, IconTint, StandardIcon},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn make_close_icon(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     IconBuilder::new(WidgetBuilder::new())
+///         .with_name(StandardIcon::Close)
+///         .with_tint(IconTint::Role(IconRole::Error))
+///         .with_size(12.0)
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct Icon {
+    /// Base widget of the icon.
+    pub widget: Widget,
+    /// Name of the icon in the registry.
+    pub name: String,
+    /// Tint of the icon.
+    pub tint: IconTint,
+    /// Default size of the icon.
+    pub size: f32,
+    /// Icon registry, that is used to fetch the icon.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub registry: IconRegistry,
+}
+
+crate::define_widget_deref!(Icon);
+
+impl Icon {
+    fn brush(&self, theme: &IconTheme) -> Brush {
+        match self.tint {
+            IconTint::Role(role) => Brush::Solid(theme.color(role)),
+            IconTint::Foreground => self.widget.foreground(),
+            IconTint::Color(color) => Brush::Solid(color),
+            IconTint::None => Brush::Solid(Color::WHITE),
+        }
+    }
+}
+
+impl Control for Icon {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        for &child in self.children() {
+            ui.measure_node(child, available_size);
+        }
+        Vector2::new(self.size, self.size)
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let Some(image) = self.registry.get(&self.name) else {
+            return;
+        };
+
+        let bounds = self.widget.bounding_rect();
+        let size = bounds.w().min(bounds.h());
+        let origin = bounds.position + (bounds.size - Vector2::new(size, size)).scale(0.5);
+        let map = |p: Vector2<f32>| origin + p.scale(size);
+
+        let texture = match &*image {
+            IconImage::Vector(primitives) => {
+                for primitive in primitives {
+                    match primitive {
+                        Primitive::Triangle { points } => drawing_context.push_triangle_filled([
+                            map(points[0]),
+                            map(points[1]),
+                            map(points[2]),
+                        ]),
+                        Primitive::Line {
+                            begin,
+                            end,
+                            thickness,
+                        } => drawing_context.push_line(map(*begin), map(*end), thickness * size),
+                        Primitive::Circle {
+                            center,
+                            radius,
+                            segments,
+                        } => drawing_context.push_circle(
+                            map(*center),
+                            radius * size,
+                            *segments,
+                            Color::WHITE,
+                        ),
+                        Primitive::Rectangle { rect, thickness } => drawing_context.push_rect(
+                            &Rect::new(rect.x(), rect.y(), rect.w(), rect.h())
+                                .transform(size, origin),
+                            thickness * size,
+                        ),
+                        Primitive::RectangleFilled { rect } => drawing_context.push_rect_filled(
+                            &Rect::new(rect.x(), rect.y(), rect.w(), rect.h())
+                                .transform(size, origin),
+                            None,
+                        ),
+                    }
+                }
+                CommandTexture::None
+            }
+            IconImage::Atlas(_) => {
+                // Size of the icon in physical pixels, it takes the scaling of the whole UI into
+                // account.
+                let pixel_size = self
+                    .visual_transform()
+                    .transform_vector(&Vector2::new(size, 0.0))
+                    .norm();
+                let Some(icon) = image.atlas_icon(pixel_size) else {
+                    return;
+                };
+                let uv = icon.uv_rect;
+                drawing_context.push_rect_filled(
+                    &Rect::new(origin.x, origin.y, size, size),
+                    Some(&[
+                        Vector2::new(uv.x(), uv.y()),
+                        Vector2::new(uv.x() + uv.w(), uv.y()),
+                        Vector2::new(uv.x() + uv.w(), uv.y() + uv.h()),
+                        Vector2::new(uv.x(), uv.y() + uv.h()),
+                    ]),
+                );
+                CommandTexture::Texture(icon.texture.clone())
+            }
+        };
+
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.brush(&self.registry.theme()),
+            texture,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
+        {
+            if let Some(msg) = message.data::<IconMessage>() {
+                match msg {
+                    IconMessage::Name(name) => {
+                        if &self.name != name {
+                            self.name.clone_from(name);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    IconMessage::Tint(tint) => {
+                        if &self.tint != tint {
+                            self.tint = *tint;
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+trait RectExt {
+    fn transform(self, scale: f32, offset: Vector2<f32>) -> Self;
+}
+
+impl RectExt for Rect<f32> {
+    fn transform(self, scale: f32, offset: Vector2<f32>) -> Self {
+        Rect {
+            position: offset + self.position.scale(scale),
+            size: self.size.scale(scale),
+        }
+    }
+}
+
+/// Icon builder creates [`Icon`] widget instances and adds them to the user interface.
+pub struct IconBuilder {
+    widget_builder: WidgetBuilder,
+    name: String,
+    tint: IconTint,
+    size: f32,
+    registry: Option<IconRegistry>,
+}
+
+impl IconBuilder {
+    /// Creates new icon builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            name: Default::default(),
+            tint: Default::default(),
+            size: 16.0,
+            registry: None,
+        }
+    }
+
+    /// Sets the name of the icon. Use [`StandardIcon`] for standard icons.
+    pub fn with_name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = name.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the tint of the icon. Default is [`IconRole::Normal`] theme color.
+    pub fn with_tint(mut self, tint: IconTint) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Sets the default size of the icon. Default is 16 units.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets a specific icon registry. By default, the registry of the user interface is used.
+    pub fn with_registry(mut self, registry: IconRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Finishes icon building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let icon = Icon {
+            widget: self.widget_builder.build(),
+            name: self.name,
+            tint: self.tint,
+            size: self.size,
+            registry: self.registry.unwrap_or_else(|| ctx.icons().clone()),
+        };
+        ctx.add_node(UiNode::new(icon))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn icon_registry() {
+        let registry = IconRegistry::with_standard_icons();
+        assert!(registry.contains(StandardIcon::Close));
+        assert!(registry.contains("ChevronRight"));
+        assert!(!registry.contains("Foo"));
+
+        let shared = registry.clone();
+        shared.register("Foo", IconImage::Vector(vec![]));
+        assert!(registry.contains("Foo"));
+        assert!(registry.unregister("Foo"));
+        assert!(!shared.contains("Foo"));
+
+        let theme = IconTheme {
+            normal: Color::BLACK,
+            ..Default::default()
+        };
+        shared.set_theme(theme);
+        assert_eq!(registry.theme().color(IconRole::Normal), Color::BLACK);
+
+        let atlas = |pixel_size| AtlasIcon {
+            texture: SharedTexture(Arc::new(())),
+            uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            pixel_size,
+        };
+        let image = IconImage::Atlas(vec![atlas(32.0), atlas(16.0), atlas(64.0)]);
+        assert_eq!(image.atlas_icon(12.0).unwrap().pixel_size, 16.0);
+        assert_eq!(image.atlas_icon(20.0).unwrap().pixel_size, 32.0);
+        assert_eq!(image.atlas_icon(100.0).unwrap().pixel_size, 64.0);
+        assert!(IconImage::Vector(vec![]).atlas_icon(16.0).is_none());
+    }
+}
@@ -102,6 +102,7 @@
 //! * [`crate::text::Text`]: The Text widget is used to display a string to the user.
 //! * [`crate::image::Image`]: The Image widget is used to display a pixel image to the user.
 //! * [`crate::vector_image::VectorImage`]: The Vector Image is used to render vector instructions as a graphical element.
+//! * [`crate::icon::Icon`]: The Icon displays a named, theme-tinted icon from the icon registry of the user interface.
 //! * [`crate::rect::RectEditor`]: The Rect allows you to specify numeric values for X, Y, Width, and Height of a rectangle.
 //! * [`crate::progress_bar::ProgressBar`]: The Progress Bar shows a bar whose fill state can be adjusted to indicate visually how full
 //! something is, for example how close to 100% is a loading process.
@@ -180,6 +181,7 @@ pub use copypasta;
 pub use fyrox_core as core;
 use message::TouchPhase;
 
+pub mod accessibility;
 mod alignment;
 pub mod bit;
 pub mod border;
@@ -191,6 +193,8 @@ pub mod check_box;
 pub mod color;
 mod control;
 pub mod curve;
+pub mod custom_data;
+pub mod damage;
 pub mod decorator;
 pub mod dock;
 pub mod draw;
@@ -199,6 +203,7 @@ pub mod expander;
 pub mod file_browser;
 pub mod formatted_text;
 pub mod grid;
+pub mod icon;
 pub mod image;
 pub mod inspector;
 pub mod key;
@@ -208,6 +213,7 @@ pub mod message;
 pub mod messagebox;
 pub mod nine_patch;
 mod node;
+pub mod number_format;
 pub mod numeric;
 pub mod path;
 pub mod popup;
@@ -219,6 +225,7 @@ pub mod scroll_panel;
 pub mod scroll_viewer;
 pub mod searchbar;
 pub mod stack_panel;
+pub mod stroke;
 pub mod tab_control;
 pub mod text;
 pub mod text_box;
@@ -230,6 +237,7 @@ pub mod uuid;
 pub mod vec;
 pub mod vector_image;
 pub mod widget;
+pub mod widget_pool;
 pub mod window;
 pub mod wrap_panel;
 
@@ -246,14 +254,18 @@ use crate::{
         scope_profile,
         visitor::prelude::*,
     },
+    damage::{DamageRegion, DamageTracker},
     draw::{CommandTexture, Draw, DrawingContext},
+    icon::IconRegistry,
     message::{
         ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
         UiMessage,
     },
+    number_format::NumberFormat,
     popup::{Placement, PopupMessage},
     ttf::{Font, FontBuilder, SharedFont},
     widget::{Widget, WidgetBuilder, WidgetMessage},
+    widget_pool::{PooledWidget, WidgetPool},
 };
 use copypasta::ClipboardContext;
 use fxhash::{FxHashMap, FxHashSet};
@@ -587,6 +599,9 @@ pub struct UserInterface {
     cursor_icon: CursorIcon,
     #[visit(skip)]
     #[reflect(hidden)]
+    ime_cursor_area: Option<Rect<f32>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
     active_tooltip: Option<TooltipEntry>,
     #[visit(skip)]
     #[reflect(hidden)]
@@ -608,6 +623,21 @@ pub struct UserInterface {
     #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    number_format: NumberFormat,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    icons: IconRegistry,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    flow_direction: FlowDirection,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    widget_pool: WidgetPool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    damage_tracker: DamageTracker,
 }
 
 fn is_on_screen(node: &UiNode, nodes: &Pool<UiNode, WidgetContainer>) -> bool {
@@ -664,11 +694,15 @@ fn draw_node(
         node.command_indices.borrow_mut().push(i);
     }
 
-    // Continue on children
-    for &child_node in node.children().iter() {
-        // Do not continue render of top-most nodes - they'll be rendered in separate pass.
-        if !nodes[child_node].is_draw_on_top() {
-            draw_node(nodes, child_node, drawing_context);
+    // Continue on children. Raised children (such as pinned sticky headers) are drawn after their
+    // siblings.
+    for raised in [false, true] {
+        for &child_node in node.children().iter() {
+            let child = &nodes[child_node];
+            // Do not continue render of top-most nodes - they'll be rendered in separate pass.
+            if !child.is_draw_on_top() && child.draw_after_siblings.get() == raised {
+                draw_node(nodes, child_node, drawing_context);
+            }
         }
     }
 
@@ -719,6 +753,7 @@ impl UserInterface {
             mouse_state: Default::default(),
             keyboard_modifiers: Default::default(),
             cursor_icon: Default::default(),
+            ime_cursor_area: None,
             active_tooltip: Default::default(),
             preview_set: Default::default(),
             clipboard: Clipboard(ClipboardContext::new().ok().map(RefCell::new)),
@@ -728,6 +763,11 @@ impl UserInterface {
             default_font,
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            number_format: Default::default(),
+            icons: IconRegistry::with_standard_icons(),
+            flow_direction: FlowDirection::LeftToRight,
+            widget_pool: Default::default(),
+            damage_tracker: Default::default(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -740,6 +780,48 @@ impl UserInterface {
         self.keyboard_modifiers
     }
 
+    /// Returns current number format, that is used by numeric widgets to display and parse numbers.
+    /// See [`NumberFormat`] docs for more info.
+    pub fn number_format(&self) -> &NumberFormat {
+        &self.number_format
+    }
+
+    /// Sets new number format, that will be used by numeric widgets to display and parse numbers.
+    /// Existing widgets will use the new format on next value change.
+    pub fn set_number_format(&mut self, number_format: NumberFormat) {
+        self.number_format = number_format;
+    }
+
+    /// Returns the icon registry of the user interface, that is used by [`icon::Icon`] widgets. The
+    /// registry contains every [`icon::StandardIcon`] by default. See [`IconRegistry`] docs for more
+    /// info.
+    pub fn icons(&self) -> &IconRegistry {
+        &self.icons
+    }
+
+    /// Returns flow direction of the user interface, that is used by every widget with
+    /// [`FlowDirection::Inherit`] direction up to the root of the tree.
+    pub fn flow_direction(&self) -> FlowDirection {
+        self.flow_direction
+    }
+
+    /// Sets new flow direction of the user interface, it is [`FlowDirection::LeftToRight`] by
+    /// default. [`FlowDirection::Inherit`] is treated as left-to-right. The direction of the active
+    /// locale could be fetched using [`FlowDirection::from_locale`]:
+    ///
+    /// ```rust
+    /// # use fyrox_ui::{FlowDirection, UserInterface};
+    /// fn on_locale_changed(ui: &mut UserInterface, locale: &str) {
+    ///     ui.set_flow_direction(FlowDirection::from_locale(locale));
+    /// }
+    /// ```
+    pub fn set_flow_direction(&mut self, flow_direction: FlowDirection) {
+        if self.flow_direction != flow_direction {
+            self.flow_direction = flow_direction;
+            self.nodes[self.root_canvas].invalidate_layout();
+        }
+    }
+
     pub fn build_ctx(&mut self) -> BuildContext<'_> {
         self.into()
     }
@@ -809,6 +891,8 @@ impl UserInterface {
     fn update_visual_transform(&mut self) {
         scope_profile!();
 
+        let root_right_to_left = self.flow_direction.is_right_to_left();
+
         self.stack.clear();
         self.stack.push(self.root_canvas);
         while let Some(node_handle) = self.stack.pop() {
@@ -821,10 +905,29 @@ impl UserInterface {
             if widget.is_globally_visible() {
                 self.stack.extend_from_slice(widget.children());
 
+                widget.right_to_left = match widget.flow_direction {
+                    FlowDirection::Inherit => parent
+                        .as_ref()
+                        .map_or(root_right_to_left, |parent| parent.right_to_left),
+                    FlowDirection::LeftToRight => false,
+                    FlowDirection::RightToLeft => true,
+                };
+
+                let mut position = widget.actual_local_position();
+                // Children of the root canvas are placed at explicit screen positions, they must not
+                // be mirrored.
+                if let Some(parent) = parent.as_ref() {
+                    if parent.right_to_left && parent.handle != self.root_canvas {
+                        position.x = parent.actual_local_size().x
+                            - position.x
+                            - widget.actual_local_size().x;
+                    }
+                }
+
                 let mut layout_transform = widget.layout_transform;
 
-                layout_transform[6] = widget.actual_local_position().x;
-                layout_transform[7] = widget.actual_local_position().y;
+                layout_transform[6] = position.x;
+                layout_transform[7] = position.y;
 
                 let visual_transform = if let Some(parent) = parent {
                     parent.visual_transform * widget.render_transform * layout_transform
@@ -910,11 +1013,17 @@ impl UserInterface {
 
         let sender = self.sender.clone();
         for node in self.nodes.iter_mut() {
+            node.update_brush_animations(dt);
             node.update(dt, &sender)
         }
 
         self.update_tooltips(dt);
 
+        self.ime_cursor_area = self
+            .nodes
+            .try_borrow(self.keyboard_focus_node)
+            .and_then(|node| node.ime_cursor_area());
+
         if !self.drag_context.is_dragging {
             // Try to fetch new cursor icon starting from current picked node. Traverse
             // tree up until cursor with different value is found.
@@ -935,6 +1044,15 @@ impl UserInterface {
         self.cursor_icon
     }
 
+    /// Returns screen-space area of the text cursor of the focused widget, if the widget accepts
+    /// text input. The area is updated on every [`Self::update`] call. `None` means that the input
+    /// method editor (IME) should be disabled, otherwise it should be enabled and its candidate window
+    /// should be placed near the area. IME events should be passed back to the user interface as
+    /// [`OsEvent::Ime`].
+    pub fn ime_cursor_area(&self) -> Option<Rect<f32>> {
+        self.ime_cursor_area
+    }
+
     pub fn draw(&mut self) -> &DrawingContext {
         scope_profile!();
 
@@ -989,8 +1107,30 @@ impl UserInterface {
                     None,
                 );
             }
+
+            // Debug overlay is not a part of any widget.
+            self.damage_tracker.invalidate();
         }
 
+        // Widgets with their draw commands in drawing order.
+        let mut drawn = Vec::new();
+        if self.damage_tracker.is_enabled() {
+            drawn.extend(
+                self.nodes
+                    .pair_iter()
+                    .map(|(handle, node)| (handle, node.command_indices.borrow()))
+                    .filter(|(_, command_indices)| !command_indices.is_empty()),
+            );
+            drawn.sort_unstable_by_key(|(_, command_indices)| command_indices[0]);
+        }
+        self.damage_tracker.update(
+            self.screen_size,
+            &self.drawing_context,
+            drawn
+                .iter()
+                .map(|(handle, command_indices)| (*handle, command_indices.as_slice())),
+        );
+
         &self.drawing_context
     }
 
@@ -2099,6 +2239,17 @@ impl UserInterface {
                     event_processed = true;
                 }
             }
+            OsEvent::Ime(ime) => {
+                if self.keyboard_focus_node.is_some() {
+                    self.send_message(WidgetMessage::ime(
+                        self.keyboard_focus_node,
+                        MessageDirection::FromWidget,
+                        ime.clone(),
+                    ));
+
+                    event_processed = true;
+                }
+            }
             &OsEvent::KeyboardModifiers(modifiers) => {
                 // TODO: Is message needed for focused node?
                 self.keyboard_modifiers = modifiers;
@@ -2361,19 +2512,7 @@ impl UserInterface {
         let sender = self.sender.clone();
         let mut stack = vec![node];
         while let Some(handle) = stack.pop() {
-            if self.prev_picked_node == handle {
-                self.prev_picked_node = Handle::NONE;
-            }
-            if self.picked_node == handle {
-                self.try_set_picked_node(Handle::NONE);
-            }
-            if self.captured_node == handle {
-                self.captured_node = Handle::NONE;
-            }
-            if self.keyboard_focus_node == handle {
-                self.keyboard_focus_node = Handle::NONE;
-            }
-            self.remove_picking_restriction(handle);
+            self.forget_node(handle);
 
             let node_ref = self.nodes.borrow(handle);
             stack.extend_from_slice(node_ref.children());
@@ -2387,6 +2526,161 @@ impl UserInterface {
         }
     }
 
+    /// Drops every reference to the given node (picked, captured, focused, etc.).
+    fn forget_node(&mut self, handle: Handle<UiNode>) {
+        if self.prev_picked_node == handle {
+            self.prev_picked_node = Handle::NONE;
+        }
+        if self.picked_node == handle {
+            self.try_set_picked_node(Handle::NONE);
+        }
+        if self.captured_node == handle {
+            self.captured_node = Handle::NONE;
+        }
+        if self.keyboard_focus_node == handle {
+            self.keyboard_focus_node = Handle::NONE;
+        }
+        self.remove_picking_restriction(handle);
+    }
+
+    /// Returns a reference to the widget pool of the user interface. See [`WidgetPool`] docs for
+    /// more info.
+    pub fn widget_pool(&self) -> &WidgetPool {
+        &self.widget_pool
+    }
+
+    /// Returns a reference to the widget pool of the user interface. See [`WidgetPool`] docs for
+    /// more info.
+    pub fn widget_pool_mut(&mut self) -> &mut WidgetPool {
+        &mut self.widget_pool
+    }
+
+    /// Detaches the given widget (with all its descendants) from the widget tree and puts it to
+    /// the widget pool with the given key, so it could be reused later by [`Self::take_pooled_node`].
+    /// If the pool for the key is full, the widget is removed instead. See [`WidgetPool`] docs for
+    /// more info.
+    pub fn recycle_node(&mut self, node: Handle<UiNode>, key: &str) {
+        if !self.nodes.is_valid_handle(node) || self.widget_pool.contains(node) {
+            return;
+        }
+
+        if self.widget_pool.is_full(key) {
+            self.remove_node(node);
+            return;
+        }
+
+        self.unlink_node_internal(node);
+
+        let mut stack = vec![node];
+        while let Some(handle) = stack.pop() {
+            self.forget_node(handle);
+            stack.extend_from_slice(self.nodes.borrow(handle).children());
+        }
+
+        let node_ref = &mut self.nodes[node];
+        let visibility = node_ref.visibility();
+        node_ref.set_visibility(false);
+
+        self.widget_pool.push(
+            key,
+            PooledWidget {
+                handle: node,
+                visibility,
+            },
+        );
+    }
+
+    /// Takes a widget with the given key from the widget pool and attaches it to the root canvas.
+    /// The widget gets the same visibility it had before recycling. Returns [`Handle::NONE`] if
+    /// there's no pooled widgets with the given key. See [`WidgetPool`] docs for more info.
+    pub fn take_pooled_node(&mut self, key: &str) -> Handle<UiNode> {
+        while let Some(entry) = self.widget_pool.pop(key) {
+            // A pooled widget could be removed by a user in the meantime.
+            if let Some(node) = self.nodes.try_borrow_mut(entry.handle) {
+                node.set_visibility(entry.visibility);
+                node.invalidate_layout();
+                self.link_nodes_internal(entry.handle, self.root_canvas, false);
+                return entry.handle;
+            }
+        }
+        Handle::NONE
+    }
+
+    /// Takes a widget with the given key from the widget pool (see [`Self::take_pooled_node`]) or
+    /// builds a new one using the given builder function if there's no pooled widgets.
+    pub fn take_pooled_node_or_build<F>(&mut self, key: &str, builder: F) -> Handle<UiNode>
+    where
+        F: FnOnce(&mut BuildContext) -> Handle<UiNode>,
+    {
+        let node = self.take_pooled_node(key);
+        if node.is_some() {
+            node
+        } else {
+            builder(&mut self.build_ctx())
+        }
+    }
+
+    /// Builds widgets using the given builder function and puts them to the widget pool, until the
+    /// pool has at least `count` widgets with the given key (the count is limited by the capacity
+    /// of the pool). It is useful to create widgets in advance (for example, on a loading screen),
+    /// to avoid allocations at the moment when widgets are needed.
+    pub fn prewarm_widget_pool<F>(&mut self, key: &str, count: usize, mut builder: F)
+    where
+        F: FnMut(&mut BuildContext) -> Handle<UiNode>,
+    {
+        let count = count.min(self.widget_pool.capacity());
+        loop {
+            let pooled = self.widget_pool.count(key);
+            if pooled >= count {
+                break;
+            }
+            let node = builder(&mut self.build_ctx());
+            self.recycle_node(node, key);
+            if self.widget_pool.count(key) == pooled {
+                // The builder produced nothing, that could be pooled.
+                break;
+            }
+        }
+    }
+
+    /// Removes pooled widgets with the given key, or every pooled widget if the key is `None`.
+    pub fn clear_widget_pool(&mut self, key: Option<&str>) {
+        for entry in self.widget_pool.drain(key) {
+            if self.nodes.is_valid_handle(entry.handle) {
+                self.remove_node(entry.handle);
+            }
+        }
+    }
+
+    /// Enables or disables damage tracking. When enabled, every [`Self::draw`] call compares the
+    /// visuals of every widget with the previous frame and collects the damaged screen regions,
+    /// that are available via [`Self::damage`]. It is useful for tools, that display the user
+    /// interface on their own and want to avoid redrawing a mostly static interface every frame.
+    /// See [`DamageRegion`] docs for more info. Damage tracking is disabled by default.
+    pub fn set_damage_tracking(&mut self, enabled: bool) {
+        self.damage_tracker.set_enabled(enabled);
+    }
+
+    /// Returns `true` if damage tracking is enabled.
+    pub fn is_damage_tracking_enabled(&self) -> bool {
+        self.damage_tracker.is_enabled()
+    }
+
+    /// Returns the screen regions, that have changed during the last [`Self::draw`] call. If
+    /// damage tracking is disabled, the region always covers the whole screen.
+    pub fn damage(&self) -> &DamageRegion {
+        self.damage_tracker.region()
+    }
+
+    /// Forces the next [`Self::draw`] call to damage the whole screen. It should be called when
+    /// the content of the frame buffer, that displays the user interface, is lost (for example,
+    /// when a window was restored after being minimized) or when the visuals of the interface
+    /// might have changed without any changes in its draw commands (for example, when the content
+    /// of a texture was changed).
+    pub fn invalidate_damage(&mut self) {
+        self.damage_tracker.invalidate();
+    }
+
     pub fn drag_context(&self) -> &DragContext {
         &self.drag_context
     }
@@ -2439,6 +2733,21 @@ impl UserInterface {
         self.nodes.try_borrow(node_handle)
     }
 
+    /// Returns a mutable reference to the widget with the given handle. Panics if the handle is
+    /// invalid. Keep in mind, that the state of widgets should be changed using messages, this
+    /// method is intended to access the data, that is not managed by the widgets themselves (for
+    /// example, [`crate::widget::Widget::custom_data`]).
+    #[inline]
+    pub fn node_mut(&mut self, node_handle: Handle<UiNode>) -> &mut UiNode {
+        self.nodes.borrow_mut(node_handle)
+    }
+
+    /// Does the same as [`Self::node_mut`], but returns `None` if the handle is invalid.
+    #[inline]
+    pub fn try_get_node_mut(&mut self, node_handle: Handle<UiNode>) -> Option<&mut UiNode> {
+        self.nodes.try_borrow_mut(node_handle)
+    }
+
     pub fn copy_node(&mut self, node: Handle<UiNode>) -> Handle<UiNode> {
         let mut map = NodeHandleMapping::default();
 
@@ -2713,15 +3022,21 @@ fn transform_size(transform_space_bounds: Vector2<f32>, matrix: &Matrix3<f32>) -
 
 #[cfg(test)]
 mod test {
-    use crate::message::{ButtonState, KeyCode};
+    use crate::message::{ButtonState, ImeEvent, KeyCode};
     use crate::{
         border::BorderBuilder,
-        core::algebra::{Rotation2, UnitComplex, Vector2},
+        brush::Brush,
+        core::{
+            algebra::{Rotation2, UnitComplex, Vector2},
+            color::Color,
+            math::Rect,
+        },
         message::MessageDirection,
+        stack_panel::StackPanelBuilder,
         text_box::TextBoxBuilder,
         transform_size,
         widget::{WidgetBuilder, WidgetMessage},
-        OsEvent, UserInterface,
+        FlowDirection, HorizontalAlignment, Orientation, OsEvent, UserInterface, VerticalAlignment,
     };
 
     #[test]
@@ -2733,6 +3048,143 @@ mod test {
         dbg!(input, transformed);
     }
 
+    #[test]
+    fn ime_events_routing() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let border = BorderBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+
+        let commit = OsEvent::Ime(ImeEvent::Commit("你".to_string()));
+        let ime_messages = |ui: &mut UserInterface| {
+            let mut messages = Vec::new();
+            while let Some(message) = ui.poll_message() {
+                if let Some(WidgetMessage::Ime(event)) = message.data() {
+                    assert_eq!(message.direction(), MessageDirection::FromWidget);
+                    messages.push((message.destination(), event.clone()));
+                }
+            }
+            messages
+        };
+        let focus = |ui: &mut UserInterface, widget| {
+            ui.send_message(WidgetMessage::focus(widget, MessageDirection::ToWidget));
+            while ui.poll_message().is_some() {}
+            ui.update(screen_size, 0.0);
+        };
+
+        // The event is sent to the focused widget, even if it does not accept text input.
+        focus(&mut ui, border);
+        assert_eq!(ui.ime_cursor_area(), None);
+        assert!(ui.process_os_event(&commit));
+        assert_eq!(
+            ime_messages(&mut ui),
+            [(border, ImeEvent::Commit("你".to_string()))]
+        );
+
+        focus(&mut ui, text_box);
+        assert!(ui.ime_cursor_area().is_some());
+        assert!(ui.process_os_event(&OsEvent::Ime(ImeEvent::Disabled)));
+        assert_eq!(ime_messages(&mut ui), [(text_box, ImeEvent::Disabled)]);
+    }
+
+    #[test]
+    fn widget_pool() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        ui.prewarm_widget_pool("Border", 2, |ctx| {
+            BorderBuilder::new(WidgetBuilder::new()).build(ctx)
+        });
+        assert_eq!(ui.widget_pool().count("Border"), 2);
+        assert_eq!(ui.node(ui.root()).children().len(), 0);
+
+        let a = ui.take_pooled_node("Border");
+        assert!(a.is_some());
+        assert!(ui.node(a).visibility());
+        assert_eq!(ui.node(a).parent(), ui.root());
+        assert_eq!(ui.widget_pool().count("Border"), 1);
+
+        ui.send_message(WidgetMessage::focus(a, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        assert_eq!(ui.keyboard_focus_node, a);
+
+        ui.recycle_node(a, "Border");
+        assert!(ui.widget_pool().contains(a));
+        assert!(ui.keyboard_focus_node.is_none());
+        assert!(ui.node(a).parent().is_none());
+        assert!(!ui.node(a).visibility());
+
+        // Pooled widgets are reused in LIFO order.
+        assert_eq!(ui.take_pooled_node("Border"), a);
+        let b = ui.take_pooled_node("Border");
+        assert!(b.is_some() && b != a);
+        assert!(ui.take_pooled_node("Border").is_none());
+        let c = ui.take_pooled_node_or_build("Border", |ctx| {
+            BorderBuilder::new(WidgetBuilder::new()).build(ctx)
+        });
+        assert!(c.is_some());
+
+        ui.widget_pool_mut().set_capacity(1);
+        ui.recycle_node(a, "Border");
+        ui.recycle_node(b, "Border");
+        assert_eq!(ui.widget_pool().count("Border"), 1);
+        assert!(ui.try_get_node(b).is_none());
+
+        ui.clear_widget_pool(None);
+        assert_eq!(ui.widget_pool().total_count(), 0);
+        assert!(ui.try_get_node(a).is_none());
+        assert!(ui.try_get_node(c).is_some());
+    }
+
+    #[test]
+    fn damage_tracking() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        ui.set_damage_tracking(true);
+
+        let border = |ui: &mut UserInterface, x: f32| {
+            BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_desired_position(Vector2::new(x, 10.0))
+                    .with_width(100.0)
+                    .with_height(50.0),
+            )
+            .build(&mut ui.build_ctx())
+        };
+        let a = border(&mut ui, 10.0);
+        let b = border(&mut ui, 500.0);
+
+        let render = |ui: &mut UserInterface| {
+            ui.update(screen_size, 1.0 / 60.0);
+            while ui.poll_message().is_some() {}
+            ui.draw();
+            ui.damage().clone()
+        };
+
+        assert!(render(&mut ui).is_full());
+        assert!(render(&mut ui).is_empty());
+
+        ui.send_message(WidgetMessage::background(
+            a,
+            MessageDirection::ToWidget,
+            Brush::Solid(Color::RED),
+        ));
+        let damage = render(&mut ui);
+        assert!(!damage.is_full());
+        assert_eq!(damage.rects(), &[Rect::new(10.0, 10.0, 100.0, 50.0)]);
+        assert!(render(&mut ui).is_empty());
+
+        ui.send_message(WidgetMessage::remove(b, MessageDirection::ToWidget));
+        let damage = render(&mut ui);
+        assert_eq!(damage.rects(), &[Rect::new(500.0, 10.0, 100.0, 50.0)]);
+
+        ui.invalidate_damage();
+        assert!(render(&mut ui).is_full());
+
+        ui.set_damage_tracking(false);
+        assert!(render(&mut ui).is_full());
+    }
+
     #[test]
     fn center() {
         let screen_size = Vector2::new(1000.0, 1000.0);
@@ -2753,6 +3205,57 @@ mod test {
         assert_eq!(actual_position, expected_position);
     }
 
+    #[test]
+    fn right_to_left_mirroring() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let ctx = &mut ui.build_ctx();
+        let first = BorderBuilder::new(WidgetBuilder::new().with_width(100.0)).build(ctx);
+        let second = BorderBuilder::new(WidgetBuilder::new().with_width(50.0)).build(ctx);
+        let panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(20.0)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_child(first)
+                .with_child(second),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.node(first).screen_position().x, 0.0);
+        assert_eq!(ui.node(second).screen_position().x, 100.0);
+
+        ui.set_flow_direction(FlowDirection::from_locale("ar-EG"));
+        ui.update(screen_size, 0.0);
+        // Children of the root canvas are not mirrored.
+        assert_eq!(ui.node(panel).screen_position().x, 0.0);
+        assert!(ui.node(panel).is_right_to_left());
+        assert_eq!(ui.node(first).screen_position().x, 300.0);
+        assert_eq!(ui.node(second).screen_position().x, 250.0);
+
+        ui.send_message(WidgetMessage::flow_direction(
+            panel,
+            MessageDirection::ToWidget,
+            FlowDirection::LeftToRight,
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert!(!ui.node(first).is_right_to_left());
+        assert_eq!(ui.node(first).screen_position().x, 0.0);
+        assert_eq!(ui.node(second).screen_position().x, 100.0);
+
+        assert_eq!(
+            FlowDirection::from_locale("he_IL"),
+            FlowDirection::RightToLeft
+        );
+        assert_eq!(
+            FlowDirection::from_locale("en-US"),
+            FlowDirection::LeftToRight
+        );
+    }
+
     #[test]
     fn test_keyboard_focus() {
         let screen_size = Vector2::new(1000.0, 1000.0);
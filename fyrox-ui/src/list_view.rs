@@ -12,6 +12,7 @@ use crate::{
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::{MessageDirection, UiMessage},
+    scroll_panel::ScrollPanel,
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -36,6 +37,10 @@ pub enum ListViewMessage {
     RemoveItem(Handle<UiNode>),
     /// A message, that is used to bring an item into view.
     BringItemIntoView(Handle<UiNode>),
+    /// A message, that is used to set items, that stay at the top of the list view when the list
+    /// is scrolled past them (section headers). See
+    /// [sticky headers](crate::scroll_panel::ScrollPanel#sticky-headers) for more info.
+    StickyItems(Vec<Handle<UiNode>>),
 }
 
 impl ListViewMessage {
@@ -59,6 +64,10 @@ impl ListViewMessage {
         /// Creates [`ListViewMessage::BringItemIntoView`] message.
         ListViewMessage:BringItemIntoView => fn bring_item_into_view(Handle<UiNode>), layout: false
     );
+    define_constructor!(
+        /// Creates [`ListViewMessage::StickyItems`] message.
+        ListViewMessage:StickyItems => fn sticky_items(Vec<Handle<UiNode>>), layout: false
+    );
 }
 
 /// List view is used to display lists with arbitrary items. It supports single-selection and by default, it stacks the items
@@ -231,6 +240,9 @@ pub struct ListView {
     /// Current scroll viewer instance that is used to provide scrolling functionality, when items does
     /// not fit in the view entirely.
     pub scroll_viewer: Handle<UiNode>,
+    /// Items, that stay at the top of the list view when the list is scrolled past them.
+    #[visit(optional)]
+    pub sticky_items: Vec<Handle<UiNode>>,
 }
 
 crate::define_widget_deref!(ListView);
@@ -260,6 +272,28 @@ impl ListView {
         }
     }
 
+    /// Returns containers of the sticky items. Containers are moved instead of the items, because
+    /// they're laid out by the items panel.
+    fn sticky_containers(&self) -> Vec<Handle<UiNode>> {
+        self.sticky_items
+            .iter()
+            .filter_map(|item| {
+                self.items
+                    .iter()
+                    .position(|i| i == item)
+                    .map(|index| self.item_containers[index])
+            })
+            .collect()
+    }
+
+    fn sync_sticky_items(&self, ui: &UserInterface) {
+        ui.send_message(ScrollViewerMessage::sticky_headers(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            self.sticky_containers(),
+        ));
+    }
+
     fn sync_decorators(&self, ui: &UserInterface) {
         for (i, &container) in self.item_containers.iter().enumerate() {
             let select = match self.selected_index {
@@ -360,6 +394,7 @@ impl Control for ListView {
         node_map.resolve(&mut self.panel);
         node_map.resolve_slice(&mut self.items);
         node_map.resolve_slice(&mut self.item_containers);
+        node_map.resolve_slice(&mut self.sticky_items);
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -395,6 +430,9 @@ impl Control for ListView {
 
                         self.fix_selection(ui);
                         self.sync_decorators(ui);
+                        if !self.sticky_items.is_empty() {
+                            self.sync_sticky_items(ui);
+                        }
                     }
                     &ListViewMessage::AddItem(item) => {
                         let item_container = generate_item_container(&mut ui.build_ctx(), item);
@@ -429,6 +467,9 @@ impl Control for ListView {
 
                             self.fix_selection(ui);
                             self.sync_decorators(ui);
+                            if self.sticky_items.contains(&item) {
+                                self.sync_sticky_items(ui);
+                            }
                         }
                     }
                     &ListViewMessage::BringItemIntoView(item) => {
@@ -440,6 +481,12 @@ impl Control for ListView {
                             ));
                         }
                     }
+                    ListViewMessage::StickyItems(items) => {
+                        if &self.sticky_items != items {
+                            self.sticky_items.clone_from(items);
+                            self.sync_sticky_items(ui);
+                        }
+                    }
                 }
             }
         }
@@ -452,6 +499,7 @@ pub struct ListViewBuilder {
     items: Vec<Handle<UiNode>>,
     panel: Option<Handle<UiNode>>,
     scroll_viewer: Option<Handle<UiNode>>,
+    sticky_items: Vec<Handle<UiNode>>,
 }
 
 impl ListViewBuilder {
@@ -462,6 +510,7 @@ impl ListViewBuilder {
             items: Vec::new(),
             panel: None,
             scroll_viewer: None,
+            sticky_items: Vec::new(),
         }
     }
 
@@ -483,6 +532,12 @@ impl ListViewBuilder {
         self
     }
 
+    /// Sets the desired sticky items, see [`ListViewMessage::StickyItems`] for more info.
+    pub fn with_sticky_items(mut self, sticky_items: Vec<Handle<UiNode>>) -> Self {
+        self.sticky_items = sticky_items;
+        self
+    }
+
     /// Finishes list view building and adds it to the user interface.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let item_containers = generate_item_containers(ctx, &self.items);
@@ -522,8 +577,13 @@ impl ListViewBuilder {
             items: self.items,
             panel,
             scroll_viewer,
+            sticky_items: self.sticky_items,
         };
 
+        if let Some(scroll_panel) = ctx[content_presenter].cast_mut::<ScrollPanel>() {
+            scroll_panel.sticky_headers = list_box.sticky_containers();
+        }
+
         ctx.add_node(UiNode::new(list_box))
     }
 }
@@ -369,6 +369,10 @@ impl Control for MenuItem {
         node_map.resolve(&mut self.popup);
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
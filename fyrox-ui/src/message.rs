@@ -485,6 +485,27 @@ impl Force {
     }
 }
 
+/// An event of an input method editor (IME). IME is used to type text, that cannot be typed
+/// directly with a keyboard (for example, CJK languages): the text is composed (preedited) first and
+/// then committed as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// IME was enabled, preedit and commit events could be received after this event.
+    Enabled,
+    /// Composed text was changed. Empty text means that the composition was finished or cancelled.
+    Preedit {
+        /// Current composed text.
+        text: String,
+        /// Byte offsets of the cursor (or the selected range) in the composed text. `None` means
+        /// that the cursor should be hidden.
+        cursor: Option<(usize, usize)>,
+    },
+    /// Composed text is ready and must be inserted.
+    Commit(String),
+    /// IME was disabled.
+    Disabled,
+}
+
 /// An event that an OS sends to a window, that is then can be used to "feed" the user interface so it can do some actions.
 pub enum OsEvent {
     /// Mouse input event.
@@ -523,6 +544,8 @@ pub enum OsEvent {
         /// Unique touch event identifier to distinguish between fingers, for example
         id: u64,
     },
+    /// Input method editor event. It is sent to the widget with keyboard focus.
+    Ime(ImeEvent),
 }
 
 /// A set of possible keyboard modifiers.
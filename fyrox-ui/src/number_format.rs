@@ -0,0 +1,216 @@
+//! Culture-aware number formatting and parsing for numeric widgets. See [`NumberFormat`] docs for
+//! more info.
+
+#![warn(missing_docs)]
+
+use std::{fmt::Display, str::FromStr};
+
+/// Number format defines how numbers are displayed by numeric widgets ([`crate::numeric::NumericUpDown`],
+/// [`crate::scroll_bar::ScrollBar`] and every numeric property editor of the inspector) and how the
+/// text, typed in by a user, is parsed back to numbers. Every user interface has its own number
+/// format (see [`crate::UserInterface::number_format`]), that is used by every numeric widget of
+/// it.
+///
+/// Parsing is tolerant: separators of the format are accepted, but the invariant form (`1234.5`)
+/// is always accepted as well. The group separator is optional when typing, but if it is used,
+/// every group must have exactly three digits, otherwise the separator is treated as a decimal
+/// separator. For example, with [`Self::german`] format `1.500` is parsed as `1500`, but `1.5` is
+/// parsed as `1.5`. An optional unit suffix is ignored as well.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::number_format::NumberFormat;
+///
+/// let format = NumberFormat::german();
+/// assert_eq!(format.format(1234.5, 2, "km"), "1.234,50 km");
+/// assert_eq!(format.parse::<f32>("1.234,5 km", "km"), Some(1234.5));
+/// assert_eq!(format.parse::<f32>("1234.5", "km"), Some(1234.5));
+/// ```
+///
+/// Changing the format of a user interface does not change the text of existing widgets, so it is
+/// better to set the format before creating any widgets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// A character that separates integer and fractional parts of a number.
+    pub decimal_separator: char,
+    /// An optional character that separates groups of thousands.
+    pub group_separator: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::invariant()
+    }
+}
+
+impl NumberFormat {
+    /// Culture-independent format: `1234.5`. This is the default format.
+    pub fn invariant() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: None,
+        }
+    }
+
+    /// English format: `1,234.5`.
+    pub fn english() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: Some(','),
+        }
+    }
+
+    /// German (and most of continental European) format: `1.234,5`.
+    pub fn german() -> Self {
+        Self {
+            decimal_separator: ',',
+            group_separator: Some('.'),
+        }
+    }
+
+    /// French format: `1 234,5` (with a narrow no-break space as the group separator).
+    pub fn french() -> Self {
+        Self {
+            decimal_separator: ',',
+            group_separator: Some('\u{202F}'),
+        }
+    }
+
+    /// Formats the given value with the given amount of decimal places (ignored for integers) and
+    /// the given unit suffix (ignored if empty).
+    pub fn format<T: Display>(&self, value: T, precision: usize, unit: &str) -> String {
+        let invariant = format!("{value:.precision$}");
+
+        let (sign, digits) = match invariant.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", invariant.as_str()),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut result = String::with_capacity(invariant.len() + unit.len() + 4);
+        result.push_str(sign);
+        match self.group_separator {
+            // Special values (inf, NaN) are kept as is.
+            Some(separator) if integer.bytes().all(|b| b.is_ascii_digit()) => {
+                for (i, digit) in integer.chars().enumerate() {
+                    if i > 0 && (integer.len() - i) % 3 == 0 {
+                        result.push(separator);
+                    }
+                    result.push(digit);
+                }
+            }
+            _ => result.push_str(integer),
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+        if !unit.is_empty() {
+            result.push(' ');
+            result.push_str(unit);
+        }
+        result
+    }
+
+    /// Parses the given text, that could be formatted using this format or typed in by a user. See
+    /// [`Self`] docs for parsing rules.
+    pub fn parse<T: FromStr>(&self, text: &str, unit: &str) -> Option<T> {
+        let mut text = text.trim();
+        if !unit.is_empty() {
+            if let Some(stripped) = text.strip_suffix(unit) {
+                text = stripped.trim_end();
+            }
+        }
+
+        let is_group_separator = |c: char| {
+            self.group_separator.is_some_and(|separator| {
+                c == separator || separator.is_whitespace() && c.is_whitespace()
+            })
+        };
+
+        if self.has_valid_groups(text, is_group_separator) {
+            let normalized = text
+                .chars()
+                .filter(|c| !is_group_separator(*c))
+                .map(|c| if c == self.decimal_separator { '.' } else { c })
+                .collect::<String>();
+            if let Ok(value) = normalized.parse() {
+                return Some(value);
+            }
+        }
+
+        text.parse()
+            .ok()
+            .or_else(|| text.replace(',', ".").parse().ok())
+    }
+
+    fn has_valid_groups(&self, text: &str, is_group_separator: impl Fn(char) -> bool) -> bool {
+        let integer = text
+            .split(self.decimal_separator)
+            .next()
+            .unwrap_or_default();
+        let mut groups = integer.split(is_group_separator);
+        let first = groups.next().unwrap_or_default();
+        let first = first.strip_prefix(['-', '+']).unwrap_or(first);
+        let mut has_groups = false;
+        for group in groups {
+            has_groups = true;
+            if group.len() != 3 || !group.bytes().all(|b| b.is_ascii_digit()) {
+                return false;
+            }
+        }
+        !has_groups || (1..=3).contains(&first.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn number_format_format() {
+        let invariant = NumberFormat::invariant();
+        assert_eq!(invariant.format(1234567.891, 2, ""), "1234567.89");
+        assert_eq!(invariant.format(12, 2, "m"), "12 m");
+
+        let english = NumberFormat::english();
+        assert_eq!(english.format(1234567.891, 2, ""), "1,234,567.89");
+        assert_eq!(english.format(-123456, 3, ""), "-123,456");
+        assert_eq!(english.format(123, 0, ""), "123");
+        assert_eq!(english.format(f32::INFINITY, 1, ""), "inf");
+
+        let german = NumberFormat::german();
+        assert_eq!(german.format(1.5, 1, "km"), "1,5 km");
+        assert_eq!(german.format(-1234.5, 1, ""), "-1.234,5");
+
+        let french = NumberFormat::french();
+        assert_eq!(french.format(1234.5, 1, ""), "1\u{202F}234,5");
+    }
+
+    #[test]
+    fn number_format_parse() {
+        let german = NumberFormat::german();
+        assert_eq!(german.parse::<f32>("1.234,5", ""), Some(1234.5));
+        assert_eq!(german.parse::<f32>("1234,5", ""), Some(1234.5));
+        assert_eq!(german.parse::<f32>("1.500", ""), Some(1500.0));
+        assert_eq!(german.parse::<f32>("1.5", ""), Some(1.5));
+        assert_eq!(german.parse::<f32>(" -1,5 km ", "km"), Some(-1.5));
+        assert_eq!(german.parse::<i32>("12.000", ""), Some(12000));
+        assert_eq!(german.parse::<i32>("abc", ""), None);
+
+        let english = NumberFormat::english();
+        assert_eq!(english.parse::<f64>("1,234,567.5", ""), Some(1234567.5));
+        assert_eq!(english.parse::<f64>("1,5", ""), Some(1.5));
+
+        let french = NumberFormat::french();
+        assert_eq!(french.parse::<f32>("1 234,5", ""), Some(1234.5));
+        assert_eq!(
+            french.parse::<f32>(&french.format(-98765.25, 2, ""), ""),
+            Some(-98765.25)
+        );
+    }
+}
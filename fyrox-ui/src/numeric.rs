@@ -236,6 +236,27 @@ pub enum DragContext<T: NumericType> {
 ///         .build(ctx)
 /// }
 /// ```
+///
+/// ## Number Format
+///
+/// The value is displayed (and parsed back) using the number format of the user interface (see
+/// [`crate::number_format::NumberFormat`]), so it could have thousands separators or a decimal comma,
+/// depending on the active culture. It is also possible to add a unit suffix to the value, for
+/// example to show `1.5 km`:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, numeric::NumericUpDownBuilder, widget::WidgetBuilder, BuildContext,
+/// #     UiNode,
+/// # };
+/// fn create_numeric_widget(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     NumericUpDownBuilder::new(WidgetBuilder::new())
+///         .with_value(1.5f32)
+///         .with_precision(1)
+///         .with_unit("km")
+///         .build(ctx)
+/// }
+/// ```
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct NumericUpDown<T: NumericType> {
     /// Base widget of the [`NumericUpDown`] widget.
@@ -256,6 +277,10 @@ pub struct NumericUpDown<T: NumericType> {
     pub max_value: T,
     /// Current precision of the widget in decimal places.
     pub precision: usize,
+    /// A unit suffix, that is displayed after the value (for example `km`). Empty string means no
+    /// suffix.
+    #[visit(optional)]
+    pub unit: String,
     /// Internal dragging context.
     #[visit(skip)]
     #[reflect(hidden)]
@@ -287,7 +312,8 @@ impl<T: NumericType> NumericUpDown<T> {
         ui.send_message(TextMessage::text(
             self.field,
             MessageDirection::ToWidget,
-            format!("{:.1$}", self.value, self.precision),
+            ui.number_format()
+                .format(self.value, self.precision, &self.unit),
         ));
     }
 
@@ -305,7 +331,7 @@ impl<T: NumericType> NumericUpDown<T> {
     fn try_parse_value(&mut self, ui: &mut UserInterface) {
         // Parse input only when focus is lost from text field.
         if let Some(field) = ui.node(self.field).cast::<TextBox>() {
-            if let Ok(value) = field.text().parse::<T>() {
+            if let Some(value) = ui.number_format().parse::<T>(&field.text(), &self.unit) {
                 let value = self.clamp_value(value);
                 ui.send_message(NumericUpDownMessage::value(
                     self.handle(),
@@ -383,6 +409,10 @@ impl<T: NumericType> Control for NumericUpDown<T> {
         node_map.resolve(&mut self.decrease);
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
@@ -436,17 +466,17 @@ impl<T: NumericType> Control for NumericUpDown<T> {
                                 ui.send_message(TextMessage::text(
                                     self.field,
                                     MessageDirection::ToWidget,
-                                    format!(
-                                        "{:.1$}",
+                                    ui.number_format().format(
                                         calculate_value_by_offset(
                                             *start_value,
                                             ((*start_mouse_pos - pos.y) * self.drag_value_scaling)
                                                 as i32,
                                             self.step,
                                             self.min_value,
-                                            self.max_value
+                                            self.max_value,
                                         ),
-                                        self.precision
+                                        self.precision,
+                                        &self.unit,
                                     ),
                                 ));
                             }
@@ -556,6 +586,7 @@ pub struct NumericUpDownBuilder<T: NumericType> {
     min_value: T,
     max_value: T,
     precision: usize,
+    unit: String,
     editable: bool,
     drag_value_scaling: f32,
 }
@@ -595,6 +626,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
             min_value: T::min_value(),
             max_value: T::max_value(),
             precision: 3,
+            unit: Default::default(),
             editable: true,
             drag_value_scaling: 0.1,
         }
@@ -639,6 +671,12 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
         self
     }
 
+    /// Sets the desired unit suffix, that will be displayed after the value.
+    pub fn with_unit<S: AsRef<str>>(mut self, unit: S) -> Self {
+        self.unit = unit.as_ref().to_owned();
+        self
+    }
+
     /// Enables or disables editing of the widget.
     pub fn with_editable(mut self, editable: bool) -> Self {
         self.editable = editable;
@@ -665,6 +703,9 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
         .with_stroke_thickness(Thickness::uniform(1.0))
         .build(ctx);
 
+        let text = ctx
+            .number_format()
+            .format(self.value, self.precision, &self.unit);
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -676,7 +717,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
                     )
                     .with_vertical_text_alignment(VerticalAlignment::Center)
                     .with_horizontal_text_alignment(HorizontalAlignment::Left)
-                    .with_text(format!("{:.1$}", self.value, self.precision))
+                    .with_text(text)
                     .with_editable(self.editable)
                     .build(ctx);
                     field
@@ -718,6 +759,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
             min_value: self.min_value,
             max_value: self.max_value,
             precision: self.precision,
+            unit: self.unit,
             drag_context: None,
             drag_value_scaling: self.drag_value_scaling,
         };
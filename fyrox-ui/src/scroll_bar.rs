@@ -111,7 +111,9 @@ impl ScrollBarMessage {
 ///
 /// By default, scroll bar does not show its actual value, you can turn it on using [`ScrollBarBuilder::show_value`]
 /// method with `true` as the first argument. To change rounding of the value, use [`ScrollBarBuilder::with_value_precision`]
-/// and provide the desired amount of decimal places there.
+/// and provide the desired amount of decimal places there. The value is formatted using the number format of the user
+/// interface (see [`crate::number_format::NumberFormat`]), optionally with a unit suffix (see
+/// [`ScrollBarBuilder::with_value_unit`]).
 ///
 /// ## Step
 ///
@@ -147,6 +149,9 @@ pub struct ScrollBar {
     pub value_text: Handle<UiNode>,
     /// Current value precison in decimal places.
     pub value_precision: usize,
+    /// A unit suffix, that is displayed after the value. Empty string means no suffix.
+    #[visit(optional)]
+    pub value_unit: String,
 }
 
 crate::define_widget_deref!(ScrollBar);
@@ -239,6 +244,10 @@ impl Control for ScrollBar {
         size
     }
 
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
@@ -272,7 +281,11 @@ impl Control for ScrollBar {
                                 ui.send_message(TextMessage::text(
                                     self.value_text,
                                     MessageDirection::ToWidget,
-                                    format!("{:.1$}", value, self.value_precision),
+                                    ui.number_format().format(
+                                        value,
+                                        self.value_precision,
+                                        &self.value_unit,
+                                    ),
                                 ));
                             }
 
@@ -417,6 +430,7 @@ pub struct ScrollBarBuilder {
     body: Option<Handle<UiNode>>,
     show_value: bool,
     value_precision: usize,
+    value_unit: String,
 }
 
 impl ScrollBarBuilder {
@@ -435,6 +449,7 @@ impl ScrollBarBuilder {
             body: None,
             show_value: false,
             value_precision: 3,
+            value_unit: Default::default(),
         }
     }
 
@@ -504,6 +519,12 @@ impl ScrollBarBuilder {
         self
     }
 
+    /// Sets the desired unit suffix, that will be displayed after the value of the scroll bar.
+    pub fn with_value_unit<S: AsRef<str>>(mut self, unit: S) -> Self {
+        self.value_unit = unit.as_ref().to_owned();
+        self
+    }
+
     /// Creates new scroll bar instance and adds it to the user interface.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let orientation = self.orientation.unwrap_or(Orientation::Horizontal);
@@ -571,6 +592,9 @@ impl ScrollBarBuilder {
         let value = self.value.unwrap_or(0.0).clamp(min, max);
 
         let value_text = if self.show_value {
+            let text = ctx
+                .number_format()
+                .format(value, self.value_precision, &self.value_unit);
             let value_text = TextBuilder::new(
                 WidgetBuilder::new()
                     .with_visibility(self.show_value)
@@ -587,7 +611,7 @@ impl ScrollBarBuilder {
                         Orientation::Vertical => 1,
                     }),
             )
-            .with_text(format!("{:.1$}", value, self.value_precision))
+            .with_text(text)
             .build(ctx);
 
             ctx.link(value_text, indicator);
@@ -649,6 +673,7 @@ impl ScrollBarBuilder {
             indicator_canvas,
             value_text,
             value_precision: self.value_precision,
+            value_unit: self.value_unit,
         });
         ctx.add_node(node)
     }
@@ -6,16 +6,24 @@
 
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle, scope_profile},
+    core::{
+        algebra::{Matrix3, Vector2},
+        color::Color,
+        math::{self, Rect},
+        pool::Handle,
+        scope_profile,
+    },
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::{MessageDirection, UiMessage},
-    widget::{Widget, WidgetBuilder},
-    BuildContext, Control, UiNode, UserInterface,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, Orientation, UiNode, UserInterface,
 };
+use fxhash::{FxHashMap, FxHashSet};
 use std::{
     any::{Any, TypeId},
+    cell::RefCell,
     ops::{Deref, DerefMut},
 };
 
@@ -28,6 +36,10 @@ pub enum ScrollPanelMessage {
     HorizontalScroll(f32),
     /// Adjusts vertical and horizontal scroll values so given node will be in "view box" of scroll panel.
     BringIntoView(Handle<UiNode>),
+    /// Sets new sticky headers of the scroll panel. See [`ScrollPanel`] docs for more info.
+    StickyHeaders(Vec<Handle<UiNode>>),
+    /// Sets new scroll bindings of the scroll panel. See [`ScrollBinding`] docs for more info.
+    ScrollBindings(Vec<ScrollBinding>),
 }
 
 impl ScrollPanelMessage {
@@ -43,6 +55,124 @@ impl ScrollPanelMessage {
         /// Creates [`ScrollPanelMessage::BringIntoView`] message.
         ScrollPanelMessage:BringIntoView => fn bring_into_view(Handle<UiNode>), layout: true
     );
+    define_constructor!(
+        /// Creates [`ScrollPanelMessage::StickyHeaders`] message.
+        ScrollPanelMessage:StickyHeaders => fn sticky_headers(Vec<Handle<UiNode>>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ScrollPanelMessage::ScrollBindings`] message.
+        ScrollPanelMessage:ScrollBindings => fn scroll_bindings(Vec<ScrollBinding>), layout: false
+    );
+}
+
+/// Scroll binding ties a property of an arbitrary widget to the scroll value of a scroll panel. It
+/// allows to make parallax backgrounds, titles that fade out when the content is scrolled, etc.
+/// Bindings are applied every time when the scroll value changes (or when new bindings are set)
+/// by sending messages to their targets.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub enum ScrollBinding {
+    /// Moves the target widget by the scroll value multiplied by the given factor (per axis). The
+    /// factor of `1.0` moves the target together with the content, `0.0` keeps it still, values
+    /// in between make it slower than the content (parallax). The offset is applied using render
+    /// transform of the target (see [`WidgetMessage::RenderTransform`]), so the binding overwrites
+    /// the render transform.
+    Parallax {
+        /// A handle of the widget to move.
+        target: Handle<UiNode>,
+        /// Scroll value multiplier for both axes.
+        factor: Vector2<f32>,
+    },
+    /// Changes opacity of the target widget depending on the scroll value along the given axis.
+    /// Opacity is linearly interpolated between `start_opacity` and `end_opacity` when the scroll
+    /// value is in `[start; end]` range and clamped outside of it.
+    Fade {
+        /// A handle of the widget to fade.
+        target: Handle<UiNode>,
+        /// Scrolling axis, that drives the opacity.
+        orientation: Orientation,
+        /// Scroll value at which the opacity is equal to `start_opacity`.
+        start: f32,
+        /// Scroll value at which the opacity is equal to `end_opacity`.
+        end: f32,
+        /// Opacity at the beginning of the range.
+        start_opacity: f32,
+        /// Opacity at the end of the range.
+        end_opacity: f32,
+    },
+}
+
+impl Default for ScrollBinding {
+    fn default() -> Self {
+        Self::Parallax {
+            target: Default::default(),
+            factor: Default::default(),
+        }
+    }
+}
+
+impl ScrollBinding {
+    /// Creates a binding, that fades out the target widget when the content is scrolled vertically
+    /// by the given distance.
+    pub fn fade_out(target: Handle<UiNode>, distance: f32) -> Self {
+        Self::Fade {
+            target,
+            orientation: Orientation::Vertical,
+            start: 0.0,
+            end: distance,
+            start_opacity: 1.0,
+            end_opacity: 0.0,
+        }
+    }
+
+    /// Returns a handle of the target widget of the binding.
+    pub fn target(&self) -> Handle<UiNode> {
+        match self {
+            Self::Parallax { target, .. } | Self::Fade { target, .. } => *target,
+        }
+    }
+
+    fn target_mut(&mut self) -> &mut Handle<UiNode> {
+        match self {
+            Self::Parallax { target, .. } | Self::Fade { target, .. } => target,
+        }
+    }
+
+    fn apply(&self, ui: &UserInterface, scroll: Vector2<f32>) {
+        match *self {
+            Self::Parallax { target, factor } => {
+                ui.send_message(WidgetMessage::render_transform(
+                    target,
+                    MessageDirection::ToWidget,
+                    Matrix3::new_translation(&-scroll.component_mul(&factor)),
+                ));
+            }
+            Self::Fade {
+                target,
+                orientation,
+                start,
+                end,
+                start_opacity,
+                end_opacity,
+            } => {
+                let value = match orientation {
+                    Orientation::Vertical => scroll.y,
+                    Orientation::Horizontal => scroll.x,
+                };
+                let t = if end != start {
+                    ((value - start) / (end - start)).clamp(0.0, 1.0)
+                } else if value >= end {
+                    1.0
+                } else {
+                    0.0
+                };
+                ui.send_message(WidgetMessage::opacity(
+                    target,
+                    MessageDirection::ToWidget,
+                    Some(math::lerpf(start_opacity, end_opacity, t)),
+                ));
+            }
+        }
+    }
 }
 
 /// Scroll panel widget is used to arrange its children widgets, so they can be offset by a certain amount of units
@@ -138,6 +268,51 @@ impl ScrollPanelMessage {
 ///     ))
 /// }
 /// ```
+///
+/// ## Sticky headers
+///
+/// Any widget in the content of a scroll panel could be marked as sticky header (see
+/// [`ScrollPanelBuilder::with_sticky_headers`] and [`ScrollPanelMessage::StickyHeaders`]). A sticky
+/// header stays at the top of the panel when the content is scrolled past it, until the end of its
+/// parent widget (a section) or the next sticky header pushes it out. This is useful for long lists
+/// with sections, such as inspectors or game menus. Pinned headers are drawn on top of their
+/// siblings. Sticky headers work only with vertical scrolling.
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, scroll_panel::{ScrollBinding, ScrollPanelBuilder},
+/// #     stack_panel::StackPanelBuilder, text::TextBuilder, widget::WidgetBuilder, BuildContext,
+/// #     UiNode,
+/// # };
+/// fn create_section(ctx: &mut BuildContext, name: &str, items: &[&str]) -> (Handle<UiNode>, Handle<UiNode>) {
+///     let header = TextBuilder::new(WidgetBuilder::new()).with_text(name).build(ctx);
+///     let mut section = WidgetBuilder::new().with_child(header);
+///     for item in items {
+///         section = section.with_child(TextBuilder::new(WidgetBuilder::new()).with_text(*item).build(ctx));
+///     }
+///     (StackPanelBuilder::new(section).build(ctx), header)
+/// }
+///
+/// fn create_scroll_panel(ctx: &mut BuildContext, title: Handle<UiNode>) -> Handle<UiNode> {
+///     let (fruits, fruits_header) = create_section(ctx, "Fruits", &["Apple", "Banana"]);
+///     let (vegetables, vegetables_header) = create_section(ctx, "Vegetables", &["Carrot", "Potato"]);
+///     ScrollPanelBuilder::new(
+///         WidgetBuilder::new().with_child(
+///             StackPanelBuilder::new(WidgetBuilder::new().with_child(fruits).with_child(vegetables))
+///                 .build(ctx),
+///         ),
+///     )
+///     .with_sticky_headers(vec![fruits_header, vegetables_header])
+///     // Fade out the title of the menu while the content is scrolled by the first 100 units.
+///     .with_scroll_bindings(vec![ScrollBinding::fade_out(title, 100.0)])
+///     .build(ctx)
+/// }
+/// ```
+///
+/// ## Scroll bindings
+///
+/// Properties of arbitrary widgets could be driven by the scroll value of the panel using
+/// [`ScrollBinding`]s, see its docs for more info.
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct ScrollPanel {
     /// Base widget of the scroll panel.
@@ -148,6 +323,113 @@ pub struct ScrollPanel {
     pub vertical_scroll_allowed: bool,
     /// A flag, that defines whether the horizontal scrolling is allowed or not.
     pub horizontal_scroll_allowed: bool,
+    /// Widgets, that stay at the top of the panel, when the content is scrolled past them.
+    #[visit(optional)]
+    pub sticky_headers: Vec<Handle<UiNode>>,
+    /// Bindings of widget properties to the scroll value.
+    #[visit(optional)]
+    pub scroll_bindings: Vec<ScrollBinding>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    sticky_state: RefCell<StickyState>,
+}
+
+#[derive(Clone, Default, Debug)]
+struct StickyState {
+    /// Arrangement rectangles of the headers, that were given by their parents, and the actual
+    /// (shifted) rectangles.
+    rects: FxHashMap<Handle<UiNode>, (Rect<f32>, Rect<f32>)>,
+    /// Widgets, that are drawn after their siblings.
+    raised: FxHashSet<Handle<UiNode>>,
+}
+
+impl ScrollPanel {
+    /// Returns position of the given widget relative to the panel or `None` if the widget is not
+    /// in the sub-tree of the panel.
+    fn relative_position(
+        &self,
+        ui: &UserInterface,
+        mut node: Handle<UiNode>,
+    ) -> Option<Vector2<f32>> {
+        let mut position = Vector2::default();
+        while node != self.handle {
+            let node_ref = ui.try_get_node(node)?;
+            position += node_ref.actual_local_position();
+            node = node_ref.parent();
+        }
+        Some(position)
+    }
+
+    fn arrange_sticky_headers(&self, ui: &UserInterface) {
+        let mut state = self.sticky_state.borrow_mut();
+        let state = &mut *state;
+
+        for raised in state.raised.drain() {
+            if let Some(raised) = ui.try_get_node(raised) {
+                raised.draw_after_siblings.set(false);
+            }
+        }
+
+        if !self.vertical_scroll_allowed {
+            state.rects.clear();
+            return;
+        }
+
+        // Slots of the headers (base rectangle and its top and bottom limit relative to the panel).
+        let mut slots = Vec::with_capacity(self.sticky_headers.len());
+        for &header in self.sticky_headers.iter() {
+            let Some(header_ref) = ui.try_get_node(header) else {
+                continue;
+            };
+            if !header_ref.is_globally_visible() {
+                continue;
+            }
+            let Some(parent_position) = self.relative_position(ui, header_ref.parent()) else {
+                continue;
+            };
+            let current = header_ref.prev_arrange.get();
+            // The header keeps the shifted rectangle if its parent was not re-arranged.
+            let base = match state.rects.get(&header) {
+                Some((base, applied)) if *applied == current => *base,
+                _ => current,
+            };
+            let top = parent_position.y + base.y();
+            let bottom_limit =
+                parent_position.y + ui.node(header_ref.parent()).actual_local_size().y;
+            slots.push((header, base, top, bottom_limit));
+        }
+        slots.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let mut rects = FxHashMap::default();
+        for (i, &(header, base, top, bottom_limit)) in slots.iter().enumerate() {
+            let mut limit = bottom_limit - (top + base.h());
+            if let Some((_, _, next_top, _)) = slots.get(i + 1) {
+                limit = limit.min(next_top - (top + base.h()));
+            }
+            let offset = (-top).min(limit).max(0.0);
+
+            let rect = Rect::new(base.x(), base.y() + offset, base.w(), base.h());
+            ui.arrange_node(header, &rect);
+            rects.insert(header, (base, rect));
+
+            if offset > 0.0 {
+                let mut node = header;
+                while node.is_some() && node != self.handle {
+                    let node_ref = ui.node(node);
+                    node_ref.draw_after_siblings.set(true);
+                    state.raised.insert(node);
+                    node = node_ref.parent();
+                }
+            }
+        }
+        state.rects = rects;
+    }
+
+    fn apply_scroll_bindings(&self, ui: &UserInterface) {
+        for binding in self.scroll_bindings.iter() {
+            binding.apply(ui, self.scroll);
+        }
+    }
 }
 
 crate::define_widget_deref!(ScrollPanel);
@@ -161,6 +443,13 @@ impl Control for ScrollPanel {
         }
     }
 
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve_slice(&mut self.sticky_headers);
+        for binding in self.scroll_bindings.iter_mut() {
+            node_map.resolve(binding.target_mut());
+        }
+    }
+
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         scope_profile!();
 
@@ -224,6 +513,10 @@ impl Control for ScrollPanel {
             ui.arrange_node(*child_handle, &child_rect);
         }
 
+        if !self.sticky_headers.is_empty() || !self.sticky_state.borrow().raised.is_empty() {
+            self.arrange_sticky_headers(ui);
+        }
+
         final_size
     }
 
@@ -247,10 +540,20 @@ impl Control for ScrollPanel {
                     ScrollPanelMessage::VerticalScroll(scroll) => {
                         self.scroll.y = scroll;
                         self.invalidate_arrange();
+                        self.apply_scroll_bindings(ui);
                     }
                     ScrollPanelMessage::HorizontalScroll(scroll) => {
                         self.scroll.x = scroll;
                         self.invalidate_arrange();
+                        self.apply_scroll_bindings(ui);
+                    }
+                    ScrollPanelMessage::StickyHeaders(ref headers) => {
+                        self.sticky_headers.clone_from(headers);
+                        self.invalidate_arrange();
+                    }
+                    ScrollPanelMessage::ScrollBindings(ref bindings) => {
+                        self.scroll_bindings.clone_from(bindings);
+                        self.apply_scroll_bindings(ui);
                     }
                     ScrollPanelMessage::BringIntoView(handle) => {
                         if let Some(node_to_focus_ref) = ui.try_get_node(handle) {
@@ -305,6 +608,8 @@ pub struct ScrollPanelBuilder {
     vertical_scroll_allowed: Option<bool>,
     horizontal_scroll_allowed: Option<bool>,
     scroll_value: Vector2<f32>,
+    sticky_headers: Vec<Handle<UiNode>>,
+    scroll_bindings: Vec<ScrollBinding>,
 }
 
 impl ScrollPanelBuilder {
@@ -315,6 +620,8 @@ impl ScrollPanelBuilder {
             vertical_scroll_allowed: None,
             horizontal_scroll_allowed: None,
             scroll_value: Default::default(),
+            sticky_headers: Default::default(),
+            scroll_bindings: Default::default(),
         }
     }
 
@@ -336,6 +643,19 @@ impl ScrollPanelBuilder {
         self
     }
 
+    /// Sets the desired sticky headers. See [`ScrollPanel`] docs for more info.
+    pub fn with_sticky_headers(mut self, sticky_headers: Vec<Handle<UiNode>>) -> Self {
+        self.sticky_headers = sticky_headers;
+        self
+    }
+
+    /// Sets the desired scroll bindings. See [`ScrollBinding`] docs for more info. Keep in mind,
+    /// that the bindings are applied on first change of the scroll value.
+    pub fn with_scroll_bindings(mut self, scroll_bindings: Vec<ScrollBinding>) -> Self {
+        self.scroll_bindings = scroll_bindings;
+        self
+    }
+
     /// Finishes scroll panel building and adds it to the user interface.
     pub fn build(self, ui: &mut BuildContext) -> Handle<UiNode> {
         ui.add_node(UiNode::new(ScrollPanel {
@@ -343,6 +663,90 @@ impl ScrollPanelBuilder {
             scroll: self.scroll_value,
             vertical_scroll_allowed: self.vertical_scroll_allowed.unwrap_or(true),
             horizontal_scroll_allowed: self.horizontal_scroll_allowed.unwrap_or(false),
+            sticky_headers: self.sticky_headers,
+            scroll_bindings: self.scroll_bindings,
+            sticky_state: Default::default(),
         }))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        border::BorderBuilder, stack_panel::StackPanelBuilder, HorizontalAlignment,
+        VerticalAlignment,
+    };
+
+    fn scroll_to(ui: &mut UserInterface, panel: Handle<UiNode>, value: f32) {
+        ui.send_message(ScrollPanelMessage::vertical_scroll(
+            panel,
+            MessageDirection::ToWidget,
+            value,
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(ui.screen_size(), 0.0);
+    }
+
+    #[test]
+    fn sticky_headers_and_bindings() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let ctx = &mut ui.build_ctx();
+
+        let mut headers = Vec::new();
+        let mut sections = Vec::new();
+        for _ in 0..2 {
+            let header = BorderBuilder::new(WidgetBuilder::new().with_height(20.0)).build(ctx);
+            let mut section = WidgetBuilder::new().with_child(header);
+            for _ in 0..4 {
+                section = section.with_child(
+                    BorderBuilder::new(WidgetBuilder::new().with_height(20.0)).build(ctx),
+                );
+            }
+            headers.push(header);
+            sections.push(StackPanelBuilder::new(section).build(ctx));
+        }
+        let title = BorderBuilder::new(WidgetBuilder::new()).build(ctx);
+        let panel = ScrollPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_width(200.0)
+                .with_height(100.0)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_child(
+                    StackPanelBuilder::new(WidgetBuilder::new().with_children(sections.clone()))
+                        .build(ctx),
+                ),
+        )
+        .with_sticky_headers(headers.clone())
+        .with_scroll_bindings(vec![ScrollBinding::fade_out(title, 100.0)])
+        .build(ctx);
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.node(headers[0]).screen_position().y, 0.0);
+        assert_eq!(ui.node(headers[1]).screen_position().y, 100.0);
+
+        // The first header is pinned to the top of the panel and drawn over the items.
+        scroll_to(&mut ui, panel, 30.0);
+        assert_eq!(ui.node(headers[0]).screen_position().y, 0.0);
+        assert_eq!(ui.node(headers[1]).screen_position().y, 70.0);
+        assert!(ui.node(sections[0]).draw_after_siblings.get());
+        assert_eq!(ui.node(title).opacity(), Some(0.7));
+
+        // The first header is pushed out by the end of its section.
+        scroll_to(&mut ui, panel, 90.0);
+        assert_eq!(ui.node(headers[0]).screen_position().y, -10.0);
+        assert_eq!(ui.node(headers[1]).screen_position().y, 10.0);
+
+        scroll_to(&mut ui, panel, 120.0);
+        assert_eq!(ui.node(headers[1]).screen_position().y, 0.0);
+        assert!(ui.node(sections[1]).draw_after_siblings.get());
+        assert_eq!(ui.node(title).opacity(), Some(0.0));
+
+        scroll_to(&mut ui, panel, 0.0);
+        assert_eq!(ui.node(headers[0]).screen_position().y, 0.0);
+        assert_eq!(ui.node(headers[1]).screen_position().y, 100.0);
+        assert!(!ui.node(sections[0]).draw_after_siblings.get());
+        assert!(!ui.node(sections[1]).draw_after_siblings.get());
+    }
+}
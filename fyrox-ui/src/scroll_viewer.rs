@@ -11,7 +11,7 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
     scroll_bar::{ScrollBar, ScrollBarBuilder, ScrollBarMessage},
-    scroll_panel::{ScrollPanelBuilder, ScrollPanelMessage},
+    scroll_panel::{ScrollBinding, ScrollPanelBuilder, ScrollPanelMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, NodeHandleMapping, Orientation, UiNode, UserInterface,
 };
@@ -31,6 +31,11 @@ pub enum ScrollViewerMessage {
     VScrollSpeed(f32),
     /// Sets the new horizontal scrolling speed.
     HScrollSpeed(f32),
+    /// Sets new sticky headers of the inner scroll panel. See
+    /// [sticky headers](crate::scroll_panel::ScrollPanel#sticky-headers) for more info.
+    StickyHeaders(Vec<Handle<UiNode>>),
+    /// Sets new scroll bindings of the inner scroll panel. See [`ScrollBinding`] docs for more info.
+    ScrollBindings(Vec<ScrollBinding>),
 }
 
 impl ScrollViewerMessage {
@@ -50,6 +55,14 @@ impl ScrollViewerMessage {
         /// Creates [`ScrollViewerMessage::HScrollSpeed`] message.
         ScrollViewerMessage:HScrollSpeed => fn h_scroll_speed(f32), layout: true
     );
+    define_constructor!(
+        /// Creates [`ScrollViewerMessage::StickyHeaders`] message.
+        ScrollViewerMessage:StickyHeaders => fn sticky_headers(Vec<Handle<UiNode>>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ScrollViewerMessage::ScrollBindings`] message.
+        ScrollViewerMessage:ScrollBindings => fn scroll_bindings(Vec<ScrollBinding>), layout: false
+    );
 }
 
 /// Scroll viewer is a scrollable region with two scroll bars for each axis. It is used to wrap a content of unknown
@@ -336,6 +349,20 @@ impl Control for ScrollViewer {
                             ui.send_message(message.reverse());
                         }
                     }
+                    ScrollViewerMessage::StickyHeaders(headers) => {
+                        ui.send_message(ScrollPanelMessage::sticky_headers(
+                            self.scroll_panel,
+                            MessageDirection::ToWidget,
+                            headers.clone(),
+                        ));
+                    }
+                    ScrollViewerMessage::ScrollBindings(bindings) => {
+                        ui.send_message(ScrollPanelMessage::scroll_bindings(
+                            self.scroll_panel,
+                            MessageDirection::ToWidget,
+                            bindings.clone(),
+                        ));
+                    }
                 }
             }
         }
@@ -352,6 +379,8 @@ pub struct ScrollViewerBuilder {
     vertical_scroll_allowed: bool,
     v_scroll_speed: f32,
     h_scroll_speed: f32,
+    sticky_headers: Vec<Handle<UiNode>>,
+    scroll_bindings: Vec<ScrollBinding>,
 }
 
 impl ScrollViewerBuilder {
@@ -366,6 +395,8 @@ impl ScrollViewerBuilder {
             vertical_scroll_allowed: true,
             v_scroll_speed: 30.0,
             h_scroll_speed: 30.0,
+            sticky_headers: Default::default(),
+            scroll_bindings: Default::default(),
         }
     }
 
@@ -411,6 +442,19 @@ impl ScrollViewerBuilder {
         self
     }
 
+    /// Sets the desired sticky headers. See [sticky headers](crate::scroll_panel::ScrollPanel#sticky-headers)
+    /// for more info.
+    pub fn with_sticky_headers(mut self, sticky_headers: Vec<Handle<UiNode>>) -> Self {
+        self.sticky_headers = sticky_headers;
+        self
+    }
+
+    /// Sets the desired scroll bindings. See [`ScrollBinding`] docs for more info.
+    pub fn with_scroll_bindings(mut self, scroll_bindings: Vec<ScrollBinding>) -> Self {
+        self.scroll_bindings = scroll_bindings;
+        self
+    }
+
     /// Finishes widget building and adds it to the user interface.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let content_presenter = ScrollPanelBuilder::new(
@@ -421,6 +465,8 @@ impl ScrollViewerBuilder {
         )
         .with_horizontal_scroll_allowed(self.horizontal_scroll_allowed)
         .with_vertical_scroll_allowed(self.vertical_scroll_allowed)
+        .with_sticky_headers(self.sticky_headers)
+        .with_scroll_bindings(self.scroll_bindings)
         .build(ctx);
 
         let v_scroll_bar = self.v_scroll_bar.unwrap_or_else(|| {
@@ -0,0 +1,299 @@
+//! Stroke style defines how lines and outlines are drawn: dash pattern, caps and joins. See
+//! [`StrokeStyle`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::core::{algebra::Vector2, reflect::prelude::*, visitor::prelude::*};
+
+/// Defines a shape of open ends of a stroke (and of every dash of it).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect, Visit)]
+pub enum LineCap {
+    /// The stroke ends exactly at its end point. This is the default mode.
+    #[default]
+    Butt,
+    /// The stroke is extended past its end point by half of its thickness.
+    Square,
+    /// The stroke ends with a half-circle with the diameter equal to the thickness of the stroke.
+    Round,
+}
+
+/// Defines a shape of a corner between two adjacent segments of a stroke.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect, Visit)]
+pub enum LineJoin {
+    /// Outer edges of the segments are extended until they meet in a sharp corner. If the corner is
+    /// longer than [`StrokeStyle::miter_limit`], [`Self::Bevel`] join is used instead. This is the
+    /// default mode.
+    #[default]
+    Miter,
+    /// Outer corners of the segments are connected with a straight line.
+    Bevel,
+    /// Outer corners of the segments are connected with a circular arc.
+    Round,
+}
+
+/// Stroke style defines how a line, a polyline or an outline of a shape is drawn. It is used by
+/// the styled methods of [`crate::draw::Draw`] (such as [`crate::draw::Draw::push_polyline_styled`])
+/// and by widgets, that draw outlines (for example, [`crate::border::Border`]).
+///
+/// Dash pattern is a list of alternating lengths of dashes and gaps, in pixels. An empty pattern
+/// means a solid stroke. A pattern with an odd amount of elements is repeated twice to get an even
+/// amount, so `[4.0]` is the same as `[4.0, 4.0]`. Dashes with zero length are drawn as dots, if
+/// the cap is either [`LineCap::Round`] or [`LineCap::Square`].
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::stroke::{LineCap, StrokeStyle};
+///
+/// // 6px dashes separated by 3px gaps, a typical style for selection marquees.
+/// let marquee = StrokeStyle::dashed(6.0, 3.0);
+/// assert!(!marquee.is_solid());
+///
+/// // Round dots for 2px thick lines.
+/// let dotted = StrokeStyle::dotted(2.0);
+/// assert_eq!(dotted.cap, LineCap::Round);
+///
+/// // A custom "dash-dot" pattern.
+/// let dash_dot = StrokeStyle::default().with_dash_pattern(vec![8.0, 3.0, 0.0, 3.0]);
+/// # assert!(!dash_dot.is_solid());
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct StrokeStyle {
+    /// Alternating lengths of dashes and gaps. Empty pattern means a solid stroke.
+    pub dash_pattern: Vec<f32>,
+    /// A distance into the dash pattern at which the stroke starts. Changing the offset over time
+    /// creates "marching ants" animation.
+    pub dash_offset: f32,
+    /// Shape of open ends of the stroke and of every dash of it.
+    pub cap: LineCap,
+    /// Shape of corners between adjacent segments.
+    pub join: LineJoin,
+    /// Maximum ratio of the miter length to the half of the stroke thickness, after which
+    /// [`LineJoin::Miter`] joins are replaced with [`LineJoin::Bevel`] joins.
+    pub miter_limit: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self::solid()
+    }
+}
+
+impl StrokeStyle {
+    /// Solid stroke with butt caps and miter joins. This is the default style.
+    pub fn solid() -> Self {
+        Self {
+            dash_pattern: Default::default(),
+            dash_offset: 0.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+
+    /// Dashed stroke with the given dash and gap lengths.
+    pub fn dashed(dash: f32, gap: f32) -> Self {
+        Self::solid().with_dash_pattern(vec![dash, gap])
+    }
+
+    /// Dotted stroke with round dots for a stroke of the given thickness. The distance between
+    /// the centers of the dots is twice the thickness.
+    pub fn dotted(thickness: f32) -> Self {
+        Self::solid()
+            .with_dash_pattern(vec![0.0, 2.0 * thickness])
+            .with_cap(LineCap::Round)
+    }
+
+    /// Sets the dash pattern. See [`Self`] docs for more info.
+    pub fn with_dash_pattern(mut self, dash_pattern: Vec<f32>) -> Self {
+        self.dash_pattern = dash_pattern;
+        self
+    }
+
+    /// Sets the distance into the dash pattern at which the stroke starts.
+    pub fn with_dash_offset(mut self, dash_offset: f32) -> Self {
+        self.dash_offset = dash_offset;
+        self
+    }
+
+    /// Sets the shape of open ends of the stroke.
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets the shape of corners between adjacent segments.
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Sets the miter limit. See [`Self::miter_limit`] docs for more info.
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Returns `true` if the stroke is solid. Invalid dash patterns (with negative lengths or with
+    /// zero total length) are treated as solid.
+    pub fn is_solid(&self) -> bool {
+        self.dash_pattern.iter().any(|length| *length < 0.0)
+            || self.dash_pattern.iter().sum::<f32>() <= 0.0
+    }
+
+    /// Splits the given polyline into dashes using the dash pattern of the style. Every dash is an
+    /// open polyline, that keeps the corners of the source polyline. Zero-length dashes are
+    /// returned as a single point. If `closed` is `true`, the last point is connected to the first
+    /// one and a dash, that crosses the first point, is not split in two. A solid style returns the
+    /// whole polyline as a single dash.
+    pub fn dashes(&self, points: &[Vector2<f32>], closed: bool) -> Vec<Vec<Vector2<f32>>> {
+        let mut path = points.to_vec();
+        if closed && points.len() > 2 {
+            path.push(points[0]);
+        }
+
+        if self.is_solid() || path.is_empty() {
+            return vec![path];
+        }
+
+        let mut pattern = self.dash_pattern.clone();
+        if pattern.len() % 2 != 0 {
+            pattern.extend_from_within(..);
+        }
+        let total = pattern.iter().sum::<f32>();
+
+        let mut index = 0;
+        let mut remaining = self.dash_offset.rem_euclid(total);
+        while remaining > 0.0 && remaining >= pattern[index] {
+            remaining -= pattern[index];
+            index = (index + 1) % pattern.len();
+        }
+        remaining = pattern[index] - remaining;
+
+        fn push_unique(dash: &mut Vec<Vector2<f32>>, point: Vector2<f32>) {
+            if dash.last() != Some(&point) {
+                dash.push(point);
+            }
+        }
+
+        let starts_on = index % 2 == 0;
+        let mut on = starts_on;
+        let mut dashes = Vec::new();
+        let mut current = Vec::new();
+        if on {
+            current.push(path[0]);
+        }
+
+        for segment in path.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            let Some(dir) = (b - a).try_normalize(f32::EPSILON) else {
+                continue;
+            };
+            let length = (b - a).norm();
+            let mut position = 0.0;
+            loop {
+                let available = length - position;
+                if remaining > available {
+                    remaining -= available;
+                    if on {
+                        push_unique(&mut current, b);
+                    }
+                    break;
+                }
+
+                position += remaining;
+                let point = a + dir.scale(position);
+                push_unique(&mut current, point);
+                if on {
+                    dashes.push(std::mem::take(&mut current));
+                }
+                on = !on;
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+            }
+        }
+
+        let ends_on = on && current.len() > 1;
+        if ends_on {
+            dashes.push(current);
+        }
+
+        // Merge the dash, that crosses the first point of a closed polyline.
+        if closed && starts_on && ends_on && dashes.len() > 1 {
+            let first = dashes.remove(0);
+            let last = dashes.last_mut().unwrap();
+            last.extend(first.into_iter().skip(1));
+        }
+
+        dashes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stroke_style_dashes() {
+        let line = [Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)];
+
+        assert_eq!(
+            StrokeStyle::solid().dashes(&line, false),
+            vec![line.to_vec()]
+        );
+
+        let dashes = StrokeStyle::dashed(3.0, 2.0).dashes(&line, false);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![Vector2::new(0.0, 0.0), Vector2::new(3.0, 0.0)],
+                vec![Vector2::new(5.0, 0.0), Vector2::new(8.0, 0.0)],
+            ]
+        );
+
+        // Odd patterns are repeated and the offset shifts the pattern.
+        let dashes = StrokeStyle::solid()
+            .with_dash_pattern(vec![4.0])
+            .with_dash_offset(2.0)
+            .dashes(&line, false);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![Vector2::new(0.0, 0.0), Vector2::new(2.0, 0.0)],
+                vec![Vector2::new(6.0, 0.0), Vector2::new(10.0, 0.0)],
+            ]
+        );
+
+        // Dots are single points.
+        let dashes = StrokeStyle::dotted(2.0).dashes(&line, false);
+        assert_eq!(dashes.len(), 3);
+        assert!(dashes.iter().all(|dash| dash.len() == 1));
+
+        // Corners are kept inside of dashes and the dash crossing the first point of a closed
+        // polyline is merged.
+        let square = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 10.0),
+            Vector2::new(0.0, 10.0),
+        ];
+        let dashes = StrokeStyle::dashed(12.0, 8.0)
+            .with_dash_offset(4.0)
+            .dashes(&square, true);
+        assert_eq!(
+            dashes,
+            vec![
+                vec![
+                    Vector2::new(10.0, 6.0),
+                    Vector2::new(10.0, 10.0),
+                    Vector2::new(2.0, 10.0)
+                ],
+                vec![
+                    Vector2::new(0.0, 4.0),
+                    Vector2::new(0.0, 0.0),
+                    Vector2::new(8.0, 0.0)
+                ],
+            ]
+        );
+    }
+}
@@ -15,14 +15,15 @@ use crate::{
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
-    message::{CursorIcon, KeyCode, MessageDirection, MouseButton, UiMessage},
+    message::{CursorIcon, ImeEvent, KeyCode, MessageDirection, MouseButton, UiMessage},
     text::TextMessage,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
-    BRUSH_DARKER, BRUSH_TEXT,
+    BRUSH_DARKER, BRUSH_LIGHTER, BRUSH_TEXT,
 };
 use copypasta::ClipboardProvider;
+use regex::Regex;
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
@@ -50,6 +51,20 @@ pub enum TextBoxMessage {
     Multiline(bool),
     /// Used to enable or disable an ability to edit text box content. Use [TextBoxMessage::editable`] to create the message.
     Editable(bool),
+    /// Used to change placeholder text of a text box. Use [TextBoxMessage::placeholder`] to create the message.
+    Placeholder(String),
+    /// Used to change placeholder brush of a text box. Use [TextBoxMessage::placeholder_brush`] to create the message.
+    PlaceholderBrush(Brush),
+    /// Used to change maximum length (in characters) of a text box. Use [TextBoxMessage::max_length`] to create the message.
+    MaxLength(Option<usize>),
+    /// Sent by a text box (with [`MessageDirection::FromWidget`]) when its text becomes valid or invalid. See
+    /// [`TextBox::is_valid`] for more info. Use [TextBoxMessage::validity`] to create the message.
+    Validity(bool),
+    /// Sent by a text box (with [`MessageDirection::FromWidget`]) when a user has finished editing: `Enter` was
+    /// pressed or the text box has lost focus. Unlike [`TextMessage::Text`], it is never sent per-keystroke,
+    /// so it is the right message to apply a value from. See the [commit mode](TextBox#text-commit-mode)
+    /// section for more info. Use [TextBoxMessage::committed`] to create the message.
+    Committed(String),
 }
 
 impl TextBoxMessage {
@@ -73,6 +88,26 @@ impl TextBoxMessage {
         /// Creates [`TextBoxMessage::Editable`].
         TextBoxMessage:Editable => fn editable(bool), layout: false
     );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::Placeholder`].
+        TextBoxMessage:Placeholder => fn placeholder(String), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::PlaceholderBrush`].
+        TextBoxMessage:PlaceholderBrush => fn placeholder_brush(Brush), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::MaxLength`].
+        TextBoxMessage:MaxLength => fn max_length(Option<usize>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::Validity`].
+        TextBoxMessage:Validity => fn validity(bool), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::Committed`].
+        TextBoxMessage:Committed => fn committed(String), layout: false
+    );
 }
 
 /// Specifies a direction on horizontal axis.
@@ -157,10 +192,213 @@ impl SelectionRange {
     }
 }
 
+/// Result of a line operation: new text, caret position and selection (as character indices).
+struct LineEdit {
+    text: String,
+    caret: usize,
+    selection: Option<(usize, usize)>,
+}
+
+/// Returns an index of the first character of a line, that contains the given character.
+fn line_begin(chars: &[char], index: usize) -> usize {
+    chars[..index.min(chars.len())]
+        .iter()
+        .rposition(|c| *c == '\n')
+        .map_or(0, |i| i + 1)
+}
+
+/// Returns an index of the line break of a line, that contains the given character (or the length of
+/// the text for the last line).
+fn line_end(chars: &[char], index: usize) -> usize {
+    let index = index.min(chars.len());
+    chars[index..]
+        .iter()
+        .position(|c| *c == '\n')
+        .map_or(chars.len(), |i| index + i)
+}
+
+/// Returns a range of whole lines, that contain the given range of characters. The range does not
+/// include the line break of the last line.
+fn lines_range(chars: &[char], begin: usize, end: usize) -> (usize, usize) {
+    // A selection, that ends at the beginning of a line, does not include the line.
+    let end = if end > begin && line_begin(chars, end) == end {
+        end - 1
+    } else {
+        end
+    };
+    (line_begin(chars, begin), line_end(chars, end))
+}
+
+fn collect(parts: &[&[char]]) -> String {
+    parts.iter().flat_map(|part| part.iter()).collect()
+}
+
+fn delete_lines(chars: &[char], begin: usize, end: usize) -> LineEdit {
+    let (begin, end) = lines_range(chars, begin, end);
+    if end < chars.len() {
+        // Remove the line with its line break.
+        LineEdit {
+            text: collect(&[&chars[..begin], &chars[end + 1..]]),
+            caret: begin,
+            selection: None,
+        }
+    } else {
+        // Last line, remove the line break of the previous line.
+        let begin = begin.saturating_sub(1);
+        LineEdit {
+            text: collect(&[&chars[..begin]]),
+            caret: line_begin(chars, begin),
+            selection: None,
+        }
+    }
+}
+
+fn move_lines(
+    chars: &[char],
+    caret: usize,
+    selection: Option<(usize, usize)>,
+    direction: VerticalDirection,
+) -> Option<LineEdit> {
+    let (begin, end) = selection.unwrap_or((caret, caret));
+    let (begin, end) = lines_range(chars, begin, end);
+    let block = &chars[begin..end];
+    let (text, shift) = match direction {
+        VerticalDirection::Up => {
+            if begin == 0 {
+                return None;
+            }
+            let prev_begin = line_begin(chars, begin - 1);
+            let prev = &chars[prev_begin..begin - 1];
+            let text = collect(&[&chars[..prev_begin], block, &['\n'], prev, &chars[end..]]);
+            (text, -(prev.len() as isize + 1))
+        }
+        VerticalDirection::Down => {
+            if end == chars.len() {
+                return None;
+            }
+            let next_end = line_end(chars, end + 1);
+            let next = &chars[end + 1..next_end];
+            let text = collect(&[&chars[..begin], next, &['\n'], block, &chars[next_end..]]);
+            (text, next.len() as isize + 1)
+        }
+    };
+    let shift = |i: usize| i.saturating_add_signed(shift);
+    Some(LineEdit {
+        text,
+        caret: shift(caret),
+        selection: selection.map(|(begin, end)| (shift(begin), shift(end))),
+    })
+}
+
+fn duplicate(chars: &[char], caret: usize, selection: Option<(usize, usize)>) -> LineEdit {
+    match selection {
+        Some((begin, end)) if begin != end => {
+            // Duplicate the selected text and select the copy.
+            let (begin, end) = (begin.min(end), begin.max(end));
+            let fragment = &chars[begin..end];
+            LineEdit {
+                text: collect(&[&chars[..end], fragment, &chars[end..]]),
+                caret: end + fragment.len(),
+                selection: Some((end, end + fragment.len())),
+            }
+        }
+        _ => {
+            let (begin, end) = lines_range(chars, caret, caret);
+            let line = &chars[begin..end];
+            LineEdit {
+                text: collect(&[&chars[..end], &['\n'], line, &chars[end..]]),
+                caret: caret + line.len() + 1,
+                selection: None,
+            }
+        }
+    }
+}
+
+/// Returns leading whitespace of a line, that contains the given character. Only the characters
+/// before the given one are taken into account.
+fn indentation(chars: &[char], index: usize) -> String {
+    let index = index.min(chars.len());
+    chars[line_begin(chars, index)..index]
+        .iter()
+        .take_while(|c| **c == ' ' || **c == '\t')
+        .collect()
+}
+
 /// Defines a function, that could be used to filter out desired characters. It must return `true` for characters, that pass
 /// the filter, and `false` - otherwise.
 pub type FilterCallback = dyn FnMut(char) -> bool;
 
+/// Input filter of a text box. The filter is applied to every inserted character (typed or pasted), characters that do
+/// not pass the filter are rejected. See [`TextBox#filtering`] for more info.
+#[derive(Clone)]
+pub enum InputFilter {
+    /// Accepts a real number (`-12.5`, `.5`). Incomplete numbers (`-`, `.`) could be entered, but the text is not
+    /// [valid](TextBox::is_valid) until it is a number.
+    Numeric,
+    /// Accepts an integer number (`-12`). A single `-` could be entered, but the text is not [valid](TextBox::is_valid)
+    /// until it is a number.
+    Integer,
+    /// Accepts every character, that matches the regular expression, for example `[0-9a-fA-F]` for hexadecimal input.
+    Regex(Regex),
+    /// Accepts every character, for which the callback returns `true`.
+    Custom(Rc<RefCell<FilterCallback>>),
+}
+
+impl Debug for InputFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputFilter::Numeric => f.write_str("Numeric"),
+            InputFilter::Integer => f.write_str("Integer"),
+            InputFilter::Regex(regex) => write!(f, "Regex({})", regex.as_str()),
+            InputFilter::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+fn is_partial_number(text: &str, allow_fraction: bool) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let mut dot = false;
+    digits.chars().all(|c| {
+        if c == '.' && allow_fraction && !dot {
+            dot = true;
+            true
+        } else {
+            c.is_ascii_digit()
+        }
+    })
+}
+
+impl InputFilter {
+    fn accepts_char(&self, c: char) -> bool {
+        match self {
+            InputFilter::Numeric => c.is_ascii_digit() || c == '-' || c == '.',
+            InputFilter::Integer => c.is_ascii_digit() || c == '-',
+            InputFilter::Regex(regex) => regex.is_match(c.encode_utf8(&mut [0; 4])),
+            InputFilter::Custom(callback) => (callback.borrow_mut())(c),
+        }
+    }
+
+    fn accepts_text(&self, text: &str) -> bool {
+        match self {
+            InputFilter::Numeric => is_partial_number(text, true),
+            InputFilter::Integer => is_partial_number(text, false),
+            InputFilter::Regex(_) | InputFilter::Custom(_) => true,
+        }
+    }
+
+    /// Returns `true` if the given text is a complete input for the filter. Numeric filters require the text to be a
+    /// number (empty text is valid), character filters require every character to pass.
+    pub fn is_valid(&self, text: &str) -> bool {
+        match self {
+            InputFilter::Numeric => text.is_empty() || text.parse::<f64>().is_ok(),
+            InputFilter::Integer => text.is_empty() || text.parse::<i64>().is_ok(),
+            InputFilter::Regex(_) | InputFilter::Custom(_) => {
+                text.chars().all(|c| self.accepts_char(c))
+            }
+        }
+    }
+}
+
 /// TextBox is a text widget that allows you to edit text and create specialized input fields. It has various options like
 /// word wrapping, text alignment, and so on.
 ///
@@ -280,6 +518,31 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// [tracking issue](https://github.com/FyroxEngine/Fyrox/issues/74) for that. Check [Font](font.md) chapter to learn how
 /// to create fonts.
 ///
+/// ## Placeholder
+///
+/// A text box could show a hint (placeholder) text while it is empty and not focused. The hint is drawn
+/// using its own brush (greyed out by default) and it is never a part of the actual text:
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, text_box::TextBoxBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// #     BRUSH_LIGHTER,
+/// # };
+/// fn create_search_box(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     TextBoxBuilder::new(WidgetBuilder::new())
+///         .with_placeholder("Search...", BRUSH_LIGHTER)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Composed input (IME)
+///
+/// Editable text box supports input method editors, that are used to type CJK and other composed text. While the text
+/// is being composed, it is drawn underlined at the caret position, and it is inserted only when the IME commits it.
+/// The user interface reports the caret area of the focused text box via [`UserInterface::ime_cursor_area`], which is
+/// used to enable IME and to place its candidate window. The engine does that automatically and translates window IME
+/// events into [`crate::message::OsEvent::Ime`].
+///
 /// ## Messages
 ///
 /// TextBox widget accepts the following list of messages:
@@ -289,6 +552,9 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// - [`TextBoxMessage::TextCommitMode`] - changes the [text commit mode](TextBox#text-commit-mode).
 /// - [`TextBoxMessage::Multiline`] - makes the TextBox either multiline (`true`) or single line (`false`)
 /// - [`TextBoxMessage::Editable`] - enables or disables editing of the text.
+/// - [`TextBoxMessage::Placeholder`] - changes the [placeholder](TextBox#placeholder) text.
+/// - [`TextBoxMessage::PlaceholderBrush`] - changes the brush of the placeholder text.
+/// - [`TextBoxMessage::MaxLength`] - changes the maximum length of the text.
 ///
 /// **Important:** Please keep in mind, that TextBox widget also accepts [`TextMessage`]s. An example of changing text at
 /// runtime could be something like this:
@@ -320,23 +586,70 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// - `Ctrl+A` - select all
 /// - `Ctrl+C` - copy selected text
 /// - `Ctrl+V` - paste text from clipboard
+/// - `Home`/`End` - move caret to the beginning/end of current line
 /// - `Ctrl+Home` - move caret to the beginning of the text
-/// - `Ctrl+End` - move caret to the beginning of the text
-/// - `Shift+Home` - select everything from current caret position until the beginning of current line
-/// - `Shift+End` - select everything from current caret position until the end of current line
+/// - `Ctrl+End` - move caret to the end of the text
 /// - `Arrows` - move caret accordingly
+/// - `Ctrl+Left`/`Ctrl+Right` - move caret to the previous/next word
+/// - `Shift` with any of the caret movement shortcuts above extends the selection, for example
+///   `Ctrl+Shift+Right` selects everything until the next word and `Shift+Home` selects everything
+///   until the beginning of current line
 /// - `Delete` - deletes next character
 /// - `Backspace` - deletes previous character
 /// - `Enter` - new line (if multiline mode is set) or `commit` message
 ///
+/// Multiline text box also supports a few line operations:
+///
+/// - `Enter` - new line with the same indentation as current line
+/// - `Ctrl+Shift+K` - delete current line (or every selected line)
+/// - `Alt+Up`/`Alt+Down` - move current line (or every selected line) up or down
+/// - `Ctrl+D` - duplicate selected text or current line (if there's no selection)
+///
 /// ## Multiline Text Box
 ///
 /// By default, text box will not add new line character to the text if you press `Enter` on keyboard. To enable this
 /// functionality use [`TextBoxBuilder::with_multiline`]
 ///
+/// Text, that does not fit into the bounds of the text box, is scrolled: the view follows the caret when it moves, and
+/// multiline text box could also be scrolled vertically using mouse wheel. Current scroll offset is stored in
+/// [`TextBox::view_position`]. Combine multiline mode with [`WrapMode::Word`] or [`WrapMode::Letter`] to get a multiline
+/// editor, that wraps long lines on the width of the text box and scrolls only vertically:
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     formatted_text::WrapMode, text_box::TextBoxBuilder, widget::WidgetBuilder, UiNode,
+/// #     UserInterface, VerticalAlignment,
+/// # };
+/// fn create_editor(ui: &mut UserInterface) -> Handle<UiNode> {
+///     TextBoxBuilder::new(WidgetBuilder::new().with_height(200.0))
+///         .with_multiline(true)
+///         .with_wrap(WrapMode::Word)
+///         .with_vertical_text_alignment(VerticalAlignment::Top)
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
+///
 /// ## Read-only Mode
 ///
-/// You can enable or disable content editing by using read-only mode. Use [`TextBoxBuilder::with_editable`] at build stage.
+/// You can enable or disable content editing by using read-only mode. Use [`TextBoxBuilder::with_editable`] at build stage
+/// or [`TextBoxMessage::Editable`] at runtime. Read-only text box ignores typing, pasting, IME input and every key that
+/// modifies the text, but the text still can be selected with mouse or keyboard, copied with `Ctrl+C` and navigated with
+/// the caret. This is useful for log output, error details, identifiers and so on:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, text_box::TextBoxBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_copyable_id(id: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+///     TextBoxBuilder::new(WidgetBuilder::new())
+///         .with_text(id)
+///         .with_editable(false)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Read-only text box does not send [`TextMessage::Text`] or [`TextBoxMessage::Committed`] when it loses focus.
 ///
 /// ## Mask Character
 ///
@@ -355,6 +668,24 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// - [`TextCommitMode::LostFocusPlusEnter`] - text box will send [`TextMessage::Text`] message when it loses focus or if Enter
 /// key was pressed. This is **default** behavior. In case of multiline text box hitting Enter key won't commit text!
 ///
+/// Regardless of the mode, the text box also sends [`TextBoxMessage::Committed`] message when a user has finished editing,
+/// so the text could be observed per-keystroke (with [`TextCommitMode::Immediate`]) and applied only when it is complete.
+/// The message is sent when the text box loses focus and when `Enter` is pressed (except [`TextCommitMode::LostFocus`]
+/// mode). Multiline text box is committed with `Ctrl+Enter`, since `Enter` inserts a line break.
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, message::UiMessage, text_box::TextBoxMessage, UiNode,
+/// # };
+/// fn apply_name(message: &UiMessage, name_field: Handle<UiNode>, name: &mut String) {
+///     if let Some(TextBoxMessage::Committed(text)) = message.data() {
+///         if message.destination() == name_field {
+///             *name = text.clone();
+///         }
+///     }
+/// }
+/// ```
+///
 /// ## Filtering
 ///
 /// It is possible specify custom input filter, it can be useful if you're creating special input fields like numerical or
@@ -374,6 +705,24 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// }
 /// ```
 ///
+/// There are also a few standard filters (see [`InputFilter`]) and a limit of the text length. Text, that does not
+/// satisfy the filter (for example, a partially entered number like `-`) or the length limit (for example, when it was
+/// set using [`TextMessage::Text`]), is invalid. The text box sends [`TextBoxMessage::Validity`] every time when its
+/// validity changes, this could be used to highlight invalid fields or to disable "OK" buttons:
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     text_box::{InputFilter, TextBoxBuilder}, widget::WidgetBuilder, UiNode, UserInterface
+/// # };
+/// fn create_age_field(ui: &mut UserInterface) -> Handle<UiNode> {
+///     TextBoxBuilder::new(WidgetBuilder::new())
+///         .with_input_filter(InputFilter::Integer)
+///         .with_max_length(3)
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
+///
 /// ## Style
 ///
 /// You can change brush of caret by using [`TextBoxBuilder::with_caret_brush`] and also selection brush by using
@@ -404,10 +753,17 @@ pub struct TextBox {
     pub caret_brush: Brush,
     /// Current selection brush of the text box.
     pub selection_brush: Brush,
-    /// Current character filter of the text box.
+    /// Current input filter of the text box.
     #[visit(skip)]
     #[reflect(hidden)]
-    pub filter: Option<Rc<RefCell<FilterCallback>>>,
+    pub filter: Option<InputFilter>,
+    /// Maximum length of the text in characters.
+    #[visit(optional)]
+    pub max_length: Option<usize>,
+    /// `true` if the text satisfies the filter and the length limit. See [`Self::is_valid`].
+    #[visit(skip)]
+    #[reflect(read_only)]
+    pub valid: bool,
     /// Current text commit mode of the text box.
     pub commit_mode: TextCommitMode,
     /// `true` if the the multiline mode is active.
@@ -418,6 +774,24 @@ pub struct TextBox {
     pub view_position: Vector2<f32>,
     /// A list of custom characters that will be treated as whitespace.
     pub skip_chars: Vec<u32>,
+    /// Hint text, that is shown while the text box is empty and unfocused. It shares font, wrapping
+    /// and alignment with the main text.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub placeholder: RefCell<FormattedText>,
+    /// Text, that is being composed by an input method editor (IME). It is drawn at the caret
+    /// position and becomes a part of the text only when it is committed.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub ime_preedit: RefCell<FormattedText>,
+    /// Position of the IME cursor in the composed text (in characters).
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub ime_preedit_cursor: Option<usize>,
+    // Content size and bounds size, that were used to clamp the view position last time.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    clamped_layout: Option<(Vector2<f32>, Vector2<f32>)>,
 }
 
 impl Debug for TextBox {
@@ -497,6 +871,47 @@ impl TextBox {
         self.ensure_caret_visible();
     }
 
+    // Moves the caret to the given position. If `select` is `true`, the selection is extended
+    // from its anchor (the position where the selection has started) to the new position,
+    // otherwise the selection is removed.
+    fn move_caret_to(&mut self, position: Position, select: bool) {
+        if select {
+            let anchor = self
+                .selection_range
+                .map(|range| range.begin)
+                .unwrap_or(self.caret_position);
+            self.selection_range = Some(SelectionRange {
+                begin: anchor,
+                end: position,
+            });
+        } else {
+            self.selection_range = None;
+        }
+        self.set_caret_position(position);
+    }
+
+    // Moves the caret to the edge of the current selection (in the given direction) and removes
+    // the selection. Returns `false` if there's no selection.
+    fn collapse_selection(&mut self, direction: HorizontalDirection) -> bool {
+        let Some(range) = self.selection_range.take() else {
+            return false;
+        };
+        let range = range.normalized();
+        self.set_caret_position(match direction {
+            HorizontalDirection::Left => range.begin,
+            HorizontalDirection::Right => range.end,
+        });
+        true
+    }
+
+    fn line_end(&self, line: usize) -> Position {
+        let text = self.formatted_text.borrow();
+        Position {
+            line,
+            offset: text.get_lines().get(line).map_or(0, |line| line.len()),
+        }
+    }
+
     fn move_caret_y(&mut self, offset: usize, direction: VerticalDirection, select: bool) {
         if select {
             if self.selection_range.is_none() {
@@ -626,22 +1041,26 @@ impl TextBox {
             .unwrap_or_else(|| self.end_position())
     }
 
-    /// Returns a position of a next word before the caret in the text.
+    /// Returns a position of the beginning of a word before the given position. If the position is
+    /// inside a word, then the beginning of this word is returned.
     pub fn find_prev_word(&self, from: Position) -> Position {
         self.position_to_char_index_unclamped(from)
             .and_then(|i| {
                 let text = self.formatted_text.borrow();
-                let len = text.get_raw_text().len();
-                text.get_raw_text()
-                    .iter()
-                    .enumerate()
-                    .rev()
-                    .skip(len.saturating_sub(i))
-                    .skip_while(|(_, c)| {
-                        !(c.is_whitespace() || self.skip_chars.contains(&c.char_code))
-                    })
-                    .find(|(_, c)| !(c.is_whitespace() || self.skip_chars.contains(&c.char_code)))
-                    .and_then(|(n, _)| self.char_index_to_position(n + 1))
+                let chars = text.get_raw_text();
+                let is_separator = |n: usize| {
+                    let c = &chars[n];
+                    c.is_whitespace() || self.skip_chars.contains(&c.char_code)
+                };
+                let mut n = i.min(chars.len());
+                while n > 0 && is_separator(n - 1) {
+                    n -= 1;
+                }
+                while n > 0 && !is_separator(n - 1) {
+                    n -= 1;
+                }
+                drop(text);
+                self.char_index_to_position(n)
             })
             .unwrap_or_default()
     }
@@ -668,6 +1087,48 @@ impl TextBox {
         }
     }
 
+    fn commit(&self, ui: &UserInterface) {
+        ui.send_message(TextBoxMessage::committed(
+            self.handle,
+            MessageDirection::FromWidget,
+            self.text(),
+        ));
+    }
+
+    /// Inserts typed (or committed by IME) text at current caret position, replacing current selection.
+    fn enter_text(&mut self, text: &str, ui: &UserInterface) {
+        for symbol in text.chars() {
+            if symbol.is_control() {
+                continue;
+            }
+            if self.filter_input(symbol.encode_utf8(&mut [0; 4])).is_some() {
+                if let Some(range) = self.selection_range {
+                    self.remove_range(ui, range);
+                    self.selection_range = None;
+                }
+                self.insert_char(symbol, ui);
+            }
+        }
+    }
+
+    fn set_ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        let mut preedit = self.ime_preedit.borrow_mut();
+        if preedit.text() != text {
+            preedit.set_text(text).build();
+        }
+        self.ime_preedit_cursor = cursor
+            .and_then(|(_, end)| text.get(..end))
+            .map(|prefix| prefix.chars().count());
+        drop(preedit);
+        self.reset_blink();
+    }
+
+    /// Returns the width of the text, that is being composed by IME.
+    fn ime_preedit_width(&self) -> f32 {
+        let preedit = self.ime_preedit.borrow();
+        preedit.get_range_width(0..preedit.get_raw_text().len())
+    }
+
     fn insert_str(&mut self, str: &str, ui: &UserInterface) {
         let position = self
             .position_to_char_index_unclamped(self.caret_position)
@@ -689,6 +1150,85 @@ impl TextBox {
         }
     }
 
+    /// Filters the given fragment, that is about to be inserted at the caret position (replacing current selection). Characters,
+    /// that do not pass the filter, are removed and the fragment is truncated to fit the length limit. Returns `None` if
+    /// the resulting text would be rejected by the filter.
+    fn filter_input(&self, fragment: &str) -> Option<String> {
+        let mut fragment = match self.filter.as_ref() {
+            Some(filter) => fragment
+                .chars()
+                .filter(|c| filter.accepts_char(*c))
+                .collect::<String>(),
+            None => fragment.to_string(),
+        };
+
+        let text = self.formatted_text.borrow().text();
+        let chars = text.chars().collect::<Vec<_>>();
+        let caret = self
+            .position_to_char_index_unclamped(self.caret_position)
+            .unwrap_or_default()
+            .min(chars.len());
+        let (begin, end) = self
+            .selection_range
+            .map(|range| {
+                let range = range.normalized();
+                (
+                    self.position_to_char_index_unclamped(range.begin),
+                    self.position_to_char_index_unclamped(range.end),
+                )
+            })
+            .and_then(|(begin, end)| Some((begin?.min(chars.len()), end?.min(chars.len()))))
+            .unwrap_or((caret, caret));
+
+        if let Some(max_length) = self.max_length {
+            let remaining = max_length.saturating_sub(chars.len() - (end - begin));
+            fragment = fragment.chars().take(remaining).collect();
+        }
+
+        if fragment.is_empty() {
+            return None;
+        }
+
+        if let Some(filter) = self.filter.as_ref() {
+            let candidate = chars[..begin]
+                .iter()
+                .copied()
+                .chain(fragment.chars())
+                .chain(chars[end..].iter().copied())
+                .collect::<String>();
+            if !filter.accepts_text(&candidate) {
+                return None;
+            }
+        }
+
+        Some(fragment)
+    }
+
+    /// Returns `true` if the text satisfies the input filter and the length limit of the text box. The text could be
+    /// invalid if the filter allows incomplete input (for example, `-` for [`InputFilter::Numeric`]) or if the text was
+    /// set using [`TextMessage::Text`], which is never filtered.
+    pub fn is_valid(&self) -> bool {
+        let text = self.formatted_text.borrow();
+        self.max_length
+            .map_or(true, |max_length| text.get_raw_text().len() <= max_length)
+            && self
+                .filter
+                .as_ref()
+                .map_or(true, |filter| filter.is_valid(&text.text()))
+    }
+
+    fn update_validity(&mut self, ui: &UserInterface) {
+        let valid = self.is_valid();
+        if self.valid != valid {
+            self.valid = valid;
+            ui.send_message(TextBoxMessage::validity(
+                self.handle,
+                MessageDirection::FromWidget,
+                valid,
+            ));
+        }
+    }
+
     /// Returns current text length in characters.
     pub fn get_text_len(&self) -> usize {
         self.formatted_text.borrow_mut().get_raw_text().len()
@@ -732,9 +1272,41 @@ impl TextBox {
         rect
     }
 
+    fn line_height(&self) -> f32 {
+        let text = self.formatted_text.borrow();
+        text.get_lines()
+            .get(self.caret_position.line)
+            .or_else(|| text.get_lines().first())
+            .map(|line| line.height)
+            .filter(|height| *height > 0.0)
+            .unwrap_or_else(|| text.get_font().0.lock().height())
+    }
+
+    /// Returns the size of the text content of the text box. It is valid only after at least one layout pass.
+    pub fn content_size(&self) -> Vector2<f32> {
+        self.formatted_text.borrow().content_size()
+    }
+
+    fn clamp_view_position(&mut self) {
+        let content_size = self.content_size();
+        let size = self.bounding_rect().size;
+        self.clamped_layout = Some((content_size, size));
+        let max = (content_size - size).sup(&Vector2::default());
+        if self.formatted_text.borrow().wrap_mode() != WrapMode::NoWrap {
+            self.view_position.x = 0.0;
+        }
+        self.view_position.x = self.view_position.x.max(0.0);
+        self.view_position.y = self.view_position.y.clamp(0.0, max.y);
+    }
+
     fn ensure_caret_visible(&mut self) {
         let local_bounds = self.bounding_rect();
+        if local_bounds.w() <= 0.0 || local_bounds.h() <= 0.0 {
+            // The text box wasn't arranged yet.
+            return;
+        }
         let caret_view_position = self.point_to_view_pos(self.caret_local_position());
+        let caret_height = self.line_height();
         // Move view position to contain the caret + add some spacing.
         let spacing_step = self.formatted_text.borrow().get_font().0.lock().ascender();
         let spacing = spacing_step * 3.0;
@@ -746,14 +1318,14 @@ impl TextBox {
         if caret_view_position.x < top_left_corner.x {
             self.view_position.x -= top_left_corner.x - caret_view_position.x + spacing;
         }
-        if caret_view_position.y > bottom_right_corner.y {
-            self.view_position.y += bottom_right_corner.y - caret_view_position.y + spacing;
+        // Vertical scrolling is done by whole lines, so the caret line is fully visible.
+        if caret_view_position.y + caret_height > bottom_right_corner.y {
+            self.view_position.y += caret_view_position.y + caret_height - bottom_right_corner.y;
         }
         if caret_view_position.y < top_left_corner.y {
-            self.view_position.y -= top_left_corner.y - caret_view_position.y + spacing;
+            self.view_position.y -= top_left_corner.y - caret_view_position.y;
         }
-        self.view_position.x = self.view_position.x.max(0.0);
-        self.view_position.y = self.view_position.y.max(0.0);
+        self.clamp_view_position();
     }
 
     fn remove_char(&mut self, direction: HorizontalDirection, ui: &UserInterface) {
@@ -980,6 +1552,77 @@ impl TextBox {
     }
 }
 
+impl TextBox {
+    fn chars(&self) -> Vec<char> {
+        self.text().chars().collect()
+    }
+
+    fn caret_index(&self) -> usize {
+        self.position_to_char_index_unclamped(self.caret_position)
+            .unwrap_or_default()
+    }
+
+    fn selection_indices(&self) -> Option<(usize, usize)> {
+        self.selection_range.and_then(|range| {
+            Some((
+                self.position_to_char_index_unclamped(range.begin)?,
+                self.position_to_char_index_unclamped(range.end)?,
+            ))
+        })
+    }
+
+    fn apply_line_edit(&mut self, edit: LineEdit, ui: &UserInterface) {
+        self.formatted_text
+            .borrow_mut()
+            .set_text(&edit.text)
+            .build();
+        let to_position = |i| {
+            self.char_index_to_position(i)
+                .unwrap_or_else(|| self.end_position())
+        };
+        let selection_range = edit.selection.map(|(begin, end)| SelectionRange {
+            begin: to_position(begin),
+            end: to_position(end),
+        });
+        let caret_position = to_position(edit.caret);
+        self.selection_range = selection_range;
+        self.set_caret_position(caret_position);
+        if self.commit_mode == TextCommitMode::Immediate {
+            ui.send_message(TextMessage::text(
+                self.handle,
+                MessageDirection::FromWidget,
+                edit.text,
+            ));
+        }
+    }
+
+    fn delete_lines(&mut self, ui: &UserInterface) {
+        let caret = self.caret_index();
+        let (begin, end) = self.selection_indices().unwrap_or((caret, caret));
+        let edit = delete_lines(&self.chars(), begin.min(end), begin.max(end));
+        self.apply_line_edit(edit, ui);
+    }
+
+    fn move_lines(&mut self, direction: VerticalDirection, ui: &UserInterface) {
+        let selection = self
+            .selection_indices()
+            .map(|(begin, end)| (begin.min(end), begin.max(end)));
+        if let Some(edit) = move_lines(&self.chars(), self.caret_index(), selection, direction) {
+            self.apply_line_edit(edit, ui);
+        }
+    }
+
+    fn duplicate(&mut self, ui: &UserInterface) {
+        let edit = duplicate(&self.chars(), self.caret_index(), self.selection_indices());
+        self.apply_line_edit(edit, ui);
+    }
+
+    fn insert_line_break(&mut self, ui: &UserInterface) {
+        let indentation = indentation(&self.chars(), self.caret_index());
+        self.insert_str(&format!("\n{indentation}"), ui);
+    }
+}
+
 impl Control for TextBox {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -990,10 +1633,32 @@ impl Control for TextBox {
     }
 
     fn measure_override(&self, _: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
-        self.formatted_text
+        let text_size = self
+            .formatted_text
             .borrow_mut()
             .set_constraint(available_size)
-            .build()
+            .build_if_needed();
+        let mut placeholder = self.placeholder.borrow_mut();
+        if placeholder.get_raw_text().is_empty() {
+            text_size
+        } else {
+            let placeholder_size = placeholder.set_constraint(available_size).build_if_needed();
+            text_size.sup(&placeholder_size)
+        }
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        // Wrap the text on the actual bounds of the text box, the available size at the measurement
+        // stage could be unlimited.
+        self.formatted_text
+            .borrow_mut()
+            .set_constraint(final_size)
+            .build_if_needed();
+        let mut placeholder = self.placeholder.borrow_mut();
+        if !placeholder.get_raw_text().is_empty() {
+            placeholder.set_constraint(final_size).build_if_needed();
+        }
+        self.widget.arrange_override(ui, final_size)
     }
 
     fn draw(&self, drawing_context: &mut DrawingContext) {
@@ -1006,69 +1671,75 @@ impl Control for TextBox {
             None,
         );
 
+        // Only the lines, that are inside the view, are turned into glyphs and drawn. This keeps
+        // large documents cheap to draw, no matter how many lines they have.
         self.formatted_text
             .borrow_mut()
-            .set_brush(self.widget.foreground());
+            .set_brush(self.widget.foreground())
+            .set_visible_range(Some(
+                self.view_position.y..(self.view_position.y + bounds.h()),
+            ));
 
         let view_bounds = self.rect_to_view_pos(bounds);
         if let Some(ref selection_range) = self.selection_range.map(|r| r.normalized()) {
             let text = self.formatted_text.borrow();
             let lines = text.get_lines();
+            let visible_lines = text.glyph_lines();
             if selection_range.begin.line == selection_range.end.line {
-                let line = lines[selection_range.begin.line];
-                // Begin line
-                let offset =
-                    text.get_range_width(line.begin..(line.begin + selection_range.begin.offset));
-                let width = text.get_range_width(
-                    (line.begin + selection_range.begin.offset)
-                        ..(line.begin + selection_range.end.offset),
-                );
-                let selection_bounds = Rect::new(
-                    view_bounds.x() + line.x_offset + offset,
-                    view_bounds.y() + line.y_offset,
-                    width,
-                    line.height,
-                );
-                drawing_context.push_rect_filled(&selection_bounds, None);
+                if visible_lines.contains(&selection_range.begin.line) {
+                    let line = lines[selection_range.begin.line];
+                    // Begin line
+                    let offset = text
+                        .get_range_width(line.begin..(line.begin + selection_range.begin.offset));
+                    let width = text.get_range_width(
+                        (line.begin + selection_range.begin.offset)
+                            ..(line.begin + selection_range.end.offset),
+                    );
+                    let selection_bounds = Rect::new(
+                        view_bounds.x() + line.x_offset + offset,
+                        view_bounds.y() + line.y_offset,
+                        width,
+                        line.height,
+                    );
+                    drawing_context.push_rect_filled(&selection_bounds, None);
+                }
             } else {
-                for (i, line) in text.get_lines().iter().enumerate() {
-                    if i >= selection_range.begin.line && i <= selection_range.end.line {
-                        let selection_bounds = if i == selection_range.begin.line {
-                            // Begin line
-                            let offset = text.get_range_width(
-                                line.begin..(line.begin + selection_range.begin.offset),
-                            );
-                            let width = text.get_range_width(
-                                (line.begin + selection_range.begin.offset)..line.end,
-                            );
-                            Rect::new(
-                                view_bounds.x() + line.x_offset + offset,
-                                view_bounds.y() + line.y_offset,
-                                width,
-                                line.height,
-                            )
-                        } else if i == selection_range.end.line {
-                            // End line
-                            let width = text.get_range_width(
-                                line.begin..(line.begin + selection_range.end.offset),
-                            );
-                            Rect::new(
-                                view_bounds.x() + line.x_offset,
-                                view_bounds.y() + line.y_offset,
-                                width,
-                                line.height,
-                            )
-                        } else {
-                            // Everything between
-                            Rect::new(
-                                view_bounds.x() + line.x_offset,
-                                view_bounds.y() + line.y_offset,
-                                line.width,
-                                line.height,
-                            )
-                        };
-                        drawing_context.push_rect_filled(&selection_bounds, None);
-                    }
+                let first = selection_range.begin.line.max(visible_lines.start);
+                let last = (selection_range.end.line + 1).min(visible_lines.end);
+                for (i, line) in lines.iter().enumerate().take(last).skip(first) {
+                    let selection_bounds = if i == selection_range.begin.line {
+                        // Begin line
+                        let offset = text.get_range_width(
+                            line.begin..(line.begin + selection_range.begin.offset),
+                        );
+                        let width = text
+                            .get_range_width((line.begin + selection_range.begin.offset)..line.end);
+                        Rect::new(
+                            view_bounds.x() + line.x_offset + offset,
+                            view_bounds.y() + line.y_offset,
+                            width,
+                            line.height,
+                        )
+                    } else if i == selection_range.end.line {
+                        // End line
+                        let width = text
+                            .get_range_width(line.begin..(line.begin + selection_range.end.offset));
+                        Rect::new(
+                            view_bounds.x() + line.x_offset,
+                            view_bounds.y() + line.y_offset,
+                            width,
+                            line.height,
+                        )
+                    } else {
+                        // Everything between
+                        Rect::new(
+                            view_bounds.x() + line.x_offset,
+                            view_bounds.y() + line.y_offset,
+                            line.width,
+                            line.height,
+                        )
+                    };
+                    drawing_context.push_rect_filled(&selection_bounds, None);
                 }
             }
         }
@@ -1086,8 +1757,58 @@ impl Control for TextBox {
             &self.formatted_text.borrow(),
         );
 
-        if self.caret_visible {
-            let caret_pos = self.point_to_view_pos(self.caret_local_position());
+        if !self.has_focus && self.formatted_text.borrow().get_raw_text().is_empty() {
+            drawing_context.draw_text(
+                self.clip_bounds(),
+                bounds.position,
+                &self.placeholder.borrow(),
+            );
+        }
+
+        let mut caret_pos = self.point_to_view_pos(self.caret_local_position());
+        let mut caret_visible = self.caret_visible;
+        if !self.ime_preedit.borrow().get_raw_text().is_empty() {
+            // The composed text covers the text after the caret until it is committed.
+            let preedit_bounds = Rect::new(
+                caret_pos.x,
+                caret_pos.y,
+                self.ime_preedit_width(),
+                self.line_height(),
+            );
+            drawing_context.push_rect_filled(&preedit_bounds, None);
+            drawing_context.commit(
+                self.clip_bounds(),
+                self.widget.background(),
+                CommandTexture::None,
+                None,
+            );
+
+            self.ime_preedit
+                .borrow_mut()
+                .set_brush(self.widget.foreground());
+            drawing_context.draw_text(self.clip_bounds(), caret_pos, &self.ime_preedit.borrow());
+
+            let underline_bounds = Rect::new(
+                preedit_bounds.x(),
+                preedit_bounds.y() + preedit_bounds.h() - 1.0,
+                preedit_bounds.w(),
+                1.0,
+            );
+            drawing_context.push_rect_filled(&underline_bounds, None);
+            drawing_context.commit(
+                self.clip_bounds(),
+                self.widget.foreground(),
+                CommandTexture::None,
+                None,
+            );
+
+            match self.ime_preedit_cursor {
+                Some(cursor) => caret_pos.x += self.ime_preedit.borrow().get_range_width(0..cursor),
+                None => caret_visible = false,
+            }
+        }
+
+        if caret_visible {
             let caret_bounds = Rect::new(
                 caret_pos.x,
                 caret_pos.y,
@@ -1114,6 +1835,31 @@ impl Control for TextBox {
         } else {
             self.caret_visible = false;
         }
+
+        // The content or the bounds could shrink after the view was moved.
+        if self.view_position != Vector2::default()
+            && self.clamped_layout != Some((self.content_size(), self.bounding_rect().size))
+        {
+            self.clamp_view_position();
+        }
+    }
+
+    fn ime_cursor_area(&self) -> Option<Rect<f32>> {
+        if !self.has_focus || !self.editable {
+            return None;
+        }
+        let position = self.point_to_view_pos(self.caret_local_position());
+        let area = Rect::new(
+            position.x,
+            position.y,
+            self.ime_preedit_width().max(2.0),
+            self.line_height(),
+        );
+        Some(area.transform(self.visual_transform()))
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -1127,26 +1873,35 @@ impl Control for TextBox {
                             && !ui.keyboard_modifiers().alt
                             && self.editable =>
                     {
-                        for symbol in text.chars() {
-                            let insert = if let Some(filter) = self.filter.as_ref() {
-                                let filter = &mut *filter.borrow_mut();
-                                filter(symbol)
-                            } else {
-                                true
-                            };
-                            if insert {
-                                if let Some(range) = self.selection_range {
-                                    self.remove_range(ui, range);
-                                    self.selection_range = None;
-                                }
-                                if !symbol.is_control() {
-                                    self.insert_char(symbol, ui);
-                                }
-                            }
-                        }
+                        self.enter_text(text, ui);
                     }
+                    WidgetMessage::Ime(ime) => match ime {
+                        ImeEvent::Preedit { text, cursor } if self.editable => {
+                            self.set_ime_preedit(text, *cursor);
+                        }
+                        ImeEvent::Commit(text) if self.editable => {
+                            self.set_ime_preedit("", None);
+                            self.enter_text(text, ui);
+                        }
+                        ImeEvent::Disabled => self.set_ime_preedit("", None),
+                        _ => {}
+                    },
                     WidgetMessage::KeyDown(code) => {
                         match code {
+                            KeyCode::ArrowUp
+                                if ui.keyboard_modifiers().alt
+                                    && self.multiline
+                                    && self.editable =>
+                            {
+                                self.move_lines(VerticalDirection::Up, ui);
+                            }
+                            KeyCode::ArrowDown
+                                if ui.keyboard_modifiers().alt
+                                    && self.multiline
+                                    && self.editable =>
+                            {
+                                self.move_lines(VerticalDirection::Down, ui);
+                            }
                             KeyCode::ArrowUp => {
                                 self.move_caret_y(
                                     1,
@@ -1162,57 +1917,34 @@ impl Control for TextBox {
                                 );
                             }
                             KeyCode::ArrowRight => {
-                                if ui.keyboard_modifiers.control {
-                                    let prev_position = self.caret_position;
+                                let modifiers = ui.keyboard_modifiers();
+                                if modifiers.control {
                                     let next_word_position =
                                         self.find_next_word(self.caret_position);
-                                    self.set_caret_position(next_word_position);
-                                    self.reset_blink();
-                                    if ui.keyboard_modifiers.shift {
-                                        if let Some(selection_range) = self.selection_range.as_mut()
-                                        {
-                                            selection_range.end = next_word_position;
-                                        } else {
-                                            self.selection_range = Some(SelectionRange {
-                                                begin: prev_position,
-                                                end: next_word_position,
-                                            });
-                                        }
-                                    } else {
-                                        self.selection_range = None;
-                                    }
-                                } else {
+                                    self.move_caret_to(next_word_position, modifiers.shift);
+                                } else if modifiers.shift
+                                    || !self.collapse_selection(HorizontalDirection::Right)
+                                {
                                     self.move_caret_x(
                                         1,
                                         HorizontalDirection::Right,
-                                        ui.keyboard_modifiers().shift,
+                                        modifiers.shift,
                                     );
                                 }
                             }
                             KeyCode::ArrowLeft => {
-                                if ui.keyboard_modifiers.control {
-                                    let prev_position = self.caret_position;
+                                let modifiers = ui.keyboard_modifiers();
+                                if modifiers.control {
                                     let prev_word_position =
                                         self.find_prev_word(self.caret_position);
-                                    self.set_caret_position(prev_word_position);
-                                    if ui.keyboard_modifiers.shift {
-                                        if let Some(selection_range) = self.selection_range.as_mut()
-                                        {
-                                            selection_range.end = prev_word_position;
-                                        } else {
-                                            self.selection_range = Some(SelectionRange {
-                                                begin: prev_position,
-                                                end: prev_word_position,
-                                            });
-                                        }
-                                    } else {
-                                        self.selection_range = None;
-                                    }
-                                } else {
+                                    self.move_caret_to(prev_word_position, modifiers.shift);
+                                } else if modifiers.shift
+                                    || !self.collapse_selection(HorizontalDirection::Left)
+                                {
                                     self.move_caret_x(
                                         1,
                                         HorizontalDirection::Left,
-                                        ui.keyboard_modifiers().shift,
+                                        modifiers.shift,
                                     );
                                 }
                             }
@@ -1225,15 +1957,18 @@ impl Control for TextBox {
                                 }
                             }
                             KeyCode::NumpadEnter | KeyCode::Enter if self.editable => {
-                                if self.multiline {
-                                    self.insert_char('\n', ui);
+                                if self.multiline && !ui.keyboard_modifiers().control {
+                                    self.insert_line_break(ui);
                                 } else if self.commit_mode == TextCommitMode::LostFocusPlusEnter {
                                     ui.send_message(TextMessage::text(
                                         self.handle,
                                         MessageDirection::FromWidget,
                                         self.text(),
                                     ));
+                                    self.commit(ui);
                                     self.has_focus = false;
+                                } else if self.commit_mode == TextCommitMode::Immediate {
+                                    self.commit(ui);
                                 }
                             }
                             KeyCode::Backspace if self.editable => {
@@ -1245,77 +1980,38 @@ impl Control for TextBox {
                                 }
                             }
                             KeyCode::End => {
-                                let text = self.formatted_text.borrow();
-                                let line = &text.get_lines()[self.caret_position.line];
-                                if ui.keyboard_modifiers().control {
-                                    let new_position = Position {
-                                        line: text.get_lines().len() - 1,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = None;
-                                } else if ui.keyboard_modifiers().shift {
-                                    let prev_position = self.caret_position;
-                                    let new_position = Position {
-                                        line: self.caret_position.line,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: prev_position,
-                                        end: Position {
-                                            line: self.caret_position.line,
-                                            offset: self.caret_position.offset,
-                                        },
-                                    });
+                                let modifiers = ui.keyboard_modifiers();
+                                let new_position = if modifiers.control {
+                                    self.end_position()
                                 } else {
-                                    let new_position = Position {
-                                        line: self.caret_position.line,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = None;
-                                }
+                                    self.line_end(self.caret_position.line)
+                                };
+                                self.move_caret_to(new_position, modifiers.shift);
                             }
                             KeyCode::Home => {
-                                if ui.keyboard_modifiers().control {
-                                    self.set_caret_position(Position { line: 0, offset: 0 });
-                                    self.selection_range = None;
-                                } else if ui.keyboard_modifiers().shift {
-                                    let prev_position = self.caret_position;
-                                    self.set_caret_position(Position {
-                                        line: self.caret_position.line,
-                                        offset: 0,
-                                    });
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: self.caret_position,
-                                        end: Position {
-                                            line: prev_position.line,
-                                            offset: prev_position.offset,
-                                        },
-                                    });
+                                let modifiers = ui.keyboard_modifiers();
+                                let new_position = if modifiers.control {
+                                    Position { line: 0, offset: 0 }
                                 } else {
-                                    self.set_caret_position(Position {
+                                    Position {
                                         line: self.caret_position.line,
                                         offset: 0,
-                                    });
-                                    self.selection_range = None;
-                                }
+                                    }
+                                };
+                                self.move_caret_to(new_position, modifiers.shift);
                             }
-                            KeyCode::KeyA if ui.keyboard_modifiers().control => {
-                                let text = self.formatted_text.borrow();
-                                if let Some(last_line) = &text.get_lines().last() {
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: Position { line: 0, offset: 0 },
-                                        end: Position {
-                                            line: text.get_lines().len() - 1,
-                                            offset: last_line.end - last_line.begin,
-                                        },
-                                    });
-                                }
+                            KeyCode::KeyA
+                                if ui.keyboard_modifiers().control
+                                    && !self.formatted_text.borrow().get_lines().is_empty() =>
+                            {
+                                // The selection is anchored at the beginning, so Shift+arrows
+                                // change it from the end, where the caret is.
+                                let end = self.end_position();
+                                self.selection_range = Some(SelectionRange {
+                                    begin: Position { line: 0, offset: 0 },
+                                    end,
+                                });
+                                self.set_caret_position(end);
                             }
                             KeyCode::KeyC if ui.keyboard_modifiers().control => {
                                 if let Some(mut clipboard) = ui.clipboard_mut() {
@@ -1339,9 +2035,28 @@ impl Control for TextBox {
                                     }
                                 }
                             }
-                            KeyCode::KeyV if ui.keyboard_modifiers().control => {
+                            KeyCode::KeyK
+                                if ui.keyboard_modifiers().control
+                                    && ui.keyboard_modifiers().shift
+                                    && self.multiline
+                                    && self.editable =>
+                            {
+                                self.delete_lines(ui);
+                            }
+                            KeyCode::KeyD
+                                if ui.keyboard_modifiers().control
+                                    && self.multiline
+                                    && self.editable =>
+                            {
+                                self.duplicate(ui);
+                            }
+                            KeyCode::KeyV if ui.keyboard_modifiers().control && self.editable => {
                                 if let Some(mut clipboard) = ui.clipboard_mut() {
-                                    if let Ok(content) = clipboard.get_contents() {
+                                    if let Some(content) = clipboard
+                                        .get_contents()
+                                        .ok()
+                                        .and_then(|content| self.filter_input(&content))
+                                    {
                                         if let Some(selection_range) = self.selection_range {
                                             self.remove_range(ui, selection_range);
                                             self.selection_range = None;
@@ -1369,15 +2084,20 @@ impl Control for TextBox {
                         if message.direction() == MessageDirection::FromWidget {
                             self.selection_range = None;
                             self.has_focus = false;
+                            self.set_ime_preedit("", None);
 
-                            if self.commit_mode == TextCommitMode::LostFocus
-                                || self.commit_mode == TextCommitMode::LostFocusPlusEnter
-                            {
-                                ui.send_message(TextMessage::text(
-                                    self.handle,
-                                    MessageDirection::FromWidget,
-                                    self.text(),
-                                ));
+                            // Read-only text cannot be changed, so there is nothing to commit.
+                            if self.editable {
+                                if self.commit_mode == TextCommitMode::LostFocus
+                                    || self.commit_mode == TextCommitMode::LostFocusPlusEnter
+                                {
+                                    ui.send_message(TextMessage::text(
+                                        self.handle,
+                                        MessageDirection::FromWidget,
+                                        self.text(),
+                                    ));
+                                }
+                                self.commit(ui);
                             }
                         }
                     }
@@ -1421,6 +2141,15 @@ impl Control for TextBox {
 
                         ui.release_mouse_capture();
                     }
+                    WidgetMessage::MouseWheel { amount, .. } if self.multiline => {
+                        let previous = self.view_position.y;
+                        self.view_position.y -= amount * self.line_height() * 3.0;
+                        self.clamp_view_position();
+                        // Let outer scroll viewers scroll if the text box is at its limit.
+                        if self.view_position.y != previous {
+                            message.set_handled(true);
+                        }
+                    }
                     _ => {}
                 }
             } else if let Some(msg) = message.data::<TextMessage>() {
@@ -1464,6 +2193,7 @@ impl Control for TextBox {
                             if text.wrap_mode() != *wrap_mode {
                                 text.set_wrap(*wrap_mode);
                                 drop(text);
+                                self.placeholder.borrow_mut().set_wrap(*wrap_mode);
                                 self.invalidate_layout();
                                 ui.send_message(message.reverse());
                             }
@@ -1472,6 +2202,8 @@ impl Control for TextBox {
                             if &text.get_font() != font {
                                 text.set_font(font.clone());
                                 drop(text);
+                                self.placeholder.borrow_mut().set_font(font.clone());
+                                self.ime_preedit.borrow_mut().set_font(font.clone());
                                 self.invalidate_layout();
                                 ui.send_message(message.reverse());
                             }
@@ -1480,6 +2212,9 @@ impl Control for TextBox {
                             if &text.vertical_alignment() != alignment {
                                 text.set_vertical_alignment(*alignment);
                                 drop(text);
+                                self.placeholder
+                                    .borrow_mut()
+                                    .set_vertical_alignment(*alignment);
                                 self.invalidate_layout();
                                 ui.send_message(message.reverse());
                             }
@@ -1488,6 +2223,9 @@ impl Control for TextBox {
                             if &text.horizontal_alignment() != alignment {
                                 text.set_horizontal_alignment(*alignment);
                                 drop(text);
+                                self.placeholder
+                                    .borrow_mut()
+                                    .set_horizontal_alignment(*alignment);
                                 self.invalidate_layout();
                                 ui.send_message(message.reverse());
                             }
@@ -1556,12 +2294,37 @@ impl Control for TextBox {
                         TextBoxMessage::Editable(editable) => {
                             if &self.editable != editable {
                                 self.editable = *editable;
+                                if !self.editable {
+                                    self.set_ime_preedit("", None);
+                                }
                                 ui.send_message(message.reverse());
                             }
                         }
+                        TextBoxMessage::Placeholder(placeholder) => {
+                            if &self.placeholder.borrow().text() != placeholder {
+                                self.placeholder.borrow_mut().set_text(placeholder);
+                                self.invalidate_layout();
+                                ui.send_message(message.reverse());
+                            }
+                        }
+                        TextBoxMessage::PlaceholderBrush(brush) => {
+                            if &self.placeholder.borrow().brush() != brush {
+                                self.placeholder.borrow_mut().set_brush(brush.clone());
+                                ui.send_message(message.reverse());
+                            }
+                        }
+                        TextBoxMessage::MaxLength(max_length) => {
+                            if &self.max_length != max_length {
+                                self.max_length = *max_length;
+                                ui.send_message(message.reverse());
+                            }
+                        }
+                        TextBoxMessage::Validity(_) | TextBoxMessage::Committed(_) => (),
                     }
                 }
             }
+
+            self.update_validity(ui);
         }
     }
 }
@@ -1573,7 +2336,8 @@ pub struct TextBoxBuilder {
     text: String,
     caret_brush: Brush,
     selection_brush: Brush,
-    filter: Option<Rc<RefCell<FilterCallback>>>,
+    filter: Option<InputFilter>,
+    max_length: Option<usize>,
     vertical_alignment: VerticalAlignment,
     horizontal_alignment: HorizontalAlignment,
     wrap: WrapMode,
@@ -1586,6 +2350,8 @@ pub struct TextBoxBuilder {
     shadow_dilation: f32,
     shadow_offset: Vector2<f32>,
     skip_chars: Vec<u32>,
+    placeholder: String,
+    placeholder_brush: Brush,
 }
 
 impl TextBoxBuilder {
@@ -1598,6 +2364,7 @@ impl TextBoxBuilder {
             caret_brush: Brush::Solid(Color::WHITE),
             selection_brush: Brush::Solid(Color::opaque(80, 118, 178)),
             filter: None,
+            max_length: None,
             vertical_alignment: VerticalAlignment::Top,
             horizontal_alignment: HorizontalAlignment::Left,
             wrap: WrapMode::NoWrap,
@@ -1610,6 +2377,8 @@ impl TextBoxBuilder {
             shadow_dilation: 1.0,
             shadow_offset: Vector2::new(1.0, 1.0),
             skip_chars: Default::default(),
+            placeholder: Default::default(),
+            placeholder_brush: BRUSH_LIGHTER,
         }
     }
 
@@ -1639,10 +2408,22 @@ impl TextBoxBuilder {
 
     /// Sets the desired character filter of the text box. See [`FilterCallback`] for more info.
     pub fn with_filter(mut self, filter: Rc<RefCell<FilterCallback>>) -> Self {
+        self.filter = Some(InputFilter::Custom(filter));
+        self
+    }
+
+    /// Sets the desired input filter of the text box. See [`InputFilter`] for more info.
+    pub fn with_input_filter(mut self, filter: InputFilter) -> Self {
         self.filter = Some(filter);
         self
     }
 
+    /// Sets the desired maximum length of the text in characters.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
     /// Sets the desired vertical text alignment of the text box.
     pub fn with_vertical_text_alignment(mut self, alignment: VerticalAlignment) -> Self {
         self.vertical_alignment = alignment;
@@ -1719,6 +2500,14 @@ impl TextBoxBuilder {
         self
     }
 
+    /// Sets the desired placeholder (hint) text and its brush. The placeholder is shown while the text
+    /// box is empty and unfocused.
+    pub fn with_placeholder<P: AsRef<str>>(mut self, text: P, brush: Brush) -> Self {
+        self.placeholder = text.as_ref().to_owned();
+        self.placeholder_brush = brush;
+        self
+    }
+
     /// Creates a new [`TextBox`] instance and adds it to the user interface.
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
         if self.widget_builder.foreground.is_none() {
@@ -1731,14 +2520,27 @@ impl TextBoxBuilder {
             self.widget_builder.cursor = Some(CursorIcon::Text);
         }
 
-        let text_box = TextBox {
+        let font = self.font.unwrap_or_else(|| ctx.default_font());
+        let placeholder = FormattedTextBuilder::new(font.clone())
+            .with_text(self.placeholder)
+            .with_brush(self.placeholder_brush)
+            .with_horizontal_alignment(self.horizontal_alignment)
+            .with_vertical_alignment(self.vertical_alignment)
+            .with_wrap(self.wrap)
+            .build();
+
+        let ime_preedit = FormattedTextBuilder::new(font.clone())
+            .with_constraint(Vector2::repeat(f32::INFINITY))
+            .build();
+
+        let mut text_box = TextBox {
             widget: self.widget_builder.build(),
             caret_position: Position::default(),
             caret_visible: false,
             blink_timer: 0.0,
             blink_interval: 0.5,
             formatted_text: RefCell::new(
-                FormattedTextBuilder::new(self.font.unwrap_or_else(|| ctx.default_font()))
+                FormattedTextBuilder::new(font)
                     .with_text(self.text)
                     .with_horizontal_alignment(self.horizontal_alignment)
                     .with_vertical_alignment(self.vertical_alignment)
@@ -1756,13 +2558,494 @@ impl TextBoxBuilder {
             caret_brush: self.caret_brush,
             has_focus: false,
             filter: self.filter,
+            max_length: self.max_length,
+            valid: true,
             commit_mode: self.commit_mode,
             multiline: self.multiline,
             editable: self.editable,
             view_position: Default::default(),
             skip_chars: self.skip_chars,
+            placeholder: RefCell::new(placeholder),
+            ime_preedit: RefCell::new(ime_preedit),
+            ime_preedit_cursor: None,
+            clamped_layout: None,
         };
+        text_box.valid = text_box.is_valid();
 
         ctx.add_node(UiNode::new(text_box))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::{KeyboardModifiers, OsEvent};
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn text_box_line_operations() {
+        let text = chars("one\ntwo\nthree");
+
+        let edit = delete_lines(&text, 5, 5);
+        assert_eq!(edit.text, "one\nthree");
+        assert_eq!(edit.caret, 4);
+        let edit = delete_lines(&text, 9, 9);
+        assert_eq!(edit.text, "one\ntwo");
+        assert_eq!(edit.caret, 4);
+        // The selection ends at the beginning of the last line, so the last line is kept.
+        assert_eq!(delete_lines(&text, 1, 8).text, "three");
+
+        let edit = move_lines(&text, 5, None, VerticalDirection::Up).unwrap();
+        assert_eq!(edit.text, "two\none\nthree");
+        assert_eq!(edit.caret, 1);
+        let edit = move_lines(&text, 5, Some((4, 7)), VerticalDirection::Down).unwrap();
+        assert_eq!(edit.text, "one\nthree\ntwo");
+        assert_eq!(edit.caret, 11);
+        assert_eq!(edit.selection, Some((10, 13)));
+        assert!(move_lines(&text, 1, None, VerticalDirection::Up).is_none());
+        assert!(move_lines(&text, 9, None, VerticalDirection::Down).is_none());
+
+        let edit = duplicate(&text, 5, None);
+        assert_eq!(edit.text, "one\ntwo\ntwo\nthree");
+        assert_eq!(edit.caret, 9);
+        let edit = duplicate(&text, 2, Some((2, 0)));
+        assert_eq!(edit.text, "onone\ntwo\nthree");
+        assert_eq!(edit.selection, Some((2, 4)));
+
+        let text = chars("fn foo() {\n    bar();\n}");
+        assert_eq!(indentation(&text, 21), "    ");
+        assert_eq!(indentation(&text, 13), "  ");
+        assert_eq!(indentation(&text, 10), "");
+    }
+
+    fn key_down(ui: &mut UserInterface, text_box: Handle<UiNode>, code: KeyCode, modifiers: &str) {
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            alt: false,
+            shift: modifiers.contains("shift"),
+            control: modifiers.contains("ctrl"),
+            system: false,
+        }));
+        ui.send_message(WidgetMessage::key_down(
+            text_box,
+            MessageDirection::FromWidget,
+            code,
+        ));
+        while ui.poll_message().is_some() {}
+    }
+
+    fn caret_and_selection(
+        ui: &UserInterface,
+        text_box: Handle<UiNode>,
+    ) -> (usize, Option<(usize, usize)>) {
+        let text_box = ui.node(text_box).query_component::<TextBox>().unwrap();
+        (
+            text_box.caret_position.offset,
+            text_box
+                .selection_range
+                .map(|range| (range.begin.offset, range.end.offset)),
+        )
+    }
+
+    #[test]
+    fn text_box_caret_navigation() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text("foo bar baz")
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+
+        key_down(&mut ui, text_box, KeyCode::ArrowRight, "ctrl");
+        assert_eq!(caret_and_selection(&ui, text_box), (4, None));
+        key_down(&mut ui, text_box, KeyCode::ArrowRight, "ctrl+shift");
+        key_down(&mut ui, text_box, KeyCode::ArrowRight, "shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (9, Some((4, 9))));
+
+        // Selection keeps its anchor.
+        key_down(&mut ui, text_box, KeyCode::Home, "shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (0, Some((4, 0))));
+        key_down(&mut ui, text_box, KeyCode::End, "ctrl+shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (11, Some((4, 11))));
+
+        // Arrows without Shift collapse the selection.
+        key_down(&mut ui, text_box, KeyCode::ArrowLeft, "");
+        assert_eq!(caret_and_selection(&ui, text_box), (4, None));
+        key_down(&mut ui, text_box, KeyCode::ArrowLeft, "ctrl+shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (0, Some((4, 0))));
+        key_down(&mut ui, text_box, KeyCode::End, "");
+        assert_eq!(caret_and_selection(&ui, text_box), (11, None));
+    }
+
+    #[test]
+    fn text_box_placeholder() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text_box = TextBoxBuilder::new(
+            WidgetBuilder::new().with_horizontal_alignment(HorizontalAlignment::Left),
+        )
+        .with_placeholder("Search...", BRUSH_LIGHTER)
+        .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+
+        // Empty text box is large enough to fit the placeholder.
+        let width = ui.node(text_box).desired_size().x;
+        assert!(width > 0.0);
+
+        ui.send_message(TextBoxMessage::placeholder(
+            text_box,
+            MessageDirection::ToWidget,
+            String::new(),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert!(ui.node(text_box).desired_size().x < width);
+    }
+
+    fn type_text(ui: &mut UserInterface, text_box: Handle<UiNode>, text: &str) {
+        ui.send_message(WidgetMessage::text(
+            text_box,
+            MessageDirection::ToWidget,
+            text.to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+    }
+
+    #[test]
+    fn text_box_input_filter() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_input_filter(InputFilter::Numeric)
+            .with_max_length(5)
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+
+        let text_of = |ui: &UserInterface| {
+            ui.node(text_box)
+                .query_component::<TextBox>()
+                .unwrap()
+                .text()
+        };
+        let is_valid = |ui: &UserInterface| {
+            ui.node(text_box)
+                .query_component::<TextBox>()
+                .unwrap()
+                .valid
+        };
+
+        type_text(&mut ui, text_box, "-");
+        assert_eq!(text_of(&ui), "-");
+        assert!(!is_valid(&ui));
+        type_text(&mut ui, text_box, "1a.-5.");
+        assert_eq!(text_of(&ui), "-1.5");
+        assert!(is_valid(&ui));
+        type_text(&mut ui, text_box, "2345");
+        assert_eq!(text_of(&ui), "-1.52");
+
+        let filter = InputFilter::Regex(Regex::new("[0-9a-f]").unwrap());
+        assert!(filter.is_valid("c0ffee"));
+        assert!(!filter.is_valid("coffee"));
+        assert!(InputFilter::Integer.is_valid("-42"));
+        assert!(!InputFilter::Integer.accepts_text("4.2"));
+    }
+
+    #[test]
+    fn text_box_committed() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let immediate = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text_commit_mode(TextCommitMode::Immediate)
+            .build(&mut ui.build_ctx());
+        let lost_focus = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text_commit_mode(TextCommitMode::LostFocus)
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+
+        // Returns committed texts and the amount of text changes, that were reported.
+        let collect = |ui: &mut UserInterface, text_box: Handle<UiNode>| {
+            let mut committed = Vec::new();
+            let mut changes = 0;
+            while let Some(message) = ui.poll_message() {
+                if message.destination() != text_box
+                    || message.direction() != MessageDirection::FromWidget
+                {
+                    continue;
+                }
+                if let Some(TextBoxMessage::Committed(text)) = message.data() {
+                    committed.push(text.clone());
+                } else if let Some(TextMessage::Text(_)) = message.data() {
+                    changes += 1;
+                }
+            }
+            (committed, changes)
+        };
+
+        for text in ["1", "2"] {
+            ui.send_message(WidgetMessage::text(
+                immediate,
+                MessageDirection::ToWidget,
+                text.to_string(),
+            ));
+        }
+        assert_eq!(collect(&mut ui, immediate), (vec![], 2));
+        ui.send_message(WidgetMessage::key_down(
+            immediate,
+            MessageDirection::FromWidget,
+            KeyCode::Enter,
+        ));
+        assert_eq!(collect(&mut ui, immediate), (vec!["12".to_string()], 0));
+
+        ui.send_message(WidgetMessage::text(
+            lost_focus,
+            MessageDirection::ToWidget,
+            "3".to_string(),
+        ));
+        ui.send_message(WidgetMessage::key_down(
+            lost_focus,
+            MessageDirection::FromWidget,
+            KeyCode::Enter,
+        ));
+        assert_eq!(collect(&mut ui, lost_focus), (vec![], 0));
+        ui.send_message(WidgetMessage::unfocus(
+            lost_focus,
+            MessageDirection::FromWidget,
+        ));
+        assert_eq!(collect(&mut ui, lost_focus), (vec!["3".to_string()], 1));
+    }
+
+    #[test]
+    fn text_box_ime_composition() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text("a")
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.ime_cursor_area(), None);
+
+        ui.send_message(WidgetMessage::focus(text_box, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert!(ui.ime_cursor_area().is_some());
+
+        let ime = |ui: &mut UserInterface, event: ImeEvent| {
+            ui.process_os_event(&OsEvent::Ime(event));
+            while ui.poll_message().is_some() {}
+        };
+        let state = |ui: &UserInterface| {
+            let text_box = ui.node(text_box).query_component::<TextBox>().unwrap();
+            (
+                text_box.text(),
+                text_box.ime_preedit.borrow().text(),
+                text_box.ime_preedit_cursor,
+            )
+        };
+
+        key_down(&mut ui, text_box, KeyCode::End, "");
+        ime(&mut ui, ImeEvent::Enabled);
+        ime(
+            &mut ui,
+            ImeEvent::Preedit {
+                text: "ni".to_string(),
+                cursor: Some((2, 2)),
+            },
+        );
+        // Composed text is not a part of the text until it is committed.
+        assert_eq!(state(&ui), ("a".to_string(), "ni".to_string(), Some(2)));
+
+        ime(
+            &mut ui,
+            ImeEvent::Preedit {
+                text: String::new(),
+                cursor: None,
+            },
+        );
+        ime(&mut ui, ImeEvent::Commit("你".to_string()));
+        assert_eq!(state(&ui), ("a你".to_string(), String::new(), None));
+        assert_eq!(caret_and_selection(&ui, text_box), (2, None));
+
+        ui.send_message(WidgetMessage::unfocus(text_box, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.ime_cursor_area(), None);
+    }
+
+    #[test]
+    fn text_box_wrap_and_scroll() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text = (0..20)
+            .map(|i| format!("Line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text_box =
+            TextBoxBuilder::new(WidgetBuilder::new().with_width(100.0).with_height(50.0))
+                .with_multiline(true)
+                .with_wrap(WrapMode::Word)
+                .with_vertical_text_alignment(VerticalAlignment::Top)
+                .with_text(text)
+                .build(&mut ui.build_ctx());
+        let long_line = TextBoxBuilder::new(WidgetBuilder::new().with_width(100.0))
+            .with_wrap(WrapMode::Word)
+            .with_text("a long line of text, that does not fit into the bounds")
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+
+        let text_box_ref = |ui: &UserInterface| {
+            ui.node(text_box)
+                .query_component::<TextBox>()
+                .unwrap()
+                .clone()
+        };
+        // Wrapping is done on the actual width of the text box.
+        let long_line = ui.node(long_line).query_component::<TextBox>().unwrap();
+        assert!(long_line.formatted_text.borrow().get_lines().len() > 1);
+
+        // The view follows the caret.
+        key_down(&mut ui, text_box, KeyCode::End, "ctrl");
+        let text_box_state = text_box_ref(&ui);
+        let view_position = text_box_state.view_position;
+        assert!(view_position.y > 0.0);
+        assert_eq!(view_position.x, 0.0);
+        let caret_bottom = text_box_state.caret_local_position().y + text_box_state.line_height();
+        assert!(caret_bottom - view_position.y <= 50.0 + f32::EPSILON);
+
+        // Mouse wheel scrolls the content, but not beyond its bounds.
+        let scroll = |ui: &mut UserInterface, amount: f32| {
+            ui.send_message(WidgetMessage::mouse_wheel(
+                text_box,
+                MessageDirection::FromWidget,
+                Vector2::default(),
+                amount,
+            ));
+            while ui.poll_message().is_some() {}
+        };
+        scroll(&mut ui, 1.0);
+        assert!(text_box_ref(&ui).view_position.y < view_position.y);
+        scroll(&mut ui, 100.0);
+        assert_eq!(text_box_ref(&ui).view_position.y, 0.0);
+        scroll(&mut ui, -100.0);
+        let max = text_box_ref(&ui).content_size().y - 50.0;
+        assert!((text_box_ref(&ui).view_position.y - max).abs() < 0.001);
+
+        // The content size is updated, when the text changes, and the view is clamped to it.
+        let content_size = text_box_ref(&ui).content_size();
+        ui.send_message(TextMessage::text(
+            text_box,
+            MessageDirection::ToWidget,
+            "Line 0\nLine 1\nLine 2\nLine 3".to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        let text_box_state = text_box_ref(&ui);
+        assert!(text_box_state.content_size().y < content_size.y);
+        let max = (text_box_state.content_size().y - 50.0).max(0.0);
+        assert!(text_box_state.view_position.y <= max + 0.001);
+    }
+
+    #[test]
+    fn text_box_read_only() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_multiline(true)
+            .with_editable(false)
+            .with_text("foo bar")
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+
+        let text = |ui: &UserInterface| {
+            ui.node(text_box)
+                .query_component::<TextBox>()
+                .unwrap()
+                .text()
+        };
+
+        ui.send_message(WidgetMessage::text(
+            text_box,
+            MessageDirection::FromWidget,
+            "x".to_string(),
+        ));
+        for code in [KeyCode::Enter, KeyCode::Backspace, KeyCode::Delete] {
+            key_down(&mut ui, text_box, code, "");
+        }
+        key_down(&mut ui, text_box, KeyCode::KeyD, "ctrl");
+        assert_eq!(text(&ui), "foo bar");
+
+        // Navigation and selection still work.
+        key_down(&mut ui, text_box, KeyCode::ArrowRight, "ctrl+shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (4, Some((0, 4))));
+        key_down(&mut ui, text_box, KeyCode::KeyA, "ctrl");
+        assert_eq!(caret_and_selection(&ui, text_box), (7, Some((0, 7))));
+        key_down(&mut ui, text_box, KeyCode::ArrowLeft, "shift");
+        assert_eq!(caret_and_selection(&ui, text_box), (6, Some((0, 6))));
+        key_down(&mut ui, text_box, KeyCode::KeyA, "ctrl");
+        key_down(&mut ui, text_box, KeyCode::Delete, "");
+        assert_eq!(text(&ui), "foo bar");
+
+        // Nothing is committed, because nothing could be changed.
+        ui.send_message(WidgetMessage::unfocus(
+            text_box,
+            MessageDirection::FromWidget,
+        ));
+        while let Some(message) = ui.poll_message() {
+            assert!(message.data::<TextBoxMessage>().is_none());
+            assert!(message.data::<TextMessage>().is_none());
+        }
+
+        ui.send_message(TextBoxMessage::editable(
+            text_box,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        key_down(&mut ui, text_box, KeyCode::End, "ctrl");
+        key_down(&mut ui, text_box, KeyCode::Backspace, "");
+        assert_eq!(text(&ui), "foo ba");
+    }
+
+    #[test]
+    fn text_box_draws_visible_lines_only() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text = (0..10000)
+            .map(|i| format!("line {i}\n"))
+            .collect::<String>();
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new().with_height(100.0))
+            .with_multiline(true)
+            .with_text(text)
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+        ui.draw();
+
+        let glyph_lines = |ui: &UserInterface| {
+            let text_box = ui.node(text_box).query_component::<TextBox>().unwrap();
+            let text = text_box.formatted_text.borrow();
+            (
+                text.glyph_lines(),
+                text.get_lines()[0].height,
+                text.get_glyphs().len(),
+            )
+        };
+
+        let (lines, line_height, glyph_count) = glyph_lines(&ui);
+        assert_eq!(lines.start, 0);
+        assert_eq!(lines.end, (100.0 / line_height).ceil() as usize);
+        assert!(glyph_count < 10 * lines.len());
+
+        // Scrolling moves the visible lines.
+        ui.node_mut(text_box)
+            .cast_mut::<TextBox>()
+            .unwrap()
+            .view_position
+            .y = 5000.0 * line_height;
+        ui.draw();
+        let (lines, _, _) = glyph_lines(&ui);
+        assert_eq!(lines.start, 5000);
+        assert_eq!(lines.end, 5000 + (100.0 / line_height).ceil() as usize);
+    }
+}
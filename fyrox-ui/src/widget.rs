@@ -4,7 +4,7 @@
 #![warn(missing_docs)]
 
 use crate::{
-    brush::Brush,
+    brush::{Brush, BrushAnimation, BrushTarget},
     core::{
         algebra::{Matrix3, Point2, Vector2},
         math::Rect,
@@ -12,15 +12,18 @@ use crate::{
         reflect::prelude::*,
         uuid::Uuid,
         visitor::prelude::*,
+        TypeUuidProvider,
     },
+    custom_data::CustomData,
     define_constructor,
-    message::{CursorIcon, Force, KeyCode, MessageDirection, UiMessage},
-    HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
+    message::{CursorIcon, Force, ImeEvent, KeyCode, MessageDirection, UiMessage},
+    FlowDirection, HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle,
+    Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
 };
 use std::{
     any::Any,
     cell::{Cell, RefCell},
+    fmt::Debug,
     rc::Rc,
     sync::mpsc::Sender,
 };
@@ -84,6 +87,13 @@ pub enum WidgetMessage {
     /// Direction: **From/To UI**.
     Text(String),
 
+    /// Initiated when widget is in focus and an input method editor (IME) sends an event. Committed
+    /// text is **not** duplicated as [`WidgetMessage::Text`], widgets that accept text input should
+    /// handle [`ImeEvent::Commit`] as well.
+    ///
+    /// Direction: **From UI**.
+    Ime(ImeEvent),
+
     /// Initiated when widget is in focus and user presses a button on a keyboard.
     ///
     /// Direction: **From UI**.
@@ -159,6 +169,11 @@ pub enum WidgetMessage {
     /// Direction: **From/To UI**
     Background(Brush),
 
+    /// A request to replace brush animations of a widget. See [`BrushAnimation`] docs for more info.
+    ///
+    /// Direction: **To UI**
+    BrushAnimations(Vec<BrushAnimation>),
+
     /// A request to change foreground brush of a widget. Foreground brushes are used for text, borders and so on.
     ///
     /// Direction: **From/To UI**
@@ -193,6 +208,12 @@ pub enum WidgetMessage {
     /// Direction: **From/To UI**
     HorizontalAlignment(HorizontalAlignment),
 
+    /// A request to set flow direction of a widget. Flow direction tells whether the children of the widget should be
+    /// laid out from left to right or from right to left. See [`FlowDirection`] docs for more info.
+    ///
+    /// Direction: **From/To UI**
+    FlowDirection(FlowDirection),
+
     /// A request to set maximum size of widget. Maximum size restricts size of a widget during layout pass. For example
     /// you can set maximum size to a button which was placed into a grid's cell, if maximum size wouldn't be set, button
     /// would be stretched to fill entire cell.
@@ -386,6 +407,11 @@ impl WidgetMessage {
         WidgetMessage:Background => fn background(Brush), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::BrushAnimations`] message.
+        WidgetMessage:BrushAnimations => fn brush_animations(Vec<BrushAnimation>), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::Foreground`] message.
         WidgetMessage:Foreground => fn foreground(Brush), layout: false
@@ -486,6 +512,11 @@ impl WidgetMessage {
         WidgetMessage:HorizontalAlignment => fn horizontal_alignment(HorizontalAlignment), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::FlowDirection`] message.
+        WidgetMessage:FlowDirection => fn flow_direction(FlowDirection), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::VerticalAlignment`] message.
         WidgetMessage:VerticalAlignment => fn vertical_alignment(VerticalAlignment), layout: false
@@ -569,6 +600,12 @@ impl WidgetMessage {
         WidgetMessage:Text => fn text(String), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::Ime`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:Ime => fn ime(ImeEvent), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::KeyDown`] message. This method is for internal use only, and should not
         /// be used anywhere else.
@@ -670,6 +707,9 @@ pub struct Widget {
     pub vertical_alignment: VerticalAlignment,
     /// Horizontal alignment of the widget.
     pub horizontal_alignment: HorizontalAlignment,
+    /// Flow direction of the widget. See [`FlowDirection`] docs for more info.
+    #[visit(optional)]
+    pub flow_direction: FlowDirection,
     /// Margin for every sides of bounding rectangle. See [`Thickness`] docs for more info.
     pub margin: Thickness,
     /// Current, **local**, visibility state of the widget.
@@ -700,6 +740,10 @@ pub struct Widget {
     #[reflect(hidden)]
     #[visit(skip)]
     pub user_data: Option<Rc<dyn Any>>,
+    /// Typed custom data of the widget. See [`CustomData`] docs for more info.
+    #[reflect(hidden)]
+    #[visit(optional)]
+    pub custom_data: CustomData,
     /// A flag, that defines whether the widget should be drawn in a separate drawind pass after any other widget that draws
     /// normally.
     pub draw_on_top: bool,
@@ -741,6 +785,19 @@ pub struct Widget {
     pub layout_events_sender: Option<Sender<LayoutEvent>>,
     /// Unique identifier of the widget.
     pub id: Uuid,
+    /// Position of the widget in keyboard focus order (tab order). `None` means that the widget is
+    /// not a part of the focus order. See [`crate::accessibility`] docs for more info.
+    #[visit(optional)]
+    pub tab_index: Option<usize>,
+    /// Name of the widget, that describes its purpose to a user (for example, for screen readers).
+    /// If not set, text content of the widget is used. See [`crate::accessibility`] docs for more
+    /// info.
+    #[visit(optional)]
+    pub accessible_name: Option<String>,
+    /// Animations of the background and foreground brushes of the widget. See [`BrushAnimation`]
+    /// docs for more info.
+    #[visit(optional)]
+    pub brush_animations: Vec<BrushAnimation>,
     //
     // Layout. Interior mutability is a must here because layout performed in a series of recursive calls.
     //
@@ -780,6 +837,16 @@ pub struct Widget {
     #[reflect(hidden)]
     #[visit(skip)]
     pub clip_bounds: Cell<Rect<f32>>,
+    /// Actual flow direction of the widget (with [`FlowDirection::Inherit`] resolved), `true` means
+    /// right-to-left direction.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) right_to_left: bool,
+    /// A flag, that tells that the widget must be drawn after its siblings (for example, a pinned
+    /// sticky header of a [`crate::scroll_panel::ScrollPanel`]).
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) draw_after_siblings: Cell<bool>,
 }
 
 impl Widget {
@@ -1198,6 +1265,9 @@ impl Widget {
                     &WidgetMessage::Opacity(opacity) => self.opacity = opacity,
                     WidgetMessage::Background(background) => self.background = background.clone(),
                     WidgetMessage::Foreground(foreground) => self.foreground = foreground.clone(),
+                    WidgetMessage::BrushAnimations(animations) => {
+                        self.brush_animations = animations.clone()
+                    }
                     WidgetMessage::Name(name) => self.name = name.clone(),
                     &WidgetMessage::Width(width) => {
                         if self.width != width {
@@ -1223,6 +1293,12 @@ impl Widget {
                             self.invalidate_layout();
                         }
                     }
+                    WidgetMessage::FlowDirection(flow_direction)
+                        if self.flow_direction != *flow_direction =>
+                    {
+                        self.flow_direction = *flow_direction;
+                        self.invalidate_layout();
+                    }
                     WidgetMessage::MaxSize(max_size) => {
                         if self.max_size != *max_size {
                             self.max_size = *max_size;
@@ -1315,6 +1391,27 @@ impl Widget {
         self.horizontal_alignment
     }
 
+    /// Sets new flow direction of the widget.
+    #[inline]
+    pub fn set_flow_direction(&mut self, flow_direction: FlowDirection) -> &mut Self {
+        self.flow_direction = flow_direction;
+        self
+    }
+
+    /// Returns current flow direction of the widget. It could be [`FlowDirection::Inherit`], use
+    /// [`Self::is_right_to_left`] to get the actual direction.
+    #[inline]
+    pub fn flow_direction(&self) -> FlowDirection {
+        self.flow_direction
+    }
+
+    /// Returns `true` if the children of the widget are laid out from right to left. The value is
+    /// updated together with visual transform of the widget.
+    #[inline]
+    pub fn is_right_to_left(&self) -> bool {
+        self.right_to_left
+    }
+
     /// Sets new margin of the widget.
     #[inline]
     pub fn set_margin(&mut self, margin: Thickness) -> &mut Self {
@@ -1458,6 +1555,42 @@ impl Widget {
         self.enabled
     }
 
+    /// Returns position of the widget in keyboard focus order (if any).
+    #[inline]
+    pub fn tab_index(&self) -> Option<usize> {
+        self.tab_index
+    }
+
+    /// Sets new position of the widget in keyboard focus order.
+    #[inline]
+    pub fn set_tab_index(&mut self, tab_index: Option<usize>) {
+        self.tab_index = tab_index;
+    }
+
+    /// Returns accessible name of the widget (if any).
+    #[inline]
+    pub fn accessible_name(&self) -> Option<&str> {
+        self.accessible_name.as_deref()
+    }
+
+    /// Sets new accessible name of the widget.
+    #[inline]
+    pub fn set_accessible_name(&mut self, accessible_name: Option<String>) {
+        self.accessible_name = accessible_name;
+    }
+
+    /// Advances brush animations of the widget and applies the animated brushes.
+    pub(crate) fn update_brush_animations(&mut self, dt: f32) {
+        for animation in self.brush_animations.iter_mut() {
+            if let Some(brush) = animation.update(dt) {
+                match animation.target {
+                    BrushTarget::Background => self.background = brush,
+                    BrushTarget::Foreground => self.foreground = brush,
+                }
+            }
+        }
+    }
+
     /// Sets new cursor of the widget.
     #[inline]
     pub fn set_cursor(&mut self, cursor: Option<CursorIcon>) {
@@ -1570,6 +1703,8 @@ pub struct WidgetBuilder {
     pub vertical_alignment: VerticalAlignment,
     /// Horizontal alignment of the widget.
     pub horizontal_alignment: HorizontalAlignment,
+    /// Flow direction of the widget.
+    pub flow_direction: FlowDirection,
     /// Max size of the widget.
     pub max_size: Option<Vector2<f32>>,
     /// Min size of the widget.
@@ -1598,6 +1733,8 @@ pub struct WidgetBuilder {
     pub allow_drop: bool,
     /// User-defined data.
     pub user_data: Option<Rc<dyn Any>>,
+    /// Typed custom data of the widget.
+    pub custom_data: CustomData,
     /// Whether to draw the widget on top of any other or not.
     pub draw_on_top: bool,
     /// Whether the widget is enabled or not.
@@ -1616,6 +1753,12 @@ pub struct WidgetBuilder {
     pub preview_messages: bool,
     /// Whether the widget will handle OS events or not.
     pub handle_os_events: bool,
+    /// Position of the widget in keyboard focus order.
+    pub tab_index: Option<usize>,
+    /// Accessible name of the widget.
+    pub accessible_name: Option<String>,
+    /// Brush animations of the widget.
+    pub brush_animations: Vec<BrushAnimation>,
     /// Layout transform of the widget.
     pub layout_transform: Matrix3<f32>,
     /// Render transform of the widget.
@@ -1641,6 +1784,7 @@ impl WidgetBuilder {
             height: f32::NAN,
             vertical_alignment: VerticalAlignment::default(),
             horizontal_alignment: HorizontalAlignment::default(),
+            flow_direction: FlowDirection::default(),
             max_size: None,
             min_size: None,
             background: None,
@@ -1656,6 +1800,7 @@ impl WidgetBuilder {
             allow_drag: false,
             allow_drop: false,
             user_data: None,
+            custom_data: Default::default(),
             draw_on_top: false,
             enabled: true,
             cursor: None,
@@ -1669,6 +1814,9 @@ impl WidgetBuilder {
             render_transform: Matrix3::identity(),
             clip_to_bounds: true,
             id: Uuid::new_v4(),
+            tab_index: None,
+            accessible_name: None,
+            brush_animations: Vec::new(),
         }
     }
 
@@ -1722,6 +1870,12 @@ impl WidgetBuilder {
         self
     }
 
+    /// Sets the desired flow direction of the widget.
+    pub fn with_flow_direction(mut self, flow_direction: FlowDirection) -> Self {
+        self.flow_direction = flow_direction;
+        self
+    }
+
     /// Sets the max size of the widget.
     pub fn with_max_size(mut self, max_size: Vector2<f32>) -> Self {
         self.max_size = Some(max_size);
@@ -1848,6 +2002,26 @@ impl WidgetBuilder {
         self
     }
 
+    /// Adds a value to the custom data of the widget. The value lives in memory only, see
+    /// [`CustomData`] docs for more info.
+    pub fn with_custom_data<T>(mut self, value: T) -> Self
+    where
+        T: Any + Clone + Debug,
+    {
+        self.custom_data.insert(value);
+        self
+    }
+
+    /// Adds a value to the custom data of the widget. The value will be saved together with the
+    /// widget, see [`CustomData`] docs for more info.
+    pub fn with_serializable_custom_data<T>(mut self, value: T) -> Self
+    where
+        T: Reflect + Visit + Default + Clone + TypeUuidProvider,
+    {
+        self.custom_data.insert_serializable(value);
+        self
+    }
+
     /// Sets the desired widget cursor.
     pub fn with_cursor(mut self, cursor: Option<CursorIcon>) -> Self {
         self.cursor = cursor;
@@ -1866,6 +2040,24 @@ impl WidgetBuilder {
         self
     }
 
+    /// Sets the desired position of the widget in keyboard focus order.
+    pub fn with_tab_index(mut self, tab_index: usize) -> Self {
+        self.tab_index = Some(tab_index);
+        self
+    }
+
+    /// Sets the desired accessible name of the widget.
+    pub fn with_accessible_name<S: AsRef<str>>(mut self, accessible_name: S) -> Self {
+        self.accessible_name = Some(accessible_name.as_ref().to_owned());
+        self
+    }
+
+    /// Adds a new animation of a brush of the widget. See [`BrushAnimation`] docs for more info.
+    pub fn with_brush_animation(mut self, animation: BrushAnimation) -> Self {
+        self.brush_animations.push(animation);
+        self
+    }
+
     /// Sets the desired tooltip for the node.
     ///
     /// ## Important
@@ -1921,6 +2113,7 @@ impl WidgetBuilder {
             column: self.column,
             vertical_alignment: self.vertical_alignment,
             horizontal_alignment: self.horizontal_alignment,
+            flow_direction: self.flow_direction,
             margin: self.margin,
             visibility: self.visibility,
             global_visibility: true,
@@ -1938,6 +2131,7 @@ impl WidgetBuilder {
             allow_drag: self.allow_drag,
             allow_drop: self.allow_drop,
             user_data: self.user_data.clone(),
+            custom_data: self.custom_data,
             draw_on_top: self.draw_on_top,
             enabled: self.enabled,
             cursor: self.cursor,
@@ -1954,6 +2148,11 @@ impl WidgetBuilder {
             visual_transform: Matrix3::identity(),
             clip_to_bounds: self.clip_to_bounds,
             id: self.id,
+            tab_index: self.tab_index,
+            accessible_name: self.accessible_name,
+            brush_animations: self.brush_animations,
+            right_to_left: false,
+            draw_after_siblings: Cell::new(false),
         }
     }
 }
@@ -0,0 +1,158 @@
+//! Widget pooling for user interfaces, that create and destroy lots of widgets every second. See
+//! [`WidgetPool`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{core::pool::Handle, UiNode};
+use fxhash::{FxHashMap, FxHashSet};
+
+#[derive(Debug)]
+pub(crate) struct PooledWidget {
+    pub(crate) handle: Handle<UiNode>,
+    pub(crate) visibility: bool,
+}
+
+/// Widget pool stores detached widgets (with all their descendants) for later reuse. Some user
+/// interfaces create and destroy lots of short-lived widgets (damage numbers, chat messages,
+/// notifications, etc.). Every new widget allocates its node, children lists, formatted text,
+/// draw command indices and so on, and every removed widget frees them - this creates allocation
+/// pressure and causes frame time spikes. Instead, such widgets could be recycled: a recycled
+/// widget stays alive with all of its buffers, but it is detached from the widget tree, so it is
+/// neither updated by the layout system nor drawn. Later, the widget could be taken from the pool,
+/// changed using messages and shown again.
+///
+/// Widgets are pooled by a string key, that is defined by a user. The key should identify the
+/// structure of the widget, every widget in the pool with a given key is considered equivalent.
+///
+/// Every user interface has its own widget pool (see [`crate::UserInterface::widget_pool`]), the
+/// pool is managed using the following methods of the user interface:
+///
+/// - [`crate::UserInterface::recycle_node`] - detaches a widget and puts it to the pool.
+/// - [`crate::UserInterface::take_pooled_node`] - takes a widget from the pool and attaches it to
+///   the root canvas.
+/// - [`crate::UserInterface::take_pooled_node_or_build`] - takes a widget from the pool or builds a
+///   new one if the pool is empty.
+/// - [`crate::UserInterface::prewarm_widget_pool`] - fills the pool in advance, to avoid
+///   allocations at the moment when widgets are needed.
+/// - [`crate::UserInterface::clear_widget_pool`] - removes pooled widgets.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::{
+///     core::pool::Handle, message::MessageDirection, text::{TextBuilder, TextMessage},
+///     widget::WidgetBuilder, UiNode, UserInterface,
+/// };
+///
+/// const DAMAGE_NUMBER: &str = "DamageNumber";
+///
+/// fn prewarm(ui: &mut UserInterface) {
+///     ui.prewarm_widget_pool(DAMAGE_NUMBER, 32, |ctx| {
+///         TextBuilder::new(WidgetBuilder::new()).build(ctx)
+///     });
+/// }
+///
+/// fn show_damage_number(ui: &mut UserInterface, damage: u32) -> Handle<UiNode> {
+///     let text = ui.take_pooled_node_or_build(DAMAGE_NUMBER, |ctx| {
+///         TextBuilder::new(WidgetBuilder::new()).build(ctx)
+///     });
+///     ui.send_message(TextMessage::text(
+///         text,
+///         MessageDirection::ToWidget,
+///         damage.to_string(),
+///     ));
+///     text
+/// }
+///
+/// fn hide_damage_number(ui: &mut UserInterface, text: Handle<UiNode>) {
+///     ui.recycle_node(text, DAMAGE_NUMBER);
+/// }
+/// ```
+///
+/// Keep in mind, that pooled widgets are not reset to their initial state, it is up to you to
+/// change every property, that might have been changed before recycling.
+#[derive(Debug)]
+pub struct WidgetPool {
+    entries: FxHashMap<String, Vec<PooledWidget>>,
+    handles: FxHashSet<Handle<UiNode>>,
+    capacity: usize,
+}
+
+impl Default for WidgetPool {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+            handles: Default::default(),
+            capacity: 256,
+        }
+    }
+}
+
+impl WidgetPool {
+    /// Returns the maximum amount of widgets, that could be stored for a single key. Widgets, that
+    /// are recycled when the limit is reached, are removed immediately.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum amount of widgets, that could be stored for a single key. It does not
+    /// remove widgets, that are already in the pool.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    /// Returns the amount of widgets in the pool with the given key.
+    pub fn count(&self, key: &str) -> usize {
+        self.entries.get(key).map_or(0, |entries| entries.len())
+    }
+
+    /// Returns the total amount of widgets in the pool.
+    pub fn total_count(&self) -> usize {
+        self.entries.values().map(|entries| entries.len()).sum()
+    }
+
+    /// Returns an iterator over all keys of the pool.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|key| key.as_str())
+    }
+
+    /// Returns `true` if the given widget is in the pool.
+    pub fn contains(&self, handle: Handle<UiNode>) -> bool {
+        self.handles.contains(&handle)
+    }
+
+    pub(crate) fn is_full(&self, key: &str) -> bool {
+        self.count(key) >= self.capacity
+    }
+
+    pub(crate) fn push(&mut self, key: &str, widget: PooledWidget) {
+        self.handles.insert(widget.handle);
+        match self.entries.get_mut(key) {
+            Some(entries) => entries.push(widget),
+            None => {
+                self.entries.insert(key.to_owned(), vec![widget]);
+            }
+        }
+    }
+
+    pub(crate) fn pop(&mut self, key: &str) -> Option<PooledWidget> {
+        let widget = self.entries.get_mut(key)?.pop()?;
+        self.handles.remove(&widget.handle);
+        Some(widget)
+    }
+
+    pub(crate) fn drain(&mut self, key: Option<&str>) -> Vec<PooledWidget> {
+        let widgets = match key {
+            Some(key) => self.entries.remove(key).unwrap_or_default(),
+            None => self
+                .entries
+                .drain()
+                .flat_map(|(_, entries)| entries)
+                .collect(),
+        };
+        for widget in widgets.iter() {
+            self.handles.remove(&widget.handle);
+        }
+        widgets
+    }
+}
@@ -0,0 +1,549 @@
+//! Condition expressions of transitions. See [`ConditionExpression`] docs for more info.
+
+use crate::{
+    animation::machine::{Parameter, ParameterContainer},
+    core::{reflect::prelude::*, visitor::prelude::*},
+};
+use std::fmt::{Display, Formatter};
+
+/// An error, that occurred during compilation of a [`ConditionExpression`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpressionError {
+    /// Human-readable description of the error.
+    pub message: String,
+    /// Range of the source text (in bytes), that caused the error. It is used to highlight the
+    /// erroneous part of an expression.
+    pub span: std::ops::Range<usize>,
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (column {})", self.message, self.span.start + 1)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BinaryOp {
+    And,
+    Or,
+    Xor,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Xor => "^",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessOrEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterOrEqual => ">=",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(f32),
+    True,
+    False,
+    Not,
+    Minus,
+    LeftParen,
+    RightParen,
+    Binary(BinaryOp),
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, std::ops::Range<usize>)>, ExpressionError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let two = |s: &str| source[i..].starts_with(s);
+        let token = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if two("&&") {
+            i += 2;
+            Token::Binary(BinaryOp::And)
+        } else if two("||") {
+            i += 2;
+            Token::Binary(BinaryOp::Or)
+        } else if two("==") {
+            i += 2;
+            Token::Binary(BinaryOp::Equal)
+        } else if two("!=") {
+            i += 2;
+            Token::Binary(BinaryOp::NotEqual)
+        } else if two("<=") {
+            i += 2;
+            Token::Binary(BinaryOp::LessOrEqual)
+        } else if two(">=") {
+            i += 2;
+            Token::Binary(BinaryOp::GreaterOrEqual)
+        } else if c.is_ascii_digit()
+            || (c == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+        {
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            match source[start..i].parse::<f32>() {
+                Ok(number) => Token::Number(number),
+                Err(_) => {
+                    return Err(ExpressionError {
+                        message: format!("Invalid number {}", &source[start..i]),
+                        span: start..i,
+                    })
+                }
+            }
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'.')
+            {
+                i += 1;
+            }
+            match &source[start..i] {
+                "true" => Token::True,
+                "false" => Token::False,
+                name => Token::Identifier(name.to_string()),
+            }
+        } else if c == b'"' {
+            match source[i + 1..].find('"') {
+                Some(length) => {
+                    i += length + 2;
+                    Token::Identifier(source[start + 1..i - 1].to_string())
+                }
+                None => {
+                    return Err(ExpressionError {
+                        message: "Unterminated parameter name".to_string(),
+                        span: start..source.len(),
+                    })
+                }
+            }
+        } else {
+            i += 1;
+            match c {
+                b'!' => Token::Not,
+                b'-' => Token::Minus,
+                b'(' => Token::LeftParen,
+                b')' => Token::RightParen,
+                b'^' => Token::Binary(BinaryOp::Xor),
+                b'<' => Token::Binary(BinaryOp::Less),
+                b'>' => Token::Binary(BinaryOp::Greater),
+                _ => {
+                    let end = start + source[start..].chars().next().map_or(1, char::len_utf8);
+                    return Err(ExpressionError {
+                        message: format!("Unexpected character {}", &source[start..end]),
+                        span: start..end,
+                    });
+                }
+            }
+        };
+        tokens.push((token, start..i));
+    }
+    Ok(tokens)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expression {
+    Bool(bool),
+    Number(f32),
+    Parameter(String),
+    Not(Box<Expression>),
+    Negate(Box<Expression>),
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Number(f32),
+}
+
+impl Expression {
+    fn evaluate(&self, parameters: &ParameterContainer) -> Option<Value> {
+        Some(match self {
+            Expression::Bool(value) => Value::Bool(*value),
+            Expression::Number(value) => Value::Number(*value),
+            Expression::Parameter(name) => match parameters.get(name)? {
+                Parameter::Rule(value) => Value::Bool(*value),
+                Parameter::Weight(value) => Value::Number(*value),
+                Parameter::Index(value) => Value::Number(*value as f32),
+                Parameter::SamplingPoint(_) => return None,
+            },
+            Expression::Not(arg) => match arg.evaluate(parameters)? {
+                Value::Bool(value) => Value::Bool(!value),
+                Value::Number(_) => return None,
+            },
+            Expression::Negate(arg) => match arg.evaluate(parameters)? {
+                Value::Number(value) => Value::Number(-value),
+                Value::Bool(_) => return None,
+            },
+            Expression::Binary { op, lhs, rhs } => {
+                let lhs = lhs.evaluate(parameters)?;
+                // Short-circuit evaluation of the logical operators.
+                match (op, lhs) {
+                    (BinaryOp::And, Value::Bool(false)) => return Some(Value::Bool(false)),
+                    (BinaryOp::Or, Value::Bool(true)) => return Some(Value::Bool(true)),
+                    _ => (),
+                }
+                let rhs = rhs.evaluate(parameters)?;
+                Value::Bool(match (lhs, rhs) {
+                    (Value::Bool(a), Value::Bool(b)) => match op {
+                        BinaryOp::And => a && b,
+                        BinaryOp::Or => a || b,
+                        BinaryOp::Xor => a ^ b,
+                        BinaryOp::Equal => a == b,
+                        BinaryOp::NotEqual => a != b,
+                        _ => return None,
+                    },
+                    (Value::Number(a), Value::Number(b)) => match op {
+                        BinaryOp::Equal => a == b,
+                        BinaryOp::NotEqual => a != b,
+                        BinaryOp::Less => a < b,
+                        BinaryOp::LessOrEqual => a <= b,
+                        BinaryOp::Greater => a > b,
+                        BinaryOp::GreaterOrEqual => a >= b,
+                        _ => return None,
+                    },
+                    _ => return None,
+                })
+            }
+        })
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, std::ops::Range<usize>)],
+    position: usize,
+    source_len: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn span(&self) -> std::ops::Range<usize> {
+        self.tokens
+            .get(self.position)
+            .map(|(_, span)| span.clone())
+            .unwrap_or(self.source_len..self.source_len)
+    }
+
+    fn error(&self, message: &str) -> ExpressionError {
+        ExpressionError {
+            message: match self.peek() {
+                Some(_) => message.to_string(),
+                None => format!("{message}, found end of expression"),
+            },
+            span: self.span(),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[BinaryOp],
+        next: fn(&mut Self) -> Result<Expression, ExpressionError>,
+    ) -> Result<Expression, ExpressionError> {
+        let mut lhs = next(self)?;
+        while let Some(Token::Binary(op)) = self.peek() {
+            let op = *op;
+            if !ops.contains(&op) {
+                break;
+            }
+            self.position += 1;
+            let rhs = next(self)?;
+            lhs = Expression::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> Result<Expression, ExpressionError> {
+        self.binary(&[BinaryOp::Or], Self::xor)
+    }
+
+    fn xor(&mut self) -> Result<Expression, ExpressionError> {
+        self.binary(&[BinaryOp::Xor], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expression, ExpressionError> {
+        self.binary(&[BinaryOp::And], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Expression, ExpressionError> {
+        let lhs = self.unary()?;
+        if let Some(Token::Binary(op)) = self.peek() {
+            let op = *op;
+            if !matches!(op, BinaryOp::And | BinaryOp::Or | BinaryOp::Xor) {
+                self.position += 1;
+                let rhs = self.unary()?;
+                if let Some(Token::Binary(next)) = self.peek() {
+                    if !matches!(next, BinaryOp::And | BinaryOp::Or | BinaryOp::Xor) {
+                        return Err(ExpressionError {
+                            message: format!(
+                                "Comparisons cannot be chained, use parentheses around {}",
+                                op.symbol()
+                            ),
+                            span: self.span(),
+                        });
+                    }
+                }
+                return Ok(Expression::Binary {
+                    op,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                });
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ExpressionError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.position += 1;
+                Ok(Expression::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Minus) => {
+                self.position += 1;
+                Ok(Expression::Negate(Box::new(self.unary()?)))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, ExpressionError> {
+        let expression = match self.peek() {
+            Some(Token::True) => Expression::Bool(true),
+            Some(Token::False) => Expression::Bool(false),
+            Some(Token::Number(number)) => Expression::Number(*number),
+            Some(Token::Identifier(name)) => Expression::Parameter(name.clone()),
+            Some(Token::LeftParen) => {
+                self.position += 1;
+                let expression = self.or()?;
+                if self.peek() != Some(&Token::RightParen) {
+                    return Err(self.error("Expected )"));
+                }
+                expression
+            }
+            _ => return Err(self.error("Expected a parameter, a value or (")),
+        };
+        self.position += 1;
+        Ok(expression)
+    }
+}
+
+fn compile(source: &str) -> Result<Expression, ExpressionError> {
+    let tokens = tokenize(source)?;
+    if tokens.is_empty() {
+        return Err(ExpressionError {
+            message: "Expression is empty".to_string(),
+            span: 0..source.len(),
+        });
+    }
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        source_len: source.len(),
+    };
+    let expression = parser.or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("Expected an operator"));
+    }
+    Ok(expression)
+}
+
+/// Condition expression is a textual form of a transition condition, that combines machine
+/// parameters using comparisons and boolean logic. The expression is compiled once it is created
+/// (or its source is changed), so the evaluation does not parse anything.
+///
+/// # Syntax
+///
+/// - Parameters are referenced by their names: `Run`, `Speed`. Names with spaces or other special
+///   characters must be quoted: `"Is Grounded"`.
+/// - Rule parameters are booleans, Weight and Index parameters are numbers. Literals are `true`,
+///   `false` and numbers (`1`, `0.5`).
+/// - Comparisons: `==`, `!=`, `<`, `<=`, `>`, `>=`. Booleans could be compared only for equality.
+/// - Boolean logic (from lowest to highest priority): `||`, `^` (xor), `&&`, `!`. Parentheses
+///   could be used to change the priority.
+///
+/// An expression, that fails to compile, is always `false`. The same is applied to an expression,
+/// that references a missing parameter or mixes incompatible types (for example `Speed && Run`,
+/// where `Speed` is a Weight parameter).
+///
+/// # Examples
+///
+/// ```rust
+/// use fyrox::animation::machine::{
+///     expression::ConditionExpression, Parameter, ParameterContainer,
+/// };
+///
+/// let mut parameters = ParameterContainer::default();
+/// parameters.add("Speed", Parameter::Weight(2.5));
+/// parameters.add("Is Grounded", Parameter::Rule(true));
+///
+/// let expression = ConditionExpression::new("Speed > 1.5 && \"Is Grounded\"");
+/// assert!(expression.is_valid());
+/// assert!(expression.evaluate(&parameters));
+///
+/// let invalid = ConditionExpression::new("Speed >");
+/// assert!(invalid.error().is_some());
+/// assert!(!invalid.evaluate(&parameters));
+/// ```
+#[derive(Clone, Debug, Reflect)]
+pub struct ConditionExpression {
+    #[reflect(
+        setter = "set_source",
+        description = "Condition expression, for example: Speed > 0.5 && !Jump"
+    )]
+    source: String,
+    #[reflect(hidden)]
+    compiled: Result<Expression, ExpressionError>,
+}
+
+impl Default for ConditionExpression {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl PartialEq for ConditionExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Visit for ConditionExpression {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.source.visit("Source", &mut region)?;
+
+        if region.is_reading() {
+            self.compiled = compile(&self.source);
+        }
+
+        Ok(())
+    }
+}
+
+impl ConditionExpression {
+    /// Creates a new expression and compiles it. Use [`Self::error`] to check whether the
+    /// expression is valid or not.
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        Self {
+            compiled: compile(&source),
+            source,
+        }
+    }
+
+    /// Returns the source text of the expression.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Sets new source text of the expression and recompiles it. Returns the old source text.
+    pub fn set_source(&mut self, source: String) -> String {
+        self.compiled = compile(&source);
+        std::mem::replace(&mut self.source, source)
+    }
+
+    /// Returns a compilation error of the expression, if any.
+    pub fn error(&self) -> Option<&ExpressionError> {
+        self.compiled.as_ref().err()
+    }
+
+    /// Returns `true` if the expression was compiled successfully.
+    pub fn is_valid(&self) -> bool {
+        self.compiled.is_ok()
+    }
+
+    /// Evaluates the expression using the given set of parameters. Returns `false` if the
+    /// expression is invalid, or it references a missing parameter, or the types of its
+    /// operands are incompatible.
+    pub fn evaluate(&self, parameters: &ParameterContainer) -> bool {
+        match self.compiled {
+            Ok(ref expression) => expression.evaluate(parameters) == Some(Value::Bool(true)),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn condition_expression() {
+        let mut parameters = ParameterContainer::default();
+        parameters.add("Run", Parameter::Rule(true));
+        parameters.add("Jump", Parameter::Rule(false));
+        parameters.add("Speed", Parameter::Weight(0.75));
+        parameters.add("Weapon", Parameter::Index(2));
+
+        for (source, expected) in [
+            ("Run", true),
+            ("!Run", false),
+            ("Run && !Jump", true),
+            ("Jump || Speed >= 0.75", true),
+            ("Speed < 0.5 || Weapon == 2 && Jump", false),
+            ("(Speed < 0.5 || Weapon == 2) && !Jump", true),
+            ("Run ^ Jump", true),
+            ("Run == true", true),
+            ("-Speed > -1", true),
+            // Type mismatches and missing parameters are false.
+            ("Speed", false),
+            ("Speed && Run", false),
+            ("Missing || Run", false),
+        ] {
+            let expression = ConditionExpression::new(source);
+            assert!(expression.is_valid(), "{source}");
+            assert_eq!(expression.evaluate(&parameters), expected, "{source}");
+        }
+
+        for (source, span) in [
+            ("", 0..0),
+            ("Run &&", 6..6),
+            ("Run Jump", 4..8),
+            ("(Run", 4..4),
+            ("Run # Jump", 4..5),
+            ("1 < Speed < 2", 10..11),
+            ("\"Run", 0..4),
+        ] {
+            let expression = ConditionExpression::new(source);
+            assert_eq!(
+                expression.error().map(|e| e.span.clone()),
+                Some(span),
+                "{source}"
+            );
+            assert!(!expression.evaluate(&parameters));
+        }
+
+        let mut expression = ConditionExpression::default();
+        assert!(!expression.is_valid());
+        expression.set_source("\"Run\" && Speed > 0.5".to_string());
+        assert!(expression.evaluate(&parameters));
+    }
+}
@@ -73,6 +73,9 @@ pub struct MachineLayer {
 
     mask: LayerMask,
 
+    #[visit(optional)]
+    blend_mode: LayerBlendMode,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode>,
 
@@ -104,6 +107,20 @@ pub struct MachineLayer {
     debug: bool,
 }
 
+/// Defines how the pose of a layer is combined with the poses of previous layers of a state machine.
+#[derive(Default, Debug, Visit, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum LayerBlendMode {
+    /// The pose of the layer is blended with the poses of previous layers using the weight of the layer. With the weight
+    /// of 1.0 the layer fully overrides the nodes it animates.
+    #[default]
+    Override,
+
+    /// The pose of the layer is treated as a difference (see [`crate::animation::Animation::make_additive`]) and is added
+    /// on top of the poses of previous layers, scaled by the weight of the layer. The layer does not affect the nodes,
+    /// that are not animated by previous layers.
+    Additive,
+}
+
 impl NameProvider for MachineLayer {
     fn name(&self) -> &str {
         &self.name
@@ -165,6 +182,7 @@ impl MachineLayer {
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
+            blend_mode: Default::default(),
         }
     }
 
@@ -530,6 +548,18 @@ impl MachineLayer {
         &self.mask
     }
 
+    /// Sets new blend mode of the layer. See docs of [`LayerBlendMode`] for more info.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: LayerBlendMode) -> LayerBlendMode {
+        std::mem::replace(&mut self.blend_mode, blend_mode)
+    }
+
+    /// Returns current blend mode of the layer.
+    #[inline]
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
     /// Returns final pose of the layer.
     #[inline]
     pub fn pose(&self) -> &AnimationPose {
@@ -15,7 +15,7 @@ use crate::{
 };
 
 pub use event::Event;
-pub use layer::MachineLayer;
+pub use layer::{LayerBlendMode, MachineLayer};
 pub use mask::LayerMask;
 pub use node::{
     blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
@@ -27,6 +27,7 @@ pub use state::State;
 pub use transition::Transition;
 
 pub mod event;
+pub mod expression;
 pub mod layer;
 pub mod mask;
 pub mod node;
@@ -76,6 +77,10 @@ pub mod transition;
 /// ability to have running character that could aim or melee attack, or crouching and aiming, and so on with any combination.
 /// Both layers use the same set of parameters, so a change in a parameter will affect all layers that use it.
 ///
+/// Layers are combined in order, using their weights and blend modes (see [`LayerBlendMode`]). An additive layer adds its
+/// pose on top of the poses of previous layers, which allows you to add small motions (breathing, recoil, leaning, etc.)
+/// to any other animation.
+///
 /// # Examples
 ///
 /// Let have a quick look at simple state machine graph with a single layer:
@@ -295,9 +300,13 @@ impl Machine {
 
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
+            let blend_mode = layer.blend_mode();
             let pose = layer.evaluate_pose(animations, &self.parameters, dt);
 
-            self.final_pose.blend_with(pose, weight);
+            match blend_mode {
+                LayerBlendMode::Override => self.final_pose.blend_with(pose, weight),
+                LayerBlendMode::Additive => self.final_pose.add_with(pose, weight),
+            }
         }
 
         &self.final_pose
@@ -2,7 +2,7 @@
 
 use crate::{
     animation::{
-        machine::{Parameter, ParameterContainer, State},
+        machine::{expression::ConditionExpression, Parameter, ParameterContainer, State},
         Animation, AnimationContainer,
     },
     core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
@@ -123,6 +123,18 @@ macro_rules! define_two_args_node {
                 func(&mut [&mut self.lhs, &mut self.rhs])
             }
 
+            fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+                func(&self.lhs);
+                func(&self.rhs);
+                true
+            }
+
+            fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+                func(&mut self.lhs);
+                func(&mut self.rhs);
+                true
+            }
+
             fn field(&self, name: &str, func: &mut dyn FnMut(Option<&dyn Reflect>)) {
                 func(match name {
                     "Lhs" => Some(&self.lhs),
@@ -245,6 +257,16 @@ impl Reflect for NotNode {
         func(&mut [&mut self.lhs])
     }
 
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        func(&self.lhs);
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        func(&mut self.lhs);
+        true
+    }
+
     fn field(&self, name: &str, func: &mut dyn FnMut(Option<&dyn Reflect>)) {
         func(match name {
             "Lhs" => Some(&self.lhs),
@@ -285,6 +307,13 @@ impl Reflect for NotNode {
 ///
 /// assert_eq!(transition_logic.calculate_value(&parameters, &AnimationContainer::default()), true);
 /// ```
+///
+/// The same condition could be written as an expression (see [`ConditionExpression`]):
+///
+/// ```rust
+/// # use fyrox::animation::machine::{expression::ConditionExpression, transition::LogicNode};
+/// let transition_logic = LogicNode::Expression(ConditionExpression::new("!Run && Jump"));
+/// ```
 #[derive(Debug, Visit, Clone, Reflect, PartialEq, AsRefStr, EnumString, EnumVariantNames)]
 pub enum LogicNode {
     /// Fetches a value of `Rule` parameter and returns its value. `false` if the parameter is not found.
@@ -299,6 +328,9 @@ pub enum LogicNode {
     Not(NotNode),
     /// Returns `true` if the animation has ended, `false` - otherwise.
     IsAnimationEnded(Handle<Animation>),
+    /// Evaluates a textual condition expression, that could combine any parameters with comparisons
+    /// and boolean logic. See [`ConditionExpression`] docs for more info.
+    Expression(ConditionExpression),
 }
 
 impl Default for LogicNode {
@@ -341,6 +373,7 @@ impl LogicNode {
             LogicNode::IsAnimationEnded(animation) => animations
                 .try_get(*animation)
                 .map_or(true, |a| a.has_ended()),
+            LogicNode::Expression(expression) => expression.evaluate(parameters),
         }
     }
 }
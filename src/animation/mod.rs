@@ -107,6 +107,14 @@ pub mod value;
 /// After that all you need to do is to fetch animation events one-by-one and emit respective sounds. See [`AnimationSignal`] docs
 /// for more info and examples.
 ///
+/// # Additive animations
+///
+/// Additive animation stores a difference between some animation and a reference pose, instead of absolute values. Such animation
+/// is meant to be added on top of other animations, for example a "breathing" or "recoil" animation could be added on top of any
+/// locomotion animation. Any animation could be converted into an additive one using [`Animation::make_additive`]. Additive
+/// animations are usually played on an animation layer with [`machine::layer::LayerBlendMode::Additive`] blend mode, applying such
+/// animation directly will make little sense.
+///
 /// # Examples
 ///
 /// Usually, animations are created from the editor or some external tool and then imported in the engine. Before trying the example
@@ -315,6 +323,43 @@ impl Animation {
         }
     }
 
+    /// Converts the animation into an additive animation, that stores a difference between the animation and a reference pose.
+    /// The reference pose is a pose of the `reference` animation at the given time (usually it is the first frame of the
+    /// same animation or of some "idle" animation). Tracks, that do not have a respective track (with the same target and
+    /// binding) in the reference animation, use their own values at the given time as reference.
+    ///
+    /// The difference is calculated for every parametric curve separately, this means that rotation differences are the
+    /// differences of Euler angles. It is precise enough for small angles, which is usually the case for additive
+    /// animations.
+    pub fn make_additive(&mut self, reference: &Animation, reference_time: f32) {
+        for track in self.tracks.iter_mut() {
+            let reference_values = reference
+                .tracks
+                .iter()
+                .find(|reference_track| {
+                    reference_track.target() == track.target()
+                        && reference_track.binding() == track.binding()
+                })
+                .unwrap_or(&*track)
+                .data_container()
+                .curves_ref()
+                .iter()
+                .map(|curve| curve.value_at(reference_time))
+                .collect::<Vec<_>>();
+
+            for (curve, reference_value) in track
+                .data_container_mut()
+                .curves_mut()
+                .iter_mut()
+                .zip(reference_values)
+            {
+                for value in curve.keys_values() {
+                    *value -= reference_value;
+                }
+            }
+        }
+    }
+
     /// Returns a reference to tracks container.
     pub fn tracks(&self) -> &[Track] {
         &self.tracks
@@ -992,3 +1037,60 @@ impl IndexMut<Handle<Animation>> for AnimationContainer {
         &mut self.pool[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            track::Track,
+            value::{TrackValue, ValueBinding},
+            Animation, AnimationPose,
+        },
+        core::{
+            algebra::Vector3,
+            curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+    };
+
+    fn position_animation(node: Handle<crate::scene::node::Node>, x: [f32; 2]) -> Animation {
+        let mut container = TrackDataContainer::new(TrackValueKind::Vector3);
+        container.curves_mut()[0] = Curve::from(vec![
+            CurveKey::new(0.0, x[0], CurveKeyKind::Linear),
+            CurveKey::new(1.0, x[1], CurveKeyKind::Linear),
+        ]);
+        let mut track = Track::new(container, ValueBinding::Position);
+        track.set_target(node);
+        let mut animation = Animation::default();
+        animation.add_track(track);
+        animation.fit_length_to_content();
+        animation
+    }
+
+    fn position(pose: &AnimationPose, node: Handle<crate::scene::node::Node>) -> Vector3<f32> {
+        match pose.poses()[&node].values.values[0].value {
+            TrackValue::Vector3(position) => position,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_additive_animation() {
+        let node = Handle::new(1, 1);
+
+        let mut additive = position_animation(node, [2.0, 3.0]);
+        let reference = additive.clone();
+        additive.make_additive(&reference, 0.0);
+        additive.set_time_position(1.0);
+        additive.tick(0.0);
+
+        let mut base = position_animation(node, [10.0, 10.0]);
+        base.tick(0.0);
+
+        let mut pose = AnimationPose::default();
+        base.pose().clone_into(&mut pose);
+        pose.add_with(additive.pose(), 0.5);
+        assert_eq!(position(&pose, node), Vector3::new(10.5, 0.0, 0.0));
+    }
+}
@@ -37,6 +37,11 @@ impl NodePose {
     pub fn blend_with(&mut self, other: &NodePose, weight: f32) {
         self.values.blend_with(&other.values, weight)
     }
+
+    /// Adds an additive pose to the current pose. See [`super::value::TrackValue::add_with`] docs for more info.
+    pub fn add_with(&mut self, other: &NodePose, weight: f32) {
+        self.values.add_with(&other.values, weight)
+    }
 }
 
 /// Animations pose is a set of node poses. See [`NodePose`] docs for more info.
@@ -83,6 +88,17 @@ impl AnimationPose {
             .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
     }
 
+    /// Adds an additive animation pose (see [`crate::animation::Animation::make_additive`]) on top of the current
+    /// pose using a weight coefficient. Node poses, that are missing in the current pose, are ignored, because there's
+    /// nothing to add the difference to. Root motion of the current pose remains unchanged.
+    pub fn add_with(&mut self, other: &AnimationPose, weight: f32) {
+        for (handle, other_pose) in other.poses.iter() {
+            if let Some(current_pose) = self.poses.get_mut(handle) {
+                current_pose.add_with(other_pose, weight);
+            }
+        }
+    }
+
     fn add_node_pose(&mut self, local_pose: NodePose) {
         self.poses.insert(local_pose.node, local_pose);
     }
@@ -156,6 +156,22 @@ impl TrackValue {
         }
     }
 
+    /// Adds a weighted difference (produced by an additive animation) to the current value. Rotations are combined by
+    /// multiplication: the current rotation is rotated by a fraction (defined by the weight) of the other rotation. Adding
+    /// is possible only if the types are the same.
+    pub fn add_with(&mut self, other: &Self, weight: f32) {
+        match (self, other) {
+            (Self::Real(a), Self::Real(b)) => *a += *b * weight,
+            (Self::Vector2(a), Self::Vector2(b)) => *a += b.scale(weight),
+            (Self::Vector3(a), Self::Vector3(b)) => *a += b.scale(weight),
+            (Self::Vector4(a), Self::Vector4(b)) => *a += b.scale(weight),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b)) => {
+                *a *= UnitQuaternion::identity().nlerp(b, weight)
+            }
+            _ => (),
+        }
+    }
+
     /// Tries to perform a numeric type casting of the current value to some other and returns a boxed value, that can
     /// be used to set the value using reflection.
     pub fn numeric_type_cast(&self, value_type: ValueType) -> Option<Box<dyn Reflect>> {
@@ -308,6 +324,13 @@ impl BoundValue {
         assert_eq!(self.binding, other.binding);
         self.value.blend_with(&other.value, weight);
     }
+
+    /// Adds a weighted value of an additive animation to the current value. See [`TrackValue::add_with`] for more
+    /// info.
+    pub fn add_with(&mut self, other: &Self, weight: f32) {
+        assert_eq!(self.binding, other.binding);
+        self.value.add_with(&other.value, weight);
+    }
 }
 
 /// A collection of values that are bounds to some properties.
@@ -328,6 +351,16 @@ impl BoundValueCollection {
         }
     }
 
+    /// Tries to add each value of the other collection to a respective (by binding) value in the current collection.
+    /// See [`TrackValue::add_with`] docs for more info.
+    pub fn add_with(&mut self, other: &Self, weight: f32) {
+        for value in self.values.iter_mut() {
+            if let Some(other_value) = other.values.iter().find(|v| v.binding == value.binding) {
+                value.add_with(other_value, weight);
+            }
+        }
+    }
+
     /// Tries to set each value from the collection to the respective property (by binding) of the given scene node.
     pub fn apply(&self, node_ref: &mut Node) {
         for bound_value in self.values.iter() {
@@ -3,6 +3,7 @@
 use crate::{
     asset::manager::ResourceManager,
     core::{
+        futures::executor::block_on,
         instant::Instant,
         log::{Log, MessageKind},
     },
@@ -12,12 +13,14 @@ use crate::{
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
     plugin::PluginConstructor,
+    resource::boot_config::BootConfig,
     utils::translate_event,
-    window::WindowAttributes,
+    window::Fullscreen,
 };
 use clap::Parser;
 use std::{
     ops::{Deref, DerefMut},
+    path::Path,
     sync::Arc,
 };
 
@@ -26,6 +29,10 @@ use std::{
 struct Args {
     #[clap(short, long, default_value = "")]
     override_scene: String,
+
+    /// Overrides a setting of the boot configuration, for example `--boot window.width=1920`.
+    #[clap(long = "boot", value_name = "KEY=VALUE")]
+    boot_overrides: Vec<String>,
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -34,6 +41,7 @@ pub struct Executor {
     engine: Engine,
     desired_update_rate: f32,
     headless: bool,
+    boot_config: Option<BootConfig>,
 }
 
 impl Deref for Executor {
@@ -79,25 +87,51 @@ impl Executor {
             engine,
             desired_update_rate: Self::DEFAULT_UPDATE_RATE,
             headless: false,
+            boot_config: None,
         }
     }
 
-    /// Creates new game executor using default window and with vsync turned on. For more flexible
-    /// way to create an executor see [`Executor::from_params`].
+    /// Creates new game executor using default window and with vsync turned on (see
+    /// [`BootConfig::default`]). For more flexible way to create an executor see
+    /// [`Executor::from_params`] and [`Executor::from_boot_config`].
     pub fn new() -> Self {
-        let mut window_attributes = WindowAttributes::default();
-        window_attributes.resizable = true;
-        window_attributes.title = "Fyrox Game".to_string();
-
         Self::from_params(
             EventLoop::new().unwrap(),
-            GraphicsContextParams {
-                window_attributes,
-                vsync: true,
-            },
+            BootConfig::default().graphics_context_params(),
         )
     }
 
+    /// Creates new game executor, that is configured by the boot configuration at the given path.
+    /// The configuration is loaded through the resource manager, if it cannot be loaded, the
+    /// default one is used. Environment variables and command line arguments override the settings
+    /// of the configuration when the executor is started. See [`BootConfig`] docs for more info.
+    pub fn from_boot_config(event_loop: EventLoop<()>, path: impl AsRef<Path>) -> Self {
+        let mut executor = Self::from_params(event_loop, Default::default());
+        let config = match block_on(
+            executor
+                .resource_manager
+                .request::<BootConfig>(path.as_ref()),
+        ) {
+            Ok(resource) => (*resource.data_ref()).clone(),
+            Err(error) => {
+                Log::err(format!(
+                    "Unable to load boot configuration {}, the default one is used. Reason: {:?}",
+                    path.as_ref().display(),
+                    error
+                ));
+                BootConfig::default()
+            }
+        };
+        executor.boot_config = Some(config);
+        executor
+    }
+
+    /// Returns the boot configuration of the executor, if it was created using
+    /// [`Executor::from_boot_config`]. Overrides are applied to it when the executor is started.
+    pub fn boot_config(&self) -> Option<&BootConfig> {
+        self.boot_config.as_ref()
+    }
+
     /// Defines whether the executor should initialize graphics context or not. Headless mode could
     /// be useful for game servers, where you don't need to have a window, renderer, sound, etc.
     /// By default, headless mode is off.
@@ -129,13 +163,36 @@ impl Executor {
     }
 
     /// Runs the executor - starts your game.
-    pub fn run(self) {
+    pub fn run(mut self) {
+        let args = Args::parse();
+
+        let mut fullscreen = false;
+        let mut quality = None;
+        if let Some(mut config) = self.boot_config.take() {
+            let errors = config
+                .apply_env_overrides(std::env::vars())
+                .into_iter()
+                .chain(config.apply_overrides(&args.boot_overrides));
+            for error in errors {
+                Log::err(format!("Invalid boot setting override: {error}"));
+            }
+
+            self.engine.graphics_context =
+                GraphicsContext::Uninitialized(config.graphics_context_params());
+            self.set_desired_update_rate(config.update_rate);
+            self.set_headless(config.headless);
+            config.mount_assets(&self.engine.resource_manager);
+            fullscreen = config.window.fullscreen;
+            quality = config.renderer.quality.map(|preset| preset.settings());
+            self.engine.services.register(config);
+        } else if !args.boot_overrides.is_empty() {
+            Log::warn("Boot setting overrides are ignored, because there's no boot configuration.");
+        }
+
         let mut engine = self.engine;
         let event_loop = self.event_loop;
         let headless = self.headless;
 
-        let args = Args::parse();
-
         engine.enable_plugins(
             if args.override_scene.is_empty() {
                 None
@@ -175,6 +232,16 @@ impl Executor {
                         .initialize_graphics_context(window_target)
                         .expect("Unable to initialize graphics context!");
 
+                    if let GraphicsContext::Initialized(ref mut ctx) = engine.graphics_context {
+                        if fullscreen {
+                            ctx.window
+                                .set_fullscreen(Some(Fullscreen::Borderless(None)));
+                        }
+                        if let Some(quality) = quality.as_ref() {
+                            Log::verify(ctx.renderer.set_quality_settings(quality));
+                        }
+                    }
+
                     engine.handle_graphics_context_created_by_plugins(
                         fixed_time_step,
                         window_target,
@@ -5,43 +5,67 @@
 
 pub mod error;
 pub mod executor;
+pub mod time;
 
 use crate::{
     asset::{
         event::ResourceEvent,
         manager::{ResourceManager, ResourceWaitContext},
+        saver::resource_savers,
+        state::LoadError,
         ResourceStateRef,
     },
     core::{
-        algebra::Vector2, futures::executor::block_on, instant, log::Log, pool::Handle,
+        algebra::Vector2, futures::executor::block_on, instant, log::Log, math::Rect, pool::Handle,
         reflect::Reflect, variable::try_inherit_properties, visitor::VisitError,
     },
-    engine::error::EngineError,
+    engine::{error::EngineError, time::TimeControl},
     event::Event,
     gui::UserInterface,
+    localization::Localization,
     material::{
         loader::MaterialLoader,
-        shader::{loader::ShaderLoader, Shader, ShaderResource, ShaderResourceExtension},
+        shader::{
+            loader::ShaderLoader, Shader, ShaderResource, ShaderResourceExtension,
+            EMBEDDED_STANDARD_SHADERS_DIR, STANDARD_SHADER_NAMES, STANDARD_SHADER_SOURCES,
+        },
         Material,
     },
     plugin::{Plugin, PluginConstructor, PluginContext, PluginRegistrationContext},
-    renderer::{framework::error::FrameworkError, framework::state::GlKind, Renderer},
+    renderer::{
+        capture::FrameCapture, framework::error::FrameworkError, framework::state::GlKind,
+        Renderer, ResourceUploadBudget,
+    },
     resource::{
-        curve::{loader::CurveLoader, CurveResourceState},
+        behavior_tree::{loader::BehaviorTreeLoader, BehaviorTreeAsset},
+        boot_config::{loader::BootConfigLoader, BootConfig},
+        curve::{loader::CurveLoader, saver::CurveSaver, CurveResourceState},
+        dialogue::{loader::DialogueLoader, Dialogue},
         model::{loader::ModelLoader, Model, ModelResource, NodeMapping},
-        texture::{loader::TextureLoader, Texture, TextureKind},
+        post_effects::{loader::PostEffectsLoader, PostEffects},
+        sound_bank::{loader::SoundBankLoader, SoundBank},
+        spritesheet::{loader::SpriteSheetLoader, SpriteSheet},
+        string_table::{loader::StringTableLoader, StringTable},
+        texture::{
+            importer::TextureImporter, loader::TextureLoader, Texture, TextureKind, TextureResource,
+        },
     },
     scene::{
         base::NodeScriptMessage,
         camera::SkyBoxKind,
-        graph::{GraphUpdateSwitches, NodePool},
+        graph::{
+            physics::{CollisionEvent, CollisionEventFilter},
+            Graph, GraphUpdateSwitches, NodePool,
+        },
         node::{constructor::NodeConstructorContainer, Node},
+        prefab_pool::PrefabPool,
         sound::SoundEngine,
+        warmup::ResourceWarmUpReport,
         Scene, SceneContainer, SceneLoader,
     },
     script::{
-        constructor::ScriptConstructorContainer, RoutingStrategy, Script, ScriptContext,
-        ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
+        constructor::ScriptConstructorContainer, service::ScriptServices, RoutingStrategy, Script,
+        ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
         ScriptMessageSender,
     },
     window::{Window, WindowBuilder},
@@ -83,7 +107,7 @@ use std::{
     time::Duration,
 };
 use winit::{
-    dpi::{Position, Size},
+    dpi::{PhysicalPosition, PhysicalSize, Position, Size},
     event_loop::EventLoopWindowTarget,
     window::WindowAttributes,
 };
@@ -196,6 +220,20 @@ impl GraphicsContext {
             panic!("Graphics context is uninitialized!")
         }
     }
+
+    /// Requests the next rendered frame (everything that is shown in the main window). Returns a
+    /// texture in pending state, that will be loaded when the frame is rendered, or a texture in
+    /// error state if the graphics context is not initialized. See [`FrameCapture`] docs for more
+    /// info.
+    pub fn capture_frame(&self) -> TextureResource {
+        match self {
+            GraphicsContext::Initialized(ctx) => ctx.renderer.frame_capture().capture_frame(),
+            GraphicsContext::Uninitialized(_) => TextureResource::new_load_error(
+                Default::default(),
+                LoadError::new("Unable to capture a frame, graphics context is uninitialized!"),
+            ),
+        }
+    }
 }
 
 struct SceneLoadingOptions {
@@ -284,6 +322,7 @@ pub struct AsyncSceneLoader {
     receiver: Receiver<SceneLoadingResult>,
     sender: Sender<SceneLoadingResult>,
     loading_scenes: FxHashMap<PathBuf, LoadingScene>,
+    warm_up_reports: FxHashMap<PathBuf, ResourceWarmUpReport>,
 }
 
 struct LoadingScene {
@@ -294,7 +333,7 @@ struct LoadingScene {
 
 struct SceneLoadingResult {
     path: PathBuf,
-    result: Result<(Scene, Vec<u8>), VisitError>,
+    result: Result<(Scene, Vec<u8>, ResourceWarmUpReport), VisitError>,
 }
 
 impl AsyncSceneLoader {
@@ -309,6 +348,7 @@ impl AsyncSceneLoader {
             receiver,
             sender,
             loading_scenes: Default::default(),
+            warm_up_reports: Default::default(),
         }
     }
 
@@ -346,10 +386,10 @@ impl AsyncSceneLoader {
                 .await
                 {
                     Ok((loader, data)) => {
-                        let scene = loader.finish(&resource_manager).await;
+                        let (scene, report) = loader.finish_with_report(&resource_manager).await;
                         Log::verify(sender.send(SceneLoadingResult {
                             path,
-                            result: Ok((scene, data)),
+                            result: Ok((scene, data, report)),
                         }));
                     }
                     Err(e) => {
@@ -392,6 +432,18 @@ impl AsyncSceneLoader {
     pub fn request_raw<P: AsRef<Path>>(&mut self, path: P) {
         self.request_with_options(path, SceneLoadingOptions { derived: false });
     }
+
+    /// Returns a resource warm-up report of the most recent successful loading of a scene at the
+    /// given path. The report is available in [`crate::plugin::Plugin::on_scene_loaded`] and after
+    /// it. See [`ResourceWarmUpReport`] docs for more info.
+    pub fn warm_up_report(&self, path: &Path) -> Option<&ResourceWarmUpReport> {
+        self.warm_up_reports.get(path)
+    }
+
+    /// Removes all stored resource warm-up reports.
+    pub fn clear_warm_up_reports(&mut self) {
+        self.warm_up_reports.clear();
+    }
 }
 
 /// See module docs.
@@ -421,6 +473,10 @@ pub struct Engine {
     #[allow(dead_code)] // Keep engine instance alive.
     sound_engine: SoundEngine,
 
+    // Shared with the renderer, it lives here because the renderer is re-created when the graphics
+    // context is re-initialized.
+    frame_capture: FrameCapture,
+
     // A set of plugin constructors.
     plugin_constructors: Vec<Box<dyn PluginConstructor>>,
 
@@ -432,17 +488,36 @@ pub struct Engine {
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    // Text cursor area of the user interface, that was passed to the IME of the window last time.
+    ime_cursor_area: Option<Rect<f32>>,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
 
     /// Script processor is used to run script methods in a strict order.
     pub script_processor: ScriptProcessor,
+
+    /// A container of services, that are available to scripts. See [`ScriptServices`] docs for
+    /// more info.
+    pub services: ScriptServices,
+
+    /// Localization service, that keeps the current locale and string tables. Locale settings of
+    /// the resource manager follow the service. See [`Localization`] docs for more info.
+    pub localization: Localization,
+
+    /// Time channels (gameplay, UI, audio, etc.), that could be paused and scaled independently. See
+    /// [`TimeControl`] docs for more info.
+    pub time: TimeControl,
+
+    /// Pools of prefab instances of every scene. See [`PrefabPool`] docs for more info.
+    pub prefab_pool: PrefabPool,
 }
 
 /// Performs dispatch of script messages.
 pub struct ScriptMessageDispatcher {
     type_groups: FxHashMap<TypeId, FxHashSet<Handle<Node>>>,
+    collision_filters: FxHashMap<Handle<Node>, CollisionEventFilter>,
     message_receiver: Receiver<ScriptMessage>,
 }
 
@@ -450,6 +525,7 @@ impl ScriptMessageDispatcher {
     fn new(message_receiver: Receiver<ScriptMessage>) -> Self {
         Self {
             type_groups: Default::default(),
+            collision_filters: Default::default(),
             message_receiver,
         }
     }
@@ -465,11 +541,74 @@ impl ScriptMessageDispatcher {
             .or_insert_with(|| FxHashSet::from_iter([receiver]));
     }
 
+    /// Subscribes a node to receive [`CollisionEvent`] messages, that pass the given filter. A node
+    /// receives events of every collider in its hierarchy (the node itself and all its descendants),
+    /// so a script of a rigid body receives events of all its colliders. Every event is delivered
+    /// to a node once per frame, even if both colliders belong to the node's hierarchy. Generation
+    /// of collision events is enabled in the physics worlds of the scene on the first subscription
+    /// (see [`crate::scene::graph::physics::PhysicsWorld::set_collision_events_enabled`]).
+    ///
+    /// ```rust
+    /// # use fyrox::{
+    /// #     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*, TypeUuidProvider},
+    /// #     impl_component_provider,
+    /// #     scene::graph::physics::{CollisionEvent, CollisionEventFilter, CollisionEventKind},
+    /// #     script::{ScriptContext, ScriptMessageContext, ScriptMessagePayload, ScriptTrait},
+    /// # };
+    /// #
+    /// #[derive(Clone, Debug, Default, Reflect, Visit)]
+    /// struct Coin;
+    ///
+    /// # impl_component_provider!(Coin);
+    /// # impl TypeUuidProvider for Coin {
+    /// #     fn type_uuid() -> Uuid {
+    /// #         Default::default()
+    /// #     }
+    /// # }
+    /// impl ScriptTrait for Coin {
+    ///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+    ///         ctx.message_dispatcher.subscribe_to_collision_events(
+    ///             ctx.handle,
+    ///             CollisionEventFilter {
+    ///                 kind: Some(CollisionEventKind::Started),
+    ///                 tag: Some("Player".to_string()),
+    ///                 ..Default::default()
+    ///             },
+    ///         );
+    ///     }
+    ///
+    ///     fn on_message(
+    ///         &mut self,
+    ///         message: &mut dyn ScriptMessagePayload,
+    ///         ctx: &mut ScriptMessageContext,
+    ///     ) {
+    ///         if let Some(_event) = message.downcast_ref::<CollisionEvent>() {
+    ///             ctx.scene.graph.remove_node_deferred(ctx.handle);
+    ///         }
+    ///     }
+    ///
+    ///     fn id(&self) -> Uuid {
+    ///         Self::type_uuid()
+    ///     }
+    /// }
+    /// ```
+    pub fn subscribe_to_collision_events(
+        &mut self,
+        receiver: Handle<Node>,
+        filter: CollisionEventFilter,
+    ) {
+        self.subscribe_to::<CollisionEvent>(receiver);
+        self.collision_filters.insert(receiver, filter);
+    }
+
     /// Unsubscribes a node from receiving any messages of the given type `T`.
     pub fn unsubscribe_from<T: 'static>(&mut self, receiver: Handle<Node>) {
         if let Some(group) = self.type_groups.get_mut(&TypeId::of::<T>()) {
             group.remove(&receiver);
         }
+        if TypeId::of::<T>() == TypeId::of::<CollisionEvent>() {
+            self.collision_filters.remove(&receiver);
+        }
     }
 
     /// Unsubscribes a node from receiving any messages.
@@ -477,6 +616,71 @@ impl ScriptMessageDispatcher {
         for group in self.type_groups.values_mut() {
             group.remove(&receiver);
         }
+        self.collision_filters.remove(&receiver);
+    }
+
+    /// Sends collision events of the last physics step to every subscribed node, that has one of the
+    /// colliders of an event in its hierarchy.
+    fn send_collision_events(&self, graph: &mut Graph, message_sender: &ScriptMessageSender) {
+        if !self
+            .type_groups
+            .get(&TypeId::of::<CollisionEvent>())
+            .is_some_and(|receivers| !receivers.is_empty())
+        {
+            return;
+        }
+
+        // The events aren't generated until somebody needs them.
+        graph.physics.set_collision_events_enabled(true);
+        graph.physics2d.set_collision_events_enabled(true);
+
+        self.deliver_collision_events(
+            graph
+                .physics
+                .collision_events()
+                .iter()
+                .chain(graph.physics2d.collision_events()),
+            graph,
+            message_sender,
+        );
+    }
+
+    fn deliver_collision_events<'a>(
+        &self,
+        events: impl Iterator<Item = &'a CollisionEvent>,
+        graph: &Graph,
+        message_sender: &ScriptMessageSender,
+    ) {
+        let Some(receivers) = self.type_groups.get(&TypeId::of::<CollisionEvent>()) else {
+            return;
+        };
+
+        // Pairs of a receiver and its collider of the event.
+        let mut targets = Vec::<(Handle<Node>, Handle<Node>)>::new();
+        for event in events {
+            targets.clear();
+            for collider in [event.collider1, event.collider2] {
+                let mut node = collider;
+                while let Some(node_ref) = graph.try_get(node) {
+                    if receivers.contains(&node)
+                        && !targets.iter().any(|(target, _)| *target == node)
+                    {
+                        targets.push((node, collider));
+                    }
+                    node = node_ref.parent();
+                }
+            }
+
+            for &(target, collider) in targets.iter() {
+                let passes = self
+                    .collision_filters
+                    .get(&target)
+                    .map_or(true, |filter| filter.matches(event, collider, graph));
+                if passes {
+                    message_sender.send_to_target(target, *event);
+                }
+            }
+        }
     }
 
     fn dispatch_messages(
@@ -484,6 +688,7 @@ impl ScriptMessageDispatcher {
         scene: &mut Scene,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        services: &ScriptServices,
         dt: f32,
         elapsed_time: f32,
         message_sender: &ScriptMessageSender,
@@ -502,6 +707,7 @@ impl ScriptMessageDispatcher {
                                 scene,
                                 resource_manager,
                                 message_sender,
+                                services,
                             };
 
                             process_node_message(&mut context, &mut |s, ctx| {
@@ -523,6 +729,7 @@ impl ScriptMessageDispatcher {
                                     scene,
                                     resource_manager,
                                     message_sender,
+                                    services,
                                 };
 
                                 if receivers.contains(&node) {
@@ -544,6 +751,7 @@ impl ScriptMessageDispatcher {
                                     scene,
                                     resource_manager,
                                     message_sender,
+                                    services,
                                 };
 
                                 if receivers.contains(&node) {
@@ -564,6 +772,7 @@ impl ScriptMessageDispatcher {
                                 scene,
                                 resource_manager,
                                 message_sender,
+                                services,
                             };
 
                             process_node_message(&mut context, &mut |s, ctx| {
@@ -623,6 +832,8 @@ impl ScriptProcessor {
         scenes: &mut SceneContainer,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        services: &ScriptServices,
+        time: &TimeControl,
         dt: f32,
         elapsed_time: f32,
     ) {
@@ -644,6 +855,10 @@ impl ScriptProcessor {
                 continue 'scene_loop;
             }
 
+            // Scripts use the time step of their scene, so they're updated with zero time step
+            // while the scene is paused.
+            let dt = time.scene_time(scripted_scene.handle).delta(dt);
+
             // Fill in initial handles to nodes to initialize, start, update.
             let mut update_queue = VecDeque::new();
             let mut start_queue = VecDeque::new();
@@ -667,6 +882,10 @@ impl ScriptProcessor {
                 }
             }
 
+            scripted_scene
+                .message_dispatcher
+                .send_collision_events(&mut scene.graph, &scripted_scene.message_sender);
+
             // We'll gather all scripts queued for destruction and destroy them all at once at the
             // end of the frame.
             let mut destruction_queue = VecDeque::new();
@@ -680,9 +899,11 @@ impl ScriptProcessor {
                     plugins,
                     handle: Default::default(),
                     scene,
+                    scene_handle: scripted_scene.handle,
                     resource_manager,
                     message_sender: &scripted_scene.message_sender,
                     message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    services,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -695,6 +916,10 @@ impl ScriptProcessor {
 
                                 process_node(&mut context, &mut |script, context| {
                                     if !script.initialized {
+                                        context.services.check_requirements(
+                                            context.scene.graph[context.handle].name(),
+                                            &script.required_services(),
+                                        );
                                         script.on_init(context);
                                         script.initialized = true;
                                     }
@@ -758,6 +983,7 @@ impl ScriptProcessor {
                         scene,
                         plugins,
                         resource_manager,
+                        services,
                         dt,
                         elapsed_time,
                         &scripted_scene.message_sender,
@@ -780,6 +1006,7 @@ impl ScriptProcessor {
                 scene,
                 node_handle: Default::default(),
                 message_sender: &scripted_scene.message_sender,
+                services,
             };
             while let Some((handle, mut script)) = destruction_queue.pop_front() {
                 context.node_handle = handle;
@@ -803,6 +1030,7 @@ impl ScriptProcessor {
                     scene: &mut detached_scene,
                     node_handle: Default::default(),
                     message_sender: &scripted_scene.message_sender,
+                    services,
                 };
 
                 // Destroy every script instance from nodes that were still alive.
@@ -911,6 +1139,14 @@ pub struct GraphicsContextParams {
     /// Whether to use vertical synchronization or not. V-sync will force your game to render frames with the synchronization
     /// rate of your monitor (which is ~60 FPS). Keep in mind that vertical synchronization might not be available on your OS.
     pub vsync: bool,
+
+    /// Budget for uploading of loaded textures to GPU, see [`ResourceUploadBudget`] docs for more
+    /// info.
+    pub texture_upload_budget: ResourceUploadBudget,
+
+    /// Budget for compilation of loaded shaders, `None` means that there's no limit. See
+    /// [`Renderer::set_shader_upload_budget`] docs for more info.
+    pub shader_upload_budget: Option<ResourceUploadBudget>,
 }
 
 impl Default for GraphicsContextParams {
@@ -918,6 +1154,8 @@ impl Default for GraphicsContextParams {
         Self {
             window_attributes: Default::default(),
             vsync: true,
+            texture_upload_budget: Default::default(),
+            shader_upload_budget: None,
         }
     }
 }
@@ -978,8 +1216,10 @@ define_process_node!(process_node_message, ScriptMessageContext);
 
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
+    scene_handle: Handle<Scene>,
     plugins: &mut [Box<dyn Plugin>],
     resource_manager: &ResourceManager,
+    services: &ScriptServices,
     message_sender: &ScriptMessageSender,
     message_dispatcher: &mut ScriptMessageDispatcher,
     dt: f32,
@@ -994,9 +1234,11 @@ pub(crate) fn process_scripts<T>(
         plugins,
         handle: Default::default(),
         scene,
+        scene_handle,
         resource_manager,
         message_sender,
         message_dispatcher,
+        services,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -1006,7 +1248,11 @@ pub(crate) fn process_scripts<T>(
     }
 }
 
-pub(crate) fn initialize_resource_manager_loaders(
+/// Registers built-in resource loaders, resource data constructors and built-in resources of the
+/// engine in the given resource manager. This function is called automatically on engine creation,
+/// use it only if you need a resource manager without the engine (for example, in asset processing
+/// tools).
+pub fn initialize_resource_manager_loaders(
     resource_manager: &ResourceManager,
     serialization_context: Arc<SerializationContext>,
 ) {
@@ -1024,6 +1270,13 @@ pub(crate) fn initialize_resource_manager_loaders(
             .insert(shader.path(), shader.into_untyped());
     }
 
+    for (name, source) in STANDARD_SHADER_NAMES.iter().zip(STANDARD_SHADER_SOURCES) {
+        state.embedded_resources.register(
+            Path::new(EMBEDDED_STANDARD_SHADERS_DIR).join(format!("{name}.shader")),
+            source.as_bytes(),
+        );
+    }
+
     for texture in SkyBoxKind::built_in_skybox_textures() {
         state
             .built_in_resources
@@ -1037,6 +1290,17 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<SoundBuffer>();
     state.constructors_container.add::<HrirSphereResourceData>();
     state.constructors_container.add::<Material>();
+    state.constructors_container.add::<SpriteSheet>();
+    state.constructors_container.add::<Dialogue>();
+    state.constructors_container.add::<BehaviorTreeAsset>();
+    state.constructors_container.add::<SoundBank>();
+    state.constructors_container.add::<PostEffects>();
+    state.constructors_container.add::<StringTable>();
+    state.constructors_container.add::<BootConfig>();
+
+    state.importers.add(TextureImporter {
+        default_import_options: Default::default(),
+    });
 
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
@@ -1046,12 +1310,50 @@ pub(crate) fn initialize_resource_manager_loaders(
     loaders.set(SoundBufferLoader {
         default_import_options: Default::default(),
     });
-    loaders.set(ShaderLoader);
+    loaders.set(ShaderLoader {
+        resource_manager: resource_manager.clone(),
+    });
     loaders.set(CurveLoader);
     loaders.set(HrirSphereLoader);
     loaders.set(MaterialLoader {
         resource_manager: resource_manager.clone(),
     });
+    loaders.set(SpriteSheetLoader {
+        resource_manager: resource_manager.clone(),
+    });
+    loaders.set(DialogueLoader {
+        resource_manager: resource_manager.clone(),
+    });
+    loaders.set(BehaviorTreeLoader {
+        resource_manager: resource_manager.clone(),
+    });
+    loaders.set(SoundBankLoader {
+        resource_manager: resource_manager.clone(),
+    });
+    loaders.set(StringTableLoader);
+    loaders.set(BootConfigLoader);
+    loaders.set(PostEffectsLoader {
+        resource_manager: resource_manager.clone(),
+    });
+
+    resource_savers().write().set(CurveSaver);
+}
+
+/// Updates the scene using the time step of its time channel. Paused audio channel pauses only the
+/// sound of the scene, the graph keeps updating.
+fn update_scene(
+    time: &TimeControl,
+    handle: Handle<Scene>,
+    scene: &mut Scene,
+    frame_size: Vector2<f32>,
+    dt: f32,
+    audio_paused: bool,
+    mut switches: GraphUpdateSwitches,
+) {
+    let time = time.scene_time(handle);
+    switches.paused |= time.paused;
+    scene.graph.sound_context.audio_paused = audio_paused;
+    scene.update(frame_size, time.delta(dt), switches);
 }
 
 impl Engine {
@@ -1080,6 +1382,8 @@ impl Engine {
     /// let graphics_context_params = GraphicsContextParams {
     ///     window_attributes,
     ///     vsync: true,
+    ///     texture_upload_budget: Default::default(),
+    ///     shader_upload_budget: None,
     /// };
     ///
     /// Engine::new(EngineInitParams {
@@ -1105,6 +1409,19 @@ impl Engine {
 
         let sound_engine = SoundEngine::without_device();
 
+        let mut services = ScriptServices::default();
+        services.register(resource_manager.clone());
+        services.register(sound_engine.clone());
+        services.register_shared(serialization_context.clone());
+        let frame_capture = FrameCapture::default();
+        services.register(frame_capture.clone());
+        let time = TimeControl::default();
+        services.register(time.clone());
+        let prefab_pool = PrefabPool::default();
+        services.register(prefab_pool.clone());
+        let mut localization = Localization::default();
+        localization.set_resource_manager(resource_manager.clone());
+
         Ok(Self {
             graphics_context: GraphicsContext::Uninitialized(graphics_context_params),
             model_events_receiver: tx,
@@ -1115,14 +1432,20 @@ impl Engine {
             resource_manager,
             scenes: SceneContainer::new(sound_engine.clone()),
             sound_engine,
+            frame_capture,
             user_interface: UserInterface::new(Vector2::new(100.0, 100.0)),
             performance_statistics: Default::default(),
             plugins: Default::default(),
             serialization_context,
             script_processor: Default::default(),
+            services,
+            localization,
+            time,
+            prefab_pool,
             plugins_enabled: false,
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
+            ime_cursor_area: None,
         })
     }
 
@@ -1321,20 +1644,27 @@ impl Engine {
                     .unwrap_or_else(|| NonZeroU32::new(1).unwrap()),
             );
 
+            let mut renderer = Renderer::new(
+                glow_context,
+                (window.inner_size().width, window.inner_size().height),
+                &self.resource_manager,
+                gl_kind,
+            )?;
+            renderer.set_frame_capture(self.frame_capture.clone());
+            renderer.set_texture_upload_budget(params.texture_upload_budget);
+            renderer.set_shader_upload_budget(params.shader_upload_budget);
+
             self.graphics_context = GraphicsContext::Initialized(InitializedGraphicsContext {
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_context,
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_surface,
-                renderer: Renderer::new(
-                    glow_context,
-                    (window.inner_size().width, window.inner_size().height),
-                    &self.resource_manager,
-                    gl_kind,
-                )?,
+                renderer,
                 window,
                 params: params.clone(),
             });
+            // IME is disabled in new windows.
+            self.ime_cursor_area = None;
 
             self.sound_engine.initialize_audio_output_device()?;
 
@@ -1380,6 +1710,9 @@ impl Engine {
             self.graphics_context = GraphicsContext::Uninitialized(GraphicsContextParams {
                 window_attributes,
                 vsync: params.vsync,
+                // The budgets could be changed at runtime.
+                texture_upload_budget: ctx.renderer.texture_upload_budget(),
+                shader_upload_budget: ctx.renderer.shader_upload_budget(),
             });
 
             self.sound_engine.destroy_audio_output_device();
@@ -1460,10 +1793,12 @@ impl Engine {
                         let mut context = PluginContext {
                             scenes: &mut self.scenes,
                             resource_manager: &self.resource_manager,
+                            localization: &mut self.localization,
                             graphics_context: &mut self.graphics_context,
                             dt,
                             lag,
                             user_interface: &mut self.user_interface,
+                            services: &mut self.services,
                             serialization_context: &self.serialization_context,
                             performance_statistics: &self.performance_statistics,
                             elapsed_time: self.elapsed_time,
@@ -1488,13 +1823,22 @@ impl Engine {
                 .loading_scenes
                 .remove(&loading_result.path)
             {
+                let result = loading_result.result.map(|(scene, data, report)| {
+                    self.async_scene_loader
+                        .warm_up_reports
+                        .insert(request.path.clone(), report);
+                    (scene, data)
+                });
+
                 let mut context = PluginContext {
                     scenes: &mut self.scenes,
                     resource_manager: &self.resource_manager,
+                    localization: &mut self.localization,
                     graphics_context: &mut self.graphics_context,
                     dt,
                     lag,
                     user_interface: &mut self.user_interface,
+                    services: &mut self.services,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
                     elapsed_time: self.elapsed_time,
@@ -1503,7 +1847,7 @@ impl Engine {
                     window_target: Some(window_target),
                 };
 
-                match loading_result.result {
+                match result {
                     Ok((mut scene, data)) => {
                         if request.options.derived {
                             // Create a resource, that will point to the scene we've loaded the
@@ -1521,6 +1865,7 @@ impl Engine {
                                         &mut |_, _, _| {},
                                     )
                                     .0,
+                                dirty: false,
                             });
 
                             Log::verify(self.resource_manager.register(
@@ -1616,9 +1961,17 @@ impl Engine {
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
             self.resource_manager.state().update(dt);
+            self.localization.update();
             ctx.renderer.update_caches(dt);
             self.handle_model_events();
 
+            self.time.advance(dt);
+            self.time
+                .retain_scenes(|scene| self.scenes.is_valid_handle(scene));
+            self.prefab_pool
+                .retain_scenes(|scene| self.scenes.is_valid_handle(scene));
+            let audio_paused = self.time.is_paused(TimeControl::AUDIO);
+
             for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| *s.enabled) {
                 let frame_size =
                     scene
@@ -1633,11 +1986,20 @@ impl Engine {
                             }
                         });
 
-                scene.update(
+                let switches = switches.get(&handle).cloned().unwrap_or_default();
+                update_scene(
+                    &self.time,
+                    handle,
+                    scene,
                     frame_size,
                     dt,
-                    switches.get(&handle).cloned().unwrap_or_default(),
+                    audio_paused,
+                    switches,
                 );
+
+                if self.script_processor.has_scripted_scene(handle) {
+                    self.prefab_pool.warm_up_scene(handle, scene);
+                }
             }
 
             self.update_plugins(dt, window_target, lag);
@@ -1655,9 +2017,26 @@ impl Engine {
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
             let time = instant::Instant::now();
-            self.user_interface.update(window_size, dt);
+            self.user_interface
+                .update(window_size, self.time.delta(TimeControl::UI, dt));
             self.performance_statistics.ui_time = instant::Instant::now() - time;
             self.elapsed_time += dt;
+
+            // Enable IME only while a widget, that accepts text input, is focused and keep its
+            // candidate window near the text cursor.
+            let ime_cursor_area = self.user_interface.ime_cursor_area();
+            if ime_cursor_area != self.ime_cursor_area {
+                if ime_cursor_area.is_some() != self.ime_cursor_area.is_some() {
+                    ctx.window.set_ime_allowed(ime_cursor_area.is_some());
+                }
+                if let Some(area) = ime_cursor_area {
+                    ctx.window.set_ime_cursor_area(
+                        PhysicalPosition::new(area.x(), area.y()),
+                        PhysicalSize::new(area.w(), area.h()),
+                    );
+                }
+                self.ime_cursor_area = ime_cursor_area;
+            }
         }
     }
 
@@ -1678,6 +2057,8 @@ impl Engine {
             &mut self.scenes,
             &mut self.plugins,
             &self.resource_manager,
+            &self.services,
+            &self.time,
             dt,
             self.elapsed_time,
         );
@@ -1696,10 +2077,12 @@ impl Engine {
             let mut context = PluginContext {
                 scenes: &mut self.scenes,
                 resource_manager: &self.resource_manager,
+                localization: &mut self.localization,
                 graphics_context: &mut self.graphics_context,
                 dt,
                 lag,
                 user_interface: &mut self.user_interface,
+                services: &mut self.services,
                 serialization_context: &self.serialization_context,
                 performance_statistics: &self.performance_statistics,
                 elapsed_time: self.elapsed_time,
@@ -1716,10 +2099,12 @@ impl Engine {
                 let mut context = PluginContext {
                     scenes: &mut self.scenes,
                     resource_manager: &self.resource_manager,
+                    localization: &mut self.localization,
                     graphics_context: &mut self.graphics_context,
                     dt,
                     lag,
                     user_interface: &mut self.user_interface,
+                    services: &mut self.services,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
                     elapsed_time: self.elapsed_time,
@@ -1751,10 +2136,12 @@ impl Engine {
                     PluginContext {
                         scenes: &mut self.scenes,
                         resource_manager: &self.resource_manager,
+                        localization: &mut self.localization,
                         graphics_context: &mut self.graphics_context,
                         dt,
                         lag,
                         user_interface: &mut self.user_interface,
+                        services: &mut self.services,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
                         elapsed_time: self.elapsed_time,
@@ -1778,10 +2165,12 @@ impl Engine {
                 plugin.on_graphics_context_initialized(PluginContext {
                     scenes: &mut self.scenes,
                     resource_manager: &self.resource_manager,
+                    localization: &mut self.localization,
                     graphics_context: &mut self.graphics_context,
                     dt,
                     lag,
                     user_interface: &mut self.user_interface,
+                    services: &mut self.services,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
                     elapsed_time: self.elapsed_time,
@@ -1804,10 +2193,12 @@ impl Engine {
                 plugin.on_graphics_context_destroyed(PluginContext {
                     scenes: &mut self.scenes,
                     resource_manager: &self.resource_manager,
+                    localization: &mut self.localization,
                     graphics_context: &mut self.graphics_context,
                     dt,
                     lag,
                     user_interface: &mut self.user_interface,
+                    services: &mut self.services,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
                     elapsed_time: self.elapsed_time,
@@ -1830,10 +2221,12 @@ impl Engine {
                 plugin.before_rendering(PluginContext {
                     scenes: &mut self.scenes,
                     resource_manager: &self.resource_manager,
+                    localization: &mut self.localization,
                     graphics_context: &mut self.graphics_context,
                     dt,
                     lag,
                     user_interface: &mut self.user_interface,
+                    services: &mut self.services,
                     serialization_context: &self.serialization_context,
                     performance_statistics: &self.performance_statistics,
                     elapsed_time: self.elapsed_time,
@@ -1864,12 +2257,15 @@ impl Engine {
             .iter_mut()
             .find(|s| s.handle == scene)
         {
-            let scene = &mut self.scenes[scene];
+            let scene_handle = scene;
+            let scene = &mut self.scenes[scene_handle];
             if *scene.enabled {
                 process_scripts(
                     scene,
+                    scene_handle,
                     &mut self.plugins,
                     &self.resource_manager,
+                    &self.services,
                     &scripted_scene.message_sender,
                     &mut scripted_scene.message_dispatcher,
                     dt,
@@ -1890,7 +2286,9 @@ impl Engine {
     /// You should only call this manually if you don't use that method.
     pub fn handle_model_events(&mut self) {
         while let Ok(event) = self.model_events_receiver.try_recv() {
-            if let ResourceEvent::Reloaded(resource) = event {
+            if let ResourceEvent::Reloaded { resource, .. }
+            | ResourceEvent::DataReplaced(resource) = event
+            {
                 if let Some(model) = resource.try_cast::<Model>() {
                     Log::info(format!(
                         "A model resource {} was reloaded, propagating changes...",
@@ -1961,10 +2359,12 @@ impl Engine {
                         PluginContext {
                             scenes: &mut self.scenes,
                             resource_manager: &self.resource_manager,
+                            localization: &mut self.localization,
                             graphics_context: &mut self.graphics_context,
                             dt: 0.0,
                             lag: &mut 0.0,
                             user_interface: &mut self.user_interface,
+                            services: &mut self.services,
                             serialization_context: &self.serialization_context,
                             performance_statistics: &self.performance_statistics,
                             elapsed_time: self.elapsed_time,
@@ -1982,10 +2382,12 @@ impl Engine {
                     plugin.on_deinit(PluginContext {
                         scenes: &mut self.scenes,
                         resource_manager: &self.resource_manager,
+                        localization: &mut self.localization,
                         graphics_context: &mut self.graphics_context,
                         dt: 0.0,
                         lag: &mut 0.0,
                         user_interface: &mut self.user_interface,
+                        services: &mut self.services,
                         serialization_context: &self.serialization_context,
                         performance_statistics: &self.performance_statistics,
                         elapsed_time: self.elapsed_time,
@@ -2036,13 +2438,25 @@ impl Drop for Engine {
 mod test {
     use crate::{
         asset::manager::ResourceManager,
+        core::algebra::Vector2,
         core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
-        engine::ScriptProcessor,
+        engine::{time::TimeControl, update_scene, ScriptMessageDispatcher, ScriptProcessor},
         impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        scene::{
+            base::BaseBuilder,
+            collider::{BitMask, ColliderBuilder, InteractionGroups},
+            graph::{
+                physics::{CollisionEvent, CollisionEventFilter, CollisionEventKind},
+                Graph,
+            },
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::RigidBodyBuilder,
+            Scene, SceneContainer,
+        },
         script::{
-            Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
-            ScriptTrait,
+            Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessageKind,
+            ScriptMessagePayload, ScriptMessageSender, ScriptTrait,
         },
     };
 
@@ -2178,6 +2592,8 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &Default::default(),
+                &Default::default(),
                 0.0,
                 0.0,
             );
@@ -2341,6 +2757,8 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &Default::default(),
+                &Default::default(),
                 0.0,
                 0.0,
             );
@@ -2358,4 +2776,187 @@ mod test {
             }
         }
     }
+
+    // A player body with two colliders and a coin body with a sensor collider.
+    struct CollisionScene {
+        graph: Graph,
+        player: Handle<Node>,
+        player_collider: Handle<Node>,
+        player_collider2: Handle<Node>,
+        coin: Handle<Node>,
+        coin_collider: Handle<Node>,
+    }
+
+    fn collision_scene() -> CollisionScene {
+        let mut graph = Graph::new();
+        let groups = |layer| InteractionGroups::new(BitMask(layer), BitMask(u32::MAX));
+        let player_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_collision_groups(groups(0b01))
+            .build(&mut graph);
+        let player_collider2 = ColliderBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let player = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_tag("Player".to_string())
+                .with_children(&[player_collider, player_collider2]),
+        )
+        .build(&mut graph);
+        let coin_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_sensor(true)
+            .with_collision_groups(groups(0b10))
+            .build(&mut graph);
+        let coin = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_tag("Coin".to_string())
+                .with_children(&[coin_collider]),
+        )
+        .build(&mut graph);
+        CollisionScene {
+            graph,
+            player,
+            player_collider,
+            player_collider2,
+            coin,
+            coin_collider,
+        }
+    }
+
+    fn pickup_event(scene: &CollisionScene) -> CollisionEvent {
+        CollisionEvent {
+            kind: CollisionEventKind::Started,
+            collider1: scene.player_collider,
+            collider2: scene.coin_collider,
+            sensor: true,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn collision_event_filter() {
+        let scene = collision_scene();
+        let event = pickup_event(&scene);
+        let matches = |filter: CollisionEventFilter, collider: Handle<Node>| {
+            filter.matches(&event, collider, &scene.graph)
+        };
+
+        assert!(matches(Default::default(), scene.coin_collider));
+        assert!(!matches(
+            CollisionEventFilter {
+                kind: Some(CollisionEventKind::Stopped),
+                ..Default::default()
+            },
+            scene.coin_collider
+        ));
+        assert!(!matches(
+            CollisionEventFilter {
+                sensor: Some(false),
+                ..Default::default()
+            },
+            scene.coin_collider
+        ));
+
+        // The tag is checked on the other collider (or its body), not on the receiver's one.
+        let tag = |tag: &str| CollisionEventFilter {
+            tag: Some(tag.to_string()),
+            ..Default::default()
+        };
+        assert!(matches(tag("Player"), scene.coin_collider));
+        assert!(!matches(tag("Coin"), scene.coin_collider));
+        assert!(matches(tag("Coin"), scene.player_collider));
+        assert!(!matches(tag("Player"), scene.player_collider));
+        // Colliders, that aren't involved in the event, have no other collider.
+        assert!(!matches(tag("Coin"), scene.player_collider2));
+
+        let layers = |layers: u32| CollisionEventFilter {
+            layers: Some(BitMask(layers)),
+            ..Default::default()
+        };
+        assert!(matches(layers(0b10), scene.coin_collider));
+        assert!(!matches(layers(0b100), scene.coin_collider));
+    }
+
+    #[test]
+    fn collision_events_delivery() {
+        let mut scene = collision_scene();
+        let (sender, receiver) = mpsc::channel();
+        let message_sender = ScriptMessageSender { sender };
+        let mut dispatcher = ScriptMessageDispatcher::new(mpsc::channel().1);
+
+        // Nothing is subscribed, so the events are not needed.
+        dispatcher.send_collision_events(&mut scene.graph, &message_sender);
+        assert!(!scene.graph.physics.is_collision_events_enabled());
+        assert!(!scene.graph.physics2d.is_collision_events_enabled());
+
+        dispatcher.subscribe_to_collision_events(scene.player, Default::default());
+        dispatcher.subscribe_to_collision_events(
+            scene.coin,
+            CollisionEventFilter {
+                tag: Some("Coin".to_string()),
+                ..Default::default()
+            },
+        );
+        dispatcher.send_collision_events(&mut scene.graph, &message_sender);
+        assert!(scene.graph.physics.is_collision_events_enabled());
+        assert!(scene.graph.physics2d.is_collision_events_enabled());
+
+        // The body receives the events of its colliders, every event is delivered once, even if
+        // both colliders belong to the body. The coin does not receive the events, because it
+        // collides with the player and not with another coin.
+        let self_collision = CollisionEvent {
+            collider2: scene.player_collider2,
+            sensor: false,
+            ..pickup_event(&scene)
+        };
+        let events = [pickup_event(&scene), self_collision];
+        dispatcher.deliver_collision_events(events.iter(), &scene.graph, &message_sender);
+        let messages = receiver
+            .try_iter()
+            .map(|message| {
+                let ScriptMessageKind::Targeted(target) = message.kind else {
+                    unreachable!()
+                };
+                (
+                    target,
+                    *message.payload.downcast_ref::<CollisionEvent>().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [(scene.player, events[0]), (scene.player, events[1])]
+        );
+    }
+
+    #[test]
+    fn audio_pause_does_not_freeze_graph() {
+        let mut scene = Scene::new();
+        let node =
+            PivotBuilder::new(BaseBuilder::new().with_lifetime(10.0)).build(&mut scene.graph);
+        let handle = Handle::new(1, 1);
+        let time = TimeControl::default();
+        let update = |scene: &mut Scene| {
+            update_scene(
+                &time,
+                handle,
+                scene,
+                Vector2::new(100.0, 100.0),
+                1.0,
+                time.is_paused(TimeControl::AUDIO),
+                Default::default(),
+            );
+            (
+                scene.graph[node].lifetime().unwrap(),
+                scene.graph.sound_context.state().is_paused(),
+            )
+        };
+
+        time.set_paused(TimeControl::AUDIO, true);
+        assert_eq!(update(&mut scene), (9.0, true));
+
+        time.set_paused(TimeControl::AUDIO, false);
+        time.set_paused(TimeControl::GAMEPLAY, true);
+        assert_eq!(update(&mut scene), (9.0, true));
+
+        time.set_paused(TimeControl::GAMEPLAY, false);
+        assert_eq!(update(&mut scene), (8.0, false));
+    }
 }
@@ -0,0 +1,264 @@
+//! Time control with named time channels. See [`TimeControl`] docs for more info.
+
+use crate::{
+    core::{parking_lot::Mutex, pool::Handle},
+    scene::Scene,
+};
+use fxhash::FxHashMap;
+use std::sync::Arc;
+
+/// State of a single time channel.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TimeChannel {
+    /// Multiplier of the time step of the channel. `1.0` is the normal speed, `0.5` - twice as slow
+    /// and so on. Negative values are clamped to zero.
+    pub scale: f32,
+    /// Whether the channel is paused or not. Paused channel has zero time step.
+    pub paused: bool,
+    /// Amount of scaled time (in seconds), that passed in the channel since the start of the
+    /// engine. It does not advance while the channel is paused.
+    pub elapsed: f32,
+}
+
+impl Default for TimeChannel {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            paused: false,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl TimeChannel {
+    /// Returns the time step of the channel for the given real time step.
+    pub fn delta(&self, dt: f32) -> f32 {
+        if self.paused {
+            0.0
+        } else {
+            dt * self.scale.max(0.0)
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct State {
+    channels: FxHashMap<String, TimeChannel>,
+    scene_channels: FxHashMap<Handle<Scene>, String>,
+}
+
+/// Time control is a set of named time channels, that could be paused and scaled independently.
+/// It removes the need of ad-hoc "is paused" flags in every game system: a pause menu pauses the
+/// gameplay channel while the UI keeps running, a bullet-time effect reduces the scale of the
+/// gameplay channel and so on.
+///
+/// There are three standard channels:
+///
+/// - [`Self::GAMEPLAY`] - the default channel of every scene. The time step of this channel is used
+///   to update scene graphs (animations, particle systems, physics stepping, etc.) and it is
+///   passed to the scripts of the scenes (see [`crate::script::ScriptContext::dt`]). A paused
+///   scene also pauses its sound context.
+/// - [`Self::UI`] - the channel of the user interface of the engine.
+/// - [`Self::AUDIO`] - pausing the channel pauses the sound of every scene. Its scale is not used.
+///
+/// Any other channel could be created just by using its name, and a scene could be assigned to
+/// it using [`Self::set_scene_channel`]. For example, a game could put its main menu background
+/// scene to a separate channel so it keeps playing while the gameplay is paused. A custom channel,
+/// that was never changed, has normal speed.
+///
+/// Time control is a shallow handle, that could be cloned and shared. It is available via
+/// [`crate::engine::Engine::time`] and to scripts as a service (see
+/// [`crate::script::service::ScriptServices`]). Plugins receive real (unscaled) time step.
+///
+/// ```rust
+/// # use fyrox::{engine::time::TimeControl, script::ScriptContext};
+/// fn toggle_pause_menu(ctx: &mut ScriptContext) {
+///     let time = ctx.services.expect::<TimeControl>();
+///     time.set_paused(TimeControl::GAMEPLAY, !time.is_paused(TimeControl::GAMEPLAY));
+/// }
+///
+/// fn bullet_time(ctx: &mut ScriptContext, enabled: bool) {
+///     let time = ctx.services.expect::<TimeControl>();
+///     time.set_scale(TimeControl::GAMEPLAY, if enabled { 0.25 } else { 1.0 });
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimeControl {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        let mut state = State::default();
+        for name in [Self::GAMEPLAY, Self::UI, Self::AUDIO] {
+            state.channels.insert(name.to_string(), Default::default());
+        }
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+}
+
+impl TimeControl {
+    /// Name of the default channel of scenes.
+    pub const GAMEPLAY: &'static str = "Gameplay";
+    /// Name of the channel of the user interface.
+    pub const UI: &'static str = "UI";
+    /// Name of the channel of the sound.
+    pub const AUDIO: &'static str = "Audio";
+
+    /// Returns the state of the channel with the given name.
+    pub fn channel(&self, name: &str) -> TimeChannel {
+        self.state
+            .lock()
+            .channels
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Sets the time scale of the given channel.
+    pub fn set_scale(&self, name: &str, scale: f32) {
+        self.with_channel(name, |channel| channel.scale = scale.max(0.0))
+    }
+
+    /// Returns the time scale of the given channel.
+    pub fn scale(&self, name: &str) -> f32 {
+        self.channel(name).scale
+    }
+
+    /// Pauses or resumes the given channel.
+    pub fn set_paused(&self, name: &str, paused: bool) {
+        self.with_channel(name, |channel| channel.paused = paused)
+    }
+
+    /// Returns `true` if the given channel is paused.
+    pub fn is_paused(&self, name: &str) -> bool {
+        self.channel(name).paused
+    }
+
+    /// Returns the time step of the given channel for the given real time step.
+    pub fn delta(&self, name: &str, dt: f32) -> f32 {
+        self.channel(name).delta(dt)
+    }
+
+    /// Returns the amount of scaled time, that passed in the given channel.
+    pub fn elapsed(&self, name: &str) -> f32 {
+        self.channel(name).elapsed
+    }
+
+    /// Returns the sorted names of the standard channels and every channel, that was changed at
+    /// least once.
+    pub fn channel_names(&self) -> Vec<String> {
+        let mut names = self
+            .state
+            .lock()
+            .channels
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Assigns the given scene to the given channel. `None` resets the channel of the scene to the
+    /// default one ([`Self::GAMEPLAY`]).
+    pub fn set_scene_channel(&self, scene: Handle<Scene>, name: Option<&str>) {
+        let mut state = self.state.lock();
+        match name {
+            Some(name) => {
+                state.scene_channels.insert(scene, name.to_string());
+            }
+            None => {
+                state.scene_channels.remove(&scene);
+            }
+        }
+    }
+
+    /// Returns the name of the channel of the given scene.
+    pub fn scene_channel(&self, scene: Handle<Scene>) -> String {
+        self.state
+            .lock()
+            .scene_channels
+            .get(&scene)
+            .cloned()
+            .unwrap_or_else(|| Self::GAMEPLAY.to_string())
+    }
+
+    /// Returns the state of the channel of the given scene.
+    pub fn scene_time(&self, scene: Handle<Scene>) -> TimeChannel {
+        let name = self.scene_channel(scene);
+        self.channel(&name)
+    }
+
+    fn with_channel(&self, name: &str, func: impl FnOnce(&mut TimeChannel)) {
+        let mut state = self.state.lock();
+        match state.channels.get_mut(name) {
+            Some(channel) => func(channel),
+            None => {
+                let mut channel = TimeChannel::default();
+                func(&mut channel);
+                state.channels.insert(name.to_string(), channel);
+            }
+        }
+    }
+
+    /// Advances the elapsed time of every channel.
+    pub(crate) fn advance(&self, dt: f32) {
+        let mut state = self.state.lock();
+        for channel in state.channels.values_mut() {
+            channel.elapsed += channel.delta(dt);
+        }
+    }
+
+    /// Removes channel assignments of the scenes, that do not exist anymore.
+    pub(crate) fn retain_scenes(&self, mut func: impl FnMut(Handle<Scene>) -> bool) {
+        self.state
+            .lock()
+            .scene_channels
+            .retain(|scene, _| func(*scene));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn time_control_channels() {
+        let time = TimeControl::default();
+        let shared = time.clone();
+
+        // Untouched channels have normal speed.
+        assert_eq!(time.delta(TimeControl::GAMEPLAY, 0.1), 0.1);
+
+        shared.set_scale(TimeControl::GAMEPLAY, 0.5);
+        assert_eq!(time.delta(TimeControl::GAMEPLAY, 0.1), 0.05);
+        shared.set_scale(TimeControl::GAMEPLAY, -1.0);
+        assert_eq!(time.scale(TimeControl::GAMEPLAY), 0.0);
+        shared.set_scale(TimeControl::GAMEPLAY, 2.0);
+
+        time.set_paused(TimeControl::UI, true);
+        assert_eq!(time.delta(TimeControl::UI, 0.1), 0.0);
+
+        time.advance(1.0);
+        assert_eq!(time.elapsed(TimeControl::GAMEPLAY), 2.0);
+        assert_eq!(time.elapsed(TimeControl::UI), 0.0);
+        assert_eq!(
+            time.channel_names(),
+            vec![
+                TimeControl::AUDIO.to_string(),
+                TimeControl::GAMEPLAY.to_string(),
+                TimeControl::UI.to_string()
+            ]
+        );
+
+        let scene = Handle::new(1, 1);
+        assert_eq!(time.scene_channel(scene), TimeControl::GAMEPLAY);
+        time.set_scene_channel(scene, Some("Menu"));
+        time.set_paused("Menu", true);
+        assert!(time.scene_time(scene).paused);
+        time.set_scene_channel(scene, None);
+        assert_eq!(time.scene_time(scene).scale, 2.0);
+    }
+}
@@ -11,6 +11,7 @@
 
 pub mod animation;
 pub mod engine;
+pub mod localization;
 pub mod material;
 pub mod plugin;
 pub mod renderer;
@@ -26,6 +27,9 @@ pub use tbc;
 pub use walkdir;
 pub use winit::*;
 
+/// Version of the engine, for example `0.32.1`.
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[doc(inline)]
 pub use fyrox_core as core;
 
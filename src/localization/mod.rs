@@ -0,0 +1,600 @@
+//! Runtime localization service. See [`Localization`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    asset::{
+        locale::{language, LocaleSettings},
+        manager::ResourceManager,
+        Resource, ResourceStateRef, TypedResourceData,
+    },
+    core::{reflect::prelude::*, visitor::prelude::*},
+    fxhash::FxHashMap,
+    resource::{
+        dialogue::player::LocalizedTextProvider,
+        string_table::{StringTable, StringTableResource},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Formatter},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// A plural form of a text. See [Unicode plural rules](https://cldr.unicode.org/index/cldr-spec/plural-rules)
+/// for more info.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    Serialize,
+    Deserialize,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum PluralCategory {
+    /// Used for zero in some languages.
+    Zero,
+    /// Used for one (singular).
+    One,
+    /// Used for two (dual).
+    Two,
+    /// Used for a few items (paucal).
+    Few,
+    /// Used for many items.
+    Many,
+    /// General plural form, every language has it.
+    #[default]
+    Other,
+}
+
+/// A rule, that selects a plural form for a number. Every language has its own rule.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PluralRule {
+    /// There are no plural forms, [`PluralCategory::Other`] is always used (Chinese, Japanese,
+    /// Korean, etc.).
+    NoPlurals,
+    /// One for `1`, other for everything else (English, German, Spanish, etc.).
+    #[default]
+    OneOther,
+    /// One for `0` and `1`, other for everything else (French, Brazilian Portuguese).
+    ZeroOrOneOther,
+    /// One for `1, 21, 31, ...`, few for `2-4, 22-24, ...`, many for everything else (Russian,
+    /// Ukrainian, Belarusian).
+    EastSlavic,
+    /// One for `1`, few for `2-4, 22-24, ...`, many for everything else (Polish).
+    Polish,
+    /// One for `1`, few for `2-4`, other for everything else (Czech, Slovak).
+    Czech,
+}
+
+impl PluralRule {
+    /// Returns a plural rule for the given locale (for example `en-US` or `ru`). Unknown languages
+    /// use [`PluralRule::OneOther`].
+    pub fn for_locale(locale: &str) -> Self {
+        match language(locale).to_lowercase().as_str() {
+            "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => Self::NoPlurals,
+            "fr" => Self::ZeroOrOneOther,
+            "ru" | "uk" | "be" => Self::EastSlavic,
+            "pl" => Self::Polish,
+            "cs" | "sk" => Self::Czech,
+            _ => Self::OneOther,
+        }
+    }
+
+    /// Selects a plural form for the given number.
+    pub fn category(self, n: u64) -> PluralCategory {
+        let (n10, n100) = (n % 10, n % 100);
+        let few = (2..=4).contains(&n10) && !(12..=14).contains(&n100);
+        match self {
+            Self::NoPlurals => PluralCategory::Other,
+            Self::OneOther if n == 1 => PluralCategory::One,
+            Self::ZeroOrOneOther if n <= 1 => PluralCategory::One,
+            Self::EastSlavic if n10 == 1 && n100 != 11 => PluralCategory::One,
+            Self::EastSlavic if few => PluralCategory::Few,
+            Self::EastSlavic => PluralCategory::Many,
+            Self::Polish if n == 1 => PluralCategory::One,
+            Self::Polish if few => PluralCategory::Few,
+            Self::Polish => PluralCategory::Many,
+            Self::Czech if n == 1 => PluralCategory::One,
+            Self::Czech if (2..=4).contains(&n) => PluralCategory::Few,
+            _ => PluralCategory::Other,
+        }
+    }
+}
+
+/// An event, that is sent to every subscriber of [`Localization`] when the current locale changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocaleChangedEvent {
+    /// Previous locale.
+    pub previous: String,
+    /// New locale.
+    pub current: String,
+}
+
+/// Localization service keeps the current locale and a set of string tables for every locale. Texts
+/// of the UI, dialogues and any other game systems are requested from the service by their keys.
+///
+/// The service drives locale settings of its resource manager (see [`Self::set_resource_manager`]),
+/// so assets with localized content (voice lines, textures with text, etc.) are loaded from their
+/// variants for the current locale. A variant is either an explicit path from the string tables
+/// (see [`StringTable::paths`]) or a file with the locale suffix or in the locale folder (see
+/// [`LocaleSettings`]). The engine attaches its resource manager to its localization service.
+///
+/// The locale could be changed at any time, loaded assets are swapped to their new variants in the
+/// background, and every subscriber (see [`Self::subscribe`]) receives [`LocaleChangedEvent`], so it
+/// could refresh its texts.
+///
+/// ## Locale resolution
+///
+/// Locales are identified by their tags, like `en-US` or `de`. When a text is requested, the
+/// service looks for it in string tables of the current locale, then in string tables of its
+/// language (`en` for `en-US`) and finally in the fallback locale.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     asset::manager::ResourceManager,
+///     localization::Localization,
+/// };
+///
+/// fn setup(localization: &mut Localization, resource_manager: &ResourceManager) {
+///     localization.add_table("en", resource_manager.request("data/strings/en.strings"));
+///     localization.add_table("de", resource_manager.request("data/strings/de.strings"));
+///     localization.set_locale("de");
+/// }
+///
+/// fn apples_text(localization: &Localization, count: u64) -> String {
+///     localization
+///         .plural_text("inventory.apples", count)
+///         .unwrap_or_default()
+/// }
+/// ```
+pub struct Localization {
+    locale: String,
+    fallback_locale: String,
+    plural_rules: FxHashMap<String, PluralRule>,
+    tables: FxHashMap<String, Vec<StringTableResource>>,
+    subscribers: Vec<Sender<LocaleChangedEvent>>,
+    resource_manager: Option<ResourceManager>,
+    // Amount of loaded tables of the locale chain, that were used for the locale settings.
+    synced_tables: usize,
+}
+
+impl Debug for Localization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Localization")
+            .field("locale", &self.locale)
+            .field("fallback_locale", &self.fallback_locale)
+            .field("plural_rules", &self.plural_rules)
+            .field("tables", &self.tables)
+            .finish()
+    }
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl Localization {
+    /// Creates a new localization service with the given current locale. The same locale is used
+    /// as fallback locale.
+    pub fn new(locale: impl Into<String>) -> Self {
+        let locale = locale.into();
+        Self {
+            fallback_locale: locale.clone(),
+            locale,
+            plural_rules: Default::default(),
+            tables: Default::default(),
+            subscribers: Default::default(),
+            resource_manager: None,
+            synced_tables: 0,
+        }
+    }
+
+    /// Sets a resource manager, which locale settings will follow the current locale, the fallback
+    /// locale and the path mappings of string tables. See [`ResourceManager::set_locale_settings`]
+    /// for more info. Keep in mind, that this method locks the state of the resource manager.
+    pub fn set_resource_manager(&mut self, resource_manager: ResourceManager) {
+        self.resource_manager = Some(resource_manager);
+        self.sync_locale_settings();
+    }
+
+    /// Returns the resource manager, that follows the locale of the service.
+    pub fn resource_manager(&self) -> Option<&ResourceManager> {
+        self.resource_manager.as_ref()
+    }
+
+    /// Returns locale settings, that matches the current state of the service.
+    pub fn locale_settings(&self) -> LocaleSettings {
+        let mut settings = LocaleSettings::new(self.locale.as_str());
+        if self.fallback_locale != self.locale {
+            settings = settings.with_fallback(self.fallback_locale.as_str());
+        }
+        // Tables with lower priority go first, so their paths are overwritten.
+        for locale in self.locale_chain().into_iter().rev() {
+            for table in self.tables(locale) {
+                if let ResourceStateRef::Ok(table) = table.state().get() {
+                    settings.paths.extend(
+                        table
+                            .paths
+                            .iter()
+                            .map(|(path, localized)| (path.clone(), localized.clone())),
+                    );
+                }
+            }
+        }
+        settings
+    }
+
+    fn loaded_tables(&self) -> usize {
+        self.locale_chain()
+            .into_iter()
+            .flat_map(|locale| self.tables(locale))
+            .filter(|table| table.is_ok())
+            .count()
+    }
+
+    fn sync_locale_settings(&mut self) {
+        self.synced_tables = self.loaded_tables();
+        if let Some(resource_manager) = self.resource_manager.as_ref() {
+            resource_manager.set_locale_settings(self.locale_settings());
+        }
+    }
+
+    /// Updates locale settings of the resource manager when string tables are loaded. It is called
+    /// by the engine every frame.
+    pub fn update(&mut self) {
+        if self.resource_manager.is_some() && self.loaded_tables() != self.synced_tables {
+            self.sync_locale_settings();
+        }
+    }
+
+    /// Returns the current locale.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Sets the current locale and notifies every subscriber. Returns `false` if the locale is the
+    /// same as the current one, no events are sent in this case.
+    pub fn set_locale(&mut self, locale: impl Into<String>) -> bool {
+        let locale = locale.into();
+        if locale == self.locale {
+            return false;
+        }
+        let event = LocaleChangedEvent {
+            previous: std::mem::replace(&mut self.locale, locale),
+            current: self.locale.clone(),
+        };
+        self.sync_locale_settings();
+        // Subscribers that dropped their receivers are not interested in events anymore.
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        true
+    }
+
+    /// Returns a locale, that is used when there's no text in the current locale.
+    pub fn fallback_locale(&self) -> &str {
+        &self.fallback_locale
+    }
+
+    /// Sets a locale, that is used when there's no text in the current locale.
+    pub fn set_fallback_locale(&mut self, locale: impl Into<String>) {
+        self.fallback_locale = locale.into();
+        self.sync_locale_settings();
+    }
+
+    /// Creates a new subscriber, that will receive an event every time when the current locale
+    /// changes.
+    pub fn subscribe(&mut self) -> Receiver<LocaleChangedEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Overrides plural rule of the given locale. By default, the rule is selected by
+    /// [`PluralRule::for_locale`].
+    pub fn set_plural_rule(&mut self, locale: impl Into<String>, rule: PluralRule) {
+        self.plural_rules.insert(locale.into(), rule);
+    }
+
+    /// Returns plural rule of the given locale.
+    pub fn plural_rule(&self, locale: &str) -> PluralRule {
+        self.plural_rules
+            .get(locale)
+            .or_else(|| self.plural_rules.get(language(locale)))
+            .cloned()
+            .unwrap_or_else(|| PluralRule::for_locale(locale))
+    }
+
+    /// Adds a string table for the given locale. Tables that were added later have higher priority
+    /// than previous tables of the same locale, this way a game could override some of the texts
+    /// with patches or mods.
+    pub fn add_table(&mut self, locale: impl Into<String>, table: StringTableResource) {
+        self.tables.entry(locale.into()).or_default().push(table);
+        self.sync_locale_settings();
+    }
+
+    /// Removes every string table of the given locale and returns them.
+    pub fn remove_tables(&mut self, locale: &str) -> Vec<StringTableResource> {
+        let tables = self.tables.remove(locale).unwrap_or_default();
+        self.sync_locale_settings();
+        tables
+    }
+
+    /// Returns a list of string tables of the given locale.
+    pub fn tables(&self, locale: &str) -> &[StringTableResource] {
+        self.tables
+            .get(locale)
+            .map(|tables| tables.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns a list of locales, that will be searched for a text, in order of priority.
+    pub fn locale_chain(&self) -> Vec<&str> {
+        let mut chain = Vec::with_capacity(4);
+        for locale in [self.locale.as_str(), self.fallback_locale.as_str()] {
+            for candidate in [locale, language(locale)] {
+                if !chain.contains(&candidate) {
+                    chain.push(candidate);
+                }
+            }
+        }
+        chain
+    }
+
+    fn find<T>(&self, mut func: impl FnMut(&str, &StringTable) -> Option<T>) -> Option<T> {
+        for locale in self.locale_chain() {
+            for table in self.tables(locale).iter().rev() {
+                let state = table.state();
+                if let ResourceStateRef::Ok(table) = state.get() {
+                    if let Some(value) = func(locale, table) {
+                        return Some(value);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a text with the given key in the current locale (see [locale resolution](#locale-resolution)).
+    pub fn text(&self, key: &str) -> Option<String> {
+        self.find(|_, table| table.text(key).map(|text| text.to_string()))
+    }
+
+    /// Same as [`Self::text`], but returns the key itself if there's no such text. It is useful to
+    /// quickly spot missing translations.
+    pub fn text_or_key(&self, key: &str) -> String {
+        self.text(key).unwrap_or_else(|| key.to_string())
+    }
+
+    /// Returns a plural form of a text with the given key for the given count. Every `{}` in the text
+    /// is replaced with the count.
+    pub fn plural_text(&self, key: &str, count: u64) -> Option<String> {
+        self.find(|locale, table| {
+            let category = self.plural_rule(locale).category(count);
+            table
+                .plural(key, category)
+                .map(|text| text.replace("{}", &count.to_string()))
+        })
+    }
+
+    /// Returns a localized version of the given asset path from the string tables, or the path
+    /// itself if there's no localized version. Keep in mind, that the resource manager, that follows
+    /// the service, resolves localized paths by itself, there's no need to request the localized
+    /// path directly.
+    pub fn localized_path(&self, path: &Path) -> PathBuf {
+        self.find(|_, table| table.localized_path(path).map(|path| path.to_path_buf()))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Requests the given asset. If the given resource manager follows the service (see
+    /// [`Self::set_resource_manager`]), the asset keeps its original path and is loaded from its
+    /// localized variant, so it is swapped when the locale changes. Otherwise, the resource is
+    /// requested using its localized path (see [`Self::localized_path`]).
+    pub fn request<T>(
+        &self,
+        resource_manager: &ResourceManager,
+        path: impl AsRef<Path>,
+    ) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let path = path.as_ref();
+        if self
+            .resource_manager
+            .as_ref()
+            .is_some_and(|attached| attached.ptr_eq(resource_manager))
+        {
+            resource_manager.request(path)
+        } else {
+            resource_manager.request(self.localized_path(path))
+        }
+    }
+}
+
+impl LocalizedTextProvider for Localization {
+    fn localized_text(&self, key: &str) -> Option<String> {
+        self.text(key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table(
+        texts: &[(&str, &str)],
+        plurals: &[(&str, &[(PluralCategory, &str)])],
+    ) -> StringTableResource {
+        let mut table = StringTable::default();
+        for (key, text) in texts {
+            table.texts.insert(key.to_string(), text.to_string());
+        }
+        for (key, forms) in plurals {
+            table.plurals.insert(
+                key.to_string(),
+                forms
+                    .iter()
+                    .map(|(category, text)| (*category, text.to_string()))
+                    .collect(),
+            );
+        }
+        Resource::new_ok(table)
+    }
+
+    #[test]
+    fn plural_rules() {
+        use PluralCategory::*;
+
+        let categories = |locale: &str| {
+            let rule = PluralRule::for_locale(locale);
+            [0, 1, 2, 5, 11, 21, 22, 25].map(|n| rule.category(n))
+        };
+        let o = Other;
+        assert_eq!(categories("en-US"), [o, One, o, o, o, o, o, o]);
+        assert_eq!(categories("ja"), [o, o, o, o, o, o, o, o]);
+        assert_eq!(categories("fr"), [One, One, o, o, o, o, o, o]);
+        assert_eq!(
+            categories("ru"),
+            [Many, One, Few, Many, Many, One, Few, Many]
+        );
+        assert_eq!(
+            categories("pl"),
+            [Many, One, Few, Many, Many, Many, Few, Many]
+        );
+        assert_eq!(categories("cs"), [o, One, Few, o, o, o, o, o]);
+    }
+
+    #[test]
+    fn localization_lookup_and_locale_change() {
+        let mut localization = Localization::new("en");
+        localization.add_table(
+            "en",
+            table(
+                &[("hello", "Hello"), ("bye", "Bye")],
+                &[(
+                    "apples",
+                    &[
+                        (PluralCategory::One, "{} apple"),
+                        (PluralCategory::Other, "{} apples"),
+                    ],
+                )],
+            ),
+        );
+        localization.add_table(
+            "ru",
+            table(
+                &[("hello", "Привет")],
+                &[(
+                    "apples",
+                    &[
+                        (PluralCategory::One, "{} яблоко"),
+                        (PluralCategory::Few, "{} яблока"),
+                        (PluralCategory::Many, "{} яблок"),
+                    ],
+                )],
+            ),
+        );
+        let events = localization.subscribe();
+
+        assert_eq!(localization.text("hello").unwrap(), "Hello");
+        assert_eq!(localization.plural_text("apples", 1).unwrap(), "1 apple");
+        assert_eq!(localization.plural_text("apples", 3).unwrap(), "3 apples");
+
+        assert!(localization.set_locale("ru-RU"));
+        assert!(!localization.set_locale("ru-RU"));
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            [LocaleChangedEvent {
+                previous: "en".to_string(),
+                current: "ru-RU".to_string()
+            }]
+        );
+
+        assert_eq!(localization.locale_chain(), ["ru-RU", "ru", "en"]);
+        assert_eq!(localization.text("hello").unwrap(), "Привет");
+        // Missing texts are taken from the fallback locale.
+        assert_eq!(localization.text("bye").unwrap(), "Bye");
+        assert_eq!(localization.text_or_key("missing"), "missing");
+        assert_eq!(localization.plural_text("apples", 22).unwrap(), "22 яблока");
+        assert_eq!(localization.plural_text("apples", 25).unwrap(), "25 яблок");
+    }
+
+    #[test]
+    fn localization_drives_resource_manager_locale() {
+        let resource_manager = ResourceManager::new();
+        let mut localization = Localization::new("en");
+        localization.set_resource_manager(resource_manager.clone());
+        assert_eq!(
+            *resource_manager.state().locale_settings(),
+            LocaleSettings::new("en")
+        );
+
+        let mut en = StringTable::default();
+        en.paths
+            .insert("voice.ogg".into(), "voice_en_us.ogg".into());
+        localization.add_table("en", Resource::new_ok(en));
+        let mut de = StringTable::default();
+        de.paths
+            .insert("voice.ogg".into(), "voice_german.ogg".into());
+        let de = Resource::new_ok(de);
+        localization.add_table("de", de.clone());
+
+        localization.set_locale("de-AT");
+        assert_eq!(
+            *resource_manager.state().locale_settings(),
+            LocaleSettings::new("de-AT")
+                .with_fallback("en")
+                .with_path("voice.ogg", "voice_german.ogg")
+        );
+
+        // Tables, that are loaded later, are picked up on update.
+        let pending = StringTableResource::new_pending("logo.strings".into());
+        localization.add_table("de-AT", pending.clone());
+        localization.update();
+        assert!(!resource_manager
+            .state()
+            .locale_settings()
+            .paths
+            .contains_key(Path::new("logo.png")));
+        let mut table = StringTable::default();
+        table.paths.insert("logo.png".into(), "logo_de.png".into());
+        pending.clone().into_untyped().commit_ok(table);
+        localization.update();
+        assert_eq!(
+            resource_manager
+                .state()
+                .locale_settings()
+                .paths
+                .get(Path::new("logo.png")),
+            Some(&PathBuf::from("logo_de.png"))
+        );
+
+        localization.remove_tables("de");
+        assert_eq!(
+            resource_manager
+                .state()
+                .locale_settings()
+                .paths
+                .get(Path::new("voice.ogg")),
+            Some(&PathBuf::from("voice_en_us.ogg"))
+        );
+        localization.set_fallback_locale("de-AT");
+        let settings = resource_manager.state().locale_settings().clone();
+        assert_eq!(settings.fallback, None);
+        assert!(!settings.paths.contains_key(Path::new("voice.ogg")));
+    }
+}
@@ -39,8 +39,12 @@ impl ResourceLoader for MaterialLoader {
         Box::pin(async move {
             let path = material.path().to_path_buf();
 
-            match Material::from_file(&path, io.as_ref(), resource_manager).await {
+            match Material::from_file(&path, io.as_ref(), resource_manager.clone()).await {
                 Ok(shader_state) => {
+                    resource_manager
+                        .wait_for_data_dependencies(&material, &shader_state)
+                        .await;
+
                     Log::info(format!("Material {:?} is loaded!", path));
 
                     material.commit_ok(shader_state);
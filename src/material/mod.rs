@@ -5,7 +5,12 @@
 #![warn(missing_docs)]
 
 use crate::{
-    asset::{io::ResourceIo, manager::ResourceManager, Resource, ResourceData, ResourceStateRef},
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData, ResourceStateRef,
+    },
     core::{
         algebra::{Matrix2, Matrix3, Matrix4, Vector2, Vector3, Vector4},
         color::Color,
@@ -400,6 +405,9 @@ pub struct Material {
     is_embedded: bool,
     shader: ShaderResource,
     properties: FxHashMap<ImmutableString, PropertyValue>,
+    permutation: Option<String>,
+    #[reflect(hidden)]
+    dirty: bool,
 }
 
 impl Visit for Material {
@@ -422,6 +430,7 @@ impl Visit for Material {
             shader.visit("Shader", &mut region)?;
             self.shader = shader;
             self.properties.visit("Properties", &mut region)?;
+            let _ = self.permutation.visit("Permutation", &mut region);
         }
 
         Ok(())
@@ -467,12 +476,22 @@ impl ResourceData for Material {
 
     fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
         let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
         let old = std::mem::replace(&mut self.is_embedded, true);
         self.visit("Material", &mut visitor)?;
-        visitor.save_binary(path)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
         self.is_embedded = old;
+        self.dirty = false;
         Ok(())
     }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
 }
 
 /// A set of possible errors that can occur when working with materials.
@@ -533,6 +552,11 @@ impl Display for MaterialError {
 }
 
 impl Material {
+    /// Current version of the format of material files. It must be bumped every time when the format
+    /// is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
     /// Creates a new instance of material with the standard shader. For the full list
     /// of properties of the standard material see [shader module docs](self::shader).
     ///
@@ -642,6 +666,8 @@ impl Material {
             shader,
             is_embedded: true,
             properties: property_values,
+            permutation: None,
+            dirty: false,
         }
     }
 
@@ -660,8 +686,15 @@ impl Material {
             is_embedded: false,
             shader: Default::default(),
             properties: Default::default(),
+            permutation: None,
+            dirty: false,
         };
         let mut visitor = Visitor::load_from_memory(&content)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
         visitor.blackboard.register(Arc::new(resource_manager));
         material.visit("Material", &mut visitor)?;
         // This could be changed during deserialization, we must keep the flag `false`.
@@ -885,6 +918,19 @@ impl Material {
     pub fn properties(&self) -> &FxHashMap<ImmutableString, PropertyValue> {
         &self.properties
     }
+
+    /// Returns a name of the shader permutation, that is used by the material.
+    pub fn permutation(&self) -> Option<&str> {
+        self.permutation.as_deref()
+    }
+
+    /// Sets a name of the shader permutation, that will be used by the material. `None` means that
+    /// the shader is used without permutation defines. The renderer uses the shader without the
+    /// permutation if the shader has no permutation with the given name. See "Defines and
+    /// permutations" section of [`shader`] module docs for more info.
+    pub fn set_permutation(&mut self, permutation: Option<String>) {
+        self.permutation = permutation;
+    }
 }
 
 /// Shared material is a material instance that can be used across multiple objects. It is useful
@@ -0,0 +1,151 @@
+//! `#include` directive resolution for shader source code.
+
+use crate::{asset::io::ResourceIo, material::shader::ShaderError};
+use std::path::{Component, Path, PathBuf};
+
+/// Parses `#include "path"` directive and returns the path, if the line is a directive.
+fn parse_include(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("#include")?.trim();
+    rest.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Lexically normalizes the given path by removing `.` and resolving `..` components, so the same
+/// file is always identified by the same path.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Searches for an included file. The path is relative to the directory of the including file
+/// first, and relative to the resource root (working directory) second.
+async fn resolve_include(
+    dir: &Path,
+    include: &str,
+    io: &dyn ResourceIo,
+) -> Result<PathBuf, ShaderError> {
+    for candidate in [dir.join(include), PathBuf::from(include)] {
+        let candidate = normalize_path(&candidate);
+        if io.is_file(&candidate).await {
+            return Ok(candidate);
+        }
+    }
+    Err(ShaderError::IncludeNotFound {
+        dir: dir.to_path_buf(),
+        include: include.to_owned(),
+    })
+}
+
+/// Replaces every `#include "path"` directive in the given source code with the content of the
+/// file. Includes are resolved recursively, relative to the directory of the including file (`dir`
+/// for the source code itself). Every file is included only once, paths of the included files are
+/// added to `included`, it also allows to skip files, that were included already.
+pub(crate) async fn expand_includes(
+    source: &str,
+    dir: &Path,
+    io: &dyn ResourceIo,
+    included: &mut Vec<PathBuf>,
+) -> Result<String, ShaderError> {
+    // Keep the source code intact if there's nothing to include.
+    if !source.lines().any(|line| parse_include(line).is_some()) {
+        return Ok(source.to_owned());
+    }
+
+    let mut output = String::with_capacity(source.len());
+    let mut stack = vec![(
+        dir.to_path_buf(),
+        source
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+            .into_iter(),
+    )];
+    while let Some((dir, lines)) = stack.last_mut() {
+        let Some(line) = lines.next() else {
+            stack.pop();
+            continue;
+        };
+
+        match parse_include(&line) {
+            Some(include) => {
+                let dir = dir.clone();
+                let path = resolve_include(&dir, include, io).await?;
+                if !included.contains(&path) {
+                    let content = io.load_file(&path).await?;
+                    let lines = String::from_utf8_lossy(&content)
+                        .lines()
+                        .map(str::to_owned)
+                        .collect::<Vec<_>>();
+                    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                    included.push(path);
+                    stack.push((dir, lines.into_iter()));
+                }
+            }
+            None => {
+                output += &line;
+                output.push('\n');
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{asset::io::memory::MemoryResourceIo, core::futures::executor::block_on};
+
+    #[test]
+    fn test_expand_includes() {
+        let io = MemoryResourceIo::new();
+        io.add_file(
+            Path::new("shaders/lib/lighting.glsl"),
+            b"#include \"../common.glsl\"\nfloat light();".to_vec(),
+        );
+        io.add_file(
+            Path::new("shaders/common.glsl"),
+            b"float common();".to_vec(),
+        );
+
+        let source =
+            "#include \"lib/lighting.glsl\"\n  #include \"shaders/common.glsl\"\nvoid main() {}";
+        let mut included = Vec::new();
+        let expanded = block_on(expand_includes(
+            source,
+            Path::new("shaders"),
+            &io,
+            &mut included,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            expanded,
+            "float common();\nfloat light();\nvoid main() {}\n"
+        );
+        assert_eq!(
+            included,
+            [
+                PathBuf::from("shaders/lib/lighting.glsl"),
+                PathBuf::from("shaders/common.glsl")
+            ]
+        );
+
+        assert!(matches!(
+            block_on(expand_includes(
+                "#include \"missing.glsl\"",
+                Path::new("shaders"),
+                &io,
+                &mut Vec::new()
+            )),
+            Err(ShaderError::IncludeNotFound { .. })
+        ));
+    }
+}
@@ -3,14 +3,21 @@
 use std::sync::Arc;
 
 use crate::{
-    asset::loader::{BoxedLoaderFuture, ResourceLoader},
+    asset::{
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+    },
     core::{log::Log, uuid::Uuid, TypeUuidProvider},
     material::shader::Shader,
 };
 use fyrox_resource::{event::ResourceEventBroadcaster, io::ResourceIo, untyped::UntypedResource};
 
 /// Default implementation for shader loading.
-pub struct ShaderLoader;
+pub struct ShaderLoader {
+    /// Resource manager, that tracks files included by shaders, so the shaders are reloaded when
+    /// the files are changed.
+    pub resource_manager: ResourceManager,
+}
 
 impl ResourceLoader for ShaderLoader {
     fn extensions(&self) -> &[&str] {
@@ -28,11 +35,15 @@ impl ResourceLoader for ShaderLoader {
         reload: bool,
         io: Arc<dyn ResourceIo>,
     ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
         Box::pin(async move {
             let path = shader.path().to_path_buf();
 
             match Shader::from_file(&path, io.as_ref()).await {
                 Ok(shader_state) => {
+                    resource_manager
+                        .set_file_dependencies(&shader, shader_state.include_paths().to_vec());
+
                     Log::info(format!("Shader {:?} is loaded!", path));
 
                     shader.commit_ok(shader_state);
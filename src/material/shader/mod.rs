@@ -163,6 +163,9 @@
 //! | fyrox_blendShapesStorage   | `sampler3D`  | 3D texture of layered blend shape storage. Use `S_FetchBlendShapeOffsets` built-in method to fetch info.          |
 //! | fyrox_blendShapesWeights   | `float[128]` | Weights of all available blend shapes.                                                                            |
 //! | fyrox_blendShapesCount     | `int`        | Total amount of blend shapes.                                                                                     |
+//! | fyrox_viewProjectionMatrix | `mat4`       | World-to-clip-space transform.                                                                                    |
+//! | fyrox_useInstancing        | `bool`       | Whether the current draw call renders multiple instances or not. See "Instancing" section below.                  |
+//! | fyrox_instanceMatrices     | `sampler2D`  | World matrices of the instances packed into a texture. Use `S_FetchMatrix` with `gl_InstanceID` to fetch one.     |
 //!
 //! To use any of the properties, just define a uniform with an appropriate name:
 //!
@@ -173,6 +176,58 @@
 //!
 //! This list will be extended in future releases.
 //!
+//! ## Instancing
+//!
+//! The renderer automatically merges surfaces of different meshes, that share the same surface data and
+//! material, and draws them in a single instanced draw call. It happens only if a render pass of the shader
+//! defines `fyrox_useInstancing` uniform and only for surfaces without skinning, blend shapes and depth
+//! offset. When `fyrox_useInstancing` is `true`, `fyrox_worldMatrix` and `fyrox_worldViewProjection` are
+//! not valid and a world matrix of an instance must be fetched from `fyrox_instanceMatrices`:
+//!
+//! ```glsl
+//! mat4 worldMatrix = fyrox_useInstancing
+//!     ? S_FetchMatrix(fyrox_instanceMatrices, gl_InstanceID)
+//!     : fyrox_worldMatrix;
+//! gl_Position = fyrox_viewProjectionMatrix * worldMatrix * vec4(vertexPosition, 1.0);
+//! ```
+//!
+//! Instancing could be disabled for a particular mesh using [`crate::scene::mesh::Mesh::set_instancing_allowed`].
+//!
+//! # Includes
+//!
+//! Source code of shaders, that are loaded from files, could include other files using `#include`
+//! directive. It allows to share common code between multiple shaders:
+//!
+//! ```glsl
+//! #include "lib/lighting.glsl"
+//! ```
+//!
+//! An included path is relative to the directory of the including file first, and relative to the
+//! resource root (working directory) second. Includes are resolved recursively and each file is
+//! included only once per shader program, so include guards are not needed. A shader is reloaded
+//! automatically when any of its included files is changed. Keep in mind, that includes are not
+//! resolved for shaders created from strings (see [`ShaderResourceExtension::from_str`]).
+//!
+//! # Defines and permutations
+//!
+//! A shader could have a set of compile-time defines, that are added to the source code of every
+//! render pass of the shader as `#define NAME VALUE`. Every render pass could also have its own
+//! defines. Permutations are named sets of defines, that produce variants of the same shader, a
+//! material selects one using [`crate::material::Material::set_permutation`]:
+//!
+//! ```ron
+//! (
+//!     defines: [(name: "MAX_LIGHTS", value: "16")],
+//!     permutations: [
+//!         (name: "Unlit", defines: [(name: "UNLIT")]),
+//!     ],
+//!     ...
+//! )
+//! ```
+//!
+//! Every permutation is compiled along with the shader itself, so keep the amount of permutations
+//! reasonable.
+//!
 //! # Drawing parameters
 //!
 //! Drawing parameters defines which GPU functions to use and at which state. For example, to render
@@ -250,6 +305,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+mod include;
 pub mod loader;
 
 /// A name of the standard shader.
@@ -309,6 +365,11 @@ pub const STANDARD_SHADER_SOURCES: [&str; 6] = [
     STANDARD_TERRAIN_SHADER_SRC,
 ];
 
+/// A virtual directory, in which source code of every standard shader is registered as an embedded
+/// resource (see [`crate::asset::embedded::EmbeddedResourceRegistry`]). It allows you to request a
+/// separate instance of a standard shader as usual, for example `__BUILT_IN__/shaders/Standard.shader`.
+pub const EMBEDDED_STANDARD_SHADERS_DIR: &str = "__BUILT_IN__/shaders";
+
 /// Internal state of the shader.
 ///
 /// # Notes
@@ -324,6 +385,9 @@ pub struct Shader {
 
     is_embedded: bool,
 
+    #[reflect(hidden)]
+    include_paths: Vec<PathBuf>,
+
     #[reflect(hidden)]
     pub(crate) cache_index: AtomicIndex,
 }
@@ -388,7 +452,7 @@ impl Default for SamplerFallback {
 }
 
 /// Shader property with default value.
-#[derive(Deserialize, Debug, PartialEq, Reflect)]
+#[derive(Deserialize, Debug, PartialEq, Clone, Reflect)]
 pub enum PropertyKind {
     /// Real number.
     Float(f32),
@@ -486,7 +550,7 @@ impl Default for PropertyKind {
 }
 
 /// Shader property definition.
-#[derive(Default, Deserialize, Debug, PartialEq, Reflect)]
+#[derive(Default, Deserialize, Debug, PartialEq, Clone, Reflect)]
 pub struct PropertyDefinition {
     /// A name of the property.
     pub name: String,
@@ -494,8 +558,29 @@ pub struct PropertyDefinition {
     pub kind: PropertyKind,
 }
 
+/// A compile-time define, that is added to the source code of shaders as `#define NAME VALUE`.
+/// See "Defines and permutations" section of [module docs](self) for more info.
+#[derive(Default, Deserialize, Debug, PartialEq, Eq, Clone, Reflect)]
+pub struct ShaderDefine {
+    /// A name of the define.
+    pub name: String,
+    /// An optional value of the define.
+    #[serde(default)]
+    pub value: String,
+}
+
+/// A named set of defines, that produces a variant of a shader. See "Defines and permutations"
+/// section of [module docs](self) for more info.
+#[derive(Default, Deserialize, Debug, PartialEq, Eq, Clone, Reflect)]
+pub struct ShaderPermutation {
+    /// A name of the permutation.
+    pub name: String,
+    /// A set of defines of the permutation.
+    pub defines: Vec<ShaderDefine>,
+}
+
 /// A render pass definition. See [`ShaderResource`] docs for more info about render passes.
-#[derive(Default, Deserialize, Debug, PartialEq, Eq, Reflect)]
+#[derive(Default, Deserialize, Debug, PartialEq, Eq, Clone, Reflect)]
 pub struct RenderPassDefinition {
     /// A name of render pass.
     pub name: String,
@@ -505,10 +590,13 @@ pub struct RenderPassDefinition {
     pub vertex_shader: String,
     /// A source code of fragment shader.
     pub fragment_shader: String,
+    /// A set of defines, that are used by this render pass only.
+    #[serde(default)]
+    pub defines: Vec<ShaderDefine>,
 }
 
 /// A definition of the shader.
-#[derive(Default, Deserialize, Debug, PartialEq, Reflect)]
+#[derive(Default, Deserialize, Debug, PartialEq, Clone, Reflect)]
 pub struct ShaderDefinition {
     /// A name of the shader.
     pub name: String,
@@ -516,9 +604,52 @@ pub struct ShaderDefinition {
     pub passes: Vec<RenderPassDefinition>,
     /// A set of property definitions.
     pub properties: Vec<PropertyDefinition>,
+    /// A set of defines, that are used by every render pass.
+    #[serde(default)]
+    pub defines: Vec<ShaderDefine>,
+    /// A set of named permutations of the shader.
+    #[serde(default)]
+    pub permutations: Vec<ShaderPermutation>,
 }
 
 impl ShaderDefinition {
+    /// Searches for a permutation with the given name.
+    pub fn find_permutation(&self, name: &str) -> Option<&ShaderPermutation> {
+        self.permutations.iter().find(|p| p.name == name)
+    }
+
+    /// Returns vertex and fragment source code of the given render pass with every define of the
+    /// shader, the render pass and the permutation (if any) added at the beginning. If a define is
+    /// specified more than once, the latest value is used.
+    pub fn pass_source_code(
+        &self,
+        pass: &RenderPassDefinition,
+        permutation: Option<&ShaderPermutation>,
+    ) -> (String, String) {
+        let mut defines = Vec::<&ShaderDefine>::new();
+        let all = self
+            .defines
+            .iter()
+            .chain(pass.defines.iter())
+            .chain(permutation.into_iter().flat_map(|p| p.defines.iter()));
+        for define in all {
+            match defines.iter_mut().find(|d| d.name == define.name) {
+                Some(existing) => *existing = define,
+                None => defines.push(define),
+            }
+        }
+
+        let mut header = String::new();
+        for define in defines {
+            header += &format!("#define {} {}\n", define.name, define.value);
+        }
+
+        (
+            format!("{header}{}", pass.vertex_shader),
+            format!("{header}{}", pass.fragment_shader),
+        )
+    }
+
     fn from_buf(buf: Vec<u8>) -> Result<Self, ShaderError> {
         Ok(ron::de::from_reader(Cursor::new(buf))?)
     }
@@ -534,14 +665,38 @@ impl Shader {
         io: &dyn ResourceIo,
     ) -> Result<Self, ShaderError> {
         let content = io.load_file(path.as_ref()).await?;
+        let mut definition = ShaderDefinition::from_buf(content)?;
+
+        let dir = path.as_ref().parent().unwrap_or(Path::new(""));
+        let mut include_paths = Vec::new();
+        for pass in definition.passes.iter_mut() {
+            for source in [&mut pass.vertex_shader, &mut pass.fragment_shader] {
+                let mut included = Vec::new();
+                *source = include::expand_includes(source, dir, io, &mut included).await?;
+                for path in included {
+                    if !include_paths.contains(&path) {
+                        include_paths.push(path);
+                    }
+                }
+            }
+        }
+
         Ok(Self {
             path: path.as_ref().to_owned(),
-            definition: ShaderDefinition::from_buf(content)?,
+            definition,
             is_embedded: false,
+            include_paths,
             cache_index: Default::default(),
         })
     }
 
+    /// Returns paths of every file, that was included by the source code of the shader. The shader
+    /// is reloaded automatically when any of the files is changed (if file system watcher is
+    /// enabled).
+    pub fn include_paths(&self) -> &[PathBuf] {
+        &self.include_paths
+    }
+
     pub(crate) fn from_str<P: AsRef<Path>>(
         str: &str,
         path: P,
@@ -550,6 +705,7 @@ impl Shader {
         Ok(Self {
             path: path.as_ref().to_owned(),
             definition: ShaderDefinition::from_str(str)?,
+            include_paths: Default::default(),
             cache_index: Default::default(),
             is_embedded,
         })
@@ -590,6 +746,14 @@ pub enum ShaderError {
 
     /// A parsing error has occurred.
     ParseError(ron::error::SpannedError),
+
+    /// A file, that is included by the shader, does not exist.
+    IncludeNotFound {
+        /// A directory of the including file.
+        dir: PathBuf,
+        /// A path from the `#include` directive.
+        include: String,
+    },
 }
 
 impl Display for ShaderError {
@@ -601,6 +765,13 @@ impl Display for ShaderError {
             ShaderError::ParseError(v) => {
                 write!(f, "A parsing error has occurred {v:?}")
             }
+            ShaderError::IncludeNotFound { dir, include } => {
+                write!(
+                    f,
+                    "Unable to find included file {include} (relative to {})",
+                    dir.display()
+                )
+            }
         }
     }
 }
@@ -765,10 +936,32 @@ lazy_static! {
 
 #[cfg(test)]
 mod test {
-    use crate::material::shader::{
-        PropertyDefinition, PropertyKind, RenderPassDefinition, SamplerFallback, ShaderDefinition,
-        ShaderResource, ShaderResourceExtension,
+    use crate::{
+        asset::manager::ResourceManager,
+        engine::{initialize_resource_manager_loaders, SerializationContext},
+        material::shader::{
+            PropertyDefinition, PropertyKind, RenderPassDefinition, SamplerFallback, Shader,
+            ShaderDefine, ShaderDefinition, ShaderPermutation, ShaderResource,
+            ShaderResourceExtension, EMBEDDED_STANDARD_SHADERS_DIR,
+        },
     };
+    use std::{path::Path, sync::Arc};
+
+    #[test]
+    fn test_embedded_standard_shader() {
+        let resource_manager = ResourceManager::new();
+        initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+
+        let path = Path::new(EMBEDDED_STANDARD_SHADERS_DIR).join("Standard.shader");
+        let shader = resource_manager.request_sync::<Shader>(&path).unwrap();
+        assert_eq!(
+            shader.data_ref().definition,
+            ShaderResource::standard().data_ref().definition
+        );
+    }
 
     #[test]
     fn test_shader_load() {
@@ -812,7 +1005,7 @@ mod test {
             )
             "#;
 
-        let shader = ShaderResource::from_str(code, "test").unwrap();
+        let shader = ShaderResource::from_str(code, "test", false).unwrap();
         let data = shader.data_ref();
 
         let reference_definition = ShaderDefinition {
@@ -829,9 +1022,50 @@ mod test {
                 draw_parameters: Default::default(),
                 vertex_shader: "<CODE>".to_string(),
                 fragment_shader: "<CODE>".to_string(),
+                defines: Default::default(),
             }],
+            defines: Default::default(),
+            permutations: Default::default(),
         };
 
         assert_eq!(data.definition, reference_definition);
     }
+
+    #[test]
+    fn test_shader_defines() {
+        let define = |name: &str, value: &str| ShaderDefine {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+
+        let definition = ShaderDefinition {
+            passes: vec![RenderPassDefinition {
+                vertex_shader: "void main() {}".to_string(),
+                fragment_shader: "void main() {}".to_string(),
+                defines: vec![define("PASS", "")],
+                ..Default::default()
+            }],
+            defines: vec![define("MAX_LIGHTS", "16")],
+            permutations: vec![ShaderPermutation {
+                name: "Many".to_string(),
+                defines: vec![define("MAX_LIGHTS", "64")],
+            }],
+            ..Default::default()
+        };
+
+        let pass = &definition.passes[0];
+        let (vertex, _) = definition.pass_source_code(pass, None);
+        assert_eq!(
+            vertex,
+            "#define MAX_LIGHTS 16\n#define PASS \nvoid main() {}"
+        );
+
+        let permutation = definition.find_permutation("Many");
+        assert!(permutation.is_some());
+        let (_, fragment) = definition.pass_source_code(pass, permutation);
+        assert_eq!(
+            fragment,
+            "#define MAX_LIGHTS 64\n#define PASS \nvoid main() {}"
+        );
+    }
 }
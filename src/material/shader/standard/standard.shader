@@ -97,6 +97,9 @@
                 // required data to these uniforms.
                 uniform mat4 fyrox_worldMatrix;
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
@@ -162,15 +165,23 @@
                         localTangent = inputTangent;
                     }
 
-                    mat3 nm = mat3(fyrox_worldMatrix);
+                    mat4 worldMatrix = fyrox_worldMatrix;
+                    mat4 worldViewProjection = fyrox_worldViewProjection;
+                    if (fyrox_useInstancing)
+                    {
+                        worldMatrix = S_FetchMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                        worldViewProjection = fyrox_viewProjectionMatrix * worldMatrix;
+                    }
+
+                    mat3 nm = mat3(worldMatrix);
                     normal = normalize(nm * localNormal);
                     tangent = normalize(nm * localTangent);
                     binormal = normalize(vertexTangent.w * cross(normal, tangent));
                     texCoord = vertexTexCoord;
-                    position = vec3(fyrox_worldMatrix * localPosition);
+                    position = vec3(worldMatrix * localPosition);
                     secondTexCoord = vertexSecondTexCoord;
 
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    gl_Position = worldViewProjection * localPosition;
                 }
                 "#,
             fragment_shader:
@@ -291,6 +302,9 @@
                 layout(location = 6) in vec4 boneIndices;
 
                 uniform mat4 fyrox_worldViewProjection;
+                uniform mat4 fyrox_viewProjectionMatrix;
+                uniform bool fyrox_useInstancing;
+                uniform sampler2D fyrox_instanceMatrices;
                 uniform bool fyrox_useSkeletalAnimation;
                 uniform sampler2D fyrox_boneMatrices;
                 uniform sampler3D fyrox_blendShapesStorage;
@@ -335,7 +349,13 @@
                     {
                         localPosition = inputPosition;
                     }
-                    gl_Position = fyrox_worldViewProjection * localPosition;
+                    mat4 worldViewProjection = fyrox_worldViewProjection;
+                    if (fyrox_useInstancing)
+                    {
+                        worldViewProjection = fyrox_viewProjectionMatrix
+                            * S_FetchMatrix(fyrox_instanceMatrices, gl_InstanceID);
+                    }
+                    gl_Position = worldViewProjection * localPosition;
                     texCoord = vertexTexCoord;
                 }
                "#,
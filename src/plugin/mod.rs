@@ -11,7 +11,9 @@ use crate::{
     },
     event::Event,
     gui::{message::UiMessage, UserInterface},
+    localization::Localization,
     scene::{Scene, SceneContainer},
+    script::service::ScriptServices,
 };
 use fyrox_core::visitor::VisitError;
 use std::{any::Any, path::Path, sync::Arc};
@@ -60,9 +62,17 @@ pub struct PluginContext<'a, 'b> {
     /// them. See [`ResourceManager`] docs for more info.
     pub resource_manager: &'a ResourceManager,
 
+    /// A reference to the localization service, it can be used to get localized texts and to
+    /// change the current locale. See [`Localization`] docs for more info.
+    pub localization: &'a mut Localization,
+
     /// A reference to user interface instance.
     pub user_interface: &'a mut UserInterface,
 
+    /// A container of services, that are available to scripts. Register game services here, so
+    /// scripts could get them from their contexts. See [`ScriptServices`] docs for more info.
+    pub services: &'a mut ScriptServices,
+
     /// A reference to the graphics_context, it contains a reference to the window and the current renderer.
     /// It could be [`GraphicsContext::Uninitialized`] if your application is suspended (possible only on
     /// Android; it is safe to call [`GraphicsContext::as_initialized_ref`] or [`GraphicsContext::as_initialized_mut`]
@@ -87,6 +87,23 @@ impl PersistentIdentifier {
         hasher.write_usize(index);
         Self(hasher.finish())
     }
+
+    /// Creates a new persistent identifier for per-instance data (world matrices) of the given
+    /// batch, that is used when the batch is rendered using hardware instancing. The matrices are
+    /// included in the identifier, because the same batch could have different sets of visible
+    /// instances for different cameras (or scenes) in the same frame.
+    pub fn new_instanced(batch: &RenderDataBatch, world_matrices: &[Matrix4<f32>]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(batch.data.key());
+        hasher.write_u64(batch.material.key() as u64);
+        hasher.write_usize(world_matrices.len());
+        for matrix in world_matrices {
+            for element in matrix.iter() {
+                hasher.write_u32(element.to_bits());
+            }
+        }
+        Self(hasher.finish())
+    }
 }
 
 /// A set of data of a surface for rendering.  
@@ -107,6 +124,9 @@ pub struct SurfaceInstanceData {
     pub persistent_identifier: PersistentIdentifier,
     /// A handle of a node that emitted this surface data. Could be none, if there's no info about scene node.
     pub node_handle: Handle<Node>,
+    /// Whether the instance could be merged with other instances of its batch and drawn using hardware
+    /// instancing or not. See [`RenderDataBatch::instanced_transforms`] for more info.
+    pub allow_instancing: bool,
 }
 
 /// A set of surface instances that share the same vertex/index data and a material.
@@ -129,6 +149,38 @@ pub struct RenderDataBatch {
     sort_index: u64,
 }
 
+impl RenderDataBatch {
+    /// Returns `true` if the given instance of the batch could be drawn using hardware instancing. It
+    /// is possible only for instances that opted in for instancing and that have no per-instance data
+    /// other than world transform (no skinning, no blend shapes, no depth offset, full element range).
+    pub fn is_instanceable(&self, instance: &SurfaceInstanceData) -> bool {
+        instance.allow_instancing
+            && !self.is_skinned
+            && instance.bone_matrices.is_empty()
+            && instance.blend_shapes_weights.is_empty()
+            && instance.depth_offset == 0.0
+            && matches!(instance.element_range, ElementRange::Full)
+    }
+
+    /// Collects world transforms of every instance, that could be drawn in a single instanced draw
+    /// call (see [`Self::is_instanceable`]). Returns an empty list if there are less than two such
+    /// instances, since there's no gain from instancing in this case. The rest of the instances must
+    /// be drawn one-by-one.
+    pub fn instanced_transforms(&self) -> Vec<Matrix4<f32>> {
+        let transforms = self
+            .instances
+            .iter()
+            .filter(|instance| self.is_instanceable(instance))
+            .map(|instance| instance.world_transform)
+            .collect::<Vec<_>>();
+        if transforms.len() > 1 {
+            transforms
+        } else {
+            Vec::new()
+        }
+    }
+}
+
 impl Debug for RenderDataBatch {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -287,6 +339,7 @@ impl RenderDataBatchStorage {
                         element_range: Default::default(),
                         persistent_identifier,
                         node_handle,
+                        allow_instancing: false,
                     },
                 ],
                 material: material.clone(),
@@ -361,3 +414,89 @@ impl RenderDataBatchStorage {
         self.batches.sort_unstable_by_key(|b| b.sort_index);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Point3, Vector2, Vector3},
+            sstorage::ImmutableString,
+        },
+        material::{Material, MaterialResource},
+        renderer::batch::{ObserverInfo, PersistentIdentifier, RenderDataBatchStorage},
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+            transform::TransformBuilder,
+        },
+    };
+
+    fn observer(position: Vector3<f32>, fov: f32) -> ObserverInfo {
+        ObserverInfo {
+            observer_position: position,
+            z_near: 0.1,
+            z_far: 100.0,
+            view_matrix: Matrix4::look_at_rh(
+                &Point3::from(position),
+                &Point3::from(position + Vector3::z()),
+                &Vector3::y(),
+            ),
+            projection_matrix: Matrix4::new_perspective(1.0, fov, 0.1, 100.0),
+        }
+    }
+
+    #[test]
+    fn instanced_identifier_depends_on_visible_instances() {
+        let mut graph = Graph::new();
+        let data = SurfaceSharedData::new(SurfaceData::make_cube(Matrix4::identity()));
+        let material = MaterialResource::new_ok(Material::standard());
+        for x in [-10.0, -1.0, 1.0, 10.0] {
+            MeshBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, 0.0, 0.0))
+                        .build(),
+                ),
+            )
+            .with_surfaces(vec![SurfaceBuilder::new(data.clone())
+                .with_material(material.clone())
+                .build()])
+            .build(&mut graph);
+        }
+        graph.update(Vector2::new(100.0, 100.0), 0.0, Default::default());
+
+        let instanced = |observer_info: ObserverInfo| {
+            let storage = RenderDataBatchStorage::from_graph(
+                &graph,
+                observer_info,
+                ImmutableString::new("GBuffer"),
+            );
+            let batch = storage
+                .batches
+                .iter()
+                .find(|batch| batch.data == data)
+                .unwrap();
+            let transforms = batch.instanced_transforms();
+            (
+                transforms.len(),
+                PersistentIdentifier::new_instanced(batch, &transforms),
+            )
+        };
+
+        // The first camera sees every mesh, the second one only the two in the middle.
+        let (wide_count, wide) = instanced(observer(Vector3::new(0.0, 0.0, -30.0), 1.5));
+        let (narrow_count, narrow) = instanced(observer(Vector3::new(0.0, 0.0, -5.0), 0.5));
+        assert_eq!(wide_count, 4);
+        assert_eq!(narrow_count, 2);
+        // Both cameras are rendered in the same frame, so they must not share instance matrices.
+        assert_ne!(wide, narrow);
+
+        // Same set of visible instances could share the matrices.
+        let (_, same) = instanced(observer(Vector3::new(0.0, 0.0, -30.0), 1.5));
+        assert_eq!(wide, same);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
         sparse::SparseBuffer,
         sstorage::ImmutableString,
     },
-    material::shader::{Shader, ShaderResource},
+    material::shader::{Shader, ShaderPermutation, ShaderResource},
     renderer::{
         cache::CacheEntry,
         framework::{framebuffer::DrawParameters, gpu_program::GpuProgram, state::PipelineState},
@@ -22,19 +22,27 @@ pub struct RenderPassData {
 
 pub struct ShaderSet {
     pub render_passes: FxHashMap<ImmutableString, RenderPassData>,
+    pub permutations: FxHashMap<String, FxHashMap<ImmutableString, RenderPassData>>,
 }
 
 impl ShaderSet {
-    pub fn new(state: &mut PipelineState, shader: &Shader) -> Option<Self> {
+    fn create_render_passes(
+        state: &mut PipelineState,
+        shader: &Shader,
+        permutation: Option<&ShaderPermutation>,
+    ) -> Option<FxHashMap<ImmutableString, RenderPassData>> {
         let mut map = FxHashMap::default();
         for render_pass in shader.definition.passes.iter() {
-            let program_name = format!("{}_{}", shader.definition.name, render_pass.name);
-            match GpuProgram::from_source(
-                state,
-                &program_name,
-                &render_pass.vertex_shader,
-                &render_pass.fragment_shader,
-            ) {
+            let program_name = match permutation {
+                Some(permutation) => format!(
+                    "{}_{}_{}",
+                    shader.definition.name, render_pass.name, permutation.name
+                ),
+                None => format!("{}_{}", shader.definition.name, render_pass.name),
+            };
+            let (vertex_shader, fragment_shader) =
+                shader.definition.pass_source_code(render_pass, permutation);
+            match GpuProgram::from_source(state, &program_name, &vertex_shader, &fragment_shader) {
                 Ok(gpu_program) => {
                     map.insert(
                         ImmutableString::new(&render_pass.name),
@@ -56,8 +64,37 @@ impl ShaderSet {
                 }
             };
         }
+        Some(map)
+    }
+
+    pub fn new(state: &mut PipelineState, shader: &Shader) -> Option<Self> {
+        let render_passes = Self::create_render_passes(state, shader, None)?;
+
+        let mut permutations = FxHashMap::default();
+        for permutation in shader.definition.permutations.iter() {
+            permutations.insert(
+                permutation.name.clone(),
+                Self::create_render_passes(state, shader, Some(permutation))?,
+            );
+        }
+
+        Some(Self {
+            render_passes,
+            permutations,
+        })
+    }
 
-        Some(Self { render_passes: map })
+    /// Returns a render pass of the given permutation, or a render pass of the shader without
+    /// permutation defines if there's no such permutation.
+    pub fn render_pass(
+        &self,
+        permutation: Option<&str>,
+        name: &ImmutableString,
+    ) -> Option<&RenderPassData> {
+        permutation
+            .and_then(|permutation| self.permutations.get(permutation))
+            .unwrap_or(&self.render_passes)
+            .get(name)
     }
 }
 
@@ -90,12 +90,40 @@ impl TextureCache {
                     let data_hash = texture.data_hash();
                     if entry.value_hash != data_hash {
                         let mut tex = entry.borrow_mut();
-                        if let Err(e) = tex.bind_mut(state, 0).set_data(
-                            texture.kind().into(),
-                            texture.pixel_kind().into(),
-                            texture.mip_count() as usize,
-                            Some(texture.data()),
-                        ) {
+                        // Upload only changed regions, if the texture was changed using region
+                        // updates only. Fallback to full upload if it fails for some reason.
+                        let regions_uploaded = texture
+                            .updated_regions_since(entry.value_hash)
+                            .is_some_and(|mut regions| {
+                                regions.all(|region| {
+                                    texture.region_data(region).is_some_and(|pixels| {
+                                        tex.bind_mut(state, 0)
+                                            .set_region(
+                                                region.x() as usize,
+                                                region.y() as usize,
+                                                region.w() as usize,
+                                                region.h() as usize,
+                                                &pixels,
+                                            )
+                                            .is_ok()
+                                    })
+                                })
+                            });
+
+                        let result = if regions_uploaded {
+                            Ok(())
+                        } else {
+                            tex.bind_mut(state, 0)
+                                .set_data(
+                                    texture.kind().into(),
+                                    texture.pixel_kind().into(),
+                                    texture.mip_count() as usize,
+                                    Some(texture.data()),
+                                )
+                                .map(|_| ())
+                        };
+
+                        if let Err(e) = result {
                             Log::writeln(
                                 MessageKind::Error,
                                 format!(
@@ -0,0 +1,473 @@
+//! Frame capture allows to grab rendered frames as textures or to record them as clips. See
+//! [`FrameCapture`] docs for more info.
+
+use crate::{
+    core::{
+        futures::{
+            channel::mpsc::{self, Sender},
+            Future, StreamExt,
+        },
+        instant,
+        log::Log,
+        parking_lot::Mutex,
+    },
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Frame encoder receives every frame, that was rendered while a recording is active. See
+/// [`FrameCapture::start_recording`] for more info.
+pub trait FrameEncoder: Send {
+    /// Encodes a frame. Pixels are stored in RGBA8 format, row-by-row, from top to bottom.
+    /// `timestamp` is the time passed since the start of the recording.
+    fn encode_frame(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        timestamp: Duration,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Called once when the recording is stopped. Encoders, that write container formats, should
+    /// finalize their output here.
+    fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+/// An encoder, that saves every frame as a separate PNG image (`frame_00000.png`, `frame_00001.png`,
+/// etc.) in the given folder. The sequence could be converted to a video using any external tool,
+/// for example: `ffmpeg -framerate 60 -i frame_%05d.png clip.mp4`.
+pub struct PngSequenceEncoder {
+    folder: PathBuf,
+    frame: usize,
+}
+
+impl PngSequenceEncoder {
+    /// Creates a new encoder, that writes frames into the given folder. The folder is created if
+    /// it does not exist.
+    pub fn new<P: AsRef<Path>>(folder: P) -> Self {
+        Self {
+            folder: folder.as_ref().to_path_buf(),
+            frame: 0,
+        }
+    }
+}
+
+impl FrameEncoder for PngSequenceEncoder {
+    fn encode_frame(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        _timestamp: Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.frame == 0 {
+            std::fs::create_dir_all(&self.folder)?;
+        }
+        let path = self.folder.join(format!("frame_{:05}.png", self.frame));
+        image::save_buffer(path, pixels, width, height, image::ColorType::Rgba8)?;
+        self.frame += 1;
+        Ok(())
+    }
+}
+
+struct EncoderFrame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    timestamp: Duration,
+}
+
+// Maximum amount of frames, that wait for encoding. Every frame is a full RGBA copy of the
+// screen, so frames are dropped when the encoder cannot keep up with the frame rate.
+const MAX_QUEUED_FRAMES: usize = 8;
+
+// Runs encoding and file writing off the render thread.
+fn spawn_job(job: impl Future<Output = ()> + Send + 'static) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::thread::spawn(move || crate::core::futures::executor::block_on(job));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::core::wasm_bindgen_futures::spawn_local(job);
+    }
+}
+
+struct Recording {
+    // Frames are encoded in order in a separate job, see `spawn_encoder`.
+    frames_sender: Sender<EncoderFrame>,
+    // Set by the encoder job when the encoder fails.
+    failed: Arc<AtomicBool>,
+    max_frames: Option<usize>,
+    frames: usize,
+    start: instant::Instant,
+}
+
+impl Recording {
+    fn new(encoder: Box<dyn FrameEncoder>, max_frames: Option<usize>) -> Self {
+        let (frames_sender, receiver) = mpsc::channel(MAX_QUEUED_FRAMES);
+        let failed = Arc::new(AtomicBool::new(false));
+        spawn_encoder(encoder, receiver, failed.clone());
+        Self {
+            frames_sender,
+            failed,
+            max_frames,
+            frames: 0,
+            start: instant::Instant::now(),
+        }
+    }
+
+    fn finish(mut self) {
+        // The job encodes the queued frames and finishes the encoder when the channel is closed.
+        self.frames_sender.close_channel();
+    }
+}
+
+fn spawn_encoder(
+    mut encoder: Box<dyn FrameEncoder>,
+    mut receiver: mpsc::Receiver<EncoderFrame>,
+    failed: Arc<AtomicBool>,
+) {
+    spawn_job(async move {
+        while let Some(frame) = receiver.next().await {
+            if let Err(err) =
+                encoder.encode_frame(frame.width, frame.height, &frame.pixels, frame.timestamp)
+            {
+                Log::err(format!("Unable to encode a frame. Reason: {err}"));
+                failed.store(true, atomic::Ordering::SeqCst);
+                break;
+            }
+        }
+        if let Err(err) = encoder.finish() {
+            Log::err(format!("Unable to finish frame recording. Reason: {err}"));
+        }
+    });
+}
+
+#[derive(Default)]
+struct State {
+    requests: Vec<(TextureResource, Option<PathBuf>)>,
+    recording: Option<Recording>,
+    dropped_frames: usize,
+}
+
+/// Frame capture grabs frames, rendered by the renderer (everything that is shown in the main
+/// window, including the UI). It could be used to implement photo modes, to attach screenshots
+/// to bug reports or to record short clips. Frame capture is a shallow handle, that could be
+/// cloned and shared, it is available to scripts as a service (see
+/// [`crate::script::service::ScriptServices`]) and via
+/// [`crate::engine::GraphicsContext::capture_frame`].
+///
+/// ```rust
+/// # use fyrox::{renderer::capture::FrameCapture, script::ScriptContext};
+/// fn take_photo(ctx: &mut ScriptContext) {
+///     let frame_capture = ctx.services.expect::<FrameCapture>();
+///     // The texture is in pending state until the next frame is rendered.
+///     let _photo = frame_capture.capture_frame();
+///     frame_capture.capture_frame_to_file("screenshot.png");
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct FrameCapture {
+    state: Arc<Mutex<State>>,
+}
+
+impl FrameCapture {
+    /// Requests the next rendered frame. Returns a texture resource in pending state, that will be
+    /// loaded as soon as the frame is rendered. The texture could be awaited just like any other
+    /// resource.
+    pub fn capture_frame(&self) -> TextureResource {
+        let texture = TextureResource::new_pending(Default::default());
+        self.state.lock().requests.push((texture.clone(), None));
+        texture
+    }
+
+    /// Requests the next rendered frame and saves it to the given path. The image format is defined
+    /// by the extension of the path.
+    pub fn capture_frame_to_file<P: AsRef<Path>>(&self, path: P) -> TextureResource {
+        let texture = TextureResource::new_pending(Default::default());
+        self.state
+            .lock()
+            .requests
+            .push((texture.clone(), Some(path.as_ref().to_path_buf())));
+        texture
+    }
+
+    /// Starts recording of rendered frames. Every rendered frame will be passed to the given
+    /// encoder until [`Self::stop_recording`] is called, until `max_frames` frames are recorded or
+    /// until the encoder fails. The encoder runs on a separate thread, so it does not block
+    /// rendering. Only a few frames could wait for encoding, if the encoder is slower than the
+    /// renderer, the rest of the frames are dropped (see [`Self::dropped_frames`]) and are not
+    /// counted in `max_frames`. Keep in mind, that reading frames back from GPU is quite slow, so
+    /// recording may reduce the frame rate. Active recording (if any) is stopped.
+    pub fn start_recording(&self, encoder: Box<dyn FrameEncoder>, max_frames: Option<usize>) {
+        self.stop_recording();
+        let mut state = self.state.lock();
+        state.recording = Some(Recording::new(encoder, max_frames));
+        state.dropped_frames = 0;
+    }
+
+    /// Stops active recording (if any). Its encoder is finalized on its thread, after every
+    /// recorded frame is encoded.
+    pub fn stop_recording(&self) {
+        let recording = self.state.lock().recording.take();
+        if let Some(recording) = recording {
+            recording.finish();
+        }
+    }
+
+    /// Returns `true` if there's an active recording.
+    pub fn is_recording(&self) -> bool {
+        self.state.lock().recording.is_some()
+    }
+
+    /// Returns the amount of frames of the last recording (active or stopped), that were dropped,
+    /// because the encoder could not keep up with the renderer. Frames with gaps in timestamps
+    /// are passed to the encoder in this case.
+    pub fn dropped_frames(&self) -> usize {
+        self.state.lock().dropped_frames
+    }
+
+    pub(crate) fn is_frame_needed(&self) -> bool {
+        let state = self.state.lock();
+        !state.requests.is_empty() || state.recording.is_some()
+    }
+
+    /// Passes a frame (RGBA8, from top to bottom) to every request and to the active encoder.
+    /// Encoding and file saving are done on separate threads, textures of requests without a file
+    /// are loaded immediately.
+    pub(crate) fn process_frame(&self, width: u32, height: u32, pixels: Vec<u8>) {
+        let mut state = self.state.lock();
+
+        let mut finished = false;
+        let mut dropped = false;
+        if let Some(recording) = state.recording.as_mut() {
+            if recording.failed.load(atomic::Ordering::SeqCst) {
+                finished = true;
+            } else {
+                let timestamp = instant::Instant::now() - recording.start;
+                match recording.frames_sender.try_send(EncoderFrame {
+                    width,
+                    height,
+                    pixels: pixels.clone(),
+                    timestamp,
+                }) {
+                    Ok(()) => {
+                        recording.frames += 1;
+                        finished = recording
+                            .max_frames
+                            .is_some_and(|max_frames| recording.frames >= max_frames);
+                    }
+                    Err(err) if err.is_full() => dropped = true,
+                    // The encoder job has stopped, it is reported by the failure flag.
+                    Err(_) => (),
+                }
+            }
+        }
+        if dropped {
+            state.dropped_frames += 1;
+        }
+        let recording = if finished {
+            state.recording.take()
+        } else {
+            None
+        };
+        let requests = std::mem::take(&mut state.requests);
+        drop(state);
+
+        if let Some(recording) = recording {
+            recording.finish();
+        }
+
+        for (texture, path) in requests {
+            let Some(mut data) = Texture::from_bytes(
+                TextureKind::Rectangle { width, height },
+                TexturePixelKind::RGBA8,
+                pixels.clone(),
+                true,
+            ) else {
+                continue;
+            };
+
+            if let Some(path) = path {
+                // The texture is loaded when the file is written.
+                spawn_job(async move {
+                    data.set_path(&path);
+                    match data.save() {
+                        Ok(()) => Log::info(format!("Frame was saved to {}.", path.display())),
+                        Err(err) => Log::err(format!(
+                            "Unable to save a frame to {}. Reason: {:?}",
+                            path.display(),
+                            err
+                        )),
+                    }
+                    texture.into_untyped().commit_ok(data);
+                });
+            } else {
+                texture.into_untyped().commit_ok(data);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Default)]
+    struct Counters {
+        frames: AtomicUsize,
+        finished: AtomicBool,
+    }
+
+    struct StubEncoder {
+        counters: Arc<Counters>,
+        fail: bool,
+    }
+
+    impl FrameEncoder for StubEncoder {
+        fn encode_frame(
+            &mut self,
+            _width: u32,
+            _height: u32,
+            pixels: &[u8],
+            _timestamp: Duration,
+        ) -> Result<(), Box<dyn Error>> {
+            assert_eq!(pixels.len(), 8);
+            self.counters.frames.fetch_add(1, atomic::Ordering::SeqCst);
+            if self.fail {
+                Err("Encoding failed!".into())
+            } else {
+                Ok(())
+            }
+        }
+
+        fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+            self.counters.finished.store(true, atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn wait_finished(counters: &Counters) {
+        let start = std::time::Instant::now();
+        while !counters.finished.load(atomic::Ordering::SeqCst) {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn frame_capture_process_frame() {
+        let frame_capture = FrameCapture::default();
+        assert!(!frame_capture.is_frame_needed());
+
+        let texture = frame_capture.capture_frame();
+        assert!(texture.is_loading());
+        assert!(frame_capture.is_frame_needed());
+        frame_capture.process_frame(2, 1, vec![255; 8]);
+        assert!(texture.is_ok());
+        assert!(!frame_capture.is_frame_needed());
+
+        // The recording stops by itself when enough frames are recorded.
+        let counters = Arc::new(Counters::default());
+        frame_capture.start_recording(
+            Box::new(StubEncoder {
+                counters: counters.clone(),
+                fail: false,
+            }),
+            Some(2),
+        );
+        frame_capture.process_frame(2, 1, vec![0; 8]);
+        assert!(frame_capture.is_recording());
+        frame_capture.process_frame(2, 1, vec![0; 8]);
+        assert!(!frame_capture.is_recording());
+        wait_finished(&counters);
+        assert_eq!(counters.frames.load(atomic::Ordering::SeqCst), 2);
+
+        // Failing encoder is finalized and the recording is stopped on the next frame.
+        let counters = Arc::new(Counters::default());
+        frame_capture.start_recording(
+            Box::new(StubEncoder {
+                counters: counters.clone(),
+                fail: true,
+            }),
+            None,
+        );
+        frame_capture.process_frame(2, 1, vec![0; 8]);
+        wait_finished(&counters);
+        assert!(frame_capture.is_recording());
+        frame_capture.process_frame(2, 1, vec![0; 8]);
+        assert!(!frame_capture.is_recording());
+        assert_eq!(counters.frames.load(atomic::Ordering::SeqCst), 1);
+    }
+
+    struct BlockingEncoder {
+        counters: Arc<Counters>,
+        released: Arc<AtomicBool>,
+    }
+
+    impl FrameEncoder for BlockingEncoder {
+        fn encode_frame(
+            &mut self,
+            _width: u32,
+            _height: u32,
+            _pixels: &[u8],
+            _timestamp: Duration,
+        ) -> Result<(), Box<dyn Error>> {
+            while !self.released.load(atomic::Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            self.counters.frames.fetch_add(1, atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+            self.counters.finished.store(true, atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn frame_capture_drops_frames_of_slow_encoder() {
+        let frame_capture = FrameCapture::default();
+        let counters = Arc::new(Counters::default());
+        let released = Arc::new(AtomicBool::new(false));
+        frame_capture.start_recording(
+            Box::new(BlockingEncoder {
+                counters: counters.clone(),
+                released: released.clone(),
+            }),
+            None,
+        );
+
+        let total = MAX_QUEUED_FRAMES * 4;
+        for _ in 0..total {
+            frame_capture.process_frame(2, 1, vec![0; 8]);
+        }
+        // The queue is bounded, so most of the frames are dropped instead of piling up.
+        let dropped = frame_capture.dropped_frames();
+        assert!(dropped >= total - MAX_QUEUED_FRAMES - 2);
+
+        released.store(true, atomic::Ordering::SeqCst);
+        frame_capture.stop_recording();
+        wait_finished(&counters);
+        assert_eq!(
+            counters.frames.load(atomic::Ordering::SeqCst) + dropped,
+            total
+        );
+        // The count is kept after the recording is stopped.
+        assert_eq!(frame_capture.dropped_frames(), dropped);
+    }
+}
@@ -8,7 +8,7 @@
 
 use crate::{
     core::{
-        algebra::{Vector2, Vector4},
+        algebra::{Matrix4, Vector2, Vector4},
         color::Color,
         math::{frustum::Frustum, Rect},
         scope_profile,
@@ -16,14 +16,15 @@ use crate::{
     },
     renderer::{
         apply_material,
-        batch::RenderDataBatchStorage,
+        batch::{PersistentIdentifier, RenderDataBatchStorage},
         cache::{shader::ShaderCache, texture::TextureCache},
         framework::{
-            error::FrameworkError, framebuffer::FrameBuffer, gpu_texture::GpuTexture,
-            state::PipelineState,
+            error::FrameworkError, framebuffer::FrameBuffer, gpu_program::BuiltInUniform,
+            gpu_texture::GpuTexture, state::PipelineState,
         },
         storage::MatrixStorageCache,
-        GeometryCache, LightData, MaterialContext, QualitySettings, RenderPassStatistics,
+        GeometryCache, InstancingData, LightData, MaterialContext, QualitySettings,
+        RenderPassStatistics,
     },
     scene::{
         camera::Camera,
@@ -166,11 +167,76 @@ impl ForwardRenderer {
                     .as_ref()
                     .and_then(|c| c.blend_shape_storage.clone());
 
-                if let Some(render_pass) = shader_cache
-                    .get(state, material.shader())
-                    .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
+                if let Some(render_pass) =
+                    shader_cache
+                        .get(state, material.shader())
+                        .and_then(|shader_set| {
+                            shader_set.render_pass(material.permutation(), &self.render_pass_name)
+                        })
                 {
+                    let instanced_transforms = if render_pass.program.built_in_uniform_locations
+                        [BuiltInUniform::UseInstancing as usize]
+                        .is_some()
+                    {
+                        batch.instanced_transforms()
+                    } else {
+                        Default::default()
+                    };
+
+                    if !instanced_transforms.is_empty() {
+                        statistics += framebuffer.draw_instances(
+                            instanced_transforms.len(),
+                            geometry,
+                            state,
+                            viewport,
+                            &render_pass.program,
+                            &render_pass.draw_params,
+                            |mut program_binding| {
+                                apply_material(MaterialContext {
+                                    material,
+                                    program_binding: &mut program_binding,
+                                    texture_cache,
+                                    world_matrix: &Matrix4::identity(),
+                                    view_projection_matrix: &initial_view_projection,
+                                    wvp_matrix: &initial_view_projection,
+                                    bone_matrices: &[],
+                                    use_skeletal_animation: false,
+                                    camera_position: &camera.global_position(),
+                                    camera_up_vector: &camera_up,
+                                    camera_side_vector: &camera_side,
+                                    z_near: camera.projection().z_near(),
+                                    z_far: camera.projection().z_far(),
+                                    use_pom: quality_settings.use_parallax_mapping,
+                                    light_position: &Default::default(),
+                                    blend_shapes_storage: blend_shapes_storage.as_ref(),
+                                    blend_shapes_weights: &[],
+                                    instancing: Some(InstancingData {
+                                        identifier: PersistentIdentifier::new_instanced(
+                                            batch,
+                                            &instanced_transforms,
+                                        ),
+                                        world_matrices: &instanced_transforms,
+                                    }),
+                                    normal_dummy: normal_dummy.clone(),
+                                    white_dummy: white_dummy.clone(),
+                                    black_dummy: black_dummy.clone(),
+                                    volume_dummy: volume_dummy.clone(),
+                                    matrix_storage,
+                                    persistent_identifier: batch.instances[0].persistent_identifier,
+                                    light_data: Some(&light_data),
+                                    ambient_light,
+                                    scene_depth: Some(&scene_depth),
+                                });
+                            },
+                        );
+                    }
+
                     for instance in batch.instances.iter() {
+                        if !instanced_transforms.is_empty() && batch.is_instanceable(instance) {
+                            // Already drawn in the instanced draw call above.
+                            continue;
+                        }
+
                         let view_projection = if instance.depth_offset != 0.0 {
                             let mut projection = camera.projection_matrix();
                             projection[14] -= instance.depth_offset;
@@ -205,6 +271,7 @@ impl ForwardRenderer {
                                     light_position: &Default::default(),
                                     blend_shapes_storage: blend_shapes_storage.as_ref(),
                                     blend_shapes_weights: &instance.blend_shapes_weights,
+                                    instancing: None,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
@@ -219,6 +219,28 @@ impl FrameBuffer {
         self.fbo
     }
 
+    /// Reads pixels of the first color attachment (or the back buffer) in RGBA8 format. Rows are
+    /// stored from bottom to top.
+    pub fn read_pixels(&self, state: &mut PipelineState, width: u32, height: u32) -> Vec<u8> {
+        let mut pixels = vec![0; width as usize * height as usize * 4];
+
+        state.set_framebuffer(self.id());
+
+        unsafe {
+            state.gl.read_pixels(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(&mut pixels),
+            );
+        }
+
+        pixels
+    }
+
     pub fn clear(
         &mut self,
         state: &mut PipelineState,
@@ -46,6 +46,8 @@ pub enum BuiltInUniform {
     LightsDirection,
     LightsParameters,
     AmbientLight,
+    UseInstancing,
+    InstanceMatrices,
     // Must be last.
     Count,
 }
@@ -437,6 +439,11 @@ fn fetch_built_in_uniform_locations(
     locations[BuiltInUniform::WorldViewProjectionMatrix as usize] =
         fetch_uniform_location(state, program, "fyrox_worldViewProjection");
 
+    locations[BuiltInUniform::UseInstancing as usize] =
+        fetch_uniform_location(state, program, "fyrox_useInstancing");
+    locations[BuiltInUniform::InstanceMatrices as usize] =
+        fetch_uniform_location(state, program, "fyrox_instanceMatrices");
+
     locations[BuiltInUniform::BoneMatrices as usize] =
         fetch_uniform_location(state, program, "fyrox_boneMatrices");
     locations[BuiltInUniform::UseSkeletalAnimation as usize] =
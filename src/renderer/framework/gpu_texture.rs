@@ -315,6 +315,107 @@ fn image_3d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize, depth
     }
 }
 
+/// Returns (type, format, internal format, swizzle mask) of the given pixel kind. Type and format are
+/// zero for compressed pixel kinds.
+fn gl_pixel_format(pixel_kind: PixelKind) -> (u32, u32, u32, Option<[i32; 4]>) {
+    match pixel_kind {
+        PixelKind::R32F => (glow::FLOAT, glow::RED, glow::R32F, None),
+        PixelKind::R16F => (glow::FLOAT, glow::RED, glow::R16F, None),
+        PixelKind::D32F => (
+            glow::FLOAT,
+            glow::DEPTH_COMPONENT,
+            glow::DEPTH_COMPONENT32F,
+            None,
+        ),
+        PixelKind::D16 => (
+            glow::UNSIGNED_SHORT,
+            glow::DEPTH_COMPONENT,
+            glow::DEPTH_COMPONENT16,
+            None,
+        ),
+        PixelKind::D24S8 => (
+            glow::UNSIGNED_INT_24_8,
+            glow::DEPTH_STENCIL,
+            glow::DEPTH24_STENCIL8,
+            None,
+        ),
+        PixelKind::RGBA8 => (glow::UNSIGNED_BYTE, glow::RGBA, glow::RGBA8, None),
+        PixelKind::SRGBA8 => (glow::UNSIGNED_BYTE, glow::RGBA, glow::SRGB8_ALPHA8, None),
+        PixelKind::RGB8 => (glow::UNSIGNED_BYTE, glow::RGB, glow::RGB8, None),
+        PixelKind::SRGB8 => (glow::UNSIGNED_BYTE, glow::RGB, glow::SRGB8, None),
+        PixelKind::RG8 => (glow::UNSIGNED_BYTE, glow::RG, glow::RG8, None),
+        PixelKind::R8 => (glow::UNSIGNED_BYTE, glow::RED, glow::R8, None),
+        PixelKind::R8UI => (glow::UNSIGNED_BYTE, glow::RED_INTEGER, glow::R8UI, None),
+        PixelKind::BGRA8 => (glow::UNSIGNED_BYTE, glow::BGRA, glow::RGBA8, None),
+        PixelKind::BGR8 => (glow::UNSIGNED_BYTE, glow::BGR, glow::RGB8, None),
+        PixelKind::RG16 => (glow::UNSIGNED_SHORT, glow::RG, glow::RG16, None),
+        PixelKind::R16 => (glow::UNSIGNED_SHORT, glow::RED, glow::R16, None),
+        PixelKind::RGB16 => (glow::UNSIGNED_SHORT, glow::RGB, glow::RGB16, None),
+        PixelKind::RGBA16 => (glow::UNSIGNED_SHORT, glow::RGBA, glow::RGBA16, None),
+        PixelKind::RGB10A2 => (
+            glow::UNSIGNED_INT_2_10_10_10_REV,
+            glow::RGBA,
+            glow::RGB10_A2,
+            None,
+        ),
+        PixelKind::DXT1RGB => (0, 0, GL_COMPRESSED_RGB_S3TC_DXT1_EXT, None),
+        PixelKind::DXT1RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT1_EXT, None),
+        PixelKind::DXT3RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT3_EXT, None),
+        PixelKind::DXT5RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT5_EXT, None),
+        PixelKind::R8RGTC => (0, 0, COMPRESSED_RED_RGTC1, None),
+        PixelKind::RG8RGTC => (0, 0, COMPRESSED_RG_RGTC2, None),
+        PixelKind::RGB32F => (glow::FLOAT, glow::RGB, glow::RGB32F, None),
+        PixelKind::RGBA32F => (glow::FLOAT, glow::RGBA, glow::RGBA32F, None),
+        PixelKind::RGBA16F => (glow::HALF_FLOAT, glow::RGBA, glow::RGBA16F, None),
+        PixelKind::RGB16F => (glow::HALF_FLOAT, glow::RGB, glow::RGB16F, None),
+        PixelKind::R11G11B10F => (glow::FLOAT, glow::RGB, glow::R11F_G11F_B10F, None),
+        PixelKind::L8 => (
+            glow::UNSIGNED_BYTE,
+            glow::RED,
+            glow::R8,
+            Some([
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::ONE as i32,
+            ]),
+        ),
+        PixelKind::LA8 => (
+            glow::UNSIGNED_BYTE,
+            glow::RG,
+            glow::RG8,
+            Some([
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::GREEN as i32,
+            ]),
+        ),
+        PixelKind::LA16 => (
+            glow::UNSIGNED_SHORT,
+            glow::RG,
+            glow::RG16,
+            Some([
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::GREEN as i32,
+            ]),
+        ),
+        PixelKind::L16 => (
+            glow::UNSIGNED_SHORT,
+            glow::RED,
+            glow::R16,
+            Some([
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::RED as i32,
+                glow::ONE as i32,
+            ]),
+        ),
+    }
+}
+
 fn image_2d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize) -> usize {
     let pixel_count = width * height;
     match pixel_kind {
@@ -672,102 +773,7 @@ impl<'a> TextureBinding<'a> {
                 .gl
                 .tex_parameter_i32(target, glow::TEXTURE_MAX_LEVEL, mip_count as i32 - 1);
 
-            let (type_, format, internal_format, swizzle_mask) = match pixel_kind {
-                PixelKind::R32F => (glow::FLOAT, glow::RED, glow::R32F, None),
-                PixelKind::R16F => (glow::FLOAT, glow::RED, glow::R16F, None),
-                PixelKind::D32F => (
-                    glow::FLOAT,
-                    glow::DEPTH_COMPONENT,
-                    glow::DEPTH_COMPONENT32F,
-                    None,
-                ),
-                PixelKind::D16 => (
-                    glow::UNSIGNED_SHORT,
-                    glow::DEPTH_COMPONENT,
-                    glow::DEPTH_COMPONENT16,
-                    None,
-                ),
-                PixelKind::D24S8 => (
-                    glow::UNSIGNED_INT_24_8,
-                    glow::DEPTH_STENCIL,
-                    glow::DEPTH24_STENCIL8,
-                    None,
-                ),
-                PixelKind::RGBA8 => (glow::UNSIGNED_BYTE, glow::RGBA, glow::RGBA8, None),
-                PixelKind::SRGBA8 => (glow::UNSIGNED_BYTE, glow::RGBA, glow::SRGB8_ALPHA8, None),
-                PixelKind::RGB8 => (glow::UNSIGNED_BYTE, glow::RGB, glow::RGB8, None),
-                PixelKind::SRGB8 => (glow::UNSIGNED_BYTE, glow::RGB, glow::SRGB8, None),
-                PixelKind::RG8 => (glow::UNSIGNED_BYTE, glow::RG, glow::RG8, None),
-                PixelKind::R8 => (glow::UNSIGNED_BYTE, glow::RED, glow::R8, None),
-                PixelKind::R8UI => (glow::UNSIGNED_BYTE, glow::RED_INTEGER, glow::R8UI, None),
-                PixelKind::BGRA8 => (glow::UNSIGNED_BYTE, glow::BGRA, glow::RGBA8, None),
-                PixelKind::BGR8 => (glow::UNSIGNED_BYTE, glow::BGR, glow::RGB8, None),
-                PixelKind::RG16 => (glow::UNSIGNED_SHORT, glow::RG, glow::RG16, None),
-                PixelKind::R16 => (glow::UNSIGNED_SHORT, glow::RED, glow::R16, None),
-                PixelKind::RGB16 => (glow::UNSIGNED_SHORT, glow::RGB, glow::RGB16, None),
-                PixelKind::RGBA16 => (glow::UNSIGNED_SHORT, glow::RGBA, glow::RGBA16, None),
-                PixelKind::RGB10A2 => (
-                    glow::UNSIGNED_INT_2_10_10_10_REV,
-                    glow::RGBA,
-                    glow::RGB10_A2,
-                    None,
-                ),
-                PixelKind::DXT1RGB => (0, 0, GL_COMPRESSED_RGB_S3TC_DXT1_EXT, None),
-                PixelKind::DXT1RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT1_EXT, None),
-                PixelKind::DXT3RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT3_EXT, None),
-                PixelKind::DXT5RGBA => (0, 0, GL_COMPRESSED_RGBA_S3TC_DXT5_EXT, None),
-                PixelKind::R8RGTC => (0, 0, COMPRESSED_RED_RGTC1, None),
-                PixelKind::RG8RGTC => (0, 0, COMPRESSED_RG_RGTC2, None),
-                PixelKind::RGB32F => (glow::FLOAT, glow::RGB, glow::RGB32F, None),
-                PixelKind::RGBA32F => (glow::FLOAT, glow::RGBA, glow::RGBA32F, None),
-                PixelKind::RGBA16F => (glow::HALF_FLOAT, glow::RGBA, glow::RGBA16F, None),
-                PixelKind::RGB16F => (glow::HALF_FLOAT, glow::RGB, glow::RGB16F, None),
-                PixelKind::R11G11B10F => (glow::FLOAT, glow::RGB, glow::R11F_G11F_B10F, None),
-                PixelKind::L8 => (
-                    glow::UNSIGNED_BYTE,
-                    glow::RED,
-                    glow::R8,
-                    Some([
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::ONE as i32,
-                    ]),
-                ),
-                PixelKind::LA8 => (
-                    glow::UNSIGNED_BYTE,
-                    glow::RG,
-                    glow::RG8,
-                    Some([
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::GREEN as i32,
-                    ]),
-                ),
-                PixelKind::LA16 => (
-                    glow::UNSIGNED_SHORT,
-                    glow::RG,
-                    glow::RG16,
-                    Some([
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::GREEN as i32,
-                    ]),
-                ),
-                PixelKind::L16 => (
-                    glow::UNSIGNED_SHORT,
-                    glow::RED,
-                    glow::R16,
-                    Some([
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::RED as i32,
-                        glow::ONE as i32,
-                    ]),
-                ),
-            };
+            let (type_, format, internal_format, swizzle_mask) = gl_pixel_format(pixel_kind);
 
             let is_compressed = pixel_kind.is_compressed();
 
@@ -970,6 +976,79 @@ impl<'a> TextureBinding<'a> {
 
         Ok(self)
     }
+
+    /// Uploads pixels of a rectangular region of the main level of a rectangle texture. It is much
+    /// faster than [`Self::set_data`] when only a small portion of a texture has changed. Compressed
+    /// textures are not supported.
+    pub fn set_region(
+        self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        data: &[u8],
+    ) -> Result<Self, FrameworkError> {
+        let GpuTextureKind::Rectangle {
+            width: texture_width,
+            height: texture_height,
+        } = self.texture.kind
+        else {
+            return Err(FrameworkError::Custom(
+                "Only rectangle textures support region updates!".to_string(),
+            ));
+        };
+
+        let pixel_kind = self.texture.pixel_kind;
+        if pixel_kind.is_compressed() {
+            return Err(FrameworkError::Custom(
+                "Compressed textures do not support region updates!".to_string(),
+            ));
+        }
+
+        if x + width > texture_width || y + height > texture_height {
+            return Err(FrameworkError::Custom(format!(
+                "Region at ({x}; {y}) of {width}x{height} size is out of bounds of {texture_width}x{texture_height} texture!"
+            )));
+        }
+
+        let expected_data_size = image_2d_size_bytes(pixel_kind, width, height);
+        if data.len() != expected_data_size {
+            return Err(FrameworkError::InvalidTextureData {
+                expected_data_size,
+                actual_data_size: data.len(),
+            });
+        }
+
+        let (type_, format, _, _) = gl_pixel_format(pixel_kind);
+
+        unsafe {
+            self.state.set_texture(
+                self.sampler_index,
+                glow::TEXTURE_2D,
+                Some(self.texture.texture),
+            );
+
+            if let Some(alignment) = pixel_kind.unpack_alignment() {
+                self.state
+                    .gl
+                    .pixel_store_i32(glow::UNPACK_ALIGNMENT, alignment);
+            }
+
+            self.state.gl.tex_sub_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                x as i32,
+                y as i32,
+                width as i32,
+                height as i32,
+                format,
+                type_,
+                glow::PixelUnpackData::Slice(data),
+            );
+        }
+
+        Ok(self)
+    }
 }
 
 const GL_COMPRESSED_RGB_S3TC_DXT1_EXT: u32 = 0x83F0;
@@ -19,7 +19,7 @@ use crate::{
     },
     renderer::{
         apply_material,
-        batch::RenderDataBatchStorage,
+        batch::{PersistentIdentifier, RenderDataBatchStorage},
         cache::shader::ShaderCache,
         framework::{
             error::FrameworkError,
@@ -27,7 +27,7 @@ use crate::{
                 Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
             },
             geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
-            gpu_program::GpuProgramBinding,
+            gpu_program::{BuiltInUniform, GpuProgramBinding},
             gpu_texture::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
@@ -36,7 +36,7 @@ use crate::{
         },
         gbuffer::decal::DecalShader,
         storage::MatrixStorageCache,
-        GeometryCache, MaterialContext, RenderPassStatistics, TextureCache,
+        GeometryCache, InstancingData, MaterialContext, RenderPassStatistics, TextureCache,
     },
     scene::{
         camera::Camera,
@@ -319,11 +319,76 @@ impl GBuffer {
                     .as_ref()
                     .and_then(|c| c.blend_shape_storage.clone());
 
-                if let Some(render_pass) = shader_cache
-                    .get(state, material.shader())
-                    .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
+                if let Some(render_pass) =
+                    shader_cache
+                        .get(state, material.shader())
+                        .and_then(|shader_set| {
+                            shader_set.render_pass(material.permutation(), &self.render_pass_name)
+                        })
                 {
+                    let instanced_transforms = if render_pass.program.built_in_uniform_locations
+                        [BuiltInUniform::UseInstancing as usize]
+                        .is_some()
+                    {
+                        batch.instanced_transforms()
+                    } else {
+                        Default::default()
+                    };
+
+                    if !instanced_transforms.is_empty() {
+                        statistics += self.framebuffer.draw_instances(
+                            instanced_transforms.len(),
+                            geometry,
+                            state,
+                            viewport,
+                            &render_pass.program,
+                            &render_pass.draw_params,
+                            |mut program_binding| {
+                                apply_material(MaterialContext {
+                                    material,
+                                    program_binding: &mut program_binding,
+                                    texture_cache,
+                                    matrix_storage,
+                                    world_matrix: &Matrix4::identity(),
+                                    view_projection_matrix: &initial_view_projection,
+                                    wvp_matrix: &initial_view_projection,
+                                    bone_matrices: &[],
+                                    use_skeletal_animation: false,
+                                    camera_position: &camera.global_position(),
+                                    camera_up_vector: &camera_up,
+                                    camera_side_vector: &camera_side,
+                                    z_near: camera.projection().z_near(),
+                                    use_pom: use_parallax_mapping,
+                                    light_position: &Default::default(),
+                                    blend_shapes_storage: blend_shapes_storage.as_ref(),
+                                    blend_shapes_weights: &[],
+                                    instancing: Some(InstancingData {
+                                        identifier: PersistentIdentifier::new_instanced(
+                                            batch,
+                                            &instanced_transforms,
+                                        ),
+                                        world_matrices: &instanced_transforms,
+                                    }),
+                                    normal_dummy: normal_dummy.clone(),
+                                    white_dummy: white_dummy.clone(),
+                                    black_dummy: black_dummy.clone(),
+                                    volume_dummy: volume_dummy.clone(),
+                                    persistent_identifier: batch.instances[0].persistent_identifier,
+                                    light_data: None,
+                                    ambient_light: Color::WHITE, // TODO
+                                    scene_depth: None,           // TODO. Add z-pre-pass.
+                                    z_far: camera.projection().z_far(),
+                                });
+                            },
+                        );
+                    }
+
                     for instance in batch.instances.iter() {
+                        if !instanced_transforms.is_empty() && batch.is_instanceable(instance) {
+                            // Already drawn in the instanced draw call above.
+                            continue;
+                        }
+
                         let apply_uniforms = |mut program_binding: GpuProgramBinding| {
                             let view_projection = if instance.depth_offset != 0.0 {
                                 let mut projection = camera.projection_matrix();
@@ -351,6 +416,7 @@ impl GBuffer {
                                 light_position: &Default::default(),
                                 blend_shapes_storage: blend_shapes_storage.as_ref(),
                                 blend_shapes_weights: &instance.blend_shapes_weights,
+                                instancing: None,
                                 normal_dummy: normal_dummy.clone(),
                                 white_dummy: white_dummy.clone(),
                                 black_dummy: black_dummy.clone(),
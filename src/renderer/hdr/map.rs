@@ -18,6 +18,13 @@ pub struct MapShader {
     pub max_luminance: UniformLocation,
     pub auto_exposure: UniformLocation,
     pub fixed_exposure: UniformLocation,
+    pub tonemapping: UniformLocation,
+    pub bloom_intensity: UniformLocation,
+    pub use_vignette: UniformLocation,
+    pub vignette_intensity: UniformLocation,
+    pub vignette_radius: UniformLocation,
+    pub vignette_smoothness: UniformLocation,
+    pub vignette_color: UniformLocation,
 }
 
 impl MapShader {
@@ -48,6 +55,18 @@ impl MapShader {
                 .uniform_location(state, &ImmutableString::new("autoExposure"))?,
             fixed_exposure: program
                 .uniform_location(state, &ImmutableString::new("fixedExposure"))?,
+            tonemapping: program.uniform_location(state, &ImmutableString::new("tonemapping"))?,
+            bloom_intensity: program
+                .uniform_location(state, &ImmutableString::new("bloomIntensity"))?,
+            use_vignette: program.uniform_location(state, &ImmutableString::new("useVignette"))?,
+            vignette_intensity: program
+                .uniform_location(state, &ImmutableString::new("vignetteIntensity"))?,
+            vignette_radius: program
+                .uniform_location(state, &ImmutableString::new("vignetteRadius"))?,
+            vignette_smoothness: program
+                .uniform_location(state, &ImmutableString::new("vignetteSmoothness"))?,
+            vignette_color: program
+                .uniform_location(state, &ImmutableString::new("vignetteColor"))?,
             program,
         })
     }
@@ -24,6 +24,7 @@ use crate::{
         },
         make_viewport_matrix, RenderPassStatistics,
     },
+    resource::post_effects::{Tonemapping, Vignette},
     scene::camera::{ColorGradingLut, Exposure},
 };
 use std::{cell::RefCell, rc::Rc};
@@ -33,6 +34,17 @@ mod downscale;
 mod luminance;
 mod map;
 
+/// Parameters of conversion of a HDR frame to LDR. They're taken either from a post effects
+/// stack or from a camera.
+pub struct LdrMappingParameters<'a> {
+    pub exposure: Exposure,
+    pub color_grading_lut: Option<&'a ColorGradingLut>,
+    pub use_color_grading: bool,
+    pub tonemapping: Tonemapping,
+    pub bloom_intensity: f32,
+    pub vignette: Option<Vignette>,
+}
+
 pub struct LumBuffer {
     framebuffer: FrameBuffer,
     size: usize,
@@ -258,16 +270,15 @@ impl HighDynamicRangeRenderer {
         ldr_framebuffer: &mut FrameBuffer,
         viewport: Rect<i32>,
         quad: &GeometryBuffer,
-        exposure: Exposure,
-        color_grading_lut: Option<&ColorGradingLut>,
-        use_color_grading: bool,
+        parameters: &LdrMappingParameters,
         texture_cache: &mut TextureCache,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         let shader = &self.map_shader;
         let frame_matrix = make_viewport_matrix(viewport);
         let avg_lum = self.adaptation_chain.avg_lum_texture();
 
-        let color_grading_lut_tex = color_grading_lut
+        let color_grading_lut_tex = parameters
+            .color_grading_lut
             .and_then(|l| texture_cache.get(state, l.lut_ref()))
             .unwrap_or_else(|| self.stub_lut.clone());
 
@@ -294,11 +305,22 @@ impl HighDynamicRangeRenderer {
                     .set_texture(&shader.hdr_sampler, &hdr_scene_frame)
                     .set_bool(
                         &shader.use_color_grading,
-                        use_color_grading && color_grading_lut.is_some(),
+                        parameters.use_color_grading && parameters.color_grading_lut.is_some(),
                     )
-                    .set_texture(&shader.color_map_sampler, &color_grading_lut_tex);
+                    .set_texture(&shader.color_map_sampler, &color_grading_lut_tex)
+                    .set_i32(&shader.tonemapping, parameters.tonemapping.shader_index())
+                    .set_f32(&shader.bloom_intensity, parameters.bloom_intensity)
+                    .set_bool(&shader.use_vignette, parameters.vignette.is_some());
+
+                if let Some(vignette) = parameters.vignette.as_ref() {
+                    program_binding
+                        .set_f32(&shader.vignette_intensity, vignette.intensity)
+                        .set_f32(&shader.vignette_radius, vignette.radius)
+                        .set_f32(&shader.vignette_smoothness, vignette.smoothness)
+                        .set_srgb_color(&shader.vignette_color, &vignette.color);
+                }
 
-                match exposure {
+                match parameters.exposure {
                     Exposure::Auto {
                         key_value,
                         min_luminance,
@@ -329,9 +351,7 @@ impl HighDynamicRangeRenderer {
         viewport: Rect<i32>,
         quad: &GeometryBuffer,
         dt: f32,
-        exposure: Exposure,
-        color_grading_lut: Option<&ColorGradingLut>,
-        use_color_grading: bool,
+        parameters: &LdrMappingParameters,
         texture_cache: &mut TextureCache,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         let mut stats = RenderPassStatistics::default();
@@ -345,9 +365,7 @@ impl HighDynamicRangeRenderer {
             ldr_framebuffer,
             viewport,
             quad,
-            exposure,
-            color_grading_lut,
-            use_color_grading,
+            parameters,
             texture_cache,
         )?;
         Ok(stats)
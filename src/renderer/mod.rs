@@ -15,6 +15,7 @@ pub mod framework;
 
 pub mod batch;
 pub mod cache;
+pub mod capture;
 pub mod debug_renderer;
 pub mod storage;
 pub mod ui_renderer;
@@ -32,7 +33,7 @@ mod skybox_shader;
 mod ssao;
 
 use crate::{
-    asset::{event::ResourceEvent, manager::ResourceManager},
+    asset::{event::ResourceEvent, manager::ResourceManager, ResourceStateRefMut},
     core::{
         algebra::{Matrix4, Vector2, Vector3},
         color::Color,
@@ -43,6 +44,7 @@ use crate::{
         reflect::prelude::*,
         scope_profile,
         sstorage::ImmutableString,
+        TypeUuidProvider,
     },
     gui::{draw::DrawingContext, UserInterface},
     material::{
@@ -53,6 +55,7 @@ use crate::{
         batch::{ObserverInfo, PersistentIdentifier, RenderDataBatchStorage},
         bloom::BloomRenderer,
         cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache, CacheEntry},
+        capture::FrameCapture,
         debug_renderer::DebugRenderer,
         flat_shader::FlatShader,
         forward_renderer::{ForwardRenderContext, ForwardRenderer},
@@ -71,12 +74,15 @@ use crate::{
         },
         fxaa::FxaaRenderer,
         gbuffer::{GBuffer, GBufferRenderContext},
-        hdr::HighDynamicRangeRenderer,
+        hdr::{HighDynamicRangeRenderer, LdrMappingParameters},
         light::{DeferredLightRenderer, DeferredRendererContext, LightingStatistics},
         storage::MatrixStorageCache,
         ui_renderer::{UiRenderContext, UiRenderer},
     },
-    resource::texture::{Texture, TextureKind, TextureResource},
+    resource::{
+        post_effects::Tonemapping,
+        texture::{Texture, TextureKind, TextureResource},
+    },
     scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
 };
 use fxhash::FxHashMap;
@@ -697,6 +703,55 @@ pub(crate) fn make_viewport_matrix(viewport: Rect<i32>) -> Matrix4<f32> {
     ))
 }
 
+/// Defines how much work the renderer can spend per frame on finalization of loaded resources -
+/// uploading textures to GPU or compiling shaders. Freshly loaded resources are finalized in the
+/// order of loading, everything that does not fit into the budget is postponed to the next frames.
+/// This spreads the work across multiple frames and prevents hitches when lots of resources are
+/// loaded at once. Textures and shaders have separate budgets, by default only textures are
+/// limited, see [`Renderer::set_texture_upload_budget`] and [`Renderer::set_shader_upload_budget`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ResourceUploadBudget {
+    /// Fixed amount of resources per frame.
+    Count(usize),
+    /// Maximum amount of time (in milliseconds) per frame. At least one resource is finalized per
+    /// frame, so the loading always progresses even if a single resource takes more time than the
+    /// budget.
+    TimeMs(f32),
+}
+
+impl Default for ResourceUploadBudget {
+    fn default() -> Self {
+        Self::Count(5)
+    }
+}
+
+// `None` budget means that there's no limit.
+struct UploadBudgetTracker {
+    budget: Option<ResourceUploadBudget>,
+    start_time: instant::Instant,
+    uploaded: usize,
+}
+
+impl UploadBudgetTracker {
+    fn new(budget: Option<ResourceUploadBudget>) -> Self {
+        Self {
+            budget,
+            start_time: instant::Instant::now(),
+            uploaded: 0,
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        match self.budget {
+            None => false,
+            Some(ResourceUploadBudget::Count(count)) => self.uploaded >= count,
+            Some(ResourceUploadBudget::TimeMs(time)) => {
+                self.uploaded > 0 && self.start_time.elapsed().as_secs_f32() * 1000.0 >= time
+            }
+        }
+    }
+}
+
 /// See module docs.
 pub struct Renderer {
     backbuffer: FrameBuffer,
@@ -735,10 +790,13 @@ pub struct Renderer {
     fxaa_renderer: FxaaRenderer,
     texture_event_receiver: Receiver<ResourceEvent>,
     shader_event_receiver: Receiver<ResourceEvent>,
+    texture_upload_budget: ResourceUploadBudget,
+    shader_upload_budget: Option<ResourceUploadBudget>,
     matrix_storage: MatrixStorageCache,
     // TextureId -> FrameBuffer mapping. This mapping is used for temporal frame buffers
     // like ones used to render UI instances.
     ui_frame_buffers: FxHashMap<usize, FrameBuffer>,
+    frame_capture: FrameCapture,
     // MUST BE LAST! Otherwise you'll get crash, because other parts of the renderer will
     // contain **pointer** to pipeline state. It must be dropped last!
     /// Pipeline state.
@@ -960,6 +1018,14 @@ impl<const N: usize> Default for LightData<N> {
     }
 }
 
+/// Per-instance data of an instanced draw call.
+pub(crate) struct InstancingData<'a> {
+    /// Identifier of the storage for world matrices of the instances.
+    pub identifier: PersistentIdentifier,
+    /// World matrices of every instance.
+    pub world_matrices: &'a [Matrix4<f32>],
+}
+
 pub(crate) struct MaterialContext<'a, 'b, 'c> {
     pub material: &'a Material,
     pub program_binding: &'a mut GpuProgramBinding<'b, 'c>,
@@ -977,6 +1043,7 @@ pub(crate) struct MaterialContext<'a, 'b, 'c> {
     pub light_position: &'a Vector3<f32>,
     pub blend_shapes_storage: Option<&'a TextureResource>,
     pub blend_shapes_weights: &'a [f32],
+    pub instancing: Option<InstancingData<'a>>,
     pub light_data: Option<&'a LightData>,
     pub ambient_light: Color,
     // TODO: Add depth pre-pass to remove Option here. Current architecture allows only forward
@@ -1025,6 +1092,29 @@ pub(crate) fn apply_material(ctx: MaterialContext) {
 
         ctx.program_binding.set_texture(location, storage.texture());
     }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::UseInstancing as usize] {
+        ctx.program_binding
+            .set_bool(location, ctx.instancing.is_some());
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::InstanceMatrices as usize] {
+        if let Some(instancing) = ctx.instancing.as_ref() {
+            let active_sampler = ctx.program_binding.active_sampler();
+
+            let storage = ctx
+                .matrix_storage
+                .try_bind_and_upload(
+                    ctx.program_binding.state,
+                    instancing.identifier,
+                    instancing.world_matrices,
+                    active_sampler,
+                )
+                .expect("Failed to upload instance matrices!");
+
+            ctx.program_binding.set_texture(location, storage.texture());
+        } else {
+            ctx.program_binding.set_texture(location, &ctx.black_dummy);
+        }
+    }
     if let Some(location) = &built_in_uniforms[BuiltInUniform::UseSkeletalAnimation as usize] {
         ctx.program_binding
             .set_bool(location, ctx.use_skeletal_animation);
@@ -1337,10 +1427,13 @@ impl Renderer {
             geometry_cache: Default::default(),
             forward_renderer: ForwardRenderer::new(),
             ui_frame_buffers: Default::default(),
+            frame_capture: Default::default(),
             fxaa_renderer: FxaaRenderer::new(&mut state)?,
             statistics: Statistics::default(),
             shader_event_receiver,
             texture_event_receiver,
+            texture_upload_budget: Default::default(),
+            shader_upload_budget: None,
             shader_cache,
             scene_render_passes: Default::default(),
             matrix_storage: MatrixStorageCache::new(&mut state)?,
@@ -1379,6 +1472,30 @@ impl Renderer {
         self.statistics
     }
 
+    /// Sets new budget for uploading of loaded textures to GPU. See [`ResourceUploadBudget`] docs
+    /// for more info.
+    pub fn set_texture_upload_budget(&mut self, budget: ResourceUploadBudget) {
+        self.texture_upload_budget = budget;
+    }
+
+    /// Returns current budget for uploading of loaded textures to GPU.
+    pub fn texture_upload_budget(&self) -> ResourceUploadBudget {
+        self.texture_upload_budget
+    }
+
+    /// Sets new budget for compilation of loaded shaders, `None` means that every loaded shader
+    /// is compiled on the next frame (default). Keep in mind, that a budget for shaders makes scene
+    /// warm-up longer, because scenes cannot be drawn until their shaders are compiled. See
+    /// [`ResourceUploadBudget`] docs for more info.
+    pub fn set_shader_upload_budget(&mut self, budget: Option<ResourceUploadBudget>) {
+        self.shader_upload_budget = budget;
+    }
+
+    /// Returns current budget for compilation of loaded shaders.
+    pub fn shader_upload_budget(&self) -> Option<ResourceUploadBudget> {
+        self.shader_upload_budget
+    }
+
     /// Unloads texture from GPU memory.
     pub fn unload_texture(&mut self, texture: TextureResource) {
         self.texture_cache.unload(texture)
@@ -1412,6 +1529,31 @@ impl Renderer {
         Ok(())
     }
 
+    /// Returns frame capture, that is used to grab rendered frames. See [`FrameCapture`] docs for
+    /// more info.
+    pub fn frame_capture(&self) -> &FrameCapture {
+        &self.frame_capture
+    }
+
+    /// Sets new frame capture. Pending requests of the current frame capture won't be processed.
+    pub fn set_frame_capture(&mut self, frame_capture: FrameCapture) {
+        self.frame_capture = frame_capture;
+    }
+
+    /// Reads the content of the back buffer in RGBA8 format, row-by-row from top to bottom.
+    fn read_back_buffer(&mut self) -> Vec<u8> {
+        let (width, height) = self.frame_size;
+        let row_size = width as usize * 4;
+        let pixels = self.backbuffer.read_pixels(&mut self.state, width, height);
+
+        // OpenGL stores rows from bottom to top.
+        let mut flipped = Vec::with_capacity(pixels.len());
+        for row in pixels.chunks_exact(row_size).rev() {
+            flipped.extend_from_slice(row);
+        }
+        flipped
+    }
+
     /// Returns current (width, height) pair of back buffer size.
     pub fn get_frame_size(&self) -> (u32, u32) {
         self.frame_size
@@ -1517,22 +1659,28 @@ impl Renderer {
         Ok(())
     }
 
-    fn update_texture_cache(&mut self, dt: f32) {
-        // Maximum amount of textures uploaded to GPU per frame. This defines throughput **only** for
-        // requests from resource manager. This is needed to prevent huge lag when there are tons of
-        // requests, so this is some kind of work load balancer.
-        const THROUGHPUT: usize = 5;
-
-        let mut uploaded = 0;
-        while let Ok(event) = self.texture_event_receiver.try_recv() {
-            if let ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded(resource) = event {
+    fn update_texture_cache(&mut self, dt: f32, budget: &mut UploadBudgetTracker) {
+        // Check the budget before taking an event, otherwise the event will be lost. Events that
+        // do not fit into the budget stay in the queue until the next frame.
+        while !budget.is_exhausted() {
+            let Ok(event) = self.texture_event_receiver.try_recv() else {
+                break;
+            };
+            if let ResourceEvent::Unloaded { key, type_uuid, .. } = event {
+                // Free GPU memory right away, there's no need to wait until the cache entry expires.
+                if type_uuid == <Texture as TypeUuidProvider>::type_uuid() {
+                    self.texture_cache.map.remove(&key);
+                }
+            } else if let ResourceEvent::Loaded(resource)
+            | ResourceEvent::Reloaded { resource, .. }
+            | ResourceEvent::DataReplaced(resource)
+            | ResourceEvent::StageLoaded { resource, .. }
+            | ResourceEvent::StageUnloaded { resource, .. } = event
+            {
                 if let Some(texture) = resource.try_cast::<Texture>() {
                     match self.texture_cache.upload(&mut self.state, &texture) {
                         Ok(_) => {
-                            uploaded += 1;
-                            if uploaded >= THROUGHPUT {
-                                break;
-                            }
+                            budget.uploaded += 1;
                         }
                         Err(e) => {
                             Log::writeln(
@@ -1548,13 +1696,20 @@ impl Renderer {
         self.texture_cache.update(dt);
     }
 
-    fn update_shader_cache(&mut self, dt: f32) {
-        while let Ok(event) = self.shader_event_receiver.try_recv() {
-            if let ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded(resource) = event {
+    fn update_shader_cache(&mut self, dt: f32, budget: &mut UploadBudgetTracker) {
+        while !budget.is_exhausted() {
+            let Ok(event) = self.shader_event_receiver.try_recv() else {
+                break;
+            };
+            if let ResourceEvent::Loaded(resource)
+            | ResourceEvent::Reloaded { resource, .. }
+            | ResourceEvent::DataReplaced(resource) = event
+            {
                 if let Some(shader) = resource.try_cast::<Shader>() {
                     // Remove and immediately "touch" the shader cache to force upload shader.
                     self.shader_cache.remove(&shader);
                     let _ = self.shader_cache.get(&mut self.state, &shader);
+                    budget.uploaded += 1;
                 }
             }
         }
@@ -1567,8 +1722,12 @@ impl Renderer {
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn update_caches(&mut self, dt: f32) {
-        self.update_texture_cache(dt);
-        self.update_shader_cache(dt);
+        // Shaders go first, because nothing could be drawn without them.
+        self.update_shader_cache(dt, &mut UploadBudgetTracker::new(self.shader_upload_budget));
+        self.update_texture_cache(
+            dt,
+            &mut UploadBudgetTracker::new(Some(self.texture_upload_budget)),
+        );
         self.geometry_cache.update(dt);
     }
 
@@ -1819,6 +1978,40 @@ impl Renderer {
                     scene_associated_data.hdr_scene_frame_texture(),
                 )?;
 
+                // Post effects of the camera override post effects of the scene.
+                let mut post_effects_state = camera
+                    .post_effects()
+                    .or(scene.rendering_options.post_effects.as_ref())
+                    .map(|post_effects| post_effects.state());
+                let post_effects =
+                    post_effects_state
+                        .as_mut()
+                        .and_then(|state| match state.get_mut() {
+                            ResourceStateRefMut::Ok(post_effects) => Some(post_effects),
+                            _ => None,
+                        });
+                let ldr_mapping_parameters = match post_effects {
+                    Some(post_effects) => LdrMappingParameters {
+                        exposure: camera.exposure(),
+                        use_color_grading: post_effects.color_grading.enabled,
+                        tonemapping: post_effects.tonemapping,
+                        bloom_intensity: post_effects.bloom.effective_intensity(),
+                        vignette: post_effects
+                            .vignette
+                            .enabled
+                            .then(|| post_effects.vignette.clone()),
+                        color_grading_lut: post_effects.color_grading_lut(),
+                    },
+                    None => LdrMappingParameters {
+                        exposure: camera.exposure(),
+                        color_grading_lut: camera.color_grading_lut_ref(),
+                        use_color_grading: camera.color_grading_enabled(),
+                        tonemapping: Tonemapping::Exponential,
+                        bloom_intensity: 1.0,
+                        vignette: None,
+                    },
+                };
+
                 // Convert high dynamic range frame to low dynamic range (sRGB) with tone mapping and gamma correction.
                 self.statistics.geometry += scene_associated_data.hdr_renderer.render(
                     state,
@@ -1828,11 +2021,10 @@ impl Renderer {
                     viewport,
                     quad,
                     dt,
-                    camera.exposure(),
-                    camera.color_grading_lut_ref(),
-                    camera.color_grading_enabled(),
+                    &ldr_mapping_parameters,
                     &mut self.texture_cache,
                 )?;
+                drop(post_effects_state);
 
                 // Apply FXAA if needed.
                 if self.quality_settings.fxaa {
@@ -1922,6 +2114,12 @@ impl Renderer {
             texture_cache: &mut self.texture_cache,
         })?;
 
+        if self.frame_capture.is_frame_needed() {
+            let pixels = self.read_back_buffer();
+            self.frame_capture
+                .process_frame(self.frame_size.0, self.frame_size.1, pixels);
+        }
+
         Ok(())
     }
 
@@ -1956,3 +2154,26 @@ impl Renderer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn upload_budget_tracker() {
+        let mut tracker = UploadBudgetTracker::new(Some(ResourceUploadBudget::Count(2)));
+        assert!(!tracker.is_exhausted());
+        tracker.uploaded = 2;
+        assert!(tracker.is_exhausted());
+
+        // At least one resource is finalized per frame.
+        let mut tracker = UploadBudgetTracker::new(Some(ResourceUploadBudget::TimeMs(0.0)));
+        assert!(!tracker.is_exhausted());
+        tracker.uploaded = 1;
+        assert!(tracker.is_exhausted());
+
+        let mut tracker = UploadBudgetTracker::new(None);
+        tracker.uploaded = usize::MAX;
+        assert!(!tracker.is_exhausted());
+    }
+}
@@ -8,6 +8,13 @@ uniform float minLuminance;
 uniform float maxLuminance;
 uniform bool autoExposure;
 uniform float fixedExposure;
+uniform int tonemapping;
+uniform float bloomIntensity;
+uniform bool useVignette;
+uniform float vignetteIntensity;
+uniform float vignetteRadius;
+uniform float vignetteSmoothness;
+uniform vec4 vignetteColor;
 
 in vec2 texCoord;
 
@@ -22,10 +29,34 @@ vec3 ColorGrading(vec3 color) {
     return texture(colorMapSampler, scale * color + offset).rgb;
 }
 
+vec3 Tonemap(vec3 color) {
+    if (tonemapping == 0) {
+        // No tonemapping.
+        return clamp(color, 0.0, 1.0);
+    } else if (tonemapping == 2) {
+        // Reinhard.
+        return color / (vec3(1.0) + color);
+    } else if (tonemapping == 3) {
+        // ACES filmic curve approximation by Krzysztof Narkowicz.
+        return clamp((color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14), 0.0, 1.0);
+    } else {
+        // Exponential.
+        return vec3(1.0) - exp(-color);
+    }
+}
+
+vec3 Vignette(vec3 color) {
+    // Distance to the center, normalized so it is 1.0 at the corners.
+    float dist = length(texCoord - vec2(0.5)) * 1.41421356;
+    float inner = vignetteRadius - max(vignetteSmoothness, 0.0001);
+    float factor = 1.0 - smoothstep(inner, vignetteRadius, dist);
+    return mix(vignetteColor.rgb, color, mix(1.0, factor, vignetteIntensity));
+}
+
 void main() {
     vec4 hdrColor = texture(hdrSampler, texCoord);
 
-    hdrColor += texture(bloomSampler, texCoord);
+    hdrColor += bloomIntensity * texture(bloomSampler, texCoord);
 
     float luminance = texture(lumSampler, vec2(0.5, 0.5)).r;
 
@@ -36,11 +67,15 @@ void main() {
         exposure = fixedExposure;
     }
 
-    vec4 ldrColor = vec4(vec3(1.0) - exp(-hdrColor.rgb * exposure), hdrColor.a);
+    vec4 ldrColor = vec4(Tonemap(hdrColor.rgb * exposure), hdrColor.a);
 
     if (useColorGrading) {
         outLdrColor = vec4(ColorGrading(S_LinearToSRGB(ldrColor).rgb), ldrColor.a);
     } else {
         outLdrColor = S_LinearToSRGB(ldrColor);
     }
+
+    if (useVignette) {
+        outLdrColor.rgb = Vignette(outLdrColor.rgb);
+    }
 }
@@ -13,12 +13,18 @@ uniform vec4 gradientColors[16];
 uniform float gradientStops[16];
 
 // Begin point of linear gradient *or* center of radial gradient
-// in normalized coordinates
+// in the gradient space.
 uniform vec2 gradientOrigin;
 
-// End point of linear gradient in normalized coordinates.
+// End point of linear gradient in the gradient space.
 uniform vec2 gradientEnd;
 
+// Radius of radial gradient in the gradient space.
+uniform float gradientRadius;
+
+// 0 - normalized to bounds, 1 - pixels relative to bounds, 2 - pixels relative to screen.
+uniform int gradientSpace;
+
 uniform vec2 resolution;
 uniform vec2 boundsMin;
 uniform vec2 boundsMax;
@@ -48,7 +54,15 @@ int find_stop_index(float t) {
 void main()
 {
     vec2 size = vec2(boundsMax.x - boundsMin.x, boundsMax.y - boundsMin.y);
-    vec2 localPosition = (vec2(gl_FragCoord.x, resolution.y - gl_FragCoord.y) - boundsMin) / size;
+    vec2 screenPosition = vec2(gl_FragCoord.x, resolution.y - gl_FragCoord.y);
+    vec2 gradientPosition;
+    if (gradientSpace == 0) {
+        gradientPosition = (screenPosition - boundsMin) / size;
+    } else if (gradientSpace == 1) {
+        gradientPosition = screenPosition - boundsMin;
+    } else {
+        gradientPosition = screenPosition;
+    }
 
     if (brushType == 0) {
         // Solid color
@@ -59,10 +73,10 @@ void main()
 
         if (brushType == 1) {
             // Linear gradient
-            t = project_point(gradientOrigin, gradientEnd, localPosition);
+            t = project_point(gradientOrigin, gradientEnd, gradientPosition);
         } else if (brushType == 2) {
             // Radial gradient
-            t = clamp(length(localPosition - gradientOrigin), 0.0, 1.0);
+            t = clamp(length(gradientPosition - gradientOrigin) / gradientRadius, 0.0, 1.0);
         }
 
         int current = find_stop_index(t);
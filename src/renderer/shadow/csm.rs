@@ -276,7 +276,10 @@ impl CsmRenderer {
                         shader_cache
                             .get(state, material.shader())
                             .and_then(|shader_set| {
-                                shader_set.render_passes.get(&DIRECTIONAL_SHADOW_PASS_NAME)
+                                shader_set.render_pass(
+                                    material.permutation(),
+                                    &DIRECTIONAL_SHADOW_PASS_NAME,
+                                )
                             })
                     {
                         for instance in batch.instances.iter() {
@@ -315,6 +318,7 @@ impl CsmRenderer {
                                         light_position: &Default::default(),
                                         blend_shapes_storage: blend_shapes_storage.as_ref(),
                                         blend_shapes_weights: &instance.blend_shapes_weights,
+                                        instancing: None,
                                         normal_dummy: normal_dummy.clone(),
                                         white_dummy: white_dummy.clone(),
                                         black_dummy: black_dummy.clone(),
@@ -269,7 +269,8 @@ impl PointShadowMapRenderer {
                         shader_cache
                             .get(state, material.shader())
                             .and_then(|shader_set| {
-                                shader_set.render_passes.get(&POINT_SHADOW_PASS_NAME)
+                                shader_set
+                                    .render_pass(material.permutation(), &POINT_SHADOW_PASS_NAME)
                             })
                     {
                         for instance in batch.instances.iter() {
@@ -300,6 +301,7 @@ impl PointShadowMapRenderer {
                                         light_position: &light_pos,
                                         blend_shapes_storage: blend_shapes_storage.as_ref(),
                                         blend_shapes_weights: &instance.blend_shapes_weights,
+                                        instancing: None,
                                         normal_dummy: normal_dummy.clone(),
                                         white_dummy: white_dummy.clone(),
                                         black_dummy: black_dummy.clone(),
@@ -175,9 +175,12 @@ impl SpotShadowMapRenderer {
                     .as_ref()
                     .and_then(|c| c.blend_shape_storage.clone());
 
-                if let Some(render_pass) = shader_cache
-                    .get(state, material.shader())
-                    .and_then(|shader_set| shader_set.render_passes.get(&SPOT_SHADOW_PASS_NAME))
+                if let Some(render_pass) =
+                    shader_cache
+                        .get(state, material.shader())
+                        .and_then(|shader_set| {
+                            shader_set.render_pass(material.permutation(), &SPOT_SHADOW_PASS_NAME)
+                        })
                 {
                     for instance in batch.instances.iter() {
                         statistics += framebuffer.draw(
@@ -214,6 +217,7 @@ impl SpotShadowMapRenderer {
                                     light_position: &Default::default(),
                                     blend_shapes_storage: blend_shapes_storage.as_ref(),
                                     blend_shapes_weights: &instance.blend_shapes_weights,
+                                    instancing: None,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
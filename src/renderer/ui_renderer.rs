@@ -11,7 +11,7 @@ use crate::{
         sstorage::ImmutableString,
     },
     gui::{
-        brush::Brush,
+        brush::{Brush, GradientSpace},
         draw::{CommandTexture, DrawingContext, SharedTexture},
     },
     renderer::{
@@ -47,6 +47,8 @@ struct UiShader {
     gradient_stops: UniformLocation,
     gradient_origin: UniformLocation,
     gradient_end: UniformLocation,
+    gradient_radius: UniformLocation,
+    gradient_space: UniformLocation,
     resolution: UniformLocation,
     bounds_min: UniformLocation,
     bounds_max: UniformLocation,
@@ -75,6 +77,10 @@ impl UiShader {
             gradient_origin: program
                 .uniform_location(state, &ImmutableString::new("gradientOrigin"))?,
             gradient_end: program.uniform_location(state, &ImmutableString::new("gradientEnd"))?,
+            gradient_radius: program
+                .uniform_location(state, &ImmutableString::new("gradientRadius"))?,
+            gradient_space: program
+                .uniform_location(state, &ImmutableString::new("gradientSpace"))?,
             bounds_min: program.uniform_location(state, &ImmutableString::new("boundsMin"))?,
             bounds_max: program.uniform_location(state, &ImmutableString::new("boundsMax"))?,
             resolution: program.uniform_location(state, &ImmutableString::new("resolution"))?,
@@ -309,10 +315,14 @@ impl UiRenderer {
             let mut raw_colors = [Vector4::default(); 16];
             let bounds_max = cmd.bounds.right_bottom_corner();
 
-            let (gradient_origin, gradient_end) = match cmd.brush {
-                Brush::Solid(_) => (Vector2::default(), Vector2::default()),
-                Brush::LinearGradient { from, to, .. } => (from, to),
-                Brush::RadialGradient { center, .. } => (center, Vector2::default()),
+            let (gradient_origin, gradient_end, gradient_radius) = match cmd.brush {
+                Brush::Solid(_) => (Vector2::default(), Vector2::default(), 1.0),
+                Brush::LinearGradient { from, to, .. } => (from, to, 1.0),
+                Brush::RadialGradient { center, radius, .. } => (
+                    center,
+                    Vector2::default(),
+                    radius.unwrap_or(1.0).max(f32::EPSILON),
+                ),
             };
 
             let params = DrawParameters {
@@ -364,6 +374,15 @@ impl UiRenderer {
                         )
                         .set_vector2(&shader.gradient_origin, &gradient_origin)
                         .set_vector2(&shader.gradient_end, &gradient_end)
+                        .set_f32(&shader.gradient_radius, gradient_radius)
+                        .set_i32(
+                            &shader.gradient_space,
+                            match cmd.brush.space() {
+                                GradientSpace::Normalized => 0,
+                                GradientSpace::Local => 1,
+                                GradientSpace::Screen => 2,
+                            },
+                        )
                         .set_i32(
                             &shader.gradient_point_count,
                             match &cmd.brush {
@@ -0,0 +1,281 @@
+//! Runtime executor of behavior trees. See [`BehaviorTreeExecutor`] docs for more info.
+
+use crate::{
+    asset::ResourceStateRef,
+    core::{pool::Handle, visitor::prelude::*},
+    fxhash::FxHashMap,
+    resource::behavior_tree::{
+        BehaviorTreeAsset, BehaviorTreeNode, BehaviorTreeNodeKind, BehaviorTreeResource,
+    },
+    utils::behavior::Status,
+};
+
+/// Blackboard is a set of named values shared between a behavior tree and the code that runs it.
+/// Scripts write their data (distance to a target, health, etc.) into the blackboard, so the tree
+/// could make decisions based on it. Flags are stored as `1.0` and `0.0`. Values that were never
+/// set are treated as zero.
+#[derive(Default, Clone, Debug, PartialEq, Visit)]
+pub struct Blackboard {
+    values: FxHashMap<String, f32>,
+}
+
+impl Blackboard {
+    /// Sets a value with the given name.
+    pub fn set(&mut self, name: impl Into<String>, value: f32) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns a value with the given name.
+    pub fn get(&self, name: &str) -> f32 {
+        self.values.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Sets a flag with the given name.
+    pub fn set_flag(&mut self, name: impl Into<String>, flag: bool) {
+        self.set(name, if flag { 1.0 } else { 0.0 });
+    }
+
+    /// Returns `true` if a value with the given name is not zero.
+    pub fn flag(&self, name: &str) -> bool {
+        self.get(name) != 0.0
+    }
+
+    /// Removes a value with the given name.
+    pub fn remove(&mut self, name: &str) -> Option<f32> {
+        self.values.remove(name)
+    }
+
+    /// Returns a reference to every value of the blackboard.
+    pub fn values(&self) -> &FxHashMap<String, f32> {
+        &self.values
+    }
+}
+
+/// A handler of user-defined actions of a behavior tree (see [`BehaviorTreeNodeKind::Action`]). It
+/// is implemented for every closure with the `FnMut(&str, &mut Blackboard) -> Status` signature.
+pub trait BehaviorActionHandler {
+    /// Performs an action with the given name. Actions that take more than one tick should return
+    /// [`Status::Running`] until they're done.
+    fn perform(&mut self, action: &str, blackboard: &mut Blackboard) -> Status;
+}
+
+impl<F> BehaviorActionHandler for F
+where
+    F: FnMut(&str, &mut Blackboard) -> Status,
+{
+    fn perform(&mut self, action: &str, blackboard: &mut Blackboard) -> Status {
+        self(action, blackboard)
+    }
+}
+
+/// Behavior tree executor ticks a [`BehaviorTreeAsset`] and owns a [`Blackboard`]. Every tick
+/// evaluates the tree starting from its root node, so it should be called every frame (or less
+/// often, if the AI doesn't need to react instantly). It is not a scene node, it is meant to be
+/// owned by a script or a plugin.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     resource::behavior_tree::{executor::{Blackboard, BehaviorTreeExecutor}, BehaviorTreeResource},
+///     utils::behavior::Status,
+/// };
+///
+/// struct Bot {
+///     brain: BehaviorTreeExecutor,
+///     distance_to_enemy: f32,
+///     ammo: u32,
+/// }
+///
+/// impl Bot {
+///     fn new(tree: BehaviorTreeResource) -> Self {
+///         Self {
+///             brain: BehaviorTreeExecutor::new(tree),
+///             distance_to_enemy: 10.0,
+///             ammo: 5,
+///         }
+///     }
+///
+///     fn update(&mut self) {
+///         self.brain.blackboard_mut().set("distance_to_enemy", self.distance_to_enemy);
+///         let ammo = &mut self.ammo;
+///         self.brain.tick(&mut |action: &str, _: &mut Blackboard| match action {
+///             "shoot" if *ammo > 0 => {
+///                 *ammo -= 1;
+///                 Status::Success
+///             }
+///             _ => Status::Failure,
+///         });
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BehaviorTreeExecutor {
+    tree: BehaviorTreeResource,
+    blackboard: Blackboard,
+}
+
+impl BehaviorTreeExecutor {
+    /// Creates a new executor for the given behavior tree with empty blackboard.
+    pub fn new(tree: BehaviorTreeResource) -> Self {
+        Self {
+            tree,
+            blackboard: Default::default(),
+        }
+    }
+
+    /// Returns a reference to the behavior tree resource.
+    pub fn tree(&self) -> &BehaviorTreeResource {
+        &self.tree
+    }
+
+    /// Returns a reference to the blackboard.
+    pub fn blackboard(&self) -> &Blackboard {
+        &self.blackboard
+    }
+
+    /// Returns a reference to the blackboard.
+    pub fn blackboard_mut(&mut self) -> &mut Blackboard {
+        &mut self.blackboard
+    }
+
+    /// Performs a single update tick of the tree. Returns [`Status::Failure`] if the tree is not
+    /// loaded or has no root node.
+    pub fn tick(&mut self, handler: &mut dyn BehaviorActionHandler) -> Status {
+        let resource = self.tree.clone();
+        let state = resource.state();
+        let ResourceStateRef::Ok(tree) = state.get() else {
+            return Status::Failure;
+        };
+        self.tick_recursive(tree, tree.root(), handler, 0)
+    }
+
+    fn tick_recursive(
+        &mut self,
+        tree: &BehaviorTreeAsset,
+        handle: Handle<BehaviorTreeNode>,
+        handler: &mut dyn BehaviorActionHandler,
+        depth: usize,
+    ) -> Status {
+        // Trees made by hand could contain cycles, the depth of an acyclic tree is always less than
+        // the number of its nodes.
+        if depth > tree.nodes().alive_count() as usize {
+            return Status::Failure;
+        }
+        let Some(node) = tree.nodes().try_borrow(handle) else {
+            return Status::Failure;
+        };
+        match node.kind {
+            BehaviorTreeNodeKind::Sequence => {
+                for child in node.children.iter() {
+                    match self.tick_recursive(tree, *child, handler, depth + 1) {
+                        Status::Success => (),
+                        status => return status,
+                    }
+                }
+                Status::Success
+            }
+            BehaviorTreeNodeKind::Selector => {
+                for child in node.children.iter() {
+                    match self.tick_recursive(tree, *child, handler, depth + 1) {
+                        Status::Failure => (),
+                        status => return status,
+                    }
+                }
+                Status::Failure
+            }
+            BehaviorTreeNodeKind::Inverter => {
+                let child = node.children.first().cloned().unwrap_or_default();
+                match self.tick_recursive(tree, child, handler, depth + 1) {
+                    Status::Success => Status::Failure,
+                    Status::Failure => Status::Success,
+                    Status::Running => Status::Running,
+                }
+            }
+            BehaviorTreeNodeKind::Condition {
+                ref key,
+                comparison,
+                value,
+            } => {
+                if comparison.compare(self.blackboard.get(key), value) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            BehaviorTreeNodeKind::SetValue { ref key, value } => {
+                self.blackboard.set(key.clone(), value);
+                Status::Success
+            }
+            BehaviorTreeNodeKind::Action { ref name } => {
+                handler.perform(name, &mut self.blackboard)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{asset::Resource, resource::behavior_tree::BlackboardComparison};
+
+    #[test]
+    fn behavior_tree_executor_tick() {
+        let mut tree = BehaviorTreeAsset::default();
+        let root = tree.add_node(BehaviorTreeNode {
+            kind: BehaviorTreeNodeKind::Selector,
+            ..Default::default()
+        });
+        let attack = tree.add_node(BehaviorTreeNode {
+            kind: BehaviorTreeNodeKind::Sequence,
+            ..Default::default()
+        });
+        let is_close = tree.add_node(BehaviorTreeNode {
+            kind: BehaviorTreeNodeKind::Condition {
+                key: "distance".to_string(),
+                comparison: BlackboardComparison::Less,
+                value: 2.0,
+            },
+            ..Default::default()
+        });
+        let hit = tree.add_node(BehaviorTreeNode {
+            kind: BehaviorTreeNodeKind::Action {
+                name: "hit".to_string(),
+            },
+            ..Default::default()
+        });
+        let walk = tree.add_node(BehaviorTreeNode {
+            kind: BehaviorTreeNodeKind::Action {
+                name: "walk".to_string(),
+            },
+            ..Default::default()
+        });
+        assert!(tree.add_child(root, attack));
+        assert!(tree.add_child(attack, is_close));
+        assert!(tree.add_child(attack, hit));
+        assert!(tree.add_child(root, walk));
+        // Cycles and duplicates are not allowed.
+        assert!(!tree.add_child(hit, root));
+        assert!(!tree.add_child(root, walk));
+
+        let mut executor = BehaviorTreeExecutor::new(Resource::new_ok(tree));
+        executor.blackboard_mut().set("distance", 3.0);
+
+        let mut log = Vec::new();
+        let mut handler = |action: &str, blackboard: &mut Blackboard| {
+            log.push(action.to_string());
+            if action == "walk" {
+                let distance = blackboard.get("distance");
+                blackboard.set("distance", distance - 1.0);
+                Status::Running
+            } else {
+                Status::Success
+            }
+        };
+
+        assert_eq!(executor.tick(&mut handler), Status::Running);
+        assert_eq!(executor.tick(&mut handler), Status::Running);
+        assert_eq!(executor.tick(&mut handler), Status::Success);
+        assert_eq!(log, ["walk", "walk", "hit"]);
+    }
+}
@@ -0,0 +1,60 @@
+//! Behavior tree loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::behavior_tree::BehaviorTreeAsset,
+};
+use std::sync::Arc;
+
+/// Default implementation for behavior tree loading.
+pub struct BehaviorTreeLoader {
+    /// Resource manager that will be used to upgrade behavior trees of older versions.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for BehaviorTreeLoader {
+    fn extensions(&self) -> &[&str] {
+        &["behavior"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        BehaviorTreeAsset::type_uuid()
+    }
+
+    fn load(
+        &self,
+        tree: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = tree.path();
+            match BehaviorTreeAsset::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(tree_state) => {
+                    Log::info(format!("Behavior tree {:?} is loaded!", path));
+
+                    tree.commit_ok(tree_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(tree, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load behavior tree from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    tree.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
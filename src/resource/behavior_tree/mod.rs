@@ -0,0 +1,362 @@
+//! Behavior tree resource contains AI logic authored as data. See [`BehaviorTreeAsset`] docs for
+//! more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector2,
+        io::FileLoadError,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod executor;
+pub mod loader;
+
+/// An error that may occur during behavior tree loading.
+#[derive(Debug)]
+pub enum BehaviorTreeError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for BehaviorTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BehaviorTreeError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            BehaviorTreeError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for BehaviorTreeError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for BehaviorTreeError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A way of comparison of a blackboard value with some constant.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum BlackboardComparison {
+    /// Value must be equal to the constant.
+    #[default]
+    Equal,
+    /// Value must not be equal to the constant.
+    NotEqual,
+    /// Value must be strictly less than the constant.
+    Less,
+    /// Value must be strictly greater than the constant.
+    Greater,
+}
+
+impl BlackboardComparison {
+    /// Compares the given value with the constant.
+    pub fn compare(self, value: f32, constant: f32) -> bool {
+        match self {
+            BlackboardComparison::Equal => value == constant,
+            BlackboardComparison::NotEqual => value != constant,
+            BlackboardComparison::Less => value < constant,
+            BlackboardComparison::Greater => value > constant,
+        }
+    }
+}
+
+/// Defines what a behavior tree node does when it is ticked.
+#[derive(
+    Default, Clone, Debug, PartialEq, Visit, Reflect, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum BehaviorTreeNodeKind {
+    /// Ticks children one by one until any of them fails or is running. Succeeds if every child
+    /// succeeded (logical AND).
+    #[default]
+    Sequence,
+    /// Ticks children one by one until any of them succeeds or is running. Fails if every child
+    /// failed (logical OR).
+    Selector,
+    /// Inverts the status of its first child. [`Status::Running`](crate::utils::behavior::Status::Running)
+    /// remains unchanged.
+    Inverter,
+    /// Succeeds if a blackboard value satisfies the condition, fails otherwise.
+    Condition {
+        /// Name of the blackboard value.
+        key: String,
+        /// A way of comparison.
+        comparison: BlackboardComparison,
+        /// A constant to compare the blackboard value with.
+        value: f32,
+    },
+    /// Writes a constant into the blackboard and succeeds.
+    SetValue {
+        /// Name of the blackboard value.
+        key: String,
+        /// A new value.
+        value: f32,
+    },
+    /// Performs a user-defined action with the given name, see
+    /// [`executor::BehaviorActionHandler`] for more info.
+    Action {
+        /// Name of the action.
+        name: String,
+    },
+}
+
+/// A single node of a behavior tree.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct BehaviorTreeNode {
+    /// Position of the node in the behavior tree editor.
+    #[reflect(hidden)]
+    pub position: Vector2<f32>,
+    /// Name of the node, it is used only in the behavior tree editor.
+    pub name: String,
+    /// Defines what the node does.
+    pub kind: BehaviorTreeNodeKind,
+    /// Children of the node, they're ticked in the same order as in the list. Only composite
+    /// nodes (sequence, selector and inverter) use their children.
+    #[reflect(hidden)]
+    pub children: Vec<Handle<BehaviorTreeNode>>,
+}
+
+/// Behavior tree asset is a data-driven version of [`crate::utils::behavior::BehaviorTree`]. Its
+/// leaf nodes do not contain code, instead they check and modify values of a blackboard or call
+/// user-defined actions by name. This way AI logic could be made in the behavior tree editor
+/// without writing hand-made state machines.
+///
+/// Behavior trees are executed using [`executor::BehaviorTreeExecutor`].
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::resource::behavior_tree::{
+///     BehaviorTreeAsset, BehaviorTreeNode, BehaviorTreeNodeKind, BlackboardComparison,
+/// };
+///
+/// let mut tree = BehaviorTreeAsset::default();
+/// let root = tree.add_node(BehaviorTreeNode {
+///     kind: BehaviorTreeNodeKind::Sequence,
+///     ..Default::default()
+/// });
+/// let sees_enemy = tree.add_node(BehaviorTreeNode {
+///     kind: BehaviorTreeNodeKind::Condition {
+///         key: "sees_enemy".to_string(),
+///         comparison: BlackboardComparison::Equal,
+///         value: 1.0,
+///     },
+///     ..Default::default()
+/// });
+/// let attack = tree.add_node(BehaviorTreeNode {
+///     kind: BehaviorTreeNodeKind::Action {
+///         name: "attack".to_string(),
+///     },
+///     ..Default::default()
+/// });
+/// assert!(tree.add_child(root, sees_enemy));
+/// assert!(tree.add_child(root, attack));
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct BehaviorTreeAsset {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    #[reflect(hidden)]
+    nodes: Pool<BehaviorTreeNode>,
+    #[reflect(hidden)]
+    root: Handle<BehaviorTreeNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl TypeUuidProvider for BehaviorTreeAsset {
+    fn type_uuid() -> Uuid {
+        uuid!("8d2f6b1e-4a73-4c95-b0e8-5f1a9c3d7e62")
+    }
+}
+
+impl ResourceData for BehaviorTreeAsset {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
+        self.visit("BehaviorTree", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl BehaviorTreeAsset {
+    /// Current version of the format of behavior tree files. It must be bumped every time when the format
+    /// is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
+    /// Loads a behavior tree from the specific file path. Resource manager is used to upgrade files
+    /// of older versions.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, BehaviorTreeError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
+        let mut tree = BehaviorTreeAsset::default();
+        tree.visit("BehaviorTree", &mut visitor)?;
+        tree.path = path.to_path_buf();
+        Ok(tree)
+    }
+
+    /// Adds a new node to the tree. The first added node becomes the root node.
+    pub fn add_node(&mut self, node: BehaviorTreeNode) -> Handle<BehaviorTreeNode> {
+        let handle = self.nodes.spawn(node);
+        if self.root.is_none() {
+            self.root = handle;
+        }
+        handle
+    }
+
+    /// Removes a node from the tree. The node is also removed from the children of every other node,
+    /// but its own children are kept in the tree.
+    pub fn remove_node(&mut self, handle: Handle<BehaviorTreeNode>) -> BehaviorTreeNode {
+        for other in self.nodes.iter_mut() {
+            other.children.retain(|child| *child != handle);
+        }
+        if self.root == handle {
+            self.root = Handle::NONE;
+        }
+        self.nodes.free(handle)
+    }
+
+    /// Makes a node a child of another node. Returns `false` if any of the handles is invalid, the
+    /// child is already attached to the parent, or if it would create a cycle.
+    pub fn add_child(
+        &mut self,
+        parent: Handle<BehaviorTreeNode>,
+        child: Handle<BehaviorTreeNode>,
+    ) -> bool {
+        if !self.nodes.is_valid_handle(parent)
+            || !self.nodes.is_valid_handle(child)
+            || self.nodes[parent].children.contains(&child)
+            || self.is_reachable(child, parent)
+        {
+            return false;
+        }
+        self.nodes[parent].children.push(child);
+        true
+    }
+
+    /// Returns `true` if `to` is `from` or any of its descendants.
+    fn is_reachable(&self, from: Handle<BehaviorTreeNode>, to: Handle<BehaviorTreeNode>) -> bool {
+        let mut stack = vec![from];
+        while let Some(handle) = stack.pop() {
+            if handle == to {
+                return true;
+            }
+            if let Some(node) = self.nodes.try_borrow(handle) {
+                stack.extend_from_slice(&node.children);
+            }
+        }
+        false
+    }
+
+    /// Returns a reference to the nodes container.
+    pub fn nodes(&self) -> &Pool<BehaviorTreeNode> {
+        &self.nodes
+    }
+
+    /// Returns a reference to the nodes container.
+    pub fn nodes_mut(&mut self) -> &mut Pool<BehaviorTreeNode> {
+        &mut self.nodes
+    }
+
+    /// Sets a node, from which execution of the tree will start.
+    pub fn set_root(&mut self, root: Handle<BehaviorTreeNode>) {
+        self.root = root;
+    }
+
+    /// Returns a handle of a node, from which execution of the tree will start.
+    pub fn root(&self) -> Handle<BehaviorTreeNode> {
+        self.root
+    }
+}
+
+/// Type alias for behavior tree resources.
+pub type BehaviorTreeResource = Resource<BehaviorTreeAsset>;
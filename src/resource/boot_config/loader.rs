@@ -0,0 +1,55 @@
+//! Boot configuration loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::boot_config::BootConfig,
+};
+use std::sync::Arc;
+
+/// Default implementation for boot configuration loading.
+pub struct BootConfigLoader;
+
+impl ResourceLoader for BootConfigLoader {
+    fn extensions(&self) -> &[&str] {
+        &["boot"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        BootConfig::type_uuid()
+    }
+
+    fn load(
+        &self,
+        config: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = config.path();
+            match BootConfig::from_file(&path, io.as_ref()).await {
+                Ok(config_state) => {
+                    Log::info(format!("Boot configuration {:?} is loaded!", path));
+
+                    config.commit_ok(config_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(config, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load boot configuration from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    config.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,584 @@
+//! Boot configuration resource contains the settings, that are used to start the engine. See
+//! [`BootConfig`] docs for more info.
+
+use crate::{
+    asset::{
+        io::{vfs::VirtualFileSystem, write_atomically, ResourceIo},
+        manager::ResourceManager,
+        Resource, ResourceData,
+    },
+    core::{
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    dpi::{LogicalSize, Size},
+    engine::GraphicsContextParams,
+    fxhash::FxHashMap,
+    renderer::QualitySettings,
+    window::WindowAttributes,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+
+/// An error that may occur during boot configuration loading or overriding.
+#[derive(Debug)]
+pub enum BootConfigError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// A parsing error has occurred.
+    ParseError(ron::error::SpannedError),
+
+    /// An override refers to a setting, that does not exist.
+    UnknownKey(String),
+
+    /// A value of an override cannot be converted to the type of the setting.
+    InvalidValue {
+        /// Key of the setting.
+        key: String,
+        /// Value of the override.
+        value: String,
+    },
+}
+
+impl Display for BootConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BootConfigError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            BootConfigError::ParseError(v) => {
+                write!(f, "A parsing error has occurred {v:?}")
+            }
+            BootConfigError::UnknownKey(key) => {
+                write!(f, "There's no {key} boot setting")
+            }
+            BootConfigError::InvalidValue { key, value } => {
+                write!(f, "{value} is not a valid value of {key} boot setting")
+            }
+        }
+    }
+}
+
+impl From<ron::error::SpannedError> for BootConfigError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::ParseError(e)
+    }
+}
+
+impl From<FileLoadError> for BootConfigError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Settings of the main window.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    /// Title of the window.
+    pub title: String,
+    /// Width of the client area of the window in logical pixels. `None` means that the size is
+    /// chosen by the OS.
+    pub width: Option<u32>,
+    /// Height of the client area of the window in logical pixels. `None` means that the size is
+    /// chosen by the OS.
+    pub height: Option<u32>,
+    /// Whether the window could be resized by a user or not.
+    pub resizable: bool,
+    /// Whether the window should be maximized at start or not.
+    pub maximized: bool,
+    /// Whether the window should be in borderless fullscreen mode on the current monitor or not.
+    pub fullscreen: bool,
+    /// Whether the window should have decorations (title bar, borders, etc.) or not.
+    pub decorations: bool,
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            title: "Fyrox Game".to_string(),
+            width: None,
+            height: None,
+            resizable: true,
+            maximized: false,
+            fullscreen: false,
+            decorations: true,
+        }
+    }
+}
+
+/// A preset of renderer quality settings. See [`QualitySettings`] for more info.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    Default,
+    Serialize,
+    Deserialize,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum QualityPreset {
+    /// See [`QualitySettings::low`].
+    Low,
+    /// See [`QualitySettings::medium`].
+    Medium,
+    /// See [`QualitySettings::high`].
+    #[default]
+    High,
+    /// See [`QualitySettings::ultra`].
+    Ultra,
+}
+
+impl QualityPreset {
+    /// Returns renderer quality settings of the preset.
+    pub fn settings(self) -> QualitySettings {
+        match self {
+            QualityPreset::Low => QualitySettings::low(),
+            QualityPreset::Medium => QualitySettings::medium(),
+            QualityPreset::High => QualitySettings::high(),
+            QualityPreset::Ultra => QualitySettings::ultra(),
+        }
+    }
+}
+
+/// Settings of the renderer.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RendererSettings {
+    /// Whether to use vertical synchronization or not. See [`GraphicsContextParams::vsync`].
+    pub vsync: bool,
+    /// Quality preset of the renderer. `None` keeps the default settings of the renderer.
+    pub quality: Option<QualityPreset>,
+}
+
+impl Default for RendererSettings {
+    fn default() -> Self {
+        Self {
+            vsync: true,
+            quality: None,
+        }
+    }
+}
+
+/// A directory, that is mounted to the virtual file system of the resource manager. See
+/// [`VirtualFileSystem`] docs for more info.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+pub struct AssetMount {
+    /// Virtual prefix under which the directory is visible.
+    pub prefix: PathBuf,
+    /// Path of the directory.
+    pub root: PathBuf,
+    /// Mounts with higher priority override the content of mounts with lower priority.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Boot configuration is a set of settings, that are used to start the engine: settings of the main
+/// window and the renderer, asset mount points, update rate and feature flags. It allows to change
+/// the way a game starts without recompiling it. Boot configurations are stored in human-readable
+/// RON format (with `.boot` extension) and loaded through the resource manager, every setting of
+/// the file is optional.
+///
+/// Settings of the file could be overridden by two more layers: environment variables and command
+/// line arguments (in this order, the last one wins). Every setting has a key, which is a path of
+/// the setting in the file (`window.width`, `renderer.quality`, `features.new_ui`, etc.), see
+/// [`Self::set`] for the list of keys. An environment variable should have [`Self::ENV_PREFIX`],
+/// and it uses double underscores instead of dots (`FYROX_BOOT_WINDOW__WIDTH=1920`). A command line
+/// argument is `--boot key=value` (`--boot window.fullscreen=true`).
+///
+/// [`crate::engine::executor::Executor::from_boot_config`] loads a boot configuration, applies all
+/// the overrides and starts the engine with the resulting settings. The effective configuration is
+/// registered as a script service, so feature flags could be checked at any time:
+///
+/// ```rust
+/// # use fyrox::{resource::boot_config::BootConfig, script::ScriptContext};
+/// fn use_new_ui(ctx: &ScriptContext) -> bool {
+///     ctx.services
+///         .get::<BootConfig>()
+///         .is_some_and(|config| config.is_feature_enabled("new_ui"))
+/// }
+/// ```
+///
+/// ## Example
+///
+/// ```ron
+/// (
+///     window: (
+///         title: "My Game",
+///         width: Some(1280),
+///         height: Some(720),
+///     ),
+///     renderer: (
+///         vsync: true,
+///         quality: Some(Medium),
+///     ),
+///     mounts: [
+///         (prefix: "data", root: "data"),
+///         (prefix: "data", root: "mods/data", priority: 1),
+///     ],
+///     update_rate: 60.0,
+///     features: {
+///         "new_ui": true,
+///     },
+/// )
+/// ```
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BootConfig {
+    #[serde(skip)]
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// Settings of the main window.
+    pub window: WindowSettings,
+    /// Settings of the renderer.
+    pub renderer: RendererSettings,
+    /// Directories, that are mounted to the virtual file system of the resource manager. If the
+    /// list is empty, the resource manager accesses the file system directly.
+    pub mounts: Vec<AssetMount>,
+    /// Update rate of the game in frames per second.
+    pub update_rate: f32,
+    /// Whether the engine should run without a window, renderer and sound or not (game servers,
+    /// for example).
+    pub headless: bool,
+    /// Named feature flags of the game.
+    pub features: FxHashMap<String, bool>,
+    #[serde(skip)]
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl Default for BootConfig {
+    fn default() -> Self {
+        Self {
+            path: Default::default(),
+            window: Default::default(),
+            renderer: Default::default(),
+            mounts: Default::default(),
+            update_rate: 60.0,
+            headless: false,
+            features: Default::default(),
+            dirty: false,
+        }
+    }
+}
+
+impl TypeUuidProvider for BootConfig {
+    fn type_uuid() -> Uuid {
+        uuid!("9d2b6f4e-3a71-4c58-b0e9-7f1a5c3d8e26")
+    }
+}
+
+impl ResourceData for BootConfig {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let text = ron::ser::to_string_pretty(self, Default::default())?;
+        write_atomically(path, |path| std::fs::write(path, text))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, BootConfigError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| BootConfigError::InvalidValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+}
+
+fn parse_optional<T: FromStr>(key: &str, value: &str) -> Result<Option<T>, BootConfigError> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        Ok(None)
+    } else {
+        parse(key, value).map(Some)
+    }
+}
+
+impl BootConfig {
+    /// Prefix of environment variables, that override boot settings.
+    pub const ENV_PREFIX: &'static str = "FYROX_BOOT_";
+
+    /// Loads a boot configuration from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, BootConfigError> {
+        let bytes = io.load_file(path).await?;
+        let mut config = Self::from_bytes(&bytes)?;
+        config.path = path.to_path_buf();
+        Ok(config)
+    }
+
+    /// Creates a boot configuration from the given content of a RON file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BootConfigError> {
+        Ok(ron::de::from_bytes(bytes)?)
+    }
+
+    /// Sets a setting with the given key from its text representation. Supported keys:
+    ///
+    /// - `window.title`, `window.width`, `window.height` (`none` resets the size),
+    ///   `window.resizable`, `window.maximized`, `window.fullscreen`, `window.decorations`;
+    /// - `renderer.vsync`, `renderer.quality` (`low`, `medium`, `high`, `ultra` or `none`);
+    /// - `mount` - adds a new mount point in `prefix=root` form, its priority is higher than the
+    ///   priority of every existing mount point;
+    /// - `update_rate`, `headless`;
+    /// - `features.<name>` - sets a feature flag.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), BootConfigError> {
+        let window = &mut self.window;
+        match key {
+            "window.title" => window.title = value.to_string(),
+            "window.width" => window.width = parse_optional(key, value)?,
+            "window.height" => window.height = parse_optional(key, value)?,
+            "window.resizable" => window.resizable = parse(key, value)?,
+            "window.maximized" => window.maximized = parse(key, value)?,
+            "window.fullscreen" => window.fullscreen = parse(key, value)?,
+            "window.decorations" => window.decorations = parse(key, value)?,
+            "renderer.vsync" => self.renderer.vsync = parse(key, value)?,
+            "renderer.quality" => self.renderer.quality = parse_optional(key, value)?,
+            "mount" => {
+                let (prefix, root) =
+                    value
+                        .split_once('=')
+                        .ok_or_else(|| BootConfigError::InvalidValue {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                        })?;
+                let priority = self
+                    .mounts
+                    .iter()
+                    .map(|mount| mount.priority + 1)
+                    .max()
+                    .unwrap_or_default();
+                self.mounts.push(AssetMount {
+                    prefix: prefix.trim().into(),
+                    root: root.trim().into(),
+                    priority,
+                });
+            }
+            "update_rate" => self.update_rate = parse(key, value)?,
+            "headless" => self.headless = parse(key, value)?,
+            _ => match key.strip_prefix("features.") {
+                Some(name) if !name.is_empty() => {
+                    self.features.insert(name.to_string(), parse(key, value)?);
+                }
+                _ => return Err(BootConfigError::UnknownKey(key.to_string())),
+            },
+        }
+        Ok(())
+    }
+
+    /// Applies overrides from the given environment variables. Only the variables with
+    /// [`Self::ENV_PREFIX`] are used, the rest of the name is lowercased and every double underscore
+    /// is replaced with a dot to get the key of a setting (`FYROX_BOOT_WINDOW__WIDTH` ->
+    /// `window.width`). Invalid overrides are skipped, the errors are returned.
+    pub fn apply_env_overrides(
+        &mut self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Vec<BootConfigError> {
+        vars.into_iter()
+            .filter_map(|(name, value)| {
+                let key = name
+                    .strip_prefix(Self::ENV_PREFIX)?
+                    .to_lowercase()
+                    .replace("__", ".");
+                self.set(&key, &value).err()
+            })
+            .collect()
+    }
+
+    /// Applies overrides in `key=value` form (command line arguments, for example). Invalid
+    /// overrides are skipped, the errors are returned.
+    pub fn apply_overrides<S: AsRef<str>>(
+        &mut self,
+        overrides: impl IntoIterator<Item = S>,
+    ) -> Vec<BootConfigError> {
+        overrides
+            .into_iter()
+            .filter_map(|value| {
+                let value = value.as_ref();
+                match value.split_once('=') {
+                    Some((key, value)) => self.set(key.trim(), value).err(),
+                    None => Some(BootConfigError::InvalidValue {
+                        key: value.to_string(),
+                        value: Default::default(),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the feature flag with the given name is set to `true`.
+    pub fn is_feature_enabled(&self, name: &str) -> bool {
+        self.features.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Returns graphics context parameters, that are defined by the window and renderer settings.
+    /// Fullscreen mode is not a part of the parameters, it should be set when the window is created.
+    pub fn graphics_context_params(&self) -> GraphicsContextParams {
+        let mut window_attributes = WindowAttributes::default();
+        window_attributes.title = self.window.title.clone();
+        window_attributes.resizable = self.window.resizable;
+        window_attributes.maximized = self.window.maximized;
+        window_attributes.decorations = self.window.decorations;
+        if let (Some(width), Some(height)) = (self.window.width, self.window.height) {
+            window_attributes.inner_size =
+                Some(Size::Logical(LogicalSize::new(width as f64, height as f64)));
+        }
+        GraphicsContextParams {
+            window_attributes,
+            vsync: self.renderer.vsync,
+            texture_upload_budget: Default::default(),
+            shader_upload_budget: None,
+        }
+    }
+
+    /// Mounts every asset mount point to a new virtual file system and makes the resource manager
+    /// use it. Does nothing if there are no mount points.
+    pub fn mount_assets(&self, resource_manager: &ResourceManager) {
+        if self.mounts.is_empty() {
+            return;
+        }
+        let vfs = VirtualFileSystem::new();
+        for mount in self.mounts.iter() {
+            vfs.mount_dir(&mount.prefix, &mount.root, mount.priority);
+        }
+        resource_manager.state().set_resource_io(Arc::new(vfs));
+    }
+}
+
+/// Type alias for boot configuration resources.
+pub type BootConfigResource = Resource<BootConfig>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn boot_config_layers() {
+        let mut config = BootConfig::from_bytes(
+            r#"(
+                window: (
+                    title: "My Game",
+                    width: Some(1280),
+                    height: Some(720),
+                ),
+                renderer: (
+                    quality: Some(Medium),
+                ),
+                mounts: [
+                    (prefix: "data", root: "data"),
+                ],
+                features: {
+                    "new_ui": true,
+                },
+            )"#
+            .as_bytes(),
+        )
+        .unwrap();
+        // Missing settings have default values.
+        assert_eq!(config.update_rate, 60.0);
+        assert!(config.window.resizable);
+        assert!(config.is_feature_enabled("new_ui"));
+        assert!(!config.is_feature_enabled("missing"));
+
+        let errors = config.apply_env_overrides([
+            ("FYROX_BOOT_WINDOW__WIDTH".to_string(), "1920".to_string()),
+            ("FYROX_BOOT_UPDATE_RATE".to_string(), "30".to_string()),
+            (
+                "FYROX_BOOT_FEATURES__NEW_UI".to_string(),
+                "false".to_string(),
+            ),
+            ("FYROX_BOOT_WINDOW__MISSING".to_string(), "1".to_string()),
+            ("PATH".to_string(), "/usr/bin".to_string()),
+        ]);
+        assert!(matches!(
+            errors.as_slice(),
+            [BootConfigError::UnknownKey(key)] if key == "window.missing"
+        ));
+        assert_eq!(config.window.width, Some(1920));
+        assert_eq!(config.update_rate, 30.0);
+        assert!(!config.is_feature_enabled("new_ui"));
+
+        // Command line arguments go after environment variables and win.
+        let errors = config.apply_overrides([
+            "update_rate=120",
+            "renderer.quality=ultra",
+            "mount=data=mods/data",
+            "window.height=tall",
+            "headless",
+        ]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(config.update_rate, 120.0);
+        assert_eq!(config.renderer.quality, Some(QualityPreset::Ultra));
+        assert_eq!(config.window.height, Some(720));
+        assert_eq!(
+            config.mounts.last(),
+            Some(&AssetMount {
+                prefix: "data".into(),
+                root: "mods/data".into(),
+                priority: 1,
+            })
+        );
+
+        let params = config.graphics_context_params();
+        assert_eq!(params.window_attributes.title, "My Game");
+        assert_eq!(
+            params.window_attributes.inner_size,
+            Some(Size::Logical(LogicalSize::new(1920.0, 720.0)))
+        );
+        assert!(params.vsync);
+    }
+}
@@ -3,7 +3,13 @@
 use crate::{
     asset::{Resource, ResourceData, CURVE_RESOURCE_UUID},
     core::{
-        curve::Curve, io::FileLoadError, reflect::prelude::*, uuid::Uuid, visitor::prelude::*,
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{BakedCurve, Curve},
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::Uuid,
+        visitor::prelude::*,
         TypeUuidProvider,
     },
 };
@@ -15,6 +21,7 @@ use std::{
 };
 
 pub mod loader;
+pub mod saver;
 
 /// An error that may occur during curve resource loading.
 #[derive(Debug)]
@@ -54,12 +61,60 @@ impl From<VisitError> for CurveResourceError {
     }
 }
 
-/// State of the [`CurveResource`]
+/// Kind of a curve resource. It defines the amount of channels of the resource and how their values
+/// should be interpreted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Visit, Reflect)]
+pub enum CurveKind {
+    /// A single channel.
+    #[default]
+    Scalar,
+    /// Two channels - X and Y.
+    Vector2,
+    /// Three channels - X, Y and Z.
+    Vector3,
+    /// Four channels - red, green, blue and alpha, in `[0; 1]` range.
+    Color,
+}
+
+impl CurveKind {
+    /// Returns the amount of channels of curves of this kind.
+    pub fn channel_count(self) -> usize {
+        match self {
+            CurveKind::Scalar => 1,
+            CurveKind::Vector2 => 2,
+            CurveKind::Vector3 => 3,
+            CurveKind::Color => 4,
+        }
+    }
+}
+
+/// State of the [`CurveResource`]. A curve resource could have multiple channels (see [`CurveKind`]),
+/// every channel is a separate [`Curve`].
+///
+/// ## Baking
+///
+/// Curves could be baked into lookup tables (see [`Self::bake`]), in this case every sampling method
+/// (such as [`Self::value_at`] or [`Self::color_at`]) uses the tables and takes constant time,
+/// which is useful for hot paths like particle systems or sound envelopes. The resolution of the
+/// tables is saved with the resource, so the tables are re-created on load. Keep in mind, that
+/// the tables must be re-created after any change of the curves, see [`Self::rebake`].
 #[derive(Debug, Visit, Default, Reflect)]
 pub struct CurveResourceState {
     pub(crate) path: PathBuf,
-    /// Actual curve.
+    /// Actual curve. It is the first channel of multi-channel curves.
     pub curve: Curve,
+    /// Kind of the curve.
+    #[visit(optional)]
+    pub kind: CurveKind,
+    /// Channels of multi-channel curves, except the first one (see [`Self::curve`]).
+    #[visit(optional)]
+    pub extra_channels: Vec<Curve>,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    bake_resolution: Option<u32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    baked: Vec<BakedCurve>,
 }
 
 impl ResourceData for CurveResourceState {
@@ -96,16 +151,139 @@ impl TypeUuidProvider for CurveResourceState {
 }
 
 impl CurveResourceState {
+    /// Creates a new single-channel curve resource.
+    pub fn new(curve: Curve) -> Self {
+        Self {
+            curve,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new multi-channel curve resource. Missing channels are filled with empty curves,
+    /// excessive ones are discarded.
+    pub fn new_multi_channel(kind: CurveKind, channels: Vec<Curve>) -> Self {
+        let mut channels = channels.into_iter();
+        Self {
+            curve: channels.next().unwrap_or_default(),
+            kind,
+            extra_channels: (1..kind.channel_count())
+                .map(|_| channels.next().unwrap_or_default())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
     /// Load a curve resource from the specific file path.
     pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, CurveResourceError> {
         let bytes = io.load_file(path).await?;
         let mut visitor = Visitor::load_from_memory(&bytes)?;
-        let mut curve = Curve::default();
-        curve.visit("Curve", &mut visitor)?;
-        Ok(Self {
-            curve,
+        let mut state = Self {
             path: path.to_path_buf(),
-        })
+            ..Default::default()
+        };
+        state.visit_content(&mut visitor)?;
+        state.rebake();
+        Ok(state)
+    }
+
+    /// Reads or writes the content of the resource in the format of curve files. The first channel
+    /// is stored in the same way as single-channel curves were always stored, so old files are
+    /// still compatible.
+    pub fn visit_content(&mut self, visitor: &mut Visitor) -> VisitResult {
+        self.curve.visit("Curve", visitor)?;
+        let _ = self.kind.visit("Kind", visitor);
+        let _ = self.extra_channels.visit("ExtraChannels", visitor);
+        let _ = self.bake_resolution.visit("BakeResolution", visitor);
+        Ok(())
+    }
+
+    /// Returns the amount of channels of the resource.
+    pub fn channel_count(&self) -> usize {
+        1 + self.extra_channels.len()
+    }
+
+    /// Returns a reference to the channel with the given index.
+    pub fn channel(&self, index: usize) -> Option<&Curve> {
+        match index {
+            0 => Some(&self.curve),
+            _ => self.extra_channels.get(index - 1),
+        }
+    }
+
+    /// Returns a reference to the channel with the given index.
+    pub fn channel_mut(&mut self, index: usize) -> Option<&mut Curve> {
+        match index {
+            0 => Some(&mut self.curve),
+            _ => self.extra_channels.get_mut(index - 1),
+        }
+    }
+
+    /// Bakes every channel of the resource into a lookup table with the given amount of samples.
+    pub fn bake(&mut self, resolution: u32) {
+        self.bake_resolution = Some(resolution);
+        self.rebake();
+    }
+
+    /// Re-creates the lookup tables, if the resource was baked. Must be called after any change
+    /// of the curves of a baked resource, otherwise the old values will be sampled.
+    pub fn rebake(&mut self) {
+        self.baked.clear();
+        if let Some(resolution) = self.bake_resolution {
+            self.baked = std::iter::once(&self.curve)
+                .chain(self.extra_channels.iter())
+                .map(|curve| curve.bake(resolution as usize))
+                .collect();
+        }
+    }
+
+    /// Removes the lookup tables, every sampling method will use the curves directly.
+    pub fn clear_baked(&mut self) {
+        self.bake_resolution = None;
+        self.baked.clear();
+    }
+
+    /// Returns the resolution of the lookup tables, if the resource was baked.
+    pub fn bake_resolution(&self) -> Option<u32> {
+        self.bake_resolution
+    }
+
+    /// Returns a value of the given channel at the given location. Missing channels have zero value.
+    #[inline]
+    pub fn value_at(&self, channel: usize, location: f32) -> f32 {
+        if let Some(baked) = self.baked.get(channel) {
+            baked.value_at(location)
+        } else {
+            self.channel(channel)
+                .map_or(0.0, |curve| curve.value_at(location))
+        }
+    }
+
+    /// Returns a value of the first two channels at the given location.
+    #[inline]
+    pub fn vec2_at(&self, location: f32) -> Vector2<f32> {
+        Vector2::new(self.value_at(0, location), self.value_at(1, location))
+    }
+
+    /// Returns a value of the first three channels at the given location.
+    #[inline]
+    pub fn vec3_at(&self, location: f32) -> Vector3<f32> {
+        Vector3::new(
+            self.value_at(0, location),
+            self.value_at(1, location),
+            self.value_at(2, location),
+        )
+    }
+
+    /// Returns a color at the given location. Values of the first four channels are treated as red,
+    /// green, blue and alpha in `[0; 1]` range.
+    #[inline]
+    pub fn color_at(&self, location: f32) -> Color {
+        Color::from(Vector4::new(
+            self.value_at(0, location),
+            self.value_at(1, location),
+            self.value_at(2, location),
+            self.value_at(3, location),
+        ))
     }
 }
 
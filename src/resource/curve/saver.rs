@@ -0,0 +1,41 @@
+//! Curve saver.
+
+use crate::{
+    asset::{io::write_atomically, saver::ResourceSaver},
+    core::{uuid::Uuid, visitor::Visitor, TypeUuidProvider},
+    resource::curve::CurveResourceState,
+};
+use std::{any::Any, error::Error, path::Path};
+
+/// Default implementation for curve saving. Curves are saved in the same binary format, that is
+/// used by [`super::loader::CurveLoader`].
+pub struct CurveSaver;
+
+impl ResourceSaver for CurveSaver {
+    fn extensions(&self) -> &[&str] {
+        &["curve", "crv"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        CurveResourceState::type_uuid()
+    }
+
+    fn save(&self, data: &dyn Any, path: &Path) -> Result<(), Box<dyn Error>> {
+        let curve_state = data
+            .downcast_ref::<CurveResourceState>()
+            .ok_or("The data is not a curve!")?;
+        let mut visitor = Visitor::new();
+        // Only the content is cloned, baked tables are not needed for saving.
+        let mut content = CurveResourceState {
+            path: Default::default(),
+            curve: curve_state.curve.clone(),
+            kind: curve_state.kind,
+            extra_channels: curve_state.extra_channels.clone(),
+            bake_resolution: curve_state.bake_resolution,
+            baked: Default::default(),
+        };
+        content.visit_content(&mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        Ok(())
+    }
+}
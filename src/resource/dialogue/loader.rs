@@ -0,0 +1,60 @@
+//! Dialogue loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::dialogue::Dialogue,
+};
+use std::sync::Arc;
+
+/// Default implementation for dialogue loading.
+pub struct DialogueLoader {
+    /// Resource manager that will be used to upgrade dialogues of older versions.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for DialogueLoader {
+    fn extensions(&self) -> &[&str] {
+        &["dialogue"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        Dialogue::type_uuid()
+    }
+
+    fn load(
+        &self,
+        dialogue: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = dialogue.path();
+            match Dialogue::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(dialogue_state) => {
+                    Log::info(format!("Dialogue {:?} is loaded!", path));
+
+                    dialogue.commit_ok(dialogue_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(dialogue, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load dialogue from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    dialogue.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
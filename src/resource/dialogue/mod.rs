@@ -0,0 +1,383 @@
+//! Dialogue resource contains a graph of branching conversation. See [`Dialogue`] docs for more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector2,
+        io::FileLoadError,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    fxhash::FxHashMap,
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+pub mod player;
+
+/// An error that may occur during dialogue loading.
+#[derive(Debug)]
+pub enum DialogueError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for DialogueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DialogueError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            DialogueError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for DialogueError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for DialogueError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A way of comparison of a dialogue variable with some value.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum DialogueComparison {
+    /// Variable must be equal to the value.
+    #[default]
+    Equal,
+    /// Variable must not be equal to the value.
+    NotEqual,
+    /// Variable must be strictly less than the value.
+    Less,
+    /// Variable must be strictly greater than the value.
+    Greater,
+}
+
+/// A condition that checks a dialogue variable. Variables that were never set are treated as
+/// zero.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DialogueCondition {
+    /// Name of the variable to check.
+    pub variable: String,
+    /// A way of comparison.
+    pub comparison: DialogueComparison,
+    /// A value to compare the variable with.
+    pub value: i64,
+}
+
+impl DialogueCondition {
+    /// Checks whether the condition is satisfied for the given set of variables.
+    pub fn is_satisfied(&self, variables: &FxHashMap<String, i64>) -> bool {
+        let variable = variables.get(&self.variable).cloned().unwrap_or_default();
+        match self.comparison {
+            DialogueComparison::Equal => variable == self.value,
+            DialogueComparison::NotEqual => variable != self.value,
+            DialogueComparison::Less => variable < self.value,
+            DialogueComparison::Greater => variable > self.value,
+        }
+    }
+}
+
+/// Checks whether every condition in the given set is satisfied. Empty set is always satisfied.
+pub fn are_conditions_satisfied(
+    conditions: &[DialogueCondition],
+    variables: &FxHashMap<String, i64>,
+) -> bool {
+    conditions.iter().all(|c| c.is_satisfied(variables))
+}
+
+/// A way of modification of a dialogue variable.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum DialogueActionKind {
+    /// Sets the variable to the value.
+    #[default]
+    Set,
+    /// Adds the value to the variable.
+    Add,
+}
+
+/// An action that modifies a dialogue variable when a node is entered.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DialogueAction {
+    /// Name of the variable to modify.
+    pub variable: String,
+    /// A way of modification.
+    pub kind: DialogueActionKind,
+    /// A value that will be used to modify the variable.
+    pub value: i64,
+}
+
+impl DialogueAction {
+    /// Applies the action to the given set of variables.
+    pub fn apply(&self, variables: &mut FxHashMap<String, i64>) {
+        let variable = variables.entry(self.variable.clone()).or_default();
+        match self.kind {
+            DialogueActionKind::Set => *variable = self.value,
+            DialogueActionKind::Add => *variable += self.value,
+        }
+    }
+}
+
+/// A choice that could be picked by a player when a dialogue reaches a node.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DialogueChoice {
+    /// Localization key of the text of the choice.
+    pub text_key: String,
+    /// A set of conditions that must be satisfied to make the choice available.
+    pub conditions: Vec<DialogueCondition>,
+    /// A node that will be entered when the choice is picked. [`Handle::NONE`] ends the dialogue.
+    pub target: Handle<DialogueNode>,
+}
+
+/// A link to another node, that will be followed automatically when a node has no choices.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DialogueLink {
+    /// A set of conditions that must be satisfied to follow the link.
+    pub conditions: Vec<DialogueCondition>,
+    /// A node that will be entered. [`Handle::NONE`] ends the dialogue.
+    pub target: Handle<DialogueNode>,
+}
+
+/// A single line of a dialogue.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DialogueNode {
+    /// Position of the node in the dialogue editor.
+    #[reflect(hidden)]
+    pub position: Vector2<f32>,
+    /// Name of the node, it is used only in the dialogue editor.
+    pub name: String,
+    /// Name of the speaker of the line.
+    pub speaker: String,
+    /// Localization key of the text of the line.
+    pub text_key: String,
+    /// A set of actions that will be executed when the node is entered.
+    pub actions: Vec<DialogueAction>,
+    /// A set of choices of the node. When there's at least one available choice, the dialogue
+    /// will wait until one of them is picked.
+    pub choices: Vec<DialogueChoice>,
+    /// A set of links to other nodes. The first link with satisfied conditions will be followed when
+    /// there's no available choices. If there's no such link, the dialogue ends.
+    pub next: Vec<DialogueLink>,
+}
+
+/// Dialogue is a graph of lines of a branching conversation. Every node of the graph is a line said
+/// by a speaker, it may offer a set of choices or continue to another node automatically. Choices
+/// and links could be guarded by conditions on dialogue variables and nodes could modify the
+/// variables when entered. Texts of the lines are stored as localization keys, so the same dialogue
+/// could be used with any language.
+///
+/// Dialogues are usually made in the dialogue editor and played using
+/// [`player::DialoguePlayer`].
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::resource::dialogue::{Dialogue, DialogueChoice, DialogueNode};
+///
+/// let mut dialogue = Dialogue::default();
+/// let bye = dialogue.add_node(DialogueNode {
+///     speaker: "Guard".to_string(),
+///     text_key: "guard.bye".to_string(),
+///     ..Default::default()
+/// });
+/// let greeting = dialogue.add_node(DialogueNode {
+///     speaker: "Guard".to_string(),
+///     text_key: "guard.greeting".to_string(),
+///     choices: vec![DialogueChoice {
+///         text_key: "player.bye".to_string(),
+///         conditions: vec![],
+///         target: bye,
+///     }],
+///     ..Default::default()
+/// });
+/// dialogue.set_entry(greeting);
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct Dialogue {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    #[reflect(hidden)]
+    nodes: Pool<DialogueNode>,
+    #[reflect(hidden)]
+    entry: Handle<DialogueNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl TypeUuidProvider for Dialogue {
+    fn type_uuid() -> Uuid {
+        uuid!("3c1b4e7a-95d2-4f08-b6a3-7e2d0c9f8a14")
+    }
+}
+
+impl ResourceData for Dialogue {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
+        self.visit("Dialogue", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl Dialogue {
+    /// Current version of the format of dialogue files. It must be bumped every time when the format
+    /// is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
+    /// Loads a dialogue from the specific file path. Resource manager is used to upgrade files of
+    /// older versions.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, DialogueError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
+        let mut dialogue = Dialogue::default();
+        dialogue.visit("Dialogue", &mut visitor)?;
+        dialogue.path = path.to_path_buf();
+        Ok(dialogue)
+    }
+
+    /// Adds a new node to the dialogue. The first added node becomes the entry node.
+    pub fn add_node(&mut self, node: DialogueNode) -> Handle<DialogueNode> {
+        let handle = self.nodes.spawn(node);
+        if self.entry.is_none() {
+            self.entry = handle;
+        }
+        handle
+    }
+
+    /// Removes a node from the dialogue. Every choice and link that leads to the node will lead
+    /// nowhere after that, which means that they end the dialogue.
+    pub fn remove_node(&mut self, handle: Handle<DialogueNode>) -> DialogueNode {
+        for other in self.nodes.iter_mut() {
+            for choice in other.choices.iter_mut() {
+                if choice.target == handle {
+                    choice.target = Handle::NONE;
+                }
+            }
+            other.next.retain(|link| link.target != handle);
+        }
+        if self.entry == handle {
+            self.entry = Handle::NONE;
+        }
+        self.nodes.free(handle)
+    }
+
+    /// Returns a reference to the nodes container.
+    pub fn nodes(&self) -> &Pool<DialogueNode> {
+        &self.nodes
+    }
+
+    /// Returns a reference to the nodes container.
+    pub fn nodes_mut(&mut self) -> &mut Pool<DialogueNode> {
+        &mut self.nodes
+    }
+
+    /// Sets a node, from which dialogue will start.
+    pub fn set_entry(&mut self, entry: Handle<DialogueNode>) {
+        self.entry = entry;
+    }
+
+    /// Returns a handle of a node, from which dialogue will start.
+    pub fn entry(&self) -> Handle<DialogueNode> {
+        self.entry
+    }
+}
+
+/// Type alias for dialogue resources.
+pub type DialogueResource = Resource<Dialogue>;
@@ -0,0 +1,301 @@
+//! Runtime player of dialogues. See [`DialoguePlayer`] docs for more info.
+
+use crate::{
+    asset::ResourceStateRef,
+    core::pool::Handle,
+    fxhash::FxHashMap,
+    resource::dialogue::{are_conditions_satisfied, Dialogue, DialogueNode, DialogueResource},
+};
+
+/// A source of localized texts for dialogues. It is used to convert text keys of dialogue lines
+/// and choices into actual text in the current language.
+pub trait LocalizedTextProvider {
+    /// Returns a localized text for the given key or `None` if there's no such key.
+    fn localized_text(&self, key: &str) -> Option<String>;
+}
+
+impl LocalizedTextProvider for FxHashMap<String, String> {
+    fn localized_text(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+fn localize(provider: &dyn LocalizedTextProvider, key: &str) -> String {
+    provider
+        .localized_text(key)
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// A choice of a dialogue line, that is available to pick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DialogueLineChoice {
+    /// Index of the choice in the node, it should be passed to [`DialoguePlayer::choose`].
+    pub index: usize,
+    /// Localized text of the choice.
+    pub text: String,
+}
+
+/// Current line of a dialogue with localized texts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DialogueLine {
+    /// Name of the speaker.
+    pub speaker: String,
+    /// Localized text of the line.
+    pub text: String,
+    /// A set of choices with satisfied conditions.
+    pub choices: Vec<DialogueLineChoice>,
+}
+
+/// Dialogue player walks the graph of a [`Dialogue`], keeps track of dialogue variables and produces
+/// localized lines. It is not a scene node, it is meant to be owned by a script or a plugin that
+/// shows the lines in the UI.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     fxhash::FxHashMap,
+///     resource::dialogue::{player::DialoguePlayer, DialogueResource},
+/// };
+///
+/// fn talk(dialogue: DialogueResource, texts: &FxHashMap<String, String>) {
+///     let mut player = DialoguePlayer::new(dialogue);
+///     player.start();
+///     while let Some(line) = player.current_line(texts) {
+///         println!("{}: {}", line.speaker, line.text);
+///         if let Some(choice) = line.choices.first() {
+///             player.choose(choice.index);
+///         } else {
+///             player.advance();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DialoguePlayer {
+    dialogue: DialogueResource,
+    current: Handle<DialogueNode>,
+    variables: FxHashMap<String, i64>,
+}
+
+impl DialoguePlayer {
+    /// Creates a new player for the given dialogue. Use [`Self::start`] to begin the dialogue.
+    pub fn new(dialogue: DialogueResource) -> Self {
+        Self {
+            dialogue,
+            current: Handle::NONE,
+            variables: Default::default(),
+        }
+    }
+
+    /// Returns a reference to the dialogue resource.
+    pub fn dialogue(&self) -> &DialogueResource {
+        &self.dialogue
+    }
+
+    /// Starts the dialogue from its entry node. Dialogue variables are kept, so they could be set
+    /// before the start. Does nothing if the dialogue is not loaded.
+    pub fn start(&mut self) {
+        let resource = self.dialogue.clone();
+        let state = resource.state();
+        if let ResourceStateRef::Ok(dialogue) = state.get() {
+            self.enter(dialogue, dialogue.entry());
+        }
+    }
+
+    /// Stops the dialogue.
+    pub fn stop(&mut self) {
+        self.current = Handle::NONE;
+    }
+
+    /// Returns `true` if the dialogue wasn't started or has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Returns a handle of the current node of the dialogue.
+    pub fn current_node(&self) -> Handle<DialogueNode> {
+        self.current
+    }
+
+    /// Returns the current line of the dialogue with the texts localized using the given provider.
+    /// Keys that are missing in the provider are used as is.
+    pub fn current_line(&self, provider: &dyn LocalizedTextProvider) -> Option<DialogueLine> {
+        let state = self.dialogue.state();
+        let ResourceStateRef::Ok(dialogue) = state.get() else {
+            return None;
+        };
+        let node = dialogue.nodes().try_borrow(self.current)?;
+        Some(DialogueLine {
+            speaker: node.speaker.clone(),
+            text: localize(provider, &node.text_key),
+            choices: node
+                .choices
+                .iter()
+                .enumerate()
+                .filter(|(_, choice)| are_conditions_satisfied(&choice.conditions, &self.variables))
+                .map(|(index, choice)| DialogueLineChoice {
+                    index,
+                    text: localize(provider, &choice.text_key),
+                })
+                .collect(),
+        })
+    }
+
+    /// Picks a choice with the given index of the current node. Returns `false` if there's no such
+    /// choice or its conditions are not satisfied.
+    pub fn choose(&mut self, index: usize) -> bool {
+        let resource = self.dialogue.clone();
+        let state = resource.state();
+        let ResourceStateRef::Ok(dialogue) = state.get() else {
+            return false;
+        };
+        let Some(target) = dialogue
+            .nodes()
+            .try_borrow(self.current)
+            .and_then(|node| node.choices.get(index))
+            .filter(|choice| are_conditions_satisfied(&choice.conditions, &self.variables))
+            .map(|choice| choice.target)
+        else {
+            return false;
+        };
+        self.enter(dialogue, target);
+        true
+    }
+
+    /// Moves the dialogue to the next node using the first link with satisfied conditions, or ends
+    /// the dialogue if there's no such link. Returns `false` if the current node has available
+    /// choices, in this case [`Self::choose`] must be used instead.
+    pub fn advance(&mut self) -> bool {
+        let resource = self.dialogue.clone();
+        let state = resource.state();
+        let ResourceStateRef::Ok(dialogue) = state.get() else {
+            return false;
+        };
+        let Some(node) = dialogue.nodes().try_borrow(self.current) else {
+            return false;
+        };
+        if node
+            .choices
+            .iter()
+            .any(|choice| are_conditions_satisfied(&choice.conditions, &self.variables))
+        {
+            return false;
+        }
+        let target = node
+            .next
+            .iter()
+            .find(|link| are_conditions_satisfied(&link.conditions, &self.variables))
+            .map(|link| link.target)
+            .unwrap_or_default();
+        self.enter(dialogue, target);
+        true
+    }
+
+    /// Sets a value of a dialogue variable.
+    pub fn set_variable(&mut self, name: impl Into<String>, value: i64) {
+        self.variables.insert(name.into(), value);
+    }
+
+    /// Returns a value of a dialogue variable. Variables that were never set are treated as zero.
+    pub fn variable(&self, name: &str) -> i64 {
+        self.variables.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Returns a reference to every dialogue variable.
+    pub fn variables(&self) -> &FxHashMap<String, i64> {
+        &self.variables
+    }
+
+    fn enter(&mut self, dialogue: &Dialogue, node: Handle<DialogueNode>) {
+        self.current = node;
+        if let Some(node) = dialogue.nodes().try_borrow(node) {
+            for action in node.actions.iter() {
+                action.apply(&mut self.variables);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        asset::Resource,
+        resource::dialogue::{
+            DialogueAction, DialogueActionKind, DialogueChoice, DialogueComparison,
+            DialogueCondition, DialogueLink,
+        },
+    };
+
+    #[test]
+    fn dialogue_player_walks_the_graph() {
+        let mut dialogue = Dialogue::default();
+        let greeting = dialogue.add_node(DialogueNode {
+            speaker: "Guard".to_string(),
+            text_key: "greeting".to_string(),
+            ..Default::default()
+        });
+        let bribe = dialogue.add_node(DialogueNode {
+            text_key: "bribe".to_string(),
+            actions: vec![DialogueAction {
+                variable: "gold".to_string(),
+                kind: DialogueActionKind::Add,
+                value: -10,
+            }],
+            ..Default::default()
+        });
+        let pass = dialogue.add_node(DialogueNode {
+            text_key: "pass".to_string(),
+            ..Default::default()
+        });
+        let rich = DialogueCondition {
+            variable: "gold".to_string(),
+            comparison: DialogueComparison::Greater,
+            value: 5,
+        };
+        dialogue.nodes_mut()[greeting].choices = vec![
+            DialogueChoice {
+                text_key: "offer".to_string(),
+                conditions: vec![rich],
+                target: bribe,
+            },
+            DialogueChoice {
+                text_key: "leave".to_string(),
+                conditions: vec![],
+                target: Handle::NONE,
+            },
+        ];
+        dialogue.nodes_mut()[bribe].next = vec![DialogueLink {
+            conditions: vec![],
+            target: pass,
+        }];
+
+        let mut texts = FxHashMap::default();
+        texts.insert("greeting".to_string(), "Halt!".to_string());
+
+        let mut player = DialoguePlayer::new(Resource::new_ok(dialogue));
+        assert!(player.is_finished());
+
+        // Not enough gold - only one choice is available.
+        player.start();
+        let line = player.current_line(&texts).unwrap();
+        assert_eq!(line.speaker, "Guard");
+        assert_eq!(line.text, "Halt!");
+        assert_eq!(line.choices.len(), 1);
+        assert_eq!(line.choices[0].text, "leave");
+        assert!(!player.choose(0));
+        assert!(!player.advance());
+
+        player.set_variable("gold", 15);
+        player.start();
+        assert_eq!(player.current_line(&texts).unwrap().choices.len(), 2);
+        assert!(player.choose(0));
+        assert_eq!(player.current_node(), bribe);
+        assert_eq!(player.variable("gold"), 5);
+        assert!(player.advance());
+        assert_eq!(player.current_line(&texts).unwrap().text, "pass");
+        assert!(player.advance());
+        assert!(player.is_finished());
+    }
+}
@@ -2,7 +2,14 @@
 
 #![warn(missing_docs)]
 
+pub mod behavior_tree;
+pub mod boot_config;
 pub mod curve;
+pub mod dialogue;
 pub mod fbx;
 pub mod model;
+pub mod post_effects;
+pub mod sound_bank;
+pub mod spritesheet;
+pub mod string_table;
 pub mod texture;
@@ -59,12 +59,18 @@ impl ResourceLoader for ModelLoader {
                 &path,
                 io,
                 node_constructors,
-                resource_manager,
+                resource_manager.clone(),
                 import_options,
             )
             .await
             {
                 Ok(raw_model) => {
+                    // Make sure that every resource, that is used by the model, is loaded before
+                    // the model itself.
+                    resource_manager
+                        .wait_for_data_dependencies(&model, &raw_model)
+                        .await;
+
                     Log::info(format!("Model {:?} is loaded!", path));
 
                     model.commit_ok(raw_model);
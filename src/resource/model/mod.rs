@@ -21,8 +21,8 @@
 use crate::{
     animation::Animation,
     asset::{
-        manager::ResourceManager, options::ImportOptions, Resource, ResourceData,
-        MODEL_RESOURCE_UUID,
+        io::write_atomically, manager::ResourceManager, options::ImportOptions, Resource,
+        ResourceData, MODEL_RESOURCE_UUID,
     },
     core::{
         algebra::{UnitQuaternion, Vector3},
@@ -70,6 +70,9 @@ pub struct Model {
     pub(crate) mapping: NodeMapping,
     #[visit(skip)]
     pub(crate) scene: Scene,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) dirty: bool,
 }
 
 impl TypeUuidProvider for Model {
@@ -94,6 +97,22 @@ pub trait ModelResourceExtension: Sized {
     /// Tries to instantiate model from given resource.
     fn instantiate(&self, dest_scene: &mut Scene) -> Handle<Node>;
 
+    /// Instantiates a prefab and applies resource overrides of the destination scene to the
+    /// instance. See [`crate::scene::resource_override::ResourceOverrideTable`] docs for more info.
+    fn instantiate_with_overrides(
+        &self,
+        dest_scene: &mut Scene,
+        resource_manager: &ResourceManager,
+    ) -> Handle<Node>;
+
+    /// Instantiates only a sub-tree of a prefab, that starts from the first node with the given
+    /// name. It allows to use a single source file (for example, a modular kit with a lot of
+    /// pieces) as a library of prefabs. The instance keeps the local transform of the node, but not
+    /// the transforms of its ancestors in the prefab. The instance is linked with the root of the
+    /// given graph, and it is a regular prefab instance - property inheritance and integrity
+    /// restoration work for it as usual. Returns `None` if there's no node with the given name.
+    fn instantiate_sub(&self, dest_graph: &mut Graph, name: &str) -> Option<Handle<Node>>;
+
     /// Instantiates a prefab and places it at specified position and orientation in global coordinates.
     fn instantiate_at(
         &self,
@@ -190,6 +209,31 @@ impl ModelResourceExtension for ModelResource {
         instance_root
     }
 
+    fn instantiate_sub(&self, dest_graph: &mut Graph, name: &str) -> Option<Handle<Node>> {
+        let data = self.data_ref();
+
+        let (sub_root, _) = data.scene.graph.find_by_name_from_root(name)?;
+        let instance_root = Self::instantiate_from(self.clone(), &data, sub_root, dest_graph).0;
+
+        dest_graph[instance_root].is_resource_instance_root = true;
+
+        Some(instance_root)
+    }
+
+    fn instantiate_with_overrides(
+        &self,
+        dest_scene: &mut Scene,
+        resource_manager: &ResourceManager,
+    ) -> Handle<Node> {
+        let instance_root = self.instantiate(dest_scene);
+
+        dest_scene
+            .resource_overrides
+            .apply(&mut dest_scene.graph, instance_root, resource_manager);
+
+        instance_root
+    }
+
     fn instantiate_at(
         &self,
         scene: &mut Scene,
@@ -313,9 +357,18 @@ impl ResourceData for Model {
     fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
         let mut visitor = Visitor::new();
         self.scene.save("Scene", &mut visitor)?;
-        visitor.save_binary(path)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
         Ok(())
     }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
 }
 
 impl Default for Model {
@@ -324,6 +377,7 @@ impl Default for Model {
             path: PathBuf::new(),
             mapping: NodeMapping::UseNames,
             scene: Scene::new(),
+            dirty: false,
         }
     }
 }
@@ -525,6 +579,7 @@ impl Model {
             path: path.as_ref().to_owned(),
             scene,
             mapping,
+            dirty: false,
         })
     }
 
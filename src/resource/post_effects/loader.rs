@@ -0,0 +1,61 @@
+//! Post effects loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::post_effects::PostEffects,
+};
+use std::sync::Arc;
+
+/// Default implementation for post effects loading.
+pub struct PostEffectsLoader {
+    /// Resource manager that will be used to load textures of post effects.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for PostEffectsLoader {
+    fn extensions(&self) -> &[&str] {
+        &["posteffects"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        PostEffects::type_uuid()
+    }
+
+    fn load(
+        &self,
+        post_effects: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = post_effects.path();
+
+            match PostEffects::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(post_effects_state) => {
+                    Log::info(format!("Post effects {:?} is loaded!", path));
+
+                    post_effects.commit_ok(post_effects_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(post_effects, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load post effects from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    post_effects.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
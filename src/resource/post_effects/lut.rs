@@ -0,0 +1,241 @@
+//! Conversion of `.cube` color look-up tables (a de-facto standard format of LUTs, supported by
+//! most of the color grading software) to unwrapped look-up table strips, that could be used
+//! by [`crate::scene::camera::ColorGradingLut`].
+
+use std::fmt::{Display, Formatter};
+
+/// Size of a side of the look-up table cube, that is used by the renderer.
+pub const LUT_SIZE: usize = 16;
+
+/// Width of an unwrapped look-up table strip in pixels.
+pub const LUT_STRIP_WIDTH: usize = LUT_SIZE * LUT_SIZE;
+
+/// Height of an unwrapped look-up table strip in pixels.
+pub const LUT_STRIP_HEIGHT: usize = LUT_SIZE;
+
+/// An error that may occur during `.cube` file parsing.
+#[derive(Debug, PartialEq)]
+pub enum CubeLutError {
+    /// There's no `LUT_3D_SIZE` keyword in the file.
+    MissingSize,
+    /// 1D look-up tables are not supported.
+    Unsupported1D,
+    /// Domain of the table is not `[0; 1]`.
+    UnsupportedDomain,
+    /// A line could not be parsed.
+    InvalidLine {
+        /// Number of the line (starting from 1).
+        line: usize,
+    },
+    /// Amount of entries does not match the size of the table.
+    InvalidEntryCount {
+        /// Expected amount of entries.
+        expected: usize,
+        /// Actual amount of entries.
+        actual: usize,
+    },
+}
+
+impl Display for CubeLutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CubeLutError::MissingSize => write!(f, "LUT_3D_SIZE is not specified."),
+            CubeLutError::Unsupported1D => write!(f, "1D look-up tables are not supported."),
+            CubeLutError::UnsupportedDomain => {
+                write!(f, "Only look-up tables with [0; 1] domain are supported.")
+            }
+            CubeLutError::InvalidLine { line } => write!(f, "Unable to parse line {line}."),
+            CubeLutError::InvalidEntryCount { expected, actual } => write!(
+                f,
+                "Invalid amount of entries. Expected {expected}, got {actual}."
+            ),
+        }
+    }
+}
+
+/// A 3D color look-up table, parsed from a `.cube` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeLut {
+    /// Size of a side of the cube.
+    pub size: usize,
+    /// Entries of the table, red component changes fastest, then green, then blue.
+    pub entries: Vec<[f32; 3]>,
+}
+
+fn parse_floats<const N: usize>(line: &str, line_number: usize) -> Result<[f32; N], CubeLutError> {
+    let mut values = [0.0; N];
+    let mut components = line.split_whitespace();
+    for value in values.iter_mut() {
+        *value = components
+            .next()
+            .and_then(|c| c.parse().ok())
+            .ok_or(CubeLutError::InvalidLine { line: line_number })?;
+    }
+    if components.next().is_some() {
+        return Err(CubeLutError::InvalidLine { line: line_number });
+    }
+    Ok(values)
+}
+
+impl CubeLut {
+    /// Parses a look-up table from the content of a `.cube` file.
+    pub fn parse(source: &str) -> Result<Self, CubeLutError> {
+        let mut size = None;
+        let mut entries = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match keyword {
+                "TITLE" => (),
+                "LUT_1D_SIZE" => return Err(CubeLutError::Unsupported1D),
+                "LUT_3D_SIZE" => {
+                    size = Some(
+                        rest.trim()
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|size| *size >= 2)
+                            .ok_or(CubeLutError::InvalidLine { line: line_number })?,
+                    );
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let expected = if keyword == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                    if parse_floats::<3>(rest, line_number)? != [expected; 3] {
+                        return Err(CubeLutError::UnsupportedDomain);
+                    }
+                }
+                _ => entries.push(parse_floats::<3>(line, line_number)?),
+            }
+        }
+
+        let size = size.ok_or(CubeLutError::MissingSize)?;
+        let expected = size * size * size;
+        if entries.len() != expected {
+            return Err(CubeLutError::InvalidEntryCount {
+                expected,
+                actual: entries.len(),
+            });
+        }
+
+        Ok(Self { size, entries })
+    }
+
+    fn entry(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+        self.entries[r + g * self.size + b * self.size * self.size]
+    }
+
+    /// Samples the table at the given color using trilinear interpolation.
+    pub fn sample(&self, color: [f32; 3]) -> [f32; 3] {
+        let max = (self.size - 1) as f32;
+        let coords = color.map(|c| c.clamp(0.0, 1.0) * max);
+        let base = coords.map(|c| (c.floor() as usize).min(self.size - 2));
+
+        let mut result = [0.0; 3];
+        for corner in 0..8 {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let weight = (0..3)
+                .map(|i| {
+                    let fract = coords[i] - base[i] as f32;
+                    if offset[i] == 1 {
+                        fract
+                    } else {
+                        1.0 - fract
+                    }
+                })
+                .product::<f32>();
+            let entry = self.entry(
+                base[0] + offset[0],
+                base[1] + offset[1],
+                base[2] + offset[2],
+            );
+            for (component, value) in result.iter_mut().zip(entry) {
+                *component += value * weight;
+            }
+        }
+        result
+    }
+
+    /// Resamples the table to [`LUT_SIZE`] cube and unwraps it to a RGB8 strip of
+    /// [`LUT_STRIP_WIDTH`]x[`LUT_STRIP_HEIGHT`] pixels, which is the format of textures expected by
+    /// [`crate::scene::camera::ColorGradingLut::new`]. Blue slices of the cube are placed one after
+    /// another horizontally, red changes along the width of a slice and green along the height.
+    pub fn to_strip(&self) -> Vec<u8> {
+        let mut bytes = vec![0; LUT_STRIP_WIDTH * LUT_STRIP_HEIGHT * 3];
+        let max = (LUT_SIZE - 1) as f32;
+        for b in 0..LUT_SIZE {
+            for g in 0..LUT_SIZE {
+                for r in 0..LUT_SIZE {
+                    let color = self.sample([r as f32 / max, g as f32 / max, b as f32 / max]);
+                    let position = (g * LUT_STRIP_WIDTH + b * LUT_SIZE + r) * 3;
+                    for (i, component) in color.iter().enumerate() {
+                        bytes[position + i] = (component.clamp(0.0, 1.0) * 255.0).round() as u8;
+                    }
+                }
+            }
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const IDENTITY: &str = r#"
+# Identity LUT
+TITLE "Identity"
+LUT_3D_SIZE 2
+DOMAIN_MIN 0.0 0.0 0.0
+DOMAIN_MAX 1.0 1.0 1.0
+0.0 0.0 0.0
+1.0 0.0 0.0
+0.0 1.0 0.0
+1.0 1.0 0.0
+0.0 0.0 1.0
+1.0 0.0 1.0
+0.0 1.0 1.0
+1.0 1.0 1.0
+"#;
+
+    #[test]
+    fn test_parse_cube_lut() {
+        let lut = CubeLut::parse(IDENTITY).unwrap();
+        assert_eq!(lut.size, 2);
+        assert_eq!(lut.entries.len(), 8);
+        let sample = lut.sample([0.25, 0.5, 0.75]);
+        for (actual, expected) in sample.iter().zip([0.25, 0.5, 0.75]) {
+            assert!((actual - expected).abs() < 1.0e-6);
+        }
+
+        assert_eq!(
+            CubeLut::parse("LUT_3D_SIZE 2\n0 0 0"),
+            Err(CubeLutError::InvalidEntryCount {
+                expected: 8,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_1D_SIZE 2"),
+            Err(CubeLutError::Unsupported1D)
+        );
+        assert_eq!(CubeLut::parse("0 0 0"), Err(CubeLutError::MissingSize));
+        assert_eq!(
+            CubeLut::parse("LUT_3D_SIZE 2\n0 0 x"),
+            Err(CubeLutError::InvalidLine { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_cube_lut_to_strip() {
+        let strip = CubeLut::parse(IDENTITY).unwrap().to_strip();
+        assert_eq!(strip.len(), LUT_STRIP_WIDTH * LUT_STRIP_HEIGHT * 3);
+
+        // Pixel of (r = 15, g = 5, b = 10) entry of the identity table.
+        let position = (5 * LUT_STRIP_WIDTH + 10 * LUT_SIZE + 15) * 3;
+        assert_eq!(&strip[position..position + 3], &[255, 85, 170]);
+    }
+}
@@ -0,0 +1,333 @@
+//! Post effects resource contains settings of the post-processing stack. See [`PostEffects`] docs
+//! for more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData,
+    },
+    core::{
+        color::Color,
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::texture::TextureResource,
+    scene::camera::ColorGradingLut,
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+pub mod lut;
+
+/// An error that may occur during post effects loading.
+#[derive(Debug)]
+pub enum PostEffectsError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for PostEffectsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostEffectsError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            PostEffectsError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for PostEffectsError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for PostEffectsError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Tonemapping operator, that maps high dynamic range colors of a frame to low dynamic range.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum Tonemapping {
+    /// No tonemapping, colors are just clamped to `[0; 1]` range.
+    None,
+    /// `1 - exp(-color)` curve. This is the operator, that is used when there's no post effects.
+    #[default]
+    Exponential,
+    /// Classic Reinhard operator (`color / (1 + color)`), it preserves more details in bright regions.
+    Reinhard,
+    /// An approximation of ACES filmic curve, it gives more contrast and saturated colors.
+    Aces,
+}
+
+impl Tonemapping {
+    /// Returns an index of the operator in the HDR mapping shader.
+    pub fn shader_index(self) -> i32 {
+        match self {
+            Tonemapping::None => 0,
+            Tonemapping::Exponential => 1,
+            Tonemapping::Reinhard => 2,
+            Tonemapping::Aces => 3,
+        }
+    }
+}
+
+/// Bloom settings. Bloom adds a glow around bright parts of a frame.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct Bloom {
+    /// Whether the bloom is enabled or not.
+    pub enabled: bool,
+    /// Strength of the glow, `1.0` is the default strength.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub intensity: f32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl Bloom {
+    /// Returns the intensity, that should be used for rendering. It is zero when the bloom is
+    /// disabled.
+    pub fn effective_intensity(&self) -> f32 {
+        if self.enabled {
+            self.intensity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Color grading settings. See [`ColorGradingLut`] docs for more info about color grading.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct ColorGrading {
+    /// Whether the color grading is enabled or not.
+    pub enabled: bool,
+    /// Unwrapped look-up table (16x16x16 cube unwrapped to a 256x16 strip). See
+    /// [`ColorGradingLut::new`] for the requirements for the texture and [`lut`] module for the
+    /// way to convert `.cube` files to such textures.
+    pub lut: Option<TextureResource>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    baked: Option<ColorGradingLut>,
+}
+
+/// Vignette settings. Vignette darkens (or tints) edges of a frame.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct Vignette {
+    /// Whether the vignette is enabled or not.
+    pub enabled: bool,
+    /// Strength of the effect in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub intensity: f32,
+    /// Distance from the center of the frame (in `[0; 1]` range, where `1.0` is a corner) at which
+    /// the effect starts.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub radius: f32,
+    /// Width of the transition between unaffected and affected parts of the frame.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub smoothness: f32,
+    /// Color of the edges.
+    pub color: Color,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            intensity: 0.5,
+            radius: 0.75,
+            smoothness: 0.4,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// Post effects is a post-processing stack, that defines how a rendered frame is converted to its
+/// final look: tonemapping, bloom, color grading and vignette. The stack could be assigned to a
+/// scene (see [`crate::scene::SceneRenderingOptions::post_effects`]) to be used by all of its
+/// cameras, and every camera could override it with its own stack (see
+/// [`crate::scene::camera::Camera::set_post_effects`]). Since it is a resource, changes of the stack
+/// are visible in every scene that use it right away, which allows to tweak the effects with live
+/// preview.
+///
+/// When there's no stack for a camera, its own exposure and color grading settings are used with
+/// exponential tonemapping. Exposure is always taken from the camera.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     resource::post_effects::{PostEffects, PostEffectsResource, Tonemapping},
+///     scene::Scene,
+/// };
+///
+/// fn setup_post_effects(scene: &mut Scene) {
+///     let mut post_effects = PostEffects::default();
+///     post_effects.tonemapping = Tonemapping::Aces;
+///     post_effects.vignette.enabled = true;
+///     scene.rendering_options.post_effects = Some(PostEffectsResource::new_ok(post_effects));
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct PostEffects {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// Tonemapping operator.
+    pub tonemapping: Tonemapping,
+    /// Bloom settings.
+    pub bloom: Bloom,
+    /// Color grading settings.
+    pub color_grading: ColorGrading,
+    /// Vignette settings.
+    pub vignette: Vignette,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl TypeUuidProvider for PostEffects {
+    fn type_uuid() -> Uuid {
+        uuid!("4c8f2e61-9b3a-4d7e-a1f5-2e6b9c0d8a43")
+    }
+}
+
+impl ResourceData for PostEffects {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
+        self.visit("PostEffects", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl PostEffects {
+    /// Current version of the format of post effects files. It must be bumped every time when the
+    /// format is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
+    /// Loads post effects from the specific file path. Resource manager is used to load the
+    /// textures of the stack.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, PostEffectsError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut post_effects = PostEffects::default();
+        post_effects.visit("PostEffects", &mut visitor)?;
+        post_effects.path = path.to_path_buf();
+        Ok(post_effects)
+    }
+
+    /// Returns a color grading look-up table, that is ready to be used for rendering. The table
+    /// is built from [`ColorGrading::lut`] texture once it is loaded, and it is rebuilt every time
+    /// when the texture is changed. `None` means that there's no texture, it is still loading or
+    /// it is not suitable for color grading.
+    pub fn color_grading_lut(&mut self) -> Option<&ColorGradingLut> {
+        let color_grading = &mut self.color_grading;
+        let Some(lut) = color_grading.lut.as_ref() else {
+            color_grading.baked = None;
+            return None;
+        };
+
+        let is_outdated = color_grading
+            .baked
+            .as_ref()
+            .map_or(true, |baked| &baked.unwrapped_lut() != lut);
+        if is_outdated {
+            color_grading.baked = if lut.is_ok() {
+                ColorGradingLut::try_from_loaded(lut.clone()).ok()
+            } else {
+                None
+            };
+        }
+
+        color_grading.baked.as_ref()
+    }
+}
+
+/// Type alias for post effects resources.
+pub type PostEffectsResource = Resource<PostEffects>;
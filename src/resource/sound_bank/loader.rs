@@ -0,0 +1,61 @@
+//! Sound bank loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::sound_bank::SoundBank,
+};
+use std::sync::Arc;
+
+/// Default implementation for sound bank loading.
+pub struct SoundBankLoader {
+    /// Resource manager that will be used to load sound buffers of sound banks.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for SoundBankLoader {
+    fn extensions(&self) -> &[&str] {
+        &["soundbank"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        SoundBank::type_uuid()
+    }
+
+    fn load(
+        &self,
+        bank: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = bank.path();
+
+            match SoundBank::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(bank_state) => {
+                    Log::info(format!("Sound bank {:?} is loaded!", path));
+
+                    bank.commit_ok(bank_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(bank, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load sound bank from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    bank.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,275 @@
+//! Sound bank resource contains a set of named sound cues. See [`SoundBank`] docs for more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData,
+    },
+    core::{
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::sound::{AudioBusGraph, SoundBufferResource},
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+pub mod loader;
+pub mod player;
+
+/// An error that may occur during sound bank loading.
+#[derive(Debug)]
+pub enum SoundBankError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for SoundBankError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoundBankError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            SoundBankError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SoundBankError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SoundBankError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Defines how a variation of a sound cue is selected when the cue is triggered.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    EnumVariantNames,
+    EnumString,
+    AsRefStr,
+)]
+pub enum SoundCueSelection {
+    /// A random variation is selected, variations with higher weight are selected more often.
+    #[default]
+    Random,
+    /// Variations are selected one by one in the order of the list.
+    RoundRobin,
+}
+
+/// A single variation of a sound cue.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SoundCueVariation {
+    /// A sound buffer that will be played.
+    pub buffer: Option<SoundBufferResource>,
+    /// Relative probability of the variation to be selected, it is used only with
+    /// [`SoundCueSelection::Random`].
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub weight: f32,
+}
+
+impl Default for SoundCueVariation {
+    fn default() -> Self {
+        Self {
+            buffer: None,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Sound cue is a named sound event, that groups a set of variations of a sound with randomization
+/// rules. Every time when the cue is triggered, a variation is selected and played with random gain
+/// and pitch from the respective ranges.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SoundCue {
+    /// Name of the event that triggers the cue.
+    pub name: String,
+    /// A set of variations of the cue.
+    pub variations: Vec<SoundCueVariation>,
+    /// Defines how a variation is selected.
+    pub selection: SoundCueSelection,
+    /// A range of gain of the sound.
+    pub gain: Range<f32>,
+    /// A range of pitch of the sound.
+    pub pitch: Range<f32>,
+    /// Probability (`[0; 1]` range) of the cue to play anything when it is triggered. It is
+    /// useful to make frequent sounds less repetitive.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub probability: f32,
+    /// Name of an audio bus the sound will be routed to.
+    pub audio_bus: String,
+    /// Spatial blend factor of the sound, see [`crate::scene::sound::Sound::set_spatial_blend`].
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub spatial_blend: f32,
+}
+
+impl Default for SoundCue {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            variations: Default::default(),
+            selection: Default::default(),
+            gain: 1.0..1.0,
+            pitch: 1.0..1.0,
+            probability: 1.0,
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            spatial_blend: 1.0,
+        }
+    }
+}
+
+/// Sound bank is a set of [`SoundCue`]s, every cue could be triggered by its name from scripts
+/// using [`player::SoundBankPlayer`]. It removes the need to create sound nodes with randomized
+/// parameters by hand for every footstep, gunshot, etc.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::resource::sound_bank::{SoundBank, SoundCue, SoundCueSelection, SoundCueVariation};
+/// use fyrox::scene::sound::SoundBufferResource;
+///
+/// fn make_bank(steps: Vec<SoundBufferResource>) -> SoundBank {
+///     let mut bank = SoundBank::default();
+///     bank.cues.push(SoundCue {
+///         name: "footstep".to_string(),
+///         variations: steps
+///             .into_iter()
+///             .map(|buffer| SoundCueVariation {
+///                 buffer: Some(buffer),
+///                 weight: 1.0,
+///             })
+///             .collect(),
+///         selection: SoundCueSelection::RoundRobin,
+///         pitch: 0.9..1.1,
+///         ..Default::default()
+///     });
+///     bank
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct SoundBank {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// A set of cues of the bank.
+    pub cues: Vec<SoundCue>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl TypeUuidProvider for SoundBank {
+    fn type_uuid() -> Uuid {
+        uuid!("b7e4a9c2-1d36-4f85-9a0b-6c2e8f51d3a7")
+    }
+}
+
+impl ResourceData for SoundBank {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
+        self.visit("SoundBank", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl SoundBank {
+    /// Current version of the format of sound bank files. It must be bumped every time when the format
+    /// is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
+    /// Loads a sound bank from the specific file path. Resource manager is used to load sound
+    /// buffers of the cues.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, SoundBankError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut bank = SoundBank::default();
+        bank.visit("SoundBank", &mut visitor)?;
+        bank.path = path.to_path_buf();
+        Ok(bank)
+    }
+
+    /// Tries to find a cue with the given name.
+    pub fn cue(&self, name: &str) -> Option<&SoundCue> {
+        self.cues.iter().find(|cue| cue.name == name)
+    }
+}
+
+/// Type alias for sound bank resources.
+pub type SoundBankResource = Resource<SoundBank>;
@@ -0,0 +1,231 @@
+//! Runtime player of sound banks. See [`SoundBankPlayer`] docs for more info.
+
+use crate::{
+    asset::ResourceStateRef,
+    core::{algebra::Vector3, numeric_range::RangeExt, pool::Handle},
+    fxhash::FxHashMap,
+    rand::{self, Rng},
+    resource::sound_bank::{SoundBankResource, SoundCue, SoundCueSelection},
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{SoundBufferResource, SoundBuilder, Status},
+        transform::TransformBuilder,
+    },
+};
+
+/// Parameters of a sound, that was produced by a triggered [`SoundCue`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoundCueInstance {
+    /// A sound buffer of the selected variation.
+    pub buffer: SoundBufferResource,
+    /// Gain of the sound.
+    pub gain: f32,
+    /// Pitch of the sound.
+    pub pitch: f32,
+    /// Name of an audio bus the sound should be routed to.
+    pub audio_bus: String,
+    /// Spatial blend factor of the sound.
+    pub spatial_blend: f32,
+}
+
+impl SoundCueInstance {
+    /// Creates a new play-once sound node at the given position, the node will be removed
+    /// automatically when the sound is played.
+    pub fn build(self, position: Vector3<f32>, graph: &mut Graph) -> Handle<Node> {
+        SoundBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .with_buffer(Some(self.buffer))
+        .with_gain(self.gain)
+        .with_pitch(self.pitch as f64)
+        .with_audio_bus(self.audio_bus)
+        .with_spatial_blend_factor(self.spatial_blend)
+        .with_play_once(true)
+        .with_status(Status::Playing)
+        .build(graph)
+    }
+}
+
+/// Sound bank player triggers cues of a sound bank by their names. It keeps the state of cues with
+/// [`SoundCueSelection::RoundRobin`] selection, so a player should be kept alive as long as the cues
+/// are triggered. It is not a scene node, it is meant to be owned by a script or a plugin.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::algebra::Vector3,
+///     resource::sound_bank::{player::SoundBankPlayer, SoundBankResource},
+///     scene::graph::Graph,
+/// };
+///
+/// fn make_step(player: &mut SoundBankPlayer, foot_position: Vector3<f32>, graph: &mut Graph) {
+///     player.trigger("footstep", foot_position, graph);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SoundBankPlayer {
+    bank: SoundBankResource,
+    round_robin: FxHashMap<String, usize>,
+}
+
+impl SoundBankPlayer {
+    /// Creates a new player for the given sound bank.
+    pub fn new(bank: SoundBankResource) -> Self {
+        Self {
+            bank,
+            round_robin: Default::default(),
+        }
+    }
+
+    /// Returns a reference to the sound bank resource.
+    pub fn bank(&self) -> &SoundBankResource {
+        &self.bank
+    }
+
+    /// Triggers a cue with the given name and creates a play-once sound node at the given position.
+    /// Returns [`Handle::NONE`] if there's no such cue, the bank is not loaded, or if the cue has
+    /// decided to play nothing (see [`SoundCue::probability`]).
+    pub fn trigger(
+        &mut self,
+        event: &str,
+        position: Vector3<f32>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        self.select(event, &mut rand::thread_rng())
+            .map(|instance| instance.build(position, graph))
+            .unwrap_or_default()
+    }
+
+    /// Selects a variation of a cue with the given name and randomizes its parameters using the
+    /// given random number generator. Unlike [`Self::trigger`], it does not create any sound nodes,
+    /// which is useful if the sound must be played in some special way.
+    pub fn select<R: Rng>(&mut self, event: &str, rng: &mut R) -> Option<SoundCueInstance> {
+        let state = self.bank.state();
+        let ResourceStateRef::Ok(bank) = state.get() else {
+            return None;
+        };
+        let cue = bank.cue(event)?;
+        if cue.variations.is_empty() || rng.gen::<f32>() >= cue.probability {
+            return None;
+        }
+        let index = match cue.selection {
+            SoundCueSelection::Random => weighted_index(cue, rng),
+            SoundCueSelection::RoundRobin => {
+                let counter = self.round_robin.entry(cue.name.clone()).or_default();
+                let index = *counter % cue.variations.len();
+                *counter = counter.wrapping_add(1);
+                index
+            }
+        };
+        let buffer = cue.variations[index].buffer.clone()?;
+        Some(SoundCueInstance {
+            buffer,
+            gain: cue.gain.random(rng),
+            pitch: cue.pitch.random(rng),
+            audio_bus: cue.audio_bus.clone(),
+            spatial_blend: cue.spatial_blend,
+        })
+    }
+}
+
+fn weighted_index<R: Rng>(cue: &SoundCue, rng: &mut R) -> usize {
+    let total = cue
+        .variations
+        .iter()
+        .map(|v| v.weight.max(0.0))
+        .sum::<f32>();
+    if total <= 0.0 {
+        return rng.gen_range(0..cue.variations.len());
+    }
+    let mut point = rng.gen::<f32>() * total;
+    for (index, variation) in cue.variations.iter().enumerate() {
+        let weight = variation.weight.max(0.0);
+        if point < weight {
+            return index;
+        }
+        point -= weight;
+    }
+    cue.variations.len() - 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        asset::{untyped::UntypedResource, Resource},
+        core::TypeUuidProvider,
+        rand::{rngs::StdRng, SeedableRng},
+        resource::sound_bank::{SoundBank, SoundCueVariation},
+        scene::sound::SoundBuffer,
+    };
+
+    fn variation(path: &str, weight: f32) -> SoundCueVariation {
+        SoundCueVariation {
+            buffer: Some(Resource::from(UntypedResource::new_pending(
+                path.into(),
+                <SoundBuffer as TypeUuidProvider>::type_uuid(),
+            ))),
+            weight,
+        }
+    }
+
+    #[test]
+    fn sound_bank_player_select() {
+        let bank = SoundBank {
+            cues: vec![
+                SoundCue {
+                    name: "step".to_string(),
+                    variations: vec![variation("a.wav", 1.0), variation("b.wav", 1.0)],
+                    selection: SoundCueSelection::RoundRobin,
+                    pitch: 0.9..1.1,
+                    ..Default::default()
+                },
+                SoundCue {
+                    name: "shot".to_string(),
+                    variations: vec![variation("c.wav", 0.0), variation("d.wav", 1.0)],
+                    audio_bus: "Weapons".to_string(),
+                    ..Default::default()
+                },
+                SoundCue {
+                    name: "never".to_string(),
+                    variations: vec![variation("e.wav", 1.0)],
+                    probability: 0.0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut rng = StdRng::seed_from_u64(123);
+        let mut player = SoundBankPlayer::new(Resource::new_ok(bank));
+
+        let paths = (0..3)
+            .map(|_| player.select("step", &mut rng).unwrap().buffer.path())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            ["a.wav", "b.wav", "a.wav"].map(std::path::PathBuf::from)
+        );
+
+        for _ in 0..10 {
+            let step = player.select("step", &mut rng).unwrap();
+            assert!((0.9..1.1).contains(&step.pitch));
+
+            let shot = player.select("shot", &mut rng).unwrap();
+            assert_eq!(shot.buffer.path(), std::path::Path::new("d.wav"));
+            assert_eq!(shot.audio_bus, "Weapons");
+            assert_eq!(shot.gain, 1.0);
+
+            assert!(player.select("never", &mut rng).is_none());
+        }
+
+        assert!(player.select("unknown", &mut rng).is_none());
+    }
+}
@@ -0,0 +1,350 @@
+//! Importer of sprite sheets exported from [Aseprite](https://www.aseprite.org/) in JSON format.
+//! Both "Hash" and "Array" layouts of frames are supported.
+
+use crate::{
+    core::{algebra::Vector2, math::Rect},
+    resource::{
+        spritesheet::{SpriteAnimation, SpriteSheet, SpriteSheetError, SpriteSheetFrame},
+        texture::TextureResource,
+    },
+};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Deserialize, Clone, Copy, Default)]
+struct AsepriteRect {
+    x: i64,
+    y: i64,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct AsepriteSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct AsepritePoint {
+    x: f32,
+    y: f32,
+}
+
+fn default_duration() -> u32 {
+    100
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteFrame {
+    frame: AsepriteRect,
+    #[serde(default)]
+    rotated: bool,
+    source_size: Option<AsepriteSize>,
+    sprite_source_size: Option<AsepriteRect>,
+    // Milliseconds.
+    #[serde(default = "default_duration")]
+    duration: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AsepriteFrames {
+    Array(Vec<AsepriteFrame>),
+    // Order of the frames is the order of the keys in the file.
+    Hash(serde_json::Map<String, serde_json::Value>),
+}
+
+#[derive(Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+    repeat: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSliceKey {
+    frame: usize,
+    bounds: AsepriteRect,
+    pivot: Option<AsepritePoint>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSlice {
+    keys: Vec<AsepriteSliceKey>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteMeta {
+    image: Option<String>,
+    size: AsepriteSize,
+    #[serde(default)]
+    frame_tags: Vec<AsepriteTag>,
+    #[serde(default)]
+    slices: Vec<AsepriteSlice>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSheet {
+    frames: AsepriteFrames,
+    meta: AsepriteMeta,
+}
+
+fn parse(json: &str) -> Result<AsepriteSheet, SpriteSheetError> {
+    serde_json::from_str(json).map_err(|e| SpriteSheetError::Import(e.to_string()))
+}
+
+fn tag_frames(tag: &AsepriteTag) -> Vec<usize> {
+    let forward = (tag.from..=tag.to).collect::<Vec<_>>();
+    let ping_pong = || {
+        let mut frames = forward.clone();
+        // Do not repeat first and last frames on the way back.
+        frames.extend((tag.from + 1..tag.to).rev());
+        frames
+    };
+    match tag.direction.as_str() {
+        "reverse" => forward.iter().rev().cloned().collect(),
+        "pingpong" => ping_pong(),
+        "pingpong_reverse" => {
+            let mut frames = ping_pong();
+            frames.reverse();
+            frames
+        }
+        _ => forward,
+    }
+}
+
+impl SpriteSheet {
+    /// Imports a sprite sheet from the JSON data exported from Aseprite. Frame tags become
+    /// animations, pivots of the frames are taken from the slices (if any). The texture must be
+    /// the image that was exported together with the JSON data, see
+    /// [`Self::aseprite_image_path`].
+    ///
+    /// ## Important notes
+    ///
+    /// Rotated frames are not supported, disable rotation in the export options of Aseprite.
+    pub fn from_aseprite_json(
+        json: &str,
+        texture: Option<TextureResource>,
+    ) -> Result<Self, SpriteSheetError> {
+        let sheet = parse(json)?;
+
+        let frames = match sheet.frames {
+            AsepriteFrames::Array(frames) => frames,
+            AsepriteFrames::Hash(map) => map
+                .into_iter()
+                .map(|(_, value)| serde_json::from_value(value))
+                .collect::<Result<Vec<AsepriteFrame>, _>>()
+                .map_err(|e| SpriteSheetError::Import(e.to_string()))?,
+        };
+
+        let mut result_frames = Vec::with_capacity(frames.len());
+        for (index, frame) in frames.iter().enumerate() {
+            if frame.rotated {
+                return Err(SpriteSheetError::Import(format!(
+                    "Frame {index} is rotated, rotated frames are not supported!"
+                )));
+            }
+            if frame.frame.x < 0 || frame.frame.y < 0 {
+                return Err(SpriteSheetError::Import(format!(
+                    "Frame {index} has negative position!"
+                )));
+            }
+
+            let size = frame.source_size.unwrap_or(AsepriteSize {
+                w: frame.frame.w,
+                h: frame.frame.h,
+            });
+            let pivot = AsepritePoint {
+                x: size.w as f32 * 0.5,
+                y: size.h as f32 * 0.5,
+            };
+
+            result_frames.push(SpriteSheetFrame {
+                rect: Rect::new(
+                    frame.frame.x as u32,
+                    frame.frame.y as u32,
+                    frame.frame.w,
+                    frame.frame.h,
+                ),
+                pivot: frame_pivot(frame, pivot),
+                duration: frame.duration as f32 / 1000.0,
+            });
+        }
+
+        // A slice key is applied to every frame starting from its frame until the next key.
+        for slice in sheet.meta.slices.iter() {
+            for (i, key) in slice.keys.iter().enumerate() {
+                let Some(pivot) = key.pivot else {
+                    continue;
+                };
+                let end = slice
+                    .keys
+                    .get(i + 1)
+                    .map_or(frames.len(), |next| next.frame.min(frames.len()));
+                for index in key.frame..end {
+                    result_frames[index].pivot = frame_pivot(
+                        &frames[index],
+                        AsepritePoint {
+                            x: key.bounds.x as f32 + pivot.x,
+                            y: key.bounds.y as f32 + pivot.y,
+                        },
+                    );
+                }
+            }
+        }
+
+        let mut animations = Vec::with_capacity(sheet.meta.frame_tags.len());
+        for tag in sheet.meta.frame_tags.iter() {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(SpriteSheetError::Import(format!(
+                    "Tag {} has invalid range of frames!",
+                    tag.name
+                )));
+            }
+            animations.push(SpriteAnimation {
+                name: tag.name.clone(),
+                frames: tag_frames(tag),
+                // Zero or absent repeat count means infinite repetition.
+                looping: tag.repeat.as_deref().map_or(true, |r| r == "0"),
+            });
+        }
+
+        Ok(Self {
+            path: Default::default(),
+            texture,
+            texture_size: Vector2::new(sheet.meta.size.w, sheet.meta.size.h),
+            frames: result_frames,
+            animations,
+            dirty: false,
+        })
+    }
+
+    /// Returns a path of the image of the sprite sheet from the JSON data exported from Aseprite.
+    /// The path is relative to the JSON file.
+    pub fn aseprite_image_path(json: &str) -> Result<Option<PathBuf>, SpriteSheetError> {
+        Ok(parse(json)?.meta.image.map(PathBuf::from))
+    }
+}
+
+// Converts a pivot in the coordinates of the source (untrimmed) image to the normalized coordinates
+// of a (possibly trimmed) frame.
+fn frame_pivot(frame: &AsepriteFrame, pivot: AsepritePoint) -> Vector2<f32> {
+    let offset = frame.sprite_source_size.unwrap_or_default();
+    Vector2::new(
+        (pivot.x - offset.x as f32) / frame.frame.w.max(1) as f32,
+        (pivot.y - offset.y as f32) / frame.frame.h.max(1) as f32,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn import_aseprite_hash() {
+        let json = r#"{
+            "frames": {
+                "knight 0.aseprite": {
+                    "frame": { "x": 0, "y": 0, "w": 16, "h": 16 },
+                    "rotated": false,
+                    "trimmed": false,
+                    "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+                    "sourceSize": { "w": 16, "h": 16 },
+                    "duration": 100
+                },
+                "knight 1.aseprite": {
+                    "frame": { "x": 16, "y": 0, "w": 16, "h": 16 },
+                    "rotated": false,
+                    "trimmed": false,
+                    "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+                    "sourceSize": { "w": 16, "h": 16 },
+                    "duration": 200
+                },
+                "knight 2.aseprite": {
+                    "frame": { "x": 32, "y": 0, "w": 16, "h": 16 },
+                    "rotated": false,
+                    "trimmed": false,
+                    "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 },
+                    "sourceSize": { "w": 16, "h": 16 },
+                    "duration": 100
+                }
+            },
+            "meta": {
+                "image": "knight.png",
+                "size": { "w": 48, "h": 16 },
+                "frameTags": [
+                    { "name": "attack", "from": 0, "to": 2, "direction": "pingpong" },
+                    { "name": "hit", "from": 1, "to": 2, "direction": "reverse", "repeat": "1" }
+                ],
+                "slices": [
+                    { "name": "pivot", "keys": [
+                        { "frame": 1, "bounds": { "x": 0, "y": 0, "w": 16, "h": 16 }, "pivot": { "x": 8, "y": 16 } }
+                    ] }
+                ]
+            }
+        }"#;
+
+        let sprite_sheet = SpriteSheet::from_aseprite_json(json, None).unwrap();
+        assert_eq!(sprite_sheet.texture_size, Vector2::new(48, 16));
+        assert_eq!(sprite_sheet.frames.len(), 3);
+        assert_eq!(sprite_sheet.frames[1].rect, Rect::new(16, 0, 16, 16));
+        assert_eq!(sprite_sheet.frames[1].duration, 0.2);
+        assert_eq!(sprite_sheet.frames[0].pivot, Vector2::new(0.5, 0.5));
+        assert_eq!(sprite_sheet.frames[2].pivot, Vector2::new(0.5, 1.0));
+
+        let attack = sprite_sheet.animation("attack").unwrap();
+        assert_eq!(attack.frames, vec![0, 1, 2, 1]);
+        assert!(attack.looping);
+
+        let hit = sprite_sheet.animation("hit").unwrap();
+        assert_eq!(hit.frames, vec![2, 1]);
+        assert!(!hit.looping);
+
+        assert_eq!(
+            SpriteSheet::aseprite_image_path(json).unwrap(),
+            Some(PathBuf::from("knight.png"))
+        );
+    }
+
+    #[test]
+    fn import_aseprite_array_trimmed() {
+        let json = r#"{
+            "frames": [
+                {
+                    "filename": "0",
+                    "frame": { "x": 2, "y": 4, "w": 8, "h": 10 },
+                    "rotated": false,
+                    "trimmed": true,
+                    "spriteSourceSize": { "x": 4, "y": 2, "w": 8, "h": 10 },
+                    "sourceSize": { "w": 16, "h": 16 },
+                    "duration": 50
+                }
+            ],
+            "meta": { "size": { "w": 16, "h": 16 } }
+        }"#;
+
+        let sprite_sheet = SpriteSheet::from_aseprite_json(json, None).unwrap();
+        assert_eq!(sprite_sheet.frames[0].rect, Rect::new(2, 4, 8, 10));
+        assert_eq!(sprite_sheet.frames[0].pivot, Vector2::new(0.5, 0.6));
+        assert!(sprite_sheet.animations.is_empty());
+    }
+
+    #[test]
+    fn import_aseprite_errors() {
+        assert!(SpriteSheet::from_aseprite_json("{}", None).is_err());
+        let json = r#"{
+            "frames": [ { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "rotated": true } ],
+            "meta": { "size": { "w": 8, "h": 8 } }
+        }"#;
+        assert!(matches!(
+            SpriteSheet::from_aseprite_json(json, None),
+            Err(SpriteSheetError::Import(_))
+        ));
+    }
+}
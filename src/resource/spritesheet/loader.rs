@@ -0,0 +1,60 @@
+//! Sprite sheet loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::spritesheet::SpriteSheet,
+};
+use std::sync::Arc;
+
+/// Default implementation for sprite sheet loading.
+pub struct SpriteSheetLoader {
+    /// Resource manager that will be used to load textures of sprite sheets.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for SpriteSheetLoader {
+    fn extensions(&self) -> &[&str] {
+        &["spritesheet"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        SpriteSheet::type_uuid()
+    }
+
+    fn load(
+        &self,
+        sprite_sheet: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        Box::pin(async move {
+            let path = sprite_sheet.path();
+            match SpriteSheet::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(sprite_sheet_state) => {
+                    Log::info(format!("Sprite sheet {:?} is loaded!", path));
+
+                    sprite_sheet.commit_ok(sprite_sheet_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(sprite_sheet, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load sprite sheet from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    sprite_sheet.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
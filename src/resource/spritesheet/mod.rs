@@ -0,0 +1,385 @@
+//! Sprite sheet resource contains frames of 2D animations packed into a single texture. See
+//! [`SpriteSheet`] docs for more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        manager::ResourceManager,
+        migration::write_version,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector2,
+        io::FileLoadError,
+        math::Rect,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::texture::{atlas::TextureAtlas, TextureResource},
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+mod aseprite;
+pub mod loader;
+
+/// An error that may occur during sprite sheet loading or importing.
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+
+    /// Source data of a sprite sheet is malformed or uses unsupported features.
+    Import(String),
+}
+
+impl Display for SpriteSheetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpriteSheetError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            SpriteSheetError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+            SpriteSheetError::Import(v) => {
+                write!(f, "Unable to import sprite sheet. Reason: {v}")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SpriteSheetError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SpriteSheetError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A single frame of a sprite sheet.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SpriteSheetFrame {
+    /// Location of the frame in the sprite sheet texture, in pixels.
+    pub rect: Rect<u32>,
+    /// Normalized position of the pivot of the frame relative to the top-left corner of the frame.
+    /// `[0.5; 0.5]` means that the pivot is at the center of the frame.
+    pub pivot: Vector2<f32>,
+    /// Duration of the frame in seconds.
+    pub duration: f32,
+}
+
+impl Default for SpriteSheetFrame {
+    fn default() -> Self {
+        Self {
+            rect: Rect::new(0, 0, 1, 1),
+            pivot: Vector2::new(0.5, 0.5),
+            duration: 0.1,
+        }
+    }
+}
+
+/// Named sequence of frames of a sprite sheet.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct SpriteAnimation {
+    /// Name of the animation.
+    pub name: String,
+    /// Indices of the frames of the sprite sheet in the order of playback. The same frame could be
+    /// used multiple times.
+    pub frames: Vec<usize>,
+    /// Whether the animation should start over when it reaches its end or not.
+    pub looping: bool,
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            frames: Default::default(),
+            looping: true,
+        }
+    }
+}
+
+/// Sprite sheet is a texture with many frames packed into it, together with a set of named
+/// animations that define the order of the frames. Every frame has its own pivot and duration.
+///
+/// Sprite sheets are usually produced by external tools - they could be imported from Aseprite
+/// JSON files (see [`SpriteSheet::from_aseprite_json`]) or from texture atlases (see
+/// [`SpriteSheet::from_atlas`]). Use [`crate::scene::animation::spritesheet::SpriteAnimationPlayer`]
+/// node to play sprite sheet animations on sprites or rectangles.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector2, math::Rect},
+///     resource::spritesheet::{SpriteAnimation, SpriteSheet, SpriteSheetFrame},
+/// };
+///
+/// let mut sprite_sheet = SpriteSheet::default();
+/// sprite_sheet.texture_size = Vector2::new(64, 32);
+/// sprite_sheet.frames = vec![
+///     SpriteSheetFrame {
+///         rect: Rect::new(0, 0, 32, 32),
+///         ..Default::default()
+///     },
+///     SpriteSheetFrame {
+///         rect: Rect::new(32, 0, 32, 32),
+///         ..Default::default()
+///     },
+/// ];
+/// sprite_sheet.animations = vec![SpriteAnimation {
+///     name: "Walk".to_string(),
+///     frames: vec![0, 1],
+///     looping: true,
+/// }];
+///
+/// assert_eq!(
+///     sprite_sheet.frame_uv_rect(1),
+///     Some(Rect::new(0.5, 0.0, 0.5, 1.0))
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct SpriteSheet {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// Texture that contains every frame of the sprite sheet.
+    pub texture: Option<TextureResource>,
+    /// Size of the texture in pixels. It is used to calculate texture coordinates of the frames
+    /// without need to wait until the texture is loaded.
+    pub texture_size: Vector2<u32>,
+    /// Every frame of the sprite sheet.
+    pub frames: Vec<SpriteSheetFrame>,
+    /// Every animation of the sprite sheet.
+    pub animations: Vec<SpriteAnimation>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) dirty: bool,
+}
+
+impl TypeUuidProvider for SpriteSheet {
+    fn type_uuid() -> Uuid {
+        uuid!("6d9c4f5a-1f0a-4e49-8e0b-3c5d2a7b9e61")
+    }
+}
+
+impl ResourceData for SpriteSheet {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        write_version(&mut visitor, Self::VERSION)?;
+        self.visit("SpriteSheet", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl SpriteSheet {
+    /// Current version of the format of sprite sheet files. It must be bumped every time when the format
+    /// is changed, and a migration from the previous version must be registered in
+    /// [`crate::asset::migration::ResourceMigrationRegistry`].
+    pub const VERSION: u32 = 0;
+
+    /// Loads a sprite sheet from the specific file path.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, SpriteSheetError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        resource_manager.state().migrations.migrate(
+            <Self as TypeUuidProvider>::type_uuid(),
+            Self::VERSION,
+            &mut visitor,
+        )?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut sprite_sheet = SpriteSheet::default();
+        sprite_sheet.visit("SpriteSheet", &mut visitor)?;
+        sprite_sheet.path = path.to_path_buf();
+        Ok(sprite_sheet)
+    }
+
+    /// Creates a sprite sheet from the given texture atlas (see
+    /// [`crate::resource::texture::atlas::TextureAtlasBuilder`]). Every region of the atlas becomes a
+    /// frame with the given duration. Regions with names like `walk/0.png`, `walk/1.png` are grouped
+    /// into animations by their parent directory (`walk` in this case), the frames of an animation
+    /// are sorted by the number at the end of their names.
+    pub fn from_atlas(
+        atlas: &TextureAtlas,
+        texture: Option<TextureResource>,
+        frame_duration: f32,
+    ) -> Self {
+        let mut regions = atlas.regions.iter().collect::<Vec<_>>();
+        regions.sort_by_cached_key(|r| natural_sort_key(&r.name));
+
+        let mut animations: Vec<SpriteAnimation> = Vec::new();
+        let mut frames = Vec::with_capacity(regions.len());
+        for (index, region) in regions.into_iter().enumerate() {
+            frames.push(SpriteSheetFrame {
+                rect: region.pixel_rect,
+                duration: frame_duration,
+                ..Default::default()
+            });
+
+            let Some(name) = Path::new(&region.name)
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .filter(|p| !p.is_empty())
+            else {
+                continue;
+            };
+            match animations.iter_mut().find(|a| a.name == name) {
+                Some(animation) => animation.frames.push(index),
+                None => animations.push(SpriteAnimation {
+                    name,
+                    frames: vec![index],
+                    looping: true,
+                }),
+            }
+        }
+
+        Self {
+            path: Default::default(),
+            texture,
+            texture_size: atlas.size,
+            frames,
+            animations,
+            dirty: false,
+        }
+    }
+
+    /// Tries to find an animation with the given name.
+    pub fn animation(&self, name: &str) -> Option<&SpriteAnimation> {
+        self.animations.iter().find(|a| a.name == name)
+    }
+
+    /// Returns location of the frame with the given index in normalized texture coordinates. The
+    /// rectangle could be passed directly to [`crate::scene::sprite::Sprite::set_uv_rect`] or
+    /// [`crate::scene::dim2::rectangle::Rectangle::set_uv_rect`].
+    pub fn frame_uv_rect(&self, index: usize) -> Option<Rect<f32>> {
+        if self.texture_size.x == 0 || self.texture_size.y == 0 {
+            return None;
+        }
+
+        self.frames.get(index).map(|frame| {
+            let width = self.texture_size.x as f32;
+            let height = self.texture_size.y as f32;
+            Rect::new(
+                frame.rect.x() as f32 / width,
+                frame.rect.y() as f32 / height,
+                frame.rect.w() as f32 / width,
+                frame.rect.h() as f32 / height,
+            )
+        })
+    }
+}
+
+// Splits the trailing number of a file stem, so `walk/10.png` goes after `walk/2.png`.
+fn natural_sort_key(name: &str) -> (String, u64, String) {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let prefix = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = stem[prefix.len()..].parse().unwrap_or_default();
+    let parent = path
+        .parent()
+        .map(|p| p.join(prefix).to_string_lossy().to_string())
+        .unwrap_or_default();
+    (parent, number, name.to_string())
+}
+
+/// Type alias for sprite sheet resources.
+pub type SpriteSheetResource = Resource<SpriteSheet>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resource::texture::atlas::TextureAtlasRegion;
+
+    #[test]
+    fn sprite_sheet_from_atlas() {
+        let region = |name: &str, x| TextureAtlasRegion {
+            name: name.to_string(),
+            pixel_rect: Rect::new(x, 0, 16, 16),
+            uv_rect: Default::default(),
+        };
+        let atlas = TextureAtlas {
+            size: Vector2::new(64, 16),
+            regions: vec![
+                region("walk/10.png", 0),
+                region("walk/2.png", 16),
+                region("idle.png", 32),
+                region("jump/0.png", 48),
+            ],
+        };
+
+        let sprite_sheet = SpriteSheet::from_atlas(&atlas, None, 0.25);
+        assert_eq!(sprite_sheet.frames.len(), 4);
+        assert_eq!(sprite_sheet.animations.len(), 2);
+
+        let walk = sprite_sheet.animation("walk").unwrap();
+        assert_eq!(walk.frames.len(), 2);
+        assert_eq!(sprite_sheet.frames[walk.frames[0]].rect.x(), 16);
+        assert_eq!(sprite_sheet.frames[walk.frames[1]].rect.x(), 0);
+        assert_eq!(sprite_sheet.frames[walk.frames[0]].duration, 0.25);
+
+        let jump = sprite_sheet.animation("jump").unwrap();
+        assert_eq!(
+            sprite_sheet.frame_uv_rect(jump.frames[0]),
+            Some(Rect::new(0.75, 0.0, 0.25, 1.0))
+        );
+    }
+}
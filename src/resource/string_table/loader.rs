@@ -0,0 +1,55 @@
+//! String table loader.
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::string_table::StringTable,
+};
+use std::sync::Arc;
+
+/// Default implementation for string table loading.
+pub struct StringTableLoader;
+
+impl ResourceLoader for StringTableLoader {
+    fn extensions(&self) -> &[&str] {
+        &["strings"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        StringTable::type_uuid()
+    }
+
+    fn load(
+        &self,
+        table: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = table.path();
+            match StringTable::from_file(&path, io.as_ref()).await {
+                Ok(table_state) => {
+                    Log::info(format!("String table {:?} is loaded!", path));
+
+                    table.commit_ok(table_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(table, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load string table from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    table.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,231 @@
+//! String table resource contains localized texts of a single locale. See [`StringTable`] docs for
+//! more info.
+
+use crate::{
+    asset::{
+        io::{write_atomically, ResourceIo},
+        Resource, ResourceData,
+    },
+    core::{
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    fxhash::FxHashMap,
+    localization::PluralCategory,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+pub mod loader;
+
+/// An error that may occur during string table loading.
+#[derive(Debug)]
+pub enum StringTableError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// A parsing error has occurred.
+    ParseError(ron::error::SpannedError),
+}
+
+impl Display for StringTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringTableError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            StringTableError::ParseError(v) => {
+                write!(f, "A parsing error has occurred {v:?}")
+            }
+        }
+    }
+}
+
+impl From<ron::error::SpannedError> for StringTableError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::ParseError(e)
+    }
+}
+
+impl From<FileLoadError> for StringTableError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// String table is a set of localized texts and asset paths of a single locale. String tables are
+/// stored in human-readable RON format, so they could be edited in any text editor or generated by
+/// translation tools. Every section of the file is optional.
+///
+/// String tables are used by [`crate::localization::Localization`], see its docs for more info.
+///
+/// ## Example
+///
+/// ```ron
+/// (
+///     texts: {
+///         "menu.start": "Spiel starten",
+///     },
+///     plurals: {
+///         "inventory.apples": {
+///             One: "{} Apfel",
+///             Other: "{} Äpfel",
+///         },
+///     },
+///     paths: {
+///         "data/voice/greeting.ogg": "data/voice/de/greeting.ogg",
+///     },
+/// )
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+pub struct StringTable {
+    #[serde(skip)]
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// A map of `Key -> Text`.
+    #[serde(default)]
+    pub texts: FxHashMap<String, String>,
+    /// A map of `Key -> Plural Forms`. Every text could contain `{}`, which will be replaced with
+    /// the actual count.
+    #[serde(default)]
+    pub plurals: FxHashMap<String, FxHashMap<PluralCategory, String>>,
+    /// A map of `Original Path -> Localized Path`. It is used to replace assets (voice lines,
+    /// textures with text, etc.) with their localized versions.
+    #[serde(default)]
+    pub paths: FxHashMap<PathBuf, PathBuf>,
+    #[serde(skip)]
+    #[visit(skip)]
+    #[reflect(hidden)]
+    dirty: bool,
+}
+
+impl TypeUuidProvider for StringTable {
+    fn type_uuid() -> Uuid {
+        uuid!("e3a1c7d9-5b24-4f68-8d0e-9c7b2a4f6e15")
+    }
+}
+
+impl ResourceData for StringTable {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_embedded(&self) -> bool {
+        false
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let text = ron::ser::to_string_pretty(self, Default::default())?;
+        write_atomically(path, |path| std::fs::write(path, text))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl StringTable {
+    /// Loads a string table from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, StringTableError> {
+        let bytes = io.load_file(path).await?;
+        let mut table = Self::from_bytes(&bytes)?;
+        table.path = path.to_path_buf();
+        Ok(table)
+    }
+
+    /// Creates a string table from the given content of a RON file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StringTableError> {
+        Ok(ron::de::from_bytes(bytes)?)
+    }
+
+    /// Returns a text with the given key.
+    pub fn text(&self, key: &str) -> Option<&str> {
+        self.texts.get(key).map(|text| text.as_str())
+    }
+
+    /// Returns a plural form of a text with the given key. If there's no such form, falls back to
+    /// [`PluralCategory::Other`].
+    pub fn plural(&self, key: &str, category: PluralCategory) -> Option<&str> {
+        let forms = self.plurals.get(key)?;
+        forms
+            .get(&category)
+            .or_else(|| forms.get(&PluralCategory::Other))
+            .map(|text| text.as_str())
+    }
+
+    /// Returns a localized version of the given asset path.
+    pub fn localized_path(&self, path: &Path) -> Option<&Path> {
+        self.paths.get(path).map(|path| path.as_path())
+    }
+}
+
+/// Type alias for string table resources.
+pub type StringTableResource = Resource<StringTable>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn string_table_from_bytes() {
+        let table = StringTable::from_bytes(
+            r#"(
+                texts: {
+                    "menu.start": "Spiel starten",
+                },
+                plurals: {
+                    "inventory.apples": {
+                        One: "{} Apfel",
+                        Other: "{} Äpfel",
+                    },
+                },
+                paths: {
+                    "data/voice/greeting.ogg": "data/voice/de/greeting.ogg",
+                },
+            )"#
+            .as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(table.text("menu.start"), Some("Spiel starten"));
+        assert_eq!(
+            table.plural("inventory.apples", PluralCategory::Few),
+            Some("{} Äpfel")
+        );
+        assert_eq!(
+            table.localized_path(Path::new("data/voice/greeting.ogg")),
+            Some(Path::new("data/voice/de/greeting.ogg"))
+        );
+        assert!(StringTable::from_bytes(b"()").is_ok());
+    }
+}
@@ -0,0 +1,407 @@
+//! Texture atlas is a single texture, that contains many smaller images. See [`TextureAtlasBuilder`]
+//! docs for more info.
+
+use crate::{
+    asset::io::write_atomically,
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        rectpack::RectPacker,
+        reflect::prelude::*,
+        visitor::{prelude::*, VisitError},
+    },
+    resource::texture::{Texture, TextureKind, TexturePixelKind},
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Default maximum size (in pixels) of a side of atlas texture.
+pub const DEFAULT_MAX_ATLAS_SIZE: u32 = 4096;
+
+/// A region of a texture atlas, that contains a single packed image.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TextureAtlasRegion {
+    /// Name of the packed image. It is used to find the region in the atlas.
+    pub name: String,
+    /// Location of the image in the atlas texture, in pixels.
+    pub pixel_rect: Rect<u32>,
+    /// Location of the image in the atlas texture, in normalized texture coordinates. It could be
+    /// passed directly to [`crate::scene::sprite::Sprite::set_uv_rect`],
+    /// [`crate::scene::dim2::rectangle::Rectangle::set_uv_rect`] or
+    /// [`crate::gui::image::ImageMessage::uv_rect`].
+    pub uv_rect: Rect<f32>,
+}
+
+/// Texture atlas contains UV remapping data for a texture produced by [`TextureAtlasBuilder`].
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TextureAtlas {
+    /// Size of the atlas texture in pixels.
+    pub size: Vector2<u32>,
+    /// A list of every packed image of the atlas.
+    pub regions: Vec<TextureAtlasRegion>,
+}
+
+impl TextureAtlas {
+    /// Tries to find a region of the image with the given name.
+    pub fn region(&self, name: &str) -> Option<&TextureAtlasRegion> {
+        self.regions.iter().find(|r| r.name == name)
+    }
+
+    /// Tries to find normalized texture coordinates of the image with the given name.
+    pub fn uv_rect(&self, name: &str) -> Option<Rect<f32>> {
+        self.region(name).map(|r| r.uv_rect)
+    }
+
+    /// Saves the atlas data at the given path.
+    pub fn save(&mut self, path: &Path) -> Result<(), VisitError> {
+        let mut visitor = Visitor::new();
+        self.visit("TextureAtlas", &mut visitor)?;
+        write_atomically(path, |path| visitor.save_binary(path))
+    }
+
+    /// Loads the atlas data from the given path.
+    pub async fn load(path: &Path) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut atlas = Self::default();
+        atlas.visit("TextureAtlas", &mut visitor)?;
+        Ok(atlas)
+    }
+}
+
+/// An error that may occur during atlas building.
+#[derive(Debug)]
+pub enum TextureAtlasError {
+    /// There's nothing to pack.
+    Empty,
+    /// An image with the same name was already added.
+    DuplicateName(String),
+    /// Pixel format or kind of the texture is not supported. Only rectangular textures with 8-bit
+    /// uncompressed pixel formats could be packed.
+    UnsupportedFormat(String),
+    /// The images do not fit into an atlas of the maximum size.
+    TooLarge {
+        /// Maximum size of a side of the atlas.
+        max_size: u32,
+    },
+}
+
+impl Display for TextureAtlasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextureAtlasError::Empty => write!(f, "There's no images to pack!"),
+            TextureAtlasError::DuplicateName(name) => {
+                write!(f, "An image with {name} name is already added!")
+            }
+            TextureAtlasError::UnsupportedFormat(name) => {
+                write!(f, "Image {name} has unsupported format!")
+            }
+            TextureAtlasError::TooLarge { max_size } => {
+                write!(f, "Images do not fit into {max_size}x{max_size} atlas!")
+            }
+        }
+    }
+}
+
+struct AtlasImage {
+    name: String,
+    width: u32,
+    height: u32,
+    // RGBA8 pixels.
+    pixels: Vec<u8>,
+}
+
+/// Texture atlas builder packs many small images into a single texture atlas. Atlases allow you to
+/// reduce amount of draw calls, because every image of an atlas could be drawn using the same
+/// texture. It is especially useful for 2D games, which usually use lots of small sprites.
+///
+/// The builder produces a texture with RGBA8 pixel format and [`TextureAtlas`] with UV remapping
+/// data, that could be used to find location of every packed image in the atlas texture.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::resource::texture::atlas::TextureAtlasBuilder;
+///
+/// let mut builder = TextureAtlasBuilder::new().with_padding(1);
+/// builder.add_rgba8("red", 2, 2, [255, 0, 0, 255].repeat(4)).unwrap();
+/// builder.add_rgba8("green", 4, 3, [0, 255, 0, 255].repeat(12)).unwrap();
+/// let (texture, atlas) = builder.build().unwrap();
+///
+/// let green_uv_rect = atlas.uv_rect("green").unwrap();
+/// ```
+pub struct TextureAtlasBuilder {
+    images: Vec<AtlasImage>,
+    padding: u32,
+    max_size: u32,
+}
+
+impl Default for TextureAtlasBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextureAtlasBuilder {
+    /// Creates new empty atlas builder.
+    pub fn new() -> Self {
+        Self {
+            images: Default::default(),
+            padding: 0,
+            max_size: DEFAULT_MAX_ATLAS_SIZE,
+        }
+    }
+
+    /// Sets amount of empty pixels between packed images. Padding prevents color bleeding from
+    /// neighbour images when texture filtering is used.
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets maximum size (in pixels) of a side of the atlas texture.
+    pub fn with_max_size(mut self, max_size: u32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Adds an image with RGBA8 pixels.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the size of the pixels buffer does not match the given size.
+    pub fn add_rgba8(
+        &mut self,
+        name: impl Into<String>,
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    ) -> Result<(), TextureAtlasError> {
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
+
+        let name = name.into();
+        if self.images.iter().any(|i| i.name == name) {
+            return Err(TextureAtlasError::DuplicateName(name));
+        }
+
+        self.images.push(AtlasImage {
+            name,
+            width,
+            height,
+            pixels,
+        });
+
+        Ok(())
+    }
+
+    /// Adds a texture. Only rectangular textures with 8-bit uncompressed pixel formats are supported,
+    /// every pixel will be converted to RGBA8 format.
+    pub fn add_texture(
+        &mut self,
+        name: impl Into<String>,
+        texture: &Texture,
+    ) -> Result<(), TextureAtlasError> {
+        let name = name.into();
+
+        let TextureKind::Rectangle { width, height } = texture.kind() else {
+            return Err(TextureAtlasError::UnsupportedFormat(name));
+        };
+
+        // Only the first mip level is used.
+        let data = texture.mip_level_data(0);
+        let pixels = match texture.pixel_kind() {
+            TexturePixelKind::RGBA8 => data.to_vec(),
+            TexturePixelKind::BGRA8 => data
+                .chunks_exact(4)
+                .flat_map(|p| [p[2], p[1], p[0], p[3]])
+                .collect(),
+            TexturePixelKind::RGB8 => data
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            TexturePixelKind::BGR8 => data
+                .chunks_exact(3)
+                .flat_map(|p| [p[2], p[1], p[0], 255])
+                .collect(),
+            TexturePixelKind::R8 | TexturePixelKind::Luminance8 => {
+                data.iter().flat_map(|&l| [l, l, l, 255]).collect()
+            }
+            TexturePixelKind::RG8 | TexturePixelKind::LuminanceAlpha8 => data
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            _ => return Err(TextureAtlasError::UnsupportedFormat(name)),
+        };
+
+        self.add_rgba8(name, width, height, pixels)
+    }
+
+    fn try_pack(&self, order: &[usize], width: u32, height: u32) -> Option<Vec<Rect<u32>>> {
+        let mut packer = RectPacker::new(width, height);
+        let mut rects = vec![Rect::default(); self.images.len()];
+        for &index in order {
+            let image = &self.images[index];
+            let rect = packer.find_free(image.width + self.padding, image.height + self.padding)?;
+            rects[index] = Rect::new(rect.x(), rect.y(), image.width, image.height);
+        }
+        Some(rects)
+    }
+
+    /// Packs every added image into a single texture. Returns the texture (in embedded state) and
+    /// the respective UV remapping data.
+    pub fn build(self) -> Result<(Texture, TextureAtlas), TextureAtlasError> {
+        if self.images.is_empty() {
+            return Err(TextureAtlasError::Empty);
+        }
+
+        // Packing larger images first gives much better results.
+        let mut order = (0..self.images.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.images[i].width.max(self.images[i].height)));
+
+        let total_area = self
+            .images
+            .iter()
+            .map(|i| ((i.width + self.padding) * (i.height + self.padding)) as f32)
+            .sum::<f32>();
+        let mut width = (total_area.sqrt().ceil() as u32).next_power_of_two();
+        let mut height = width;
+
+        let rects = loop {
+            if width > self.max_size || height > self.max_size {
+                return Err(TextureAtlasError::TooLarge {
+                    max_size: self.max_size,
+                });
+            }
+
+            if let Some(rects) = self.try_pack(&order, width, height) {
+                break rects;
+            }
+
+            if width <= height {
+                width *= 2;
+            } else {
+                height *= 2;
+            }
+        };
+
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let mut regions = Vec::with_capacity(self.images.len());
+        for (image, rect) in self.images.into_iter().zip(rects) {
+            let row_size = (image.width * 4) as usize;
+            for y in 0..image.height {
+                let src = (y * image.width * 4) as usize;
+                let dest = (((rect.y() + y) * width + rect.x()) * 4) as usize;
+                pixels[dest..dest + row_size].copy_from_slice(&image.pixels[src..src + row_size]);
+            }
+
+            regions.push(TextureAtlasRegion {
+                name: image.name,
+                pixel_rect: rect,
+                uv_rect: Rect::new(
+                    rect.x() as f32 / width as f32,
+                    rect.y() as f32 / height as f32,
+                    rect.w() as f32 / width as f32,
+                    rect.h() as f32 / height as f32,
+                ),
+            });
+        }
+
+        let texture = Texture::from_bytes(
+            TextureKind::Rectangle { width, height },
+            TexturePixelKind::RGBA8,
+            pixels,
+            true,
+        )
+        .expect("Atlas texture size must match its pixels buffer!");
+
+        Ok((
+            texture,
+            TextureAtlas {
+                size: Vector2::new(width, height),
+                regions,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn atlas_builder_packs_images() {
+        let mut builder = TextureAtlasBuilder::new().with_padding(1);
+        builder
+            .add_rgba8("red", 2, 2, [255, 0, 0, 255].repeat(4))
+            .unwrap();
+        builder
+            .add_rgba8("green", 4, 3, [0, 255, 0, 255].repeat(12))
+            .unwrap();
+        assert!(matches!(
+            builder.add_rgba8("red", 1, 1, vec![0; 4]),
+            Err(TextureAtlasError::DuplicateName(_))
+        ));
+
+        let (texture, atlas) = builder.build().unwrap();
+        assert!(matches!(
+            texture.kind(),
+            TextureKind::Rectangle {
+                width: 8,
+                height: 8
+            }
+        ));
+        assert_eq!(atlas.size, Vector2::new(8, 8));
+
+        let red = atlas.region("red").unwrap();
+        let green = atlas.region("green").unwrap();
+        assert!(!red.pixel_rect.intersects(green.pixel_rect));
+        assert_eq!(
+            atlas.uv_rect("green"),
+            Some(Rect::new(
+                green.pixel_rect.x() as f32 / 8.0,
+                green.pixel_rect.y() as f32 / 8.0,
+                0.5,
+                3.0 / 8.0
+            ))
+        );
+
+        let data = texture.data();
+        let offset = ((red.pixel_rect.y() * 8 + red.pixel_rect.x()) * 4) as usize;
+        assert_eq!(&data[offset..offset + 4], &[255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn atlas_builder_errors() {
+        assert!(matches!(
+            TextureAtlasBuilder::new().build(),
+            Err(TextureAtlasError::Empty)
+        ));
+
+        let mut builder = TextureAtlasBuilder::new().with_max_size(4);
+        builder.add_rgba8("big", 8, 8, vec![0; 256]).unwrap();
+        assert!(matches!(
+            builder.build(),
+            Err(TextureAtlasError::TooLarge { max_size: 4 })
+        ));
+    }
+
+    #[test]
+    fn atlas_builder_converts_textures() {
+        let texture = Texture::from_bytes(
+            TextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            TexturePixelKind::BGR8,
+            vec![1, 2, 3],
+            true,
+        )
+        .unwrap();
+
+        let mut builder = TextureAtlasBuilder::new();
+        builder.add_texture("pixel", &texture).unwrap();
+        let (atlas_texture, _) = builder.build().unwrap();
+        assert_eq!(&atlas_texture.data()[0..4], &[3, 2, 1, 255]);
+    }
+}
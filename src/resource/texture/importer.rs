@@ -0,0 +1,194 @@
+//! Texture importer, see [`TextureImporter`] docs for more info.
+
+use crate::{
+    asset::{
+        import::{ImportContext, ResourceImporter},
+        options::default_import_options,
+    },
+    core::uuid::{uuid, Uuid},
+    resource::texture::{
+        bytes_in_mip_level, decode_image, Texture, TextureImportOptions, TextureKind,
+        TexturePixelKind,
+    },
+};
+use std::path::Path;
+
+/// Magic number at the beginning of derived data of [`TextureImporter`].
+pub const DERIVED_DATA_MAGIC: [u8; 4] = *b"FTXD";
+
+/// Decodes images in commonly used formats (PNG, JPG, TGA, etc.), generates their mip levels and
+/// compresses them once, then stores the result in the derived data cache, so the texture loader
+/// only needs to copy the data on every load. DDS textures are not imported, because they're already stored in a GPU-friendly
+/// format. See [`crate::asset::import`] module docs for more info.
+pub struct TextureImporter {
+    /// Import options, that are used for textures without import options file and registered
+    /// default import options.
+    pub default_import_options: TextureImportOptions,
+}
+
+impl TextureImporter {
+    fn import_options(&self, path: &Path) -> TextureImportOptions {
+        default_import_options::<TextureImportOptions>(path)
+            .unwrap_or_else(|| self.default_import_options.clone())
+    }
+}
+
+impl ResourceImporter for TextureImporter {
+    fn extensions(&self) -> &[&str] {
+        &["jpg", "jpeg", "tga", "gif", "bmp", "png", "tiff", "tif"]
+    }
+
+    fn importer_uuid(&self) -> Uuid {
+        uuid!("5b0d4e2c-8f3a-4c61-9e7b-2a6d1f8c3b94")
+    }
+
+    fn version(&self) -> u32 {
+        // Version 0 stored decoded pixels without mip levels.
+        1
+    }
+
+    fn default_options(&self, path: &Path) -> Option<Vec<u8>> {
+        ron::to_string(&self.import_options(path))
+            .ok()
+            .map(String::into_bytes)
+    }
+
+    fn import(&self, context: ImportContext) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let import_options = context
+            .options
+            .and_then(|options| ron::de::from_bytes::<TextureImportOptions>(options).ok())
+            .unwrap_or_else(|| self.import_options(context.path));
+        let image = decode_image(context.source, &import_options)?;
+        let texture = Texture::from_decoded_image(image, import_options)?;
+        Ok(write_derived_data(&texture))
+    }
+}
+
+/// A rectangle texture with its mip chain, stored in derived data of [`TextureImporter`].
+pub(crate) struct DerivedTexture {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) pixel_kind: TexturePixelKind,
+    pub(crate) mip_count: u32,
+    pub(crate) bytes: Vec<u8>,
+}
+
+fn write_derived_data(texture: &Texture) -> Vec<u8> {
+    let (width, height) = match texture.kind() {
+        TextureKind::Rectangle { width, height } => (width, height),
+        _ => unreachable!("Imported images are always rectangle textures!"),
+    };
+    let bytes = texture.data();
+    let mut data = Vec::with_capacity(DERIVED_DATA_MAGIC.len() + 16 + bytes.len());
+    data.extend_from_slice(&DERIVED_DATA_MAGIC);
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&height.to_le_bytes());
+    data.extend_from_slice(&texture.pixel_kind().id().to_le_bytes());
+    data.extend_from_slice(&texture.mip_count().to_le_bytes());
+    data.extend_from_slice(bytes);
+    data
+}
+
+/// Tries to read derived data of [`TextureImporter`]. `None` means that the data has some other
+/// format.
+pub(crate) fn read_derived_data(data: &[u8]) -> Option<DerivedTexture> {
+    let data = data.strip_prefix(&DERIVED_DATA_MAGIC)?;
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(
+            data.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+    let width = read_u32(0)?;
+    let height = read_u32(4)?;
+    let pixel_kind = TexturePixelKind::new(read_u32(8)?).ok()?;
+    let mip_count = read_u32(12)?;
+    let bytes = data.get(16..)?;
+    let kind = TextureKind::Rectangle { width, height };
+    let expected_size = (0..mip_count as usize)
+        .map(|mip| bytes_in_mip_level(kind, pixel_kind, mip) as usize)
+        .sum::<usize>();
+    if mip_count == 0 || bytes.len() != expected_size {
+        return None;
+    }
+    Some(DerivedTexture {
+        width,
+        height,
+        pixel_kind,
+        mip_count,
+        bytes: bytes.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resource::texture::{CompressionOptions, MipFilter, TextureMinificationFilter};
+    use image::{DynamicImage, ImageOutputFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn png() -> Vec<u8> {
+        let image = RgbaImage::from_fn(4, 4, |x, y| Rgba([x as u8 * 60, y as u8 * 60, 10, 255]));
+        let mut data = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn texture_importer_derived_data() {
+        let importer = TextureImporter {
+            default_import_options: Default::default(),
+        };
+        let source = png();
+        let import = |options: &TextureImportOptions| {
+            importer
+                .import(ImportContext {
+                    path: Path::new("test.png"),
+                    source: &source,
+                    options: Some(ron::to_string(options).unwrap().as_bytes()),
+                })
+                .unwrap()
+        };
+
+        // The loader gets the same texture from the derived data as from the source, the mip
+        // chain is taken from the derived data.
+        for import_options in [
+            TextureImportOptions::default()
+                .with_minification_filter(TextureMinificationFilter::LinearMipMapLinear),
+            TextureImportOptions::default()
+                .with_minification_filter(TextureMinificationFilter::LinearMipMapLinear)
+                .with_mip_filter(MipFilter::Kaiser)
+                .with_srgb_mip_filtering(true),
+            TextureImportOptions::default()
+                .with_minification_filter(TextureMinificationFilter::LinearMipMapLinear)
+                .with_compression(CompressionOptions::Quality),
+            TextureImportOptions::default()
+                .with_minification_filter(TextureMinificationFilter::Linear),
+        ] {
+            let derived = import(&import_options);
+            assert!(derived.starts_with(&DERIVED_DATA_MAGIC));
+            let expected = Texture::load_from_memory(&source, import_options.clone()).unwrap();
+            let derived_texture = read_derived_data(&derived).unwrap();
+            assert_eq!(derived_texture.mip_count, expected.mip_count());
+            assert_eq!(derived_texture.bytes, expected.data());
+
+            let texture = Texture::load_from_memory(&derived, import_options).unwrap();
+            assert_eq!(texture.mip_count(), expected.mip_count());
+            assert_eq!(texture.pixel_kind(), expected.pixel_kind());
+            assert_eq!(texture.data(), expected.data());
+        }
+
+        // Options of the source are applied by the importer.
+        let derived = import(&TextureImportOptions {
+            flip_green_channel: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            read_derived_data(&derived).unwrap().bytes[..4],
+            [0, 255, 10, 255]
+        );
+
+        assert!(read_derived_data(&source).is_none());
+    }
+}
@@ -0,0 +1,535 @@
+//! Mip-map chain generation with filtering options that are not supported by general-purpose image
+//! resizers: Kaiser filter, sRGB-correct filtering, normal map renormalization and alpha-coverage
+//! preservation. Mip levels are computed once on texture import, so there's no runtime cost.
+
+use crate::resource::texture::{MipFilter, TexturePixelKind};
+use std::f32::consts::PI;
+
+/// A set of options for mip-map chain generation.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct MipGenerationOptions {
+    pub filter: MipFilter,
+    pub srgb: bool,
+    pub normal_map: bool,
+    pub alpha_coverage_reference: Option<f32>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ChannelType {
+    U8,
+    U16,
+    F32,
+}
+
+impl ChannelType {
+    fn size(self) -> usize {
+        match self {
+            ChannelType::U8 => 1,
+            ChannelType::U16 => 2,
+            ChannelType::F32 => 4,
+        }
+    }
+
+    fn read(self, bytes: &[u8]) -> f32 {
+        match self {
+            ChannelType::U8 => bytes[0] as f32 / 255.0,
+            ChannelType::U16 => u16::from_ne_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+            ChannelType::F32 => f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    fn write(self, value: f32, dest: &mut Vec<u8>) {
+        match self {
+            ChannelType::U8 => dest.push((value.clamp(0.0, 1.0) * 255.0).round() as u8),
+            ChannelType::U16 => dest.extend_from_slice(
+                &((value.clamp(0.0, 1.0) * 65535.0).round() as u16).to_ne_bytes(),
+            ),
+            ChannelType::F32 => dest.extend_from_slice(&value.to_ne_bytes()),
+        }
+    }
+}
+
+fn pixel_layout(pixel_kind: TexturePixelKind) -> Option<(ChannelType, usize)> {
+    match pixel_kind {
+        TexturePixelKind::Luminance8 => Some((ChannelType::U8, 1)),
+        TexturePixelKind::LuminanceAlpha8 => Some((ChannelType::U8, 2)),
+        TexturePixelKind::RGB8 => Some((ChannelType::U8, 3)),
+        TexturePixelKind::RGBA8 => Some((ChannelType::U8, 4)),
+        TexturePixelKind::Luminance16 => Some((ChannelType::U16, 1)),
+        TexturePixelKind::LuminanceAlpha16 => Some((ChannelType::U16, 2)),
+        TexturePixelKind::RGB16 => Some((ChannelType::U16, 3)),
+        TexturePixelKind::RGBA16 => Some((ChannelType::U16, 4)),
+        TexturePixelKind::RGB32F => Some((ChannelType::F32, 3)),
+        TexturePixelKind::RGBA32F => Some((ChannelType::F32, 4)),
+        _ => None,
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1.0e-6 {
+        1.0
+    } else {
+        let x = x * PI;
+        x.sin() / x
+    }
+}
+
+// Zeroth order modified Bessel function of the first kind.
+fn bessel_i0(x: f32) -> f32 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x_sqr = x * x * 0.25;
+    for k in 1..32 {
+        term *= half_x_sqr / (k * k) as f32;
+        sum += term;
+        if term < sum * 1.0e-8 {
+            break;
+        }
+    }
+    sum
+}
+
+const KAISER_ALPHA: f32 = 4.0;
+
+impl MipFilter {
+    // Filter support (radius) in destination pixels.
+    fn support(self) -> f32 {
+        match self {
+            MipFilter::Nearest => 0.5,
+            MipFilter::Bilinear | MipFilter::Hamming => 1.0,
+            MipFilter::CatmullRom => 2.0,
+            MipFilter::Lanczos | MipFilter::Kaiser => 3.0,
+        }
+    }
+
+    fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            MipFilter::Nearest => {
+                if x <= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            MipFilter::Bilinear => (1.0 - x).max(0.0),
+            MipFilter::Hamming => {
+                if x < 1.0 {
+                    sinc(x) * (0.54 + 0.46 * (PI * x).cos())
+                } else {
+                    0.0
+                }
+            }
+            MipFilter::CatmullRom => {
+                if x < 1.0 {
+                    1.5 * x * x * x - 2.5 * x * x + 1.0
+                } else if x < 2.0 {
+                    -0.5 * x * x * x + 2.5 * x * x - 4.0 * x + 2.0
+                } else {
+                    0.0
+                }
+            }
+            MipFilter::Lanczos => {
+                if x < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+            MipFilter::Kaiser => {
+                let t = x / self.support();
+                if t < 1.0 {
+                    sinc(x) * bessel_i0(PI * KAISER_ALPHA * (1.0 - t * t).sqrt())
+                        / bessel_i0(PI * KAISER_ALPHA)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+// A set of `(source index, weight)` pairs for every destination pixel along an axis.
+fn compute_weights(filter: MipFilter, src_size: usize, dst_size: usize) -> Vec<Vec<(usize, f32)>> {
+    let scale = src_size as f32 / dst_size as f32;
+    let support = filter.support() * scale;
+    (0..dst_size)
+        .map(|dst| {
+            let center = (dst as f32 + 0.5) * scale;
+            let first = (center - support).floor() as isize;
+            let last = (center + support).ceil() as isize;
+            let mut weights = (first..=last)
+                .filter_map(|src| {
+                    let weight = filter.weight((src as f32 + 0.5 - center) / scale);
+                    (weight != 0.0).then(|| (src.clamp(0, src_size as isize - 1) as usize, weight))
+                })
+                .collect::<Vec<_>>();
+            let sum = weights.iter().map(|(_, weight)| *weight).sum::<f32>();
+            if sum.abs() > f32::EPSILON {
+                for (_, weight) in weights.iter_mut() {
+                    *weight /= sum;
+                }
+            } else {
+                weights = vec![((center as usize).min(src_size - 1), 1.0)];
+            }
+            weights
+        })
+        .collect()
+}
+
+struct Level {
+    width: usize,
+    height: usize,
+    channels: usize,
+    data: Vec<f32>,
+}
+
+impl Level {
+    fn downsample(&self, filter: MipFilter, width: usize, height: usize) -> Level {
+        let channels = self.channels;
+
+        // Horizontal pass.
+        let weights = compute_weights(filter, self.width, width);
+        let mut horizontal = vec![0.0; width * self.height * channels];
+        for y in 0..self.height {
+            for (x, pixel_weights) in weights.iter().enumerate() {
+                let dest = (y * width + x) * channels;
+                for &(src_x, weight) in pixel_weights {
+                    let src = (y * self.width + src_x) * channels;
+                    for c in 0..channels {
+                        horizontal[dest + c] += self.data[src + c] * weight;
+                    }
+                }
+            }
+        }
+
+        // Vertical pass.
+        let weights = compute_weights(filter, self.height, height);
+        let mut data = vec![0.0; width * height * channels];
+        for (y, pixel_weights) in weights.iter().enumerate() {
+            for &(src_y, weight) in pixel_weights {
+                for x in 0..width {
+                    let dest = (y * width + x) * channels;
+                    let src = (src_y * width + x) * channels;
+                    for c in 0..channels {
+                        data[dest + c] += horizontal[src + c] * weight;
+                    }
+                }
+            }
+        }
+
+        Level {
+            width,
+            height,
+            channels,
+            data,
+        }
+    }
+
+    fn renormalize(&mut self) {
+        if self.channels < 3 {
+            return;
+        }
+        for pixel in self.data.chunks_exact_mut(self.channels) {
+            let x = pixel[0] * 2.0 - 1.0;
+            let y = pixel[1] * 2.0 - 1.0;
+            let z = pixel[2] * 2.0 - 1.0;
+            let length = (x * x + y * y + z * z).sqrt();
+            if length > f32::EPSILON {
+                pixel[0] = (x / length) * 0.5 + 0.5;
+                pixel[1] = (y / length) * 0.5 + 0.5;
+                pixel[2] = (z / length) * 0.5 + 0.5;
+            }
+        }
+    }
+
+    fn alpha_coverage(&self, alpha_index: usize, reference: f32, scale: f32) -> f32 {
+        let count = self
+            .data
+            .chunks_exact(self.channels)
+            .filter(|pixel| (pixel[alpha_index] * scale).min(1.0) > reference)
+            .count();
+        count as f32 / (self.width * self.height) as f32
+    }
+
+    // Finds a scale of alpha channel, that gives the closest alpha coverage to the desired one.
+    fn find_alpha_scale(&self, alpha_index: usize, reference: f32, coverage: f32) -> f32 {
+        let mut min_scale = 0.0;
+        let mut max_scale = 4.0;
+        let mut best_scale = 1.0;
+        let mut best_error = f32::MAX;
+        for _ in 0..16 {
+            let scale = (min_scale + max_scale) * 0.5;
+            let current = self.alpha_coverage(alpha_index, reference, scale);
+            let error = (current - coverage).abs();
+            if error < best_error {
+                best_error = error;
+                best_scale = scale;
+            }
+            if current < coverage {
+                min_scale = scale;
+            } else if current > coverage {
+                max_scale = scale;
+            } else {
+                break;
+            }
+        }
+        best_scale
+    }
+}
+
+fn color_channel_count(channels: usize) -> usize {
+    match channels {
+        1 | 2 => 1,
+        _ => 3,
+    }
+}
+
+fn alpha_channel_index(channels: usize) -> Option<usize> {
+    match channels {
+        2 => Some(1),
+        4 => Some(3),
+        _ => None,
+    }
+}
+
+/// Generates a mip-map chain for the given image. The first level is the source image itself,
+/// every next level is two times smaller. Returns `None` if the pixel kind is not supported.
+pub(crate) fn generate_mip_chain(
+    bytes: &[u8],
+    width: u32,
+    height: u32,
+    pixel_kind: TexturePixelKind,
+    options: &MipGenerationOptions,
+) -> Option<Vec<(u32, u32, Vec<u8>)>> {
+    let (channel_type, channels) = pixel_layout(pixel_kind)?;
+    let pixel_count = width as usize * height as usize;
+    if bytes.len() != pixel_count * channels * channel_type.size() {
+        return None;
+    }
+
+    let color_channels = color_channel_count(channels);
+    let alpha_index = alpha_channel_index(channels);
+    let apply_srgb = options.srgb && channel_type != ChannelType::F32;
+
+    let mut data = bytes
+        .chunks_exact(channel_type.size())
+        .map(|channel| channel_type.read(channel))
+        .collect::<Vec<_>>();
+    if apply_srgb {
+        for pixel in data.chunks_exact_mut(channels) {
+            for value in pixel.iter_mut().take(color_channels) {
+                *value = srgb_to_linear(*value);
+            }
+        }
+    }
+
+    let mut current = Level {
+        width: width as usize,
+        height: height as usize,
+        channels,
+        data,
+    };
+
+    let alpha_coverage = match (options.alpha_coverage_reference, alpha_index) {
+        (Some(reference), Some(alpha_index)) => Some((
+            reference,
+            alpha_index,
+            current.alpha_coverage(alpha_index, reference, 1.0),
+        )),
+        _ => None,
+    };
+
+    let mut levels = vec![(width, height, bytes.to_vec())];
+
+    let mut level_width = width.checked_shr(1).unwrap_or_default();
+    let mut level_height = height.checked_shr(1).unwrap_or_default();
+    while level_width != 0 && level_height != 0 {
+        current = current.downsample(options.filter, level_width as usize, level_height as usize);
+        if options.normal_map {
+            current.renormalize();
+        }
+
+        // Alpha scaling is applied only to the output, the chain itself must keep original alpha
+        // to not accumulate the error.
+        let alpha_scale = alpha_coverage.map(|(reference, alpha_index, coverage)| {
+            (
+                alpha_index,
+                current.find_alpha_scale(alpha_index, reference, coverage),
+            )
+        });
+
+        let mut level_bytes = Vec::with_capacity(current.data.len() * channel_type.size());
+        for pixel in current.data.chunks_exact(channels) {
+            for (c, value) in pixel.iter().enumerate() {
+                let mut value = *value;
+                if let Some((alpha_index, alpha_scale)) = alpha_scale {
+                    if c == alpha_index {
+                        value = (value * alpha_scale).min(1.0);
+                    }
+                }
+                if apply_srgb && c < color_channels {
+                    value = linear_to_srgb(value.max(0.0));
+                }
+                channel_type.write(value, &mut level_bytes);
+            }
+        }
+        levels.push((level_width, level_height, level_bytes));
+
+        level_width = level_width.checked_shr(1).unwrap_or_default();
+        level_height = level_height.checked_shr(1).unwrap_or_default();
+    }
+
+    Some(levels)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn options(filter: MipFilter) -> MipGenerationOptions {
+        MipGenerationOptions {
+            filter,
+            srgb: false,
+            normal_map: false,
+            alpha_coverage_reference: None,
+        }
+    }
+
+    #[test]
+    fn mip_chain_of_uniform_image_is_uniform() {
+        let bytes = [10u8, 200, 30, 255].repeat(8 * 4);
+        for filter in [
+            MipFilter::Nearest,
+            MipFilter::Bilinear,
+            MipFilter::Hamming,
+            MipFilter::CatmullRom,
+            MipFilter::Lanczos,
+            MipFilter::Kaiser,
+        ] {
+            let levels =
+                generate_mip_chain(&bytes, 8, 4, TexturePixelKind::RGBA8, &options(filter))
+                    .unwrap();
+            assert_eq!(
+                levels
+                    .iter()
+                    .map(|(width, height, _)| (*width, *height))
+                    .collect::<Vec<_>>(),
+                [(8, 4), (4, 2), (2, 1)]
+            );
+            for (width, height, level) in levels {
+                assert_eq!(
+                    level,
+                    [10u8, 200, 30, 255].repeat((width * height) as usize)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_correct_mip_filtering() {
+        // Black and white stripes.
+        let bytes = [0u8, 255].repeat(2);
+        let linear = generate_mip_chain(
+            &bytes,
+            2,
+            2,
+            TexturePixelKind::Luminance8,
+            &options(MipFilter::Nearest),
+        )
+        .unwrap();
+        assert_eq!(linear[1].2, [128]);
+
+        let srgb = generate_mip_chain(
+            &bytes,
+            2,
+            2,
+            TexturePixelKind::Luminance8,
+            &MipGenerationOptions {
+                srgb: true,
+                ..options(MipFilter::Nearest)
+            },
+        )
+        .unwrap();
+        // 50% of linear intensity is ~188 in sRGB.
+        assert_eq!(srgb[1].2, [188]);
+    }
+
+    #[test]
+    fn normal_map_renormalization() {
+        // Two opposite normals along X axis and two normals along Z axis.
+        let bytes = [255u8, 128, 128, 0, 128, 128, 128, 128, 255, 128, 128, 255];
+        let levels = generate_mip_chain(
+            &bytes,
+            2,
+            2,
+            TexturePixelKind::RGB8,
+            &MipGenerationOptions {
+                normal_map: true,
+                ..options(MipFilter::Nearest)
+            },
+        )
+        .unwrap();
+        // Average is (0, 0, 0.5), which must be renormalized to (0, 0, 1).
+        assert_eq!(levels[1].2, [128, 128, 255]);
+    }
+
+    #[test]
+    fn alpha_coverage_preservation() {
+        // 5 of 16 pixels are fully opaque, the first mip level has alpha values of 0.5 and 0.25.
+        let mut bytes = Vec::new();
+        for y in 0..4 {
+            for x in 0..4 {
+                bytes.extend_from_slice(&[255, 255, 255]);
+                let opaque = (x % 2 == 0 && y % 2 == 0) || (x == 1 && y == 0);
+                bytes.push(if opaque { 255 } else { 0 });
+            }
+        }
+        let coverage = |level: &[u8]| {
+            level
+                .chunks_exact(4)
+                .filter(|pixel| pixel[3] as f32 / 255.0 > 0.6)
+                .count() as f32
+                / (level.len() / 4) as f32
+        };
+
+        let plain = generate_mip_chain(
+            &bytes,
+            4,
+            4,
+            TexturePixelKind::RGBA8,
+            &options(MipFilter::Nearest),
+        )
+        .unwrap();
+        assert_eq!(coverage(&plain[1].2), 0.0);
+
+        let preserved = generate_mip_chain(
+            &bytes,
+            4,
+            4,
+            TexturePixelKind::RGBA8,
+            &MipGenerationOptions {
+                alpha_coverage_reference: Some(0.6),
+                ..options(MipFilter::Nearest)
+            },
+        )
+        .unwrap();
+        assert_eq!(coverage(&preserved[1].2), 0.25);
+    }
+}
@@ -21,11 +21,15 @@
 //! access to pixels of render target.
 
 use crate::{
-    asset::{options::ImportOptions, Resource, ResourceData, TEXTURE_RESOURCE_UUID},
+    asset::{
+        io::write_atomically, options::ImportOptions, task::run_offloaded, Resource, ResourceData,
+        TEXTURE_RESOURCE_UUID,
+    },
     core::{
         algebra::{Vector2, Vector3},
         futures::io::Error,
         io::FileLoadError,
+        math::Rect,
         reflect::prelude::*,
         uuid::Uuid,
         visitor::{PodVecView, Visit, VisitError, VisitResult, Visitor},
@@ -41,6 +45,7 @@ use image::{ColorType, DynamicImage, ImageError, ImageFormat, Pixel};
 use serde::{Deserialize, Serialize};
 use std::{
     any::Any,
+    collections::VecDeque,
     fmt::{Debug, Display, Formatter},
     hash::{Hash, Hasher},
     io::Cursor,
@@ -50,7 +55,10 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod atlas;
+pub mod importer;
 pub mod loader;
+mod mips;
 
 /// Texture kind.
 #[derive(Copy, Clone, Debug, Reflect)]
@@ -255,6 +263,19 @@ pub struct Texture {
     is_embedded: bool,
     data_hash: u64,
     is_render_target: bool,
+    #[reflect(hidden)]
+    region_updates: VecDeque<TextureRegionUpdate>,
+}
+
+/// Maximum amount of region updates, that is tracked by a texture. Renderer uploads the entire
+/// texture, if there were more updates since the last upload.
+const MAX_REGION_UPDATES: usize = 32;
+
+#[derive(Debug, Clone)]
+struct TextureRegionUpdate {
+    /// Data hash of the texture before the update.
+    prev_hash: u64,
+    region: Rect<u32>,
 }
 
 impl TypeUuidProvider for Texture {
@@ -287,6 +308,10 @@ impl ResourceData for Texture {
     fn is_embedded(&self) -> bool {
         self.is_embedded
     }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.bytes.len()
+    }
 }
 
 impl Visit for Texture {
@@ -343,6 +368,7 @@ impl Default for Texture {
             is_embedded: false,
             data_hash: 0,
             is_render_target: false,
+            region_updates: Default::default(),
         }
     }
 }
@@ -376,6 +402,10 @@ pub enum MipFilter {
     /// Gaussian filtration. It has perfect filtration quality, but it is ~37x times slower than
     /// [`Self::Nearest`].
     Lanczos,
+    /// Kaiser-windowed sinc filtration. It produces sharp mip levels with much less ringing than
+    /// [`Self::Lanczos`], which makes it a good choice for detailed textures. It is the slowest
+    /// filter available.
+    Kaiser,
 }
 
 impl MipFilter {
@@ -386,6 +416,9 @@ impl MipFilter {
             MipFilter::CatmullRom => fr::FilterType::CatmullRom,
             MipFilter::Hamming => fr::FilterType::Hamming,
             MipFilter::Lanczos => fr::FilterType::Lanczos3,
+            // Kaiser filter is not supported by the resizer, mip levels are generated by the
+            // engine itself in this case (see `mips` module).
+            MipFilter::Kaiser => fr::FilterType::Lanczos3,
         }
     }
 }
@@ -425,6 +458,12 @@ pub struct TextureImportOptions {
     pub(crate) mip_filter: MipFilter,
     #[serde(default)]
     pub(crate) flip_green_channel: bool,
+    #[serde(default)]
+    pub(crate) srgb_mip_filtering: bool,
+    #[serde(default)]
+    pub(crate) normal_map_mips: bool,
+    #[serde(default)]
+    pub(crate) alpha_coverage_reference: Option<f32>,
 }
 
 impl Default for TextureImportOptions {
@@ -438,6 +477,9 @@ impl Default for TextureImportOptions {
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
             flip_green_channel: false,
+            srgb_mip_filtering: false,
+            normal_map_mips: false,
+            alpha_coverage_reference: None,
         }
     }
 }
@@ -526,6 +568,73 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Sets a filter, that will be used to generate mip levels.
+    pub fn with_mip_filter(mut self, mip_filter: MipFilter) -> Self {
+        self.mip_filter = mip_filter;
+        self
+    }
+
+    /// Sets a filter, that will be used to generate mip levels.
+    pub fn set_mip_filter(&mut self, mip_filter: MipFilter) {
+        self.mip_filter = mip_filter;
+    }
+
+    /// Enables or disables sRGB-correct mip filtering. When enabled, color channels are converted
+    /// to linear space before filtering and back to sRGB after, which prevents darkening of mip
+    /// levels of sRGB textures (albedo, UI images, etc.). Should be disabled for textures with
+    /// non-color data (normal maps, roughness maps, etc.).
+    pub fn with_srgb_mip_filtering(mut self, enabled: bool) -> Self {
+        self.srgb_mip_filtering = enabled;
+        self
+    }
+
+    /// Enables or disables sRGB-correct mip filtering. See [`Self::with_srgb_mip_filtering`]
+    /// for more info.
+    pub fn set_srgb_mip_filtering(&mut self, enabled: bool) {
+        self.srgb_mip_filtering = enabled;
+    }
+
+    /// Marks the texture as a normal map. Normals of every mip level will be renormalized after
+    /// filtering, otherwise averaged normals become shorter in distant mip levels, which makes
+    /// lighting too flat.
+    pub fn with_normal_map_mips(mut self, enabled: bool) -> Self {
+        self.normal_map_mips = enabled;
+        self
+    }
+
+    /// Marks the texture as a normal map. See [`Self::with_normal_map_mips`] for more info.
+    pub fn set_normal_map_mips(&mut self, enabled: bool) {
+        self.normal_map_mips = enabled;
+    }
+
+    /// Sets alpha reference value (alpha test threshold) which is used to preserve alpha coverage
+    /// in mip levels. When set, alpha channel of every mip level is scaled so the amount of pixels
+    /// with alpha larger than the reference value stays the same as in the source image. It
+    /// prevents alpha-tested geometry (foliage, fences, etc.) from "thinning out" with distance.
+    pub fn with_alpha_coverage_reference(mut self, reference: Option<f32>) -> Self {
+        self.alpha_coverage_reference = reference;
+        self
+    }
+
+    /// Sets alpha reference value which is used to preserve alpha coverage in mip levels. See
+    /// [`Self::with_alpha_coverage_reference`] for more info.
+    pub fn set_alpha_coverage_reference(&mut self, reference: Option<f32>) {
+        self.alpha_coverage_reference = reference;
+    }
+
+    fn mip_generation_options(&self) -> Option<mips::MipGenerationOptions> {
+        let is_default = !matches!(self.mip_filter, MipFilter::Kaiser)
+            && !self.srgb_mip_filtering
+            && !self.normal_map_mips
+            && self.alpha_coverage_reference.is_none();
+        (!is_default).then_some(mips::MipGenerationOptions {
+            filter: self.mip_filter,
+            srgb: self.srgb_mip_filtering,
+            normal_map: self.normal_map_mips,
+            alpha_coverage_reference: self.alpha_coverage_reference,
+        })
+    }
 }
 
 /// Type alias for texture resources.
@@ -569,6 +678,15 @@ pub trait TextureResourceExtension: Sized {
         serialize_content: bool,
     ) -> Option<Self>;
 
+    /// Creates a texture resource, that is meant to be changed at runtime. See
+    /// [`Texture::new_dynamic`] for more info.
+    fn new_dynamic(
+        width: u32,
+        height: u32,
+        pixel_kind: TexturePixelKind,
+        bytes: Vec<u8>,
+    ) -> Option<Self>;
+
     /// Creates a deep clone of the texture. Unlike [`TextureResource::clone`], this method clones the actual texture data,
     /// which could be slow.
     fn deep_clone(&self) -> Self;
@@ -591,6 +709,7 @@ impl TextureResourceExtension for TextureResource {
             is_embedded: false,
             data_hash: 0,
             is_render_target: true,
+            region_updates: Default::default(),
         })
     }
 
@@ -618,6 +737,17 @@ impl TextureResourceExtension for TextureResource {
         )?))
     }
 
+    fn new_dynamic(
+        width: u32,
+        height: u32,
+        pixel_kind: TexturePixelKind,
+        bytes: Vec<u8>,
+    ) -> Option<Self> {
+        Some(Resource::new_ok(Texture::new_dynamic(
+            width, height, pixel_kind, bytes,
+        )?))
+    }
+
     fn deep_clone(&self) -> Self {
         Resource::new_ok(self.data_ref().clone())
     }
@@ -949,6 +1079,15 @@ pub enum TextureError {
     Image(image::ImageError),
     /// An error occurred during file loading.
     FileLoadError(FileLoadError),
+    /// A region is out of bounds of a texture.
+    InvalidRegion,
+    /// Size of the data does not match the size of a texture (or its region).
+    InvalidDataSize {
+        /// Expected data size in bytes.
+        expected: usize,
+        /// Actual data size in bytes.
+        actual: usize,
+    },
 }
 
 impl Display for TextureError {
@@ -966,10 +1105,21 @@ impl Display for TextureError {
             TextureError::FileLoadError(v) => {
                 write!(f, "A file load error has occurred {v:?}")
             }
+            TextureError::InvalidRegion => {
+                write!(f, "The region is out of bounds of the texture!")
+            }
+            TextureError::InvalidDataSize { expected, actual } => {
+                write!(
+                    f,
+                    "Invalid data size. Expected {expected} bytes, got {actual} bytes."
+                )
+            }
         }
     }
 }
 
+impl std::error::Error for TextureError {}
+
 impl From<FileLoadError> for TextureError {
     fn from(v: FileLoadError) -> Self {
         Self::FileLoadError(v)
@@ -1231,6 +1381,60 @@ where
     }
 }
 
+/// Pixels of a rectangle texture without mip levels.
+pub(crate) struct DecodedImage {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) pixel_kind: TexturePixelKind,
+    pub(crate) pixels: Vec<u8>,
+}
+
+/// Decodes an image in one of the commonly used formats (PNG, JPG, etc.).
+pub(crate) fn decode_image(
+    data: &[u8],
+    import_options: &TextureImportOptions,
+) -> Result<DecodedImage, TextureError> {
+    // Commonly used formats are all rectangle textures.
+    let mut dyn_img = image::load_from_memory(data)
+        // Try to load as TGA, this is needed because TGA is badly designed format and does not
+        // have an identifier in the beginning of the file (so called "magic") that allows quickly
+        // check if the file is really contains expected data.
+        .or_else(|_| image::load_from_memory_with_format(data, ImageFormat::Tga))?;
+
+    if import_options.flip_green_channel {
+        match dyn_img {
+            DynamicImage::ImageRgb8(ref mut img) => flip_green_channel(img.pixels_mut()),
+            DynamicImage::ImageRgba8(ref mut img) => flip_green_channel(img.pixels_mut()),
+            DynamicImage::ImageRgb16(ref mut img) => flip_green_channel(img.pixels_mut()),
+            DynamicImage::ImageRgba16(ref mut img) => flip_green_channel(img.pixels_mut()),
+            DynamicImage::ImageRgb32F(ref mut img) => flip_green_channel(img.pixels_mut()),
+            DynamicImage::ImageRgba32F(ref mut img) => flip_green_channel(img.pixels_mut()),
+            _ => (),
+        }
+    }
+
+    let pixel_kind = match dyn_img {
+        DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
+        DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
+        DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
+        DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
+        DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
+        DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
+        DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
+        DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
+        DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
+        DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
+        _ => return Err(TextureError::UnsupportedFormat),
+    };
+
+    Ok(DecodedImage {
+        width: dyn_img.width(),
+        height: dyn_img.height(),
+        pixel_kind,
+        pixels: dyn_img.into_bytes(),
+    })
+}
+
 impl Texture {
     /// Tries to load a texture from given data in one of the following formats: PNG, BMP, TGA, JPG, DDS, GIF. Use
     /// this method if you want to load a texture from embedded data.
@@ -1242,6 +1446,9 @@ impl Texture {
     /// because DDS can already contain such data, you should generate mips and compress DDS textures manually using
     /// some offline tool like DirectXTexTool or similar.
     ///
+    /// Textures requested from the resource manager are imported by [`importer::TextureImporter`]
+    /// first, so their mips are generated once and then loaded from the derived data cache.
+    ///
     /// # Important notes
     ///
     /// Textures loaded with this method won't be correctly serialized! It means that if you'll made a scene with
@@ -1257,6 +1464,29 @@ impl Texture {
         data: &[u8],
         import_options: TextureImportOptions,
     ) -> Result<Self, TextureError> {
+        // Derived data of the texture importer already contains the mip chain.
+        if let Some(derived) = importer::read_derived_data(data) {
+            return Ok(Self {
+                path: Default::default(),
+                pixel_kind: derived.pixel_kind,
+                kind: TextureKind::Rectangle {
+                    width: derived.width,
+                    height: derived.height,
+                },
+                data_hash: data_hash(&derived.bytes),
+                bytes: derived.bytes.into(),
+                mip_count: derived.mip_count,
+                minification_filter: import_options.minification_filter,
+                magnification_filter: import_options.magnification_filter,
+                s_wrap_mode: import_options.s_wrap_mode,
+                t_wrap_mode: import_options.t_wrap_mode,
+                anisotropy: import_options.anisotropy,
+                is_embedded: false,
+                is_render_target: false,
+                region_updates: Default::default(),
+            });
+        }
+
         // DDS is special. It can contain various kinds of textures as well as textures with
         // various pixel formats.
         //
@@ -1343,58 +1573,68 @@ impl Texture {
                 },
                 is_embedded: false,
                 is_render_target: false,
+                region_updates: Default::default(),
             })
         } else {
-            // Commonly used formats are all rectangle textures.
-            let mut dyn_img = image::load_from_memory(data)
-                // Try to load as TGA, this is needed because TGA is badly designed format and does not
-                // have an identifier in the beginning of the file (so called "magic") that allows quickly
-                // check if the file is really contains expected data.
-                .or_else(|_| image::load_from_memory_with_format(data, ImageFormat::Tga))?;
-
-            let width = dyn_img.width();
-            let height = dyn_img.height();
-
-            if import_options.flip_green_channel {
-                match dyn_img {
-                    DynamicImage::ImageRgb8(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    DynamicImage::ImageRgba8(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    DynamicImage::ImageRgb16(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    DynamicImage::ImageRgba16(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    DynamicImage::ImageRgb32F(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    DynamicImage::ImageRgba32F(ref mut img) => flip_green_channel(img.pixels_mut()),
-                    _ => (),
-                }
-            }
+            Self::from_decoded_image(decode_image(data, &import_options)?, import_options)
+        }
+    }
 
-            let src_pixel_kind = match dyn_img {
-                DynamicImage::ImageLuma8(_) => TexturePixelKind::Luminance8,
-                DynamicImage::ImageLumaA8(_) => TexturePixelKind::LuminanceAlpha8,
-                DynamicImage::ImageRgb8(_) => TexturePixelKind::RGB8,
-                DynamicImage::ImageRgba8(_) => TexturePixelKind::RGBA8,
-                DynamicImage::ImageLuma16(_) => TexturePixelKind::Luminance16,
-                DynamicImage::ImageLumaA16(_) => TexturePixelKind::LuminanceAlpha16,
-                DynamicImage::ImageRgb16(_) => TexturePixelKind::RGB16,
-                DynamicImage::ImageRgba16(_) => TexturePixelKind::RGBA16,
-                DynamicImage::ImageRgb32F(_) => TexturePixelKind::RGB32F,
-                DynamicImage::ImageRgba32F(_) => TexturePixelKind::RGBA32F,
-                _ => return Err(TextureError::UnsupportedFormat),
-            };
-            let mut final_pixel_kind = src_pixel_kind;
+    /// Creates a texture from a decoded image, generates mip levels and compresses the data if
+    /// needed.
+    fn from_decoded_image(
+        image: DecodedImage,
+        import_options: TextureImportOptions,
+    ) -> Result<Self, TextureError> {
+        let DecodedImage {
+            width,
+            height,
+            pixel_kind: src_pixel_kind,
+            pixels,
+        } = image;
+
+        let mut final_pixel_kind = src_pixel_kind;
+
+        let mut mip_count = 0;
+        let mut bytes = Vec::with_capacity(
+            width as usize * height as usize * src_pixel_kind.size_in_bytes().unwrap_or(4),
+        );
+
+        let mut push_level = |buffer: &[u8], level_width: u32, level_height: u32| {
+            if import_options.compression == CompressionOptions::NoCompression {
+                bytes.extend_from_slice(buffer)
+            } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
+                src_pixel_kind,
+                buffer,
+                level_width as usize,
+                level_height as usize,
+                import_options.compression,
+            ) {
+                final_pixel_kind = new_pixel_kind;
+                bytes.extend_from_slice(&compressed_data);
+            } else {
+                bytes.extend_from_slice(buffer)
+            }
+        };
 
-            let mut mip_count = 0;
-            let mut bytes = Vec::with_capacity(
-                width as usize * height as usize * src_pixel_kind.size_in_bytes().unwrap_or(4),
-            );
+        if import_options.minification_filter.is_using_mip_mapping() {
+            if let Some(mip_options) = import_options.mip_generation_options() {
+                let levels =
+                    mips::generate_mip_chain(&pixels, width, height, src_pixel_kind, &mip_options)
+                        .ok_or(TextureError::UnsupportedFormat)?;
 
-            if import_options.minification_filter.is_using_mip_mapping() {
+                for (level_width, level_height, level) in levels {
+                    mip_count += 1;
+                    push_level(&level, level_width, level_height);
+                }
+            } else {
                 let src_pixel_type = convert_pixel_type_enum(src_pixel_kind);
                 let mut level_width = width;
                 let mut level_height = height;
                 let mut current_level = fr::Image::from_vec_u8(
                     NonZeroU32::new(level_width).unwrap(),
                     NonZeroU32::new(level_height).unwrap(),
-                    dyn_img.as_bytes().to_vec(),
+                    pixels,
                     src_pixel_type,
                 )
                 .map_err(|_| TextureError::UnsupportedFormat)?;
@@ -1420,59 +1660,34 @@ impl Texture {
 
                     mip_count += 1;
 
-                    if import_options.compression == CompressionOptions::NoCompression {
-                        bytes.extend_from_slice(current_level.buffer())
-                    } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
-                        src_pixel_kind,
-                        current_level.buffer(),
-                        level_width as usize,
-                        level_height as usize,
-                        import_options.compression,
-                    ) {
-                        final_pixel_kind = new_pixel_kind;
-                        bytes.extend_from_slice(&compressed_data);
-                    } else {
-                        bytes.extend_from_slice(current_level.buffer())
-                    }
+                    push_level(current_level.buffer(), level_width, level_height);
 
                     level_width = level_width.checked_shr(1).unwrap_or_default();
                     level_height = level_height.checked_shr(1).unwrap_or_default();
                 }
-            } else {
-                mip_count = 1;
-
-                if import_options.compression == CompressionOptions::NoCompression {
-                    bytes.extend_from_slice(dyn_img.as_bytes());
-                } else if let Some((compressed_data, new_pixel_kind)) = try_compress(
-                    src_pixel_kind,
-                    dyn_img.as_bytes(),
-                    width as usize,
-                    height as usize,
-                    import_options.compression,
-                ) {
-                    final_pixel_kind = new_pixel_kind;
-                    bytes.extend_from_slice(&compressed_data);
-                } else {
-                    bytes.extend_from_slice(dyn_img.as_bytes())
-                }
             }
+        } else {
+            mip_count = 1;
 
-            Ok(Self {
-                path: Default::default(),
-                pixel_kind: final_pixel_kind,
-                kind: TextureKind::Rectangle { width, height },
-                data_hash: data_hash(&bytes),
-                bytes: bytes.into(),
-                mip_count,
-                minification_filter: import_options.minification_filter,
-                magnification_filter: import_options.magnification_filter,
-                s_wrap_mode: import_options.s_wrap_mode,
-                t_wrap_mode: import_options.t_wrap_mode,
-                anisotropy: import_options.anisotropy,
-                is_embedded: false,
-                is_render_target: false,
-            })
+            push_level(&pixels, width, height);
         }
+
+        Ok(Self {
+            path: Default::default(),
+            pixel_kind: final_pixel_kind,
+            kind: TextureKind::Rectangle { width, height },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            mip_count,
+            minification_filter: import_options.minification_filter,
+            magnification_filter: import_options.magnification_filter,
+            s_wrap_mode: import_options.s_wrap_mode,
+            t_wrap_mode: import_options.t_wrap_mode,
+            anisotropy: import_options.anisotropy,
+            is_embedded: false,
+            is_render_target: false,
+            region_updates: Default::default(),
+        })
     }
 
     /// Tries to load a texture from a file.
@@ -1487,7 +1702,15 @@ impl Texture {
         import_options: TextureImportOptions,
     ) -> Result<Self, TextureError> {
         let data = io.load_file(path.as_ref()).await?;
-        let mut texture = Self::load_from_memory(&data, import_options)?;
+        // Decoding is the heaviest part, move it off the main thread where it's possible.
+        let mut texture = run_offloaded(move || Self::load_from_memory(&data, import_options))
+            .await
+            .map_err(|err| {
+                TextureError::Io(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    err.to_string(),
+                ))
+            })??;
         texture.path = path.as_ref().to_path_buf();
         Ok(texture)
     }
@@ -1698,13 +1921,15 @@ impl Texture {
             | TexturePixelKind::R16F => return Err(TextureError::UnsupportedFormat),
         };
         if let TextureKind::Rectangle { width, height } = self.kind {
-            Ok(image::save_buffer(
-                &self.path,
-                self.bytes.as_ref(),
-                width,
-                height,
-                color_type,
-            )?)
+            write_atomically(&self.path, |path| {
+                Ok(image::save_buffer(
+                    path,
+                    self.bytes.as_ref(),
+                    width,
+                    height,
+                    color_type,
+                )?)
+            })
         } else {
             Err(TextureError::UnsupportedFormat)
         }
@@ -1715,6 +1940,136 @@ impl Texture {
     pub fn modify(&mut self) -> TextureDataRefMut<'_> {
         TextureDataRefMut { texture: self }
     }
+
+    /// Creates a texture, that is meant to be changed at runtime (minimaps, procedural painting,
+    /// video frames, etc.), using the given buffer as its content. The texture is a rectangle
+    /// texture with a single mip level, linear filtration and clamped coordinates. Its content could
+    /// be changed using [`Self::update_region`] or [`Self::replace_data`]. Returns [`None`] if the
+    /// size of the buffer does not match the size and the pixel kind of the texture.
+    pub fn new_dynamic(
+        width: u32,
+        height: u32,
+        pixel_kind: TexturePixelKind,
+        bytes: Vec<u8>,
+    ) -> Option<Self> {
+        let mut texture = Self::from_bytes(
+            TextureKind::Rectangle { width, height },
+            pixel_kind,
+            bytes,
+            false,
+        )?;
+        texture.minification_filter = TextureMinificationFilter::Linear;
+        texture.magnification_filter = TextureMagnificationFilter::Linear;
+        texture.s_wrap_mode = TextureWrapMode::ClampToEdge;
+        texture.t_wrap_mode = TextureWrapMode::ClampToEdge;
+        texture.anisotropy = 1.0;
+        Some(texture)
+    }
+
+    /// Replaces the entire content of the texture with the given buffer and returns the previous
+    /// buffer, so it could be reused (for example, to decode the next video frame into it). The size
+    /// of the new buffer must match the size of the current one.
+    pub fn replace_data(&mut self, bytes: Vec<u8>) -> Result<Vec<u8>, TextureError> {
+        if bytes.len() != self.bytes.len() {
+            return Err(TextureError::InvalidDataSize {
+                expected: self.bytes.len(),
+                actual: bytes.len(),
+            });
+        }
+        let mut texture = self.modify();
+        Ok(std::mem::replace(&mut texture.bytes.0, bytes))
+    }
+
+    /// Copies the given pixels into a rectangular region of the texture. Unlike [`Self::modify`],
+    /// only the changed region is uploaded to GPU on the next frame, which makes this method a
+    /// good fit for frequent small changes. The pixels must be stored row-by-row, from top to
+    /// bottom, and must have the pixel kind of the texture. Only uncompressed rectangle textures
+    /// with a single mip level are supported.
+    pub fn update_region(&mut self, region: Rect<u32>, pixels: &[u8]) -> Result<(), TextureError> {
+        let (TextureKind::Rectangle { width, height }, Some(pixel_size), 1) =
+            (self.kind, self.pixel_kind.size_in_bytes(), self.mip_count)
+        else {
+            return Err(TextureError::UnsupportedFormat);
+        };
+
+        if region.x() + region.w() > width || region.y() + region.h() > height {
+            return Err(TextureError::InvalidRegion);
+        }
+
+        let row_size = region.w() as usize * pixel_size;
+        let expected = row_size * region.h() as usize;
+        if pixels.len() != expected {
+            return Err(TextureError::InvalidDataSize {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+
+        if expected == 0 {
+            return Ok(());
+        }
+
+        for (row, src) in pixels.chunks_exact(row_size).enumerate() {
+            let offset =
+                ((region.y() as usize + row) * width as usize + region.x() as usize) * pixel_size;
+            self.bytes[offset..(offset + row_size)].copy_from_slice(src);
+        }
+
+        // Hashing the entire texture on every update is too slow for large textures, so the new
+        // hash is derived from the previous one and the changed pixels.
+        let mut hasher = FxHasher::default();
+        self.data_hash.hash(&mut hasher);
+        (region.x(), region.y(), region.w(), region.h()).hash(&mut hasher);
+        pixels.hash(&mut hasher);
+        let prev_hash = std::mem::replace(&mut self.data_hash, hasher.finish());
+
+        if self.region_updates.len() == MAX_REGION_UPDATES {
+            self.region_updates.pop_front();
+        }
+        self.region_updates
+            .push_back(TextureRegionUpdate { prev_hash, region });
+
+        Ok(())
+    }
+
+    /// Returns a copy of the pixels of the given region of an uncompressed rectangle texture. Pixels
+    /// are stored row-by-row, from top to bottom.
+    pub fn region_data(&self, region: Rect<u32>) -> Option<Vec<u8>> {
+        let TextureKind::Rectangle { width, height } = self.kind else {
+            return None;
+        };
+        let pixel_size = self.pixel_kind.size_in_bytes()?;
+        if region.x() + region.w() > width || region.y() + region.h() > height {
+            return None;
+        }
+
+        let row_size = region.w() as usize * pixel_size;
+        let mut pixels = Vec::with_capacity(row_size * region.h() as usize);
+        for row in region.y()..(region.y() + region.h()) {
+            let offset = (row as usize * width as usize + region.x() as usize) * pixel_size;
+            pixels.extend_from_slice(&self.bytes[offset..(offset + row_size)]);
+        }
+        Some(pixels)
+    }
+
+    /// Returns regions, that were changed by [`Self::update_region`] since the texture had the given
+    /// data hash. [`None`] means that the texture was changed in some other way (or there were too
+    /// many updates) and it must be uploaded entirely.
+    pub(crate) fn updated_regions_since(
+        &self,
+        data_hash: u64,
+    ) -> Option<impl Iterator<Item = Rect<u32>> + '_> {
+        let first = self
+            .region_updates
+            .iter()
+            .position(|update| update.prev_hash == data_hash)?;
+        Some(
+            self.region_updates
+                .iter()
+                .skip(first)
+                .map(|update| update.region),
+        )
+    }
 }
 
 /// A special reference holder that provides mutable access to content of the
@@ -1726,6 +2081,7 @@ pub struct TextureDataRefMut<'a> {
 impl<'a> Drop for TextureDataRefMut<'a> {
     fn drop(&mut self) {
         self.texture.data_hash = data_hash(&self.texture.bytes);
+        self.texture.region_updates.clear();
     }
 }
 
@@ -1767,8 +2123,12 @@ impl<'a> TextureDataRefMut<'a> {
 
 #[cfg(test)]
 pub mod test {
-    use crate::resource::texture::{
-        TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension,
+    use crate::{
+        core::math::Rect,
+        resource::texture::{
+            Texture, TextureError, TextureKind, TexturePixelKind, TextureResource,
+            TextureResourceExtension,
+        },
     };
 
     pub fn create_test_texture() -> TextureResource {
@@ -1783,4 +2143,40 @@ pub mod test {
         )
         .unwrap()
     }
+
+    #[test]
+    fn test_texture_update_region() {
+        let mut texture = Texture::new_dynamic(3, 2, TexturePixelKind::R8, vec![0; 6]).unwrap();
+        let initial_hash = texture.data_hash();
+
+        let region = Rect::new(1, 0, 2, 2);
+        texture.update_region(region, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(texture.data(), [0, 1, 2, 0, 3, 4]);
+        assert_eq!(texture.region_data(region).unwrap(), [1, 2, 3, 4]);
+        assert_ne!(texture.data_hash(), initial_hash);
+        assert_eq!(
+            texture
+                .updated_regions_since(initial_hash)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [region]
+        );
+
+        assert!(matches!(
+            texture.update_region(Rect::new(2, 0, 2, 1), &[1, 2]),
+            Err(TextureError::InvalidRegion)
+        ));
+        assert!(matches!(
+            texture.update_region(region, &[1]),
+            Err(TextureError::InvalidDataSize {
+                expected: 4,
+                actual: 1
+            })
+        ));
+
+        // Full modification invalidates region updates.
+        let old = texture.replace_data(vec![5; 6]).unwrap();
+        assert_eq!(old, [0, 1, 2, 0, 3, 4]);
+        assert!(texture.updated_regions_since(initial_hash).is_none());
+    }
 }
@@ -0,0 +1,443 @@
+//! Sprite animation player is a node that plays animations of a sprite sheet on a sprite or a
+//! rectangle. See [`SpriteAnimationPlayer`] docs for more info.
+
+use crate::{
+    asset::ResourceStateRef,
+    core::{
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    resource::spritesheet::{SpriteSheet, SpriteSheetResource},
+    scene::{
+        base::{Base, BaseBuilder},
+        dim2::rectangle::Rectangle,
+        graph::Graph,
+        node::{Node, NodeTrait, UpdateContext},
+        sprite::Sprite,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Sprite animation player is a node that plays an animation of a sprite sheet (see
+/// [`SpriteSheet`] docs) by changing texture coordinates of a target node. The target node could be
+/// either [`Sprite`] or [`Rectangle`]. Keep in mind, that the player does not change the texture of
+/// the target node - the material of the target node must use the texture of the sprite sheet.
+///
+/// Every frame of a sprite sheet has its own duration, so the player does not have a fixed frame
+/// rate. The playback speed could be changed using [`SpriteAnimationPlayer::set_speed`].
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     resource::spritesheet::SpriteSheetResource,
+///     scene::{
+///         animation::spritesheet::SpriteAnimationPlayerBuilder, base::BaseBuilder, graph::Graph,
+///         node::Node,
+///     },
+/// };
+///
+/// fn create_walk_animation_player(
+///     sprite_sheet: SpriteSheetResource,
+///     rectangle: Handle<Node>,
+///     graph: &mut Graph,
+/// ) -> Handle<Node> {
+///     SpriteAnimationPlayerBuilder::new(BaseBuilder::new())
+///         .with_sprite_sheet(Some(sprite_sheet))
+///         .with_target(rectangle)
+///         .with_animation("Walk")
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Clone, Debug)]
+pub struct SpriteAnimationPlayer {
+    base: Base,
+
+    #[reflect(setter = "set_sprite_sheet")]
+    sprite_sheet: InheritableVariable<Option<SpriteSheetResource>>,
+
+    #[reflect(setter = "set_target")]
+    target: InheritableVariable<Handle<Node>>,
+
+    #[reflect(setter = "set_animation")]
+    animation: InheritableVariable<String>,
+
+    #[reflect(setter = "set_speed")]
+    speed: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_playing")]
+    playing: InheritableVariable<bool>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    position: usize,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    frame_time: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    current_frame: Option<usize>,
+}
+
+impl Default for SpriteAnimationPlayer {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            sprite_sheet: Default::default(),
+            target: Default::default(),
+            animation: Default::default(),
+            speed: 1.0.into(),
+            playing: true.into(),
+            position: 0,
+            frame_time: 0.0,
+            current_frame: None,
+        }
+    }
+}
+
+impl SpriteAnimationPlayer {
+    /// Sets new sprite sheet and rewinds the current animation to the beginning.
+    pub fn set_sprite_sheet(
+        &mut self,
+        sprite_sheet: Option<SpriteSheetResource>,
+    ) -> Option<SpriteSheetResource> {
+        self.rewind();
+        self.sprite_sheet.set_value_and_mark_modified(sprite_sheet)
+    }
+
+    /// Returns current sprite sheet.
+    pub fn sprite_sheet(&self) -> Option<SpriteSheetResource> {
+        (*self.sprite_sheet).clone()
+    }
+
+    /// Sets new target node, which texture coordinates will be animated. The node must be either
+    /// [`Sprite`] or [`Rectangle`].
+    pub fn set_target(&mut self, target: Handle<Node>) -> Handle<Node> {
+        // Force the new target to use the current frame.
+        self.current_frame = None;
+        self.target.set_value_and_mark_modified(target)
+    }
+
+    /// Returns current target node.
+    pub fn target(&self) -> Handle<Node> {
+        *self.target
+    }
+
+    /// Sets the name of the animation to play and rewinds it to the beginning. Empty name means that
+    /// the player will play every frame of the sprite sheet in order.
+    pub fn set_animation(&mut self, animation: String) -> String {
+        self.rewind();
+        self.animation.set_value_and_mark_modified(animation)
+    }
+
+    /// Returns the name of the current animation.
+    pub fn animation(&self) -> &str {
+        &self.animation
+    }
+
+    /// Starts playing the animation with the given name. Unlike [`Self::set_animation`], the
+    /// animation is not rewound if it is already playing.
+    pub fn play(&mut self, animation: &str) {
+        if *self.animation != animation {
+            self.set_animation(animation.to_string());
+        }
+        self.set_playing(true);
+    }
+
+    /// Sets playback speed multiplier. Default is 1.0.
+    pub fn set_speed(&mut self, speed: f32) -> f32 {
+        self.speed.set_value_and_mark_modified(speed)
+    }
+
+    /// Returns playback speed multiplier.
+    pub fn speed(&self) -> f32 {
+        *self.speed
+    }
+
+    /// Starts or pauses the playback.
+    pub fn set_playing(&mut self, playing: bool) -> bool {
+        self.playing.set_value_and_mark_modified(playing)
+    }
+
+    /// Returns `true` if the current animation is playing, `false` - otherwise.
+    pub fn is_playing(&self) -> bool {
+        *self.playing
+    }
+
+    /// Rewinds the current animation to its first frame.
+    pub fn rewind(&mut self) {
+        self.position = 0;
+        self.frame_time = 0.0;
+        self.current_frame = None;
+    }
+
+    /// Returns an index of the current frame of the sprite sheet, if any. The index is updated only
+    /// when the sprite sheet is fully loaded.
+    pub fn current_frame(&self) -> Option<usize> {
+        self.current_frame
+    }
+
+    fn frames_count(&self, sprite_sheet: &SpriteSheet) -> usize {
+        if self.animation.is_empty() {
+            sprite_sheet.frames.len()
+        } else {
+            sprite_sheet
+                .animation(&self.animation)
+                .map_or(0, |a| a.frames.len())
+        }
+    }
+
+    fn frame_index(&self, sprite_sheet: &SpriteSheet, position: usize) -> Option<usize> {
+        if self.animation.is_empty() {
+            (position < sprite_sheet.frames.len()).then_some(position)
+        } else {
+            sprite_sheet
+                .animation(&self.animation)
+                .and_then(|a| a.frames.get(position).cloned())
+        }
+    }
+
+    fn is_looping(&self, sprite_sheet: &SpriteSheet) -> bool {
+        self.animation.is_empty()
+            || sprite_sheet
+                .animation(&self.animation)
+                .is_some_and(|a| a.looping)
+    }
+
+    // Advances playback by the given amount of time. Returns an index of the current frame of the
+    // sprite sheet.
+    fn advance(&mut self, sprite_sheet: &SpriteSheet, dt: f32) -> Option<usize> {
+        let count = self.frames_count(sprite_sheet);
+        if count == 0 {
+            return None;
+        }
+        self.position = self.position.min(count - 1);
+
+        if *self.playing {
+            self.frame_time += dt * *self.speed;
+            // Iteration limit prevents infinite loops on frames with zero duration.
+            for _ in 0..count {
+                let duration = self
+                    .frame_index(sprite_sheet, self.position)
+                    .and_then(|i| sprite_sheet.frames.get(i))
+                    .map_or(0.0, |f| f.duration);
+                if self.frame_time < duration {
+                    break;
+                }
+                if self.position + 1 < count {
+                    self.position += 1;
+                } else if self.is_looping(sprite_sheet) {
+                    self.position = 0;
+                } else {
+                    self.frame_time = duration;
+                    self.playing.set_value_silent(false);
+                    break;
+                }
+                self.frame_time -= duration;
+            }
+        }
+
+        self.frame_index(sprite_sheet, self.position)
+    }
+}
+
+impl TypeUuidProvider for SpriteAnimationPlayer {
+    fn type_uuid() -> Uuid {
+        uuid!("a8f1d0e2-7b3c-4c5e-9f6d-2e4b8a1c3d57")
+    }
+}
+
+impl Deref for SpriteAnimationPlayer {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for SpriteAnimationPlayer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl NodeTrait for SpriteAnimationPlayer {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let Some(resource) = (*self.sprite_sheet).clone() else {
+            return;
+        };
+        let state = resource.state();
+        let ResourceStateRef::Ok(sprite_sheet) = state.get() else {
+            return;
+        };
+
+        let frame = self.advance(sprite_sheet, context.dt);
+        if frame == self.current_frame {
+            return;
+        }
+        self.current_frame = frame;
+
+        let Some(uv_rect) = frame.and_then(|i| sprite_sheet.frame_uv_rect(i)) else {
+            return;
+        };
+        if let Some(target) = context.nodes.try_borrow_mut(*self.target) {
+            if let Some(rectangle) = target.cast_mut::<Rectangle>() {
+                rectangle.set_uv_rect(uv_rect);
+            } else if let Some(sprite) = target.cast_mut::<Sprite>() {
+                sprite.set_uv_rect(uv_rect);
+            }
+        }
+    }
+}
+
+/// A builder for [`SpriteAnimationPlayer`] node.
+pub struct SpriteAnimationPlayerBuilder {
+    base_builder: BaseBuilder,
+    sprite_sheet: Option<SpriteSheetResource>,
+    target: Handle<Node>,
+    animation: String,
+    speed: f32,
+    playing: bool,
+}
+
+impl SpriteAnimationPlayerBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            sprite_sheet: None,
+            target: Handle::NONE,
+            animation: Default::default(),
+            speed: 1.0,
+            playing: true,
+        }
+    }
+
+    /// Sets desired sprite sheet.
+    pub fn with_sprite_sheet(mut self, sprite_sheet: Option<SpriteSheetResource>) -> Self {
+        self.sprite_sheet = sprite_sheet;
+        self
+    }
+
+    /// Sets desired target node. See [`SpriteAnimationPlayer::set_target`] docs for more info.
+    pub fn with_target(mut self, target: Handle<Node>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets desired animation. See [`SpriteAnimationPlayer::set_animation`] docs for more info.
+    pub fn with_animation(mut self, animation: impl Into<String>) -> Self {
+        self.animation = animation.into();
+        self
+    }
+
+    /// Sets desired playback speed.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets whether the animation should be played or not.
+    pub fn with_playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    /// Creates an instance of [`SpriteAnimationPlayer`] node.
+    pub fn build_node(self) -> Node {
+        Node::new(SpriteAnimationPlayer {
+            base: self.base_builder.build_base(),
+            sprite_sheet: self.sprite_sheet.into(),
+            target: self.target.into(),
+            animation: self.animation.into(),
+            speed: self.speed.into(),
+            playing: self.playing.into(),
+            position: 0,
+            frame_time: 0.0,
+            current_frame: None,
+        })
+    }
+
+    /// Creates an instance of [`SpriteAnimationPlayer`] node and adds it to the given scene graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{algebra::Vector2, math::Rect},
+        resource::spritesheet::{SpriteAnimation, SpriteSheetFrame},
+    };
+
+    fn make_sprite_sheet() -> SpriteSheet {
+        let frame = |x, duration| SpriteSheetFrame {
+            rect: Rect::new(x, 0, 8, 8),
+            duration,
+            ..Default::default()
+        };
+        SpriteSheet {
+            texture_size: Vector2::new(24, 8),
+            frames: vec![frame(0, 0.1), frame(8, 0.2), frame(16, 0.1)],
+            animations: vec![
+                SpriteAnimation {
+                    name: "Loop".to_string(),
+                    frames: vec![0, 1],
+                    looping: true,
+                },
+                SpriteAnimation {
+                    name: "Once".to_string(),
+                    frames: vec![2, 1],
+                    looping: false,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sprite_animation_player_advance() {
+        let sprite_sheet = make_sprite_sheet();
+        let mut player = SpriteAnimationPlayer::default();
+        player.set_animation("Loop".to_string());
+
+        assert_eq!(player.advance(&sprite_sheet, 0.05), Some(0));
+        assert_eq!(player.advance(&sprite_sheet, 0.1), Some(1));
+        // Second frame lasts twice longer.
+        assert_eq!(player.advance(&sprite_sheet, 0.1), Some(1));
+        assert_eq!(player.advance(&sprite_sheet, 0.1), Some(0));
+
+        player.play("Once");
+        assert_eq!(player.advance(&sprite_sheet, 0.0), Some(2));
+        assert_eq!(player.advance(&sprite_sheet, 1.0), Some(1));
+        assert!(!player.is_playing());
+
+        player.set_animation(Default::default());
+        player.set_playing(true);
+        assert_eq!(player.advance(&sprite_sheet, 0.35), Some(2));
+    }
+}
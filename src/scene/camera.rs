@@ -17,8 +17,12 @@ use crate::{
         visitor::{Visit, VisitResult, Visitor},
         TypeUuidProvider,
     },
-    resource::texture::{
-        TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension, TextureWrapMode,
+    resource::{
+        post_effects::PostEffectsResource,
+        texture::{
+            TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension,
+            TextureWrapMode,
+        },
     },
     scene::{
         base::{Base, BaseBuilder},
@@ -331,6 +335,10 @@ pub struct Camera {
     #[reflect(setter = "set_color_grading_enabled")]
     color_grading_enabled: InheritableVariable<bool>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_post_effects")]
+    post_effects: InheritableVariable<Option<PostEffectsResource>>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     view_matrix: Matrix4<f32>,
@@ -683,6 +691,23 @@ impl Camera {
     pub fn exposure(&self) -> Exposure {
         *self.exposure
     }
+
+    /// Sets a post-processing stack of the camera, it overrides the stack of the scene (see
+    /// [`crate::scene::SceneRenderingOptions::post_effects`]). Color grading settings of the
+    /// camera are ignored when there's a stack. See [`PostEffects`] docs for more info.
+    ///
+    /// [`PostEffects`]: crate::resource::post_effects::PostEffects
+    pub fn set_post_effects(
+        &mut self,
+        post_effects: Option<PostEffectsResource>,
+    ) -> Option<PostEffectsResource> {
+        self.post_effects.set_value_and_mark_modified(post_effects)
+    }
+
+    /// Returns current post-processing stack of the camera.
+    pub fn post_effects(&self) -> Option<&PostEffectsResource> {
+        self.post_effects.as_ref()
+    }
 }
 
 impl NodeTrait for Camera {
@@ -729,6 +754,9 @@ pub enum ColorGradingLutCreationError {
 
     /// Texture error.
     Texture(LoadError),
+
+    /// Texture is not loaded yet.
+    NotLoaded,
 }
 
 impl Display for ColorGradingLutCreationError {
@@ -751,6 +779,9 @@ impl Display for ColorGradingLutCreationError {
             ColorGradingLutCreationError::Texture(v) => {
                 write!(f, "Texture load error: {v:?}")
             }
+            ColorGradingLutCreationError::NotLoaded => {
+                write!(f, "Texture is not loaded yet.")
+            }
         }
     }
 }
@@ -801,84 +832,99 @@ impl ColorGradingLut {
     /// color grading.
     pub async fn new(unwrapped_lut: TextureResource) -> Result<Self, ColorGradingLutCreationError> {
         match unwrapped_lut.await {
-            Ok(unwrapped_lut) => {
-                let data = unwrapped_lut.data_ref();
-
-                if data.pixel_kind() != TexturePixelKind::RGBA8
-                    && data.pixel_kind() != TexturePixelKind::RGB8
-                {
-                    return Err(ColorGradingLutCreationError::InvalidPixelFormat(
-                        data.pixel_kind(),
-                    ));
-                }
+            Ok(unwrapped_lut) => Self::try_from_loaded(unwrapped_lut),
+            Err(e) => Err(ColorGradingLutCreationError::Texture(e)),
+        }
+    }
 
-                let bytes = data.data();
+    /// Creates 3D look-up texture from 2D strip, that is already loaded. Unlike [`Self::new`], it
+    /// does not wait until the texture is loaded and returns [`ColorGradingLutCreationError::NotLoaded`]
+    /// error if the texture is still loading. See [`Self::new`] docs for the input texture
+    /// requirements.
+    pub fn try_from_loaded(
+        unwrapped_lut: TextureResource,
+    ) -> Result<Self, ColorGradingLutCreationError> {
+        if unwrapped_lut.is_loading() {
+            return Err(ColorGradingLutCreationError::NotLoaded);
+        }
+        if let ResourceStateRef::LoadError { error, .. } = unwrapped_lut.state().get() {
+            return Err(ColorGradingLutCreationError::Texture(error.clone()));
+        }
 
-                const RGBA8_SIZE: usize = 16 * 16 * 16 * 4;
-                const RGB8_SIZE: usize = 16 * 16 * 16 * 3;
+        let data = unwrapped_lut.data_ref();
 
-                if data.pixel_kind() == TexturePixelKind::RGBA8 {
-                    if bytes.len() != RGBA8_SIZE {
-                        return Err(ColorGradingLutCreationError::NotEnoughData {
-                            required: RGBA8_SIZE,
-                            current: bytes.len(),
-                        });
-                    }
-                } else if bytes.len() != RGB8_SIZE {
-                    return Err(ColorGradingLutCreationError::NotEnoughData {
-                        required: RGB8_SIZE,
-                        current: bytes.len(),
-                    });
-                }
+        if data.pixel_kind() != TexturePixelKind::RGBA8
+            && data.pixel_kind() != TexturePixelKind::RGB8
+        {
+            return Err(ColorGradingLutCreationError::InvalidPixelFormat(
+                data.pixel_kind(),
+            ));
+        }
 
-                let pixel_size = if data.pixel_kind() == TexturePixelKind::RGBA8 {
-                    4
-                } else {
-                    3
-                };
+        let bytes = data.data();
 
-                let mut lut_bytes = Vec::with_capacity(16 * 16 * 16 * 3);
+        const RGBA8_SIZE: usize = 16 * 16 * 16 * 4;
+        const RGB8_SIZE: usize = 16 * 16 * 16 * 3;
 
-                for z in 0..16 {
-                    for y in 0..16 {
-                        for x in 0..16 {
-                            let pixel_index = z * 16 + y * 16 * 16 + x;
-                            let pixel_byte_pos = pixel_index * pixel_size;
+        if data.pixel_kind() == TexturePixelKind::RGBA8 {
+            if bytes.len() != RGBA8_SIZE {
+                return Err(ColorGradingLutCreationError::NotEnoughData {
+                    required: RGBA8_SIZE,
+                    current: bytes.len(),
+                });
+            }
+        } else if bytes.len() != RGB8_SIZE {
+            return Err(ColorGradingLutCreationError::NotEnoughData {
+                required: RGB8_SIZE,
+                current: bytes.len(),
+            });
+        }
 
-                            lut_bytes.push(bytes[pixel_byte_pos]); // R
-                            lut_bytes.push(bytes[pixel_byte_pos + 1]); // G
-                            lut_bytes.push(bytes[pixel_byte_pos + 2]); // B
-                        }
-                    }
+        let pixel_size = if data.pixel_kind() == TexturePixelKind::RGBA8 {
+            4
+        } else {
+            3
+        };
+
+        let mut lut_bytes = Vec::with_capacity(16 * 16 * 16 * 3);
+
+        for z in 0..16 {
+            for y in 0..16 {
+                for x in 0..16 {
+                    let pixel_index = z * 16 + y * 16 * 16 + x;
+                    let pixel_byte_pos = pixel_index * pixel_size;
+
+                    lut_bytes.push(bytes[pixel_byte_pos]); // R
+                    lut_bytes.push(bytes[pixel_byte_pos + 1]); // G
+                    lut_bytes.push(bytes[pixel_byte_pos + 2]); // B
                 }
+            }
+        }
 
-                let lut = TextureResource::from_bytes(
-                    TextureKind::Volume {
-                        width: 16,
-                        height: 16,
-                        depth: 16,
-                    },
-                    TexturePixelKind::RGB8,
-                    lut_bytes,
-                    false,
-                )
-                .unwrap();
+        let lut = TextureResource::from_bytes(
+            TextureKind::Volume {
+                width: 16,
+                height: 16,
+                depth: 16,
+            },
+            TexturePixelKind::RGB8,
+            lut_bytes,
+            false,
+        )
+        .unwrap();
 
-                let mut lut_ref = lut.data_ref();
+        let mut lut_ref = lut.data_ref();
 
-                lut_ref.set_s_wrap_mode(TextureWrapMode::ClampToEdge);
-                lut_ref.set_t_wrap_mode(TextureWrapMode::ClampToEdge);
+        lut_ref.set_s_wrap_mode(TextureWrapMode::ClampToEdge);
+        lut_ref.set_t_wrap_mode(TextureWrapMode::ClampToEdge);
 
-                drop(lut_ref);
-                drop(data);
+        drop(lut_ref);
+        drop(data);
 
-                Ok(Self {
-                    lut: Some(lut),
-                    unwrapped_lut: Some(unwrapped_lut),
-                })
-            }
-            Err(e) => Err(ColorGradingLutCreationError::Texture(e)),
-        }
+        Ok(Self {
+            lut: Some(lut),
+            unwrapped_lut: Some(unwrapped_lut),
+        })
     }
 
     /// Returns color grading unwrapped look-up table. This is initial texture that was
@@ -999,6 +1045,7 @@ pub struct CameraBuilder {
     exposure: Exposure,
     color_grading_lut: Option<ColorGradingLut>,
     color_grading_enabled: bool,
+    post_effects: Option<PostEffectsResource>,
     projection: Projection,
 }
 
@@ -1017,6 +1064,7 @@ impl CameraBuilder {
             exposure: Exposure::Manual(std::f32::consts::E),
             color_grading_lut: None,
             color_grading_enabled: false,
+            post_effects: None,
             projection: Projection::default(),
         }
     }
@@ -1081,6 +1129,12 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets desired post-processing stack, see [`Camera::set_post_effects`] for more info.
+    pub fn with_post_effects(mut self, post_effects: PostEffectsResource) -> Self {
+        self.post_effects = Some(post_effects);
+        self
+    }
+
     /// Sets desired exposure options.
     pub fn with_exposure(mut self, exposure: Exposure) -> Self {
         self.exposure = exposure;
@@ -1113,6 +1167,7 @@ impl CameraBuilder {
             exposure: self.exposure.into(),
             color_grading_lut: self.color_grading_lut.into(),
             color_grading_enabled: self.color_grading_enabled.into(),
+            post_effects: self.post_effects.into(),
         }
     }
 
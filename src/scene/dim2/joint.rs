@@ -149,6 +149,10 @@ pub struct Joint {
     #[reflect(setter = "set_contacts_enabled")]
     pub(crate) contacts_enabled: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_auto_rebinding")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) auto_rebind: InheritableVariable<bool>,
+
     #[visit(optional)]
     #[reflect(hidden)]
     pub(crate) local_frames: RefCell<Option<JointLocalFrames>>,
@@ -167,6 +171,7 @@ impl Default for Joint {
             body2: Default::default(),
             local_frames: Default::default(),
             contacts_enabled: InheritableVariable::new_modified(true),
+            auto_rebind: true.into(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
@@ -196,6 +201,7 @@ impl Clone for Joint {
             body2: self.body2.clone(),
             local_frames: self.local_frames.clone(),
             contacts_enabled: self.contacts_enabled.clone(),
+            auto_rebind: self.auto_rebind.clone(),
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
     }
@@ -255,6 +261,17 @@ impl Joint {
     pub fn is_contacts_enabled(&self) -> bool {
         *self.contacts_enabled
     }
+
+    /// Sets whether the joint should automatically rebind two rigid bodies if the joint has changed its
+    /// global position.
+    pub fn set_auto_rebinding(&mut self, enabled: bool) -> bool {
+        self.auto_rebind.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns true if automatic rebinding of the joint is enabled or not.
+    pub fn is_auto_rebinding_enabled(&self) -> bool {
+        *self.auto_rebind
+    }
 }
 
 impl NodeTrait for Joint {
@@ -289,7 +306,7 @@ impl NodeTrait for Joint {
     }
 
     fn sync_transform(&self, new_global_transform: &Matrix4<f32>, _context: &mut SyncContext) {
-        if !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
+        if *self.auto_rebind && !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
             self.local_frames.borrow_mut().take();
         }
     }
@@ -330,6 +347,7 @@ pub struct JointBuilder {
     body1: Handle<Node>,
     body2: Handle<Node>,
     contacts_enabled: bool,
+    auto_rebind: bool,
 }
 
 impl JointBuilder {
@@ -341,6 +359,7 @@ impl JointBuilder {
             body1: Default::default(),
             body2: Default::default(),
             contacts_enabled: true,
+            auto_rebind: true,
         }
     }
 
@@ -370,6 +389,13 @@ impl JointBuilder {
         self
     }
 
+    /// Sets whether the joint should automatically rebind two rigid bodies if the joint has changed its
+    /// global position.
+    pub fn with_auto_rebinding_enabled(mut self, auto_rebind: bool) -> Self {
+        self.auto_rebind = auto_rebind;
+        self
+    }
+
     /// Creates new Joint node, but does not add it to the graph.
     pub fn build_joint(self) -> Joint {
         Joint {
@@ -379,6 +405,7 @@ impl JointBuilder {
             body2: self.body2.into(),
             local_frames: Default::default(),
             contacts_enabled: self.contacts_enabled.into(),
+            auto_rebind: self.auto_rebind.into(),
             native: Cell::new(ImpulseJointHandle::invalid()),
         }
     }
@@ -393,3 +420,73 @@ impl JointBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            dim2::{joint::JointBuilder, rigidbody::RigidBodyBuilder},
+            graph::Graph,
+            rigidbody::RigidBodyType,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn body1_frame_position(auto_rebind: bool) -> Vector2<f32> {
+        let mut graph = Graph::new();
+        let mut create_rigid_body = |x| {
+            RigidBodyBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, 0.0, 0.0))
+                        .build(),
+                ),
+            )
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph)
+        };
+        let body1 = create_rigid_body(0.0);
+        let body2 = create_rigid_body(2.0);
+        let joint = JointBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_body1(body1)
+        .with_body2(body2)
+        .with_auto_rebinding_enabled(auto_rebind)
+        .build(&mut graph);
+
+        let frame_position = |graph: &Graph| {
+            graph[joint]
+                .as_joint2d()
+                .local_frames
+                .borrow()
+                .as_ref()
+                .unwrap()
+                .body1
+                .position
+        };
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        assert_eq!(frame_position(&graph), Vector2::new(1.0, 0.0));
+
+        graph[joint]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.5, 0.0, 0.0));
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        frame_position(&graph)
+    }
+
+    #[test]
+    fn test_joint_2d_auto_rebind() {
+        // Moved joint binds the bodies at its new position.
+        assert_eq!(body1_frame_position(true), Vector2::new(0.5, 0.0));
+        assert_eq!(body1_frame_position(false), Vector2::new(1.0, 0.0));
+    }
+}
@@ -1,5 +1,10 @@
 //! The module contains 2D scene nodes and physics. Despite the naming, scene nodes are still 3D
 //! but physics simulation is in true 2D.
+//!
+//! 2D physics nodes mirror 3D ones: rigid bodies, colliders, joints (with auto rebinding) and
+//! debug drawing (see [`physics::PhysicsWorld::draw`]). Ragdolls (see
+//! [`crate::scene::ragdoll::Ragdoll`]) accept 2D rigid bodies as well, and the editor ragdoll
+//! wizard could generate them.
 
 pub mod collider;
 pub mod joint;
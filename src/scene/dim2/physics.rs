@@ -24,12 +24,16 @@ use crate::{
         debug::SceneDrawingContext,
         dim2::{self, collider::ColliderShape, joint::JointParams, rigidbody::ApplyAction},
         graph::{
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEvent, CollisionEventKind, FeatureId, IntegrationParameters,
+                PhysicsPerformanceStatistics,
+            },
             NodePool,
         },
         node::{Node, NodeTrait},
     },
 };
+use fxhash::FxHashMap;
 use fyrox_core::variable::InheritableVariable;
 use rapier2d::{
     dynamics::{
@@ -39,10 +43,13 @@ use rapier2d::{
         RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, CollisionEventFlags,
+        Cuboid, InteractionGroups, NarrowPhase, Ray, SharedShape,
+    },
+    pipeline::{
+        ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter,
+        QueryPipeline,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
 };
 use std::{
     cell::RefCell,
@@ -334,6 +341,61 @@ fn isometry2_to_mat4(isometry: &Isometry2<f32>) -> Matrix4<f32> {
     .to_homogeneous()
 }
 
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<CollisionEvent>>,
+    // Colliders that were removed since the last step, rapier generates events for them on the next
+    // step (see 3D physics world for more info).
+    removed: Mutex<FxHashMap<ColliderHandle, Handle<Node>>>,
+}
+
+impl CollisionEventCollector {
+    fn node_of(&self, colliders: &ColliderSet, handle: ColliderHandle) -> Handle<Node> {
+        colliders
+            .get(handle)
+            .map(|c| Handle::decode_from_u128(c.user_data))
+            .or_else(|| self.removed.lock().get(&handle).cloned())
+            .unwrap_or_default()
+    }
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier2d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier2d::geometry::ContactPair>,
+    ) {
+        let (kind, collider1, collider2, flags) = match event {
+            rapier2d::geometry::CollisionEvent::Started(a, b, flags) => {
+                (CollisionEventKind::Started, a, b, flags)
+            }
+            rapier2d::geometry::CollisionEvent::Stopped(a, b, flags) => {
+                (CollisionEventKind::Stopped, a, b, flags)
+            }
+        };
+        let event = CollisionEvent {
+            kind,
+            collider1: self.node_of(colliders, collider1),
+            collider2: self.node_of(colliders, collider2),
+            sensor: flags.contains(CollisionEventFlags::SENSOR),
+            removed: flags.contains(CollisionEventFlags::REMOVED),
+        };
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier2d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -393,7 +455,14 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_collector: CollisionEventCollector,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events_enabled: bool,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -452,7 +521,9 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_collector: Default::default(),
+            collision_events: Default::default(),
+            collision_events_enabled: false,
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -505,10 +576,16 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_collector,
             );
+
+            self.event_collector.removed.lock().clear();
         }
 
+        self.collision_events.clear();
+        self.collision_events
+            .append(&mut self.event_collector.events.lock());
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
@@ -518,6 +595,14 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        if let Some(body) = self.bodies.get(handle) {
+            let mut removed = self.event_collector.removed.lock();
+            for &collider in body.colliders() {
+                if let Some(native) = self.colliders.get(collider) {
+                    removed.insert(collider, Handle::decode_from_u128(native.user_data));
+                }
+            }
+        }
         self.bodies.remove(
             handle,
             &mut self.islands,
@@ -540,9 +625,50 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
-        self.colliders
+        match self
+            .colliders
             .remove(handle, &mut self.islands, &mut self.bodies, false)
-            .is_some()
+        {
+            Some(collider) => {
+                self.event_collector
+                    .removed
+                    .lock()
+                    .insert(handle, Handle::decode_from_u128(collider.user_data));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns collision events of the last simulation step. See
+    /// [`crate::scene::graph::physics::PhysicsWorld::collision_events`] for more info.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Enables or disables generation of collision events. See
+    /// [`crate::scene::graph::physics::PhysicsWorld::set_collision_events_enabled`] for more info.
+    pub fn set_collision_events_enabled(&mut self, enabled: bool) {
+        if self.collision_events_enabled != enabled {
+            self.collision_events_enabled = enabled;
+            let active_events = self.active_events();
+            for (_, collider) in self.colliders.iter_mut() {
+                collider.set_active_events(active_events);
+            }
+        }
+    }
+
+    /// Returns `true` if generation of collision events is enabled.
+    pub fn is_collision_events_enabled(&self) -> bool {
+        self.collision_events_enabled
+    }
+
+    fn active_events(&self) -> ActiveEvents {
+        if self.collision_events_enabled {
+            ActiveEvents::COLLISION_EVENTS
+        } else {
+            ActiveEvents::empty()
+        }
     }
 
     pub(crate) fn add_joint(
@@ -927,7 +1053,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(self.active_events());
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -992,10 +1119,10 @@ impl PhysicsWorld {
                 if let (Some(body1), Some(body2)) = (
                     nodes
                         .try_borrow(joint.body1())
-                        .and_then(|n| n.cast::<scene::rigidbody::RigidBody>()),
+                        .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>()),
                     nodes
                         .try_borrow(joint.body2())
-                        .and_then(|n| n.cast::<scene::rigidbody::RigidBody>()),
+                        .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>()),
                 ) {
                     let (local_frame1, local_frame2) = calculate_local_frames(joint, body1, body2);
                     native.data =
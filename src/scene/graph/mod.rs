@@ -30,7 +30,7 @@ use crate::{
         log::{Log, MessageKind},
         math::aabb::AxisAlignedBoundingBox,
         math::Matrix4Ext,
-        pool::{Handle, MultiBorrowContext, Pool, Ticket},
+        pool::{FreeInfo, Handle, MultiBorrowContext, Pool, Ticket},
         reflect::prelude::*,
         sstorage::ImmutableString,
         variable::try_inherit_properties,
@@ -70,6 +70,7 @@ use std::{
     any::Any,
     fmt::Debug,
     ops::{Index, IndexMut},
+    panic::Location,
     sync::mpsc::{channel, Receiver, Sender},
     time::Duration,
 };
@@ -125,6 +126,9 @@ pub struct Graph {
     #[reflect(hidden)]
     stack: Vec<Handle<Node>>,
 
+    #[reflect(hidden)]
+    deferred_removals: Vec<(Handle<Node>, &'static Location<'static>)>,
+
     /// Backing physics "world". It is responsible for the physics simulation.
     pub physics: PhysicsWorld,
 
@@ -163,6 +167,7 @@ impl Default for Graph {
             root: Handle::NONE,
             pool: Pool::new(),
             stack: Vec::new(),
+            deferred_removals: Default::default(),
             sound_context: Default::default(),
             performance_statistics: Default::default(),
             event_broadcaster: Default::default(),
@@ -279,6 +284,7 @@ impl Graph {
         Self {
             physics: Default::default(),
             stack: Vec::new(),
+            deferred_removals: Default::default(),
             root,
             pool,
             physics2d: Default::default(),
@@ -494,7 +500,64 @@ impl Graph {
     /// Destroys the node and its children recursively. Scripts of the destroyed nodes will be removed in the next
     /// update tick.
     #[inline]
+    #[track_caller]
     pub fn remove_node(&mut self, node_handle: Handle<Node>) {
+        self.remove_node_internal(node_handle, Location::caller());
+    }
+
+    /// Requests the node and its children to be destroyed at the beginning of the next [`Graph::update`] call
+    /// (or the next [`Graph::remove_deferred_nodes`] call). Unlike [`Graph::remove_node`], every handle to the
+    /// node stays valid until then, which makes it safe to call while other code still works with the node
+    /// (for example, when a script destroys a node it has collided with). Removal requests of nodes, that were
+    /// destroyed already (for example, as descendants of other removed nodes), are ignored.
+    ///
+    /// If handle diagnostics is enabled (see [`Graph::set_handle_diagnostics_enabled`]), the place of this call is
+    /// reported as the place where the node was destroyed.
+    #[inline]
+    #[track_caller]
+    pub fn remove_node_deferred(&mut self, node_handle: Handle<Node>) {
+        self.deferred_removals
+            .push((node_handle, Location::caller()));
+    }
+
+    /// Destroys every node, that was requested to be destroyed using [`Graph::remove_node_deferred`].
+    pub fn remove_deferred_nodes(&mut self) {
+        for (node_handle, location) in std::mem::take(&mut self.deferred_removals) {
+            if self.pool.is_valid_handle(node_handle) {
+                self.remove_node_internal(node_handle, location);
+            }
+        }
+    }
+
+    /// Enables or disables the debug mode, that detects use of dangling handles to the nodes of the graph (handles
+    /// of destroyed nodes). When enabled, the graph remembers where every node was destroyed and reports it when a
+    /// dangling handle causes a panic: panic messages of indexing (`graph[handle]`) contain the info. Non-panicking
+    /// methods (such as [`Graph::try_get`]) just return `None`, use [`Graph::removal_info`] to find out where the node
+    /// was destroyed. See [`Pool::set_handle_diagnostics_enabled`] for more info.
+    #[inline]
+    pub fn set_handle_diagnostics_enabled(&mut self, enabled: bool) {
+        self.pool.set_handle_diagnostics_enabled(enabled);
+    }
+
+    /// Returns `true` if the handle diagnostics is enabled, `false` - otherwise. See
+    /// [`Graph::set_handle_diagnostics_enabled`] for more info.
+    #[inline]
+    pub fn is_handle_diagnostics_enabled(&self) -> bool {
+        self.pool.is_handle_diagnostics_enabled()
+    }
+
+    /// Returns information about the place, where a node at the given handle was destroyed. It is available only
+    /// when the handle diagnostics is enabled (see [`Graph::set_handle_diagnostics_enabled`]).
+    #[inline]
+    pub fn removal_info(&self, node_handle: Handle<Node>) -> Option<&FreeInfo> {
+        self.pool.free_info(node_handle)
+    }
+
+    fn remove_node_internal(
+        &mut self,
+        node_handle: Handle<Node>,
+        location: &'static Location<'static>,
+    ) {
         self.unlink_internal(node_handle);
 
         self.stack.clear();
@@ -505,7 +568,7 @@ impl Graph {
             }
 
             // Remove associated entities.
-            let mut node = self.pool.free(handle);
+            let mut node = self.pool.free_with_location(handle, location);
             node.on_removed_from_graph(self);
 
             self.event_broadcaster
@@ -988,7 +1051,7 @@ impl Graph {
     //
     // To do so, we at first, build node handle mapping (original handle -> instance handle) starting from
     // instance root. Then we must find all inheritable properties and try to remap them to instance handles.
-    fn remap_handles(&mut self, instances: &[(Handle<Node>, ModelResource)]) {
+    pub(crate) fn remap_handles(&mut self, instances: &[(Handle<Node>, ModelResource)]) {
         for (instance_root, resource) in instances {
             // Prepare old -> new handle mapping first by walking over the graph
             // starting from instance root.
@@ -1550,7 +1613,10 @@ impl Graph {
     /// Update switches allows you to disable update for parts of the update pipeline, it could be useful for editors
     /// where you need to have preview mode to update only specific set of nodes, etc.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
-        self.sound_context.state().pause(switches.paused);
+        self.remove_deferred_nodes();
+
+        let sound_paused = switches.paused || self.sound_context.audio_paused;
+        self.sound_context.state().pause(sound_paused);
 
         if switches.paused {
             return;
@@ -2100,7 +2166,7 @@ mod test {
             visitor::Visitor,
         },
         engine::{self, SerializationContext},
-        resource::model::{Model, ModelResourceExtension},
+        resource::model::{Model, ModelResource, ModelResourceExtension, NodeMapping},
         scene::{
             base::BaseBuilder,
             graph::Graph,
@@ -2132,6 +2198,34 @@ mod test {
         assert_eq!(graph.pool.alive_count(), 4);
     }
 
+    #[test]
+    fn graph_deferred_node_removal() {
+        let mut graph = Graph::new();
+        graph.set_handle_diagnostics_enabled(true);
+
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let parent =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut graph);
+
+        let line = line!() + 1;
+        graph.remove_node_deferred(parent);
+        // Descendant is removed along with its parent, so the request must be ignored.
+        graph.remove_node_deferred(child);
+        assert!(graph.is_valid_handle(parent));
+        assert!(graph.is_valid_handle(child));
+
+        graph.remove_deferred_nodes();
+        assert!(!graph.is_valid_handle(parent));
+        assert!(!graph.is_valid_handle(child));
+        assert_eq!(graph.pool.alive_count(), 1);
+
+        // Deferred removal is reported at the place of the request.
+        let info = graph.removal_info(parent).unwrap();
+        assert_eq!(info.location.file(), file!());
+        assert_eq!(info.location.line(), line);
+        assert!(graph.removal_info(child).is_some());
+    }
+
     #[test]
     fn test_graph_search() {
         let mut graph = Graph::new();
@@ -2282,6 +2376,32 @@ mod test {
         resource_manager
     }
 
+    #[test]
+    fn test_instantiate_sub() {
+        let model = ModelResource::new_ok(Model {
+            path: Default::default(),
+            mapping: NodeMapping::UseNames,
+            scene: create_scene(),
+            dirty: false,
+        });
+
+        let mut graph = Graph::new();
+        assert!(model.instantiate_sub(&mut graph, "Unknown").is_none());
+
+        let instance = model.instantiate_sub(&mut graph, "MeshPivot").unwrap();
+        assert_eq!(graph[instance].parent(), graph.get_root());
+        assert!(graph[instance].is_resource_instance_root());
+        assert_eq!(graph[instance].resource(), Some(model.clone()));
+        assert!(graph.find_by_name(instance, "Mesh").is_some());
+        assert!(graph.find_by_name_from_root("Pivot").is_none());
+
+        let mesh = model.instantiate_sub(&mut graph, "Mesh").unwrap();
+        assert_eq!(
+            **graph[mesh].local_transform().position(),
+            Vector3::new(3.0, 2.0, 1.0)
+        );
+    }
+
     #[test]
     fn test_restore_integrity() {
         if !Path::new("test_output").exists() {
@@ -19,9 +19,10 @@ use crate::{
     },
     scene::{
         self,
-        collider::{self, ColliderShape, GeometrySource},
+        collider::{self, BitMask, ColliderShape, GeometrySource},
         debug::SceneDrawingContext,
-        graph::{isometric_global_transform, NodePool},
+        dim2,
+        graph::{isometric_global_transform, Graph, NodePool},
         joint::{JointLocalFrames, JointParams},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
@@ -33,6 +34,7 @@ use crate::{
     },
     utils::raw_mesh::{RawMeshBuilder, RawVertex},
 };
+use fxhash::FxHashMap;
 use fyrox_core::algebra::Translation;
 use rapier3d::{
     dynamics::{
@@ -41,10 +43,13 @@ use rapier3d::{
         RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, CollisionEventFlags,
+        Cuboid, InteractionGroups, NarrowPhase, Ray, SharedShape,
+    },
+    pipeline::{
+        ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter,
+        QueryPipeline,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
     prelude::JointAxis,
 };
 use std::{
@@ -882,6 +887,173 @@ impl Default for IntegrationParameters {
     }
 }
 
+/// Kind of a collision event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Two colliders have started touching (or intersecting, if one of them is a sensor).
+    Started,
+    /// Two colliders have stopped touching (or intersecting, if one of them is a sensor).
+    Stopped,
+}
+
+/// A collision event between two colliders. Collision events are generated by physics worlds
+/// (both 3D and 2D) every simulation step, see [`PhysicsWorld::collision_events`]. Scripts could
+/// also receive the events as script messages, see
+/// [`crate::engine::ScriptMessageDispatcher::subscribe_to_collision_events`] for more info.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CollisionEvent {
+    /// Kind of the event.
+    pub kind: CollisionEventKind,
+    /// The first collider involved in the event.
+    pub collider1: Handle<Node>,
+    /// The second collider involved in the event.
+    pub collider2: Handle<Node>,
+    /// `true` if at least one of the colliders is a sensor, which means that the event is a trigger
+    /// event.
+    pub sensor: bool,
+    /// `true` if the event is generated because at least one of the colliders was removed. Handles
+    /// of removed colliders are not valid anymore.
+    pub removed: bool,
+}
+
+impl CollisionEvent {
+    /// Returns `true` if the given collider is involved in the event.
+    pub fn involves(&self, collider: Handle<Node>) -> bool {
+        self.collider1 == collider || self.collider2 == collider
+    }
+
+    /// Returns the other collider of the event, if the given collider is involved in the event.
+    pub fn other(&self, collider: Handle<Node>) -> Option<Handle<Node>> {
+        if self.collider1 == collider {
+            Some(self.collider2)
+        } else if self.collider2 == collider {
+            Some(self.collider1)
+        } else {
+            None
+        }
+    }
+}
+
+/// A set of conditions, that could be used to select collision events of interest. Every condition
+/// is optional, an empty filter passes every event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CollisionEventFilter {
+    /// Passes only events of the given kind.
+    pub kind: Option<CollisionEventKind>,
+    /// Passes only trigger events (`Some(true)`) or only contact events (`Some(false)`).
+    pub sensor: Option<bool>,
+    /// Passes only events, where the other collider (the one, that does not belong to the receiver)
+    /// or its parent node (that usually is a rigid body) has the given tag.
+    pub tag: Option<String>,
+    /// Passes only events, where at least one of the colliders is a member of at least one of the
+    /// given collision groups (see [`InteractionGroups::memberships`](collider::InteractionGroups)).
+    pub layers: Option<BitMask>,
+}
+
+impl CollisionEventFilter {
+    /// Returns `true` if the given event passes the filter. `collider` is the collider of the
+    /// receiver of the event, the tag is checked on the other collider of the event. The graph is
+    /// used to fetch tags and collision groups of the colliders, removed colliders pass only the
+    /// kind and sensor checks.
+    pub fn matches(&self, event: &CollisionEvent, collider: Handle<Node>, graph: &Graph) -> bool {
+        if self.kind.is_some_and(|kind| kind != event.kind)
+            || self.sensor.is_some_and(|sensor| sensor != event.sensor)
+        {
+            return false;
+        }
+
+        if let Some(tag) = self.tag.as_ref() {
+            let has_tag =
+                |handle: Handle<Node>| graph.try_get(handle).is_some_and(|node| node.tag() == tag);
+            let other_has_tag = event.other(collider).is_some_and(|other| {
+                has_tag(other)
+                    || graph
+                        .try_get(other)
+                        .is_some_and(|node| has_tag(node.parent()))
+            });
+            if !other_has_tag {
+                return false;
+            }
+        }
+
+        let colliders = [event.collider1, event.collider2];
+
+        if let Some(layers) = self.layers {
+            if !colliders.iter().any(|&collider| {
+                graph.try_get(collider).is_some_and(|node| {
+                    let memberships = if let Some(collider) = node.cast::<collider::Collider>() {
+                        collider.collision_groups().memberships
+                    } else if let Some(collider) = node.cast::<dim2::collider::Collider>() {
+                        collider.collision_groups().memberships
+                    } else {
+                        return false;
+                    };
+                    memberships.0 & layers.0 != 0
+                })
+            }) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Default)]
+struct CollisionEventCollector {
+    events: Mutex<Vec<CollisionEvent>>,
+    // Colliders that were removed since the last step. Rapier generates events for them on the next
+    // step, when the colliders are already gone, so their nodes must be remembered beforehand.
+    removed: Mutex<FxHashMap<ColliderHandle, Handle<Node>>>,
+}
+
+impl CollisionEventCollector {
+    fn node_of(&self, colliders: &ColliderSet, handle: ColliderHandle) -> Handle<Node> {
+        colliders
+            .get(handle)
+            .map(|c| Handle::decode_from_u128(c.user_data))
+            .or_else(|| self.removed.lock().get(&handle).cloned())
+            .unwrap_or_default()
+    }
+}
+
+impl EventHandler for CollisionEventCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier3d::geometry::ContactPair>,
+    ) {
+        let (kind, collider1, collider2, flags) = match event {
+            rapier3d::geometry::CollisionEvent::Started(a, b, flags) => {
+                (CollisionEventKind::Started, a, b, flags)
+            }
+            rapier3d::geometry::CollisionEvent::Stopped(a, b, flags) => {
+                (CollisionEventKind::Stopped, a, b, flags)
+            }
+        };
+        let event = CollisionEvent {
+            kind,
+            collider1: self.node_of(colliders, collider1),
+            collider2: self.node_of(colliders, collider2),
+            sensor: flags.contains(CollisionEventFlags::SENSOR),
+            removed: flags.contains(CollisionEventFlags::REMOVED),
+        };
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier3d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -941,7 +1113,14 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_collector: CollisionEventCollector,
+    // Collision events of the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events_enabled: bool,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -1001,7 +1180,9 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_collector: Default::default(),
+            collision_events: Default::default(),
+            collision_events_enabled: false,
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -1054,10 +1235,16 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_collector,
             );
+
+            self.event_collector.removed.lock().clear();
         }
 
+        self.collision_events.clear();
+        self.collision_events
+            .append(&mut self.event_collector.events.lock());
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
@@ -1067,6 +1254,14 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        if let Some(body) = self.bodies.get(handle) {
+            let mut removed = self.event_collector.removed.lock();
+            for &collider in body.colliders() {
+                if let Some(native) = self.colliders.get(collider) {
+                    removed.insert(collider, Handle::decode_from_u128(native.user_data));
+                }
+            }
+        }
         self.bodies.remove(
             handle,
             &mut self.islands,
@@ -1089,9 +1284,55 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
-        self.colliders
+        match self
+            .colliders
             .remove(handle, &mut self.islands, &mut self.bodies, false)
-            .is_some()
+        {
+            Some(collider) => {
+                self.event_collector
+                    .removed
+                    .lock()
+                    .insert(handle, Handle::decode_from_u128(collider.user_data));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns collision events of the last simulation step. A collider must be attached to a rigid
+    /// body to generate the events. The events are generated only if they're enabled, see
+    /// [`Self::set_collision_events_enabled`]. Use [`CollisionEventFilter`] to select events of
+    /// interest.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Enables or disables generation of collision events (see [`Self::collision_events`]). The
+    /// events are disabled by default, because they have a cost for every pair of colliders in
+    /// contact. They're enabled automatically, when a script subscribes to the events (see
+    /// [`crate::engine::ScriptMessageDispatcher::subscribe_to_collision_events`]).
+    pub fn set_collision_events_enabled(&mut self, enabled: bool) {
+        if self.collision_events_enabled != enabled {
+            self.collision_events_enabled = enabled;
+            let active_events = self.active_events();
+            for (_, collider) in self.colliders.iter_mut() {
+                collider.set_active_events(active_events);
+            }
+        }
+    }
+
+    /// Returns `true` if generation of collision events is enabled, see
+    /// [`Self::set_collision_events_enabled`].
+    pub fn is_collision_events_enabled(&self) -> bool {
+        self.collision_events_enabled
+    }
+
+    fn active_events(&self) -> ActiveEvents {
+        if self.collision_events_enabled {
+            ActiveEvents::COLLISION_EVENTS
+        } else {
+            ActiveEvents::empty()
+        }
     }
 
     pub(super) fn add_joint(
@@ -1509,7 +1750,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(self.active_events());
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
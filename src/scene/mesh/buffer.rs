@@ -625,6 +625,30 @@ impl<'a> VertexBufferRefMut<'a> {
         self.vertex_buffer.vertex_count += 1;
     }
 
+    /// Removes every vertex, for which the given predicate returns `false`. The predicate receives
+    /// an index of a vertex. Order of the remaining vertices is preserved, but their indices are
+    /// changed, so the triangles that use the buffer must be remapped.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let vertex_size = self.vertex_buffer.vertex_size as usize;
+        let mut kept = 0;
+        for n in 0..self.vertex_buffer.vertex_count as usize {
+            if predicate(n) {
+                if kept != n {
+                    self.vertex_buffer
+                        .data
+                        .as_slice_mut()
+                        .copy_within(n * vertex_size..(n + 1) * vertex_size, kept * vertex_size);
+                }
+                kept += 1;
+            }
+        }
+        self.vertex_buffer.data.drain(kept * vertex_size..);
+        self.vertex_buffer.vertex_count = kept as u32;
+    }
+
     /// Adds new attribute at the end of layout, reorganizes internal data storage to be
     /// able to contain new attribute. Default value of the new attribute in the buffer
     /// becomes `fill_value`. Graphically this could be represented like so:
@@ -1618,6 +1642,17 @@ mod test {
         assert_eq!(buffer.vertex_count(), 2);
     }
 
+    #[test]
+    fn test_retain_vertices() {
+        let mut buffer = create_test_buffer();
+
+        buffer.modify().retain(|n| n != 1);
+
+        assert_eq!(buffer.vertex_count(), 2);
+        test_view_original_equal(buffer.get(0).unwrap(), &VERTICES[0]);
+        test_view_original_equal(buffer.get(1).unwrap(), &VERTICES[2]);
+    }
+
     #[test]
     fn test_add_attribute() {
         let mut buffer = create_test_buffer();
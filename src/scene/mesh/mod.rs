@@ -137,6 +137,10 @@ pub struct Mesh {
     #[visit(optional)]
     blend_shapes: InheritableVariable<Vec<BlendShape>>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_instancing_allowed")]
+    allow_instancing: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     local_bounding_box: Cell<AxisAlignedBoundingBox>,
@@ -161,6 +165,7 @@ impl Default for Mesh {
             render_path: InheritableVariable::new_modified(RenderPath::Deferred),
             decal_layer_index: InheritableVariable::new_modified(0),
             blend_shapes: Default::default(),
+            allow_instancing: InheritableVariable::new_modified(true),
         }
     }
 }
@@ -309,6 +314,19 @@ impl Mesh {
     pub fn decal_layer_index(&self) -> u8 {
         *self.decal_layer_index
     }
+
+    /// Allows or disallows the renderer to merge surfaces of the mesh with surfaces of other meshes
+    /// that share the same surface data and material, and draw them in a single instanced draw call.
+    /// Instancing is allowed by default, disable it if a shader of the mesh' material relies on
+    /// per-draw uniforms (for example, `fyrox_worldMatrix` in a fragment shader).
+    pub fn set_instancing_allowed(&mut self, allow: bool) -> bool {
+        self.allow_instancing.set_value_and_mark_modified(allow)
+    }
+
+    /// Returns `true` if the mesh could be drawn using hardware instancing, `false` - otherwise.
+    pub fn is_instancing_allowed(&self) -> bool {
+        *self.allow_instancing
+    }
 }
 
 impl NodeTrait for Mesh {
@@ -420,6 +438,7 @@ impl NodeTrait for Mesh {
                         index,
                     ),
                     node_handle: self.self_handle,
+                    allow_instancing: *self.allow_instancing,
                 },
             );
         }
@@ -486,6 +505,7 @@ pub struct MeshBuilder {
     render_path: RenderPath,
     decal_layer_index: u8,
     blend_shapes: Vec<BlendShape>,
+    allow_instancing: bool,
 }
 
 impl MeshBuilder {
@@ -497,6 +517,7 @@ impl MeshBuilder {
             render_path: RenderPath::Deferred,
             decal_layer_index: 0,
             blend_shapes: Default::default(),
+            allow_instancing: true,
         }
     }
 
@@ -526,6 +547,13 @@ impl MeshBuilder {
         self
     }
 
+    /// Sets whether the mesh could be drawn using hardware instancing or not. See
+    /// [`Mesh::set_instancing_allowed`] for more info.
+    pub fn with_instancing_allowed(mut self, allow: bool) -> Self {
+        self.allow_instancing = allow;
+        self
+    }
+
     /// Creates new mesh.
     pub fn build_node(self) -> Node {
         Node::new(Mesh {
@@ -537,6 +565,7 @@ impl MeshBuilder {
             render_path: self.render_path.into(),
             decal_layer_index: self.decal_layer_index.into(),
             world_bounding_box: Default::default(),
+            allow_instancing: self.allow_instancing.into(),
         })
     }
 
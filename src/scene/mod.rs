@@ -20,12 +20,15 @@ pub mod navmesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
+pub mod prefab_pool;
 pub mod ragdoll;
+pub mod resource_override;
 pub mod rigidbody;
 pub mod sound;
 pub mod sprite;
 pub mod terrain;
 pub mod transform;
+pub mod warmup;
 
 use crate::{
     asset::{self, manager::ResourceManager, untyped::UntypedResource},
@@ -40,7 +43,7 @@ use crate::{
     },
     engine::SerializationContext,
     renderer::framework::state::PolygonFillMode,
-    resource::texture::TextureResource,
+    resource::{post_effects::PostEffectsResource, texture::TextureResource},
     scene::{
         base::BaseBuilder,
         camera::Camera,
@@ -48,7 +51,10 @@ use crate::{
         graph::{map::NodeHandleMap, Graph, GraphPerformanceStatistics, GraphUpdateSwitches},
         navmesh::NavigationalMeshBuilder,
         node::Node,
+        prefab_pool::PrefabWarmUpList,
+        resource_override::ResourceOverrideTable,
         sound::SoundEngine,
+        warmup::{ResourceWarmUpProbe, ResourceWarmUpReport},
     },
     utils::navmesh::Navmesh,
 };
@@ -160,6 +166,13 @@ pub struct SceneRenderingOptions {
 
     /// Color of the ambient lighting.
     pub ambient_lighting_color: Color,
+
+    /// Post-processing stack, that is used by every camera of the scene, unless the camera has its
+    /// own stack (see [`camera::Camera::set_post_effects`]). See [`PostEffects`] docs for more info.
+    ///
+    /// [`PostEffects`]: crate::resource::post_effects::PostEffects
+    #[visit(optional)]
+    pub post_effects: Option<PostEffectsResource>,
 }
 
 impl Default for SceneRenderingOptions {
@@ -169,6 +182,7 @@ impl Default for SceneRenderingOptions {
             clear_color: None,
             polygon_rasterization_mode: Default::default(),
             ambient_lighting_color: Color::opaque(100, 100, 100),
+            post_effects: None,
         }
     }
 }
@@ -180,6 +194,7 @@ impl Clone for SceneRenderingOptions {
             clear_color: self.clear_color,
             polygon_rasterization_mode: self.polygon_rasterization_mode,
             ambient_lighting_color: self.ambient_lighting_color,
+            post_effects: self.post_effects.clone(),
         }
     }
 }
@@ -211,6 +226,13 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: InheritableVariable<bool>,
+
+    /// Resource overrides of the scene. See [`ResourceOverrideTable`] docs for more info.
+    pub resource_overrides: InheritableVariable<ResourceOverrideTable>,
+
+    /// Prefabs, that will be instantiated into the prefab pool of the scene when the scene starts
+    /// running. See [`prefab_pool::PrefabPool`] docs for more info.
+    pub prefab_warm_up: InheritableVariable<PrefabWarmUpList>,
 }
 
 impl Default for Scene {
@@ -221,6 +243,8 @@ impl Default for Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            resource_overrides: Default::default(),
+            prefab_warm_up: Default::default(),
         }
     }
 }
@@ -264,6 +288,7 @@ impl Display for PerformanceStatistics {
 pub struct SceneLoader {
     scene: Scene,
     path: Option<PathBuf>,
+    probe: ResourceWarmUpProbe,
 }
 
 impl SceneLoader {
@@ -301,28 +326,42 @@ impl SceneLoader {
             ));
         }
 
+        let probe = ResourceWarmUpProbe::new(&resource_manager);
+
         visitor.blackboard.register(serialization_context);
         visitor.blackboard.register(Arc::new(resource_manager));
 
         let mut scene = Scene::default();
         scene.visit(region_name, visitor)?;
 
-        Ok(Self { scene, path })
+        Ok(Self { scene, path, probe })
     }
 
     /// Finishes scene loading.
     pub async fn finish(self, resource_manager: &ResourceManager) -> Scene {
+        self.finish_with_report(resource_manager).await.0
+    }
+
+    /// Finishes scene loading and creates a report about every resource requested while loading
+    /// the scene. See [`ResourceWarmUpReport`] docs for more info.
+    pub async fn finish_with_report(
+        self,
+        resource_manager: &ResourceManager,
+    ) -> (Scene, ResourceWarmUpReport) {
         let mut scene = self.scene;
 
+        // Waiting for dependencies and resolving is the finalization stage of model loading.
+        asset::stats::mark_finalization_started();
+
         Log::info("SceneLoader::finish() - Collecting resources used by the scene...");
 
         let mut used_resources = scene.collect_used_resources();
 
         // Do not wait for self resources.
-        if let Some(path) = self.path {
+        if let Some(path) = self.path.as_ref() {
             let exclusion_list = used_resources
                 .iter()
-                .filter(|res| res.path() == path)
+                .filter(|res| res.path() == *path)
                 .cloned()
                 .collect::<Vec<_>>();
 
@@ -339,7 +378,7 @@ impl SceneLoader {
         ));
 
         // Wait everything.
-        join_all(used_resources.into_iter()).await;
+        join_all(used_resources.iter().cloned()).await;
 
         Log::info(format!(
             "SceneLoader::finish() - All {} resources have finished loading.",
@@ -362,7 +401,11 @@ impl SceneLoader {
         // And do resolve to extract correct graphical data and so on.
         scene.resolve(resource_manager);
 
-        scene
+        let report = self
+            .probe
+            .finish(self.path.as_deref(), &used_resources, resource_manager);
+
+        (scene, report)
     }
 }
 
@@ -382,6 +425,8 @@ impl Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            resource_overrides: Default::default(),
+            prefab_warm_up: Default::default(),
         }
     }
 
@@ -391,6 +436,10 @@ impl Scene {
 
         self.graph.resolve(resource_manager);
 
+        let root = self.graph.get_root();
+        self.resource_overrides
+            .apply(&mut self.graph, root, resource_manager);
+
         Log::writeln(MessageKind::Information, "Resolve succeeded!");
     }
 
@@ -431,6 +480,8 @@ impl Scene {
                 drawing_context: self.drawing_context.clone(),
                 performance_statistics: Default::default(),
                 enabled: self.enabled.clone(),
+                resource_overrides: self.resource_overrides.clone(),
+                prefab_warm_up: self.prefab_warm_up.clone(),
             },
             old_new_map,
         )
@@ -445,6 +496,10 @@ impl Scene {
         let _ = self
             .rendering_options
             .visit("RenderingOptions", &mut region);
+        let _ = self
+            .resource_overrides
+            .visit("ResourceOverrides", &mut region);
+        let _ = self.prefab_warm_up.visit("PrefabWarmUp", &mut region);
 
         // Backward compatibility.
         let mut navmeshes = NavMeshContainer::default();
@@ -4,7 +4,10 @@ use crate::{
     core::{parking_lot::Mutex, uuid::Uuid, TypeUuidProvider},
     scene::{
         self,
-        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        animation::{
+            absm::AnimationBlendingStateMachine, spritesheet::SpriteAnimationPlayer,
+            AnimationPlayer,
+        },
         camera::Camera,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
@@ -57,6 +60,7 @@ impl NodeConstructorContainer {
         container.add::<Terrain>();
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
+        container.add::<SpriteAnimationPlayer>();
         container.add::<NavigationalMesh>();
         container.add::<Ragdoll>();
 
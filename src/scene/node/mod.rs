@@ -552,6 +552,14 @@ impl Reflect for Node {
         self.0.deref_mut().fields_mut(func)
     }
 
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        self.0.deref().contents(func)
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        self.0.deref_mut().contents_mut(func)
+    }
+
     fn field(&self, name: &str, func: &mut dyn FnMut(Option<&dyn Reflect>)) {
         self.0.deref().field(name, func)
     }
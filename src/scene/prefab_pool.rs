@@ -0,0 +1,420 @@
+//! Prefab instance pooling. See [`PrefabPool`] docs for more info.
+
+use crate::{
+    asset::untyped::UntypedResource,
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        log::Log,
+        parking_lot::Mutex,
+        pool::Handle,
+        reflect::prelude::*,
+        variable::{self, try_inherit_properties},
+        visitor::prelude::*,
+    },
+    resource::model::{ModelResource, ModelResourceExtension},
+    scene::{graph::Graph, node::Node, Scene},
+};
+use fxhash::FxHashMap;
+use std::{any::TypeId, sync::Arc};
+
+/// A prefab and the amount of its instances, that should be created in advance.
+#[derive(Clone, Default, Debug, PartialEq, Visit, Reflect)]
+pub struct PrefabWarmUp {
+    /// A prefab to instantiate.
+    pub prefab: Option<ModelResource>,
+    /// Amount of instances, that should be in the pool of the scene.
+    pub count: usize,
+}
+
+impl PrefabWarmUp {
+    /// Creates new warm-up entry.
+    pub fn new(prefab: ModelResource, count: usize) -> Self {
+        Self {
+            prefab: Some(prefab),
+            count,
+        }
+    }
+}
+
+/// A list of prefabs, that will be pre-instantiated into the pool of a scene when the scene starts
+/// running. It is stored in the scene (see [`Scene::prefab_warm_up`]) and could be edited in the
+/// scene settings of the editor.
+#[derive(Clone, Default, Debug, PartialEq, Visit, Reflect)]
+pub struct PrefabWarmUpList {
+    /// A list of prefabs to warm up.
+    pub entries: Vec<PrefabWarmUp>,
+}
+
+impl PrefabWarmUpList {
+    /// Adds new entry to the list.
+    pub fn add(&mut self, entry: PrefabWarmUp) {
+        self.entries.push(entry);
+    }
+
+    /// Returns `true` if the list has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Default, Debug)]
+struct Bucket {
+    free: Vec<Handle<Node>>,
+    // Total amount of instances created by the pool.
+    created: usize,
+}
+
+#[derive(Default, Debug)]
+struct State {
+    buckets: FxHashMap<(Handle<Scene>, ModelResource), Bucket>,
+}
+
+/// Prefab pool keeps disabled instances of prefabs, that could be acquired and released instead of
+/// instantiating and deleting them. Instantiation of a large prefab could take considerable time,
+/// which causes hitches when a lot of short-living objects (bullets, particle effects, debris,
+/// etc.) are spawned in a single frame. The pool allows you to create such objects in advance and
+/// reuse them.
+///
+/// Every scene has its own set of pooled instances, a prefab instance that is not used lives in the
+/// scene graph as a disabled node attached to the root of the graph (so its scripts are not
+/// updated and it is not rendered). When an instance is released, its reflected state is reset to
+/// the state of the prefab, which means that every property, that was changed after the instance
+/// was acquired (position, health of a script, enabled flags of its children and so on), is
+/// restored. Nodes added to the instance after it was acquired are left untouched.
+///
+/// Scenes could have a list of prefabs, that are instantiated into the pool automatically when the
+/// scene starts running (see [`Scene::prefab_warm_up`] and [`PrefabWarmUpList`]). It is done by the
+/// engine for every scene that has script processing enabled, as soon as the prefabs are loaded.
+///
+/// Prefab pool is a shallow handle, that could be cloned and shared. It is available via
+/// [`crate::engine::Engine::prefab_pool`] and to scripts as a service (see
+/// [`crate::script::service::ScriptServices`]).
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::algebra::{UnitQuaternion, Vector3},
+/// #     resource::model::ModelResource,
+/// #     scene::prefab_pool::PrefabPool,
+/// #     script::ScriptContext,
+/// # };
+/// fn shoot(ctx: &mut ScriptContext, bullet: &ModelResource) {
+///     let pool = ctx.services.expect::<PrefabPool>();
+///     let position = ctx.scene.graph[ctx.handle].global_position();
+///     pool.acquire_at(
+///         ctx.scene_handle,
+///         ctx.scene,
+///         bullet,
+///         position,
+///         UnitQuaternion::default(),
+///     );
+/// }
+///
+/// fn on_bullet_hit(ctx: &mut ScriptContext) {
+///     // Returns the bullet back to the pool instead of deleting it.
+///     let pool = ctx.services.expect::<PrefabPool>();
+///     pool.release(ctx.scene_handle, ctx.scene, ctx.handle);
+/// }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct PrefabPool {
+    state: Arc<Mutex<State>>,
+}
+
+impl PrefabPool {
+    /// Makes sure that the pool of the given scene has at least `count` instances of the prefab.
+    /// The prefab must be loaded.
+    pub fn warm_up(
+        &self,
+        scene_handle: Handle<Scene>,
+        scene: &mut Scene,
+        prefab: &ModelResource,
+        count: usize,
+    ) {
+        let mut state = self.state.lock();
+        let bucket = state
+            .buckets
+            .entry((scene_handle, prefab.clone()))
+            .or_default();
+        while bucket.created < count {
+            let instance = prefab.instantiate(scene);
+            park(&mut scene.graph, instance);
+            bucket.free.push(instance);
+            bucket.created += 1;
+        }
+    }
+
+    /// Warms up the pool of the given scene using its warm-up list (see [`Scene::prefab_warm_up`]).
+    /// Prefabs, that are not loaded yet, are skipped, so the method could be called multiple times
+    /// until every prefab is ready.
+    pub fn warm_up_scene(&self, scene_handle: Handle<Scene>, scene: &mut Scene) {
+        if scene.prefab_warm_up.is_empty() {
+            return;
+        }
+
+        let entries = scene.prefab_warm_up.entries.clone();
+        for entry in entries {
+            if let Some(prefab) = entry.prefab.as_ref() {
+                if prefab.is_ok() {
+                    self.warm_up(scene_handle, scene, prefab, entry.count);
+                }
+            }
+        }
+    }
+
+    /// Takes a free instance of the prefab from the pool of the given scene, or instantiates the
+    /// prefab if there's no free instances. The instance is enabled and attached to the root of
+    /// the graph.
+    pub fn acquire(
+        &self,
+        scene_handle: Handle<Scene>,
+        scene: &mut Scene,
+        prefab: &ModelResource,
+    ) -> Handle<Node> {
+        let mut state = self.state.lock();
+        let bucket = state
+            .buckets
+            .entry((scene_handle, prefab.clone()))
+            .or_default();
+
+        while let Some(instance) = bucket.free.pop() {
+            // The instance could be deleted while it was in the pool.
+            if is_instance_of(&scene.graph, instance, prefab) {
+                scene.graph[instance].set_enabled(true);
+                scene
+                    .graph
+                    .update_hierarchical_data_for_descendants(instance);
+                return instance;
+            }
+            bucket.created = bucket.created.saturating_sub(1);
+        }
+
+        bucket.created += 1;
+        prefab.instantiate(scene)
+    }
+
+    /// Does the same as [`Self::acquire`], but also places the instance at the specified position
+    /// and orientation in global coordinates.
+    pub fn acquire_at(
+        &self,
+        scene_handle: Handle<Scene>,
+        scene: &mut Scene,
+        prefab: &ModelResource,
+        position: Vector3<f32>,
+        orientation: UnitQuaternion<f32>,
+    ) -> Handle<Node> {
+        let instance = self.acquire(scene_handle, scene, prefab);
+
+        scene.graph[instance]
+            .local_transform_mut()
+            .set_position(position)
+            .set_rotation(orientation);
+
+        scene
+            .graph
+            .update_hierarchical_data_for_descendants(instance);
+
+        instance
+    }
+
+    /// Returns the given instance back to the pool of the given scene. The instance is reset to the
+    /// state of its prefab, disabled and attached to the root of the graph. Returns `false` if the
+    /// handle is not a root of a prefab instance, in this case the node is left untouched.
+    pub fn release(
+        &self,
+        scene_handle: Handle<Scene>,
+        scene: &mut Scene,
+        instance: Handle<Node>,
+    ) -> bool {
+        let Some(prefab) = scene
+            .graph
+            .try_get(instance)
+            .filter(|node| node.is_resource_instance_root())
+            .and_then(|node| node.resource())
+        else {
+            Log::warn(format!(
+                "Unable to return node {instance} to the prefab pool, because it is not \
+                a prefab instance!"
+            ));
+            return false;
+        };
+
+        reset_instance(&mut scene.graph, instance, &prefab);
+        park(&mut scene.graph, instance);
+
+        let mut state = self.state.lock();
+        let bucket = state.buckets.entry((scene_handle, prefab)).or_default();
+        if !bucket.free.contains(&instance) {
+            bucket.free.push(instance);
+        }
+
+        true
+    }
+
+    /// Returns the amount of free instances of the prefab in the pool of the given scene.
+    pub fn free_count(&self, scene_handle: Handle<Scene>, prefab: &ModelResource) -> usize {
+        self.state
+            .lock()
+            .buckets
+            .get(&(scene_handle, prefab.clone()))
+            .map_or(0, |bucket| bucket.free.len())
+    }
+
+    /// Forgets every instance of the given scene. Instances are not deleted from the scene.
+    pub fn clear(&self, scene_handle: Handle<Scene>) {
+        self.retain_scenes(|scene| scene != scene_handle)
+    }
+
+    /// Removes pools of the scenes, that do not exist anymore.
+    pub(crate) fn retain_scenes(&self, mut func: impl FnMut(Handle<Scene>) -> bool) {
+        self.state
+            .lock()
+            .buckets
+            .retain(|(scene, _), _| func(*scene));
+    }
+}
+
+fn is_instance_of(graph: &Graph, instance: Handle<Node>, prefab: &ModelResource) -> bool {
+    graph.try_get(instance).is_some_and(|node| {
+        node.is_resource_instance_root() && node.resource().as_ref() == Some(prefab)
+    })
+}
+
+fn park(graph: &mut Graph, instance: Handle<Node>) {
+    let root = graph.get_root();
+    if graph[instance].parent() != root {
+        graph.link_nodes(instance, root);
+    }
+    graph[instance].set_enabled(false);
+    graph.update_hierarchical_data_for_descendants(instance);
+}
+
+// Restores properties of every node of the instance using respective nodes of the prefab.
+fn reset_instance(graph: &mut Graph, instance: Handle<Node>, prefab: &ModelResource) {
+    let data = prefab.data_ref();
+    let resource_graph = &data.get_scene().graph;
+
+    let handles = graph.traverse_handle_iter(instance).collect::<Vec<_>>();
+    for handle in handles {
+        let node = &mut graph[handle];
+        if node.resource().as_ref() != Some(prefab) {
+            continue;
+        }
+
+        let Some(resource_node) = resource_graph.try_get(node.original_handle_in_resource) else {
+            continue;
+        };
+
+        node.as_reflect_mut(&mut |node_reflect| {
+            variable::mark_inheritable_properties_non_modified(
+                node_reflect,
+                &[TypeId::of::<UntypedResource>()],
+            );
+            resource_node.as_reflect(&mut |resource_node_reflect| {
+                Log::verify(try_inherit_properties(
+                    node_reflect,
+                    resource_node_reflect,
+                    &[TypeId::of::<UntypedResource>()],
+                ));
+            })
+        });
+    }
+
+    drop(data);
+
+    // Inherited handles point to the nodes of the prefab, map them back to the instance.
+    graph.remap_handles(&[(instance, prefab.clone())]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        resource::model::{Model, NodeMapping},
+        scene::{base::BaseBuilder, pivot::PivotBuilder, transform::TransformBuilder},
+    };
+
+    fn make_prefab() -> ModelResource {
+        let mut scene = Scene::new();
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Bullet")
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                        .build(),
+                )
+                .with_children(&[PivotBuilder::new(BaseBuilder::new().with_name("Trail"))
+                    .build(&mut scene.graph)]),
+        )
+        .build(&mut scene.graph);
+
+        ModelResource::new_ok(Model {
+            path: Default::default(),
+            mapping: NodeMapping::UseNames,
+            scene,
+            dirty: false,
+        })
+    }
+
+    #[test]
+    fn prefab_pool_acquire_release() {
+        let prefab = make_prefab();
+        let pool = PrefabPool::default();
+        let scene_handle = Handle::new(1, 1);
+        let mut scene = Scene::new();
+
+        scene
+            .prefab_warm_up
+            .add(PrefabWarmUp::new(prefab.clone(), 2));
+        pool.warm_up_scene(scene_handle, &mut scene);
+        pool.warm_up_scene(scene_handle, &mut scene);
+        assert_eq!(pool.free_count(scene_handle, &prefab), 2);
+        assert_eq!(pool.free_count(Handle::new(2, 1), &prefab), 0);
+
+        let instance = pool.acquire(scene_handle, &mut scene, &prefab);
+        assert_eq!(pool.free_count(scene_handle, &prefab), 1);
+        assert!(scene.graph[instance].is_globally_enabled());
+
+        // Change the state of the instance, it must be restored on release.
+        let parent = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        scene.graph.link_nodes(instance, parent);
+        scene.graph[instance]
+            .local_transform_mut()
+            .set_position(Vector3::new(5.0, 5.0, 5.0));
+        let (bullet, _) = scene.graph.find_by_name(instance, "Bullet").unwrap();
+        scene.graph[bullet]
+            .local_transform_mut()
+            .set_position(Vector3::new(4.0, 4.0, 4.0));
+        let (trail, _) = scene.graph.find_by_name(instance, "Trail").unwrap();
+        scene.graph[trail].set_visibility(false);
+
+        assert!(pool.release(scene_handle, &mut scene, instance));
+        assert!(!pool.release(scene_handle, &mut scene, parent));
+        assert_eq!(pool.free_count(scene_handle, &prefab), 2);
+        assert_eq!(scene.graph[instance].parent(), scene.graph.get_root());
+        assert!(!scene.graph[instance].is_globally_enabled());
+        assert_eq!(
+            **scene.graph[instance].local_transform().position(),
+            Vector3::default()
+        );
+        assert_eq!(
+            **scene.graph[bullet].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(scene.graph[trail].visibility());
+
+        // Deleted instances are skipped.
+        scene.graph.remove_node(instance);
+        let other = pool.acquire(scene_handle, &mut scene, &prefab);
+        assert_ne!(other, instance);
+        assert_eq!(pool.free_count(scene_handle, &prefab), 0);
+
+        // Empty pool instantiates the prefab.
+        let new = pool.acquire(scene_handle, &mut scene, &prefab);
+        assert!(scene.graph.is_valid_handle(new));
+        assert_ne!(new, other);
+
+        pool.clear(scene_handle);
+        assert_eq!(pool.free_count(scene_handle, &prefab), 0);
+    }
+}
@@ -14,7 +14,8 @@ use crate::{
     impl_query_component,
     scene::{
         base::{Base, BaseBuilder},
-        graph::Graph,
+        dim2,
+        graph::{Graph, NodePool},
         node::{Node, NodeTrait, UpdateContext},
         rigidbody::{RigidBody, RigidBodyType},
     },
@@ -127,6 +128,20 @@ impl Reflect for Limb {
         func(&mut [&mut self.bone, &mut self.physical_bone, &mut self.children])
     }
 
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        func(&self.bone);
+        func(&self.physical_bone);
+        func(&self.children);
+        true
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        func(&mut self.bone);
+        func(&mut self.physical_bone);
+        func(&mut self.children);
+        true
+    }
+
     fn field(&self, name: &str, func: &mut dyn FnMut(Option<&dyn Reflect>)) {
         func(match name {
             "Bone" => Some(&self.bone),
@@ -171,6 +186,72 @@ impl Limb {
     }
 }
 
+/// A rigid body of a limb, ragdolls could be made of either 3D or 2D rigid bodies.
+enum LimbBody<'a> {
+    Body(&'a mut RigidBody),
+    Body2D(&'a mut dim2::rigidbody::RigidBody),
+}
+
+impl<'a> LimbBody<'a> {
+    fn from_node(nodes: &'a mut NodePool, handle: Handle<Node>) -> Option<Self> {
+        let node = nodes.try_borrow_mut(handle)?;
+        if node.query_component_ref::<RigidBody>().is_some() {
+            node.query_component_mut::<RigidBody>().map(Self::Body)
+        } else {
+            node.query_component_mut::<dim2::rigidbody::RigidBody>()
+                .map(Self::Body2D)
+        }
+    }
+
+    /// Returns linear and angular velocities, 2D velocities are stored in XY plane.
+    fn velocities(&self) -> (Vector3<f32>, Vector3<f32>) {
+        match self {
+            Self::Body(body) => (body.lin_vel(), body.ang_vel()),
+            Self::Body2D(body) => (
+                body.lin_vel().push(0.0),
+                Vector3::new(0.0, 0.0, body.ang_vel()),
+            ),
+        }
+    }
+
+    fn set_velocities(&mut self, lin_vel: Vector3<f32>, ang_vel: Vector3<f32>) {
+        match self {
+            Self::Body(body) => {
+                body.set_lin_vel(lin_vel);
+                body.set_ang_vel(ang_vel);
+            }
+            Self::Body2D(body) => {
+                body.set_lin_vel(lin_vel.xy());
+                body.set_ang_vel(ang_vel.z);
+            }
+        }
+    }
+
+    fn body_type(&self) -> RigidBodyType {
+        match self {
+            Self::Body(body) => body.body_type(),
+            Self::Body2D(body) => body.body_type(),
+        }
+    }
+
+    fn set_body_type(&mut self, body_type: RigidBodyType) {
+        match self {
+            Self::Body(body) => body.set_body_type(body_type),
+            Self::Body2D(body) => body.set_body_type(body_type),
+        };
+    }
+
+    fn base_mut(&mut self) -> &mut Base {
+        match self {
+            Self::Body(body) => body,
+            Self::Body2D(body) => body,
+        }
+    }
+}
+
+/// Ragdoll is a set of rigid bodies (either 3D or 2D), that are attached to bones of a character.
+/// When the ragdoll is active, the bones follow their bodies, otherwise the bodies follow their
+/// bones.
 #[derive(Clone, Reflect, Visit, Debug, Default)]
 pub struct Ragdoll {
     base: Base,
@@ -218,39 +299,28 @@ impl NodeTrait for Ragdoll {
 
     fn update(&mut self, ctx: &mut UpdateContext) {
         // Get linear and angular velocities of the character rigid body and transfer it onto rag doll bodies when it is just activated.
-        let mut new_lin_vel = None;
-        let mut new_ang_vel = None;
+        let mut new_velocities = None;
         if *self.is_active && !self.prev_enabled {
-            if let Some(character_rigid_body) = ctx
-                .nodes
-                .try_borrow_mut(*self.character_rigid_body)
-                .and_then(|n| n.query_component_mut::<RigidBody>())
+            if let Some(character_rigid_body) =
+                LimbBody::from_node(ctx.nodes, *self.character_rigid_body)
             {
-                new_lin_vel = Some(character_rigid_body.lin_vel());
-                new_ang_vel = Some(character_rigid_body.ang_vel());
+                new_velocities = Some(character_rigid_body.velocities());
             }
         }
         self.prev_enabled = *self.is_active;
 
         self.root_limb.iterate_recursive(&mut |limb| {
-            if let Some(limb_body) = ctx
-                .nodes
-                .try_borrow_mut(limb.physical_bone)
-                .and_then(|n| n.query_component_mut::<RigidBody>())
-            {
+            if let Some(mut limb_body) = LimbBody::from_node(ctx.nodes, limb.physical_bone) {
                 if *self.is_active {
                     // Transfer linear and angular velocities to rag doll bodies.
-                    if let Some(lin_vel) = new_lin_vel {
-                        limb_body.set_lin_vel(lin_vel);
-                    }
-                    if let Some(ang_vel) = new_ang_vel {
-                        limb_body.set_ang_vel(ang_vel);
+                    if let Some((lin_vel, ang_vel)) = new_velocities {
+                        limb_body.set_velocities(lin_vel, ang_vel);
                     }
 
                     if limb_body.body_type() != RigidBodyType::Dynamic {
                         limb_body.set_body_type(RigidBodyType::Dynamic);
                     }
-                    let body_transform = limb_body.global_transform();
+                    let body_transform = limb_body.base_mut().global_transform();
 
                     // Sync transform of the bone with respective body.
                     let bone_parent = ctx.nodes[limb.bone].parent();
@@ -283,8 +353,7 @@ impl NodeTrait for Ragdoll {
                     );
                 } else {
                     limb_body.set_body_type(RigidBodyType::KinematicPositionBased);
-                    limb_body.set_lin_vel(Default::default());
-                    limb_body.set_ang_vel(Default::default());
+                    limb_body.set_velocities(Default::default(), Default::default());
 
                     let self_transform_inverse =
                         self.global_transform().try_inverse().unwrap_or_default();
@@ -316,14 +385,14 @@ impl NodeTrait for Ragdoll {
         if *self.is_active {
             if let Some(root_limb_body) = ctx.nodes.try_borrow(self.root_limb.bone) {
                 let position = root_limb_body.global_position();
-                if let Some(capsule) = ctx
-                    .nodes
-                    .try_borrow_mut(*self.character_rigid_body)
-                    .and_then(|n| n.query_component_mut::<RigidBody>())
+                if let Some(mut capsule) =
+                    LimbBody::from_node(ctx.nodes, *self.character_rigid_body)
                 {
-                    capsule.set_lin_vel(Default::default());
-                    capsule.set_ang_vel(Default::default());
-                    capsule.local_transform_mut().set_position(position);
+                    capsule.set_velocities(Default::default(), Default::default());
+                    capsule
+                        .base_mut()
+                        .local_transform_mut()
+                        .set_position(position);
                 }
             }
         }
@@ -392,3 +461,70 @@ impl RagdollBuilder {
         graph.add_node(Node::new(ragdoll))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            dim2::rigidbody::{RigidBody, RigidBodyBuilder},
+            graph::Graph,
+            pivot::PivotBuilder,
+            ragdoll::{Limb, Ragdoll, RagdollBuilder},
+            rigidbody::RigidBodyType,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_ragdoll_2d() {
+        let mut graph = Graph::new();
+        let bone = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new())
+            .with_gravity_scale(0.0)
+            .build(&mut graph);
+        let character = RigidBodyBuilder::new(BaseBuilder::new())
+            .with_lin_vel(Vector2::new(3.0, 0.0))
+            .with_gravity_scale(0.0)
+            .build(&mut graph);
+        let ragdoll = RagdollBuilder::new(BaseBuilder::new())
+            .with_character_rigid_body(character)
+            .with_active(false)
+            .with_root_limb(Limb {
+                bone,
+                physical_bone: body,
+                children: vec![],
+            })
+            .build(&mut graph);
+
+        // Inactive ragdoll bodies follow their bones.
+        graph.update(Vector2::new(1.0, 1.0), 1.0 / 60.0, Default::default());
+        let rigid_body = graph[body].cast::<RigidBody>().unwrap();
+        assert_eq!(
+            rigid_body.body_type(),
+            RigidBodyType::KinematicPositionBased
+        );
+        assert_eq!(
+            **rigid_body.local_transform().position(),
+            Vector3::new(1.0, 2.0, 0.0)
+        );
+
+        // Velocity of the character is transferred to the bodies on activation.
+        graph[ragdoll]
+            .cast_mut::<Ragdoll>()
+            .unwrap()
+            .set_active(true);
+        graph.update(Vector2::new(1.0, 1.0), 1.0 / 60.0, Default::default());
+        let rigid_body = graph[body].cast::<RigidBody>().unwrap();
+        assert_eq!(rigid_body.body_type(), RigidBodyType::Dynamic);
+        assert_eq!(rigid_body.lin_vel(), Vector2::new(3.0, 0.0));
+    }
+}
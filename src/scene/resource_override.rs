@@ -0,0 +1,235 @@
+//! Resource overrides allow a scene to replace resources of its nodes with other resources. See
+//! [`ResourceOverrideTable`] docs for more info.
+
+use crate::{
+    asset::{self, manager::ResourceManager, untyped::UntypedResource},
+    core::{log::Log, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    scene::{graph::Graph, node::Node},
+};
+use fxhash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// A single resource override. It replaces every resource that has `original` path with a
+/// resource at `replacement` path. If `original` is a directory, then every resource from the
+/// directory (and its sub-directories) is replaced with a resource with the same relative path
+/// in `replacement` directory.
+#[derive(Clone, Default, Debug, PartialEq, Visit, Reflect)]
+pub struct ResourceOverride {
+    /// Path of a resource (or a directory with resources) that should be replaced.
+    pub original: PathBuf,
+    /// Path of a resource (or a directory with resources) that will be used instead.
+    pub replacement: PathBuf,
+}
+
+impl ResourceOverride {
+    /// Creates new resource override.
+    pub fn new(original: impl Into<PathBuf>, replacement: impl Into<PathBuf>) -> Self {
+        Self {
+            original: original.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Returns a replacement path for the given resource path, or `None` if the override cannot
+    /// be applied to the path.
+    pub fn replacement_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.original.as_os_str().is_empty() {
+            return None;
+        }
+
+        let relative = path.strip_prefix(&self.original).ok()?;
+        if relative.as_os_str().is_empty() {
+            Some(self.replacement.clone())
+        } else {
+            Some(self.replacement.join(relative))
+        }
+    }
+}
+
+/// Resource override table is a set of resource replacements, that is applied to a scene when the
+/// scene is resolved (on load and when any of its prefabs is reloaded) and to prefab instances
+/// created by [`crate::resource::model::ModelResourceExtension::instantiate_with_overrides`].
+/// It allows you to make theme variations of shared prefabs (for example, a level that uses
+/// winter versions of materials) without duplicating the prefabs.
+///
+/// Overrides are checked in order and the first matching override is used. Only resources that
+/// are directly used by scene nodes are replaced, resources used by other resources are left
+/// untouched (for example, to replace textures of a material, override the material itself).
+/// Replaced values are not marked as modified, so the overrides are never "baked" into the
+/// scene when it is saved.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox::scene::{resource_override::ResourceOverride, Scene};
+///
+/// fn use_winter_theme(scene: &mut Scene) {
+///     scene
+///         .resource_overrides
+///         .add(ResourceOverride::new("data/materials/summer", "data/materials/winter"));
+/// }
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Visit, Reflect)]
+pub struct ResourceOverrideTable {
+    /// A list of overrides.
+    pub overrides: Vec<ResourceOverride>,
+}
+
+impl ResourceOverrideTable {
+    /// Adds new resource override to the end of the table.
+    pub fn add(&mut self, resource_override: ResourceOverride) {
+        self.overrides.push(resource_override);
+    }
+
+    /// Returns `true` if the table has no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Returns a replacement path for the given resource path using the first matching override.
+    pub fn replacement_path(&self, path: &Path) -> Option<PathBuf> {
+        self.overrides
+            .iter()
+            .find_map(|resource_override| resource_override.replacement_path(path))
+    }
+
+    /// Replaces resources of every node in the hierarchy starting from `root` (including) using
+    /// the overrides of the table. Replacement resources are requested from the given resource
+    /// manager. Replacements of a different type than original resources are ignored.
+    pub fn apply(&self, graph: &mut Graph, root: Handle<Node>, resource_manager: &ResourceManager) {
+        if self.is_empty() || !graph.is_valid_handle(root) {
+            return;
+        }
+
+        let mut replacements = FxHashMap::<PathBuf, Option<UntypedResource>>::default();
+        let handles = graph.traverse_handle_iter(root).collect::<Vec<_>>();
+        for handle in handles {
+            let node = &mut graph[handle];
+            asset::remap_used_resources(node as &mut dyn Reflect, &mut |resource| {
+                let path = resource.path();
+                let new_path = self.replacement_path(&path)?;
+                if new_path == path {
+                    return None;
+                }
+
+                replacements
+                    .entry(new_path)
+                    .or_insert_with_key(|new_path| {
+                        let replacement = resource_manager.request_untyped(new_path);
+                        if replacement.type_uuid() == resource.type_uuid() {
+                            Some(replacement)
+                        } else {
+                            Log::warn(format!(
+                                "Unable to override resource {} with {}, because \
+                                their types do not match!",
+                                path.display(),
+                                new_path.display()
+                            ));
+                            None
+                        }
+                    })
+                    .clone()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        asset::Resource,
+        core::{variable::InheritableVariable, TypeUuidProvider},
+        engine::{self, SerializationContext},
+        material::{Material, MaterialResource},
+        scene::{
+            base::BaseBuilder,
+            sprite::{Sprite, SpriteBuilder},
+            Scene,
+        },
+    };
+    use std::sync::Arc;
+
+    fn pending_material(path: &str) -> MaterialResource {
+        Resource::from(UntypedResource::new_pending(
+            path.into(),
+            <Material as TypeUuidProvider>::type_uuid(),
+        ))
+    }
+
+    #[test]
+    fn resource_override_replacement_path() {
+        let mut table = ResourceOverrideTable::default();
+        table.add(ResourceOverride::new(
+            "data/textures/summer/grass.png",
+            "data/textures/grass_snow.png",
+        ));
+        table.add(ResourceOverride::new(
+            "data/textures/summer",
+            "data/textures/winter",
+        ));
+
+        assert_eq!(
+            table.replacement_path(Path::new("data/textures/summer/grass.png")),
+            Some(PathBuf::from("data/textures/grass_snow.png"))
+        );
+        assert_eq!(
+            table.replacement_path(Path::new("data/textures/summer/trees/oak.png")),
+            Some(PathBuf::from("data/textures/winter/trees/oak.png"))
+        );
+        assert_eq!(
+            table.replacement_path(Path::new("data/textures/summer_sky.png")),
+            None
+        );
+        assert_eq!(
+            ResourceOverride::default().replacement_path(Path::new("data/a.png")),
+            None
+        );
+    }
+
+    #[test]
+    fn resource_override_apply() {
+        let resource_manager = ResourceManager::new();
+        engine::initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+
+        let mut scene = Scene::new();
+        let summer = SpriteBuilder::new(BaseBuilder::new())
+            .with_material(pending_material("summer/stone.material"))
+            .build(&mut scene.graph);
+        // Emulate a prefab instance, which has its properties inherited from the prefab.
+        *scene.graph[summer]
+            .cast_mut::<Sprite>()
+            .unwrap()
+            .material_mut() =
+            InheritableVariable::new_non_modified(pending_material("summer/stone.material"));
+        let other = SpriteBuilder::new(BaseBuilder::new())
+            .with_material(pending_material("common/wood.material"))
+            .build(&mut scene.graph);
+
+        scene
+            .resource_overrides
+            .add(ResourceOverride::new("summer", "winter"));
+        let root = scene.graph.get_root();
+        scene
+            .resource_overrides
+            .apply(&mut scene.graph, root, &resource_manager);
+
+        let material_path = |handle: Handle<Node>| {
+            scene.graph[handle]
+                .cast::<Sprite>()
+                .unwrap()
+                .material()
+                .path()
+        };
+        assert_eq!(material_path(summer), Path::new("winter/stone.material"));
+        assert_eq!(material_path(other), Path::new("common/wood.material"));
+        assert!(!scene.graph[summer]
+            .cast::<Sprite>()
+            .unwrap()
+            .material()
+            .is_modified());
+    }
+}
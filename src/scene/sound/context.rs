@@ -22,6 +22,10 @@ use std::{sync::MutexGuard, time::Duration};
 pub struct SoundContext {
     #[visit(optional)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+    /// Whether the sound is paused by the engine (see [`crate::engine::time::TimeControl::AUDIO`]),
+    /// regardless of the graph update switches.
+    #[visit(skip)]
+    pub(crate) audio_paused: bool,
 }
 
 /// Proxy for guarded access to the sound context.
@@ -104,7 +108,10 @@ impl Default for SoundContext {
         // There's no need to serialize native sources, because they'll be re-created automatically.
         state.serialization_options.skip_sources = true;
         drop(state);
-        Self { native }
+        Self {
+            native,
+            audio_paused: false,
+        }
     }
 }
 
@@ -117,6 +124,7 @@ impl SoundContext {
     pub fn deep_clone(&self) -> Self {
         Self {
             native: self.native.deep_clone(),
+            audio_paused: self.audio_paused,
         }
     }
 
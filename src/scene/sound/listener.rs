@@ -9,6 +9,7 @@ use crate::{
         pool::Handle,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
+        variable::InheritableVariable,
         visitor::prelude::*,
         TypeUuidProvider,
     },
@@ -16,6 +17,7 @@ use crate::{
         base::{Base, BaseBuilder},
         graph::Graph,
         node::{Node, NodeTrait, SyncContext},
+        sound::HrirSphereResource,
     },
 };
 use std::ops::{Deref, DerefMut};
@@ -37,6 +39,10 @@ use std::ops::{Deref, DerefMut};
 #[derive(Visit, Reflect, Default, Clone, Debug)]
 pub struct Listener {
     base: Base,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_hrir_sphere_resource")]
+    hrir_sphere: InheritableVariable<Option<HrirSphereResource>>,
 }
 
 impl Deref for Listener {
@@ -53,6 +59,23 @@ impl DerefMut for Listener {
     }
 }
 
+impl Listener {
+    /// Sets a HRIR sphere, that will be used to render sounds for this listener instead of the HRIR sphere of the
+    /// HRTF renderer of the sound context. It allows to use a sphere, that suits the player the best. It has no effect
+    /// if the sound context does not use the HRTF renderer.
+    pub fn set_hrir_sphere_resource(
+        &mut self,
+        resource: Option<HrirSphereResource>,
+    ) -> Option<HrirSphereResource> {
+        self.hrir_sphere.set_value_and_mark_modified(resource)
+    }
+
+    /// Returns a HRIR sphere of the listener (if any).
+    pub fn hrir_sphere_resource(&self) -> Option<HrirSphereResource> {
+        (*self.hrir_sphere).clone()
+    }
+}
+
 impl TypeUuidProvider for Listener {
     fn type_uuid() -> Uuid {
         uuid!("2c7dabc1-5666-4256-b020-01532701e4c6")
@@ -88,24 +111,38 @@ impl NodeTrait for Listener {
         let native = state.listener_mut();
         native.set_position(self.global_position());
         native.set_orientation_lh(self.look_vector(), self.up_vector());
+        if native.hrir_sphere_resource() != self.hrir_sphere.as_ref() {
+            native.set_hrir_sphere_resource((*self.hrir_sphere).clone());
+        }
     }
 }
 
 /// Allows you to create listener in declarative manner.
 pub struct ListenerBuilder {
     base_builder: BaseBuilder,
+    hrir_sphere: Option<HrirSphereResource>,
 }
 
 impl ListenerBuilder {
     /// Creates new listner builder.
     pub fn new(base_builder: BaseBuilder) -> Self {
-        Self { base_builder }
+        Self {
+            base_builder,
+            hrir_sphere: None,
+        }
+    }
+
+    /// Sets desired HRIR sphere of the listener. See [`Listener::set_hrir_sphere_resource`] for more info.
+    pub fn with_hrir_sphere_resource(mut self, resource: Option<HrirSphereResource>) -> Self {
+        self.hrir_sphere = resource;
+        self
     }
 
     /// Creates listener instance.
     pub fn build_listener(self) -> Listener {
         Listener {
             base: self.base_builder.build_base(),
+            hrir_sphere: self.hrir_sphere.into(),
         }
     }
 
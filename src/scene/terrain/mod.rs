@@ -1554,6 +1554,7 @@ impl NodeTrait for Terrain {
                                     node.persistent_index,
                                 ),
                                 node_handle: self.self_handle,
+                                allow_instancing: false,
                             },
                         );
                     } else {
@@ -1577,6 +1578,7 @@ impl NodeTrait for Terrain {
                                             node.persistent_index,
                                         ),
                                         node_handle: self.self_handle,
+                                        allow_instancing: false,
                                     },
                                 );
                             }
@@ -0,0 +1,261 @@
+//! Resource warm-up report contains information about every resource, that was requested while
+//! loading a scene. See [`ResourceWarmUpReport`] docs for more info.
+
+use crate::{
+    asset::{manager::ResourceManager, state::ResourceState, untyped::UntypedResource},
+    core::{instant::Instant, uuid::Uuid},
+};
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// State of a resource at the moment when a warm-up report was created.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourceWarmUpStatus {
+    /// The resource is fully loaded.
+    #[default]
+    Loaded,
+    /// The resource is failed to load.
+    Failed,
+    /// The resource is still loading. It could happen to resources, that are not used by the scene
+    /// directly.
+    Pending,
+}
+
+/// Information about a single resource requested while loading a scene.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceWarmUpEntry {
+    /// Path of the resource.
+    pub path: PathBuf,
+    /// Type uuid of the resource.
+    pub type_uuid: Uuid,
+    /// Type name of the resource, empty if the type has no registered constructor.
+    pub type_name: String,
+    /// State of the resource at the moment when the report was created.
+    pub status: ResourceWarmUpStatus,
+    /// `true` if the resource is used by the scene directly, `false` if it was loaded as a
+    /// dependency of some other resource (for example, a texture of a prefab).
+    pub direct: bool,
+    /// `true` if the resource was already in the resource manager before the scene started
+    /// loading, `false` if it was loaded from scratch.
+    pub cache_hit: bool,
+    /// Total time (in milliseconds) spent to load the resource. Always zero for cache hits.
+    pub load_time_ms: f64,
+    /// Time (in milliseconds) spent in the resource IO. Always zero for cache hits.
+    pub io_time_ms: f64,
+    /// Estimated amount of memory (in bytes) used by the resource. See
+    /// [`crate::asset::ResourceData::memory_usage`] for more info.
+    pub memory: usize,
+}
+
+/// Resource warm-up report contains every resource requested while loading a scene: how long each
+/// resource took to load, whether it was already loaded (cache hit) or loaded from scratch (cold
+/// load) and how much memory it uses. The report could be saved as JSON (see [`Self::to_json`]) and
+/// analyzed by external tools, for example to check load-time budgets of levels at build time.
+///
+/// Resources loaded as dependencies are found by comparing the contents of the resource manager
+/// before and after loading, so the report will also include resources loaded concurrently by
+/// other code.
+///
+/// Use [`crate::scene::SceneLoader::finish_with_report`] or
+/// [`crate::engine::AsyncSceneLoader::warm_up_report`] to get the report.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceWarmUpReport {
+    /// Path of the scene, empty if the scene was loaded from memory.
+    pub scene_path: PathBuf,
+    /// Total time (in milliseconds) spent to load the scene, including deserialization and
+    /// resolving.
+    pub total_time_ms: f64,
+    /// Every resource requested while loading the scene. Resources used by the scene directly go
+    /// first.
+    pub resources: Vec<ResourceWarmUpEntry>,
+}
+
+pub(crate) struct ResourceWarmUpProbe {
+    start: Instant,
+    preloaded: FxHashSet<usize>,
+}
+
+impl ResourceWarmUpProbe {
+    pub(crate) fn new(resource_manager: &ResourceManager) -> Self {
+        Self {
+            start: Instant::now(),
+            preloaded: resource_manager
+                .state()
+                .iter()
+                .map(|resource| resource.key())
+                .collect(),
+        }
+    }
+
+    #[allow(clippy::mutable_key_type)]
+    pub(crate) fn finish(
+        self,
+        scene_path: Option<&Path>,
+        used_resources: &FxHashSet<UntypedResource>,
+        resource_manager: &ResourceManager,
+    ) -> ResourceWarmUpReport {
+        let statistics = resource_manager.statistics();
+        let state = resource_manager.state();
+        let constructors = state.constructors_container.map.lock();
+
+        let mut resources = Vec::new();
+        let mut add_entry = |resource: &UntypedResource, direct: bool| {
+            let cache_hit = self.preloaded.contains(&resource.key());
+            let (status, memory) = match *resource.lock() {
+                ResourceState::Ok(ref data) => (ResourceWarmUpStatus::Loaded, data.memory_usage()),
+                ResourceState::LoadError { .. } => (ResourceWarmUpStatus::Failed, 0),
+                ResourceState::Pending { .. } => (ResourceWarmUpStatus::Pending, 0),
+            };
+            let path = resource.path();
+            let type_uuid = resource.type_uuid();
+            let timing = if cache_hit {
+                None
+            } else {
+                statistics.find(&path)
+            };
+            resources.push(ResourceWarmUpEntry {
+                type_name: constructors
+                    .get(&type_uuid)
+                    .map(|constructor| constructor.type_name.clone())
+                    .unwrap_or_default(),
+                path,
+                type_uuid,
+                status,
+                direct,
+                cache_hit,
+                load_time_ms: timing.map_or(0.0, |t| t.total().as_secs_f64() * 1000.0),
+                io_time_ms: timing.map_or(0.0, |t| t.io.as_secs_f64() * 1000.0),
+                memory,
+            });
+        };
+
+        for resource in used_resources {
+            add_entry(resource, true);
+        }
+
+        for resource in state.iter() {
+            if !self.preloaded.contains(&resource.key()) && !used_resources.contains(resource) {
+                add_entry(resource, false);
+            }
+        }
+
+        ResourceWarmUpReport {
+            scene_path: scene_path
+                .map(|path| path.to_path_buf())
+                .unwrap_or_default(),
+            total_time_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+            resources,
+        }
+    }
+}
+
+impl ResourceWarmUpReport {
+    /// Returns an iterator over resources, that were already loaded before the scene started
+    /// loading.
+    pub fn cache_hits(&self) -> impl Iterator<Item = &ResourceWarmUpEntry> {
+        self.resources.iter().filter(|entry| entry.cache_hit)
+    }
+
+    /// Returns an iterator over resources, that were loaded from scratch.
+    pub fn cold_loads(&self) -> impl Iterator<Item = &ResourceWarmUpEntry> {
+        self.resources.iter().filter(|entry| !entry.cache_hit)
+    }
+
+    /// Returns an iterator over resources, that are failed to load.
+    pub fn failed(&self) -> impl Iterator<Item = &ResourceWarmUpEntry> {
+        self.resources
+            .iter()
+            .filter(|entry| entry.status == ResourceWarmUpStatus::Failed)
+    }
+
+    /// Returns total estimated amount of memory (in bytes) used by the resources of the report.
+    pub fn total_memory(&self) -> usize {
+        self.resources.iter().map(|entry| entry.memory).sum()
+    }
+
+    /// Serializes the report into a pretty-printed JSON string.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserializes a report from the given JSON string.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the report as a JSON file at the given path.
+    pub fn save_json(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        asset::{Resource, ResourceData},
+        core::{futures::executor::block_on, TypeUuidProvider},
+        engine::{self, SerializationContext},
+        material::{Material, MaterialResource},
+        scene::{base::BaseBuilder, sprite::SpriteBuilder, Scene, SceneLoader},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn resource_warm_up_report() {
+        let resource_manager = ResourceManager::new();
+        engine::initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+
+        let mut material = Material::standard_sprite();
+        material.set_path("cached.material".into());
+        let cached = MaterialResource::from(UntypedResource::new_ok(material));
+        resource_manager.state().push(cached.clone().into_untyped());
+
+        let probe = ResourceWarmUpProbe::new(&resource_manager);
+
+        let mut scene = Scene::new();
+        SpriteBuilder::new(BaseBuilder::new())
+            .with_material(cached)
+            .build(&mut scene.graph);
+        let missing: Resource<Material> = resource_manager.request("missing.material");
+        SpriteBuilder::new(BaseBuilder::new())
+            .with_material(missing)
+            .build(&mut scene.graph);
+
+        let loader = SceneLoader {
+            scene,
+            path: None,
+            probe,
+        };
+        let (_, report) = block_on(loader.finish_with_report(&resource_manager));
+
+        let entry = |path: &str| {
+            report
+                .resources
+                .iter()
+                .find(|entry| entry.path == Path::new(path))
+                .unwrap()
+        };
+        let cached_entry = entry("cached.material");
+        assert!(cached_entry.cache_hit);
+        assert!(cached_entry.direct);
+        assert_eq!(cached_entry.status, ResourceWarmUpStatus::Loaded);
+        assert_eq!(
+            cached_entry.type_uuid,
+            <Material as TypeUuidProvider>::type_uuid()
+        );
+        assert!(cached_entry.memory > 0);
+        let missing_entry = entry("missing.material");
+        assert!(!missing_entry.cache_hit);
+        assert_eq!(missing_entry.status, ResourceWarmUpStatus::Failed);
+        assert_eq!(report.cold_loads().count(), 1);
+        assert_eq!(report.failed().count(), 1);
+
+        let json = report.to_json().unwrap();
+        assert_eq!(ResourceWarmUpReport::from_json(&json).unwrap(), report);
+    }
+}
@@ -15,6 +15,7 @@ use crate::{
     event::Event,
     plugin::Plugin,
     scene::{node::Node, Scene},
+    script::service::{ScriptServices, ServiceId},
     utils::component::ComponentProvider,
 };
 use std::{
@@ -25,6 +26,7 @@ use std::{
 };
 
 pub mod constructor;
+pub mod service;
 
 /// A script message's payload.
 pub trait ScriptMessagePayload: Any + Send {
@@ -209,6 +211,9 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// in most of the script methods.
     pub scene: &'b mut Scene,
 
+    /// Handle of the scene the script instance belongs to.
+    pub scene_handle: Handle<Scene>,
+
     /// A reference to resource manager, use it to load resources.
     pub resource_manager: &'a ResourceManager,
 
@@ -219,6 +224,10 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A message dispatcher. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. See [`ScriptTrait::on_message`] for more examples.
     pub message_dispatcher: &'c mut ScriptMessageDispatcher,
+
+    /// A container of services, that are registered by the engine and plugins. See [`ScriptServices`]
+    /// docs for more info.
+    pub services: &'a ScriptServices,
 }
 
 /// A set of data, that provides contextual information for script methods.
@@ -256,6 +265,10 @@ pub struct ScriptMessageContext<'a, 'b, 'c> {
     /// An message sender. Every message sent via this sender will be then passed to every [`ScriptTrait::on_message`]
     /// method of every script.
     pub message_sender: &'c ScriptMessageSender,
+
+    /// A container of services, that are registered by the engine and plugins. See [`ScriptServices`]
+    /// docs for more info.
+    pub services: &'a ScriptServices,
 }
 
 /// A set of data that will be passed to a script instance just before its destruction.
@@ -284,6 +297,10 @@ pub struct ScriptDeinitContext<'a, 'b, 'c> {
     /// An message sender. Every message sent via this sender will be then passed to every [`ScriptTrait::on_message`]
     /// method of every script.
     pub message_sender: &'c ScriptMessageSender,
+
+    /// A container of services, that are registered by the engine and plugins. See [`ScriptServices`]
+    /// docs for more info.
+    pub services: &'a ScriptServices,
 }
 
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
@@ -300,6 +317,13 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// in games. If you need a method that will be called in any case, use [`ScriptTrait::on_start`].
     fn on_init(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Returns a list of services, that the script needs (see [`ScriptServices`] docs for more
+    /// info). The engine checks the list before [`ScriptTrait::on_init`] and reports missing
+    /// services, so the script could use [`ScriptServices::expect`] to get them.
+    fn required_services(&self) -> Vec<ServiceId> {
+        Vec::new()
+    }
+
     /// The method is called after [`ScriptTrait::on_init`], but in separate pass, which means that all
     /// script instances are already initialized. However, if implementor of this method creates a new
     /// node with a script, there will be a second pass of initialization. The method is guaranteed to
@@ -478,6 +502,14 @@ impl Reflect for Script {
         self.instance.deref_mut().fields_mut(func)
     }
 
+    fn contents<'a>(&'a self, func: &mut dyn FnMut(&'a dyn Reflect)) -> bool {
+        self.instance.deref().contents(func)
+    }
+
+    fn contents_mut<'a>(&'a mut self, func: &mut dyn FnMut(&'a mut dyn Reflect)) -> bool {
+        self.instance.deref_mut().contents_mut(func)
+    }
+
     fn field(&self, name: &str, func: &mut dyn FnMut(Option<&dyn Reflect>)) {
         self.instance.deref().field(name, func)
     }
@@ -0,0 +1,213 @@
+//! Services are shared objects, that scripts could request from the engine. See [`ScriptServices`]
+//! docs for more info.
+
+use crate::core::log::Log;
+use fxhash::FxHashMap;
+use std::{
+    any::{Any, TypeId},
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+/// Unique identifier of a service type, that is used to declare script requirements. See
+/// [`crate::script::ScriptTrait::required_services`] for more info.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ServiceId {
+    /// Type id of the service.
+    pub type_id: TypeId,
+    /// Name of the service type, used for diagnostics only.
+    pub type_name: &'static str,
+}
+
+impl ServiceId {
+    /// Creates a new identifier of the given service type.
+    pub fn of<T: Any>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+impl Display for ServiceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.type_name)
+    }
+}
+
+/// A container of services, that are available to scripts via `services` field of script contexts.
+/// A service is any shared object: the engine registers its resource manager
+/// ([`crate::asset::manager::ResourceManager`]), sound engine ([`crate::scene::sound::SoundEngine`])
+/// and serialization context ([`crate::engine::SerializationContext`]), plugins could register
+/// their own services using `services` field of [`crate::plugin::PluginContext`].
+///
+/// Services are stored in [`Arc`], so they must be [`Send`] and [`Sync`] and use interior
+/// mutability if they need to be modified. Scripts, that get their dependencies from the container
+/// instead of plugins or global variables, could be tested with a container, that has mock services.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{parking_lot::Mutex, reflect::prelude::*, uuid::Uuid, visitor::prelude::*, TypeUuidProvider},
+/// #     impl_component_provider,
+/// #     script::{service::ServiceId, ScriptContext, ScriptTrait},
+/// # };
+/// #
+/// #[derive(Default)]
+/// struct Score(Mutex<u32>);
+///
+/// #[derive(Clone, Debug, Default, Reflect, Visit)]
+/// struct Coin;
+///
+/// # impl_component_provider!(Coin);
+/// # impl TypeUuidProvider for Coin {
+/// #     fn type_uuid() -> Uuid {
+/// #         Default::default()
+/// #     }
+/// # }
+/// impl ScriptTrait for Coin {
+///     fn required_services(&self) -> Vec<ServiceId> {
+///         vec![ServiceId::of::<Score>()]
+///     }
+///
+///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+///         *ctx.services.expect::<Score>().0.lock() += 1;
+///     }
+///
+///     fn id(&self) -> Uuid {
+///         Self::type_uuid()
+///     }
+/// }
+///
+/// // The service should be registered by a plugin, for example in `Plugin::init`:
+/// // context.services.register(Score::default());
+/// ```
+#[derive(Default, Clone)]
+pub struct ScriptServices {
+    services: FxHashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl ScriptServices {
+    /// Registers a new service. If there's a service of the same type, it will be replaced.
+    pub fn register<T: Any + Send + Sync>(&mut self, service: T) {
+        self.register_shared(Arc::new(service))
+    }
+
+    /// Registers a service, that is shared with some other code already. If there's a service of
+    /// the same type, it will be replaced.
+    pub fn register_shared<T: Any + Send + Sync>(&mut self, service: Arc<T>) {
+        self.services.insert(TypeId::of::<T>(), service);
+    }
+
+    /// Removes a service of the given type. Returns `true` if the service was registered.
+    pub fn unregister<T: Any>(&mut self) -> bool {
+        self.services.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Returns `true` if there's a service of the given type.
+    pub fn contains<T: Any>(&self) -> bool {
+        self.services.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Tries to get a reference to a service of the given type.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.services
+            .get(&TypeId::of::<T>())
+            .and_then(|service| service.downcast_ref::<T>())
+    }
+
+    /// Tries to get a shared reference to a service of the given type, that could be stored
+    /// in a script or passed to a background task.
+    pub fn get_shared<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.services
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|service| service.downcast::<T>().ok())
+    }
+
+    /// Returns a reference to a service of the given type. This method should be used for services,
+    /// that are declared in [`crate::script::ScriptTrait::required_services`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no such service.
+    pub fn expect<T: Any>(&self) -> &T {
+        self.get::<T>().unwrap_or_else(|| {
+            panic!(
+                "There's no {} service. Register it in a plugin.",
+                std::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Returns every service of the given list, that is not registered.
+    pub fn missing(&self, required: &[ServiceId]) -> Vec<ServiceId> {
+        required
+            .iter()
+            .filter(|id| !self.services.contains_key(&id.type_id))
+            .copied()
+            .collect()
+    }
+
+    /// Checks if every of the given services is registered and logs an error with the names of the
+    /// missing ones, otherwise. Returns `true` if every service is registered.
+    pub(crate) fn check_requirements(&self, script_name: &str, required: &[ServiceId]) -> bool {
+        let missing = self.missing(required);
+        if missing.is_empty() {
+            true
+        } else {
+            let names = missing
+                .iter()
+                .map(|id| id.type_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Log::err(format!(
+                "Script {script_name} requires services, that are not registered: {names}."
+            ));
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct Counter(AtomicU32);
+
+    struct Name(&'static str);
+
+    #[test]
+    fn test_script_services() {
+        let mut services = ScriptServices::default();
+        assert!(services.get::<Counter>().is_none());
+
+        services.register(Counter::default());
+        let shared = Arc::new(Name("foo"));
+        services.register_shared(shared.clone());
+
+        services
+            .expect::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        let counter = services.get_shared::<Counter>().unwrap();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert!(Arc::ptr_eq(
+            &services.get_shared::<Name>().unwrap(),
+            &shared
+        ));
+        assert_eq!(services.expect::<Name>().0, "foo");
+
+        let required = [ServiceId::of::<Counter>(), ServiceId::of::<u32>()];
+        assert_eq!(services.missing(&required), [ServiceId::of::<u32>()]);
+        assert!(!services.check_requirements("Test", &required));
+        assert!(services.check_requirements("Test", &required[..1]));
+
+        assert!(services.unregister::<Counter>());
+        assert!(!services.contains::<Counter>());
+        assert!(!services.unregister::<Counter>());
+    }
+}
@@ -34,6 +34,7 @@ pub mod inverter;
 pub mod leaf;
 
 /// Status of execution of behavior tree node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Status {
     /// Action was successful.
     Success,
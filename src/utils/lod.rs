@@ -0,0 +1,714 @@
+//! Level of detail generation utilities: mesh simplification (see [`decimate_surface`]) and
+//! billboard impostor baking (see [`bake_impostor`]).
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
+        color::Color,
+        math::TriangleDefinition,
+        sstorage::ImmutableString,
+    },
+    material::{MaterialResource, PropertyValue},
+    resource::texture::{Texture, TextureKind, TexturePixelKind},
+    scene::mesh::{
+        buffer::{TriangleBuffer, VertexAttributeUsage, VertexFetchError, VertexReadTrait},
+        surface::SurfaceData,
+    },
+};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// Options of mesh simplification, see [`decimate_surface`] docs for more info.
+#[derive(Clone, Debug)]
+pub struct DecimationOptions {
+    /// Desired amount of triangles of a simplified mesh relative to the amount of triangles of
+    /// the source mesh, in `[0; 1]` range.
+    pub target_ratio: f32,
+    /// Maximum error of a single edge collapse (sum of squared distances to the planes of the source
+    /// triangles). Simplification stops earlier than the target is reached, if there are no
+    /// collapses with smaller error.
+    pub max_error: f32,
+    /// Whether to keep open borders of a mesh in place or not. Borders are the most noticeable
+    /// part of a simplified mesh, so it is better to keep them.
+    pub preserve_borders: bool,
+}
+
+impl Default for DecimationOptions {
+    fn default() -> Self {
+        Self {
+            target_ratio: 0.5,
+            max_error: f32::MAX,
+            preserve_borders: true,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct Quadric(Matrix4<f64>);
+
+impl Quadric {
+    fn from_plane(normal: Vector3<f64>, point: Vector3<f64>, weight: f64) -> Self {
+        let plane = Vector4::new(normal.x, normal.y, normal.z, -normal.dot(&point));
+        Self(plane * plane.transpose() * weight)
+    }
+
+    fn error(&self, point: &Vector3<f64>) -> f64 {
+        let point = Vector4::new(point.x, point.y, point.z, 1.0);
+        point.dot(&(self.0 * point)).max(0.0)
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        self.0 += other.0;
+    }
+}
+
+struct Collapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    from_version: u32,
+    to_version: u32,
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, the heap must pop the cheapest collapse first.
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+fn triangle_normal(a: &Vector3<f64>, b: &Vector3<f64>, c: &Vector3<f64>) -> Vector3<f64> {
+    (b - a).cross(&(c - a))
+}
+
+// A set of vertices with the same position. Vertices are duplicated on seams (because of different
+// texture coordinates or normals), such vertices must be collapsed together, otherwise holes
+// will appear.
+struct Group {
+    position: Vector3<f64>,
+    vertices: Vec<u32>,
+    triangles: Vec<usize>,
+    quadric: Quadric,
+    version: u32,
+    collapsed: bool,
+}
+
+struct Decimator {
+    groups: Vec<Group>,
+    group_of: Vec<usize>,
+    tex_coords: Option<Vec<Vector2<f32>>>,
+    triangles: Vec<[u32; 3]>,
+    alive: Vec<bool>,
+    heap: BinaryHeap<Collapse>,
+}
+
+impl Decimator {
+    fn triangle_groups(&self, triangle: usize) -> [usize; 3] {
+        self.triangles[triangle].map(|vertex| self.group_of[vertex as usize])
+    }
+
+    fn neighbours(&self, group: usize) -> FxHashSet<usize> {
+        let mut neighbours = FxHashSet::default();
+        for &triangle in self.groups[group].triangles.iter() {
+            if self.alive[triangle] {
+                neighbours.extend(self.triangle_groups(triangle));
+            }
+        }
+        neighbours.remove(&group);
+        neighbours
+    }
+
+    fn push_edge(&mut self, a: usize, b: usize) {
+        let mut quadric = self.groups[a].quadric;
+        quadric.add(&self.groups[b].quadric);
+        let to_b = quadric.error(&self.groups[b].position);
+        let to_a = quadric.error(&self.groups[a].position);
+        let (cost, from, to) = if to_b <= to_a {
+            (to_b, a, b)
+        } else {
+            (to_a, b, a)
+        };
+        self.heap.push(Collapse {
+            cost,
+            from,
+            to,
+            from_version: self.groups[from].version,
+            to_version: self.groups[to].version,
+        });
+    }
+
+    fn is_valid(&self, collapse: &Collapse) -> bool {
+        let from = &self.groups[collapse.from];
+        let to = &self.groups[collapse.to];
+        if from.collapsed
+            || to.collapsed
+            || from.version != collapse.from_version
+            || to.version != collapse.to_version
+        {
+            return false;
+        }
+
+        // Link condition: the only common neighbours of the collapsed groups must be the opposite
+        // vertices of the shared triangles, otherwise the collapse creates non-manifold geometry.
+        let mut shared = 0;
+        for &triangle in from.triangles.iter() {
+            if self.alive[triangle] && self.triangle_groups(triangle).contains(&collapse.to) {
+                shared += 1;
+            }
+        }
+        let common = self
+            .neighbours(collapse.from)
+            .intersection(&self.neighbours(collapse.to))
+            .count();
+        if common > shared {
+            return false;
+        }
+
+        // The collapse must not flip the remaining triangles around the collapsed group.
+        for &triangle in from.triangles.iter() {
+            if !self.alive[triangle] {
+                continue;
+            }
+            let groups = self.triangle_groups(triangle);
+            if groups.contains(&collapse.to) {
+                continue;
+            }
+            let positions = groups.map(|group| self.groups[group].position);
+            let moved = groups.map(|group| {
+                if group == collapse.from {
+                    to.position
+                } else {
+                    self.groups[group].position
+                }
+            });
+            let old_normal = triangle_normal(&positions[0], &positions[1], &positions[2]);
+            let new_normal = triangle_normal(&moved[0], &moved[1], &moved[2]);
+            if old_normal.dot(&new_normal) <= 0.0 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn collapse(&mut self, from: usize, to: usize) -> usize {
+        // Every vertex of the collapsed group is replaced with a vertex of the target group with
+        // the closest texture coordinates, so seams are kept as much as possible.
+        let mut remap = FxHashMap::default();
+        for &vertex in self.groups[from].vertices.iter() {
+            let replacement = match self.tex_coords.as_ref() {
+                Some(tex_coords) => *self.groups[to]
+                    .vertices
+                    .iter()
+                    .min_by(|a, b| {
+                        let uv = tex_coords[vertex as usize];
+                        let da = (tex_coords[**a as usize] - uv).norm_squared();
+                        let db = (tex_coords[**b as usize] - uv).norm_squared();
+                        da.total_cmp(&db)
+                    })
+                    .unwrap(),
+                None => self.groups[to].vertices[0],
+            };
+            remap.insert(vertex, replacement);
+        }
+
+        let mut removed = 0;
+        let triangles = std::mem::take(&mut self.groups[from].triangles);
+        for &triangle in triangles.iter() {
+            if !self.alive[triangle] {
+                continue;
+            }
+            if self.triangle_groups(triangle).contains(&to) {
+                self.alive[triangle] = false;
+                removed += 1;
+            } else {
+                for vertex in self.triangles[triangle].iter_mut() {
+                    if let Some(replacement) = remap.get(vertex) {
+                        *vertex = *replacement;
+                    }
+                }
+                self.groups[to].triangles.push(triangle);
+            }
+        }
+
+        let quadric = self.groups[from].quadric;
+        let alive = &self.alive;
+        let target = &mut self.groups[to];
+        target.triangles.retain(|triangle| alive[*triangle]);
+        target.quadric.add(&quadric);
+        target.version += 1;
+        let source = &mut self.groups[from];
+        source.collapsed = true;
+        source.version += 1;
+
+        for neighbour in self.neighbours(to) {
+            self.push_edge(to, neighbour);
+        }
+
+        removed
+    }
+}
+
+/// Simplifies the given surface data by collapsing its edges one by one (starting from the edges,
+/// that introduce the smallest error) until the desired amount of triangles is reached. This is the
+/// classic quadric error metric approach ("Surface Simplification Using Quadric Error Metrics" by
+/// Michael Garland and Paul S. Heckbert). Edges are collapsed to one of their vertices, so the
+/// simplified mesh uses a subset of the vertices of the source mesh with all their attributes
+/// (normals, texture coordinates, bone weights, etc.) intact.
+///
+/// The result is an embedded surface data, blend shapes are not preserved. Use this function
+/// to generate meshes for levels of detail (see [`crate::scene::base::LodGroup`]).
+pub fn decimate_surface(
+    data: &SurfaceData,
+    options: &DecimationOptions,
+) -> Result<SurfaceData, VertexFetchError> {
+    let vertex_buffer = &data.vertex_buffer;
+    let positions = vertex_buffer
+        .iter()
+        .map(|view| view.read_3_f32(VertexAttributeUsage::Position))
+        .collect::<Result<Vec<_>, _>>()?;
+    let tex_coords = if vertex_buffer.has_attribute(VertexAttributeUsage::TexCoord0) {
+        Some(
+            vertex_buffer
+                .iter()
+                .map(|view| view.read_2_f32(VertexAttributeUsage::TexCoord0))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    } else {
+        None
+    };
+
+    let mut groups = Vec::<Group>::new();
+    let mut group_of = Vec::with_capacity(positions.len());
+    let mut welded = FxHashMap::default();
+    for (index, position) in positions.iter().enumerate() {
+        let key = [position.x, position.y, position.z].map(f32::to_bits);
+        let group = *welded.entry(key).or_insert_with(|| {
+            groups.push(Group {
+                position: position.cast::<f64>(),
+                vertices: Vec::new(),
+                triangles: Vec::new(),
+                quadric: Default::default(),
+                version: 0,
+                collapsed: false,
+            });
+            groups.len() - 1
+        });
+        groups[group].vertices.push(index as u32);
+        group_of.push(group);
+    }
+
+    let triangles = data
+        .geometry_buffer
+        .iter()
+        .map(|triangle| triangle.0)
+        .filter(|triangle| triangle.iter().all(|i| (*i as usize) < positions.len()))
+        .collect::<Vec<_>>();
+
+    let mut decimator = Decimator {
+        alive: vec![true; triangles.len()],
+        groups,
+        group_of,
+        tex_coords,
+        triangles,
+        heap: Default::default(),
+    };
+
+    let mut edges = FxHashMap::<(usize, usize), u32>::default();
+    for triangle in 0..decimator.triangles.len() {
+        let [a, b, c] = decimator.triangle_groups(triangle);
+        if a == b || b == c || a == c {
+            decimator.alive[triangle] = false;
+            continue;
+        }
+        let [pa, pb, pc] = [a, b, c].map(|group| decimator.groups[group].position);
+        let normal = triangle_normal(&pa, &pb, &pc);
+        let area = normal.norm();
+        let quadric = if area > f64::EPSILON {
+            Quadric::from_plane(normal / area, pa, area)
+        } else {
+            Quadric::default()
+        };
+        for group in [a, b, c] {
+            decimator.groups[group].triangles.push(triangle);
+            decimator.groups[group].quadric.add(&quadric);
+        }
+        for (u, v) in [(a, b), (b, c), (c, a)] {
+            *edges.entry((u.min(v), u.max(v))).or_default() += 1;
+        }
+    }
+
+    if options.preserve_borders {
+        // Border edges are "locked" by planes, that are perpendicular to their triangles.
+        for triangle in 0..decimator.triangles.len() {
+            if !decimator.alive[triangle] {
+                continue;
+            }
+            let groups = decimator.triangle_groups(triangle);
+            let positions = groups.map(|group| decimator.groups[group].position);
+            let normal = triangle_normal(&positions[0], &positions[1], &positions[2]);
+            for i in 0..3 {
+                let (u, v) = (groups[i], groups[(i + 1) % 3]);
+                if edges.get(&(u.min(v), u.max(v))) != Some(&1) {
+                    continue;
+                }
+                let edge = positions[(i + 1) % 3] - positions[i];
+                let Some(border_normal) = edge.cross(&normal).try_normalize(f64::EPSILON) else {
+                    continue;
+                };
+                let quadric =
+                    Quadric::from_plane(border_normal, positions[i], 1000.0 * edge.norm_squared());
+                decimator.groups[u].quadric.add(&quadric);
+                decimator.groups[v].quadric.add(&quadric);
+            }
+        }
+    }
+
+    for &(a, b) in edges.keys() {
+        decimator.push_edge(a, b);
+    }
+
+    let mut alive_count = decimator.alive.iter().filter(|alive| **alive).count();
+    let target = (alive_count as f32 * options.target_ratio.clamp(0.0, 1.0)).ceil() as usize;
+    while alive_count > target {
+        let Some(collapse) = decimator.heap.pop() else {
+            break;
+        };
+        if collapse.cost > options.max_error as f64 {
+            break;
+        }
+        if decimator.is_valid(&collapse) {
+            alive_count -= decimator.collapse(collapse.from, collapse.to);
+        }
+    }
+
+    // Remove unused vertices and remap indices of the remaining triangles.
+    let mut used = vec![false; positions.len()];
+    for (triangle, alive) in decimator.triangles.iter().zip(decimator.alive.iter()) {
+        if *alive {
+            for vertex in triangle {
+                used[*vertex as usize] = true;
+            }
+        }
+    }
+    let mut new_indices = vec![0; positions.len()];
+    let mut next_index = 0;
+    for (new_index, used) in new_indices.iter_mut().zip(used.iter()) {
+        if *used {
+            *new_index = next_index;
+            next_index += 1;
+        }
+    }
+    let mut vertex_buffer = vertex_buffer.clone();
+    vertex_buffer.modify().retain(|vertex| used[vertex]);
+    let triangles = decimator
+        .triangles
+        .iter()
+        .zip(decimator.alive.iter())
+        .filter(|(_, alive)| **alive)
+        .map(|(triangle, _)| TriangleDefinition(triangle.map(|i| new_indices[i as usize])))
+        .collect();
+
+    Ok(SurfaceData::new(
+        vertex_buffer,
+        TriangleBuffer::new(triangles),
+        true,
+    ))
+}
+
+/// A surface, that should be rendered into an impostor, see [`bake_impostor`] for more info.
+pub struct ImpostorSource<'a> {
+    /// Geometry of the surface.
+    pub data: &'a SurfaceData,
+    /// Material of the surface. Diffuse color and diffuse texture of the material are used to
+    /// color the impostor.
+    pub material: &'a MaterialResource,
+    /// Transformation from the local space of the surface to the space of the impostor.
+    pub transform: Matrix4<f32>,
+}
+
+/// An image of a model, that is used as a billboard at the farthest level of detail.
+pub struct Impostor {
+    /// Pixels of the image in RGBA8 format, row-by-row from top to bottom.
+    pub pixels: Vec<u8>,
+    /// Width and height of the image in pixels.
+    pub resolution: u32,
+    /// Center of the imaged area in the space of the impostor.
+    pub center: Vector3<f32>,
+    /// Width and height of the imaged area in the space of the impostor.
+    pub size: f32,
+}
+
+struct Sampler<'a> {
+    color: Color,
+    texture: Option<(&'a [u8], u32, u32, usize)>,
+}
+
+impl<'a> Sampler<'a> {
+    fn sample(&self, uv: Vector2<f32>) -> Vector4<f32> {
+        let mut color = self.color.as_frgba();
+        if let Some((bytes, width, height, bpp)) = self.texture {
+            let x = ((uv.x.rem_euclid(1.0) * width as f32) as u32).min(width - 1);
+            let y = ((uv.y.rem_euclid(1.0) * height as f32) as u32).min(height - 1);
+            let position = (y * width + x) as usize * bpp;
+            if let Some(texel) = bytes.get(position..position + bpp) {
+                for (component, value) in color.iter_mut().zip(texel) {
+                    *component *= *value as f32 / 255.0;
+                }
+            }
+        }
+        color
+    }
+}
+
+fn texture_data(texture: &Texture) -> Option<(&[u8], u32, u32, usize)> {
+    let bpp = match texture.pixel_kind() {
+        TexturePixelKind::RGBA8 => 4,
+        TexturePixelKind::RGB8 => 3,
+        _ => return None,
+    };
+    match texture.kind() {
+        TextureKind::Rectangle { width, height } if width > 0 && height > 0 => {
+            Some((texture.mip_level_data(0), width, height, bpp))
+        }
+        _ => None,
+    }
+}
+
+/// Renders the given surfaces into an image, that could be used as a billboard impostor (a
+/// sprite, that replaces a model at large distances). The surfaces are rendered in software using
+/// orthographic projection, the image shows the model as it is seen by an observer looking along
+/// the positive Z axis of the impostor space. Only diffuse color and texture (RGB8 and RGBA8 formats
+/// are supported) of the materials are taken into account along with simple directional lighting
+/// from the observer. Uncovered pixels are fully transparent.
+///
+/// Returns `None` if there's nothing to render.
+pub fn bake_impostor(
+    sources: &[ImpostorSource],
+    resolution: u32,
+) -> Result<Option<Impostor>, VertexFetchError> {
+    struct BakedVertex {
+        position: Vector3<f32>,
+        normal: Vector3<f32>,
+        uv: Vector2<f32>,
+    }
+
+    let resolution = resolution.max(1);
+    let mut meshes = Vec::with_capacity(sources.len());
+    let mut min = Vector3::repeat(f32::MAX);
+    let mut max = Vector3::repeat(-f32::MAX);
+    for source in sources {
+        let normal_matrix = source
+            .transform
+            .try_inverse()
+            .unwrap_or_default()
+            .transpose();
+        let mut vertices = Vec::new();
+        for view in source.data.vertex_buffer.iter() {
+            let position = source
+                .transform
+                .transform_point(&Point3::from(
+                    view.read_3_f32(VertexAttributeUsage::Position)?,
+                ))
+                .coords;
+            let normal = view
+                .read_3_f32(VertexAttributeUsage::Normal)
+                .map(|normal| {
+                    normal_matrix
+                        .transform_vector(&normal)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default()
+                })
+                .unwrap_or_else(|_| Vector3::new(0.0, 0.0, -1.0));
+            let uv = view
+                .read_2_f32(VertexAttributeUsage::TexCoord0)
+                .unwrap_or_default();
+            min = min.inf(&position);
+            max = max.sup(&position);
+            vertices.push(BakedVertex {
+                position,
+                normal,
+                uv,
+            });
+        }
+        meshes.push(vertices);
+    }
+
+    if meshes.iter().all(|vertices| vertices.is_empty()) {
+        return Ok(None);
+    }
+
+    // Keep a small margin around the model, so it is not touching the edges of the image.
+    let center = (min + max).scale(0.5);
+    let size = (max.x - min.x).max(max.y - min.y).max(f32::EPSILON) * 1.05;
+    let scale = resolution as f32 / size;
+    let left = center.x + size * 0.5;
+    let top = center.y + size * 0.5;
+
+    let mut pixels = vec![0u8; (resolution * resolution * 4) as usize];
+    let mut depth = vec![f32::MAX; (resolution * resolution) as usize];
+    for (source, vertices) in sources.iter().zip(meshes.iter()) {
+        let material = source.material.data_ref();
+        let color = match material.property_ref(&ImmutableString::new("diffuseColor")) {
+            Some(PropertyValue::Color(color)) => *color,
+            _ => Color::WHITE,
+        };
+        let texture = material
+            .property_ref(&ImmutableString::new("diffuseTexture"))
+            .and_then(|property| property.as_sampler())
+            .filter(|texture| texture.is_ok());
+        let texture_ref = texture.as_ref().map(|texture| texture.data_ref());
+        let sampler = Sampler {
+            color,
+            texture: texture_ref
+                .as_ref()
+                .and_then(|texture| texture_data(texture)),
+        };
+
+        for triangle in source.data.geometry_buffer.iter() {
+            let [Some(a), Some(b), Some(c)] = triangle.0.map(|i| vertices.get(i as usize)) else {
+                continue;
+            };
+            // Screen space: X goes to the left of the observer, Y goes down.
+            let project = |v: &BakedVertex| {
+                Vector3::new(
+                    (left - v.position.x) * scale,
+                    (top - v.position.y) * scale,
+                    v.position.z,
+                )
+            };
+            let (pa, pb, pc) = (project(a), project(b), project(c));
+            let area = (pb.x - pa.x) * (pc.y - pa.y) - (pb.y - pa.y) * (pc.x - pa.x);
+            if area.abs() <= f32::EPSILON {
+                continue;
+            }
+
+            let x_min = pa.x.min(pb.x).min(pc.x).floor().max(0.0) as u32;
+            let y_min = pa.y.min(pb.y).min(pc.y).floor().max(0.0) as u32;
+            let x_max = (pa.x.max(pb.x).max(pc.x).ceil() as u32).min(resolution);
+            let y_max = (pa.y.max(pb.y).max(pc.y).ceil() as u32).min(resolution);
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                    let wa = ((pb.x - px) * (pc.y - py) - (pb.y - py) * (pc.x - px)) / area;
+                    let wb = ((pc.x - px) * (pa.y - py) - (pc.y - py) * (pa.x - px)) / area;
+                    let wc = 1.0 - wa - wb;
+                    if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                        continue;
+                    }
+                    let z = pa.z * wa + pb.z * wb + pc.z * wc;
+                    let index = (y * resolution + x) as usize;
+                    if z >= depth[index] {
+                        continue;
+                    }
+                    depth[index] = z;
+
+                    let normal = (a.normal * wa + b.normal * wb + c.normal * wc)
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default();
+                    let uv = a.uv * wa + b.uv * wb + c.uv * wc;
+                    let lighting = 0.35 + 0.65 * (-normal.z).max(0.0);
+                    let texel = sampler.sample(uv);
+                    let pixel = &mut pixels[index * 4..index * 4 + 4];
+                    for (i, component) in pixel.iter_mut().take(3).enumerate() {
+                        *component = ((texel[i] * lighting).clamp(0.0, 1.0) * 255.0) as u8;
+                    }
+                    pixel[3] = 255;
+                }
+            }
+        }
+    }
+
+    Ok(Some(Impostor {
+        pixels,
+        resolution,
+        center,
+        size,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::material::Material;
+
+    #[test]
+    fn test_decimate_surface() {
+        let sphere = SurfaceData::make_sphere(32, 32, 1.0, &Matrix4::identity());
+        let source_triangles = sphere.geometry_buffer.len();
+
+        let decimated = decimate_surface(
+            &sphere,
+            &DecimationOptions {
+                target_ratio: 0.25,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let triangles = decimated.geometry_buffer.len();
+        assert!(triangles < source_triangles / 2);
+        assert!(triangles >= source_triangles / 8);
+
+        let vertex_count = decimated.vertex_buffer.vertex_count();
+        assert!(vertex_count < sphere.vertex_buffer.vertex_count());
+        assert!(decimated
+            .geometry_buffer
+            .iter()
+            .all(|triangle| triangle.0.iter().all(|i| *i < vertex_count)));
+
+        // The shape must be preserved.
+        for view in decimated.vertex_buffer.iter() {
+            let position = view.read_3_f32(VertexAttributeUsage::Position).unwrap();
+            assert!((position.norm() - 1.0).abs() < 1.0e-3);
+        }
+
+        let untouched = decimate_surface(
+            &sphere,
+            &DecimationOptions {
+                target_ratio: 1.0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(untouched.geometry_buffer.len(), source_triangles);
+    }
+
+    #[test]
+    fn test_bake_impostor() {
+        let quad = SurfaceData::make_quad(&Matrix4::new_scaling(2.0));
+        let material = MaterialResource::new_ok(Material::standard());
+        let impostor = bake_impostor(
+            &[ImpostorSource {
+                data: &quad,
+                material: &material,
+                transform: Matrix4::identity(),
+            }],
+            32,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(impostor.pixels.len(), 32 * 32 * 4);
+        assert!(impostor.size >= 2.0);
+        // The center is covered, the corners are not (because of the margin).
+        let alpha = |x: usize, y: usize| impostor.pixels[(y * 32 + x) * 4 + 3];
+        assert_eq!(alpha(16, 16), 255);
+        assert_eq!(alpha(0, 0), 0);
+
+        assert!(bake_impostor(&[], 32).unwrap().is_none());
+    }
+}
@@ -0,0 +1,421 @@
+//! A widget, that shows different content depending on the loading state of a resource. See
+//! [`ResourceStateView`] docs for more info.
+
+use crate::{
+    asset::{manager::ResourceManager, state::ResourceState, untyped::UntypedResource},
+    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        define_constructor, define_widget_deref,
+        formatted_text::WrapMode,
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+/// Simplified loading state of a resource, that is observed by a [`ResourceStateView`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ResourceViewState {
+    /// There's no resource bound to the view.
+    #[default]
+    Empty,
+    /// The resource is loading.
+    Pending,
+    /// The resource failed to load, the payload is a human-readable description of the error.
+    Error(String),
+    /// The resource is fully loaded.
+    Ok,
+}
+
+impl ResourceViewState {
+    fn of(resource: Option<&UntypedResource>) -> Self {
+        let Some(resource) = resource else {
+            return Self::Empty;
+        };
+        match *resource.lock() {
+            ResourceState::Pending { .. } => Self::Pending,
+            ResourceState::LoadError {
+                ref path,
+                ref error,
+                ..
+            } => Self::Error(match error.0 {
+                Some(ref error) => format!("Unable to load {}: {:?}", path.display(), error),
+                None => format!("Unable to load {}", path.display()),
+            }),
+            ResourceState::Ok(_) => Self::Ok,
+        }
+    }
+}
+
+/// A set of messages, that could be used to alter [`ResourceStateView`] widget state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceStateViewMessage {
+    /// Sets a new resource to observe, `None` unbinds the current resource. Direction:
+    /// [`MessageDirection::ToWidget`].
+    Resource(Option<UntypedResource>),
+    /// Emitted when the state of the observed resource has changed. Direction:
+    /// [`MessageDirection::FromWidget`].
+    StateChanged(ResourceViewState),
+    /// Emitted when the retry button was clicked. The resource is reloaded automatically, if the
+    /// view has a resource manager, otherwise it is up to the user to reload the resource.
+    /// Direction: [`MessageDirection::FromWidget`].
+    Retry,
+}
+
+impl ResourceStateViewMessage {
+    define_constructor!(
+        /// Creates [`ResourceStateViewMessage::Resource`] message.
+        ResourceStateViewMessage:Resource => fn resource(Option<UntypedResource>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ResourceStateViewMessage::StateChanged`] message.
+        ResourceStateViewMessage:StateChanged => fn state_changed(ResourceViewState), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ResourceStateViewMessage::Retry`] message.
+        ResourceStateViewMessage:Retry => fn retry(), layout: false
+    );
+}
+
+/// Resource state view is a widget, that reflects the loading state of a resource: it shows
+/// "pending" content (a loading text by default) while the resource is loading, an error panel
+/// with a retry button if the resource failed to load and the actual content when the resource is
+/// loaded. It removes the need to poll the state of a resource manually, which is a common task
+/// for menus that show downloadable or streamed content.
+///
+/// The content is built by the user and is shown only when the resource is loaded, it could be
+/// filled with the data of the resource on [`ResourceStateViewMessage::StateChanged`] message with
+/// [`ResourceViewState::Ok`] state.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     asset::manager::ResourceManager,
+/// #     core::pool::Handle,
+/// #     gui::{text::TextBuilder, widget::WidgetBuilder, BuildContext, UiNode},
+/// #     utils::resource_state_view::ResourceStateViewBuilder,
+/// # };
+/// fn create_preview(resource_manager: &ResourceManager, ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let resource = resource_manager.request_untyped("data/level_preview.png");
+///     let content = TextBuilder::new(WidgetBuilder::new())
+///         .with_text("Preview is ready!")
+///         .build(ctx);
+///     ResourceStateViewBuilder::new(WidgetBuilder::new())
+///         .with_resource(resource)
+///         .with_resource_manager(resource_manager.clone())
+///         .with_content(content)
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Clone, Visit, Reflect)]
+pub struct ResourceStateView {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resource: Option<UntypedResource>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resource_manager: Option<ResourceManager>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    state: ResourceViewState,
+    pending: Handle<UiNode>,
+    error: Handle<UiNode>,
+    error_text: Handle<UiNode>,
+    retry: Handle<UiNode>,
+    content: Handle<UiNode>,
+}
+
+define_widget_deref!(ResourceStateView);
+
+impl Debug for ResourceStateView {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceStateView")
+            .field("widget", &self.widget)
+            .field("resource", &self.resource)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl ResourceStateView {
+    /// Returns the currently observed resource.
+    pub fn resource(&self) -> Option<&UntypedResource> {
+        self.resource.as_ref()
+    }
+
+    /// Returns the last observed state of the resource.
+    pub fn state(&self) -> &ResourceViewState {
+        &self.state
+    }
+
+    fn sync_state(&mut self, sender: &Sender<UiMessage>) {
+        let state = ResourceViewState::of(self.resource.as_ref());
+        if state == self.state {
+            return;
+        }
+
+        let send = |message: UiMessage| {
+            let _ = sender.send(message);
+        };
+        let visibility = |handle: Handle<UiNode>, visible: bool| {
+            WidgetMessage::visibility(handle, MessageDirection::ToWidget, visible)
+        };
+        send(visibility(
+            self.pending,
+            state == ResourceViewState::Pending,
+        ));
+        send(visibility(
+            self.error,
+            matches!(state, ResourceViewState::Error(_)),
+        ));
+        send(visibility(self.content, state == ResourceViewState::Ok));
+        if let ResourceViewState::Error(ref text) = state {
+            send(TextMessage::text(
+                self.error_text,
+                MessageDirection::ToWidget,
+                text.clone(),
+            ));
+        }
+        send(ResourceStateViewMessage::state_changed(
+            self.handle,
+            MessageDirection::FromWidget,
+            state.clone(),
+        ));
+
+        self.state = state;
+    }
+}
+
+impl Control for ResourceStateView {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn update(&mut self, _dt: f32, sender: &Sender<UiMessage>) {
+        self.sync_state(sender);
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
+        {
+            if let Some(ResourceStateViewMessage::Resource(resource)) = message.data() {
+                if resource != &self.resource {
+                    self.resource.clone_from(resource);
+                    self.sync_state(&ui.sender());
+                }
+            }
+        } else if message.destination() == self.retry {
+            if let Some(ButtonMessage::Click) = message.data() {
+                if let (Some(resource), Some(resource_manager)) =
+                    (self.resource.as_ref(), self.resource_manager.as_ref())
+                {
+                    resource.reload(resource_manager);
+                }
+                ui.send_message(ResourceStateViewMessage::retry(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                ));
+            }
+        }
+    }
+}
+
+/// Resource state view builder creates [`ResourceStateView`] widgets and adds them to the user
+/// interface.
+pub struct ResourceStateViewBuilder {
+    widget_builder: WidgetBuilder,
+    resource: Option<UntypedResource>,
+    resource_manager: Option<ResourceManager>,
+    content: Handle<UiNode>,
+    pending_content: Option<Handle<UiNode>>,
+}
+
+impl ResourceStateViewBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            resource: None,
+            resource_manager: None,
+            content: Handle::NONE,
+            pending_content: None,
+        }
+    }
+
+    /// Sets the resource to observe.
+    pub fn with_resource(mut self, resource: impl Into<UntypedResource>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    /// Sets the resource manager, that will be used to reload the resource when the retry button
+    /// is clicked.
+    pub fn with_resource_manager(mut self, resource_manager: ResourceManager) -> Self {
+        self.resource_manager = Some(resource_manager);
+        self
+    }
+
+    /// Sets the content, that will be shown when the resource is loaded.
+    pub fn with_content(mut self, content: Handle<UiNode>) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Sets the content, that will be shown while the resource is loading. By default, it is a
+    /// "Loading..." text.
+    pub fn with_pending_content(mut self, pending_content: Handle<UiNode>) -> Self {
+        self.pending_content = Some(pending_content);
+        self
+    }
+
+    /// Finishes widget building and adds it to the user interface, returning a handle to the
+    /// instance.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let pending = self.pending_content.unwrap_or_else(|| {
+            TextBuilder::new(WidgetBuilder::new())
+                .with_text("Loading...")
+                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx)
+        });
+
+        let error_text;
+        let retry;
+        let error = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_child({
+                    error_text =
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                            .with_wrap(WrapMode::Word)
+                            .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                            .build(ctx);
+                    error_text
+                })
+                .with_child({
+                    retry = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(100.0)
+                            .with_height(24.0)
+                            .with_horizontal_alignment(HorizontalAlignment::Center)
+                            .with_margin(Thickness::uniform(2.0)),
+                    )
+                    .with_text("Retry")
+                    .build(ctx);
+                    retry
+                }),
+        )
+        .build(ctx);
+
+        let view = ResourceStateView {
+            widget: self
+                .widget_builder
+                .with_child(pending)
+                .with_child(error)
+                .with_child(self.content)
+                .build(),
+            resource: self.resource,
+            resource_manager: self.resource_manager,
+            state: ResourceViewState::Empty,
+            pending,
+            error,
+            error_text,
+            retry,
+            content: self.content,
+        };
+
+        // Everything is hidden until the first update, which will show the content for the
+        // actual state of the resource.
+        for handle in [pending, error, self.content] {
+            if let Some(node) = ctx.try_get_node_mut(handle) {
+                node.set_visibility(false);
+            }
+        }
+
+        ctx.add_node(UiNode::new(view))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{algebra::Vector2, TypeUuidProvider},
+        material::Material,
+    };
+
+    fn update(ui: &mut UserInterface, view: Handle<UiNode>) -> Vec<ResourceViewState> {
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+        let mut states = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            if let Some(ResourceStateViewMessage::StateChanged(state)) = message.data() {
+                if message.destination() == view {
+                    states.push(state.clone());
+                }
+            }
+        }
+        states
+    }
+
+    fn visible_parts(ui: &UserInterface, view: Handle<UiNode>) -> [bool; 3] {
+        let view = ui.node(view).cast::<ResourceStateView>().unwrap();
+        [view.pending, view.error, view.content].map(|handle| ui.node(handle).visibility())
+    }
+
+    #[test]
+    fn resource_state_view_follows_resource_state() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let resource = UntypedResource::new_pending("test.mat".into(), Material::type_uuid());
+        let content = TextBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let view = ResourceStateViewBuilder::new(WidgetBuilder::new())
+            .with_resource(resource.clone())
+            .with_content(content)
+            .build(&mut ui.build_ctx());
+
+        assert_eq!(update(&mut ui, view), [ResourceViewState::Pending]);
+        assert_eq!(visible_parts(&ui, view), [true, false, false]);
+        // The state is reported only when it changes.
+        assert!(update(&mut ui, view).is_empty());
+
+        resource.commit_error("test.mat".into(), "Broken".to_string());
+        assert!(matches!(
+            update(&mut ui, view).as_slice(),
+            [ResourceViewState::Error(error)] if error.starts_with("Unable to load test.mat")
+        ));
+        assert_eq!(visible_parts(&ui, view), [false, true, false]);
+
+        ui.send_message(ResourceStateViewMessage::resource(
+            view,
+            MessageDirection::ToWidget,
+            Some(UntypedResource::new_ok(Material::standard())),
+        ));
+        assert_eq!(update(&mut ui, view), [ResourceViewState::Ok]);
+        assert_eq!(visible_parts(&ui, view), [false, false, true]);
+
+        ui.send_message(ResourceStateViewMessage::resource(
+            view,
+            MessageDirection::ToWidget,
+            None,
+        ));
+        assert_eq!(update(&mut ui, view), [ResourceViewState::Empty]);
+        assert_eq!(visible_parts(&ui, view), [false, false, false]);
+    }
+}
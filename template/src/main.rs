@@ -534,6 +534,7 @@ opt-level = 3
             base_path.join(".gitignore"),
             r#"
 /target
+/.cache
 *.log
 "#,
         );